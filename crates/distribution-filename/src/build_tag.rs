@@ -61,3 +61,12 @@ impl FromStr for BuildTag {
         Ok(BuildTag(prefix.parse::<u64>()?, suffix.map(Arc::from)))
     }
 }
+
+impl BuildTag {
+    /// Returns the CPU features required by a hardware-variant wheel, as declared by a build tag
+    /// of the form `1cpu.avx512f.avx512bw`, or `None` if the build tag doesn't declare a variant.
+    pub fn cpu_features(&self) -> Option<impl Iterator<Item = &str>> {
+        let features = self.1.as_deref()?.strip_prefix("cpu.")?;
+        Some(features.split('.').filter(|feature| !feature.is_empty()))
+    }
+}
@@ -6,7 +6,7 @@ use thiserror::Error;
 use url::Url;
 
 use pep440_rs::{Version, VersionParseError};
-use platform_tags::{TagCompatibility, Tags};
+use platform_tags::{IncompatibleTag, TagCompatibility, Tags};
 use uv_normalize::{InvalidNameError, PackageName};
 
 use crate::{BuildTag, BuildTagError};
@@ -53,11 +53,29 @@ impl WheelFilename {
     /// Returns `true` if the wheel is compatible with the given tags.
     pub fn is_compatible(&self, compatible_tags: &Tags) -> bool {
         compatible_tags.is_compatible(&self.python_tag, &self.abi_tag, &self.platform_tag)
+            && self.is_cpu_compatible(compatible_tags)
     }
 
     /// Return the [`TagCompatibility`] of the wheel with the given tags
     pub fn compatibility(&self, compatible_tags: &Tags) -> TagCompatibility {
-        compatible_tags.compatibility(&self.python_tag, &self.abi_tag, &self.platform_tag)
+        match compatible_tags.compatibility(&self.python_tag, &self.abi_tag, &self.platform_tag) {
+            TagCompatibility::Compatible(_) if !self.is_cpu_compatible(compatible_tags) => {
+                TagCompatibility::Incompatible(IncompatibleTag::CpuFeatures)
+            }
+            compatibility => compatibility,
+        }
+    }
+
+    /// Returns `true` if the target provides the CPU features required by the wheel, if it's a
+    /// hardware-variant wheel.
+    fn is_cpu_compatible(&self, compatible_tags: &Tags) -> bool {
+        let Some(features) = self.build_tag.as_ref().and_then(BuildTag::cpu_features) else {
+            return true;
+        };
+        let available = compatible_tags.cpu_features();
+        features
+            .map(str::to_ascii_lowercase)
+            .all(|feature| available.contains(&feature))
     }
 
     /// The wheel filename without the extension.
@@ -236,6 +254,8 @@ pub enum WheelFilenameError {
 
 #[cfg(test)]
 mod tests {
+    use platform_tags::CpuFeatures;
+
     use super::*;
 
     #[test]
@@ -332,4 +352,32 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn cpu_variant() {
+        let tags = Tags::new(vec![(
+            "py3".to_string(),
+            "none".to_string(),
+            "any".to_string(),
+        )]);
+        let generic = WheelFilename::from_str("foo-1.0-py3-none-any.whl").unwrap();
+        let variant = WheelFilename::from_str("foo-1.0-1cpu.avx2.fma-py3-none-any.whl").unwrap();
+        let build = WheelFilename::from_str("foo-1.0-1local-py3-none-any.whl").unwrap();
+
+        // Without any CPU features, only variant wheels are incompatible.
+        assert!(generic.is_compatible(&tags));
+        assert!(build.is_compatible(&tags));
+        assert!(!variant.is_compatible(&tags));
+        assert_eq!(
+            variant.compatibility(&tags),
+            TagCompatibility::Incompatible(IncompatibleTag::CpuFeatures)
+        );
+
+        // Variant wheels require all of their features.
+        let tags = tags.with_cpu_features(CpuFeatures::new(["avx2"]));
+        assert!(!variant.is_compatible(&tags));
+        let tags = tags.with_cpu_features(CpuFeatures::new(["avx2", "fma", "avx512f"]));
+        assert!(variant.is_compatible(&tags));
+        assert!(variant.compatibility(&tags).is_compatible());
+    }
 }
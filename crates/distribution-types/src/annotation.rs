@@ -25,6 +25,12 @@ impl std::fmt::Display for SourceAnnotation {
                 RequirementOrigin::Project(path, project_name) => {
                     write!(f, "{project_name} ({})", path.portable_display())
                 }
+                RequirementOrigin::Group(path, project_name, group) => match project_name {
+                    Some(project_name) => {
+                        write!(f, "{project_name} ({}:{group})", path.portable_display())
+                    }
+                    None => write!(f, "({}:{group})", path.portable_display()),
+                },
                 RequirementOrigin::Workspace => {
                     write!(f, "(workspace)")
                 }
@@ -40,6 +46,10 @@ impl std::fmt::Display for SourceAnnotation {
                     // Project is not used for override
                     write!(f, "--override {project_name} ({})", path.portable_display())
                 }
+                RequirementOrigin::Group(path, _, group) => {
+                    // Groups are not used for overrides
+                    write!(f, "--override {}:{group}", path.portable_display())
+                }
                 RequirementOrigin::Workspace => {
                     write!(f, "--override (workspace)")
                 }
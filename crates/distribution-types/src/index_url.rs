@@ -1,6 +1,8 @@
 use itertools::Either;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
 use std::ops::Deref;
 use std::path::Path;
 use std::str::FromStr;
@@ -9,6 +11,7 @@ use thiserror::Error;
 use url::{ParseError, Url};
 
 use pep508_rs::{VerbatimUrl, VerbatimUrlError};
+use uv_normalize::{InvalidNameError, PackageName};
 
 use crate::Verbatim;
 
@@ -55,6 +58,14 @@ impl IndexUrl {
         }
     }
 
+    /// Returns `true` if the index refers to the same URL as the given index.
+    ///
+    /// Trailing slashes are ignored, such that `https://pypi.org/simple` and
+    /// `https://pypi.org/simple/` are considered equivalent.
+    pub fn same_url(&self, other: &IndexUrl) -> bool {
+        self.url().as_str().trim_end_matches('/') == other.url().as_str().trim_end_matches('/')
+    }
+
     /// Return the redacted URL for the index, omitting any sensitive credentials.
     pub fn redacted(&self) -> Cow<'_, Url> {
         let url = self.url();
@@ -175,6 +186,354 @@ impl Deref for IndexUrl {
     }
 }
 
+/// Mirrors of package indexes, keyed by the index they mirror.
+///
+/// If an index is unavailable (e.g., it returns a server error or times out), requests fail over
+/// to its mirrors, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexMirrors(Vec<(IndexUrl, Vec<IndexUrl>)>);
+
+impl IndexMirrors {
+    /// Return the mirrors for the given index, if any.
+    pub fn get(&self, index: &IndexUrl) -> &[IndexUrl] {
+        self.0
+            .iter()
+            .find(|(primary, _)| primary.url() == index.url())
+            .map(|(_, mirrors)| mirrors.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if no mirrors are configured.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Merge the mirrors in `self` with those in `other`, preferring the mirrors in `self` for
+    /// any index that's configured in both.
+    #[must_use]
+    pub fn merge(self, other: IndexMirrors) -> Self {
+        let mut mirrors = self.0;
+        for (primary, other) in other.0 {
+            if !mirrors
+                .iter()
+                .any(|(existing, _)| existing.url() == primary.url())
+            {
+                mirrors.push((primary, other));
+            }
+        }
+        Self(mirrors)
+    }
+}
+
+impl FromIterator<(IndexUrl, Vec<IndexUrl>)> for IndexMirrors {
+    fn from_iter<T: IntoIterator<Item = (IndexUrl, Vec<IndexUrl>)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl serde::ser::Serialize for IndexMirrors {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_map(
+            self.0
+                .iter()
+                .map(|(primary, mirrors)| (primary.to_string(), mirrors)),
+        )
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for IndexMirrors {
+    fn deserialize<D>(deserializer: D) -> Result<IndexMirrors, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let map = BTreeMap::<String, Vec<IndexUrl>>::deserialize(deserializer)?;
+        map.into_iter()
+            .map(|(primary, mirrors)| {
+                let primary = IndexUrl::from_str(&primary).map_err(serde::de::Error::custom)?;
+                Ok((primary, mirrors))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for IndexMirrors {
+    fn schema_name() -> String {
+        "IndexMirrors".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Object.into()),
+            object: Some(Box::new(schemars::schema::ObjectValidation {
+                additional_properties: Some(Box::new(gen.subschema_for::<Vec<IndexUrl>>())),
+                ..schemars::schema::ObjectValidation::default()
+            })),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("A mapping from the URL of a package index to the URLs of its mirrors, in order of preference.".to_string()),
+                ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// Fixed addresses for the hosts of package indexes, keyed by index, bypassing DNS resolution.
+///
+/// Requests to the host of each index (e.g., `pypi.internal` for `https://pypi.internal/simple`)
+/// connect to the given address instead; the port is taken from the URL of the request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexResolve(Vec<(IndexUrl, IpAddr)>);
+
+impl IndexResolve {
+    /// Return an iterator over the indexes and their addresses.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&IndexUrl, IpAddr)> {
+        self.0.iter().map(|(index, ip)| (index, *ip))
+    }
+
+    /// Returns `true` if no addresses are configured.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(IndexUrl, IpAddr)> for IndexResolve {
+    fn from_iter<T: IntoIterator<Item = (IndexUrl, IpAddr)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl serde::ser::Serialize for IndexResolve {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_map(self.0.iter().map(|(index, ip)| (index.to_string(), ip)))
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for IndexResolve {
+    fn deserialize<D>(deserializer: D) -> Result<IndexResolve, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let map = BTreeMap::<String, IpAddr>::deserialize(deserializer)?;
+        map.into_iter()
+            .map(|(index, ip)| {
+                let index = IndexUrl::from_str(&index).map_err(serde::de::Error::custom)?;
+                Ok((index, ip))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for IndexResolve {
+    fn schema_name() -> String {
+        "IndexResolve".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Object.into()),
+            object: Some(Box::new(schemars::schema::ObjectValidation {
+                additional_properties: Some(Box::new(gen.subschema_for::<String>())),
+                ..schemars::schema::ObjectValidation::default()
+            })),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("A mapping from the URL of a package index to the IP address to connect to for its host.".to_string()),
+                ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// The hash-checking policy for the packages served by an index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum IndexHashPolicy {
+    /// Every package served by the index must have a hash, as with `--require-hashes`.
+    Require,
+    /// Hashes are validated if present, but packages without hashes are allowed, as with
+    /// `--verify-hashes`.
+    Verify,
+}
+
+impl FromStr for IndexHashPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "require" => Ok(Self::Require),
+            "verify" => Ok(Self::Verify),
+            _ => Err(format!(
+                "Unknown hash-checking policy `{s}` (expected one of: `require`, `verify`)"
+            )),
+        }
+    }
+}
+
+impl Display for IndexHashPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Require => write!(f, "require"),
+            Self::Verify => write!(f, "verify"),
+        }
+    }
+}
+
+/// The hash-checking policies of package indexes, keyed by index.
+///
+/// Packages served by an index with a policy are checked according to that policy, rather than
+/// the global hash-checking mode (e.g., `--require-hashes`), such that hashes can be required for
+/// packages from a public index, but not for those from a trusted internal mirror.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexHashPolicies(Vec<(IndexUrl, IndexHashPolicy)>);
+
+impl IndexHashPolicies {
+    /// Return the hash-checking policy for the given index, if any.
+    ///
+    /// Trailing slashes are ignored when matching the index.
+    pub fn get(&self, index: &IndexUrl) -> Option<IndexHashPolicy> {
+        self.0
+            .iter()
+            .find(|(candidate, _)| candidate.same_url(index))
+            .map(|(_, policy)| *policy)
+    }
+
+    /// Return an iterator over the indexes and their policies.
+    pub fn iter(&self) -> impl Iterator<Item = (&IndexUrl, IndexHashPolicy)> {
+        self.0.iter().map(|(index, policy)| (index, *policy))
+    }
+
+    /// Returns `true` if no policies are configured.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(IndexUrl, IndexHashPolicy)> for IndexHashPolicies {
+    fn from_iter<T: IntoIterator<Item = (IndexUrl, IndexHashPolicy)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl serde::ser::Serialize for IndexHashPolicies {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        serializer.collect_map(
+            self.0
+                .iter()
+                .map(|(index, policy)| (index.to_string(), policy)),
+        )
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for IndexHashPolicies {
+    fn deserialize<D>(deserializer: D) -> Result<IndexHashPolicies, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        let map = BTreeMap::<String, IndexHashPolicy>::deserialize(deserializer)?;
+        map.into_iter()
+            .map(|(index, policy)| {
+                let index = IndexUrl::from_str(&index).map_err(serde::de::Error::custom)?;
+                Ok((index, policy))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for IndexHashPolicies {
+    fn schema_name() -> String {
+        "IndexHashPolicies".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::Object.into()),
+            object: Some(Box::new(schemars::schema::ObjectValidation {
+                additional_properties: Some(Box::new(gen.subschema_for::<IndexHashPolicy>())),
+                ..schemars::schema::ObjectValidation::default()
+            })),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some("A mapping from the URL of a package index to the hash-checking policy for the packages it serves.".to_string()),
+                ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// An assertion that a package must be served by a specific index (e.g., `torch=https://download.pytorch.org/whl/cpu`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequiredIndex {
+    package: PackageName,
+    index: IndexUrl,
+}
+
+impl RequiredIndex {
+    /// Return the name of the package.
+    pub fn package(&self) -> &PackageName {
+        &self.package
+    }
+
+    /// Return the index that must serve the package.
+    pub fn index(&self) -> &IndexUrl {
+        &self.index
+    }
+
+    /// Returns `true` if the given index satisfies the assertion.
+    ///
+    /// Trailing slashes are ignored, such that `https://pypi.org/simple` and
+    /// `https://pypi.org/simple/` are considered equivalent.
+    pub fn matches(&self, index: &IndexUrl) -> bool {
+        self.index.same_url(index)
+    }
+}
+
+/// An error that can occur when parsing a [`RequiredIndex`].
+#[derive(Error, Debug)]
+pub enum RequiredIndexError {
+    #[error("Expected `<package>=<index>`, found: `{0}`")]
+    MissingSeparator(String),
+    #[error(transparent)]
+    PackageName(#[from] InvalidNameError),
+    #[error(transparent)]
+    IndexUrl(#[from] IndexUrlError),
+}
+
+impl FromStr for RequiredIndex {
+    type Err = RequiredIndexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((package, index)) = s.split_once('=') else {
+            return Err(RequiredIndexError::MissingSeparator(s.to_string()));
+        };
+        Ok(Self {
+            package: PackageName::from_str(package.trim())?,
+            index: IndexUrl::from_str(index.trim())?,
+        })
+    }
+}
+
+impl Display for RequiredIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.package, self.index)
+    }
+}
+
 /// A directory with distributions or a URL to an HTML file with a flat listing of distributions.
 ///
 /// Also known as `--find-links`.
@@ -297,6 +656,8 @@ pub struct IndexLocations {
     extra_index: Vec<IndexUrl>,
     flat_index: Vec<FlatIndexLocation>,
     no_index: bool,
+    #[serde(default)]
+    mirrors: IndexMirrors,
 }
 
 impl Default for IndexLocations {
@@ -307,6 +668,7 @@ impl Default for IndexLocations {
             extra_index: Vec::new(),
             flat_index: Vec::new(),
             no_index: false,
+            mirrors: IndexMirrors::default(),
         }
     }
 }
@@ -324,9 +686,16 @@ impl IndexLocations {
             extra_index,
             flat_index,
             no_index,
+            mirrors: IndexMirrors::default(),
         }
     }
 
+    /// Set the mirrors to fail over to when an index is unavailable.
+    #[must_use]
+    pub fn with_mirrors(self, mirrors: IndexMirrors) -> Self {
+        Self { mirrors, ..self }
+    }
+
     /// Combine a set of index locations.
     ///
     /// If either the current or the other index locations have `no_index` set, the result will
@@ -346,6 +715,7 @@ impl IndexLocations {
             extra_index: self.extra_index.into_iter().chain(extra_index).collect(),
             flat_index: self.flat_index.into_iter().chain(flat_index).collect(),
             no_index: self.no_index || no_index,
+            mirrors: self.mirrors,
         }
     }
 }
@@ -397,6 +767,7 @@ impl<'a> IndexLocations {
             index: self.index.clone(),
             extra_index: self.extra_index.clone(),
             no_index: self.no_index,
+            mirrors: self.mirrors.clone(),
         }
     }
 
@@ -419,6 +790,7 @@ pub struct IndexUrls {
     index: Option<IndexUrl>,
     extra_index: Vec<IndexUrl>,
     no_index: bool,
+    mirrors: IndexMirrors,
 }
 
 impl Default for IndexUrls {
@@ -428,6 +800,7 @@ impl Default for IndexUrls {
             index: Some(DEFAULT_INDEX_URL.clone()),
             extra_index: Vec::new(),
             no_index: false,
+            mirrors: IndexMirrors::default(),
         }
     }
 }
@@ -467,6 +840,11 @@ impl<'a> IndexUrls {
     pub fn indexes(&'a self) -> impl Iterator<Item = &'a IndexUrl> + 'a {
         self.extra_index().chain(self.index())
     }
+
+    /// Return the mirrors for the given index, in order of preference.
+    pub fn mirrors(&'a self, index: &IndexUrl) -> &'a [IndexUrl] {
+        self.mirrors.get(index)
+    }
 }
 
 impl From<IndexLocations> for IndexUrls {
@@ -475,6 +853,7 @@ impl From<IndexLocations> for IndexUrls {
             index: locations.index,
             extra_index: locations.extra_index,
             no_index: locations.no_index,
+            mirrors: locations.mirrors,
         }
     }
 }
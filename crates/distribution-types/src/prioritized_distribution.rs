@@ -25,6 +25,8 @@ struct PrioritizedDistInner {
     wheels: Vec<(RegistryBuiltWheel, WheelCompatibility)>,
     /// The hashes for each distribution.
     hashes: Vec<HashDigest>,
+    /// Whether the source distribution should be installed in lieu of a compatible wheel.
+    prefer_source: bool,
 }
 
 /// A distribution that can be used for both resolution and installation.
@@ -58,6 +60,17 @@ pub enum CompatibleDist<'a> {
         /// The prioritized distribution that the wheel and sdist came from.
         prioritized: &'a PrioritizedDist,
     },
+    /// The distribution should be resolved using a compatible wheel distribution, but installed
+    /// using a source distribution, since source distributions are preferred (e.g., via
+    /// `--prefer-source`).
+    PreferredSource {
+        /// The sdist to be used during installation.
+        sdist: &'a RegistrySourceDist,
+        /// The wheel to be used during resolution.
+        wheel: &'a RegistryBuiltWheel,
+        /// The prioritized distribution that the wheel and sdist came from.
+        prioritized: &'a PrioritizedDist,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -88,6 +101,9 @@ impl Display for IncompatibleDist {
                     IncompatibleTag::Platform => {
                         f.write_str("has no wheels with a matching platform tag")
                     }
+                    IncompatibleTag::CpuFeatures => {
+                        f.write_str("has no wheels built for the available CPU features")
+                    }
                 },
                 IncompatibleWheel::Yanked(yanked) => match yanked {
                     Yanked::Bool(_) => f.write_str("was yanked"),
@@ -196,6 +212,7 @@ impl PrioritizedDist {
             wheels: vec![(dist, compatibility)],
             source: None,
             hashes,
+            prefer_source: false,
         }))
     }
 
@@ -210,6 +227,7 @@ impl PrioritizedDist {
             wheels: vec![],
             source: Some((dist, compatibility)),
             hashes,
+            prefer_source: false,
         }))
     }
 
@@ -251,10 +269,30 @@ impl PrioritizedDist {
         self.0.hashes.extend(hashes);
     }
 
+    /// Set whether the source distribution should be installed in lieu of a compatible wheel.
+    ///
+    /// The wheel will still be used during resolution, to avoid building the source distribution
+    /// just to extract its metadata.
+    pub fn set_prefer_source(&mut self, prefer_source: bool) {
+        self.0.prefer_source = prefer_source;
+    }
+
     /// Return the highest-priority distribution for the package version, if any.
     pub fn get(&self) -> Option<CompatibleDist> {
         let best_wheel = self.0.best_wheel_index.map(|i| &self.0.wheels[i]);
         match (&best_wheel, &self.0.source) {
+            // If source distributions are preferred, resolve using the wheel, but install the
+            // source distribution, unless the wheel is a better match for the required hashes.
+            (
+                Some((wheel, WheelCompatibility::Compatible(wheel_hash, ..))),
+                Some((sdist, SourceDistCompatibility::Compatible(sdist_hash))),
+            ) if self.0.prefer_source && sdist_hash >= wheel_hash => {
+                Some(CompatibleDist::PreferredSource {
+                    sdist,
+                    wheel,
+                    prioritized: self,
+                })
+            }
             // If both are compatible, break ties based on the hash outcome. For example, prefer a
             // source distribution with a matching hash over a wheel with a mismatched hash. When
             // the outcomes are equivalent (e.g., both have a matching hash), prefer the wheel.
@@ -394,6 +432,9 @@ impl<'a> CompatibleDist<'a> {
             } => ResolvedDistRef::InstallableRegistryBuiltDist { wheel, prioritized },
             CompatibleDist::IncompatibleWheel {
                 wheel, prioritized, ..
+            }
+            | CompatibleDist::PreferredSource {
+                wheel, prioritized, ..
             } => ResolvedDistRef::InstallableRegistryBuiltDist { wheel, prioritized },
         }
     }
@@ -410,6 +451,9 @@ impl<'a> CompatibleDist<'a> {
             } => ResolvedDistRef::InstallableRegistryBuiltDist { wheel, prioritized },
             CompatibleDist::IncompatibleWheel {
                 sdist, prioritized, ..
+            }
+            | CompatibleDist::PreferredSource {
+                sdist, prioritized, ..
             } => ResolvedDistRef::InstallableRegistrySourceDist { sdist, prioritized },
         }
     }
@@ -422,7 +466,8 @@ impl<'a> CompatibleDist<'a> {
             CompatibleDist::SourceDist { .. } => false,
             CompatibleDist::InstalledDist(_)
             | CompatibleDist::CompatibleWheel { .. }
-            | CompatibleDist::IncompatibleWheel { .. } => true,
+            | CompatibleDist::IncompatibleWheel { .. }
+            | CompatibleDist::PreferredSource { .. } => true,
         }
     }
 }
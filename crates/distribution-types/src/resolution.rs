@@ -65,6 +65,26 @@ impl Resolution {
         self.packages.values().map(Requirement::from)
     }
 
+    /// Retain only the distributions that match the given predicate.
+    #[must_use]
+    pub fn filter(mut self, predicate: impl Fn(&ResolvedDist) -> bool) -> Self {
+        self.packages.retain(|_, dist| predicate(dist));
+        self.hashes
+            .retain(|name, _| self.packages.contains_key(name));
+        self
+    }
+
+    /// Install any editable distributions as regular (non-editable) distributions instead.
+    #[must_use]
+    pub fn non_editable(mut self) -> Self {
+        for dist in self.packages.values_mut() {
+            if let ResolvedDist::Installable(Dist::Source(SourceDist::Directory(dist))) = dist {
+                dist.editable = false;
+            }
+        }
+        self
+    }
+
     /// Return the [`ResolutionDiagnostic`]s that were produced during resolution.
     pub fn diagnostics(&self) -> &[ResolutionDiagnostic] {
         &self.diagnostics
@@ -79,6 +99,8 @@ pub enum ResolutionDiagnostic {
         dist: ResolvedDist,
         /// The extra that was requested. For example, `colorama` in `black[colorama]`.
         extra: ExtraName,
+        /// The extras that are provided by the distribution, in sorted order.
+        available: Vec<ExtraName>,
     },
     MissingDev {
         /// The distribution that was requested with a non-existent development dependency group.
@@ -103,8 +125,23 @@ impl Diagnostic for ResolutionDiagnostic {
     /// Convert the diagnostic into a user-facing message.
     fn message(&self) -> String {
         match self {
-            Self::MissingExtra { dist, extra } => {
-                format!("The package `{dist}` does not have an extra named `{extra}`")
+            Self::MissingExtra {
+                dist,
+                extra,
+                available,
+            } => {
+                if available.is_empty() {
+                    format!("The package `{dist}` does not have an extra named `{extra}` (no extras are available)")
+                } else {
+                    format!(
+                        "The package `{dist}` does not have an extra named `{extra}` (available extras: {})",
+                        available
+                            .iter()
+                            .map(|extra| format!("`{extra}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                }
             }
             Self::MissingDev { dist, dev } => {
                 format!("The package `{dist}` does not have a development dependency group named `{dev}`")
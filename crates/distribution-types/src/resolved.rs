@@ -76,6 +76,15 @@ impl ResolvedDist {
 }
 
 impl ResolvedDistRef<'_> {
+    /// Returns the [`IndexUrl`], if the distribution is from a registry.
+    pub fn index(&self) -> Option<&IndexUrl> {
+        match self {
+            Self::InstallableRegistrySourceDist { sdist, .. } => Some(&sdist.index),
+            Self::InstallableRegistryBuiltDist { wheel, .. } => Some(&wheel.index),
+            Self::Installed(_) => None,
+        }
+    }
+
     pub fn to_owned(&self) -> ResolvedDist {
         match self {
             Self::InstallableRegistrySourceDist { sdist, prioritized } => {
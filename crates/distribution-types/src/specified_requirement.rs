@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
-use pep508_rs::{MarkerEnvironment, UnnamedRequirement};
+use pep508_rs::{MarkerEnvironment, MarkerTree, UnnamedRequirement};
 use pypi_types::{Requirement, RequirementSource};
 use uv_normalize::ExtraName;
 
@@ -82,6 +82,19 @@ impl UnresolvedRequirement {
             Self::Unnamed(requirement) => requirement.url.is_editable(),
         }
     }
+
+    /// Restrict the requirement to environments that satisfy the given marker, in addition to
+    /// any marker on the requirement itself.
+    pub fn and_marker(&mut self, marker: MarkerTree) {
+        let existing = match self {
+            Self::Named(requirement) => &mut requirement.marker,
+            Self::Unnamed(requirement) => &mut requirement.marker,
+        };
+        match existing {
+            Some(existing) => existing.and(marker),
+            None => *existing = Some(marker),
+        }
+    }
 }
 
 impl From<Requirement> for UnresolvedRequirementSpecification {
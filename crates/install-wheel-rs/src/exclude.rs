@@ -0,0 +1,238 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Component, Path};
+use std::str::FromStr;
+
+use fs_err as fs;
+use glob::{MatchOptions, Pattern, PatternError};
+use rustc_hash::FxHashMap;
+use tracing::debug;
+
+use crate::linker::LINK_MODE_FILE;
+use crate::record::RecordEntry;
+use crate::uninstall::symlinked_ancestor;
+use crate::Error;
+
+/// The files in the `.dist-info` directory that are required for the installation to be valid
+/// (or to be uninstalled), and so are never excluded.
+const REQUIRED_DIST_INFO: &[&str] = &[
+    "METADATA",
+    "RECORD",
+    "WHEEL",
+    "INSTALLER",
+    "REQUESTED",
+    "direct_url.json",
+    "entry_points.txt",
+    LINK_MODE_FILE,
+];
+
+/// A glob pattern for files to exclude from an installed wheel, e.g., `**/tests/**`.
+///
+/// Patterns are matched against paths relative to `site-packages`, using `/` as the separator.
+/// As in `.gitignore`, `*` doesn't match across directories, while `**` does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExcludePattern(Pattern);
+
+impl ExcludePattern {
+    /// Returns `true` if the pattern matches the given path, relative to `site-packages`.
+    pub fn matches(&self, path: &str) -> bool {
+        self.0.matches_with(
+            path,
+            MatchOptions {
+                case_sensitive: true,
+                require_literal_separator: true,
+                require_literal_leading_dot: false,
+            },
+        )
+    }
+}
+
+impl FromStr for ExcludePattern {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Pattern::new(s).map(Self)
+    }
+}
+
+impl fmt::Display for ExcludePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl serde::Serialize for ExcludePattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.0.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ExcludePattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for ExcludePattern {
+    fn schema_name() -> String {
+        "ExcludePattern".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                description: Some(
+                    "A glob pattern for files to exclude from an installed wheel, e.g., `**/tests/**`."
+                        .to_string(),
+                ),
+                ..schemars::schema::Metadata::default()
+            })),
+            ..schemars::schema::SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// Remove the installed files that match any of the `patterns`, along with their entries in the
+/// `record`, returning the number of files removed.
+///
+/// Only files within `site-packages` are considered. Files that aren't present (like those
+/// deferred by a lazy installation) or that are contained in a symlinked directory (as with
+/// `--link-mode=farm`, in which case removing them would modify the cache) are retained.
+pub(crate) fn exclude_files(
+    site_packages: &Path,
+    dist_info_prefix: &str,
+    patterns: &[ExcludePattern],
+    record: &mut Vec<RecordEntry>,
+) -> Result<usize, Error> {
+    let dist_info = format!("{dist_info_prefix}.dist-info");
+
+    let mut removed = 0usize;
+    let mut visited = BTreeSet::new();
+    let mut symlinks = FxHashMap::default();
+    let mut retained = Vec::with_capacity(record.len());
+    for entry in record.drain(..) {
+        if !is_excluded(&entry.path, &dist_info, patterns) {
+            retained.push(entry);
+            continue;
+        }
+
+        let path = site_packages.join(&entry.path);
+        if symlinked_ancestor(site_packages, &path, &mut symlinks).is_some() {
+            debug!("Retaining file in symlinked directory: {}", path.display());
+            retained.push(entry);
+            continue;
+        }
+
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                debug!("Excluded file: {}", path.display());
+                removed += 1;
+                if let Some(parent) = path.parent() {
+                    visited.insert(parent.to_path_buf());
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => retained.push(entry),
+            Err(err) => return Err(err.into()),
+        }
+    }
+    *record = retained;
+
+    // Remove any directories that were left empty, visiting the deepest directories first.
+    for dir in visited.iter().rev() {
+        let mut dir = dir.as_path();
+        while dir != site_packages {
+            match fs::read_dir(dir) {
+                Ok(mut entries) if entries.next().is_none() => fs::remove_dir(dir)?,
+                Ok(_) => break,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+            let Some(parent) = dir.parent() else {
+                break;
+            };
+            dir = parent;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Returns `true` if the file at the given `RECORD` path should be excluded.
+fn is_excluded(path: &str, dist_info: &str, patterns: &[ExcludePattern]) -> bool {
+    // Ignore files outside of `site-packages`, like scripts and headers.
+    if !Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return false;
+    }
+
+    // Never exclude the metadata that describes the installation itself.
+    if let Some(name) = path
+        .strip_prefix(dist_info)
+        .and_then(|path| path.strip_prefix('/'))
+    {
+        if REQUIRED_DIST_INFO.contains(&name) {
+            return false;
+        }
+    }
+
+    patterns.iter().any(|pattern| pattern.matches(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excluded() {
+        let patterns = [
+            "**/tests/**",
+            "*.dist-info/licenses/**",
+            "**/__pycache__/**",
+        ]
+        .into_iter()
+        .map(|pattern| ExcludePattern::from_str(pattern).unwrap())
+        .collect::<Vec<_>>();
+        let dist_info = "foo-1.0.0.dist-info";
+
+        assert!(is_excluded("foo/tests/test_foo.py", dist_info, &patterns));
+        assert!(is_excluded("tests/conftest.py", dist_info, &patterns));
+        assert!(is_excluded(
+            "foo/__pycache__/bar.cpython-312.pyc",
+            dist_info,
+            &patterns
+        ));
+        assert!(is_excluded(
+            "foo-1.0.0.dist-info/licenses/LICENSE",
+            dist_info,
+            &patterns
+        ));
+        assert!(!is_excluded("foo/testing.py", dist_info, &patterns));
+        assert!(!is_excluded("foo/tests.py", dist_info, &patterns));
+        assert!(!is_excluded("../../bin/tests/foo", dist_info, &patterns));
+
+        let patterns = [ExcludePattern::from_str("**").unwrap()];
+        assert!(is_excluded("foo/__init__.py", dist_info, &patterns));
+        assert!(!is_excluded(
+            "foo-1.0.0.dist-info/RECORD",
+            dist_info,
+            &patterns
+        ));
+        assert!(!is_excluded(
+            "foo-1.0.0.dist-info/METADATA",
+            dist_info,
+            &patterns
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+//! Lazy installation of wheels.
+//!
+//! Rather than linking the entire wheel into site-packages, only the metadata (the `.dist-info`
+//! and `.data` directories) and any `.pth` files are installed upfront. The remaining top-level
+//! entries are materialized from the cache on first import, by a small loader that's registered
+//! via a `.pth` file.
+//!
+//! Both steps respect the [`LinkMode`]: the metadata is linked as it would be for an eager
+//! installation, and the loader links (or copies) the deferred entries in the same way.
+
+use std::path::Path;
+
+use fs_err as fs;
+use tracing::debug;
+
+use uv_fs::CASE_SHADOWED_DIR;
+use uv_normalize::PackageName;
+
+use crate::linker::{synchronized_copy, LinkMode, Locks};
+use crate::record::RecordEntry;
+use crate::Error;
+
+/// The loader that materializes deferred entries, to be prefixed with its configuration.
+const LOADER: &str = include_str!("lazy_loader.py");
+
+/// Link the metadata of a wheel into site-packages with the given [`LinkMode`], returning the
+/// number of files that were linked along with the top-level entries that were deferred.
+pub(crate) fn link_wheel_files(
+    site_packages: &Path,
+    wheel: &Path,
+    link_mode: LinkMode,
+    locks: &Locks,
+) -> Result<(usize, Vec<String>), Error> {
+    let mut count = 0usize;
+    let mut deferred = Vec::new();
+
+    // As with `--link-mode=farm`, the metadata directories are modified during installation, so
+    // they're linked file-by-file rather than symbolically linked as a whole.
+    let metadata_link_mode = match link_mode {
+        LinkMode::Farm => LinkMode::Hardlink,
+        link_mode => link_mode,
+    };
+
+    for entry in fs::read_dir(wheel)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name == CASE_SHADOWED_DIR {
+            continue;
+        }
+        let is_dir = entry.file_type()?.is_dir();
+        if !is_eager(&file_name, is_dir) {
+            deferred.push(file_name);
+            continue;
+        }
+
+        let out_path = site_packages.join(&file_name);
+        if is_dir {
+            fs::create_dir_all(&out_path)?;
+            count += metadata_link_mode.link_wheel_files(&out_path, entry.path(), locks)?;
+        } else {
+            // `.pth` files are executed at interpreter startup, so they're always copied.
+            synchronized_copy(&entry.path(), &out_path, locks)?;
+            count += 1;
+        }
+    }
+
+    deferred.sort();
+    Ok((count, deferred))
+}
+
+/// Write the loader for the deferred entries of a wheel, along with the `.pth` file that imports
+/// it at interpreter startup, and add both to the `RECORD`.
+///
+/// The loader materializes the deferred entries with the given [`LinkMode`].
+pub(crate) fn write_loader(
+    site_packages: &Path,
+    wheel: &Path,
+    name: &PackageName,
+    deferred: &[String],
+    link_mode: LinkMode,
+    record: &mut Vec<RecordEntry>,
+) -> Result<(), Error> {
+    let module = format!("_uv_lazy_{}", name.as_dist_info_name());
+    let modules = top_level_modules(deferred);
+    debug!(
+        ?name,
+        "Deferring {} top-level entries ({})",
+        deferred.len(),
+        modules.join(", ")
+    );
+
+    let loader = format!(
+        "# Generated by uv to materialize a lazily-installed wheel on first import.\n\
+         SOURCE = {}\n\
+         TARGET = {}\n\
+         ENTRIES = {}\n\
+         MODULES = frozenset({})\n\
+         LINK_MODE = {}\n\n\
+         {LOADER}",
+        literal(&wheel.to_string_lossy()),
+        literal(&site_packages.to_string_lossy()),
+        literal(&deferred),
+        literal(&modules),
+        literal(&link_mode),
+    );
+
+    for (path, contents) in [
+        (format!("{module}.py"), loader),
+        (format!("{module}.pth"), format!("import {module}\n")),
+    ] {
+        fs::write(site_packages.join(&path), contents.as_bytes())?;
+        record.push(RecordEntry {
+            path,
+            hash: None,
+            size: Some(contents.len() as u64),
+        });
+    }
+
+    Ok(())
+}
+
+/// Format a value as a Python literal. JSON strings and arrays of strings are valid Python, and a
+/// [`LinkMode`] serializes to a string.
+fn literal(value: &impl serde::Serialize) -> String {
+    serde_json::to_string(value).expect("strings to be serializable")
+}
+
+/// Returns `true` if the top-level entry should be installed upfront.
+fn is_eager(file_name: &str, is_dir: bool) -> bool {
+    if is_dir {
+        file_name.ends_with(".dist-info") || file_name.ends_with(".data")
+    } else {
+        Path::new(file_name)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("pth"))
+    }
+}
+
+/// Return the importable top-level module names among the given entries, e.g., `torch` for
+/// `torch/` and `_cffi_backend` for `_cffi_backend.cpython-312-x86_64-linux-gnu.so`.
+fn top_level_modules(entries: &[String]) -> Vec<&str> {
+    let mut modules = entries
+        .iter()
+        .filter_map(|entry| {
+            let path = Path::new(entry);
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some("py" | "pyc" | "so" | "pyd") => entry.split('.').next(),
+                Some(_) => None,
+                None => Some(entry.as_str()),
+            }
+        })
+        .collect::<Vec<_>>();
+    modules.dedup();
+    modules
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_eager, top_level_modules};
+
+    #[test]
+    fn eager_entries() {
+        assert!(is_eager("torch-2.4.0.dist-info", true));
+        assert!(is_eager("torch-2.4.0.data", true));
+        assert!(is_eager("distutils-precedence.pth", false));
+        assert!(!is_eager("torch", true));
+        assert!(!is_eager("six.py", false));
+    }
+
+    #[test]
+    fn modules() {
+        let entries = [
+            "_cffi_backend.cpython-312-x86_64-linux-gnu.so".to_string(),
+            "six.py".to_string(),
+            "torch".to_string(),
+            "torch.libs".to_string(),
+            "torchgen".to_string(),
+        ];
+        assert_eq!(
+            top_level_modules(&entries),
+            vec!["_cffi_backend", "six", "torch", "torchgen"]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+import importlib
+import os
+import shutil
+import sys
+import threading
+
+_lock = threading.Lock()
+
+
+def _clone(source, target):
+    try:
+        if sys.platform == "darwin":
+            import ctypes
+
+            libc = ctypes.CDLL(None, use_errno=True)
+            if libc.clonefile(os.fsencode(source), os.fsencode(target), 0) == 0:
+                return
+        elif sys.platform == "linux":
+            import fcntl
+
+            with open(source, "rb") as src, open(target, "wb") as dst:
+                # `FICLONE`, i.e., a copy-on-write reflink.
+                fcntl.ioctl(dst.fileno(), 0x40049409, src.fileno())
+            shutil.copystat(source, target)
+            return
+    except (OSError, AttributeError):
+        pass
+    shutil.copy2(source, target)
+
+
+def _hardlink(source, target):
+    try:
+        os.link(source, target)
+    except OSError:
+        shutil.copy2(source, target)
+
+
+def _symlink(source, target):
+    try:
+        os.symlink(source, target)
+    except OSError:
+        shutil.copy2(source, target)
+
+
+_LINKERS = {
+    "clone": _clone,
+    "copy": shutil.copy2,
+    "hardlink": _hardlink,
+    "symlink": _symlink,
+    # As in site-packages, top-level directories are linked as a whole (below), and files are
+    # hard linked.
+    "farm": _hardlink,
+}
+
+
+def _link(source, target):
+    # Leave any existing file in place (e.g., from an interrupted materialization); in particular,
+    # never write through a symlink into the cache.
+    if not os.path.lexists(target):
+        _LINKERS[LINK_MODE](source, target)
+
+
+def _materialize():
+    for entry in ENTRIES:
+        source = os.path.join(SOURCE, entry)
+        target = os.path.join(TARGET, entry)
+        if not os.path.exists(source):
+            raise ImportError(
+                f"Failed to materialize lazily-installed `{entry}`: {source} no longer exists. "
+                "Reinstall the package to restore it."
+            )
+        if os.path.isdir(source):
+            if LINK_MODE == "farm" and not os.path.lexists(target):
+                try:
+                    os.symlink(source, target, target_is_directory=True)
+                    continue
+                except OSError:
+                    pass
+            shutil.copytree(source, target, copy_function=_link, dirs_exist_ok=True)
+        else:
+            _link(source, target)
+    importlib.invalidate_caches()
+
+
+class _LazyFinder:
+    """Materialize the deferred files on the first import of any of the wheel's modules."""
+
+    def find_spec(self, fullname, path=None, target=None):
+        if fullname.partition(".")[0] not in MODULES:
+            return None
+        with _lock:
+            if self in sys.meta_path:
+                _materialize()
+                sys.meta_path.remove(self)
+        # Defer to the regular finders, which can now locate the materialized files.
+        return None
+
+
+if not all(os.path.exists(os.path.join(TARGET, entry)) for entry in ENTRIES):
+    sys.meta_path.insert(0, _LazyFinder())
@@ -7,17 +7,22 @@ use platform_info::PlatformInfoError;
 use thiserror::Error;
 use zip::result::ZipError;
 
+pub use exclude::ExcludePattern;
 use pep440_rs::Version;
 use platform_tags::{Arch, Os};
 use pypi_types::Scheme;
+pub use relocate::relocate_scripts;
 pub use uninstall::{uninstall_egg, uninstall_legacy_editable, uninstall_wheel, Uninstall};
 use uv_fs::Simplified;
 use uv_normalize::PackageName;
 pub use wheel::{parse_wheel_file, read_record_file, LibKind};
 
+mod exclude;
+mod lazy;
 pub mod linker;
 pub mod metadata;
 mod record;
+mod relocate;
 mod script;
 mod uninstall;
 mod wheel;
@@ -31,6 +36,9 @@ pub struct Layout {
     pub python_version: (u8, u8),
     /// The `os.name` value for the current platform.
     pub os_name: String,
+    /// The architecture of the Python interpreter, which may differ from that of the running
+    /// process (e.g., an ARM64 interpreter on Windows on ARM, used by an emulated x86-64 process).
+    pub arch: Arch,
     /// The [`Scheme`] paths for the interpreter.
     pub scheme: Scheme,
 }
@@ -14,23 +14,45 @@ use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use tempfile::tempdir_in;
 use tracing::{debug, instrument};
-use uv_warnings::warn_user_once;
+use uv_fs::{is_case_insensitive, CASE_SHADOWED_DIR};
+use uv_normalize::PackageName;
+use uv_warnings::{warn_user, warn_user_once};
 use walkdir::WalkDir;
 
+use crate::exclude::exclude_files;
+use crate::lazy;
 use crate::script::{scripts_from_ini, Script};
 use crate::wheel::{
-    extra_dist_info, install_data, parse_wheel_file, read_record_file, write_script_entrypoints,
-    LibKind,
+    extra_dist_info, install_data, parse_wheel_file, read_record_file, write_file_recorded,
+    write_script_entrypoints, LibKind,
 };
-use crate::{Error, Layout};
+use crate::{Error, ExcludePattern, Layout};
 
 #[derive(Debug, Default)]
 pub struct Locks(Mutex<FxHashMap<PathBuf, Arc<Mutex<()>>>>);
 
+impl Locks {
+    /// Return the lock for the given path, creating it if necessary.
+    fn get(&self, path: &Path) -> Arc<Mutex<()>> {
+        let mut locks = self.0.lock().unwrap();
+        locks
+            .entry(path.to_path_buf())
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone()
+    }
+}
+
 /// Install the given wheel to the given venv
 ///
 /// The caller must ensure that the wheel is compatible to the environment.
 ///
+/// If `lazy` is set, only the wheel's metadata is installed upfront, and its top-level modules are
+/// materialized from `wheel` with the same `link_mode` on first import. As with symlinks, the
+/// wheel must outlive the installation.
+///
+/// Any files within `site-packages` that match one of the `exclude` patterns are removed after
+/// installation, and omitted from the `RECORD`.
+///
 /// <https://packaging.python.org/en/latest/specifications/binary-distribution-format/#installing-a-wheel-distribution-1-0-py32-none-any-whl>
 ///
 /// Wheel 1.0: <https://www.python.org/dev/peps/pep-0427/>
@@ -43,6 +65,8 @@ pub fn install_wheel(
     direct_url: Option<&DirectUrl>,
     installer: Option<&str>,
     link_mode: LinkMode,
+    lazy: bool,
+    exclude: &[ExcludePattern],
     locks: &Locks,
 ) -> Result<(), Error> {
     let dist_info_prefix = find_dist_info(&wheel)?;
@@ -78,8 +102,16 @@ pub fn install_wheel(
         LibKind::Pure => &layout.scheme.purelib,
         LibKind::Plat => &layout.scheme.platlib,
     };
-    let num_unpacked = link_mode.link_wheel_files(site_packages, &wheel, locks)?;
-    debug!(?name, "Extracted {num_unpacked} files");
+    let (num_unpacked, deferred) = if lazy {
+        lazy::link_wheel_files(site_packages, wheel.as_ref(), link_mode, locks)?
+    } else {
+        (
+            link_mode.link_wheel_files(site_packages, &wheel, locks)?,
+            Vec::new(),
+        )
+    };
+    let num_shadowed = install_shadowed(site_packages, wheel.as_ref(), &name, &deferred, locks)?;
+    debug!(?name, "Extracted {} files", num_unpacked + num_shadowed);
 
     // Read the RECORD file.
     let mut record_file = File::open(
@@ -89,6 +121,18 @@ pub fn install_wheel(
     )?;
     let mut record = read_record_file(&mut record_file)?;
 
+    if !deferred.is_empty() {
+        debug!(?name, "Writing lazy loader");
+        lazy::write_loader(
+            site_packages,
+            wheel.as_ref(),
+            &name,
+            &deferred,
+            link_mode,
+            &mut record,
+        )?;
+    }
+
     let (console_scripts, gui_scripts) =
         parse_scripts(&wheel, &dist_info_prefix, None, layout.python_version.1)?;
 
@@ -149,6 +193,24 @@ pub fn install_wheel(
         &mut record,
     )?;
 
+    if link_mode == LinkMode::Farm && !lazy {
+        // Mark the installation as linked into the cache, such that uninstalling it removes the
+        // symlinks, rather than the files they point to.
+        debug!(?name, "Writing link mode");
+        write_file_recorded(
+            site_packages,
+            &PathBuf::from(format!("{dist_info_prefix}.dist-info")).join(LINK_MODE_FILE),
+            "farm",
+            &mut record,
+        )?;
+        write_pycache_prefix(site_packages)?;
+    }
+
+    if !exclude.is_empty() {
+        let num_excluded = exclude_files(site_packages, &dist_info_prefix, exclude, &mut record)?;
+        debug!(?name, "Excluded {num_excluded} files");
+    }
+
     debug!(?name, "Writing record");
     let mut record_writer = csv::WriterBuilder::new()
         .has_headers(false)
@@ -162,6 +224,33 @@ pub fn install_wheel(
     Ok(())
 }
 
+/// The file in the `.dist-info` directory that records the [`LinkMode`] of the installation, if it
+/// was installed with [`LinkMode::Farm`].
+pub(crate) const LINK_MODE_FILE: &str = "uv_link_mode";
+
+/// The `.pth` file that redirects the bytecode of an environment installed with
+/// [`LinkMode::Farm`].
+const PYCACHE_PREFIX_PTH: &str = "_uv_pycache_prefix.pth";
+
+/// Redirect any bytecode written by the interpreter to a `__pycache__` directory at the root of
+/// the environment, by way of `sys.pycache_prefix`.
+///
+/// With [`LinkMode::Farm`], package directories are symlinks into the cache, so the interpreter
+/// would otherwise write `__pycache__` directories into the cache, where they'd be shared by every
+/// environment. A prefix that's already set (e.g., via `PYTHONPYCACHEPREFIX`) is respected.
+fn write_pycache_prefix(site_packages: &Path) -> Result<(), Error> {
+    let path = site_packages.join(PYCACHE_PREFIX_PTH);
+    if path.is_file() {
+        return Ok(());
+    }
+    debug!("Writing pycache prefix: {}", path.display());
+    uv_fs::write_atomic_sync(
+        path,
+        "import os, sys; sys.pycache_prefix = sys.pycache_prefix or os.path.join(sys.prefix, '__pycache__')\n",
+    )?;
+    Ok(())
+}
+
 /// Find the `dist-info` directory in an unzipped wheel.
 ///
 /// See: <https://github.com/PyO3/python-pkginfo-rs>
@@ -246,6 +335,18 @@ pub enum LinkMode {
     /// break all installed packages by way of removing the underlying source files. Use symlinks
     /// with caution.
     Symlink,
+    /// Compose the `site-packages` directory as a farm of links into the cache, symbolically
+    /// linking each top-level package directory and hard linking any top-level files.
+    ///
+    /// As the contents of each package are shared across all environments, rather than linked
+    /// file-by-file, this mode reduces both the disk usage and the install time for machines with
+    /// many similar environments. As with `symlink`, clearing the cache will break all installed
+    /// packages, and modifying an installed package will modify it for every environment. To keep
+    /// the cache free of bytecode, the interpreter is configured to write bytecode to a
+    /// `__pycache__` directory at the root of the environment instead.
+    ///
+    /// This mode is experimental, requires `--preview`, and may change without warning.
+    Farm,
 }
 
 impl Default for LinkMode {
@@ -258,6 +359,90 @@ impl Default for LinkMode {
     }
 }
 
+/// Returns `true` if the entry is the [`CASE_SHADOWED_DIR`] at the root of a wheel, which is
+/// installed by [`install_shadowed`] rather than linked.
+fn is_case_shadowed(entry: &walkdir::DirEntry) -> bool {
+    entry.depth() == 1 && entry.file_name() == CASE_SHADOWED_DIR
+}
+
+/// Install the files of a wheel that were extracted to the [`CASE_SHADOWED_DIR`], since their
+/// paths differ only by case from that of another file in the wheel.
+///
+/// If the directory that would contain the file is on a case-sensitive filesystem, the file is
+/// copied to its own path. Otherwise, the path refers to the file that shadowed it, so the file
+/// can't be installed and the user is warned instead.
+///
+/// Files within top-level entries that were deferred by a lazy installation are skipped, since
+/// the lazy loader materializes those entries from the cache as a whole.
+///
+/// Returns the number of files installed.
+fn install_shadowed(
+    site_packages: &Path,
+    wheel: &Path,
+    name: &PackageName,
+    deferred: &[String],
+    locks: &Locks,
+) -> Result<usize, Error> {
+    let shadowed_dir = wheel.join(CASE_SHADOWED_DIR);
+    if !shadowed_dir.is_dir() {
+        return Ok(0);
+    }
+
+    // Each file is stored under the index of its entry in the archive.
+    let mut shadowed = Vec::new();
+    for index in fs::read_dir(&shadowed_dir)? {
+        let index = index?.path();
+        for entry in WalkDir::new(&index) {
+            let entry = entry?;
+            if entry.file_type().is_dir() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(&index).unwrap().to_path_buf();
+            if relative.components().next().is_some_and(|top_level| {
+                deferred
+                    .iter()
+                    .any(|entry| top_level.as_os_str() == entry.as_str())
+            }) {
+                debug!(
+                    "Skipping shadowed file within deferred entry: {}",
+                    relative.display()
+                );
+                continue;
+            }
+            shadowed.push((entry.into_path(), relative));
+        }
+    }
+
+    // The parent directory may be linked into the cache (e.g., with `--link-mode=farm`), so the
+    // filesystem is probed for each file.
+    let mut count = 0usize;
+    let mut skipped = Vec::new();
+    for (path, relative) in shadowed {
+        let out_path = site_packages.join(&relative);
+        let parent = out_path.parent().unwrap_or(site_packages);
+        if is_case_insensitive(parent)? {
+            skipped.push(relative);
+            continue;
+        }
+        synchronized_copy(&path, &out_path, locks)?;
+        count += 1;
+    }
+
+    if !skipped.is_empty() {
+        skipped.sort();
+        warn_user!(
+            "The wheel for `{name}` contains paths that differ only by case, which refer to the same file on a case-insensitive filesystem; the following files were not installed:\n{}",
+            skipped
+                .iter()
+                .map(|relative| format!("  `{}`", relative.display()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    Ok(count)
+}
+
 impl LinkMode {
     /// Extract a wheel by linking all of its files into site packages.
     #[instrument(skip_all)]
@@ -272,12 +457,14 @@ impl LinkMode {
             Self::Copy => copy_wheel_files(site_packages, wheel, locks),
             Self::Hardlink => hardlink_wheel_files(site_packages, wheel, locks),
             Self::Symlink => symlink_wheel_files(site_packages, wheel, locks),
+            Self::Farm => farm_wheel_files(site_packages, wheel, locks),
         }
     }
 
-    /// Returns `true` if the link mode is [`LinkMode::Symlink`].
+    /// Returns `true` if the link mode creates symbolic links into the cache, i.e., if it's
+    /// [`LinkMode::Symlink`] or [`LinkMode::Farm`].
     pub fn is_symlink(&self) -> bool {
-        matches!(self, Self::Symlink)
+        matches!(self, Self::Symlink | Self::Farm)
     }
 }
 
@@ -299,11 +486,15 @@ fn clone_wheel_files(
     // subdirectory unless the subdirectory exists already in which case we'll need to recursively
     // merge its contents with the existing directory.
     for entry in fs::read_dir(wheel.as_ref())? {
+        let entry = entry?;
+        if entry.file_name() == CASE_SHADOWED_DIR {
+            continue;
+        }
         clone_recursive(
             site_packages.as_ref(),
             wheel.as_ref(),
             locks,
-            &entry?,
+            &entry,
             &mut attempt,
         )?;
         count += 1;
@@ -457,7 +648,10 @@ fn copy_wheel_files(
     let mut count = 0usize;
 
     // Walk over the directory.
-    for entry in walkdir::WalkDir::new(&wheel) {
+    for entry in walkdir::WalkDir::new(&wheel)
+        .into_iter()
+        .filter_entry(|entry| !is_case_shadowed(entry))
+    {
         let entry = entry?;
         let path = entry.path();
 
@@ -487,7 +681,10 @@ fn hardlink_wheel_files(
     let mut count = 0usize;
 
     // Walk over the directory.
-    for entry in walkdir::WalkDir::new(&wheel) {
+    for entry in walkdir::WalkDir::new(&wheel)
+        .into_iter()
+        .filter_entry(|entry| !is_case_shadowed(entry))
+    {
         let entry = entry?;
         let path = entry.path();
 
@@ -583,7 +780,10 @@ fn symlink_wheel_files(
     let mut count = 0usize;
 
     // Walk over the directory.
-    for entry in WalkDir::new(&wheel) {
+    for entry in WalkDir::new(&wheel)
+        .into_iter()
+        .filter_entry(|entry| !is_case_shadowed(entry))
+    {
         let entry = entry?;
         let path = entry.path();
 
@@ -669,19 +869,151 @@ fn symlink_wheel_files(
     Ok(count)
 }
 
+/// Extract a wheel by symbolically linking its top-level directories into site packages, and hard
+/// linking its top-level files.
+///
+/// Unlike [`symlink_wheel_files`], the number of links is proportional to the number of top-level
+/// entries in the wheel, rather than the number of files. The `.dist-info` and `.data` directories
+/// are modified during installation, so they're hard linked file-by-file instead.
+///
+/// If a directory already exists in site packages (e.g., a namespace package that's shared by
+/// multiple wheels), the two are merged: an existing symlink is replaced by a directory of links to
+/// its contents, into which the wheel's entries are then linked.
+fn farm_wheel_files(
+    site_packages: impl AsRef<Path>,
+    wheel: impl AsRef<Path>,
+    locks: &Locks,
+) -> Result<usize, Error> {
+    let mut attempt = Attempt::default();
+    let mut count = 0usize;
+
+    for entry in fs::read_dir(wheel.as_ref())? {
+        let entry = entry?;
+        if entry.file_name() == CASE_SHADOWED_DIR {
+            continue;
+        }
+        let from = entry.path();
+        let to = site_packages.as_ref().join(entry.file_name());
+
+        if entry.file_type()?.is_dir()
+            && from
+                .extension()
+                .is_some_and(|ext| ext == "dist-info" || ext == "data")
+        {
+            count += hardlink_wheel_files(&to, &from, locks)?;
+            continue;
+        }
+
+        // Multiple wheels may contribute to the same top-level directory, so hold a lock while
+        // linking (and, if necessary, merging) it.
+        let lock = locks.get(&to);
+        let _guard = lock.lock().unwrap();
+        count += farm_link(&from, &to, &mut attempt)?;
+    }
+
+    Ok(count)
+}
+
+/// Link an entry from a wheel in the cache into site packages, merging it with any existing
+/// directory.
+///
+/// Returns the number of links created.
+fn farm_link(from: &Path, to: &Path, attempt: &mut Attempt) -> Result<usize, Error> {
+    if !from.is_dir() {
+        farm_link_file(from, to)?;
+        return Ok(1);
+    }
+
+    match fs::symlink_metadata(to) {
+        Ok(metadata) => {
+            if metadata.is_symlink() {
+                let target = to.parent().unwrap().join(fs::read_link(to)?);
+                if target == from {
+                    return Ok(0);
+                }
+
+                // Replace the symlink with a directory of links to the existing contents, such
+                // that the contents of both can be merged.
+                debug!("Merging {} into {}", from.display(), to.display());
+                remove_symlink(to)?;
+                fs::create_dir(to)?;
+                for entry in fs::read_dir(&target)? {
+                    let entry = entry?;
+                    farm_link(&entry.path(), &to.join(entry.file_name()), attempt)?;
+                }
+            }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            // Fallback to hard linking each file if symlinks aren't supported for this installation.
+            if *attempt != Attempt::UseCopyFallback {
+                match create_symlink(from, to) {
+                    Ok(()) => {
+                        *attempt = Attempt::Subsequent;
+                        return Ok(1);
+                    }
+                    Err(err) if *attempt == Attempt::Initial => {
+                        debug!(
+                            "Failed to symlink `{}` to `{}`, attempting to hard link files as a fallback: {err}",
+                            from.display(),
+                            to.display()
+                        );
+                        *attempt = Attempt::UseCopyFallback;
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            warn_user_once!("Failed to symlink directories; falling back to linking each file. This may lead to degraded performance. If this is intentional, use `--link-mode=hardlink` to suppress this warning.");
+            fs::create_dir(to)?;
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    let mut count = 0usize;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        count += farm_link(&entry.path(), &to.join(entry.file_name()), attempt)?;
+    }
+    Ok(count)
+}
+
+/// Hard link a file from a wheel in the cache into site packages, falling back to copying if hard
+/// links aren't supported, and overwriting any existing file.
+fn farm_link_file(from: &Path, to: &Path) -> Result<(), Error> {
+    let link = |from: &Path, to: &Path| {
+        fs::hard_link(from, to).or_else(|err| {
+            if err.kind() == std::io::ErrorKind::AlreadyExists {
+                return Err(err);
+            }
+            debug!(
+                "Failed to hard link `{}` to `{}`, copying instead: {err}",
+                from.display(),
+                to.display()
+            );
+            fs::copy(from, to).map(|_| ())
+        })
+    };
+
+    if let Err(err) = link(from, to) {
+        if err.kind() != std::io::ErrorKind::AlreadyExists {
+            return Err(err.into());
+        }
+        debug!("File already exists, overwriting: {}", to.display());
+        // Removing and recreating would lead to race conditions.
+        let tempdir = tempdir_in(to.parent().unwrap())?;
+        let tempfile = tempdir.path().join(from.file_name().unwrap());
+        link(from, &tempfile)?;
+        fs::rename(&tempfile, to)?;
+    }
+    Ok(())
+}
+
 /// Copy from `from` to `to`, ensuring that the parent directory is locked. Avoids simultaneous
 /// writes to the same file, which can lead to corruption.
 ///
 /// See: <https://github.com/astral-sh/uv/issues/4831>
-fn synchronized_copy(from: &Path, to: &Path, locks: &Locks) -> std::io::Result<()> {
+pub(crate) fn synchronized_copy(from: &Path, to: &Path, locks: &Locks) -> std::io::Result<()> {
     // Ensure we have a lock for the directory.
-    let dir_lock = {
-        let mut locks_guard = locks.0.lock().unwrap();
-        locks_guard
-            .entry(to.parent().unwrap().to_path_buf())
-            .or_insert_with(|| Arc::new(Mutex::new(())))
-            .clone()
-    };
+    let dir_lock = locks.get(to.parent().unwrap());
 
     // Acquire a lock on the directory.
     let _dir_guard = dir_lock.lock().unwrap();
@@ -705,3 +1037,82 @@ fn create_symlink<P: AsRef<Path>, Q: AsRef<Path>>(original: P, link: Q) -> std::
         std::os::windows::fs::symlink_file(original, link)
     }
 }
+
+/// Remove a symbolic link, regardless of whether it points to a file or a directory.
+#[cfg(unix)]
+pub(crate) fn remove_symlink(path: &Path) -> std::io::Result<()> {
+    fs::remove_file(path)
+}
+
+/// Remove a symbolic link, regardless of whether it points to a file or a directory.
+#[cfg(windows)]
+pub(crate) fn remove_symlink(path: &Path) -> std::io::Result<()> {
+    // On Windows, directory symlinks must be removed as directories.
+    fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use fs_err as fs;
+    use uv_fs::{is_case_insensitive, CASE_SHADOWED_DIR};
+    use uv_normalize::PackageName;
+
+    use super::{install_shadowed, Locks};
+
+    #[test]
+    fn shadowed_files() -> anyhow::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let wheel = temp_dir.path().join("wheel");
+        let site_packages = temp_dir.path().join("site-packages");
+
+        // The wheel contains `pkg/Foo.py`, which was shadowed by `pkg/foo.py` on extraction.
+        fs::create_dir_all(wheel.join(CASE_SHADOWED_DIR).join("1").join("pkg"))?;
+        fs::write(
+            wheel
+                .join(CASE_SHADOWED_DIR)
+                .join("1")
+                .join("pkg")
+                .join("Foo.py"),
+            "first",
+        )?;
+        fs::create_dir_all(site_packages.join("pkg"))?;
+        fs::write(site_packages.join("pkg").join("foo.py"), "second")?;
+
+        let name = PackageName::from_str("pkg")?;
+        let count = install_shadowed(&site_packages, &wheel, &name, &[], &Locks::default())?;
+
+        if is_case_insensitive(&site_packages)? {
+            assert_eq!(count, 0);
+            assert_eq!(
+                fs::read_to_string(site_packages.join("pkg").join("foo.py"))?,
+                "second"
+            );
+        } else {
+            assert_eq!(count, 1);
+            assert_eq!(
+                fs::read_to_string(site_packages.join("pkg").join("Foo.py"))?,
+                "first"
+            );
+            assert_eq!(
+                fs::read_to_string(site_packages.join("pkg").join("foo.py"))?,
+                "second"
+            );
+        }
+
+        // Files within deferred entries are left to the lazy loader.
+        let site_packages = temp_dir.path().join("lazy");
+        let count = install_shadowed(
+            &site_packages,
+            &wheel,
+            &name,
+            &["pkg".to_string()],
+            &Locks::default(),
+        )?;
+        assert_eq!(count, 0);
+        assert!(!site_packages.join("pkg").exists());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+//! Rewrite the scripts in an environment after moving it to a new location.
+
+use std::path::Path;
+
+use fs_err as fs;
+use tracing::debug;
+
+use uv_fs::Simplified;
+
+use crate::wheel::{format_shebang, LAUNCHER_MAGIC_NUMBER};
+use crate::Error;
+
+/// Rewrite the scripts in the given directory, such that any references to an environment at
+/// `from` (e.g., in shebangs, Windows launchers, or activation scripts) refer to `to` instead.
+///
+/// This is intended to be called after an environment is built at `from`, but before it's moved
+/// to `to`, since only the scripts refer to the environment by absolute path.
+pub fn relocate_scripts(scripts: &Path, from: &Path, to: &Path) -> Result<(), Error> {
+    let from = from.simplified_display().to_string();
+    let to = to.simplified_display().to_string();
+
+    for entry in fs::read_dir(scripts)? {
+        let entry = entry?;
+
+        // Skip directories and symlinks (e.g., to the base interpreter).
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+
+        let path = entry.path();
+        let contents = fs::read(&path)?;
+        let relocated = if contents.ends_with(&LAUNCHER_MAGIC_NUMBER) {
+            relocate_launcher(&contents, &from, &to)
+        } else if let Ok(contents) = std::str::from_utf8(&contents) {
+            relocate_text(contents, &from, &to).map(String::into_bytes)
+        } else {
+            None
+        };
+
+        if let Some(relocated) = relocated {
+            debug!("Relocating script: {}", path.user_display());
+            fs::write(&path, relocated)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrite a Windows launcher, which ends with the path to the Python executable, followed by the
+/// length of the path and the launcher magic number.
+///
+/// Returns `None` if the launcher doesn't refer to `from`.
+fn relocate_launcher(contents: &[u8], from: &str, to: &str) -> Option<Vec<u8>> {
+    let trailer = contents
+        .len()
+        .checked_sub(LAUNCHER_MAGIC_NUMBER.len() + 4)?;
+    let length = u32::from_le_bytes(contents[trailer..trailer + 4].try_into().ok()?);
+    let start = trailer.checked_sub(usize::try_from(length).ok()?)?;
+    let python = std::str::from_utf8(&contents[start..trailer]).ok()?;
+    let python = format!("{to}{}", python.strip_prefix(from)?);
+
+    let mut launcher = Vec::with_capacity(start + python.len() + 8);
+    launcher.extend_from_slice(&contents[..start]);
+    launcher.extend_from_slice(python.as_bytes());
+    launcher.extend_from_slice(&u32::try_from(python.len()).ok()?.to_le_bytes());
+    launcher.extend_from_slice(&LAUNCHER_MAGIC_NUMBER);
+    Some(launcher)
+}
+
+/// Rewrite a text script, such as an entrypoint or an activation script.
+///
+/// A simple shebang is re-formatted for the new location, since it may now need to be wrapped in
+/// `/bin/sh` (e.g., if the new path is too long); any other references are replaced verbatim.
+///
+/// Returns `None` if the script doesn't refer to `from`.
+fn relocate_text(contents: &str, from: &str, to: &str) -> Option<String> {
+    if !contents.contains(from) {
+        return None;
+    }
+
+    if let Some(executable) = contents
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("#!"))
+        .and_then(|executable| executable.strip_prefix(from))
+    {
+        let shebang = format_shebang(format!("{to}{executable}"), "posix", false);
+        let rest = contents.split_once('\n').map_or("", |(_, rest)| rest);
+        return Some(format!("{shebang}\n{}", rest.replace(from, to)));
+    }
+
+    Some(contents.replace(from, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{relocate_launcher, relocate_text};
+    use crate::wheel::LAUNCHER_MAGIC_NUMBER;
+
+    #[test]
+    fn shebang() {
+        let script = "#!/tmp/staging/bin/python\nimport sys\n";
+        assert_eq!(
+            relocate_text(script, "/tmp/staging", "/home/user/tools/black").as_deref(),
+            Some("#!/home/user/tools/black/bin/python\nimport sys\n")
+        );
+
+        // A path with spaces requires a `/bin/sh` wrapper.
+        assert_eq!(
+            relocate_text(script, "/tmp/staging", "/home/user/my tools/black").as_deref(),
+            Some("#!/bin/sh\n'''exec' '/home/user/my tools/black/bin/python' \"$0\" \"$@\"\n' '''\nimport sys\n")
+        );
+
+        // Scripts that don't refer to the environment are left unchanged.
+        assert_eq!(relocate_text(script, "/tmp/other", "/home/user"), None);
+    }
+
+    #[test]
+    fn launcher() {
+        let python = "C:\\staging\\Scripts\\python.exe";
+        let mut launcher = b"launcher".to_vec();
+        launcher.extend_from_slice(python.as_bytes());
+        launcher.extend_from_slice(&u32::try_from(python.len()).unwrap().to_le_bytes());
+        launcher.extend_from_slice(&LAUNCHER_MAGIC_NUMBER);
+
+        let python = "C:\\tools\\black\\Scripts\\python.exe";
+        let mut expected = b"launcher".to_vec();
+        expected.extend_from_slice(python.as_bytes());
+        expected.extend_from_slice(&u32::try_from(python.len()).unwrap().to_le_bytes());
+        expected.extend_from_slice(&LAUNCHER_MAGIC_NUMBER);
+
+        assert_eq!(
+            relocate_launcher(&launcher, "C:\\staging", "C:\\tools\\black"),
+            Some(expected)
+        );
+    }
+}
@@ -2,10 +2,12 @@ use std::collections::BTreeSet;
 use std::path::{Component, Path, PathBuf};
 
 use fs_err as fs;
+use rustc_hash::FxHashMap;
 use std::sync::{LazyLock, Mutex};
 use tracing::debug;
 use uv_fs::write_atomic_sync;
 
+use crate::linker::{remove_symlink, LINK_MODE_FILE};
 use crate::wheel::read_record_file;
 use crate::Error;
 
@@ -30,13 +32,41 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
         read_record_file(&mut record_file)?
     };
 
+    // Determine whether the distribution was installed with `--link-mode=farm`.
+    let is_farm = fs::read_to_string(dist_info.join(LINK_MODE_FILE))
+        .is_ok_and(|link_mode| link_mode.trim() == "farm");
+
     let mut file_count = 0usize;
     let mut dir_count = 0usize;
 
     // Uninstall the files, keeping track of any directories that are left empty.
     let mut visited = BTreeSet::new();
+    let mut symlinks = FxHashMap::default();
     for entry in &record {
         let path = site_packages.join(&entry.path);
+
+        // If the file is contained in a symlinked directory (as with `--link-mode=farm`), remove
+        // the symlink, rather than the file, which would otherwise be removed from the cache.
+        let link = if is_farm {
+            symlinked_ancestor(site_packages, &path, &mut symlinks)
+        } else {
+            None
+        };
+        if let Some(link) = link {
+            match remove_symlink(&link) {
+                Ok(()) => {
+                    debug!("Removed symlink: {}", link.display());
+                    file_count += 1;
+                    if let Some(parent) = link.parent() {
+                        visited.insert(normalize_path(parent));
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+            continue;
+        }
+
         match fs::remove_file(&path) {
             Ok(()) => {
                 debug!("Removed file: {}", path.display());
@@ -120,6 +150,33 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
     })
 }
 
+/// Returns the nearest ancestor of `path` within `site_packages` that's a symlink, if any.
+///
+/// The results are memoized in `symlinks`, keyed by directory.
+pub(crate) fn symlinked_ancestor(
+    site_packages: &Path,
+    path: &Path,
+    symlinks: &mut FxHashMap<PathBuf, bool>,
+) -> Option<PathBuf> {
+    let relative = path.strip_prefix(site_packages).ok()?;
+    let mut ancestor = site_packages.to_path_buf();
+    let mut components = relative.components().peekable();
+    while let Some(component) = components.next() {
+        // Stop at the file itself, or at any path outside of `site-packages` (like `bin`).
+        if components.peek().is_none() || !matches!(component, Component::Normal(_)) {
+            break;
+        }
+        ancestor.push(component);
+        let is_symlink = *symlinks.entry(ancestor.clone()).or_insert_with(|| {
+            fs::symlink_metadata(&ancestor).is_ok_and(|metadata| metadata.is_symlink())
+        });
+        if is_symlink {
+            return Some(ancestor);
+        }
+    }
+    None
+}
+
 /// Uninstall the egg represented by the `.egg-info` directory.
 ///
 /// See: <https://github.com/pypa/pip/blob/41587f5e0017bcd849f42b314dc8a34a7db75621/src/pip/_internal/req/req_uninstall.py#L483>
@@ -13,6 +13,7 @@ use walkdir::WalkDir;
 use zip::write::FileOptions;
 use zip::ZipWriter;
 
+use platform_tags::Arch;
 use pypi_types::DirectUrl;
 use uv_fs::{relative_to, Simplified};
 use uv_normalize::PackageName;
@@ -21,7 +22,7 @@ use crate::record::RecordEntry;
 use crate::script::Script;
 use crate::{Error, Layout};
 
-const LAUNCHER_MAGIC_NUMBER: [u8; 4] = [b'U', b'V', b'U', b'V'];
+pub(crate) const LAUNCHER_MAGIC_NUMBER: [u8; 4] = [b'U', b'V', b'U', b'V'];
 
 #[cfg(all(windows, target_arch = "x86"))]
 const LAUNCHER_I686_GUI: &[u8] =
@@ -31,19 +32,21 @@ const LAUNCHER_I686_GUI: &[u8] =
 const LAUNCHER_I686_CONSOLE: &[u8] =
     include_bytes!("../../uv-trampoline/trampolines/uv-trampoline-i686-console.exe");
 
-#[cfg(all(windows, target_arch = "x86_64"))]
+// Builds for x86-64 and ARM64 include the launchers for both architectures, since either can be
+// used on Windows on ARM.
+#[cfg(all(windows, any(target_arch = "x86_64", target_arch = "aarch64")))]
 const LAUNCHER_X86_64_GUI: &[u8] =
     include_bytes!("../../uv-trampoline/trampolines/uv-trampoline-x86_64-gui.exe");
 
-#[cfg(all(windows, target_arch = "x86_64"))]
+#[cfg(all(windows, any(target_arch = "x86_64", target_arch = "aarch64")))]
 const LAUNCHER_X86_64_CONSOLE: &[u8] =
     include_bytes!("../../uv-trampoline/trampolines/uv-trampoline-x86_64-console.exe");
 
-#[cfg(all(windows, target_arch = "aarch64"))]
+#[cfg(all(windows, any(target_arch = "x86_64", target_arch = "aarch64")))]
 const LAUNCHER_AARCH64_GUI: &[u8] =
     include_bytes!("../../uv-trampoline/trampolines/uv-trampoline-aarch64-gui.exe");
 
-#[cfg(all(windows, target_arch = "aarch64"))]
+#[cfg(all(windows, any(target_arch = "x86_64", target_arch = "aarch64")))]
 const LAUNCHER_AARCH64_CONSOLE: &[u8] =
     include_bytes!("../../uv-trampoline/trampolines/uv-trampoline-aarch64-console.exe");
 
@@ -128,7 +131,11 @@ fn copy_and_hash(reader: &mut impl Read, writer: &mut impl Write) -> io::Result<
 /// executable.
 ///
 /// See: <https://github.com/pypa/pip/blob/0ad4c94be74cc24874c6feb5bb3c2152c398a18e/src/pip/_vendor/distlib/scripts.py#L136-L165>
-fn format_shebang(executable: impl AsRef<Path>, os_name: &str, relocatable: bool) -> String {
+pub(crate) fn format_shebang(
+    executable: impl AsRef<Path>,
+    os_name: &str,
+    relocatable: bool,
+) -> String {
     // Convert the executable to a simplified path.
     let executable = executable.as_ref().simplified_display().to_string();
 
@@ -158,15 +165,44 @@ fn format_shebang(executable: impl AsRef<Path>, os_name: &str, relocatable: bool
     format!("#!{executable}")
 }
 
+/// Return the bundled Windows launcher for the given architecture, if any.
+#[allow(unused_variables)]
+fn windows_launcher(arch: Arch, is_gui: bool) -> Option<&'static [u8]> {
+    match arch {
+        #[cfg(all(windows, target_arch = "x86"))]
+        Arch::X86 => Some(if is_gui {
+            LAUNCHER_I686_GUI
+        } else {
+            LAUNCHER_I686_CONSOLE
+        }),
+        #[cfg(all(windows, any(target_arch = "x86_64", target_arch = "aarch64")))]
+        Arch::X86_64 => Some(if is_gui {
+            LAUNCHER_X86_64_GUI
+        } else {
+            LAUNCHER_X86_64_CONSOLE
+        }),
+        #[cfg(all(windows, any(target_arch = "x86_64", target_arch = "aarch64")))]
+        Arch::Aarch64 => Some(if is_gui {
+            LAUNCHER_AARCH64_GUI
+        } else {
+            LAUNCHER_AARCH64_CONSOLE
+        }),
+        _ => None,
+    }
+}
+
 /// A Windows script is a minimal .exe launcher binary with the python entrypoint script appended as
 /// stored zip file.
 ///
+/// The launcher matches the architecture of the interpreter, falling back to that of the running
+/// process (e.g., for an x86 interpreter, which an x86-64 launcher can run).
+///
 /// <https://github.com/pypa/pip/blob/fd0ea6bc5e8cb95e518c23d901c26ca14db17f89/src/pip/_vendor/distlib/scripts.py#L248-L262>
-#[allow(unused_variables)]
 pub(crate) fn windows_script_launcher(
     launcher_python_script: &str,
     is_gui: bool,
     python_executable: impl AsRef<Path>,
+    arch: Arch,
 ) -> Result<Vec<u8>, Error> {
     // This method should only be called on Windows, but we avoid `#[cfg(windows)]` to retain
     // compilation on all platforms.
@@ -174,38 +210,15 @@ pub(crate) fn windows_script_launcher(
         return Err(Error::NotWindows);
     }
 
-    let launcher_bin: &[u8] = match env::consts::ARCH {
-        #[cfg(all(windows, target_arch = "x86"))]
-        "x86" => {
-            if is_gui {
-                LAUNCHER_I686_GUI
-            } else {
-                LAUNCHER_I686_CONSOLE
-            }
-        }
-        #[cfg(all(windows, target_arch = "x86_64"))]
-        "x86_64" => {
-            if is_gui {
-                LAUNCHER_X86_64_GUI
-            } else {
-                LAUNCHER_X86_64_CONSOLE
-            }
-        }
-        #[cfg(all(windows, target_arch = "aarch64"))]
-        "aarch64" => {
-            if is_gui {
-                LAUNCHER_AARCH64_GUI
-            } else {
-                LAUNCHER_AARCH64_CONSOLE
-            }
-        }
-        #[cfg(windows)]
-        arch => {
-            return Err(Error::UnsupportedWindowsArch(arch));
-        }
-        #[cfg(not(windows))]
-        arch => &[],
+    let native = match env::consts::ARCH {
+        "x86" => Some(Arch::X86),
+        "x86_64" => Some(Arch::X86_64),
+        "aarch64" => Some(Arch::Aarch64),
+        _ => None,
     };
+    let launcher_bin: &[u8] = windows_launcher(arch, is_gui)
+        .or_else(|| native.and_then(|native| windows_launcher(native, is_gui)))
+        .ok_or(Error::UnsupportedWindowsArch(env::consts::ARCH))?;
 
     let mut payload: Vec<u8> = Vec::new();
     {
@@ -313,7 +326,12 @@ pub(crate) fn write_script_entrypoints(
             write_file_recorded(
                 site_packages,
                 &entrypoint_relative,
-                &windows_script_launcher(&launcher_python_script, is_gui, &launcher_executable)?,
+                &windows_script_launcher(
+                    &launcher_python_script,
+                    is_gui,
+                    &launcher_executable,
+                    layout.arch,
+                )?,
                 record,
             )?;
         } else {
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use uv_normalize::PackageName;
+use uv_normalize::{GroupName, PackageName};
 
 /// The origin of a dependency, e.g., a `-r requirements.txt` file.
 #[derive(
@@ -12,6 +12,9 @@ pub enum RequirementOrigin {
     File(PathBuf),
     /// The requirement was provided via a local project (e.g., a `pyproject.toml` file).
     Project(PathBuf, PackageName),
+    /// The requirement was provided via a dependency group in a `pyproject.toml` file (i.e., the
+    /// `[dependency-groups]` table), for a project with the given name, if any.
+    Group(PathBuf, Option<PackageName>, GroupName),
     /// The requirement was provided via a workspace.
     Workspace,
 }
@@ -22,6 +25,7 @@ impl RequirementOrigin {
         match self {
             RequirementOrigin::File(path) => path.as_path(),
             RequirementOrigin::Project(path, _) => path.as_path(),
+            RequirementOrigin::Group(path, _, _) => path.as_path(),
             // Multiple toml are merged and difficult to track files where Requirement is defined. Returns a dummy path instead.
             RequirementOrigin::Workspace => Path::new("(workspace)"),
         }
@@ -0,0 +1,140 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// The set of CPU features available on a target (e.g., `avx512f` or `sve`), used to select
+/// between hardware-variant wheels.
+///
+/// A variant wheel declares the features it requires in its build tag, as in
+/// `numpy-2.1.0-1cpu.avx512f.avx512bw-cp312-cp312-manylinux_2_17_x86_64.whl`. Such wheels are
+/// only compatible with targets that provide all of the required features.
+///
+/// Features are named as in Rust's `is_x86_feature_detected!` and `is_aarch64_feature_detected!`
+/// macros. On x86-64, the microarchitecture levels (`x86_64_v2`, `x86_64_v3`, and `x86_64_v4`)
+/// are available as features, too.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuFeatures(Arc<BTreeSet<String>>);
+
+impl CpuFeatures {
+    /// Create a set of CPU features from the given feature names.
+    pub fn new<S: AsRef<str>>(features: impl IntoIterator<Item = S>) -> Self {
+        Self(Arc::new(
+            features
+                .into_iter()
+                .map(|feature| feature.as_ref().trim().to_ascii_lowercase())
+                .filter(|feature| !feature.is_empty())
+                .collect(),
+        ))
+    }
+
+    /// Returns the CPU features of the host, unless overridden by `UV_CPU_FEATURES`.
+    pub fn host() -> Self {
+        Self::from_env().unwrap_or_else(Self::detect)
+    }
+
+    /// Read the CPU features from `UV_CPU_FEATURES`, a comma-separated list of feature names.
+    ///
+    /// An empty value disables all variant wheels.
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var("UV_CPU_FEATURES").ok()?;
+        Some(Self::new(value.split(',')))
+    }
+
+    /// Detect the CPU features of the host.
+    pub fn detect() -> Self {
+        Self::new(detect_features())
+    }
+
+    /// Returns `true` if the set includes the given feature.
+    pub fn contains(&self, feature: &str) -> bool {
+        self.0.contains(feature)
+    }
+
+    /// Returns an iterator over the features in the set.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+/// Detect the features of an `x86_64` CPU, along with the microarchitecture levels they satisfy.
+#[cfg(target_arch = "x86_64")]
+fn detect_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+
+    macro_rules! detect {
+        ($($feature:tt),*) => {
+            $(
+                if std::arch::is_x86_feature_detected!($feature) {
+                    features.push($feature);
+                }
+            )*
+        };
+    }
+    detect!(
+        "sse3", "ssse3", "sse4.1", "sse4.2", "popcnt", "avx", "avx2", "bmi1", "bmi2", "f16c",
+        "fma", "lzcnt", "movbe", "avx512f", "avx512bw", "avx512cd", "avx512dq", "avx512vl"
+    );
+
+    // See: https://en.wikipedia.org/wiki/X86-64#Microarchitecture_levels
+    let levels: [(&str, &[&str]); 3] = [
+        (
+            "x86_64_v2",
+            &["sse3", "ssse3", "sse4.1", "sse4.2", "popcnt"],
+        ),
+        (
+            "x86_64_v3",
+            &[
+                "avx", "avx2", "bmi1", "bmi2", "f16c", "fma", "lzcnt", "movbe",
+            ],
+        ),
+        (
+            "x86_64_v4",
+            &["avx512f", "avx512bw", "avx512cd", "avx512dq", "avx512vl"],
+        ),
+    ];
+    for (level, required) in levels {
+        if !required.iter().all(|feature| features.contains(feature)) {
+            break;
+        }
+        features.push(level);
+    }
+
+    features
+}
+
+/// Detect the features of an `aarch64` CPU.
+#[cfg(target_arch = "aarch64")]
+fn detect_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+
+    macro_rules! detect {
+        ($($feature:tt),*) => {
+            $(
+                if std::arch::is_aarch64_feature_detected!($feature) {
+                    features.push($feature);
+                }
+            )*
+        };
+    }
+    detect!("neon", "fp16", "dotprod", "i8mm", "bf16", "sve", "sve2");
+
+    features
+}
+
+/// Feature detection isn't supported on other architectures.
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn detect_features() -> Vec<&'static str> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        let features = CpuFeatures::new(["AVX2", " fma", ""]);
+        assert_eq!(features.iter().collect::<Vec<_>>(), vec!["avx2", "fma"]);
+        assert!(features.contains("avx2"));
+        assert!(!features.contains("avx512f"));
+    }
+}
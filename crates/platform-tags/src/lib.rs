@@ -1,5 +1,7 @@
+pub use cpu::CpuFeatures;
 pub use platform::{Arch, Os, Platform, PlatformError};
-pub use tags::{IncompatibleTag, TagCompatibility, TagPriority, Tags, TagsError};
+pub use tags::{compatible_tags, IncompatibleTag, TagCompatibility, TagPriority, Tags, TagsError};
 
+mod cpu;
 mod platform;
 mod tags;
@@ -5,7 +5,7 @@ use std::{cmp, num::NonZeroU32};
 
 use rustc_hash::FxHashMap;
 
-use crate::{Arch, Os, Platform, PlatformError};
+use crate::{Arch, CpuFeatures, Os, Platform, PlatformError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum TagsError {
@@ -27,6 +27,8 @@ pub enum IncompatibleTag {
     Python,
     Abi,
     Platform,
+    /// The wheel's tags are compatible, but it requires CPU features that aren't available.
+    CpuFeatures,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -67,6 +69,8 @@ pub struct Tags {
     /// `python_tag` |--> `abi_tag` |--> `platform_tag` |--> priority
     #[allow(clippy::type_complexity)]
     map: Arc<FxHashMap<String, FxHashMap<String, FxHashMap<String, TagPriority>>>>,
+    /// The CPU features available on the target, used to select hardware-variant wheels.
+    cpu_features: CpuFeatures,
 }
 
 impl Tags {
@@ -84,7 +88,25 @@ impl Tags {
                 .entry(platform.to_string())
                 .or_insert(TagPriority::try_from(index).expect("valid tag priority"));
         }
-        Self { map: Arc::new(map) }
+        Self {
+            map: Arc::new(map),
+            cpu_features: CpuFeatures::default(),
+        }
+    }
+
+    /// Set the CPU features available on the target.
+    ///
+    /// By default, no CPU features are assumed, such that hardware-variant wheels are considered
+    /// incompatible.
+    #[must_use]
+    pub fn with_cpu_features(mut self, cpu_features: CpuFeatures) -> Self {
+        self.cpu_features = cpu_features;
+        self
+    }
+
+    /// Returns the CPU features available on the target.
+    pub fn cpu_features(&self) -> &CpuFeatures {
+        &self.cpu_features
     }
 
     /// Returns the compatible tags for the given Python implementation (e.g., `cpython`), version,
@@ -95,9 +117,29 @@ impl Tags {
         implementation_name: &str,
         implementation_version: (u8, u8),
         gil_disabled: bool,
+    ) -> Result<Self, TagsError> {
+        Self::from_platform_tags(
+            compatible_tags(platform)?,
+            python_version,
+            implementation_name,
+            implementation_version,
+            gil_disabled,
+        )
+    }
+
+    /// Returns the compatible tags for the given Python implementation (e.g., `cpython`) and
+    /// version, and the given platform tags (e.g., `macosx_11_0_arm64`) in priority order.
+    ///
+    /// Like [`Tags::from_env`], but allows the caller to reorder or filter the platform tags, as
+    /// returned by [`compatible_tags`].
+    pub fn from_platform_tags(
+        platform_tags: Vec<String>,
+        python_version: (u8, u8),
+        implementation_name: &str,
+        implementation_version: (u8, u8),
+        gil_disabled: bool,
     ) -> Result<Self, TagsError> {
         let implementation = Implementation::parse(implementation_name, gil_disabled)?;
-        let platform_tags = compatible_tags(platform)?;
 
         let mut tags = Vec::with_capacity(5 * platform_tags.len());
 
@@ -393,7 +435,7 @@ impl Implementation {
 /// and "any".
 ///
 /// Bit of a mess, needs to be cleaned up.
-fn compatible_tags(platform: &Platform) -> Result<Vec<String>, PlatformError> {
+pub fn compatible_tags(platform: &Platform) -> Result<Vec<String>, PlatformError> {
     let os = platform.os();
     let arch = platform.arch();
 
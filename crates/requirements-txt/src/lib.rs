@@ -54,8 +54,7 @@ use uv_configuration::{NoBinary, NoBuild, PackageNameSpecifier};
 use uv_fs::Simplified;
 use uv_warnings::warn_user;
 
-use crate::requirement::EditableError;
-pub use crate::requirement::RequirementsTxtRequirement;
+pub use crate::requirement::{EditableError, RequirementsTxtRequirement};
 
 mod requirement;
 
@@ -20,17 +20,14 @@ pub enum EditableError {
     #[error("Editable `{0}` must refer to a local directory, not an HTTPS URL: `{1}`")]
     Https(PackageName, String),
 
-    #[error("Editable `{0}` must refer to a local directory, not a Git URL: `{1}`")]
-    Git(PackageName, String),
-
     #[error("Editable must refer to a local directory, not an archive: `{0}`")]
     UnnamedFile(String),
 
     #[error("Editable must refer to a local directory, not an HTTPS URL: `{0}`")]
     UnnamedHttps(String),
 
-    #[error("Editable must refer to a local directory, not a Git URL: `{0}`")]
-    UnnamedGit(String),
+    #[error("Editable Git requirements are only supported by `uv pip install` and `uv pip sync` (found: `{0}`)")]
+    Git(String),
 }
 
 /// A requirement specifier in a `requirements.txt` file.
@@ -56,12 +53,29 @@ impl RequirementsTxtRequirement {
         }
     }
 
+    /// Returns `true` if the requirement refers to a Git repository.
+    pub fn is_git(&self) -> bool {
+        match self {
+            Self::Named(requirement) => matches!(
+                requirement.version_or_url,
+                Some(pep508_rs::VersionOrUrl::Url(VerbatimParsedUrl {
+                    parsed_url: ParsedUrl::Git(_),
+                    ..
+                }))
+            ),
+            Self::Unnamed(requirement) => matches!(requirement.url.parsed_url, ParsedUrl::Git(_)),
+        }
+    }
+
     /// Convert the [`RequirementsTxtRequirement`] into an editable requirement.
     ///
+    /// Git URLs are returned as-is, as they must be checked out into a local directory before
+    /// they can be installed in editable mode.
+    ///
     /// # Errors
     ///
     /// Returns [`EditableError`] if the requirement cannot be interpreted as editable.
-    /// Specifically, only local directory URLs are supported.
+    /// Specifically, only local directory and Git URLs are supported.
     pub fn into_editable(self) -> Result<Self, EditableError> {
         match self {
             RequirementsTxtRequirement::Named(requirement) => {
@@ -81,8 +95,14 @@ impl RequirementsTxtRequirement {
                     ParsedUrl::Archive(_) => {
                         return Err(EditableError::Https(requirement.name, url.to_string()))
                     }
-                    ParsedUrl::Git(_) => {
-                        return Err(EditableError::Git(requirement.name, url.to_string()))
+                    ParsedUrl::Git(parsed_url) => {
+                        return Ok(Self::Named(pep508_rs::Requirement {
+                            version_or_url: Some(pep508_rs::VersionOrUrl::Url(VerbatimParsedUrl {
+                                verbatim: url.verbatim,
+                                parsed_url: ParsedUrl::Git(parsed_url),
+                            })),
+                            ..requirement
+                        }))
                     }
                 };
 
@@ -106,8 +126,14 @@ impl RequirementsTxtRequirement {
                     ParsedUrl::Archive(_) => {
                         return Err(EditableError::UnnamedHttps(requirement.to_string()))
                     }
-                    ParsedUrl::Git(_) => {
-                        return Err(EditableError::UnnamedGit(requirement.to_string()))
+                    ParsedUrl::Git(parsed_url) => {
+                        return Ok(Self::Unnamed(UnnamedRequirement {
+                            url: VerbatimParsedUrl {
+                                verbatim: requirement.url.verbatim,
+                                parsed_url: ParsedUrl::Git(parsed_url),
+                            },
+                            ..requirement
+                        }))
                     }
                 };
 
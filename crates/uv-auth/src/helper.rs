@@ -0,0 +1,218 @@
+use std::process::Stdio;
+use std::sync::{Arc, OnceLock};
+
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tracing::{debug, instrument};
+use url::Url;
+
+use uv_warnings::warn_user;
+
+use crate::credentials::Credentials;
+use crate::realm::Realm;
+
+static CREDENTIAL_HELPERS: OnceLock<CredentialHelpers> = OnceLock::new();
+
+/// The username used for credentials provided by a helper, if none is configured.
+const DEFAULT_USERNAME: &str = "__token__";
+
+/// External commands that provide credentials for package indexes.
+///
+/// The helpers are process-wide: once installed via [`CredentialHelpers::init`], they're consulted
+/// by every [`crate::AuthMiddleware`].
+#[derive(Debug, Default)]
+pub struct CredentialHelpers(Vec<CredentialHelper>);
+
+impl CredentialHelpers {
+    /// Create a set of [`CredentialHelpers`].
+    pub fn new(helpers: Vec<CredentialHelper>) -> Self {
+        Self(helpers)
+    }
+
+    /// Install the helpers for all clients created by this process.
+    ///
+    /// Has no effect if the helpers were already installed.
+    pub fn init(self) {
+        let _ = CREDENTIAL_HELPERS.set(self);
+    }
+
+    /// Returns the installed helpers, if any.
+    pub(crate) fn get() -> Option<&'static Self> {
+        CREDENTIAL_HELPERS.get()
+    }
+
+    /// Returns the helper responsible for the given URL, if any.
+    ///
+    /// A helper is responsible for any URL in the same realm as its index, since indexes commonly
+    /// serve files from paths outside of the index itself. If multiple helpers share a realm, the
+    /// helper whose index is the longest prefix of the URL is preferred, followed by the first
+    /// helper for the realm.
+    pub(crate) fn find(&self, url: &Url) -> Option<&CredentialHelper> {
+        let realm = Realm::from(url);
+        let candidates = self
+            .0
+            .iter()
+            .filter(|helper| helper.realm == realm)
+            .collect::<Vec<_>>();
+        candidates
+            .iter()
+            .filter(|helper| url.path().starts_with(helper.index.path()))
+            .max_by_key(|helper| helper.index.path().len())
+            .or_else(|| candidates.first())
+            .copied()
+    }
+}
+
+/// An external command that prints a (typically short-lived) token for a package index to
+/// `stdout`, such as `aws codeartifact get-authorization-token`.
+#[derive(Debug)]
+pub struct CredentialHelper {
+    /// The URL of the index.
+    index: Url,
+    /// The realm of the index.
+    realm: Realm,
+    /// The command to run, along with its arguments.
+    command: Vec<String>,
+    /// The username to pair with the token.
+    username: Option<String>,
+    /// The result of the most recent invocation, or `None` if the helper hasn't been invoked.
+    ///
+    /// The lock is held for the duration of an invocation, such that concurrent requests wait on
+    /// a single invocation of the helper.
+    cached: Mutex<Option<Option<Arc<Credentials>>>>,
+}
+
+impl CredentialHelper {
+    /// Create a [`CredentialHelper`] that runs the given command to obtain a token for an index.
+    ///
+    /// If no username is provided, the token is paired with `__token__`.
+    pub fn new(index: Url, command: Vec<String>, username: Option<String>) -> Self {
+        let realm = Realm::from(&index);
+        Self {
+            index,
+            realm,
+            command,
+            username,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Return the credentials provided by the helper, invoking it if necessary.
+    ///
+    /// The credentials are cached for the lifetime of the process. If `stale` credentials are
+    /// provided (e.g., as the server rejected them), and they're still cached, the helper is
+    /// invoked again to refresh them.
+    pub(crate) async fn credentials(
+        &self,
+        stale: Option<&Arc<Credentials>>,
+    ) -> Option<Arc<Credentials>> {
+        let mut cached = self.cached.lock().await;
+        match (cached.as_ref(), stale) {
+            (Some(Some(credentials)), Some(stale)) if Arc::ptr_eq(credentials, stale) => {}
+            (Some(credentials), _) => return credentials.clone(),
+            (None, _) => {}
+        }
+
+        let credentials = self.invoke().await.map(Arc::new);
+        *cached = Some(credentials.clone());
+        credentials
+    }
+
+    /// Run the helper, returning the credentials it provides.
+    #[instrument(skip_all, fields(index = %self.index))]
+    async fn invoke(&self) -> Option<Credentials> {
+        let (program, args) = self.command.split_first()?;
+
+        debug!("Running credential helper for {}: `{program}`", self.index);
+        let output = match Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output()
+            .await
+        {
+            Ok(output) => output,
+            Err(err) => {
+                warn_user!(
+                    "Failed to run credential helper `{program}` for {}: {err}",
+                    self.index
+                );
+                return None;
+            }
+        };
+
+        if !output.status.success() {
+            warn_user!(
+                "Credential helper `{program}` for {} failed ({})",
+                self.index,
+                output.status
+            );
+            return None;
+        }
+
+        let Ok(token) = String::from_utf8(output.stdout) else {
+            warn_user!(
+                "Credential helper `{program}` for {} returned a non-UTF-8 token",
+                self.index
+            );
+            return None;
+        };
+        let token = token.trim();
+        if token.is_empty() {
+            warn_user!(
+                "Credential helper `{program}` for {} did not return a token",
+                self.index
+            );
+            return None;
+        }
+
+        Some(Credentials::new(
+            Some(
+                self.username
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_USERNAME.to_string()),
+            ),
+            Some(token.to_string()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper(index: &str) -> CredentialHelper {
+        CredentialHelper::new(Url::parse(index).unwrap(), vec![], None)
+    }
+
+    #[test]
+    fn find_helper() {
+        let helpers = CredentialHelpers::new(vec![
+            helper("https://example.com/pypi/first/simple/"),
+            helper("https://example.com/pypi/second/simple/"),
+            helper("https://other.example.com/simple/"),
+        ]);
+
+        let find = |url: &str| {
+            helpers
+                .find(&Url::parse(url).unwrap())
+                .map(|helper| helper.index.as_str())
+        };
+
+        assert_eq!(
+            find("https://example.com/pypi/second/simple/flask/"),
+            Some("https://example.com/pypi/second/simple/")
+        );
+        assert_eq!(
+            find("https://example.com/files/flask-3.0.0-py3-none-any.whl"),
+            Some("https://example.com/pypi/first/simple/")
+        );
+        assert_eq!(
+            find("https://other.example.com/simple/flask/"),
+            Some("https://other.example.com/simple/")
+        );
+        assert_eq!(find("http://example.com/pypi/first/simple/"), None);
+        assert_eq!(find("https://pypi.org/simple/flask/"), None);
+    }
+}
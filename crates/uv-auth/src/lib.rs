@@ -1,5 +1,6 @@
 mod cache;
 mod credentials;
+mod helper;
 mod keyring;
 mod middleware;
 mod realm;
@@ -9,6 +10,7 @@ use std::sync::{Arc, LazyLock};
 use cache::CredentialsCache;
 use credentials::Credentials;
 
+pub use helper::{CredentialHelper, CredentialHelpers};
 pub use keyring::KeyringProvider;
 pub use middleware::AuthMiddleware;
 use realm::Realm;
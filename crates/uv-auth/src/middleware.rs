@@ -5,6 +5,7 @@ use url::Url;
 
 use crate::{
     credentials::{Credentials, Username},
+    helper::{CredentialHelper, CredentialHelpers},
     realm::Realm,
     CredentialsCache, KeyringProvider, CREDENTIALS_CACHE,
 };
@@ -22,6 +23,7 @@ pub struct AuthMiddleware {
     netrc: Option<Netrc>,
     keyring: Option<KeyringProvider>,
     cache: Option<CredentialsCache>,
+    credential_helpers: Option<CredentialHelpers>,
 }
 
 impl AuthMiddleware {
@@ -30,6 +32,7 @@ impl AuthMiddleware {
             netrc: Netrc::new().ok(),
             keyring: None,
             cache: None,
+            credential_helpers: None,
         }
     }
 
@@ -56,6 +59,22 @@ impl AuthMiddleware {
         self
     }
 
+    /// Configure the [`CredentialHelpers`] to use.
+    ///
+    /// If not set, the process-wide helpers are used.
+    #[must_use]
+    pub fn with_credential_helpers(mut self, credential_helpers: CredentialHelpers) -> Self {
+        self.credential_helpers = Some(credential_helpers);
+        self
+    }
+
+    /// Get the configured credential helpers, if any.
+    fn credential_helpers(&self) -> Option<&CredentialHelpers> {
+        self.credential_helpers
+            .as_ref()
+            .or_else(|| CredentialHelpers::get())
+    }
+
     /// Get the configured authentication store.
     ///
     /// If not set, the global store is used.
@@ -93,6 +112,15 @@ impl Middleware for AuthMiddleware {
     /// - Perform the request
     /// - Add the username and password to the cache if successful
     ///
+    /// ## If a credential helper is configured for the index
+    ///
+    /// The helper takes precedence over all other sources of credentials for requests without
+    /// authentication.
+    ///
+    /// - Invoke the helper, unless it was already invoked for a prior request
+    /// - Perform the request
+    /// - On 401 or 403, invoke the helper again to refresh the token and retry the request
+    ///
     /// ## If the request has no authentication
     ///
     /// We may or may not need authentication. We'll check for cached credentials for the URL,
@@ -176,6 +204,17 @@ impl Middleware for AuthMiddleware {
                 .await;
         }
 
+        // If a credential helper is configured for the index, defer to it
+        if let Some(helper) = self
+            .credential_helpers()
+            .and_then(|helpers| helpers.find(request.url()))
+        {
+            trace!("Request for {url} is unauthenticated, using credential helper");
+            return self
+                .complete_request_with_helper(helper, request, extensions, next)
+                .await;
+        }
+
         // We have no credentials
         trace!("Request for {url} is unauthenticated, checking cache");
 
@@ -293,6 +332,54 @@ impl AuthMiddleware {
         result
     }
 
+    /// Run a request to completion with the credentials provided by a [`CredentialHelper`].
+    ///
+    /// If the server rejects the credentials (e.g., as the token expired), the helper is invoked
+    /// again and the request is retried once.
+    async fn complete_request_with_helper(
+        &self,
+        helper: &CredentialHelper,
+        request: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let Some(credentials) = helper.credentials(None).await else {
+            trace!(
+                "No credentials from helper, attempting unauthenticated request for {}",
+                request.url()
+            );
+            return next.run(request, extensions).await;
+        };
+
+        let retry_request = request.try_clone().ok_or_else(|| {
+            Error::Middleware(anyhow!(
+                "Request object is not clonable. Are you passing a streaming body?".to_string()
+            ))
+        })?;
+
+        let response = next
+            .clone()
+            .run(credentials.authenticate(request), extensions)
+            .await?;
+        if !matches!(
+            response.status(),
+            StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED
+        ) {
+            return Ok(response);
+        }
+
+        debug!(
+            "Request for {} failed with {}, refreshing credentials from helper",
+            retry_request.url(),
+            response.status()
+        );
+        let Some(refreshed) = helper.credentials(Some(&credentials)).await else {
+            return Ok(response);
+        };
+        next.run(refreshed.authenticate(retry_request), extensions)
+            .await
+    }
+
     /// Fetch credentials for a URL.
     ///
     /// Supports netrc file and keyring lookups.
@@ -441,6 +528,60 @@ mod tests {
         Ok(())
     }
 
+    /// Credentials from a helper are attached to requests, and refreshed when rejected.
+    #[cfg(unix)]
+    #[test(tokio::test)]
+    async fn test_credential_helper() -> Result<(), Error> {
+        let server = start_test_server("user", "password").await;
+        let base_url = Url::parse(&server.uri())?;
+
+        // The first invocation returns an expired token; subsequent invocations return a valid one.
+        let temp_dir = tempfile::tempdir()?;
+        let marker = temp_dir.path().join("invoked");
+        let command = vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            format!(
+                "if [ -f '{0}' ]; then echo password; else touch '{0}'; echo expired; fi",
+                marker.display()
+            ),
+        ];
+
+        let helpers = CredentialHelpers::new(vec![CredentialHelper::new(
+            base_url,
+            command,
+            Some("user".to_string()),
+        )]);
+        let client = test_client_builder()
+            .with(
+                AuthMiddleware::new()
+                    .with_cache(CredentialsCache::new())
+                    .with_credential_helpers(helpers),
+            )
+            .build();
+
+        assert_eq!(
+            client
+                .get(format!("{}/foo", server.uri()))
+                .send()
+                .await?
+                .status(),
+            200
+        );
+        assert!(marker.exists());
+
+        assert_eq!(
+            client
+                .get(format!("{}/bar", server.uri()))
+                .send()
+                .await?
+                .status(),
+            200
+        );
+
+        Ok(())
+    }
+
     /// Without seeding the cache, authenticated requests are not cached
     #[test(tokio::test)]
     async fn test_credentials_in_url_no_seed() -> Result<(), Error> {
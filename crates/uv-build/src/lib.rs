@@ -9,6 +9,7 @@ use regex::Regex;
 use rustc_hash::FxHashMap;
 use serde::de::{value, SeqAccess, Visitor};
 use serde::{de, Deserialize, Deserializer};
+use std::collections::BTreeSet;
 use std::ffi::OsString;
 use std::fmt::{Display, Formatter};
 use std::io;
@@ -91,6 +92,8 @@ pub enum Error {
     InvalidSourceDist(String),
     #[error("Invalid `pyproject.toml`")]
     InvalidPyprojectToml(#[from] toml::de::Error),
+    #[error("Invalid requirement in `build-system.requires`")]
+    InvalidBuildRequirement(#[source] Box<pep508_rs::Pep508Error<VerbatimParsedUrl>>),
     #[error("Editable installs with setup.py legacy builds are unsupported, please specify a build backend in pyproject.toml")]
     EditableSetupPy,
     #[error("Failed to install requirements from {0}")]
@@ -165,6 +168,33 @@ impl Display for MissingHeaderCause {
 }
 
 impl Error {
+    /// Returns `true` if the error was likely caused by a transient condition, such that
+    /// recreating the build environment and retrying may succeed.
+    ///
+    /// Covers network failures while fetching the build requirements (e.g., timeouts and reset
+    /// connections) and races on the temporary directory in which the build environment is
+    /// created (e.g., files that are removed or created concurrently).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::RequirementsInstall(_, err) => err.chain().any(|cause| {
+                cause.downcast_ref::<io::Error>().is_some_and(|err| {
+                    matches!(
+                        err.kind(),
+                        io::ErrorKind::TimedOut
+                            | io::ErrorKind::ConnectionReset
+                            | io::ErrorKind::ConnectionAborted
+                            | io::ErrorKind::Interrupted
+                    )
+                })
+            }),
+            Self::Virtualenv(uv_virtualenv::Error::Io(err)) => matches!(
+                err.kind(),
+                io::ErrorKind::NotFound | io::ErrorKind::AlreadyExists
+            ),
+            _ => false,
+        }
+    }
+
     fn from_command_output(
         message: String,
         output: &Output,
@@ -248,13 +278,32 @@ struct Project {
 #[serde(rename_all = "kebab-case")]
 struct BuildSystem {
     /// PEP 508 dependencies required to execute the build system.
-    requires: Vec<pep508_rs::Requirement<VerbatimParsedUrl>>,
+    ///
+    /// These are parsed relative to the source tree (see [`BuildSystem::requirements`]), such
+    /// that direct references to local paths (e.g., an in-repository build backend) resolve
+    /// against the project rather than the working directory.
+    requires: Vec<String>,
     /// A string naming a Python object that will be used to perform the build.
     build_backend: Option<String>,
     /// Specify that their backend code is hosted in-tree, this key contains a list of directories.
     backend_path: Option<BackendPath>,
 }
 
+impl BuildSystem {
+    /// Parse the `requires` of the build system, resolving any relative paths against the
+    /// given source tree.
+    fn requirements(&self, source_tree: &Path) -> Result<Vec<Requirement>, Error> {
+        self.requires
+            .iter()
+            .map(|requirement| {
+                pep508_rs::Requirement::<VerbatimParsedUrl>::parse(requirement, source_tree)
+                    .map(Requirement::from)
+                    .map_err(|err| Error::InvalidBuildRequirement(Box::new(err)))
+            })
+            .collect()
+    }
+}
+
 impl BackendPath {
     /// Return an iterator over the paths in the backend path.
     fn iter(&self) -> impl Iterator<Item = &str> {
@@ -398,6 +447,10 @@ pub struct SourceBuild {
     environment_variables: FxHashMap<OsString, OsString>,
     /// Runner for Python scripts.
     runner: PythonRunner,
+    /// The packages that were installed into the build environment.
+    build_inputs: BTreeSet<PackageName>,
+    /// The command to run on each platform-specific wheel, once built, if any.
+    repair_wheel_command: Option<String>,
 }
 
 impl SourceBuild {
@@ -452,6 +505,7 @@ impl SourceBuild {
 
         // Setup the build environment. If build isolation is disabled, we assume the build
         // environment is already setup.
+        let mut build_inputs = BTreeSet::new();
         if build_isolation.is_isolated(package_name.as_ref()) {
             let resolved_requirements = Self::get_resolved_requirements(
                 build_context,
@@ -467,6 +521,8 @@ impl SourceBuild {
                 .map_err(|err| {
                     Error::RequirementsInstall("build-system.requires (install)", err)
                 })?;
+
+            build_inputs.extend(resolved_requirements.packages().cloned());
         }
 
         // Figure out what the modified path should be
@@ -504,7 +560,7 @@ impl SourceBuild {
         let runner = PythonRunner::new(concurrent_builds);
         if build_isolation.is_isolated(package_name.as_ref()) {
             if let Some(pep517_backend) = &pep517_backend {
-                create_pep517_build_environment(
+                if let Some(resolution) = create_pep517_build_environment(
                     &runner,
                     &source_tree,
                     &venv,
@@ -517,7 +573,10 @@ impl SourceBuild {
                     &modified_path,
                     &temp_dir,
                 )
-                .await?;
+                .await?
+                {
+                    build_inputs.extend(resolution.packages().cloned());
+                }
             }
         }
 
@@ -534,9 +593,27 @@ impl SourceBuild {
             environment_variables,
             modified_path,
             runner,
+            build_inputs,
+            repair_wheel_command: build_context
+                .repair_wheel_command()
+                .map(ToString::to_string),
         })
     }
 
+    /// Read the requirements of the build backend for the given source tree, i.e., its
+    /// `build-system.requires`, or those of the default backend if none are declared.
+    ///
+    /// Any additional requirements reported by the backend's `get_requires_for_build_*` hooks are
+    /// not included.
+    pub fn build_system_requirements(source_tree: &Path) -> Result<Vec<Requirement>, Error> {
+        let (pep517_backend, _) =
+            Self::extract_pep517_backend(source_tree, SetupPyStrategy::Pep517, &DEFAULT_BACKEND)
+                .map_err(|err| *err)?;
+        Ok(pep517_backend
+            .map(|backend| backend.requirements)
+            .unwrap_or_default())
+    }
+
     async fn get_resolved_requirements(
         build_context: &impl BuildContext,
         source_build_context: SourceBuildContext,
@@ -604,12 +681,8 @@ impl SourceBuild {
                         backend: build_system
                             .build_backend
                             .unwrap_or_else(|| "setuptools.build_meta:__legacy__".to_string()),
+                        requirements: build_system.requirements(source_tree)?,
                         backend_path: build_system.backend_path,
-                        requirements: build_system
-                            .requires
-                            .into_iter()
-                            .map(Requirement::from)
-                            .collect(),
                     }
                 } else {
                     // If a `pyproject.toml` is present, but `[build-system]` is missing, proceed with
@@ -754,9 +827,11 @@ impl SourceBuild {
     /// The location will be inside `temp_dir`, i.e. you must use the wheel before dropping the temp
     /// dir.
     ///
+    /// For [`BuildKind::Sdist`] builds, a source distribution is built in lieu of a wheel.
+    ///
     /// <https://packaging.python.org/en/latest/specifications/source-distribution-format/>
     #[instrument(skip_all, fields(version_id = self.version_id))]
-    pub async fn build_wheel(&self, wheel_dir: &Path) -> Result<String, Error> {
+    pub async fn build(&self, wheel_dir: &Path) -> Result<String, Error> {
         // The build scripts run with the extracted root as cwd, so they need the absolute path.
         let wheel_dir = fs::canonicalize(wheel_dir)?;
 
@@ -764,29 +839,32 @@ impl SourceBuild {
             // Prevent clashes from two uv processes building wheels in parallel.
             let tmp_dir = tempdir_in(&wheel_dir)?;
             let filename = self.pep517_build(tmp_dir.path(), pep517_backend).await?;
+            let filename = self.repair(tmp_dir.path(), filename).await?;
 
             let from = tmp_dir.path().join(&filename);
             let to = wheel_dir.join(&filename);
             rename_with_retry(from, to).await?;
             Ok(filename)
         } else {
-            if self.build_kind != BuildKind::Wheel {
-                return Err(Error::EditableSetupPy);
-            }
+            let (command, artifact) = match self.build_kind {
+                BuildKind::Wheel => ("bdist_wheel", "wheel"),
+                BuildKind::Sdist => ("sdist", "source distribution"),
+                BuildKind::Editable => return Err(Error::EditableSetupPy),
+            };
             // We checked earlier that setup.py exists.
             let span = info_span!(
                 "run_python_script",
-                script="setup.py bdist_wheel",
+                script=format!("setup.py {command}"),
                 python_version = %self.venv.interpreter().python_version()
             );
             let output = self
                 .runner
-                .run_setup_py(&self.venv, "bdist_wheel", &self.source_tree)
+                .run_setup_py(&self.venv, command, &self.source_tree)
                 .instrument(span)
                 .await?;
             if !output.status.success() {
                 return Err(Error::from_command_output(
-                    "Failed building wheel through setup.py".to_string(),
+                    format!("Failed building {artifact} through setup.py"),
                     &output,
                     &self.version_id,
                 ));
@@ -796,19 +874,91 @@ impl SourceBuild {
             let [dist_wheel] = dist_dir.as_slice() else {
                 return Err(Error::from_command_output(
                     format!(
-                        "Expected exactly wheel in `dist/` after invoking setup.py, found {dist_dir:?}"
+                        "Expected exactly {artifact} in `dist/` after invoking setup.py, found {dist_dir:?}"
                     ),
                     &output,
                     &self.version_id)
                 );
             };
 
-            let from = dist_wheel.path();
-            let to = wheel_dir.join(dist_wheel.file_name());
-            fs_err::copy(from, to)?;
+            // Prevent clashes from two uv processes building wheels in parallel.
+            let tmp_dir = tempdir_in(&wheel_dir)?;
+            let filename = dist_wheel.file_name().to_string_lossy().to_string();
+            fs_err::copy(dist_wheel.path(), tmp_dir.path().join(&filename))?;
+            let filename = self.repair(tmp_dir.path(), filename).await?;
 
-            Ok(dist_wheel.file_name().to_string_lossy().to_string())
+            let from = tmp_dir.path().join(&filename);
+            let to = wheel_dir.join(&filename);
+            rename_with_retry(from, to).await?;
+            Ok(filename)
+        }
+    }
+
+    /// Run the command provided via `--repair-wheel-command`, if any, on a freshly built wheel,
+    /// returning the filename of the repaired wheel.
+    ///
+    /// The command is expected to vendor any external shared libraries into the wheel (e.g., with
+    /// `auditwheel repair`), writing the result to `{dest_dir}`. The repaired wheel replaces the
+    /// original in `wheel_dir`; its filename may differ, e.g., to reflect a `manylinux` platform
+    /// tag.
+    async fn repair(&self, wheel_dir: &Path, filename: String) -> Result<String, Error> {
+        // Only platform-specific wheels can link against external libraries.
+        if self.build_kind != BuildKind::Wheel || filename.ends_with("-any.whl") {
+            return Ok(filename);
+        }
+        let Some(command) = self.repair_wheel_command.as_deref() else {
+            return Ok(filename);
+        };
+
+        let wheel = wheel_dir.join(&filename);
+        let dest_dir = tempdir_in(wheel_dir)?;
+        let command = repair_wheel_command(command);
+
+        debug!("Repairing `{filename}` with: {command}");
+        let mut process = if cfg!(windows) {
+            let mut process = Command::new("cmd");
+            process.arg("/C");
+            process
+        } else {
+            let mut process = Command::new("sh");
+            process.arg("-c");
+            process
+        };
+        let output = process
+            .arg(&command)
+            .env("UV_REPAIR_WHEEL", &wheel)
+            .env("UV_REPAIR_DEST_DIR", dest_dir.path())
+            .output()
+            .await
+            .map_err(|err| Error::CommandFailed(PathBuf::from(&command), err))?;
+        if !output.status.success() {
+            return Err(Error::from_command_output(
+                format!("Failed to repair wheel through `{command}`"),
+                &output,
+                &self.version_id,
+            ));
         }
+
+        // The command must produce exactly one wheel.
+        let repaired = fs::read_dir(dest_dir.path())?
+            .map(|entry| entry.map(|entry| entry.file_name().to_string_lossy().to_string()))
+            .filter_ok(|name| name.ends_with(".whl"))
+            .collect::<io::Result<Vec<_>>>()?;
+        let [repaired] = repaired.as_slice() else {
+            return Err(Error::from_command_output(
+                format!(
+                    "Expected exactly one wheel in `{{dest_dir}}` after invoking `{command}`, found {}",
+                    repaired.len()
+                ),
+                &output,
+                &self.version_id,
+            ));
+        };
+
+        debug!("Repaired `{filename}` as `{repaired}`");
+        fs::remove_file(&wheel)?;
+        rename_with_retry(dest_dir.path().join(repaired), wheel_dir.join(repaired)).await?;
+        Ok(repaired.clone())
     }
 
     async fn pep517_build(
@@ -816,12 +966,24 @@ impl SourceBuild {
         wheel_dir: &Path,
         pep517_backend: &Pep517Backend,
     ) -> Result<String, Error> {
-        let metadata_directory = self
-            .metadata_directory
-            .as_deref()
-            .map_or("None".to_string(), |path| {
-                format!(r#""{}""#, path.escape_for_python())
-            });
+        // Unlike `build_wheel` and `build_editable`, `build_sdist` doesn't accept a metadata
+        // directory.
+        let metadata_directory = if self.build_kind == BuildKind::Sdist {
+            String::new()
+        } else {
+            let metadata_directory = self
+                .metadata_directory
+                .as_deref()
+                .map_or("None".to_string(), |path| {
+                    format!(r#""{}""#, path.escape_for_python())
+                });
+            format!(", {metadata_directory}")
+        };
+        let artifact = if self.build_kind == BuildKind::Sdist {
+            "source distribution"
+        } else {
+            "wheel"
+        };
 
         // Write the hook output to a file so that we can read it back reliably.
         let outfile = self
@@ -830,7 +992,7 @@ impl SourceBuild {
             .join(format!("build_{}.txt", self.build_kind));
 
         debug!(
-            r#"Calling `{}.build_{}("{}", {}, {})`"#,
+            r#"Calling `{}.build_{}("{}", {}{})`"#,
             pep517_backend.backend,
             self.build_kind,
             wheel_dir.escape_for_python(),
@@ -841,7 +1003,7 @@ impl SourceBuild {
             r#"
             {}
 
-            wheel_filename = backend.build_{}("{}", {}, {})
+            wheel_filename = backend.build_{}("{}", {}{})
             with open("{}", "w") as fp:
                 fp.write(wheel_filename)
             "#,
@@ -871,7 +1033,7 @@ impl SourceBuild {
         if !output.status.success() {
             return Err(Error::from_command_output(
                 format!(
-                    "Build backend failed to build wheel through `build_{}()`",
+                    "Build backend failed to build {artifact} through `build_{}()`",
                     self.build_kind
                 ),
                 &output,
@@ -883,7 +1045,7 @@ impl SourceBuild {
         if !wheel_dir.join(&distribution_filename).is_file() {
             return Err(Error::from_command_output(
                 format!(
-                    "Build backend failed to produce wheel through `build_{}()`: `{distribution_filename}` not found",
+                    "Build backend failed to produce {artifact} through `build_{}()`: `{distribution_filename}` not found",
                     self.build_kind
                 ),
                 &output,
@@ -900,7 +1062,44 @@ impl SourceBuildTrait for SourceBuild {
     }
 
     async fn wheel<'a>(&'a self, wheel_dir: &'a Path) -> anyhow::Result<String> {
-        Ok(self.build_wheel(wheel_dir).await?)
+        Ok(self.build(wheel_dir).await?)
+    }
+
+    fn build_inputs(&self) -> &BTreeSet<PackageName> {
+        &self.build_inputs
+    }
+}
+
+/// Render the command used to repair a built wheel.
+///
+/// The `{wheel}` and `{dest_dir}` placeholders are replaced with quoted references to the
+/// `UV_REPAIR_WHEEL` and `UV_REPAIR_DEST_DIR` environment variables, which are set on the
+/// command, rather than with the paths themselves, such that paths containing spaces or shell
+/// metacharacters are passed through verbatim.
+///
+/// The special value `auto` selects the standard tool for the current platform: `auditwheel` on
+/// Linux, `delocate` on macOS, and `delvewheel` on Windows, each of which must be available on
+/// the `PATH`.
+fn repair_wheel_command(command: &str) -> String {
+    let command = if command == "auto" {
+        if cfg!(target_os = "macos") {
+            "delocate-wheel --wheel-dir {dest_dir} {wheel}"
+        } else if cfg!(windows) {
+            "delvewheel repair --wheel-dir {dest_dir} {wheel}"
+        } else {
+            "auditwheel repair --wheel-dir {dest_dir} {wheel}"
+        }
+    } else {
+        command
+    };
+    if cfg!(windows) {
+        command
+            .replace("{wheel}", "\"%UV_REPAIR_WHEEL%\"")
+            .replace("{dest_dir}", "\"%UV_REPAIR_DEST_DIR%\"")
+    } else {
+        command
+            .replace("{wheel}", "\"$UV_REPAIR_WHEEL\"")
+            .replace("{dest_dir}", "\"$UV_REPAIR_DEST_DIR\"")
     }
 }
 
@@ -911,6 +1110,9 @@ fn escape_path_for_python(path: &Path) -> String {
 }
 
 /// Not a method because we call it before the builder is completely initialized
+///
+/// Returns the resolution of the build requirements, if any additional requirements reported by
+/// the backend had to be installed.
 async fn create_pep517_build_environment(
     runner: &PythonRunner,
     source_tree: &Path,
@@ -923,7 +1125,7 @@ async fn create_pep517_build_environment(
     environment_variables: &FxHashMap<OsString, OsString>,
     modified_path: &OsString,
     temp_dir: &TempDir,
-) -> Result<(), Error> {
+) -> Result<Option<Resolution>, Error> {
     // Write the hook output to a file so that we can read it back reliably.
     let outfile = temp_dir
         .path()
@@ -1023,9 +1225,11 @@ async fn create_pep517_build_environment(
             .install(&resolution, venv)
             .await
             .map_err(|err| Error::RequirementsInstall("build-system.requires (install)", err))?;
+
+        return Ok(Some(resolution));
     }
 
-    Ok(())
+    Ok(None)
 }
 
 /// A runner that manages the execution of external python processes with a
@@ -1098,11 +1302,65 @@ impl PythonRunner {
 
 #[cfg(test)]
 mod test {
+    use std::io;
     use std::process::{ExitStatus, Output};
 
+    use anyhow::Context;
     use indoc::indoc;
 
-    use crate::Error;
+    use crate::{repair_wheel_command, Error};
+
+    #[test]
+    #[cfg(unix)]
+    fn repair_command() {
+        let command = repair_wheel_command(
+            "auditwheel repair --plat manylinux_2_28_x86_64 -w {dest_dir} {wheel}",
+        );
+        assert_eq!(
+            command,
+            r#"auditwheel repair --plat manylinux_2_28_x86_64 -w "$UV_REPAIR_DEST_DIR" "$UV_REPAIR_WHEEL""#
+        );
+
+        // The placeholders are substituted in the platform-specific default, too.
+        let command = repair_wheel_command("auto");
+        assert!(command.ends_with(r#" --wheel-dir "$UV_REPAIR_DEST_DIR" "$UV_REPAIR_WHEEL""#));
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn repair_command() {
+        let command = repair_wheel_command("delvewheel repair -w {dest_dir} {wheel}");
+        assert_eq!(
+            command,
+            r#"delvewheel repair -w "%UV_REPAIR_DEST_DIR%" "%UV_REPAIR_WHEEL%""#
+        );
+    }
+
+    #[test]
+    fn transient() {
+        // A timeout while downloading a build requirement, wrapped in context by the installer.
+        let err = Err::<(), _>(io::Error::new(io::ErrorKind::TimedOut, "network timeout"))
+            .context("Failed to download `setuptools==69.2.0`")
+            .unwrap_err();
+        assert!(Error::RequirementsInstall("build-system.requires (install)", err).is_transient());
+
+        // A race while creating the virtual environment in the temporary directory.
+        let err = uv_virtualenv::Error::Io(io::Error::from(io::ErrorKind::NotFound));
+        assert!(Error::Virtualenv(err).is_transient());
+
+        // Resolution failures are deterministic.
+        let err = anyhow::anyhow!("No solution found when resolving: setuptools>=1000");
+        assert!(!Error::RequirementsInstall("build-system.requires (resolve)", err).is_transient());
+
+        // As are failures in the build backend itself.
+        let err = Error::BuildBackend {
+            message: "Build backend failed to build wheel through `build_wheel()`".to_string(),
+            exit_code: ExitStatus::default(),
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        assert!(!err.is_transient());
+    }
 
     #[test]
     fn missing_header() {
@@ -15,6 +15,12 @@ impl ArchiveId {
     pub fn new() -> Self {
         Self(nanoid::nanoid!())
     }
+
+    /// Create an identifier for an archive from the SHA-256 digest of the wheel from which it was
+    /// extracted, such that identical wheels share a single archive.
+    pub fn from_sha256(digest: &str) -> Self {
+        Self(format!("sha256-{digest}"))
+    }
 }
 
 impl AsRef<Path> for ArchiveId {
@@ -27,6 +27,27 @@ pub struct CacheArgs {
     /// Linux, and `{FOLDERID_LocalAppData}\uv\cache` on Windows.
     #[arg(global = true, long, env = "UV_CACHE_DIR")]
     pub cache_dir: Option<PathBuf>,
+
+    /// Path to the directory in which to stage build environments and extracted archives.
+    ///
+    /// Defaults to the cache directory, or to the system temporary directory when `--no-cache` is
+    /// provided. Useful when either is located on a small filesystem (e.g., a `tmpfs`).
+    #[arg(global = true, long, env = "UV_STAGING_DIR")]
+    pub staging_dir: Option<PathBuf>,
+
+    /// Avoid reading from or writing to the cache of Python interpreter metadata.
+    ///
+    /// By default, the results of querying an interpreter (e.g., its version, tags, markers, and
+    /// installation paths) are cached, keyed by the path and modification time of the executable,
+    /// such that repeated invocations don't need to launch a Python subprocess. Disabling the cache
+    /// is primarily useful for debugging interpreter discovery.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_NO_INTERPRETER_CACHE",
+        value_parser = clap::builder::BoolishValueParser::new(),
+    )]
+    pub no_interpreter_cache: bool,
 }
 
 impl Cache {
@@ -36,17 +57,25 @@ impl Cache {
     /// 3. The system-appropriate cache directory.
     /// 4. A `.uv_cache` directory in the current working directory.
     ///
+    /// If a staging directory is provided, build environments and extracted archives are staged
+    /// within it, as is the temporary cache directory.
+    ///
     /// Returns an absolute cache dir.
-    pub fn from_settings(no_cache: bool, cache_dir: Option<PathBuf>) -> Result<Self, io::Error> {
-        if no_cache {
-            Cache::temp()
+    pub fn from_settings(
+        no_cache: bool,
+        cache_dir: Option<PathBuf>,
+        staging_dir: Option<PathBuf>,
+    ) -> Result<Self, io::Error> {
+        let cache = if no_cache {
+            Cache::temp_in(staging_dir.as_deref())?
         } else if let Some(cache_dir) = cache_dir {
-            Ok(Cache::from_path(cache_dir))
+            Cache::from_path(cache_dir)
         } else if let Some(project_dirs) = ProjectDirs::from("", "", "uv") {
-            Ok(Cache::from_path(project_dirs.cache_dir()))
+            Cache::from_path(project_dirs.cache_dir())
         } else {
-            Ok(Cache::from_path(".uv_cache"))
-        }
+            Cache::from_path(".uv_cache")
+        };
+        Ok(cache.with_staging_dir(staging_dir))
     }
 }
 
@@ -54,6 +83,9 @@ impl TryFrom<CacheArgs> for Cache {
     type Error = io::Error;
 
     fn try_from(value: CacheArgs) -> Result<Self, Self::Error> {
-        Cache::from_settings(value.no_cache, value.cache_dir)
+        Ok(
+            Cache::from_settings(value.no_cache, value.cache_dir, value.staging_dir)?
+                .with_interpreter_cache(!value.no_interpreter_cache),
+        )
     }
 }
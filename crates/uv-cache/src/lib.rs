@@ -1,9 +1,11 @@
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::io::Write;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use fs_err as fs;
@@ -11,15 +13,18 @@ use rustc_hash::FxHashSet;
 use tracing::debug;
 
 pub use archive::ArchiveId;
+use distribution_filename::WheelFilename;
 use distribution_types::InstalledDist;
 use pypi_types::Metadata23;
-use uv_fs::{cachedir, directories};
+use uv_fs::{cachedir, directories, LockedFile, Simplified};
 use uv_normalize::PackageName;
 
 pub use crate::by_timestamp::CachedByTimestamp;
 #[cfg(feature = "clap")]
 pub use crate::cli::CacheArgs;
-use crate::removal::{rm_rf, Removal};
+pub use crate::relocate::{copy_dir, link_dir, move_dir, rebase_symlinks, Relocation};
+use crate::removal::rm_rf;
+pub use crate::removal::Removal;
 pub use crate::timestamp::Timestamp;
 pub use crate::wheel::WheelCache;
 use crate::wheel::WheelCacheKind;
@@ -28,10 +33,15 @@ mod archive;
 mod by_timestamp;
 #[cfg(feature = "clap")]
 mod cli;
+mod relocate;
 mod removal;
 mod timestamp;
 mod wheel;
 
+/// The minimum interval between periodic prunes of the cached environments (see
+/// [`Cache::prune_environments_periodically`]).
+pub const ENVIRONMENT_PRUNE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
 /// A [`CacheEntry`] which may or may not exist yet.
 #[derive(Debug, Clone)]
 pub struct CacheEntry(PathBuf);
@@ -114,6 +124,9 @@ impl Deref for CacheShard {
 pub struct Cache {
     /// The cache directory.
     root: PathBuf,
+    /// The directories of any buckets that are stored outside of the cache directory (e.g., on a
+    /// different filesystem).
+    buckets: BTreeMap<CacheBucket, PathBuf>,
     /// The refresh strategy to use when reading from the cache.
     refresh: Refresh,
     /// A temporary cache directory, if the user requested `--no-cache`.
@@ -121,6 +134,10 @@ pub struct Cache {
     /// Included to ensure that the temporary directory exists for the length of the operation, but
     /// is dropped at the end as appropriate.
     temp_dir: Option<Arc<tempfile::TempDir>>,
+    /// The directory in which to stage build trees and extracted archives, if not the cache root.
+    staging_dir: Option<PathBuf>,
+    /// Whether to read and write the results of interpreter queries.
+    interpreter_cache: bool,
 }
 
 impl Cache {
@@ -128,18 +145,35 @@ impl Cache {
     pub fn from_path(root: impl Into<PathBuf>) -> Self {
         Self {
             root: root.into(),
+            buckets: BTreeMap::new(),
             refresh: Refresh::None(Timestamp::now()),
             temp_dir: None,
+            staging_dir: None,
+            interpreter_cache: true,
         }
     }
 
     /// Create a temporary cache directory.
     pub fn temp() -> Result<Self, io::Error> {
-        let temp_dir = tempfile::tempdir()?;
+        Self::temp_in(None)
+    }
+
+    /// Create a temporary cache directory within the given directory, or within the system
+    /// temporary directory if `None`.
+    pub fn temp_in(dir: Option<&Path>) -> Result<Self, io::Error> {
+        let temp_dir = if let Some(dir) = dir {
+            fs::create_dir_all(dir)?;
+            tempfile::tempdir_in(dir)?
+        } else {
+            tempfile::tempdir()?
+        };
         Ok(Self {
             root: temp_dir.path().to_path_buf(),
+            buckets: BTreeMap::new(),
             refresh: Refresh::None(Timestamp::now()),
             temp_dir: Some(Arc::new(temp_dir)),
+            staging_dir: None,
+            interpreter_cache: true,
         })
     }
 
@@ -149,6 +183,48 @@ impl Cache {
         Self { refresh, ..self }
     }
 
+    /// Store the given buckets in the provided directories, rather than within the cache root.
+    ///
+    /// Ignored for temporary caches, which are always self-contained.
+    #[must_use]
+    pub fn with_buckets(self, buckets: BTreeMap<CacheBucket, PathBuf>) -> Self {
+        if self.is_temporary() {
+            return self;
+        }
+        Self { buckets, ..self }
+    }
+
+    /// Stage build trees and extracted archives in the given directory, rather than within the
+    /// cache root.
+    ///
+    /// Ignored for temporary caches, which are already created within the staging directory.
+    #[must_use]
+    pub fn with_staging_dir(self, staging_dir: Option<PathBuf>) -> Self {
+        if self.is_temporary() {
+            return self;
+        }
+        Self {
+            staging_dir,
+            ..self
+        }
+    }
+
+    /// Enable or disable the caching of interpreter query results.
+    ///
+    /// When disabled, every interpreter is queried afresh, and the results aren't persisted.
+    #[must_use]
+    pub fn with_interpreter_cache(self, interpreter_cache: bool) -> Self {
+        Self {
+            interpreter_cache,
+            ..self
+        }
+    }
+
+    /// Returns `true` if interpreter query results should be read from and written to the cache.
+    pub fn interpreter_cache(&self) -> bool {
+        self.interpreter_cache
+    }
+
     /// Return the root of the cache.
     pub fn root(&self) -> &Path {
         &self.root
@@ -156,7 +232,15 @@ impl Cache {
 
     /// The folder for a specific cache bucket
     pub fn bucket(&self, cache_bucket: CacheBucket) -> PathBuf {
-        self.root.join(cache_bucket.to_str())
+        match self.buckets.get(&cache_bucket) {
+            Some(path) => path.clone(),
+            None => self.root.join(cache_bucket.to_str()),
+        }
+    }
+
+    /// Returns `true` if the given bucket is stored outside of the cache root.
+    pub fn is_relocated(&self, cache_bucket: CacheBucket) -> bool {
+        self.buckets.contains_key(&cache_bucket)
     }
 
     /// Compute an entry in the cache.
@@ -179,10 +263,39 @@ impl Cache {
         self.bucket(CacheBucket::Archive).join(id)
     }
 
-    /// Create an ephemeral Python environment in the cache.
+    /// Create an ephemeral Python environment in the cache, or in the staging directory, if set.
     pub fn environment(&self) -> io::Result<tempfile::TempDir> {
-        fs::create_dir_all(self.bucket(CacheBucket::Builds))?;
-        tempfile::tempdir_in(self.bucket(CacheBucket::Builds))
+        let dir = match &self.staging_dir {
+            Some(staging_dir) => staging_dir.clone(),
+            None => self.bucket(CacheBucket::Builds),
+        };
+        fs::create_dir_all(&dir)?;
+        tempfile::tempdir_in(dir)
+    }
+
+    /// Return the directory in which archives are extracted before being persisted to the cache,
+    /// i.e., the staging directory, if set, and the cache root otherwise.
+    pub fn staging_root(&self) -> &Path {
+        self.staging_dir.as_deref().unwrap_or(&self.root)
+    }
+
+    /// Create a temporary directory in which to extract an archive before persisting it to the
+    /// cache, verifying that at least `size` bytes are available, if known.
+    ///
+    /// The directory is created in the staging directory, if set, and in the cache root
+    /// otherwise.
+    pub fn staging(&self, size: Option<u64>) -> io::Result<tempfile::TempDir> {
+        let dir = self.staging_root();
+        fs::create_dir_all(dir)?;
+        if let Some(size) = size {
+            uv_fs::ensure_available_space(dir, size)?;
+        }
+        tempfile::tempdir_in(dir)
+    }
+
+    /// Return the [`Refresh`] policy for the cache.
+    pub fn refresh(&self) -> &Refresh {
+        &self.refresh
     }
 
     /// Returns `true` if a cache entry must be revalidated given the [`Refresh`] policy.
@@ -190,7 +303,9 @@ impl Cache {
         match &self.refresh {
             Refresh::None(_) => false,
             Refresh::All(_) => true,
-            Refresh::Packages(packages, _) => packages.contains(package),
+            Refresh::Packages(packages, _) | Refresh::Python(packages, _) => {
+                packages.contains(package)
+            }
         }
     }
 
@@ -214,11 +329,42 @@ impl Cache {
                     return Ok(Freshness::Fresh);
                 }
             }
+            // Interpreter metadata is validated separately (see `interpreter_freshness`), so
+            // entries that aren't linked to a package (e.g., flat indexes) are only refreshed if
+            // packages were requested alongside `--refresh-python`.
+            Refresh::Python(packages, timestamp) => {
+                if !packages.is_empty()
+                    && package.map_or(true, |package| packages.contains(package))
+                {
+                    timestamp
+                } else {
+                    return Ok(Freshness::Fresh);
+                }
+            }
         };
 
+        Self::freshness_at(entry, *timestamp)
+    }
+
+    /// Returns the [`Freshness`] for a cached interpreter query, validating it against the
+    /// [`Refresh`] policy.
+    ///
+    /// Interpreter metadata is only refreshed by `--refresh` and `--refresh-python`; it isn't
+    /// linked to any package.
+    pub fn interpreter_freshness(&self, entry: &CacheEntry) -> io::Result<Freshness> {
+        match &self.refresh {
+            Refresh::None(_) | Refresh::Packages(..) => Ok(Freshness::Fresh),
+            Refresh::All(timestamp) | Refresh::Python(_, timestamp) => {
+                Self::freshness_at(entry, *timestamp)
+            }
+        }
+    }
+
+    /// Returns the [`Freshness`] for a cache entry, given the cutoff timestamp.
+    fn freshness_at(entry: &CacheEntry, timestamp: Timestamp) -> io::Result<Freshness> {
         match fs::metadata(entry.path()) {
             Ok(metadata) => {
-                if Timestamp::from_metadata(&metadata) >= *timestamp {
+                if Timestamp::from_metadata(&metadata) >= timestamp {
                     Ok(Freshness::Fresh)
                 } else {
                     Ok(Freshness::Stale)
@@ -236,13 +382,63 @@ impl Cache {
         path: impl AsRef<Path>,
     ) -> io::Result<ArchiveId> {
         // Create a unique ID for the artifact.
-        // TODO(charlie): Support content-addressed persistence via SHAs.
         let id = ArchiveId::new();
+        self.persist_as(temp_dir, path, id).await
+    }
 
+    /// Persist a temporary directory to the artifact store, keyed by the SHA-256 digest of the
+    /// wheel from which it was extracted.
+    ///
+    /// If the store already contains an archive for the digest (e.g., as the same wheel was
+    /// retrieved from another index), the temporary directory is discarded in favor of the
+    /// existing archive, such that environments share a single copy of each wheel.
+    pub async fn persist_sha256(
+        &self,
+        temp_dir: impl AsRef<Path>,
+        path: impl AsRef<Path>,
+        digest: &str,
+    ) -> io::Result<ArchiveId> {
+        let id = ArchiveId::from_sha256(digest);
+        let archive = self.archive(&id);
+        if archive.is_dir() {
+            debug!("Reusing existing archive: {}", archive.display());
+            rm_rf(temp_dir.as_ref())?;
+            fs_err::create_dir_all(path.as_ref().parent().expect("Cache entry to have parent"))?;
+            uv_fs::replace_symlink(&archive, path.as_ref())?;
+            return Ok(id);
+        }
+        self.persist_as(temp_dir, path, id).await
+    }
+
+    /// Persist a temporary directory to the artifact store under the given ID.
+    async fn persist_as(
+        &self,
+        temp_dir: impl AsRef<Path>,
+        path: impl AsRef<Path>,
+        id: ArchiveId,
+    ) -> io::Result<ArchiveId> {
         // Move the temporary directory into the directory store.
         let archive_entry = self.entry(CacheBucket::Archive, "", &id);
         fs_err::create_dir_all(archive_entry.dir())?;
-        uv_fs::rename_with_retry(temp_dir.as_ref(), archive_entry.path()).await?;
+        if let Err(err) = uv_fs::rename_with_retry(temp_dir.as_ref(), archive_entry.path()).await {
+            if archive_entry.path().is_dir() {
+                // If another process persisted the same (content-addressed) archive concurrently,
+                // use it instead.
+                rm_rf(temp_dir.as_ref())?;
+            } else if relocate::is_cross_device(&err) {
+                // If the staging directory is on a different filesystem, fall back to copying
+                // into the directory store, then renaming the copy into place atomically.
+                debug!(
+                    "Copying {} to {} across filesystems",
+                    temp_dir.as_ref().display(),
+                    archive_entry.path().display()
+                );
+                relocate::copy_into_place(temp_dir.as_ref(), archive_entry.path())?;
+                rm_rf(temp_dir.as_ref())?;
+            } else {
+                return Err(err);
+            }
+        }
 
         // Create a symlink to the directory store.
         fs_err::create_dir_all(path.as_ref().parent().expect("Cache entry to have parent"))?;
@@ -266,6 +462,13 @@ impl Cache {
         // Add the CACHEDIR.TAG.
         cachedir::ensure_tag(root)?;
 
+        // Create any buckets that live outside the cache directory.
+        let mut buckets = BTreeMap::new();
+        for (bucket, path) in &self.buckets {
+            fs::create_dir_all(path)?;
+            buckets.insert(*bucket, fs::canonicalize(path)?);
+        }
+
         // Add the .gitignore.
         match fs::OpenOptions::new()
             .write(true)
@@ -280,9 +483,9 @@ impl Cache {
         // Add an empty .gitignore to the build bucket, to ensure that the cache's own .gitignore
         // doesn't interfere with source distribution builds. Build backends (like hatchling) will
         // traverse upwards to look for .gitignore files.
-        fs::create_dir_all(root.join(CacheBucket::SourceDistributions.to_str()))?;
+        fs::create_dir_all(self.bucket(CacheBucket::SourceDistributions))?;
         match fs::OpenOptions::new().write(true).create_new(true).open(
-            root.join(CacheBucket::SourceDistributions.to_str())
+            self.bucket(CacheBucket::SourceDistributions)
                 .join(".gitignore"),
         ) {
             Ok(_) => {}
@@ -297,22 +500,34 @@ impl Cache {
         // We have to put this below the gitignore. Otherwise, if the build backend uses the rust
         // ignore crate it will walk up to the top level .gitignore and ignore its python source
         // files.
-        fs::OpenOptions::new().create(true).write(true).open(
-            root.join(CacheBucket::SourceDistributions.to_str())
-                .join(".git"),
-        )?;
+        fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(self.bucket(CacheBucket::SourceDistributions).join(".git"))?;
 
         Ok(Self {
             root: fs::canonicalize(root)?,
+            buckets,
             ..self
         })
     }
 
     /// Clear the cache, removing all entries.
+    ///
+    /// Buckets stored outside the cache directory are left in place, as they may be shared with
+    /// other caches or contain unrelated files.
     pub fn clear(&self) -> Result<Removal, io::Error> {
         rm_rf(&self.root)
     }
 
+    /// Move the cache directory to the given destination, which must not exist or be empty.
+    ///
+    /// Buckets stored outside the cache directory are left in place. Any symlinks within the cache
+    /// are updated to point into the new directory.
+    pub fn relocate(&self, destination: &Path) -> Result<Relocation, io::Error> {
+        move_dir(&self.root, destination)
+    }
+
     /// Remove a package from the cache.
     ///
     /// Returns the number of entries removed from the cache.
@@ -324,6 +539,69 @@ impl Cache {
         Ok(summary)
     }
 
+    /// Remove the wheels built from source for a package, along with the source distributions
+    /// they were built from, leaving any pre-built wheels in place.
+    ///
+    /// Returns the number of entries removed from the cache.
+    pub fn remove_builds(&self, name: &PackageName) -> Result<Removal, io::Error> {
+        CacheBucket::SourceDistributions.remove(self, name)
+    }
+
+    /// Find the wheels built from source whose build environment included the given package
+    /// (e.g., as the build backend), returning the name of each built package alongside the cache
+    /// directory that contains its wheels.
+    pub fn built_dependents(&self, name: &PackageName) -> Vec<(PackageName, PathBuf)> {
+        let mut dependents = walkdir::WalkDir::new(self.bucket(CacheBucket::SourceDistributions))
+            .max_depth(6)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name() == "build-inputs.msgpack")
+            .filter_map(|entry| {
+                let build_inputs = fs_err::read(entry.path()).ok()?;
+                let build_inputs = rmp_serde::from_slice::<Vec<PackageName>>(&build_inputs).ok()?;
+                if !build_inputs.contains(name) {
+                    return None;
+                }
+                let directory = entry.path().parent()?;
+                let metadata = fs_err::read(directory.join("metadata.msgpack")).ok()?;
+                let metadata = rmp_serde::from_slice::<Metadata23>(&metadata).ok()?;
+                Some((metadata.name, directory.to_path_buf()))
+            })
+            .collect::<Vec<_>>();
+        dependents.sort();
+        dependents
+    }
+
+    /// Remove the wheels built from source in the given cache directory (as returned by
+    /// [`Cache::built_dependents`]), such that they're rebuilt when next required. The source
+    /// distribution itself is retained.
+    pub fn remove_built_wheels(&self, directory: &Path) -> Result<Removal, io::Error> {
+        let mut summary = Removal::default();
+        for entry in fs::read_dir(directory)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            let file_name = entry.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                continue;
+            };
+            // The built wheel itself, e.g., `foo-1.0.0-py3-none-any.whl`.
+            let is_wheel = file_type.is_file() && WheelFilename::from_str(file_name).is_ok();
+            // The unzipped wheel, a symlink into the archive bucket named for the wheel's stem,
+            // e.g., `foo-1.0.0-py3-none-any`.
+            let is_unzipped_wheel =
+                file_type.is_symlink() && WheelFilename::from_stem(file_name).is_ok();
+            // The metadata of the built wheel, and the packages in its build environment.
+            let is_record = file_type.is_file()
+                && matches!(file_name, "metadata.msgpack" | "build-inputs.msgpack");
+            if is_wheel || is_unzipped_wheel || is_record {
+                let path = entry.path();
+                debug!("Removing built wheel: {}", path.display());
+                summary += rm_rf(path)?;
+            }
+        }
+        Ok(summary)
+    }
+
     /// Run the garbage collector on the cache, removing any dangling entries.
     pub fn prune(&self, ci: bool) -> Result<Removal, io::Error> {
         let mut summary = Removal::default();
@@ -371,7 +649,20 @@ impl Cache {
             Err(err) => return Err(err),
         }
 
-        // Third, if enabled, remove all unzipped wheels, leaving only the wheel archives.
+        // Third, remove any virtual environment templates, which are recreated on demand.
+        match fs::read_dir(self.bucket(CacheBucket::VenvTemplates)) {
+            Ok(entries) => {
+                for entry in entries {
+                    let path = entry?.path();
+                    debug!("Removing virtual environment template: {}", path.display());
+                    summary += rm_rf(path)?;
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => return Err(err),
+        }
+
+        // Fourth, if enabled, remove all unzipped wheels, leaving only the wheel archives.
         if ci {
             // Remove the entire pre-built wheel cache, since every entry is an unzipped wheel.
             match fs::read_dir(self.bucket(CacheBucket::Wheels)) {
@@ -399,7 +690,7 @@ impl Cache {
             }
         }
 
-        // Third, remove any unused archives (by searching for archives that are not symlinked).
+        // Fifth, remove any unused archives (by searching for archives that are not symlinked).
         // TODO(charlie): Remove any unused source distributions. This requires introspecting the
         // cache contents, e.g., reading and deserializing the manifests.
         let mut references = FxHashSet::default();
@@ -435,11 +726,308 @@ impl Cache {
 
         Ok(summary)
     }
+
+    /// Remove any cached registry distributions that aren't reachable, as determined by the given
+    /// predicate, which receives the package name and, if known, the version of each entry.
+    ///
+    /// Only distributions from PyPI and alternate indexes (and their Simple API responses) are
+    /// considered; distributions from direct URLs, local paths, and Git repositories are retained.
+    /// The removed entries may leave archives unreferenced, so this should be followed by
+    /// [`Cache::prune`].
+    pub fn prune_unreachable(
+        &self,
+        is_reachable: impl Fn(&PackageName, Option<&str>) -> bool,
+    ) -> Result<Removal, io::Error> {
+        /// Return the directories for each index in the given bucket.
+        fn indexes(bucket: &Path) -> impl Iterator<Item = PathBuf> {
+            std::iter::once(bucket.join(WheelCacheKind::Pypi))
+                .chain(directories(bucket.join(WheelCacheKind::Index)))
+        }
+
+        /// Return the entries in the given directory, if it exists.
+        fn entries(path: &Path) -> Result<Vec<PathBuf>, io::Error> {
+            match fs::read_dir(path) {
+                Ok(entries) => entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .collect(),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+                Err(err) => Err(err),
+            }
+        }
+
+        /// Parse the package name from the name of a per-package directory.
+        fn package_name(path: &Path) -> Option<PackageName> {
+            PackageName::new(path.file_name()?.to_str()?.to_string()).ok()
+        }
+
+        let mut summary = Removal::default();
+
+        // For wheels, we expect a directory per package, containing entries named after each
+        // wheel (e.g., `foo-1.0.0-py3-none-any.http`), from which the version can be determined.
+        for index in indexes(&self.bucket(CacheBucket::Wheels)) {
+            for package in directories(index) {
+                let Some(name) = package_name(&package) else {
+                    continue;
+                };
+                if !is_reachable(&name, None) {
+                    debug!("Removing unreachable cache entry: {}", package.display());
+                    summary += rm_rf(package)?;
+                    continue;
+                }
+                for entry in entries(&package)? {
+                    let Some(version) = entry
+                        .file_name()
+                        .and_then(|file_name| file_name.to_str())
+                        .and_then(|file_name| file_name.split('-').nth(1))
+                    else {
+                        continue;
+                    };
+                    if !is_reachable(&name, Some(version)) {
+                        debug!("Removing unreachable cache entry: {}", entry.display());
+                        summary += rm_rf(entry)?;
+                    }
+                }
+            }
+        }
+
+        // For source distributions, we expect a directory per package, followed by a directory
+        // per version.
+        for index in indexes(&self.bucket(CacheBucket::SourceDistributions)) {
+            for package in directories(index) {
+                let Some(name) = package_name(&package) else {
+                    continue;
+                };
+                if !is_reachable(&name, None) {
+                    debug!("Removing unreachable cache entry: {}", package.display());
+                    summary += rm_rf(package)?;
+                    continue;
+                }
+                for version in directories(&package) {
+                    let Some(version_str) = version.file_name().and_then(|name| name.to_str())
+                    else {
+                        continue;
+                    };
+                    if !is_reachable(&name, Some(version_str)) {
+                        debug!("Removing unreachable cache entry: {}", version.display());
+                        summary += rm_rf(version)?;
+                    }
+                }
+            }
+        }
+
+        // For Simple API responses, we expect a file per package (e.g., `foo.rkyv`).
+        for index in indexes(&self.bucket(CacheBucket::Simple)) {
+            for entry in entries(&index)? {
+                let Some(name) = entry
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| PackageName::new(stem.to_string()).ok())
+                else {
+                    continue;
+                };
+                if !is_reachable(&name, None) {
+                    debug!("Removing unreachable cache entry: {}", entry.display());
+                    summary += rm_rf(entry)?;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Returns the filenames of the cached wheels for the given package that were downloaded from
+    /// PyPI or an alternate index.
+    ///
+    /// The filenames are derived from the names of the cache entries (e.g., an entry for
+    /// `foo-1.0.0-py3-none-any.http` yields `foo-1.0.0-py3-none-any.whl`), and aren't validated.
+    pub fn registry_wheels(&self, name: &PackageName) -> Vec<String> {
+        let bucket = self.bucket(CacheBucket::Wheels);
+        let mut wheels = std::iter::once(bucket.join(WheelCacheKind::Pypi))
+            .chain(directories(bucket.join(WheelCacheKind::Index)))
+            .filter_map(|index| fs::read_dir(index.join(name.as_ref())).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension().is_some_and(|ext| ext == "http") {
+                    Some(format!("{}.whl", path.file_stem()?.to_str()?))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        wheels.sort_unstable();
+        wheels.dedup();
+        wheels
+    }
+
+    /// Mark the cached environment at the given root as used, for the purposes of
+    /// [`Cache::prune_environments`].
+    pub fn touch_environment(&self, root: &Path) -> Result<(), io::Error> {
+        std::fs::File::options()
+            .write(true)
+            .open(root.join("pyvenv.cfg"))?
+            .set_modified(std::time::SystemTime::now())
+    }
+
+    /// Acquire a shared lock on the cached environments, which prevents them from being pruned
+    /// while it's held.
+    ///
+    /// The lock should be held for as long as a cached environment is in use.
+    pub fn lock_environments(&self) -> Result<LockedFile, io::Error> {
+        let root = self.bucket(CacheBucket::Environments);
+        fs::create_dir_all(&root)?;
+        LockedFile::acquire_shared(root.join(".lock"), root.user_display())
+    }
+
+    /// Remove any cached environments that haven't been used within the given `max_age`, then
+    /// remove the least-recently used environments until the remainder fit within `max_size`
+    /// bytes.
+    ///
+    /// Waits for any environments that are in use (see [`Cache::lock_environments`]) to be
+    /// released. The environment at `keep`, if any, is retained regardless.
+    pub fn prune_environments(
+        &self,
+        max_age: Option<std::time::Duration>,
+        max_size: Option<u64>,
+        keep: Option<&Path>,
+    ) -> Result<Removal, io::Error> {
+        let root = self.bucket(CacheBucket::Environments);
+        if !root.is_dir() {
+            return Ok(Removal::default());
+        }
+        let _lock = LockedFile::acquire(root.join(".lock"), root.user_display())?;
+        self.prune_environments_unlocked(max_age, max_size, keep)
+    }
+
+    /// Prune the cached environments, as in [`Cache::prune_environments`], unless they were
+    /// pruned within the last [`ENVIRONMENT_PRUNE_INTERVAL`] or are currently in use.
+    ///
+    /// Returns `None` if the environments weren't pruned.
+    pub fn prune_environments_periodically(
+        &self,
+        max_age: Option<std::time::Duration>,
+        max_size: Option<u64>,
+        keep: Option<&Path>,
+    ) -> Result<Option<Removal>, io::Error> {
+        let root = self.bucket(CacheBucket::Environments);
+        if !root.is_dir() {
+            return Ok(None);
+        }
+
+        // Pruning requires a walk of every cached environment, so avoid doing so on every run.
+        let marker = root.join(".last-prune");
+        let last_pruned = fs_err::metadata(&marker).and_then(|metadata| metadata.modified());
+        if last_pruned.is_ok_and(|last_pruned| {
+            last_pruned
+                .elapsed()
+                .is_ok_and(|elapsed| elapsed < ENVIRONMENT_PRUNE_INTERVAL)
+        }) {
+            debug!("Skipping pruning of cached environments, which were pruned recently");
+            return Ok(None);
+        }
+
+        let Some(_lock) = LockedFile::try_acquire(root.join(".lock"), root.user_display())? else {
+            debug!("Skipping pruning of cached environments, which are in use");
+            return Ok(None);
+        };
+
+        let summary = self.prune_environments_unlocked(max_age, max_size, keep)?;
+        fs_err::File::create(&marker)?;
+
+        Ok(Some(summary))
+    }
+
+    /// Prune the cached environments, as in [`Cache::prune_environments`], without acquiring a
+    /// lock.
+    fn prune_environments_unlocked(
+        &self,
+        max_age: Option<std::time::Duration>,
+        max_size: Option<u64>,
+        keep: Option<&Path>,
+    ) -> Result<Removal, io::Error> {
+        let mut summary = Removal::default();
+
+        // Collect the cached environments, which are stored as symlinks (grouped by interpreter)
+        // to their content-addressed locations in the archive bucket.
+        let mut environments = Vec::new();
+        match fs::read_dir(self.bucket(CacheBucket::Environments)) {
+            Ok(interpreters) => {
+                for interpreter in interpreters {
+                    let interpreter = interpreter?;
+                    if !interpreter.file_type()?.is_dir() {
+                        continue;
+                    }
+                    for entry in fs::read_dir(interpreter.path())? {
+                        let entry = entry?;
+                        let link = entry.path();
+                        let Ok(root) = fs_err::canonicalize(&link) else {
+                            debug!("Removing dangling cache entry: {}", link.display());
+                            summary += rm_rf(&link)?;
+                            continue;
+                        };
+                        if keep.is_some_and(|keep| {
+                            fs_err::canonicalize(keep).is_ok_and(|keep| keep == root)
+                        }) {
+                            continue;
+                        }
+                        let last_used = fs_err::metadata(root.join("pyvenv.cfg"))
+                            .and_then(|metadata| metadata.modified())
+                            .unwrap_or(std::time::UNIX_EPOCH);
+                        // Only compute the size of the environment if it's needed.
+                        let size = if max_size.is_some() {
+                            walkdir::WalkDir::new(&root)
+                                .into_iter()
+                                .filter_map(Result::ok)
+                                .filter_map(|entry| entry.metadata().ok())
+                                .filter(std::fs::Metadata::is_file)
+                                .map(|metadata| metadata.len())
+                                .sum::<u64>()
+                        } else {
+                            0
+                        };
+                        environments.push((last_used, size, link, root));
+                    }
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(summary),
+            Err(err) => return Err(err),
+        }
+
+        // Order the environments from least- to most-recently used.
+        environments.sort_by_key(|(last_used, ..)| *last_used);
+
+        let now = std::time::SystemTime::now();
+        let mut total_size = environments.iter().map(|(_, size, ..)| size).sum::<u64>();
+        for (last_used, size, link, root) in environments {
+            let expired = max_age.is_some_and(|max_age| {
+                now.duration_since(last_used)
+                    .is_ok_and(|elapsed| elapsed > max_age)
+            });
+            let oversized = max_size.is_some_and(|max_size| total_size > max_size);
+            if !expired && !oversized {
+                continue;
+            }
+            debug!("Removing cached environment: {}", root.display());
+            summary += rm_rf(&link)?;
+            summary += rm_rf(&root)?;
+            total_size -= size;
+        }
+
+        Ok(summary)
+    }
 }
 
 /// The different kinds of data in the cache are stored in different bucket, which in our case
 /// are subdirectories of the cache root.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+///
+/// Buckets can be stored outside the cache root (see [`Cache::with_buckets`]), in which case
+/// they're referred to by their unversioned, kebab-case name (e.g., `source-distributions`).
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum CacheBucket {
     /// Wheels (excluding built wheels), alongside their metadata and cache policy.
     ///
@@ -679,9 +1267,42 @@ pub enum CacheBucket {
     Builds,
     /// Reusable virtual environments used to invoke Python tools.
     Environments,
+    /// Templates from which ephemeral virtual environments are cloned, one per interpreter.
+    ///
+    /// Cache structure: `venv-templates-v0/<digest(interpreter, options)>/`.
+    VenvTemplates,
+    /// Managed Python distribution archives, as they're being downloaded.
+    ///
+    /// Archives are streamed to a `.part` file so that interrupted downloads can be resumed, then
+    /// renamed once their checksum is verified. Archives are removed once they've been installed.
+    Python,
+    /// The results of checking requested package names against the list of popular packages, to
+    /// avoid repeating the check on every invocation.
+    ///
+    /// Cache structure: `typosquats-v0/<package_name>`, containing the name of the popular package
+    /// that the requested package resembles, if any.
+    Typosquats,
 }
 
 impl CacheBucket {
+    /// The unversioned name of the bucket, as used in configuration.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SourceDistributions => "source-distributions",
+            Self::FlatIndex => "flat-index",
+            Self::Git => "git",
+            Self::Interpreter => "interpreter",
+            Self::Simple => "simple",
+            Self::Wheels => "wheels",
+            Self::Archive => "archive",
+            Self::Builds => "builds",
+            Self::Environments => "environments",
+            Self::VenvTemplates => "venv-templates",
+            Self::Python => "python",
+            Self::Typosquats => "typosquats",
+        }
+    }
+
     fn to_str(self) -> &'static str {
         match self {
             // Note, next time we change the version we should change the name of this bucket to `source-dists-v0`
@@ -696,6 +1317,9 @@ impl CacheBucket {
             Self::Archive => "archive-v0",
             Self::Builds => "builds-v0",
             Self::Environments => "environments-v1",
+            Self::VenvTemplates => "venv-templates-v0",
+            Self::Python => "python-v0",
+            Self::Typosquats => "typosquats-v0",
         }
     }
 
@@ -704,14 +1328,43 @@ impl CacheBucket {
     /// Returns the number of entries removed from the cache.
     fn remove(self, cache: &Cache, name: &PackageName) -> Result<Removal, io::Error> {
         /// Returns `true` if the [`Path`] represents a built wheel for the given package.
+        ///
+        /// Built wheels may be further scoped by their build environment (e.g., the toolchain),
+        /// so the metadata is searched for within nested directories, too.
         fn is_match(path: &Path, name: &PackageName) -> bool {
-            let Ok(metadata) = fs_err::read(path.join("metadata.msgpack")) else {
-                return false;
-            };
-            let Ok(metadata) = rmp_serde::from_slice::<Metadata23>(&metadata) else {
-                return false;
-            };
-            metadata.name == *name
+            walkdir::WalkDir::new(path)
+                .max_depth(3)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_name() == "metadata.msgpack")
+                .any(|entry| {
+                    let Ok(metadata) = fs_err::read(entry.path()) else {
+                        return false;
+                    };
+                    let Ok(metadata) = rmp_serde::from_slice::<Metadata23>(&metadata) else {
+                        return false;
+                    };
+                    metadata.name == *name
+                })
+        }
+
+        /// Returns `true` if the environment at the given root has the given package installed,
+        /// i.e., if its `site-packages` contains a matching `.dist-info` directory.
+        fn contains_package(root: &Path, name: &PackageName) -> bool {
+            walkdir::WalkDir::new(root)
+                .max_depth(4)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_dir())
+                .any(|entry| {
+                    entry
+                        .file_name()
+                        .to_str()
+                        .and_then(|file_name| file_name.strip_suffix(".dist-info"))
+                        .and_then(|stem| stem.split_once('-'))
+                        .and_then(|(package, _)| PackageName::new(package.to_string()).ok())
+                        .is_some_and(|package| package == *name)
+                })
         }
 
         let mut summary = Removal::default();
@@ -810,8 +1463,37 @@ impl CacheBucket {
                 // Nothing to do.
             }
             Self::Environments => {
+                // For cached environments, we expect a directory for every interpreter, followed
+                // by a symlink per environment to its content-addressed location in the archive
+                // bucket. To determine whether the environment is relevant, we need to search its
+                // `site-packages` for the package.
+                let root = cache.bucket(self);
+                for interpreter in directories(root) {
+                    let Ok(entries) = fs::read_dir(&interpreter) else {
+                        continue;
+                    };
+                    for entry in entries {
+                        let link = entry?.path();
+                        let Ok(environment) = fs_err::canonicalize(&link) else {
+                            continue;
+                        };
+                        if contains_package(&environment, name) {
+                            summary += rm_rf(&link)?;
+                            summary += rm_rf(&environment)?;
+                        }
+                    }
+                }
+            }
+            Self::VenvTemplates => {
+                // Nothing to do.
+            }
+            Self::Python => {
                 // Nothing to do.
             }
+            Self::Typosquats => {
+                // For typosquat checks, we expect a file per package, indexed by name.
+                summary += rm_rf(cache.bucket(self).join(name.to_string()))?;
+            }
         }
         Ok(summary)
     }
@@ -828,6 +1510,9 @@ impl CacheBucket {
             Self::Archive,
             Self::Builds,
             Self::Environments,
+            Self::VenvTemplates,
+            Self::Python,
+            Self::Typosquats,
         ]
         .iter()
         .copied()
@@ -990,19 +1675,28 @@ pub enum Refresh {
     None(Timestamp),
     /// Refresh entries linked to the given packages, if created before the given timestamp.
     Packages(Vec<PackageName>, Timestamp),
+    /// Refresh cached interpreter metadata, along with any entries linked to the given packages,
+    /// if created before the given timestamp.
+    Python(Vec<PackageName>, Timestamp),
     /// Refresh all entries created before the given timestamp.
     All(Timestamp),
 }
 
 impl Refresh {
     /// Determine the refresh strategy to use based on the command-line arguments.
-    pub fn from_args(refresh: Option<bool>, refresh_package: Vec<PackageName>) -> Self {
+    pub fn from_args(
+        refresh: Option<bool>,
+        refresh_package: Vec<PackageName>,
+        refresh_python: bool,
+    ) -> Self {
         let timestamp = Timestamp::now();
         match refresh {
             Some(true) => Self::All(timestamp),
             Some(false) => Self::None(timestamp),
             None => {
-                if refresh_package.is_empty() {
+                if refresh_python {
+                    Self::Python(refresh_package, timestamp)
+                } else if refresh_package.is_empty() {
                     Self::None(timestamp)
                 } else {
                     Self::Packages(refresh_package, timestamp)
@@ -1016,41 +1710,43 @@ impl Refresh {
         matches!(self, Self::None(_))
     }
 
+    /// Return the cutoff timestamp of the policy.
+    fn timestamp(&self) -> Timestamp {
+        match self {
+            Self::None(timestamp)
+            | Self::Packages(_, timestamp)
+            | Self::Python(_, timestamp)
+            | Self::All(timestamp) => *timestamp,
+        }
+    }
+
     /// Combine two [`Refresh`] policies, taking the "max" of the two policies.
     #[must_use]
     pub fn combine(self, other: Refresh) -> Self {
-        /// Return the maximum of two timestamps.
-        fn max(a: Timestamp, b: Timestamp) -> Timestamp {
-            if a > b {
-                a
-            } else {
-                b
-            }
-        }
+        // Take the `max` of the two timestamps.
+        let timestamp = max(self.timestamp(), other.timestamp());
 
         match (self, other) {
-            // If the policy is `None`, return the existing refresh policy.
-            // Take the `max` of the two timestamps.
-            (Self::None(t1), Refresh::None(t2)) => Refresh::None(max(t1, t2)),
-            (Self::None(t1), Refresh::All(t2)) => Refresh::All(max(t1, t2)),
-            (Self::None(t1), Refresh::Packages(packages, t2)) => {
-                Refresh::Packages(packages, max(t1, t2))
+            // If either policy is `All`, refresh everything.
+            (Self::All(_), _) | (_, Self::All(_)) => Self::All(timestamp),
+
+            // If either policy is `None`, return the other policy.
+            (Self::None(_), Self::None(_)) => Self::None(timestamp),
+            (Self::None(_), Self::Packages(packages, _))
+            | (Self::Packages(packages, _), Self::None(_)) => Self::Packages(packages, timestamp),
+            (Self::None(_), Self::Python(packages, _))
+            | (Self::Python(packages, _), Self::None(_)) => Self::Python(packages, timestamp),
+
+            // Otherwise, take the union of the packages, refreshing interpreters if either
+            // policy does.
+            (Self::Packages(packages1, _), Self::Packages(packages2, _)) => {
+                Self::Packages(packages1.into_iter().chain(packages2).collect(), timestamp)
             }
-
-            // If the policy is `All`, refresh all packages.
-            (Self::All(t1), Refresh::None(t2)) => Refresh::All(max(t1, t2)),
-            (Self::All(t1), Refresh::All(t2)) => Refresh::All(max(t1, t2)),
-            (Self::All(t1), Refresh::Packages(_packages, t2)) => Refresh::All(max(t1, t2)),
-
-            // If the policy is `Packages`, take the "max" of the two policies.
-            (Self::Packages(packages, t1), Refresh::None(t2)) => {
-                Refresh::Packages(packages, max(t1, t2))
+            (Self::Packages(packages1, _), Self::Python(packages2, _))
+            | (Self::Python(packages1, _), Self::Packages(packages2, _))
+            | (Self::Python(packages1, _), Self::Python(packages2, _)) => {
+                Self::Python(packages1.into_iter().chain(packages2).collect(), timestamp)
             }
-            (Self::Packages(_packages, t1), Refresh::All(t2)) => Refresh::All(max(t1, t2)),
-            (Self::Packages(packages1, t1), Refresh::Packages(packages2, t2)) => Refresh::Packages(
-                packages1.into_iter().chain(packages2).collect(),
-                max(t1, t2),
-            ),
         }
     }
 }
@@ -0,0 +1,407 @@
+//! Move the cache (or individual entries within it) between directories, potentially across
+//! filesystems.
+
+use std::error::Error as _;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rustc_hash::FxHashMap;
+use tracing::debug;
+
+use crate::removal::rm_rf;
+
+/// A summary of a cache relocation.
+#[derive(Debug, Default)]
+pub struct Relocation {
+    /// Whether the cache was moved by renaming it, rather than copying its contents.
+    pub renamed: bool,
+    /// The number of files copied.
+    pub num_files: u64,
+    /// The number of hard links that were preserved when copying.
+    pub num_hard_links: u64,
+    /// The number of symlinks that were updated to point into the new directory.
+    pub num_symlinks: u64,
+}
+
+/// Move the directory at `source` to `destination`, which must not exist or be empty.
+///
+/// If both directories are on the same filesystem, this is a single rename. Otherwise, the
+/// contents are copied (preserving hard links between files where possible), and the source is
+/// removed. In either case, absolute symlinks into `source` are rewritten to point into
+/// `destination`.
+pub fn move_dir(source: &Path, destination: &Path) -> io::Result<Relocation> {
+    let mut relocation = Relocation::default();
+
+    match fs_err::read_dir(destination) {
+        Ok(mut entries) => {
+            if entries.next().is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!(
+                        "Destination already exists and is not empty: {}",
+                        destination.display()
+                    ),
+                ));
+            }
+            fs_err::remove_dir(destination)?;
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    if let Some(parent) = destination.parent() {
+        fs_err::create_dir_all(parent)?;
+    }
+
+    match fs_err::rename(source, destination) {
+        Ok(()) => {
+            debug!("Renamed {} to {}", source.display(), destination.display());
+            relocation.renamed = true;
+            relocation.num_symlinks += rebase_symlinks(source, destination)?;
+        }
+        Err(err) if is_cross_device(&err) => {
+            debug!(
+                "Copying {} to {} across filesystems",
+                source.display(),
+                destination.display()
+            );
+            copy_dir(source, destination, Some(&mut relocation))?;
+            rm_rf(source)?;
+        }
+        Err(err) => return Err(err),
+    }
+
+    Ok(relocation)
+}
+
+/// Recursively copy the directory at `source` to `destination`.
+///
+/// Files that are hard-linked to one another within `source` are hard-linked within
+/// `destination`, rather than copied repeatedly. Absolute symlinks into `source` are rewritten to
+/// point into `destination`; other symlinks are preserved as-is.
+pub fn copy_dir(
+    source: &Path,
+    destination: &Path,
+    mut relocation: Option<&mut Relocation>,
+) -> io::Result<()> {
+    // A map from (device, inode) to the first copy of each multiply-linked file.
+    let mut links: FxHashMap<(u64, u64), PathBuf> = FxHashMap::default();
+
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .expect("walkdir starts with root");
+        let target = destination.join(relative);
+        let file_type = entry.file_type();
+
+        if file_type.is_symlink() {
+            let link = fs_err::read_link(entry.path())?;
+            let link = match link.strip_prefix(source) {
+                Ok(suffix) => {
+                    if let Some(relocation) = relocation.as_deref_mut() {
+                        relocation.num_symlinks += 1;
+                    }
+                    destination.join(suffix)
+                }
+                Err(_) => link,
+            };
+            uv_fs::replace_symlink(link, &target)?;
+        } else if file_type.is_dir() {
+            fs_err::create_dir_all(&target)?;
+        } else {
+            match link_key(&entry.metadata()?) {
+                Some(key) => {
+                    if let Some(existing) = links.get(&key) {
+                        fs_err::hard_link(existing, &target)?;
+                        if let Some(relocation) = relocation.as_deref_mut() {
+                            relocation.num_hard_links += 1;
+                        }
+                        continue;
+                    }
+                    fs_err::copy(entry.path(), &target)?;
+                    links.insert(key, target);
+                }
+                None => {
+                    fs_err::copy(entry.path(), &target)?;
+                }
+            }
+            if let Some(relocation) = relocation.as_deref_mut() {
+                relocation.num_files += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Recursively clone the directory at `source` to `destination`, hard-linking each file rather
+/// than copying it.
+///
+/// Read-only files (and files that can't be hard-linked) are copied instead, such that restoring
+/// write access to `destination` leaves `source` untouched. Symlinks are preserved as-is. Since
+/// the files are shared, they must be replaced, rather than modified in place, to leave `source`
+/// intact.
+pub fn link_dir(source: &Path, destination: &Path) -> io::Result<()> {
+    for entry in walkdir::WalkDir::new(source) {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .expect("walkdir starts with root");
+        let target = destination.join(relative);
+        let file_type = entry.file_type();
+
+        if file_type.is_symlink() {
+            uv_fs::replace_symlink(fs_err::read_link(entry.path())?, &target)?;
+        } else if file_type.is_dir() {
+            fs_err::create_dir_all(&target)?;
+        } else if entry.metadata()?.permissions().readonly() {
+            fs_err::copy(entry.path(), &target)?;
+        } else if let Err(err) = fs_err::hard_link(entry.path(), &target) {
+            debug!("Failed to hard link {}: {err}", entry.path().display());
+            fs_err::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Copy the directory at `source` to `destination`, which must not exist.
+///
+/// The contents are copied into a temporary directory alongside `destination`, then renamed into
+/// place, such that concurrent readers never observe a partially-copied directory. Returns `false`
+/// if `destination` was created concurrently, in which case the copy is discarded.
+pub(crate) fn copy_into_place(source: &Path, destination: &Path) -> io::Result<bool> {
+    let parent = destination
+        .parent()
+        .expect("destination to have a parent directory");
+    let temp_dir = tempfile::tempdir_in(parent)?.into_path();
+    if let Err(err) = copy_dir(source, &temp_dir, None) {
+        rm_rf(&temp_dir)?;
+        return Err(err);
+    }
+
+    match fs_err::rename(&temp_dir, destination) {
+        Ok(()) => {
+            rebase_symlinks(&temp_dir, destination)?;
+            Ok(true)
+        }
+        Err(err) => {
+            rm_rf(&temp_dir)?;
+            if destination.is_dir() {
+                Ok(false)
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Rewrite any absolute symlinks within `destination` that point into `source`, returning the
+/// number of symlinks that were rewritten.
+///
+/// This is intended to be called after `source` is renamed to `destination`.
+pub fn rebase_symlinks(source: &Path, destination: &Path) -> io::Result<u64> {
+    let mut num_symlinks = 0;
+    for entry in walkdir::WalkDir::new(destination) {
+        let entry = entry?;
+        if !entry.file_type().is_symlink() {
+            continue;
+        }
+        let link = fs_err::read_link(entry.path())?;
+        if let Ok(suffix) = link.strip_prefix(source) {
+            uv_fs::replace_symlink(destination.join(suffix), entry.path())?;
+            num_symlinks += 1;
+        }
+    }
+    Ok(num_symlinks)
+}
+
+/// Returns the (device, inode) pair identifying a file with multiple hard links, if any.
+#[cfg(unix)]
+fn link_key(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    if metadata.nlink() > 1 {
+        Some((metadata.dev(), metadata.ino()))
+    } else {
+        None
+    }
+}
+
+/// Returns the (device, inode) pair identifying a file with multiple hard links, if any.
+///
+/// Hard links can't be detected on this platform, so every file is copied.
+#[cfg(not(unix))]
+fn link_key(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Returns `true` if the error indicates that a rename crossed a filesystem boundary.
+pub(crate) fn is_cross_device(err: &io::Error) -> bool {
+    // `fs_err` wraps the underlying OS error, so look through to its source.
+    let code = err.raw_os_error().or_else(|| {
+        err.get_ref()
+            .and_then(|inner| inner.source())
+            .and_then(|source| source.downcast_ref::<io::Error>())
+            .and_then(io::Error::raw_os_error)
+    });
+
+    // `EXDEV` on Unix, and `ERROR_NOT_SAME_DEVICE` on Windows.
+    if cfg!(unix) {
+        code == Some(18)
+    } else if cfg!(windows) {
+        code == Some(17)
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use std::os::unix::fs::MetadataExt;
+
+    use super::{copy_into_place, is_cross_device, link_dir, move_dir};
+
+    #[test]
+    fn relocate_rewrites_symlinks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("source");
+        let destination = temp_dir.path().join("nested").join("destination");
+
+        fs_err::create_dir_all(source.join("archive-v0")).unwrap();
+        fs_err::create_dir_all(source.join("wheels-v1")).unwrap();
+        fs_err::write(source.join("archive-v0").join("file"), "contents").unwrap();
+        fs_err::hard_link(
+            source.join("archive-v0").join("file"),
+            source.join("archive-v0").join("link"),
+        )
+        .unwrap();
+        uv_fs::replace_symlink(
+            source.join("archive-v0"),
+            source.join("wheels-v1").join("entry"),
+        )
+        .unwrap();
+
+        let relocation = move_dir(&source, &destination).unwrap();
+        assert!(relocation.renamed);
+        assert_eq!(relocation.num_symlinks, 1);
+        assert!(!source.exists());
+        assert_eq!(
+            fs_err::read_link(destination.join("wheels-v1").join("entry")).unwrap(),
+            destination.join("archive-v0")
+        );
+        assert_eq!(
+            fs_err::metadata(destination.join("archive-v0").join("file"))
+                .unwrap()
+                .ino(),
+            fs_err::metadata(destination.join("archive-v0").join("link"))
+                .unwrap()
+                .ino()
+        );
+    }
+
+    #[test]
+    fn relocate_non_empty_destination() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("source");
+        let destination = temp_dir.path().join("destination");
+        fs_err::create_dir_all(&source).unwrap();
+        fs_err::create_dir_all(&destination).unwrap();
+        fs_err::write(destination.join("file"), "contents").unwrap();
+
+        assert!(move_dir(&source, &destination).is_err());
+        assert!(source.exists());
+    }
+
+    #[test]
+    fn copy_into_place_rewrites_symlinks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("source");
+        let destination = temp_dir.path().join("archive-v0").join("id");
+        fs_err::create_dir_all(source.join("pkg")).unwrap();
+        fs_err::create_dir_all(destination.parent().unwrap()).unwrap();
+        fs_err::write(source.join("pkg").join("file"), "contents").unwrap();
+        uv_fs::replace_symlink(source.join("pkg"), source.join("link")).unwrap();
+
+        assert!(copy_into_place(&source, &destination).unwrap());
+        assert_eq!(
+            fs_err::read_to_string(destination.join("pkg").join("file")).unwrap(),
+            "contents"
+        );
+        assert_eq!(
+            fs_err::read_link(destination.join("link")).unwrap(),
+            destination.join("pkg")
+        );
+        // No temporary directories are left behind.
+        assert_eq!(
+            fs_err::read_dir(destination.parent().unwrap())
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn copy_into_place_existing_destination() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("source");
+        let destination = temp_dir.path().join("destination");
+        fs_err::create_dir_all(&source).unwrap();
+        fs_err::write(source.join("file"), "new").unwrap();
+        fs_err::create_dir_all(&destination).unwrap();
+        fs_err::write(destination.join("file"), "existing").unwrap();
+
+        assert!(!copy_into_place(&source, &destination).unwrap());
+        assert_eq!(
+            fs_err::read_to_string(destination.join("file")).unwrap(),
+            "existing"
+        );
+        assert_eq!(fs_err::read_dir(temp_dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn link_dir_shares_writable_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("source");
+        let destination = temp_dir.path().join("destination");
+        fs_err::create_dir_all(source.join("pkg")).unwrap();
+        fs_err::write(source.join("pkg").join("file"), "contents").unwrap();
+        fs_err::write(source.join("pkg").join("read-only"), "contents").unwrap();
+        uv_fs::set_read_only(source.join("pkg").join("read-only"), true).unwrap();
+        uv_fs::replace_symlink(source.join("pkg"), source.join("link")).unwrap();
+
+        link_dir(&source, &destination).unwrap();
+
+        // Writable files are hard-linked, while read-only files are copied.
+        let ino = |path: std::path::PathBuf| fs_err::metadata(path).unwrap().ino();
+        assert_eq!(
+            ino(source.join("pkg").join("file")),
+            ino(destination.join("pkg").join("file"))
+        );
+        assert_ne!(
+            ino(source.join("pkg").join("read-only")),
+            ino(destination.join("pkg").join("read-only"))
+        );
+
+        // Symlinks are preserved as-is.
+        assert_eq!(
+            fs_err::read_link(destination.join("link")).unwrap(),
+            source.join("pkg")
+        );
+    }
+
+    #[test]
+    fn cross_device_wrapped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let err = fs_err::rename(
+            temp_dir.path().join("missing"),
+            temp_dir.path().join("other"),
+        )
+        .unwrap_err();
+        assert!(!is_cross_device(&err));
+        assert!(is_cross_device(&std::io::Error::from_raw_os_error(18)));
+    }
+}
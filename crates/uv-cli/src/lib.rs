@@ -1,4 +1,5 @@
 use std::ffi::OsString;
+use std::net::IpAddr;
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -7,16 +8,21 @@ use anyhow::{anyhow, Result};
 use clap::builder::styling::Style;
 use clap::{Args, Parser, Subcommand};
 
-use distribution_types::{FlatIndexLocation, IndexUrl};
-use pep508_rs::Requirement;
+use distribution_types::{FlatIndexLocation, IndexHashPolicy, IndexUrl, RequiredIndex};
+use install_wheel_rs::ExcludePattern;
+use pep440_rs::VersionSpecifiers;
+use pep508_rs::{MarkerTree, Requirement};
 use pypi_types::VerbatimParsedUrl;
 use uv_cache::CacheArgs;
 use uv_configuration::{
-    ConfigSettingEntry, IndexStrategy, KeyringProviderType, PackageNameSpecifier, TargetTriple,
+    AbiCheck, ConfigSettingEntry, EditorConfig, FileConflicts, IndexStrategy, IndexVerification,
+    IpVersion, KeyringProviderType, MacosArch, ModulePath, PackageNameSpecifier, SysPlatform,
+    TargetTriple, TyposquatCheck,
 };
-use uv_normalize::{ExtraName, PackageName};
+use uv_normalize::{ExtraName, GroupName, PackageName};
 use uv_python::{PythonDownloads, PythonPreference, PythonVersion};
 use uv_resolver::{AnnotationStyle, ExcludeNewer, PrereleaseMode, ResolutionMode};
+use uv_tool::ToolName;
 
 pub mod compat;
 pub mod options;
@@ -30,6 +36,42 @@ pub enum VersionFormat {
     Json,
 }
 
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub enum DiffFormat {
+    /// Display the differences as plain text.
+    #[default]
+    Text,
+    /// Display the differences as a Markdown table.
+    Markdown,
+    /// Display the differences as JSON.
+    Json,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TreeFormat {
+    /// Display the dependency tree as plain text.
+    #[default]
+    Text,
+    /// Display the dependency graph in the Graphviz DOT language.
+    Dot,
+    /// Display the dependency graph as a Mermaid flowchart.
+    Mermaid,
+    /// Display the dependency graph as JSON, with a list of nodes and a list of edges.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Export the dependencies as a Nix expression, e.g., for use with `fetchurl`.
+    Nix,
+    /// Export the dependencies as a Bazel (Starlark) file, e.g., for use with `http_file`.
+    Bazel,
+    /// Export the direct dependencies declared by the project, with their declared specifiers
+    /// rather than the locked versions, e.g., for use with `pip-compile`.
+    #[value(name = "requirements.in")]
+    RequirementsIn,
+}
+
 #[derive(Debug, Default, Clone, clap::ValueEnum)]
 pub enum ListFormat {
     /// Display the list of packages in a human-readable table.
@@ -42,6 +84,64 @@ pub enum ListFormat {
     Json,
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ToolFormat {
+    /// Display the tools in a human-readable format.
+    #[default]
+    Text,
+    /// Display the tools in a machine-readable JSON format.
+    Json,
+    /// Display the tools in a machine-readable TOML format.
+    Toml,
+    /// Display the tools as shell variable assignments, suitable for `eval`.
+    Shell,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ToolShowFormat {
+    /// Display the receipt in a human-readable format.
+    #[default]
+    Text,
+    /// Display the receipt in a machine-readable JSON format.
+    Json,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum InstallSummary {
+    /// Group the changes into added, changed, and removed packages, truncating long listings.
+    Minimal,
+    /// List every package that was added or removed.
+    #[default]
+    Detailed,
+    /// Don't list the changes.
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RequiresPythonFix {
+    /// Narrow the project's `requires-python` to the Python versions supported by the dependency.
+    Narrow,
+    /// Add the dependency with a marker that limits it to the Python versions it supports.
+    Marker,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BuildBackend {
+    /// Use [hatchling](https://pypi.org/project/hatchling) as the build backend.
+    #[default]
+    Hatchling,
+    /// Use [setuptools](https://pypi.org/project/setuptools) as the build backend.
+    Setuptools,
+    /// Use [flit-core](https://pypi.org/project/flit-core) as the build backend.
+    Flit,
+    /// Use [maturin](https://pypi.org/project/maturin) as the build backend, with a Rust extension
+    /// module.
+    Maturin,
+    /// Use [scikit-build-core](https://pypi.org/project/scikit-build-core) as the build backend,
+    /// with a C++ extension module.
+    ScikitBuildCore,
+}
+
 fn extra_name_with_clap_error(arg: &str) -> Result<ExtraName> {
     ExtraName::from_str(arg).map_err(|_err| {
         anyhow!(
@@ -92,6 +192,14 @@ pub struct Cli {
     #[arg(global = true, long, env = "UV_NO_CONFIG", value_parser = clap::builder::BoolishValueParser::new(), help_heading = "Global options")]
     pub no_config: bool,
 
+    /// Avoid applying the user-level constraints file.
+    ///
+    /// Normally, the constraints in `constraints.txt` in the user configuration directory (e.g.,
+    /// `~/.config/uv/constraints.txt`) are applied to the `pip` interface, tools, and scripts.
+    /// They're not applied when locking a project, such that the lockfile remains reproducible.
+    #[arg(global = true, long, env = "UV_NO_USER_CONSTRAINTS", value_parser = clap::builder::BoolishValueParser::new(), help_heading = "Global options")]
+    pub no_user_constraints: bool,
+
     /// Display the concise help for this command.
     #[arg(global = true, short, long, action = clap::ArgAction::HelpShort, help_heading = "Global options")]
     help: Option<bool>,
@@ -114,6 +222,7 @@ pub struct GlobalArgs {
     #[arg(
         global = true,
         long,
+        env = "UV_PYTHON_PREFERENCE",
         help_heading = "Python options",
         display_order = 700
     )]
@@ -174,10 +283,57 @@ pub struct GlobalArgs {
     #[arg(global = true, long, overrides_with("native_tls"), hide = true)]
     pub no_native_tls: bool,
 
+    /// Only connect to hosts over the given IP version (`4` or `6`).
+    ///
+    /// By default, uv connects over whichever addresses a host resolves to. On networks with
+    /// broken IPv6 connectivity, connections to hosts that publish IPv6 (`AAAA`) records can
+    /// stall until they time out; with `--ip-version 4`, those records are ignored.
+    #[arg(global = true, long, env = "UV_IP_VERSION", value_enum)]
+    pub ip_version: Option<IpVersion>,
+
+    /// Connect to the host of the given index at a fixed address, bypassing DNS resolution, in the
+    /// form `<INDEX_URL>=<IP>` (e.g., `https://pypi.internal/simple=10.0.0.5`).
+    ///
+    /// Useful on networks with split-horizon DNS, where an index's hostname doesn't resolve to a
+    /// reachable address. The port is taken from the index URL.
+    ///
+    /// May be provided multiple times.
+    #[arg(
+        global = true,
+        long,
+        env = "UV_INDEX_RESOLVE",
+        value_delimiter = ' ',
+        value_name = "INDEX_URL=IP",
+        value_parser = parse_index_resolve
+    )]
+    pub index_resolve: Vec<(IndexUrl, IpAddr)>,
+
+    /// Verify index responses against the TUF metadata published by each index, per PEP 458.
+    ///
+    /// With `if-available`, the pages of indexes that publish TUF metadata must match their signed
+    /// targets, files that aren't signed are ignored, and the hashes of downloaded distributions
+    /// are always validated. With `required`, indexes that don't publish TUF metadata are
+    /// rejected.
+    ///
+    /// The first root metadata of each index is trusted on first use, and persisted outside the
+    /// cache.
+    #[arg(global = true, long, env = "UV_INDEX_VERIFICATION", value_enum)]
+    pub index_verification: Option<IndexVerification>,
+
+    /// A shell command to run on each platform-specific wheel built from source, e.g., to vendor
+    /// external shared libraries into the wheel.
+    ///
+    /// The `{wheel}` placeholder refers to the built wheel, and the command is expected to write
+    /// the repaired wheel to `{dest_dir}`, e.g., `auditwheel repair -w {dest_dir} {wheel}`. If set
+    /// to `auto`, uv uses `auditwheel` on Linux, `delocate-wheel` on macOS, and `delvewheel` on
+    /// Windows.
+    #[arg(global = true, long, env = "UV_REPAIR_WHEEL_COMMAND")]
+    pub repair_wheel_command: Option<String>,
+
     /// Disable network access.
     ///
     /// When disabled, uv will only use locally cached data and locally available files.
-    #[arg(global = true, long, overrides_with("no_offline"))]
+    #[arg(global = true, long, env = "UV_OFFLINE", value_parser = clap::builder::BoolishValueParser::new(), overrides_with("no_offline"))]
     pub offline: bool,
 
     #[arg(global = true, long, overrides_with("offline"), hide = true)]
@@ -213,8 +369,29 @@ pub struct GlobalArgs {
     #[arg(global = true, long)]
     pub no_progress: bool,
 
+    /// The format in which to report progress.
+    ///
+    /// By default, progress is displayed as spinners and progress bars. With `json-lines`, progress
+    /// is instead reported as a stream of JSON objects on stderr, one per line, such that other
+    /// tools can render their own progress indicators. Any other output on stderr (e.g., messages,
+    /// warnings, and errors) is reported as JSON objects, too.
+    #[arg(global = true, long, value_enum, env = "UV_PROGRESS_FORMAT")]
+    pub progress_format: Option<ProgressFormat>,
+
+    /// Write a structured log of the invocation to the given file, in JSON Lines format.
+    ///
+    /// The log includes the command, the resolved settings, a summary of each resolution, the
+    /// network requests, and timings, independent of the console verbosity. If the file already
+    /// exists, entries are appended to it.
+    #[arg(global = true, long, env = "UV_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
     /// Change to the given directory prior to running the command.
-    #[arg(global = true, long, hide = true)]
+    ///
+    /// Relative paths provided on the command line are resolved against the given directory, and
+    /// paths in the output are displayed relative to it, as if uv had been invoked from within
+    /// the directory.
+    #[arg(global = true, long, env = "UV_WORKING_DIRECTORY")]
     pub directory: Option<PathBuf>,
 }
 
@@ -230,6 +407,16 @@ pub enum ColorChoice {
     Never,
 }
 
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProgressFormat {
+    /// Display progress as spinners and progress bars.
+    #[default]
+    Bars,
+
+    /// Report progress as JSON objects on stderr, one per line.
+    JsonLines,
+}
+
 impl From<ColorChoice> for anstream::ColorChoice {
     fn from(value: ColorChoice) -> Self {
         match value {
@@ -247,6 +434,13 @@ pub enum Commands {
     #[command(flatten)]
     Project(Box<ProjectCommand>),
 
+    /// Inspect the members of the workspace and the dependencies between them (experimental).
+    #[command(
+        after_help = "Use `uv help workspace` for more details.",
+        after_long_help = ""
+    )]
+    Workspace(WorkspaceNamespace),
+
     /// Run and manage tools provided by Python packages (experimental).
     #[command(
         after_help = "Use `uv help tool` for more details.",
@@ -322,6 +516,10 @@ pub enum Commands {
     /// When using uv, the virtual environment does not need to be activated. uv
     /// will find a virtual environment (named `.venv`) in the working directory
     /// or any parent directories.
+    ///
+    /// The virtual environments created by uv are recorded, such that they can
+    /// later be found with `uv venv list` and cleaned up with `uv venv remove`
+    /// or `uv venv prune`.
     #[command(
         alias = "virtualenv",
         alias = "v",
@@ -335,6 +533,12 @@ pub enum Commands {
         after_long_help = ""
     )]
     Cache(CacheNamespace),
+    /// Show local usage statistics (experimental).
+    ///
+    /// Summarizes the invocations of commands that resolve or install packages, as recorded in a
+    /// log in the user data directory. The log never leaves the machine. Recording is disabled by
+    /// default; set `UV_STATS` to enable it.
+    Stats(StatsArgs),
     /// Manage the uv executable.
     #[command(name = "self")]
     #[cfg(feature = "self-update")]
@@ -366,6 +570,9 @@ pub enum Commands {
         ),
     )]
     Help(HelpArgs),
+    /// Run an external subcommand, i.e., a `uv-<name>` executable on the `PATH`.
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
 }
 
 #[derive(Args, Debug)]
@@ -405,7 +612,80 @@ pub enum CacheCommand {
     /// Prune all unreachable objects from the cache.
     Prune(PruneArgs),
     /// Show the cache directory.
-    Dir,
+    Dir(DirArgs),
+    /// Move the cache to a different directory.
+    ///
+    /// If the destination is on the same filesystem, the cache is renamed in place. Otherwise, its
+    /// contents are copied (preserving hard links between cache entries) before the original is
+    /// removed. Cache buckets that are stored outside the cache directory (via `cache-buckets`)
+    /// are left in place.
+    ///
+    /// The cache directory isn't persisted by this command; set `UV_CACHE_DIR` or `cache-dir` to
+    /// the destination to continue using the relocated cache.
+    Move(MoveArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct DirArgs {
+    /// Show the directory of each cache bucket, rather than the cache root.
+    ///
+    /// Each line contains the name of a bucket followed by its directory, which may be outside the
+    /// cache root if it was configured via `cache-buckets`.
+    #[arg(long)]
+    pub per_bucket: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// Display the statistics as JSON, rather than as a human-readable summary.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Remove all recorded statistics.
+    #[arg(long, conflicts_with = "json")]
+    pub clear: bool,
+}
+
+#[derive(Subcommand)]
+pub enum VenvCommand {
+    /// List the virtual environments created by uv.
+    ///
+    /// Environments that no longer exist, or that were created for a project that no longer
+    /// exists, are marked as such.
+    List(VenvListArgs),
+    /// Remove virtual environments, along with their records.
+    Remove(VenvRemoveArgs),
+    /// Remove the virtual environments that were created for projects that no longer exist.
+    ///
+    /// The records of any environments that were deleted by other means are removed as well.
+    Prune(VenvPruneArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct VenvListArgs {
+    /// Display the environments as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct VenvRemoveArgs {
+    /// The paths to the virtual environments to remove.
+    #[arg(required = true)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct VenvPruneArgs {
+    /// Show the environments that would be removed, without removing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct MoveArgs {
+    /// The directory to move the cache to, which must not exist or be empty.
+    pub destination: PathBuf,
 }
 
 #[derive(Args, Debug)]
@@ -413,6 +693,13 @@ pub enum CacheCommand {
 pub struct CleanArgs {
     /// The packages to remove from the cache.
     pub package: Vec<PackageName>,
+
+    /// Also remove any wheels that were built from source with the given packages in their build
+    /// environment (e.g., as the build backend), such that they're rebuilt when next required.
+    ///
+    /// By default, such wheels are retained, and listed after the packages are removed.
+    #[arg(long, requires = "package")]
+    pub built_dependents: bool,
 }
 
 #[derive(Args, Debug)]
@@ -433,6 +720,52 @@ pub struct PruneArgs {
     /// that were built from source.
     #[arg(long)]
     pub ci: bool,
+
+    /// Only remove the cached environments used by `uv tool run` (and `uv run --with`), leaving
+    /// the rest of the cache intact.
+    #[arg(long, conflicts_with = "ci")]
+    pub tool_runs: bool,
+
+    /// When pruning with `--tool-runs`, only remove the cached environments that haven't been used
+    /// in the given number of days.
+    ///
+    /// By default, every cached environment is removed.
+    #[arg(long, requires = "tool_runs", value_name = "DAYS")]
+    pub max_age: Option<u64>,
+
+    /// When pruning with `--tool-runs`, remove the least-recently used cached environments until
+    /// their total size is within the given limit, e.g., `500MB` or `2GiB`.
+    ///
+    /// By default, every cached environment is removed.
+    #[arg(long, requires = "tool_runs", value_parser = parse_size, value_name = "SIZE")]
+    pub max_size: Option<u64>,
+
+    /// Remove any cached distributions that aren't reachable from the lockfiles provided via
+    /// `--lockfiles`.
+    ///
+    /// Each `uv.lock` or `requirements.txt` file is treated as a root: cached distributions from
+    /// PyPI and alternate indexes are retained only if some root includes the package (and, for
+    /// pinned versions, the same version). Useful for keeping a shared cache, e.g., in continuous
+    /// integration, limited to what the current branches actually need.
+    ///
+    /// Distributions from direct URLs, local paths, and Git repositories are always retained.
+    #[arg(long, requires = "lockfiles", conflicts_with = "tool_runs")]
+    pub unused: bool,
+
+    /// The lockfiles (i.e., `uv.lock` or `requirements.txt` files) to treat as roots when pruning
+    /// with `--unused`.
+    ///
+    /// Accepts glob patterns (e.g., `**/uv.lock`), relative to the current working directory.
+    #[arg(long, num_args = 1.., requires = "unused", value_name = "GLOB")]
+    pub lockfiles: Vec<String>,
+
+    /// Only remove the wheels built from source for the given packages, along with their cached
+    /// source distributions, such that they're rebuilt on next use.
+    ///
+    /// Pre-built wheels for the packages are retained. Useful after changing the compiler
+    /// toolchain or a system library that the builds link against.
+    #[arg(long, conflicts_with_all = ["ci", "tool_runs", "unused"], value_name = "PACKAGE")]
+    pub builds_for: Vec<PackageName>,
 }
 
 #[derive(Args)]
@@ -609,6 +942,26 @@ pub enum ProjectCommand {
         after_long_help = ""
     )]
     Sync(SyncArgs),
+    /// Run the project's tests against each of its test Python versions (experimental).
+    ///
+    /// For each of the Python versions declared in `tool.uv.test-pythons`, an
+    /// environment is synced from the lockfile, along with the development
+    /// dependencies and any groups declared in `tool.uv.test-groups`, and the
+    /// test command is run in it. The project environment is used for a version
+    /// it satisfies; otherwise, a secondary environment for the interpreter is
+    /// used (e.g., `.venv-cpython-3.9`). Any versions that aren't installed are
+    /// downloaded as needed.
+    ///
+    /// The test command is read from `tool.uv.test-command`, unless one is
+    /// provided as arguments, e.g., `uv test -- pytest -k slow`.
+    ///
+    /// Once every version has run, a summary of the results is displayed; the
+    /// command fails if any version fails.
+    #[command(
+        after_help = "Use `uv help test` for more details.",
+        after_long_help = ""
+    )]
+    Test(TestArgs),
     /// Update the project's lockfile (experimental).
     ///
     /// If the project lockfile (`uv.lock`) does not exist, it will be created.
@@ -624,6 +977,103 @@ pub enum ProjectCommand {
     Lock(LockArgs),
     /// Display the project's dependency tree (experimental).
     Tree(TreeArgs),
+    /// Explain why a package is included in the project's lockfile (experimental).
+    ///
+    /// Lists every chain of dependencies from a workspace member to the given
+    /// package, along with the version constraints and markers along each
+    /// edge, where known.
+    ///
+    /// Version constraints are read from the `pyproject.toml` files of the
+    /// workspace members and, for transitive dependencies, from the packages
+    /// installed in the project environment, as they aren't recorded in the
+    /// `uv.lock` file.
+    #[command(
+        after_help = "Use `uv help why` for more details.",
+        after_long_help = ""
+    )]
+    Why(WhyArgs),
+    /// Compare two lockfiles, or a lockfile and the current environment (experimental).
+    ///
+    /// Lists the packages that were added, removed, or changed between the two,
+    /// along with any changes to their versions, sources, and the markers under
+    /// which they're required.
+    ///
+    /// If only one lockfile is provided, it's compared against the packages
+    /// installed in the current environment.
+    #[command(
+        after_help = "Use `uv help diff` for more details.",
+        after_long_help = ""
+    )]
+    Diff(DiffArgs),
+    /// Unpack the project's locked dependencies into a project-local directory
+    /// (experimental).
+    ///
+    /// The pure-Python dependencies of the project are installed into the
+    /// output directory (`vendor` by default), along with an `__init__.py` that
+    /// adds the directory to `sys.path` when imported, and a `uv-vendor.json`
+    /// manifest listing the vendored packages. Any workspace members that the
+    /// project depends on are vendored as regular (non-editable) packages.
+    ///
+    /// Intended for environments in which packages can't be installed at
+    /// runtime, like serverless bundles and application plugins. uv will exit
+    /// with an error if any dependency includes compiled extensions.
+    #[command(
+        after_help = "Use `uv help vendor` for more details.",
+        after_long_help = ""
+    )]
+    Vendor(VendorArgs),
+    /// Export the project's locked dependencies for use by Nix or Bazel
+    /// (experimental).
+    ///
+    /// Emits a specification of the locked packages required on any
+    /// platform, including the URL and SHA-256 hash of every wheel and source
+    /// distribution and the dependencies between the packages, along with the
+    /// markers under which each dependency applies, such that hermetic build
+    /// systems can select and fetch the artifacts for each target themselves
+    /// while trusting uv's resolution.
+    ///
+    /// uv will exit with an error if any dependency lacks remote artifacts
+    /// with known hashes (e.g., local paths and Git dependencies).
+    #[command(
+        after_help = "Use `uv help export` for more details.",
+        after_long_help = ""
+    )]
+    Export(ExportArgs),
+    /// Build the project into source distributions and wheels (experimental).
+    ///
+    /// By default, both a source distribution and a wheel are built, and are
+    /// written to the `dist` directory within the project root.
+    ///
+    /// The project is built in an isolated environment that satisfies its
+    /// `build-system.requires`. With `--locked`, the build environment is
+    /// instead pinned to the build requirements in the `uv.lock` file, such
+    /// that the artifacts can be reproduced across machines.
+    ///
+    /// uv will search for a project in the current directory or any parent
+    /// directory. If a project cannot be found, uv will exit with an error.
+    #[command(
+        after_help = "Use `uv help build` for more details.",
+        after_long_help = ""
+    )]
+    Build(ProjectBuildArgs),
+    /// Answer queries about the project over JSON-RPC, for editor integrations
+    /// (experimental).
+    ///
+    /// Starts a long-running process that reads JSON-RPC 2.0 requests from
+    /// stdin and writes responses to stdout, one per line. The package
+    /// metadata fetched while answering a request is kept in memory, such that
+    /// subsequent requests avoid repeating the work.
+    ///
+    /// The supported methods are `environment/info`, `lock/status`,
+    /// `dependencies/get`, `resolve`, and `shutdown`.
+    ///
+    /// uv will search for a project in the current directory or any parent
+    /// directory. If a project cannot be found, uv will exit with an error.
+    #[command(
+        after_help = "Use `uv help server` for more details.",
+        after_long_help = ""
+    )]
+    Server(ServerArgs),
 }
 
 /// A re-implementation of `Option`, used to avoid Clap's automatic `Option` flattening in
@@ -643,6 +1093,74 @@ impl<T> Maybe<T> {
     }
 }
 
+/// A constraints file that only applies in environments that satisfy the given marker.
+#[derive(Debug, Clone)]
+pub struct MarkedConstraints {
+    pub path: PathBuf,
+    pub marker: MarkerTree,
+}
+
+/// Parse a string into a [`MarkedConstraints`], in the form `<PATH>; <MARKER>`, mirroring the
+/// syntax of a PEP 508 requirement.
+fn parse_marked_constraints(input: &str) -> Result<MarkedConstraints, String> {
+    let Some((path, marker)) = input.split_once(';') else {
+        return Err(
+            "expected a path and a marker, separated by a semicolon (e.g., `constraints-win.txt; sys_platform == 'win32'`)"
+                .to_string(),
+        );
+    };
+    let path = parse_file_path(path.trim())?;
+    let marker = MarkerTree::from_str(marker.trim()).map_err(|err| err.to_string())?;
+    Ok(MarkedConstraints { path, marker })
+}
+
+/// A requirements file whose packages are added to the given optional dependency group.
+#[derive(Debug, Clone)]
+pub struct OptionalRequirements {
+    pub extra: ExtraName,
+    pub path: PathBuf,
+}
+
+/// Parse a string into an [`OptionalRequirements`], in the form `<EXTRA>=<PATH>`.
+fn parse_optional_requirements(input: &str) -> Result<OptionalRequirements, String> {
+    let Some((extra, path)) = input.split_once('=') else {
+        return Err(format!(
+            "expected an extra and a path, separated by `=` (e.g., `docs=requirements-docs.txt`), but found: `{input}`"
+        ));
+    };
+    let extra = ExtraName::from_str(extra.trim()).map_err(|err| err.to_string())?;
+    let path = parse_file_path(path.trim())?;
+    Ok(OptionalRequirements { extra, path })
+}
+
+/// Parse an `<INDEX_URL>=<IP>` pair into an index and the address to connect to for its host.
+fn parse_index_resolve(input: &str) -> Result<(IndexUrl, IpAddr), String> {
+    // Split at the last `=`, since the URL itself may contain one (e.g., in a query string).
+    let Some((index, ip)) = input.rsplit_once('=') else {
+        return Err(format!("expected `INDEX_URL=IP`, but found: `{input}`"));
+    };
+    let index = IndexUrl::from_str(index.trim()).map_err(|err| err.to_string())?;
+    let ip = ip
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .map_err(|err| format!("invalid IP address `{ip}`: {err}"))?;
+    Ok((index, ip))
+}
+
+/// Parse an `<INDEX_URL>=<POLICY>` pair into an index and the hash-checking policy for the
+/// packages it serves.
+fn parse_index_hash_policy(input: &str) -> Result<(IndexUrl, IndexHashPolicy), String> {
+    // Split at the last `=`, since the URL itself may contain one (e.g., in a query string).
+    let Some((index, policy)) = input.rsplit_once('=') else {
+        return Err(format!("expected `INDEX_URL=POLICY`, but found: `{input}`"));
+    };
+    let index = IndexUrl::from_str(index.trim()).map_err(|err| err.to_string())?;
+    let policy = IndexHashPolicy::from_str(policy.trim())?;
+    Ok((index, policy))
+}
+
 /// Parse a string into an [`IndexUrl`], mapping the empty string to `None`.
 fn parse_index_url(input: &str) -> Result<Maybe<IndexUrl>, String> {
     if input.is_empty() {
@@ -682,6 +1200,53 @@ fn parse_maybe_file_path(input: &str) -> Result<Maybe<PathBuf>, String> {
     }
 }
 
+/// Parse a `KEY=VALUE` pair into an environment variable name and value.
+fn parse_env_var(input: &str) -> Result<(String, String), String> {
+    let Some((key, value)) = input.split_once('=') else {
+        return Err(format!("expected `KEY=VALUE`, but found: `{input}`"));
+    };
+    let is_valid = key
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && key
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_');
+    if !is_valid {
+        return Err(format!("invalid environment variable name: `{key}`"));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Parse a size in bytes, with an optional decimal (e.g., `MB`) or binary (e.g., `MiB`) unit.
+fn parse_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let number = number
+        .parse::<u64>()
+        .map_err(|_| format!("invalid size: `{input}`"))?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "m" | "mb" => 1_000_000,
+        "g" | "gb" => 1_000_000_000,
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        _ => {
+            return Err(format!(
+                "invalid size unit: `{unit}` (expected e.g. `MB` or `GiB`)"
+            ))
+        }
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("size is too large: `{input}`"))
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct PipCompileArgs {
@@ -704,6 +1269,16 @@ pub struct PipCompileArgs {
     #[arg(long, short, env = "UV_CONSTRAINT", value_delimiter = ' ', value_parser = parse_maybe_file_path)]
     pub constraint: Vec<Maybe<PathBuf>>,
 
+    /// Constrain versions using the given requirements file, but only in environments that
+    /// satisfy the given marker, provided as `<PATH>; <MARKER>` (e.g.,
+    /// `constraints-win.txt; sys_platform == 'win32'`).
+    ///
+    /// The marker is combined with any marker on the constraints themselves. May be provided
+    /// more than once, to apply different constraints on different platforms within a single
+    /// resolution.
+    #[arg(long, value_parser = parse_marked_constraints)]
+    pub constraint_marker: Vec<MarkedConstraints>,
+
     /// Override versions using the given requirements files.
     ///
     /// Overrides files are `requirements.txt`-like files that force a specific version of a
@@ -740,6 +1315,24 @@ pub struct PipCompileArgs {
     #[arg(long, overrides_with("all_extras"), hide = true)]
     pub no_all_extras: bool,
 
+    /// Include dependencies from the given dependency group; may be provided more than once.
+    ///
+    /// Dependency groups are defined via the `[dependency-groups]` table of a `pyproject.toml`,
+    /// as specified in PEP 735.
+    ///
+    /// Only applies to `pyproject.toml` sources.
+    #[arg(long)]
+    pub group: Vec<GroupName>,
+
+    /// Apply this marker to all of the requirements in the input files (e.g.,
+    /// `sys_platform == 'win32'`).
+    ///
+    /// The marker is combined with any marker on the requirement itself, and included in the
+    /// output when markers are retained (e.g., with `--universal`). Doesn't apply to the
+    /// dependencies of `pyproject.toml`, `setup.py`, or `setup.cfg` sources.
+    #[arg(long, value_parser = MarkerTree::from_str)]
+    pub marker: Option<MarkerTree>,
+
     #[command(flatten)]
     pub resolver: ResolverArgs,
 
@@ -748,6 +1341,8 @@ pub struct PipCompileArgs {
 
     /// Ignore package dependencies, instead only add those packages explicitly listed
     /// on the command line to the resulting the requirements file.
+    ///
+    /// The output is annotated to indicate that it's not a complete set of requirements.
     #[arg(long)]
     pub no_deps: bool,
 
@@ -761,6 +1356,17 @@ pub struct PipCompileArgs {
     #[arg(long, short)]
     pub output_file: Option<PathBuf>,
 
+    /// Only include the direct dependencies (i.e., the packages listed in the input files) in the
+    /// output file.
+    ///
+    /// The complete resolution, including the transitive dependencies and the hashes of every
+    /// package, is written to a sidecar alongside the output file (e.g., `requirements.txt.lock`
+    /// for `requirements.txt`). The sidecar is used to preserve the pinned versions of the
+    /// transitive dependencies on subsequent invocations, and can be passed to `uv pip sync` to
+    /// reproduce the environment.
+    #[arg(long, requires = "output_file")]
+    pub only_direct: bool,
+
     /// Include extras in the output file.
     ///
     /// By default, uv strips extras, as any packages pulled in by the extras are already included
@@ -846,6 +1452,16 @@ pub struct PipCompileArgs {
     #[arg(long, overrides_with("generate_hashes"), hide = true)]
     pub no_generate_hashes: bool,
 
+    /// Record the hash-checking policy for the packages served by an index, in the form
+    /// `<INDEX_URL>=<POLICY>` (e.g., `https://pypi.internal/simple=verify`).
+    ///
+    /// The policies are recorded in the header of the output file, such that the file can be
+    /// installed with the same `--index-hash-policy` options.
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_name = "INDEX_URL=POLICY", value_parser = parse_index_hash_policy)]
+    pub index_hash_policy: Option<Vec<(IndexUrl, IndexHashPolicy)>>,
+
     /// Use legacy `setuptools` behavior when building source distributions without a
     /// `pyproject.toml`.
     #[arg(long, overrides_with("no_legacy_setup_py"))]
@@ -921,6 +1537,35 @@ pub struct PipCompileArgs {
     #[arg(long)]
     pub python_platform: Option<TargetTriple>,
 
+    /// The architecture policy to apply when selecting wheels on macOS.
+    ///
+    /// By default, uv prefers wheels built for the interpreter's architecture, falling back to
+    /// `universal2` wheels. Use `prefer-universal2` or `require-universal2` to favor (or require)
+    /// `universal2` wheels, or `x86_64` to select `x86_64` wheels for an interpreter running under
+    /// Rosetta 2.
+    ///
+    /// uv will error if the interpreter's architecture is inconsistent with the policy, as the
+    /// selected wheels would otherwise fail at import time. When targeting an ARM-based macOS
+    /// platform with `--python-platform`, `x86_64` resolves for `x86_64-apple-darwin` instead. Has
+    /// no effect on other platforms.
+    #[arg(long, value_enum)]
+    pub macos_arch: Option<MacosArch>,
+
+    /// Resolve for the exact environment described by the given JSON file, emitting a flat list of
+    /// pins without environment markers.
+    ///
+    /// The file contains the values of the PEP 508 environment markers (e.g., `sys_platform`,
+    /// `platform_machine`, `python_full_version`), either at the top level or under an
+    /// `environment` key (as in the output of `pip inspect`). Every marker is evaluated against
+    /// the target environment, for deployment systems that can't evaluate markers at install time.
+    ///
+    /// Unless `--python-version` is provided, the target's `python_full_version` is used as the
+    /// Python version for the resolution. Unless `--python-platform` is provided, wheels are
+    /// selected for the target's platform, as determined by its `sys_platform` and
+    /// `platform_machine`.
+    #[arg(long, conflicts_with("universal"), conflicts_with("no_strip_markers"))]
+    pub target_environment: Option<PathBuf>,
+
     /// Perform a universal resolution, attempting to generate a single `requirements.txt` output
     /// file that is compatible with all operating systems, architectures, and Python
     /// implementations.
@@ -946,6 +1591,19 @@ pub struct PipCompileArgs {
     #[arg(long, alias = "unsafe-package")]
     pub no_emit_package: Option<Vec<PackageName>>,
 
+    /// Omit a package from the output resolution, along with any dependencies that are only
+    /// required by omitted packages.
+    ///
+    /// Useful for packages that are provided by the target environment (e.g., by the platform).
+    /// Unlike `--no-emit-package`, the dependencies of an omitted package are pruned, such that
+    /// the output contains exactly the packages required by the remaining packages. The omitted
+    /// and pruned packages are listed at the end of the output, along with the packages that
+    /// require them.
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_name = "PACKAGE")]
+    pub omit: Vec<PackageName>,
+
     /// Include `--index-url` and `--extra-index-url` entries in the generated output file.
     #[arg(long, overrides_with("no_emit_index_url"))]
     pub emit_index_url: bool,
@@ -987,6 +1645,47 @@ pub struct PipCompileArgs {
     #[arg(long, overrides_with("emit_index_annotation"), hide = true)]
     pub no_emit_index_annotation: bool,
 
+    /// Require that a package is served by a specific index, as in `--require-index
+    /// torch=https://download.pytorch.org/whl/cpu`.
+    ///
+    /// If the package is resolved from any other index, or from a source other than an index
+    /// (e.g., a URL or a local path), compilation fails. Useful when combining multiple indexes,
+    /// to guard against a package being served from an unexpected index.
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_name = "PACKAGE=INDEX")]
+    pub require_index: Vec<RequiredIndex>,
+
+    /// Fail if a requested extra isn't provided by the resolved version of a package.
+    ///
+    /// By default, requesting an extra that doesn't exist (e.g., `black[tensorboard]`) emits a
+    /// warning naming the package, its version, and the extras it provides.
+    #[arg(long, overrides_with("no_strict_extras"))]
+    pub strict_extras: bool,
+
+    #[arg(long, overrides_with("strict_extras"), hide = true)]
+    pub no_strict_extras: bool,
+
+    /// Include comment annotations indicating the packages whose versions were pinned by a
+    /// constraint rather than by the dependency graph (e.g., `# constrained-by -c constraints.txt`).
+    ///
+    /// Constraints that had no effect on the resolution are listed at the end of the output, to
+    /// help prune stale constraint files.
+    #[arg(long, overrides_with("no_emit_constraint_annotation"))]
+    pub emit_constraint_annotation: bool,
+
+    #[arg(long, overrides_with("emit_constraint_annotation"), hide = true)]
+    pub no_emit_constraint_annotation: bool,
+
+    /// Write a structured trace of the resolver's decisions to the given file.
+    ///
+    /// The trace is written as JSON lines, with one event per line: the version selected for each
+    /// package, the dependencies it introduced, the constraints propagated to other packages,
+    /// versions that were rejected, and any backtracking.
+    /// Useful for diagnosing slow or unexpected resolutions, e.g., when filing a bug report.
+    #[arg(long)]
+    pub resolver_trace: Option<PathBuf>,
+
     #[command(flatten)]
     pub compat_args: compat::PipCompileCompatArgs,
 }
@@ -1022,6 +1721,14 @@ pub struct PipSyncArgs {
     #[arg(long, short, env = "UV_BUILD_CONSTRAINT", value_delimiter = ' ', value_parser = parse_maybe_file_path)]
     pub build_constraint: Vec<Maybe<PathBuf>>,
 
+    /// The directory in which to check out editable Git requirements (e.g., `-e git+https://...`).
+    ///
+    /// Defaults to `src` within the virtual environment, or within the current directory if
+    /// installing into a system Python environment. Existing checkouts are reused as-is, unless
+    /// the package is upgraded (e.g., with `--upgrade`).
+    #[arg(long, env = "UV_SRC")]
+    pub src: Option<PathBuf>,
+
     #[command(flatten)]
     pub installer: InstallerArgs,
 
@@ -1067,6 +1774,22 @@ pub struct PipSyncArgs {
     #[arg(long, overrides_with("verify_hashes"), hide = true)]
     pub no_verify_hashes: bool,
 
+    /// The hash-checking policy for the packages served by an index, in the form
+    /// `<INDEX_URL>=<POLICY>` (e.g., `https://pypi.internal/simple=verify`).
+    ///
+    /// With a `require` policy, every package served by the index must have a hash, as with
+    /// `--require-hashes`. With a `verify` policy, hashes are validated if present, but packages
+    /// without hashes are allowed (and needn't be pinned), as with `--verify-hashes`. Packages
+    /// served by any other index, or by a direct URL or local path, are checked according to
+    /// `--require-hashes` and `--verify-hashes`.
+    ///
+    /// For example, `--require-hashes --index-hash-policy https://pypi.internal/simple=verify`
+    /// requires hashes for every package, except those served by the internal mirror.
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_name = "INDEX_URL=POLICY", value_parser = parse_index_hash_policy)]
+    pub index_hash_policy: Option<Vec<(IndexUrl, IndexHashPolicy)>>,
+
     /// The Python interpreter into which packages should be installed.
     ///
     /// By default, syncing requires a virtual environment. An path to an
@@ -1085,6 +1808,17 @@ pub struct PipSyncArgs {
     )]
     pub python: Option<String>,
 
+    /// The path to a Python executable whose environment should be synced.
+    ///
+    /// Unlike `--python`, the executable is used as-is, without applying uv's discovery rules:
+    /// it need not belong to a virtual environment, and `--system` is not required. This is
+    /// useful for syncing environments that are managed outside of uv (e.g., in a container image
+    /// build).
+    ///
+    /// Takes precedence over `--python` and `--system`.
+    #[arg(long, value_name = "PATH", help_heading = "Python options")]
+    pub python_executable: Option<PathBuf>,
+
     /// Install packages into the system Python environment.
     ///
     /// By default, uv installs into the virtual environment in the current working directory or
@@ -1218,6 +1952,89 @@ pub struct PipSyncArgs {
     #[arg(long)]
     pub python_platform: Option<TargetTriple>,
 
+    /// The architecture policy to apply when selecting wheels on macOS.
+    ///
+    /// By default, uv prefers wheels built for the interpreter's architecture, falling back to
+    /// `universal2` wheels. Use `prefer-universal2` or `require-universal2` to favor (or require)
+    /// `universal2` wheels, or `x86_64` to select `x86_64` wheels for an interpreter running under
+    /// Rosetta 2.
+    ///
+    /// uv will error if the interpreter's architecture is inconsistent with the policy, as the
+    /// selected wheels would otherwise fail at import time. When targeting an ARM-based macOS
+    /// platform with `--python-platform`, `x86_64` resolves for `x86_64-apple-darwin` instead. Has
+    /// no effect on other platforms.
+    #[arg(long, value_enum)]
+    pub macos_arch: Option<MacosArch>,
+
+    /// Validate that the selected wheels are compatible with the current platform before
+    /// modifying the environment.
+    ///
+    /// Checks each wheel's tags against the host interpreter, including the glibc or musl version
+    /// required by `manylinux` and `musllinux` wheels, and the minimum macOS version required by
+    /// `macosx` wheels, and the CPU features required by hardware-variant wheels (as detected on
+    /// the host, regardless of `UV_CPU_FEATURES`). Incompatible wheels typically fail at import
+    /// time, rather than at install time; this is most relevant when `--python-platform` is
+    /// provided.
+    #[arg(long, value_enum)]
+    pub abi_check: Option<AbiCheck>,
+
+    /// Detect distributions that provide the same files before modifying the environment.
+    ///
+    /// When multiple distributions provide the same file (e.g., a fork that ships the same
+    /// top-level module as the original package), the file that's left in the environment depends
+    /// on the order of installation. With `warn` or `error`, the conflicting files and the
+    /// packages that provide them are reported before any files are written.
+    #[arg(long, value_enum)]
+    pub file_conflicts: Option<FileConflicts>,
+
+    /// Install the given packages lazily, deferring the installation of their modules until
+    /// they're first imported.
+    ///
+    /// The package metadata and entry points are installed immediately, while the package
+    /// contents are materialized from the cache on first import. This can significantly reduce
+    /// install times for large packages (like `torch`) in ephemeral environments, in which much of
+    /// the package may never be imported. In both cases, the files are linked with the configured
+    /// `--link-mode`.
+    ///
+    /// As with `--link-mode=symlink`, clearing the cache will break lazily installed packages that
+    /// have not yet been imported.
+    #[arg(long, value_name = "PACKAGE")]
+    pub lazy_install: Vec<PackageName>,
+
+    /// Exclude the files that match the given glob pattern from the installed packages, e.g.,
+    /// `**/tests/**`.
+    ///
+    /// Patterns are matched against each file's path relative to `site-packages`, as listed in the
+    /// package's `RECORD`; `*` doesn't match across directories, while `**` does. Matching files
+    /// are removed after installation and omitted from the `RECORD`, such that the package can
+    /// still be uninstalled cleanly. The package metadata (e.g., `METADATA` and `RECORD`) and any
+    /// files installed outside of `site-packages` (e.g., scripts) are never excluded.
+    ///
+    /// This can be used to shrink environments that are deployed as-is (e.g., container layers or
+    /// serverless bundles) by omitting test suites, translations, or bytecode shipped in wheels.
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude_package_data: Vec<ExcludePattern>,
+
+    /// The maximum total size of the distributions to download, e.g., `500MB` or `2GiB`.
+    ///
+    /// The size of each distribution is determined from the index before any downloads begin; if
+    /// the total exceeds the limit, the installation is aborted and the largest distributions are
+    /// reported. Distributions that are already cached don't count towards the limit, nor do those
+    /// whose size isn't reported by the index.
+    #[arg(long, env = "UV_MAX_DOWNLOAD_SIZE", value_parser = parse_size)]
+    pub max_download_size: Option<u64>,
+
+    /// Verify that the installed packages can be imported.
+    ///
+    /// After installation, each top-level module provided by the newly installed packages is
+    /// imported in a separate Python process. Any failures (e.g., due to missing shared libraries
+    /// or ABI mismatches in native extensions) are reported immediately, rather than surfacing at
+    /// runtime.
+    #[arg(long)]
+    pub verify_imports: bool,
+
     /// Validate the Python environment after completing the installation, to detect and with
     /// missing dependencies or other issues.
     #[arg(long, overrides_with("no_strict"))]
@@ -1231,6 +2048,21 @@ pub struct PipSyncArgs {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// The level of detail with which to summarize the changes to the environment.
+    ///
+    /// By default, every package that was added or removed is listed (`detailed`). With
+    /// `minimal`, the changes are grouped into added, changed, and removed packages, with direct
+    /// dependencies listed ahead of transitive dependencies, and each group is truncated to a
+    /// handful of packages, unless `--verbose` is provided. With `none`, the changes aren't
+    /// listed.
+    #[arg(long, value_enum, default_value_t = InstallSummary::default())]
+    pub summary: InstallSummary,
+
+    /// Report the download size, installed size, and file count of each installed package, along
+    /// with a total.
+    #[arg(long)]
+    pub show_sizes: bool,
+
     #[command(flatten)]
     pub compat_args: compat::PipSyncCompatArgs,
 }
@@ -1252,10 +2084,18 @@ pub struct PipInstallArgs {
     #[arg(long, short, group = "sources", value_parser = parse_file_path)]
     pub requirement: Vec<PathBuf>,
 
-    /// Install the editable package based on the provided local file path.
+    /// Install the editable package based on the provided local file path or Git URL.
     #[arg(long, short, group = "sources")]
     pub editable: Vec<String>,
 
+    /// The directory in which to check out editable Git requirements (e.g., `-e git+https://...`).
+    ///
+    /// Defaults to `src` within the virtual environment, or within the current directory if
+    /// installing into a system Python environment. Existing checkouts are reused as-is, unless
+    /// the package is upgraded (e.g., with `--upgrade`).
+    #[arg(long, env = "UV_SRC")]
+    pub src: Option<PathBuf>,
+
     /// Constrain versions using the given requirements files.
     ///
     /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
@@ -1302,6 +2142,15 @@ pub struct PipInstallArgs {
     #[arg(long, overrides_with("all_extras"), hide = true)]
     pub no_all_extras: bool,
 
+    /// Include dependencies from the given dependency group; may be provided more than once.
+    ///
+    /// Dependency groups are defined via the `[dependency-groups]` table of a `pyproject.toml`,
+    /// as specified in PEP 735.
+    ///
+    /// Only applies to `pyproject.toml` sources.
+    #[arg(long)]
+    pub group: Vec<GroupName>,
+
     #[command(flatten)]
     pub installer: ResolverInstallerArgs,
 
@@ -1310,6 +2159,9 @@ pub struct PipInstallArgs {
 
     /// Ignore package dependencies, instead only installing those packages explicitly listed
     /// on the command line or in the requirements files.
+    ///
+    /// Any dependencies of the installed packages that are missing from the environment can be
+    /// identified with `uv pip check`.
     #[arg(long, overrides_with("deps"))]
     pub no_deps: bool,
 
@@ -1355,6 +2207,22 @@ pub struct PipInstallArgs {
     #[arg(long, overrides_with("verify_hashes"), hide = true)]
     pub no_verify_hashes: bool,
 
+    /// The hash-checking policy for the packages served by an index, in the form
+    /// `<INDEX_URL>=<POLICY>` (e.g., `https://pypi.internal/simple=verify`).
+    ///
+    /// With a `require` policy, every package served by the index must have a hash, as with
+    /// `--require-hashes`. With a `verify` policy, hashes are validated if present, but packages
+    /// without hashes are allowed (and needn't be pinned), as with `--verify-hashes`. Packages
+    /// served by any other index, or by a direct URL or local path, are checked according to
+    /// `--require-hashes` and `--verify-hashes`.
+    ///
+    /// For example, `--require-hashes --index-hash-policy https://pypi.internal/simple=verify`
+    /// requires hashes for every package, except those served by the internal mirror.
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_name = "INDEX_URL=POLICY", value_parser = parse_index_hash_policy)]
+    pub index_hash_policy: Option<Vec<(IndexUrl, IndexHashPolicy)>>,
+
     /// The Python interpreter into which packages should be installed.
     ///
     /// By default, installation requires a virtual environment. An path to an
@@ -1485,11 +2353,38 @@ pub struct PipInstallArgs {
     #[arg(long)]
     pub python_version: Option<PythonVersion>,
 
-    /// The platform for which requirements should be installed.
+    /// Ignore the `Requires-Python` metadata of the candidate distributions.
     ///
-    /// Represented as a "target triple", a string that describes the target platform in terms of
-    /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
-    /// `aaarch64-apple-darwin`.
+    /// By default, uv will only select distributions whose `Requires-Python` is satisfied by the
+    /// current interpreter. With `--ignore-requires-python`, distributions are selected regardless
+    /// of the Python versions they claim to support, which may result in packages that can't be
+    /// imported.
+    ///
+    /// Prefer `--requires-python-override`, which limits the scope of the override.
+    #[arg(long, conflicts_with = "requires_python_override")]
+    pub ignore_requires_python: bool,
+
+    /// Check the `Requires-Python` metadata of the candidate distributions against the given
+    /// version specifier, rather than the version of the current interpreter (e.g., `>=3.12`).
+    ///
+    /// A distribution is considered compatible if its `Requires-Python` is satisfied by at least
+    /// one Python version in the given range.
+    ///
+    /// Useful for installing packages that support the current interpreter, but haven't yet
+    /// updated their `Requires-Python` metadata (e.g., a package that declares
+    /// `Requires-Python: <3.13`, but works on Python 3.13).
+    ///
+    /// Unlike `--python-version`, only the `Requires-Python` check is affected: environment
+    /// markers are still evaluated against the current interpreter, and wheels are still selected
+    /// for its platform tags.
+    #[arg(long, value_name = "SPECIFIER", conflicts_with = "python_version")]
+    pub requires_python_override: Option<VersionSpecifiers>,
+
+    /// The platform for which requirements should be installed.
+    ///
+    /// Represented as a "target triple", a string that describes the target platform in terms of
+    /// its CPU, vendor, and operating system name, like `x86_64-unknown-linux-gnu` or
+    /// `aaarch64-apple-darwin`.
     ///
     /// WARNING: When specified, uv will select wheels that are compatible with the _target_
     /// platform; as a result, the installed distributions may not be compatible with the _current_
@@ -1499,6 +2394,98 @@ pub struct PipInstallArgs {
     #[arg(long)]
     pub python_platform: Option<TargetTriple>,
 
+    /// The architecture policy to apply when selecting wheels on macOS.
+    ///
+    /// By default, uv prefers wheels built for the interpreter's architecture, falling back to
+    /// `universal2` wheels. Use `prefer-universal2` or `require-universal2` to favor (or require)
+    /// `universal2` wheels, or `x86_64` to select `x86_64` wheels for an interpreter running under
+    /// Rosetta 2.
+    ///
+    /// uv will error if the interpreter's architecture is inconsistent with the policy, as the
+    /// selected wheels would otherwise fail at import time. When targeting an ARM-based macOS
+    /// platform with `--python-platform`, `x86_64` resolves for `x86_64-apple-darwin` instead. Has
+    /// no effect on other platforms.
+    #[arg(long, value_enum)]
+    pub macos_arch: Option<MacosArch>,
+
+    /// Validate that the selected wheels are compatible with the current platform before
+    /// modifying the environment.
+    ///
+    /// Checks each wheel's tags against the host interpreter, including the glibc or musl version
+    /// required by `manylinux` and `musllinux` wheels, and the minimum macOS version required by
+    /// `macosx` wheels, and the CPU features required by hardware-variant wheels (as detected on
+    /// the host, regardless of `UV_CPU_FEATURES`). Incompatible wheels typically fail at import
+    /// time, rather than at install time; this is most relevant when `--python-platform` is
+    /// provided.
+    #[arg(long, value_enum)]
+    pub abi_check: Option<AbiCheck>,
+
+    /// Detect distributions that provide the same files before modifying the environment.
+    ///
+    /// When multiple distributions provide the same file (e.g., a fork that ships the same
+    /// top-level module as the original package), the file that's left in the environment depends
+    /// on the order of installation. With `warn` or `error`, the conflicting files and the
+    /// packages that provide them are reported before any files are written.
+    #[arg(long, value_enum)]
+    pub file_conflicts: Option<FileConflicts>,
+
+    /// Install the given packages lazily, deferring the installation of their modules until
+    /// they're first imported.
+    ///
+    /// The package metadata and entry points are installed immediately, while the package
+    /// contents are materialized from the cache on first import. This can significantly reduce
+    /// install times for large packages (like `torch`) in ephemeral environments, in which much of
+    /// the package may never be imported. In both cases, the files are linked with the configured
+    /// `--link-mode`.
+    ///
+    /// As with `--link-mode=symlink`, clearing the cache will break lazily installed packages that
+    /// have not yet been imported.
+    #[arg(long, value_name = "PACKAGE")]
+    pub lazy_install: Vec<PackageName>,
+
+    /// Exclude the files that match the given glob pattern from the installed packages, e.g.,
+    /// `**/tests/**`.
+    ///
+    /// Patterns are matched against each file's path relative to `site-packages`, as listed in the
+    /// package's `RECORD`; `*` doesn't match across directories, while `**` does. Matching files
+    /// are removed after installation and omitted from the `RECORD`, such that the package can
+    /// still be uninstalled cleanly. The package metadata (e.g., `METADATA` and `RECORD`) and any
+    /// files installed outside of `site-packages` (e.g., scripts) are never excluded.
+    ///
+    /// This can be used to shrink environments that are deployed as-is (e.g., container layers or
+    /// serverless bundles) by omitting test suites, translations, or bytecode shipped in wheels.
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude_package_data: Vec<ExcludePattern>,
+
+    /// The maximum total size of the distributions to download, e.g., `500MB` or `2GiB`.
+    ///
+    /// The size of each distribution is determined from the index before any downloads begin; if
+    /// the total exceeds the limit, the installation is aborted and the largest distributions are
+    /// reported. Distributions that are already cached don't count towards the limit, nor do those
+    /// whose size isn't reported by the index.
+    #[arg(long, env = "UV_MAX_DOWNLOAD_SIZE", value_parser = parse_size)]
+    pub max_download_size: Option<u64>,
+
+    /// Abort the installation if it would downgrade any of the packages that are already
+    /// installed.
+    ///
+    /// The packages that would be downgraded are reported before any changes are made to the
+    /// environment. Useful for protecting long-lived, shared environments from regressions caused
+    /// by installing packages whose requirements conflict with those already present.
+    #[arg(long)]
+    pub break_on_downgrade: bool,
+
+    /// Verify that the installed packages can be imported.
+    ///
+    /// After installation, each top-level module provided by the newly installed packages is
+    /// imported in a separate Python process. Any failures (e.g., due to missing shared libraries
+    /// or ABI mismatches in native extensions) are reported immediately, rather than surfacing at
+    /// runtime.
+    #[arg(long)]
+    pub verify_imports: bool,
+
     /// Validate the Python environment after completing the installation, to detect and with
     /// missing dependencies or other issues.
     #[arg(long, overrides_with("no_strict"))]
@@ -1507,11 +2494,36 @@ pub struct PipInstallArgs {
     #[arg(long, overrides_with("strict"), hide = true)]
     pub no_strict: bool,
 
+    /// Fail if a requested extra isn't provided by the resolved version of a package.
+    ///
+    /// By default, requesting an extra that doesn't exist (e.g., `black[tensorboard]`) emits a
+    /// warning naming the package, its version, and the extras it provides.
+    #[arg(long, overrides_with("no_strict_extras"))]
+    pub strict_extras: bool,
+
+    #[arg(long, overrides_with("strict_extras"), hide = true)]
+    pub no_strict_extras: bool,
+
     /// Perform a dry run, i.e., don't actually install anything but resolve the dependencies and
     /// print the resulting plan.
     #[arg(long)]
     pub dry_run: bool,
 
+    /// The level of detail with which to summarize the changes to the environment.
+    ///
+    /// By default, every package that was added or removed is listed (`detailed`). With
+    /// `minimal`, the changes are grouped into added, changed, and removed packages, with direct
+    /// dependencies listed ahead of transitive dependencies, and each group is truncated to a
+    /// handful of packages, unless `--verbose` is provided. With `none`, the changes aren't
+    /// listed.
+    #[arg(long, value_enum, default_value_t = InstallSummary::default())]
+    pub summary: InstallSummary,
+
+    /// Report the download size, installed size, and file count of each installed package, along
+    /// with a total.
+    #[arg(long)]
+    pub show_sizes: bool,
+
     #[command(flatten)]
     pub compat_args: compat::PipInstallCompatArgs,
 }
@@ -1651,6 +2663,9 @@ pub struct PipFreezeArgs {
     #[arg(long, overrides_with("system"), hide = true)]
     pub no_system: bool,
 
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
     #[command(flatten)]
     pub compat_args: compat::PipGlobalCompatArgs,
 }
@@ -1674,6 +2689,13 @@ pub struct PipListArgs {
     #[arg(long, value_enum, default_value_t = ListFormat::default())]
     pub format: ListFormat,
 
+    /// Include the installed size and file count of each package.
+    ///
+    /// The sizes are computed from the files listed in each package's `RECORD`. Packages without
+    /// a `RECORD` (e.g., legacy `.egg-info` installations) are reported without a size.
+    #[arg(long)]
+    pub sizes: bool,
+
     /// Validate the Python environment, to detect packages with missing dependencies and other
     /// issues.
     #[arg(long, overrides_with("no_strict"))]
@@ -1715,6 +2737,9 @@ pub struct PipListArgs {
     #[arg(long, overrides_with("system"), hide = true)]
     pub no_system: bool,
 
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
     #[command(flatten)]
     pub compat_args: compat::PipListCompatArgs,
 }
@@ -1754,6 +2779,9 @@ pub struct PipCheckArgs {
 
     #[arg(long, overrides_with("system"), hide = true)]
     pub no_system: bool,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
 }
 
 #[derive(Args)]
@@ -1803,6 +2831,9 @@ pub struct PipShowArgs {
     #[arg(long, overrides_with("system"), hide = true)]
     pub no_system: bool,
 
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
     #[command(flatten)]
     pub compat_args: compat::PipGlobalCompatArgs,
 }
@@ -1858,6 +2889,9 @@ pub struct PipTreeArgs {
     #[arg(long, overrides_with("system"))]
     pub no_system: bool,
 
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
     #[command(flatten)]
     pub compat_args: compat::PipGlobalCompatArgs,
 }
@@ -1865,6 +2899,9 @@ pub struct PipTreeArgs {
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct VenvArgs {
+    #[command(subcommand)]
+    pub command: Option<VenvCommand>,
+
     /// The Python interpreter to use for the virtual environment.
     ///
     /// During virtual environment creation, uv will not look for Python
@@ -1919,6 +2956,9 @@ pub struct VenvArgs {
     pub allow_existing: bool,
 
     /// The path to the virtual environment to create.
+    ///
+    /// To create a virtual environment at a path that matches a subcommand
+    /// (e.g., `list`), use a relative path instead (e.g., `./list`).
     #[arg(default_value = ".venv")]
     pub name: PathBuf,
 
@@ -1997,6 +3037,9 @@ pub struct VenvArgs {
     #[arg(long, value_enum, env = "UV_LINK_MODE")]
     pub link_mode: Option<install_wheel_rs::linker::LinkMode>,
 
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
     #[command(flatten)]
     pub compat_args: compat::VenvCompatArgs,
 }
@@ -2066,6 +3109,16 @@ pub struct InitArgs {
     #[arg(long, alias = "no_project")]
     pub no_workspace: bool,
 
+    /// The build backend to use for the project.
+    ///
+    /// Determines the `[build-system]` table in the `pyproject.toml`, along with the layout of the
+    /// project. The `maturin` and `scikit-build-core` backends include the scaffolding for an
+    /// extension module, implemented in Rust and C++ respectively.
+    ///
+    /// Defaults to `hatchling`.
+    #[arg(long, value_enum, conflicts_with = "virtual")]
+    pub build_backend: Option<BuildBackend>,
+
     /// The Python interpreter to use to determine the minimum supported Python version.
     ///
     /// See `uv help python` to view supported request formats.
@@ -2156,6 +3209,25 @@ pub struct RunArgs {
     #[arg(long)]
     pub isolated: bool,
 
+    /// The directories to add to `sys.path`, overriding the `module-path`
+    /// setting of the project or script.
+    ///
+    /// When provided, the implicit `sys.path` entry for the directory of the
+    /// script being run (or the current directory) is disabled, and only the
+    /// given directories are added: `project-root`, `src`, or `members` (the
+    /// root of each workspace member).
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_enum, conflicts_with = "no_module_path")]
+    pub module_path: Option<Vec<ModulePath>>,
+
+    /// Avoid adding any directories to `sys.path`, including the implicit entry
+    /// for the directory of the script being run (or the current directory).
+    ///
+    /// Imports then resolve as they would for the installed package.
+    #[arg(long, conflicts_with = "module_path")]
+    pub no_module_path: bool,
+
     /// Assert that the `uv.lock` will remain unchanged.
     ///
     /// Requires that the lockfile is up-to-date. If the lockfile is missing or
@@ -2200,11 +3272,31 @@ pub struct RunArgs {
     #[arg(long, alias = "no_workspace", conflicts_with = "package")]
     pub no_project: bool,
 
+    /// Run the command in a named environment, as declared in the project's
+    /// `tool.uv.envs` setting.
+    ///
+    /// Each named environment is synced into its own virtual environment
+    /// in the project's `.venvs` directory (e.g., `.venvs/docs`), with the extras,
+    /// development dependencies, and Python version declared for it.
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with("isolated"),
+        conflicts_with("no_project"),
+        conflicts_with("all_pythons")
+    )]
+    pub env: Option<String>,
+
     /// The Python interpreter to use for the run environment.
     ///
     /// If the interpreter request is satisfied by a discovered environment, the
     /// environment will be used.
     ///
+    /// Within a project, if the project environment (`.venv`) doesn't satisfy
+    /// the request, it's left untouched; instead, the command runs in a
+    /// secondary environment for the requested interpreter (e.g.,
+    /// `.venv-cpython-3.9`), which is reused by subsequent runs.
+    ///
     /// See `uv help python` to view supported request formats.
     #[arg(
         long,
@@ -2215,6 +3307,23 @@ pub struct RunArgs {
     )]
     pub python: Option<String>,
 
+    /// Run the command against each of the Python versions declared in the
+    /// project's `tool.uv.test-pythons` setting.
+    ///
+    /// The command is run once per version, in order, in a dedicated
+    /// environment for each interpreter. Any versions that aren't installed are
+    /// downloaded as needed. Once every version has run, a summary of the
+    /// results is displayed; the command fails if any version fails.
+    #[arg(
+        long,
+        conflicts_with("python"),
+        conflicts_with("isolated"),
+        conflicts_with("no_project"),
+        verbatim_doc_comment,
+        help_heading = "Python options"
+    )]
+    pub all_pythons: bool,
+
     /// Whether to show resolver and installer output from any environment modifications.
     ///
     /// By default, environment modifications are omitted, but enabled under `--verbose`.
@@ -2246,6 +3355,14 @@ pub struct SyncArgs {
     #[arg(long, overrides_with("dev"))]
     pub no_dev: bool,
 
+    /// Include dependencies from the given dependency group; may be provided more than once.
+    ///
+    /// Dependency groups are defined via the `[dependency-groups]` table of a `pyproject.toml`,
+    /// as specified in PEP 735. A group may include the dependencies of other groups with
+    /// `{ include-group = "<name>" }`.
+    #[arg(long)]
+    pub group: Vec<GroupName>,
+
     /// Do not remove extraneous packages.
     ///
     /// When enabled, uv will make the minimum necessary changes to satisfy the
@@ -2257,6 +3374,66 @@ pub struct SyncArgs {
     #[arg(long)]
     pub no_clean: bool,
 
+    /// Verify that the installed packages can be imported.
+    ///
+    /// After installation, each top-level module provided by the newly installed packages is
+    /// imported in a separate Python process. Any failures (e.g., due to missing shared libraries
+    /// or ABI mismatches in native extensions) are reported immediately, rather than surfacing at
+    /// runtime.
+    #[arg(long)]
+    pub verify_imports: bool,
+
+    /// Rebuild and reinstall all editable packages, including the project itself.
+    ///
+    /// By default, editables are only rebuilt when their `pyproject.toml` has changed since they
+    /// were installed, or when their installed dependencies or entry points no longer match those
+    /// declared in the `pyproject.toml`. Use this flag to pick up changes that can't be detected
+    /// statically (e.g., to dynamic metadata), without reinstalling every package.
+    #[arg(long)]
+    pub reinstall_editables: bool,
+
+    /// The level of detail with which to summarize the changes to the environment.
+    ///
+    /// By default, every package that was added or removed is listed (`detailed`). With
+    /// `minimal`, the changes are grouped into added, changed, and removed packages, with direct
+    /// dependencies listed ahead of transitive dependencies, and each group is truncated to a
+    /// handful of packages, unless `--verbose` is provided. With `none`, the changes aren't
+    /// listed.
+    #[arg(long, value_enum, default_value_t = InstallSummary::default())]
+    pub summary: InstallSummary,
+
+    /// Report the download size, installed size, and file count of each installed package, along
+    /// with a total.
+    #[arg(long)]
+    pub show_sizes: bool,
+
+    /// Mark the environment's `site-packages` directory as read-only after syncing.
+    ///
+    /// Prevents other tools (e.g., a subprocess running `pip install`) from modifying the
+    /// environment. uv restores write access for the duration of any subsequent operation that
+    /// modifies the environment, such as `uv run` or `uv pip install`, and marks it read-only
+    /// again once it completes. A subsequent `uv sync` without this flag leaves the environment
+    /// writable.
+    #[arg(long)]
+    pub read_only_env: bool,
+
+    /// Generate the given editor configuration for the project environment,
+    /// overriding the `editor-config` setting of the workspace.
+    ///
+    /// After syncing, the paths to the environment and its interpreter are
+    /// written to `.uv/env.json` in the workspace root and the root of each
+    /// workspace member. With `vscode` or `pyright`, `.vscode/settings.json` or
+    /// `pyrightconfig.json` are updated in each of those directories, too.
+    ///
+    /// May be provided multiple times.
+    #[arg(long, value_enum, conflicts_with = "no_editor_config")]
+    pub editor_config: Option<Vec<EditorConfig>>,
+
+    /// Do not generate any editor configuration, ignoring the `editor-config`
+    /// setting of the workspace.
+    #[arg(long)]
+    pub no_editor_config: bool,
+
     /// Assert that the `uv.lock` will remain unchanged.
     ///
     /// Requires that the lockfile is up-to-date. If the lockfile is missing or
@@ -2293,6 +3470,27 @@ pub struct SyncArgs {
     #[arg(long)]
     pub package: Option<PackageName>,
 
+    /// Sync only the workspace members affected by changes to the given paths.
+    ///
+    /// Each path is attributed to the workspace member that contains it. The
+    /// affected members are those members, along with any members that depend
+    /// on them, directly or transitively. Changes to the lockfile, or to paths
+    /// outside of every member, affect all members.
+    ///
+    /// When syncing exactly, installed packages that are required by the
+    /// unaffected members are retained.
+    #[arg(long, num_args = 1.., conflicts_with = "package")]
+    pub only_affected_by: Option<Vec<PathBuf>>,
+
+    /// Sync a named environment, as declared in the project's `tool.uv.envs`
+    /// setting, rather than the project environment.
+    ///
+    /// Each named environment is synced into its own virtual environment
+    /// in the project's `.venvs` directory (e.g., `.venvs/docs`), with the extras,
+    /// development dependencies, and Python version declared for it.
+    #[arg(long, value_name = "NAME")]
+    pub env: Option<String>,
+
     /// The Python interpreter to use for the project environment.
     ///
     /// By default, the first interpreter that meets the project's
@@ -2314,6 +3512,57 @@ pub struct SyncArgs {
     pub python: Option<String>,
 }
 
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct TestArgs {
+    /// The command to run, in lieu of the project's `tool.uv.test-command`.
+    #[command(subcommand)]
+    pub command: Option<ExternalCommand>,
+
+    /// Include dependencies from the given dependency group, in addition to those declared in
+    /// `tool.uv.test-groups`; may be provided more than once.
+    #[arg(long)]
+    pub group: Vec<GroupName>,
+
+    /// Stop after the first Python version that fails.
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Assert that the `uv.lock` will remain unchanged.
+    ///
+    /// Requires that the lockfile is up-to-date. If the lockfile is missing or
+    /// needs to be updated, uv will exit with an error.
+    #[arg(long, conflicts_with = "frozen")]
+    pub locked: bool,
+
+    /// Test without updating the `uv.lock` file.
+    ///
+    /// Instead of checking if the lockfile is up-to-date, uses the versions in
+    /// the lockfile as the source of truth. If the lockfile is missing, uv will
+    /// exit with an error.
+    #[arg(long, conflicts_with = "locked")]
+    pub frozen: bool,
+
+    #[command(flatten)]
+    pub installer: ResolverInstallerArgs,
+
+    #[command(flatten)]
+    pub build: BuildArgs,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
+    /// The Python versions to test against, in lieu of the project's
+    /// `tool.uv.test-pythons`; may be provided more than once.
+    ///
+    /// If neither is provided, the tests are run once, in the project
+    /// environment.
+    ///
+    /// See `uv help python` to view supported request formats.
+    #[arg(long, short, verbatim_doc_comment, help_heading = "Python options")]
+    pub python: Vec<String>,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct LockArgs {
@@ -2328,6 +3577,100 @@ pub struct LockArgs {
     #[arg(long, conflicts_with = "locked")]
     pub frozen: bool,
 
+    /// Verify that the resolution is deterministic, by resolving the project from scratch the
+    /// given number of times (default: 3) and comparing the results.
+    ///
+    /// Any existing lockfile is ignored and left unchanged, such that no resolution is guided by
+    /// the previously locked versions. If any resolution differs from the first, the differing
+    /// packages are reported and uv exits with an error.
+    #[arg(
+        long,
+        value_name = "RUNS",
+        num_args = 0..=1,
+        default_missing_value = "3",
+        value_parser = clap::value_parser!(u32).range(2..),
+        conflicts_with_all = ["locked", "frozen"]
+    )]
+    pub verify_determinism: Option<u32>,
+
+    /// When verifying determinism, shuffle the order in which the resolver considers candidate
+    /// packages between runs.
+    ///
+    /// By default, the resolver considers packages of equal priority in the order in which they're
+    /// discovered. Shuffling that order detects resolutions that depend on it, e.g., on the order
+    /// in which the requirements are declared or in which the dependencies are listed.
+    #[arg(long, requires = "verify_determinism")]
+    pub shuffle: bool,
+
+    /// Prune the artifacts and dependencies that are specific to the given platform from the
+    /// lockfile.
+    ///
+    /// Wheels that are only compatible with the platform are omitted, as are any dependencies that
+    /// are only required on the platform. Syncing the project on a pruned platform fails with an
+    /// error.
+    ///
+    /// The pruned platforms are recorded in the lockfile and retained when it's updated by other
+    /// commands (e.g., `uv sync`), until `--prune-platform` or `--no-prune-platform` is provided.
+    #[arg(
+        long,
+        value_name = "PLATFORM",
+        conflicts_with_all = ["frozen", "verify_determinism"]
+    )]
+    pub prune_platform: Vec<SysPlatform>,
+
+    /// Include all platforms in the lockfile, removing any previously pruned platforms.
+    #[arg(
+        long,
+        conflicts_with_all = ["frozen", "verify_determinism", "prune_platform"]
+    )]
+    pub no_prune_platform: bool,
+
+    /// Suggest a `requires-python` lower bound that would permit the newest versions of any
+    /// packages that were excluded due to the project's supported Python range.
+    ///
+    /// When a newer version of a package is excluded because it requires a newer Python version
+    /// than the project supports, uv notes the exclusion. With this flag, uv also suggests the
+    /// lowest `requires-python` bound that would permit the newest versions of all such packages.
+    #[arg(long, conflicts_with_all = ["frozen", "verify_determinism"])]
+    pub suggest_requires_python: bool,
+
+    /// Revert `uv.lock` to its most recent backup, without resolving the project.
+    ///
+    /// Backups are only created when `tool.uv.lock-backups` is set. The current `uv.lock` is
+    /// itself backed up before it's replaced, such that a restore can be reverted by running
+    /// `uv lock --restore` again.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "locked",
+            "frozen",
+            "verify_determinism",
+            "prune_platform",
+            "no_prune_platform",
+            "suggest_requires_python",
+        ]
+    )]
+    pub restore: bool,
+
+    /// Lock the dependencies of a PEP 723 script, rather than the project.
+    ///
+    /// If a directory is provided, all scripts in the directory with inline metadata are locked.
+    /// Each script is resolved separately, and the resolutions are stored in a single
+    /// `.uv-scripts.lock` in the script's directory, which is respected by `uv run`. When locking
+    /// a directory, entries for scripts that no longer exist are removed.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = [
+            "verify_determinism",
+            "prune_platform",
+            "no_prune_platform",
+            "suggest_requires_python",
+            "restore",
+        ]
+    )]
+    pub script: Option<PathBuf>,
+
     #[command(flatten)]
     pub resolver: ResolverArgs,
 
@@ -2359,10 +3702,59 @@ pub struct LockArgs {
 
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
+#[command(group = clap::ArgGroup::new("sources").required(true).multiple(true))]
+#[command(group = clap::ArgGroup::new("requirements_files").multiple(true))]
 pub struct AddArgs {
     /// The packages to add, as PEP 508 requirements (e.g., `ruff==0.5.0`).
-    #[arg(required = true)]
-    pub requirements: Vec<String>,
+    #[arg(group = "sources")]
+    pub packages: Vec<String>,
+
+    /// Add all packages listed in the given `requirements.txt` files.
+    ///
+    /// The packages are added to `project.dependencies`, or to the group selected by `--dev` or
+    /// `--optional`. Any constraints referenced by the files (via `-c`) are added to
+    /// `tool.uv.constraint-dependencies` in the workspace root.
+    #[arg(
+        long,
+        short,
+        group = "sources",
+        group = "requirements_files",
+        value_parser = parse_file_path
+    )]
+    pub requirements: Vec<PathBuf>,
+
+    /// Add all packages listed in the given `requirements.txt` files as development dependencies.
+    ///
+    /// Can be combined with `--requirements` and `--optional-requirements` to import the
+    /// requirements files of a project into their respective groups in a single invocation.
+    #[arg(
+        long,
+        group = "sources",
+        group = "requirements_files",
+        value_parser = parse_file_path
+    )]
+    pub dev_requirements: Vec<PathBuf>,
+
+    /// Add all packages listed in the given `requirements.txt` file to an optional dependency
+    /// group, in the form `<EXTRA>=<PATH>` (e.g., `docs=requirements-docs.txt`).
+    ///
+    /// May be provided more than once.
+    #[arg(
+        long,
+        group = "sources",
+        group = "requirements_files",
+        value_parser = parse_optional_requirements
+    )]
+    pub optional_requirements: Vec<OptionalRequirements>,
+
+    /// Remove any dependencies that aren't listed in the requirements files.
+    ///
+    /// With `--sync-manifest`, each targeted dependency group (i.e., `project.dependencies`,
+    /// `tool.uv.dev-dependencies`, or an optional dependency group) is made to match the
+    /// requirements files imported into it exactly, such that repeated imports keep the
+    /// `pyproject.toml` in sync.
+    #[arg(long, requires = "requirements_files")]
+    pub sync_manifest: bool,
 
     /// Add the requirements as development dependencies.
     #[arg(long, conflicts_with("optional"))]
@@ -2399,6 +3791,22 @@ pub struct AddArgs {
     )]
     pub raw_sources: bool,
 
+    /// Write the requirements to the `pyproject.toml` exactly as provided.
+    ///
+    /// By default, uv normalizes the requirement (e.g., `Flask >= 3` is written as `flask>=3`) and
+    /// adds a lower bound to requirements without a version specifier. With `--raw`, the
+    /// requirement string is written verbatim. Implies `--raw-sources`.
+    #[arg(
+        long,
+        conflicts_with = "editable",
+        conflicts_with = "no_editable",
+        conflicts_with = "extra",
+        conflicts_with = "rev",
+        conflicts_with = "tag",
+        conflicts_with = "branch"
+    )]
+    pub raw: bool,
+
     /// Commit to use when adding a dependency from Git.
     #[arg(long, group = "git-ref", action = clap::ArgAction::Set)]
     pub rev: Option<String>,
@@ -2420,6 +3828,13 @@ pub struct AddArgs {
     #[arg(long)]
     pub extra: Option<Vec<ExtraName>>,
 
+    /// Apply this marker to all added packages (e.g., `sys_platform == 'win32'`).
+    ///
+    /// The marker is combined with any marker on the requirement itself, and written to the
+    /// `pyproject.toml` alongside the requirement.
+    #[arg(long, short, value_parser = MarkerTree::from_str, conflicts_with = "raw")]
+    pub marker: Option<MarkerTree>,
+
     /// Avoid syncing the virtual environment after re-locking the project.
     #[arg(long, conflicts_with = "frozen")]
     pub no_sync: bool,
@@ -2437,6 +3852,25 @@ pub struct AddArgs {
     #[arg(long, conflicts_with = "locked")]
     pub frozen: bool,
 
+    /// Suggest a `requires-python` lower bound that would permit the newest versions of any
+    /// packages that were excluded due to the project's supported Python range.
+    ///
+    /// When a newer version of a package is excluded because it requires a newer Python version
+    /// than the project supports, uv notes the exclusion. With this flag, uv also suggests the
+    /// lowest `requires-python` bound that would permit the newest versions of all such packages.
+    #[arg(long, conflicts_with = "frozen")]
+    pub suggest_requires_python: bool,
+
+    /// Resolve conflicts between the added dependencies and the project's `requires-python`.
+    ///
+    /// If an added dependency doesn't support all of the Python versions in the project's
+    /// `requires-python`, resolution fails by default. With `narrow`, uv instead raises the lower
+    /// bound of the project's `requires-python` to the lowest Python version that the dependency
+    /// supports; with `marker`, uv adds the dependency with a `python_full_version` marker, such
+    /// that it's only installed on the Python versions it supports.
+    #[arg(long, value_enum, conflicts_with_all = ["frozen", "locked"])]
+    pub fix_requires_python: Option<RequiresPythonFix>,
+
     #[command(flatten)]
     pub installer: ResolverInstallerArgs,
 
@@ -2446,6 +3880,14 @@ pub struct AddArgs {
     #[command(flatten)]
     pub refresh: RefreshArgs,
 
+    /// Check the names of any added packages that aren't already in the lockfile against a list of
+    /// popular packages, to detect typos (e.g., `reqeusts` for `requests`).
+    ///
+    /// With `warn` (the default), a warning is shown for names that are a single edit away from
+    /// that of a popular package; with `error`, the package isn't added.
+    #[arg(long, value_enum, env = "UV_TYPOSQUAT_CHECK")]
+    pub typosquat_check: Option<TyposquatCheck>,
+
     /// Add the dependency to a specific package in the workspace.
     #[arg(long, conflicts_with = "isolated")]
     pub package: Option<PackageName>,
@@ -2523,23 +3965,299 @@ pub struct RemoveArgs {
     pub python: Option<String>,
 }
 
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// The lockfile to compare from.
+    pub old: PathBuf,
+
+    /// The lockfile to compare to.
+    ///
+    /// If omitted, the lockfile is compared against the packages installed in
+    /// the current environment.
+    pub new: Option<PathBuf>,
+
+    /// The format in which to display the differences.
+    #[arg(long, value_enum, default_value_t = DiffFormat::default())]
+    pub format: DiffFormat,
+
+    /// The Python interpreter whose environment should be compared against,
+    /// if only one lockfile is provided.
+    ///
+    /// By default, uv compares against the packages in the active virtual
+    /// environment, or the `.venv` in the current directory or any parent
+    /// directory.
+    ///
+    /// See `uv help python` for details on Python discovery and supported
+    /// request formats.
+    #[arg(
+        long,
+        short,
+        env = "UV_PYTHON",
+        verbatim_doc_comment,
+        conflicts_with = "new",
+        help_heading = "Python options"
+    )]
+    pub python: Option<String>,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
-pub struct TreeArgs {
-    /// Show a platform-independent dependency tree.
+pub struct ProjectBuildArgs {
+    /// The directory of the project to build.
     ///
-    /// Shows resolved package versions for all Python versions and platforms,
-    /// rather than filtering to those that are relevant for the current
-    /// environment.
+    /// Defaults to the project in the current directory.
+    pub src: Option<PathBuf>,
+
+    /// Build every member of the workspace.
     ///
-    /// Multiple versions may be shown for a each package.
+    /// Members are built in dependency order, and their distributions are written to a shared
+    /// `dist` directory at the workspace root. Dependencies on other workspace members are pinned
+    /// to the exact versions that were built, such that the distributions can be published
+    /// together.
     #[arg(long)]
-    pub universal: bool,
-
-    #[command(flatten)]
-    pub tree: DisplayTreeArgs,
+    pub all: bool,
 
-    /// Assert that the `uv.lock` will remain unchanged.
+    /// Build a source distribution ("sdist") from the project.
+    ///
+    /// By default, both a source distribution and a wheel are built.
+    #[arg(long)]
+    pub sdist: bool,
+
+    /// Build a binary distribution ("wheel") from the project.
+    ///
+    /// By default, both a source distribution and a wheel are built.
+    #[arg(long)]
+    pub wheel: bool,
+
+    /// The directory to which the distributions should be written.
+    ///
+    /// Defaults to the `dist` directory within the project root, or within the workspace root when
+    /// building with `--all`.
+    #[arg(long, short)]
+    pub out_dir: Option<PathBuf>,
+
+    /// Build in an environment pinned to the build requirements in the
+    /// `uv.lock` file.
+    ///
+    /// Requires that the lockfile is up-to-date and contains the build
+    /// requirements of the project, as enabled by
+    /// `tool.uv.lock-build-requirements`. If the lockfile is missing or needs to
+    /// be updated, uv will exit with an error.
+    #[arg(long)]
+    pub locked: bool,
+
+    #[command(flatten)]
+    pub resolver: ResolverArgs,
+
+    #[command(flatten)]
+    pub build: BuildArgs,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
+    /// The Python interpreter to use for the build environment.
+    ///
+    /// By default, the first interpreter that meets the project's
+    /// `requires-python` constraint is used.
+    ///
+    /// See `uv help python` for details on Python discovery and supported
+    /// request formats.
+    #[arg(
+        long,
+        short,
+        env = "UV_PYTHON",
+        verbatim_doc_comment,
+        help_heading = "Python options"
+    )]
+    pub python: Option<String>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ExportArgs {
+    /// The format to export the dependencies in.
+    #[arg(long, value_enum)]
+    pub format: ExportFormat,
+
+    /// Include optional dependencies from the extra group name.
+    ///
+    /// May be provided more than once.
+    #[arg(long, conflicts_with = "all_extras", value_parser = extra_name_with_clap_error)]
+    pub extra: Option<Vec<ExtraName>>,
+
+    /// Include all optional dependencies.
+    #[arg(long, conflicts_with = "extra")]
+    pub all_extras: bool,
+
+    #[arg(long, overrides_with("all_extras"), hide = true)]
+    pub no_all_extras: bool,
+
+    /// Include development dependencies.
+    ///
+    /// By default, development dependencies are omitted from the export.
+    #[arg(long, overrides_with("no_dev"))]
+    pub dev: bool,
+
+    #[arg(long, overrides_with("dev"), hide = true)]
+    pub no_dev: bool,
+
+    /// Include dependencies from the given dependency group; may be provided more than once.
+    ///
+    /// Dependency groups are defined via the `[dependency-groups]` table of a `pyproject.toml`,
+    /// as specified in PEP 735. A group may include the dependencies of other groups with
+    /// `{ include-group = "<name>" }`.
+    #[arg(long)]
+    pub group: Vec<GroupName>,
+
+    /// Write the exported specification to the given file, rather than to
+    /// stdout.
+    #[arg(long, short)]
+    pub output_file: Option<PathBuf>,
+
+    /// Assert that the `uv.lock` will remain unchanged.
+    ///
+    /// Requires that the lockfile is up-to-date. If the lockfile is missing or
+    /// needs to be updated, uv will exit with an error.
+    #[arg(long, conflicts_with = "frozen")]
+    pub locked: bool,
+
+    /// Export without updating the `uv.lock` file.
+    ///
+    /// Instead of checking if the lockfile is up-to-date, uses the versions in
+    /// the lockfile as the source of truth. If the lockfile is missing, uv will
+    /// exit with an error.
+    #[arg(long, conflicts_with = "locked")]
+    pub frozen: bool,
+
+    #[command(flatten)]
+    pub resolver: ResolverArgs,
+
+    #[command(flatten)]
+    pub build: BuildArgs,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
+    /// The Python interpreter to export the dependencies for.
+    ///
+    /// The interpreter determines the markers and platform tags used to select
+    /// the dependencies and artifacts from the lockfile.
+    ///
+    /// See `uv help python` for details on Python discovery and supported
+    /// request formats.
+    #[arg(
+        long,
+        short,
+        env = "UV_PYTHON",
+        verbatim_doc_comment,
+        help_heading = "Python options"
+    )]
+    pub python: Option<String>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct VendorArgs {
+    /// Include optional dependencies from the extra group name.
+    ///
+    /// May be provided more than once.
+    #[arg(long, conflicts_with = "all_extras", value_parser = extra_name_with_clap_error)]
+    pub extra: Option<Vec<ExtraName>>,
+
+    /// Include all optional dependencies.
+    #[arg(long, conflicts_with = "extra")]
+    pub all_extras: bool,
+
+    #[arg(long, overrides_with("all_extras"), hide = true)]
+    pub no_all_extras: bool,
+
+    /// Include development dependencies.
+    ///
+    /// By default, development dependencies are omitted from the vendor
+    /// directory.
+    #[arg(long, overrides_with("no_dev"))]
+    pub dev: bool,
+
+    #[arg(long, overrides_with("dev"), hide = true)]
+    pub no_dev: bool,
+
+    /// The directory into which the dependencies should be unpacked, relative
+    /// to the project root.
+    ///
+    /// Any existing contents of the directory are replaced, provided that it
+    /// was created by `uv vendor`.
+    #[arg(long, default_value = "vendor")]
+    pub output_dir: PathBuf,
+
+    /// Assert that the `uv.lock` will remain unchanged.
+    ///
+    /// Requires that the lockfile is up-to-date. If the lockfile is missing or
+    /// needs to be updated, uv will exit with an error.
+    #[arg(long, conflicts_with = "frozen")]
+    pub locked: bool,
+
+    /// Vendor without updating the `uv.lock` file.
+    ///
+    /// Instead of checking if the lockfile is up-to-date, uses the versions in
+    /// the lockfile as the source of truth. If the lockfile is missing, uv will
+    /// exit with an error.
+    #[arg(long, conflicts_with = "locked")]
+    pub frozen: bool,
+
+    #[command(flatten)]
+    pub installer: ResolverInstallerArgs,
+
+    #[command(flatten)]
+    pub build: BuildArgs,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
+    /// The Python interpreter to vendor the dependencies for.
+    ///
+    /// By default, the first interpreter that meets the project's
+    /// `requires-python` constraint is used.
+    ///
+    /// See `uv help python` for details on Python discovery and supported
+    /// request formats.
+    #[arg(
+        long,
+        short,
+        env = "UV_PYTHON",
+        verbatim_doc_comment,
+        help_heading = "Python options"
+    )]
+    pub python: Option<String>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct TreeArgs {
+    /// Show a platform-independent dependency tree.
+    ///
+    /// Shows resolved package versions for all Python versions and platforms,
+    /// rather than filtering to those that are relevant for the current
+    /// environment.
+    ///
+    /// Multiple versions may be shown for a each package.
+    #[arg(long)]
+    pub universal: bool,
+
+    #[command(flatten)]
+    pub tree: DisplayTreeArgs,
+
+    /// The format in which to display the dependencies.
+    ///
+    /// The `dot`, `mermaid`, and `json` formats display the dependency graph,
+    /// rather than a tree: each package is listed once, along with its
+    /// version and the markers of the forks that contain it, if any, and
+    /// each edge is annotated with the extras it enables, the extra or group
+    /// that includes it, and the markers under which it applies. `--depth`,
+    /// `--prune`, `--package`, and `--invert` are respected.
+    #[arg(long, value_enum, default_value_t = TreeFormat::default())]
+    pub format: TreeFormat,
+
+    /// Assert that the `uv.lock` will remain unchanged.
     ///
     /// Requires that the lockfile is up-to-date. If the lockfile is missing or
     /// needs to be updated, uv will exit with an error.
@@ -2558,6 +4276,9 @@ pub struct TreeArgs {
     #[command(flatten)]
     pub resolver: ResolverArgs,
 
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
     /// The Python version to use when filtering the tree.
     ///
     /// For example, pass `--python-version 3.10` to display the dependencies
@@ -2597,6 +4318,145 @@ pub struct TreeArgs {
     pub python: Option<String>,
 }
 
+#[derive(Args)]
+pub struct ServerArgs {
+    #[command(flatten)]
+    pub build: BuildArgs,
+
+    #[command(flatten)]
+    pub resolver: ResolverArgs,
+
+    /// The Python interpreter to use for locking and resolving.
+    ///
+    /// The interpreter is discovered once, when the server starts.
+    ///
+    /// See `uv help python` for details on Python discovery and supported
+    /// request formats.
+    #[arg(
+        long,
+        short,
+        env = "UV_PYTHON",
+        verbatim_doc_comment,
+        help_heading = "Python options"
+    )]
+    pub python: Option<String>,
+}
+
+#[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct WhyArgs {
+    /// The package to explain.
+    pub package: PackageName,
+
+    /// Show the dependency chains for all Python versions and platforms.
+    ///
+    /// By default, only the dependency chains that are relevant for the
+    /// current environment are shown.
+    #[arg(long)]
+    pub universal: bool,
+
+    /// Assert that the `uv.lock` will remain unchanged.
+    ///
+    /// Requires that the lockfile is up-to-date. If the lockfile is missing or
+    /// needs to be updated, uv will exit with an error.
+    #[arg(long, conflicts_with = "frozen")]
+    pub locked: bool,
+
+    /// Explain the package without locking the project.
+    ///
+    /// If the lockfile is missing, uv will exit with an error.
+    #[arg(long, conflicts_with = "locked")]
+    pub frozen: bool,
+
+    #[command(flatten)]
+    pub build: BuildArgs,
+
+    #[command(flatten)]
+    pub resolver: ResolverArgs,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+
+    /// The Python version to use when filtering the dependency chains.
+    ///
+    /// Defaults to the version of the discovered Python interpreter.
+    #[arg(long, conflicts_with = "universal")]
+    pub python_version: Option<PythonVersion>,
+
+    /// The platform to use when filtering the dependency chains.
+    ///
+    /// Represented as a "target triple", a string that describes the target
+    /// platform in terms of its CPU, vendor, and operating system name, like
+    /// `x86_64-unknown-linux-gnu` or `aaarch64-apple-darwin`.
+    #[arg(long, conflicts_with = "universal")]
+    pub python_platform: Option<TargetTriple>,
+
+    /// The Python interpreter to use for locking and filtering.
+    ///
+    /// By default, the dependency chains are filtered to match the platform as
+    /// reported by the Python interpreter. Use `--universal` to display the
+    /// chains for all platforms, or use `--python-version` or
+    /// `--python-platform` to override a subset of markers.
+    ///
+    /// See `uv help python` for details on Python discovery and supported
+    /// request formats.
+    #[arg(
+        long,
+        short,
+        env = "UV_PYTHON",
+        verbatim_doc_comment,
+        help_heading = "Python options"
+    )]
+    pub python: Option<String>,
+}
+
+#[derive(Args)]
+pub struct WorkspaceNamespace {
+    #[command(subcommand)]
+    pub command: WorkspaceCommand,
+}
+
+#[derive(Subcommand)]
+pub enum WorkspaceCommand {
+    /// List the members of the workspace.
+    List(WorkspaceListArgs),
+    /// Display the dependencies between the members of the workspace.
+    Tree(WorkspaceTreeArgs),
+}
+
+#[derive(Args)]
+pub struct WorkspaceListArgs {
+    /// Only list the given members, along with any members that depend on them, directly or
+    /// transitively.
+    ///
+    /// Useful for determining which members need to be rebuilt or retested after a change to a
+    /// given member.
+    #[arg(long)]
+    pub affected_by: Vec<PackageName>,
+
+    /// Display the members, their dependencies, and their shared external dependencies as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct WorkspaceTreeArgs {
+    /// Display only the given members, and the members they depend on.
+    #[arg(long)]
+    pub package: Vec<PackageName>,
+
+    /// Show the members that depend on each member, rather than its dependencies.
+    ///
+    /// With `--package`, displays the members that would be affected by a change to the given
+    /// members.
+    #[arg(long)]
+    pub invert: bool,
+
+    /// Include dependencies on packages outside the workspace.
+    #[arg(long, conflicts_with = "invert")]
+    pub external: bool,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct ToolNamespace {
@@ -2624,13 +4484,44 @@ pub enum ToolCommand {
     Upgrade(ToolUpgradeArgs),
     /// List installed tools.
     List(ToolListArgs),
+    /// Show the receipt of an installed tool.
+    ///
+    /// The receipt records the requirements, options, and Python request with which the tool was
+    /// installed, along with the executables it provides.
+    Show(ToolShowArgs),
     /// Uninstall a tool.
     Uninstall(ToolUninstallArgs),
+    /// Repair tool environments that are linked to a missing Python interpreter.
+    ///
+    /// A tool environment is considered broken if its base interpreter no longer exists (e.g.,
+    /// because a uv-managed Python version was uninstalled or upgraded), or if its executables
+    /// no longer refer to the environment. Broken environments are recreated from the
+    /// requirements and Python request recorded when the tool was installed, and their
+    /// executables are reinstalled.
+    Repair(ToolRepairArgs),
+    /// Remove orphaned tool environments.
+    ///
+    /// A tool environment is considered orphaned if it's missing a valid receipt, or if all of
+    /// its executables were removed (e.g., by deleting them from the executable directory by
+    /// hand). Orphaned environments are removed, along with any executables that link to a tool
+    /// environment that no longer exists.
+    ///
+    /// Tools that are only missing some of their executables are retained; use `uv tool repair`
+    /// to reinstall them.
+    Prune(ToolPruneArgs),
     /// Ensure that the tool executable directory is on `PATH`.
     #[command(alias = "ensurepath")]
-    UpdateShell,
+    UpdateShell(ToolUpdateShellArgs),
     /// Show the tools directory.
     Dir(ToolDirArgs),
+    /// List the tools that are running in the background.
+    ///
+    /// Only tools that were launched with `uv tool run --detach` are listed.
+    Ps,
+    /// Stop tools that are running in the background.
+    ///
+    /// Only tools that were launched with `uv tool run --detach` can be stopped.
+    Kill(ToolKillArgs),
 }
 
 #[derive(Args)]
@@ -2667,6 +4558,48 @@ pub struct ToolRunArgs {
     #[arg(long)]
     pub isolated: bool,
 
+    /// Constrain the tool's dependencies to the versions pinned in the current project's lockfile.
+    ///
+    /// Any package that the tool shares with the project is installed at the version recorded in
+    /// `uv.lock`, such that tools like `mypy` or `pytest` run against the same library versions as
+    /// the project. The requested packages themselves (i.e., those provided via `--from` and
+    /// `--with`) are not constrained.
+    #[arg(long)]
+    pub project_constraints: bool,
+
+    /// Run the tool in the background.
+    ///
+    /// The output of the tool is written to a log file, and uv exits as soon as the tool has
+    /// started. Use `uv tool ps` to list the tools running in the background, and `uv tool kill`
+    /// to stop them.
+    #[arg(long)]
+    pub detach: bool,
+
+    /// Remove cached tool environments that haven't been used in the given number of days.
+    ///
+    /// Tool environments are cached for reuse across invocations. When set, any cached
+    /// environments that have gone unused for longer than the given number of days are removed
+    /// after the tool exits. To avoid slowing down every invocation, the cached environments are
+    /// pruned at most once per hour, and only if no other invocation is using them.
+    #[arg(long, env = "UV_TOOL_RUN_CACHE_DAYS")]
+    pub cache_days: Option<u64>,
+
+    /// The maximum total size of the cached tool environments, e.g., `500MB` or `2GiB`.
+    ///
+    /// When set, the least-recently used cached environments are removed until the total size
+    /// is within the limit, after the tool exits. As with `--cache-days`, the cached
+    /// environments are pruned at most once per hour.
+    #[arg(long, env = "UV_TOOL_RUN_CACHE_MAX_SIZE", value_parser = parse_size)]
+    pub cache_max_size: Option<u64>,
+
+    /// Check the names of the requested packages against a list of popular packages, to detect
+    /// typos (e.g., `reqeusts` for `requests`).
+    ///
+    /// With `warn` (the default), a warning is shown for names that are a single edit away from
+    /// that of a popular package; with `error`, the tool isn't installed.
+    #[arg(long, value_enum, env = "UV_TYPOSQUAT_CHECK")]
+    pub typosquat_check: Option<TyposquatCheck>,
+
     #[command(flatten)]
     pub installer: ResolverInstallerArgs,
 
@@ -2719,6 +4652,26 @@ pub struct ToolInstallArgs {
     #[arg(long, value_parser = parse_maybe_file_path)]
     pub with_requirements: Vec<Maybe<PathBuf>>,
 
+    /// Constrain versions using the given requirements files.
+    ///
+    /// Constraints files are `requirements.txt`-like files that only control the _version_ of a
+    /// requirement that's installed. However, including a package in a constraints file will _not_
+    /// trigger the installation of that package.
+    ///
+    /// The constraints are recorded in the tool receipt, and respected by `uv tool upgrade`.
+    #[arg(long, short, value_delimiter = ' ', value_parser = parse_maybe_file_path)]
+    pub constraint: Vec<Maybe<PathBuf>>,
+
+    /// Override versions using the given requirements files.
+    ///
+    /// Overrides files are `requirements.txt`-like files that force a specific version of a
+    /// requirement to be installed, regardless of the requirements declared by any constituent
+    /// package, and regardless of whether this would be considered an invalid resolution.
+    ///
+    /// The overrides are recorded in the tool receipt, and respected by `uv tool upgrade`.
+    #[arg(long, value_delimiter = ' ', value_parser = parse_maybe_file_path)]
+    pub r#override: Vec<Maybe<PathBuf>>,
+
     #[command(flatten)]
     pub installer: ResolverInstallerArgs,
 
@@ -2734,6 +4687,77 @@ pub struct ToolInstallArgs {
     #[arg(long)]
     pub force: bool,
 
+    /// Reinstall the tool from its lockfile, without resolving.
+    ///
+    /// The packages recorded in the tool's `uv.lock` are installed exactly. Requires that the tool
+    /// was previously installed with the same requirements; if the lockfile is missing, uv will
+    /// exit with an error.
+    #[arg(long, conflicts_with_all = ["upgrade", "upgrade_package"])]
+    pub locked: bool,
+
+    /// Install the tool from a workspace member, using the versions pinned in the workspace's
+    /// lockfile.
+    ///
+    /// The package must be a member of the workspace containing the given `uv.lock`. The member is
+    /// installed (non-editable) along with exactly the dependencies recorded in the lockfile,
+    /// without resolving. Subsequent upgrades via `uv tool upgrade` re-resolve the member's
+    /// requirements.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["from", "editable", "with", "with_requirements", "constraint", "override", "locked", "upgrade", "upgrade_package"]
+    )]
+    pub from_lock: Option<PathBuf>,
+
+    /// Install the tool under a suffixed name, e.g., `--suffix @23` to install `black@23`.
+    ///
+    /// The suffix is applied to the tool environment and to each of its executables, such that
+    /// multiple versions of the same tool can be installed side-by-side. The suffixed name (e.g.,
+    /// `black@23`) is then used to refer to the installation in `uv tool upgrade` and
+    /// `uv tool uninstall`.
+    ///
+    /// The suffix must start with `@`.
+    #[arg(long)]
+    pub suffix: Option<String>,
+
+    /// Only install the executable with the given name; may be provided more than once.
+    ///
+    /// By default, all executables provided by the tool are installed. The selection is recorded
+    /// in the tool receipt, and respected by `uv tool upgrade`.
+    #[arg(long, value_name = "NAME")]
+    pub entrypoint: Vec<String>,
+
+    /// Don't install the executable with the given name; may be provided more than once.
+    ///
+    /// The selection is recorded in the tool receipt, and respected by `uv tool upgrade`.
+    #[arg(long, value_name = "NAME", conflicts_with = "entrypoint")]
+    pub exclude_entrypoint: Vec<String>,
+
+    /// Install the executables provided by the tool's dependencies, in addition to those provided
+    /// by the tool itself.
+    ///
+    /// For example, `uv tool install jupyterlab --include-deps` will also install the `jupyter`
+    /// executable, which is provided by the `jupyter-core` dependency.
+    #[arg(long)]
+    pub include_deps: bool,
+
+    /// Mark the tool environment's `site-packages` directory as read-only after installing.
+    ///
+    /// Prevents the tool (or any of its subprocesses) from modifying its own environment, e.g.,
+    /// via `pip install`. uv restores write access for the duration of `uv tool upgrade`, and
+    /// marks the environment read-only again once it completes.
+    #[arg(long)]
+    pub read_only_env: bool,
+
+    /// Set an environment variable for the tool's executables, as a `KEY=VALUE` pair; may be
+    /// provided more than once.
+    ///
+    /// Each executable is installed as a launcher that sets the variables before running the
+    /// tool (e.g., `--env ANSIBLE_CONFIG=/etc/ansible/ansible.cfg`). The variables are recorded
+    /// in the tool receipt, and respected by `uv tool upgrade`.
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_env_var)]
+    pub env: Vec<(String, String)>,
+
     /// The Python interpreter to use to build the tool environment.
     ///
     /// See `uv help python` for details on Python discovery and supported
@@ -2746,6 +4770,13 @@ pub struct ToolInstallArgs {
         help_heading = "Python options"
     )]
     pub python: Option<String>,
+
+    /// Select the output format between: `text` (default), `json`, `toml`, or `shell`.
+    ///
+    /// With a machine-readable format, a summary of the installed tool is written to stdout, in
+    /// the same format as `uv tool list`. The progress output is still written to stderr.
+    #[arg(long, value_enum, default_value_t = ToolFormat::default())]
+    pub format: ToolFormat,
 }
 
 #[derive(Args)]
@@ -2754,6 +4785,18 @@ pub struct ToolListArgs {
     /// Whether to display the path to each tool environment and installed executable.
     #[arg(long)]
     pub show_paths: bool,
+
+    /// Select the output format between: `text` (default), `json`, `toml`, or `shell`.
+    ///
+    /// The `json` and `toml` formats include the name, version, requirements, Python interpreter,
+    /// and environment path of each tool, along with its executables.
+    ///
+    /// The `shell` format emits a shell variable assignment for the version and environment path of
+    /// each tool, and for the path to each of its executables (e.g., `uv_tool_black_version`,
+    /// `uv_tool_black_path`, and `uv_tool_black_bin_blackd`), alongside a `uv_tools` variable
+    /// listing the tool names. The machine-readable formats always include paths.
+    #[arg(long, value_enum, default_value_t = ToolFormat::default())]
+    pub format: ToolFormat,
 }
 
 #[derive(Args)]
@@ -2767,34 +4810,150 @@ pub struct ToolDirArgs {
     pub bin: bool,
 }
 
+#[derive(Args)]
+pub struct ToolUpdateShellArgs {
+    /// Show the changes that would be made, without modifying any shell configuration files (or,
+    /// on Windows, the registry).
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct ToolUninstallArgs {
-    /// The name of the tool to uninstall.
+    /// The name of the tool to uninstall, including any suffix (e.g., `black@23`).
     #[arg(required = true)]
-    pub name: Option<PackageName>,
+    pub name: Option<ToolName>,
 
     /// Uninstall all tools.
     #[arg(long, conflicts_with("name"))]
     pub all: bool,
+
+    /// Show the environment directories and executables that would be removed, without
+    /// uninstalling anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct ToolShowArgs {
+    /// The name of the tool to show, including any suffix (e.g., `black@23`).
+    pub name: ToolName,
+
+    /// Select the output format between: `text` (default) or `json`.
+    #[arg(long, value_enum, default_value_t = ToolShowFormat::default())]
+    pub format: ToolShowFormat,
+}
+
+#[derive(Args)]
+pub struct ToolRepairArgs {
+    /// The name of the tool to repair, including any suffix (e.g., `black@23`).
+    #[arg(required = true)]
+    pub name: Option<ToolName>,
+
+    /// Repair all broken tools.
+    #[arg(long, conflicts_with("name"))]
+    pub all: bool,
+}
+
+#[derive(Args)]
+pub struct ToolPruneArgs {
+    /// Show the environments and executables that would be removed, without removing them.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct ToolKillArgs {
+    /// The process ID of the tool to stop, as shown by `uv tool ps`.
+    #[arg(required = true)]
+    pub pid: Vec<u32>,
+
+    /// Stop all tools that are running in the background.
+    #[arg(long, conflicts_with("pid"))]
+    pub all: bool,
 }
 
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct ToolUpgradeArgs {
-    /// The name of the tool to upgrade.
+    /// The name of the tool to upgrade, including any suffix (e.g., `black@23`).
     #[arg(required = true)]
-    pub name: Option<PackageName>,
+    pub name: Option<ToolName>,
 
     /// Upgrade all tools.
     #[arg(long, conflicts_with("name"))]
     pub all: bool,
 
+    /// Include the following extra requirements in the tool environment.
+    ///
+    /// The requirements are added to those that the tool was installed with, and are recorded in
+    /// the tool's receipt, such that they're retained by subsequent upgrades.
+    #[arg(long, conflicts_with_all = ["all", "frozen"])]
+    pub with: Vec<String>,
+
+    /// Remove the following extra requirements from the tool environment.
+    ///
+    /// Only requirements that were added with `--with` (during installation or a previous
+    /// upgrade) can be removed.
+    #[arg(long, value_name = "PACKAGE", conflicts_with_all = ["all", "frozen"])]
+    pub without: Vec<PackageName>,
+
+    /// Reinstall the packages recorded in each tool's lockfile, without resolving.
+    ///
+    /// Rather than upgrading to the latest compatible versions, the tool environment is restored
+    /// to exactly the resolution that was last installed. If a lockfile is missing, uv will exit
+    /// with an error.
+    #[arg(long, conflicts_with_all = ["upgrade", "upgrade_package"])]
+    pub frozen: bool,
+
+    /// Perform a dry run, i.e., don't actually upgrade anything but report the version each tool
+    /// would be upgraded to.
+    #[arg(long, conflicts_with = "frozen")]
+    pub dry_run: bool,
+
+    /// The maximum number of tools to upgrade concurrently, with `--all`.
+    ///
+    /// When upgrading multiple tools concurrently, the per-package output of each upgrade is
+    /// omitted in favor of a summary of the outcome for each tool.
+    ///
+    /// Defaults to the number of available CPU cores.
+    #[arg(long, env = "UV_CONCURRENT_TOOL_UPGRADES", value_name = "N")]
+    pub concurrent_upgrades: Option<std::num::NonZeroUsize>,
+
+    /// Rebuild the tool environment with the given Python interpreter.
+    ///
+    /// The tool is reinstalled on the new interpreter with the requirements and options from its
+    /// receipt, and the new Python request is recorded, such that it's respected by subsequent
+    /// upgrades. By default, the tool's existing interpreter is retained.
+    ///
+    /// See `uv help python` for details on Python discovery and supported
+    /// request formats.
+    #[arg(
+        long,
+        short,
+        conflicts_with = "frozen",
+        verbatim_doc_comment,
+        help_heading = "Python options"
+    )]
+    pub python: Option<String>,
+
+    /// Select the output format between: `text` (default), `json`, `toml`, or `shell`.
+    ///
+    /// With a machine-readable format, a summary of the upgraded tools is written to stdout, in
+    /// the same format as `uv tool list`. With `--dry-run`, the installed and latest version of
+    /// each tool is written instead. The progress output is still written to stderr.
+    #[arg(long, value_enum, default_value_t = ToolFormat::default())]
+    pub format: ToolFormat,
+
     #[command(flatten)]
     pub installer: ResolverInstallerArgs,
 
     #[command(flatten)]
     pub build: BuildArgs,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
 }
 
 #[derive(Args)]
@@ -2817,7 +4976,8 @@ pub enum PythonCommand {
     ///
     /// Use `--all-versions` to view all available patch versions.
     ///
-    /// Use `--only-installed` to omit available downloads.
+    /// Use `--only-installed` to omit available downloads, or `--only-downloads`
+    /// to omit installed versions.
     List(PythonListArgs),
 
     /// Download and install Python versions.
@@ -2877,8 +5037,27 @@ pub struct PythonListArgs {
     /// Only show installed Python versions, exclude available downloads.
     ///
     /// By default, available downloads for the current platform are shown.
-    #[arg(long)]
+    #[arg(long, conflicts_with = "only_downloads")]
     pub only_installed: bool,
+
+    /// Only show available Python downloads, exclude installed versions.
+    ///
+    /// Unlike the default listing, downloads are shown even if automatic Python downloads are
+    /// disabled. Combine with `--all-versions`, `--all-platforms`, and `--json` to export the
+    /// complete catalog of managed Python downloads.
+    #[arg(long)]
+    pub only_downloads: bool,
+
+    /// Display the Python versions as JSON.
+    ///
+    /// Each entry includes the installation key, version, implementation, platform, and path,
+    /// along with the source URL and SHA-256 digest of the archive for downloads and for managed
+    /// installations that recorded their provenance when they were installed.
+    #[arg(long)]
+    pub json: bool,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
 }
 
 #[derive(Args)]
@@ -2900,6 +5079,34 @@ pub struct PythonInstallArgs {
     /// installed.
     #[arg(long, short, alias = "force")]
     pub reinstall: bool,
+
+    /// Remove the `ensurepip` module from the installed Python version(s).
+    ///
+    /// Prevents `pip` from being bootstrapped into the installation, or into virtual environments
+    /// created with `python -m venv`.
+    #[arg(long)]
+    pub no_ensurepip: bool,
+
+    /// Install the given Python module as `sitecustomize` in the installed Python version(s).
+    ///
+    /// The module is imported during the startup of every interpreter that uses the installation.
+    #[arg(long, value_name = "PATH")]
+    pub sitecustomize: Option<PathBuf>,
+
+    /// Set `PYTHONSAFEPATH` by default in the installed Python version(s), via `sitecustomize`.
+    ///
+    /// Since `sitecustomize` is imported during interpreter startup, the variable applies to the
+    /// Python processes spawned by the interpreter (e.g., via `subprocess`), rather than the
+    /// interpreter itself.
+    #[arg(long)]
+    pub safe_path: bool,
+
+    /// Install a build that runs under emulation if there's no build for the current architecture.
+    ///
+    /// For example, installs an x86-64 build on Windows on ARM if there's no ARM64 build for the
+    /// requested version. Requests that specify an architecture are never substituted.
+    #[arg(long)]
+    pub allow_emulated: bool,
 }
 
 #[derive(Args)]
@@ -2923,6 +5130,9 @@ pub struct PythonFindArgs {
     ///
     /// See `uv help python` to view supported request formats.
     pub request: Option<String>,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
 }
 
 #[derive(Args)]
@@ -2937,14 +5147,25 @@ pub struct PythonPinArgs {
     /// See `uv help python` to view supported request formats.
     pub request: Option<String>,
 
-    /// Write the resolved Python interpreter path instead of the request.
+    /// Write the resolved Python interpreter instead of the request.
     ///
     /// Ensures that the exact same interpreter is used.
     ///
-    /// This option is usually not safe to use when committing the
-    /// `.python-version` file to version control.
-    #[arg(long, overrides_with("resolved"))]
-    pub resolved: bool,
+    /// By default, or with `--resolved=path`, the path to the interpreter is written. This option
+    /// is usually not safe to use when committing the `.python-version` file to version control.
+    ///
+    /// With `--resolved=key`, the key of the interpreter (e.g., `cpython-3.12.3-macos-aarch64-none`)
+    /// is written instead, such that collaborators use an identical toolchain. When discovering
+    /// an interpreter for such a key, managed installations are preferred.
+    #[arg(
+        long,
+        overrides_with("resolved"),
+        value_enum,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "path"
+    )]
+    pub resolved: Option<ResolvedPin>,
 
     #[arg(long, overrides_with("no_resolved"), hide = true)]
     pub no_resolved: bool,
@@ -2956,6 +5177,17 @@ pub struct PythonPinArgs {
     /// the workspace's `requires-python` constraint.
     #[arg(long)]
     pub no_workspace: bool,
+
+    #[command(flatten)]
+    pub refresh: RefreshArgs,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum ResolvedPin {
+    /// Write the path to the resolved Python interpreter.
+    Path,
+    /// Write the key of the resolved Python interpreter, e.g., `cpython-3.12.3-macos-aarch64-none`.
+    Key,
 }
 
 #[derive(Args)]
@@ -3023,6 +5255,14 @@ pub struct RefreshArgs {
     /// Refresh cached data for a specific package.
     #[arg(long, help_heading = "Cache options")]
     pub refresh_package: Vec<PackageName>,
+
+    /// Refresh cached interpreter metadata.
+    ///
+    /// The results of querying an interpreter (e.g., its version, markers, and installation
+    /// paths) are cached, keyed by the path and modification time of the executable. Refreshing
+    /// forces each interpreter to be queried again, without discarding any other cached data.
+    #[arg(long, help_heading = "Cache options")]
+    pub refresh_python: bool,
 }
 
 #[derive(Args)]
@@ -3066,6 +5306,32 @@ pub struct BuildArgs {
     /// Don't install pre-built wheels for a specific package.
     #[arg(long, help_heading = "Build options")]
     pub no_binary_package: Vec<PackageName>,
+
+    /// Prefer building from source distributions over installing pre-built wheels.
+    ///
+    /// Packages that publish a source distribution will be built and installed from source, while
+    /// packages that only publish wheels will continue to be installed from wheels. As with
+    /// `--no-binary`, the resolver will still use pre-built wheels to extract package metadata, if
+    /// available.
+    #[arg(
+        long,
+        overrides_with("no_prefer_source"),
+        help_heading = "Build options"
+    )]
+    pub prefer_source: bool,
+
+    #[arg(
+        long,
+        overrides_with("prefer_source"),
+        hide = true,
+        help_heading = "Build options"
+    )]
+    pub no_prefer_source: bool,
+
+    /// Prefer building from source distributions over installing pre-built wheels for a specific
+    /// package.
+    #[arg(long, help_heading = "Build options")]
+    pub prefer_source_package: Vec<PackageName>,
 }
 
 /// Arguments that are used by commands that need to install (but not resolve) packages.
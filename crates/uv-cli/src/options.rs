@@ -23,9 +23,10 @@ impl From<RefreshArgs> for Refresh {
             refresh,
             no_refresh,
             refresh_package,
+            refresh_python,
         } = value;
 
-        Self::from_args(flag(refresh, no_refresh), refresh_package)
+        Self::from_args(flag(refresh, no_refresh), refresh_package, refresh_python)
     }
 }
 
@@ -213,6 +214,9 @@ pub fn resolver_options(resolver_args: ResolverArgs, build_args: BuildArgs) -> R
         no_binary,
         binary,
         no_binary_package,
+        prefer_source,
+        no_prefer_source,
+        prefer_source_package,
     } = build_args;
 
     ResolverOptions {
@@ -229,6 +233,7 @@ pub fn resolver_options(resolver_args: ResolverArgs, build_args: BuildArgs) -> R
             None
         },
         find_links: index_args.find_links,
+        index_mirrors: None,
         upgrade: flag(upgrade, no_upgrade),
         upgrade_package: Some(upgrade_package),
         index_strategy,
@@ -249,6 +254,8 @@ pub fn resolver_options(resolver_args: ResolverArgs, build_args: BuildArgs) -> R
         no_build_package: Some(no_build_package),
         no_binary: flag(no_binary, binary),
         no_binary_package: Some(no_binary_package),
+        prefer_source: flag(prefer_source, no_prefer_source),
+        prefer_source_package: Some(prefer_source_package),
         no_sources: if no_sources { Some(true) } else { None },
     }
 }
@@ -289,6 +296,9 @@ pub fn resolver_installer_options(
         no_binary,
         binary,
         no_binary_package,
+        prefer_source,
+        no_prefer_source,
+        prefer_source_package,
     } = build_args;
 
     ResolverInstallerOptions {
@@ -305,6 +315,7 @@ pub fn resolver_installer_options(
             None
         },
         find_links: index_args.find_links,
+        index_mirrors: None,
         upgrade: flag(upgrade, no_upgrade),
         upgrade_package: if upgrade_package.is_empty() {
             None
@@ -336,6 +347,7 @@ pub fn resolver_installer_options(
         exclude_newer,
         link_mode,
         compile_bytecode: flag(compile_bytecode, no_compile_bytecode),
+        exclude_package_data: None,
         no_build: flag(no_build, build),
         no_build_package: if no_build_package.is_empty() {
             None
@@ -348,6 +360,12 @@ pub fn resolver_installer_options(
         } else {
             Some(no_binary_package)
         },
+        prefer_source: flag(prefer_source, no_prefer_source),
+        prefer_source_package: if prefer_source_package.is_empty() {
+            None
+        } else {
+            Some(prefer_source_package)
+        },
         no_sources: if no_sources { Some(true) } else { None },
     }
 }
@@ -2,6 +2,7 @@ use std::error::Error;
 use std::fmt::Debug;
 use std::ops::Deref;
 use std::path::Path;
+use std::time::Duration;
 use std::{env, iter};
 
 use itertools::Itertools;
@@ -21,8 +22,9 @@ use uv_fs::Simplified;
 use uv_version::version;
 use uv_warnings::warn_user_once;
 
+use crate::connect::{ConnectOverrides, HttpSettings};
 use crate::linehaul::LineHaul;
-use crate::middleware::OfflineMiddleware;
+use crate::middleware::{OfflineMiddleware, RedirectMiddleware, RequestLogMiddleware};
 use crate::tls::read_identity;
 use crate::Connectivity;
 
@@ -151,10 +153,19 @@ impl<'a> BaseClientBuilder<'a> {
             // Configure the builder.
             let client_core = ClientBuilder::new()
                 .user_agent(user_agent_string)
-                .pool_max_idle_per_host(20)
-                .read_timeout(std::time::Duration::from_secs(timeout))
+                .read_timeout(Duration::from_secs(timeout))
+                // Redirects are followed by the `RedirectMiddleware`, such that each hop is
+                // authenticated independently.
+                .redirect(reqwest::redirect::Policy::none())
                 .tls_built_in_root_certs(false);
 
+            // Configure the connection pool and HTTP version.
+            let client_core = if let Some(settings) = HttpSettings::get() {
+                settings.apply(client_core)
+            } else {
+                HttpSettings::default().apply(client_core)
+            };
+
             // Configure TLS.
             let client_core = if self.native_tls || ssl_cert_file_exists {
                 client_core.tls_built_in_native_certs(true)
@@ -175,6 +186,13 @@ impl<'a> BaseClientBuilder<'a> {
                 client_core
             };
 
+            // Configure any overrides for IP version and DNS resolution.
+            let client_core = if let Some(overrides) = ConnectOverrides::get() {
+                overrides.apply(client_core)
+            } else {
+                client_core
+            };
+
             client_core.build().expect("Failed to build HTTP client")
         });
 
@@ -192,10 +210,17 @@ impl<'a> BaseClientBuilder<'a> {
                 );
                 let client = client.with(retry_strategy);
 
+                // Follow any redirects, passing each redirected request through the
+                // authentication middleware.
+                let client = client.with(RedirectMiddleware);
+
                 // Initialize the authentication middleware to set headers.
                 let client =
                     client.with(AuthMiddleware::new().with_keyring(self.keyring.to_provider()));
 
+                // Record each request (including any retries) for the `--log-file`.
+                let client = client.with(RequestLogMiddleware);
+
                 client.build()
             }
             Connectivity::Offline => reqwest_middleware::ClientBuilder::new(client.clone())
@@ -0,0 +1,141 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use reqwest::ClientBuilder;
+use tracing::debug;
+
+use distribution_types::IndexResolve;
+use uv_configuration::IpVersion;
+
+static CONNECT_OVERRIDES: OnceLock<ConnectOverrides> = OnceLock::new();
+
+static HTTP_SETTINGS: OnceLock<HttpSettings> = OnceLock::new();
+
+/// Overrides for how clients connect to hosts, for networks with broken IPv6 connectivity or
+/// split-horizon DNS.
+///
+/// The overrides are process-wide: once installed via [`ConnectOverrides::init`], they apply to
+/// every client built by a [`crate::BaseClientBuilder`].
+#[derive(Debug, Default, Clone)]
+pub struct ConnectOverrides {
+    ip_version: Option<IpVersion>,
+    resolve: IndexResolve,
+}
+
+impl ConnectOverrides {
+    /// Create a set of [`ConnectOverrides`].
+    ///
+    /// If multiple indexes share a host, the address of the first takes precedence.
+    pub fn new(ip_version: Option<IpVersion>, resolve: IndexResolve) -> Self {
+        Self {
+            ip_version,
+            resolve,
+        }
+    }
+
+    /// Install the overrides for all clients created by this process.
+    ///
+    /// Has no effect if the overrides were already installed.
+    pub fn init(self) {
+        let _ = CONNECT_OVERRIDES.set(self);
+    }
+
+    /// Returns the installed overrides, if any.
+    pub(crate) fn get() -> Option<&'static Self> {
+        CONNECT_OVERRIDES.get()
+    }
+
+    /// Apply the overrides to a [`ClientBuilder`].
+    pub(crate) fn apply(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        // Binding to an unspecified local address of a given version restricts connections to
+        // remote addresses of the same version.
+        match self.ip_version {
+            Some(IpVersion::V4) => {
+                debug!("Restricting connections to IPv4");
+                builder = builder.local_address(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+            }
+            Some(IpVersion::V6) => {
+                debug!("Restricting connections to IPv6");
+                builder = builder.local_address(IpAddr::V6(Ipv6Addr::UNSPECIFIED));
+            }
+            None => {}
+        }
+
+        // Later overrides replace earlier ones, so apply them in reverse to give precedence to
+        // the first override for each host. The port is ignored in favor of that of the URL.
+        for (index, ip) in self.resolve.iter().rev() {
+            let Some(host) = index.url().host_str() else {
+                continue;
+            };
+            debug!("Resolving `{host}` to {ip} for index: {index}");
+            builder = builder.resolve(host, SocketAddr::new(ip, 0));
+        }
+
+        builder
+    }
+}
+
+/// Settings for the HTTP connections made by clients, for proxies and mirrors that behave poorly
+/// under the defaults.
+///
+/// Like [`ConnectOverrides`], the settings are process-wide: once installed via
+/// [`HttpSettings::init`], they apply to every client built by a [`crate::BaseClientBuilder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpSettings {
+    /// Whether to negotiate HTTP/2 with servers that support it.
+    pub http2: bool,
+    /// The maximum number of idle connections to keep open per host.
+    pub pool_max_idle_per_host: usize,
+    /// The duration after which an idle connection is closed.
+    pub pool_idle_timeout: Duration,
+    /// The interval at which to send TCP keep-alive probes on idle connections, if any.
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: Duration::from_secs(90),
+            tcp_keepalive: None,
+        }
+    }
+}
+
+impl HttpSettings {
+    /// Install the settings for all clients created by this process.
+    ///
+    /// Has no effect if the settings were already installed.
+    pub fn init(self) {
+        let _ = HTTP_SETTINGS.set(self);
+    }
+
+    /// Returns the installed settings, if any.
+    pub(crate) fn get() -> Option<&'static Self> {
+        HTTP_SETTINGS.get()
+    }
+
+    /// Apply the settings to a [`ClientBuilder`].
+    pub(crate) fn apply(&self, builder: ClientBuilder) -> ClientBuilder {
+        debug!(
+            "Using up to {} idle connections per host (HTTP/2: {})",
+            self.pool_max_idle_per_host,
+            if self.http2 { "enabled" } else { "disabled" }
+        );
+
+        let builder = builder
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .tcp_keepalive(self.tcp_keepalive);
+
+        // Unless HTTP/2 is enabled, avoid negotiating it with the server, as some proxies
+        // mishandle multiplexed connections.
+        if self.http2 {
+            builder
+        } else {
+            builder.http1_only()
+        }
+    }
+}
@@ -43,6 +43,24 @@ impl Error {
         matches!(&*self.kind, ErrorKind::Offline(_))
     }
 
+    /// Returns `true` if this error indicates that the server is unavailable, such that the
+    /// request may succeed against a mirror.
+    pub(crate) fn is_unavailable(&self) -> bool {
+        match &*self.kind {
+            ErrorKind::WrappedReqwestError(err) => err.is_unavailable(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this error corresponds to a network error, i.e., the request failed to
+    /// connect, timed out, or the server returned a server error.
+    pub fn is_network(&self) -> bool {
+        match &*self.kind {
+            ErrorKind::WrappedReqwestError(err) => err.is_network(),
+            _ => false,
+        }
+    }
+
     /// Returns `true` if this error corresponds to an I/O "not found" error.
     pub(crate) fn is_file_not_exists(&self) -> bool {
         let ErrorKind::Io(ref err) = &*self.kind else {
@@ -227,6 +245,9 @@ pub enum ErrorKind {
 
     #[error("Network connectivity is disabled, but the requested data wasn't found in the cache for: `{0}`")]
     Offline(String),
+
+    #[error("Failed to verify {0} against the TUF metadata of the index")]
+    IndexVerification(Url, #[source] Box<crate::tuf::TufError>),
 }
 
 impl From<reqwest::Error> for ErrorKind {
@@ -262,22 +283,7 @@ impl WrappedReqwestError {
     /// * dns error: failed to lookup address information: Name or service not known
     /// * failed to lookup address information: Name or service not known
     fn is_likely_offline(&self) -> bool {
-        let reqwest_err = match &self.0 {
-            reqwest_middleware::Error::Reqwest(err) => Some(err),
-            reqwest_middleware::Error::Middleware(err) => err.chain().find_map(|err| {
-                if let Some(err) = err.downcast_ref::<reqwest::Error>() {
-                    Some(err)
-                } else if let Some(reqwest_middleware::Error::Reqwest(err)) =
-                    err.downcast_ref::<reqwest_middleware::Error>()
-                {
-                    Some(err)
-                } else {
-                    None
-                }
-            }),
-        };
-
-        if let Some(reqwest_err) = reqwest_err {
+        if let Some(reqwest_err) = self.reqwest_error() {
             if !reqwest_err.is_connect() {
                 return false;
             }
@@ -293,6 +299,52 @@ impl WrappedReqwestError {
         }
         false
     }
+
+    /// Returns `true` if the error indicates that the server is unavailable, i.e., it returned a
+    /// server error or the request timed out or failed to connect (excluding DNS failures, which
+    /// are more likely to indicate that the user is offline).
+    pub(crate) fn is_unavailable(&self) -> bool {
+        if self.is_likely_offline() {
+            return false;
+        }
+        if self.status().is_some_and(|status| status.is_server_error()) {
+            return true;
+        }
+        self.reqwest_error()
+            .is_some_and(|err| err.is_timeout() || err.is_connect())
+    }
+
+    /// Returns `true` if the error is a network error, i.e., the request failed to connect, timed
+    /// out, or the server returned a server error.
+    ///
+    /// Client errors (e.g., `404 Not Found` or `401 Unauthorized`) aren't considered network
+    /// errors.
+    pub fn is_network(&self) -> bool {
+        if self.status().is_some_and(|status| status.is_server_error()) {
+            return true;
+        }
+        self.reqwest_error().is_some_and(|err| {
+            err.is_connect() || err.is_timeout() || err.is_request() || err.is_body()
+        })
+    }
+
+    /// Return the underlying [`reqwest::Error`], if any.
+    fn reqwest_error(&self) -> Option<&reqwest::Error> {
+        match &self.0 {
+            reqwest_middleware::Error::Reqwest(err) => Some(err),
+            reqwest_middleware::Error::Middleware(err) => err.chain().find_map(|err| {
+                if let Some(err) = err.downcast_ref::<reqwest::Error>() {
+                    Some(err)
+                } else if let Some(reqwest_middleware::Error::Reqwest(err)) =
+                    err.downcast_ref::<reqwest_middleware::Error>()
+                {
+                    Some(err)
+                } else {
+                    None
+                }
+            }),
+        }
+    }
 }
 
 impl From<reqwest::Error> for WrappedReqwestError {
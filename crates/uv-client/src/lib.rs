@@ -1,16 +1,20 @@
 pub use base_client::{BaseClient, BaseClientBuilder};
 pub use cached_client::{CacheControl, CachedClient, CachedClientError, DataWithCachePolicy};
+pub use connect::{ConnectOverrides, HttpSettings};
 pub use error::{Error, ErrorKind, WrappedReqwestError};
 pub use flat_index::{FlatIndexClient, FlatIndexEntries, FlatIndexError};
 pub use linehaul::LineHaul;
+pub use middleware::LOG_FILE_TARGET;
 pub use registry_client::{
     Connectivity, RegistryClient, RegistryClientBuilder, SimpleMetadata, SimpleMetadatum,
     VersionFiles,
 };
 pub use rkyvutil::OwnedArchive;
+pub use tuf::{TufError, TufSettings};
 
 mod base_client;
 mod cached_client;
+mod connect;
 mod error;
 mod flat_index;
 mod html;
@@ -21,3 +25,4 @@ mod registry_client;
 mod remote_metadata;
 mod rkyvutil;
 mod tls;
+mod tuf;
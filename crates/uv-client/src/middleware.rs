@@ -1,8 +1,10 @@
 use http::Extensions;
 use std::fmt::Debug;
+use std::time::Instant;
 
-use reqwest::{Request, Response};
+use reqwest::{Method, Request, Response, StatusCode};
 use reqwest_middleware::{Middleware, Next};
+use tracing::{debug, info};
 use url::Url;
 
 /// A custom error type for the offline middleware.
@@ -45,3 +47,163 @@ impl Middleware for OfflineMiddleware {
         ))
     }
 }
+
+/// The target for structured events that are only written to the `--log-file` (e.g., the command
+/// and its resolved settings, or each request), and never to the console.
+pub const LOG_FILE_TARGET: &str = "uv_log";
+
+/// A middleware that records each request, along with its outcome and duration, as a structured
+/// event for the `--log-file`.
+pub(crate) struct RequestLogMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for RequestLogMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let method = req.method().clone();
+
+        // Avoid logging any credentials embedded in the URL.
+        let mut url = req.url().clone();
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+
+        let start = Instant::now();
+        let result = next.run(req, extensions).await;
+        let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        match &result {
+            Ok(response) => info!(
+                target: LOG_FILE_TARGET,
+                event = "request",
+                %method,
+                %url,
+                status = response.status().as_u16(),
+                duration_ms,
+            ),
+            Err(err) => info!(
+                target: LOG_FILE_TARGET,
+                event = "request",
+                %method,
+                %url,
+                error = %err,
+                duration_ms,
+            ),
+        }
+
+        result
+    }
+}
+
+/// The maximum number of redirects to follow for a single request, matching `reqwest`'s default.
+const MAX_REDIRECTS: usize = 10;
+
+/// A middleware that follows redirects, in lieu of `reqwest`'s built-in redirect policy.
+///
+/// Following redirects within the middleware stack ensures that every redirected request passes
+/// through the authentication middleware, such that it's authenticated with the credentials for
+/// its own realm. Credentials from the original request are only forwarded to targets within the
+/// same realm: for example, when an index redirects to a presigned S3 URL, the index credentials
+/// are dropped (as S3 would reject them), while a redirect to another path on the index retains
+/// them.
+pub(crate) struct RedirectMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for RedirectMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut request = req;
+        let mut redirects = 0;
+        loop {
+            // If the request can't be cloned (e.g., it has a streaming body), it can't be
+            // redirected.
+            let Some(previous) = request.try_clone() else {
+                return next.run(request, extensions).await;
+            };
+
+            let response = next.clone().run(request, extensions).await?;
+            let Some(redirect) = redirect_request(previous, &response) else {
+                return Ok(response);
+            };
+
+            redirects += 1;
+            if redirects > MAX_REDIRECTS {
+                return Err(reqwest_middleware::Error::Middleware(anyhow::anyhow!(
+                    "Too many redirects (more than {MAX_REDIRECTS}) for: `{}`",
+                    response.url()
+                )));
+            }
+
+            debug!(
+                "Following redirect from {} to {}",
+                response.url(),
+                redirect.url()
+            );
+            request = redirect;
+        }
+    }
+}
+
+/// Construct the request to send in response to a redirect, if the response is a redirect.
+fn redirect_request(mut request: Request, response: &Response) -> Option<Request> {
+    let method = match response.status() {
+        // Per RFC 9110, a `303` always becomes a `GET`, while `301` and `302` are, in practice,
+        // converted to a `GET` for any method other than `HEAD`.
+        StatusCode::SEE_OTHER => Method::GET,
+        StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND => {
+            if request.method() == Method::HEAD {
+                Method::HEAD
+            } else {
+                Method::GET
+            }
+        }
+        StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT => request.method().clone(),
+        _ => return None,
+    };
+
+    let location = response
+        .headers()
+        .get(http::header::LOCATION)?
+        .to_str()
+        .ok()?;
+    let mut url = request.url().join(location).ok()?;
+
+    if same_realm(request.url(), &url) {
+        // Forward any credentials embedded in the original URL.
+        if url.username().is_empty() && url.password().is_none() {
+            let _ = url.set_username(request.url().username());
+            let _ = url.set_password(request.url().password());
+        }
+    } else {
+        // Never forward credentials to a different realm; the authentication middleware will
+        // attach any credentials that are known for the redirect target instead.
+        let headers = request.headers_mut();
+        headers.remove(http::header::AUTHORIZATION);
+        headers.remove(http::header::COOKIE);
+        headers.remove(http::header::PROXY_AUTHORIZATION);
+    }
+
+    if method != *request.method() {
+        *request.body_mut() = None;
+        request.headers_mut().remove(http::header::CONTENT_TYPE);
+        request.headers_mut().remove(http::header::CONTENT_LENGTH);
+    }
+    *request.method_mut() = method;
+    *request.url_mut() = url;
+
+    Some(request)
+}
+
+/// Returns `true` if the two URLs share a realm (i.e., a scheme, host, and port).
+fn same_realm(left: &Url, right: &Url) -> bool {
+    left.scheme() == right.scheme()
+        && left.host_str() == right.host_str()
+        && left.port_or_known_default() == right.port_or_known_default()
+}
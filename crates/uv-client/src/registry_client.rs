@@ -1,7 +1,8 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::{Arc, LazyLock, Mutex};
 
 use async_http_range_reader::AsyncHttpRangeReader;
 use futures::FutureExt;
@@ -9,7 +10,7 @@ use http::HeaderMap;
 use reqwest::{Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use tokio_util::compat::TokioAsyncReadCompatExt;
-use tracing::{info_span, instrument, trace, warn, Instrument};
+use tracing::{debug, info_span, instrument, trace, warn, Instrument};
 use url::Url;
 
 use distribution_filename::{DistFilename, SourceDistFilename, WheelFilename};
@@ -21,6 +22,7 @@ use platform_tags::Platform;
 use pypi_types::{Metadata23, SimpleJson};
 use uv_cache::{Cache, CacheBucket, CacheEntry, WheelCache};
 use uv_configuration::IndexStrategy;
+use uv_configuration::IndexVerification;
 use uv_configuration::KeyringProviderType;
 use uv_normalize::PackageName;
 
@@ -29,6 +31,7 @@ use crate::cached_client::CacheControl;
 use crate::html::SimpleHtml;
 use crate::remote_metadata::wheel_metadata_from_remote_zip;
 use crate::rkyvutil::OwnedArchive;
+use crate::tuf::{IndexTrust, TrustedTargets, TufError, TufSettings};
 use crate::{CachedClient, CachedClientError, Error, ErrorKind};
 
 /// A builder for an [`RegistryClient`].
@@ -37,6 +40,7 @@ pub struct RegistryClientBuilder<'a> {
     index_urls: IndexUrls,
     index_strategy: IndexStrategy,
     cache: Cache,
+    tuf_settings: Option<TufSettings>,
     base_client_builder: BaseClientBuilder<'a>,
 }
 
@@ -46,6 +50,7 @@ impl RegistryClientBuilder<'_> {
             index_urls: IndexUrls::default(),
             index_strategy: IndexStrategy::default(),
             cache,
+            tuf_settings: None,
             base_client_builder: BaseClientBuilder::new(),
         }
     }
@@ -64,6 +69,14 @@ impl<'a> RegistryClientBuilder<'a> {
         self
     }
 
+    /// Verify index responses against TUF metadata with the given settings, rather than those
+    /// installed for the process via [`TufSettings::init`].
+    #[must_use]
+    pub fn tuf_settings(mut self, tuf_settings: TufSettings) -> Self {
+        self.tuf_settings = Some(tuf_settings);
+        self
+    }
+
     #[must_use]
     pub fn keyring(mut self, keyring_type: KeyringProviderType) -> Self {
         self.base_client_builder = self.base_client_builder.keyring(keyring_type);
@@ -131,6 +144,11 @@ impl<'a> RegistryClientBuilder<'a> {
             connectivity,
             client,
             timeout,
+            tuf_settings: self
+                .tuf_settings
+                .or_else(|| TufSettings::get().cloned())
+                .unwrap_or_default(),
+            trusted_targets: Arc::default(),
         }
     }
 }
@@ -141,11 +159,17 @@ impl<'a> From<BaseClientBuilder<'a>> for RegistryClientBuilder<'a> {
             index_urls: IndexUrls::default(),
             index_strategy: IndexStrategy::default(),
             cache: Cache::temp().unwrap(),
+            tuf_settings: None,
             base_client_builder: value,
         }
     }
 }
 
+/// The indexes that failed with a server error or timeout during this process, which are
+/// deprioritized in favor of their mirrors.
+static UNHEALTHY_INDEXES: LazyLock<Mutex<HashSet<Url>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
 /// A client for fetching packages from a `PyPI`-compatible index.
 #[derive(Debug, Clone)]
 pub struct RegistryClient {
@@ -161,6 +185,10 @@ pub struct RegistryClient {
     connectivity: Connectivity,
     /// Configured client timeout, in seconds.
     timeout: u64,
+    /// The settings for verifying index responses against the TUF metadata of each index.
+    tuf_settings: TufSettings,
+    /// The verified TUF targets of each index, or `None` for indexes without TUF metadata.
+    trusted_targets: Arc<tokio::sync::Mutex<HashMap<Url, Option<Arc<TrustedTargets>>>>>,
 }
 
 impl RegistryClient {
@@ -184,6 +212,11 @@ impl RegistryClient {
         self.timeout
     }
 
+    /// Return the policy for verifying index responses against TUF metadata.
+    pub fn index_verification(&self) -> IndexVerification {
+        self.tuf_settings.policy
+    }
+
     /// Fetch a package from the `PyPI` simple API.
     ///
     /// "simple" here refers to [PEP 503 – Simple Repository API](https://peps.python.org/pep-0503/)
@@ -200,37 +233,33 @@ impl RegistryClient {
         }
 
         let mut results = Vec::new();
-        for index in it {
-            match self.simple_single_index(package_name, index).await {
-                Ok(metadata) => {
+        if self.index_strategy == IndexStrategy::FirstIndex {
+            // If we're only using the first match, query the indexes in order, stopping at the
+            // first index that contains the package.
+            for index in it {
+                if let Some(metadata) =
+                    available(self.simple_with_mirrors(package_name, index).await)?
+                {
                     results.push((index.clone(), metadata));
-
-                    // If we're only using the first match, we can stop here.
-                    if self.index_strategy == IndexStrategy::FirstIndex {
-                        break;
-                    }
+                    break;
                 }
-                Err(err) => match err.into_kind() {
-                    // The package is unavailable due to a lack of connectivity.
-                    ErrorKind::Offline(_) => continue,
-
-                    // The package could not be found in the remote index.
-                    ErrorKind::WrappedReqwestError(err) => {
-                        if err.status() == Some(StatusCode::NOT_FOUND)
-                            || err.status() == Some(StatusCode::UNAUTHORIZED)
-                            || err.status() == Some(StatusCode::FORBIDDEN)
-                        {
-                            continue;
-                        }
-                        return Err(ErrorKind::from(err).into());
-                    }
-
-                    // The package could not be found in the local index.
-                    ErrorKind::FileNotFound(_) => continue,
-
-                    other => return Err(other.into()),
-                },
-            };
+            }
+        } else {
+            // Otherwise, every index is queried, so issue the requests concurrently (over HTTP/2,
+            // they're multiplexed onto a single connection per host), then collect the results in
+            // priority order.
+            let indexes = it.collect::<Vec<_>>();
+            let responses = futures::future::join_all(
+                indexes
+                    .iter()
+                    .map(|index| self.simple_with_mirrors(package_name, index)),
+            )
+            .await;
+            for (index, response) in indexes.into_iter().zip(responses) {
+                if let Some(metadata) = available(response)? {
+                    results.push((index.clone(), metadata));
+                }
+            }
         }
 
         if results.is_empty() {
@@ -245,6 +274,48 @@ impl RegistryClient {
         Ok(results)
     }
 
+    /// Fetch the [`SimpleMetadata`] from an index for a given package, falling back to its
+    /// mirrors (if any) when the index is unavailable.
+    ///
+    /// Indexes that were previously unavailable are tried last.
+    async fn simple_with_mirrors(
+        &self,
+        package_name: &PackageName,
+        index: &IndexUrl,
+    ) -> Result<OwnedArchive<SimpleMetadata>, Error> {
+        let mirrors = self.index_urls.mirrors(index);
+        if mirrors.is_empty() {
+            return self.simple_single_index(package_name, index).await;
+        }
+
+        let mut candidates = std::iter::once(index).chain(mirrors).collect::<Vec<_>>();
+        {
+            let unhealthy = UNHEALTHY_INDEXES.lock().unwrap();
+            candidates.sort_by_key(|candidate| unhealthy.contains(candidate.url()));
+        }
+
+        let mut candidates = candidates.into_iter().peekable();
+        while let Some(candidate) = candidates.next() {
+            match self.simple_single_index(package_name, candidate).await {
+                Err(err) if err.is_unavailable() && candidates.peek().is_some() => {
+                    if UNHEALTHY_INDEXES
+                        .lock()
+                        .unwrap()
+                        .insert(candidate.url().clone())
+                    {
+                        warn!(
+                            "Index `{candidate}` is unavailable, falling back to a mirror: {err}"
+                        );
+                    } else {
+                        debug!("Index `{candidate}` is still unavailable: {err}");
+                    }
+                }
+                result => return result,
+            }
+        }
+        unreachable!("an index always has at least one candidate")
+    }
+
     /// Fetch the [`SimpleMetadata`] from a single index for a given package.
     ///
     /// The index can either be a PEP 503-compatible remote repository, or a local directory laid
@@ -266,10 +337,17 @@ impl RegistryClient {
 
         trace!("Fetching metadata for {package_name} from {url}");
 
+        // Verified responses are cached separately, such that a response that was cached without
+        // verification is never treated as verified.
+        let trust = self.index_trust(index).await?;
         let cache_entry = self.cache.entry(
             CacheBucket::Simple,
             WheelCache::Index(index).root(),
-            format!("{package_name}.rkyv"),
+            if trust.is_verified() {
+                format!("{package_name}.verified.rkyv")
+            } else {
+                format!("{package_name}.rkyv")
+            },
         );
         let cache_control = match self.connectivity {
             Connectivity::Online => CacheControl::from(
@@ -283,8 +361,60 @@ impl RegistryClient {
         if matches!(index, IndexUrl::Path(_)) {
             self.fetch_local_index(package_name, &url).await
         } else {
-            self.fetch_remote_index(package_name, &url, &cache_entry, cache_control)
-                .await
+            self.fetch_remote_index(
+                package_name,
+                &url,
+                &cache_entry,
+                cache_control,
+                trust.targets(),
+            )
+            .await
+        }
+    }
+
+    /// Return the [`IndexTrust`] of an index, fetching and verifying its TUF metadata on first
+    /// use.
+    ///
+    /// In offline mode, the TUF metadata can't be fetched, so only responses that were cached
+    /// after verification are trusted for indexes that were verified previously (or for every
+    /// index, if verification is required).
+    async fn index_trust(&self, index: &IndexUrl) -> Result<IndexTrust, Error> {
+        let policy = self.tuf_settings.policy;
+        if !policy.is_enabled() || matches!(index, IndexUrl::Path(_)) {
+            return Ok(IndexTrust::Unverified);
+        }
+
+        let trust_dir = self.tuf_settings.index_dir(index.url());
+        if self.connectivity.is_offline() {
+            let verified_previously = trust_dir.join("root.json").is_file();
+            return if policy == IndexVerification::Required || verified_previously {
+                Ok(IndexTrust::Offline)
+            } else {
+                Ok(IndexTrust::Unverified)
+            };
+        }
+
+        let mut trusted_targets = self.trusted_targets.lock().await;
+        let targets = if let Some(targets) = trusted_targets.get(index.url()) {
+            targets.clone()
+        } else {
+            let targets = TrustedTargets::fetch(&self.uncached_client(), index.url(), &trust_dir)
+                .await?
+                .map(Arc::new);
+            trusted_targets.insert(index.url().clone(), targets.clone());
+            targets
+        };
+
+        match targets {
+            Some(targets) => Ok(IndexTrust::Verified(targets)),
+            None if policy == IndexVerification::Required => Err(ErrorKind::IndexVerification(
+                index.url().clone(),
+                Box::new(TufError::Unavailable(
+                    index.url().join("../tuf/").map_err(ErrorKind::from)?,
+                )),
+            )
+            .into()),
+            None => Ok(IndexTrust::Unverified),
         }
     }
 
@@ -295,12 +425,19 @@ impl RegistryClient {
         url: &Url,
         cache_entry: &CacheEntry,
         cache_control: CacheControl,
+        targets: Option<&TrustedTargets>,
     ) -> Result<OwnedArchive<SimpleMetadata>, Error> {
+        // TUF targets record the HTML representation of each page, so JSON can't be verified.
+        let accept = if targets.is_some() {
+            "text/html"
+        } else {
+            MediaType::accepts()
+        };
         let simple_request = self
             .uncached_client()
             .get(url.clone())
-            .header("Accept-Encoding", "gzip")
-            .header("Accept", MediaType::accepts())
+            .header("Accept-Encoding", "gzip, br")
+            .header("Accept", accept)
             .build()
             .map_err(ErrorKind::from)?;
         let parse_simple_response = |response: Response| {
@@ -325,16 +462,67 @@ impl RegistryClient {
                 })?;
 
                 let unarchived = match media_type {
+                    MediaType::Json if targets.is_some() => {
+                        return Err(ErrorKind::IndexVerification(
+                            url.clone(),
+                            Box::new(TufError::UnsupportedResponse(url)),
+                        )
+                        .into());
+                    }
                     MediaType::Json => {
+                        let mut next = next_page(response.headers(), &url);
                         let bytes = response.bytes().await.map_err(ErrorKind::from)?;
-                        let data: SimpleJson = serde_json::from_slice(bytes.as_ref())
+                        let mut data: SimpleJson = serde_json::from_slice(bytes.as_ref())
                             .map_err(|err| Error::from_json_err(err, url.clone()))?;
 
+                        // If the index paginates its response, collect the files from each page.
+                        // Each page is cached separately, keyed by its URL, and revalidated against
+                        // its own `ETag` whenever the first page changes, such that unchanged pages
+                        // are answered with a `304 Not Modified` rather than re-downloaded.
+                        let mut seen = HashSet::from([url.clone()]);
+                        while let Some(page) = next.take() {
+                            if !seen.insert(page.clone()) {
+                                warn!("Ignoring cyclic pagination for {package_name} at: {page}");
+                                break;
+                            }
+                            trace!("Fetching next page of metadata for {package_name} from {page}");
+                            let SimplePage {
+                                url: page,
+                                next: next_url,
+                                body,
+                            } = self
+                                .fetch_simple_page(package_name, &page, cache_entry)
+                                .await?;
+                            let page = Url::parse(&page).map_err(ErrorKind::from)?;
+                            next = next_url
+                                .as_deref()
+                                .map(Url::parse)
+                                .transpose()
+                                .map_err(ErrorKind::from)?;
+                            let SimpleJson { files } = serde_json::from_slice(&body)
+                                .map_err(|err| Error::from_json_err(err, page.clone()))?;
+
+                            // Resolve any relative URLs against the page on which they appear.
+                            data.files.extend(files.into_iter().map(|mut file| {
+                                if let Ok(absolute) = page.join(&file.url) {
+                                    file.url = absolute.to_string();
+                                }
+                                file
+                            }));
+                        }
+                        data.files
+                            .sort_unstable_by(|f1, f2| f1.filename.cmp(&f2.filename));
+
                         SimpleMetadata::from_files(data.files, package_name, &url)
                     }
                     MediaType::Html => {
-                        let text = response.text().await.map_err(ErrorKind::from)?;
-                        SimpleMetadata::from_html(&text, package_name, &url)?
+                        if let Some(targets) = targets {
+                            let bytes = response.bytes().await.map_err(ErrorKind::from)?;
+                            SimpleMetadata::from_verified_html(&bytes, package_name, &url, targets)?
+                        } else {
+                            let text = response.text().await.map_err(ErrorKind::from)?;
+                            SimpleMetadata::from_html(&text, package_name, &url)?
+                        }
                     }
                 };
                 OwnedArchive::from_unarchived(&unarchived)
@@ -356,6 +544,54 @@ impl RegistryClient {
             })
     }
 
+    /// Fetch a subsequent page of a paginated JSON Simple API response.
+    ///
+    /// Pages are always revalidated, sending the `ETag` of the cached page (if any) in an
+    /// `If-None-Match` header.
+    async fn fetch_simple_page(
+        &self,
+        package_name: &PackageName,
+        url: &Url,
+        cache_entry: &CacheEntry,
+    ) -> Result<SimplePage, Error> {
+        let cache_entry = cache_entry.with_file(format!(
+            "{package_name}.{}.msgpack",
+            cache_key::cache_digest(&url.to_string())
+        ));
+        let page_request = self
+            .uncached_client()
+            .get(url.clone())
+            .header("Accept-Encoding", "gzip, br")
+            .header("Accept", "application/vnd.pypi.simple.v1+json")
+            .build()
+            .map_err(ErrorKind::from)?;
+        let parse_page_response = |response: Response| {
+            async {
+                let url = response.url().clone();
+                let next = next_page(response.headers(), &url);
+                let body = response.bytes().await.map_err(ErrorKind::from)?;
+                Ok::<SimplePage, Error>(SimplePage {
+                    url: url.to_string(),
+                    next: next.map(String::from),
+                    body: body.to_vec(),
+                })
+            }
+            .instrument(info_span!("parse_simple_page", package = %package_name))
+        };
+        self.cached_client()
+            .get_serde(
+                page_request,
+                &cache_entry,
+                CacheControl::MustRevalidate,
+                parse_page_response,
+            )
+            .await
+            .map_err(|err| match err {
+                CachedClientError::Client(err) => err,
+                CachedClientError::Callback(err) => err,
+            })
+    }
+
     /// Fetch the [`SimpleMetadata`] from a local file, using a PEP 503-compatible directory
     /// structure.
     async fn fetch_local_index(
@@ -484,10 +720,16 @@ impl RegistryClient {
             let mut url = url.clone();
             url.set_path(&format!("{}.metadata", url.path()));
 
+            // As with the Simple API, verified metadata is cached separately.
+            let trust = self.index_trust(index).await?;
             let cache_entry = self.cache.entry(
                 CacheBucket::Wheels,
                 WheelCache::Index(index).wheel_dir(filename.name.as_ref()),
-                format!("{}.msgpack", filename.stem()),
+                if trust.is_verified() {
+                    format!("{}.verified.msgpack", filename.stem())
+                } else {
+                    format!("{}.msgpack", filename.stem())
+                },
             );
             let cache_control = match self.connectivity {
                 Connectivity::Online => CacheControl::from(
@@ -501,6 +743,13 @@ impl RegistryClient {
             let response_callback = |response: Response| async {
                 let bytes = response.bytes().await.map_err(ErrorKind::from)?;
 
+                // Verify the metadata against its signed target.
+                if let Some(targets) = trust.targets() {
+                    targets.verify_target(&url, &bytes).map_err(|err| {
+                        Error::from(ErrorKind::IndexVerification(url.clone(), Box::new(err)))
+                    })?;
+                }
+
                 info_span!("parse_metadata21")
                     .in_scope(|| Metadata23::parse_metadata(bytes.as_ref()))
                     .map_err(|err| {
@@ -605,6 +854,36 @@ impl RegistryClient {
     }
 }
 
+/// Return the [`SimpleMetadata`] fetched from an index, or `None` if the package isn't available
+/// from the index (e.g., because it's missing, or because the index is inaccessible).
+fn available(
+    response: Result<OwnedArchive<SimpleMetadata>, Error>,
+) -> Result<Option<OwnedArchive<SimpleMetadata>>, Error> {
+    match response {
+        Ok(metadata) => Ok(Some(metadata)),
+        Err(err) => match err.into_kind() {
+            // The package is unavailable due to a lack of connectivity.
+            ErrorKind::Offline(_) => Ok(None),
+
+            // The package could not be found in the remote index.
+            ErrorKind::WrappedReqwestError(err) => {
+                if err.status() == Some(StatusCode::NOT_FOUND)
+                    || err.status() == Some(StatusCode::UNAUTHORIZED)
+                    || err.status() == Some(StatusCode::FORBIDDEN)
+                {
+                    return Ok(None);
+                }
+                Err(ErrorKind::from(err).into())
+            }
+
+            // The package could not be found in the local index.
+            ErrorKind::FileNotFound(_) => Ok(None),
+
+            other => Err(other.into()),
+        },
+    }
+}
+
 /// Read a wheel's `METADATA` file from a zip file.
 async fn read_metadata_async_seek(
     filename: &WheelFilename,
@@ -767,6 +1046,36 @@ impl SimpleMetadata {
     }
 }
 
+impl SimpleMetadata {
+    /// Read the [`SimpleMetadata`] from an HTML index, after verifying the page and its files
+    /// against the TUF targets of the index.
+    fn from_verified_html(
+        bytes: &[u8],
+        package_name: &PackageName,
+        url: &Url,
+        targets: &TrustedTargets,
+    ) -> Result<Self, Error> {
+        let verification_error =
+            |err: TufError| Error::from(ErrorKind::IndexVerification(url.clone(), Box::new(err)));
+        targets
+            .verify_target(url, bytes)
+            .map_err(verification_error)?;
+
+        let text = String::from_utf8_lossy(bytes);
+        let SimpleHtml { base, files } =
+            SimpleHtml::parse(&text, url).map_err(|err| Error::from_html_err(err, url.clone()))?;
+        let files = targets
+            .pin_files(files, base.as_url())
+            .map_err(verification_error)?;
+
+        Ok(SimpleMetadata::from_files(
+            files,
+            package_name,
+            base.as_url(),
+        ))
+    }
+}
+
 impl IntoIterator for SimpleMetadata {
     type Item = SimpleMetadatum;
     type IntoIter = std::vec::IntoIter<SimpleMetadatum>;
@@ -810,6 +1119,48 @@ impl MediaType {
     }
 }
 
+/// A subsequent page of a paginated JSON Simple API response, as cached alongside its `ETag`.
+#[derive(Debug, Serialize, Deserialize)]
+struct SimplePage {
+    /// The URL of the page, after following any redirects.
+    url: String,
+    /// The URL of the next page, if any.
+    next: Option<String>,
+    /// The raw JSON body of the page.
+    body: Vec<u8>,
+}
+
+/// Return the URL of the next page of a paginated Simple API response, as advertised by a `Link`
+/// header with `rel="next"`.
+///
+/// See: <https://www.rfc-editor.org/rfc/rfc8288#section-3>
+fn next_page(headers: &HeaderMap, base: &Url) -> Option<Url> {
+    headers
+        .get_all(http::header::LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let (target, params) = link.trim().split_once(';')?;
+            let target = target.trim().strip_prefix('<')?.strip_suffix('>')?;
+            let next = params.split(';').any(|param| {
+                param.split_once('=').is_some_and(|(key, value)| {
+                    key.trim().eq_ignore_ascii_case("rel")
+                        && value
+                            .trim()
+                            .trim_matches('"')
+                            .split_ascii_whitespace()
+                            .any(|rel| rel.eq_ignore_ascii_case("next"))
+                })
+            });
+            if next {
+                base.join(target).ok()
+            } else {
+                None
+            }
+        })
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
 pub enum Connectivity {
     /// Allow access to the network.
@@ -934,4 +1285,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn next_page() {
+        let base = Url::parse("https://example.org/simple/botocore/").unwrap();
+
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::LINK,
+            http::HeaderValue::from_static(
+                r#"<https://example.org/simple/botocore/?page=1>; rel="prev", <?page=3>; rel="next""#,
+            ),
+        );
+        assert_eq!(
+            super::next_page(&headers, &base)
+                .map(String::from)
+                .as_deref(),
+            Some("https://example.org/simple/botocore/?page=3")
+        );
+
+        // Without a `next` relation, there's no next page.
+        headers.insert(
+            http::header::LINK,
+            http::HeaderValue::from_static(r#"<https://example.org/simple/>; rel="index""#),
+        );
+        assert_eq!(super::next_page(&headers, &base), None);
+    }
 }
@@ -0,0 +1,1008 @@
+//! Verification of Simple API responses against [TUF](https://theupdateframework.io/) metadata,
+//! as published by indexes that implement [PEP 458](https://peps.python.org/pep-0458/).
+//!
+//! The metadata is expected under `tuf/`, alongside the index (e.g., `https://example.com/tuf/`
+//! for `https://example.com/simple/`). The first root metadata is trusted on first use and pinned
+//! in a trust store outside the cache, such that clearing the cache doesn't reset trust;
+//! subsequent roots must be signed by the previously trusted root. The versions of the other
+//! top-level roles are recorded alongside the root, to reject rollbacks to older metadata.
+//!
+//! Only the top-level `targets` role is supported (i.e., delegations are not followed), and
+//! only `ed25519` keys are recognized when counting signatures against a threshold.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+use url::Url;
+
+use uv_configuration::IndexVerification;
+
+use crate::base_client::BaseClient;
+use crate::{Error, ErrorKind};
+
+static TUF_SETTINGS: OnceLock<TufSettings> = OnceLock::new();
+
+/// The settings for verifying index responses against TUF metadata.
+///
+/// The settings are process-wide: once installed via [`TufSettings::init`], they apply to every
+/// [`crate::RegistryClient`] that isn't configured with its own settings.
+#[derive(Debug, Default, Clone)]
+pub struct TufSettings {
+    /// The verification policy.
+    pub(crate) policy: IndexVerification,
+    /// The directory in which the trusted root (and the versions of the other roles) of each
+    /// index are persisted.
+    pub(crate) trust_dir: PathBuf,
+}
+
+impl TufSettings {
+    /// Create a set of [`TufSettings`], persisting trusted metadata to `trust_dir`.
+    pub fn new(policy: IndexVerification, trust_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            policy,
+            trust_dir: trust_dir.into(),
+        }
+    }
+
+    /// Install the settings for all clients created by this process.
+    ///
+    /// Has no effect if the settings were already installed.
+    pub fn init(self) {
+        let _ = TUF_SETTINGS.set(self);
+    }
+
+    /// Returns the installed settings, if any.
+    pub(crate) fn get() -> Option<&'static Self> {
+        TUF_SETTINGS.get()
+    }
+
+    /// Return the directory that holds the trusted metadata of the given index.
+    pub(crate) fn index_dir(&self, index: &Url) -> PathBuf {
+        self.trust_dir
+            .join(cache_key::cache_digest(&index.to_string()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TufError {
+    #[error(
+        "The index doesn't publish TUF metadata (at: {0}), but index verification is required"
+    )]
+    Unavailable(Url),
+    #[error("The `{role}` metadata was rolled back from version {trusted} to version {found}")]
+    Rollback {
+        role: Role,
+        trusted: u64,
+        found: u64,
+    },
+    #[error("The TUF metadata at {0} can't be represented in canonical JSON: {1}")]
+    NonCanonical(Url, String),
+    #[error("Received invalid TUF metadata from: {0}")]
+    Parse(Url, #[source] serde_json::Error),
+    #[error("Expected `{expected}` metadata at {url}, but found `{found}`")]
+    Type {
+        url: Url,
+        expected: Role,
+        found: String,
+    },
+    #[error("The `{role}` metadata is signed by {valid} trusted key(s), but requires a threshold of {threshold}")]
+    Threshold {
+        role: Role,
+        valid: usize,
+        threshold: usize,
+    },
+    #[error("The trusted root metadata doesn't define the `{0}` role")]
+    MissingRole(Role),
+    #[error("The `{0}` metadata expired at {1}")]
+    Expired(Role, DateTime<Utc>),
+    #[error("Expected version {expected} of the `{role}` metadata, but found version {found}")]
+    Version {
+        role: Role,
+        expected: u64,
+        found: u64,
+    },
+    #[error("The `{0}` metadata isn't listed in the `{1}` metadata")]
+    MissingMeta(Role, Role),
+    #[error("The `{0}` metadata doesn't match the length or hash recorded in the `{1}` metadata")]
+    MetaMismatch(Role, Role),
+    #[error("`{0}` isn't listed in the signed targets of the index")]
+    UnknownTarget(String),
+    #[error("`{path}` doesn't match its signed target (expected SHA-256 `{expected}`, but found `{actual}`)")]
+    TargetMismatch {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("The signed target for `{0}` doesn't include a SHA-256 hash")]
+    MissingHash(String),
+    #[error("The Simple API response from {0} is JSON, but only HTML responses can be verified")]
+    UnsupportedResponse(Url),
+}
+
+/// A top-level TUF role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Root,
+    Timestamp,
+    Snapshot,
+    Targets,
+}
+
+impl Role {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Root => "root",
+            Self::Timestamp => "timestamp",
+            Self::Snapshot => "snapshot",
+            Self::Targets => "targets",
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A signed metadata file, prior to verification.
+#[derive(Debug, Deserialize)]
+struct Envelope {
+    signatures: Vec<Signature>,
+    signed: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct Signature {
+    keyid: String,
+    sig: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Root {
+    #[serde(rename = "_type")]
+    kind: String,
+    version: u64,
+    expires: DateTime<Utc>,
+    #[serde(default)]
+    consistent_snapshot: bool,
+    keys: BTreeMap<String, Key>,
+    roles: BTreeMap<String, RoleKeys>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Key {
+    keytype: String,
+    scheme: String,
+    keyval: KeyValue,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyValue {
+    public: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RoleKeys {
+    keyids: Vec<String>,
+    threshold: usize,
+}
+
+/// The `timestamp` and `snapshot` metadata, which record the versions (and, optionally, the
+/// hashes) of other metadata files.
+#[derive(Debug, Deserialize)]
+struct MetaIndex {
+    #[serde(rename = "_type")]
+    kind: String,
+    version: u64,
+    expires: DateTime<Utc>,
+    meta: BTreeMap<String, MetaFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetaFile {
+    version: u64,
+    length: Option<u64>,
+    hashes: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Targets {
+    #[serde(rename = "_type")]
+    kind: String,
+    version: u64,
+    expires: DateTime<Utc>,
+    targets: BTreeMap<String, TargetFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TargetFile {
+    length: u64,
+    hashes: BTreeMap<String, String>,
+}
+
+impl TargetFile {
+    fn sha256(&self, path: &str) -> Result<&str, TufError> {
+        self.hashes
+            .get("sha256")
+            .map(String::as_str)
+            .ok_or_else(|| TufError::MissingHash(path.to_string()))
+    }
+}
+
+/// A metadata file that declares its role and expiry.
+trait Metadata: DeserializeOwned {
+    fn kind(&self) -> &str;
+    fn version(&self) -> u64;
+    fn expires(&self) -> DateTime<Utc>;
+}
+
+macro_rules! impl_metadata {
+    ($($ty:ty),*) => {
+        $(
+            impl Metadata for $ty {
+                fn kind(&self) -> &str {
+                    &self.kind
+                }
+
+                fn version(&self) -> u64 {
+                    self.version
+                }
+
+                fn expires(&self) -> DateTime<Utc> {
+                    self.expires
+                }
+            }
+        )*
+    };
+}
+
+impl_metadata!(Root, MetaIndex, Targets);
+
+impl Root {
+    /// Verify that the `signed` payload carries a threshold of valid signatures from the keys
+    /// that this root assigns to the given role.
+    fn verify_signatures(
+        &self,
+        role: Role,
+        message: &[u8],
+        signatures: &[Signature],
+    ) -> Result<(), TufError> {
+        let keys = self
+            .roles
+            .get(role.as_str())
+            .ok_or(TufError::MissingRole(role))?;
+
+        // Each key counts at most once towards the threshold.
+        let mut valid = HashSet::new();
+        for signature in signatures {
+            if !keys.keyids.contains(&signature.keyid) {
+                continue;
+            }
+            let Some(key) = self.keys.get(&signature.keyid) else {
+                continue;
+            };
+            if key.verify(message, &signature.sig) {
+                valid.insert(signature.keyid.as_str());
+            }
+        }
+
+        if valid.len() < keys.threshold.max(1) {
+            return Err(TufError::Threshold {
+                role,
+                valid: valid.len(),
+                threshold: keys.threshold,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Key {
+    /// Returns `true` if the signature is a valid signature of the message by this key.
+    fn verify(&self, message: &[u8], signature: &str) -> bool {
+        match (self.keytype.as_str(), self.scheme.as_str()) {
+            ("ed25519", "ed25519") => {
+                let (Ok(public), Ok(signature)) =
+                    (hex::decode(&self.keyval.public), hex::decode(signature))
+                else {
+                    return false;
+                };
+                UnparsedPublicKey::new(&ED25519, public)
+                    .verify(message, &signature)
+                    .is_ok()
+            }
+            (keytype, scheme) => {
+                debug!("Ignoring TUF key with unsupported type `{keytype}` (scheme: `{scheme}`)");
+                false
+            }
+        }
+    }
+}
+
+impl MetaFile {
+    /// Returns `true` if the bytes match the length and hashes recorded for the file.
+    fn matches(&self, bytes: &[u8]) -> bool {
+        if self
+            .length
+            .is_some_and(|length| length != bytes.len() as u64)
+        {
+            return false;
+        }
+        match self.hashes.as_ref().and_then(|hashes| hashes.get("sha256")) {
+            Some(expected) => *expected == sha256(bytes),
+            None => true,
+        }
+    }
+}
+
+/// The trust established for an index.
+#[derive(Debug, Clone)]
+pub(crate) enum IndexTrust {
+    /// Responses from the index aren't verified.
+    Unverified,
+    /// The TUF metadata of the index was verified, and responses must match its signed targets.
+    Verified(Arc<TrustedTargets>),
+    /// The index is verified, but its TUF metadata can't be fetched in offline mode, so only
+    /// responses that were cached after verification may be used.
+    Offline,
+}
+
+impl IndexTrust {
+    /// Returns `true` if responses from the index must be verified.
+    pub(crate) fn is_verified(&self) -> bool {
+        !matches!(self, Self::Unverified)
+    }
+
+    /// Return the signed targets against which to verify responses, if available.
+    pub(crate) fn targets(&self) -> Option<&TrustedTargets> {
+        match self {
+            Self::Verified(targets) => Some(targets),
+            Self::Unverified | Self::Offline => None,
+        }
+    }
+}
+
+/// The versions of the `timestamp`, `snapshot`, and `targets` metadata that were last trusted for
+/// an index. Newer metadata must never regress to an older version.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct TrustedVersions {
+    timestamp: u64,
+    snapshot: u64,
+    targets: u64,
+}
+
+/// The signed targets of an index, after verifying the chain of TUF metadata.
+#[derive(Debug)]
+pub(crate) struct TrustedTargets {
+    targets: BTreeMap<String, TargetFile>,
+}
+
+impl TrustedTargets {
+    /// Fetch and verify the TUF metadata published alongside the given index.
+    ///
+    /// The trusted root, and the versions of the other top-level roles, are read from (and, once
+    /// updated, written back to) `trust_dir`. Returns `None` if the index doesn't publish TUF
+    /// metadata.
+    pub(crate) async fn fetch(
+        client: &BaseClient,
+        index: &Url,
+        trust_dir: &Path,
+    ) -> Result<Option<Self>, Error> {
+        let base = index.join("../tuf/").map_err(ErrorKind::from)?;
+        let verification_error =
+            |err: TufError| Error::from(ErrorKind::IndexVerification(index.clone(), Box::new(err)));
+
+        // Load the trusted root, or trust the initial root on first use.
+        let root_path = trust_dir.join("root.json");
+        let mut root = match fs_err::tokio::read(&root_path).await {
+            Ok(bytes) => {
+                let url = Url::from_file_path(&root_path).unwrap_or_else(|()| base.clone());
+                verify_root(&bytes, &url, None).map_err(verification_error)?
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                let url = base.join("1.root.json").map_err(ErrorKind::from)?;
+                let Some(bytes) = get(client, &url).await? else {
+                    return Ok(None);
+                };
+                let root = verify_root(&bytes, &url, None).map_err(verification_error)?;
+                warn!("Trusting the initial TUF root of {index} on first use");
+                write_trusted(&root_path, &bytes).await?;
+                root
+            }
+            Err(err) => return Err(ErrorKind::Io(err).into()),
+        };
+
+        // Walk the chain of root updates, each of which must be signed by its predecessor.
+        loop {
+            let url = base
+                .join(&format!("{}.root.json", root.version + 1))
+                .map_err(ErrorKind::from)?;
+            let Some(bytes) = get(client, &url).await? else {
+                break;
+            };
+            let next = verify_root(&bytes, &url, Some(&root)).map_err(verification_error)?;
+            debug!("Updated TUF root of {index} to version {}", next.version);
+            write_trusted(&root_path, &bytes).await?;
+            root = next;
+        }
+        check_expiry(&root, Role::Root).map_err(verification_error)?;
+
+        // Load the versions of the metadata that were trusted previously, to detect rollbacks.
+        let versions_path = trust_dir.join("versions.json");
+        let trusted = match fs_err::tokio::read(&versions_path).await {
+            Ok(bytes) => serde_json::from_slice::<TrustedVersions>(&bytes).unwrap_or_else(|err| {
+                warn!("Ignoring invalid trusted TUF versions for {index}: {err}");
+                TrustedVersions::default()
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => TrustedVersions::default(),
+            Err(err) => return Err(ErrorKind::Io(err).into()),
+        };
+
+        // The timestamp metadata is always fetched by name, and points to the current snapshot.
+        let url = base.join("timestamp.json").map_err(ErrorKind::from)?;
+        let bytes = get(client, &url).await?.ok_or_else(|| {
+            verification_error(TufError::MissingMeta(Role::Timestamp, Role::Root))
+        })?;
+        let timestamp = verify::<MetaIndex>(&bytes, &url, Role::Timestamp, &[&root])
+            .map_err(verification_error)?;
+        check_rollback(Role::Timestamp, trusted.timestamp, timestamp.version)
+            .map_err(verification_error)?;
+        check_expiry(&timestamp, Role::Timestamp).map_err(verification_error)?;
+
+        let snapshot: MetaIndex = fetch_meta(
+            client,
+            &base,
+            &root,
+            &timestamp,
+            (Role::Timestamp, Role::Snapshot),
+        )
+        .await?
+        .map_err(verification_error)?;
+        check_rollback(Role::Snapshot, trusted.snapshot, snapshot.version)
+            .map_err(verification_error)?;
+
+        let targets: Targets = fetch_meta(
+            client,
+            &base,
+            &root,
+            &snapshot,
+            (Role::Snapshot, Role::Targets),
+        )
+        .await?
+        .map_err(verification_error)?;
+        check_rollback(Role::Targets, trusted.targets, targets.version)
+            .map_err(verification_error)?;
+
+        // Record the versions that are now trusted.
+        let versions = TrustedVersions {
+            timestamp: timestamp.version,
+            snapshot: snapshot.version,
+            targets: targets.version,
+        };
+        if versions != trusted {
+            let bytes = serde_json::to_vec(&versions).expect("versions are serializable");
+            write_trusted(&versions_path, &bytes).await?;
+        }
+
+        Ok(Some(Self {
+            targets: targets.targets,
+        }))
+    }
+
+    /// Verify the body of a response (e.g., a Simple API page, or the `.metadata` file of a
+    /// wheel) against its signed target.
+    pub(crate) fn verify_target(&self, url: &Url, bytes: &[u8]) -> Result<(), TufError> {
+        let path = target_path(url);
+        let target = self
+            .targets
+            .get(&path)
+            .ok_or_else(|| TufError::UnknownTarget(path.clone()))?;
+        let expected = target.sha256(&path)?;
+        let actual = sha256(bytes);
+        if target.length != bytes.len() as u64 || expected != actual {
+            return Err(TufError::TargetMismatch {
+                path,
+                expected: expected.to_string(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Pin the files listed on a verified Simple API page to their signed targets.
+    ///
+    /// Files that aren't listed in the targets are omitted. The signed SHA-256 hash and length
+    /// replace those advertised by the page, such that they're enforced on download whenever
+    /// hashes are validated.
+    pub(crate) fn pin_files(
+        &self,
+        files: Vec<pypi_types::File>,
+        base: &Url,
+    ) -> Result<Vec<pypi_types::File>, TufError> {
+        let mut pinned = Vec::with_capacity(files.len());
+        for mut file in files {
+            let Ok(url) = base.join(&file.url) else {
+                continue;
+            };
+            let path = target_path(&url);
+            let Some(target) = self.targets.get(&path) else {
+                warn!(
+                    "Skipping `{}`, which isn't listed in the signed targets of the index",
+                    file.filename
+                );
+                continue;
+            };
+            let expected = target.sha256(&path)?;
+            if let Some(advertised) = file.hashes.sha256.as_deref() {
+                if advertised != expected {
+                    return Err(TufError::TargetMismatch {
+                        path,
+                        expected: expected.to_string(),
+                        actual: advertised.to_string(),
+                    });
+                }
+            }
+            file.hashes = pypi_types::Hashes {
+                sha256: Some(expected.into()),
+                ..pypi_types::Hashes::default()
+            };
+            file.size = Some(target.length);
+            pinned.push(file);
+        }
+        Ok(pinned)
+    }
+}
+
+/// Fetch the metadata for `role`, as recorded in the `parent` metadata, and verify it against
+/// the root.
+async fn fetch_meta<T: Metadata>(
+    client: &BaseClient,
+    base: &Url,
+    root: &Root,
+    parent: &MetaIndex,
+    (parent_role, role): (Role, Role),
+) -> Result<Result<T, TufError>, Error> {
+    let Some(meta) = parent.meta.get(&format!("{role}.json")) else {
+        return Ok(Err(TufError::MissingMeta(role, parent_role)));
+    };
+
+    // With consistent snapshots, metadata files are prefixed with their version.
+    let name = if root.consistent_snapshot {
+        format!("{}.{role}.json", meta.version)
+    } else {
+        format!("{role}.json")
+    };
+    let url = base.join(&name).map_err(ErrorKind::from)?;
+    let Some(bytes) = get(client, &url).await? else {
+        return Ok(Err(TufError::MissingMeta(role, parent_role)));
+    };
+
+    Ok(verify_meta(&bytes, &url, root, meta, (parent_role, role)))
+}
+
+/// Verify a metadata file against the root, and against its entry in the `parent` metadata.
+fn verify_meta<T: Metadata>(
+    bytes: &[u8],
+    url: &Url,
+    root: &Root,
+    meta: &MetaFile,
+    (parent_role, role): (Role, Role),
+) -> Result<T, TufError> {
+    if !meta.matches(bytes) {
+        return Err(TufError::MetaMismatch(role, parent_role));
+    }
+    let metadata = verify::<T>(bytes, url, role, &[root])?;
+    if metadata.version() != meta.version {
+        return Err(TufError::Version {
+            role,
+            expected: meta.version,
+            found: metadata.version(),
+        });
+    }
+    check_expiry(&metadata, role)?;
+    Ok(metadata)
+}
+
+/// Verify a root metadata file, which must be signed by both its predecessor (if any) and itself.
+fn verify_root(bytes: &[u8], url: &Url, previous: Option<&Root>) -> Result<Root, TufError> {
+    let envelope = parse_envelope(bytes, url)?;
+    let message = canonical_json(&envelope.signed, url)?;
+    let root: Root =
+        serde_json::from_value(envelope.signed).map_err(|err| TufError::Parse(url.clone(), err))?;
+    check_kind(&root, url, Role::Root)?;
+
+    if let Some(previous) = previous {
+        previous.verify_signatures(Role::Root, &message, &envelope.signatures)?;
+        if root.version != previous.version + 1 {
+            return Err(TufError::Version {
+                role: Role::Root,
+                expected: previous.version + 1,
+                found: root.version,
+            });
+        }
+    }
+    root.verify_signatures(Role::Root, &message, &envelope.signatures)?;
+
+    Ok(root)
+}
+
+/// Parse a metadata file, verifying its signatures against each of the given roots.
+fn verify<T: Metadata>(
+    bytes: &[u8],
+    url: &Url,
+    role: Role,
+    roots: &[&Root],
+) -> Result<T, TufError> {
+    let envelope = parse_envelope(bytes, url)?;
+    let message = canonical_json(&envelope.signed, url)?;
+    for root in roots {
+        root.verify_signatures(role, &message, &envelope.signatures)?;
+    }
+    let metadata: T =
+        serde_json::from_value(envelope.signed).map_err(|err| TufError::Parse(url.clone(), err))?;
+    check_kind(&metadata, url, role)?;
+    Ok(metadata)
+}
+
+fn parse_envelope(bytes: &[u8], url: &Url) -> Result<Envelope, TufError> {
+    serde_json::from_slice(bytes).map_err(|err| TufError::Parse(url.clone(), err))
+}
+
+fn check_kind(metadata: &impl Metadata, url: &Url, role: Role) -> Result<(), TufError> {
+    if metadata.kind() != role.as_str() {
+        return Err(TufError::Type {
+            url: url.clone(),
+            expected: role,
+            found: metadata.kind().to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn check_rollback(role: Role, trusted: u64, found: u64) -> Result<(), TufError> {
+    if found < trusted {
+        return Err(TufError::Rollback {
+            role,
+            trusted,
+            found,
+        });
+    }
+    Ok(())
+}
+
+fn check_expiry(metadata: &impl Metadata, role: Role) -> Result<(), TufError> {
+    if metadata.expires() <= Utc::now() {
+        return Err(TufError::Expired(role, metadata.expires()));
+    }
+    Ok(())
+}
+
+/// Serialize the `signed` payload in the [OLPC canonical JSON](http://wiki.laptop.org/go/Canonical_JSON)
+/// form over which TUF signatures are computed.
+///
+/// Object keys are sorted by their UTF-8 bytes, no insignificant whitespace is emitted, and only
+/// `"` and `\` are escaped within strings. Floating-point numbers can't be represented.
+fn canonical_json(value: &serde_json::Value, url: &Url) -> Result<Vec<u8>, TufError> {
+    fn write_string(string: &str, out: &mut Vec<u8>) {
+        out.push(b'"');
+        for byte in string.bytes() {
+            if matches!(byte, b'"' | b'\\') {
+                out.push(b'\\');
+            }
+            out.push(byte);
+        }
+        out.push(b'"');
+    }
+
+    fn write(value: &serde_json::Value, url: &Url, out: &mut Vec<u8>) -> Result<(), TufError> {
+        match value {
+            serde_json::Value::Null => out.extend_from_slice(b"null"),
+            serde_json::Value::Bool(true) => out.extend_from_slice(b"true"),
+            serde_json::Value::Bool(false) => out.extend_from_slice(b"false"),
+            serde_json::Value::Number(number) => {
+                if !(number.is_i64() || number.is_u64()) {
+                    return Err(TufError::NonCanonical(url.clone(), number.to_string()));
+                }
+                out.extend_from_slice(number.to_string().as_bytes());
+            }
+            serde_json::Value::String(string) => write_string(string, out),
+            serde_json::Value::Array(items) => {
+                out.push(b'[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push(b',');
+                    }
+                    write(item, url, out)?;
+                }
+                out.push(b']');
+            }
+            serde_json::Value::Object(map) => {
+                let mut entries = map.iter().collect::<Vec<_>>();
+                entries.sort_unstable_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+                out.push(b'{');
+                for (index, (key, value)) in entries.into_iter().enumerate() {
+                    if index > 0 {
+                        out.push(b',');
+                    }
+                    write_string(key, out);
+                    out.push(b':');
+                    write(value, url, out)?;
+                }
+                out.push(b'}');
+            }
+        }
+        Ok(())
+    }
+
+    let mut out = Vec::new();
+    write(value, url, &mut out)?;
+    Ok(out)
+}
+
+/// Return the TUF target path of a URL on the index, i.e., its path without the leading slash.
+///
+/// Directory URLs (like Simple API pages) map to their `index.html`.
+fn target_path(url: &Url) -> String {
+    let path = url.path().trim_start_matches('/');
+    if path.is_empty() || path.ends_with('/') {
+        format!("{path}index.html")
+    } else {
+        path.to_string()
+    }
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Fetch a metadata file, returning `None` if the index doesn't publish it.
+async fn get(client: &BaseClient, url: &Url) -> Result<Option<Vec<u8>>, Error> {
+    let response = client
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(ErrorKind::from)?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let bytes = response
+        .error_for_status()
+        .map_err(ErrorKind::from)?
+        .bytes()
+        .await
+        .map_err(ErrorKind::from)?;
+    Ok(Some(bytes.to_vec()))
+}
+
+/// Persist trusted metadata to the trust store.
+async fn write_trusted(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        fs_err::tokio::create_dir_all(parent)
+            .await
+            .map_err(ErrorKind::Io)?;
+    }
+    uv_fs::write_atomic(path, bytes)
+        .await
+        .map_err(ErrorKind::Io)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+    use serde_json::json;
+
+    use super::*;
+
+    fn keypair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    fn sign(signed: &serde_json::Value, keys: &[(&str, &Ed25519KeyPair)]) -> Vec<u8> {
+        let url = Url::parse("https://example.com/tuf/").unwrap();
+        let message = canonical_json(signed, &url).unwrap();
+        let signatures = keys
+            .iter()
+            .map(|(keyid, key)| json!({ "keyid": keyid, "sig": hex::encode(key.sign(&message)) }))
+            .collect::<Vec<_>>();
+        serde_json::to_vec(&json!({ "signatures": signatures, "signed": signed })).unwrap()
+    }
+
+    fn root(version: u64, keys: &[(&str, &Ed25519KeyPair)], threshold: usize) -> serde_json::Value {
+        let keyids = keys.iter().map(|(keyid, _)| *keyid).collect::<Vec<_>>();
+        let role = json!({ "keyids": keyids, "threshold": threshold });
+        json!({
+            "_type": "root",
+            "version": version,
+            "expires": "2100-01-01T00:00:00Z",
+            "consistent_snapshot": false,
+            "keys": keys.iter().map(|(keyid, key)| {
+                (keyid.to_string(), json!({
+                    "keytype": "ed25519",
+                    "scheme": "ed25519",
+                    "keyval": { "public": hex::encode(key.public_key().as_ref()) },
+                }))
+            }).collect::<serde_json::Map<_, _>>(),
+            "roles": {
+                "root": role,
+                "timestamp": role,
+                "snapshot": role,
+                "targets": role,
+            },
+        })
+    }
+
+    #[test]
+    fn threshold() {
+        let url = Url::parse("https://example.com/tuf/1.root.json").unwrap();
+        let (a, b) = (keypair(), keypair());
+        let signed = root(1, &[("a", &a), ("b", &b)], 2);
+
+        // Both keys sign, meeting the threshold.
+        let bytes = sign(&signed, &[("a", &a), ("b", &b)]);
+        assert!(verify_root(&bytes, &url, None).is_ok());
+
+        // A repeated signature from the same key doesn't count twice.
+        let bytes = sign(&signed, &[("a", &a), ("a", &a)]);
+        assert!(matches!(
+            verify_root(&bytes, &url, None),
+            Err(TufError::Threshold { valid: 1, .. })
+        ));
+
+        // A signature over a different payload is rejected.
+        let mut tampered = signed.clone();
+        tampered["version"] = json!(2);
+        let bytes = serde_json::to_vec(&json!({
+            "signatures": serde_json::from_slice::<serde_json::Value>(&sign(&signed, &[("a", &a), ("b", &b)])).unwrap()["signatures"],
+            "signed": tampered,
+        }))
+        .unwrap();
+        assert!(matches!(
+            verify_root(&bytes, &url, None),
+            Err(TufError::Threshold { valid: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn root_rotation() {
+        let url = Url::parse("https://example.com/tuf/2.root.json").unwrap();
+        let (old, new) = (keypair(), keypair());
+        let bytes = sign(&root(1, &[("old", &old)], 1), &[("old", &old)]);
+        let previous = verify_root(&bytes, &url, None).unwrap();
+
+        // The new root must be signed by the previous root's keys, as well as its own.
+        let signed = root(2, &[("new", &new)], 1);
+        let bytes = sign(&signed, &[("new", &new)]);
+        assert!(matches!(
+            verify_root(&bytes, &url, Some(&previous)),
+            Err(TufError::Threshold {
+                role: Role::Root,
+                ..
+            })
+        ));
+        let bytes = sign(&signed, &[("old", &old), ("new", &new)]);
+        assert!(verify_root(&bytes, &url, Some(&previous)).is_ok());
+
+        // Versions can't be skipped.
+        let signed = root(3, &[("new", &new)], 1);
+        let bytes = sign(&signed, &[("old", &old), ("new", &new)]);
+        assert!(matches!(
+            verify_root(&bytes, &url, Some(&previous)),
+            Err(TufError::Version {
+                expected: 2,
+                found: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn targets() {
+        let page = b"<a href=\"../../packages/anyio-4.3.0-py3-none-any.whl#sha256=abc\">anyio</a>";
+        let wheel = "packages/anyio-4.3.0-py3-none-any.whl";
+        let trusted = TrustedTargets {
+            targets: BTreeMap::from([
+                (
+                    "simple/anyio/index.html".to_string(),
+                    TargetFile {
+                        length: page.len() as u64,
+                        hashes: BTreeMap::from([("sha256".to_string(), sha256(page))]),
+                    },
+                ),
+                (
+                    wheel.to_string(),
+                    TargetFile {
+                        length: 42,
+                        hashes: BTreeMap::from([("sha256".to_string(), "abc".to_string())]),
+                    },
+                ),
+            ]),
+        };
+
+        let url = Url::parse("https://example.com/simple/anyio/").unwrap();
+        assert!(trusted.verify_target(&url, page).is_ok());
+        assert!(matches!(
+            trusted.verify_target(&url, b"tampered"),
+            Err(TufError::TargetMismatch { .. })
+        ));
+        let url = Url::parse("https://example.com/simple/sniffio/").unwrap();
+        assert!(matches!(
+            trusted.verify_target(&url, page),
+            Err(TufError::UnknownTarget(path)) if path == "simple/sniffio/index.html"
+        ));
+
+        let file = |name: &str, sha256: &str| pypi_types::File {
+            core_metadata: None,
+            dist_info_metadata: None,
+            data_dist_info_metadata: None,
+            filename: name.to_string(),
+            hashes: pypi_types::Hashes {
+                sha256: Some(sha256.into()),
+                ..pypi_types::Hashes::default()
+            },
+            requires_python: None,
+            size: None,
+            upload_time: None,
+            url: format!("../../packages/{name}"),
+            yanked: None,
+        };
+
+        // Unsigned files are omitted, and signed files are pinned to their targets.
+        let base = Url::parse("https://example.com/simple/anyio/").unwrap();
+        let files = vec![
+            file("anyio-4.3.0-py3-none-any.whl", "abc"),
+            file("anyio-4.3.0.tar.gz", "def"),
+        ];
+        let pinned = trusted.pin_files(files, &base).unwrap();
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned[0].size, Some(42));
+
+        // Files that advertise a different hash are rejected.
+        let files = vec![file("anyio-4.3.0-py3-none-any.whl", "def")];
+        assert!(matches!(
+            trusted.pin_files(files, &base),
+            Err(TufError::TargetMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn canonical() {
+        let url = Url::parse("https://example.com/tuf/1.root.json").unwrap();
+        let value =
+            json!({ "b": "quote \" backslash \\ newline \n", "a": [1, true, null], "B": {} });
+        assert_eq!(
+            String::from_utf8(canonical_json(&value, &url).unwrap()).unwrap(),
+            "{\"B\":{},\"a\":[1,true,null],\"b\":\"quote \\\" backslash \\\\ newline \n\"}"
+        );
+        assert!(matches!(
+            canonical_json(&json!({ "version": 1.5 }), &url),
+            Err(TufError::NonCanonical(..))
+        ));
+    }
+
+    #[test]
+    fn rollback() {
+        assert!(check_rollback(Role::Snapshot, 2, 2).is_ok());
+        assert!(check_rollback(Role::Snapshot, 2, 3).is_ok());
+        assert!(matches!(
+            check_rollback(Role::Snapshot, 2, 1),
+            Err(TufError::Rollback {
+                role: Role::Snapshot,
+                trusted: 2,
+                found: 1,
+            })
+        ));
+    }
+}
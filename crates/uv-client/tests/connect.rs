@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use futures::future;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::HOST;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexResolve, IndexUrl};
+use uv_cache::Cache;
+use uv_client::{ConnectOverrides, RegistryClientBuilder};
+
+/// Requests to the host of an index with a fixed address connect to that address, without
+/// resolving the hostname.
+#[tokio::test]
+async fn test_index_resolve() -> Result<()> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Spawn the server loop in a background task
+    let server_task = tokio::spawn(async move {
+        let svc = service_fn(move |req: Request<hyper::body::Incoming>| {
+            // Get the Host header and send it back in the response
+            let host = req
+                .headers()
+                .get(HOST)
+                .and_then(|v| v.to_str().ok())
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            future::ok::<_, hyper::Error>(Response::new(Full::new(Bytes::from(host))))
+        });
+        let (socket, _) = listener.accept().await.unwrap();
+        let socket = TokioIo::new(socket);
+        tokio::task::spawn(async move {
+            http1::Builder::new()
+                .serve_connection(socket, svc)
+                .with_upgrades()
+                .await
+                .expect("Server Started");
+        });
+    });
+
+    // The `.invalid` TLD is reserved, and guaranteed to never resolve.
+    let index = format!("http://pypi.invalid:{}/simple", addr.port());
+    ConnectOverrides::new(
+        None,
+        IndexResolve::from_iter([(IndexUrl::from_str(&index)?, addr.ip())]),
+    )
+    .init();
+
+    // Initialize uv-client
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache).build();
+
+    // Send a request to the index, which should reach our dummy server
+    let res = client
+        .cached_client()
+        .uncached()
+        .get(format!("{index}/"))
+        .send()
+        .await?;
+
+    // Check the HTTP status
+    assert!(res.status().is_success());
+
+    // The request should retain the hostname of the index
+    let body = res.text().await?;
+    assert_eq!(body, format!("pypi.invalid:{}", addr.port()));
+
+    // Wait for the server task to complete, to be a good citizen.
+    server_task.await?;
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use futures::future;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::CONTENT_TYPE;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexLocations, IndexMirrors, IndexUrl};
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+use uv_normalize::PackageName;
+
+/// Requests to an index that returns a server error fail over to its mirror, and the results are
+/// attributed to the original index.
+#[tokio::test]
+async fn test_index_mirror_failover() -> Result<()> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Spawn the server loop in a background task, serving the primary index with a server error
+    // and the mirror with a single distribution.
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let socket = TokioIo::new(socket);
+            tokio::task::spawn(async move {
+                let svc = service_fn(move |req: Request<hyper::body::Incoming>| {
+                    let response = if req.uri().path().starts_with("/mirror/") {
+                        Response::builder()
+                            .header(CONTENT_TYPE, "text/html")
+                            .body(Full::new(Bytes::from(
+                                r#"<html><body><a href="anyio-4.3.0.tar.gz">anyio-4.3.0.tar.gz</a></body></html>"#,
+                            )))
+                            .unwrap()
+                    } else {
+                        Response::builder()
+                            .status(StatusCode::SERVICE_UNAVAILABLE)
+                            .body(Full::new(Bytes::new()))
+                            .unwrap()
+                    };
+                    future::ok::<_, hyper::Error>(response)
+                });
+                http1::Builder::new()
+                    .serve_connection(socket, svc)
+                    .await
+                    .expect("Server Started");
+            });
+        }
+    });
+
+    let primary = IndexUrl::from_str(&format!("http://{addr}/primary/simple"))?;
+    let mirror = IndexUrl::from_str(&format!("http://{addr}/mirror/simple"))?;
+    let index_locations = IndexLocations::new(Some(primary.clone()), vec![], vec![], false)
+        .with_mirrors(IndexMirrors::from_iter([(primary.clone(), vec![mirror])]));
+
+    // Initialize uv-client
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache)
+        .index_urls(index_locations.index_urls())
+        .retries(0)
+        .build();
+
+    let results = client.simple(&PackageName::from_str("anyio")?).await?;
+    assert_eq!(results.len(), 1);
+
+    let (index, metadata) = &results[0];
+    assert_eq!(*index, primary);
+    assert_eq!(metadata.iter().count(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+use futures::future;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH, LINK};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use uv_cache::Cache;
+use uv_client::RegistryClientBuilder;
+use uv_normalize::PackageName;
+
+/// Paginated JSON responses are merged across pages, and unchanged pages are revalidated against
+/// their cached `ETag` rather than re-downloaded.
+#[tokio::test]
+async fn test_pagination_etag() -> Result<()> {
+    // Set up the TCP listener on a random available port
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    // Spawn the server loop in a background task, serving a project across two pages. The first
+    // page must always be revalidated, while the second page is tagged with an `ETag`.
+    let not_modified = Arc::new(AtomicUsize::new(0));
+    let counter = not_modified.clone();
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let socket = TokioIo::new(socket);
+            let counter = counter.clone();
+            tokio::task::spawn(async move {
+                let svc = service_fn(move |req: Request<hyper::body::Incoming>| {
+                    let response = if req.uri().query() == Some("page=2") {
+                        if req
+                            .headers()
+                            .get(IF_NONE_MATCH)
+                            .is_some_and(|etag| etag == "\"page-2\"")
+                        {
+                            counter.fetch_add(1, Ordering::SeqCst);
+                            Response::builder()
+                                .status(StatusCode::NOT_MODIFIED)
+                                .header(ETAG, "\"page-2\"")
+                                .body(Full::new(Bytes::new()))
+                                .unwrap()
+                        } else {
+                            Response::builder()
+                                .header(CONTENT_TYPE, "application/vnd.pypi.simple.v1+json")
+                                .header(CACHE_CONTROL, "max-age=0")
+                                .header(ETAG, "\"page-2\"")
+                                .body(Full::new(Bytes::from(
+                                    r#"{"files": [{"filename": "anyio-4.3.0.tar.gz", "url": "anyio-4.3.0.tar.gz", "hashes": {}}]}"#,
+                                )))
+                                .unwrap()
+                        }
+                    } else {
+                        Response::builder()
+                            .header(CONTENT_TYPE, "application/vnd.pypi.simple.v1+json")
+                            .header(CACHE_CONTROL, "max-age=0")
+                            .header(LINK, r#"<?page=2>; rel="next""#)
+                            .body(Full::new(Bytes::from(
+                                r#"{"files": [{"filename": "anyio-4.2.0.tar.gz", "url": "anyio-4.2.0.tar.gz", "hashes": {}}]}"#,
+                            )))
+                            .unwrap()
+                    };
+                    future::ok::<_, hyper::Error>(response)
+                });
+                http1::Builder::new()
+                    .serve_connection(socket, svc)
+                    .await
+                    .expect("Server Started");
+            });
+        }
+    });
+
+    let index = IndexUrl::from_str(&format!("http://{addr}/simple"))?;
+    let index_locations = IndexLocations::new(Some(index), vec![], vec![], false);
+
+    // Initialize uv-client
+    let cache = Cache::temp()?.init()?;
+    let client = RegistryClientBuilder::new(cache)
+        .index_urls(index_locations.index_urls())
+        .retries(0)
+        .build();
+    let package_name = PackageName::from_str("anyio")?;
+
+    // The files on both pages are included in the metadata.
+    let results = client.simple(&package_name).await?;
+    assert_eq!(results[0].1.iter().count(), 2);
+    assert_eq!(not_modified.load(Ordering::SeqCst), 0);
+
+    // On revalidation, the second page is served from the cache.
+    let results = client.simple(&package_name).await?;
+    assert_eq!(results[0].1.iter().count(), 2);
+    assert_eq!(not_modified.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
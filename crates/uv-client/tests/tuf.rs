@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use futures::future;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tokio::net::TcpListener;
+
+use distribution_types::{IndexLocations, IndexUrl};
+use pep508_rs::VerbatimUrl;
+use uv_cache::Cache;
+use uv_client::{ErrorKind, OwnedArchive, RegistryClientBuilder, TufError, TufSettings};
+use uv_configuration::IndexVerification;
+use uv_normalize::PackageName;
+
+/// The files served by the test index, by path.
+type Files = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+/// Serve the given files over HTTP, returning the address of the server.
+async fn serve(files: Files) -> Result<SocketAddr> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let files = files.clone();
+            let svc = service_fn(move |req: Request<hyper::body::Incoming>| {
+                let response = match files.lock().unwrap().get(req.uri().path()) {
+                    Some(body) => Response::builder()
+                        .header("Content-Type", "text/html")
+                        .body(Full::new(Bytes::from(body.clone())))
+                        .unwrap(),
+                    None => Response::builder()
+                        .status(StatusCode::NOT_FOUND)
+                        .body(Full::new(Bytes::new()))
+                        .unwrap(),
+                };
+                future::ok::<_, hyper::Error>(response)
+            });
+            tokio::task::spawn(async move {
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(socket), svc)
+                    .await;
+            });
+        }
+    });
+
+    Ok(addr)
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Sign a metadata payload with the given key.
+///
+/// `serde_json` sorts object keys, so for ASCII payloads without floats, its compact output is
+/// the canonical JSON over which TUF signatures are computed.
+fn sign(signed: &serde_json::Value, key: &Ed25519KeyPair) -> Vec<u8> {
+    let message = serde_json::to_vec(signed).unwrap();
+    serde_json::to_vec(&json!({
+        "signatures": [{ "keyid": "key", "sig": hex::encode(key.sign(&message)) }],
+        "signed": signed,
+    }))
+    .unwrap()
+}
+
+/// A test index that publishes TUF metadata for a single Simple API page.
+struct Repository {
+    key: Ed25519KeyPair,
+    files: Files,
+}
+
+impl Repository {
+    fn new() -> Self {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let role = json!({ "keyids": ["key"], "threshold": 1 });
+        let root = json!({
+            "_type": "root",
+            "version": 1,
+            "expires": "2100-01-01T00:00:00Z",
+            "consistent_snapshot": false,
+            "keys": {
+                "key": {
+                    "keytype": "ed25519",
+                    "scheme": "ed25519",
+                    "keyval": { "public": hex::encode(key.public_key().as_ref()) },
+                },
+            },
+            "roles": { "root": role, "timestamp": role, "snapshot": role, "targets": role },
+        });
+        let files = Arc::new(Mutex::new(HashMap::from([(
+            "/tuf/1.root.json".to_string(),
+            sign(&root, &key),
+        )])));
+        Self { key, files }
+    }
+
+    /// Publish the given Simple API page for `anyio`, signed at the given metadata version.
+    fn publish(&self, page: &[u8], signed_page: &[u8], version: u64) {
+        let wheel = b"wheel";
+        let targets = json!({
+            "_type": "targets",
+            "version": version,
+            "expires": "2100-01-01T00:00:00Z",
+            "targets": {
+                "simple/anyio/index.html": {
+                    "length": signed_page.len(),
+                    "hashes": { "sha256": sha256(signed_page) },
+                },
+                "packages/anyio-4.3.0-py3-none-any.whl": {
+                    "length": wheel.len(),
+                    "hashes": { "sha256": sha256(wheel) },
+                },
+            },
+        });
+        let snapshot = json!({
+            "_type": "snapshot",
+            "version": version,
+            "expires": "2100-01-01T00:00:00Z",
+            "meta": { "targets.json": { "version": version } },
+        });
+        let timestamp = json!({
+            "_type": "timestamp",
+            "version": version,
+            "expires": "2100-01-01T00:00:00Z",
+            "meta": { "snapshot.json": { "version": version } },
+        });
+
+        let mut files = self.files.lock().unwrap();
+        files.insert("/tuf/targets.json".to_string(), sign(&targets, &self.key));
+        files.insert("/tuf/snapshot.json".to_string(), sign(&snapshot, &self.key));
+        files.insert(
+            "/tuf/timestamp.json".to_string(),
+            sign(&timestamp, &self.key),
+        );
+        files.insert("/simple/anyio/".to_string(), page.to_vec());
+    }
+}
+
+fn page() -> Vec<u8> {
+    format!(
+        r#"<a href="../../packages/anyio-4.3.0-py3-none-any.whl#sha256={}">anyio-4.3.0-py3-none-any.whl</a>
+<a href="../../packages/anyio-4.3.0.tar.gz">anyio-4.3.0.tar.gz</a>"#,
+        sha256(b"wheel")
+    )
+    .into_bytes()
+}
+
+#[tokio::test]
+async fn verify_simple_page() -> Result<()> {
+    let repository = Repository::new();
+    let page = page();
+    repository.publish(&page, &page, 2);
+    let addr = serve(repository.files.clone()).await?;
+
+    let index = IndexUrl::from(VerbatimUrl::parse_url(format!("http://{addr}/simple/"))?);
+    let trust_dir = tempfile::tempdir()?;
+    let client = |cache: Cache| {
+        RegistryClientBuilder::new(cache)
+            .index_urls(IndexLocations::new(Some(index.clone()), vec![], vec![], false).into())
+            .tuf_settings(TufSettings::new(
+                IndexVerification::Required,
+                trust_dir.path(),
+            ))
+            .build()
+    };
+    let anyio = PackageName::from_str("anyio")?;
+
+    // The page matches its signed target; the unsigned source distribution is omitted, and the
+    // wheel is pinned to its signed length.
+    let cache = Cache::temp()?.init()?;
+    let results = client(cache.clone()).simple(&anyio).await?;
+    let metadata = OwnedArchive::deserialize(&results[0].1);
+    let files = &metadata.iter().next().unwrap().files;
+    assert_eq!(files.wheels.len(), 1);
+    assert!(files.source_dists.is_empty());
+    assert_eq!(files.wheels[0].file.size, Some(5));
+
+    // The trusted root is persisted outside of the cache.
+    assert_eq!(std::fs::read_dir(trust_dir.path())?.count(), 1);
+    assert!(walk(cache.root())
+        .iter()
+        .all(|path| !path.ends_with("root.json")));
+
+    // A page that doesn't match its signed target is rejected.
+    let mut tampered = page.clone();
+    tampered.extend_from_slice(b"\n<a href=\"../../packages/evil.whl\">evil.whl</a>");
+    repository.publish(&tampered, &page, 3);
+    let err = client(Cache::temp()?.init()?)
+        .simple(&anyio)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err.kind(),
+            ErrorKind::IndexVerification(_, err) if matches!(**err, TufError::TargetMismatch { .. })
+        ),
+        "{err:?}"
+    );
+
+    // Metadata that was rolled back to an older version is rejected.
+    repository.publish(&page, &page, 1);
+    let err = client(Cache::temp()?.init()?)
+        .simple(&anyio)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err.kind(),
+            ErrorKind::IndexVerification(_, err) if matches!(**err, TufError::Rollback { .. })
+        ),
+        "{err:?}"
+    );
+
+    Ok(())
+}
+
+/// Return every file path under the given directory.
+fn walk(root: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut paths = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
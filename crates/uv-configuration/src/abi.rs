@@ -0,0 +1,14 @@
+/// Whether to validate that installed wheels are compatible with the host.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum AbiCheck {
+    /// Install wheels as selected, without validating them against the host.
+    #[default]
+    Ignore,
+    /// Warn when installing a wheel that's incompatible with the host.
+    Warn,
+    /// Fail before installing any wheel that's incompatible with the host.
+    Error,
+}
@@ -1,4 +1,6 @@
-use uv_auth::{self, KeyringProvider};
+use url::Url;
+
+use uv_auth::{self, CredentialHelper, KeyringProvider};
 
 /// Keyring provider type to use for credential lookup.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -26,3 +28,30 @@ impl KeyringProviderType {
         }
     }
 }
+
+/// An external command that provides credentials for a package index.
+///
+/// The command is run (without a shell) when credentials are first needed for the index, and
+/// should print a token to `stdout`. The token is reused for the remainder of the invocation,
+/// unless the index rejects it, in which case the command is run again.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct IndexCredentialHelper {
+    /// The URL of the index for which the command provides credentials.
+    pub url: Url,
+    /// The command to run, along with its arguments.
+    pub command: Vec<String>,
+    /// The username to pair with the token (defaults to `__token__`).
+    pub username: Option<String>,
+}
+
+impl IndexCredentialHelper {
+    pub fn to_helper(&self) -> CredentialHelper {
+        CredentialHelper::new(
+            self.url.clone(),
+            self.command.clone(),
+            self.username.clone(),
+        )
+    }
+}
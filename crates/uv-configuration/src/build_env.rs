@@ -0,0 +1,37 @@
+use std::collections::BTreeMap;
+
+use uv_normalize::PackageName;
+
+/// Extra environment variables to set when building specific packages from source, as declared in
+/// `tool.uv.build-env`.
+///
+/// For example, `llama-cpp-python = { CMAKE_ARGS = "-DGGML_CUDA=on" }` sets `CMAKE_ARGS` in the
+/// isolated build environment of `llama-cpp-python`, and no other package.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(transparent)]
+pub struct PackageBuildEnv(BTreeMap<PackageName, BTreeMap<String, String>>);
+
+impl PackageBuildEnv {
+    /// Returns the environment variables to set when building the given package, if any.
+    pub fn get(&self, package_name: &PackageName) -> Option<&BTreeMap<String, String>> {
+        self.0
+            .get(package_name)
+            .filter(|variables| !variables.is_empty())
+    }
+
+    /// Returns `true` if no environment variables are declared for any package.
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(BTreeMap::is_empty)
+    }
+
+    /// Returns an iterator over the packages with environment variables, and their variables.
+    pub fn iter(&self) -> impl Iterator<Item = (&PackageName, &BTreeMap<String, String>)> {
+        self.0.iter().filter(|(_, variables)| !variables.is_empty())
+    }
+}
+
+impl From<BTreeMap<PackageName, BTreeMap<String, String>>> for PackageBuildEnv {
+    fn from(build_env: BTreeMap<PackageName, BTreeMap<String, String>>) -> Self {
+        Self(build_env)
+    }
+}
@@ -21,6 +21,8 @@ pub enum BuildKind {
     Wheel,
     /// A PEP 660 editable installation wheel build
     Editable,
+    /// A PEP 517 source distribution build
+    Sdist,
 }
 
 impl Display for BuildKind {
@@ -28,6 +30,7 @@ impl Display for BuildKind {
         match self {
             Self::Wheel => f.write_str("wheel"),
             Self::Editable => f.write_str("editable"),
+            Self::Sdist => f.write_str("sdist"),
         }
     }
 }
@@ -37,6 +40,8 @@ impl Display for BuildKind {
 pub struct BuildOptions {
     no_binary: NoBinary,
     no_build: NoBuild,
+    #[serde(default)]
+    prefer_source: PreferSource,
 }
 
 impl BuildOptions {
@@ -44,6 +49,16 @@ impl BuildOptions {
         Self {
             no_binary,
             no_build,
+            prefer_source: PreferSource::None,
+        }
+    }
+
+    /// Set the [`PreferSource`] strategy to use.
+    #[must_use]
+    pub fn with_prefer_source(self, prefer_source: PreferSource) -> Self {
+        Self {
+            prefer_source,
+            ..self
         }
     }
 
@@ -52,6 +67,7 @@ impl BuildOptions {
         Self {
             no_binary: self.no_binary.combine(no_binary),
             no_build: self.no_build.combine(no_build),
+            prefer_source: self.prefer_source,
         }
     }
 
@@ -79,6 +95,29 @@ impl BuildOptions {
         }
     }
 
+    /// Returns `true` if source distributions should be preferred over wheels for the given
+    /// package.
+    ///
+    /// Unlike [`BuildOptions::no_binary_package`], wheels remain usable when a package doesn't
+    /// publish a source distribution. Source distributions are never preferred for packages that
+    /// can't be built.
+    pub fn prefer_source_package(&self, package_name: &PackageName) -> bool {
+        if self.no_build_package(package_name) {
+            return false;
+        }
+        match &self.prefer_source {
+            PreferSource::None => false,
+            PreferSource::All => true,
+            PreferSource::Packages(packages) => packages.contains(package_name),
+        }
+    }
+
+    /// Returns `true` if only wheels built from source should be used for the given package,
+    /// i.e., if wheels are disabled or source distributions are preferred.
+    pub fn source_only_package(&self, package_name: &PackageName) -> bool {
+        self.no_binary_package(package_name) || self.prefer_source_package(package_name)
+    }
+
     pub fn no_build_requirement(&self, package_name: Option<&PackageName>) -> bool {
         match package_name {
             Some(name) => self.no_build_package(name),
@@ -110,6 +149,11 @@ impl BuildOptions {
     pub fn no_binary(&self) -> &NoBinary {
         &self.no_binary
     }
+
+    /// Return the [`PreferSource`] strategy to use.
+    pub fn prefer_source(&self) -> &PreferSource {
+        &self.prefer_source
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -308,6 +352,42 @@ impl NoBuild {
     }
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub enum PreferSource {
+    /// Prefer wheels over source distributions.
+    #[default]
+    None,
+
+    /// Prefer source distributions over wheels for all packages.
+    All,
+
+    /// Prefer source distributions over wheels for the given packages.
+    Packages(Vec<PackageName>),
+}
+
+impl PreferSource {
+    /// Determine the source preference to use for the given arguments.
+    pub fn from_args(prefer_source: Option<bool>, prefer_source_package: Vec<PackageName>) -> Self {
+        match prefer_source {
+            Some(true) => Self::All,
+            Some(false) => Self::None,
+            None => {
+                if prefer_source_package.is_empty() {
+                    Self::None
+                } else {
+                    Self::Packages(prefer_source_package)
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if wheels are preferred for all packages.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "kebab-case")]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
@@ -350,6 +430,16 @@ pub enum IndexStrategy {
     UnsafeBestMatch,
 }
 
+impl std::fmt::Display for IndexStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FirstIndex => write!(f, "first-index"),
+            Self::UnsafeFirstMatch => write!(f, "unsafe-first-match"),
+            Self::UnsafeBestMatch => write!(f, "unsafe-best-match"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
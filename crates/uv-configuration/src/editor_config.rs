@@ -0,0 +1,13 @@
+/// An editor configuration to generate for the project environment after `uv sync`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum EditorConfig {
+    /// Only write the interpreter and environment paths to `.uv/env.json`.
+    Env,
+    /// Set `python.defaultInterpreterPath` in `.vscode/settings.json`.
+    Vscode,
+    /// Set `venvPath` and `venv` in `pyrightconfig.json`.
+    Pyright,
+}
@@ -0,0 +1,15 @@
+/// How to handle distributions in the same installation that provide the same files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum FileConflicts {
+    /// Install each distribution as selected, such that later distributions overwrite the files of
+    /// earlier ones.
+    #[default]
+    Ignore,
+    /// Warn when multiple distributions provide the same file.
+    Warn,
+    /// Fail before installing distributions that provide the same file.
+    Error,
+}
@@ -1,27 +1,45 @@
+pub use abi::*;
 pub use authentication::*;
+pub use build_env::*;
 pub use build_options::*;
 pub use concurrency::*;
 pub use config_settings::*;
 pub use constraints::*;
+pub use editor_config::*;
 pub use extras::*;
+pub use file_conflicts::*;
 pub use hash::*;
+pub use macos_arch::*;
+pub use module_path::*;
 pub use name_specifiers::*;
+pub use network::*;
 pub use overrides::*;
 pub use package_options::*;
 pub use preview::*;
 pub use sources::*;
+pub use sys_platform::*;
 pub use target_triple::*;
+pub use typosquat::*;
 
+mod abi;
 mod authentication;
+mod build_env;
 mod build_options;
 mod concurrency;
 mod config_settings;
 mod constraints;
+mod editor_config;
 mod extras;
+mod file_conflicts;
 mod hash;
+mod macos_arch;
+mod module_path;
 mod name_specifiers;
+mod network;
 mod overrides;
 mod package_options;
 mod preview;
 mod sources;
+mod sys_platform;
 mod target_triple;
+mod typosquat;
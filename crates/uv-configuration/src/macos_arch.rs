@@ -0,0 +1,162 @@
+use platform_tags::{Arch, Os, Platform};
+
+use crate::TargetTriple;
+
+/// The architecture policy to apply when selecting wheels on macOS.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum MacosArch {
+    /// Prefer wheels built for the interpreter's architecture, falling back to `universal2` wheels.
+    #[default]
+    Native,
+    /// Prefer `universal2` wheels over wheels built for the interpreter's architecture.
+    PreferUniversal2,
+    /// Only select `universal2` wheels, ignoring wheels built for a single architecture.
+    RequireUniversal2,
+    /// Select `x86_64` wheels, as when running under Rosetta 2. Requires an `x86_64` interpreter.
+    #[serde(rename = "x86_64")]
+    #[cfg_attr(feature = "clap", value(name = "x86_64"))]
+    #[cfg_attr(feature = "schemars", schemars(rename = "x86_64"))]
+    X86_64,
+}
+
+impl MacosArch {
+    /// Apply the policy to the target platform, if any.
+    ///
+    /// When selecting `x86_64` wheels for an ARM-based macOS target, the target is replaced by
+    /// `x86_64-apple-darwin`, such that both the platform tags and the markers describe the
+    /// translated environment.
+    pub fn python_platform(self, python_platform: Option<TargetTriple>) -> Option<TargetTriple> {
+        match (self, python_platform) {
+            (Self::X86_64, Some(TargetTriple::Macos | TargetTriple::Aarch64AppleDarwin)) => {
+                Some(TargetTriple::X8664AppleDarwin)
+            }
+            (_, python_platform) => python_platform,
+        }
+    }
+
+    /// Apply the policy to the given platform tags, which are expected in priority order.
+    ///
+    /// Has no effect on platforms other than macOS.
+    pub fn platform_tags(self, platform: &Platform, platform_tags: Vec<String>) -> Vec<String> {
+        if !matches!(platform.os(), Os::Macos { .. }) {
+            return platform_tags;
+        }
+        match self {
+            Self::Native => platform_tags,
+            // An `x86_64` process can't load `arm64` code, even from a universal build.
+            Self::X86_64 => platform_tags
+                .into_iter()
+                .filter(|tag| !tag.ends_with("_arm64"))
+                .collect(),
+            Self::PreferUniversal2 => {
+                let (universal2, rest): (Vec<_>, Vec<_>) = platform_tags
+                    .into_iter()
+                    .partition(|tag| tag.ends_with("_universal2"));
+                universal2.into_iter().chain(rest).collect()
+            }
+            Self::RequireUniversal2 => platform_tags
+                .into_iter()
+                .filter(|tag| tag.ends_with("_universal2"))
+                .collect(),
+        }
+    }
+
+    /// Validate that the policy is consistent with the interpreter's platform.
+    ///
+    /// Returns an error message if the interpreter can't load the wheels selected by the policy;
+    /// e.g., if `x86_64` wheels are requested for a native `arm64` interpreter, they'd fail at
+    /// import time.
+    pub fn check(self, platform: &Platform) -> Result<(), String> {
+        if !matches!(platform.os(), Os::Macos { .. }) {
+            return Ok(());
+        }
+        match self {
+            Self::X86_64 if platform.arch() != Arch::X86_64 => Err(format!(
+                "`x86_64` wheels were requested, but the interpreter is running as `{}`; use an `x86_64` interpreter (e.g., by running uv under Rosetta 2 with `arch -x86_64`)",
+                platform.arch()
+            )),
+            Self::PreferUniversal2 | Self::RequireUniversal2
+                if !matches!(platform.arch(), Arch::X86_64 | Arch::Aarch64) =>
+            {
+                Err(format!(
+                    "`universal2` wheels were requested, but the interpreter is running as `{}`",
+                    platform.arch()
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use platform_tags::{Arch, Os, Platform};
+
+    use crate::TargetTriple;
+
+    use super::MacosArch;
+
+    #[test]
+    fn platform_tags() {
+        let platform = Platform::new(
+            Os::Macos {
+                major: 14,
+                minor: 0,
+            },
+            Arch::Aarch64,
+        );
+        let tags = vec![
+            "macosx_14_0_arm64".to_string(),
+            "macosx_14_0_universal2".to_string(),
+            "macosx_13_0_arm64".to_string(),
+            "macosx_13_0_universal2".to_string(),
+        ];
+
+        assert_eq!(
+            MacosArch::PreferUniversal2.platform_tags(&platform, tags.clone()),
+            vec![
+                "macosx_14_0_universal2",
+                "macosx_13_0_universal2",
+                "macosx_14_0_arm64",
+                "macosx_13_0_arm64",
+            ]
+        );
+        assert_eq!(
+            MacosArch::RequireUniversal2.platform_tags(&platform, tags.clone()),
+            vec!["macosx_14_0_universal2", "macosx_13_0_universal2"]
+        );
+        assert_eq!(
+            MacosArch::Native.platform_tags(&platform, tags.clone()),
+            tags
+        );
+        assert_eq!(
+            MacosArch::X86_64.platform_tags(&platform, tags.clone()),
+            vec!["macosx_14_0_universal2", "macosx_13_0_universal2"]
+        );
+        assert!(MacosArch::X86_64.check(&platform).is_err());
+    }
+
+    #[test]
+    fn python_platform() {
+        assert_eq!(
+            MacosArch::X86_64.python_platform(Some(TargetTriple::Aarch64AppleDarwin)),
+            Some(TargetTriple::X8664AppleDarwin)
+        );
+        assert_eq!(
+            MacosArch::X86_64.python_platform(Some(TargetTriple::Macos)),
+            Some(TargetTriple::X8664AppleDarwin)
+        );
+        assert_eq!(
+            MacosArch::X86_64.python_platform(Some(TargetTriple::Linux)),
+            Some(TargetTriple::Linux)
+        );
+        assert_eq!(MacosArch::X86_64.python_platform(None), None);
+        assert_eq!(
+            MacosArch::PreferUniversal2.python_platform(Some(TargetTriple::Aarch64AppleDarwin)),
+            Some(TargetTriple::Aarch64AppleDarwin)
+        );
+    }
+}
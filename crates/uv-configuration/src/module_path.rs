@@ -0,0 +1,13 @@
+/// A directory to add to `sys.path` when running a command in a project with `uv run`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ModulePath {
+    /// The root of the project (i.e., the directory containing its `pyproject.toml`).
+    ProjectRoot,
+    /// The `src` directory within the project root, if it exists.
+    Src,
+    /// The root of each member of the workspace.
+    Members,
+}
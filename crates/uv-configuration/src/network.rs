@@ -0,0 +1,39 @@
+/// The IP version to use when connecting to a host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum IpVersion {
+    /// Only connect over IPv4, ignoring any IPv6 (`AAAA`) records.
+    #[serde(rename = "4")]
+    #[cfg_attr(feature = "clap", value(name = "4"))]
+    V4,
+    /// Only connect over IPv6, ignoring any IPv4 (`A`) records.
+    #[serde(rename = "6")]
+    #[cfg_attr(feature = "clap", value(name = "6"))]
+    V6,
+}
+
+/// The policy for verifying index responses against the [PEP 458](https://peps.python.org/pep-0458/)
+/// (TUF) metadata published by the index.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum IndexVerification {
+    /// Don't consult TUF metadata.
+    #[default]
+    Disabled,
+    /// Verify responses from indexes that publish TUF metadata, and accept responses from indexes
+    /// that don't.
+    IfAvailable,
+    /// Verify responses from every index, and reject indexes that don't publish TUF metadata.
+    Required,
+}
+
+impl IndexVerification {
+    /// Returns `true` if responses should be verified against TUF metadata.
+    pub fn is_enabled(self) -> bool {
+        !matches!(self, Self::Disabled)
+    }
+}
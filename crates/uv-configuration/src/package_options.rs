@@ -45,6 +45,26 @@ impl Reinstall {
     pub fn is_all(&self) -> bool {
         matches!(self, Self::All)
     }
+
+    /// Combine a set of [`Reinstall`] values.
+    #[must_use]
+    pub fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            // If either is `All`, reinstall everything.
+            (_, Self::All) | (Self::All, _) => Self::All,
+            // If either is `None`, use the other.
+            (Self::None, reinstall) | (reinstall, Self::None) => reinstall,
+            // If both are `Packages`, combine them.
+            (Self::Packages(mut packages1), Self::Packages(packages2)) => {
+                for package in packages2 {
+                    if !packages1.contains(&package) {
+                        packages1.push(package);
+                    }
+                }
+                Self::Packages(packages1)
+            }
+        }
+    }
 }
 
 /// Create a [`Refresh`] policy by integrating the [`Reinstall`] policy.
@@ -116,6 +136,31 @@ impl Upgrade {
         }
     }
 
+    /// Extend the upgrade strategy to allow upgrades for the given packages.
+    #[must_use]
+    pub fn with_packages(self, names: impl IntoIterator<Item = PackageName>) -> Self {
+        match self {
+            Self::All => Self::All,
+            Self::None => {
+                let packages = names
+                    .into_iter()
+                    .map(|name| (name, Vec::new()))
+                    .collect::<FxHashMap<_, _>>();
+                if packages.is_empty() {
+                    Self::None
+                } else {
+                    Self::Packages(packages)
+                }
+            }
+            Self::Packages(mut packages) => {
+                for name in names {
+                    packages.entry(name).or_default();
+                }
+                Self::Packages(packages)
+            }
+        }
+    }
+
     /// Returns an iterator over the constraints.
     ///
     /// When upgrading, users can provide bounds on the upgrade (e.g., `--upgrade-package flask<3`).
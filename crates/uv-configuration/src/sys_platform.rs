@@ -0,0 +1,77 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use pep508_rs::MarkerTree;
+
+/// An operating system family, as identified by the `sys_platform` marker.
+///
+/// Used to prune the artifacts and dependencies for unsupported platforms from the lockfile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum SysPlatform {
+    /// Linux, including both glibc- and musl-based distributions.
+    Linux,
+    /// macOS.
+    Macos,
+    /// Windows.
+    Windows,
+}
+
+impl SysPlatform {
+    /// Returns a marker that's satisfied on this platform.
+    ///
+    /// Along with `sys_platform`, the marker includes the other variables that are commonly used
+    /// to identify the platform (e.g., `platform_system == 'Windows'`), since the marker algebra
+    /// can't infer the relationships between them.
+    pub fn marker(self) -> MarkerTree {
+        let marker = match self {
+            Self::Linux => "sys_platform == 'linux' or platform_system == 'Linux'",
+            Self::Macos => "sys_platform == 'darwin' or platform_system == 'Darwin'",
+            Self::Windows => {
+                "sys_platform == 'win32' or platform_system == 'Windows' or os_name == 'nt'"
+            }
+        };
+        MarkerTree::from_str(marker).expect("platform marker should be valid")
+    }
+
+    /// Returns `true` if the given wheel platform tag (e.g., `manylinux_2_17_x86_64`) is specific
+    /// to this platform.
+    pub fn matches_platform_tag(self, tag: &str) -> bool {
+        match self {
+            Self::Linux => {
+                tag.starts_with("manylinux")
+                    || tag.starts_with("musllinux")
+                    || tag.starts_with("linux_")
+            }
+            Self::Macos => tag.starts_with("macosx_"),
+            Self::Windows => tag == "win32" || tag.starts_with("win_"),
+        }
+    }
+}
+
+impl Display for SysPlatform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Linux => f.write_str("linux"),
+            Self::Macos => f.write_str("macos"),
+            Self::Windows => f.write_str("windows"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SysPlatform;
+
+    #[test]
+    fn matches_platform_tag() {
+        assert!(SysPlatform::Linux.matches_platform_tag("manylinux_2_17_x86_64"));
+        assert!(SysPlatform::Linux.matches_platform_tag("musllinux_1_1_aarch64"));
+        assert!(SysPlatform::Macos.matches_platform_tag("macosx_11_0_arm64"));
+        assert!(SysPlatform::Windows.matches_platform_tag("win_amd64"));
+        assert!(SysPlatform::Windows.matches_platform_tag("win32"));
+        assert!(!SysPlatform::Windows.matches_platform_tag("any"));
+        assert!(!SysPlatform::Linux.matches_platform_tag("macosx_11_0_arm64"));
+    }
+}
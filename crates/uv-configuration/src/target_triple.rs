@@ -26,6 +26,11 @@ pub enum TargetTriple {
     #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-pc-windows-msvc"))]
     X8664PcWindowsMsvc,
 
+    /// An ARM64 Windows target.
+    #[cfg_attr(feature = "clap", value(name = "aarch64-pc-windows-msvc"))]
+    #[cfg_attr(feature = "schemars", schemars(rename = "aarch64-pc-windows-msvc"))]
+    Aarch64PcWindowsMsvc,
+
     /// An x86 Linux target. Equivalent to `x86_64-manylinux_2_17`.
     #[cfg_attr(feature = "clap", value(name = "x86_64-unknown-linux-gnu"))]
     #[cfg_attr(feature = "schemars", schemars(rename = "x86_64-unknown-linux-gnu"))]
@@ -98,6 +103,7 @@ impl TargetTriple {
     pub fn platform(self) -> Platform {
         match self {
             Self::Windows | Self::X8664PcWindowsMsvc => Platform::new(Os::Windows, Arch::X86_64),
+            Self::Aarch64PcWindowsMsvc => Platform::new(Os::Windows, Arch::Aarch64),
             Self::Linux | Self::X8664UnknownLinuxGnu => Platform::new(
                 Os::Manylinux {
                     major: 2,
@@ -181,6 +187,7 @@ impl TargetTriple {
     pub fn platform_machine(self) -> &'static str {
         match self {
             Self::Windows | Self::X8664PcWindowsMsvc => "x86_64",
+            Self::Aarch64PcWindowsMsvc => "ARM64",
             Self::Linux | Self::X8664UnknownLinuxGnu => "x86_64",
             Self::Macos | Self::Aarch64AppleDarwin => "arm64",
             Self::X8664AppleDarwin => "x86_64",
@@ -200,6 +207,7 @@ impl TargetTriple {
     pub fn platform_system(self) -> &'static str {
         match self {
             Self::Windows | Self::X8664PcWindowsMsvc => "Windows",
+            Self::Aarch64PcWindowsMsvc => "Windows",
             Self::Linux | Self::X8664UnknownLinuxGnu => "Linux",
             Self::Macos | Self::Aarch64AppleDarwin => "Darwin",
             Self::X8664AppleDarwin => "Darwin",
@@ -219,6 +227,7 @@ impl TargetTriple {
     pub fn platform_version(self) -> &'static str {
         match self {
             Self::Windows | Self::X8664PcWindowsMsvc => "",
+            Self::Aarch64PcWindowsMsvc => "",
             Self::Linux | Self::X8664UnknownLinuxGnu => "",
             Self::Macos | Self::Aarch64AppleDarwin => "",
             Self::X8664AppleDarwin => "",
@@ -238,6 +247,7 @@ impl TargetTriple {
     pub fn platform_release(self) -> &'static str {
         match self {
             Self::Windows | Self::X8664PcWindowsMsvc => "",
+            Self::Aarch64PcWindowsMsvc => "",
             Self::Linux | Self::X8664UnknownLinuxGnu => "",
             Self::Macos | Self::Aarch64AppleDarwin => "",
             Self::X8664AppleDarwin => "",
@@ -257,6 +267,7 @@ impl TargetTriple {
     pub fn os_name(self) -> &'static str {
         match self {
             Self::Windows | Self::X8664PcWindowsMsvc => "nt",
+            Self::Aarch64PcWindowsMsvc => "nt",
             Self::Linux | Self::X8664UnknownLinuxGnu => "posix",
             Self::Macos | Self::Aarch64AppleDarwin => "posix",
             Self::X8664AppleDarwin => "posix",
@@ -276,6 +287,7 @@ impl TargetTriple {
     pub fn sys_platform(self) -> &'static str {
         match self {
             Self::Windows | Self::X8664PcWindowsMsvc => "win32",
+            Self::Aarch64PcWindowsMsvc => "win32",
             Self::Linux | Self::X8664UnknownLinuxGnu => "linux",
             Self::Macos | Self::Aarch64AppleDarwin => "darwin",
             Self::X8664AppleDarwin => "darwin",
@@ -0,0 +1,14 @@
+/// How to handle requirements whose names are near-misses for popular packages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum TyposquatCheck {
+    /// Allow each package as requested, regardless of its name.
+    Ignore,
+    /// Warn when a package name is similar to that of a popular package.
+    #[default]
+    Warn,
+    /// Fail before adding a package whose name is similar to that of a popular package.
+    Error,
+}
@@ -2,14 +2,16 @@
 //! [installer][`uv_installer`] and [build][`uv_build`] through [`BuildDispatch`]
 //! implementing [`BuildContext`].
 
+use std::env;
 use std::ffi::{OsStr, OsString};
 use std::path::Path;
+use std::sync::OnceLock;
 
 use anyhow::{anyhow, Context, Result};
 use futures::FutureExt;
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 use distribution_types::{CachedDist, IndexLocations, Name, Resolution, SourceDist};
 use pypi_types::Requirement;
@@ -17,8 +19,8 @@ use uv_build::{SourceBuild, SourceBuildContext};
 use uv_cache::Cache;
 use uv_client::RegistryClient;
 use uv_configuration::{
-    BuildKind, BuildOptions, ConfigSettings, Constraints, IndexStrategy, Reinstall,
-    SetupPyStrategy, SourceStrategy,
+    BuildKind, BuildOptions, ConfigSettings, Constraints, IndexStrategy, PackageBuildEnv,
+    Reinstall, SetupPyStrategy, SourceStrategy,
 };
 use uv_configuration::{Concurrency, PreviewMode};
 use uv_distribution::DistributionDatabase;
@@ -30,6 +32,22 @@ use uv_resolver::{
     ResolverMarkers,
 };
 use uv_types::{BuildContext, BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
+use uv_warnings::warn_user_once;
+
+/// The number of times to retry setting up a build environment after a transient failure, unless
+/// overridden via `UV_BUILD_RETRIES`.
+const DEFAULT_BUILD_RETRIES: u32 = 2;
+
+/// The command to run on each platform-specific wheel built from source, if any.
+static REPAIR_WHEEL_COMMAND: OnceLock<String> = OnceLock::new();
+
+/// Configure the command to run on each platform-specific wheel built from source, per the
+/// `--repair-wheel-command` argument.
+///
+/// Has no effect if the command was already configured.
+pub fn init_repair_wheel_command(command: String) {
+    let _ = REPAIR_WHEEL_COMMAND.set(command);
+}
 
 /// The main implementation of [`BuildContext`], used by the CLI, see [`BuildContext`]
 /// documentation.
@@ -52,8 +70,11 @@ pub struct BuildDispatch<'a> {
     exclude_newer: Option<ExcludeNewer>,
     source_build_context: SourceBuildContext,
     build_extra_env_vars: FxHashMap<OsString, OsString>,
+    package_build_env: PackageBuildEnv,
     sources: SourceStrategy,
     concurrency: Concurrency,
+    build_retries: u32,
+    repair_wheel_command: Option<&'static str>,
     preview_mode: PreviewMode,
 }
 
@@ -98,8 +119,11 @@ impl<'a> BuildDispatch<'a> {
             exclude_newer,
             source_build_context: SourceBuildContext::default(),
             build_extra_env_vars: FxHashMap::default(),
+            package_build_env: PackageBuildEnv::default(),
             sources,
             concurrency,
+            build_retries: build_retries(),
+            repair_wheel_command: REPAIR_WHEEL_COMMAND.get().map(String::as_str),
             preview_mode,
         }
     }
@@ -118,6 +142,14 @@ impl<'a> BuildDispatch<'a> {
             .collect();
         self
     }
+
+    /// Set the environment variables to be used when building specific packages, in addition to
+    /// those set for all source distributions.
+    #[must_use]
+    pub fn with_package_build_env(mut self, package_build_env: PackageBuildEnv) -> Self {
+        self.package_build_env = package_build_env;
+        self
+    }
 }
 
 impl<'a> BuildContext for BuildDispatch<'a> {
@@ -135,6 +167,10 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.build_options
     }
 
+    fn package_build_env(&self) -> &PackageBuildEnv {
+        &self.package_build_env
+    }
+
     fn sources(&self) -> SourceStrategy {
         self.sources
     }
@@ -143,6 +179,14 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.index_locations
     }
 
+    fn content_addressed_archives(&self) -> bool {
+        self.link_mode == install_wheel_rs::linker::LinkMode::Farm && self.preview_mode.is_enabled()
+    }
+
+    fn repair_wheel_command(&self) -> Option<&str> {
+        self.repair_wheel_command
+    }
+
     async fn resolve<'data>(&'data self, requirements: &'data [Requirement]) -> Result<Resolution> {
         let python_requirement = PythonRequirement::from_interpreter(self.interpreter);
         let markers = self.interpreter.markers();
@@ -332,22 +376,65 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             return Err(anyhow!("Building source distributions is disabled"));
         }
 
-        let builder = SourceBuild::setup(
-            source,
-            subdirectory,
-            self.interpreter,
-            self,
-            self.source_build_context.clone(),
-            version_id.to_string(),
-            self.setup_py,
-            self.config_settings.clone(),
-            self.build_isolation,
-            build_kind,
-            self.build_extra_env_vars.clone(),
-            self.concurrency.builds,
-        )
-        .boxed_local()
-        .await?;
-        Ok(builder)
+        // Layer any variables declared for this package over those set for all builds.
+        let mut environment_variables = self.build_extra_env_vars.clone();
+        if let Some(variables) = dist
+            .map(distribution_types::Name::name)
+            .and_then(|name| self.package_build_env.get(name))
+        {
+            debug!(
+                "Setting build environment variables for {version_id}: {}",
+                variables.keys().join(", ")
+            );
+            environment_variables.extend(
+                variables
+                    .iter()
+                    .map(|(key, value)| (OsString::from(key), OsString::from(value))),
+            );
+        }
+
+        // Each attempt creates a fresh build environment, so transient failures (e.g., network
+        // timeouts while installing the build requirements) can be retried.
+        let mut attempt = 0;
+        loop {
+            match SourceBuild::setup(
+                source,
+                subdirectory,
+                self.interpreter,
+                self,
+                self.source_build_context.clone(),
+                version_id.to_string(),
+                self.setup_py,
+                self.config_settings.clone(),
+                self.build_isolation,
+                build_kind,
+                environment_variables.clone(),
+                self.concurrency.builds,
+            )
+            .boxed_local()
+            .await
+            {
+                Ok(builder) => return Ok(builder),
+                Err(err) if attempt < self.build_retries && err.is_transient() => {
+                    attempt += 1;
+                    warn!(
+                        "Transient failure while setting up build environment for {version_id}, retrying ({attempt}/{}): {err}",
+                        self.build_retries
+                    );
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+/// Read the number of times to retry setting up a build environment from `UV_BUILD_RETRIES`.
+fn build_retries() -> u32 {
+    match env::var("UV_BUILD_RETRIES") {
+        Ok(value) => value.parse::<u32>().unwrap_or_else(|_| {
+            warn_user_once!("Ignoring invalid value from environment for `UV_BUILD_RETRIES`. Expected a non-negative integer, got \"{value}\".");
+            DEFAULT_BUILD_RETRIES
+        }),
+        Err(_) => DEFAULT_BUILD_RETRIES,
     }
 }
@@ -20,7 +20,7 @@ use distribution_types::{
     SourceDist,
 };
 use platform_tags::Tags;
-use pypi_types::HashDigest;
+use pypi_types::{HashAlgorithm, HashDigest};
 use uv_cache::{ArchiveId, ArchiveTimestamp, CacheBucket, CacheEntry, Timestamp, WheelCache};
 use uv_client::{
     CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
@@ -97,6 +97,19 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         }
     }
 
+    /// Return the [`HashPolicy`] to enforce for the given distribution.
+    ///
+    /// When index responses are verified against TUF metadata, the hashes of registry
+    /// distributions are pinned to their signed targets, and are always validated on download.
+    pub fn hash_policy<'h>(&self, dist: &'h Dist, hashes: HashPolicy<'h>) -> HashPolicy<'h> {
+        if !hashes.is_validate() && self.client.unmanaged.index_verification().is_enabled() {
+            if let Some(file) = dist.file().filter(|file| !file.hashes.is_empty()) {
+                return HashPolicy::Validate(&file.hashes);
+            }
+        }
+        hashes
+    }
+
     /// Either fetch the wheel or fetch and build the source distribution
     ///
     /// Returns a wheel that's compliant with the given platform tags.
@@ -110,6 +123,7 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         tags: &Tags,
         hashes: HashPolicy<'_>,
     ) -> Result<LocalWheel, Error> {
+        let hashes = self.hash_policy(dist, hashes);
         match dist {
             Dist::Built(built) => self.get_wheel(built, hashes).await,
             Dist::Source(source) => self.build_wheel(source, tags, hashes).await,
@@ -127,6 +141,7 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         dist: &Dist,
         hashes: HashPolicy<'_>,
     ) -> Result<ArchiveMetadata, Error> {
+        let hashes = self.hash_policy(dist, hashes);
         match dist {
             Dist::Built(built) => self.get_wheel_metadata(built, hashes).await,
             Dist::Source(source) => {
@@ -533,8 +548,13 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                 let mut hashers = algorithms.into_iter().map(Hasher::from).collect::<Vec<_>>();
                 let mut hasher = uv_extract::hash::HashReader::new(reader.compat(), &mut hashers);
 
-                // Download and unzip the wheel to a temporary directory.
-                let temp_dir = tempfile::tempdir_in(self.build_context.cache().root())
+                // Download and unzip the wheel to a temporary directory. The central directory
+                // isn't available while streaming, so the compressed size serves as a lower bound
+                // on the space required.
+                let temp_dir = self
+                    .build_context
+                    .cache()
+                    .staging(size)
                     .map_err(Error::CacheWrite)?;
 
                 match progress {
@@ -552,11 +572,14 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                     hasher.finish().await.map_err(Error::HashExhaustion)?;
                 }
 
+                let hashes = hashers
+                    .into_iter()
+                    .map(HashDigest::from)
+                    .collect::<Vec<_>>();
+
                 // Persist the temporary directory to the directory store.
                 let id = self
-                    .build_context
-                    .cache()
-                    .persist(temp_dir.into_path(), wheel_entry.path())
+                    .persist_archive(temp_dir, wheel_entry.path(), &hashes)
                     .await
                     .map_err(Error::CacheRead)?;
 
@@ -564,10 +587,7 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                     reporter.on_download_complete(dist.name(), progress);
                 }
 
-                Ok(Archive::new(
-                    id,
-                    hashers.into_iter().map(HashDigest::from).collect(),
-                ))
+                Ok(Archive::new(id, hashes))
             }
             .instrument(info_span!("wheel", wheel = %dist))
         };
@@ -670,16 +690,21 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
                     }
                 }
 
-                // Unzip the wheel to a temporary directory.
-                let temp_dir = tempfile::tempdir_in(self.build_context.cache().root())
-                    .map_err(Error::CacheWrite)?;
+                // Unzip the wheel to a temporary directory, reserving space for its unpacked
+                // contents.
                 let mut file = writer.into_inner();
+                let size = unpacked_size(&file).await.or(size);
                 file.seek(io::SeekFrom::Start(0))
                     .await
                     .map_err(Error::CacheWrite)?;
+                let temp_dir = self
+                    .build_context
+                    .cache()
+                    .staging(size)
+                    .map_err(Error::CacheWrite)?;
 
                 // If no hashes are required, parallelize the unzip operation.
-                let hashes = if hashes.is_none() {
+                let hashes: Vec<HashDigest> = if hashes.is_none() {
                     let file = file.into_std().await;
                     tokio::task::spawn_blocking({
                         let target = temp_dir.path().to_owned();
@@ -707,9 +732,7 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
 
                 // Persist the temporary directory to the directory store.
                 let id = self
-                    .build_context
-                    .cache()
-                    .persist(temp_dir.into_path(), wheel_entry.path())
+                    .persist_archive(temp_dir, wheel_entry.path(), &hashes)
                     .await
                     .map_err(Error::CacheRead)?;
 
@@ -818,10 +841,20 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
             })
         } else {
             // If necessary, compute the hashes of the wheel.
-            let file = fs_err::tokio::File::open(path)
+            let mut file = fs_err::tokio::File::open(path)
+                .await
+                .map_err(Error::CacheRead)?;
+            let size = match unpacked_size(file.file()).await {
+                Some(size) => Some(size),
+                None => file.metadata().await.ok().map(|metadata| metadata.len()),
+            };
+            file.seek(io::SeekFrom::Start(0))
                 .await
                 .map_err(Error::CacheRead)?;
-            let temp_dir = tempfile::tempdir_in(self.build_context.cache().root())
+            let temp_dir = self
+                .build_context
+                .cache()
+                .staging(size)
                 .map_err(Error::CacheWrite)?;
 
             // Create a hasher for each hash algorithm.
@@ -835,13 +868,14 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
             // Exhaust the reader to compute the hash.
             hasher.finish().await.map_err(Error::HashExhaustion)?;
 
-            let hashes = hashers.into_iter().map(HashDigest::from).collect();
+            let hashes = hashers
+                .into_iter()
+                .map(HashDigest::from)
+                .collect::<Vec<_>>();
 
             // Persist the temporary directory to the directory store.
             let id = self
-                .build_context
-                .cache()
-                .persist(temp_dir.into_path(), wheel_entry.path())
+                .persist_archive(temp_dir, wheel_entry.path(), &hashes)
                 .await
                 .map_err(Error::CacheWrite)?;
 
@@ -868,10 +902,14 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
     async fn unzip_wheel(&self, path: &Path, target: &Path) -> Result<ArchiveId, Error> {
         let temp_dir = tokio::task::spawn_blocking({
             let path = path.to_owned();
-            let root = self.build_context.cache().root().to_path_buf();
+            let cache = self.build_context.cache().clone();
             move || -> Result<TempDir, uv_extract::Error> {
                 // Unzip the wheel into a temporary directory.
-                let temp_dir = tempfile::tempdir_in(root)?;
+                let size = fs_err::File::open(&path)
+                    .ok()
+                    .and_then(|file| uv_extract::unpacked_size(file).ok())
+                    .or_else(|| fs_err::metadata(&path).ok().map(|metadata| metadata.len()));
+                let temp_dir = cache.staging(size)?;
                 uv_extract::unzip(fs_err::File::open(path)?, temp_dir.path())?;
                 Ok(temp_dir)
             }
@@ -889,6 +927,33 @@ impl<'a, Context: BuildContext> DistributionDatabase<'a, Context> {
         Ok(id)
     }
 
+    /// Persist an unzipped wheel to the cache, returning the ID of the archive.
+    ///
+    /// If the build context requests content-addressed archives (as with `--link-mode=farm`) and
+    /// the SHA-256 digest of the wheel is known, identical wheels are only stored once.
+    async fn persist_archive(
+        &self,
+        temp_dir: TempDir,
+        path: &Path,
+        hashes: &[HashDigest],
+    ) -> io::Result<ArchiveId> {
+        let cache = self.build_context.cache();
+        if !self.build_context.content_addressed_archives() {
+            return cache.persist(temp_dir.into_path(), path).await;
+        }
+        match hashes
+            .iter()
+            .find(|hash| hash.algorithm == HashAlgorithm::Sha256)
+        {
+            Some(hash) => {
+                cache
+                    .persist_sha256(temp_dir.into_path(), path, &hash.digest)
+                    .await
+            }
+            None => cache.persist(temp_dir.into_path(), path).await,
+        }
+    }
+
     /// Returns a GET [`reqwest::Request`] for the given URL.
     fn request(&self, url: Url) -> Result<reqwest::Request, reqwest::Error> {
         self.client
@@ -944,6 +1009,15 @@ impl<'a> ManagedClient<'a> {
     }
 }
 
+/// Read the unpacked size of a wheel from the central directory of the zip file, if possible.
+async fn unpacked_size(file: &tokio::fs::File) -> Option<u64> {
+    let file = file.try_clone().await.ok()?.into_std().await;
+    tokio::task::spawn_blocking(move || uv_extract::unpacked_size(file).ok())
+        .await
+        .ok()
+        .flatten()
+}
+
 /// Returns the value of the `Content-Length` header from the [`reqwest::Response`], if present.
 fn content_length(response: &reqwest::Response) -> Option<u64> {
     response
@@ -7,6 +7,7 @@ use distribution_types::{CachedRegistryDist, Hashed, IndexLocations, IndexUrl};
 use pep440_rs::Version;
 use platform_tags::Tags;
 use uv_cache::{Cache, CacheBucket, WheelCache};
+use uv_configuration::BuildOptions;
 use uv_fs::{directories, files, symlinks};
 use uv_normalize::PackageName;
 use uv_types::HashStrategy;
@@ -21,7 +22,17 @@ pub struct RegistryWheelIndex<'a> {
     tags: &'a Tags,
     index_locations: &'a IndexLocations,
     hasher: &'a HashStrategy,
-    index: FxHashMap<&'a PackageName, BTreeMap<Version, CachedRegistryDist>>,
+    build_options: &'a BuildOptions,
+    index: FxHashMap<&'a PackageName, IndexedWheels>,
+}
+
+/// The wheels indexed for a package, keyed by version.
+#[derive(Debug, Default)]
+struct IndexedWheels {
+    /// The best wheel for each version, whether downloaded from the registry or built from source.
+    all: BTreeMap<Version, CachedRegistryDist>,
+    /// The best wheel for each version that was downloaded from the registry.
+    prebuilt: BTreeMap<Version, CachedRegistryDist>,
 }
 
 impl<'a> RegistryWheelIndex<'a> {
@@ -31,12 +42,14 @@ impl<'a> RegistryWheelIndex<'a> {
         tags: &'a Tags,
         index_locations: &'a IndexLocations,
         hasher: &'a HashStrategy,
+        build_options: &'a BuildOptions,
     ) -> Self {
         Self {
             cache,
             tags,
             index_locations,
             hasher,
+            build_options,
             index: FxHashMap::default(),
         }
     }
@@ -48,7 +61,18 @@ impl<'a> RegistryWheelIndex<'a> {
         &mut self,
         name: &'a PackageName,
     ) -> impl Iterator<Item = (&Version, &CachedRegistryDist)> {
-        self.get_impl(name).iter().rev()
+        self.get_impl(name).all.iter().rev()
+    }
+
+    /// Return an iterator over the available wheels for a given package that were downloaded from
+    /// the registry, omitting any wheels that were built from source.
+    ///
+    /// If the package is not yet indexed, this will index the package by reading from the cache.
+    pub fn get_prebuilt(
+        &mut self,
+        name: &'a PackageName,
+    ) -> impl Iterator<Item = (&Version, &CachedRegistryDist)> {
+        self.get_impl(name).prebuilt.iter().rev()
     }
 
     /// Get the best wheel for the given package name and version.
@@ -59,11 +83,11 @@ impl<'a> RegistryWheelIndex<'a> {
         name: &'a PackageName,
         version: &Version,
     ) -> Option<&CachedRegistryDist> {
-        self.get_impl(name).get(version)
+        self.get_impl(name).all.get(version)
     }
 
     /// Get an entry in the index.
-    fn get_impl(&mut self, name: &'a PackageName) -> &BTreeMap<Version, CachedRegistryDist> {
+    fn get_impl(&mut self, name: &'a PackageName) -> &IndexedWheels {
         let versions = match self.index.entry(name) {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => entry.insert(Self::index(
@@ -72,6 +96,7 @@ impl<'a> RegistryWheelIndex<'a> {
                 self.tags,
                 self.index_locations,
                 self.hasher,
+                self.build_options,
             )),
         };
         versions
@@ -84,8 +109,13 @@ impl<'a> RegistryWheelIndex<'a> {
         tags: &Tags,
         index_locations: &IndexLocations,
         hasher: &HashStrategy,
-    ) -> BTreeMap<Version, CachedRegistryDist> {
-        let mut versions = BTreeMap::new();
+        build_options: &BuildOptions,
+    ) -> IndexedWheels {
+        let mut versions = IndexedWheels::default();
+
+        // If wheels are disabled or source distributions are preferred, only consider the wheels
+        // that were built from source; wheels downloaded from the registry are cached separately.
+        let source_only = build_options.source_only_package(package);
 
         // Collect into owned `IndexUrl`.
         let flat_index_urls: Vec<IndexUrl> = index_locations
@@ -95,49 +125,57 @@ impl<'a> RegistryWheelIndex<'a> {
 
         for index_url in index_locations.indexes().chain(flat_index_urls.iter()) {
             // Index all the wheels that were downloaded directly from the registry.
-            let wheel_dir = cache.shard(
-                CacheBucket::Wheels,
-                WheelCache::Index(index_url).wheel_dir(package.to_string()),
-            );
+            if !source_only {
+                let wheel_dir = cache.shard(
+                    CacheBucket::Wheels,
+                    WheelCache::Index(index_url).wheel_dir(package.to_string()),
+                );
 
-            // For registry wheels, the cache structure is: `<index>/<package-name>/<wheel>.http`
-            // or `<index>/<package-name>/<version>/<wheel>.rev`.
-            for file in files(&wheel_dir) {
-                match index_url {
-                    // Add files from remote registries.
-                    IndexUrl::Pypi(_) | IndexUrl::Url(_) => {
-                        if file
-                            .extension()
-                            .is_some_and(|ext| ext.eq_ignore_ascii_case("http"))
-                        {
-                            if let Some(wheel) =
-                                CachedWheel::from_http_pointer(wheel_dir.join(file), cache)
+                // For registry wheels, the cache structure is: `<index>/<package-name>/<wheel>.http`
+                // or `<index>/<package-name>/<version>/<wheel>.rev`.
+                for file in files(&wheel_dir) {
+                    match index_url {
+                        // Add files from remote registries.
+                        IndexUrl::Pypi(_) | IndexUrl::Url(_) => {
+                            if file
+                                .extension()
+                                .is_some_and(|ext| ext.eq_ignore_ascii_case("http"))
                             {
-                                // Enforce hash-checking based on the built distribution.
-                                if wheel.satisfies(
-                                    hasher
-                                        .get_package(&wheel.filename.name, &wheel.filename.version),
-                                ) {
-                                    Self::add_wheel(wheel, tags, &mut versions);
+                                if let Some(wheel) =
+                                    CachedWheel::from_http_pointer(wheel_dir.join(file), cache)
+                                {
+                                    // Enforce hash-checking based on the built distribution.
+                                    if wheel.satisfies(
+                                        hasher.get_package(
+                                            &wheel.filename.name,
+                                            &wheel.filename.version,
+                                        ),
+                                    ) {
+                                        Self::add_wheel(&wheel, tags, &mut versions.prebuilt);
+                                        Self::add_wheel(&wheel, tags, &mut versions.all);
+                                    }
                                 }
                             }
                         }
-                    }
-                    // Add files from local registries (e.g., `--find-links`).
-                    IndexUrl::Path(_) => {
-                        if file
-                            .extension()
-                            .is_some_and(|ext| ext.eq_ignore_ascii_case("rev"))
-                        {
-                            if let Some(wheel) =
-                                CachedWheel::from_local_pointer(wheel_dir.join(file), cache)
+                        // Add files from local registries (e.g., `--find-links`).
+                        IndexUrl::Path(_) => {
+                            if file
+                                .extension()
+                                .is_some_and(|ext| ext.eq_ignore_ascii_case("rev"))
                             {
-                                // Enforce hash-checking based on the built distribution.
-                                if wheel.satisfies(
-                                    hasher
-                                        .get_package(&wheel.filename.name, &wheel.filename.version),
-                                ) {
-                                    Self::add_wheel(wheel, tags, &mut versions);
+                                if let Some(wheel) =
+                                    CachedWheel::from_local_pointer(wheel_dir.join(file), cache)
+                                {
+                                    // Enforce hash-checking based on the built distribution.
+                                    if wheel.satisfies(
+                                        hasher.get_package(
+                                            &wheel.filename.name,
+                                            &wheel.filename.version,
+                                        ),
+                                    ) {
+                                        Self::add_wheel(&wheel, tags, &mut versions.prebuilt);
+                                        Self::add_wheel(&wheel, tags, &mut versions.all);
+                                    }
                                 }
                             }
                         }
@@ -186,7 +224,7 @@ impl<'a> RegistryWheelIndex<'a> {
                             if revision.satisfies(
                                 hasher.get_package(&wheel.filename.name, &wheel.filename.version),
                             ) {
-                                Self::add_wheel(wheel, tags, &mut versions);
+                                Self::add_wheel(&wheel, tags, &mut versions.all);
                             }
                         }
                     }
@@ -199,11 +237,11 @@ impl<'a> RegistryWheelIndex<'a> {
 
     /// Add the [`CachedWheel`] to the index.
     fn add_wheel(
-        wheel: CachedWheel,
+        wheel: &CachedWheel,
         tags: &Tags,
         versions: &mut BTreeMap<Version, CachedRegistryDist>,
     ) {
-        let dist_info = wheel.into_registry_dist();
+        let dist_info = wheel.clone().into_registry_dist();
 
         // Pick the wheel with the highest priority
         let compatibility = dist_info.filename.compatibility(tags);
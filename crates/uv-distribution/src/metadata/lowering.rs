@@ -85,7 +85,9 @@ impl LoweredRequirement {
                 }
                 git_source(&git, subdirectory, rev, tag, branch)?
             }
-            Source::Url { url, subdirectory } => {
+            Source::Url {
+                url, subdirectory, ..
+            } => {
                 if matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))) {
                     return Err(LoweringError::ConflictingUrls);
                 }
@@ -192,7 +194,9 @@ impl LoweredRequirement {
                 }
                 git_source(&git, subdirectory, rev, tag, branch)?
             }
-            Source::Url { url, subdirectory } => {
+            Source::Url {
+                url, subdirectory, ..
+            } => {
                 if matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_))) {
                     return Err(LoweringError::ConflictingUrls);
                 }
@@ -66,7 +66,8 @@ impl RequiresDist {
         project_workspace: &ProjectWorkspace,
         preview_mode: PreviewMode,
     ) -> Result<Self, MetadataError> {
-        // Collect any `tool.uv.sources` and `tool.uv.dev_dependencies` from `pyproject.toml`.
+        // Collect any `tool.uv.sources`, `tool.uv.dev_dependencies`, and `dependency-groups` from
+        // `pyproject.toml`.
         let empty = BTreeMap::default();
         let sources = project_workspace
             .current_project()
@@ -78,9 +79,11 @@ impl RequiresDist {
             .unwrap_or(&empty);
 
         let dev_dependencies = {
-            let dev_dependencies = project_workspace
-                .current_project()
-                .pyproject_toml()
+            let pyproject_toml = project_workspace.current_project().pyproject_toml();
+
+            // The `tool.uv.dev-dependencies` are modeled as the `dev` group.
+            let mut groups = BTreeMap::default();
+            let dev_dependencies = pyproject_toml
                 .tool
                 .as_ref()
                 .and_then(|tool| tool.uv.as_ref())
@@ -88,25 +91,49 @@ impl RequiresDist {
                 .into_iter()
                 .flatten()
                 .cloned()
-                .map(|requirement| {
-                    let requirement_name = requirement.name.clone();
-                    LoweredRequirement::from_requirement(
-                        requirement,
-                        &metadata.name,
-                        project_workspace.project_root(),
-                        sources,
-                        project_workspace.workspace(),
-                        preview_mode,
-                    )
-                    .map(LoweredRequirement::into_inner)
-                    .map_err(|err| MetadataError::LoweringError(requirement_name.clone(), err))
-                })
-                .collect::<Result<Vec<_>, _>>()?;
-            if dev_dependencies.is_empty() {
-                BTreeMap::default()
-            } else {
-                BTreeMap::from([(DEV_DEPENDENCIES.clone(), dev_dependencies)])
+                .collect::<Vec<_>>();
+            if !dev_dependencies.is_empty() {
+                groups.insert(DEV_DEPENDENCIES.clone(), dev_dependencies);
+            }
+
+            // Expand any groups that include other groups, such that each group contains the
+            // full set of requirements it's composed of.
+            if let Some(dependency_groups) = pyproject_toml.dependency_groups.as_ref() {
+                let dependency_groups = dependency_groups
+                    .flatten(project_workspace.project_root())
+                    .map_err(|err| MetadataError::DependencyGroup(metadata.name.clone(), err))?;
+                for (group, requirements) in dependency_groups {
+                    groups
+                        .entry(group)
+                        .or_insert_with(Vec::new)
+                        .extend(requirements);
+                }
             }
+
+            groups
+                .into_iter()
+                .map(|(group, requirements)| {
+                    let requirements = requirements
+                        .into_iter()
+                        .map(|requirement| {
+                            let requirement_name = requirement.name.clone();
+                            LoweredRequirement::from_requirement(
+                                requirement,
+                                &metadata.name,
+                                project_workspace.project_root(),
+                                sources,
+                                project_workspace.workspace(),
+                                preview_mode,
+                            )
+                            .map(LoweredRequirement::into_inner)
+                            .map_err(|err| {
+                                MetadataError::LoweringError(requirement_name.clone(), err)
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok((group, requirements))
+                })
+                .collect::<Result<BTreeMap<_, _>, MetadataError>>()?
         };
 
         let requires_dist = metadata
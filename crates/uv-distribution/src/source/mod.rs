@@ -1,6 +1,7 @@
 //! Fetch and build source distributions from remote sources.
 
 use std::borrow::Cow;
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -30,6 +31,7 @@ use uv_client::{
 use uv_configuration::{BuildKind, PreviewMode};
 use uv_extract::hash::Hasher;
 use uv_fs::{rename_with_retry, write_atomic, LockedFile};
+use uv_normalize::PackageName;
 use uv_types::{BuildContext, SourceBuildTrait};
 
 use crate::distribution_database::ManagedClient;
@@ -42,6 +44,7 @@ use crate::{Reporter, RequiresDist};
 
 mod built_wheel_metadata;
 mod revision;
+mod toolchain;
 
 /// Fetch and build a source distribution from a remote source, or from a local cache.
 pub(crate) struct SourceDistributionBuilder<'a, T: BuildContext> {
@@ -59,6 +62,10 @@ pub(crate) const LOCAL_REVISION: &str = "revision.rev";
 /// The name of the file that contains the cached distribution metadata, encoded via `MsgPack`.
 pub(crate) const METADATA: &str = "metadata.msgpack";
 
+/// The name of the file that lists the packages in the build environments of the wheels built from
+/// source, encoded via `MsgPack`.
+pub(crate) const BUILD_INPUTS: &str = "build-inputs.msgpack";
+
 impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
     /// Initialize a [`SourceDistributionBuilder`] from a [`BuildContext`].
     pub(crate) fn new(build_context: &'a T, preview_mode: PreviewMode) -> Self {
@@ -467,6 +474,10 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         // Scope all operations to the revision. Within the revision, there's no need to check for
         // freshness, since entries have to be fresher than the revision itself.
         let cache_shard = cache_shard.shard(revision.id());
+        let source_dist_entry = cache_shard.entry(filename);
+
+        // Scope any built artifacts to the build environment variables for the package, if any.
+        let cache_shard = self.build_env_shard(source, cache_shard);
 
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(tags, &cache_shard) {
@@ -479,7 +490,6 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             .map(|reporter| reporter.on_build_start(source));
 
         // Build the source distribution.
-        let source_dist_entry = cache_shard.entry(filename);
         let (disk_filename, wheel_filename, metadata) = self
             .build_distribution(source, source_dist_entry.path(), subdirectory, &cache_shard)
             .await?;
@@ -538,6 +548,10 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         // Scope all operations to the revision. Within the revision, there's no need to check for
         // freshness, since entries have to be fresher than the revision itself.
         let cache_shard = cache_shard.shard(revision.id());
+        let source_dist_entry = cache_shard.entry(filename);
+
+        // Scope any built artifacts to the build environment variables for the package, if any.
+        let cache_shard = self.build_env_shard(source, cache_shard);
 
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
@@ -550,7 +564,6 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         }
 
         // Otherwise, we either need to build the metadata or the wheel.
-        let source_dist_entry = cache_shard.entry(filename);
 
         // If the backend supports `prepare_metadata_for_build_wheel`, use it.
         if let Some(metadata) = self
@@ -699,14 +712,16 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         // Scope all operations to the revision. Within the revision, there's no need to check for
         // freshness, since entries have to be fresher than the revision itself.
         let cache_shard = cache_shard.shard(revision.id());
+        let source_entry = cache_shard.entry("source");
+
+        // Scope any built artifacts to the build environment variables for the package, if any.
+        let cache_shard = self.build_env_shard(source, cache_shard);
 
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(tags, &cache_shard) {
             return Ok(built_wheel);
         }
 
-        let source_entry = cache_shard.entry("source");
-
         // Otherwise, we need to build a wheel.
         let task = self
             .reporter
@@ -767,6 +782,10 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         // Scope all operations to the revision. Within the revision, there's no need to check for
         // freshness, since entries have to be fresher than the revision itself.
         let cache_shard = cache_shard.shard(revision.id());
+        let source_entry = cache_shard.entry("source");
+
+        // Scope any built artifacts to the build environment variables for the package, if any.
+        let cache_shard = self.build_env_shard(source, cache_shard);
 
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
@@ -778,8 +797,6 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
             });
         }
 
-        let source_entry = cache_shard.entry("source");
-
         // If the backend supports `prepare_metadata_for_build_wheel`, use it.
         if let Some(metadata) = self
             .build_metadata(source, source_entry.path(), None)
@@ -916,6 +933,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         // freshness, since entries have to be fresher than the revision itself.
         let cache_shard = cache_shard.shard(revision.id());
 
+        // Scope any built artifacts to the build environment variables for the package, if any.
+        let cache_shard = self.build_env_shard(source, cache_shard);
+
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(tags, &cache_shard) {
             return Ok(built_wheel);
@@ -987,6 +1007,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         // freshness, since entries have to be fresher than the revision itself.
         let cache_shard = cache_shard.shard(revision.id());
 
+        // Scope any built artifacts to the build environment variables for the package, if any.
+        let cache_shard = self.build_env_shard(source, cache_shard);
+
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
         if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
@@ -1146,6 +1169,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
 
         let _lock = lock_shard(&cache_shard).await?;
 
+        // Scope any built artifacts to the build environment variables for the package, if any.
+        let cache_shard = self.build_env_shard(source, cache_shard);
+
         // If the cache contains a compatible wheel, return it.
         if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(tags, &cache_shard) {
             return Ok(built_wheel);
@@ -1216,6 +1242,9 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
 
         let _lock = lock_shard(&cache_shard).await?;
 
+        // Scope any built artifacts to the build environment variables for the package, if any.
+        let cache_shard = self.build_env_shard(source, cache_shard);
+
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
         if self
@@ -1414,6 +1443,30 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         Ok(hashes)
     }
 
+    /// Scope the cache shard to the extra environment variables declared for the package's build,
+    /// if any, such that wheels built with different variables don't share a cache entry.
+    ///
+    /// Similarly, scope the shard to the compiler toolchain (i.e., the default compilers, along
+    /// with any configured via `CC`, `SDKROOT`, or `CUDA_HOME`), such that switching toolchains
+    /// doesn't reuse wheels built against an incompatible ABI.
+    ///
+    /// Finally, scope the shard to the command used to repair built wheels (via
+    /// `--repair-wheel-command`), if any, since it rewrites the wheel itself.
+    fn build_env_shard(&self, source: &BuildableSource<'_>, cache_shard: CacheShard) -> CacheShard {
+        let cache_shard = match source
+            .name()
+            .and_then(|name| self.build_context.package_build_env().get(name))
+        {
+            Some(variables) => cache_shard.shard(cache_key::cache_digest(variables)),
+            None => cache_shard,
+        };
+        let cache_shard = cache_shard.shard(toolchain::toolchain_digest());
+        match self.build_context.repair_wheel_command() {
+            Some(command) => cache_shard.shard(cache_key::cache_digest(&command)),
+            None => cache_shard,
+        }
+    }
+
     /// Build a source distribution, storing the built wheel in the cache.
     ///
     /// Returns the un-normalized disk filename, the parsed, normalized filename and the metadata
@@ -1444,7 +1497,7 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
         fs::create_dir_all(&cache_shard)
             .await
             .map_err(Error::CacheWrite)?;
-        let disk_filename = self
+        let builder = self
             .build_context
             .setup_build(
                 source_root,
@@ -1458,11 +1511,16 @@ impl<'a, T: BuildContext> SourceDistributionBuilder<'a, T> {
                 },
             )
             .await
-            .map_err(|err| Error::Build(source.to_string(), err))?
+            .map_err(|err| Error::Build(source.to_string(), err))?;
+        let disk_filename = builder
             .wheel(cache_shard)
             .await
             .map_err(|err| Error::Build(source.to_string(), err))?;
 
+        // Record the packages in the build environment, such that the wheel can be invalidated
+        // alongside them (e.g., via `uv cache clean`).
+        write_build_inputs(cache_shard, builder.build_inputs()).await?;
+
         // Read the metadata from the wheel.
         let filename = WheelFilename::from_str(&disk_filename)?;
         let metadata = read_wheel_metadata(&filename, cache_shard.join(&disk_filename))?;
@@ -1773,6 +1831,32 @@ fn read_wheel_metadata(
     Ok(Metadata23::parse_metadata(&dist_info)?)
 }
 
+/// Record the packages in the build environment of a wheel built into the given [`CacheShard`],
+/// alongside those of any other wheels built into the same shard (e.g., for other platforms).
+async fn write_build_inputs(
+    cache_shard: &CacheShard,
+    build_inputs: &BTreeSet<PackageName>,
+) -> Result<(), Error> {
+    if build_inputs.is_empty() {
+        return Ok(());
+    }
+
+    let path = cache_shard.join(BUILD_INPUTS);
+    let mut recorded = match fs::read(&path).await {
+        Ok(cached) => rmp_serde::from_slice::<BTreeSet<PackageName>>(&cached).unwrap_or_default(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeSet::new(),
+        Err(err) => return Err(Error::CacheRead(err)),
+    };
+    if recorded.is_superset(build_inputs) {
+        return Ok(());
+    }
+    recorded.extend(build_inputs.iter().cloned());
+
+    write_atomic(path, rmp_serde::to_vec(&recorded)?)
+        .await
+        .map_err(Error::CacheWrite)
+}
+
 /// Apply an advisory lock to a [`CacheShard`] to prevent concurrent builds.
 async fn lock_shard(cache_shard: &CacheShard) -> Result<LockedFile, Error> {
     let root = cache_shard.as_ref();
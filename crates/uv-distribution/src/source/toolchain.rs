@@ -0,0 +1,114 @@
+//! Fingerprints of the compiler toolchain used to build source distributions.
+//!
+//! Wheels built with extension modules are only compatible with the toolchain (and SDK) they were
+//! built against, which isn't reflected in their tags. The fingerprint covers the default
+//! compilers, along with any toolchain configured via the conventional environment variables
+//! (e.g., `CC`, `SDKROOT`, or `CUDA_HOME`), and is incorporated into the cache key for any built
+//! wheels, such that switching toolchains triggers a rebuild rather than silently reusing an
+//! incompatible wheel.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+
+use tracing::debug;
+
+/// The compilers whose versions are included in the fingerprint, along with the command that's
+/// used by default when the variable isn't set.
+const COMPILERS: &[(&str, &str)] = &[("CC", "cc"), ("CXX", "c++")];
+
+/// The variables that point to a CUDA installation.
+const CUDA: &[&str] = &["CUDA_HOME", "CUDA_PATH"];
+
+/// The fingerprint of the current toolchain, computed once per process.
+static TOOLCHAIN: LazyLock<String> = LazyLock::new(|| {
+    let fingerprint = fingerprint();
+    debug!("Detected toolchain fingerprint: {fingerprint:?}");
+    cache_key::cache_digest(&fingerprint)
+});
+
+/// Return the digest of the toolchain used to build source distributions.
+pub(crate) fn toolchain_digest() -> &'static str {
+    TOOLCHAIN.as_str()
+}
+
+/// Collect the toolchain variables, along with the versions they resolve to.
+fn fingerprint() -> BTreeMap<&'static str, String> {
+    let mut fingerprint = BTreeMap::new();
+
+    // Include the version reported by each compiler, since the same command (e.g., `gcc`) may
+    // refer to a different compiler over time. If the variable isn't set, fingerprint the default
+    // compiler instead, such that upgrading the system compiler also triggers a rebuild.
+    for &(var, default) in COMPILERS {
+        let value = env(var).unwrap_or_else(|| default.to_string());
+        let version = compiler_version(&value).unwrap_or_default();
+        fingerprint.insert(var, format!("{value} {version}"));
+    }
+
+    // Resolve the SDK path, since it's commonly a symlink to the latest versioned SDK (e.g.,
+    // `MacOSX.sdk` to `MacOSX14.2.sdk`).
+    if let Some(value) = env("SDKROOT") {
+        let value = fs_err::canonicalize(&value)
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or(value);
+        fingerprint.insert("SDKROOT", value);
+    }
+    if let Some(value) = env("MACOSX_DEPLOYMENT_TARGET") {
+        fingerprint.insert("MACOSX_DEPLOYMENT_TARGET", value);
+    }
+
+    // Include the version of the CUDA installation, if it can be determined.
+    for &var in CUDA {
+        let Some(value) = env(var) else {
+            continue;
+        };
+        let version = cuda_version(Path::new(&value)).unwrap_or_default();
+        fingerprint.insert(var, format!("{value} {version}"));
+    }
+    if let Some(value) = env("CUDA_VERSION") {
+        fingerprint.insert("CUDA_VERSION", value);
+    }
+
+    fingerprint
+}
+
+/// Read a non-empty environment variable.
+fn env(var: &str) -> Option<String> {
+    std::env::var(var)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Return the first line of the output of `<compiler> --version`.
+///
+/// The compiler may be specified with a wrapper or arguments (e.g., `ccache gcc`), which are
+/// passed through.
+fn compiler_version(compiler: &str) -> Option<String> {
+    let mut parts = compiler.split_whitespace();
+    let program = parts.next()?;
+    let output = Command::new(program)
+        .args(parts)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .inspect_err(|err| debug!("Failed to query compiler version for `{compiler}`: {err}"))
+        .ok()?;
+    if !output.status.success() {
+        debug!("Failed to query compiler version for `{compiler}`");
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+}
+
+/// Return the version of the CUDA installation at the given root, as recorded in its
+/// `version.json` (CUDA 11 and later) or `version.txt` (earlier releases).
+fn cuda_version(root: &Path) -> Option<String> {
+    ["version.json", "version.txt"]
+        .iter()
+        .find_map(|file| fs_err::read_to_string(root.join(file)).ok())
+}
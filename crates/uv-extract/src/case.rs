@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use rustc_hash::FxHashMap;
+use tracing::debug;
+
+use uv_fs::CASE_SHADOWED_DIR;
+
+/// An archive entry that can't be extracted to its own path on a case-insensitive filesystem, since
+/// a later entry has the same path, up to case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CaseConflict {
+    /// The index of the shadowed entry within the archive.
+    pub(crate) index: usize,
+    /// The path of the shadowed entry.
+    pub(crate) shadowed: String,
+    /// The path of the entry that's extracted in its place.
+    pub(crate) retained: String,
+}
+
+/// Find the archive entries whose paths differ from that of a later entry only by case.
+///
+/// On a case-insensitive filesystem, such entries refer to the same file. Extracting the archive
+/// sequentially would leave the contents of the last entry under the name of the first, so the
+/// last entry is retained at its own path, while the earlier entries are extracted to
+/// [`shadowed_path`].
+pub(crate) fn find_case_conflicts<S: AsRef<str>>(names: &[S]) -> Vec<CaseConflict> {
+    let mut last = FxHashMap::default();
+    for (index, name) in names.iter().enumerate() {
+        last.insert(name.as_ref().to_lowercase(), index);
+    }
+
+    names
+        .iter()
+        .enumerate()
+        .filter_map(|(index, name)| {
+            let retained = names[last[&name.as_ref().to_lowercase()]].as_ref();
+            if retained == name.as_ref() {
+                return None;
+            }
+            Some(CaseConflict {
+                index,
+                shadowed: name.as_ref().to_string(),
+                retained: retained.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Return the path at which a shadowed archive entry is extracted, within the
+/// [`CASE_SHADOWED_DIR`] of the target directory.
+pub(crate) fn shadowed_path(target: &Path, index: usize, name: &Path) -> PathBuf {
+    target
+        .join(CASE_SHADOWED_DIR)
+        .join(index.to_string())
+        .join(name)
+}
+
+/// Log the archive entries that were extracted aside due to case conflicts.
+pub(crate) fn debug_case_conflicts(conflicts: &[CaseConflict]) {
+    for conflict in conflicts {
+        debug!(
+            "Extracting `{}` to `{CASE_SHADOWED_DIR}`, since it differs only by case from `{}`",
+            conflict.shadowed, conflict.retained
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_conflicts() {
+        let names = [
+            "pkg/__init__.py",
+            "pkg/Foo.py",
+            "pkg/foo.py",
+            "pkg/FOO.py",
+            "pkg/bar.py",
+            "PKG/bar.py",
+        ];
+        let conflicts = find_case_conflicts(&names);
+        assert_eq!(
+            conflicts
+                .iter()
+                .map(|conflict| (conflict.index, conflict.retained.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "pkg/FOO.py"), (2, "pkg/FOO.py"), (4, "PKG/bar.py")]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub use error::Error;
 pub use sync::*;
 
+mod case;
 mod error;
 pub mod hash;
 pub mod stream;
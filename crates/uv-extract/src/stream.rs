@@ -1,12 +1,14 @@
 use std::path::Path;
 use std::pin::Pin;
 
+use crate::case::{debug_case_conflicts, shadowed_path, CaseConflict};
 use crate::Error;
 use distribution_filename::SourceDistExtension;
 use futures::StreamExt;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
 use tracing::warn;
+use uv_fs::is_case_insensitive;
 
 const DEFAULT_BUF_SIZE: usize = 128 * 1024;
 
@@ -25,12 +27,48 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
 
     let mut directories = FxHashSet::default();
 
+    // The index and path of each entry seen so far, keyed by the lowercased path, used to detect
+    // entries whose paths differ only by case.
+    let mut seen = FxHashMap::default();
+    let mut conflicts = Vec::new();
+    let mut case_insensitive = None;
+
+    let mut index = 0;
     while let Some(mut entry) = zip.next_with_entry().await? {
         // Construct the (expected) path to the file on-disk.
-        let path = entry.reader().entry().filename().as_str()?;
-        let path = target.join(path);
+        let name = entry.reader().entry().filename().as_str()?.to_string();
+        let path = target.join(&name);
         let is_dir = entry.reader().entry().dir()?;
 
+        // On a case-insensitive filesystem, an entry whose path differs from that of an earlier
+        // entry only by case refers to the same file. Move the earlier file aside, such that the
+        // file on-disk is named after the last entry, as well as containing its contents, while
+        // the earlier file can still be installed to its own path on a case-sensitive filesystem.
+        if !is_dir {
+            if let Some((previous_index, previous)) =
+                seen.insert(name.to_lowercase(), (index, name.clone()))
+            {
+                if previous != name {
+                    let insensitive = match case_insensitive {
+                        Some(insensitive) => insensitive,
+                        None => *case_insensitive.insert(is_case_insensitive(target)?),
+                    };
+                    if insensitive {
+                        let shadowed = shadowed_path(target, previous_index, Path::new(&previous));
+                        if let Some(parent) = shadowed.parent() {
+                            fs_err::tokio::create_dir_all(parent).await?;
+                        }
+                        fs_err::tokio::rename(target.join(&previous), shadowed).await?;
+                        conflicts.push(CaseConflict {
+                            index: previous_index,
+                            shadowed: previous,
+                            retained: name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
         // Either create the directory or write the file to disk.
         if is_dir {
             if directories.insert(path.clone()) {
@@ -58,8 +96,11 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
         // Close current file to get access to the next one. See docs:
         // https://docs.rs/async_zip/0.0.16/async_zip/base/read/stream/
         zip = entry.skip().await?;
+        index += 1;
     }
 
+    debug_case_conflicts(&conflicts);
+
     // On Unix, we need to set file permissions, which are stored in the central directory, at the
     // end of the archive. The `ZipFileReader` reads until it sees a central directory signature,
     // which indicates the first entry in the central directory. So we continue reading from there.
@@ -82,9 +123,13 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
             // https://github.com/pypa/pip/blob/3898741e29b7279e7bffe044ecfbe20f6a438b1e/src/pip/_internal/utils/unpacking.py#L88-L100
             let has_any_executable_bit = mode & 0o111;
             if has_any_executable_bit != 0 {
-                // Construct the (expected) path to the file on-disk.
-                let path = entry.filename().as_str()?;
-                let path = target.join(path);
+                // Construct the (expected) path to the file on-disk, accounting for any entries
+                // that were moved aside, since they were shadowed by a later entry.
+                let name = entry.filename().as_str()?;
+                let path = match conflicts.iter().find(|conflict| conflict.shadowed == name) {
+                    Some(conflict) => shadowed_path(target, conflict.index, Path::new(name)),
+                    None => target.join(name),
+                };
 
                 let permissions = fs_err::tokio::metadata(&path).await?.permissions();
                 if permissions.mode() & 0o111 != 0o111 {
@@ -227,6 +272,166 @@ pub async fn untar_xz<R: tokio::io::AsyncRead + Unpin>(
     Ok(())
 }
 
+/// The maximum number of files to write concurrently in [`untar_parallel`].
+const MAX_CONCURRENT_WRITES: usize = 64;
+
+/// Unpack the entries of a tar archive into the destination directory, writing regular files on
+/// the blocking thread pool.
+///
+/// The contents of each file are buffered in memory, such that the archive can continue to be
+/// decompressed while earlier files are written. Other entries (e.g., links) are unpacked in
+/// order, once any pending writes have completed.
+///
+/// Files beneath a symlink are also unpacked in order, via [`tokio_tar::Entry::unpack_in`], which
+/// verifies that the resolved path remains within the destination directory.
+async fn untar_in_parallel<'a>(
+    mut archive: tokio_tar::Archive<&'a mut (dyn tokio::io::AsyncRead + Unpin)>,
+    dst: &Path,
+) -> std::io::Result<()> {
+    let mut writes = tokio::task::JoinSet::new();
+    let mut pending = FxHashSet::default();
+    let mut symlinks = Vec::new();
+
+    let mut entries = archive.entries()?;
+    let mut pinned = Pin::new(&mut entries);
+    while let Some(entry) = pinned.next().await {
+        let mut file = entry?;
+        let entry_type = file.header().entry_type();
+
+        // On Windows, skip symlink entries, as they're not supported. pip recursively copies the
+        // symlink target instead.
+        if cfg!(windows) && entry_type.is_symlink() {
+            warn!(
+                "Skipping symlink in tar archive: {}",
+                file.path()?.display()
+            );
+            continue;
+        }
+
+        // Directories can be created alongside any pending writes.
+        if entry_type.is_dir() {
+            file.unpack_in(dst).await?;
+            continue;
+        }
+
+        let path = if entry_type.is_file() {
+            crate::tar::unpacked_at(dst, &file.path()?)
+        } else {
+            if entry_type.is_symlink() {
+                if let Some(symlink) = crate::tar::unpacked_at(dst, &file.path()?) {
+                    symlinks.push(symlink);
+                }
+            }
+            None
+        };
+
+        // Unpack anything other than a regular file in order, since it may depend on the files
+        // that precede it (e.g., a hard link to an earlier file). The same applies to a file that
+        // overwrites an earlier entry, and to a file beneath a symlink, which could otherwise be
+        // written outside of the destination directory.
+        let Some(path) = path.filter(|path| {
+            !pending.contains(path) && !symlinks.iter().any(|symlink| path.starts_with(symlink))
+        }) else {
+            while let Some(result) = writes.join_next().await {
+                result??;
+            }
+            pending.clear();
+            file.unpack_in(dst).await?;
+            continue;
+        };
+
+        let mut contents =
+            Vec::with_capacity(usize::try_from(file.header().size()?).unwrap_or_default());
+        tokio::io::AsyncReadExt::read_to_end(&mut file, &mut contents).await?;
+        let mode = file.header().mode()?;
+
+        while writes.len() >= MAX_CONCURRENT_WRITES {
+            if let Some(result) = writes.join_next().await {
+                result??;
+            }
+        }
+        pending.insert(path.clone());
+        writes.spawn_blocking(move || write_file(&path, &contents, mode));
+    }
+
+    while let Some(result) = writes.join_next().await {
+        result??;
+    }
+    Ok(())
+}
+
+/// Write a file unpacked from a tar archive, preserving its executable bit.
+fn write_file(path: &Path, contents: &[u8], mode: u32) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs_err::create_dir_all(parent)?;
+    }
+    fs_err::write(path, contents)?;
+
+    #[cfg(unix)]
+    {
+        use std::fs::Permissions;
+        use std::os::unix::fs::PermissionsExt;
+
+        let has_any_executable_bit = mode & 0o111;
+        if has_any_executable_bit != 0 {
+            let permissions = fs_err::metadata(path)?.permissions();
+            if permissions.mode() & 0o111 != 0o111 {
+                fs_err::set_permissions(path, Permissions::from_mode(permissions.mode() | 0o111))?;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    Ok(())
+}
+
+/// Unpack a `.tar.gz`, `.tar.bz2`, `.tar.zst`, or `.tar.xz` archive into the target directory,
+/// writing its files in parallel.
+///
+/// Unlike [`archive`], the contents of each file are buffered in memory, such that they can be
+/// written concurrently while the archive is decompressed. This is useful for large archives
+/// that are already on disk (e.g., Python distributions), whose extraction is dominated by
+/// file creation. `.zip` archives are unpacked with [`unzip`].
+pub async fn untar_parallel<R: tokio::io::AsyncRead + Unpin>(
+    reader: R,
+    ext: SourceDistExtension,
+    target: impl AsRef<Path>,
+) -> Result<(), Error> {
+    let reader = tokio::io::BufReader::with_capacity(DEFAULT_BUF_SIZE, reader);
+    let target = target.as_ref();
+    match ext {
+        SourceDistExtension::Zip => unzip(reader, target).await,
+        SourceDistExtension::TarGz => {
+            let mut decompressed_bytes =
+                async_compression::tokio::bufread::GzipDecoder::new(reader);
+            Ok(untar_in_parallel(tar_archive(&mut decompressed_bytes), target).await?)
+        }
+        SourceDistExtension::TarBz2 => {
+            let mut decompressed_bytes = async_compression::tokio::bufread::BzDecoder::new(reader);
+            Ok(untar_in_parallel(tar_archive(&mut decompressed_bytes), target).await?)
+        }
+        SourceDistExtension::TarXz => {
+            let mut decompressed_bytes = async_compression::tokio::bufread::XzDecoder::new(reader);
+            Ok(untar_in_parallel(tar_archive(&mut decompressed_bytes), target).await?)
+        }
+        SourceDistExtension::TarZst => {
+            let mut decompressed_bytes =
+                async_compression::tokio::bufread::ZstdDecoder::new(reader);
+            Ok(untar_in_parallel(tar_archive(&mut decompressed_bytes), target).await?)
+        }
+    }
+}
+
+/// Read a tar archive from the given decompressed stream.
+fn tar_archive(
+    decompressed_bytes: &mut (dyn tokio::io::AsyncRead + Unpin),
+) -> tokio_tar::Archive<&mut (dyn tokio::io::AsyncRead + Unpin)> {
+    tokio_tar::ArchiveBuilder::new(decompressed_bytes)
+        .set_preserve_mtime(false)
+        .build()
+}
+
 /// Unzip a `.zip`, `.tar.gz`, `.tar.bz2`, `.tar.zst`, or `.tar.xz` archive into the target directory,
 /// without requiring `Seek`.
 pub async fn archive<R: tokio::io::AsyncRead + Unpin>(
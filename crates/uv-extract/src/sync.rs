@@ -5,6 +5,9 @@ use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 use zip::ZipArchive;
 
+use uv_fs::{is_case_insensitive, CASE_SHADOWED_DIR};
+
+use crate::case::{debug_case_conflicts, find_case_conflicts, shadowed_path};
 use crate::vendor::{CloneableSeekableReader, HasLength};
 use crate::Error;
 
@@ -16,6 +19,26 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
     // Unzip in parallel.
     let reader = std::io::BufReader::new(reader);
     let archive = ZipArchive::new(CloneableSeekableReader::new(reader))?;
+
+    // On a case-insensitive filesystem, entries whose paths differ only by case would race to
+    // write the same file; extract all but the last of each aside.
+    let shadowed = {
+        let mut archive = archive.clone();
+        let names = (0..archive.len())
+            .map(|file_number| Ok(archive.by_index_raw(file_number)?.name().to_string()))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let conflicts = find_case_conflicts(&names);
+        if !conflicts.is_empty() && is_case_insensitive(target)? {
+            debug_case_conflicts(&conflicts);
+            conflicts
+                .into_iter()
+                .map(|conflict| conflict.index)
+                .collect::<FxHashSet<_>>()
+        } else {
+            FxHashSet::default()
+        }
+    };
+
     let directories = Mutex::new(FxHashSet::default());
     (0..archive.len())
         .into_par_iter()
@@ -29,7 +52,11 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
             };
 
             // Create necessary parent directories.
-            let path = target.join(enclosed_name);
+            let path = if shadowed.contains(&file_number) {
+                shadowed_path(target, file_number, enclosed_name)
+            } else {
+                target.join(enclosed_name)
+            };
             if file.is_dir() {
                 let mut directories = directories.lock().unwrap();
                 if directories.insert(path.clone()) {
@@ -84,6 +111,17 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
         .collect::<Result<_, Error>>()
 }
 
+/// Return the total size of the entries in a `.zip` archive once unpacked, as recorded in the
+/// archive's central directory.
+pub fn unpacked_size<R: std::io::Read + std::io::Seek>(reader: R) -> Result<u64, Error> {
+    let mut archive = ZipArchive::new(std::io::BufReader::new(reader))?;
+    let mut size = 0u64;
+    for file_number in 0..archive.len() {
+        size = size.saturating_add(archive.by_index_raw(file_number)?.size());
+    }
+    Ok(size)
+}
+
 /// Extract the top-level directory from an unpacked archive.
 ///
 /// The specification says:
@@ -93,8 +131,16 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
 /// This function returns the path to that top-level directory.
 pub fn strip_component(source: impl AsRef<Path>) -> Result<PathBuf, Error> {
     // TODO(konstin): Verify the name of the directory.
-    let top_level =
-        fs_err::read_dir(source.as_ref())?.collect::<std::io::Result<Vec<fs_err::DirEntry>>>()?;
+
+    // Entries that were extracted aside due to case conflicts can't be built from, so they're
+    // ignored.
+    let top_level = fs_err::read_dir(source.as_ref())?
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .map_or(true, |entry| entry.file_name() != CASE_SHADOWED_DIR)
+        })
+        .collect::<std::io::Result<Vec<fs_err::DirEntry>>>()?;
     match top_level.as_slice() {
         [root] => Ok(root.path()),
         [] => Err(Error::EmptyArchive),
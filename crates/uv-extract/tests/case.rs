@@ -0,0 +1,73 @@
+use std::io::{Cursor, Write};
+
+use fs_err as fs;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+use uv_fs::{is_case_insensitive, CASE_SHADOWED_DIR};
+
+/// Build a `.zip` archive containing the given files, in order.
+fn archive(files: &[(&str, &str)]) -> zip::result::ZipResult<Cursor<Vec<u8>>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in files {
+        writer.start_file(*name, FileOptions::default())?;
+        writer.write_all(contents.as_bytes())?;
+    }
+    writer.finish()
+}
+
+/// Entries that differ only by case are both extracted: the last to its own path, and the earlier
+/// to the shadowed directory if the filesystem is case-insensitive.
+#[test]
+fn unzip_case_conflicts() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let target = temp_dir.path().join("wheel");
+
+    let reader = archive(&[
+        ("pkg/__init__.py", ""),
+        ("pkg/Foo.py", "first"),
+        ("pkg/foo.py", "second"),
+    ])?;
+    uv_extract::unzip(reader, &target)?;
+
+    assert_eq!(
+        fs::read_to_string(target.join("pkg").join("foo.py"))?,
+        "second"
+    );
+    if is_case_insensitive(&target)? {
+        assert_eq!(
+            fs::read_to_string(
+                target
+                    .join(CASE_SHADOWED_DIR)
+                    .join("1")
+                    .join("pkg")
+                    .join("Foo.py")
+            )?,
+            "first"
+        );
+    } else {
+        assert_eq!(
+            fs::read_to_string(target.join("pkg").join("Foo.py"))?,
+            "first"
+        );
+        assert!(!target.join(CASE_SHADOWED_DIR).exists());
+    }
+
+    Ok(())
+}
+
+/// The shadowed directory is ignored when locating the top-level directory of a source
+/// distribution.
+#[test]
+fn strip_component_case_conflicts() -> anyhow::Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    fs::create_dir_all(temp_dir.path().join("pkg-1.0"))?;
+    fs::create_dir_all(temp_dir.path().join(CASE_SHADOWED_DIR).join("1"))?;
+
+    assert_eq!(
+        uv_extract::strip_component(temp_dir.path())?,
+        temp_dir.path().join("pkg-1.0")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
+use distribution_filename::SourceDistExtension;
+use fs_err as fs;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio_tar::{Builder, EntryType, Header};
+
+/// The number of modules in the test archive, exceeding the number of concurrent writes.
+const MODULES: usize = 200;
+
+/// Build a tar archive resembling a Python distribution.
+async fn tarball() -> std::io::Result<Vec<u8>> {
+    let mut builder = Builder::new(Vec::new());
+
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Directory);
+    header.set_mode(0o755);
+    header.set_size(0);
+    builder.append_data(&mut header, "python/", &[][..]).await?;
+
+    append_file(&mut builder, "python/bin/python3", b"#!/bin/sh\n", 0o755).await?;
+    for module in 0..MODULES {
+        append_file(
+            &mut builder,
+            &format!("python/lib/mod{module}.py"),
+            format!("VALUE = {module}\n").as_bytes(),
+            0o644,
+        )
+        .await?;
+    }
+
+    // A later entry for the same path takes precedence.
+    append_file(&mut builder, "python/lib/mod0.py", b"VALUE = -1\n", 0o644).await?;
+
+    builder.into_inner().await
+}
+
+async fn append_file<W: AsyncWrite + Unpin + Send>(
+    builder: &mut Builder<W>,
+    path: &str,
+    contents: &[u8],
+    mode: u32,
+) -> std::io::Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Regular);
+    header.set_mode(mode);
+    header.set_size(contents.len() as u64);
+    builder.append_data(&mut header, path, contents).await
+}
+
+/// Assert that the test archive was unpacked into the given directory.
+fn assert_unpacked(target: &Path) -> anyhow::Result<()> {
+    let python = target.join("python");
+    assert_eq!(
+        fs::read_to_string(python.join("bin").join("python3"))?,
+        "#!/bin/sh\n"
+    );
+    assert_eq!(
+        fs::read_to_string(python.join("lib").join("mod0.py"))?,
+        "VALUE = -1\n"
+    );
+    for module in 1..MODULES {
+        assert_eq!(
+            fs::read_to_string(python.join("lib").join(format!("mod{module}.py")))?,
+            format!("VALUE = {module}\n")
+        );
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = fs::metadata(python.join("bin").join("python3"))?
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o111, 0o111);
+        let mode = fs::metadata(python.join("lib").join("mod1.py"))?
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o111, 0);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn untar_parallel_gz() -> anyhow::Result<()> {
+    let mut encoder = GzipEncoder::new(Vec::new());
+    encoder.write_all(&tarball().await?).await?;
+    encoder.shutdown().await?;
+    let archive = encoder.into_inner();
+
+    let temp_dir = tempfile::tempdir()?;
+    uv_extract::stream::untar_parallel(
+        archive.as_slice(),
+        SourceDistExtension::TarGz,
+        temp_dir.path(),
+    )
+    .await?;
+
+    assert_unpacked(temp_dir.path())
+}
+
+#[tokio::test]
+async fn untar_parallel_zst() -> anyhow::Result<()> {
+    let mut encoder = ZstdEncoder::new(Vec::new());
+    encoder.write_all(&tarball().await?).await?;
+    encoder.shutdown().await?;
+    let archive = encoder.into_inner();
+
+    let temp_dir = tempfile::tempdir()?;
+    uv_extract::stream::untar_parallel(
+        archive.as_slice(),
+        SourceDistExtension::TarZst,
+        temp_dir.path(),
+    )
+    .await?;
+
+    assert_unpacked(temp_dir.path())
+}
+
+/// A file beneath a symlink to a directory outside of the target must not be written through the
+/// symlink.
+#[cfg(unix)]
+#[tokio::test]
+async fn untar_parallel_symlink_escape() -> anyhow::Result<()> {
+    let outside = tempfile::tempdir()?;
+
+    let mut builder = Builder::new(Vec::new());
+    let mut header = Header::new_gnu();
+    header.set_entry_type(EntryType::Symlink);
+    header.set_mode(0o777);
+    header.set_size(0);
+    header.set_link_name(outside.path())?;
+    builder.append_data(&mut header, "pkg", &[][..]).await?;
+    append_file(&mut builder, "pkg/foo", b"escaped\n", 0o644).await?;
+
+    let mut encoder = GzipEncoder::new(Vec::new());
+    encoder.write_all(&builder.into_inner().await?).await?;
+    encoder.shutdown().await?;
+    let archive = encoder.into_inner();
+
+    let temp_dir = tempfile::tempdir()?;
+    let result = uv_extract::stream::untar_parallel(
+        archive.as_slice(),
+        SourceDistExtension::TarGz,
+        temp_dir.path(),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(!outside.path().join("foo").exists());
+
+    Ok(())
+}
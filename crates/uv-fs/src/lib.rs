@@ -11,6 +11,31 @@ pub use crate::path::*;
 pub mod cachedir;
 mod path;
 
+/// The directory within an unpacked archive that holds the entries whose paths differ from that of
+/// a later entry only by case, if the archive was unpacked on a case-insensitive filesystem.
+///
+/// Each entry is stored under its index within the archive, e.g., `.uv-shadowed/3/pkg/Foo.py`,
+/// such that it can be installed to its original path on a case-sensitive filesystem.
+pub const CASE_SHADOWED_DIR: &str = ".uv-shadowed";
+
+/// Returns `true` if the filesystem containing the given directory is case-insensitive.
+///
+/// Creates the directory, if it doesn't exist.
+pub fn is_case_insensitive(dir: &Path) -> std::io::Result<bool> {
+    fs_err::create_dir_all(dir)?;
+    let probe = tempfile::Builder::new()
+        .prefix(".uv-case-probe")
+        .tempfile_in(dir)?;
+    let Some(name) = probe.path().file_name() else {
+        return Ok(false);
+    };
+    let insensitive = dir.join(name.to_string_lossy().to_uppercase()).exists();
+    if insensitive {
+        debug!("Detected case-insensitive filesystem at: {}", dir.display());
+    }
+    Ok(insensitive)
+}
+
 /// Reads data from the path and requires that it be valid UTF-8 or UTF-16.
 ///
 /// This uses BOM sniffing to determine if the data should be transcoded
@@ -283,11 +308,166 @@ pub fn files(path: impl AsRef<Path>) -> impl Iterator<Item = PathBuf> {
         .map(|entry| entry.path())
 }
 
+/// Returns `true` if the given file or directory is read-only.
+///
+/// If the path can't be read, it's assumed to be writable.
+pub fn is_read_only(path: impl AsRef<Path>) -> bool {
+    fs_err::metadata(path.as_ref()).is_ok_and(|metadata| metadata.permissions().readonly())
+}
+
+/// Remove (or restore) write access to the given directory and all of its contents, returning the
+/// number of entries whose permissions were changed.
+///
+/// Symlinks are skipped, such that files outside of the directory are never modified. Files that
+/// may share their contents with another path (e.g., hardlinked from the cache) are replaced with
+/// a copy before being marked read-only, such that the permissions of the other path are left
+/// intact. On Windows, the read-only attribute is only respected for files.
+pub fn set_read_only(path: impl AsRef<Path>, read_only: bool) -> std::io::Result<usize> {
+    let path = path.as_ref();
+    let file_type = fs_err::symlink_metadata(path)?.file_type();
+    if file_type.is_symlink() {
+        return Ok(0);
+    }
+
+    // Restore write access to a directory before modifying its contents, and remove it after.
+    let mut changed = 0;
+    if file_type.is_dir() {
+        if !read_only {
+            changed += usize::from(set_permissions(path, false)?);
+        }
+        for entry in fs_err::read_dir(path)? {
+            changed += set_read_only(entry?.path(), read_only)?;
+        }
+        if read_only {
+            changed += usize::from(set_permissions(path, true)?);
+        }
+    } else {
+        changed += usize::from(set_permissions(path, read_only)?);
+    }
+
+    Ok(changed)
+}
+
+/// Remove (or restore) write access to a single file or directory, returning `true` if its
+/// permissions were changed.
+fn set_permissions(path: &Path, read_only: bool) -> std::io::Result<bool> {
+    let metadata = fs_err::metadata(path)?;
+    let mut permissions = metadata.permissions();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = if read_only {
+            permissions.mode() & !0o222
+        } else {
+            permissions.mode() | 0o200
+        };
+        if mode == permissions.mode() {
+            return Ok(false);
+        }
+        permissions.set_mode(mode);
+    }
+
+    #[cfg(not(unix))]
+    {
+        if permissions.readonly() == read_only {
+            return Ok(false);
+        }
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(read_only);
+    }
+
+    // Permissions are shared by every link to a file, so replace a linked file with a copy of its
+    // own before removing write access.
+    if read_only && metadata.is_file() && is_linked(&metadata) {
+        trace!(
+            "Copying linked file before marking it read-only: {}",
+            path.user_display()
+        );
+        let parent = path.parent().unwrap_or(Path::new("."));
+        let copy = NamedTempFile::new_in(parent)?;
+        fs_err::copy(path, copy.path())?;
+        copy.persist(path).map_err(|err| err.error)?;
+    }
+
+    fs_err::set_permissions(path, permissions)?;
+    Ok(true)
+}
+
+/// Returns `true` if the file may share its contents (and permissions) with another path.
+///
+/// On Windows, the link count isn't available, so every file is assumed to be linked.
+fn is_linked(metadata: &std::fs::Metadata) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.nlink() > 1
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        true
+    }
+}
+
+/// Remove a directory and all of its contents.
+///
+/// If the directory contains read-only contents (e.g., an environment synced with
+/// `--read-only-env`), write access is restored before retrying the removal.
+pub fn force_remove_dir_all(path: impl AsRef<Path>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    match fs_err::remove_dir_all(path) {
+        Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => {
+            debug!("Restoring write access to remove: {}", path.user_display());
+            set_read_only(path, false)?;
+            fs_err::remove_dir_all(path)
+        }
+        result => result,
+    }
+}
+
+/// Verify that the filesystem containing the given directory has at least `required` bytes
+/// available.
+///
+/// If the available space can't be determined, the check is skipped.
+#[allow(clippy::cast_precision_loss)]
+pub fn ensure_available_space(path: impl AsRef<Path>, required: u64) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let available = match fs2::available_space(path) {
+        Ok(available) => available,
+        Err(err) => {
+            debug!(
+                "Failed to determine available space at {}: {err}",
+                path.user_display()
+            );
+            return Ok(());
+        }
+    };
+    if available < required {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "Insufficient space at `{}`: {:.1} MiB required, but only {:.1} MiB available",
+                path.user_display(),
+                required as f64 / (1024.0 * 1024.0),
+                available as f64 / (1024.0 * 1024.0),
+            ),
+        ));
+    }
+    Ok(())
+}
+
 /// A file lock that is automatically released when dropped.
 #[derive(Debug)]
 pub struct LockedFile(fs_err::File);
 
 impl LockedFile {
+    /// The interval at which to poll a contended lock.
+    #[cfg(feature = "tokio")]
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
     pub fn acquire(path: impl AsRef<Path>, resource: impl Display) -> Result<Self, std::io::Error> {
         let file = fs_err::File::create(path.as_ref())?;
         trace!("Checking lock for `{resource}`");
@@ -315,6 +495,103 @@ impl LockedFile {
             }
         }
     }
+
+    /// Acquire a lock on the file, recording the ID of the current process in the file, such that
+    /// other processes waiting on the lock can report which process holds it.
+    ///
+    /// If a `timeout` is provided, an error is returned if the lock can't be acquired within the
+    /// timeout; otherwise, waits indefinitely.
+    #[cfg(feature = "tokio")]
+    pub async fn acquire_with_timeout(
+        path: impl AsRef<Path>,
+        resource: impl Display,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Self, std::io::Error> {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let path = path.as_ref();
+
+        // Avoid truncating the file, which would erase the ID of the process holding the lock.
+        let mut file = fs_err::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        trace!("Checking lock for `{resource}`");
+        if let Err(err) = file.file().try_lock_exclusive() {
+            // Log error code and enum kind to help debugging more exotic failures
+            debug!("Try lock error, waiting for exclusive lock: {:?}", err);
+            let holder = Self::holder(&mut file)
+                .map(|pid| format!(", held by PID {pid}"))
+                .unwrap_or_default();
+            warn_user!(
+                "Waiting to acquire lock for {} (lockfile: {}{})",
+                resource,
+                path.user_display(),
+                holder,
+            );
+
+            // Poll the lock, rather than blocking the thread, such that the wait can be cancelled.
+            let acquire = async {
+                loop {
+                    tokio::time::sleep(Self::POLL_INTERVAL).await;
+                    match file.file().try_lock_exclusive() {
+                        Ok(()) => return Ok(()),
+                        Err(err)
+                            if err.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {
+                        }
+                        Err(err) => {
+                            // Not an fs_err method, we need to build our own path context
+                            return Err(std::io::Error::new(
+                                std::io::ErrorKind::Other,
+                                format!("Could not lock {}: {}", path.user_display(), err),
+                            ));
+                        }
+                    }
+                }
+            };
+            if let Some(timeout) = timeout {
+                tokio::time::timeout(timeout, acquire).await.map_err(|_| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        format!(
+                            "Timed out after {}s waiting to acquire lock for {} (lockfile: {}{})",
+                            timeout.as_secs(),
+                            resource,
+                            path.user_display(),
+                            holder,
+                        ),
+                    )
+                })??;
+            } else {
+                acquire.await?;
+            }
+        }
+        debug!("Acquired lock for `{resource}`");
+
+        // Record the current process as the holder of the lock.
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        write!(file, "{}", std::process::id())?;
+        file.flush()?;
+
+        Ok(Self(file))
+    }
+
+    /// Read the ID of the process holding the lock, if recorded.
+    ///
+    /// On Windows, the contents of a locked file can't be read by other processes, in which case
+    /// the holder is unknown.
+    #[cfg(feature = "tokio")]
+    fn holder(file: &mut fs_err::File) -> Option<u32> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let mut contents = String::new();
+        file.seek(SeekFrom::Start(0)).ok()?;
+        file.read_to_string(&mut contents).ok()?;
+        contents.trim().parse().ok()
+    }
 }
 
 impl Drop for LockedFile {
@@ -328,3 +605,58 @@ impl Drop for LockedFile {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LockedFile;
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn lock_timeout() {
+        use std::time::Duration;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(".lock");
+
+        // The lockfile records the process holding the lock (on Windows, the locked file can't be
+        // read).
+        let lock = LockedFile::acquire_with_timeout(&path, "test", None)
+            .await
+            .unwrap();
+        if cfg!(unix) {
+            assert_eq!(
+                fs_err::read_to_string(&path).unwrap(),
+                std::process::id().to_string()
+            );
+        }
+
+        // A contended lock times out, rather than waiting indefinitely.
+        let err = LockedFile::acquire_with_timeout(&path, "test", Some(Duration::ZERO))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+
+        // Once released, the lock can be acquired again.
+        drop(lock);
+        LockedFile::acquire_with_timeout(&path, "test", Some(Duration::ZERO))
+            .await
+            .unwrap();
+    }
+
+    #[test]
+    fn lock_shared() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(".lock");
+
+        // Shared locks can be held concurrently, but exclude an exclusive lock.
+        let first = LockedFile::acquire_shared(&path, "test").unwrap();
+        let second = LockedFile::acquire_shared(&path, "test").unwrap();
+        assert!(LockedFile::try_acquire(&path, "test").unwrap().is_none());
+
+        drop(first);
+        assert!(LockedFile::try_acquire(&path, "test").unwrap().is_none());
+
+        drop(second);
+        assert!(LockedFile::try_acquire(&path, "test").unwrap().is_some());
+    }
+}
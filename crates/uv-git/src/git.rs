@@ -186,8 +186,33 @@ impl GitRepository {
         })
     }
 
+    /// Checks out the given revision, detaching `HEAD`, along with any submodules.
+    ///
+    /// Unlike [`GitCheckout::reset`], this refuses to discard any local changes in the working
+    /// tree.
+    fn checkout(&self, revision: GitOid) -> Result<()> {
+        ProcessBuilder::new("git")
+            .arg("checkout")
+            .arg("--quiet")
+            .arg("--detach")
+            .arg(revision.as_str())
+            .cwd(&self.path)
+            .exec_with_output()?;
+
+        ProcessBuilder::new("git")
+            .arg("submodule")
+            .arg("update")
+            .arg("--recursive")
+            .arg("--init")
+            .cwd(&self.path)
+            .exec_with_output()
+            .map(drop)?;
+
+        Ok(())
+    }
+
     /// Parses the object ID of the given `refname`.
-    fn rev_parse(&self, refname: &str) -> Result<GitOid> {
+    pub(crate) fn rev_parse(&self, refname: &str) -> Result<GitOid> {
         let result = ProcessBuilder::new("git")
             .arg("rev-parse")
             .arg(refname)
@@ -266,6 +291,53 @@ impl GitRemote {
         Ok((GitDatabase { repo }, rev))
     }
 
+    /// Clones or updates a working copy of this remote at `into`, and checks out a reference or
+    /// revision.
+    ///
+    /// Unlike [`GitRemote::checkout`], which populates a bare database in the cache, the working
+    /// copy is a regular clone with `origin` pointing at the remote, such that it can be modified
+    /// and committed to like any other clone.
+    ///
+    /// When `locked_rev` is provided, it takes precedence over `reference`.
+    pub(crate) fn working_copy(
+        &self,
+        into: &Path,
+        reference: &GitReference,
+        locked_rev: Option<GitOid>,
+        client: &ClientWithMiddleware,
+    ) -> Result<GitOid> {
+        let locked_ref = locked_rev.map(|oid| GitReference::FullCommit(oid.to_string()));
+        let reference = locked_ref.as_ref().unwrap_or(reference);
+
+        // Only reuse `into` if it's the root of a repository, rather than a directory nested
+        // within some other repository.
+        let mut repo = if into.join(".git").exists() {
+            GitRepository::open(into)?
+        } else {
+            paths::create_dir_all(into)?;
+            let repo = GitRepository::init(into)?;
+            ProcessBuilder::new("git")
+                .arg("remote")
+                .arg("add")
+                .arg("origin")
+                .arg(self.url.as_str())
+                .cwd(into)
+                .exec_with_output()?;
+            repo
+        };
+
+        fetch(&mut repo, self.url.as_str(), reference, client)
+            .with_context(|| format!("failed to fetch into: {}", into.user_display()))?;
+        let rev = match locked_rev {
+            Some(rev) => rev,
+            None => reference.resolve(&repo)?,
+        };
+        repo.checkout(rev)
+            .with_context(|| format!("failed to check out `{rev}` in: {}", into.user_display()))?;
+
+        Ok(rev)
+    }
+
     /// Creates a [`GitDatabase`] of this remote at `db_path`.
     #[allow(clippy::unused_self)]
     pub(crate) fn db_at(&self, db_path: &Path) -> Result<GitDatabase> {
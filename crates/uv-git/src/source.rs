@@ -10,7 +10,7 @@ use url::Url;
 
 use cache_key::{cache_digest, RepositoryUrl};
 
-use crate::git::GitRemote;
+use crate::git::{GitRemote, GitRepository};
 use crate::{GitOid, GitSha, GitUrl};
 
 /// A remote Git source that can be checked out locally.
@@ -116,6 +116,50 @@ impl GitSource {
             path: checkout_path,
         })
     }
+
+    /// Check out the repository into a working copy at `into`, e.g., for an editable install.
+    ///
+    /// If a working copy already exists at `into`, it's used as-is, unless `update` is set, in
+    /// which case the requested revision is fetched and checked out.
+    #[instrument(skip(self), fields(repository = %self.git.repository, rev = ?self.git.precise))]
+    pub fn working_copy(self, into: &Path, update: bool) -> Result<Fetch> {
+        let remote = GitRemote::new(&self.git.repository);
+
+        if !update && into.join(".git").exists() {
+            debug!("Using existing working copy at: {}", into.display());
+            let head = GitRepository::open(into)?.rev_parse("HEAD")?;
+            return Ok(Fetch {
+                git: self.git.with_precise(GitSha::from(head)),
+                path: into.to_path_buf(),
+            });
+        }
+
+        debug!("Updating working copy at: {}", into.display());
+
+        // Report the checkout operation to the reporter.
+        let task = self
+            .reporter
+            .as_ref()
+            .map(|reporter| reporter.on_checkout_start(remote.url(), self.git.reference.as_rev()));
+
+        let actual_rev = remote.working_copy(
+            into,
+            &self.git.reference,
+            self.git.precise.map(GitOid::from),
+            &self.client,
+        )?;
+
+        if let Some(task) = task {
+            if let Some(reporter) = self.reporter.as_ref() {
+                reporter.on_checkout_complete(remote.url(), actual_rev.as_str(), task);
+            }
+        }
+
+        Ok(Fetch {
+            git: self.git.with_precise(GitSha::from(actual_rev)),
+            path: into.to_path_buf(),
+        })
+    }
 }
 
 pub struct Fetch {
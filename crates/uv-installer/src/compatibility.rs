@@ -0,0 +1,306 @@
+use distribution_filename::WheelFilename;
+use distribution_types::CachedDist;
+use platform_tags::{Arch, CpuFeatures, IncompatibleTag, Os, Platform, TagCompatibility, Tags};
+
+/// A wheel that can't be used on the host, e.g., because it requires a newer version of glibc
+/// than the host provides.
+#[derive(thiserror::Error, Debug)]
+#[error("`{filename}` is incompatible with the current platform: {reason}")]
+pub struct IncompatibleWheel {
+    pub filename: WheelFilename,
+    pub reason: String,
+}
+
+/// Validate that the given wheels can be imported on the host, as described by the interpreter's
+/// [`Tags`] and [`Platform`] and the host's [`CpuFeatures`], returning any that can't.
+///
+/// Wheels are selected based on the target tags, which may differ from the host's (e.g., when
+/// `--python-platform` or `UV_CPU_FEATURES` is provided). Installing a wheel that targets a newer
+/// `manylinux` or macOS than the host supports, or that requires CPU features the host lacks, is
+/// likely to fail at import time (or crash), rather than at install time.
+pub fn incompatible_wheels<'a>(
+    wheels: impl IntoIterator<Item = &'a CachedDist>,
+    tags: &Tags,
+    platform: &Platform,
+    cpu_features: &CpuFeatures,
+) -> Vec<IncompatibleWheel> {
+    wheels
+        .into_iter()
+        .filter_map(|wheel| {
+            let filename = wheel.filename();
+            let reason = match filename.compatibility(tags) {
+                // The CPU features of the tags may be overridden, so validate against the host.
+                TagCompatibility::Compatible(_)
+                | TagCompatibility::Incompatible(IncompatibleTag::CpuFeatures) => {
+                    cpu_reason(filename, cpu_features)?
+                }
+                TagCompatibility::Incompatible(IncompatibleTag::Platform) => {
+                    platform_reason(&filename.platform_tag, platform)
+                }
+                TagCompatibility::Incompatible(_) => format!(
+                    "it was built for `{}-{}`, which the interpreter doesn't support",
+                    filename.python_tag.join("."),
+                    filename.abi_tag.join("."),
+                ),
+            };
+            Some(IncompatibleWheel {
+                filename: filename.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Explain which of the CPU features required by a hardware-variant wheel are missing on the host,
+/// if any.
+fn cpu_reason(filename: &WheelFilename, cpu_features: &CpuFeatures) -> Option<String> {
+    let missing = filename
+        .build_tag
+        .as_ref()?
+        .cpu_features()?
+        .map(str::to_ascii_lowercase)
+        .filter(|feature| !cpu_features.contains(feature))
+        .map(|feature| format!("`{feature}`"))
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "it requires CPU features that the host doesn't support: {}",
+            missing.join(", ")
+        ))
+    }
+}
+
+/// Explain why none of the given platform tags are supported by the host.
+fn platform_reason(platform_tags: &[String], platform: &Platform) -> String {
+    platform_tags
+        .iter()
+        .find_map(|tag| describe(tag, platform))
+        .unwrap_or_else(|| {
+            format!(
+                "none of its platform tags (`{}`) are supported on {} ({})",
+                platform_tags.join("."),
+                platform.os(),
+                platform.arch()
+            )
+        })
+}
+
+/// Describe the requirement imposed by a single platform tag that the host doesn't meet, if it
+/// can be determined.
+fn describe(tag: &str, platform: &Platform) -> Option<String> {
+    if let Some(((major, minor), arch)) = parse_manylinux(tag) {
+        return match platform.os() {
+            Os::Manylinux {
+                major: host_major,
+                minor: host_minor,
+            } => {
+                if (major, minor) > (*host_major, *host_minor) {
+                    Some(format!(
+                        "it requires glibc {major}.{minor}, but the host provides glibc {host_major}.{host_minor}"
+                    ))
+                } else {
+                    describe_arch(arch, platform.arch())
+                }
+            }
+            Os::Musllinux { .. } => Some("it requires glibc, but the host uses musl".to_string()),
+            os => Some(format!("it requires Linux, but the host is running {os}")),
+        };
+    }
+
+    if let Some(((major, minor), arch)) = parse_versioned(tag, "musllinux_") {
+        return match platform.os() {
+            Os::Musllinux {
+                major: host_major,
+                minor: host_minor,
+            } => {
+                if (major, minor) > (*host_major, *host_minor) {
+                    Some(format!(
+                        "it requires musl {major}.{minor}, but the host provides musl {host_major}.{host_minor}"
+                    ))
+                } else {
+                    describe_arch(arch, platform.arch())
+                }
+            }
+            Os::Manylinux { .. } => Some("it requires musl, but the host uses glibc".to_string()),
+            os => Some(format!("it requires Linux, but the host is running {os}")),
+        };
+    }
+
+    if let Some(((major, minor), arch)) = parse_versioned(tag, "macosx_") {
+        return match platform.os() {
+            Os::Macos {
+                major: host_major,
+                minor: host_minor,
+            } => {
+                // Before macOS 11, the minor version was significant (e.g., 10.15).
+                let (major, minor) = if major >= 11 {
+                    (major, 0)
+                } else {
+                    (major, minor)
+                };
+                let (host_major, host_minor) = if *host_major >= 11 {
+                    (*host_major, 0)
+                } else {
+                    (*host_major, *host_minor)
+                };
+                if (major, minor) > (host_major, host_minor) {
+                    Some(format!(
+                        "it requires macOS {major}.{minor} or later, but the host is running macOS {host_major}.{host_minor}"
+                    ))
+                } else {
+                    describe_arch(arch, platform.arch())
+                }
+            }
+            os => Some(format!("it requires macOS, but the host is running {os}")),
+        };
+    }
+
+    if let Some(arch) = tag.strip_prefix("win") {
+        return match platform.os() {
+            Os::Windows => {
+                let arch = match arch {
+                    "32" => "i686",
+                    "_amd64" => "x86_64",
+                    "_arm64" => "aarch64",
+                    _ => return None,
+                };
+                describe_arch(arch, platform.arch())
+            }
+            os => Some(format!("it requires Windows, but the host is running {os}")),
+        };
+    }
+
+    if let Some(arch) = tag.strip_prefix("linux_") {
+        return match platform.os() {
+            Os::Manylinux { .. } | Os::Musllinux { .. } => describe_arch(arch, platform.arch()),
+            os => Some(format!("it requires Linux, but the host is running {os}")),
+        };
+    }
+
+    None
+}
+
+/// Describe the mismatch between the architecture required by a platform tag and the host
+/// architecture, if any.
+fn describe_arch(arch: &str, host: Arch) -> Option<String> {
+    let compatible = match arch {
+        "universal2" => matches!(host, Arch::X86_64 | Arch::Aarch64),
+        "intel" => matches!(host, Arch::X86 | Arch::X86_64),
+        "arm64" => host == Arch::Aarch64,
+        "i386" => host == Arch::X86,
+        // Legacy multi-architecture macOS tags (e.g., `fat64`); don't attempt to validate them.
+        "universal" | "fat" | "fat3" | "fat32" | "fat64" => true,
+        arch => arch == host.to_string(),
+    };
+    if compatible {
+        None
+    } else {
+        Some(format!(
+            "it was built for {arch}, but the host architecture is {host}"
+        ))
+    }
+}
+
+/// Parse the glibc version and architecture from a `manylinux` platform tag, including the legacy
+/// aliases defined in PEP 513, PEP 571, and PEP 599.
+fn parse_manylinux(tag: &str) -> Option<((u16, u16), &str)> {
+    if let Some(arch) = tag.strip_prefix("manylinux1_") {
+        return Some(((2, 5), arch));
+    }
+    if let Some(arch) = tag.strip_prefix("manylinux2010_") {
+        return Some(((2, 12), arch));
+    }
+    if let Some(arch) = tag.strip_prefix("manylinux2014_") {
+        return Some(((2, 17), arch));
+    }
+    parse_versioned(tag, "manylinux_")
+}
+
+/// Parse a platform tag of the form `{prefix}{major}_{minor}_{arch}`.
+fn parse_versioned<'a>(tag: &'a str, prefix: &str) -> Option<((u16, u16), &'a str)> {
+    let rest = tag.strip_prefix(prefix)?;
+    let mut parts = rest.splitn(3, '_');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let arch = parts.next()?;
+    Some(((major, minor), arch))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use distribution_filename::WheelFilename;
+    use platform_tags::{Arch, CpuFeatures, Os, Platform};
+
+    use super::{cpu_reason, describe};
+
+    #[test]
+    fn manylinux() {
+        let platform = Platform::new(
+            Os::Manylinux {
+                major: 2,
+                minor: 17,
+            },
+            Arch::X86_64,
+        );
+        assert_eq!(describe("manylinux2014_x86_64", &platform), None);
+        assert_eq!(describe("manylinux1_x86_64", &platform), None);
+        assert_eq!(
+            describe("manylinux_2_28_x86_64", &platform).as_deref(),
+            Some("it requires glibc 2.28, but the host provides glibc 2.17")
+        );
+        assert_eq!(
+            describe("manylinux2014_aarch64", &platform).as_deref(),
+            Some("it was built for aarch64, but the host architecture is x86_64")
+        );
+        assert_eq!(
+            describe("musllinux_1_1_x86_64", &platform).as_deref(),
+            Some("it requires musl, but the host uses glibc")
+        );
+    }
+
+    #[test]
+    fn macos() {
+        let platform = Platform::new(
+            Os::Macos {
+                major: 12,
+                minor: 6,
+            },
+            Arch::Aarch64,
+        );
+        assert_eq!(describe("macosx_11_0_arm64", &platform), None);
+        assert_eq!(describe("macosx_10_9_universal2", &platform), None);
+        assert_eq!(
+            describe("macosx_14_0_arm64", &platform).as_deref(),
+            Some("it requires macOS 14.0 or later, but the host is running macOS 12.0")
+        );
+        assert_eq!(
+            describe("macosx_10_9_x86_64", &platform).as_deref(),
+            Some("it was built for x86_64, but the host architecture is aarch64")
+        );
+    }
+
+    #[test]
+    fn cpu_features() {
+        let cpu_features = CpuFeatures::new(["avx2", "fma"]);
+        let filename = WheelFilename::from_str(
+            "numpy-2.1.0-1cpu.avx2.fma-cp312-cp312-manylinux_2_17_x86_64.whl",
+        )
+        .unwrap();
+        assert_eq!(cpu_reason(&filename, &cpu_features), None);
+        let filename = WheelFilename::from_str(
+            "numpy-2.1.0-1cpu.avx512f.AVX2.avx512bw-cp312-cp312-manylinux_2_17_x86_64.whl",
+        )
+        .unwrap();
+        assert_eq!(
+            cpu_reason(&filename, &cpu_features).as_deref(),
+            Some("it requires CPU features that the host doesn't support: `avx512f`, `avx512bw`")
+        );
+        let filename =
+            WheelFilename::from_str("numpy-2.1.0-cp312-cp312-manylinux_2_17_x86_64.whl").unwrap();
+        assert_eq!(cpu_reason(&filename, &cpu_features), None);
+    }
+}
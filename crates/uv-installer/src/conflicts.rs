@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use distribution_types::{CachedDist, InstalledDist, Name};
+use install_wheel_rs::read_record_file;
+use uv_normalize::PackageName;
+
+/// The number of conflicting paths to display for each set of packages.
+const MAX_DISPLAYED_PATHS: usize = 5;
+
+/// A set of files that are provided by more than one of the distributions in an installation.
+///
+/// Installing these distributions would leave the environment in an order-dependent state, in
+/// which the files of one distribution silently replace those of another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileConflict {
+    /// The packages that provide the files, in sorted order.
+    pub packages: Vec<PackageName>,
+    /// The conflicting files, relative to `site-packages`.
+    pub paths: Vec<PathBuf>,
+}
+
+impl Display for FileConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let packages = match self.packages.as_slice() {
+            [] => String::new(),
+            [package] => format!("`{package}`"),
+            [first, second] => format!("`{first}` and `{second}`"),
+            [rest @ .., last] => format!(
+                "{}, and `{last}`",
+                rest.iter()
+                    .map(|package| format!("`{package}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let paths = self
+            .paths
+            .iter()
+            .take(MAX_DISPLAYED_PATHS)
+            .map(|path| format!("`{}`", path.display()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "The packages {packages} provide the same files: {paths}")?;
+        if self.paths.len() > MAX_DISPLAYED_PATHS {
+            write!(f, " (and {} more)", self.paths.len() - MAX_DISPLAYED_PATHS)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FileConflict {}
+
+/// Identify any files that would be written to `site-packages` by more than one of the given
+/// wheels, or by one of the given wheels and a distribution that will remain installed.
+///
+/// Only the files that are linked directly into `site-packages` are considered, i.e., the
+/// `.dist-info` and `.data` directories are excluded, as are any bytecode caches. The files of
+/// installed distributions are read from their `RECORD`; distributions without a `RECORD` (e.g.,
+/// those installed via `.egg-info`) are skipped.
+pub fn file_conflicts<'a>(
+    wheels: impl IntoIterator<Item = &'a CachedDist>,
+    installed: impl IntoIterator<Item = &'a InstalledDist>,
+) -> Result<Vec<FileConflict>, io::Error> {
+    // Map from each file to the packages that provide it.
+    let mut owners: BTreeMap<PathBuf, Vec<PackageName>> = BTreeMap::new();
+    for wheel in wheels {
+        for path in wheel_files(wheel.path())? {
+            owners.entry(path).or_default().push(wheel.name().clone());
+        }
+    }
+
+    // Installed files only conflict with those of the wheels; any conflicts among the installed
+    // distributions themselves already exist in the environment.
+    let mut existing: BTreeMap<PathBuf, Vec<PackageName>> = BTreeMap::new();
+    for dist in installed {
+        for path in installed_files(dist)? {
+            if owners.contains_key(&path) {
+                existing.entry(path).or_default().push(dist.name().clone());
+            }
+        }
+    }
+    for (path, packages) in existing {
+        owners.entry(path).or_default().extend(packages);
+    }
+
+    // Group the conflicting files by the set of packages that provide them.
+    let mut conflicts: BTreeMap<Vec<PackageName>, Vec<PathBuf>> = BTreeMap::new();
+    for (path, mut packages) in owners {
+        if packages.len() < 2 {
+            continue;
+        }
+        packages.sort_unstable();
+        packages.dedup();
+        conflicts.entry(packages).or_default().push(path);
+    }
+
+    Ok(conflicts
+        .into_iter()
+        .map(|(packages, paths)| FileConflict { packages, paths })
+        .collect())
+}
+
+/// Return the files in an unzipped wheel that would be linked into `site-packages`, relative to
+/// the wheel root.
+fn wheel_files(wheel: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    let mut files = Vec::new();
+    let mut walker = WalkDir::new(wheel).min_depth(1).into_iter();
+    while let Some(entry) = walker.next() {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy();
+
+        if entry.file_type().is_dir() {
+            let top_level = entry.depth() == 1
+                && (file_name.ends_with(".dist-info") || file_name.ends_with(".data"));
+            if top_level || file_name == "__pycache__" {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+
+        if file_name.ends_with(".pyc") {
+            continue;
+        }
+
+        let relative = entry
+            .path()
+            .strip_prefix(wheel)
+            .expect("walkdir entries should be within the root");
+        files.push(relative.to_path_buf());
+    }
+    Ok(files)
+}
+
+/// Return the files of an installed distribution that were written to `site-packages`, relative
+/// to `site-packages`, as listed in its `RECORD`.
+fn installed_files(dist: &InstalledDist) -> Result<Vec<PathBuf>, io::Error> {
+    let record = match fs_err::File::open(dist.path().join("RECORD")) {
+        Ok(record) => record,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let entries = read_record_file(&mut io::BufReader::new(record))
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| PathBuf::from(entry.path))
+        .filter(|path| {
+            // Skip any files outside `site-packages` (e.g., scripts), along with the metadata
+            // directory and bytecode caches.
+            let mut components = path.components();
+            let Some(Component::Normal(top_level)) = components.next() else {
+                return false;
+            };
+            if Path::new(top_level)
+                .extension()
+                .is_some_and(|extension| extension == "dist-info" || extension == "data")
+            {
+                return false;
+            }
+            !path.extension().is_some_and(|extension| extension == "pyc")
+                && !path
+                    .components()
+                    .any(|component| component.as_os_str() == "__pycache__")
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use uv_normalize::PackageName;
+
+    use super::FileConflict;
+
+    #[test]
+    fn display() {
+        let conflict = FileConflict {
+            packages: vec![
+                PackageName::from_str("foo").unwrap(),
+                PackageName::from_str("foo-fork").unwrap(),
+            ],
+            paths: (0..7)
+                .map(|i| PathBuf::from(format!("foo/module{i}.py")))
+                .collect(),
+        };
+        assert_eq!(
+            conflict.to_string(),
+            "The packages `foo` and `foo-fork` provide the same files: `foo/module0.py`, `foo/module1.py`, `foo/module2.py`, `foo/module3.py`, `foo/module4.py` (and 2 more)"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::Deserialize;
+use tracing::debug;
+
+use distribution_types::{Dist, InstalledDist, Name, Resolution, ResolvedDist, SourceDist};
+use pep440_rs::VersionSpecifier;
+use pep508_rs::{ExtraName, MarkerTree, VersionOrUrl};
+use pypi_types::{RequiresDist, VerbatimParsedUrl};
+use uv_normalize::PackageName;
+
+use crate::SitePackages;
+
+/// A mapping from entry point group, to entry point name, to object reference.
+type EntryPoints = BTreeMap<String, BTreeMap<String, String>>;
+
+/// The subset of a `pyproject.toml` that's relevant for determining the entry points of an
+/// editable.
+#[derive(Debug, Deserialize)]
+struct PyProjectToml {
+    project: Option<Project>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Project {
+    scripts: Option<BTreeMap<String, String>>,
+    gui_scripts: Option<BTreeMap<String, String>>,
+    entry_points: Option<EntryPoints>,
+    dynamic: Option<Vec<String>>,
+}
+
+/// Return the editables in the [`Resolution`] that are installed, but whose installed metadata
+/// no longer matches their `pyproject.toml`.
+///
+/// Freshness is typically determined by comparing the modification time of the `pyproject.toml`
+/// against that of the installed distribution, which misses changes that preserve (or predate)
+/// the modification time, e.g., when switching branches. Here, the statically declared
+/// dependencies and entry points are compared against those recorded at build time instead.
+pub fn stale_editables(resolution: &Resolution, site_packages: &SitePackages) -> Vec<PackageName> {
+    resolution
+        .distributions()
+        .filter_map(|dist| {
+            let ResolvedDist::Installable(Dist::Source(SourceDist::Directory(sdist))) = dist else {
+                return None;
+            };
+            if !sdist.editable {
+                return None;
+            }
+            let [installed] = site_packages.get_packages(dist.name()).as_slice() else {
+                return None;
+            };
+            if is_stale(installed, &sdist.install_path) {
+                debug!("Installed editable metadata is stale: {}", dist.name());
+                Some(dist.name().clone())
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Return all the editables in the [`Resolution`].
+pub fn editables(resolution: &Resolution) -> Vec<PackageName> {
+    resolution
+        .distributions()
+        .filter(|dist| {
+            matches!(
+                dist,
+                ResolvedDist::Installable(Dist::Source(SourceDist::Directory(sdist))) if sdist.editable
+            )
+        })
+        .map(|dist| dist.name().clone())
+        .collect()
+}
+
+/// Returns `true` if the metadata of the installed distribution is known to differ from that
+/// declared in the `pyproject.toml` of the source tree.
+///
+/// If the metadata can't be determined statically (e.g., the project relies on `setup.py`, or
+/// declares the relevant fields as dynamic), the installed distribution is assumed to be fresh.
+fn is_stale(installed: &InstalledDist, source_tree: &Path) -> bool {
+    let Ok(contents) = fs_err::read_to_string(source_tree.join("pyproject.toml")) else {
+        return false;
+    };
+
+    // Compare the dependencies.
+    if let Ok(requires_dist) = RequiresDist::parse_pyproject_toml(&contents) {
+        if let Ok(metadata) = installed.metadata() {
+            if dependencies(&requires_dist.requires_dist) != dependencies(&metadata.requires_dist) {
+                return true;
+            }
+        }
+    }
+
+    // Compare the entry points.
+    let Ok(PyProjectToml {
+        project: Some(project),
+    }) = toml::from_str::<PyProjectToml>(&contents)
+    else {
+        return false;
+    };
+    let dynamic = project.dynamic.unwrap_or_default();
+    if ["scripts", "gui-scripts", "entry-points"]
+        .iter()
+        .any(|field| dynamic.iter().any(|dynamic| dynamic == field))
+    {
+        return false;
+    }
+    let mut declared = project.entry_points.unwrap_or_default();
+    if let Some(scripts) = project.scripts {
+        declared.insert("console_scripts".to_string(), scripts);
+    }
+    if let Some(gui_scripts) = project.gui_scripts {
+        declared.insert("gui_scripts".to_string(), gui_scripts);
+    }
+    declared.retain(|_, entries| !entries.is_empty());
+
+    let Ok(recorded) = recorded_entry_points(installed.path()) else {
+        return false;
+    };
+
+    declared != recorded
+}
+
+/// A requirement, normalized for comparison.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct NormalizedRequirement {
+    name: PackageName,
+    extras: BTreeSet<ExtraName>,
+    specifiers: BTreeSet<VersionSpecifier>,
+    marker: Option<MarkerTree>,
+}
+
+/// Normalize a set of requirements for comparison.
+///
+/// Names and extras are normalized, specifiers and extras are compared irrespective of order, and
+/// markers are compared by their meaning, rather than their spelling. Direct URLs are omitted,
+/// since build backends are free to rewrite them (e.g., to resolve relative paths).
+fn dependencies(
+    requirements: &[pep508_rs::Requirement<VerbatimParsedUrl>],
+) -> BTreeSet<NormalizedRequirement> {
+    requirements
+        .iter()
+        .map(|requirement| NormalizedRequirement {
+            name: requirement.name.clone(),
+            extras: requirement.extras.iter().cloned().collect(),
+            specifiers: match &requirement.version_or_url {
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) => {
+                    specifiers.iter().cloned().collect()
+                }
+                _ => BTreeSet::new(),
+            },
+            marker: requirement
+                .marker
+                .clone()
+                .filter(|marker| !marker.is_true()),
+        })
+        .collect()
+}
+
+/// Read the entry points recorded in the `entry_points.txt` file of an installed distribution.
+fn recorded_entry_points(dist_info: &Path) -> Result<EntryPoints, std::io::Error> {
+    let contents = match fs_err::read_to_string(dist_info.join("entry_points.txt")) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(EntryPoints::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut entry_points = EntryPoints::new();
+    let mut group = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            group = Some(name.trim().to_string());
+            continue;
+        }
+        let (Some(group), Some((name, value))) = (group.as_ref(), line.split_once('=')) else {
+            continue;
+        };
+        entry_points
+            .entry(group.clone())
+            .or_default()
+            .insert(name.trim().to_string(), value.trim().to_string());
+    }
+    Ok(entry_points)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pypi_types::VerbatimParsedUrl;
+
+    use super::dependencies;
+
+    fn requirements(requirements: &[&str]) -> Vec<pep508_rs::Requirement<VerbatimParsedUrl>> {
+        requirements
+            .iter()
+            .map(|requirement| pep508_rs::Requirement::from_str(requirement).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn equivalent() {
+        assert_eq!(
+            dependencies(&requirements(&[
+                "Foo_Bar[b,a]>=1.0,<2 ; python_version >= '3.8' and sys_platform == 'linux'",
+                "baz",
+            ])),
+            dependencies(&requirements(&[
+                "baz",
+                "foo-bar[A,B]<2,>=1.0 ; sys_platform == \"linux\" and python_version >= \"3.8\"",
+            ])),
+        );
+    }
+
+    #[test]
+    fn different() {
+        assert_ne!(
+            dependencies(&requirements(&["foo>=1.0,<2"])),
+            dependencies(&requirements(&["foo>=1.0,<3"])),
+        );
+        assert_ne!(
+            dependencies(&requirements(&["foo ; python_version >= '3.8'"])),
+            dependencies(&requirements(&["foo"])),
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rustc_hash::FxHashSet;
+use thiserror::Error;
+
+use distribution_types::{InstalledDist, Name};
+use uv_normalize::PackageName;
+
+#[derive(Debug, Error)]
+#[error("Failed to finalize the installation of `{0}`")]
+pub struct FlushError(PackageName, #[source] install_wheel_rs::Error);
+
+/// When to flush the files written by an installation to disk.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// Leave flushing to the operating system.
+    #[default]
+    Never,
+    /// Flush every installed file (and its parent directory) to disk in a single batch, once all
+    /// distributions have been installed, rather than interleaving the flushes with the writes.
+    Batch,
+}
+
+impl FromStr for FsyncPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(Self::Never),
+            "batch" => Ok(Self::Batch),
+            _ => Err(format!(
+                "Unknown fsync policy `{s}` (expected one of: `never`, `batch`)"
+            )),
+        }
+    }
+}
+
+/// Flush the files installed by the given distributions to disk, along with the directories that
+/// contain them, returning the number of files flushed.
+pub fn fsync_installed(dists: &[InstalledDist]) -> Result<usize, FlushError> {
+    let files = installed_files(dists)?;
+
+    files.par_iter().try_for_each(|(name, path)| {
+        fsync(path, false).map_err(|err| FlushError(name.clone(), err.into()))
+    })?;
+
+    // Directories can't be opened for writing on Windows, and are flushed alongside their
+    // contents on NTFS.
+    if cfg!(unix) {
+        let directories = files
+            .iter()
+            .filter_map(|(name, path)| Some((name, path.parent()?)))
+            .collect::<FxHashSet<_>>();
+        directories.par_iter().try_for_each(|(name, path)| {
+            fsync(path, true).map_err(|err| FlushError((*name).clone(), err.into()))
+        })?;
+    }
+
+    Ok(files.len())
+}
+
+/// Remove the `com.apple.quarantine` extended attribute from the files installed by the given
+/// distributions, returning the number of files from which it was removed.
+///
+/// Quarantined files are subject to Gatekeeper and XProtect assessment when they're first
+/// executed or loaded, which is prohibitively slow for packages with many extension modules. The
+/// attribute can be propagated from the cache, e.g., when installing with `--link-mode=clone`
+/// from an archive that was downloaded by a quarantine-aware application.
+#[cfg(target_os = "macos")]
+pub fn clear_quarantine(dists: &[InstalledDist]) -> Result<usize, FlushError> {
+    let files = installed_files(dists)?;
+    let cleared = files
+        .par_iter()
+        .map(
+            |(name, path)| match rustix::fs::removexattr(path, "com.apple.quarantine") {
+                Ok(()) => Ok(1),
+                Err(rustix::io::Errno::NOATTR | rustix::io::Errno::NOENT) => Ok(0),
+                Err(err) => Err(FlushError(name.clone(), io::Error::from(err).into())),
+            },
+        )
+        .try_reduce(|| 0, |a, b| Ok(a + b))?;
+    Ok(cleared)
+}
+
+/// Collect the absolute paths of the files listed in the `RECORD` of each distribution.
+///
+/// Distributions without a `RECORD` file (e.g., legacy `.egg-info` installations) are skipped.
+fn installed_files(dists: &[InstalledDist]) -> Result<Vec<(PackageName, PathBuf)>, FlushError> {
+    let mut files = Vec::new();
+    for dist in dists {
+        let record = match fs_err::File::open(dist.path().join("RECORD")) {
+            Ok(mut record) => install_wheel_rs::read_record_file(&mut record)
+                .map_err(|err| FlushError(dist.name().clone(), err))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(FlushError(dist.name().clone(), err.into())),
+        };
+
+        // The paths in the `RECORD` are relative to `site-packages`.
+        let Some(site_packages) = dist.path().parent() else {
+            continue;
+        };
+        files.extend(
+            record
+                .into_iter()
+                .map(|entry| (dist.name().clone(), site_packages.join(entry.path))),
+        );
+    }
+    Ok(files)
+}
+
+/// Flush the file or directory at the given path to disk, ignoring paths that no longer exist
+/// (e.g., files that were excluded from the installation).
+fn fsync(path: &Path, directory: bool) -> io::Result<()> {
+    let file = match fs_err::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    if directory {
+        file.sync_all()
+    } else {
+        file.sync_data()
+    }
+}
@@ -1,18 +1,25 @@
 use anyhow::{Context, Error, Result};
-use install_wheel_rs::{linker::LinkMode, Layout};
+use install_wheel_rs::{linker::LinkMode, ExcludePattern, Layout};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::convert;
+use std::sync::Arc;
 use tokio::sync::oneshot;
 use tracing::instrument;
 
-use distribution_types::CachedDist;
+use distribution_types::{CachedDist, Name};
 use uv_cache::Cache;
+use uv_normalize::PackageName;
 use uv_python::PythonEnvironment;
 
+use crate::Journal;
+
 pub struct Installer<'a> {
     venv: &'a PythonEnvironment,
     link_mode: LinkMode,
+    lazy: Vec<PackageName>,
+    exclude: Vec<ExcludePattern>,
     cache: Option<&'a Cache>,
+    journal: Option<Arc<Journal>>,
     reporter: Option<Box<dyn Reporter>>,
     installer_name: Option<String>,
 }
@@ -23,7 +30,10 @@ impl<'a> Installer<'a> {
         Self {
             venv,
             link_mode: LinkMode::default(),
+            lazy: Vec::new(),
+            exclude: Vec::new(),
             cache: None,
+            journal: None,
             reporter: None,
             installer_name: Some("uv".to_string()),
         }
@@ -35,6 +45,19 @@ impl<'a> Installer<'a> {
         Self { link_mode, ..self }
     }
 
+    /// Set the packages to install lazily, deferring the installation of their modules until they're
+    /// first imported.
+    #[must_use]
+    pub fn with_lazy(self, lazy: Vec<PackageName>) -> Self {
+        Self { lazy, ..self }
+    }
+
+    /// Set the patterns for files to exclude from the installed wheels.
+    #[must_use]
+    pub fn with_exclude(self, exclude: Vec<ExcludePattern>) -> Self {
+        Self { exclude, ..self }
+    }
+
     /// Set the [`Cache`] to use for this installer.
     #[must_use]
     pub fn with_cache(self, cache: &'a Cache) -> Self {
@@ -44,6 +67,16 @@ impl<'a> Installer<'a> {
         }
     }
 
+    /// Set the [`Journal`] in which to record each installation, such that an interrupted
+    /// installation can be rolled back.
+    #[must_use]
+    pub fn with_journal(self, journal: Arc<Journal>) -> Self {
+        Self {
+            journal: Some(journal),
+            ..self
+        }
+    }
+
     /// Set the [`Reporter`] to use for this installer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -69,6 +102,9 @@ impl<'a> Installer<'a> {
             venv,
             cache,
             link_mode,
+            lazy,
+            exclude,
+            journal,
             reporter,
             installer_name,
         } = self;
@@ -79,6 +115,11 @@ impl<'a> Installer<'a> {
                     "Symlink-based installation is not supported with `--no-cache`. The created environment will be rendered unusable by the removal of the cache."
                 ));
             }
+            if !lazy.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Lazy installation is not supported with `--no-cache`. The created environment will be rendered unusable by the removal of the cache."
+                ));
+            }
         }
 
         let (tx, rx) = oneshot::channel();
@@ -91,6 +132,9 @@ impl<'a> Installer<'a> {
                 layout,
                 installer_name,
                 link_mode,
+                &lazy,
+                &exclude,
+                journal.as_deref(),
                 reporter,
                 relocatable,
             );
@@ -111,6 +155,11 @@ impl<'a> Installer<'a> {
                     "Symlink-based installation is not supported with `--no-cache`. The created environment will be rendered unusable by the removal of the cache."
                 ));
             }
+            if !self.lazy.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Lazy installation is not supported with `--no-cache`. The created environment will be rendered unusable by the removal of the cache."
+                ));
+            }
         }
 
         install(
@@ -118,6 +167,9 @@ impl<'a> Installer<'a> {
             self.venv.interpreter().layout(),
             self.installer_name,
             self.link_mode,
+            &self.lazy,
+            &self.exclude,
+            self.journal.as_deref(),
             self.reporter,
             self.venv.relocatable(),
         )
@@ -131,11 +183,18 @@ fn install(
     layout: Layout,
     installer_name: Option<String>,
     link_mode: LinkMode,
+    lazy: &[PackageName],
+    exclude: &[ExcludePattern],
+    journal: Option<&Journal>,
     reporter: Option<Box<dyn Reporter>>,
     relocatable: bool,
 ) -> Result<Vec<CachedDist>> {
     let locks = install_wheel_rs::linker::Locks::default();
     wheels.par_iter().try_for_each(|wheel| {
+        if let Some(journal) = journal {
+            journal.install(wheel)?;
+        }
+
         install_wheel_rs::linker::install_wheel(
             &layout,
             relocatable,
@@ -149,10 +208,16 @@ fn install(
                 .as_ref(),
             installer_name.as_deref(),
             link_mode,
+            lazy.contains(wheel.name()),
+            exclude,
             &locks,
         )
         .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
 
+        if let Some(journal) = journal {
+            journal.commit(wheel.name())?;
+        }
+
         if let Some(reporter) = reporter.as_ref() {
             reporter.on_install_progress(wheel);
         }
@@ -0,0 +1,285 @@
+//! A journal of the changes made to an environment during an installation.
+//!
+//! Before a package is removed from or linked into the environment, the change is appended to the
+//! journal; once all changes for the package are complete, the package is committed. The journal
+//! is removed once the installation completes.
+//!
+//! If an installation is interrupted (e.g., the process is killed), the journal is left behind.
+//! The next installation into the environment rolls back the changes for any packages that were
+//! never committed, removing them entirely rather than leaving half-installed packages behind.
+//! Committed packages are left in place, such that re-running the installation resumes from where
+//! it left off.
+
+use std::collections::BTreeSet;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use walkdir::WalkDir;
+
+use distribution_types::{CachedDist, InstalledDist, Name};
+use uv_normalize::PackageName;
+use uv_python::PythonEnvironment;
+
+/// The name of the journal file within the environment's `site-packages` directory.
+const JOURNAL: &str = ".uv-journal.jsonl";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum Entry {
+    /// An installed distribution is about to be removed.
+    Uninstall { name: PackageName, path: PathBuf },
+    /// A wheel is about to be linked into the environment from the given (unzipped) directory.
+    Install { name: PackageName, wheel: PathBuf },
+    /// All changes for the package are complete.
+    Commit { name: PackageName },
+}
+
+/// A journal of the changes made to an environment during an installation.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    file: Mutex<Option<fs_err::File>>,
+}
+
+impl Journal {
+    /// Start a new journal for the given environment.
+    ///
+    /// Any incomplete changes from a previous installation should be rolled back (via
+    /// [`Journal::recover`]) first.
+    pub fn create(venv: &PythonEnvironment) -> Result<Self, JournalError> {
+        let path = journal_path(venv);
+        let file = fs_err::File::create(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(Some(file)),
+        })
+    }
+
+    /// Record that the given distribution is about to be removed.
+    pub fn uninstall(&self, dist: &InstalledDist) -> Result<(), JournalError> {
+        self.append(&Entry::Uninstall {
+            name: dist.name().clone(),
+            path: dist.path().to_path_buf(),
+        })
+    }
+
+    /// Record that the given wheel is about to be linked into the environment.
+    pub fn install(&self, wheel: &CachedDist) -> Result<(), JournalError> {
+        self.append(&Entry::Install {
+            name: wheel.name().clone(),
+            wheel: wheel.path().to_path_buf(),
+        })
+    }
+
+    /// Record that all changes for the given package are complete.
+    pub fn commit(&self, name: &PackageName) -> Result<(), JournalError> {
+        self.append(&Entry::Commit { name: name.clone() })
+    }
+
+    /// Mark the installation as complete, removing the journal.
+    pub fn finish(&self) -> Result<(), JournalError> {
+        // Close the journal before removing it, which is required on Windows.
+        if self.file.lock().unwrap().take().is_some() {
+            fs_err::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+
+    fn append(&self, entry: &Entry) -> Result<(), JournalError> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap();
+        let file = file.as_mut().expect("journal to be open");
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Roll back any changes left incomplete by an interrupted installation into the given
+    /// environment, returning the packages that were removed as a result.
+    ///
+    /// If the environment has no journal, there's nothing to recover, and no packages are
+    /// returned.
+    pub fn recover(venv: &PythonEnvironment) -> Result<Vec<PackageName>, JournalError> {
+        let path = journal_path(venv);
+        let file = match fs_err::File::open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        // Collect the changes for any packages that were never committed. A truncated entry can
+        // only be the last one, and indicates that the change itself never started.
+        let mut pending = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let Ok(entry) = serde_json::from_str::<Entry>(&line) else {
+                debug!("Skipping malformed journal entry: {line}");
+                continue;
+            };
+            match entry {
+                Entry::Commit { name } => pending.retain(|entry| match entry {
+                    Entry::Uninstall { name: other, .. } | Entry::Install { name: other, .. } => {
+                        *other != name
+                    }
+                    Entry::Commit { .. } => false,
+                }),
+                entry => pending.push(entry),
+            }
+        }
+
+        // Roll back the changes in reverse order, removing each affected package entirely.
+        let site_packages = site_packages(venv);
+        let mut removed = BTreeSet::new();
+        for entry in pending.into_iter().rev() {
+            match entry {
+                Entry::Uninstall { name, path } => {
+                    debug!("Completing interrupted removal of: {name}");
+                    uninstall_path(&path)?;
+                    removed.insert(name);
+                }
+                Entry::Install { name, wheel } => {
+                    debug!("Rolling back interrupted installation of: {name}");
+                    unlink_wheel(&wheel, &site_packages)?;
+                    removed.insert(name);
+                }
+                Entry::Commit { .. } => {}
+            }
+        }
+
+        fs_err::remove_file(&path)?;
+        Ok(removed.into_iter().collect())
+    }
+}
+
+/// Return the path to the journal for the given environment.
+fn journal_path(venv: &PythonEnvironment) -> PathBuf {
+    venv.interpreter().layout().scheme.purelib.join(JOURNAL)
+}
+
+/// Return the `site-packages` directories into which wheels are linked.
+fn site_packages(venv: &PythonEnvironment) -> Vec<PathBuf> {
+    let scheme = venv.interpreter().layout().scheme;
+    if scheme.purelib == scheme.platlib {
+        vec![scheme.purelib]
+    } else {
+        vec![scheme.purelib, scheme.platlib]
+    }
+}
+
+/// Remove whatever remains of an installed distribution, given its `.dist-info` directory.
+fn uninstall_path(path: &Path) -> Result<(), JournalError> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    // Only `.dist-info` distributions can be removed based on their `RECORD`; others (e.g.,
+    // legacy editables) are left as-is.
+    if path
+        .extension()
+        .map_or(true, |extension| extension != "dist-info")
+    {
+        debug!("Unable to complete removal of: {}", path.display());
+        return Ok(());
+    }
+
+    match install_wheel_rs::uninstall_wheel(path) {
+        Ok(_) => {}
+        // If the `RECORD` was already removed, all that's left is to remove the metadata itself,
+        // such that the package is no longer considered installed.
+        Err(install_wheel_rs::Error::MissingRecord(_)) => {
+            if path.is_dir() {
+                fs_err::remove_dir_all(path)?;
+            }
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok(())
+}
+
+/// Remove any files that were linked into `site-packages` from the given wheel.
+fn unlink_wheel(wheel: &Path, site_packages: &[PathBuf]) -> Result<(), JournalError> {
+    let Ok(entries) = fs_err::read_dir(wheel) else {
+        return Ok(());
+    };
+
+    let mut dist_info = None;
+    let mut top_level = Vec::new();
+    for entry in entries {
+        let file_name = entry?.file_name();
+        let Some(file_name) = file_name.to_str().map(ToString::to_string) else {
+            continue;
+        };
+        if file_name.ends_with(".dist-info") {
+            dist_info = Some(file_name);
+        } else if !file_name.ends_with(".data") {
+            top_level.push(file_name);
+        }
+    }
+
+    for site_packages in site_packages {
+        // If the `RECORD` was written, the wheel was linked in full, so uninstall it as usual.
+        if let Some(dist_info) = dist_info.as_ref() {
+            let path = site_packages.join(dist_info);
+            if path.join("RECORD").is_file() {
+                install_wheel_rs::uninstall_wheel(&path)?;
+                continue;
+            }
+        }
+
+        // Otherwise, remove each file that the wheel would've linked, along with any directories
+        // that are left empty.
+        for entry in &top_level {
+            let mut directories = Vec::new();
+            for file in WalkDir::new(wheel.join(entry)) {
+                let file = file?;
+                let relative = file
+                    .path()
+                    .strip_prefix(wheel)
+                    .expect("walked from the wheel");
+                let target = site_packages.join(relative);
+                if file.file_type().is_dir() {
+                    directories.push(target);
+                } else {
+                    match fs_err::remove_file(&target) {
+                        Ok(()) => {}
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+            }
+
+            // Visit the deepest directories first. Directories that aren't empty (e.g., because
+            // they're shared with another package) are left in place.
+            for directory in directories.iter().rev() {
+                let _ = fs_err::remove_dir_all(directory.join("__pycache__"));
+                let _ = fs_err::remove_dir(directory);
+            }
+        }
+
+        if let Some(dist_info) = dist_info.as_ref() {
+            let path = site_packages.join(dist_info);
+            if path.is_dir() {
+                fs_err::remove_dir_all(&path)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum JournalError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    WalkDir(#[from] walkdir::Error),
+    #[error(transparent)]
+    Uninstall(#[from] install_wheel_rs::Error),
+}
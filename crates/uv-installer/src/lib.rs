@@ -1,15 +1,31 @@
+pub use compatibility::{incompatible_wheels, IncompatibleWheel};
 pub use compile::{compile_tree, CompileError};
+pub use conflicts::{file_conflicts, FileConflict};
+pub use editable::{editables, stale_editables};
+#[cfg(target_os = "macos")]
+pub use flush::clear_quarantine;
+pub use flush::{fsync_installed, FlushError, FsyncPolicy};
 pub use installer::{Installer, Reporter as InstallReporter};
+pub use journal::{Journal, JournalError};
 pub use plan::{Plan, Planner};
 pub use preparer::{Preparer, Reporter as PrepareReporter};
 pub use site_packages::{SatisfiesResult, SitePackages, SitePackagesDiagnostic};
+pub use sizes::{installed_size, InstalledSize, InstalledSizeError};
 pub use uninstall::{uninstall, UninstallError};
+pub use verify::{verify_imports, ImportFailure, VerifyImportsError, VerifyImportsResult};
 
+mod compatibility;
 mod compile;
+mod conflicts;
+mod editable;
+mod flush;
 mod preparer;
 
 mod installer;
+mod journal;
 mod plan;
 mod satisfies;
 mod site_packages;
+mod sizes;
 mod uninstall;
+mod verify;
@@ -8,8 +8,8 @@ use tracing::debug;
 use distribution_filename::{DistExtension, WheelFilename};
 use distribution_types::{
     CachedDirectUrlDist, CachedDist, DirectUrlBuiltDist, DirectUrlSourceDist, DirectorySourceDist,
-    Error, GitSourceDist, Hashed, IndexLocations, InstalledDist, Name, PathBuiltDist,
-    PathSourceDist, RemoteSource, Verbatim,
+    Dist, Error, GitSourceDist, Hashed, IndexLocations, InstalledDist, Name, PathBuiltDist,
+    PathSourceDist, RemoteSource, Resolution, Verbatim,
 };
 use platform_tags::Tags;
 use pypi_types::{Requirement, RequirementSource};
@@ -30,12 +30,28 @@ use crate::SitePackages;
 #[derive(Debug)]
 pub struct Planner<'a> {
     requirements: &'a [Requirement],
+    resolution: Option<&'a Resolution>,
 }
 
 impl<'a> Planner<'a> {
     /// Set the requirements use in the [`Plan`].
     pub fn new(requirements: &'a [Requirement]) -> Self {
-        Self { requirements }
+        Self {
+            requirements,
+            resolution: None,
+        }
+    }
+
+    /// Set the [`Resolution`] from which the requirements were derived.
+    ///
+    /// If the resolution selected a pre-built wheel for a registry package, wheels that were
+    /// built from source (e.g., by a previous `--prefer-source` installation) won't be reused.
+    #[must_use]
+    pub fn with_resolution(self, resolution: &'a Resolution) -> Self {
+        Self {
+            resolution: Some(resolution),
+            ..self
+        }
     }
 
     /// Partition a set of requirements into those that should be linked from the cache, those that
@@ -61,7 +77,8 @@ impl<'a> Planner<'a> {
         tags: &Tags,
     ) -> Result<Plan> {
         // Index all the already-downloaded wheels in the cache.
-        let mut registry_index = RegistryWheelIndex::new(cache, tags, index_locations, hasher);
+        let mut registry_index =
+            RegistryWheelIndex::new(cache, tags, index_locations, hasher, build_options);
         let built_index = BuiltWheelIndex::new(cache, tags, hasher);
 
         let mut cached = vec![];
@@ -141,12 +158,24 @@ impl<'a> Planner<'a> {
             // Identify any cached distributions that satisfy the requirement.
             match &requirement.source {
                 RequirementSource::Registry { specifier, .. } => {
-                    if let Some((_version, distribution)) = registry_index
-                        .get(&requirement.name)
-                        .find(|(version, _)| specifier.contains(version))
-                    {
+                    let prebuilt = self
+                        .resolution
+                        .and_then(|resolution| resolution.get_remote(&requirement.name))
+                        .is_some_and(|dist| matches!(dist, Dist::Built(_)));
+                    let distribution = if prebuilt {
+                        registry_index
+                            .get_prebuilt(&requirement.name)
+                            .find(|(version, _)| specifier.contains(version))
+                            .map(|(_, distribution)| distribution.clone())
+                    } else {
+                        registry_index
+                            .get(&requirement.name)
+                            .find(|(version, _)| specifier.contains(version))
+                            .map(|(_, distribution)| distribution.clone())
+                    };
+                    if let Some(distribution) = distribution {
                         debug!("Requirement already cached: {distribution}");
-                        cached.push(CachedDist::Registry(distribution.clone()));
+                        cached.push(CachedDist::Registry(distribution));
                         continue;
                     }
                 }
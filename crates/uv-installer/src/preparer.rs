@@ -144,7 +144,7 @@ impl<'a, Context: BuildContext> Preparer<'a, Context> {
 
         let id = dist.distribution_id();
         if in_flight.downloads.register(id.clone()) {
-            let policy = self.hashes.get(&dist);
+            let policy = self.database.hash_policy(&dist, self.hashes.get(&dist));
 
             let result = self
                 .database
@@ -110,7 +110,7 @@ impl RequirementSatisfaction {
                         VcsInfo {
                             vcs: VcsKind::Git,
                             requested_revision: installed_reference,
-                            commit_id: _,
+                            commit_id: installed_precise,
                         },
                     subdirectory: installed_subdirectory,
                 } = direct_url.as_ref()
@@ -146,6 +146,19 @@ impl RequirementSatisfaction {
                     return Ok(Self::OutOfDate);
                 }
 
+                // If the requested reference now resolves to a different commit (e.g., a branch
+                // that has since been updated), the installed distribution is out-of-date.
+                if let Some(requested_precise) = requested_precise {
+                    if installed_precise.as_deref() != Some(requested_precise.to_string().as_str())
+                    {
+                        debug!(
+                            "Commit mismatch: {:?} vs. {:?}",
+                            installed_precise, requested_precise
+                        );
+                        return Ok(Self::OutOfDate);
+                    }
+                }
+
                 Ok(Self::Satisfied)
             }
             RequirementSource::Path {
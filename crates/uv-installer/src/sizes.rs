@@ -0,0 +1,73 @@
+use std::io;
+
+use thiserror::Error;
+
+use distribution_types::{InstalledDist, Name};
+use uv_normalize::PackageName;
+
+#[derive(Debug, Error)]
+#[error("Failed to read the `RECORD` file of `{0}`")]
+pub struct InstalledSizeError(PackageName, #[source] install_wheel_rs::Error);
+
+/// The on-disk footprint of an installed distribution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct InstalledSize {
+    /// The number of files installed by the distribution.
+    pub files: usize,
+    /// The total size of those files, in bytes.
+    pub bytes: u64,
+}
+
+impl std::ops::Add for InstalledSize {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            files: self.files + rhs.files,
+            bytes: self.bytes + rhs.bytes,
+        }
+    }
+}
+
+/// Compute the [`InstalledSize`] of a distribution from the files listed in its `RECORD`.
+///
+/// Returns `None` if the distribution doesn't have a `RECORD` file (e.g., for legacy
+/// `.egg-info` installations). Files that are listed in the `RECORD`, but are missing from disk,
+/// are omitted.
+pub fn installed_size(dist: &InstalledDist) -> Result<Option<InstalledSize>, InstalledSizeError> {
+    let record = match fs_err::File::open(dist.path().join("RECORD")) {
+        Ok(mut record) => install_wheel_rs::read_record_file(&mut record)
+            .map_err(|err| InstalledSizeError(dist.name().clone(), err))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(InstalledSizeError(
+                dist.name().clone(),
+                install_wheel_rs::Error::Io(err),
+            ))
+        }
+    };
+
+    // The paths in the `RECORD` are relative to `site-packages`.
+    let Some(site_packages) = dist.path().parent() else {
+        return Ok(None);
+    };
+
+    let mut size = InstalledSize::default();
+    for entry in record {
+        // Prefer the size on disk, since installation can rewrite files (e.g., scripts), and the
+        // `RECORD` omits the size of some entries (like the `RECORD` itself).
+        let bytes = match fs_err::metadata(site_packages.join(&entry.path)) {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                return Err(InstalledSizeError(
+                    dist.name().clone(),
+                    install_wheel_rs::Error::Io(err),
+                ))
+            }
+        };
+        size.files += 1;
+        size.bytes += bytes;
+    }
+    Ok(Some(size))
+}
@@ -0,0 +1,223 @@
+use std::collections::BTreeSet;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use futures::{stream, StreamExt, TryStreamExt};
+use thiserror::Error;
+use tokio::process::Command;
+use tracing::{debug, instrument};
+
+use distribution_types::{InstalledDist, Name};
+use uv_normalize::PackageName;
+use uv_python::PythonEnvironment;
+
+/// Import a single module, after prepending the given `site-packages` directories to `sys.path`.
+///
+/// The interpreter is run in isolated mode, such that modules in the working directory can't
+/// shadow the installed modules.
+const VERIFY_IMPORT_SCRIPT: &str = r"
+import importlib, sys
+module, *paths = sys.argv[1:]
+sys.path[:0] = [path for path in paths if path not in sys.path]
+importlib.import_module(module)
+";
+
+/// This is longer than any import should ever take.
+const IMPORT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error)]
+pub enum VerifyImportsError {
+    #[error("Failed to read the `RECORD` file of `{0}`")]
+    Record(PackageName, #[source] install_wheel_rs::Error),
+    #[error("Failed to start Python interpreter to verify imports")]
+    PythonSubcommand(#[source] io::Error),
+}
+
+/// A module that failed to import.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ImportFailure {
+    /// The package that provides the module.
+    pub package: PackageName,
+    /// The name of the top-level module.
+    pub module: String,
+    /// The error raised while importing the module, e.g., the last line of the traceback.
+    pub error: String,
+}
+
+/// The result of verifying the imports of a set of installed distributions.
+#[derive(Debug, Default)]
+pub struct VerifyImportsResult {
+    /// The number of modules that were imported.
+    pub modules: usize,
+    /// The modules that failed to import.
+    pub failures: Vec<ImportFailure>,
+}
+
+/// Import each top-level module provided by the given distributions, each in a separate Python
+/// process, and report any failures.
+///
+/// This surfaces broken installations (e.g., native extensions that are missing shared libraries
+/// or were built against an incompatible ABI) immediately, rather than when the module is first
+/// used.
+#[instrument(skip_all)]
+pub async fn verify_imports<'a>(
+    dists: impl IntoIterator<Item = &'a InstalledDist>,
+    venv: &PythonEnvironment,
+) -> Result<VerifyImportsResult, VerifyImportsError> {
+    let mut modules = Vec::new();
+    for dist in dists {
+        for module in top_level_modules(dist)? {
+            modules.push((dist.name().clone(), module));
+        }
+    }
+
+    let site_packages = venv
+        .site_packages()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+
+    let concurrency = std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+    debug!(
+        "Verifying imports of {} module(s) with {concurrency} worker(s)",
+        modules.len()
+    );
+
+    let count = modules.len();
+    let mut failures = stream::iter(modules)
+        .map(|(package, module)| {
+            let site_packages = &site_packages;
+            async move {
+                let error = import_module(venv.python_executable(), &module, site_packages).await?;
+                Ok::<_, VerifyImportsError>(error.map(|error| ImportFailure {
+                    package,
+                    module,
+                    error,
+                }))
+            }
+        })
+        .buffer_unordered(concurrency.get())
+        .try_filter_map(|failure| async move { Ok(failure) })
+        .try_collect::<Vec<_>>()
+        .await?;
+    failures.sort_unstable();
+
+    Ok(VerifyImportsResult {
+        modules: count,
+        failures,
+    })
+}
+
+/// Import the given module, returning the error if the import failed.
+async fn import_module(
+    python_executable: &Path,
+    module: &str,
+    site_packages: &[String],
+) -> Result<Option<String>, VerifyImportsError> {
+    debug!("Verifying import of: `{module}`");
+    let child = Command::new(python_executable)
+        .arg("-I")
+        .arg("-c")
+        .arg(VERIFY_IMPORT_SCRIPT)
+        .arg(module)
+        .args(site_packages)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(VerifyImportsError::PythonSubcommand)?;
+
+    let output = match tokio::time::timeout(IMPORT_TIMEOUT, child.wait_with_output()).await {
+        Ok(output) => output.map_err(VerifyImportsError::PythonSubcommand)?,
+        Err(_) => {
+            return Ok(Some(format!(
+                "Timed out after {}s",
+                IMPORT_TIMEOUT.as_secs_f32()
+            )))
+        }
+    };
+
+    if output.status.success() {
+        return Ok(None);
+    }
+
+    // Report the last line of the traceback, which contains the exception, if any.
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .last()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| match output.status.code() {
+            Some(code) => format!("Python exited with code {code}"),
+            None => "Python was terminated by a signal".to_string(),
+        });
+    Ok(Some(error))
+}
+
+/// Determine the top-level modules provided by an installed distribution.
+///
+/// Uses the `top_level.txt` file written by setuptools, if present, and otherwise infers the
+/// modules from the installed files listed in the `RECORD`.
+fn top_level_modules(dist: &InstalledDist) -> Result<BTreeSet<String>, VerifyImportsError> {
+    let (InstalledDist::Registry(_) | InstalledDist::Url(_)) = dist else {
+        return Ok(BTreeSet::new());
+    };
+
+    if let Ok(top_level) = fs_err::read_to_string(dist.path().join("top_level.txt")) {
+        return Ok(top_level
+            .lines()
+            .map(str::trim)
+            // Ex) `google/protobuf`, for namespace packages.
+            .filter_map(|line| line.split('/').next())
+            .filter(|module| is_importable(module))
+            .map(ToString::to_string)
+            .collect());
+    }
+
+    let record = match fs_err::File::open(dist.path().join("RECORD")) {
+        Ok(mut record) => install_wheel_rs::read_record_file(&mut record)
+            .map_err(|err| VerifyImportsError::Record(dist.name().clone(), err))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(BTreeSet::new()),
+        Err(err) => {
+            return Err(VerifyImportsError::Record(
+                dist.name().clone(),
+                install_wheel_rs::Error::Io(err),
+            ))
+        }
+    };
+
+    Ok(record
+        .iter()
+        .filter_map(|entry| {
+            let mut components = entry.path.split('/');
+            let first = components.next()?;
+            if components.next().is_some() {
+                // Ex) `numpy/__init__.py`
+                Some(first)
+            } else {
+                // Ex) `six.py` or `_cffi_backend.cpython-312-x86_64-linux-gnu.so`
+                let (stem, extension) = first.rsplit_once('.')?;
+                matches!(extension, "py" | "so" | "pyd")
+                    .then(|| stem.split('.').next().unwrap_or(stem))
+            }
+        })
+        .filter(|module| is_importable(module))
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Returns `true` if the given `RECORD` path component names an importable module, as opposed
+/// to, e.g., the `.dist-info` directory, the scripts directory (`../../bin`), or the path hooks of
+/// an editable installation.
+fn is_importable(module: &str) -> bool {
+    !module.is_empty()
+        && !module.starts_with("__editable__")
+        && module != "__pycache__"
+        && !module.chars().next().is_some_and(|c| c.is_ascii_digit())
+        && module.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
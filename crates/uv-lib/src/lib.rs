@@ -0,0 +1,424 @@
+//! Embed uv's resolver, project, and tool operations in other Rust programs.
+//!
+//! Each operation is an `async` function that mirrors the corresponding command (e.g., [`lock`]
+//! for `uv lock`), reads the same `uv.toml` and `pyproject.toml` configuration, and returns a
+//! structured result in lieu of writing to the terminal. Operations must be run within a Tokio
+//! runtime.
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use uv_lib::Options;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let options = Options::new(".cache/uv").with_python("3.12");
+//!
+//! let lock = uv_lib::lock(Path::new("path/to/project"), &options).await?;
+//! for package in &lock.packages {
+//!     println!("{package}");
+//! }
+//!
+//! let sync = uv_lib::sync(Path::new("path/to/project"), &options).await?;
+//! println!("Installed {} packages", sync.installed.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use distribution_types::{FlatIndexLocation, IndexUrl};
+use uv_cache::Cache;
+use uv_configuration::Concurrency;
+use uv_resolver::ExcludeNewer;
+use uv_settings::ResolverInstallerOptions;
+
+pub use pep440_rs::Version;
+pub use uv::api::{
+    LockOutcome, Package, ResolveOutcome, SyncOutcome, ToolExecutable, ToolInstallOutcome,
+};
+pub use uv_normalize::PackageName;
+
+/// An error encountered while performing an operation.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The requirements could not be satisfied.
+    ///
+    /// The message includes an explanation of the conflict, as reported by `uv lock`.
+    #[error("{0}")]
+    NoSolution(String),
+    /// The tool does not provide any executables.
+    #[error("No executables are provided by `{0}`")]
+    NoExecutables(PackageName),
+    /// An option could not be parsed, e.g., a malformed index URL.
+    #[error("Invalid value for `{option}`: {message}")]
+    InvalidOption {
+        option: &'static str,
+        message: String,
+    },
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl From<uv::api::Error> for Error {
+    fn from(err: uv::api::Error) -> Self {
+        match err {
+            uv::api::Error::NoSolution(err) => Self::NoSolution(format!("{}\n{err}", err.header())),
+            uv::api::Error::NoExecutables(name) => Self::NoExecutables(name),
+            uv::api::Error::Other(err) => Self::Other(err),
+        }
+    }
+}
+
+/// The preference for managed or system Python installations.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PythonPreference {
+    /// Only use managed Python installations.
+    OnlyManaged,
+    /// Prefer managed Python installations over system Python installations.
+    #[default]
+    Managed,
+    /// Prefer system Python installations over managed Python installations.
+    System,
+    /// Only use system Python installations.
+    OnlySystem,
+}
+
+impl From<PythonPreference> for uv_python::PythonPreference {
+    fn from(preference: PythonPreference) -> Self {
+        match preference {
+            PythonPreference::OnlyManaged => Self::OnlyManaged,
+            PythonPreference::Managed => Self::Managed,
+            PythonPreference::System => Self::System,
+            PythonPreference::OnlySystem => Self::OnlySystem,
+        }
+    }
+}
+
+/// Whether managed Python installations may be downloaded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PythonDownloads {
+    /// Download managed Python installations when needed.
+    #[default]
+    Automatic,
+    /// Only use managed Python installations that were installed explicitly.
+    Manual,
+    /// Never download managed Python installations.
+    Never,
+}
+
+impl From<PythonDownloads> for uv_python::PythonDownloads {
+    fn from(downloads: PythonDownloads) -> Self {
+        match downloads {
+            PythonDownloads::Automatic => Self::Automatic,
+            PythonDownloads::Manual => Self::Manual,
+            PythonDownloads::Never => Self::Never,
+        }
+    }
+}
+
+/// The options that determine how packages are resolved and installed.
+///
+/// Options that are set here take precedence over those in any `uv.toml` or `pyproject.toml`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ResolverOptions {
+    index_url: Option<String>,
+    extra_index_urls: Vec<String>,
+    find_links: Vec<String>,
+    no_index: bool,
+    exclude_newer: Option<String>,
+    upgrade: bool,
+    no_build: bool,
+    no_binary: bool,
+}
+
+impl ResolverOptions {
+    /// Set the URL of the default package index (by default, <https://pypi.org/simple>).
+    #[must_use]
+    pub fn with_index_url(mut self, index_url: impl Into<String>) -> Self {
+        self.index_url = Some(index_url.into());
+        self
+    }
+
+    /// Add an extra package index, which takes precedence over the default index.
+    #[must_use]
+    pub fn with_extra_index_url(mut self, extra_index_url: impl Into<String>) -> Self {
+        self.extra_index_urls.push(extra_index_url.into());
+        self
+    }
+
+    /// Add a location to search for distributions, e.g., a directory of wheels or an HTML page
+    /// of links.
+    #[must_use]
+    pub fn with_find_links(mut self, find_links: impl Into<String>) -> Self {
+        self.find_links.push(find_links.into());
+        self
+    }
+
+    /// Ignore the package indexes, relying only on `find-links` locations.
+    #[must_use]
+    pub fn with_no_index(mut self, no_index: bool) -> Self {
+        self.no_index = no_index;
+        self
+    }
+
+    /// Limit candidate packages to those uploaded prior to the given date, as an RFC 3339
+    /// timestamp (e.g., `2006-12-02T02:07:43Z`) or a date (e.g., `2006-12-02`).
+    #[must_use]
+    pub fn with_exclude_newer(mut self, exclude_newer: impl Into<String>) -> Self {
+        self.exclude_newer = Some(exclude_newer.into());
+        self
+    }
+
+    /// Allow package upgrades, ignoring any pinned versions in an existing lockfile.
+    #[must_use]
+    pub fn with_upgrade(mut self, upgrade: bool) -> Self {
+        self.upgrade = upgrade;
+        self
+    }
+
+    /// Don't build source distributions.
+    #[must_use]
+    pub fn with_no_build(mut self, no_build: bool) -> Self {
+        self.no_build = no_build;
+        self
+    }
+
+    /// Don't install pre-built wheels.
+    #[must_use]
+    pub fn with_no_binary(mut self, no_binary: bool) -> Self {
+        self.no_binary = no_binary;
+        self
+    }
+
+    /// Convert to the options used by uv's settings, in which unset values defer to any
+    /// configuration files.
+    fn to_settings(&self) -> Result<ResolverInstallerOptions, Error> {
+        let index_url = |option: &'static str, url: &str| {
+            IndexUrl::from_str(url).map_err(|err| Error::InvalidOption {
+                option,
+                message: err.to_string(),
+            })
+        };
+        Ok(ResolverInstallerOptions {
+            index_url: self
+                .index_url
+                .as_deref()
+                .map(|url| index_url("index-url", url))
+                .transpose()?,
+            extra_index_url: (!self.extra_index_urls.is_empty())
+                .then(|| {
+                    self.extra_index_urls
+                        .iter()
+                        .map(|url| index_url("extra-index-url", url))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            find_links: (!self.find_links.is_empty())
+                .then(|| {
+                    self.find_links
+                        .iter()
+                        .map(|location| {
+                            FlatIndexLocation::from_str(location).map_err(|err| {
+                                Error::InvalidOption {
+                                    option: "find-links",
+                                    message: err.to_string(),
+                                }
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+            no_index: self.no_index.then_some(true),
+            exclude_newer: self
+                .exclude_newer
+                .as_deref()
+                .map(|exclude_newer| {
+                    ExcludeNewer::from_str(exclude_newer).map_err(|message| Error::InvalidOption {
+                        option: "exclude-newer",
+                        message,
+                    })
+                })
+                .transpose()?,
+            upgrade: self.upgrade.then_some(true),
+            no_build: self.no_build.then_some(true),
+            no_binary: self.no_binary.then_some(true),
+            ..ResolverInstallerOptions::default()
+        })
+    }
+}
+
+/// The options shared by each operation.
+#[derive(Debug, Clone)]
+pub struct Options {
+    cache_dir: PathBuf,
+    python: Option<String>,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    offline: bool,
+    native_tls: bool,
+    concurrent_downloads: Option<usize>,
+    concurrent_builds: Option<usize>,
+    concurrent_installs: Option<usize>,
+    resolver: ResolverOptions,
+    no_config: bool,
+}
+
+impl Options {
+    /// Create the [`Options`] for operations that use the cache in the given directory, which is
+    /// created if it doesn't exist.
+    pub fn new(cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            cache_dir: cache_dir.into(),
+            python: None,
+            python_preference: PythonPreference::default(),
+            python_downloads: PythonDownloads::default(),
+            offline: false,
+            native_tls: false,
+            concurrent_downloads: None,
+            concurrent_builds: None,
+            concurrent_installs: None,
+            resolver: ResolverOptions::default(),
+            no_config: false,
+        }
+    }
+
+    /// Request a Python interpreter, e.g., `3.12` or the path to a Python executable.
+    #[must_use]
+    pub fn with_python(mut self, python: impl Into<String>) -> Self {
+        self.python = Some(python.into());
+        self
+    }
+
+    /// Set the preference for managed or system Python installations.
+    #[must_use]
+    pub fn with_python_preference(mut self, python_preference: PythonPreference) -> Self {
+        self.python_preference = python_preference;
+        self
+    }
+
+    /// Set whether Python installations may be downloaded, if no suitable interpreter is found.
+    #[must_use]
+    pub fn with_python_downloads(mut self, python_downloads: PythonDownloads) -> Self {
+        self.python_downloads = python_downloads;
+        self
+    }
+
+    /// Disable network access, relying only on locally cached data and locally available files.
+    #[must_use]
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Load TLS certificates from the platform's native certificate store.
+    #[must_use]
+    pub fn with_native_tls(mut self, native_tls: bool) -> Self {
+        self.native_tls = native_tls;
+        self
+    }
+
+    /// Set the maximum number of concurrent downloads.
+    #[must_use]
+    pub fn with_concurrent_downloads(mut self, downloads: usize) -> Self {
+        self.concurrent_downloads = Some(downloads);
+        self
+    }
+
+    /// Set the maximum number of source distributions that are built concurrently.
+    #[must_use]
+    pub fn with_concurrent_builds(mut self, builds: usize) -> Self {
+        self.concurrent_builds = Some(builds);
+        self
+    }
+
+    /// Set the maximum number of threads used to install packages.
+    #[must_use]
+    pub fn with_concurrent_installs(mut self, installs: usize) -> Self {
+        self.concurrent_installs = Some(installs);
+        self
+    }
+
+    /// Set the resolver and installer options (e.g., the index URL), which take precedence over
+    /// those in any `uv.toml` or `pyproject.toml`.
+    #[must_use]
+    pub fn with_resolver_options(mut self, resolver: ResolverOptions) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Avoid discovering `uv.toml` and `pyproject.toml` configuration files.
+    #[must_use]
+    pub fn with_no_config(mut self, no_config: bool) -> Self {
+        self.no_config = no_config;
+        self
+    }
+
+    /// Convert to the options used by uv's operations, initializing the cache.
+    fn to_api(&self) -> Result<uv::api::Options, Error> {
+        let cache = Cache::from_path(&self.cache_dir)
+            .init()
+            .map_err(|err| Error::Other(err.into()))?;
+
+        let default = Concurrency::default();
+        let concurrency = Concurrency {
+            downloads: self
+                .concurrent_downloads
+                .unwrap_or(default.downloads)
+                .max(1),
+            builds: self.concurrent_builds.unwrap_or(default.builds).max(1),
+            installs: self.concurrent_installs.unwrap_or(default.installs).max(1),
+        };
+
+        let mut options = uv::api::Options::new(cache)
+            .with_python_preference(self.python_preference.into())
+            .with_python_downloads(self.python_downloads.into())
+            .with_offline(self.offline)
+            .with_native_tls(self.native_tls)
+            .with_concurrency(concurrency)
+            .with_resolver_options(self.resolver.to_settings()?)
+            .with_no_config(self.no_config);
+        if let Some(python) = &self.python {
+            options = options.with_python(python.clone());
+        }
+        Ok(options)
+    }
+}
+
+/// Resolve the given requirements (e.g., `flask>=3`) for the requested Python interpreter.
+///
+/// Like `uv pip compile`, the resolution is specific to the interpreter; nothing is installed.
+pub async fn resolve(requirements: &[String], options: &Options) -> Result<ResolveOutcome, Error> {
+    Ok(uv::api::resolve(requirements, &options.to_api()?).await?)
+}
+
+/// Lock the project at the given path, updating its `uv.lock` if necessary.
+pub async fn lock(project: &Path, options: &Options) -> Result<LockOutcome, Error> {
+    Ok(uv::api::lock(project, &options.to_api()?).await?)
+}
+
+/// Sync the environment of the project at the given path with its lockfile, locking the project
+/// first if necessary.
+///
+/// As with `uv sync`, the project's development dependencies are included, and any extraneous
+/// packages are removed from the environment.
+pub async fn sync(project: &Path, options: &Options) -> Result<SyncOutcome, Error> {
+    Ok(uv::api::sync(project, &options.to_api()?).await?)
+}
+
+/// Install the given package as a tool, with its executables available in the tools `bin`
+/// directory.
+///
+/// If provided, `from` is the requirement from which to install the tool (e.g., `ruff>=0.6`, or
+/// a URL), in lieu of the package name; `with` includes additional requirements in the tool
+/// environment. Unless `force` is set, executables that were not installed by uv are not
+/// overwritten.
+pub async fn tool_install(
+    package: &PackageName,
+    from: Option<&str>,
+    with: &[String],
+    force: bool,
+    options: &Options,
+) -> Result<ToolInstallOutcome, Error> {
+    Ok(uv::api::tool_install(package, from, with, force, &options.to_api()?).await?)
+}
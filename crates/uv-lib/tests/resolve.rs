@@ -0,0 +1,78 @@
+#![cfg(feature = "python")]
+
+use std::path::PathBuf;
+
+use uv_lib::{Error, Options, PythonDownloads, ResolverOptions};
+
+/// Return the options for resolving against the local `find-links` directory, without network
+/// access or configuration files.
+fn options(cache_dir: &std::path::Path) -> Options {
+    let links = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("scripts")
+        .join("links");
+    Options::new(cache_dir)
+        .with_python_downloads(PythonDownloads::Never)
+        .with_offline(true)
+        .with_no_config(true)
+        .with_resolver_options(
+            ResolverOptions::default()
+                .with_no_index(true)
+                .with_find_links(links.to_string_lossy()),
+        )
+}
+
+#[tokio::test]
+async fn resolve() -> anyhow::Result<()> {
+    let cache_dir = tempfile::tempdir()?;
+    let options = options(cache_dir.path());
+
+    let outcome = uv_lib::resolve(&["ok<2".to_string()], &options).await?;
+    assert_eq!(
+        outcome
+            .packages
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["ok==1.0.0"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolve_no_solution() -> anyhow::Result<()> {
+    let cache_dir = tempfile::tempdir()?;
+    let options = options(cache_dir.path());
+
+    let err = uv_lib::resolve(&["ok>2".to_string()], &options)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::NoSolution(_)), "{err}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolve_invalid_option() -> anyhow::Result<()> {
+    let cache_dir = tempfile::tempdir()?;
+    let options = options(cache_dir.path())
+        .with_resolver_options(ResolverOptions::default().with_exclude_newer("not-a-date"));
+
+    let err = uv_lib::resolve(&["ok".to_string()], &options)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            Error::InvalidOption {
+                option: "exclude-newer",
+                ..
+            }
+        ),
+        "{err}"
+    );
+
+    Ok(())
+}
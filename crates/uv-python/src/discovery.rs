@@ -793,6 +793,15 @@ pub fn find_python_installations<'a>(
             })
         }),
         PythonRequest::Key(request) => Box::new({
+            // If the request identifies a single installation (e.g., as written by
+            // `uv python pin --resolved=key`), prefer a managed installation with the exact key over
+            // any system interpreters.
+            let preference =
+                if request.is_fully_specified() && preference == PythonPreference::System {
+                    PythonPreference::Managed
+                } else {
+                    preference
+                };
             debug!("Searching for {request} in {preference}");
             python_interpreters(
                 request.version(),
@@ -1482,7 +1491,7 @@ impl VersionRequest {
     }
 
     /// Return true if a patch version is present in the request.
-    fn has_patch(&self) -> bool {
+    pub(crate) fn has_patch(&self) -> bool {
         match self {
             Self::Any => false,
             Self::Major(..) => false,
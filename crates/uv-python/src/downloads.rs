@@ -1,20 +1,20 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::io;
 use std::path::{Path, PathBuf};
-use std::pin::Pin;
 use std::str::FromStr;
-use std::task::{Context, Poll};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
 use distribution_filename::{ExtensionError, SourceDistExtension};
-use futures::TryStreamExt;
+use futures::StreamExt;
 use owo_colors::OwoColorize;
 use pypi_types::{HashAlgorithm, HashDigest};
 use thiserror::Error;
-use tokio::io::{AsyncRead, ReadBuf};
-use tokio_util::compat::FuturesAsyncReadCompatExt;
+use tokio::io::AsyncWriteExt;
 use tracing::{debug, instrument};
 use url::Url;
-use uv_cache::Cache;
+use uv_cache::{Cache, CacheBucket};
 use uv_client::WrappedReqwestError;
 use uv_extract::hash::Hasher;
 use uv_fs::{rename_with_retry, Simplified};
@@ -23,6 +23,7 @@ use crate::implementation::{
     Error as ImplementationError, ImplementationName, LenientImplementationName,
 };
 use crate::installation::PythonInstallationKey;
+use crate::managed::PythonProvenance;
 use crate::platform::{self, Arch, Libc, Os};
 use crate::{Interpreter, PythonRequest, PythonVersion, VersionRequest};
 
@@ -44,6 +45,8 @@ pub enum Error {
     NetworkMiddlewareError(#[from] anyhow::Error),
     #[error("Failed to extract archive: {0}")]
     ExtractError(String, #[source] uv_extract::Error),
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
     #[error("Failed to hash installation")]
     HashExhaustion(#[source] io::Error),
     #[error("Hash mismatch for `{installation}`\n\nExpected:\n{expected}\n\nComputed:\n{actual}")]
@@ -52,6 +55,12 @@ pub enum Error {
         expected: String,
         actual: String,
     },
+    #[error("Hash mismatch for `{installation}` against the checksum pinned in `python-install-checksums`\n\nExpected:\n{expected}\n\nComputed:\n{actual}")]
+    PinnedHashMismatch {
+        installation: String,
+        expected: String,
+        actual: String,
+    },
     #[error("Invalid download URL")]
     InvalidUrl(#[from] url::ParseError),
     #[error("Failed to create download directory")]
@@ -216,6 +225,17 @@ impl PythonDownloadRequest {
         self.libc.as_ref()
     }
 
+    /// Returns `true` if the request identifies a single installation key, i.e., if the
+    /// implementation, patch version, operating system, architecture, and libc are all specified
+    /// (e.g., `cpython-3.12.3-macos-aarch64-none`).
+    pub fn is_fully_specified(&self) -> bool {
+        self.implementation.is_some()
+            && self.arch.is_some()
+            && self.os.is_some()
+            && self.libc.is_some()
+            && self.version.as_ref().is_some_and(VersionRequest::has_patch)
+    }
+
     /// Iterate over all [`PythonDownload`]'s that match this request.
     pub fn iter_downloads(&self) -> impl Iterator<Item = &'static ManagedPythonDownload> + '_ {
         ManagedPythonDownload::iter_all()
@@ -370,6 +390,80 @@ impl FromStr for PythonDownloadRequest {
 
 include!("downloads.inc");
 
+static PINNED_CHECKSUMS: OnceLock<PinnedChecksums> = OnceLock::new();
+
+/// Whether automatic downloads may fall back to a build that runs under emulation.
+static ALLOW_EMULATED: AtomicBool = AtomicBool::new(false);
+
+/// Allow downloads performed automatically when a command requires a missing Python version (e.g.,
+/// when creating a virtual environment) to fall back to a build that runs under emulation, as
+/// configured via `python-install-allow-emulated`.
+pub fn allow_emulated() {
+    ALLOW_EMULATED.store(true, Ordering::SeqCst);
+}
+
+/// Returns `true` if automatic downloads may fall back to a build that runs under emulation.
+pub(crate) fn is_emulated_allowed() -> bool {
+    ALLOW_EMULATED.load(Ordering::SeqCst)
+}
+
+/// The SHA-256 checksums that managed Python downloads must match, keyed by installation (e.g.,
+/// `cpython-3.12.4-linux-x86_64-gnu`), as configured via `python-install-checksums`.
+///
+/// The checksums are process-wide: once installed via [`PinnedChecksums::init`], they apply to
+/// every download, whether requested via `uv python install` or performed automatically when a
+/// command requires a missing Python version.
+#[derive(Debug, Default, Clone)]
+pub struct PinnedChecksums(BTreeMap<String, String>);
+
+impl PinnedChecksums {
+    /// Create a set of [`PinnedChecksums`].
+    pub fn new(checksums: BTreeMap<String, String>) -> Self {
+        Self(checksums)
+    }
+
+    /// Install the checksums for all downloads performed by this process.
+    ///
+    /// Has no effect if the checksums were already installed.
+    pub fn init(self) {
+        let _ = PINNED_CHECKSUMS.set(self);
+    }
+
+    /// Returns the installed checksums, if any.
+    fn get() -> Option<&'static Self> {
+        PINNED_CHECKSUMS.get()
+    }
+
+    /// Verify the digest of the archive for the given installation against its pinned checksum,
+    /// if any.
+    fn verify(&self, key: &PythonInstallationKey, digest: &str) -> Result<(), Error> {
+        let Some(expected) = self.0.get(&key.to_string()) else {
+            return Ok(());
+        };
+        debug!("Verifying {key} against pinned checksum: {expected}");
+        if digest.eq_ignore_ascii_case(expected) {
+            Ok(())
+        } else {
+            Err(Error::PinnedHashMismatch {
+                installation: key.to_string(),
+                expected: expected.clone(),
+                actual: digest.to_string(),
+            })
+        }
+    }
+}
+
+/// Compute the SHA-256 digest of a previously downloaded archive.
+async fn hash_archive(archive: &Path) -> Result<String, Error> {
+    let mut hashers = vec![Hasher::from(HashAlgorithm::Sha256)];
+    let file = fs_err::tokio::File::open(archive).await?;
+    uv_extract::hash::HashReader::new(file, &mut hashers)
+        .finish()
+        .await
+        .map_err(Error::HashExhaustion)?;
+    Ok(HashDigest::from(hashers.pop().unwrap()).digest.to_string())
+}
+
 pub enum DownloadResult {
     AlreadyAvailable(PathBuf),
     Fetched(PathBuf),
@@ -386,6 +480,34 @@ impl ManagedPythonDownload {
             .ok_or(Error::NoDownloadFound(request.clone()))
     }
 
+    /// Return the first [`PythonDownload`] matching a request, falling back to a build that runs
+    /// under emulation (e.g., an x86-64 build on Windows on ARM) if there's no build for the
+    /// architecture of the running process.
+    ///
+    /// The request is filled with defaults. The fallback only applies if the request doesn't
+    /// specify an architecture.
+    pub fn from_request_or_emulated(
+        request: &PythonDownloadRequest,
+    ) -> Result<&'static ManagedPythonDownload, Error> {
+        let filled = request.clone().fill();
+        match Self::from_request(&filled) {
+            Err(Error::NoDownloadFound(_)) if request.arch().is_none() => {
+                Arch::supported_from_env()
+                    .into_iter()
+                    .skip(1)
+                    .find_map(|arch| Self::from_request(&filled.clone().with_arch(arch)).ok())
+                    .inspect(|download| {
+                        debug!(
+                            "No download found for {filled}, falling back to: {}",
+                            download.key()
+                        );
+                    })
+                    .ok_or(Error::NoDownloadFound(filled))
+            }
+            result => result,
+        }
+    }
+
     /// Iterate over all [`PythonDownload`]'s.
     pub fn iter_all() -> impl Iterator<Item = &'static ManagedPythonDownload> {
         PYTHON_DOWNLOADS.iter()
@@ -408,6 +530,9 @@ impl ManagedPythonDownload {
     }
 
     /// Download and extract
+    ///
+    /// If a checksum is pinned for the installation (see [`PinnedChecksums`]), the archive must
+    /// match it in addition to the checksum that uv knows for the download.
     #[instrument(skip(client, parent_path, cache, reporter), fields(download = % self.key()))]
     pub async fn fetch(
         &self,
@@ -427,68 +552,68 @@ impl ManagedPythonDownload {
         let filename = url.path_segments().unwrap().last().unwrap();
         let ext = SourceDistExtension::from_path(filename)
             .map_err(|err| Error::MissingExtension(url.to_string(), err))?;
-        let response = client.get(url.clone()).send().await?;
 
-        // Ensure the request was successful.
-        response.error_for_status_ref()?;
+        // Download the archive into the cache. Archives are written to a `.part` file first, so
+        // that an interrupted download can be resumed on the next attempt, and are only moved
+        // into place once the checksum has been verified.
+        let downloads = cache.bucket(CacheBucket::Python);
+        fs_err::tokio::create_dir_all(&downloads)
+            .await
+            .map_err(Error::DownloadDirError)?;
+        let archive = downloads.join(filename);
+        let digest = if archive.is_file() {
+            debug!(
+                "Using previously downloaded archive: {}",
+                archive.user_display()
+            );
+            hash_archive(&archive).await?
+        } else {
+            let partial = downloads.join(format!("{filename}.part"));
+            let digest = self
+                .download_archive(client, &url, &partial, reporter)
+                .await?;
+            rename_with_retry(&partial, &archive)
+                .await
+                .map_err(|err| Error::CopyError {
+                    to: archive.clone(),
+                    err,
+                })?;
+            digest
+        };
 
-        let size = response.content_length();
-        let progress = reporter
-            .as_ref()
-            .map(|reporter| (reporter, reporter.on_download_start(&self.key, size)));
+        // Verify the archive against the pinned checksum, if any.
+        if let Some(pinned) = PinnedChecksums::get() {
+            if let Err(err) = pinned.verify(self.key(), &digest) {
+                fs_err::tokio::remove_file(&archive).await?;
+                return Err(err);
+            }
+        }
 
-        // Download and extract into a temporary directory.
+        // Extract into a temporary directory.
         let temp_dir = tempfile::tempdir_in(cache.root()).map_err(Error::DownloadDirError)?;
 
         debug!(
-            "Downloading {url} to temporary location: {}",
+            "Extracting {filename} to temporary location: {}",
             temp_dir.path().simplified().display()
         );
 
-        let stream = response
-            .bytes_stream()
-            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
-            .into_async_read();
-
-        let mut hashers = self
-            .sha256
-            .into_iter()
-            .map(|_| Hasher::from(HashAlgorithm::Sha256))
-            .collect::<Vec<_>>();
-        let mut hasher = uv_extract::hash::HashReader::new(stream.compat(), &mut hashers);
-
-        debug!("Extracting {filename}");
-
-        match progress {
-            Some((&reporter, progress)) => {
-                let mut reader = ProgressReader::new(&mut hasher, progress, reporter);
-                uv_extract::stream::archive(&mut reader, ext, temp_dir.path())
-                    .await
+        match ext {
+            // Zip archives are extracted in parallel, now that the archive is fully on disk.
+            SourceDistExtension::Zip => {
+                let file = fs_err::File::open(&archive)?;
+                let target = temp_dir.path().to_path_buf();
+                tokio::task::spawn_blocking(move || uv_extract::unzip(file, &target))
+                    .await?
                     .map_err(|err| Error::ExtractError(filename.to_string(), err))?;
             }
-            None => {
-                uv_extract::stream::archive(&mut hasher, ext, temp_dir.path())
+            // Tarballs are decompressed as a single stream, but their files are written in
+            // parallel.
+            _ => {
+                let file = fs_err::tokio::File::open(&archive).await?;
+                uv_extract::stream::untar_parallel(file, ext, temp_dir.path())
                     .await
                     .map_err(|err| Error::ExtractError(filename.to_string(), err))?;
             }
-        };
-
-        hasher.finish().await.map_err(Error::HashExhaustion)?;
-
-        if let Some((&reporter, progress)) = progress {
-            reporter.on_progress(&self.key, progress);
-        }
-
-        // Check the hash
-        if let Some(expected) = self.sha256 {
-            let actual = HashDigest::from(hashers.pop().unwrap()).digest;
-            if !actual.eq_ignore_ascii_case(expected) {
-                return Err(Error::HashMismatch {
-                    installation: self.key.to_string(),
-                    expected: expected.to_string(),
-                    actual: actual.to_string(),
-                });
-            }
         }
 
         // Extract the top-level directory.
@@ -527,9 +652,130 @@ impl ManagedPythonDownload {
                 err,
             })?;
 
+        // Record where the installation came from.
+        PythonProvenance {
+            url: url.to_string(),
+            sha256: digest,
+        }
+        .write(&path)?;
+
+        // The archive is no longer needed once the installation is in place.
+        if let Err(err) = fs_err::tokio::remove_file(&archive).await {
+            debug!("Failed to remove downloaded archive: {err}");
+        }
+
         Ok(DownloadResult::Fetched(path))
     }
 
+    /// Download the archive to the given `.part` file, verifying its checksum as it's written.
+    ///
+    /// Returns the SHA-256 digest of the archive.
+    ///
+    /// If the file already exists (e.g., from an interrupted download), the download is resumed
+    /// via an HTTP range request. If the server doesn't support range requests, the download is
+    /// restarted from scratch.
+    async fn download_archive(
+        &self,
+        client: &uv_client::BaseClient,
+        url: &Url,
+        partial: &Path,
+        reporter: Option<&dyn Reporter>,
+    ) -> Result<String, Error> {
+        let offset = match fs_err::tokio::metadata(partial).await {
+            Ok(metadata) => metadata.len(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => 0,
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut response = if offset > 0 {
+            debug!("Resuming download of {url} from byte {offset}");
+            client
+                .get(url.clone())
+                .header(reqwest::header::RANGE, format!("bytes={offset}-"))
+                .send()
+                .await?
+        } else {
+            client.get(url.clone()).send().await?
+        };
+
+        // If the partial download can't be resumed (e.g., it's already the full length, or the
+        // server's copy changed), start over.
+        if response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            debug!("Server rejected range request for {url}; restarting download");
+            response = client.get(url.clone()).send().await?;
+        }
+
+        // Ensure the request was successful.
+        response.error_for_status_ref()?;
+
+        let resumed = offset > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+
+        let mut hashers = vec![Hasher::from(HashAlgorithm::Sha256)];
+
+        let mut file = if resumed {
+            // Seed the hashers with the bytes that were already downloaded.
+            let existing = fs_err::tokio::File::open(partial).await?;
+            uv_extract::hash::HashReader::new(existing, &mut hashers)
+                .finish()
+                .await
+                .map_err(Error::HashExhaustion)?;
+            fs_err::tokio::OpenOptions::new()
+                .append(true)
+                .open(partial)
+                .await?
+        } else {
+            fs_err::tokio::File::create(partial).await?
+        };
+
+        let size = response
+            .content_length()
+            .map(|length| if resumed { length + offset } else { length });
+        let progress = reporter
+            .as_ref()
+            .map(|reporter| (reporter, reporter.on_download_start(&self.key, size)));
+        if let Some((&reporter, progress)) = progress {
+            if resumed {
+                reporter.on_download_progress(progress, offset);
+            }
+        }
+
+        debug!("Downloading {url} to: {}", partial.simplified_display());
+
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            for hasher in &mut hashers {
+                hasher.update(&chunk);
+            }
+            file.write_all(&chunk).await?;
+            if let Some((&reporter, progress)) = progress {
+                reporter.on_download_progress(progress, chunk.len() as u64);
+            }
+        }
+        file.flush().await?;
+        drop(file);
+
+        if let Some((&reporter, progress)) = progress {
+            reporter.on_progress(&self.key, progress);
+        }
+
+        // Check the hash. If it doesn't match, discard the download, since a resumed attempt
+        // would fail in the same way.
+        let actual = HashDigest::from(hashers.pop().unwrap()).digest.to_string();
+        if let Some(expected) = self.sha256 {
+            if !actual.eq_ignore_ascii_case(expected) {
+                fs_err::tokio::remove_file(partial).await?;
+                return Err(Error::HashMismatch {
+                    installation: self.key.to_string(),
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+        }
+
+        Ok(actual)
+    }
+
     pub fn python_version(&self) -> PythonVersion {
         self.key.version()
     }
@@ -600,38 +846,203 @@ pub trait Reporter: Send + Sync {
     fn on_download_complete(&self);
 }
 
-/// An asynchronous reader that reports progress as bytes are read.
-struct ProgressReader<'a, R> {
-    reader: R,
-    index: usize,
-    reporter: &'a dyn Reporter,
-}
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::future;
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
 
-impl<'a, R> ProgressReader<'a, R> {
-    /// Create a new [`ProgressReader`] that wraps another reader.
-    fn new(reader: R, index: usize, reporter: &'a dyn Reporter) -> Self {
-        Self {
-            reader,
-            index,
-            reporter,
+    use http_body_util::Full;
+    use hyper::body::{Bytes, Incoming};
+    use hyper::header::{CONTENT_RANGE, RANGE};
+    use hyper::server::conn::http1;
+    use hyper::service::service_fn;
+    use hyper::{Request, Response, StatusCode};
+    use hyper_util::rt::TokioIo;
+    use tokio::net::TcpListener;
+    use url::Url;
+
+    use crate::installation::PythonInstallationKey;
+    use crate::platform::Arch;
+
+    use super::{Error, ManagedPythonDownload, PinnedChecksums, PythonDownloadRequest};
+
+    const DIGEST: &str = "a3a05a0bc9a8e3f9f1c7b6b4d0f5c6b4a7c1d5e0f7c0cbe0a2f3c3c6c8b6a1d2";
+
+    /// The contents of the archive served by [`serve`].
+    const ARCHIVE: &[u8] = b"hello world";
+
+    /// The SHA-256 digest of [`ARCHIVE`].
+    const ARCHIVE_DIGEST: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    /// Serve [`ARCHIVE`] on a local port, honoring range requests if `ranges` is set.
+    ///
+    /// Returns the URL of the archive, along with the `Range` headers of the requests received.
+    async fn serve(ranges: bool) -> anyhow::Result<(Url, Arc<Mutex<Vec<String>>>)> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let requested = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = requested.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let seen = seen.clone();
+                let svc = service_fn(move |req: Request<Incoming>| {
+                    let range = req
+                        .headers()
+                        .get(RANGE)
+                        .and_then(|value| value.to_str().ok())
+                        .map(ToString::to_string);
+                    let offset = range
+                        .as_deref()
+                        .and_then(|range| range.strip_prefix("bytes="))
+                        .and_then(|range| range.strip_suffix('-'))
+                        .and_then(|offset| offset.parse::<usize>().ok())
+                        .filter(|_| ranges);
+                    seen.lock().unwrap().extend(range);
+
+                    let response = match offset {
+                        Some(offset) => Response::builder()
+                            .status(StatusCode::PARTIAL_CONTENT)
+                            .header(
+                                CONTENT_RANGE,
+                                format!("bytes {offset}-{}/{}", ARCHIVE.len() - 1, ARCHIVE.len()),
+                            )
+                            .body(Full::new(Bytes::from_static(&ARCHIVE[offset..]))),
+                        None => Response::builder().body(Full::new(Bytes::from_static(ARCHIVE))),
+                    };
+                    future::ready(response)
+                });
+                tokio::spawn(http1::Builder::new().serve_connection(TokioIo::new(socket), svc));
+            }
+        });
+
+        Ok((
+            Url::parse(&format!("http://{addr}/archive.tar.gz"))?,
+            requested,
+        ))
+    }
+
+    fn download() -> ManagedPythonDownload {
+        ManagedPythonDownload {
+            key: PythonInstallationKey::from_str("cpython-3.12.4-linux-x86_64-gnu").unwrap(),
+            url: "",
+            sha256: Some(ARCHIVE_DIGEST),
         }
     }
-}
 
-impl<R> AsyncRead for ProgressReader<'_, R>
-where
-    R: AsyncRead + Unpin,
-{
-    fn poll_read(
-        mut self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &mut ReadBuf<'_>,
-    ) -> Poll<io::Result<()>> {
-        Pin::new(&mut self.as_mut().reader)
-            .poll_read(cx, buf)
-            .map_ok(|()| {
-                self.reporter
-                    .on_download_progress(self.index, buf.filled().len() as u64);
-            })
+    fn pinned() -> PinnedChecksums {
+        PinnedChecksums::new(BTreeMap::from([(
+            "cpython-3.12.4-linux-x86_64-gnu".to_string(),
+            DIGEST.to_string(),
+        )]))
+    }
+
+    #[test]
+    fn pinned_checksum_match() {
+        let key = PythonInstallationKey::from_str("cpython-3.12.4-linux-x86_64-gnu").unwrap();
+        assert!(pinned().verify(&key, DIGEST).is_ok());
+        assert!(pinned().verify(&key, &DIGEST.to_uppercase()).is_ok());
+    }
+
+    #[test]
+    fn pinned_checksum_mismatch() {
+        let key = PythonInstallationKey::from_str("cpython-3.12.4-linux-x86_64-gnu").unwrap();
+        let digest = "0".repeat(64);
+        assert!(matches!(
+            pinned().verify(&key, &digest),
+            Err(Error::PinnedHashMismatch { expected, actual, .. })
+                if expected == DIGEST && actual == digest
+        ));
+    }
+
+    #[test]
+    fn pinned_checksum_missing() {
+        // Installations without a pinned checksum are only verified against the known checksum.
+        let key = PythonInstallationKey::from_str("cpython-3.11.9-linux-x86_64-gnu").unwrap();
+        assert!(pinned().verify(&key, &"0".repeat(64)).is_ok());
+    }
+
+    #[test]
+    fn emulated_prefers_current_arch() {
+        // If there's a build for the architecture of the running process, it's always used.
+        let request = PythonDownloadRequest::from_str("cpython-3.12").unwrap();
+        let native = ManagedPythonDownload::from_request(&request.clone().fill()).unwrap();
+        let download = ManagedPythonDownload::from_request_or_emulated(&request).unwrap();
+        assert_eq!(download.key(), native.key());
+        assert_eq!(*download.key().arch(), Arch::from_env());
+    }
+
+    #[test]
+    fn emulated_explicit_arch() {
+        // Requests that specify an architecture are never substituted.
+        let request =
+            PythonDownloadRequest::from_str("cpython-3.12.4-windows-powerpc64le-none").unwrap();
+        assert!(matches!(
+            ManagedPythonDownload::from_request_or_emulated(&request),
+            Err(Error::NoDownloadFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn download_resume() -> anyhow::Result<()> {
+        let (url, requested) = serve(true).await?;
+        let temp_dir = tempfile::tempdir()?;
+        let partial = temp_dir.path().join("archive.tar.gz.part");
+        fs_err::write(&partial, &ARCHIVE[..6])?;
+
+        let client = uv_client::BaseClientBuilder::new().build();
+        let digest = download()
+            .download_archive(&client, &url, &partial, None)
+            .await?;
+
+        // The remaining bytes are appended to the partial download, and the digest covers both.
+        assert_eq!(*requested.lock().unwrap(), ["bytes=6-"]);
+        assert_eq!(fs_err::read(&partial)?, ARCHIVE);
+        assert_eq!(digest, ARCHIVE_DIGEST);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn download_resume_unsupported() -> anyhow::Result<()> {
+        let (url, requested) = serve(false).await?;
+        let temp_dir = tempfile::tempdir()?;
+        let partial = temp_dir.path().join("archive.tar.gz.part");
+        fs_err::write(&partial, &ARCHIVE[..6])?;
+
+        let client = uv_client::BaseClientBuilder::new().build();
+        let digest = download()
+            .download_archive(&client, &url, &partial, None)
+            .await?;
+
+        // The server ignores the range and returns the full archive, which replaces the partial
+        // download.
+        assert_eq!(*requested.lock().unwrap(), ["bytes=6-"]);
+        assert_eq!(fs_err::read(&partial)?, ARCHIVE);
+        assert_eq!(digest, ARCHIVE_DIGEST);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn download_resume_mismatch() -> anyhow::Result<()> {
+        let (url, _) = serve(true).await?;
+        let temp_dir = tempfile::tempdir()?;
+        let partial = temp_dir.path().join("archive.tar.gz.part");
+        fs_err::write(&partial, b"jello ")?;
+
+        let client = uv_client::BaseClientBuilder::new().build();
+        let result = download()
+            .download_archive(&client, &url, &partial, None)
+            .await;
+
+        // The existing bytes are included in the digest, so a corrupt partial download is
+        // detected and discarded.
+        assert!(matches!(result, Err(Error::HashMismatch { .. })));
+        assert!(!partial.exists());
+
+        Ok(())
     }
 }
@@ -3,6 +3,7 @@ use std::env;
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 use uv_cache::Cache;
 use uv_fs::{LockedFile, Simplified};
@@ -208,6 +209,49 @@ impl PythonEnvironment {
         }
     }
 
+    /// Grab a file lock for the environment to prevent concurrent writes across processes, giving
+    /// up if the lock can't be acquired within the given timeout.
+    ///
+    /// The lockfile records the ID of the process holding the lock, for reporting on contention.
+    pub async fn lock_with_timeout(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<LockedFile, std::io::Error> {
+        if let Some(target) = self.0.interpreter.target() {
+            // If we're installing into a `--target`, use a target-specific lockfile.
+            LockedFile::acquire_with_timeout(
+                target.root().join(".lock"),
+                target.root().user_display(),
+                timeout,
+            )
+            .await
+        } else if let Some(prefix) = self.0.interpreter.prefix() {
+            // Likewise, if we're installing into a `--prefix`, use a prefix-specific lockfile.
+            LockedFile::acquire_with_timeout(
+                prefix.root().join(".lock"),
+                prefix.root().user_display(),
+                timeout,
+            )
+            .await
+        } else if self.0.interpreter.is_virtualenv() {
+            // If the environment a virtualenv, use a virtualenv-specific lockfile.
+            LockedFile::acquire_with_timeout(
+                self.0.root.join(".lock"),
+                self.0.root.user_display(),
+                timeout,
+            )
+            .await
+        } else {
+            // Otherwise, use a global lockfile.
+            LockedFile::acquire_with_timeout(
+                env::temp_dir().join(format!("uv-{}.lock", cache_key::cache_digest(&self.0.root))),
+                self.0.root.user_display(),
+                timeout,
+            )
+            .await
+        }
+    }
+
     /// Return the [`Interpreter`] for this environment.
     ///
     /// See also [`PythonEnvironment::interpreter`].
@@ -99,7 +99,7 @@ impl PythonInstallation {
             {
                 if let Some(request) = PythonDownloadRequest::from_request(&request) {
                     debug!("Requested Python not found, checking for available download...");
-                    match Self::fetch(request.fill(), client_builder, cache, reporter).await {
+                    match Self::fetch(request, client_builder, cache, reporter).await {
                         Ok(installation) => Ok(installation),
                         Err(Error::Download(downloads::Error::NoDownloadFound(_))) => {
                             Err(Error::MissingPython(err))
@@ -115,6 +115,9 @@ impl PythonInstallation {
     }
 
     /// Download and install the requested installation.
+    ///
+    /// The request is filled with defaults. If enabled via [`downloads::allow_emulated`], a build
+    /// that runs under emulation is used if there's no build for the current architecture.
     pub async fn fetch<'a>(
         request: PythonDownloadRequest,
         client_builder: &BaseClientBuilder<'a>,
@@ -125,7 +128,11 @@ impl PythonInstallation {
         let installations_dir = installations.root();
         let _lock = installations.acquire_lock()?;
 
-        let download = ManagedPythonDownload::from_request(&request)?;
+        let download = if downloads::is_emulated_allowed() {
+            ManagedPythonDownload::from_request_or_emulated(&request)?
+        } else {
+            ManagedPythonDownload::from_request(&request.fill())?
+        };
         let client = client_builder.build();
 
         info!("Fetching requested Python...");
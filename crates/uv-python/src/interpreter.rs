@@ -16,7 +16,7 @@ use install_wheel_rs::Layout;
 use pep440_rs::Version;
 use pep508_rs::{MarkerEnvironment, StringVersion};
 use platform_tags::Platform;
-use platform_tags::{Tags, TagsError};
+use platform_tags::{CpuFeatures, Tags, TagsError};
 use pypi_types::Scheme;
 use uv_cache::{Cache, CacheBucket, CachedByTimestamp, Freshness, Timestamp};
 use uv_fs::{write_atomic_sync, PythonExt, Simplified};
@@ -177,7 +177,8 @@ impl Interpreter {
                 self.implementation_name(),
                 self.implementation_tuple(),
                 self.gil_disabled,
-            )?;
+            )?
+            .with_cpu_features(CpuFeatures::host());
             self.tags.set(tags).expect("tags should not be set");
         }
         Ok(self.tags.get().expect("tags should be set"))
@@ -403,6 +404,7 @@ impl Interpreter {
             python_version: self.python_tuple(),
             sys_executable: self.sys_executable().to_path_buf(),
             os_name: self.markers.os_name().to_string(),
+            arch: self.platform().arch(),
             scheme: if let Some(target) = self.target.as_ref() {
                 target.scheme()
             } else if let Some(prefix) = self.prefix.as_ref() {
@@ -699,9 +701,18 @@ impl InterpreterInfo {
                 }
             })?;
 
+        // If the interpreter cache is disabled, always run the Python script.
+        if !cache.interpreter_cache() {
+            trace!(
+                "Querying interpreter executable at {} (interpreter cache disabled)",
+                executable.display()
+            );
+            return Self::query(executable, cache);
+        }
+
         // Read from the cache.
         if cache
-            .freshness(&cache_entry, None)
+            .interpreter_freshness(&cache_entry)
             .is_ok_and(Freshness::is_fresh)
         {
             if let Ok(data) = fs::read(cache_entry.path()) {
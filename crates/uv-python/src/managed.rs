@@ -7,9 +7,10 @@ use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use thiserror::Error;
-use tracing::warn;
+use tracing::{debug, warn};
 
 use uv_state::{StateBucket, StateStore};
+use uv_warnings::warn_user;
 
 use crate::downloads::Error as DownloadError;
 use crate::implementation::{
@@ -52,6 +53,8 @@ pub enum Error {
     NameError(String),
     #[error(transparent)]
     NameParseError(#[from] installation::PythonInstallationKeyError),
+    #[error("Python installation already contains a `sitecustomize` module: {0}", path.user_display())]
+    SitecustomizeExists { path: PathBuf },
 }
 /// A collection of uv-managed Python installations installed on the current system.
 #[derive(Debug, Clone)]
@@ -179,20 +182,29 @@ impl ManagedPythonInstallations {
     }
 
     /// Iterate over Python installations that support the current platform.
+    ///
+    /// Installations for an architecture that can run on this machine under emulation (or, for an
+    /// emulated process, natively) are included, following those for the architecture of the
+    /// running process.
     pub fn find_matching_current_platform(
         &self,
     ) -> Result<impl DoubleEndedIterator<Item = ManagedPythonInstallation>, Error> {
-        let platform_key = platform_key_from_env();
+        let os = Os::from_env();
+        let libc = Libc::from_env();
+        let arches = Arch::supported_from_env();
 
         let iter = ManagedPythonInstallations::from_settings()?
             .find_all()?
-            .filter(move |installation| {
-                installation
-                    .path
-                    .file_name()
-                    .map(OsStr::to_string_lossy)
-                    .is_some_and(|filename| filename.ends_with(&platform_key))
-            });
+            .filter_map(move |installation| {
+                let key = installation.key();
+                if *key.os() != os || *key.libc() != libc {
+                    return None;
+                }
+                let rank = arches.iter().position(|arch| arch == key.arch())?;
+                Some((rank, installation))
+            })
+            .sorted_by_key(|(rank, _)| *rank)
+            .map(|(_, installation)| installation);
 
         Ok(iter)
     }
@@ -227,6 +239,82 @@ static EXTERNALLY_MANAGED: &str = "[externally-managed]
 Error=This Python installation is managed by uv and should not be modified.
 ";
 
+/// The name of the file in which the provenance of a managed Python installation is recorded.
+static PROVENANCE: &str = "uv-provenance.json";
+
+/// The name of the file in which the post-install hooks applied to a managed Python installation
+/// are recorded.
+static HOOKS: &str = "uv-hooks.json";
+
+/// The name to which the `ensurepip` module is moved when it's disabled.
+static ENSUREPIP_DISABLED: &str = "ensurepip.uv-disabled";
+
+/// The `sitecustomize` snippet that enables `PYTHONSAFEPATH` by default.
+static SAFE_PATH: &str = "# Enable `PYTHONSAFEPATH` by default (`python-install-safe-path`).
+import os
+
+os.environ.setdefault(\"PYTHONSAFEPATH\", \"1\")
+del os
+";
+
+/// Post-install steps applied to a managed Python installation, e.g., to standardize interpreter
+/// behavior across an organization.
+///
+/// The applied hooks are recorded in the installation, such that they can be reverted when the
+/// hooks change or the installation is uninstalled.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PythonInstallHooks {
+    /// Remove the `ensurepip` module, such that `pip` can't be bootstrapped into the installation
+    /// (or into virtual environments created with `python -m venv`).
+    #[serde(default)]
+    pub disable_ensurepip: bool,
+    /// The contents of a `sitecustomize` module to install into the `site-packages` directory.
+    #[serde(default)]
+    pub sitecustomize: Option<String>,
+    /// Set `PYTHONSAFEPATH` by default, via the `sitecustomize` module.
+    ///
+    /// Since `sitecustomize` is imported during interpreter startup, the variable applies to the
+    /// Python processes spawned by the interpreter (e.g., via `subprocess`), rather than the
+    /// interpreter itself.
+    #[serde(default)]
+    pub safe_path: bool,
+}
+
+impl PythonInstallHooks {
+    /// Returns `true` if no hooks are enabled.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns the contents of the `sitecustomize` module to install, if any.
+    fn sitecustomize(&self) -> Option<String> {
+        match (self.safe_path, self.sitecustomize.as_deref()) {
+            (false, None) => None,
+            (false, Some(contents)) => Some(contents.to_string()),
+            (true, None) => Some(SAFE_PATH.to_string()),
+            (true, Some(contents)) => Some(format!("{SAFE_PATH}\n{contents}")),
+        }
+    }
+}
+
+/// The provenance of a managed Python installation, recorded when it's downloaded.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PythonProvenance {
+    /// The URL from which the Python distribution was downloaded.
+    pub url: String,
+    /// The SHA-256 digest of the downloaded archive.
+    pub sha256: String,
+}
+
+impl PythonProvenance {
+    /// Record the provenance in the installation at the given path.
+    pub(crate) fn write(&self, installation: &Path) -> Result<(), io::Error> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs_err::write(installation.join(PROVENANCE), contents)
+    }
+}
+
 /// A uv-managed Python installation on the current system.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct ManagedPythonInstallation {
@@ -310,11 +398,34 @@ impl ManagedPythonInstallation {
         }
     }
 
-    /// Ensure the environment is marked as externally managed with the
-    /// standard `EXTERNALLY-MANAGED` file.
-    pub fn ensure_externally_managed(&self) -> Result<(), Error> {
-        // Construct the path to the `stdlib` directory.
-        let stdlib = if matches!(self.key.os, Os(target_lexicon::OperatingSystem::Windows)) {
+    /// Read the [`PythonProvenance`] recorded for the installation, if any.
+    ///
+    /// Installations created by older versions of uv don't include a provenance record.
+    pub fn provenance(&self) -> Option<PythonProvenance> {
+        let path = self.path.join(PROVENANCE);
+        let contents = match fs_err::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                warn!("Failed to read provenance of `{}`: {err}", self.key);
+                return None;
+            }
+        };
+        match serde_json::from_str(&contents) {
+            Ok(provenance) => Some(provenance),
+            Err(err) => {
+                warn!(
+                    "Ignoring malformed provenance at `{}`: {err}",
+                    path.user_display()
+                );
+                None
+            }
+        }
+    }
+
+    /// The path to the installation's standard library.
+    fn stdlib(&self) -> PathBuf {
+        if matches!(self.key.os, Os(target_lexicon::OperatingSystem::Windows)) {
             self.python_dir().join("Lib")
         } else {
             let python = if matches!(
@@ -326,23 +437,140 @@ impl ManagedPythonInstallation {
                 format!("python{}", self.key.version().python_version())
             };
             self.python_dir().join("lib").join(python)
-        };
+        }
+    }
 
-        let file = stdlib.join("EXTERNALLY-MANAGED");
+    /// Ensure the environment is marked as externally managed with the
+    /// standard `EXTERNALLY-MANAGED` file.
+    pub fn ensure_externally_managed(&self) -> Result<(), Error> {
+        let file = self.stdlib().join("EXTERNALLY-MANAGED");
         fs_err::write(file, EXTERNALLY_MANAGED)?;
 
         Ok(())
     }
-}
 
-/// Generate a platform portion of a key from the environment.
-fn platform_key_from_env() -> String {
-    let os = Os::from_env();
-    let arch = Arch::from_env();
-    let libc = Libc::from_env();
-    format!("{os}-{arch}-{libc}").to_lowercase()
+    /// Read the [`PythonInstallHooks`] recorded for the installation.
+    ///
+    /// Returns the default (empty) hooks if none were recorded.
+    pub fn hooks(&self) -> PythonInstallHooks {
+        let path = self.path.join(HOOKS);
+        let contents = match fs_err::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return PythonInstallHooks::default()
+            }
+            Err(err) => {
+                warn!("Failed to read hooks of `{}`: {err}", self.key);
+                return PythonInstallHooks::default();
+            }
+        };
+        match serde_json::from_str(&contents) {
+            Ok(hooks) => hooks,
+            Err(err) => {
+                warn!(
+                    "Ignoring malformed hooks at `{}`: {err}",
+                    path.user_display()
+                );
+                PythonInstallHooks::default()
+            }
+        }
+    }
+
+    /// Apply the given [`PythonInstallHooks`] to the installation, reverting any hooks that were
+    /// previously applied.
+    ///
+    /// Returns `true` if the installation was modified.
+    pub fn apply_hooks(&self, hooks: &PythonInstallHooks) -> Result<bool, Error> {
+        let previous = self.hooks();
+        if previous == *hooks {
+            return Ok(false);
+        }
+        self.revert_hooks(&previous)?;
+
+        let stdlib = self.stdlib();
+        let site_packages = stdlib.join("site-packages");
+        let sitecustomize = site_packages.join("sitecustomize.py");
+        if hooks.sitecustomize().is_some() && sitecustomize.exists() {
+            return Err(Error::SitecustomizeExists {
+                path: sitecustomize,
+            });
+        }
+
+        if hooks.disable_ensurepip {
+            let ensurepip = stdlib.join("ensurepip");
+            if ensurepip.is_dir() {
+                debug!("Disabling `ensurepip` for `{}`", self.key);
+                fs_err::rename(&ensurepip, stdlib.join(ENSUREPIP_DISABLED))?;
+            }
+        }
+        if let Some(contents) = hooks.sitecustomize() {
+            debug!("Installing `sitecustomize` for `{}`", self.key);
+            fs_err::create_dir_all(&site_packages)?;
+            fs_err::write(&sitecustomize, contents)?;
+        }
+
+        let record = self.path.join(HOOKS);
+        if hooks.is_empty() {
+            match fs_err::remove_file(record) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        } else {
+            let contents = serde_json::to_string_pretty(hooks).map_err(io::Error::from)?;
+            fs_err::write(record, contents)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Revert the [`PythonInstallHooks`] recorded for the installation, if any.
+    ///
+    /// Returns `true` if the installation was modified.
+    pub fn remove_hooks(&self) -> Result<bool, Error> {
+        let previous = self.hooks();
+        if previous.is_empty() {
+            return Ok(false);
+        }
+        self.revert_hooks(&previous)?;
+        fs_err::remove_file(self.path.join(HOOKS))?;
+        Ok(true)
+    }
+
+    /// Revert the given [`PythonInstallHooks`], without updating the record.
+    fn revert_hooks(&self, hooks: &PythonInstallHooks) -> Result<(), Error> {
+        let stdlib = self.stdlib();
+        if hooks.disable_ensurepip {
+            let disabled = stdlib.join(ENSUREPIP_DISABLED);
+            if disabled.is_dir() {
+                debug!("Restoring `ensurepip` for `{}`", self.key);
+                fs_err::rename(&disabled, stdlib.join("ensurepip"))?;
+            }
+        }
+        if let Some(expected) = hooks.sitecustomize() {
+            // Only remove the `sitecustomize` module if it's the one we installed, to avoid
+            // deleting a module that was since replaced by the user.
+            let path = stdlib.join("site-packages").join("sitecustomize.py");
+            match fs_err::read_to_string(&path) {
+                Ok(contents) if contents == expected => {
+                    fs_err::remove_file(&path)?;
+                    debug!("Removed `sitecustomize` for `{}`", self.key);
+                }
+                Ok(_) => {
+                    warn_user!(
+                        "Leaving `{}` in place, since it was modified after it was installed by uv",
+                        path.user_display()
+                    );
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
 }
 
+/// Generate a platform portion of a key from the environment.
 impl fmt::Display for ManagedPythonInstallation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -355,3 +583,62 @@ impl fmt::Display for ManagedPythonInstallation {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_hooks() -> Result<(), Error> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("cpython-3.12.4-linux-x86_64-gnu");
+        let stdlib = path.join("install").join("lib").join("python3.12");
+        fs_err::create_dir_all(stdlib.join("ensurepip"))?;
+        fs_err::create_dir_all(stdlib.join("site-packages"))?;
+        let installation = ManagedPythonInstallation::new(path)?;
+        let sitecustomize = stdlib.join("site-packages").join("sitecustomize.py");
+
+        let hooks = PythonInstallHooks {
+            disable_ensurepip: true,
+            sitecustomize: Some("import sys\n".to_string()),
+            safe_path: true,
+        };
+        assert!(installation.apply_hooks(&hooks)?);
+        assert!(!installation.apply_hooks(&hooks)?);
+        assert_eq!(installation.hooks(), hooks);
+        assert!(!stdlib.join("ensurepip").exists());
+        let contents = fs_err::read_to_string(&sitecustomize)?;
+        assert!(contents.contains("PYTHONSAFEPATH"));
+        assert!(contents.ends_with("import sys\n"));
+
+        // Changing the hooks reverts those that no longer apply.
+        let hooks = PythonInstallHooks {
+            safe_path: true,
+            ..PythonInstallHooks::default()
+        };
+        assert!(installation.apply_hooks(&hooks)?);
+        assert!(stdlib.join("ensurepip").is_dir());
+        assert!(!fs_err::read_to_string(&sitecustomize)?.contains("import sys"));
+
+        assert!(installation.remove_hooks()?);
+        assert!(!sitecustomize.exists());
+        assert!(installation.hooks().is_empty());
+
+        // An existing `sitecustomize` module is never overwritten.
+        fs_err::write(&sitecustomize, "")?;
+        assert!(matches!(
+            installation.apply_hooks(&hooks),
+            Err(Error::SitecustomizeExists { .. })
+        ));
+
+        // Nor is it removed if it was replaced after it was installed.
+        fs_err::remove_file(&sitecustomize)?;
+        assert!(installation.apply_hooks(&hooks)?);
+        fs_err::write(&sitecustomize, "import site\n")?;
+        assert!(installation.remove_hooks()?);
+        assert_eq!(fs_err::read_to_string(&sitecustomize)?, "import site\n");
+        assert!(installation.hooks().is_empty());
+
+        Ok(())
+    }
+}
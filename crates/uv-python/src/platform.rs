@@ -1,6 +1,9 @@
 use std::fmt::Display;
 use std::ops::Deref;
+use std::sync::OnceLock;
 use std::{fmt, str::FromStr};
+
+use itertools::Itertools;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -59,9 +62,96 @@ impl Os {
 }
 
 impl Arch {
+    /// The architecture of the running process.
     pub fn from_env() -> Self {
         Self(target_lexicon::HOST.architecture)
     }
+
+    /// Return the architectures that can run on this machine, in order of preference.
+    ///
+    /// The architecture of the running process is always preferred. Windows on ARM can also run
+    /// x86-64 and x86 executables under emulation, and Apple Silicon can run x86-64 executables
+    /// under Rosetta 2. Conversely, a process that is itself emulated (e.g., in a Rosetta shell)
+    /// can run native ARM64 executables.
+    pub fn supported_from_env() -> Vec<Self> {
+        let aarch64 = Self(target_lexicon::Architecture::Aarch64(
+            target_lexicon::Aarch64Architecture::Aarch64,
+        ));
+        let x86_64 = Self(target_lexicon::Architecture::X86_64);
+        let x86 = Self(target_lexicon::Architecture::X86_32(
+            target_lexicon::X86_32Architecture::I686,
+        ));
+
+        let mut supported = vec![Self::from_env()];
+        if is_arm64_machine() {
+            match *Os::from_env() {
+                target_lexicon::OperatingSystem::Windows => {
+                    supported.extend([aarch64, x86_64, x86]);
+                }
+                target_lexicon::OperatingSystem::Darwin => {
+                    supported.extend([aarch64, x86_64]);
+                }
+                _ => {}
+            }
+        }
+        supported.into_iter().unique().collect()
+    }
+}
+
+/// Returns `true` if the machine has an ARM64 processor, even if the running process is emulated.
+fn is_arm64_machine() -> bool {
+    static ARM64: OnceLock<bool> = OnceLock::new();
+    *ARM64.get_or_init(|| {
+        if matches!(
+            target_lexicon::HOST.architecture,
+            target_lexicon::Architecture::Aarch64(_)
+        ) {
+            return true;
+        }
+
+        #[cfg(windows)]
+        {
+            use windows_sys::Win32::System::SystemInformation::IMAGE_FILE_MACHINE_ARM64;
+            use windows_sys::Win32::System::Threading::{GetCurrentProcess, IsWow64Process2};
+
+            let mut process_machine = 0;
+            let mut native_machine = 0;
+            // SAFETY: The pseudo-handle returned by `GetCurrentProcess` is always valid, and
+            // the out-pointers refer to initialized locals.
+            let success = unsafe {
+                IsWow64Process2(
+                    GetCurrentProcess(),
+                    &mut process_machine,
+                    &mut native_machine,
+                )
+            };
+            success != 0 && native_machine == IMAGE_FILE_MACHINE_ARM64
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            // Under Rosetta 2, the kernel still reports ARM64 support.
+            let mut value: libc::c_int = 0;
+            let mut size = std::mem::size_of::<libc::c_int>();
+            // SAFETY: The name is a valid C string, and `value` is large enough to hold the
+            // result, as described by `size`.
+            let result = unsafe {
+                libc::sysctlbyname(
+                    c"hw.optional.arm64".as_ptr(),
+                    std::ptr::addr_of_mut!(value).cast(),
+                    &mut size,
+                    std::ptr::null_mut(),
+                    0,
+                )
+            };
+            result == 0 && value == 1
+        }
+
+        #[cfg(not(any(windows, target_os = "macos")))]
+        {
+            false
+        }
+    })
 }
 
 impl Display for Libc {
@@ -193,3 +283,18 @@ impl From<&platform_tags::Os> for Os {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::Arch;
+
+    #[test]
+    fn supported_from_env() {
+        // The architecture of the running process is always preferred, and never repeated.
+        let supported = Arch::supported_from_env();
+        assert_eq!(supported.first(), Some(&Arch::from_env()));
+        assert!(supported.iter().all_unique());
+    }
+}
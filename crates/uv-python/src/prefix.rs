@@ -24,6 +24,33 @@ impl Prefix {
         std::iter::once(self.0.join(&virtualenv.purelib))
     }
 
+    /// Return the Python versions (e.g., `3.12`) for which the `--prefix` directory already
+    /// contains a `lib/pythonX.Y/site-packages` directory, in sorted order.
+    ///
+    /// On Windows, `site-packages` isn't versioned, so no versions are detected.
+    pub fn site_packages_versions(&self) -> Vec<String> {
+        let Ok(entries) = fs_err::read_dir(self.0.join("lib")) else {
+            return Vec::new();
+        };
+        let mut versions = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let version = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("python")?
+                    .to_string();
+                entry
+                    .path()
+                    .join("site-packages")
+                    .is_dir()
+                    .then_some(version)
+            })
+            .collect::<Vec<_>>();
+        versions.sort();
+        versions
+    }
+
     /// Initialize the `--prefix` directory.
     pub fn init(&self, virtualenv: &Scheme) -> std::io::Result<()> {
         for site_packages in self.site_packages(virtualenv) {
@@ -0,0 +1,238 @@
+//! Working copies of editable Git requirements (e.g., `-e git+https://...`).
+//!
+//! Like pip, each requirement is cloned into a directory (by default, `src` within the target
+//! environment) and then installed from that directory in editable mode. The checkouts are
+//! regular clones, so they can be modified and committed to directly; they're only updated when
+//! the requirement is upgraded (e.g., with `--upgrade`).
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use cache_key::RepositoryUrl;
+use distribution_types::{UnresolvedRequirement, UnresolvedRequirementSpecification};
+use pep508_rs::{UnnamedRequirement, UnnamedRequirementUrl, VerbatimUrl};
+use pypi_types::{ParsedGitUrl, ParsedUrl, Requirement, RequirementSource, VerbatimParsedUrl};
+use uv_cache::{Cache, CacheBucket};
+use uv_client::BaseClient;
+use uv_configuration::Upgrade;
+use uv_fs::Simplified;
+use uv_git::{GitSource, GitUrl};
+use uv_normalize::PackageName;
+
+/// The name of the receipt written to the `.git` directory of each checkout.
+const RECEIPT: &str = "uv-checkout.toml";
+
+/// A receipt recording the repository and revision from which a checkout was created.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CheckoutReceipt {
+    /// The URL of the remote repository.
+    repository: String,
+    /// The requested reference (e.g., a branch or tag), if any.
+    reference: Option<String>,
+    /// The commit that was last checked out.
+    commit: String,
+}
+
+/// Manages the working copies of editable Git requirements within a `src` directory.
+pub struct EditableCheckouts<'a> {
+    /// The directory in which to create the checkouts.
+    src: PathBuf,
+    /// The upgrade strategy, which determines whether existing checkouts are updated.
+    upgrade: &'a Upgrade,
+    client: &'a BaseClient,
+    cache: &'a Cache,
+}
+
+impl<'a> EditableCheckouts<'a> {
+    /// Initialize the checkouts within the given `src` directory.
+    pub fn new(
+        src: impl Into<PathBuf>,
+        upgrade: &'a Upgrade,
+        client: &'a BaseClient,
+        cache: &'a Cache,
+    ) -> Self {
+        Self {
+            src: src.into(),
+            upgrade,
+            client,
+            cache,
+        }
+    }
+
+    /// Check out each editable Git requirement, returning the equivalent editable requirements
+    /// on the local checkouts.
+    pub async fn checkout(
+        &self,
+        requirements: Vec<UnresolvedRequirementSpecification>,
+    ) -> Result<Vec<UnresolvedRequirementSpecification>> {
+        let mut editables = Vec::with_capacity(requirements.len());
+        for entry in requirements {
+            let requirement = match entry.requirement {
+                UnresolvedRequirement::Named(requirement) => {
+                    let RequirementSource::Git {
+                        repository,
+                        reference,
+                        precise,
+                        subdirectory,
+                        ..
+                    } = requirement.source
+                    else {
+                        bail!("Expected a Git requirement for: `{}`", requirement.name);
+                    };
+                    let git = match precise {
+                        Some(precise) => GitUrl::from_commit(repository, reference, precise),
+                        None => GitUrl::from_reference(repository, reference),
+                    };
+                    let path = self
+                        .checkout_one(&requirement.name, git, subdirectory.as_deref())
+                        .await?;
+                    UnresolvedRequirement::Named(Requirement::from(pep508_rs::Requirement {
+                        name: requirement.name,
+                        extras: requirement.extras,
+                        marker: requirement.marker,
+                        origin: requirement.origin,
+                        version_or_url: Some(pep508_rs::VersionOrUrl::Url(editable_url(&path)?)),
+                    }))
+                }
+                UnresolvedRequirement::Unnamed(requirement) => {
+                    let ParsedUrl::Git(ParsedGitUrl { url, subdirectory }) =
+                        &requirement.url.parsed_url
+                    else {
+                        bail!("Expected a Git requirement, found: `{requirement}`");
+                    };
+                    let name = checkout_name(&requirement.url.verbatim, url)?;
+                    let path = self
+                        .checkout_one(&name, url.clone(), subdirectory.as_deref())
+                        .await?;
+                    UnresolvedRequirement::Unnamed(UnnamedRequirement {
+                        url: editable_url(&path)?,
+                        extras: requirement.extras,
+                        marker: requirement.marker,
+                        origin: requirement.origin,
+                    })
+                }
+            };
+            editables.push(UnresolvedRequirementSpecification {
+                requirement,
+                hashes: entry.hashes,
+            });
+        }
+        Ok(editables)
+    }
+
+    /// Check out a single repository, returning the path to the (editable) project within it.
+    async fn checkout_one(
+        &self,
+        name: &PackageName,
+        git: GitUrl,
+        subdirectory: Option<&Path>,
+    ) -> Result<PathBuf> {
+        let destination = self.src.join(name.as_ref());
+        let receipt_path = destination.join(".git").join(RECEIPT);
+
+        // If the directory already exists, ensure that it's a checkout of the same repository.
+        let exists = destination.exists();
+        if exists {
+            let receipt = fs_err::tokio::read_to_string(&receipt_path)
+                .await
+                .ok()
+                .and_then(|contents| toml::from_str::<CheckoutReceipt>(&contents).ok());
+            let matches = receipt.is_some_and(|receipt| {
+                url::Url::parse(&receipt.repository).is_ok_and(|repository| {
+                    RepositoryUrl::new(&repository) == RepositoryUrl::new(git.repository())
+                })
+            });
+            if !matches {
+                return Err(anyhow!(
+                    "`{}` already exists, but isn't a checkout of `{}`",
+                    destination.user_display(),
+                    git.repository()
+                ));
+            }
+        }
+
+        let update = !exists || self.upgrade.contains(name);
+        debug!(
+            "{} checkout of `{}` at: {}",
+            if !exists {
+                "Creating"
+            } else if update {
+                "Updating"
+            } else {
+                "Using"
+            },
+            git.repository(),
+            destination.user_display()
+        );
+
+        let source = GitSource::new(
+            git.clone(),
+            self.client.client(),
+            self.cache.bucket(CacheBucket::Git),
+        );
+        let fetch = {
+            let destination = destination.clone();
+            tokio::task::spawn_blocking(move || source.working_copy(&destination, update))
+                .await?
+                .with_context(|| format!("Failed to check out `{}`", git.repository()))?
+        };
+
+        // Record the checkout, so that it can be recognized (and updated) on subsequent installs.
+        let receipt = CheckoutReceipt {
+            repository: git.repository().to_string(),
+            reference: git.reference().as_str().map(ToString::to_string),
+            commit: fetch
+                .git()
+                .precise()
+                .map(|precise| precise.to_string())
+                .unwrap_or_default(),
+        };
+        fs_err::tokio::write(&receipt_path, toml::to_string(&receipt)?).await?;
+
+        Ok(match subdirectory {
+            Some(subdirectory) => destination.join(subdirectory),
+            None => destination,
+        })
+    }
+}
+
+/// Return the editable directory URL for a checkout.
+fn editable_url(path: &Path) -> Result<VerbatimParsedUrl> {
+    let url = VerbatimParsedUrl::parse_absolute_path(path)?;
+    let ParsedUrl::Directory(parsed_url) = url.parsed_url else {
+        bail!("Expected a directory: `{}`", path.user_display());
+    };
+    Ok(VerbatimParsedUrl {
+        parsed_url: ParsedUrl::Directory(pypi_types::ParsedDirectoryUrl {
+            editable: true,
+            ..parsed_url
+        }),
+        verbatim: url.verbatim,
+    })
+}
+
+/// Determine the name of the checkout for an unnamed requirement, from the `#egg=` fragment or,
+/// failing that, the name of the repository.
+fn checkout_name(verbatim: &VerbatimUrl, git: &GitUrl) -> Result<PackageName> {
+    let egg = verbatim.raw().fragment().and_then(|fragment| {
+        fragment
+            .split('&')
+            .find_map(|fragment| fragment.strip_prefix("egg="))
+    });
+    let name = egg.or_else(|| {
+        git.repository()
+            .path_segments()
+            .and_then(Iterator::last)
+            .map(|segment| segment.trim_end_matches(".git"))
+    });
+    name.and_then(|name| PackageName::new(name.to_string()).ok())
+        .ok_or_else(|| {
+            anyhow!(
+                "Unable to determine a name for the checkout of `{verbatim}`; add an `#egg=<name>` fragment to the URL"
+            )
+        })
+}
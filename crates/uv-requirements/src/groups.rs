@@ -0,0 +1,74 @@
+//! Reading PEP 735 dependency groups (i.e., the `[dependency-groups]` table) from a
+//! `pyproject.toml` file.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use pep508_rs::RequirementOrigin;
+use pypi_types::Requirement;
+use uv_fs::Simplified;
+use uv_normalize::{GroupName, PackageName};
+use uv_workspace::dependency_groups;
+
+/// The subset of a `pyproject.toml` that's relevant for reading dependency groups.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyProjectToml {
+    project: Option<Project>,
+    dependency_groups: Option<dependency_groups::DependencyGroups>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: PackageName,
+}
+
+/// The dependency groups declared in a `pyproject.toml` file.
+#[derive(Debug)]
+pub struct DependencyGroups {
+    path: Box<Path>,
+    project: Option<PackageName>,
+    groups: dependency_groups::DependencyGroups,
+}
+
+impl DependencyGroups {
+    /// Read the dependency groups from the `pyproject.toml` file at the given path.
+    pub fn from_pyproject_toml(path: &Path) -> Result<Self> {
+        let contents = fs_err::read_to_string(path)?;
+        let pyproject_toml = toml::from_str::<PyProjectToml>(&contents)
+            .with_context(|| format!("Failed to parse: `{}`", path.user_display()))?;
+        Ok(Self {
+            path: path.into(),
+            project: pyproject_toml.project.map(|project| project.name),
+            groups: pyproject_toml.dependency_groups.unwrap_or_default(),
+        })
+    }
+
+    /// Returns `true` if the `pyproject.toml` declares the given group.
+    pub fn contains(&self, group: &GroupName) -> bool {
+        self.groups.contains(group)
+    }
+
+    /// Return the requirements of the given group, including those of any groups it includes.
+    ///
+    /// Each requirement is annotated with the group that it was requested by.
+    pub fn requirements(&self, group: &GroupName) -> Result<Vec<Requirement>> {
+        let origin =
+            RequirementOrigin::Group(self.path.to_path_buf(), self.project.clone(), group.clone());
+        let requirements = self
+            .groups
+            .requirements(group, self.path.parent().unwrap_or(Path::new("")))
+            .with_context(|| {
+                format!(
+                    "Failed to read dependency group `{group}` from `{}`",
+                    self.path.user_display()
+                )
+            })?;
+        Ok(requirements
+            .into_iter()
+            .map(|requirement| Requirement::from(requirement.with_origin(origin.clone())))
+            .collect())
+    }
+}
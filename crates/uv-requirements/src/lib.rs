@@ -1,13 +1,19 @@
+pub use crate::checkout::*;
+pub use crate::groups::*;
 pub use crate::lookahead::*;
 pub use crate::source_tree::*;
 pub use crate::sources::*;
 pub use crate::specification::*;
+pub use crate::typosquat::*;
 pub use crate::unnamed::*;
 
+mod checkout;
 mod confirm;
+mod groups;
 mod lookahead;
 mod source_tree;
 mod sources;
 mod specification;
+mod typosquat;
 mod unnamed;
 pub mod upgrade;
@@ -185,7 +185,8 @@ impl<'a, Context: BuildContext> SourceTreeResolver<'a, Context> {
             HashStrategy::None => HashPolicy::None,
             HashStrategy::Generate => HashPolicy::Generate,
             HashStrategy::Verify(_) => HashPolicy::Generate,
-            HashStrategy::Require(_) => {
+            HashStrategy::PerIndex { mode, .. } if !mode.is_require() => HashPolicy::Generate,
+            HashStrategy::Require(_) | HashStrategy::PerIndex { .. } => {
                 return Err(anyhow::anyhow!(
                     "Hash-checking is not supported for local directories: {}",
                     path.user_display()
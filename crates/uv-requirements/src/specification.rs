@@ -40,7 +40,7 @@ use distribution_types::{
 use pep508_rs::{UnnamedRequirement, UnnamedRequirementUrl};
 use pypi_types::Requirement;
 use pypi_types::VerbatimParsedUrl;
-use requirements_txt::{RequirementsTxt, RequirementsTxtRequirement};
+use requirements_txt::{EditableError, RequirementsTxt, RequirementsTxtRequirement};
 use uv_client::BaseClientBuilder;
 use uv_configuration::{NoBinary, NoBuild};
 use uv_fs::{Simplified, CWD};
@@ -49,7 +49,7 @@ use uv_workspace::pyproject::PyProjectToml;
 
 use crate::RequirementsSource;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct RequirementsSpecification {
     /// The name of the project specifying requirements.
     pub project: Option<PackageName>,
@@ -59,6 +59,9 @@ pub struct RequirementsSpecification {
     pub constraints: Vec<Requirement>,
     /// The overrides for the project.
     pub overrides: Vec<UnresolvedRequirementSpecification>,
+    /// The editable requirements that refer to Git repositories, which must be checked out
+    /// (see [`crate::EditableCheckouts`]) before they can be installed.
+    pub git_editables: Vec<UnresolvedRequirementSpecification>,
     /// The source trees from which to extract requirements.
     pub source_trees: Vec<PathBuf>,
     /// The extras used to collect requirements.
@@ -95,12 +98,18 @@ impl RequirementsSpecification {
             }
             RequirementsSource::Editable(name) => {
                 let requirement = RequirementsTxtRequirement::parse(name, &*CWD, true)
-                    .with_context(|| format!("Failed to parse: `{name}`"))?;
-                Self {
-                    requirements: vec![UnresolvedRequirementSpecification::from(
-                        requirement.into_editable()?,
-                    )],
-                    ..Self::default()
+                    .with_context(|| format!("Failed to parse: `{name}`"))?
+                    .into_editable()?;
+                if requirement.is_git() {
+                    Self {
+                        git_editables: vec![UnresolvedRequirementSpecification::from(requirement)],
+                        ..Self::default()
+                    }
+                } else {
+                    Self {
+                        requirements: vec![UnresolvedRequirementSpecification::from(requirement)],
+                        ..Self::default()
+                    }
                 }
             }
             RequirementsSource::RequirementsTxt(path) => {
@@ -113,18 +122,25 @@ impl RequirementsSpecification {
                 }
 
                 let requirements_txt = RequirementsTxt::parse(path, &*CWD, client_builder).await?;
+                let (git_editables, editables): (Vec<_>, Vec<_>) = requirements_txt
+                    .editables
+                    .into_iter()
+                    .partition(|entry| entry.requirement.is_git());
                 Self {
                     requirements: requirements_txt
                         .requirements
                         .into_iter()
                         .map(UnresolvedRequirementSpecification::from)
                         .chain(
-                            requirements_txt
-                                .editables
+                            editables
                                 .into_iter()
                                 .map(UnresolvedRequirementSpecification::from),
                         )
                         .collect(),
+                    git_editables: git_editables
+                        .into_iter()
+                        .map(UnresolvedRequirementSpecification::from)
+                        .collect(),
                     constraints: requirements_txt
                         .constraints
                         .into_iter()
@@ -219,6 +235,7 @@ impl RequirementsSpecification {
         for source in requirements {
             let source = Self::from_source(source, client_builder).await?;
             spec.requirements.extend(source.requirements);
+            spec.git_editables.extend(source.git_editables);
             spec.constraints.extend(source.constraints);
             spec.overrides.extend(source.overrides);
             spec.extras.extend(source.extras);
@@ -250,7 +267,7 @@ impl RequirementsSpecification {
         // Overrides are ignored, as are the hashes, as they are not relevant for constraints.
         for source in constraints {
             let source = Self::from_source(source, client_builder).await?;
-            for entry in source.requirements {
+            for entry in source.requirements.into_iter().chain(source.git_editables) {
                 match entry.requirement {
                     UnresolvedRequirement::Named(requirement) => {
                         spec.constraints.push(requirement);
@@ -286,6 +303,7 @@ impl RequirementsSpecification {
         for source in overrides {
             let source = Self::from_source(source, client_builder).await?;
             spec.overrides.extend(source.requirements);
+            spec.overrides.extend(source.git_editables);
             spec.overrides.extend(source.overrides);
 
             if let Some(index_url) = source.index_url {
@@ -320,7 +338,18 @@ impl RequirementsSpecification {
         requirements: &[RequirementsSource],
         client_builder: &BaseClientBuilder<'_>,
     ) -> Result<Self> {
-        Self::from_sources(requirements, &[], &[], client_builder).await
+        let spec = Self::from_sources(requirements, &[], &[], client_builder).await?;
+        spec.reject_git_editables()?;
+        Ok(spec)
+    }
+
+    /// Return an error if the specification includes any editable Git requirements, for commands
+    /// that can't check them out.
+    pub fn reject_git_editables(&self) -> Result<(), EditableError> {
+        if let Some(entry) = self.git_editables.first() {
+            return Err(EditableError::Git(entry.requirement.to_string()));
+        }
+        Ok(())
     }
 
     /// Initialize a [`RequirementsSpecification`] from a list of [`Requirement`].
@@ -334,6 +363,27 @@ impl RequirementsSpecification {
         }
     }
 
+    /// Initialize a [`RequirementsSpecification`] from a list of [`Requirement`], including
+    /// constraints and overrides.
+    pub fn from_constraints(
+        requirements: Vec<Requirement>,
+        constraints: Vec<Requirement>,
+        overrides: Vec<Requirement>,
+    ) -> Self {
+        Self {
+            requirements: requirements
+                .into_iter()
+                .map(UnresolvedRequirementSpecification::from)
+                .collect(),
+            constraints,
+            overrides: overrides
+                .into_iter()
+                .map(UnresolvedRequirementSpecification::from)
+                .collect(),
+            ..Self::default()
+        }
+    }
+
     /// Return true if the specification does not include any requirements to install.
     pub fn is_empty(&self) -> bool {
         self.requirements.is_empty() && self.source_trees.is_empty() && self.overrides.is_empty()
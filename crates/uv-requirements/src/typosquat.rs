@@ -0,0 +1,312 @@
+//! Detect requirements whose names are near-misses for popular packages (e.g., `reqeusts`), which
+//! are a common vector for typosquatting.
+
+use std::str::FromStr;
+
+use uv_normalize::PackageName;
+
+/// A selection of the most downloaded packages on PyPI, by normalized name.
+static POPULAR_PACKAGES: &[&str] = &[
+    "aiohttp",
+    "aiosignal",
+    "alembic",
+    "annotated-types",
+    "anyio",
+    "argcomplete",
+    "arrow",
+    "asgiref",
+    "attrs",
+    "autopep8",
+    "babel",
+    "bcrypt",
+    "beautifulsoup4",
+    "black",
+    "bleach",
+    "boto3",
+    "botocore",
+    "build",
+    "cachetools",
+    "celery",
+    "certifi",
+    "cffi",
+    "chardet",
+    "charset-normalizer",
+    "click",
+    "cloudpickle",
+    "colorama",
+    "coverage",
+    "cryptography",
+    "cycler",
+    "cython",
+    "dask",
+    "databricks-sdk",
+    "decorator",
+    "deprecated",
+    "dill",
+    "distlib",
+    "distro",
+    "django",
+    "djangorestframework",
+    "dnspython",
+    "docker",
+    "docutils",
+    "elasticsearch",
+    "email-validator",
+    "et-xmlfile",
+    "exceptiongroup",
+    "fastapi",
+    "filelock",
+    "flake8",
+    "flask",
+    "fonttools",
+    "frozenlist",
+    "fsspec",
+    "gitdb",
+    "gitpython",
+    "google-api-core",
+    "google-auth",
+    "google-cloud-storage",
+    "googleapis-common-protos",
+    "greenlet",
+    "grpcio",
+    "gunicorn",
+    "h11",
+    "httpcore",
+    "httplib2",
+    "httpx",
+    "huggingface-hub",
+    "hypothesis",
+    "idna",
+    "importlib-metadata",
+    "iniconfig",
+    "ipython",
+    "isodate",
+    "isort",
+    "itsdangerous",
+    "jinja2",
+    "jmespath",
+    "joblib",
+    "jsonpointer",
+    "jsonschema",
+    "jupyter",
+    "keras",
+    "kiwisolver",
+    "lxml",
+    "markdown",
+    "markupsafe",
+    "matplotlib",
+    "mccabe",
+    "mock",
+    "more-itertools",
+    "msgpack",
+    "multidict",
+    "mypy",
+    "mypy-extensions",
+    "networkx",
+    "nltk",
+    "nodeenv",
+    "numpy",
+    "oauthlib",
+    "openai",
+    "openpyxl",
+    "opentelemetry-api",
+    "opentelemetry-sdk",
+    "orjson",
+    "packaging",
+    "pandas",
+    "paramiko",
+    "pathspec",
+    "pendulum",
+    "pexpect",
+    "pillow",
+    "pip",
+    "platformdirs",
+    "pluggy",
+    "poetry",
+    "pre-commit",
+    "prometheus-client",
+    "prompt-toolkit",
+    "protobuf",
+    "psutil",
+    "psycopg2",
+    "psycopg2-binary",
+    "ptyprocess",
+    "py",
+    "pyarrow",
+    "pyasn1",
+    "pyasn1-modules",
+    "pycodestyle",
+    "pycparser",
+    "pydantic",
+    "pydantic-core",
+    "pyflakes",
+    "pygments",
+    "pyjwt",
+    "pylint",
+    "pymongo",
+    "pymysql",
+    "pynacl",
+    "pyopenssl",
+    "pyparsing",
+    "pyspark",
+    "pytest",
+    "pytest-asyncio",
+    "pytest-cov",
+    "pytest-mock",
+    "pytest-xdist",
+    "python-dateutil",
+    "python-dotenv",
+    "pytz",
+    "pyyaml",
+    "pyzmq",
+    "redis",
+    "regex",
+    "requests",
+    "requests-oauthlib",
+    "requests-toolbelt",
+    "rich",
+    "rsa",
+    "ruamel-yaml",
+    "ruff",
+    "s3transfer",
+    "scikit-learn",
+    "scipy",
+    "seaborn",
+    "selenium",
+    "setuptools",
+    "simplejson",
+    "six",
+    "smmap",
+    "sniffio",
+    "sortedcontainers",
+    "soupsieve",
+    "sqlalchemy",
+    "sqlparse",
+    "starlette",
+    "sympy",
+    "tabulate",
+    "tenacity",
+    "tensorflow",
+    "termcolor",
+    "threadpoolctl",
+    "tokenizers",
+    "toml",
+    "tomli",
+    "tomlkit",
+    "toolz",
+    "torch",
+    "tornado",
+    "tqdm",
+    "traitlets",
+    "transformers",
+    "typer",
+    "typing-extensions",
+    "tzdata",
+    "tzlocal",
+    "ujson",
+    "urllib3",
+    "uvicorn",
+    "virtualenv",
+    "wcwidth",
+    "websocket-client",
+    "websockets",
+    "werkzeug",
+    "wheel",
+    "wrapt",
+    "xlrd",
+    "yarl",
+    "zipp",
+];
+
+/// The minimum length of a package name for it to be considered a potential typo, to avoid
+/// flagging short names that are trivially close to one another (e.g., `rq` and `rsa`).
+const MIN_LENGTH: usize = 5;
+
+/// A requirement whose name is a near-miss for that of a popular package.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "The package `{name}` is similar to the popular package `{popular}`; did you mean `{popular}`?"
+)]
+pub struct PossibleTyposquat {
+    /// The name of the requested package.
+    pub name: PackageName,
+    /// The name of the popular package that it resembles.
+    pub popular: PackageName,
+}
+
+impl PossibleTyposquat {
+    /// Determine whether the given package name is a near-miss for a popular package, i.e.,
+    /// within a single edit of a popular package name, where swapping two adjacent characters
+    /// counts as a single edit.
+    pub fn detect(name: &PackageName) -> Option<Self> {
+        let name_str = name.as_ref();
+        if name_str.len() < MIN_LENGTH || POPULAR_PACKAGES.contains(&name_str) {
+            return None;
+        }
+        POPULAR_PACKAGES
+            .iter()
+            .filter(|popular| popular.len().abs_diff(name_str.len()) <= 1)
+            .map(|popular| (distance(name_str.as_bytes(), popular.as_bytes()), popular))
+            .find(|(distance, _)| *distance == 1)
+            .map(|(_, popular)| Self {
+                name: name.clone(),
+                popular: PackageName::from_str(popular).expect("popular packages are valid names"),
+            })
+    }
+}
+
+/// Compute the optimal string alignment distance between two strings, i.e., the Levenshtein
+/// distance, extended such that transposing two adjacent characters counts as a single edit.
+fn distance(a: &[u8], b: &[u8]) -> usize {
+    let mut matrix = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=b.len() {
+        matrix[0][j] = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            matrix[i][j] = (matrix[i - 1][j] + 1)
+                .min(matrix[i][j - 1] + 1)
+                .min(matrix[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                matrix[i][j] = matrix[i][j].min(matrix[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    matrix[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use uv_normalize::PackageName;
+
+    use super::{distance, PossibleTyposquat};
+
+    #[test]
+    fn optimal_string_alignment() {
+        assert_eq!(distance(b"requests", b"requests"), 0);
+        assert_eq!(distance(b"reqeusts", b"requests"), 1);
+        assert_eq!(distance(b"request", b"requests"), 1);
+        assert_eq!(distance(b"requestz", b"requests"), 1);
+        assert_eq!(distance(b"numpy", b"scipy"), 3);
+    }
+
+    #[test]
+    fn detect() {
+        let detect = |name: &str| {
+            PossibleTyposquat::detect(&PackageName::from_str(name).unwrap())
+                .map(|typosquat| typosquat.popular.to_string())
+        };
+        assert_eq!(detect("reqeusts").as_deref(), Some("requests"));
+        assert_eq!(detect("nunpy").as_deref(), Some("numpy"));
+        assert_eq!(detect("python-datutil").as_deref(), Some("python-dateutil"));
+        assert_eq!(detect("requests"), None);
+        assert_eq!(detect("my-project"), None);
+        assert_eq!(detect("rsa"), None);
+    }
+}
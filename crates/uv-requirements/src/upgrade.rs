@@ -1,12 +1,17 @@
 use std::path::Path;
 
 use anyhow::Result;
+use rustc_hash::FxHashMap;
 
-use requirements_txt::RequirementsTxt;
+use cache_key::RepositoryUrl;
+use distribution_types::UnresolvedRequirementSpecification;
+use pep508_rs::VersionOrUrl;
+use pypi_types::{ParsedGitUrl, ParsedUrl, RequirementSource};
+use requirements_txt::{RequirementsTxt, RequirementsTxtRequirement};
 use uv_client::{BaseClientBuilder, Connectivity};
 use uv_configuration::Upgrade;
 use uv_fs::CWD;
-use uv_git::ResolvedRepositoryReference;
+use uv_git::{GitSha, RepositoryReference, ResolvedRepositoryReference};
 use uv_resolver::{Lock, Preference, PreferenceError};
 
 #[derive(Debug, Default)]
@@ -18,18 +23,23 @@ pub struct LockedRequirements {
 }
 
 /// Load the preferred requirements from an existing `requirements.txt`, applying the upgrade strategy.
+///
+/// In addition to the pinned versions, any Git requirements that were pinned to a precise commit
+/// in the existing output are mapped back to the references (e.g., branches or tags) in the given
+/// input requirements, such that recompiling doesn't pick up new commits unless upgrading.
 pub async fn read_requirements_txt(
     output_file: Option<&Path>,
+    requirements: &[UnresolvedRequirementSpecification],
     upgrade: &Upgrade,
-) -> Result<Vec<Preference>> {
+) -> Result<LockedRequirements> {
     // As an optimization, skip reading the lockfile is we're upgrading all packages anyway.
     if upgrade.is_all() {
-        return Ok(Vec::new());
+        return Ok(LockedRequirements::default());
     }
 
     // If the lockfile doesn't exist, don't respect any pinned versions.
     let Some(output_file) = output_file.filter(|path| path.exists()) else {
-        return Ok(Vec::new());
+        return Ok(LockedRequirements::default());
     };
 
     // Parse the requirements from the lockfile.
@@ -40,26 +50,72 @@ pub async fn read_requirements_txt(
     )
     .await?;
 
-    // Map each entry in the lockfile to a preference.
-    let preferences = requirements_txt
+    // Ignore pinned versions for the packages that are being upgraded.
+    let entries = requirements_txt
         .requirements
+        .into_iter()
+        .filter(|entry| match &entry.requirement {
+            RequirementsTxtRequirement::Named(requirement) => !upgrade.contains(&requirement.name),
+            RequirementsTxtRequirement::Unnamed(_) => true,
+        })
+        .collect::<Vec<_>>();
+
+    // Map each pinned Git repository in the lockfile to its commit. If a repository was pinned to
+    // multiple commits, it's ambiguous, and so is omitted.
+    let mut commits: FxHashMap<RepositoryUrl, Option<GitSha>> = FxHashMap::default();
+    for entry in &entries {
+        let RequirementsTxtRequirement::Named(requirement) = &entry.requirement else {
+            continue;
+        };
+        let Some(VersionOrUrl::Url(url)) = requirement.version_or_url.as_ref() else {
+            continue;
+        };
+        let ParsedUrl::Git(ParsedGitUrl { url, .. }) = &url.parsed_url else {
+            continue;
+        };
+        let Some(precise) = url.precise() else {
+            continue;
+        };
+        commits
+            .entry(RepositoryUrl::new(url.repository()))
+            .and_modify(|sha| {
+                if *sha != Some(precise) {
+                    *sha = None;
+                }
+            })
+            .or_insert(Some(precise));
+    }
+
+    // Pin the references in the input requirements to the locked commits.
+    let git = requirements
+        .iter()
+        .filter_map(|requirement| {
+            let RequirementSource::Git {
+                repository,
+                reference,
+                precise: None,
+                ..
+            } = requirement.requirement.source().into_owned()
+            else {
+                return None;
+            };
+            let url = RepositoryUrl::new(&repository);
+            let sha = (*commits.get(&url)?)?;
+            Some(ResolvedRepositoryReference {
+                reference: RepositoryReference { url, reference },
+                sha,
+            })
+        })
+        .collect();
+
+    // Map each entry in the lockfile to a preference.
+    let preferences = entries
         .into_iter()
         .map(Preference::from_entry)
         .filter_map(Result::transpose)
         .collect::<Result<Vec<_>, PreferenceError>>()?;
 
-    // Apply the upgrade strategy to the requirements.
-    Ok(match upgrade {
-        // Respect all pinned versions from the existing lockfile.
-        Upgrade::None => preferences,
-        // Ignore all pinned versions from the existing lockfile.
-        Upgrade::All => vec![],
-        // Ignore pinned versions for the specified packages.
-        Upgrade::Packages(packages) => preferences
-            .into_iter()
-            .filter(|preference| !packages.contains_key(preference.name()))
-            .collect(),
-    })
+    Ok(LockedRequirements { preferences, git })
 }
 
 /// Load the preferred requirements from an existing lockfile, applying the upgrade strategy.
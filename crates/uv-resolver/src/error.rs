@@ -1,20 +1,29 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Formatter;
+use std::ops::Bound;
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use owo_colors::OwoColorize;
+
 use pubgrub::{DefaultStringReporter, DerivationTree, Derived, External, Range, Reporter};
 use rustc_hash::FxHashMap;
+use url::Url;
 
 use distribution_types::{BuiltDist, IndexLocations, InstalledDist, SourceDist};
 use pep440_rs::Version;
 use pep508_rs::MarkerTree;
+use uv_fs::Simplified;
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
 use crate::dependency_provider::UvDependencyProvider;
 use crate::fork_urls::ForkUrls;
-use crate::pubgrub::{PubGrubPackage, PubGrubReportFormatter, PubGrubSpecifierError};
-use crate::python_requirement::PythonRequirement;
+use crate::pubgrub::{
+    PubGrubPackage, PubGrubPackageInner, PubGrubPython, PubGrubReportFormatter,
+    PubGrubSpecifierError,
+};
+use crate::python_requirement::{PythonRequirement, PythonTarget};
 use crate::resolver::{IncompletePackage, ResolverMarkers, UnavailablePackage, UnavailableReason};
 
 #[derive(Debug, thiserror::Error)]
@@ -98,6 +107,9 @@ pub enum ResolveError {
     #[error("In `--require-hashes` mode, all requirements must be pinned upfront with `==`, but found: `{0}`")]
     UnhashedPackage(PackageName),
 
+    #[error("The hash-checking policy for `{1}` requires a hash for every package it serves, but found: `{0}`")]
+    UnhashedIndexPackage(PackageName, Url),
+
     /// Something unexpected happened.
     #[error("{0}")]
     Failure(String),
@@ -125,6 +137,8 @@ pub struct NoSolutionError {
     incomplete_packages: FxHashMap<PackageName, BTreeMap<Version, IncompletePackage>>,
     fork_urls: ForkUrls,
     markers: ResolverMarkers,
+    /// The user-level constraints file that was applied to the resolution, if any.
+    user_constraints: Option<PathBuf>,
 }
 
 impl NoSolutionError {
@@ -150,6 +164,7 @@ impl NoSolutionError {
             incomplete_packages,
             fork_urls,
             markers,
+            user_constraints: None,
         }
     }
 
@@ -197,10 +212,71 @@ impl NoSolutionError {
             .expect("derivation tree should contain at least one external term")
     }
 
+    /// Note that the constraints in the given user-level constraints file were applied to the
+    /// resolution.
+    #[must_use]
+    pub fn with_user_constraints(mut self, path: PathBuf) -> Self {
+        self.user_constraints = Some(path);
+        self
+    }
+
     /// Initialize a [`NoSolutionHeader`] for this error.
     pub fn header(&self) -> NoSolutionHeader {
         NoSolutionHeader::new(self.markers.clone())
     }
+
+    /// Return the packages that were rejected due to the `requires-python` of the resolution,
+    /// along with the lowest Python version supported by any of the rejected versions of each.
+    ///
+    /// Only lower bounds are considered, since those are what a narrower `requires-python` (or a
+    /// Python marker on the dependency) can satisfy; packages that dropped support for newer Python
+    /// versions are omitted.
+    pub fn requires_python_conflicts(&self) -> BTreeMap<PackageName, Version> {
+        fn collect(derivation_tree: &ErrorTree, conflicts: &mut BTreeMap<PackageName, Version>) {
+            match derivation_tree {
+                DerivationTree::External(External::FromDependencyOf(
+                    package,
+                    _,
+                    dependency,
+                    dependency_set,
+                )) => {
+                    if !matches!(
+                        &**dependency,
+                        PubGrubPackageInner::Python(PubGrubPython::Target)
+                    ) {
+                        return;
+                    }
+                    let Some(name) = package.name_no_root() else {
+                        return;
+                    };
+                    let Some((Bound::Included(lower), _)) = dependency_set.bounding_range() else {
+                        return;
+                    };
+                    match conflicts.get_mut(name) {
+                        Some(existing) if *lower < *existing => *existing = lower.clone(),
+                        Some(_) => {}
+                        None => {
+                            conflicts.insert(name.clone(), lower.clone());
+                        }
+                    }
+                }
+                DerivationTree::External(_) => {}
+                DerivationTree::Derived(derived) => {
+                    collect(&derived.cause1, conflicts);
+                    collect(&derived.cause2, conflicts);
+                }
+            }
+        }
+
+        let mut conflicts = BTreeMap::new();
+        if matches!(
+            self.python_requirement.target(),
+            Some(PythonTarget::RequiresPython(_))
+        ) {
+            collect(&self.error, &mut conflicts);
+        }
+        conflicts
+    }
 }
 
 impl std::error::Error for NoSolutionError {}
@@ -228,6 +304,18 @@ impl std::fmt::Display for NoSolutionError {
             write!(f, "\n\n{hint}")?;
         }
 
+        // Note any user-level constraints, which may not be apparent from the derivation report.
+        if let Some(path) = &self.user_constraints {
+            write!(
+                f,
+                "\n\n{}{} The constraints in `{}` were applied to the resolution (use `{}` to ignore them)",
+                "hint".bold().cyan(),
+                ":".bold(),
+                path.user_display().cyan(),
+                "--no-user-constraints".green(),
+            )?;
+        }
+
         Ok(())
     }
 }
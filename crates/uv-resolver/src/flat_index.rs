@@ -76,6 +76,7 @@ impl FlatIndex {
 
                 let compatibility =
                     Self::wheel_compatibility(&filename, &file.hashes, tags, hasher, build_options);
+                let prefer_source = build_options.prefer_source_package(&filename.name);
                 let dist = RegistryBuiltWheel {
                     filename,
                     file: Box::new(file),
@@ -86,13 +87,16 @@ impl FlatIndex {
                         entry.get_mut().insert_built(dist, vec![], compatibility);
                     }
                     Entry::Vacant(entry) => {
-                        entry.insert(PrioritizedDist::from_built(dist, vec![], compatibility));
+                        entry
+                            .insert(PrioritizedDist::from_built(dist, vec![], compatibility))
+                            .set_prefer_source(prefer_source);
                     }
                 }
             }
             DistFilename::SourceDistFilename(filename) => {
                 let compatibility =
                     Self::source_dist_compatibility(&filename, &file.hashes, hasher, build_options);
+                let prefer_source = build_options.prefer_source_package(&filename.name);
                 let dist = RegistrySourceDist {
                     name: filename.name.clone(),
                     version: filename.version.clone(),
@@ -106,7 +110,9 @@ impl FlatIndex {
                         entry.get_mut().insert_source(dist, vec![], compatibility);
                     }
                     Entry::Vacant(entry) => {
-                        entry.insert(PrioritizedDist::from_source(dist, vec![], compatibility));
+                        entry
+                            .insert(PrioritizedDist::from_source(dist, vec![], compatibility))
+                            .set_prefer_source(prefer_source);
                     }
                 }
             }
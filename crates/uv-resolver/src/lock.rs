@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::hash_map::Entry;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::convert::Infallible;
 use std::fmt::{Debug, Display};
@@ -12,6 +13,7 @@ use itertools::Itertools;
 use petgraph::visit::EdgeRef;
 use pubgrub::Range;
 use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
+use sha2::{Digest, Sha256};
 use toml_edit::{value, Array, ArrayOfTables, InlineTable, Item, Table, Value};
 use url::Url;
 
@@ -19,12 +21,12 @@ use cache_key::RepositoryUrl;
 use distribution_filename::{DistExtension, ExtensionError, SourceDistExtension, WheelFilename};
 use distribution_types::{
     BuiltDist, DirectUrlBuiltDist, DirectUrlSourceDist, DirectorySourceDist, Dist,
-    DistributionMetadata, FileLocation, GitSourceDist, HashComparison, IndexUrl, Name,
-    PathBuiltDist, PathSourceDist, PrioritizedDist, RegistryBuiltDist, RegistryBuiltWheel,
+    DistributionMetadata, FileLocation, GitSourceDist, HashComparison, IndexLocations, IndexUrl,
+    Name, PathBuiltDist, PathSourceDist, PrioritizedDist, RegistryBuiltDist, RegistryBuiltWheel,
     RegistrySourceDist, RemoteSource, Resolution, ResolvedDist, SourceDistCompatibility,
     ToUrlError, UrlString, VersionId, WheelCompatibility,
 };
-use pep440_rs::{Version, VersionSpecifier};
+use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
 use pep508_rs::{
     ExtraOperator, MarkerEnvironment, MarkerExpression, MarkerTree, VerbatimUrl, VerbatimUrlError,
 };
@@ -32,7 +34,9 @@ use platform_tags::{TagCompatibility, TagPriority, Tags};
 use pypi_types::{
     HashDigest, ParsedArchiveUrl, ParsedGitUrl, ParsedUrl, Requirement, RequirementSource,
 };
-use uv_configuration::{ExtrasSpecification, Upgrade};
+use uv_configuration::{
+    BuildOptions, ExtrasSpecification, IndexStrategy, PackageBuildEnv, SysPlatform, Upgrade,
+};
 use uv_distribution::{ArchiveMetadata, Metadata};
 use uv_fs::{PortablePath, PortablePathBuf};
 use uv_git::{GitReference, GitSha, RepositoryReference, ResolvedRepositoryReference};
@@ -61,6 +65,9 @@ pub struct Lock {
     requires_python: Option<RequiresPython>,
     /// We discard the lockfile if these options match.
     options: ResolverOptions,
+    /// The digests of the extra environment variables that were set when building specific
+    /// packages, which are recorded as inputs to the build.
+    build_env: BTreeMap<PackageName, String>,
     /// The actual locked version and their metadata.
     packages: Vec<Package>,
     /// A map from package ID to index in `packages`.
@@ -208,6 +215,9 @@ impl Lock {
             resolution_mode: graph.options.resolution_mode,
             prerelease_mode: graph.options.prerelease_mode,
             exclude_newer: graph.options.exclude_newer,
+            index_strategy: graph.options.index_strategy,
+            indexes: Vec::new(),
+            override_dependencies: Vec::new(),
         };
         let lock = Self::new(
             VERSION,
@@ -383,6 +393,7 @@ impl Lock {
             fork_markers,
             requires_python,
             options,
+            build_env: BTreeMap::new(),
             packages,
             by_id,
         })
@@ -418,12 +429,114 @@ impl Lock {
         self.options.exclude_newer
     }
 
+    /// Returns the index strategy used to generate this lock.
+    pub fn index_strategy(&self) -> IndexStrategy {
+        self.options.index_strategy
+    }
+
+    /// Returns the indexes used to generate this lock, as recorded by [`Lock::recorded_indexes`].
+    pub fn indexes(&self) -> &[String] {
+        &self.options.indexes
+    }
+
+    /// Returns the overrides used to generate this lock, as recorded by
+    /// [`Lock::recorded_overrides`].
+    pub fn override_dependencies(&self) -> &[String] {
+        &self.options.override_dependencies
+    }
+
+    /// Returns the indexes to record in the lockfile for the given [`IndexLocations`].
+    ///
+    /// The indexes are recorded by their redacted URL (i.e., without credentials), in priority
+    /// order, including any `--find-links` locations. If only the default index is used, no
+    /// indexes are recorded.
+    pub fn recorded_indexes(index_locations: &IndexLocations) -> Vec<String> {
+        let indexes = index_locations
+            .indexes()
+            .map(|index| index.redacted().to_string())
+            .chain(
+                index_locations
+                    .flat_index()
+                    .map(|index| index.redacted().to_string()),
+            )
+            .collect::<Vec<_>>();
+        let default = IndexLocations::default();
+        if default
+            .indexes()
+            .map(|index| index.redacted().to_string())
+            .eq(indexes.iter().cloned())
+        {
+            return Vec::new();
+        }
+        indexes
+    }
+
+    /// Returns the overrides to record in the lockfile, in a canonical order.
+    pub fn recorded_overrides(overrides: &[Requirement]) -> Vec<String> {
+        overrides
+            .iter()
+            .map(ToString::to_string)
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    /// Returns the digests of the extra environment variables that were set when building specific
+    /// packages, keyed by package name.
+    pub fn build_env(&self) -> &BTreeMap<PackageName, String> {
+        &self.build_env
+    }
+
+    /// Returns the digests of the extra environment variables to set when building specific
+    /// packages, as recorded in the lockfile.
+    ///
+    /// Only the digests are recorded, since the variables may contain secrets (e.g., credentials
+    /// passed to a build backend).
+    pub fn build_env_digests(build_env: &PackageBuildEnv) -> BTreeMap<PackageName, String> {
+        build_env
+            .iter()
+            .map(|(name, variables)| {
+                let mut hasher = Sha256::new();
+                for (key, value) in variables {
+                    hasher.update(key.as_bytes());
+                    hasher.update(b"=");
+                    hasher.update(value.as_bytes());
+                    hasher.update(b"\n");
+                }
+                (name.clone(), format!("sha256:{:x}", hasher.finalize()))
+            })
+            .collect()
+    }
+
     /// If this lockfile was built from a forking resolution with non-identical forks, return the
     /// markers of those forks, otherwise `None`.
     pub fn fork_markers(&self) -> &Option<BTreeSet<MarkerTree>> {
         &self.fork_markers
     }
 
+    /// Returns the markers on the dependency edges that point to each package, across all
+    /// dependencies, optional dependencies, and development dependencies.
+    ///
+    /// A `None` marker indicates an unconditional edge. Packages that aren't depended on by any
+    /// other package (e.g., workspace members) are omitted.
+    pub fn dependency_markers(&self) -> BTreeMap<&PackageName, Vec<Option<&MarkerTree>>> {
+        let mut markers: BTreeMap<&PackageName, Vec<Option<&MarkerTree>>> = BTreeMap::new();
+        for package in &self.packages {
+            let dependencies = package
+                .dependencies
+                .iter()
+                .chain(package.optional_dependencies.values().flatten())
+                .chain(package.dev_dependencies.values().flatten());
+            for dependency in dependencies {
+                markers
+                    .entry(&dependency.package_id.name)
+                    .or_default()
+                    .push(dependency.marker.as_ref());
+            }
+        }
+        markers
+    }
+
     /// Convert the [`Lock`] to a [`Resolution`] using the given marker environment, tags, and root.
     pub fn to_resolution(
         &self,
@@ -432,9 +545,192 @@ impl Lock {
         tags: &Tags,
         extras: &ExtrasSpecification,
         dev: &[GroupName],
+        build_options: &BuildOptions,
+    ) -> Result<Resolution, LockError> {
+        // The lock can't be installed on any platform that was pruned from it.
+        if let Some(platform) = self
+            .options
+            .prune_platforms
+            .iter()
+            .find(|platform| platform.marker().evaluate(marker_env, &[]))
+        {
+            return Err(LockErrorKind::PrunedPlatform {
+                platform: *platform,
+            }
+            .into());
+        }
+
+        let mut map = BTreeMap::default();
+        let mut hashes = BTreeMap::default();
+        for (dist, _) in self.walk(project, Some(marker_env), extras, dev) {
+            map.insert(
+                dist.id.name.clone(),
+                ResolvedDist::Installable(dist.to_dist(
+                    project.workspace().install_path(),
+                    tags,
+                    build_options,
+                )?),
+            );
+            hashes.insert(dist.id.name.clone(), dist.hashes());
+        }
+        let diagnostics = vec![];
+        Ok(Resolution::new(map, hashes, diagnostics))
+    }
+
+    /// Convert the [`Lock`] to a [`Resolution`] that includes the given requirements (and their
+    /// dependencies), rather than the members of a workspace.
+    ///
+    /// This is used for environments that aren't backed by a project, like tool environments. Any
+    /// local sources are resolved relative to the given install path.
+    pub fn to_resolution_for_requirements(
+        &self,
+        requirements: &[Requirement],
+        install_path: &Path,
+        marker_env: &MarkerEnvironment,
+        tags: &Tags,
+        build_options: &BuildOptions,
     ) -> Result<Resolution, LockError> {
         let mut queue: VecDeque<(&Package, Option<&ExtraName>)> = VecDeque::new();
+        for requirement in requirements {
+            if !requirement.evaluate_markers(Some(marker_env), &[]) {
+                continue;
+            }
+            let root = self
+                .find_by_name(&requirement.name)
+                .map_err(|_| LockErrorKind::AmbiguousRequirement {
+                    name: requirement.name.clone(),
+                })?
+                .ok_or_else(|| LockErrorKind::MissingRequirement {
+                    name: requirement.name.clone(),
+                })?;
+            queue.push_back((root, None));
+            for extra in &requirement.extras {
+                queue.push_back((root, Some(extra)));
+            }
+        }
+
+        let mut map = BTreeMap::default();
+        let mut hashes = BTreeMap::default();
+        for (dist, _) in self.traverse(queue, Some(marker_env), &[]) {
+            map.insert(
+                dist.id.name.clone(),
+                ResolvedDist::Installable(dist.to_dist(install_path, tags, build_options)?),
+            );
+            hashes.insert(dist.id.name.clone(), dist.hashes());
+        }
+        let diagnostics = vec![];
+        Ok(Resolution::new(map, hashes, diagnostics))
+    }
+
+    /// Return the packages that are reachable from the workspace members on any platform, along
+    /// with their (reachable) dependencies and the markers under which each dependency applies.
+    ///
+    /// Includes the dependencies of any enabled extras and development dependency groups. The
+    /// packages are sorted by name and version.
+    pub fn to_dependency_graph(
+        &self,
+        project: &VirtualProject,
+        extras: &ExtrasSpecification,
+        dev: &[GroupName],
+    ) -> Vec<(&Package, Vec<(&Package, Option<&MarkerTree>)>)> {
+        let mut graph: BTreeMap<&PackageId, (&Package, Vec<(&Package, Option<&MarkerTree>)>)> =
+            BTreeMap::default();
+        for (dist, dependencies) in self.walk(project, None, extras, dev) {
+            let (_, edges) = graph
+                .entry(&dist.id)
+                .or_insert_with(|| (dist, Vec::default()));
+            for dependency in dependencies {
+                let marker = dependency.marker.as_ref();
+                if !edges.iter().any(|(package, existing)| {
+                    package.id == dependency.package_id && *existing == marker
+                }) {
+                    edges.push((self.find_by_id(&dependency.package_id), marker));
+                }
+            }
+        }
+        graph.into_values().collect()
+    }
+
+    /// Return a constraint pinning each registry package that's reachable from the workspace
+    /// members in the given marker environment to its locked version.
+    ///
+    /// Packages from any other source (e.g., the workspace members themselves, or Git and path
+    /// dependencies) are omitted.
+    pub fn to_constraints(
+        &self,
+        project: &VirtualProject,
+        marker_env: &MarkerEnvironment,
+        extras: &ExtrasSpecification,
+        dev: &[GroupName],
+    ) -> Vec<Requirement> {
+        let mut seen = FxHashSet::default();
+        self.walk(project, Some(marker_env), extras, dev)
+            .into_iter()
+            .filter(|(dist, _)| matches!(dist.id.source, Source::Registry(..)))
+            .filter(|(dist, _)| seen.insert(&dist.id))
+            .map(|(dist, _)| Requirement {
+                name: dist.id.name.clone(),
+                extras: vec![],
+                marker: None,
+                source: RequirementSource::Registry {
+                    specifier: VersionSpecifier::equals_version(dist.id.version.clone()).into(),
+                    index: None,
+                },
+                origin: None,
+            })
+            .collect()
+    }
+
+    /// Return the packages that apply to the given marker environment, i.e., those that are
+    /// reachable from the root packages (those that aren't depended on by any other package) via
+    /// dependency edges whose markers evaluate to `true`.
+    ///
+    /// As the enabled extras and development dependency groups aren't known, the dependencies of
+    /// every extra and group are included.
+    pub fn packages_for_environment(&self, marker_env: &MarkerEnvironment) -> Vec<&Package> {
+        let dependency_markers = self.dependency_markers();
+
+        let mut queue: VecDeque<(&Package, Option<&ExtraName>)> = VecDeque::new();
+        for root in self
+            .packages
+            .iter()
+            .filter(|package| !dependency_markers.contains_key(package.name()))
+        {
+            queue.push_back((root, None));
+            for extra in root.optional_dependencies.keys() {
+                queue.push_back((root, Some(extra)));
+            }
+        }
+
+        let dev = self
+            .packages
+            .iter()
+            .flat_map(|package| package.dev_dependencies.keys())
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
         let mut seen = FxHashSet::default();
+        self.traverse(queue, Some(marker_env), &dev)
+            .into_iter()
+            .map(|(dist, _)| dist)
+            .filter(|dist| seen.insert(&dist.id))
+            .collect()
+    }
+
+    /// Traverse the [`Lock`] from the workspace members, returning each visited package (once for
+    /// its base dependencies, and once for each of its enabled extras) along with the dependencies
+    /// that apply in the given marker environment, or on any platform if no marker environment is
+    /// given.
+    fn walk<'lock>(
+        &'lock self,
+        project: &VirtualProject,
+        marker_env: Option<&MarkerEnvironment>,
+        extras: &ExtrasSpecification,
+        dev: &[GroupName],
+    ) -> Vec<(&'lock Package, Vec<&'lock Dependency>)> {
+        let mut queue: VecDeque<(&Package, Option<&ExtraName>)> = VecDeque::new();
 
         // Add the workspace packages to the queue.
         for root_name in project.packages() {
@@ -474,8 +770,20 @@ impl Lock {
             }
         }
 
-        let mut map = BTreeMap::default();
-        let mut hashes = BTreeMap::default();
+        self.traverse(queue, marker_env, dev)
+    }
+
+    /// Traverse the [`Lock`] from the given roots, returning each visited package along with the
+    /// dependencies that apply in the given marker environment, or on any platform if no marker
+    /// environment is given.
+    fn traverse<'lock: 'extra, 'extra>(
+        &'lock self,
+        mut queue: VecDeque<(&'lock Package, Option<&'extra ExtraName>)>,
+        marker_env: Option<&MarkerEnvironment>,
+        dev: &[GroupName],
+    ) -> Vec<(&'lock Package, Vec<&'lock Dependency>)> {
+        let mut seen = FxHashSet::default();
+        let mut visited = Vec::new();
         while let Some((dist, extra)) = queue.pop_front() {
             let deps =
                 if let Some(extra) = extra {
@@ -487,11 +795,15 @@ impl Lock {
                         }),
                     ))
                 };
+            let mut dependencies = Vec::new();
             for dep in deps {
                 if dep
                     .marker
                     .as_ref()
-                    .map_or(true, |marker| marker.evaluate(marker_env, &[]))
+                    .zip(marker_env)
+                    .map_or(true, |(marker, marker_env)| {
+                        marker.evaluate(marker_env, &[])
+                    })
                 {
                     let dep_dist = self.find_by_id(&dep.package_id);
                     if seen.insert((&dep.package_id, None)) {
@@ -502,23 +814,166 @@ impl Lock {
                             queue.push_back((dep_dist, Some(extra)));
                         }
                     }
+                    dependencies.push(dep);
                 }
             }
-            map.insert(
-                dist.id.name.clone(),
-                ResolvedDist::Installable(dist.to_dist(project.workspace().install_path(), tags)?),
-            );
-            hashes.insert(dist.id.name.clone(), dist.hashes());
+            visited.push((dist, dependencies));
         }
-        let diagnostics = vec![];
-        Ok(Resolution::new(map, hashes, diagnostics))
+        visited
+    }
+
+    /// Set the locked requirements of the build environment for the given workspace member.
+    #[must_use]
+    pub fn with_build_dependencies(
+        mut self,
+        name: &PackageName,
+        mut build_dependencies: Vec<BuildDependency>,
+    ) -> Self {
+        build_dependencies.sort();
+        for package in &mut self.packages {
+            if package.id.name == *name
+                && matches!(
+                    package.id.source,
+                    Source::Editable(_) | Source::Directory(_)
+                )
+            {
+                package.build_dependencies.clone_from(&build_dependencies);
+            }
+        }
+        self
+    }
+
+    /// Record the extra environment variables that were set when building specific packages.
+    #[must_use]
+    pub fn with_build_env(mut self, build_env: &PackageBuildEnv) -> Self {
+        self.build_env = Self::build_env_digests(build_env);
+        self
+    }
+
+    /// Set the indexes used to generate this lock, as returned by [`Lock::recorded_indexes`].
+    #[must_use]
+    pub fn with_indexes(mut self, indexes: Vec<String>) -> Self {
+        self.options.indexes = indexes;
+        self
+    }
+
+    /// Set the overrides used to generate this lock, as returned by [`Lock::recorded_overrides`].
+    #[must_use]
+    pub fn with_override_dependencies(mut self, override_dependencies: Vec<String>) -> Self {
+        self.options.override_dependencies = override_dependencies;
+        self
+    }
+
+    /// Remove the artifacts and dependencies that are specific to the given platforms, recording
+    /// the platforms in the lock.
+    ///
+    /// Wheels are removed if every platform tag is specific to a pruned platform, and dependencies
+    /// are removed if their markers can only be satisfied on a pruned platform. Any packages that
+    /// are no longer reachable from the workspace members are then removed entirely.
+    #[must_use]
+    pub fn with_pruned_platforms(mut self, mut platforms: Vec<SysPlatform>) -> Self {
+        platforms.sort_unstable();
+        platforms.dedup();
+        if platforms.is_empty() {
+            self.options.prune_platforms = platforms;
+            return self;
+        }
+
+        // Determine the marker satisfied by the remaining platforms.
+        let mut supported = MarkerTree::TRUE;
+        for platform in &platforms {
+            supported.and(platform.marker().negate());
+        }
+        let is_supported = |marker: Option<&MarkerTree>| {
+            marker.map_or(true, |marker| !marker.is_disjoint(&supported))
+        };
+
+        // The roots are the workspace members, along with any packages that aren't depended on by
+        // any other package.
+        let mut reachable = {
+            let dependents = self.dependency_markers();
+            self.packages
+                .iter()
+                .filter(|package| {
+                    matches!(package.id.source, Source::Editable(_))
+                        || !dependents.contains_key(&package.id.name)
+                })
+                .map(|package| package.id.clone())
+                .collect::<FxHashSet<_>>()
+        };
+
+        for package in &mut self.packages {
+            package.wheels.retain(|wheel| {
+                !wheel.filename.platform_tag.iter().all(|tag| {
+                    platforms
+                        .iter()
+                        .any(|platform| platform.matches_platform_tag(tag))
+                })
+            });
+            package
+                .dependencies
+                .retain(|dependency| is_supported(dependency.marker.as_ref()));
+            for dependencies in package.optional_dependencies.values_mut() {
+                dependencies.retain(|dependency| is_supported(dependency.marker.as_ref()));
+            }
+            for dependencies in package.dev_dependencies.values_mut() {
+                dependencies.retain(|dependency| is_supported(dependency.marker.as_ref()));
+            }
+        }
+
+        // Remove any packages that are only required on the pruned platforms.
+        let mut queue = reachable.iter().cloned().collect::<VecDeque<_>>();
+        while let Some(id) = queue.pop_front() {
+            let package = &self.packages[self.by_id[&id]];
+            for dependency in package
+                .dependencies
+                .iter()
+                .chain(package.optional_dependencies.values().flatten())
+                .chain(package.dev_dependencies.values().flatten())
+            {
+                if reachable.insert(dependency.package_id.clone()) {
+                    queue.push_back(dependency.package_id.clone());
+                }
+            }
+        }
+        self.packages
+            .retain(|package| reachable.contains(&package.id));
+        self.by_id = self
+            .packages
+            .iter()
+            .enumerate()
+            .map(|(index, package)| (package.id.clone(), index))
+            .collect();
+
+        if let Some(fork_markers) = self.fork_markers.as_mut() {
+            fork_markers.retain(|marker| is_supported(Some(marker)));
+            if fork_markers.is_empty() {
+                self.fork_markers = None;
+            }
+        }
+
+        self.options.prune_platforms = platforms;
+        self
+    }
+
+    /// Returns the platforms whose artifacts and dependencies were pruned from this lock.
+    pub fn pruned_platforms(&self) -> &[SysPlatform] {
+        &self.options.prune_platforms
     }
 
     /// Returns the TOML representation of this lockfile.
     pub fn to_toml(&self) -> anyhow::Result<String> {
+        let mut doc = toml_edit::DocumentMut::new();
+        *doc.as_table_mut() = self.to_toml_table()?;
+        Ok(doc.to_string())
+    }
+
+    /// Returns the TOML representation of this lockfile as a table, e.g., to embed it within
+    /// another document.
+    pub fn to_toml_table(&self) -> anyhow::Result<Table> {
         // We construct a TOML document manually instead of going through Serde to enable
         // the use of inline tables.
-        let mut doc = toml_edit::DocumentMut::new();
+        let mut doc = Table::new();
         doc.insert("version", value(i64::from(self.version)));
 
         if let Some(ref requires_python) = self.requires_python {
@@ -555,9 +1010,47 @@ impl Lock {
             if let Some(exclude_newer) = self.options.exclude_newer {
                 options_table.insert("exclude-newer", value(exclude_newer.to_string()));
             }
+            if self.options.index_strategy != IndexStrategy::default() {
+                options_table.insert(
+                    "index-strategy",
+                    value(self.options.index_strategy.to_string()),
+                );
+            }
+            if !self.options.indexes.is_empty() {
+                options_table.insert(
+                    "indexes",
+                    value(each_element_on_its_line_array(self.options.indexes.iter())),
+                );
+            }
+            if !self.options.override_dependencies.is_empty() {
+                options_table.insert(
+                    "override-dependencies",
+                    value(each_element_on_its_line_array(
+                        self.options.override_dependencies.iter(),
+                    )),
+                );
+            }
+            if !self.options.prune_platforms.is_empty() {
+                options_table.insert(
+                    "prune-platforms",
+                    value(Array::from_iter(
+                        self.options.prune_platforms.iter().map(ToString::to_string),
+                    )),
+                );
+            }
             doc.insert("options", Item::Table(options_table));
         }
 
+        // Write the digests of the build environment variables, such that changing the variables
+        // invalidates the affected packages.
+        if !self.build_env.is_empty() {
+            let mut build_env_table = Table::new();
+            for (name, digest) in &self.build_env {
+                build_env_table.insert(name.as_ref(), value(digest));
+            }
+            doc.insert("build-env", Item::Table(build_env_table));
+        }
+
         // Count the number of packages for each package name. When
         // there's only one package for a particular package name (the
         // overwhelmingly common case), we can omit some data (like source and
@@ -573,7 +1066,7 @@ impl Lock {
         }
 
         doc.insert("package", Item::ArrayOfTables(packages));
-        Ok(doc.to_string())
+        Ok(doc)
     }
 
     /// Returns the package with the given name. If there are multiple
@@ -669,6 +1162,19 @@ struct ResolverOptions {
     prerelease_mode: PrereleaseMode,
     /// The [`ExcludeNewer`] used to generate this lock.
     exclude_newer: Option<ExcludeNewer>,
+    /// The [`IndexStrategy`] used to generate this lock.
+    #[serde(default)]
+    index_strategy: IndexStrategy,
+    /// The redacted URLs of the indexes used to generate this lock, or empty if only the default
+    /// index was used.
+    #[serde(default)]
+    indexes: Vec<String>,
+    /// The overrides used to generate this lock.
+    #[serde(default)]
+    override_dependencies: Vec<String>,
+    /// The platforms whose artifacts and dependencies were pruned from this lock.
+    #[serde(default)]
+    prune_platforms: Vec<SysPlatform>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize)]
@@ -684,6 +1190,8 @@ struct LockWire {
     /// We discard the lockfile if these options match.
     #[serde(default)]
     options: ResolverOptions,
+    #[serde(default)]
+    build_env: BTreeMap<PackageName, String>,
     #[serde(rename = "package", alias = "distribution", default)]
     packages: Vec<PackageWire>,
 }
@@ -695,6 +1203,7 @@ impl From<Lock> for LockWire {
             requires_python: lock.requires_python,
             fork_markers: lock.fork_markers,
             options: lock.options,
+            build_env: lock.build_env,
             packages: lock.packages.into_iter().map(PackageWire::from).collect(),
         }
     }
@@ -726,13 +1235,15 @@ impl TryFrom<LockWire> for Lock {
             .into_iter()
             .map(|dist| dist.unwire(&unambiguous_package_ids))
             .collect::<Result<Vec<_>, _>>()?;
-        Lock::new(
+        let mut lock = Lock::new(
             wire.version,
             packages,
             wire.requires_python,
             wire.options,
             wire.fork_markers,
-        )
+        )?;
+        lock.build_env = wire.build_env;
+        Ok(lock)
     }
 }
 
@@ -750,6 +1261,10 @@ pub struct Package {
     dependencies: Vec<Dependency>,
     optional_dependencies: BTreeMap<ExtraName, Vec<Dependency>>,
     dev_dependencies: BTreeMap<GroupName, Vec<Dependency>>,
+    /// The locked requirements of the package's build environment.
+    ///
+    /// Only populated for workspace members that set `tool.uv.lock-build-requirements`.
+    build_dependencies: Vec<BuildDependency>,
 }
 
 impl Package {
@@ -768,6 +1283,7 @@ impl Package {
             dependencies: vec![],
             optional_dependencies: BTreeMap::default(),
             dev_dependencies: BTreeMap::default(),
+            build_dependencies: vec![],
         })
     }
 
@@ -812,8 +1328,18 @@ impl Package {
     }
 
     /// Convert the [`Package`] to a [`Dist`] that can be used in installation.
-    fn to_dist(&self, workspace_root: &Path, tags: &Tags) -> Result<Dist, LockError> {
-        if let Some(best_wheel_index) = self.find_best_wheel(tags) {
+    fn to_dist(
+        &self,
+        workspace_root: &Path,
+        tags: &Tags,
+        build_options: &BuildOptions,
+    ) -> Result<Dist, LockError> {
+        // If source distributions are preferred, ignore any wheels, as long as there's a source
+        // distribution to build.
+        let prefer_source =
+            self.sdist.is_some() && build_options.prefer_source_package(self.name());
+
+        if let Some(best_wheel_index) = self.find_best_wheel(tags).filter(|_| !prefer_source) {
             return match &self.id.source {
                 Source::Registry(url) => {
                     let wheels = self
@@ -1176,6 +1702,15 @@ impl Package {
             table.insert("dev-dependencies", Item::Table(dev_dependencies));
         }
 
+        if !self.build_dependencies.is_empty() {
+            let deps = each_element_on_its_line_array(
+                self.build_dependencies
+                    .iter()
+                    .map(|dep| dep.to_toml().into_inline_table()),
+            );
+            table.insert("build-dependencies", value(deps));
+        }
+
         if let Some(ref sdist) = self.sdist {
             table.insert("sdist", value(sdist.to_toml()?));
         }
@@ -1224,6 +1759,51 @@ impl Package {
         &self.id.version
     }
 
+    /// Returns the remote artifacts of the package (i.e., its wheels and source distribution
+    /// with a URL), along with their hashes.
+    ///
+    /// Archives that contain the package in a subdirectory are omitted, since the package can't be
+    /// built from the artifact alone.
+    pub fn remote_artifacts(&self) -> impl Iterator<Item = (&UrlString, Option<&HashDigest>)> {
+        let subdirectory = matches!(
+            self.id.source,
+            Source::Direct(
+                _,
+                DirectSource {
+                    subdirectory: Some(_)
+                }
+            )
+        );
+        self.wheels
+            .iter()
+            .filter_map(|wheel| match &wheel.url {
+                WheelWireSource::Url { url } => Some((url, wheel.hash.as_ref())),
+                WheelWireSource::Filename { .. } => None,
+            })
+            .chain(
+                self.sdist
+                    .iter()
+                    .filter_map(|sdist| Some((sdist.url()?, sdist.hash()))),
+            )
+            .filter(move |_| !subdirectory)
+            .map(|(url, hash)| (url, hash.map(|hash| &hash.0)))
+    }
+
+    /// Returns the names of the package's dependencies, including its optional and development
+    /// dependencies.
+    pub fn dependency_names(&self) -> impl Iterator<Item = &PackageName> {
+        self.dependencies
+            .iter()
+            .chain(self.optional_dependencies.values().flatten())
+            .chain(self.dev_dependencies.values().flatten())
+            .map(|dependency| &dependency.package_id.name)
+    }
+
+    /// Returns the locked requirements of the package's build environment.
+    pub fn build_dependencies(&self) -> &[BuildDependency] {
+        &self.build_dependencies
+    }
+
     pub fn fork_markers(&self) -> Option<&BTreeSet<MarkerTree>> {
         self.fork_markers.as_ref()
     }
@@ -1239,8 +1819,22 @@ impl Package {
         }
     }
 
+    /// Returns a description of the source of the [`Package`], as written to the lockfile (e.g.,
+    /// `registry+https://pypi.org/simple`).
+    pub fn source_description(&self) -> String {
+        self.id.source.to_string()
+    }
+
+    /// Returns the URL of the [`Package`], if it's a direct URL dependency.
+    pub fn direct_url(&self) -> Option<Url> {
+        match &self.id.source {
+            Source::Direct(url, _) => Some(url.to_url()),
+            _ => None,
+        }
+    }
+
     /// Returns all the hashes associated with this [`Package`].
-    fn hashes(&self) -> Vec<HashDigest> {
+    pub fn hashes(&self) -> Vec<HashDigest> {
         let mut hashes = Vec::new();
         if let Some(ref sdist) = self.sdist {
             if let Some(hash) = sdist.hash() {
@@ -1295,6 +1889,8 @@ struct PackageWire {
     optional_dependencies: BTreeMap<ExtraName, Vec<DependencyWire>>,
     #[serde(default)]
     dev_dependencies: BTreeMap<GroupName, Vec<DependencyWire>>,
+    #[serde(default)]
+    build_dependencies: Vec<BuildDependency>,
 }
 
 impl PackageWire {
@@ -1323,6 +1919,7 @@ impl PackageWire {
                 .into_iter()
                 .map(|(group, deps)| Ok((group, unwire_deps(deps)?)))
                 .collect::<Result<_, LockError>>()?,
+            build_dependencies: self.build_dependencies,
         })
     }
 }
@@ -1348,6 +1945,7 @@ impl From<Package> for PackageWire {
                 .into_iter()
                 .map(|(group, deps)| (group, wire_deps(deps)))
                 .collect(),
+            build_dependencies: dist.build_dependencies,
         }
     }
 }
@@ -2451,6 +3049,56 @@ impl From<Dependency> for DependencyWire {
     }
 }
 
+/// A pinned requirement of a package's build environment in a lockfile.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Ord, serde::Deserialize)]
+pub struct BuildDependency {
+    name: PackageName,
+    version: Version,
+}
+
+impl BuildDependency {
+    pub fn new(name: PackageName, version: Version) -> BuildDependency {
+        BuildDependency { name, version }
+    }
+
+    /// Returns the [`PackageName`] of the build dependency.
+    pub fn name(&self) -> &PackageName {
+        &self.name
+    }
+
+    /// Returns the locked [`Version`] of the build dependency.
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Returns a [`Requirement`] that pins the build dependency to its locked version.
+    pub fn to_requirement(&self) -> Requirement {
+        Requirement {
+            name: self.name.clone(),
+            extras: vec![],
+            marker: None,
+            source: RequirementSource::Registry {
+                specifier: VersionSpecifier::equals_version(self.version.clone()).into(),
+                index: None,
+            },
+            origin: None,
+        }
+    }
+
+    fn to_toml(&self) -> Table {
+        let mut table = Table::new();
+        table.insert("name", value(self.name.to_string()));
+        table.insert("version", value(self.version.to_string()));
+        table
+    }
+}
+
+impl std::fmt::Display for BuildDependency {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}=={}", self.name, self.version)
+    }
+}
+
 /// A single hash for a distribution artifact in a lockfile.
 ///
 /// A hash is encoded as a single TOML string in the format
@@ -2593,6 +3241,20 @@ enum LockErrorKind {
         /// entry.
         dependency: Dependency,
     },
+    /// An error that occurs when a requirement doesn't have a corresponding
+    /// package in the lockfile.
+    #[error("found requirement `{name}` with no locked package")]
+    MissingRequirement {
+        /// The name of the requirement.
+        name: PackageName,
+    },
+    /// An error that occurs when a requirement matches multiple packages in
+    /// the lockfile.
+    #[error("found requirement `{name}` with multiple locked packages")]
+    AmbiguousRequirement {
+        /// The name of the requirement.
+        name: PackageName,
+    },
     /// An error that occurs when a hash is expected (or not) for a particular
     /// artifact, but one was not found (or was).
     #[error("since the package `{id}` comes from a {source} dependency, a hash was {expected} but one was not found for {artifact_type}", source = id.source.name(), expected = if *expected { "expected" } else { "not expected" })]
@@ -2687,6 +3349,17 @@ enum LockErrorKind {
         /// The name of the dependency that is missing a `source` field.
         name: PackageName,
     },
+    /// An error that occurs when installing from a lock on a platform that
+    /// was pruned from it.
+    #[error(
+        "the lockfile doesn't support `{platform}`, as it was pruned with \
+         `--prune-platform {platform}`; run `uv lock --no-prune-platform` to \
+         include it"
+    )]
+    PrunedPlatform {
+        /// The platform that was pruned.
+        platform: SysPlatform,
+    },
 }
 
 /// An error that occurs when a source string could not be parsed.
@@ -2774,6 +3447,9 @@ pub struct TreeDisplay<'env> {
     package: Vec<PackageName>,
     /// Whether to de-duplicate the displayed dependencies.
     no_dedupe: bool,
+    /// The markers of the forks that contain each package, for packages that aren't included in
+    /// every fork.
+    fork_markers: FxHashMap<&'env PackageId, &'env BTreeSet<MarkerTree>>,
 }
 
 impl<'env> TreeDisplay<'env> {
@@ -2909,6 +3585,12 @@ impl<'env> TreeDisplay<'env> {
             .filter(|id| !non_roots.contains(*id))
             .collect::<Vec<_>>();
 
+        let fork_markers = lock
+            .packages
+            .iter()
+            .filter_map(|package| Some((&package.id, package.fork_markers.as_ref()?)))
+            .collect();
+
         Self {
             roots,
             dependencies,
@@ -2918,6 +3600,50 @@ impl<'env> TreeDisplay<'env> {
             prune,
             package,
             no_dedupe,
+            fork_markers,
+        }
+    }
+
+    /// Return the dependencies of the given package, excluding any pruned packages.
+    fn children(&'env self, id: &'env PackageId) -> Vec<Node<'env>> {
+        self.dependencies
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(|dep| Node::Dependency(dep.as_ref()))
+            .chain(
+                self.optional_dependencies
+                    .get(id)
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|(extra, deps)| {
+                        deps.iter()
+                            .map(move |dep| Node::OptionalDependency(extra, dep))
+                    }),
+            )
+            .chain(
+                self.dev_dependencies
+                    .get(id)
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|(group, deps)| {
+                        deps.iter().map(move |dep| Node::DevDependency(group, dep))
+                    }),
+            )
+            .filter(|dep| !self.prune.contains(&dep.package_id().name))
+            .collect()
+    }
+
+    /// Return the nodes from which the tree is rendered.
+    fn starts(&self) -> Vec<&'env PackageId> {
+        if self.package.is_empty() {
+            self.roots.clone()
+        } else {
+            let by_package: FxHashMap<_, _> = self.roots.iter().map(|id| (&id.name, *id)).collect();
+            self.package
+                .iter()
+                .filter_map(|package| by_package.get(package).copied())
+                .collect()
         }
     }
 
@@ -2963,33 +3689,7 @@ impl<'env> TreeDisplay<'env> {
             }
         }
 
-        let dependencies: Vec<Node<'env>> = self
-            .dependencies
-            .get(node.package_id())
-            .into_iter()
-            .flatten()
-            .map(|dep| Node::Dependency(dep.as_ref()))
-            .chain(
-                self.optional_dependencies
-                    .get(node.package_id())
-                    .into_iter()
-                    .flatten()
-                    .flat_map(|(extra, deps)| {
-                        deps.iter()
-                            .map(move |dep| Node::OptionalDependency(extra, dep))
-                    }),
-            )
-            .chain(
-                self.dev_dependencies
-                    .get(node.package_id())
-                    .into_iter()
-                    .flatten()
-                    .flat_map(|(group, deps)| {
-                        deps.iter().map(move |dep| Node::DevDependency(group, dep))
-                    }),
-            )
-            .filter(|dep| !self.prune.contains(&dep.package_id().name))
-            .collect::<Vec<_>>();
+        let dependencies = self.children(node.package_id());
 
         let mut lines = vec![line];
 
@@ -3121,6 +3821,452 @@ impl std::fmt::Display for TreeDisplay<'_> {
     }
 }
 
+impl<'env> TreeDisplay<'env> {
+    /// Collect the packages and edges displayed in the tree into a [`TreeGraph`].
+    ///
+    /// The depth limit, pruned packages, and selected packages are applied as in the rendered
+    /// tree, but each package appears once, regardless of de-duplication. If the tree is inverted,
+    /// each edge points from a dependency to the package that depends on it.
+    pub fn graph(&self) -> TreeGraph {
+        let mut ids: FxHashMap<&PackageId, usize> = FxHashMap::default();
+        let mut order: Vec<&PackageId> = Vec::new();
+        let mut edges = Vec::new();
+
+        // Visit the packages in breadth-first order, such that each package is reached at its
+        // minimum depth.
+        let mut queue: VecDeque<(&PackageId, usize)> = VecDeque::new();
+        for id in self.starts() {
+            if let Entry::Vacant(entry) = ids.entry(id) {
+                entry.insert(order.len());
+                order.push(id);
+                queue.push_back((id, 0));
+            }
+        }
+
+        while let Some((id, depth)) = queue.pop_front() {
+            if depth >= self.depth {
+                continue;
+            }
+            for child in self.children(id) {
+                let child_id = child.package_id();
+                if let Entry::Vacant(entry) = ids.entry(child_id) {
+                    entry.insert(order.len());
+                    order.push(child_id);
+                    queue.push_back((child_id, depth + 1));
+                }
+
+                let (dependency, extra, group) = match child {
+                    Node::Root(_) => continue,
+                    Node::Dependency(dep) => (dep, None, None),
+                    Node::OptionalDependency(extra, dep) => (dep, Some(extra.clone()), None),
+                    Node::DevDependency(group, dep) => (dep, None, Some(group.clone())),
+                };
+                edges.push(TreeGraphEdge {
+                    from: ids[id],
+                    to: ids[child_id],
+                    extras: dependency.extra.clone(),
+                    extra,
+                    group,
+                    marker: dependency
+                        .marker
+                        .as_ref()
+                        .and_then(MarkerTree::contents)
+                        .map(|marker| marker.to_string()),
+                });
+            }
+        }
+
+        // Identify each package by name and version, falling back to the source for packages
+        // that are locked at the same version from multiple sources.
+        let mut counts: FxHashMap<(&PackageName, &Version), usize> = FxHashMap::default();
+        for id in &order {
+            *counts.entry((&id.name, &id.version)).or_default() += 1;
+        }
+        let nodes = order
+            .iter()
+            .map(|id| TreeGraphNode {
+                id: if counts[&(&id.name, &id.version)] > 1 {
+                    id.to_string()
+                } else {
+                    format!("{}=={}", id.name, id.version)
+                },
+                name: id.name.clone(),
+                version: id.version.to_string(),
+                source: id.source.to_string(),
+                markers: self
+                    .fork_markers
+                    .get(id)
+                    .into_iter()
+                    .flat_map(|markers| markers.iter())
+                    .filter_map(MarkerTree::contents)
+                    .map(|marker| marker.to_string())
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
+        let edges = edges
+            .into_iter()
+            .map(|edge| TreeGraphEdge {
+                from: nodes[edge.from].id.clone(),
+                to: nodes[edge.to].id.clone(),
+                extras: edge.extras,
+                extra: edge.extra,
+                group: edge.group,
+                marker: edge.marker,
+            })
+            .collect();
+
+        TreeGraph { nodes, edges }
+    }
+}
+
+/// The packages and edges of a [`TreeDisplay`], for export to other tools.
+#[derive(Debug, serde::Serialize)]
+pub struct TreeGraph {
+    nodes: Vec<TreeGraphNode>,
+    edges: Vec<TreeGraphEdge<String>>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct TreeGraphNode {
+    /// The identifier of the package within the graph, e.g., `anyio==4.3.0`.
+    id: String,
+    name: PackageName,
+    version: String,
+    source: String,
+    /// The markers of the forks that contain the package, if it isn't included in every fork.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    markers: Vec<String>,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct TreeGraphEdge<T> {
+    from: T,
+    to: T,
+    /// The extras of the dependency that are enabled by the edge.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    extras: BTreeSet<ExtraName>,
+    /// The extra of the dependent package that includes the edge, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extra: Option<ExtraName>,
+    /// The development dependency group of the dependent package that includes the edge, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<GroupName>,
+    /// The markers under which the edge applies, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    marker: Option<String>,
+}
+
+impl TreeGraphNode {
+    /// The lines of a human-readable label for the package, e.g., `anyio v4.3.0`.
+    fn labels(&self) -> Vec<String> {
+        std::iter::once(format!("{} v{}", self.name, self.version))
+            .chain(self.markers.iter().cloned())
+            .collect()
+    }
+}
+
+impl<T> TreeGraphEdge<T> {
+    /// The lines of a human-readable label for the edge, e.g., `[socks]` and `extra: proxy`.
+    fn labels(&self) -> Vec<String> {
+        let mut labels = Vec::new();
+        if !self.extras.is_empty() {
+            labels.push(format!("[{}]", self.extras.iter().join(",")));
+        }
+        if let Some(extra) = &self.extra {
+            labels.push(format!("extra: {extra}"));
+        }
+        if let Some(group) = &self.group {
+            labels.push(format!("group: {group}"));
+        }
+        if let Some(marker) = &self.marker {
+            labels.push(marker.clone());
+        }
+        labels
+    }
+}
+
+impl TreeGraph {
+    /// Render the graph in the Graphviz DOT language.
+    pub fn to_dot(&self) -> String {
+        fn escape(label: &str) -> String {
+            label.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+
+        let mut output = String::from("digraph {\n");
+        for node in &self.nodes {
+            output.push_str(&format!(
+                "    \"{}\" [label=\"{}\"]\n",
+                escape(&node.id),
+                node.labels().iter().map(|label| escape(label)).join("\\n")
+            ));
+        }
+        for edge in &self.edges {
+            let labels = edge.labels();
+            output.push_str(&format!(
+                "    \"{}\" -> \"{}\"",
+                escape(&edge.from),
+                escape(&edge.to)
+            ));
+            if !labels.is_empty() {
+                output.push_str(&format!(
+                    " [label=\"{}\"]",
+                    labels.iter().map(|label| escape(label)).join("\\n")
+                ));
+            }
+            output.push('\n');
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Render the graph as a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        fn escape(label: &str) -> String {
+            label.replace('"', "#quot;")
+        }
+
+        // Mermaid identifiers can't contain most punctuation, so refer to nodes by index.
+        let index: FxHashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id.as_str(), index))
+            .collect();
+
+        let mut output = String::from("flowchart TD\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            output.push_str(&format!(
+                "    n{index}[\"{}\"]\n",
+                node.labels().iter().map(|label| escape(label)).join("<br>")
+            ));
+        }
+        for edge in &self.edges {
+            let labels = edge.labels();
+            let from = index[edge.from.as_str()];
+            let to = index[edge.to.as_str()];
+            if labels.is_empty() {
+                output.push_str(&format!("    n{from} --> n{to}\n"));
+            } else {
+                output.push_str(&format!(
+                    "    n{from} -->|\"{}\"| n{to}\n",
+                    labels.iter().map(|label| escape(label)).join("<br>")
+                ));
+            }
+        }
+        output
+    }
+}
+
+/// The version constraints declared by each package on its dependencies, keyed by the name of
+/// the dependent package and then by the name of the dependency.
+pub type DependencyConstraints = FxHashMap<PackageName, FxHashMap<PackageName, VersionSpecifiers>>;
+
+/// Display every dependency chain in a [`Lock`] that leads to a given package.
+#[derive(Debug)]
+pub struct WhyDisplay<'env> {
+    /// The locked versions of the requested package.
+    targets: Vec<&'env PackageId>,
+    /// The inverted edges in the [`Lock`], from each package to the packages that depend on it.
+    dependents: FxHashMap<&'env PackageId, Vec<Edge<'env>>>,
+    /// The markers to use when filtering the edges, if any.
+    markers: Option<&'env MarkerEnvironment>,
+    /// The version constraints declared on each edge, where known.
+    constraints: &'env DependencyConstraints,
+}
+
+impl<'env> WhyDisplay<'env> {
+    /// Create a new [`WhyDisplay`] for the given package.
+    pub fn new(
+        lock: &'env Lock,
+        markers: Option<&'env MarkerEnvironment>,
+        package: &PackageName,
+        constraints: &'env DependencyConstraints,
+    ) -> Self {
+        // Index the dependents of each package, across its dependencies, extras, and groups.
+        let mut dependents: FxHashMap<_, Vec<_>> = FxHashMap::default();
+        for parent in &lock.packages {
+            let edges = parent
+                .dependencies
+                .iter()
+                .map(|dependency| (dependency, EdgeKind::Dependency))
+                .chain(
+                    parent
+                        .optional_dependencies
+                        .iter()
+                        .flat_map(|(extra, dependencies)| {
+                            dependencies
+                                .iter()
+                                .map(move |dependency| (dependency, EdgeKind::Extra(extra)))
+                        }),
+                )
+                .chain(
+                    parent
+                        .dev_dependencies
+                        .iter()
+                        .flat_map(|(group, dependencies)| {
+                            dependencies
+                                .iter()
+                                .map(move |dependency| (dependency, EdgeKind::Group(group)))
+                        }),
+                );
+
+            for (dependency, kind) in edges {
+                dependents
+                    .entry(&dependency.package_id)
+                    .or_default()
+                    .push(Edge {
+                        parent: &parent.id,
+                        dependency,
+                        kind,
+                    });
+            }
+        }
+
+        let targets = lock
+            .packages
+            .iter()
+            .map(|dist| &dist.id)
+            .filter(|id| id.name == *package)
+            .collect();
+
+        Self {
+            targets,
+            dependents,
+            markers,
+            constraints,
+        }
+    }
+
+    /// Walk from the given package up to the roots of the [`Lock`], collecting every chain of
+    /// edges along the way.
+    fn visit(
+        &self,
+        id: &'env PackageId,
+        path: &mut Vec<Edge<'env>>,
+        chains: &mut Vec<Vec<Edge<'env>>>,
+    ) {
+        // If nothing depends on the package, it's a root, and the chain is complete.
+        let Some(edges) = self.dependents.get(id) else {
+            chains.push(path.iter().rev().copied().collect());
+            return;
+        };
+
+        for edge in edges {
+            // Skip dependencies that don't apply to the current environment.
+            if let Some(environment_markers) = self.markers {
+                if let Some(dependency_markers) = edge.dependency.marker.as_ref() {
+                    if !dependency_markers.evaluate(environment_markers, &[]) {
+                        continue;
+                    }
+                }
+            }
+
+            // Skip dependency cycles.
+            if edge.parent == id
+                || path.iter().any(|seen| {
+                    seen.parent == edge.parent || &seen.dependency.package_id == edge.parent
+                })
+            {
+                continue;
+            }
+
+            path.push(*edge);
+            self.visit(edge.parent, path, chains);
+            path.pop();
+        }
+    }
+
+    /// Render a single edge, along with its version constraint, extra or group, and markers.
+    fn render_edge(&self, edge: &Edge<'env>) -> String {
+        let mut line = format!("{}", edge.dependency.package_id.name);
+        if !edge.dependency.extra.is_empty() {
+            line.push_str(&format!("[{}]", edge.dependency.extra.iter().join(",")));
+        }
+        line.push_str(&format!(" v{}", edge.dependency.package_id.version));
+
+        let mut annotations = Vec::new();
+        if let Some(specifiers) = self
+            .constraints
+            .get(&edge.parent.name)
+            .and_then(|constraints| constraints.get(&edge.dependency.package_id.name))
+            .filter(|specifiers| !specifiers.is_empty())
+        {
+            annotations.push(format!("requires: {specifiers}"));
+        }
+        match edge.kind {
+            EdgeKind::Dependency => {}
+            EdgeKind::Extra(extra) => annotations.push(format!("extra: {extra}")),
+            EdgeKind::Group(group) => annotations.push(format!("group: {group}")),
+        }
+        if let Some(marker) = edge.dependency.marker.as_ref() {
+            annotations.push(format!("marker: {marker}"));
+        }
+
+        if annotations.is_empty() {
+            line
+        } else {
+            format!("{line} ({})", annotations.join(", "))
+        }
+    }
+
+    /// Render each dependency chain as a single line, from the root to the requested package.
+    fn render(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for id in &self.targets {
+            let mut chains = Vec::new();
+            self.visit(id, &mut Vec::new(), &mut chains);
+
+            for chain in chains {
+                let Some(root) = chain.first().map(|edge| edge.parent) else {
+                    // The package is itself a root (e.g., a workspace member).
+                    lines.push(format!("{} v{}", id.name, id.version));
+                    continue;
+                };
+
+                let mut line = format!("{} v{}", root.name, root.version);
+                for edge in &chain {
+                    line.push_str(" -> ");
+                    line.push_str(&self.render_edge(edge));
+                }
+                lines.push(line);
+            }
+        }
+
+        lines.sort();
+        lines.dedup();
+        lines
+    }
+}
+
+/// An edge from a package to one of its dependencies.
+#[derive(Debug, Copy, Clone)]
+struct Edge<'env> {
+    /// The package that declares the dependency.
+    parent: &'env PackageId,
+    /// The dependency itself.
+    dependency: &'env Dependency,
+    /// Whether the dependency is enabled by default, or by an extra or group.
+    kind: EdgeKind<'env>,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum EdgeKind<'env> {
+    Dependency,
+    Extra(&'env ExtraName),
+    Group(&'env GroupName),
+}
+
+impl std::fmt::Display for WhyDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for line in self.render() {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
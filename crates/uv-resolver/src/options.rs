@@ -10,6 +10,7 @@ pub struct Options {
     pub dependency_mode: DependencyMode,
     pub exclude_newer: Option<ExcludeNewer>,
     pub index_strategy: IndexStrategy,
+    pub shuffle: Option<u64>,
 }
 
 /// Builder for [`Options`].
@@ -20,6 +21,7 @@ pub struct OptionsBuilder {
     dependency_mode: DependencyMode,
     exclude_newer: Option<ExcludeNewer>,
     index_strategy: IndexStrategy,
+    shuffle: Option<u64>,
 }
 
 impl OptionsBuilder {
@@ -63,6 +65,14 @@ impl OptionsBuilder {
         self
     }
 
+    /// Sets the seed with which to shuffle the order in which candidate packages are considered,
+    /// rather than considering them in the order they're discovered.
+    #[must_use]
+    pub fn shuffle(mut self, shuffle: Option<u64>) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
     /// Builds the options.
     pub fn build(self) -> Options {
         Options {
@@ -71,6 +81,7 @@ impl OptionsBuilder {
             dependency_mode: self.dependency_mode,
             exclude_newer: self.exclude_newer,
             index_strategy: self.index_strategy,
+            shuffle: self.shuffle,
         }
     }
 }
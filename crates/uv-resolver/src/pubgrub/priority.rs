@@ -20,9 +20,22 @@ use crate::pubgrub::PubGrubPackageInner;
 ///
 /// See: <https://github.com/pypa/pip/blob/ef78c129b1a966dbbbdb8ebfffc43723e89110d1/src/pip/_internal/resolution/resolvelib/provider.py#L120>
 #[derive(Clone, Debug, Default)]
-pub(crate) struct PubGrubPriorities(FxHashMap<PackageName, PubGrubPriority>);
+pub(crate) struct PubGrubPriorities {
+    priorities: FxHashMap<PackageName, PubGrubPriority>,
+    /// The seed from which to derive a pseudo-random order for packages of equal priority, in lieu
+    /// of the order in which they were added, if any.
+    shuffle: Option<u64>,
+}
 
 impl PubGrubPriorities {
+    /// Create an empty priority map, shuffling packages of equal priority with the given seed.
+    pub(crate) fn new(shuffle: Option<u64>) -> Self {
+        Self {
+            priorities: FxHashMap::default(),
+            shuffle,
+        }
+    }
+
     /// Add a [`PubGrubPackage`] to the priority map.
     pub(crate) fn insert(
         &mut self,
@@ -30,14 +43,18 @@ impl PubGrubPriorities {
         version: &Range<Version>,
         urls: &ForkUrls,
     ) {
-        let next = self.0.len();
         // The root package and Python constraints have no explicit priority, the root package is
         // always first and the Python version (range) is fixed.
         let Some(name) = package.name_no_root() else {
             return;
         };
 
-        match self.0.entry(name.clone()) {
+        let next = match self.shuffle {
+            Some(seed) => shuffled_index(name, seed),
+            None => self.priorities.len(),
+        };
+
+        match self.priorities.entry(name.clone()) {
             std::collections::hash_map::Entry::Occupied(mut entry) => {
                 // Preserve the original index.
                 let index = match entry.get() {
@@ -82,14 +99,31 @@ impl PubGrubPriorities {
         match &**package {
             PubGrubPackageInner::Root(_) => Some(PubGrubPriority::Root),
             PubGrubPackageInner::Python(_) => Some(PubGrubPriority::Root),
-            PubGrubPackageInner::Marker { name, .. } => self.0.get(name).copied(),
-            PubGrubPackageInner::Extra { name, .. } => self.0.get(name).copied(),
-            PubGrubPackageInner::Dev { name, .. } => self.0.get(name).copied(),
-            PubGrubPackageInner::Package { name, .. } => self.0.get(name).copied(),
+            PubGrubPackageInner::Marker { name, .. } => self.priorities.get(name).copied(),
+            PubGrubPackageInner::Extra { name, .. } => self.priorities.get(name).copied(),
+            PubGrubPackageInner::Dev { name, .. } => self.priorities.get(name).copied(),
+            PubGrubPackageInner::Package { name, .. } => self.priorities.get(name).copied(),
         }
     }
 }
 
+/// Derive a pseudo-random index for the given package from the seed, such that a given seed
+/// always produces the same order.
+///
+/// Uses an FNV-1a hash of the package name, mixed with a SplitMix64 finalizer.
+#[allow(clippy::cast_possible_truncation)]
+fn shuffled_index(name: &PackageName, seed: u64) -> usize {
+    let mut z = name
+        .as_ref()
+        .bytes()
+        .fold(0xCBF2_9CE4_8422_2325 ^ seed, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+        });
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (z ^ (z >> 31)) as usize
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum PubGrubPriority {
     /// The package has no specific priority.
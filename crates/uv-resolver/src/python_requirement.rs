@@ -1,8 +1,10 @@
 use pep440_rs::{Version, VersionSpecifiers};
 use pep508_rs::MarkerTree;
+use pubgrub::Range;
 use uv_python::{Interpreter, PythonVersion};
 
-use crate::{RequiresPython, RequiresPythonBound};
+use crate::pubgrub::PubGrubSpecifier;
+use crate::{RequiresPython, RequiresPythonBound, RequiresPythonError};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct PythonRequirement {
@@ -14,6 +16,11 @@ pub struct PythonRequirement {
     ///
     /// If `None`, the target version is the same as the installed version.
     target: Option<PythonTarget>,
+    /// Whether to ignore the `Requires-Python` metadata of the candidate distributions.
+    ignore_requires_python: bool,
+    /// The range of Python versions to check the `Requires-Python` metadata of the candidate
+    /// distributions against, in lieu of the installed version.
+    requires_python_override: Option<Range<Version>>,
 }
 
 impl PythonRequirement {
@@ -25,6 +32,8 @@ impl PythonRequirement {
             target: Some(PythonTarget::Version(
                 python_version.python_full_version().only_release(),
             )),
+            ignore_requires_python: false,
+            requires_python_override: None,
         }
     }
 
@@ -37,6 +46,8 @@ impl PythonRequirement {
         Self {
             installed: interpreter.python_full_version().version.only_release(),
             target: Some(PythonTarget::RequiresPython(requires_python.clone())),
+            ignore_requires_python: false,
+            requires_python_override: None,
         }
     }
 
@@ -45,9 +56,42 @@ impl PythonRequirement {
         Self {
             installed: interpreter.python_full_version().version.only_release(),
             target: None,
+            ignore_requires_python: false,
+            requires_python_override: None,
         }
     }
 
+    /// Ignore the `Requires-Python` metadata of the candidate distributions, such that they're
+    /// considered compatible with any version of Python.
+    #[must_use]
+    pub fn ignore_requires_python(self) -> Self {
+        Self {
+            installed: self.installed,
+            target: None,
+            ignore_requires_python: true,
+            requires_python_override: None,
+        }
+    }
+
+    /// Check the `Requires-Python` metadata of the candidate distributions against the given
+    /// specifiers, rather than the installed (or target) version of Python, such that any
+    /// distribution that supports at least one version in the range is considered compatible.
+    ///
+    /// Unlike `--python-version`, the override is limited to the `Requires-Python` check: markers
+    /// continue to be evaluated against the installed interpreter.
+    pub fn with_requires_python_override(
+        self,
+        specifiers: &VersionSpecifiers,
+    ) -> Result<Self, RequiresPythonError> {
+        let range = Range::from(PubGrubSpecifier::from_release_specifiers(specifiers)?);
+        Ok(Self {
+            installed: self.installed,
+            target: None,
+            ignore_requires_python: false,
+            requires_python_override: Some(range),
+        })
+    }
+
     /// Narrow the [`PythonRequirement`] to the given version, if it's stricter (i.e., greater)
     /// than the current `Requires-Python` minimum.
     pub fn narrow(&self, target: &RequiresPythonBound) -> Option<Self> {
@@ -58,6 +102,8 @@ impl PythonRequirement {
         Some(Self {
             installed: self.installed.clone(),
             target: Some(PythonTarget::RequiresPython(requires_python)),
+            ignore_requires_python: self.ignore_requires_python,
+            requires_python_override: self.requires_python_override.clone(),
         })
     }
 
@@ -66,6 +112,22 @@ impl PythonRequirement {
         &self.installed
     }
 
+    /// Returns `true` if the installed version of Python satisfies the given `Requires-Python`
+    /// specifiers, or if `Requires-Python` is ignored.
+    ///
+    /// If an override was provided, the specifiers need only intersect with the override range.
+    pub fn installed_satisfies(&self, requires_python: &VersionSpecifiers) -> bool {
+        if self.ignore_requires_python {
+            return true;
+        }
+        if let Some(range) = self.requires_python_override.as_ref() {
+            return PubGrubSpecifier::from_release_specifiers(requires_python).is_ok_and(
+                |requires_python| !Range::from(requires_python).intersection(range).is_empty(),
+            );
+        }
+        requires_python.contains(&self.installed)
+    }
+
     /// Return the target version of Python.
     pub fn target(&self) -> Option<&PythonTarget> {
         self.target.as_ref()
@@ -11,7 +11,7 @@ use pep508_rs::MarkerTree;
 use uv_normalize::PackageName;
 
 use crate::resolution::{RequirementsTxtDist, ResolutionGraphNode};
-use crate::{ResolutionGraph, ResolverMarkers};
+use crate::{ConstraintEffects, ResolutionGraph, ResolverMarkers};
 
 static UNIVERSAL_MARKERS: ResolverMarkers = ResolverMarkers::Universal {
     fork_preferences: None,
@@ -38,6 +38,9 @@ pub struct DisplayResolutionGraph<'a> {
     include_annotations: bool,
     /// Whether to include indexes in the output, to indicate which index was used for each package.
     include_index_annotation: bool,
+    /// The effect of the constraints on the resolution, used to indicate which packages were
+    /// pinned by a constraint rather than by the dependency graph.
+    constraint_effects: Option<&'a ConstraintEffects>,
     /// The style of annotation comments, used to indicate the dependencies that requested each
     /// package.
     annotation_style: AnnotationStyle,
@@ -60,6 +63,7 @@ impl<'a> From<&'a ResolutionGraph> for DisplayResolutionGraph<'a> {
             false,
             true,
             false,
+            None,
             AnnotationStyle::default(),
         )
     }
@@ -77,6 +81,7 @@ impl<'a> DisplayResolutionGraph<'a> {
         include_markers: bool,
         include_annotations: bool,
         include_index_annotation: bool,
+        constraint_effects: Option<&'a ConstraintEffects>,
         annotation_style: AnnotationStyle,
     ) -> DisplayResolutionGraph<'a> {
         Self {
@@ -88,6 +93,7 @@ impl<'a> DisplayResolutionGraph<'a> {
             include_markers,
             include_annotations,
             include_index_annotation,
+            constraint_effects,
             annotation_style,
         }
     }
@@ -96,6 +102,9 @@ impl<'a> DisplayResolutionGraph<'a> {
 /// Write the graph in the `{name}=={version}` format of requirements.txt that pip uses.
 impl std::fmt::Display for DisplayResolutionGraph<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Determine the constraints that pinned each package, if requested.
+        let mut constrained_by = SourceAnnotations::default();
+
         // Determine the annotation sources for each package.
         let sources = if self.include_annotations {
             let mut sources = SourceAnnotations::default();
@@ -120,6 +129,18 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                 })
             {
                 if let Some(origin) = &requirement.origin {
+                    // If we're tracking the effect of each constraint, separate the constraints
+                    // that pinned the package from the dependency graph, and omit the constraints
+                    // that had no effect.
+                    if let Some(effects) = self.constraint_effects {
+                        if effects.constrained.contains(&requirement.name) {
+                            constrained_by.add(
+                                &requirement.name,
+                                SourceAnnotation::Constraint(origin.clone()),
+                            );
+                        }
+                        continue;
+                    }
                     sources.add(
                         &requirement.name,
                         SourceAnnotation::Constraint(origin.clone()),
@@ -278,6 +299,18 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                 writeln!(f, "{line}")?;
             }
 
+            // If enabled, include the constraints that pinned each package (e.g.,
+            // `# constrained-by -c constraints.txt`).
+            if let Some(constraints) = constrained_by.get(node.name()) {
+                for constraint in constraints {
+                    writeln!(
+                        f,
+                        "{}",
+                        format!("    # constrained-by {constraint}").green()
+                    )?;
+                }
+            }
+
             // If enabled, include indexes to indicate which index was used for each package (e.g.,
             // `# from https://pypi.org/simple`).
             if self.include_index_annotation {
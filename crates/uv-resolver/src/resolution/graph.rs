@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::BTreeSet;
 
 use indexmap::IndexSet;
@@ -9,12 +10,14 @@ use pubgrub::Range;
 use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
 
 use distribution_types::{
-    Dist, DistributionMetadata, Name, ResolutionDiagnostic, ResolvedDist, VersionId,
+    Dist, DistributionMetadata, IndexUrl, Name, ResolutionDiagnostic, ResolvedDist, VersionId,
     VersionOrUrlRef,
 };
-use pep440_rs::{Version, VersionSpecifier};
+use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
 use pep508_rs::{MarkerEnvironment, MarkerTree, MarkerTreeKind, VerbatimUrl};
-use pypi_types::{HashDigest, ParsedUrlError, Requirement, VerbatimParsedUrl, Yanked};
+use pypi_types::{
+    HashDigest, ParsedUrlError, Requirement, RequirementSource, VerbatimParsedUrl, Yanked,
+};
 use uv_configuration::{Constraints, Overrides};
 use uv_distribution::Metadata;
 use uv_git::GitResolver;
@@ -29,7 +32,7 @@ use crate::resolution_mode::ResolutionStrategy;
 use crate::resolver::{Resolution, ResolutionDependencyEdge, ResolutionPackage};
 use crate::{
     InMemoryIndex, MetadataResponse, Options, PythonRequirement, RequiresPython, ResolveError,
-    ResolverMarkers, VersionsResponse,
+    ResolverMarkers, VersionMap, VersionsResponse,
 };
 
 pub(crate) type MarkersForDistribution =
@@ -56,6 +59,8 @@ pub struct ResolutionGraph {
     pub(crate) overrides: Overrides,
     /// The options that were used to build the graph.
     pub(crate) options: Options,
+    /// The packages for which newer versions were excluded due to the `Requires-Python` range.
+    pub(crate) python_exclusions: Vec<PythonExclusion>,
     /// If there are multiple options for a package, track which fork they belong to so we
     /// can write that to the lockfile and later get the correct preference per fork back.
     pub(crate) package_markers: FxHashMap<PackageName, MarkersForDistribution>,
@@ -200,6 +205,21 @@ impl ResolutionGraph {
             report_missing_lower_bounds(&petgraph, &mut diagnostics);
         }
 
+        let python_exclusions = requires_python
+            .as_ref()
+            .map(|requires_python| {
+                find_python_exclusions(
+                    &petgraph,
+                    requirements,
+                    constraints,
+                    overrides,
+                    requires_python,
+                    preferences,
+                    index,
+                )
+            })
+            .unwrap_or_default();
+
         Ok(Self {
             petgraph,
             requires_python,
@@ -209,6 +229,7 @@ impl ResolutionGraph {
             constraints: constraints.clone(),
             overrides: overrides.clone(),
             options,
+            python_exclusions,
             fork_markers,
         })
     }
@@ -285,9 +306,13 @@ impl ResolutionGraph {
         // Validate the extra.
         if let Some(extra) = extra {
             if !metadata.provides_extras.contains(extra) {
+                let mut available = metadata.provides_extras.clone();
+                available.sort();
+                available.dedup();
                 diagnostics.push(ResolutionDiagnostic::MissingExtra {
                     dist: dist.clone(),
                     extra: extra.clone(),
+                    available,
                 });
             }
         }
@@ -475,11 +500,64 @@ impl ResolutionGraph {
         self.dists().any(|dist| dist.name() == name)
     }
 
+    /// Return the names of the packages in the graph that are only required transitively, i.e.,
+    /// that aren't required by the root of the resolution.
+    pub fn transitive_dependencies(&self) -> Vec<PackageName> {
+        let direct = self
+            .petgraph
+            .node_indices()
+            .filter(|index| matches!(self.petgraph[*index], ResolutionGraphNode::Root))
+            .flat_map(|index| self.petgraph.neighbors(index))
+            .filter_map(|index| match &self.petgraph[index] {
+                ResolutionGraphNode::Root => None,
+                ResolutionGraphNode::Dist(dist) => Some(dist.name()),
+            })
+            .collect::<FxHashSet<_>>();
+        self.dists()
+            .map(AnnotatedDist::name)
+            .filter(|name| !direct.contains(name))
+            .cloned()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Return the resolved version of the given package, if it's in the graph.
+    pub fn version(&self, name: &PackageName) -> Option<&Version> {
+        self.dists()
+            .find(|dist| dist.name() == name)
+            .map(|dist| &dist.version)
+    }
+
+    /// Return the index that served each resolved version of the given package.
+    ///
+    /// Versions that weren't resolved from an index (e.g., URL or path dependencies) are
+    /// represented as `None`.
+    pub fn indexes<'a>(
+        &'a self,
+        name: &'a PackageName,
+    ) -> impl Iterator<Item = Option<&'a IndexUrl>> + 'a {
+        self.dists()
+            .filter(move |dist| dist.is_base() && dist.name() == name)
+            .map(|dist| dist.dist.index())
+    }
+
     /// Return the [`ResolutionDiagnostic`]s that were encountered while building the graph.
     pub fn diagnostics(&self) -> &[ResolutionDiagnostic] {
         &self.diagnostics
     }
 
+    /// Return the supported Python range of the resolution, if any.
+    pub fn requires_python(&self) -> Option<&RequiresPython> {
+        self.requires_python.as_ref()
+    }
+
+    /// Return the packages for which newer versions were excluded from the resolution, as they
+    /// require a Python version outside of the supported range.
+    pub fn python_exclusions(&self) -> &[PythonExclusion] {
+        &self.python_exclusions
+    }
+
     /// Return the marker tree specific to this resolution.
     ///
     /// This accepts an in-memory-index and marker environment, all
@@ -646,6 +724,380 @@ impl ResolutionGraph {
             Some(&package_markers[&(version.clone(), url.cloned())])
         }
     }
+
+    /// Determine which constraints restricted the versions selected in the resolution, and which
+    /// had no effect on it.
+    ///
+    /// A version constraint is considered effective if it excluded an available version of the
+    /// package that every requirement on that package would otherwise have permitted. A URL
+    /// constraint is considered effective whenever the package is present in the resolution.
+    pub fn constraint_effects(&self, index: &InMemoryIndex) -> ConstraintEffects {
+        let mut effects = ConstraintEffects::default();
+
+        for constraint in self.constraints.requirements() {
+            let resolved = self
+                .dists()
+                .filter(|dist| dist.is_base() && dist.name() == &constraint.name)
+                .map(|dist| &dist.version)
+                .collect::<Vec<_>>();
+
+            // If the package isn't in the resolution, the constraint can't have affected it.
+            if resolved.is_empty() {
+                effects.unused.push(constraint.clone());
+                continue;
+            }
+
+            let RequirementSource::Registry { specifier, .. } = &constraint.source else {
+                effects.constrained.insert(constraint.name.clone());
+                continue;
+            };
+
+            // Collect the specifiers imposed by the requirements on the package, i.e., everything
+            // but the constraints.
+            let requirements = self.overrides.apply(
+                self.requirements.iter().chain(
+                    self.dists()
+                        .flat_map(|dist| dist.metadata.requires_dist.iter()),
+                ),
+            );
+            let specifiers = requirements
+                .filter(|requirement| requirement.name == constraint.name)
+                .filter_map(|requirement| match &requirement.source {
+                    RequirementSource::Registry { specifier, .. } => Some(specifier.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            // Pre-releases are only candidates if one was selected.
+            let prereleases = resolved.iter().any(|version| version.any_prerelease());
+
+            let excluded = index
+                .packages()
+                .get(&constraint.name)
+                .is_some_and(|response| {
+                    let VersionsResponse::Found(version_maps) = &*response else {
+                        return false;
+                    };
+                    version_maps
+                        .iter()
+                        .flat_map(VersionMap::iter)
+                        .map(|(version, _)| version)
+                        .filter(|version| prereleases || !version.any_prerelease())
+                        .filter(|version| {
+                            specifiers
+                                .iter()
+                                .all(|specifier| specifier.contains(version))
+                        })
+                        .any(|version| !specifier.contains(version))
+                });
+
+            if excluded {
+                effects.constrained.insert(constraint.name.clone());
+            } else {
+                effects.unused.push(constraint.clone());
+            }
+        }
+
+        effects.unused.sort_by(|a, b| a.name.cmp(&b.name));
+
+        effects
+    }
+
+    /// Determine the packages to omit from the resolution, given the packages that are provided
+    /// externally (e.g., by the platform).
+    ///
+    /// In addition to the given packages, any packages that are only reachable through them are
+    /// pruned, such that the remaining packages form a closed set of requirements, apart from the
+    /// omitted packages themselves.
+    pub fn omit(&self, packages: &[PackageName]) -> OmittedPackages {
+        let mut omitted = OmittedPackages::default();
+        if packages.is_empty() {
+            return omitted;
+        }
+
+        // Find all packages that are reachable from the root without passing through an omitted
+        // package.
+        let mut reachable = FxHashSet::default();
+        let mut queue = self
+            .petgraph
+            .node_indices()
+            .filter(|index| matches!(self.petgraph[*index], ResolutionGraphNode::Root))
+            .collect::<Vec<_>>();
+        let mut visited = queue.iter().copied().collect::<FxHashSet<_>>();
+        while let Some(index) = queue.pop() {
+            for neighbor in self.petgraph.neighbors(index) {
+                let ResolutionGraphNode::Dist(dist) = &self.petgraph[neighbor] else {
+                    continue;
+                };
+                if packages.contains(dist.name()) {
+                    continue;
+                }
+                reachable.insert(dist.name());
+                if visited.insert(neighbor) {
+                    queue.push(neighbor);
+                }
+            }
+        }
+
+        for index in self.petgraph.node_indices() {
+            let ResolutionGraphNode::Dist(dist) = &self.petgraph[index] else {
+                continue;
+            };
+            if !dist.is_base() || reachable.contains(dist.name()) {
+                continue;
+            }
+
+            // Determine the (retained) packages that depend on the omitted package.
+            let mut required_by = self
+                .petgraph
+                .neighbors_directed(index, Direction::Incoming)
+                .filter_map(|neighbor| match &self.petgraph[neighbor] {
+                    ResolutionGraphNode::Root => None,
+                    ResolutionGraphNode::Dist(dist) => Some(dist.name()),
+                })
+                .filter(|name| {
+                    if packages.contains(dist.name()) {
+                        reachable.contains(name)
+                    } else {
+                        !reachable.contains(name)
+                    }
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            required_by.sort();
+            required_by.dedup();
+
+            let package = OmittedPackage {
+                name: dist.name().clone(),
+                version: dist.version.clone(),
+                required_by,
+            };
+            if packages.contains(dist.name()) {
+                omitted.omitted.push(package);
+            } else {
+                omitted.pruned.push(package);
+            }
+        }
+
+        omitted
+            .omitted
+            .sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        omitted
+            .pruned
+            .sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+        omitted
+    }
+
+    /// Verify that the packages that remain after [`ResolutionGraph::omit`] form a closed set,
+    /// i.e., that every requirement of a remaining package is satisfied by a remaining or
+    /// omitted package.
+    ///
+    /// The requirements are re-evaluated from the package metadata (after applying any
+    /// overrides), rather than from the edges of the graph, such that pruning can't drop a
+    /// package that's still required. Returns the unsatisfied requirements, along with the
+    /// packages that declare them.
+    pub fn verify_omitted(
+        &self,
+        omitted: &OmittedPackages,
+        marker_env: Option<&MarkerEnvironment>,
+    ) -> Vec<(PackageName, Requirement)> {
+        // Without transitive dependencies, the output is not expected to be closed.
+        if self.options.dependency_mode.is_direct() {
+            return Vec::new();
+        }
+
+        let pruned = omitted
+            .pruned
+            .iter()
+            .map(|package| &package.name)
+            .collect::<FxHashSet<_>>();
+        let explicit = omitted
+            .omitted
+            .iter()
+            .map(|package| &package.name)
+            .collect::<FxHashSet<_>>();
+
+        // Collect the versions that are available to satisfy a requirement.
+        let mut versions: FxHashMap<&PackageName, Vec<&Version>> = FxHashMap::default();
+        for index in self.petgraph.node_indices() {
+            let ResolutionGraphNode::Dist(dist) = &self.petgraph[index] else {
+                continue;
+            };
+            if !pruned.contains(dist.name()) {
+                versions.entry(dist.name()).or_default().push(&dist.version);
+            }
+        }
+
+        let requires_python = self
+            .requires_python
+            .as_ref()
+            .map(RequiresPython::to_marker_tree);
+
+        let mut unsatisfied = Vec::new();
+        for index in self.petgraph.node_indices() {
+            let ResolutionGraphNode::Dist(dist) = &self.petgraph[index] else {
+                continue;
+            };
+            if pruned.contains(dist.name()) || explicit.contains(dist.name()) {
+                continue;
+            }
+
+            let extras = dist.extra.iter().cloned().collect::<Vec<_>>();
+            let requirements = match &dist.dev {
+                Some(group) => dist
+                    .metadata
+                    .dev_dependencies
+                    .get(group)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                None => dist.metadata.requires_dist.as_slice(),
+            };
+            for requirement in self.overrides.apply(requirements) {
+                if !requirement.evaluate_markers(marker_env, &extras) {
+                    continue;
+                }
+
+                // In a universal resolution, skip any requirements that can't apply within the
+                // supported Python versions.
+                if marker_env.is_none() {
+                    if let (Some(marker), Some(requires_python)) =
+                        (&requirement.marker, &requires_python)
+                    {
+                        if marker.is_disjoint(requires_python) {
+                            continue;
+                        }
+                    }
+                }
+
+                let satisfied = versions.get(&requirement.name).is_some_and(|versions| {
+                    match &requirement.source {
+                        RequirementSource::Registry { specifier, .. } => {
+                            versions.iter().any(|version| specifier.contains(version))
+                        }
+                        _ => true,
+                    }
+                });
+                if !satisfied {
+                    unsatisfied.push((dist.name().clone(), requirement.into_owned()));
+                }
+            }
+        }
+
+        unsatisfied.sort_by(|(a, a_req), (b, b_req)| (a, &a_req.name).cmp(&(b, &b_req.name)));
+        unsatisfied.dedup_by(|(a, a_req), (b, b_req)| a == b && a_req == b_req);
+        unsatisfied
+    }
+}
+
+/// The effect of the constraints on a [`ResolutionGraph`].
+#[derive(Debug, Default)]
+pub struct ConstraintEffects {
+    /// The packages whose selected version was restricted by a constraint.
+    pub(crate) constrained: FxHashSet<PackageName>,
+    /// The constraints that had no effect on the resolution.
+    pub(crate) unused: Vec<Requirement>,
+}
+
+impl ConstraintEffects {
+    /// Return the constraints that had no effect on the resolution.
+    pub fn unused(&self) -> &[Requirement] {
+        &self.unused
+    }
+}
+
+/// The packages omitted from a [`ResolutionGraph`], as computed by [`ResolutionGraph::omit`].
+#[derive(Debug, Default)]
+pub struct OmittedPackages {
+    /// The packages that were omitted explicitly.
+    pub(crate) omitted: Vec<OmittedPackage>,
+    /// The packages that were pruned, as they're only required by omitted packages.
+    pub(crate) pruned: Vec<OmittedPackage>,
+}
+
+impl OmittedPackages {
+    /// Return the packages that were omitted explicitly.
+    pub fn omitted(&self) -> &[OmittedPackage] {
+        &self.omitted
+    }
+
+    /// Return the packages that were pruned, as they're only required by omitted packages.
+    pub fn pruned(&self) -> &[OmittedPackage] {
+        &self.pruned
+    }
+
+    /// Return the names of all omitted and pruned packages.
+    pub fn names(&self) -> impl Iterator<Item = &PackageName> {
+        self.omitted
+            .iter()
+            .chain(&self.pruned)
+            .map(|package| &package.name)
+    }
+}
+
+/// A package omitted from a [`ResolutionGraph`].
+#[derive(Debug, Clone)]
+pub struct OmittedPackage {
+    /// The name of the package.
+    pub(crate) name: PackageName,
+    /// The version of the package that was selected.
+    pub(crate) version: Version,
+    /// For an omitted package, the retained packages that depend on it; for a pruned package,
+    /// the omitted or pruned packages that depend on it.
+    pub(crate) required_by: Vec<PackageName>,
+}
+
+impl OmittedPackage {
+    /// Return the name of the package.
+    pub fn name(&self) -> &PackageName {
+        &self.name
+    }
+
+    /// Return the version of the package that was selected.
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Return the packages that depend on the package.
+    pub fn required_by(&self) -> &[PackageName] {
+        &self.required_by
+    }
+}
+
+/// A package for which newer versions were excluded from a [`ResolutionGraph`], as they require a
+/// Python version outside of the supported range.
+#[derive(Debug, Clone)]
+pub struct PythonExclusion {
+    /// The name of the package.
+    pub(crate) name: PackageName,
+    /// The version of the package that was selected.
+    pub(crate) selected: Version,
+    /// The newest version of the package that was excluded.
+    pub(crate) excluded: Version,
+    /// The `Requires-Python` of the excluded version.
+    pub(crate) requires_python: VersionSpecifiers,
+}
+
+impl PythonExclusion {
+    /// Return the name of the package.
+    pub fn name(&self) -> &PackageName {
+        &self.name
+    }
+
+    /// Return the version of the package that was selected.
+    pub fn selected(&self) -> &Version {
+        &self.selected
+    }
+
+    /// Return the newest version of the package that was excluded.
+    pub fn excluded(&self) -> &Version {
+        &self.excluded
+    }
+
+    /// Return the `Requires-Python` of the excluded version.
+    pub fn requires_python(&self) -> &VersionSpecifiers {
+        &self.requires_python
+    }
 }
 
 impl From<ResolutionGraph> for distribution_types::Resolution {
@@ -664,6 +1116,185 @@ impl From<ResolutionGraph> for distribution_types::Resolution {
     }
 }
 
+impl From<&ResolutionGraph> for distribution_types::Resolution {
+    fn from(graph: &ResolutionGraph) -> Self {
+        Self::new(
+            graph
+                .dists()
+                .map(|node| (node.name().clone(), node.dist.clone()))
+                .collect(),
+            graph
+                .dists()
+                .map(|node| (node.name().clone(), node.hashes.clone()))
+                .collect(),
+            graph.diagnostics.clone(),
+        )
+    }
+}
+
+/// Find any packages for which newer versions were excluded due to their `Requires-Python`, i.e.,
+/// for which every newer version permitted by the requirements on the package requires a Python
+/// version outside of the supported range.
+///
+/// Only direct dependencies are considered: those named by the requirements themselves, or by any
+/// local project among them (e.g., a workspace member), since these are the packages that the user
+/// can act on.
+///
+/// Packages pinned to a preferred version (e.g., from an existing lockfile) are skipped, since the
+/// newer versions may not have been considered at all.
+fn find_python_exclusions(
+    petgraph: &Graph<ResolutionGraphNode, Option<MarkerTree>>,
+    requirements: &[Requirement],
+    constraints: &Constraints,
+    overrides: &Overrides,
+    requires_python: &RequiresPython,
+    preferences: &Preferences,
+    index: &InMemoryIndex,
+) -> Vec<PythonExclusion> {
+    let dists = petgraph
+        .node_weights()
+        .filter_map(|node| match node {
+            ResolutionGraphNode::Root => None,
+            ResolutionGraphNode::Dist(dist) => Some(dist),
+        })
+        .collect::<Vec<_>>();
+
+    // Determine the direct dependencies, including those of any local projects (e.g., workspace
+    // members) among the requirements.
+    let direct = requirements
+        .iter()
+        .chain(
+            dists
+                .iter()
+                .filter(|dist| {
+                    requirements.iter().any(|requirement| {
+                        requirement.name == *dist.name()
+                            && !matches!(requirement.source, RequirementSource::Registry { .. })
+                    })
+                })
+                .flat_map(|dist| {
+                    dist.metadata
+                        .requires_dist
+                        .iter()
+                        .chain(dist.metadata.dev_dependencies.values().flatten())
+                }),
+        )
+        .map(|requirement| &requirement.name)
+        .collect::<FxHashSet<_>>();
+
+    let mut exclusions = Vec::new();
+    for dist in &dists {
+        if !dist.is_base() {
+            continue;
+        }
+        let name = dist.name();
+        if !direct.contains(name) {
+            continue;
+        }
+
+        // If the package was selected at multiple versions (e.g., in different forks), only
+        // consider the newest.
+        if dists
+            .iter()
+            .any(|other| other.name() == name && other.version > dist.version)
+        {
+            continue;
+        }
+
+        if preferences
+            .get(name)
+            .any(|(_, version)| *version == dist.version)
+        {
+            continue;
+        }
+
+        let Some(response) = index.packages().get(name) else {
+            continue;
+        };
+        let VersionsResponse::Found(version_maps) = &*response else {
+            continue;
+        };
+
+        // Collect the specifiers imposed on the package by its requirements and constraints.
+        let specifiers = overrides
+            .apply(
+                requirements.iter().chain(
+                    dists
+                        .iter()
+                        .flat_map(|dist| dist.metadata.requires_dist.iter()),
+                ),
+            )
+            .chain(
+                constraints
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .map(Cow::Borrowed),
+            )
+            .filter(|requirement| requirement.name == *name)
+            .filter_map(|requirement| match &requirement.source {
+                RequirementSource::Registry { specifier, .. } => Some(specifier.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Pre-releases are only candidates if one was selected.
+        let prereleases = dist.version.any_prerelease();
+
+        // Determine the `Requires-Python` of each newer version that the requirements permit.
+        let newer = version_maps
+            .iter()
+            .flat_map(VersionMap::iter)
+            .filter(|(version, _)| **version > dist.version)
+            .filter(|(version, _)| prereleases || !version.any_prerelease())
+            .filter(|(version, _)| {
+                specifiers
+                    .iter()
+                    .all(|specifier| specifier.contains(version))
+            })
+            .map(|(version, handle)| {
+                let requires_python = handle.prioritized_dist().and_then(|prioritized| {
+                    prioritized
+                        .best_wheel()
+                        .and_then(|(wheel, _)| wheel.file.requires_python.clone())
+                        .or_else(|| {
+                            prioritized
+                                .source_dist()
+                                .and_then(|sdist| sdist.file.requires_python.clone())
+                        })
+                });
+                (version, requires_python)
+            })
+            .collect::<Vec<_>>();
+
+        // Only report the newest version if every newer version was excluded due to its
+        // `Requires-Python`; otherwise, the package was narrowed for some other reason.
+        if !newer.iter().all(|(_, version_requires_python)| {
+            version_requires_python
+                .as_ref()
+                .is_some_and(|specifiers| !requires_python.is_contained_by(specifiers))
+        }) {
+            continue;
+        }
+        let Some((excluded, Some(version_requires_python))) =
+            newer.into_iter().max_by_key(|(version, _)| *version)
+        else {
+            continue;
+        };
+
+        exclusions.push(PythonExclusion {
+            name: name.clone(),
+            selected: dist.version.clone(),
+            excluded: excluded.clone(),
+            requires_python: version_requires_python,
+        });
+    }
+
+    exclusions.sort_by(|a, b| a.name.cmp(&b.name));
+
+    exclusions
+}
+
 /// Find any packages that don't have any lower bound on them when in resolution-lowest mode.
 fn report_missing_lower_bounds(
     petgraph: &Graph<ResolutionGraphNode, Option<MarkerTree>>,
@@ -7,8 +7,10 @@ use uv_distribution::Metadata;
 use uv_normalize::{ExtraName, GroupName, PackageName};
 
 pub use crate::resolution::display::{AnnotationStyle, DisplayResolutionGraph};
-pub use crate::resolution::graph::ResolutionGraph;
 pub(crate) use crate::resolution::graph::ResolutionGraphNode;
+pub use crate::resolution::graph::{
+    ConstraintEffects, OmittedPackage, OmittedPackages, PythonExclusion, ResolutionGraph,
+};
 pub(crate) use crate::resolution::requirements_txt::RequirementsTxtDist;
 
 mod display;
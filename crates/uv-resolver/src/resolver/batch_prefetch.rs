@@ -143,7 +143,8 @@ impl BatchPrefetcher {
             match dist {
                 CompatibleDist::InstalledDist(_) => {}
                 CompatibleDist::SourceDist { sdist, .. }
-                | CompatibleDist::IncompatibleWheel { sdist, .. } => {
+                | CompatibleDist::IncompatibleWheel { sdist, .. }
+                | CompatibleDist::PreferredSource { sdist, .. } => {
                     // Source distributions must meet both the _target_ Python version and the
                     // _installed_ Python version (to build successfully).
                     if let Some(requires_python) = sdist.file.requires_python.as_ref() {
@@ -152,7 +153,7 @@ impl BatchPrefetcher {
                                 continue;
                             }
                         }
-                        if !requires_python.contains(python_requirement.installed()) {
+                        if !python_requirement.installed_satisfies(requires_python) {
                             continue;
                         }
                     }
@@ -165,7 +166,7 @@ impl BatchPrefetcher {
                                 continue;
                             }
                         } else {
-                            if !requires_python.contains(python_requirement.installed()) {
+                            if !python_requirement.installed_satisfies(requires_python) {
                                 continue;
                             }
                         }
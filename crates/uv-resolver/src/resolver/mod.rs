@@ -22,8 +22,8 @@ use tracing::{debug, info, instrument, trace, warn, Level};
 
 use distribution_types::{
     BuiltDist, CompatibleDist, Dist, DistributionMetadata, IncompatibleDist, IncompatibleSource,
-    IncompatibleWheel, IndexLocations, InstalledDist, PythonRequirementKind, RemoteSource,
-    ResolvedDist, ResolvedDistRef, SourceDist, VersionOrUrlRef,
+    IncompatibleWheel, IndexLocations, IndexUrl, InstalledDist, PythonRequirementKind,
+    RemoteSource, ResolvedDist, ResolvedDistRef, SourceDist, VersionOrUrlRef,
 };
 pub(crate) use fork_map::{ForkMap, ForkSet};
 use locals::Locals;
@@ -67,6 +67,8 @@ pub use crate::resolver::provider::{
 };
 use crate::resolver::reporter::Facade;
 pub use crate::resolver::reporter::{BuildId, Reporter};
+pub use crate::resolver::trace::ResolverTrace;
+use crate::resolver::trace::{TraceDependency, TraceEvent};
 use crate::yanks::AllowedYanks;
 use crate::{marker, DependencyMode, Exclusions, FlatIndex, Options};
 
@@ -79,6 +81,7 @@ mod locals;
 mod provider;
 mod reporter;
 mod resolver_markers;
+mod trace;
 mod urls;
 
 pub struct Resolver<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider> {
@@ -122,6 +125,8 @@ struct ResolverState<InstalledPackages: InstalledPackagesProvider> {
     options: Options,
     /// The reporter to use for this resolver.
     reporter: Option<Arc<dyn Reporter>>,
+    /// The trace to which resolver decisions are written, if any.
+    trace: Option<ResolverTrace>,
 }
 
 impl<'a, Context: BuildContext, InstalledPackages: InstalledPackagesProvider>
@@ -244,6 +249,7 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
             incomplete_packages: DashMap::default(),
             options,
             reporter: None,
+            trace: None,
         };
         Ok(Self { state, provider })
     }
@@ -262,6 +268,18 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
         }
     }
 
+    /// Set the [`ResolverTrace`] to which resolver decisions should be written.
+    #[must_use]
+    pub fn with_trace(self, trace: ResolverTrace) -> Self {
+        Self {
+            state: ResolverState {
+                trace: Some(trace),
+                ..self.state
+            },
+            provider: self.provider,
+        }
+    }
+
     /// Resolve a set of requirements into a set of pinned versions.
     pub async fn resolve(self) -> Result<ResolutionGraph, ResolveError> {
         let state = Arc::new(self.state);
@@ -292,6 +310,9 @@ impl<Provider: ResolverProvider, InstalledPackages: InstalledPackagesProvider>
         // Wait for both to complete.
         let ((), resolution) = tokio::try_join!(requests_fut, resolve_fut)?;
         state.on_complete();
+        if let Some(trace) = &state.trace {
+            trace.flush();
+        }
         resolution
     }
 }
@@ -322,6 +343,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
             self.markers.clone(),
             self.python_requirement.clone(),
             self.requires_python.clone(),
+            self.options.shuffle,
         );
         let mut preferences = self.preferences.clone();
         let mut forked_states = if let ResolverMarkers::Universal {
@@ -338,6 +360,9 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
         let mut resolutions = vec![];
 
         'FORK: while let Some(mut state) = forked_states.pop() {
+            self.trace(|| TraceEvent::Fork {
+                markers: state.markers.to_string(),
+            });
             if let ResolverMarkers::Fork(markers) = &state.markers {
                 if let Some(requires_python) = state.requires_python.as_ref() {
                     debug!(
@@ -352,6 +377,9 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
             loop {
                 // Run unit propagation.
                 if let Err(err) = state.pubgrub.unit_propagation(state.next.clone()) {
+                    self.trace(|| TraceEvent::Conflict {
+                        markers: state.markers.to_string(),
+                    });
                     return Err(self.convert_no_solution_err(
                         err,
                         state.fork_urls,
@@ -360,6 +388,18 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                         &index_locations,
                     ));
                 }
+                self.trace(|| TraceEvent::Propagate {
+                    package: state.next.to_string(),
+                    constraints: state
+                        .pubgrub
+                        .partial_solution
+                        .prioritized_packages()
+                        .map(|(package, range)| TraceDependency {
+                            package: package.to_string(),
+                            range: range.to_string(),
+                        })
+                        .collect(),
+                });
 
                 // Pre-visit all candidate packages, to allow metadata to be fetched in parallel.
                 Self::pre_visit(
@@ -421,6 +461,10 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                     }
 
                     Self::trace_resolution(&resolution);
+                    self.trace(|| TraceEvent::Solved {
+                        markers: resolution.markers.to_string(),
+                        packages: resolution.nodes.len(),
+                    });
                     resolutions.push(resolution);
                     continue 'FORK;
                 };
@@ -470,6 +514,11 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                             .term_intersection_for_package(&state.next)
                             .expect("a package was chosen but we don't have a term");
 
+                        self.trace(|| TraceEvent::NoVersions {
+                            package: state.next.to_string(),
+                            range: term_intersection.unwrap_positive().to_string(),
+                        });
+
                         // Check if the decision was due to the package being unavailable
                         if let PubGrubPackageInner::Package { ref name, .. } = &*state.next {
                             if let Some(entry) = self.unavailable_packages.get(name) {
@@ -497,11 +546,34 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                 let version = match version {
                     ResolverVersion::Available(version) => version,
                     ResolverVersion::Unavailable(version, reason) => {
+                        self.trace(|| TraceEvent::Unavailable {
+                            package: state.next.to_string(),
+                            version: version.to_string(),
+                            reason: reason.to_string(),
+                        });
                         state.add_unavailable_version(version, reason)?;
                         continue;
                     }
                 };
 
+                self.trace(|| TraceEvent::Select {
+                    package: state.next.to_string(),
+                    range: term_intersection.unwrap_positive().to_string(),
+                    version: version.to_string(),
+                });
+
+                // If we've previously added dependencies for other versions of this package, then
+                // the resolver must have backtracked over those decisions.
+                if let Some(previous) = state.added_dependencies.get(&state.next) {
+                    if !previous.is_empty() && !previous.contains(&version) {
+                        self.trace(|| TraceEvent::Backtrack {
+                            package: state.next.to_string(),
+                            version: version.to_string(),
+                            previous: previous.iter().map(ToString::to_string).collect(),
+                        });
+                    }
+                }
+
                 // Only consider registry packages for prefetch.
                 if url.is_none() {
                     prefetcher.prefetch_batches(
@@ -557,6 +629,17 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                             ));
                     }
                     ForkedDependencies::Unforked(dependencies) => {
+                        self.trace(|| TraceEvent::Dependencies {
+                            package: state.next.to_string(),
+                            version: version.to_string(),
+                            dependencies: dependencies
+                                .iter()
+                                .map(|dependency| TraceDependency {
+                                    package: dependency.package.to_string(),
+                                    range: dependency.version.to_string(),
+                                })
+                                .collect(),
+                        });
                         state.add_package_version_dependencies(
                             for_package.as_deref(),
                             &version,
@@ -588,6 +671,10 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                             state.markers,
                             start.elapsed().as_secs_f32()
                         );
+                        self.trace(|| TraceEvent::Split {
+                            package: state.next.to_string(),
+                            forks: forks.len(),
+                        });
 
                         for new_fork_state in self.forks_to_fork_states(
                             state,
@@ -632,6 +719,15 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
         )
     }
 
+    /// Record an event in the resolver trace, if one was requested.
+    ///
+    /// The event is constructed lazily, to avoid formatting costs when tracing is disabled.
+    fn trace(&self, event: impl FnOnce() -> TraceEvent) {
+        if let Some(trace) = &self.trace {
+            trace.record(&event());
+        }
+    }
+
     /// When trace level logging is enabled, we dump the final
     /// unioned resolution, including markers, to help with
     /// debugging. Namely, this tells use precisely the state
@@ -956,7 +1052,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                     )));
                 }
             }
-            if !requires_python.contains(python_requirement.installed()) {
+            if !python_requirement.installed_satisfies(requires_python) {
                 return Ok(Some(ResolverVersion::Unavailable(
                     version.clone(),
                     UnavailableVersion::IncompatibleDist(IncompatibleDist::Source(
@@ -1043,7 +1139,8 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
         let incompatibility = match dist {
             CompatibleDist::InstalledDist(_) => None,
             CompatibleDist::SourceDist { sdist, .. }
-            | CompatibleDist::IncompatibleWheel { sdist, .. } => {
+            | CompatibleDist::IncompatibleWheel { sdist, .. }
+            | CompatibleDist::PreferredSource { sdist, .. } => {
                 // Source distributions must meet both the _target_ Python version and the
                 // _installed_ Python version (to build successfully).
                 sdist
@@ -1061,7 +1158,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                                 ));
                             }
                         }
-                        if !requires_python.contains(python_requirement.installed()) {
+                        if !python_requirement.installed_satisfies(requires_python) {
                             return Some(IncompatibleDist::Source(
                                 IncompatibleSource::RequiresPython(
                                     requires_python.clone(),
@@ -1089,7 +1186,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                                 ));
                             }
                         } else {
-                            if !requires_python.contains(python_requirement.installed()) {
+                            if !python_requirement.installed_satisfies(requires_python) {
                                 return Some(IncompatibleDist::Wheel(
                                     IncompatibleWheel::RequiresPython(
                                         requires_python.clone(),
@@ -1139,11 +1236,14 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
         if matches!(&**package, PubGrubPackageInner::Package { .. }) {
             if self.index.distributions().register(candidate.version_id()) {
                 // Verify that the package is allowed under the hash-checking policy.
-                if !self
-                    .hasher
-                    .allows_package(candidate.name(), candidate.version())
-                {
-                    return Err(ResolveError::UnhashedPackage(candidate.name().clone()));
+                if !self.hasher.allows_package(
+                    candidate.name(),
+                    candidate.version(),
+                    dist.for_resolution().index(),
+                ) {
+                    return Err(
+                        self.unhashed_package(candidate.name(), dist.for_resolution().index())
+                    );
                 }
 
                 let request = Request::from(dist.for_resolution());
@@ -1154,6 +1254,17 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
         Ok(Some(ResolverVersion::Available(version)))
     }
 
+    /// Return the error for a package that isn't allowed under the hash-checking policy, given
+    /// the index that serves it.
+    fn unhashed_package(&self, name: &PackageName, index: Option<&IndexUrl>) -> ResolveError {
+        match index.filter(|index| self.hasher.index_policy(index).is_some()) {
+            Some(index) => {
+                ResolveError::UnhashedIndexPackage(name.clone(), index.redacted().into_owned())
+            }
+            None => ResolveError::UnhashedPackage(name.clone()),
+        }
+    }
+
     /// Given a candidate package and version, return its dependencies.
     #[instrument(skip_all, fields(%package, %version))]
     fn get_dependencies_forking(
@@ -1814,7 +1925,8 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                 match dist {
                     CompatibleDist::InstalledDist(_) => {}
                     CompatibleDist::SourceDist { sdist, .. }
-                    | CompatibleDist::IncompatibleWheel { sdist, .. } => {
+                    | CompatibleDist::IncompatibleWheel { sdist, .. }
+                    | CompatibleDist::PreferredSource { sdist, .. } => {
                         // Source distributions must meet both the _target_ Python version and the
                         // _installed_ Python version (to build successfully).
                         if let Some(requires_python) = sdist.file.requires_python.as_ref() {
@@ -1823,7 +1935,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                                     return Ok(None);
                                 }
                             }
-                            if !requires_python.contains(python_requirement.installed()) {
+                            if !python_requirement.installed_satisfies(requires_python) {
                                 return Ok(None);
                             }
                         }
@@ -1836,7 +1948,7 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                                     return Ok(None);
                                 }
                             } else {
-                                if !requires_python.contains(python_requirement.installed()) {
+                                if !python_requirement.installed_satisfies(requires_python) {
                                     return Ok(None);
                                 }
                             }
@@ -1847,11 +1959,14 @@ impl<InstalledPackages: InstalledPackagesProvider> ResolverState<InstalledPackag
                 // Emit a request to fetch the metadata for this version.
                 if self.index.distributions().register(candidate.version_id()) {
                     // Verify that the package is allowed under the hash-checking policy.
-                    if !self
-                        .hasher
-                        .allows_package(candidate.name(), candidate.version())
-                    {
-                        return Err(ResolveError::UnhashedPackage(candidate.name().clone()));
+                    if !self.hasher.allows_package(
+                        candidate.name(),
+                        candidate.version(),
+                        dist.for_resolution().index(),
+                    ) {
+                        return Err(
+                            self.unhashed_package(candidate.name(), dist.for_resolution().index())
+                        );
                     }
 
                     let dist = dist.for_resolution().to_owned();
@@ -2068,13 +2183,14 @@ impl ForkState {
         markers: ResolverMarkers,
         python_requirement: PythonRequirement,
         requires_python: Option<MarkerTree>,
+        shuffle: Option<u64>,
     ) -> Self {
         Self {
             pubgrub,
             next: root,
             pins: FilePins::default(),
             fork_urls: ForkUrls::default(),
-            priorities: PubGrubPriorities::default(),
+            priorities: PubGrubPriorities::new(shuffle),
             added_dependencies: FxHashMap::default(),
             markers,
             python_requirement,
@@ -0,0 +1,113 @@
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::Serialize;
+use tracing::debug;
+
+/// A structured trace of the decisions made by the resolver, written as JSON lines.
+///
+/// Each line is a single [`TraceEvent`], tagged by its `event` field, along with the number of
+/// milliseconds elapsed since the resolution started. The trace is intended for offline analysis
+/// of pathological resolutions, e.g., attached to a bug report.
+pub struct ResolverTrace {
+    writer: Mutex<BufWriter<fs_err::File>>,
+    start: Instant,
+}
+
+impl ResolverTrace {
+    /// Create a trace that writes to the file at the given path, truncating it if it exists.
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = fs_err::File::create(path.as_ref())?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+            start: Instant::now(),
+        })
+    }
+
+    /// Append an event to the trace.
+    ///
+    /// Tracing is best-effort: failures to write are logged, but never fail the resolution.
+    pub(crate) fn record(&self, event: &TraceEvent) {
+        #[derive(Serialize)]
+        struct Line<'a> {
+            elapsed_ms: u128,
+            #[serde(flatten)]
+            event: &'a TraceEvent,
+        }
+
+        let line = Line {
+            elapsed_ms: self.start.elapsed().as_millis(),
+            event,
+        };
+        let mut writer = self.writer.lock().unwrap();
+        if let Err(err) = serde_json::to_writer(&mut *writer, &line)
+            .map_err(std::io::Error::from)
+            .and_then(|()| writer.write_all(b"\n"))
+        {
+            debug!("Failed to write resolver trace event: {err}");
+        }
+    }
+
+    /// Flush any buffered events to disk.
+    pub(crate) fn flush(&self) {
+        if let Err(err) = self.writer.lock().unwrap().flush() {
+            debug!("Failed to flush resolver trace: {err}");
+        }
+    }
+}
+
+/// A dependency, as recorded in the trace.
+#[derive(Debug, Serialize)]
+pub(crate) struct TraceDependency {
+    pub(crate) package: String,
+    pub(crate) range: String,
+}
+
+/// A single resolver decision.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub(crate) enum TraceEvent {
+    /// The resolver started solving a fork (or the root resolution, if not forking).
+    Fork { markers: String },
+    /// Unit propagation derived the given constraints from the decision for a package (or from
+    /// the root requirements), i.e., the ranges that the undecided packages must now satisfy.
+    Propagate {
+        package: String,
+        constraints: Vec<TraceDependency>,
+    },
+    /// A version was selected for a package, from the candidates in the given range.
+    Select {
+        package: String,
+        range: String,
+        version: String,
+    },
+    /// A package was revisited with a different version after a conflict forced the resolver to
+    /// undo earlier decisions.
+    Backtrack {
+        package: String,
+        version: String,
+        previous: Vec<String>,
+    },
+    /// No version of a package satisfied the given range.
+    NoVersions { package: String, range: String },
+    /// The selected version of a package can't be used (e.g., no compatible distributions).
+    Unavailable {
+        package: String,
+        version: String,
+        reason: String,
+    },
+    /// The dependencies of a selected version were added as constraints.
+    Dependencies {
+        package: String,
+        version: String,
+        dependencies: Vec<TraceDependency>,
+    },
+    /// A package's dependencies diverged across markers, splitting the resolution.
+    Split { package: String, forks: usize },
+    /// The resolver found a conflict that it could not resolve in a fork.
+    Conflict { markers: String },
+    /// A fork was solved.
+    Solved { markers: String, packages: usize },
+}
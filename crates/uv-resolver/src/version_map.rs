@@ -98,6 +98,7 @@ impl VersionMap {
                 simple_metadata,
                 no_binary: build_options.no_binary_package(package_name),
                 no_build: build_options.no_build_package(package_name),
+                prefer_source: build_options.prefer_source_package(package_name),
                 index: index.clone(),
                 tags: tags.cloned(),
                 allowed_yanks: allowed_yanks.clone(),
@@ -273,6 +274,8 @@ struct VersionMapLazy {
     no_binary: bool,
     /// When true, source dists aren't allowed.
     no_build: bool,
+    /// When true, source dists are preferred over compatible wheels for installation.
+    prefer_source: bool,
     /// The URL of the index where this package came from.
     index: IndexUrl,
     /// The set of compatibility tags that determines whether a wheel is usable
@@ -337,6 +340,7 @@ impl VersionMapLazy {
                 .deserialize(&mut SharedDeserializeMap::new())
                 .expect("archived version files should deserialize");
             let mut priority_dist = init.cloned().unwrap_or_default();
+            priority_dist.set_prefer_source(self.prefer_source);
             for (filename, file) in files.all() {
                 // Support resolving as if it were an earlier timestamp, at least as long files have
                 // upload time information.
@@ -9,6 +9,7 @@ use thiserror::Error;
 
 use pep508_rs::PackageName;
 use pypi_types::VerbatimParsedUrl;
+use uv_configuration::ModulePath;
 use uv_settings::{GlobalOptions, ResolverInstallerOptions};
 use uv_workspace::pyproject::Source;
 
@@ -82,6 +83,8 @@ pub struct ToolUv {
     #[serde(flatten)]
     pub top_level: ResolverInstallerOptions,
     pub sources: Option<BTreeMap<PackageName, Source>>,
+    #[serde(rename = "module-path")]
+    pub module_path: Option<Vec<ModulePath>>,
 }
 
 #[derive(Debug, Error)]
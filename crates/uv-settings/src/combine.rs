@@ -1,9 +1,13 @@
+use std::collections::BTreeMap;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
-use distribution_types::IndexUrl;
+use distribution_types::{IndexHashPolicies, IndexMirrors, IndexResolve, IndexUrl};
 use install_wheel_rs::linker::LinkMode;
-use uv_configuration::{ConfigSettings, IndexStrategy, KeyringProviderType, TargetTriple};
+use pep440_rs::VersionSpecifiers;
+use uv_configuration::{
+    ConfigSettings, IndexStrategy, IpVersion, KeyringProviderType, MacosArch, TargetTriple,
+};
 use uv_python::{PythonDownloads, PythonPreference, PythonVersion};
 use uv_resolver::{AnnotationStyle, ExcludeNewer, PrereleaseMode, ResolutionMode};
 
@@ -68,10 +72,14 @@ macro_rules! impl_combine_or {
 
 impl_combine_or!(AnnotationStyle);
 impl_combine_or!(ExcludeNewer);
+impl_combine_or!(IndexHashPolicies);
+impl_combine_or!(IndexResolve);
 impl_combine_or!(IndexStrategy);
 impl_combine_or!(IndexUrl);
+impl_combine_or!(IpVersion);
 impl_combine_or!(KeyringProviderType);
 impl_combine_or!(LinkMode);
+impl_combine_or!(MacosArch);
 impl_combine_or!(NonZeroUsize);
 impl_combine_or!(PathBuf);
 impl_combine_or!(PrereleaseMode);
@@ -79,6 +87,7 @@ impl_combine_or!(PythonVersion);
 impl_combine_or!(ResolutionMode);
 impl_combine_or!(String);
 impl_combine_or!(TargetTriple);
+impl_combine_or!(VersionSpecifiers);
 impl_combine_or!(PythonPreference);
 impl_combine_or!(PythonDownloads);
 impl_combine_or!(bool);
@@ -97,6 +106,20 @@ impl<T> Combine for Option<Vec<T>> {
     }
 }
 
+impl<K: Ord, V> Combine for Option<BTreeMap<K, V>> {
+    /// Combine two maps by merging the map in `self` with the map in `other`, if they're both
+    /// `Some`, preferring the values in `self`.
+    fn combine(self, other: Option<BTreeMap<K, V>>) -> Option<BTreeMap<K, V>> {
+        match (self, other) {
+            (Some(a), Some(mut b)) => {
+                b.extend(a);
+                Some(b)
+            }
+            (a, b) => a.or(b),
+        }
+    }
+}
+
 impl Combine for Option<ConfigSettings> {
     /// Combine two maps by merging the map in `self` with the map in `other`, if they're both
     /// `Some`.
@@ -108,6 +131,18 @@ impl Combine for Option<ConfigSettings> {
     }
 }
 
+impl Combine for Option<IndexMirrors> {
+    /// Combine two sets of mirrors by merging the mirrors in `self` with those in `other`, if
+    /// they're both `Some`, preferring the mirrors in `self` for any index that's configured in
+    /// both.
+    fn combine(self, other: Option<IndexMirrors>) -> Option<IndexMirrors> {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.merge(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
 impl Combine for serde::de::IgnoredAny {
     fn combine(self, _other: Self) -> Self {
         self
@@ -160,6 +160,23 @@ impl From<Options> for FilesystemOptions {
     }
 }
 
+/// Returns the path to the user-level constraints file (e.g., `~/.config/uv/constraints.txt`), if
+/// it exists.
+///
+/// The constraints are applied to resolutions in the `pip` interface, tools, and scripts, but not to
+/// project lockfiles.
+pub fn user_constraints_file() -> Option<PathBuf> {
+    let file = config_dir()?.join("uv").join("constraints.txt");
+
+    debug!("Searching for user constraints in: `{}`", file.display());
+    if file.is_file() {
+        debug!("Found user constraints in: `{}`", file.display());
+        Some(file)
+    } else {
+        None
+    }
+}
+
 /// Returns the path to the user configuration directory.
 ///
 /// This is similar to the `config_dir()` returned by the `dirs` crate, but it uses the
@@ -1,13 +1,19 @@
-use std::{fmt::Debug, num::NonZeroUsize, path::PathBuf};
+use std::{collections::BTreeMap, fmt::Debug, num::NonZeroUsize, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use distribution_types::{FlatIndexLocation, IndexUrl};
+use distribution_types::{
+    FlatIndexLocation, IndexHashPolicies, IndexMirrors, IndexResolve, IndexUrl,
+};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ExcludePattern;
+use pep440_rs::VersionSpecifiers;
 use pep508_rs::Requirement;
 use pypi_types::VerbatimParsedUrl;
+use uv_cache::CacheBucket;
 use uv_configuration::{
-    ConfigSettings, IndexStrategy, KeyringProviderType, PackageNameSpecifier, TargetTriple,
+    ConfigSettings, IndexCredentialHelper, IndexStrategy, IndexVerification, IpVersion,
+    KeyringProviderType, MacosArch, PackageNameSpecifier, TargetTriple,
 };
 use uv_macros::{CombineOptions, OptionsMetadata};
 use uv_normalize::{ExtraName, PackageName};
@@ -49,6 +55,8 @@ pub struct Options {
     )]
     pub override_dependencies: Option<Vec<Requirement<VerbatimParsedUrl>>>,
     pub constraint_dependencies: Option<Vec<Requirement<VerbatimParsedUrl>>>,
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    pub build_env: Option<BTreeMap<PackageName, BTreeMap<String, String>>>,
 
     // NOTE(charlie): These fields should be kept in-sync with `ToolUv` in
     // `crates/uv-workspace/src/pyproject.rs`.
@@ -64,9 +72,57 @@ pub struct Options {
     #[cfg_attr(feature = "schemars", schemars(skip))]
     dev_dependencies: serde::de::IgnoredAny,
 
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    dev_tools: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    lock_build_requirements: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    test_pythons: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    test_command: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    test_groups: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    envs: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    pypackages: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    scripts: serde::de::IgnoredAny,
+
     #[serde(default, skip_serializing)]
     #[cfg_attr(feature = "schemars", schemars(skip))]
     managed: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    requires_implementation: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    module_path: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    editor_config: serde::de::IgnoredAny,
+
+    #[serde(default, skip_serializing)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    lock_backups: serde::de::IgnoredAny,
 }
 
 impl Options {
@@ -86,6 +142,20 @@ impl Options {
 #[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GlobalOptions {
+    /// Enforce a requirement on the version of uv.
+    ///
+    /// If the version of uv does not meet the requirement at runtime, uv will exit with an error.
+    /// Accepts a [PEP 440](https://peps.python.org/pep-0440/) specifier, like `==0.5.0` or
+    /// `>=0.5.0`.
+    #[option(
+        default = "null",
+        value_type = "str",
+        example = r#"
+            required-version = ">=0.5.0"
+        "#
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub required_version: Option<VersionSpecifiers>,
     /// Whether to load TLS certificates from the platform's native certificate store.
     ///
     /// By default, uv loads certificates from the bundled `webpki-roots` crate. The
@@ -103,6 +173,114 @@ pub struct GlobalOptions {
         "#
     )]
     pub native_tls: Option<bool>,
+    /// Only connect to hosts over the given IP version (`"4"` or `"6"`).
+    ///
+    /// By default, uv connects over whichever addresses a host resolves to. On networks with
+    /// broken IPv6 connectivity, connections to hosts that publish IPv6 (`AAAA`) records can
+    /// stall until they time out; with `ip-version = "4"`, those records are ignored.
+    #[option(
+        default = "None",
+        value_type = "str",
+        example = r#"
+            ip-version = "4"
+        "#
+    )]
+    pub ip_version: Option<IpVersion>,
+    /// Fixed addresses to connect to for the hosts of package indexes, bypassing DNS resolution,
+    /// keyed by the URL of the index.
+    ///
+    /// Useful on networks with split-horizon DNS, where an index's hostname doesn't resolve to a
+    /// reachable address. The port is taken from the index URL.
+    #[option(
+        default = "{}",
+        value_type = "dict",
+        example = r#"
+            index-resolve = { "https://pypi.internal/simple" = "10.0.0.5" }
+        "#
+    )]
+    pub index_resolve: Option<IndexResolve>,
+    /// Whether to negotiate HTTP/2 with servers that support it.
+    ///
+    /// With HTTP/2, concurrent requests to the same host (e.g., Simple API fetches) are
+    /// multiplexed over a single connection. By default, uv only uses HTTP/1.1, as some proxies
+    /// mishandle multiplexed connections.
+    #[option(
+        default = "false",
+        value_type = "bool",
+        example = r#"
+            http2 = true
+        "#
+    )]
+    pub http2: Option<bool>,
+    /// The maximum number of idle connections to keep open per host.
+    ///
+    /// Set to `0` to disable connection reuse, e.g., for proxies that drop idle connections
+    /// without closing them.
+    #[option(
+        default = "20",
+        value_type = "int",
+        example = r#"
+            http-pool-max-idle-per-host = 4
+        "#
+    )]
+    pub http_pool_max_idle_per_host: Option<usize>,
+    /// The number of seconds after which an idle connection is closed.
+    #[option(
+        default = "90",
+        value_type = "int",
+        example = r#"
+            http-pool-idle-timeout = 30
+        "#
+    )]
+    pub http_pool_idle_timeout: Option<u64>,
+    /// The interval (in seconds) at which to send TCP keep-alive probes on idle connections.
+    ///
+    /// By default, TCP keep-alive is disabled.
+    #[option(
+        default = "None",
+        value_type = "int",
+        example = r#"
+            http-tcp-keepalive = 60
+        "#
+    )]
+    pub http_tcp_keepalive: Option<u64>,
+    /// Verify index responses against the TUF metadata published by each index, per
+    /// [PEP 458](https://peps.python.org/pep-0458/).
+    ///
+    /// With `if-available`, the pages of indexes that publish TUF metadata must match their signed
+    /// targets, files that aren't signed are ignored, and the hashes of downloaded distributions
+    /// are always validated. With `required`, indexes that don't publish TUF metadata are
+    /// rejected.
+    ///
+    /// The first root metadata of each index is trusted on first use, and persisted outside the
+    /// cache.
+    #[option(
+        default = "\"disabled\"",
+        value_type = "str",
+        example = r#"
+            index-verification = "required"
+        "#
+    )]
+    pub index_verification: Option<IndexVerification>,
+    /// External commands that provide credentials for package indexes, such as short-lived
+    /// tokens for AWS CodeArtifact or Google Artifact Registry.
+    ///
+    /// Each command is run (without a shell) the first time credentials are needed for requests
+    /// to its index, and should print a token to `stdout`. The token is paired with the given
+    /// `username` (or `__token__`, if omitted) and reused for the remainder of the invocation.
+    /// If the index rejects the token (e.g., as it expired), the command is run again.
+    ///
+    /// Credential helpers take precedence over credentials from the netrc file and the keyring.
+    #[option(
+        default = "[]",
+        value_type = "list[dict]",
+        example = r#"
+            credential-helpers = [
+                { url = "https://example-111122223333.d.codeartifact.us-east-1.amazonaws.com/pypi/example/simple/", command = ["aws", "codeartifact", "get-authorization-token", "--domain", "example", "--query", "authorizationToken", "--output", "text"], username = "aws" },
+            ]
+        "#
+    )]
+    pub credential_helpers: Option<Vec<IndexCredentialHelper>>,
     /// Disable network access, relying only on locally cached data and locally available files.
     #[option(
         default = "false",
@@ -134,6 +312,62 @@ pub struct GlobalOptions {
         "#
     )]
     pub cache_dir: Option<PathBuf>,
+    /// Paths at which to store individual cache buckets, rather than within the cache directory.
+    ///
+    /// For example, the `archive` bucket (which contains the unzipped wheels that are linked into
+    /// virtual environments) can be placed on the same filesystem as your environments to allow
+    /// for hard links, while the `wheels` and `simple` buckets (which contain downloads and index
+    /// metadata) are placed elsewhere.
+    ///
+    /// The available buckets are `wheels`, `source-distributions`, `flat-index`, `git`,
+    /// `interpreter`, `simple`, `archive`, `builds`, `environments`, and `python`.
+    #[option(
+        default = "{}",
+        value_type = "dict",
+        example = r#"
+            cache-buckets = { archive = "/mnt/fast/uv-archive" }
+        "#
+    )]
+    pub cache_buckets: Option<BTreeMap<CacheBucket, PathBuf>>,
+    /// Path to the directory in which to stage build environments and extracted archives.
+    ///
+    /// Defaults to the cache directory, or to the system temporary directory when `no-cache` is
+    /// enabled. Useful when either is located on a small filesystem (e.g., a `tmpfs`), since
+    /// large wheels can require several times their size in free space while being unpacked.
+    #[option(
+        default = "None",
+        value_type = "str",
+        example = r#"
+            staging-dir = "/scratch/uv"
+        "#
+    )]
+    pub staging_dir: Option<PathBuf>,
+    /// The maximum number of tools that `uv tool upgrade --all` will upgrade concurrently.
+    ///
+    /// When upgrading multiple tools concurrently, the per-package output of each upgrade is
+    /// omitted in favor of a summary of the outcome for each tool.
+    ///
+    /// Defaults to the number of available CPU cores.
+    #[option(
+        default = "None",
+        value_type = "int",
+        example = r#"
+            concurrent-tool-upgrades = 4
+        "#
+    )]
+    pub concurrent_tool_upgrades: Option<NonZeroUsize>,
+    /// The maximum number of seconds to wait for another uv process to release its lock on an
+    /// environment (e.g., when an editor and a terminal both sync the same project).
+    ///
+    /// By default, uv waits indefinitely, reporting the ID of the process holding the lock.
+    #[option(
+        default = "None",
+        value_type = "int",
+        example = r#"
+            lock-timeout = 300
+        "#
+    )]
+    pub lock_timeout: Option<u64>,
     /// Whether to enable experimental, preview features.
     #[option(
         default = "false",
@@ -164,6 +398,76 @@ pub struct GlobalOptions {
         possible_values = true
     )]
     pub python_downloads: Option<PythonDownloads>,
+    /// Whether to fall back to a Python build that runs under emulation if there's no build for
+    /// the architecture of the running process.
+    ///
+    /// For example, when enabled, `uv python install` on Windows on ARM installs an x86-64 build
+    /// if there's no ARM64 build for the requested version. Applies to automatic downloads as well,
+    /// e.g., when creating a virtual environment. Requests that specify an architecture (e.g.,
+    /// `cpython-3.12-windows-x86_64-none`) are never substituted.
+    #[option(
+        default = "false",
+        value_type = "bool",
+        example = r#"
+            python-install-allow-emulated = true
+        "#
+    )]
+    pub python_install_allow_emulated: Option<bool>,
+    /// The SHA-256 checksums that managed Python downloads must match, keyed by installation
+    /// (e.g., `cpython-3.12.4-linux-x86_64-gnu`).
+    ///
+    /// When uv downloads a Python version with a pinned checksum, whether via `uv python install`
+    /// or automatically, the archive must match the pinned checksum in addition to the checksum that uv knows for the download.
+    /// Useful for restricting installations to a set of audited builds, e.g., when the downloads
+    /// are served from a mirror via `UV_PYTHON_INSTALL_MIRROR`.
+    #[option(
+        default = "{}",
+        value_type = "dict",
+        example = r#"
+            python-install-checksums = { "cpython-3.12.4-linux-x86_64-gnu" = "a3a05a0bc9a8e3f9f1c7b6b4d0f5c6b4a7c1d5e0f7c0cbe0a2f3c3c6c8b6a1d2" }
+        "#
+    )]
+    pub python_install_checksums: Option<BTreeMap<String, String>>,
+    /// Whether to retain the `ensurepip` module in managed Python installations.
+    ///
+    /// When disabled, `uv python install` removes `ensurepip`, such that `pip` can't be
+    /// bootstrapped into the installation (or into virtual environments created with
+    /// `python -m venv`).
+    #[option(
+        default = "true",
+        value_type = "bool",
+        example = r#"
+            python-install-ensurepip = false
+        "#
+    )]
+    pub python_install_ensurepip: Option<bool>,
+    /// Path to a Python module to install as `sitecustomize` in managed Python installations.
+    ///
+    /// The module is imported during the startup of every interpreter that uses the
+    /// installation, including those of virtual environments created from it. `uv python install`
+    /// refuses to overwrite a `sitecustomize` module that it didn't install.
+    #[option(
+        default = "None",
+        value_type = "str",
+        example = r#"
+            python-install-sitecustomize = "/etc/uv/sitecustomize.py"
+        "#
+    )]
+    pub python_install_sitecustomize: Option<PathBuf>,
+    /// Whether to set `PYTHONSAFEPATH` by default in managed Python installations.
+    ///
+    /// When enabled, `uv python install` installs a `sitecustomize` module that sets
+    /// `PYTHONSAFEPATH=1` unless it's already set. Since `sitecustomize` is imported during
+    /// interpreter startup, the variable applies to the Python processes spawned by the
+    /// interpreter (e.g., via `subprocess`), rather than the interpreter itself.
+    #[option(
+        default = "false",
+        value_type = "bool",
+        example = r#"
+            python-install-safe-path = true
+        "#
+    )]
+    pub python_install_safe_path: Option<bool>,
 }
 
 /// Settings relevant to all installer operations.
@@ -176,18 +480,22 @@ pub struct InstallerOptions {
     pub extra_index_url: Option<Vec<IndexUrl>>,
     pub no_index: Option<bool>,
     pub find_links: Option<Vec<FlatIndexLocation>>,
+    pub index_mirrors: Option<IndexMirrors>,
     pub index_strategy: Option<IndexStrategy>,
     pub keyring_provider: Option<KeyringProviderType>,
     pub config_settings: Option<ConfigSettings>,
     pub exclude_newer: Option<ExcludeNewer>,
     pub link_mode: Option<LinkMode>,
     pub compile_bytecode: Option<bool>,
+    pub exclude_package_data: Option<Vec<ExcludePattern>>,
     pub reinstall: Option<bool>,
     pub reinstall_package: Option<Vec<PackageName>>,
     pub no_build: Option<bool>,
     pub no_build_package: Option<Vec<PackageName>>,
     pub no_binary: Option<bool>,
     pub no_binary_package: Option<Vec<PackageName>>,
+    pub prefer_source: Option<bool>,
+    pub prefer_source_package: Option<Vec<PackageName>>,
     pub no_build_isolation: Option<bool>,
     pub no_sources: Option<bool>,
 }
@@ -202,6 +510,7 @@ pub struct ResolverOptions {
     pub extra_index_url: Option<Vec<IndexUrl>>,
     pub no_index: Option<bool>,
     pub find_links: Option<Vec<FlatIndexLocation>>,
+    pub index_mirrors: Option<IndexMirrors>,
     pub index_strategy: Option<IndexStrategy>,
     pub keyring_provider: Option<KeyringProviderType>,
     pub resolution: Option<ResolutionMode>,
@@ -215,6 +524,8 @@ pub struct ResolverOptions {
     pub no_build_package: Option<Vec<PackageName>>,
     pub no_binary: Option<bool>,
     pub no_binary_package: Option<Vec<PackageName>>,
+    pub prefer_source: Option<bool>,
+    pub prefer_source_package: Option<Vec<PackageName>>,
     pub no_build_isolation: Option<bool>,
     pub no_build_isolation_package: Option<Vec<PackageName>>,
     pub no_sources: Option<bool>,
@@ -288,6 +599,22 @@ pub struct ResolverInstallerOptions {
         "#
     )]
     pub find_links: Option<Vec<FlatIndexLocation>>,
+    /// Mirrors to fall back to when an index is unavailable, keyed by the URL of the index.
+    ///
+    /// If a request to an index fails with a server error (5xx), a timeout, or a connection error,
+    /// uv will retry the request against each of its mirrors, in order. Indexes that fail are
+    /// deprioritized for the remainder of the invocation.
+    ///
+    /// Results are attributed to the original index, such that mirrors are expected to serve the
+    /// same distributions.
+    #[option(
+        default = "{}",
+        value_type = "dict",
+        example = r#"
+            index-mirrors = { "https://pypi.internal/simple" = ["https://pypi-mirror.internal/simple"] }
+        "#
+    )]
+    pub index_mirrors: Option<IndexMirrors>,
     /// The strategy to use when resolving against multiple index URLs.
     ///
     /// By default, uv will stop at the first index on which a given package is available, and
@@ -419,6 +746,22 @@ pub struct ResolverInstallerOptions {
         "#
     )]
     pub compile_bytecode: Option<bool>,
+    /// Exclude the files that match the given glob patterns from the installed packages, e.g.,
+    /// `**/tests/**`.
+    ///
+    /// Patterns are matched against each file's path relative to `site-packages`, as listed in the
+    /// package's `RECORD`; `*` doesn't match across directories, while `**` does. Matching files
+    /// are removed after installation and omitted from the `RECORD`, such that the package can
+    /// still be uninstalled cleanly. The package metadata (e.g., `METADATA` and `RECORD`) and any
+    /// files installed outside of `site-packages` (e.g., scripts) are never excluded.
+    #[option(
+        default = "[]",
+        value_type = "list[str]",
+        example = r#"
+            exclude-package-data = ["**/tests/**", "**/__pycache__/**"]
+        "#
+    )]
+    pub exclude_package_data: Option<Vec<ExcludePattern>>,
     /// Ignore the `tool.uv.sources` table when resolving dependencies. Used to lock against the
     /// standards-compliant, publishable package metadata, as opposed to using any local or Git
     /// sources.
@@ -513,6 +856,35 @@ pub struct ResolverInstallerOptions {
         "#
     )]
     pub no_binary_package: Option<Vec<PackageName>>,
+    /// Prefer building from source distributions over installing pre-built wheels.
+    ///
+    /// Packages that publish a source distribution will be built and installed from source, while
+    /// packages that only publish wheels will continue to be installed from wheels. As with
+    /// `no-binary`, the resolver will still use pre-built wheels to extract package metadata, if
+    /// available.
+    ///
+    /// Wheels built from source are cached separately from those downloaded from the registry, so
+    /// enabling this setting will never reuse a previously downloaded wheel (and disabling it will
+    /// never reuse a wheel built from source in lieu of a published wheel). However, packages
+    /// that are already installed will not be reinstalled unless `--reinstall` is provided.
+    #[option(
+        default = "false",
+        value_type = "bool",
+        example = r#"
+            prefer-source = true
+        "#
+    )]
+    pub prefer_source: Option<bool>,
+    /// Prefer building from source distributions over installing pre-built wheels for a specific
+    /// package.
+    #[option(
+        default = "[]",
+        value_type = "list[str]",
+        example = r#"
+            prefer-source-package = ["numpy"]
+        "#
+    )]
+    pub prefer_source_package: Option<Vec<PackageName>>,
 }
 
 /// Settings that are specific to the `uv pip` command-line interface.
@@ -658,6 +1030,22 @@ pub struct PipOptions {
         "#
     )]
     pub find_links: Option<Vec<FlatIndexLocation>>,
+    /// Mirrors to fall back to when an index is unavailable, keyed by the URL of the index.
+    ///
+    /// If a request to an index fails with a server error (5xx), a timeout, or a connection error,
+    /// uv will retry the request against each of its mirrors, in order. Indexes that fail are
+    /// deprioritized for the remainder of the invocation.
+    ///
+    /// Results are attributed to the original index, such that mirrors are expected to serve the
+    /// same distributions.
+    #[option(
+        default = "{}",
+        value_type = "dict",
+        example = r#"
+            index-mirrors = { "https://pypi.internal/simple" = ["https://pypi-mirror.internal/simple"] }
+        "#
+    )]
+    pub index_mirrors: Option<IndexMirrors>,
     /// The strategy to use when resolving against multiple index URLs.
     ///
     /// By default, uv will stop at the first index on which a given package is available, and
@@ -765,6 +1153,18 @@ pub struct PipOptions {
         "#
     )]
     pub strict: Option<bool>,
+    /// Fail if a requested extra isn't provided by the resolved version of a package.
+    ///
+    /// By default, requesting an extra that doesn't exist (e.g., `black[tensorboard]`) emits a
+    /// warning naming the package, its version, and the extras it provides.
+    #[option(
+        default = "false",
+        value_type = "bool",
+        example = r#"
+            strict-extras = true
+        "#
+    )]
+    pub strict_extras: Option<bool>,
     /// Include optional dependencies from the extra group name; may be provided more than once.
     ///
     /// Only applies to `pyproject.toml`, `setup.py`, and `setup.cfg` sources.
@@ -956,6 +1356,26 @@ pub struct PipOptions {
         "#
     )]
     pub python_platform: Option<TargetTriple>,
+    /// The architecture policy to apply when selecting wheels on macOS.
+    ///
+    /// By default, uv prefers wheels built for the interpreter's architecture, falling back to
+    /// `universal2` wheels. Use `prefer-universal2` or `require-universal2` to favor (or require)
+    /// `universal2` wheels, e.g., to build environments that can be used on both Intel and Apple
+    /// Silicon machines; or `x86_64` to select `x86_64` wheels for an interpreter running under
+    /// Rosetta 2. uv will error if the interpreter's architecture is inconsistent with the policy.
+    /// When targeting an ARM-based macOS platform with `python-platform`, `x86_64` resolves for
+    /// `x86_64-apple-darwin` instead.
+    ///
+    /// Has no effect on platforms other than macOS.
+    #[option(
+        default = "\"native\"",
+        value_type = "str",
+        example = r#"
+            macos-arch = "prefer-universal2"
+        "#,
+        possible_values = true
+    )]
+    pub macos_arch: Option<MacosArch>,
     /// Perform a universal resolution, attempting to generate a single `requirements.txt` output
     /// file that is compatible with all operating systems, architectures, and Python
     /// implementations.
@@ -1044,6 +1464,19 @@ pub struct PipOptions {
         "#
     )]
     pub emit_index_annotation: Option<bool>,
+    /// Include comment annotations indicating the packages whose versions were pinned by a
+    /// constraint rather than by the dependency graph (e.g., `# constrained-by -c constraints.txt`).
+    ///
+    /// Constraints that had no effect on the resolution are listed at the end of the output, to
+    /// help prune stale constraint files.
+    #[option(
+        default = "false",
+        value_type = "bool",
+        example = r#"
+            emit-constraint-annotation = true
+        "#
+    )]
+    pub emit_constraint_annotation: Option<bool>,
     /// The style of the annotation comments included in the output file, used to indicate the
     /// source of each package.
     #[option(
@@ -1086,6 +1519,22 @@ pub struct PipOptions {
         "#
     )]
     pub compile_bytecode: Option<bool>,
+    /// Exclude the files that match the given glob patterns from the installed packages, e.g.,
+    /// `**/tests/**`.
+    ///
+    /// Patterns are matched against each file's path relative to `site-packages`, as listed in the
+    /// package's `RECORD`; `*` doesn't match across directories, while `**` does. Matching files
+    /// are removed after installation and omitted from the `RECORD`, such that the package can
+    /// still be uninstalled cleanly. The package metadata (e.g., `METADATA` and `RECORD`) and any
+    /// files installed outside of `site-packages` (e.g., scripts) are never excluded.
+    #[option(
+        default = "[]",
+        value_type = "list[str]",
+        example = r#"
+            exclude-package-data = ["**/tests/**", "**/__pycache__/**"]
+        "#
+    )]
+    pub exclude_package_data: Option<Vec<ExcludePattern>>,
     /// Require a matching hash for each requirement.
     ///
     /// Hash-checking mode is all or nothing. If enabled, _all_ requirements must be provided
@@ -1119,6 +1568,25 @@ pub struct PipOptions {
         "#
     )]
     pub verify_hashes: Option<bool>,
+    /// The hash-checking policy for the packages served by each index, keyed by the URL of the
+    /// index.
+    ///
+    /// With a `require` policy, every package served by the index must have a hash, as with
+    /// `--require-hashes`. With a `verify` policy, hashes are validated if present, but packages
+    /// without hashes are allowed (and needn't be pinned), as with `--verify-hashes`. Packages
+    /// served by any other index, or by a direct URL or local path, are checked according to
+    /// `require-hashes` and `verify-hashes`.
+    ///
+    /// When compiling, the policies are recorded in the header of the output file.
+    #[option(
+        default = "{}",
+        value_type = "dict",
+        example = r#"
+            require-hashes = true
+            index-hash-policy = { "https://pypi.internal.example.com/simple" = "verify" }
+        "#
+    )]
+    pub index_hash_policy: Option<IndexHashPolicies>,
     /// Ignore the `tool.uv.sources` table when resolving dependencies. Used to lock against the
     /// standards-compliant, publishable package metadata, as opposed to using any local or Git
     /// sources.
@@ -1212,6 +1680,7 @@ impl From<ResolverInstallerOptions> for ResolverOptions {
             extra_index_url: value.extra_index_url,
             no_index: value.no_index,
             find_links: value.find_links,
+            index_mirrors: value.index_mirrors,
             index_strategy: value.index_strategy,
             keyring_provider: value.keyring_provider,
             resolution: value.resolution,
@@ -1225,6 +1694,8 @@ impl From<ResolverInstallerOptions> for ResolverOptions {
             no_build_package: value.no_build_package,
             no_binary: value.no_binary,
             no_binary_package: value.no_binary_package,
+            prefer_source: value.prefer_source,
+            prefer_source_package: value.prefer_source_package,
             no_build_isolation: value.no_build_isolation,
             no_build_isolation_package: value.no_build_isolation_package,
             no_sources: value.no_sources,
@@ -1239,18 +1710,22 @@ impl From<ResolverInstallerOptions> for InstallerOptions {
             extra_index_url: value.extra_index_url,
             no_index: value.no_index,
             find_links: value.find_links,
+            index_mirrors: value.index_mirrors,
             index_strategy: value.index_strategy,
             keyring_provider: value.keyring_provider,
             config_settings: value.config_settings,
             exclude_newer: value.exclude_newer,
             link_mode: value.link_mode,
             compile_bytecode: value.compile_bytecode,
+            exclude_package_data: value.exclude_package_data,
             reinstall: value.reinstall,
             reinstall_package: value.reinstall_package,
             no_build: value.no_build,
             no_build_package: value.no_build_package,
             no_binary: value.no_binary,
             no_binary_package: value.no_binary_package,
+            prefer_source: value.prefer_source,
+            prefer_source_package: value.prefer_source_package,
             no_build_isolation: value.no_build_isolation,
             no_sources: value.no_sources,
         }
@@ -1261,16 +1736,20 @@ impl From<ResolverInstallerOptions> for InstallerOptions {
 ///
 /// A mirror of [`ResolverInstallerOptions`], without upgrades and reinstalls, which shouldn't be
 /// persisted in a tool receipt.
+///
+/// Unlike the other options, unknown fields are ignored, such that receipts written by newer
+/// versions of uv remain readable.
 #[derive(
     Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, CombineOptions, OptionsMetadata,
 )]
-#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ToolOptions {
     pub index_url: Option<IndexUrl>,
     pub extra_index_url: Option<Vec<IndexUrl>>,
     pub no_index: Option<bool>,
     pub find_links: Option<Vec<FlatIndexLocation>>,
+    pub index_mirrors: Option<IndexMirrors>,
     pub index_strategy: Option<IndexStrategy>,
     pub keyring_provider: Option<KeyringProviderType>,
     pub resolution: Option<ResolutionMode>,
@@ -1281,11 +1760,14 @@ pub struct ToolOptions {
     pub exclude_newer: Option<ExcludeNewer>,
     pub link_mode: Option<LinkMode>,
     pub compile_bytecode: Option<bool>,
+    pub exclude_package_data: Option<Vec<ExcludePattern>>,
     pub no_sources: Option<bool>,
     pub no_build: Option<bool>,
     pub no_build_package: Option<Vec<PackageName>>,
     pub no_binary: Option<bool>,
     pub no_binary_package: Option<Vec<PackageName>>,
+    pub prefer_source: Option<bool>,
+    pub prefer_source_package: Option<Vec<PackageName>>,
 }
 
 impl From<ResolverInstallerOptions> for ToolOptions {
@@ -1295,6 +1777,7 @@ impl From<ResolverInstallerOptions> for ToolOptions {
             extra_index_url: value.extra_index_url,
             no_index: value.no_index,
             find_links: value.find_links,
+            index_mirrors: value.index_mirrors,
             index_strategy: value.index_strategy,
             keyring_provider: value.keyring_provider,
             resolution: value.resolution,
@@ -1305,11 +1788,14 @@ impl From<ResolverInstallerOptions> for ToolOptions {
             exclude_newer: value.exclude_newer,
             link_mode: value.link_mode,
             compile_bytecode: value.compile_bytecode,
+            exclude_package_data: value.exclude_package_data,
             no_sources: value.no_sources,
             no_build: value.no_build,
             no_build_package: value.no_build_package,
             no_binary: value.no_binary,
             no_binary_package: value.no_binary_package,
+            prefer_source: value.prefer_source,
+            prefer_source_package: value.prefer_source_package,
         }
     }
 }
@@ -1321,6 +1807,7 @@ impl From<ToolOptions> for ResolverInstallerOptions {
             extra_index_url: value.extra_index_url,
             no_index: value.no_index,
             find_links: value.find_links,
+            index_mirrors: value.index_mirrors,
             index_strategy: value.index_strategy,
             keyring_provider: value.keyring_provider,
             resolution: value.resolution,
@@ -1331,6 +1818,7 @@ impl From<ToolOptions> for ResolverInstallerOptions {
             exclude_newer: value.exclude_newer,
             link_mode: value.link_mode,
             compile_bytecode: value.compile_bytecode,
+            exclude_package_data: value.exclude_package_data,
             no_sources: value.no_sources,
             upgrade: None,
             upgrade_package: None,
@@ -1340,6 +1828,8 @@ impl From<ToolOptions> for ResolverInstallerOptions {
             no_build_package: value.no_build_package,
             no_binary: value.no_binary,
             no_binary_package: value.no_binary_package,
+            prefer_source: value.prefer_source,
+            prefer_source_package: value.prefer_source_package,
         }
     }
 }
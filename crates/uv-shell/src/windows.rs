@@ -38,6 +38,15 @@ pub fn prepend_path(path: &Path) -> anyhow::Result<bool> {
     Ok(true)
 }
 
+/// Returns `true` if the given [`Path`] is already in the `PATH` environment variable in the
+/// Windows registry.
+pub fn contains_path(path: &Path) -> anyhow::Result<bool> {
+    let windows_path = get_windows_path_var()?;
+    Ok(windows_path.map_or(true, |windows_path| {
+        prepend_to_path(windows_path, OsString::from(path).encode_wide().collect()).is_none()
+    }))
+}
+
 /// Set the windows `PATH` variable in the registry.
 fn apply_windows_path_var(path: Vec<u16>) -> anyhow::Result<()> {
     let root = RegKey::predef(HKEY_CURRENT_USER);
@@ -99,6 +99,12 @@ pub enum StateBucket {
     ManagedPython,
     /// Installed tools.
     Tools,
+    /// The local usage statistics log.
+    Stats,
+    /// The virtual environments created by uv.
+    Environments,
+    /// The trusted TUF metadata of verified indexes.
+    TrustedIndexes,
 }
 
 impl StateBucket {
@@ -106,6 +112,9 @@ impl StateBucket {
         match self {
             Self::ManagedPython => "python",
             Self::Tools => "tools",
+            Self::Stats => "stats",
+            Self::Environments => "environments",
+            Self::TrustedIndexes => "trusted-indexes",
         }
     }
 }
@@ -3,7 +3,7 @@ use core::fmt;
 use fs_err as fs;
 
 use pep440_rs::Version;
-use pep508_rs::{InvalidNameError, PackageName};
+use pep508_rs::PackageName;
 
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -15,14 +15,16 @@ use tracing::{debug, warn};
 
 use install_wheel_rs::read_record_file;
 
+pub use name::{InvalidToolNameError, ToolName};
 pub use receipt::ToolReceipt;
-pub use tool::{Tool, ToolEntrypoint};
+pub use tool::{EntrypointSelection, Tool, ToolEntrypoint};
 use uv_cache::Cache;
 use uv_fs::{LockedFile, Simplified};
 use uv_installer::SitePackages;
 use uv_python::{Interpreter, PythonEnvironment};
 use uv_state::{StateBucket, StateStore};
 
+mod name;
 mod receipt;
 mod tool;
 
@@ -34,16 +36,20 @@ pub enum Error {
     ReceiptWrite(PathBuf, #[source] Box<toml::ser::Error>),
     #[error("Failed to read `uv-receipt.toml` at {0}")]
     ReceiptRead(PathBuf, #[source] Box<toml::de::Error>),
+    #[error("Failed to read `uv-receipt.toml` at {0}, which was written by a newer version of uv (schema version {1})")]
+    UnsupportedReceiptVersion(PathBuf, u32, #[source] Box<toml::de::Error>),
     #[error(transparent)]
     VirtualEnvError(#[from] uv_virtualenv::Error),
     #[error("Failed to read package entry points {0}")]
     EntrypointRead(#[from] install_wheel_rs::Error),
     #[error("Failed to find dist-info directory `{0}` in environment at {1}")]
     DistInfoMissing(String, PathBuf),
+    #[error("Failed to relocate the scripts in the environment at {0}")]
+    Relocate(PathBuf, #[source] install_wheel_rs::Error),
     #[error("Failed to find a directory for executables")]
     NoExecutableDirectory,
     #[error(transparent)]
-    ToolName(#[from] InvalidNameError),
+    ToolName(#[from] InvalidToolNameError),
     #[error(transparent)]
     EnvironmentError(#[from] uv_python::Error),
     #[error("Failed to find a receipt for tool `{0}` at {1}")]
@@ -86,8 +92,8 @@ impl InstalledTools {
         }
     }
 
-    /// Return the expected directory for a tool with the given [`PackageName`].
-    pub fn tool_dir(&self, name: &PackageName) -> PathBuf {
+    /// Return the expected directory for a tool with the given [`ToolName`].
+    pub fn tool_dir(&self, name: &ToolName) -> PathBuf {
         self.root.join(name.to_string())
     }
 
@@ -98,27 +104,26 @@ impl InstalledTools {
     ///
     /// Note it is generally incorrect to use this without [`Self::acquire_lock`].
     #[allow(clippy::type_complexity)]
-    pub fn tools(&self) -> Result<Vec<(PackageName, Result<Tool, Error>)>, Error> {
+    pub fn tools(&self) -> Result<Vec<(ToolName, Result<Tool, Error>)>, Error> {
         let mut tools = Vec::new();
         for directory in uv_fs::directories(self.root()) {
             let name = directory.file_name().unwrap().to_string_lossy().to_string();
-            let name = PackageName::from_str(&name)?;
+            // Skip any hidden directories, e.g., those used to swap in a staged environment.
+            if name.starts_with('.') {
+                continue;
+            }
+            let name = ToolName::from_str(&name)?;
             let path = directory.join("uv-receipt.toml");
-            let contents = match fs_err::read_to_string(&path) {
-                Ok(contents) => contents,
-                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            match ToolReceipt::from_path(&path) {
+                Ok(tool_receipt) => tools.push((name, Ok(tool_receipt.tool))),
+                Err(Error::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
                     let err = Error::MissingToolReceipt(name.to_string(), path);
                     tools.push((name, Err(err)));
-                    continue;
                 }
-                Err(err) => return Err(err.into()),
-            };
-            match ToolReceipt::from_string(contents) {
-                Ok(tool_receipt) => tools.push((name, Ok(tool_receipt.tool))),
-                Err(err) => {
-                    let err = Error::ReceiptRead(path, Box::new(err));
+                Err(err @ (Error::ReceiptRead(..) | Error::UnsupportedReceiptVersion(..))) => {
                     tools.push((name, Err(err)));
                 }
+                Err(err) => return Err(err),
             }
         }
         Ok(tools)
@@ -130,7 +135,7 @@ impl InstalledTools {
     /// error.
     ///
     /// Note it is generally incorrect to use this without [`Self::acquire_lock`].
-    pub fn get_tool_receipt(&self, name: &PackageName) -> Result<Option<Tool>, Error> {
+    pub fn get_tool_receipt(&self, name: &ToolName) -> Result<Option<Tool>, Error> {
         let path = self.tool_dir(name).join("uv-receipt.toml");
         match ToolReceipt::from_path(&path) {
             Ok(tool_receipt) => Ok(Some(tool_receipt.tool)),
@@ -149,12 +154,17 @@ impl InstalledTools {
 
     /// Add a receipt for a tool.
     ///
-    /// Any existing receipt will be replaced.
+    /// Any existing receipt will be replaced, though any fields that are unknown to this version
+    /// of uv (i.e., that were written by a newer version) are retained.
     ///
     /// Note it is generally incorrect to use this without [`Self::acquire_lock`].
-    pub fn add_tool_receipt(&self, name: &PackageName, tool: Tool) -> Result<(), Error> {
-        let tool_receipt = ToolReceipt::from(tool);
+    pub fn add_tool_receipt(&self, name: &ToolName, tool: Tool) -> Result<(), Error> {
         let path = self.tool_dir(name).join("uv-receipt.toml");
+        let tool_receipt = match fs_err::read_to_string(&path) {
+            Ok(raw) => ToolReceipt::from(tool).with_raw(raw),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => ToolReceipt::from(tool),
+            Err(err) => return Err(err.into()),
+        };
 
         debug!(
             "Adding metadata entry for tool `{name}` at {}",
@@ -178,7 +188,7 @@ impl InstalledTools {
     /// # Errors
     ///
     /// If no such environment exists for the tool.
-    pub fn remove_environment(&self, name: &PackageName) -> Result<(), Error> {
+    pub fn remove_environment(&self, name: &ToolName) -> Result<(), Error> {
         let environment_path = self.tool_dir(name);
 
         debug!(
@@ -186,7 +196,7 @@ impl InstalledTools {
             environment_path.user_display()
         );
 
-        fs_err::remove_dir_all(environment_path)?;
+        uv_fs::force_remove_dir_all(environment_path)?;
 
         Ok(())
     }
@@ -199,7 +209,7 @@ impl InstalledTools {
     /// Note it is generally incorrect to use this without [`Self::acquire_lock`].
     pub fn get_environment(
         &self,
-        name: &PackageName,
+        name: &ToolName,
         cache: &Cache,
     ) -> Result<Option<PythonEnvironment>, Error> {
         let environment_path = self.tool_dir(name);
@@ -231,13 +241,13 @@ impl InstalledTools {
     /// Note it is generally incorrect to use this without [`Self::acquire_lock`].
     pub fn create_environment(
         &self,
-        name: &PackageName,
+        name: &ToolName,
         interpreter: Interpreter,
     ) -> Result<PythonEnvironment, Error> {
         let environment_path = self.tool_dir(name);
 
         // Remove any existing environment.
-        match fs_err::remove_dir_all(&environment_path) {
+        match uv_fs::force_remove_dir_all(&environment_path) {
             Ok(()) => {
                 debug!(
                     "Removed existing environment for tool `{name}`: {}",
@@ -266,6 +276,194 @@ impl InstalledTools {
         Ok(venv)
     }
 
+    /// Create a new [`PythonEnvironment`] for the given tool in a staging directory within the
+    /// cache, leaving any existing environment for the tool untouched.
+    ///
+    /// The environment is moved into the tools directory by [`Self::commit_environment`]; if the
+    /// [`StagedEnvironment`] is dropped before then, it's removed.
+    pub fn stage_environment(
+        &self,
+        name: &ToolName,
+        interpreter: Interpreter,
+        cache: &Cache,
+    ) -> Result<StagedEnvironment, Error> {
+        let temp_dir = cache.environment()?;
+
+        debug!(
+            "Staging environment for tool `{name}`: {}",
+            temp_dir.path().user_display()
+        );
+
+        let environment = uv_virtualenv::create_venv(
+            temp_dir.path(),
+            interpreter,
+            uv_virtualenv::Prompt::None,
+            false,
+            false,
+            false,
+        )?;
+
+        Ok(StagedEnvironment {
+            name: name.clone(),
+            location: Staging::New(temp_dir),
+            environment,
+            read_only: false,
+        })
+    }
+
+    /// Stage the existing [`PythonEnvironment`] for the given tool, such that it can be modified
+    /// in place, and restored if the modification fails.
+    ///
+    /// The existing environment is renamed aside, and replaced with a clone in which each file is
+    /// hard-linked to the original. Since the installer replaces files rather than modifying them,
+    /// the original is left intact. If the [`StagedEnvironment`] is dropped before it's committed
+    /// by [`Self::commit_environment`], the clone is removed and the original is restored.
+    ///
+    /// See [`Self::stage_environment`].
+    ///
+    /// Note it is generally incorrect to use this without [`Self::acquire_lock`].
+    pub fn stage_existing_environment(
+        &self,
+        name: &ToolName,
+        cache: &Cache,
+    ) -> Result<StagedEnvironment, Error> {
+        let environment_path = self.tool_dir(name);
+        let swap_dir = tempfile::tempdir_in(&self.root)?;
+        let backup_path = swap_dir.path().join("backup");
+
+        debug!(
+            "Moving existing environment for tool `{name}` aside: {}",
+            backup_path.user_display()
+        );
+
+        fs_err::rename(&environment_path, &backup_path)?;
+        let backup = Backup {
+            name: name.clone(),
+            environment_path: environment_path.clone(),
+            swap_dir: Some(swap_dir),
+        };
+
+        // Since the clone is at the same location as the original, absolute symlinks within the
+        // environment remain valid.
+        uv_cache::link_dir(&backup_path, &environment_path)?;
+        let environment = PythonEnvironment::from_root(&environment_path, cache)?;
+
+        // If the existing environment was marked read-only, restore write access to the clone (the
+        // read-only files of which were copied, rather than linked), and mark the environment
+        // read-only again once it's committed.
+        let read_only = environment
+            .site_packages()
+            .any(|site_packages| uv_fs::is_read_only(&*site_packages));
+        if read_only {
+            uv_fs::set_read_only(&environment_path, false)?;
+        }
+
+        Ok(StagedEnvironment {
+            name: name.clone(),
+            location: Staging::Existing(backup),
+            environment,
+            read_only,
+        })
+    }
+
+    /// Move a [`StagedEnvironment`] into the tools directory, replacing any existing environment
+    /// for the tool.
+    ///
+    /// For a new environment, the scripts are first rewritten to refer to its final location. Any
+    /// existing environment is only removed once the staged environment is in place, and is
+    /// restored if the staged environment can't be moved into place.
+    ///
+    /// Note it is generally incorrect to use this without [`Self::acquire_lock`].
+    pub fn commit_environment(
+        &self,
+        staged: StagedEnvironment,
+        cache: &Cache,
+    ) -> Result<PythonEnvironment, Error> {
+        let StagedEnvironment {
+            name,
+            location,
+            environment,
+            read_only,
+        } = staged;
+        let environment_path = self.tool_dir(&name);
+
+        match location {
+            Staging::New(temp_dir) => {
+                self.swap_environment(&name, temp_dir.path(), &environment, &environment_path)?;
+            }
+            Staging::Existing(backup) => {
+                // The environment was modified in place, so the original can be discarded.
+                backup.discard();
+            }
+        }
+
+        debug!(
+            "Committed environment for tool `{name}`: {}",
+            environment_path.user_display()
+        );
+
+        let environment = PythonEnvironment::from_root(&environment_path, cache)?;
+        if read_only {
+            for site_packages in environment.site_packages() {
+                uv_fs::set_read_only(&*site_packages, true)?;
+            }
+        }
+
+        Ok(environment)
+    }
+
+    /// Move the environment at `temp_dir` to `environment_path`, replacing any existing
+    /// environment.
+    fn swap_environment(
+        &self,
+        name: &ToolName,
+        temp_dir: &Path,
+        environment: &PythonEnvironment,
+        environment_path: &Path,
+    ) -> Result<(), Error> {
+        // Point the scripts at the final location of the environment.
+        install_wheel_rs::relocate_scripts(environment.scripts(), temp_dir, environment_path)
+            .map_err(|err| Error::Relocate(temp_dir.to_path_buf(), err))?;
+
+        // Move the environment into the tools directory first (which may require a copy, if the
+        // cache is on a different filesystem), such that the swap itself is a pair of renames.
+        let swap_dir = tempfile::tempdir_in(&self.root)?;
+        let staged_path = swap_dir.path().join("staged");
+        let backup_path = swap_dir.path().join("backup");
+        uv_cache::move_dir(temp_dir, &staged_path)?;
+
+        // Move any existing environment out of the way, then move the new environment into place.
+        let has_backup = match fs_err::rename(environment_path, &backup_path) {
+            Ok(()) => true,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => false,
+            Err(err) => return Err(err.into()),
+        };
+        if let Err(err) = fs_err::rename(&staged_path, environment_path) {
+            if has_backup {
+                debug!(
+                    "Restoring existing environment for tool `{name}`: {}",
+                    environment_path.user_display()
+                );
+                fs_err::rename(&backup_path, environment_path)?;
+            }
+            return Err(err.into());
+        }
+
+        // Point any absolute symlinks within the environment at its final location, rather than
+        // the intermediate one.
+        uv_cache::rebase_symlinks(&staged_path, environment_path)?;
+
+        // Remove the previous environment explicitly, since it may contain read-only files that
+        // would prevent the swap directory from being removed on drop.
+        if has_backup {
+            if let Err(err) = uv_fs::force_remove_dir_all(&backup_path) {
+                debug!("Failed to remove previous environment for tool `{name}`: {err}");
+            }
+        }
+
+        Ok(())
+    }
+
     /// Create a temporary tools directory.
     pub fn temp() -> Result<Self, Error> {
         Ok(Self::from_path(
@@ -274,15 +472,15 @@ impl InstalledTools {
     }
 
     /// Return the [`Version`] of an installed tool.
-    pub fn version(&self, name: &PackageName, cache: &Cache) -> Result<Version, Error> {
+    pub fn version(&self, name: &ToolName, cache: &Cache) -> Result<Version, Error> {
         let environment_path = self.tool_dir(name);
         let environment = PythonEnvironment::from_root(&environment_path, cache)?;
         let site_packages = SitePackages::from_environment(&environment)
             .map_err(|err| Error::EnvironmentRead(environment_path.clone(), err.to_string()))?;
-        let packages = site_packages.get_packages(name);
+        let packages = site_packages.get_packages(name.package());
         let package = packages
             .first()
-            .ok_or_else(|| Error::MissingToolPackage(name.clone()))?;
+            .ok_or_else(|| Error::MissingToolPackage(name.package().clone()))?;
         Ok(package.version().clone())
     }
 
@@ -315,6 +513,98 @@ impl InstalledTools {
     }
 }
 
+/// A tool environment that's being built or modified, before it's committed to the tools
+/// directory by [`InstalledTools::commit_environment`].
+#[derive(Debug)]
+pub struct StagedEnvironment {
+    /// The name of the tool.
+    name: ToolName,
+    /// Where the environment is staged.
+    location: Staging,
+    /// The staged environment.
+    environment: PythonEnvironment,
+    /// Whether the environment should be marked read-only once it's committed, as the environment
+    /// it was cloned from was.
+    read_only: bool,
+}
+
+impl StagedEnvironment {
+    /// Return the staged [`PythonEnvironment`].
+    pub fn environment(&self) -> &PythonEnvironment {
+        &self.environment
+    }
+}
+
+#[derive(Debug)]
+enum Staging {
+    /// A new environment, built in a temporary directory within the cache, which is removed on
+    /// drop.
+    New(tempfile::TempDir),
+    /// A clone of the existing environment, modified in place.
+    Existing(Backup),
+}
+
+/// An existing tool environment that was renamed aside, while a clone of it is modified in its
+/// place.
+///
+/// Unless discarded, the clone is removed and the original environment is restored on drop.
+#[derive(Debug)]
+struct Backup {
+    /// The name of the tool.
+    name: ToolName,
+    /// The path to the tool environment.
+    environment_path: PathBuf,
+    /// The temporary directory containing the original environment.
+    swap_dir: Option<tempfile::TempDir>,
+}
+
+impl Backup {
+    /// Remove the original environment.
+    fn discard(mut self) {
+        if let Some(swap_dir) = self.swap_dir.take() {
+            // Remove the original environment explicitly, since it may contain read-only files that
+            // would prevent the swap directory from being removed on drop.
+            if let Err(err) = uv_fs::force_remove_dir_all(swap_dir.path()) {
+                debug!(
+                    "Failed to remove previous environment for tool `{}`: {err}",
+                    self.name
+                );
+            }
+        }
+    }
+}
+
+impl Drop for Backup {
+    fn drop(&mut self) {
+        let Some(swap_dir) = self.swap_dir.take() else {
+            return;
+        };
+
+        debug!(
+            "Restoring existing environment for tool `{}`: {}",
+            self.name,
+            self.environment_path.user_display()
+        );
+        if let Err(err) = uv_fs::force_remove_dir_all(&self.environment_path) {
+            if err.kind() != io::ErrorKind::NotFound {
+                warn!(
+                    "Failed to remove modified environment for tool `{}`: {err}",
+                    self.name
+                );
+                return;
+            }
+        }
+        if let Err(err) = fs_err::rename(swap_dir.path().join("backup"), &self.environment_path) {
+            warn!(
+                "Failed to restore existing environment for tool `{}`: {err}",
+                self.name
+            );
+            // Retain the original environment, such that it can be recovered manually.
+            let _ = swap_dir.into_path();
+        }
+    }
+}
+
 /// A uv-managed tool installed on the current system..
 #[derive(Debug, Clone)]
 pub struct InstalledTool {
@@ -0,0 +1,111 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use pep508_rs::{InvalidNameError, PackageName};
+
+/// The name under which a tool is installed.
+///
+/// A tool is identified by the name of the package that provides it and an optional suffix (e.g.,
+/// `@23` in `black@23`), such that multiple versions of a tool can be installed side-by-side. The
+/// suffix is applied to both the tool environment and its executables.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ToolName {
+    package: PackageName,
+    suffix: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum InvalidToolNameError {
+    #[error(transparent)]
+    Package(#[from] InvalidNameError),
+    #[error("Tool suffixes must start with `@`, followed by letters, digits, `.`, `-`, or `_`; found: `{0}`")]
+    Suffix(String),
+}
+
+impl ToolName {
+    /// Create a [`ToolName`] for the given package, with an optional suffix.
+    pub fn new(package: PackageName, suffix: Option<String>) -> Result<Self, InvalidToolNameError> {
+        if let Some(suffix) = suffix.as_deref() {
+            let valid = suffix.strip_prefix('@').is_some_and(|rest| {
+                !rest.is_empty()
+                    && rest
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            });
+            if !valid {
+                return Err(InvalidToolNameError::Suffix(suffix.to_string()));
+            }
+        }
+        Ok(Self { package, suffix })
+    }
+
+    /// Return the name of the package that provides the tool.
+    pub fn package(&self) -> &PackageName {
+        &self.package
+    }
+
+    /// Return the suffix applied to the tool, if any.
+    pub fn suffix(&self) -> Option<&str> {
+        self.suffix.as_deref()
+    }
+}
+
+impl From<PackageName> for ToolName {
+    fn from(package: PackageName) -> Self {
+        Self {
+            package,
+            suffix: None,
+        }
+    }
+}
+
+impl FromStr for ToolName {
+    type Err = InvalidToolNameError;
+
+    /// Parse a [`ToolName`], e.g., `black` or `black@23`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.find('@') {
+            Some(index) => Self::new(
+                PackageName::from_str(&name[..index])?,
+                Some(name[index..].to_string()),
+            ),
+            None => Ok(Self::from(PackageName::from_str(name)?)),
+        }
+    }
+}
+
+impl Display for ToolName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.package)?;
+        if let Some(suffix) = &self.suffix {
+            write!(f, "{suffix}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::ToolName;
+
+    #[test]
+    fn parse() {
+        let name = ToolName::from_str("black").unwrap();
+        assert_eq!(name.package().as_ref(), "black");
+        assert_eq!(name.suffix(), None);
+        assert_eq!(name.to_string(), "black");
+
+        let name = ToolName::from_str("Black@23.1").unwrap();
+        assert_eq!(name.package().as_ref(), "black");
+        assert_eq!(name.suffix(), Some("@23.1"));
+        assert_eq!(name.to_string(), "black@23.1");
+
+        assert!(ToolName::from_str("black@").is_err());
+        assert!(ToolName::from_str("black@23/24").is_err());
+        assert!(ToolName::from_str("@23").is_err());
+    }
+}
@@ -2,12 +2,32 @@ use std::path::Path;
 
 use serde::Deserialize;
 
+use uv_settings::ToolOptions;
+
 use crate::Tool;
 
+/// The version of the receipt schema written by this version of uv.
+///
+/// Bump this whenever a change is made to the receipt that can't be read by older versions of uv.
+/// Receipts without a `schema-version` predate versioning, and are treated as version `0`.
+pub(crate) const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// The keys of the receipt that are understood by this version of uv. Any other keys were written
+/// by a newer version of uv, and are preserved when the receipt is rewritten.
+const KNOWN_KEYS: &[&str] = &["schema-version", "tool"];
+
+/// The keys of the `[tool]` table that are understood by this version of uv.
+const KNOWN_TOOL_KEYS: &[&str] = &["requirements", "python", "entrypoints", "options"];
+
 /// A `uv-receipt.toml` file tracking the installation of a tool.
 #[allow(dead_code)]
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct ToolReceipt {
+    /// The version of the receipt schema, if any.
+    #[serde(default)]
+    pub(crate) schema_version: u32,
+
     pub(crate) tool: Tool,
 
     /// The raw unserialized document.
@@ -15,6 +35,15 @@ pub struct ToolReceipt {
     pub(crate) raw: String,
 }
 
+/// The subset of a receipt that is read to determine its schema version, even if the rest of the
+/// receipt is unreadable.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SchemaVersion {
+    #[serde(default)]
+    schema_version: u32,
+}
+
 impl ToolReceipt {
     /// Parse a [`ToolReceipt`] from a raw TOML string.
     pub(crate) fn from_string(raw: String) -> Result<Self, toml::de::Error> {
@@ -23,30 +52,209 @@ impl ToolReceipt {
     }
 
     ///  Read a [`ToolReceipt`] from the given path.
+    ///
+    /// If the receipt can't be parsed, but was written by a newer version of uv, returns
+    /// [`crate::Error::UnsupportedReceiptVersion`], such that callers can avoid treating the
+    /// installation as broken.
     pub(crate) fn from_path(path: &Path) -> Result<ToolReceipt, crate::Error> {
         match fs_err::read_to_string(path) {
-            Ok(contents) => Ok(ToolReceipt::from_string(contents)
-                .map_err(|err| crate::Error::ReceiptRead(path.to_owned(), Box::new(err)))?),
+            Ok(contents) => {
+                let version = toml::from_str::<SchemaVersion>(&contents)
+                    .map(|version| version.schema_version)
+                    .ok();
+                ToolReceipt::from_string(contents).map_err(|err| match version {
+                    Some(version) if version > CURRENT_SCHEMA_VERSION => {
+                        crate::Error::UnsupportedReceiptVersion(
+                            path.to_owned(),
+                            version,
+                            Box::new(err),
+                        )
+                    }
+                    _ => crate::Error::ReceiptRead(path.to_owned(), Box::new(err)),
+                })
+            }
             Err(err) => Err(err.into()),
         }
     }
 
     /// Returns the TOML representation of this receipt.
+    ///
+    /// The receipt is written with the [`CURRENT_SCHEMA_VERSION`], unless the raw document was
+    /// written with a newer schema version, which is retained. Any fields in the raw document
+    /// that aren't understood by this version of uv are retained, too.
     pub(crate) fn to_toml(&self) -> Result<String, toml_edit::ser::Error> {
+        // Avoid downgrading the schema version of a receipt written by a newer version of uv,
+        // since its unknown fields are retained.
+        let schema_version = toml::from_str::<SchemaVersion>(&self.raw)
+            .map_or(0, |version| version.schema_version)
+            .max(CURRENT_SCHEMA_VERSION);
+
         // We construct a TOML document manually instead of going through Serde to enable
         // the use of inline tables.
         let mut doc = toml_edit::DocumentMut::new();
-        doc.insert("tool", toml_edit::Item::Table(self.tool.to_toml()?));
+        doc.insert(
+            "schema-version",
+            toml_edit::value(i64::from(schema_version)),
+        );
+        let mut tool = self.tool.to_toml()?;
+
+        // Preserve any unknown fields from the existing receipt.
+        if let Ok(existing) = self.raw.parse::<toml_edit::DocumentMut>() {
+            for (key, item) in existing.iter() {
+                if key == "tool" {
+                    if let Some(table) = item.as_table() {
+                        for (key, item) in table {
+                            if key == "options" {
+                                preserve_unknown_options(&mut tool, item);
+                            } else if !KNOWN_TOOL_KEYS.contains(&key) {
+                                tool.insert(key, item.clone());
+                            }
+                        }
+                    }
+                } else if !KNOWN_KEYS.contains(&key) {
+                    doc.insert(key, item.clone());
+                }
+            }
+        }
+
+        doc.insert("tool", toml_edit::Item::Table(tool));
 
         Ok(doc.to_string())
     }
+
+    /// Set the raw document from which unknown fields should be preserved when the receipt is
+    /// rewritten.
+    #[must_use]
+    pub(crate) fn with_raw(self, raw: String) -> Self {
+        Self { raw, ..self }
+    }
+}
+
+/// Copy any entries of the existing `[tool.options]` table that aren't understood by this version
+/// of uv into the `[tool]` table being written.
+fn preserve_unknown_options(tool: &mut toml_edit::Table, existing: &toml_edit::Item) {
+    let Some(existing) = existing.as_table_like() else {
+        return;
+    };
+    for (key, item) in existing.iter() {
+        if is_known_option(key, item) {
+            continue;
+        }
+        let options = tool
+            .entry("options")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut();
+        if let Some(options) = options {
+            options.insert(key, item.clone());
+        }
+    }
+}
+
+/// Returns `true` if the given `[tool.options]` entry is understood by this version of uv.
+///
+/// [`ToolOptions`] ignores unknown fields, so an entry is understood if it survives a round-trip.
+fn is_known_option(key: &str, item: &toml_edit::Item) -> bool {
+    let mut doc = toml_edit::DocumentMut::new();
+    doc.insert(key, item.clone());
+    let Ok(options) = toml::from_str::<ToolOptions>(&doc.to_string()) else {
+        return false;
+    };
+    toml::to_string(&options)
+        .ok()
+        .and_then(|options| options.parse::<toml::Table>().ok())
+        .is_some_and(|options| options.contains_key(key))
 }
 
 impl From<Tool> for ToolReceipt {
     fn from(tool: Tool) -> Self {
         ToolReceipt {
+            schema_version: CURRENT_SCHEMA_VERSION,
             tool,
             raw: String::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::EntrypointSelection;
+
+    use super::*;
+
+    #[test]
+    fn unknown_fields() {
+        let raw = r#"
+        schema-version = 2
+        future = "value"
+
+        [tool]
+        requirements = [{ name = "black" }]
+        entrypoints = [
+            { name = "black", install-path = "/home/ferris/.local/bin/black" },
+        ]
+        future-tool = true
+
+        [tool.options]
+        future-option = "value"
+        "#;
+
+        // Unknown fields should be ignored when reading the receipt...
+        let receipt = ToolReceipt::from_string(raw.to_string()).unwrap();
+        assert_eq!(receipt.schema_version, 2);
+
+        // ...but preserved when writing it back, without downgrading the schema version.
+        let toml = receipt.to_toml().unwrap();
+        assert!(toml.contains("schema-version = 2"));
+        assert!(toml.contains("future = \"value\""));
+        assert!(toml.contains("future-tool = true"));
+        assert!(toml.contains("future-option = \"value\""));
+    }
+
+    #[test]
+    fn known_options() {
+        let raw = r#"
+        [tool]
+        requirements = [{ name = "black" }]
+        entrypoints = []
+
+        [tool.options]
+        index-url = "https://test.pypi.org/simple"
+        future-option = "value"
+        "#;
+
+        // Known options are written from the receipt, such that they can be removed, while unknown
+        // options are preserved.
+        let receipt = ToolReceipt::from_string(raw.to_string()).unwrap();
+        let tool = Tool::new(
+            receipt.tool.requirements().to_vec(),
+            vec![],
+            vec![],
+            None,
+            std::iter::empty(),
+            EntrypointSelection::default(),
+            BTreeMap::new(),
+            ToolOptions::default(),
+        );
+        let toml = ToolReceipt::from(tool)
+            .with_raw(raw.to_string())
+            .to_toml()
+            .unwrap();
+        assert!(toml.contains("schema-version = 1"));
+        assert!(!toml.contains("index-url"));
+        assert!(toml.contains("future-option = \"value\""));
+    }
+
+    #[test]
+    fn legacy() {
+        let raw = r#"
+        [tool]
+        requirements = [{ name = "black" }]
+        entrypoints = []
+        "#;
+
+        let receipt = ToolReceipt::from_string(raw.to_string()).unwrap();
+        assert_eq!(receipt.schema_version, 0);
+    }
+}
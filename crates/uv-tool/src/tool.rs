@@ -1,6 +1,7 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use toml_edit::value;
 use toml_edit::Table;
 use toml_edit::Value;
@@ -17,10 +18,18 @@ use uv_settings::ToolOptions;
 pub struct Tool {
     /// The requirements requested by the user during installation.
     requirements: Vec<Requirement>,
+    /// The constraints requested by the user during installation.
+    constraints: Vec<Requirement>,
+    /// The overrides requested by the user during installation.
+    overrides: Vec<Requirement>,
     /// The Python requested by the user during installation.
     python: Option<String>,
     /// A mapping of entry point names to their metadata.
     entrypoints: Vec<ToolEntrypoint>,
+    /// The executables selected by the user during installation.
+    selection: EntrypointSelection,
+    /// The environment variables set by the executables, as requested via `--env`.
+    env: BTreeMap<String, String>,
     /// The [`ToolOptions`] used to install this tool.
     options: ToolOptions,
 }
@@ -28,9 +37,17 @@ pub struct Tool {
 #[derive(Clone, Debug, Deserialize)]
 struct ToolWire {
     requirements: Vec<RequirementWire>,
+    #[serde(default)]
+    constraints: Vec<Requirement>,
+    #[serde(default)]
+    overrides: Vec<Requirement>,
     python: Option<String>,
     entrypoints: Vec<ToolEntrypoint>,
     #[serde(default)]
+    selection: EntrypointSelection,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
     options: ToolOptions,
 }
 
@@ -52,8 +69,12 @@ impl From<Tool> for ToolWire {
                 .into_iter()
                 .map(RequirementWire::Requirement)
                 .collect(),
+            constraints: tool.constraints,
+            overrides: tool.overrides,
             python: tool.python,
             entrypoints: tool.entrypoints,
+            selection: tool.selection,
+            env: tool.env,
             options: tool.options,
         }
     }
@@ -72,13 +93,49 @@ impl TryFrom<ToolWire> for Tool {
                     RequirementWire::Deprecated(requirement) => Requirement::from(requirement),
                 })
                 .collect(),
+            constraints: tool.constraints,
+            overrides: tool.overrides,
             python: tool.python,
             entrypoints: tool.entrypoints,
+            selection: tool.selection,
+            env: tool.env,
             options: tool.options,
         })
     }
 }
 
+/// The executables to install for a tool, as selected via `--entrypoint`, `--exclude-entrypoint`,
+/// and `--include-deps`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EntrypointSelection {
+    /// If non-empty, only install the executables with the given names.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Don't install the executables with the given names.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Install the executables provided by the tool's dependencies, in addition to those provided
+    /// by the tool itself.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub include_deps: bool,
+}
+
+impl EntrypointSelection {
+    /// Returns `true` if the executable with the given name should be installed.
+    ///
+    /// The name is compared without any platform-specific extension (e.g., `.exe`).
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name
+            .strip_suffix(std::env::consts::EXE_SUFFIX)
+            .unwrap_or(name);
+        if !self.include.is_empty() && !self.include.iter().any(|include| include == name) {
+            return false;
+        }
+        !self.exclude.iter().any(|exclude| exclude == name)
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct ToolEntrypoint {
@@ -113,20 +170,44 @@ fn each_element_on_its_line_array(elements: impl Iterator<Item = impl Into<Value
     array
 }
 
+/// Serialize a list of requirements as a TOML array, with each requirement on its own line.
+fn requirements_array(requirements: &[Requirement]) -> Result<Array, toml_edit::ser::Error> {
+    let requirements = requirements
+        .iter()
+        .map(|requirement| {
+            serde::Serialize::serialize(&requirement, toml_edit::ser::ValueSerializer::new())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(match requirements.as_slice() {
+        [] => Array::new(),
+        [requirement] => Array::from_iter([requirement]),
+        requirements => each_element_on_its_line_array(requirements.iter()),
+    })
+}
+
 impl Tool {
     /// Create a new `Tool`.
     pub fn new(
         requirements: Vec<Requirement>,
+        constraints: Vec<Requirement>,
+        overrides: Vec<Requirement>,
         python: Option<String>,
         entrypoints: impl Iterator<Item = ToolEntrypoint>,
+        selection: EntrypointSelection,
+        env: BTreeMap<String, String>,
         options: ToolOptions,
     ) -> Self {
         let mut entrypoints: Vec<_> = entrypoints.collect();
         entrypoints.sort();
         Self {
             requirements,
+            constraints,
+            overrides,
             python,
             entrypoints,
+            selection,
+            env,
             options,
         }
     }
@@ -141,25 +222,18 @@ impl Tool {
     pub(crate) fn to_toml(&self) -> Result<Table, toml_edit::ser::Error> {
         let mut table = Table::new();
 
-        table.insert("requirements", {
-            let requirements = self
-                .requirements
-                .iter()
-                .map(|requirement| {
-                    serde::Serialize::serialize(
-                        &requirement,
-                        toml_edit::ser::ValueSerializer::new(),
-                    )
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+        table.insert(
+            "requirements",
+            value(requirements_array(&self.requirements)?),
+        );
 
-            let requirements = match requirements.as_slice() {
-                [] => Array::new(),
-                [requirement] => Array::from_iter([requirement]),
-                requirements => each_element_on_its_line_array(requirements.iter()),
-            };
-            value(requirements)
-        });
+        if !self.constraints.is_empty() {
+            table.insert("constraints", value(requirements_array(&self.constraints)?));
+        }
+
+        if !self.overrides.is_empty() {
+            table.insert("overrides", value(requirements_array(&self.overrides)?));
+        }
 
         if let Some(ref python) = self.python {
             table.insert("python", value(python));
@@ -175,6 +249,27 @@ impl Tool {
             value(entrypoints)
         });
 
+        if self.selection != EntrypointSelection::default() {
+            let serialized = serde::Serialize::serialize(
+                &self.selection,
+                toml_edit::ser::ValueSerializer::new(),
+            )?;
+            let Value::InlineTable(serialized) = serialized else {
+                return Err(toml_edit::ser::Error::Custom(
+                    "Expected an inline table".to_string(),
+                ));
+            };
+            table.insert("selection", Item::Table(serialized.into_table()));
+        }
+
+        if !self.env.is_empty() {
+            let mut env = Table::new();
+            for (key, val) in &self.env {
+                env.insert(key, value(val));
+            }
+            table.insert("env", Item::Table(env));
+        }
+
         if self.options != ToolOptions::default() {
             let serialized =
                 serde::Serialize::serialize(&self.options, toml_edit::ser::ValueSerializer::new())?;
@@ -197,6 +292,22 @@ impl Tool {
         &self.requirements
     }
 
+    pub fn constraints(&self) -> &[Requirement] {
+        &self.constraints
+    }
+
+    pub fn overrides(&self) -> &[Requirement] {
+        &self.overrides
+    }
+
+    pub fn selection(&self) -> &EntrypointSelection {
+        &self.selection
+    }
+
+    pub fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
     pub fn python(&self) -> &Option<String> {
         &self.python
     }
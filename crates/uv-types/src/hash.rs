@@ -4,11 +4,12 @@ use std::sync::Arc;
 use url::Url;
 
 use distribution_types::{
-    DistributionMetadata, HashPolicy, Name, Resolution, UnresolvedRequirement, VersionId,
+    DistributionMetadata, HashPolicy, IndexHashPolicies, IndexHashPolicy, IndexUrl, Name,
+    Resolution, UnresolvedRequirement, VersionId,
 };
 use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
-use pypi_types::{HashDigest, HashError, Requirement, RequirementSource};
+use pypi_types::{HashDigest, HashError, ParsedUrl, Requirement, RequirementSource};
 use uv_configuration::HashCheckingMode;
 use uv_normalize::PackageName;
 
@@ -27,6 +28,18 @@ pub enum HashStrategy {
     ///
     /// If necessary, hashes should be generated to ensure that the archive is valid.
     Require(Arc<FxHashMap<VersionId, Vec<HashDigest>>>),
+    /// Hashes should be validated against a pre-defined list of hashes, with the hash-checking
+    /// mode determined by the index that serves each package.
+    ///
+    /// Packages served by an index with a [`IndexHashPolicy::Require`] policy must have a hash,
+    /// as must any other packages (including direct URLs) if the default `mode` is
+    /// [`HashCheckingMode::Require`]. All other packages are validated if a hash is present, but
+    /// allowed otherwise.
+    PerIndex {
+        hashes: Arc<FxHashMap<VersionId, Vec<HashDigest>>>,
+        policies: Arc<IndexHashPolicies>,
+        mode: HashCheckingMode,
+    },
 }
 
 impl HashStrategy {
@@ -35,7 +48,7 @@ impl HashStrategy {
         match self {
             Self::None => HashPolicy::None,
             Self::Generate => HashPolicy::Generate,
-            Self::Verify(hashes) => {
+            Self::Verify(hashes) | Self::PerIndex { hashes, .. } => {
                 if let Some(hashes) = hashes.get(&distribution.version_id()) {
                     HashPolicy::Validate(hashes.as_slice())
                 } else {
@@ -56,7 +69,7 @@ impl HashStrategy {
         match self {
             Self::None => HashPolicy::None,
             Self::Generate => HashPolicy::Generate,
-            Self::Verify(hashes) => {
+            Self::Verify(hashes) | Self::PerIndex { hashes, .. } => {
                 if let Some(hashes) =
                     hashes.get(&VersionId::from_registry(name.clone(), version.clone()))
                 {
@@ -79,7 +92,7 @@ impl HashStrategy {
         match self {
             Self::None => HashPolicy::None,
             Self::Generate => HashPolicy::Generate,
-            Self::Verify(hashes) => {
+            Self::Verify(hashes) | Self::PerIndex { hashes, .. } => {
                 if let Some(hashes) = hashes.get(&VersionId::from_url(url)) {
                     HashPolicy::Validate(hashes.as_slice())
                 } else {
@@ -96,7 +109,15 @@ impl HashStrategy {
     }
 
     /// Returns `true` if the given registry-based package is allowed.
-    pub fn allows_package(&self, name: &PackageName, version: &Version) -> bool {
+    ///
+    /// The index is the index that serves the package, if any (e.g., it's `None` for packages
+    /// that are already installed).
+    pub fn allows_package(
+        &self,
+        name: &PackageName,
+        version: &Version,
+        index: Option<&IndexUrl>,
+    ) -> bool {
         match self {
             Self::None => true,
             Self::Generate => true,
@@ -104,6 +125,18 @@ impl HashStrategy {
             Self::Require(hashes) => {
                 hashes.contains_key(&VersionId::from_registry(name.clone(), version.clone()))
             }
+            Self::PerIndex {
+                hashes,
+                policies,
+                mode,
+            } => {
+                let require = match index.and_then(|index| policies.get(index)) {
+                    Some(policy) => matches!(policy, IndexHashPolicy::Require),
+                    None => mode.is_require(),
+                };
+                !require
+                    || hashes.contains_key(&VersionId::from_registry(name.clone(), version.clone()))
+            }
         }
     }
 
@@ -114,6 +147,18 @@ impl HashStrategy {
             Self::Generate => true,
             Self::Verify(_) => true,
             Self::Require(hashes) => hashes.contains_key(&VersionId::from_url(url)),
+            Self::PerIndex { hashes, mode, .. } => {
+                !mode.is_require() || hashes.contains_key(&VersionId::from_url(url))
+            }
+        }
+    }
+
+    /// Return the hash-checking policy for the packages served by the given index, if the index
+    /// has one.
+    pub fn index_policy(&self, index: &IndexUrl) -> Option<IndexHashPolicy> {
+        match self {
+            Self::PerIndex { policies, .. } => policies.get(index),
+            _ => None,
         }
     }
 
@@ -123,10 +168,16 @@ impl HashStrategy {
     /// that reference the environment as true. In other words, it does
     /// environment independent expression evaluation. (Which in turn devolves
     /// to "only evaluate marker expressions that reference an extra name.")
+    ///
+    /// If any `policies` are given, the hash-checking mode for each package is determined by the
+    /// index that serves it, falling back to `mode` for packages from other indexes. As the
+    /// serving index is only known once a requirement is resolved, requirements without hashes
+    /// needn't be pinned; instead, the resolver verifies that they're allowed without a hash.
     pub fn from_requirements<'a>(
         requirements: impl Iterator<Item = (&'a UnresolvedRequirement, &'a [String])>,
         markers: Option<&MarkerEnvironment>,
         mode: HashCheckingMode,
+        policies: &IndexHashPolicies,
     ) -> Result<Self, HashStrategyError> {
         let mut hashes = FxHashMap::<VersionId, Vec<HashDigest>>::default();
 
@@ -137,6 +188,19 @@ impl HashStrategy {
                 continue;
             }
 
+            // Parse the hashes, including any hash embedded in a direct URL.
+            let digests = digests
+                .iter()
+                .map(|digest| HashDigest::from_str(digest))
+                .chain(Self::url_fragment(requirement).transpose())
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // With per-index policies, the resolver verifies that any requirement without a hash
+            // is allowed by the policy of the serving index.
+            if digests.is_empty() && !policies.is_empty() {
+                continue;
+            }
+
             // Every requirement must be either a pinned version or a direct URL.
             let id = match &requirement {
                 UnresolvedRequirement::Named(requirement) => {
@@ -161,21 +225,97 @@ impl HashStrategy {
                 continue;
             }
 
-            // Parse the hashes.
-            let digests = digests
-                .iter()
-                .map(|digest| HashDigest::from_str(digest))
-                .collect::<Result<Vec<_>, _>>()?;
-
             hashes.insert(id, digests);
         }
 
+        if !policies.is_empty() {
+            return Ok(Self::PerIndex {
+                hashes: Arc::new(hashes),
+                policies: Arc::new(policies.clone()),
+                mode,
+            });
+        }
+
         match mode {
             HashCheckingMode::Verify => Ok(Self::Verify(Arc::new(hashes))),
             HashCheckingMode::Require => Ok(Self::Require(Arc::new(hashes))),
         }
     }
 
+    /// Generate the required hashes from the hash fragments (e.g., `#sha256=...`) of any direct
+    /// URL requirements.
+    ///
+    /// Hash fragments are always verified, even in the absence of `--require-hashes` or
+    /// `--verify-hashes`.
+    pub fn from_url_fragments<'a>(
+        requirements: impl Iterator<Item = &'a UnresolvedRequirement>,
+        markers: Option<&MarkerEnvironment>,
+    ) -> Result<Self, HashStrategyError> {
+        let mut hashes = FxHashMap::<VersionId, Vec<HashDigest>>::default();
+
+        for requirement in requirements {
+            if !requirement.evaluate_markers(markers, &[]) {
+                continue;
+            }
+            let Some(digest) = Self::url_fragment(requirement)? else {
+                continue;
+            };
+            let url = match requirement {
+                UnresolvedRequirement::Named(requirement) => {
+                    let RequirementSource::Url { url, .. } = &requirement.source else {
+                        continue;
+                    };
+                    url
+                }
+                UnresolvedRequirement::Unnamed(requirement) => &requirement.url.verbatim,
+            };
+            hashes.insert(VersionId::from_url(url), vec![digest]);
+        }
+
+        if hashes.is_empty() {
+            Ok(Self::None)
+        } else {
+            Ok(Self::Verify(Arc::new(hashes)))
+        }
+    }
+
+    /// Extract the hash embedded in the fragment of a direct URL requirement, as in
+    /// `https://example.com/foo-1.0-py3-none-any.whl#sha256=...`.
+    fn url_fragment(requirement: &UnresolvedRequirement) -> Result<Option<HashDigest>, HashError> {
+        let fragment = match requirement {
+            UnresolvedRequirement::Named(requirement) => {
+                let RequirementSource::Url { url, .. } = &requirement.source else {
+                    return Ok(None);
+                };
+                url.fragment()
+            }
+            UnresolvedRequirement::Unnamed(requirement) => {
+                if !matches!(requirement.url.parsed_url, ParsedUrl::Archive(_)) {
+                    return Ok(None);
+                }
+                requirement.url.verbatim.fragment()
+            }
+        };
+        fragment.map_or(Ok(None), Self::fragment_digest)
+    }
+
+    /// Extract the hash embedded in the fragment of a URL, as in
+    /// `https://example.com/foo-1.0-py3-none-any.whl#sha256=...`.
+    pub fn url_digest(url: &Url) -> Result<Option<HashDigest>, HashError> {
+        url.fragment().map_or(Ok(None), Self::fragment_digest)
+    }
+
+    /// Parse the hash in a URL fragment, which may contain other parameters, as in
+    /// `#sha256=...&subdirectory=...`.
+    fn fragment_digest(fragment: &str) -> Result<Option<HashDigest>, HashError> {
+        fragment
+            .split('&')
+            .filter_map(|part| part.split_once('='))
+            .find(|(algorithm, _)| matches!(*algorithm, "md5" | "sha256" | "sha384" | "sha512"))
+            .map(|(algorithm, digest)| HashDigest::from_str(&format!("{algorithm}:{digest}")))
+            .transpose()
+    }
+
     /// Generate the required hashes from a [`Resolution`].
     pub fn from_resolution(
         resolution: &Resolution,
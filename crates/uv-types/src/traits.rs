@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 
@@ -7,7 +8,7 @@ use distribution_types::{CachedDist, IndexLocations, InstalledDist, Resolution,
 use pep508_rs::PackageName;
 use pypi_types::Requirement;
 use uv_cache::Cache;
-use uv_configuration::{BuildKind, BuildOptions, SourceStrategy};
+use uv_configuration::{BuildKind, BuildOptions, PackageBuildEnv, SourceStrategy};
 use uv_git::GitResolver;
 use uv_python::PythonEnvironment;
 
@@ -63,12 +64,28 @@ pub trait BuildContext {
     /// This method exists to avoid fetching source distributions if we know we can't build them.
     fn build_options(&self) -> &BuildOptions;
 
+    /// The extra environment variables to set when building specific packages.
+    ///
+    /// Since these variables can affect the build output, they should be included in the cache
+    /// key of any wheels built from source.
+    fn package_build_env(&self) -> &PackageBuildEnv;
+
     /// Whether to incorporate `tool.uv.sources` when resolving requirements.
     fn sources(&self) -> SourceStrategy;
 
     /// The index locations being searched.
     fn index_locations(&self) -> &IndexLocations;
 
+    /// Whether to store unzipped wheels in the cache by their SHA-256 digest, such that identical
+    /// wheels share a single archive, as required by `--link-mode=farm`.
+    fn content_addressed_archives(&self) -> bool;
+
+    /// The command to run on each platform-specific wheel built from source, if any.
+    ///
+    /// Since the command rewrites the built wheel, it should be included in the cache key of any
+    /// wheels built from source.
+    fn repair_wheel_command(&self) -> Option<&str>;
+
     /// Resolve the given requirements into a ready-to-install set of package versions.
     fn resolve<'a>(
         &'a self,
@@ -119,6 +136,12 @@ pub trait SourceBuildTrait {
     ///
     /// Returns the filename of the built wheel inside the given `wheel_dir`.
     fn wheel<'a>(&'a self, wheel_dir: &'a Path) -> impl Future<Output = Result<String>> + 'a;
+
+    /// The packages that were installed into the build environment, i.e., the build backend and
+    /// its dependencies, along with any additional requirements reported by the backend.
+    ///
+    /// Empty if build isolation is disabled.
+    fn build_inputs(&self) -> &BTreeSet<PackageName>;
 }
 
 /// A wrapper for [`uv_installer::SitePackages`]
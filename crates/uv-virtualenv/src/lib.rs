@@ -6,6 +6,7 @@ use thiserror::Error;
 use platform_tags::PlatformError;
 use uv_python::{Interpreter, PythonEnvironment};
 
+mod template;
 mod virtualenv;
 
 #[derive(Debug, Error)]
@@ -62,9 +63,44 @@ pub fn create_venv(
         system_site_packages,
         allow_existing,
         relocatable,
+        true,
     )?;
 
     // Create the corresponding `PythonEnvironment`.
     let interpreter = interpreter.with_virtualenv(virtualenv);
     Ok(PythonEnvironment::from_interpreter(interpreter))
 }
+
+/// Create a virtualenv by cloning a template for the interpreter, creating the template in the
+/// `templates` directory if necessary.
+///
+/// Intended for ephemeral environments: the virtualenv omits the prompt and activation scripts.
+/// The location must not exist, or must be an empty directory.
+pub fn create_venv_from_template(
+    location: &Path,
+    interpreter: Interpreter,
+    templates: &Path,
+    system_site_packages: bool,
+    relocatable: bool,
+) -> Result<PythonEnvironment, Error> {
+    let virtualenv = template::create_from_template(
+        location,
+        &interpreter,
+        templates,
+        system_site_packages,
+        relocatable,
+    )?;
+    let interpreter = interpreter.with_virtualenv(virtualenv);
+    Ok(PythonEnvironment::from_interpreter(interpreter))
+}
+
+/// Return the [`PythonEnvironment`] for a relocatable virtualenv at the given location, created
+/// from the given interpreter, without querying the virtualenv's interpreter.
+pub fn relocatable_venv_at(
+    location: &Path,
+    interpreter: Interpreter,
+) -> Result<PythonEnvironment, Error> {
+    let virtualenv = virtualenv::virtualenv_at(location.canonicalize()?, &interpreter);
+    let interpreter = interpreter.with_virtualenv(virtualenv);
+    Ok(PythonEnvironment::from_interpreter(interpreter))
+}
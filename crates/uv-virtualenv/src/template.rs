@@ -0,0 +1,126 @@
+//! Create a virtual environment by cloning a template.
+
+use std::io;
+use std::path::Path;
+
+use fs_err as fs;
+use tracing::debug;
+
+use cache_key::cache_digest;
+use uv_fs::Simplified;
+use uv_python::{Interpreter, VirtualEnvironment};
+use uv_version::version;
+
+use crate::virtualenv::{clone_or_copy, create, virtualenv_at};
+use crate::{Error, Prompt};
+
+/// Create a [`VirtualEnvironment`] at the given location by cloning the template for the
+/// interpreter from the `templates` directory, creating the template if necessary.
+pub(crate) fn create_from_template(
+    location: &Path,
+    interpreter: &Interpreter,
+    templates: &Path,
+    system_site_packages: bool,
+    relocatable: bool,
+) -> Result<VirtualEnvironment, Error> {
+    // The template only depends on the base interpreter and the environment options, since the
+    // environment has no prompt. Non-relocatable environments only embed their own location in
+    // the activation scripts, which are omitted, so the clone is valid at any location.
+    let template = templates.join(cache_digest(&(
+        interpreter.sys_executable(),
+        interpreter.markers().python_full_version().string.as_str(),
+        interpreter.markers().implementation_name(),
+        system_site_packages,
+        relocatable,
+        version(),
+    )));
+
+    if !template.join("pyvenv.cfg").is_file() {
+        debug!(
+            "Creating virtual environment template at: {}",
+            template.user_display()
+        );
+        fs::create_dir_all(templates)?;
+        let temp_dir = tempfile::tempdir_in(templates)?;
+        create(
+            temp_dir.path(),
+            interpreter,
+            Prompt::None,
+            system_site_packages,
+            false,
+            relocatable,
+            false,
+        )?;
+        match fs::rename(temp_dir.path(), &template) {
+            Ok(()) => {}
+            // Another process created the template concurrently.
+            Err(err) if template.join("pyvenv.cfg").is_file() => {
+                debug!("Using concurrently created template: {err}");
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    match location.metadata() {
+        Ok(metadata) if metadata.is_dir() => {
+            if location.read_dir()?.next().is_some() {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!(
+                        "The directory `{}` exists, but it's not empty",
+                        location.user_display()
+                    ),
+                )));
+            }
+        }
+        Ok(_) => {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("File exists at `{}`", location.user_display()),
+            )));
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            fs::create_dir_all(location)?;
+        }
+        Err(err) => return Err(Error::Io(err)),
+    }
+
+    let location = location.canonicalize()?;
+    debug!(
+        "Cloning virtual environment template to: {}",
+        location.user_display()
+    );
+    clone_dir(&template, &location)?;
+
+    Ok(virtualenv_at(location, interpreter))
+}
+
+/// Recursively clone the contents of the directory `from` into the existing directory `to`.
+///
+/// Files are cloned via copy-on-write if the filesystem supports it (and copied otherwise), such
+/// that modifying the clone leaves the template intact. Symlinks are recreated as-is.
+fn clone_dir(from: &Path, to: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            let link = fs::read_link(&path)?;
+            #[cfg(unix)]
+            fs_err::os::unix::fs::symlink(link, &target)?;
+            #[cfg(windows)]
+            if path.is_dir() {
+                fs_err::os::windows::fs::symlink_dir(link, &target)?;
+            } else {
+                fs_err::os::windows::fs::symlink_file(link, &target)?;
+            }
+        } else if file_type.is_dir() {
+            fs::create_dir(&target)?;
+            clone_dir(&path, &target)?;
+        } else {
+            clone_or_copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
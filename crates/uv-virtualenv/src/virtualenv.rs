@@ -3,7 +3,7 @@
 use std::env::consts::EXE_SUFFIX;
 use std::io;
 use std::io::{BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use fs_err as fs;
 use fs_err::File;
@@ -43,6 +43,8 @@ fn write_cfg(f: &mut impl Write, data: &[(String, String)]) -> io::Result<()> {
 }
 
 /// Create a [`VirtualEnvironment`] at the given location.
+///
+/// If `activators` is `false`, the activation scripts are omitted.
 pub(crate) fn create(
     location: &Path,
     interpreter: &Interpreter,
@@ -50,6 +52,7 @@ pub(crate) fn create(
     system_site_packages: bool,
     allow_existing: bool,
     relocatable: bool,
+    activators: bool,
 ) -> Result<VirtualEnvironment, Error> {
     // Determine the base Python executable; that is, the Python executable that should be
     // considered the "base" for the virtual environment. This is typically the Python executable
@@ -95,7 +98,7 @@ pub(crate) fn create(
                     info!("Allowing existing directory");
                 } else if location.join("pyvenv.cfg").is_file() {
                     info!("Removing existing directory");
-                    fs::remove_dir_all(location)?;
+                    uv_fs::force_remove_dir_all(location)?;
                     fs::create_dir_all(location)?;
                 } else if location
                     .read_dir()
@@ -153,11 +156,10 @@ pub(crate) fn create(
 
     // Different names for the python interpreter
     fs::create_dir_all(&scripts)?;
-    let executable = scripts.join(format!("python{EXE_SUFFIX}"));
 
     #[cfg(unix)]
     {
-        uv_fs::replace_symlink(&base_python, &executable)?;
+        uv_fs::replace_symlink(&base_python, scripts.join(format!("python{EXE_SUFFIX}")))?;
         uv_fs::replace_symlink(
             "python",
             scripts.join(format!("python{}", interpreter.python_major())),
@@ -278,7 +280,7 @@ pub(crate) fn create(
     }
 
     // Add all the activate scripts for different shells
-    for (name, template) in ACTIVATE_TEMPLATES {
+    for (name, template) in ACTIVATE_TEMPLATES.iter().filter(|_| activators) {
         let path_sep = if cfg!(windows) { ";" } else { ":" };
 
         let relative_site_packages = [
@@ -402,7 +404,12 @@ pub(crate) fn create(
     fs::write(site_packages.join("_virtualenv.py"), VIRTUALENV_PATCH)?;
     fs::write(site_packages.join("_virtualenv.pth"), "import _virtualenv")?;
 
-    Ok(VirtualEnvironment {
+    Ok(virtualenv_at(location, interpreter))
+}
+
+/// Return the [`VirtualEnvironment`] for the given interpreter at the given (canonical) location.
+pub(crate) fn virtualenv_at(location: PathBuf, interpreter: &Interpreter) -> VirtualEnvironment {
+    VirtualEnvironment {
         scheme: Scheme {
             purelib: location.join(&interpreter.virtualenv().purelib),
             platlib: location.join(&interpreter.virtualenv().platlib),
@@ -410,9 +417,11 @@ pub(crate) fn create(
             data: location.join(&interpreter.virtualenv().data),
             include: location.join(&interpreter.virtualenv().include),
         },
+        executable: location
+            .join(&interpreter.virtualenv().scripts)
+            .join(format!("python{EXE_SUFFIX}")),
         root: location,
-        executable,
-    })
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -497,6 +506,16 @@ impl WindowsExecutable {
     }
 }
 
+/// Copy a file, using a copy-on-write clone if the filesystem supports it.
+pub(crate) fn clone_or_copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    if reflink_copy::reflink(from, to).is_ok() {
+        return Ok(());
+    }
+    fs_err::copy(from, to)?;
+    Ok(())
+}
+
 /// <https://github.com/python/cpython/blob/d457345bbc6414db0443819290b04a9a4333313d/Lib/venv/__init__.py#L261-L267>
 /// <https://github.com/pypa/virtualenv/blob/d9fdf48d69f0d0ca56140cf0381edbb5d6fe09f5/src/virtualenv/create/via_global_ref/builtin/cpython/cpython3.py#L78-L83>
 ///
@@ -516,7 +535,7 @@ fn copy_launcher_windows(
         .join("scripts")
         .join("nt")
         .join(executable.exe(interpreter));
-    match fs_err::copy(shim, scripts.join(executable.exe(interpreter))) {
+    match clone_or_copy(shim, scripts.join(executable.exe(interpreter))) {
         Ok(_) => return Ok(()),
         Err(err) if err.kind() == io::ErrorKind::NotFound => {}
         Err(err) => {
@@ -533,7 +552,7 @@ fn copy_launcher_windows(
         .join("scripts")
         .join("nt")
         .join(executable.launcher());
-    match fs_err::copy(shim, scripts.join(executable.exe(interpreter))) {
+    match clone_or_copy(shim, scripts.join(executable.exe(interpreter))) {
         Ok(_) => return Ok(()),
         Err(err) if err.kind() == io::ErrorKind::NotFound => {}
         Err(err) => {
@@ -544,7 +563,7 @@ fn copy_launcher_windows(
     // Third priority: on Conda at least, we can look for the launcher shim next to
     // the Python executable itself.
     let shim = base_python.with_file_name(executable.launcher());
-    match fs_err::copy(shim, scripts.join(executable.exe(interpreter))) {
+    match clone_or_copy(shim, scripts.join(executable.exe(interpreter))) {
         Ok(_) => return Ok(()),
         Err(err) if err.kind() == io::ErrorKind::NotFound => {}
         Err(err) => {
@@ -555,7 +574,7 @@ fn copy_launcher_windows(
     // Fourth priority: if the launcher shim doesn't exist, assume this is
     // an embedded Python. Copy the Python executable itself, along with
     // the DLLs, `.pyd` files, and `.zip` files in the same directory.
-    match fs_err::copy(
+    match clone_or_copy(
         base_python.with_file_name(executable.exe(interpreter)),
         scripts.join(executable.exe(interpreter)),
     ) {
@@ -582,7 +601,7 @@ fn copy_launcher_windows(
                         ext.eq_ignore_ascii_case("dll") || ext.eq_ignore_ascii_case("pyd")
                     }) {
                         if let Some(file_name) = path.file_name() {
-                            fs_err::copy(&path, scripts.join(file_name))?;
+                            clone_or_copy(&path, scripts.join(file_name))?;
                         }
                     }
                 }
@@ -599,7 +618,7 @@ fn copy_launcher_windows(
                             .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
                         {
                             if let Some(file_name) = path.file_name() {
-                                fs_err::copy(&path, scripts.join(file_name))?;
+                                clone_or_copy(&path, scripts.join(file_name))?;
                             }
                         }
                     }
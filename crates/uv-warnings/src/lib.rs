@@ -21,17 +21,46 @@ pub fn disable() {
     ENABLED.store(false, std::sync::atomic::Ordering::SeqCst);
 }
 
+/// Whether user-facing warnings are reported as JSON Lines events.
+pub static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Report user-facing warnings as JSON Lines events, e.g., `{"event":"warning","message":"..."}`.
+pub fn enable_json() {
+    JSON.store(true, std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Write a warning to stderr, in the configured format.
+#[doc(hidden)]
+pub fn write(message: &str) {
+    use owo_colors::OwoColorize;
+
+    if JSON.load(std::sync::atomic::Ordering::SeqCst) {
+        let event = serde_json::json!({
+            "event": "warning",
+            "message": anstream::adapter::strip_str(message).to_string(),
+        });
+        anstream::eprintln!("{event}");
+    } else {
+        anstream::eprintln!(
+            "{}{} {}",
+            "warning".yellow().bold(),
+            ":".bold(),
+            message.bold()
+        );
+    }
+}
+
 /// Warn a user, if warnings are enabled.
 #[macro_export]
 macro_rules! warn_user {
     ($($arg:tt)*) => {
-        use $crate::anstream::eprintln;
+        // Allow the arguments to use colors without importing the trait.
+        #[allow(unused_imports)]
         use $crate::owo_colors::OwoColorize;
 
         if $crate::ENABLED.load(std::sync::atomic::Ordering::SeqCst) {
             let message = format!("{}", format_args!($($arg)*));
-            let formatted = message.bold();
-            eprintln!("{}{} {formatted}", "warning".yellow().bold(), ":".bold());
+            $crate::write(&message);
         }
     };
 }
@@ -43,14 +72,15 @@ pub static WARNINGS: LazyLock<Mutex<FxHashSet<String>>> = LazyLock::new(Mutex::d
 #[macro_export]
 macro_rules! warn_user_once {
     ($($arg:tt)*) => {
-        use $crate::anstream::eprintln;
+        // Allow the arguments to use colors without importing the trait.
+        #[allow(unused_imports)]
         use $crate::owo_colors::OwoColorize;
 
         if $crate::ENABLED.load(std::sync::atomic::Ordering::SeqCst) {
             if let Ok(mut states) = $crate::WARNINGS.lock() {
                 let message = format!("{}", format_args!($($arg)*));
                 if states.insert(message.clone()) {
-                    eprintln!("{}{} {}", "warning".yellow().bold(), ":".bold(), message.bold());
+                    $crate::write(&message);
                 }
             }
         }
@@ -0,0 +1,187 @@
+//! PEP 735 dependency groups (i.e., the `[dependency-groups]` table), including groups that
+//! include other groups via `{ include-group = "..." }`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use pep508_rs::Pep508Error;
+use pypi_types::VerbatimParsedUrl;
+use uv_normalize::GroupName;
+
+/// The dependency groups declared in a `pyproject.toml`, keyed by group name.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DependencyGroups(BTreeMap<GroupName, Vec<DependencyGroupSpecifier>>);
+
+/// An entry in a dependency group: either a PEP 508 requirement, or a reference to another group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DependencyGroupSpecifier {
+    /// Ex) `"pytest>=8"`
+    Requirement(String),
+    /// Ex) `{ include-group = "test" }`
+    #[serde(rename_all = "kebab-case")]
+    IncludeGroup { include_group: GroupName },
+}
+
+#[derive(Debug, Error)]
+pub enum DependencyGroupError {
+    #[error("Dependency group `{0}` was not found")]
+    NotFound(GroupName),
+    #[error("Failed to parse entry in dependency group `{0}`")]
+    Parse(GroupName, #[source] Box<Pep508Error<VerbatimParsedUrl>>),
+    #[error("Detected a cycle in the dependency groups: {}", _0.iter().map(|group| format!("`{group}`")).collect::<Vec<_>>().join(" -> "))]
+    Cycle(Vec<GroupName>),
+}
+
+impl DependencyGroups {
+    /// Returns `true` if the given group is declared.
+    pub fn contains(&self, group: &GroupName) -> bool {
+        self.0.contains_key(group)
+    }
+
+    /// Return the names of the declared groups.
+    pub fn names(&self) -> impl Iterator<Item = &GroupName> {
+        self.0.keys()
+    }
+
+    /// Return the requirements of every declared group, with any included groups expanded in
+    /// place.
+    ///
+    /// Relative paths in the requirements are resolved against the given directory.
+    pub fn flatten(
+        &self,
+        root: &Path,
+    ) -> Result<
+        BTreeMap<GroupName, Vec<pep508_rs::Requirement<VerbatimParsedUrl>>>,
+        DependencyGroupError,
+    > {
+        self.0
+            .keys()
+            .map(|group| Ok((group.clone(), self.requirements(group, root)?)))
+            .collect()
+    }
+
+    /// Return the requirements of the given group, including those of any groups it includes.
+    ///
+    /// Relative paths in the requirements are resolved against the given directory.
+    pub fn requirements(
+        &self,
+        group: &GroupName,
+        root: &Path,
+    ) -> Result<Vec<pep508_rs::Requirement<VerbatimParsedUrl>>, DependencyGroupError> {
+        let mut requirements = Vec::new();
+        self.collect(group, root, &mut Vec::new(), &mut requirements)?;
+        Ok(requirements)
+    }
+
+    /// Recursively collect the requirements of the given group, tracking the chain of included
+    /// groups to detect cycles.
+    fn collect<'a>(
+        &'a self,
+        group: &'a GroupName,
+        root: &Path,
+        parents: &mut Vec<&'a GroupName>,
+        requirements: &mut Vec<pep508_rs::Requirement<VerbatimParsedUrl>>,
+    ) -> Result<(), DependencyGroupError> {
+        let Some(specifiers) = self.0.get(group) else {
+            return Err(DependencyGroupError::NotFound(group.clone()));
+        };
+        if parents.contains(&group) {
+            return Err(DependencyGroupError::Cycle(
+                parents
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(group))
+                    .cloned()
+                    .collect(),
+            ));
+        }
+
+        parents.push(group);
+        for specifier in specifiers {
+            match specifier {
+                DependencyGroupSpecifier::Requirement(requirement) => {
+                    let requirement =
+                        pep508_rs::Requirement::<VerbatimParsedUrl>::parse(requirement, root)
+                            .map_err(|err| {
+                                DependencyGroupError::Parse(group.clone(), Box::new(err))
+                            })?;
+                    // Avoid duplicating requirements that are reachable via multiple groups.
+                    if !requirements.contains(&requirement) {
+                        requirements.push(requirement);
+                    }
+                }
+                DependencyGroupSpecifier::IncludeGroup { include_group } => {
+                    self.collect(include_group, root, parents, requirements)?;
+                }
+            }
+        }
+        parents.pop();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{DependencyGroupError, DependencyGroups};
+
+    #[test]
+    fn include_group() {
+        let groups: DependencyGroups = toml::from_str(
+            r#"
+            test = ["pytest"]
+            lint = ["ruff", "mypy"]
+            typing = ["mypy", { include-group = "test" }]
+            ci = [{ include-group = "test" }, { include-group = "lint" }, { include-group = "typing" }]
+            "#,
+        )
+        .unwrap();
+
+        let requirements = groups
+            .requirements(&"ci".parse().unwrap(), Path::new(""))
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(requirements, ["pytest", "ruff", "mypy"]);
+    }
+
+    #[test]
+    fn include_group_cycle() {
+        let groups: DependencyGroups = toml::from_str(
+            r#"
+            a = ["pytest", { include-group = "b" }]
+            b = [{ include-group = "c" }]
+            c = [{ include-group = "a" }]
+            "#,
+        )
+        .unwrap();
+
+        let err = groups.flatten(Path::new("")).unwrap_err();
+        assert!(matches!(err, DependencyGroupError::Cycle(_)));
+        assert_eq!(
+            err.to_string(),
+            "Detected a cycle in the dependency groups: `a` -> `b` -> `c` -> `a`"
+        );
+    }
+
+    #[test]
+    fn include_group_missing() {
+        let groups: DependencyGroups = toml::from_str(
+            r#"
+            ci = [{ include-group = "test" }]
+            "#,
+        )
+        .unwrap();
+
+        let err = groups.flatten(Path::new("")).unwrap_err();
+        assert_eq!(err.to_string(), "Dependency group `test` was not found");
+    }
+}
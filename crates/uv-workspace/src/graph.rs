@@ -0,0 +1,342 @@
+//! The dependency graph between the members of a workspace.
+//!
+//! Unlike the lockfile, the graph is derived directly from the static metadata in each member's
+//! `pyproject.toml`, so it can be computed without resolving (or building) anything.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use pypi_types::{MetadataError, RequiresDist};
+use uv_normalize::PackageName;
+
+use crate::workspace::{Workspace, WorkspaceError};
+
+/// The dependencies of a single workspace member.
+#[derive(Debug, Clone)]
+pub struct MemberDependencies {
+    /// The path to the member's project root.
+    root: PathBuf,
+    /// The other workspace members that this member depends on, including via optional
+    /// dependencies.
+    members: BTreeSet<PackageName>,
+    /// The other workspace members that this member depends on via its development dependencies.
+    dev_members: BTreeSet<PackageName>,
+    /// The dependencies on packages outside the workspace.
+    external: BTreeSet<PackageName>,
+    /// Whether the member declares its dependencies as dynamic, in which case they can't be
+    /// determined without building the member.
+    dynamic: bool,
+}
+
+impl MemberDependencies {
+    /// The path to the member's project root.
+    pub fn root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    /// The workspace members that this member depends on.
+    pub fn members(&self) -> &BTreeSet<PackageName> {
+        &self.members
+    }
+
+    /// The workspace members that this member depends on via development dependencies.
+    pub fn dev_members(&self) -> &BTreeSet<PackageName> {
+        &self.dev_members
+    }
+
+    /// The dependencies on packages outside the workspace.
+    pub fn external(&self) -> &BTreeSet<PackageName> {
+        &self.external
+    }
+
+    /// Whether the member's dependencies are dynamic, and thus omitted from the graph.
+    pub fn is_dynamic(&self) -> bool {
+        self.dynamic
+    }
+
+    /// Iterate over all workspace members this member depends on, of any kind.
+    fn all_members(&self) -> impl Iterator<Item = &PackageName> {
+        self.members.iter().chain(self.dev_members.iter())
+    }
+}
+
+/// The dependency graph between the members of a [`Workspace`].
+#[derive(Debug, Clone)]
+pub struct WorkspaceGraph {
+    /// The path to the workspace root.
+    install_path: PathBuf,
+    members: BTreeMap<PackageName, MemberDependencies>,
+}
+
+impl WorkspaceGraph {
+    /// Build the graph from the `pyproject.toml` files of the workspace members.
+    pub fn from_workspace(workspace: &Workspace) -> Result<Self, WorkspaceError> {
+        let mut members = BTreeMap::new();
+
+        for (name, member) in workspace.packages() {
+            let (requirements, dynamic) =
+                match RequiresDist::parse_pyproject_toml(&member.pyproject_toml().raw) {
+                    Ok(requires_dist) => (requires_dist.requires_dist, false),
+                    Err(MetadataError::DynamicField(_) | MetadataError::PoetrySyntax) => {
+                        (Vec::new(), true)
+                    }
+                    Err(err) => {
+                        return Err(WorkspaceError::Dependencies(
+                            member.root().join("pyproject.toml"),
+                            Box::new(err),
+                        ))
+                    }
+                };
+
+            let mut dependencies = MemberDependencies {
+                root: member.root().clone(),
+                members: BTreeSet::new(),
+                dev_members: BTreeSet::new(),
+                external: BTreeSet::new(),
+                dynamic,
+            };
+
+            for requirement in requirements {
+                if workspace.packages().contains_key(&requirement.name) {
+                    if requirement.name != *name {
+                        dependencies.members.insert(requirement.name);
+                    }
+                } else {
+                    dependencies.external.insert(requirement.name);
+                }
+            }
+
+            let dev_dependencies = member
+                .pyproject_toml()
+                .tool
+                .as_ref()
+                .and_then(|tool| tool.uv.as_ref())
+                .and_then(|uv| uv.dev_dependencies.as_ref())
+                .into_iter()
+                .flatten();
+            for requirement in dev_dependencies {
+                if workspace.packages().contains_key(&requirement.name) {
+                    if requirement.name != *name {
+                        dependencies.dev_members.insert(requirement.name.clone());
+                    }
+                } else {
+                    dependencies.external.insert(requirement.name.clone());
+                }
+            }
+
+            members.insert(name.clone(), dependencies);
+        }
+
+        Ok(Self {
+            install_path: workspace.install_path().clone(),
+            members,
+        })
+    }
+
+    /// Iterate over the members of the workspace, along with their dependencies.
+    pub fn members(&self) -> impl Iterator<Item = (&PackageName, &MemberDependencies)> {
+        self.members.iter()
+    }
+
+    /// Return the dependencies of the given member, if it's part of the workspace.
+    pub fn get(&self, name: &PackageName) -> Option<&MemberDependencies> {
+        self.members.get(name)
+    }
+
+    /// Return the members that directly depend on the given member.
+    pub fn dependents(&self, name: &PackageName) -> BTreeSet<&PackageName> {
+        self.members
+            .iter()
+            .filter(|(_, dependencies)| dependencies.all_members().any(|member| member == name))
+            .map(|(member, _)| member)
+            .collect()
+    }
+
+    /// Return the members that aren't depended on by any other member.
+    pub fn roots(&self) -> BTreeSet<&PackageName> {
+        self.members
+            .keys()
+            .filter(|name| self.dependents(name).is_empty())
+            .collect()
+    }
+
+    /// Return the members in dependency order, such that each member appears after every member it
+    /// depends on.
+    ///
+    /// Development dependencies are ignored, since they aren't required to build a member. Members
+    /// that don't depend on each other are ordered by name.
+    pub fn topological_order(&self) -> Result<Vec<&PackageName>, WorkspaceError> {
+        let mut remaining = self
+            .members
+            .iter()
+            .map(|(name, dependencies)| {
+                (name, dependencies.members.iter().collect::<BTreeSet<_>>())
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let mut order = Vec::with_capacity(self.members.len());
+        while !remaining.is_empty() {
+            let ready = remaining
+                .iter()
+                .filter(|(_, dependencies)| dependencies.is_empty())
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>();
+            if ready.is_empty() {
+                return Err(WorkspaceError::DependencyCycle(
+                    remaining.into_keys().cloned().collect(),
+                ));
+            }
+            for name in ready {
+                remaining.remove(name);
+                for dependencies in remaining.values_mut() {
+                    dependencies.remove(name);
+                }
+                order.push(name);
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Return the set of members affected by a change to any of the given members: the members
+    /// themselves, along with any member that depends on them, directly or transitively.
+    pub fn affected_by<'a>(
+        &'a self,
+        changed: impl IntoIterator<Item = &'a PackageName>,
+    ) -> BTreeSet<&'a PackageName> {
+        let mut affected = BTreeSet::new();
+        let mut queue = changed
+            .into_iter()
+            .filter_map(|name| self.members.get_key_value(name).map(|(name, _)| name))
+            .collect::<VecDeque<_>>();
+        while let Some(name) = queue.pop_front() {
+            if affected.insert(name) {
+                queue.extend(self.dependents(name));
+            }
+        }
+        affected
+    }
+
+    /// Return the member that contains the given absolute path, if any.
+    ///
+    /// If the path is contained in multiple members (e.g., a root project and a member nested
+    /// within it), the member with the most specific root is returned.
+    pub fn member_for_path(&self, path: &Path) -> Option<&PackageName> {
+        self.members
+            .iter()
+            .filter(|(_, dependencies)| path.starts_with(&dependencies.root))
+            .max_by_key(|(_, dependencies)| dependencies.root.components().count())
+            .map(|(name, _)| name)
+    }
+
+    /// Return the set of members affected by changes to any of the given absolute paths.
+    ///
+    /// Each path is attributed to the most specific member that contains it. A change to the
+    /// lockfile, or to a path outside of every member, is assumed to affect all members.
+    pub fn affected_by_paths(
+        &self,
+        paths: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> BTreeSet<&PackageName> {
+        let lockfile = self.install_path.join("uv.lock");
+        let mut changed = Vec::new();
+        for path in paths {
+            let path = path.as_ref();
+            if path == lockfile {
+                return self.members.keys().collect();
+            }
+            let Some(member) = self.member_for_path(path) else {
+                return self.members.keys().collect();
+            };
+            changed.push(member);
+        }
+        self.affected_by(changed)
+    }
+
+    /// Return the external packages that are required by more than one member, along with the
+    /// members that require them.
+    pub fn shared_dependencies(&self) -> BTreeMap<&PackageName, BTreeSet<&PackageName>> {
+        let mut shared: BTreeMap<&PackageName, BTreeSet<&PackageName>> = BTreeMap::new();
+        for (member, dependencies) in &self.members {
+            for dependency in &dependencies.external {
+                shared.entry(dependency).or_default().insert(member);
+            }
+        }
+        shared.retain(|_, members| members.len() > 1);
+        shared
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)] // Avoid path escaping for the unit tests
+mod tests {
+    use std::env;
+    use std::str::FromStr;
+
+    use uv_normalize::PackageName;
+
+    use crate::workspace::{DiscoveryOptions, Workspace};
+    use crate::WorkspaceGraph;
+
+    #[tokio::test]
+    async fn albatross_root_workspace() {
+        let root = env::current_dir()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("scripts")
+            .join("workspaces")
+            .join("albatross-root-workspace");
+        let workspace = Workspace::discover(&root, &DiscoveryOptions::default())
+            .await
+            .unwrap();
+        let graph = WorkspaceGraph::from_workspace(&workspace).unwrap();
+
+        let albatross = PackageName::from_str("albatross").unwrap();
+        let bird_feeder = PackageName::from_str("bird-feeder").unwrap();
+        let seeds = PackageName::from_str("seeds").unwrap();
+
+        assert_eq!(
+            graph.get(&albatross).unwrap().members(),
+            &[bird_feeder.clone()].into_iter().collect()
+        );
+        assert_eq!(
+            graph.get(&bird_feeder).unwrap().members(),
+            &[seeds.clone()].into_iter().collect()
+        );
+        assert_eq!(graph.roots(), [&albatross].into_iter().collect());
+        assert_eq!(
+            graph.topological_order().unwrap(),
+            [&seeds, &bird_feeder, &albatross]
+        );
+        assert_eq!(
+            graph.affected_by([&seeds]),
+            [&albatross, &bird_feeder, &seeds].into_iter().collect()
+        );
+        assert_eq!(
+            graph.affected_by([&albatross]),
+            [&albatross].into_iter().collect()
+        );
+        assert!(graph.shared_dependencies().is_empty());
+
+        let seeds_module = root
+            .join("packages")
+            .join("seeds")
+            .join("src")
+            .join("seeds");
+        assert_eq!(graph.member_for_path(&seeds_module), Some(&seeds));
+        assert_eq!(
+            graph.member_for_path(&root.join("src").join("albatross")),
+            Some(&albatross)
+        );
+        assert_eq!(
+            graph.affected_by_paths([&seeds_module]),
+            [&albatross, &bird_feeder, &seeds].into_iter().collect()
+        );
+        assert_eq!(
+            graph.affected_by_paths([root.join("uv.lock")]),
+            [&albatross, &bird_feeder, &seeds].into_iter().collect()
+        );
+    }
+}
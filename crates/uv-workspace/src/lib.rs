@@ -1,8 +1,11 @@
+pub use graph::{MemberDependencies, WorkspaceGraph};
 pub use workspace::{
     check_nested_workspaces, DiscoveryOptions, ProjectWorkspace, VirtualProject, Workspace,
     WorkspaceError, WorkspaceMember,
 };
 
+pub mod dependency_groups;
+mod graph;
 pub mod pyproject;
 pub mod pyproject_mut;
 mod workspace;
@@ -1,12 +1,14 @@
 //! Reads the following fields from `pyproject.toml`:
 //!
 //! * `project.{dependencies,optional-dependencies}`
+//! * `dependency-groups`
 //! * `tool.uv.sources`
 //! * `tool.uv.workspace`
 //!
 //! Then lowers them into a dependency specification.
 
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::{collections::BTreeMap, mem};
 
 use glob::Pattern;
@@ -16,9 +18,12 @@ use url::Url;
 
 use pep440_rs::VersionSpecifiers;
 use pypi_types::{RequirementSource, VerbatimParsedUrl};
+use uv_configuration::{EditorConfig, ModulePath};
 use uv_git::GitReference;
 use uv_macros::OptionsMetadata;
-use uv_normalize::{ExtraName, PackageName};
+use uv_normalize::{ExtraName, GroupName, PackageName};
+
+use crate::dependency_groups::DependencyGroups;
 
 /// A `pyproject.toml` as specified in PEP 517.
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -26,6 +31,8 @@ use uv_normalize::{ExtraName, PackageName};
 pub struct PyProjectToml {
     /// PEP 621-compliant project metadata.
     pub project: Option<Project>,
+    /// PEP 735 dependency groups.
+    pub dependency_groups: Option<DependencyGroups>,
     /// Tool-specific metadata.
     pub tool: Option<Tool>,
     /// The raw unserialized document.
@@ -44,7 +51,9 @@ impl PyProjectToml {
 // Ignore raw document in comparison.
 impl PartialEq for PyProjectToml {
     fn eq(&self, other: &Self) -> bool {
-        self.project.eq(&other.project) && self.tool.eq(&other.tool)
+        self.project.eq(&other.project)
+            && self.dependency_groups.eq(&other.dependency_groups)
+            && self.tool.eq(&other.tool)
     }
 }
 
@@ -98,6 +107,19 @@ pub struct ToolUv {
         "#
     )]
     pub managed: Option<bool>,
+    /// Whether to lock the build requirements of the project (i.e., its `build-system.requires`)
+    /// into the `uv.lock` file.
+    ///
+    /// When enabled, `uv build --locked` builds the project in an isolated environment that's
+    /// pinned to the locked versions, such that its artifacts can be reproduced across machines.
+    #[option(
+        default = r#"false"#,
+        value_type = "bool",
+        example = r#"
+            lock-build-requirements = true
+        "#
+    )]
+    pub lock_build_requirements: Option<bool>,
     #[cfg_attr(
         feature = "schemars",
         schemars(
@@ -115,6 +137,245 @@ pub struct ToolUv {
     )]
     pub override_dependencies: Option<Vec<pep508_rs::Requirement<VerbatimParsedUrl>>>,
     pub constraint_dependencies: Option<Vec<pep508_rs::Requirement<VerbatimParsedUrl>>>,
+    /// Command-line tools to install into isolated environments within the project environment,
+    /// with their executables linked into its `bin` directory.
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(
+            with = "Option<Vec<String>>",
+            description = "PEP 508-style requirements, e.g., `ruff==0.5.0`, or `ruff @ https://...`."
+        )
+    )]
+    pub dev_tools: Option<Vec<pep508_rs::Requirement<VerbatimParsedUrl>>>,
+    /// The Python versions to test the project against with `uv test` and
+    /// `uv run --all-pythons`.
+    ///
+    /// Each entry is a Python request (e.g., `3.12` or `pypy@3.10`); any versions that aren't
+    /// installed are downloaded as needed.
+    #[option(
+        default = r#"[]"#,
+        value_type = "list[str]",
+        example = r#"
+            test-pythons = ["3.10", "3.11", "3.12"]
+        "#
+    )]
+    pub test_pythons: Option<Vec<String>>,
+    /// The command to run with `uv test`, as a list of arguments.
+    ///
+    /// The command is run in a separate environment for each of the `test-pythons`, each of which
+    /// is synced from the lockfile.
+    #[option(
+        default = r#"[]"#,
+        value_type = "list[str]",
+        example = r#"
+            test-command = ["pytest", "-x"]
+        "#
+    )]
+    pub test_command: Option<Vec<String>>,
+    /// The dependency groups to install into the test environments used by `uv test`, in addition
+    /// to the development dependencies.
+    #[option(
+        default = r#"[]"#,
+        value_type = "list[str]",
+        example = r#"
+            test-groups = ["test"]
+        "#
+    )]
+    pub test_groups: Option<Vec<GroupName>>,
+    /// Named environments for the project, each of which is synced into its own virtual
+    /// environment in the project's `.venvs` directory (e.g., `.venvs/docs`), and selected with
+    /// `uv run --env` or `uv sync --env`.
+    ///
+    /// Each environment can enable a set of extras, omit the development dependencies, or request
+    /// a specific Python version. All environments are installed from the same lockfile.
+    #[option(
+        default = r#"{}"#,
+        value_type = "dict[str, dict]",
+        example = r#"
+            envs.docs = { extras = ["docs"], dev = false }
+            envs.gpu = { extras = ["cuda"], python = "3.11" }
+        "#
+    )]
+    pub envs: Option<BTreeMap<String, ToolUvEnvironment>>,
+    /// Whether to install the project's dependencies into a local `__pypackages__` directory,
+    /// rather than a virtual environment.
+    ///
+    /// Dependencies are installed into a directory for each Python version (e.g.,
+    /// `__pypackages__/3.12/lib`), with scripts in its `bin` subdirectory. `uv run` exposes the
+    /// directory to the command via the `PYTHONPATH`, akin to PEP 582.
+    #[option(
+        default = r#"false"#,
+        value_type = "bool",
+        example = r#"
+            pypackages = true
+        "#
+    )]
+    pub pypackages: Option<bool>,
+    /// Scripts to install into the project environment, mapping each name to a file relative to the
+    /// project root, akin to the `scripts` argument to setuptools.
+    ///
+    /// Unlike `[project.scripts]`, which must refer to a Python function, each entry refers to an
+    /// arbitrary file (e.g., a Python or shell script). On sync, uv generates a wrapper in the
+    /// environment's `bin` directory that executes the file in place, such that changes to the
+    /// file take effect without re-syncing.
+    #[option(
+        default = r#"{}"#,
+        value_type = "dict[str, str]",
+        example = r#"
+            scripts = { hello = "bin/hello.py", deploy = "scripts/deploy.sh" }
+        "#
+    )]
+    pub scripts: Option<BTreeMap<String, PathBuf>>,
+    /// Extra environment variables to set when building specific packages from source, keyed by
+    /// package name.
+    ///
+    /// The variables are only injected into the isolated build environment of the named package
+    /// (e.g., to pass `CMAKE_ARGS` to `llama-cpp-python`). A digest of the variables is recorded
+    /// in the `uv.lock` file, such that changing them re-resolves the named package, and any
+    /// wheels built with them are cached separately from those built without. The variables are
+    /// respected by the `uv pip` interface, too.
+    #[option(
+        default = r#"{}"#,
+        value_type = "dict[str, dict[str, str]]",
+        example = r#"
+            build-env = { llama-cpp-python = { CMAKE_ARGS = "-DGGML_CUDA=on" } }
+        "#
+    )]
+    pub build_env: Option<BTreeMap<PackageName, BTreeMap<String, String>>>,
+    /// The Python implementations that the project supports, enforced when discovering an
+    /// interpreter for the project environment (e.g., in `uv sync` and `uv run`).
+    ///
+    /// `names` restricts the interpreter to the given implementations (e.g., `cpython`, `pypy`,
+    /// or `graalpy`), while `free-threaded = false` rejects free-threaded builds (and
+    /// `free-threaded = true` requires them). By default, any interpreter is accepted.
+    #[option(
+        default = r#"{}"#,
+        value_type = "dict",
+        example = r#"
+            requires-implementation = { names = ["cpython"], free-threaded = false }
+        "#
+    )]
+    pub requires_implementation: Option<RequiresImplementation>,
+    /// The directories to add to `sys.path` when running commands with `uv run`.
+    ///
+    /// By default, Python adds the directory of the script being run (or the current directory,
+    /// for `python -m` and `python -c`) to `sys.path`, which can make modules importable in
+    /// development that are missing from the built package. When set, uv disables that implicit
+    /// entry (by passing `-P` to the interpreter) and adds only the listed directories: `project-root` (the
+    /// directory containing the `pyproject.toml`), `src` (the project's `src` directory), and
+    /// `members` (the root of each workspace member). An empty list adds no directories, matching
+    /// the behavior of the installed package.
+    ///
+    /// Workspace members can override the setting in their own `pyproject.toml`, and scripts with
+    /// inline metadata in their own `[tool.uv]` table.
+    #[option(
+        default = r#"null"#,
+        value_type = "list[str]",
+        example = r#"
+            module-path = ["src"]
+        "#
+    )]
+    pub module_path: Option<Vec<ModulePath>>,
+    /// The editor configurations to generate for the project environment after `uv sync`.
+    ///
+    /// When set, `uv sync` writes the path to the environment's interpreter and the environment
+    /// itself to `.uv/env.json` in the workspace root and the root of each workspace member, such
+    /// that editors and other tools opened in any member can locate the environment. Additionally,
+    /// `vscode` sets `python.defaultInterpreterPath` in `.vscode/settings.json`, and `pyright`
+    /// sets `venvPath` and `venv` in `pyrightconfig.json`, in each of those directories, retaining
+    /// any other settings.
+    ///
+    /// Only read from the workspace root.
+    #[option(
+        default = r#"null"#,
+        value_type = "list[str]",
+        example = r#"
+            editor-config = ["vscode", "pyright"]
+        "#
+    )]
+    pub editor_config: Option<Vec<EditorConfig>>,
+    /// The number of backups of `uv.lock` to retain.
+    ///
+    /// When set, the previous lockfile is copied to a timestamped backup in `.uv/lock-backups`
+    /// whenever uv updates the lockfile, and all but the given number of most recent backups are
+    /// removed. Use `uv lock --restore` to revert to the most recent backup.
+    ///
+    /// Only read from the workspace root.
+    #[option(
+        default = r#"0"#,
+        value_type = "int",
+        example = r#"
+            lock-backups = 5
+        "#
+    )]
+    pub lock_backups: Option<usize>,
+}
+
+/// The Python implementations supported by a project, as declared in
+/// `tool.uv.requires-implementation`.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RequiresImplementation {
+    /// The names of the supported implementations (e.g., `cpython` or `pypy`), compared
+    /// case-insensitively. If omitted, any implementation is supported.
+    pub names: Option<Vec<String>>,
+    /// Whether the interpreter must (`true`) or must not (`false`) be a free-threaded build. If
+    /// omitted, either is supported.
+    pub free_threaded: Option<bool>,
+}
+
+impl RequiresImplementation {
+    /// Returns `true` if an interpreter with the given implementation name (e.g., `cpython`) and
+    /// build variant satisfies the constraint.
+    pub fn allows(&self, implementation: &str, free_threaded: bool) -> bool {
+        if let Some(names) = &self.names {
+            if !names
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(implementation))
+            {
+                return false;
+            }
+        }
+        if let Some(expected) = self.free_threaded {
+            if expected != free_threaded {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl std::fmt::Display for RequiresImplementation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names = match self.names.as_deref() {
+            None | Some([]) => "any implementation".to_string(),
+            Some(names) => names
+                .iter()
+                .map(|name| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(" or "),
+        };
+        match self.free_threaded {
+            None => write!(f, "{names}"),
+            Some(true) => write!(f, "{names} (free-threaded)"),
+            Some(false) => write!(f, "{names} (not free-threaded)"),
+        }
+    }
+}
+
+/// A named environment, as declared in `tool.uv.envs`.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ToolUvEnvironment {
+    /// The extras to include in the environment.
+    pub extras: Option<Vec<ExtraName>>,
+    /// Whether to include the development dependencies in the environment. Defaults to `true`.
+    pub dev: Option<bool>,
+    /// The Python interpreter to use for the environment (e.g., `3.11`), in lieu of the project's
+    /// default.
+    pub python: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, OptionsMetadata, Default, Debug, Clone, PartialEq, Eq)]
@@ -205,6 +466,9 @@ pub enum Source {
         /// For source distributions, the path to the directory with the `pyproject.toml`, if it's
         /// not in the archive root.
         subdirectory: Option<String>,
+        /// The expected SHA-256 hash of the archive (e.g., `sha256:...`), which is enforced when
+        /// locking and installing.
+        hash: Option<String>,
     },
     /// The path to a dependency, either a wheel (a `.whl` file), source distribution (a `.zip` or
     /// `.tar.gz` file), or source tree (i.e., a directory containing a `pyproject.toml` or
@@ -313,6 +577,7 @@ impl Source {
             } => Source::Url {
                 url: url.to_url(),
                 subdirectory: subdirectory.map(|path| path.to_string_lossy().into_owned()),
+                hash: None,
             },
             RequirementSource::Git {
                 repository,
@@ -354,7 +619,7 @@ impl Source {
 }
 
 /// The type of a dependency in a `pyproject.toml`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DependencyType {
     /// A dependency in `project.dependencies`.
     Production,
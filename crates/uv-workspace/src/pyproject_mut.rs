@@ -1,11 +1,12 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 use std::str::FromStr;
 use std::{fmt, mem};
 
 use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
-use pep508_rs::{ExtraName, PackageName, Requirement, VersionOrUrl};
+use pep508_rs::{ExtraName, MarkerTree, PackageName, Requirement, VersionOrUrl};
 use thiserror::Error;
-use toml_edit::{Array, DocumentMut, Item, RawString, Table, TomlError, Value};
+use toml_edit::{Array, DocumentMut, Item, RawString, Table, TableLike, TomlError, Value};
 use uv_fs::PortablePath;
 
 use crate::pyproject::{DependencyType, PyProjectToml, Source};
@@ -32,6 +33,8 @@ pub enum Error {
     MalformedSources,
     #[error("Workspace in `pyproject.toml` is malformed")]
     MalformedWorkspace,
+    #[error("Project metadata in `pyproject.toml` is malformed")]
+    MalformedProject,
     #[error("Expected a dependency at index {0}")]
     MissingDependency(usize),
     #[error("Cannot perform ambiguous update; found multiple entries with matching package names")]
@@ -85,10 +88,14 @@ impl PyProjectTomlMut {
 
     /// Adds a dependency to `project.dependencies`.
     ///
+    /// If `verbatim` is provided, it's written to the `pyproject.toml` exactly as given, rather than
+    /// the normalized form of `req`.
+    ///
     /// Returns `true` if the dependency was added, `false` if it was updated.
     pub fn add_dependency(
         &mut self,
         req: &Requirement,
+        verbatim: Option<&str>,
         source: Option<&Source>,
     ) -> Result<ArrayEdit, Error> {
         // Get or create `project.dependencies`.
@@ -104,7 +111,7 @@ impl PyProjectTomlMut {
             .ok_or(Error::MalformedDependencies)?;
 
         let name = req.name.clone();
-        let edit = add_dependency(req, dependencies, source.is_some())?;
+        let edit = add_dependency(req, verbatim, dependencies, source.is_some())?;
 
         if let Some(source) = source {
             self.add_source(&name, source)?;
@@ -115,10 +122,14 @@ impl PyProjectTomlMut {
 
     /// Adds a development dependency to `tool.uv.dev-dependencies`.
     ///
+    /// If `verbatim` is provided, it's written to the `pyproject.toml` exactly as given, rather than
+    /// the normalized form of `req`.
+    ///
     /// Returns `true` if the dependency was added, `false` if it was updated.
     pub fn add_dev_dependency(
         &mut self,
         req: &Requirement,
+        verbatim: Option<&str>,
         source: Option<&Source>,
     ) -> Result<ArrayEdit, Error> {
         // Get or create `tool.uv.dev-dependencies`.
@@ -138,7 +149,7 @@ impl PyProjectTomlMut {
             .ok_or(Error::MalformedDependencies)?;
 
         let name = req.name.clone();
-        let edit = add_dependency(req, dev_dependencies, source.is_some())?;
+        let edit = add_dependency(req, verbatim, dev_dependencies, source.is_some())?;
 
         if let Some(source) = source {
             self.add_source(&name, source)?;
@@ -149,11 +160,15 @@ impl PyProjectTomlMut {
 
     /// Adds a dependency to `project.optional-dependencies`.
     ///
+    /// If `verbatim` is provided, it's written to the `pyproject.toml` exactly as given, rather than
+    /// the normalized form of `req`.
+    ///
     /// Returns `true` if the dependency was added, `false` if it was updated.
     pub fn add_optional_dependency(
         &mut self,
         group: &ExtraName,
         req: &Requirement,
+        verbatim: Option<&str>,
         source: Option<&Source>,
     ) -> Result<ArrayEdit, Error> {
         // Get or create `project.optional-dependencies`.
@@ -175,7 +190,7 @@ impl PyProjectTomlMut {
             .ok_or(Error::MalformedDependencies)?;
 
         let name = req.name.clone();
-        let added = add_dependency(req, group, source.is_some())?;
+        let added = add_dependency(req, verbatim, group, source.is_some())?;
 
         if let Some(source) = source {
             self.add_source(&name, source)?;
@@ -184,6 +199,126 @@ impl PyProjectTomlMut {
         Ok(added)
     }
 
+    /// Adds a constraint to `tool.uv.constraint-dependencies`.
+    ///
+    /// Returns the position of the constraint in the array, and whether it was added or updated.
+    pub fn add_constraint(&mut self, req: &Requirement) -> Result<ArrayEdit, Error> {
+        // Get or create `tool.uv.constraint-dependencies`.
+        let constraints = self
+            .doc
+            .entry("tool")
+            .or_insert(implicit())
+            .as_table_mut()
+            .ok_or(Error::MalformedSources)?
+            .entry("uv")
+            .or_insert(Item::Table(Table::new()))
+            .as_table_mut()
+            .ok_or(Error::MalformedSources)?
+            .entry("constraint-dependencies")
+            .or_insert(Item::Value(Value::Array(Array::new())))
+            .as_array_mut()
+            .ok_or(Error::MalformedDependencies)?;
+
+        add_dependency(req, None, constraints, false)
+    }
+
+    /// Set `project.requires-python` to the given specifiers.
+    pub fn set_requires_python(&mut self, specifiers: &VersionSpecifiers) -> Result<(), Error> {
+        self.doc
+            .entry("project")
+            .or_insert(Item::Table(Table::new()))
+            .as_table_mut()
+            .ok_or(Error::MalformedProject)?
+            .insert("requires-python", toml_edit::value(specifiers.to_string()));
+        Ok(())
+    }
+
+    /// Returns the names of the dependencies declared in the given dependency group, sorted and
+    /// deduplicated.
+    pub fn dependency_names(&self, dependency_type: &DependencyType) -> Vec<PackageName> {
+        let dependencies = match dependency_type {
+            DependencyType::Production => self
+                .doc
+                .get("project")
+                .and_then(Item::as_table)
+                .and_then(|project| project.get("dependencies")),
+            DependencyType::Dev => self
+                .doc
+                .get("tool")
+                .and_then(Item::as_table)
+                .and_then(|tool| tool.get("uv"))
+                .and_then(Item::as_table)
+                .and_then(|tool_uv| tool_uv.get("dev-dependencies")),
+            DependencyType::Optional(group) => self
+                .doc
+                .get("project")
+                .and_then(Item::as_table)
+                .and_then(|project| project.get("optional-dependencies"))
+                .and_then(Item::as_table)
+                .and_then(|extras| extras.get(group.as_ref())),
+        };
+
+        let mut names = dependencies
+            .and_then(Item::as_array)
+            .into_iter()
+            .flat_map(Array::iter)
+            .filter_map(|dep| dep.as_str().and_then(try_parse_requirement))
+            .map(|req| req.name)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Add a marker to an existing dependency in the given dependency group, such that the
+    /// dependency is only required when the marker is satisfied.
+    pub fn add_dependency_marker(
+        &mut self,
+        dependency_type: &DependencyType,
+        index: usize,
+        marker: MarkerTree,
+    ) -> Result<(), Error> {
+        let dependencies = match dependency_type {
+            DependencyType::Production => self
+                .doc
+                .get_mut("project")
+                .and_then(Item::as_table_mut)
+                .and_then(|project| project.get_mut("dependencies")),
+            DependencyType::Dev => self
+                .doc
+                .get_mut("tool")
+                .and_then(Item::as_table_mut)
+                .and_then(|tool| tool.get_mut("uv"))
+                .and_then(Item::as_table_mut)
+                .and_then(|tool_uv| tool_uv.get_mut("dev-dependencies")),
+            DependencyType::Optional(group) => self
+                .doc
+                .get_mut("project")
+                .and_then(Item::as_table_mut)
+                .and_then(|project| project.get_mut("optional-dependencies"))
+                .and_then(Item::as_table_mut)
+                .and_then(|extras| extras.get_mut(group.as_ref())),
+        }
+        .and_then(Item::as_array_mut)
+        .ok_or(Error::MalformedDependencies)?;
+
+        let Some(req) = dependencies.get(index) else {
+            return Err(Error::MissingDependency(index));
+        };
+
+        let mut req = req
+            .as_str()
+            .and_then(try_parse_requirement)
+            .ok_or(Error::MalformedDependencies)?;
+        match req.marker.as_mut() {
+            Some(existing) => existing.and(marker),
+            None => req.marker = Some(marker),
+        }
+        dependencies.replace(index, req.to_string());
+
+        Ok(())
+    }
+
     /// Set the minimum version for an existing dependency in `project.dependencies`.
     pub fn set_dependency_minimum_version(
         &mut self,
@@ -312,7 +447,7 @@ impl PyProjectTomlMut {
             .ok_or(Error::MalformedSources)?
             .entry("sources")
             .or_insert(Item::Table(Table::new()))
-            .as_table_mut()
+            .as_table_like_mut()
             .ok_or(Error::MalformedSources)?;
 
         add_source(name, source, sources)?;
@@ -403,7 +538,7 @@ impl PyProjectTomlMut {
             .map(|tool_uv| tool_uv.as_table_mut().ok_or(Error::MalformedSources))
             .transpose()?
             .and_then(|tool_uv| tool_uv.get_mut("sources"))
-            .map(|sources| sources.as_table_mut().ok_or(Error::MalformedSources))
+            .map(|sources| sources.as_table_like_mut().ok_or(Error::MalformedSources))
             .transpose()?
         {
             sources.remove(name.as_ref());
@@ -465,6 +600,73 @@ impl PyProjectTomlMut {
 
         types
     }
+
+    /// Pin any dependency on one of the given packages to an exact version, across
+    /// `project.dependencies` and `project.optional-dependencies`.
+    ///
+    /// Extras and markers are retained, while any existing specifiers are replaced. Dependencies
+    /// with a direct URL are left untouched. Returns the names of the packages that were pinned.
+    pub fn pin_dependencies(
+        &mut self,
+        versions: &BTreeMap<PackageName, Version>,
+    ) -> Result<BTreeSet<PackageName>, Error> {
+        let mut pinned = BTreeSet::new();
+
+        let Some(project) = self.doc.get_mut("project").and_then(Item::as_table_mut) else {
+            return Ok(pinned);
+        };
+
+        // Pin `project.dependencies`.
+        if let Some(dependencies) = project.get_mut("dependencies") {
+            let dependencies = dependencies
+                .as_array_mut()
+                .ok_or(Error::MalformedDependencies)?;
+            pin_dependencies(dependencies, versions, &mut pinned)?;
+        }
+
+        // Pin `project.optional-dependencies`.
+        if let Some(extras) = project.get_mut("optional-dependencies") {
+            let extras = extras
+                .as_table_like_mut()
+                .ok_or(Error::MalformedDependencies)?;
+            for (_, dependencies) in extras.iter_mut() {
+                let dependencies = dependencies
+                    .as_array_mut()
+                    .ok_or(Error::MalformedDependencies)?;
+                pin_dependencies(dependencies, versions, &mut pinned)?;
+            }
+        }
+
+        Ok(pinned)
+    }
+}
+
+/// Pin any dependency in the array on one of the given packages to an exact version, recording
+/// the names of the pinned packages.
+fn pin_dependencies(
+    deps: &mut Array,
+    versions: &BTreeMap<PackageName, Version>,
+    pinned: &mut BTreeSet<PackageName>,
+) -> Result<(), Error> {
+    for index in 0..deps.len() {
+        let mut req = deps
+            .get(index)
+            .and_then(Value::as_str)
+            .and_then(try_parse_requirement)
+            .ok_or(Error::MalformedDependencies)?;
+        if matches!(req.version_or_url, Some(VersionOrUrl::Url(_))) {
+            continue;
+        }
+        let Some(version) = versions.get(&req.name) else {
+            continue;
+        };
+        req.version_or_url = Some(VersionOrUrl::VersionSpecifier(VersionSpecifiers::from(
+            VersionSpecifier::equals_version(version.clone()),
+        )));
+        deps.replace(index, req.to_string());
+        pinned.insert(req.name);
+    }
+    Ok(())
 }
 
 /// Returns an implicit table.
@@ -476,24 +678,52 @@ fn implicit() -> Item {
 
 /// Adds a dependency to the given `deps` array.
 ///
+/// If the existing dependencies are sorted by name, the dependency is inserted such that they
+/// remain sorted; otherwise, it's appended to the end of the array.
+///
 /// Returns `true` if the dependency was added, `false` if it was updated.
 pub fn add_dependency(
     req: &Requirement,
+    verbatim: Option<&str>,
     deps: &mut Array,
     has_source: bool,
 ) -> Result<ArrayEdit, Error> {
     // Find matching dependencies.
     let mut to_replace = find_dependencies(&req.name, deps);
+
+    // A requirement with a marker replaces an existing requirement with the same marker. If every
+    // existing requirement is conditional on some other marker, it's added alongside them, such
+    // that (e.g.) platform-specific requirements can be declared separately.
+    if let Some(marker) = req.marker.as_ref() {
+        if to_replace
+            .iter()
+            .any(|(_, existing)| existing.marker.as_ref() == Some(marker))
+        {
+            to_replace.retain(|(_, existing)| existing.marker.as_ref() == Some(marker));
+        } else if to_replace
+            .iter()
+            .all(|(_, existing)| existing.marker.is_some())
+        {
+            to_replace.clear();
+        }
+    }
     match to_replace.as_slice() {
         [] => {
-            deps.push(req.to_string());
+            let index = insertion_index(&req.name, deps);
+            let value = verbatim.map_or_else(|| req.to_string(), ToString::to_string);
+            insert_dependency(deps, index, value);
             reformat_array_multiline(deps);
-            Ok(ArrayEdit::Add(deps.len() - 1))
+            Ok(ArrayEdit::Add(index))
         }
         [_] => {
             let (i, mut old_req) = to_replace.remove(0);
-            update_requirement(&mut old_req, req, has_source);
-            deps.replace(i, old_req.to_string());
+            let value = if let Some(verbatim) = verbatim {
+                verbatim.to_string()
+            } else {
+                update_requirement(&mut old_req, req, has_source);
+                old_req.to_string()
+            };
+            deps.replace(i, value);
             reformat_array_multiline(deps);
             Ok(ArrayEdit::Update(i))
         }
@@ -534,6 +764,23 @@ fn remove_dependency(req: &PackageName, deps: &mut Array) -> Vec<Requirement> {
         .into_iter()
         .rev() // Reverse to preserve indices as we remove them.
         .filter_map(|(i, _)| {
+            // Drop any comment on the same line as the removed dependency, which is attached to
+            // the following entry (or the end of the array).
+            if let Some(next) = deps.get_mut(i + 1) {
+                let prefix = next
+                    .decor()
+                    .prefix()
+                    .and_then(RawString::as_str)
+                    .unwrap_or("");
+                let (_, rest) = split_same_line(prefix);
+                let rest = rest.to_string();
+                next.decor_mut().set_prefix(rest);
+            } else {
+                let (_, rest) = split_same_line(deps.trailing().as_str().unwrap_or(""));
+                let rest = rest.to_string();
+                deps.set_trailing(rest);
+            }
+
             deps.remove(i)
                 .as_str()
                 .and_then(|req| Requirement::from_str(req).ok())
@@ -562,15 +809,37 @@ fn find_dependencies(name: &PackageName, deps: &Array) -> Vec<(usize, Requiremen
 }
 
 // Add a source to `tool.uv.sources`.
-fn add_source(req: &PackageName, source: &Source, sources: &mut Table) -> Result<(), Error> {
+fn add_source(
+    req: &PackageName,
+    source: &Source,
+    sources: &mut dyn TableLike,
+) -> Result<(), Error> {
     // Serialize as an inline table.
     let mut doc = toml::to_string(&source)
         .map_err(Box::new)?
         .parse::<DocumentMut>()
         .unwrap();
-    let table = mem::take(doc.as_table_mut()).into_inline_table();
+    let mut table = mem::take(doc.as_table_mut()).into_inline_table();
+
+    // Preserve the style of any existing entry, e.g., a `[tool.uv.sources.foo]` table remains a
+    // standard table, rather than being replaced with an inline table.
+    let item = match sources.get(req.as_ref()) {
+        Some(Item::Table(existing)) => {
+            let mut replacement = table.into_table();
+            replacement.decor_mut().clone_from(existing.decor());
+            if let Some(position) = existing.position() {
+                replacement.set_position(position);
+            }
+            Item::Table(replacement)
+        }
+        Some(Item::Value(existing)) => {
+            table.decor_mut().clone_from(existing.decor());
+            Item::Value(Value::InlineTable(table))
+        }
+        _ => Item::Value(Value::InlineTable(table)),
+    };
 
-    sources.insert(req.as_ref(), Item::Value(Value::InlineTable(table)));
+    sources.insert(req.as_ref(), item);
 
     Ok(())
 }
@@ -585,8 +854,106 @@ fn try_parse_requirement(req: &str) -> Option<Requirement> {
     Requirement::from_str(req).ok()
 }
 
-/// Reformats a TOML array to multi line while trying to preserve all comments
-/// and move them around. This also formats the array to have a trailing comma.
+/// Returns the index at which a dependency with the given name should be inserted.
+///
+/// If the existing dependencies are sorted by name, returns the index that keeps them sorted;
+/// otherwise, returns the end of the array.
+fn insertion_index(name: &PackageName, deps: &Array) -> usize {
+    let names = deps
+        .iter()
+        .map(|dep| {
+            dep.as_str()
+                .and_then(try_parse_requirement)
+                .map(|req| req.name)
+        })
+        .collect::<Option<Vec<_>>>();
+
+    // With fewer than two dependencies, there's no ordering to infer.
+    match names {
+        Some(names) if names.len() >= 2 && names.windows(2).all(|pair| pair[0] <= pair[1]) => {
+            names.partition_point(|existing| existing <= name)
+        }
+        _ => deps.len(),
+    }
+}
+
+/// Insert a dependency into a TOML array at the given index.
+///
+/// In a multi-line array, any comment on the same line as the preceding entry remains attached to
+/// that entry, rather than moving to the inserted dependency.
+fn insert_dependency(deps: &mut Array, index: usize, value: String) {
+    if !is_multiline(deps) {
+        deps.insert(index, value);
+        return;
+    }
+
+    // The comment on the same line as the preceding entry (if any) precedes the next entry, or
+    // the end of the array.
+    let leading = if let Some(next) = deps.get_mut(index) {
+        let prefix = next
+            .decor()
+            .prefix()
+            .and_then(RawString::as_str)
+            .unwrap_or("");
+        let (leading, rest) = split_same_line(prefix);
+        let (leading, rest) = (leading.to_string(), rest.to_string());
+        next.decor_mut().set_prefix(rest);
+        leading
+    } else {
+        let (leading, rest) = split_same_line(deps.trailing().as_str().unwrap_or(""));
+        let (leading, rest) = (leading.to_string(), rest.to_string());
+        deps.set_trailing(rest);
+        leading
+    };
+
+    let mut value = Value::from(value);
+    value
+        .decor_mut()
+        .set_prefix(format!("{leading}\n{}", indentation(deps)));
+    value.decor_mut().set_suffix("");
+    deps.insert_formatted(index, value);
+}
+
+/// Split the whitespace and comment on the same line as an array entry from the remainder of the
+/// decoration that follows it.
+fn split_same_line(decor: &str) -> (&str, &str) {
+    match decor.find('\n') {
+        Some(index) => decor.split_at(index),
+        None => ("", decor),
+    }
+}
+
+/// Returns `true` if the array spans multiple lines.
+fn is_multiline(deps: &Array) -> bool {
+    deps.iter().any(|item| {
+        item.decor()
+            .prefix()
+            .and_then(RawString::as_str)
+            .is_some_and(|prefix| prefix.contains('\n'))
+    }) || deps
+        .trailing()
+        .as_str()
+        .is_some_and(|trailing| trailing.contains('\n'))
+}
+
+/// Returns the indentation of the entries in a multi-line array, as observed from its first entry.
+fn indentation(deps: &Array) -> String {
+    deps.iter()
+        .next()
+        .and_then(|item| item.decor().prefix().and_then(RawString::as_str))
+        .and_then(|prefix| prefix.rsplit_once('\n'))
+        .map(|(_, indentation)| indentation)
+        .filter(|indentation| !indentation.is_empty())
+        .unwrap_or("    ")
+        .to_string()
+}
+
+/// Reformats a TOML array to multi line while trying to preserve all comments.
+///
+/// Entries that already start on their own line are left untouched, such that any indentation,
+/// blank lines, and comments (including comments on the same line as an entry) are preserved.
+/// Entries on the same line as the preceding entry are moved onto their own line, using the
+/// indentation of the first entry. Finally, the array is given a trailing comma.
 fn reformat_array_multiline(deps: &mut Array) {
     fn find_comments(s: Option<&RawString>) -> impl Iterator<Item = &str> {
         s.and_then(|x| x.as_str())
@@ -598,53 +965,52 @@ fn reformat_array_multiline(deps: &mut Array) {
             })
     }
 
-    let mut indentation_prefix = None;
+    // An empty array is collapsed, retaining any comments.
+    if deps.is_empty() {
+        deps.set_trailing(&{
+            let mut rv = String::new();
+            for comment in find_comments(Some(deps.trailing())) {
+                rv.push_str("\n    ");
+                rv.push_str(comment);
+            }
+            if !rv.is_empty() {
+                rv.push('\n');
+            }
+            rv
+        });
+        deps.set_trailing_comma(false);
+        return;
+    }
+
+    let indentation = indentation(deps);
+    let mut trailing = None;
 
-    for item in deps.iter_mut() {
+    let len = deps.len();
+    for (index, item) in deps.iter_mut().enumerate() {
         let decor = item.decor_mut();
-        let mut prefix = String::new();
-        // calculating the indentation prefix as the indentation of the first dependency entry
-        if indentation_prefix.is_none() {
-            let decor_prefix = decor
-                .prefix()
-                .and_then(|s| s.as_str())
-                .map(|s| s.split('#').next().unwrap_or("").to_string())
-                .unwrap_or(String::new())
-                .trim_start_matches('\n')
-                .to_string();
-
-            // if there is no indentation then apply a default one
-            indentation_prefix = Some(if decor_prefix.is_empty() {
-                "    ".to_string()
-            } else {
-                decor_prefix
-            });
-        }
 
-        let indentation_prefix_str = format!("\n{}", indentation_prefix.as_ref().unwrap());
+        let prefix = decor.prefix().and_then(RawString::as_str).unwrap_or("");
+        if !prefix.contains('\n') {
+            decor.set_prefix(format!("\n{indentation}"));
+        }
 
-        for comment in find_comments(decor.prefix()).chain(find_comments(decor.suffix())) {
-            prefix.push_str(&indentation_prefix_str);
-            prefix.push_str(comment);
+        // Without a trailing comma, the whitespace (and any comment) following the last entry
+        // is part of its suffix; move it to the end of the array, after the comma.
+        let suffix = decor.suffix().and_then(RawString::as_str).unwrap_or("");
+        if index == len - 1 && suffix.contains('\n') {
+            trailing = Some(suffix.to_string());
         }
-        prefix.push_str(&indentation_prefix_str);
-        decor.set_prefix(prefix);
         decor.set_suffix("");
     }
 
-    deps.set_trailing(&{
-        let mut comments = find_comments(Some(deps.trailing())).peekable();
-        let mut rv = String::new();
-        if comments.peek().is_some() {
-            for comment in comments {
-                rv.push_str("\n    ");
-                rv.push_str(comment);
-            }
-        }
-        if !rv.is_empty() || !deps.is_empty() {
-            rv.push('\n');
-        }
-        rv
-    });
+    let trailing = match trailing {
+        Some(suffix) => format!("{suffix}{}", deps.trailing().as_str().unwrap_or("")),
+        None => deps.trailing().as_str().unwrap_or("").to_string(),
+    };
+    if trailing.contains('\n') {
+        deps.set_trailing(trailing);
+    } else {
+        deps.set_trailing("\n");
+    }
     deps.set_trailing_comma(true);
 }
@@ -1,6 +1,6 @@
 //! Resolve the current [`ProjectWorkspace`] or [`Workspace`].
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 
 use either::Either;
@@ -10,11 +10,14 @@ use tracing::{debug, trace, warn};
 
 use pep508_rs::{RequirementOrigin, VerbatimUrl};
 use pypi_types::{Requirement, RequirementSource};
+use uv_configuration::{EditorConfig, ModulePath};
 use uv_fs::{absolutize_path, normalize_path, relative_to, Simplified};
 use uv_normalize::{GroupName, PackageName, DEV_DEPENDENCIES};
 use uv_warnings::warn_user;
 
-use crate::pyproject::{Project, PyProjectToml, Source, ToolUvWorkspace};
+use crate::pyproject::{
+    Project, PyProjectToml, RequiresImplementation, Source, ToolUvEnvironment, ToolUvWorkspace,
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum WorkspaceError {
@@ -42,6 +45,10 @@ pub enum WorkspaceError {
     Toml(PathBuf, #[source] Box<toml::de::Error>),
     #[error("Failed to normalize workspace member path")]
     Normalize(#[source] std::io::Error),
+    #[error("Failed to read dependencies from: `{}`", _0.user_display())]
+    Dependencies(PathBuf, #[source] Box<pypi_types::MetadataError>),
+    #[error("Detected a dependency cycle between workspace members: {}", _0.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", "))]
+    DependencyCycle(Vec<PackageName>),
 }
 
 #[derive(Debug, Default, Clone)]
@@ -335,6 +342,18 @@ impl Workspace {
         }
     }
 
+    /// Returns the names of the dependency groups declared by any of the workspace members,
+    /// including the `dev` group (i.e., `tool.uv.dev-dependencies`).
+    pub fn groups(&self) -> BTreeSet<GroupName> {
+        let mut groups = BTreeSet::from([DEV_DEPENDENCIES.clone()]);
+        for member in self.packages.values() {
+            if let Some(dependency_groups) = member.pyproject_toml().dependency_groups.as_ref() {
+                groups.extend(dependency_groups.names().cloned());
+            }
+        }
+        groups
+    }
+
     /// Returns the set of overrides for the workspace.
     pub fn overrides(&self) -> Vec<Requirement> {
         let Some(workspace_package) = self
@@ -367,6 +386,154 @@ impl Workspace {
             .collect()
     }
 
+    /// Returns the set of development tools for the workspace.
+    pub fn dev_tools(&self) -> Vec<Requirement> {
+        let Some(workspace_package) = self
+            .packages
+            .values()
+            .find(|workspace_package| workspace_package.root() == self.install_path())
+        else {
+            return vec![];
+        };
+
+        let Some(dev_tools) = workspace_package
+            .pyproject_toml()
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.dev_tools.as_ref())
+        else {
+            return vec![];
+        };
+
+        dev_tools
+            .iter()
+            .map(|requirement| {
+                Requirement::from(
+                    requirement
+                        .clone()
+                        .with_origin(RequirementOrigin::Workspace),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the Python versions to test the workspace against, as declared in
+    /// `tool.uv.test-pythons`.
+    pub fn test_pythons(&self) -> &[String] {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.test_pythons.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Returns the command to run with `uv test`, as declared in `tool.uv.test-command`.
+    pub fn test_command(&self) -> Option<&[String]> {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.test_command.as_deref())
+    }
+
+    /// Returns the dependency groups to install into the test environments, as declared in
+    /// `tool.uv.test-groups`.
+    pub fn test_groups(&self) -> &[GroupName] {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.test_groups.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Returns the extra environment variables to set when building specific packages, as declared
+    /// in `tool.uv.build-env`.
+    pub fn build_env(&self) -> BTreeMap<PackageName, BTreeMap<String, String>> {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.build_env.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the named environments declared in `tool.uv.envs`.
+    pub fn environments(&self) -> Option<&BTreeMap<String, ToolUvEnvironment>> {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.envs.as_ref())
+    }
+
+    /// Returns the Python implementations supported by the workspace, as declared in
+    /// `tool.uv.requires-implementation`.
+    pub fn requires_implementation(&self) -> Option<&RequiresImplementation> {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.requires_implementation.as_ref())
+    }
+
+    /// Returns the directories to add to `sys.path` in `uv run`, as declared in
+    /// `tool.uv.module-path`.
+    pub fn module_path(&self) -> Option<&[ModulePath]> {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.module_path.as_deref())
+    }
+
+    /// Returns the editor configurations to generate after `uv sync`, as declared in
+    /// `tool.uv.editor-config`.
+    pub fn editor_config(&self) -> Option<&[EditorConfig]> {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.editor_config.as_deref())
+    }
+
+    /// Returns the number of lockfile backups to retain, as declared in `tool.uv.lock-backups`.
+    pub fn lock_backups(&self) -> usize {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.lock_backups)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the project's dependencies should be installed into a local
+    /// `__pypackages__` directory, as declared in `tool.uv.pypackages`.
+    pub fn pypackages(&self) -> bool {
+        self.pyproject_toml
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.pypackages)
+            .unwrap_or(false)
+    }
+
+    /// The path to the `__pypackages__` directory for the workspace.
+    pub fn pypackages_dir(&self) -> PathBuf {
+        self.install_path.join("__pypackages__")
+    }
+
+    /// The path to the virtual environment for the named environment.
+    ///
+    /// Named environments are stored in a dedicated `.venvs` directory, such that they can't
+    /// collide with the environments created alongside `.venv` for secondary interpreters (e.g.,
+    /// `.venv-cpython-3.9`).
+    pub fn named_venv(&self, name: &str) -> PathBuf {
+        self.install_path.join(".venvs").join(name)
+    }
+
     /// Returns the set of constraints for the workspace.
     pub fn constraints(&self) -> Vec<Requirement> {
         let Some(workspace_package) = self
@@ -1312,6 +1479,20 @@ impl VirtualProject {
         }
     }
 
+    /// Restrict the project to the given workspace members, treating the workspace as virtual.
+    ///
+    /// Used to sync a subset of the workspace, e.g., the members affected by a change. Names that
+    /// aren't workspace members are ignored.
+    #[must_use]
+    pub fn with_members(self, members: &BTreeSet<PackageName>) -> Self {
+        let mut workspace = match self {
+            VirtualProject::Project(project) => project.workspace,
+            VirtualProject::Virtual(workspace) => workspace,
+        };
+        workspace.packages.retain(|name, _| members.contains(name));
+        VirtualProject::Virtual(workspace)
+    }
+
     /// Return the root of the project.
     pub fn root(&self) -> &Path {
         match self {
@@ -1359,8 +1540,10 @@ impl VirtualProject {
             }
             VirtualProject::Virtual(workspace) => {
                 // For virtual projects, we might have dev dependencies that are attached to the
-                // workspace root (which isn't a member).
-                if name == &*DEV_DEPENDENCIES {
+                // workspace root (which isn't a member). If the root is itself a project (e.g.,
+                // when restricted to a subset of the members), its dev dependencies are attached
+                // to the member instead.
+                if name == &*DEV_DEPENDENCIES && workspace.pyproject_toml.project.is_none() {
                     Either::Right(
                         workspace
                             .pyproject_toml
@@ -1455,6 +1638,7 @@ mod tests {
                 "requires-python": ">=3.12",
                 "optional-dependencies": null
               },
+              "dependency-groups": null,
               "tool": null
             }
           }
@@ -1499,6 +1683,7 @@ mod tests {
                     "requires-python": ">=3.12",
                     "optional-dependencies": null
                   },
+                  "dependency-groups": null,
                   "tool": null
                 }
               }
@@ -1564,6 +1749,7 @@ mod tests {
                     "requires-python": ">=3.12",
                     "optional-dependencies": null
                   },
+                  "dependency-groups": null,
                   "tool": {
                     "uv": {
                       "sources": {
@@ -1578,9 +1764,22 @@ mod tests {
                         "exclude": null
                       },
                       "managed": null,
+                      "lock-build-requirements": null,
                       "dev-dependencies": null,
                       "override-dependencies": null,
-                      "constraint-dependencies": null
+                      "constraint-dependencies": null,
+                      "dev-tools": null,
+                      "test-pythons": null,
+                      "test-command": null,
+                      "test-groups": null,
+                      "envs": null,
+                      "pypackages": null,
+                      "scripts": null,
+                      "build-env": null,
+                      "requires-implementation": null,
+                      "module-path": null,
+                      "editor-config": null,
+                      "lock-backups": null
                     }
                   }
                 }
@@ -1640,6 +1839,7 @@ mod tests {
                 "sources": {},
                 "pyproject_toml": {
                   "project": null,
+                  "dependency-groups": null,
                   "tool": {
                     "uv": {
                       "sources": null,
@@ -1650,9 +1850,22 @@ mod tests {
                         "exclude": null
                       },
                       "managed": null,
+                      "lock-build-requirements": null,
                       "dev-dependencies": null,
                       "override-dependencies": null,
-                      "constraint-dependencies": null
+                      "constraint-dependencies": null,
+                      "dev-tools": null,
+                      "test-pythons": null,
+                      "test-command": null,
+                      "test-groups": null,
+                      "envs": null,
+                      "pypackages": null,
+                      "scripts": null,
+                      "build-env": null,
+                      "requires-implementation": null,
+                      "module-path": null,
+                      "editor-config": null,
+                      "lock-backups": null
                     }
                   }
                 }
@@ -1697,6 +1910,7 @@ mod tests {
                     "requires-python": ">=3.12",
                     "optional-dependencies": null
                   },
+                  "dependency-groups": null,
                   "tool": null
                 }
               }
@@ -0,0 +1,663 @@
+//! A high-level interface to uv's resolver, project, and tool operations.
+//!
+//! This module backs the `uv-lib` crate, which is the supported entrypoint for embedding uv in
+//! other Rust programs, and which wraps these operations in its own option and error types. It's
+//! not intended for use outside of `uv-lib`. Each operation mirrors the corresponding command
+//! (e.g., `uv lock`), but produces no terminal output, and reports its result as a structured
+//! value.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+
+use distribution_types::{
+    CachedDist, InstalledDist, InstalledMetadata, InstalledVersion, LocalDist, Name, Resolution,
+};
+use pep440_rs::Version;
+use uv_cache::Cache;
+use uv_cli::ToolFormat;
+use uv_client::{BaseClientBuilder, Connectivity};
+use uv_configuration::{Concurrency, ExtrasSpecification, PreviewMode};
+use uv_normalize::PackageName;
+use uv_python::{
+    EnvironmentPreference, PythonDownloads, PythonInstallation, PythonPreference, PythonRequest,
+};
+use uv_requirements::{RequirementsSource, RequirementsSpecification};
+use uv_resolver::{NoSolutionError, ResolveError};
+use uv_settings::{Combine, FilesystemOptions, ResolverInstallerOptions};
+use uv_tool::{EntrypointSelection, InstalledTools, ToolName};
+use uv_workspace::{DiscoveryOptions, VirtualProject, Workspace};
+
+use crate::commands::pip::loggers::{InstallLogger, SummaryResolveLogger};
+use crate::commands::pip::operations::Modifications;
+use crate::commands::project::dev_tools::sync_dev_tools;
+use crate::commands::project::lock::do_safe_lock;
+use crate::commands::project::sync::do_sync;
+use crate::commands::project::{FoundInterpreter, ProjectError};
+use crate::commands::{pip, project, tool, ExitStatus, SharedState};
+use crate::printer::Printer;
+use crate::settings::ResolverInstallerSettings;
+
+/// An error encountered while performing an operation.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The requirements could not be satisfied.
+    #[error("{}", .0.header())]
+    NoSolution(#[source] Box<NoSolutionError>),
+    /// The tool does not provide any executables.
+    #[error("No executables are provided by `{0}`")]
+    NoExecutables(PackageName),
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<ProjectError>() {
+            Ok(ProjectError::Operation(pip::operations::Error::Resolve(
+                ResolveError::NoSolution(err),
+            ))) => Self::NoSolution(Box::new(err)),
+            Ok(err) => Self::Other(err.into()),
+            Err(err) => Self::Other(err),
+        }
+    }
+}
+
+/// The options shared by each operation.
+#[derive(Debug, Clone)]
+pub struct Options {
+    cache: Cache,
+    python: Option<String>,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    connectivity: Connectivity,
+    native_tls: bool,
+    concurrency: Concurrency,
+    resolver: ResolverInstallerOptions,
+    no_config: bool,
+}
+
+impl Options {
+    /// Create the [`Options`] for operations that use the given cache.
+    ///
+    /// The cache must be initialized (e.g., with [`Cache::init`]).
+    pub fn new(cache: Cache) -> Self {
+        Self {
+            cache,
+            python: None,
+            python_preference: PythonPreference::default(),
+            python_downloads: PythonDownloads::default(),
+            connectivity: Connectivity::default(),
+            native_tls: false,
+            concurrency: Concurrency::default(),
+            resolver: ResolverInstallerOptions::default(),
+            no_config: false,
+        }
+    }
+
+    /// Request a Python interpreter, e.g., `3.12` or the path to a Python executable.
+    #[must_use]
+    pub fn with_python(mut self, python: impl Into<String>) -> Self {
+        self.python = Some(python.into());
+        self
+    }
+
+    /// Set the preference for managed or system Python installations.
+    #[must_use]
+    pub fn with_python_preference(mut self, python_preference: PythonPreference) -> Self {
+        self.python_preference = python_preference;
+        self
+    }
+
+    /// Set whether Python installations may be downloaded, if no suitable interpreter is found.
+    #[must_use]
+    pub fn with_python_downloads(mut self, python_downloads: PythonDownloads) -> Self {
+        self.python_downloads = python_downloads;
+        self
+    }
+
+    /// Disable network access, relying only on locally cached data and locally available files.
+    #[must_use]
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.connectivity = if offline {
+            Connectivity::Offline
+        } else {
+            Connectivity::Online
+        };
+        self
+    }
+
+    /// Load TLS certificates from the platform's native certificate store.
+    #[must_use]
+    pub fn with_native_tls(mut self, native_tls: bool) -> Self {
+        self.native_tls = native_tls;
+        self
+    }
+
+    /// Set the maximum number of concurrent downloads, builds, and installs.
+    #[must_use]
+    pub fn with_concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set the resolver and installer options (e.g., the index URL), which take precedence over
+    /// those in any `uv.toml` or `pyproject.toml`.
+    #[must_use]
+    pub fn with_resolver_options(mut self, resolver: ResolverInstallerOptions) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Avoid discovering `uv.toml` and `pyproject.toml` configuration files.
+    #[must_use]
+    pub fn with_no_config(mut self, no_config: bool) -> Self {
+        self.no_config = no_config;
+        self
+    }
+
+    /// Combine the requested resolver options with those in the given configuration, as in
+    /// `uv.toml` or the `[tool.uv]` table of a `pyproject.toml`.
+    fn resolver_options(&self, filesystem: Option<FilesystemOptions>) -> ResolverInstallerOptions {
+        self.resolver.clone().combine(
+            filesystem
+                .map(FilesystemOptions::into_options)
+                .map(|options| options.top_level)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Read the user-level configuration, unless configuration discovery is disabled.
+    fn user_config(&self) -> anyhow::Result<Option<FilesystemOptions>> {
+        if self.no_config {
+            return Ok(None);
+        }
+        Ok(FilesystemOptions::user()?)
+    }
+
+    /// Read the configuration for the given workspace, combined with the user-level configuration,
+    /// unless configuration discovery is disabled.
+    fn workspace_config(&self, workspace: &Workspace) -> anyhow::Result<Option<FilesystemOptions>> {
+        if self.no_config {
+            return Ok(None);
+        }
+        let project = FilesystemOptions::find(workspace.install_path())?;
+        let user = FilesystemOptions::user()?;
+        Ok(project.combine(user))
+    }
+
+    fn python_request(&self) -> Option<PythonRequest> {
+        self.python.as_deref().map(PythonRequest::parse)
+    }
+}
+
+/// A package at a specific version.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Package {
+    pub name: PackageName,
+    pub version: Version,
+}
+
+impl fmt::Display for Package {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}=={}", self.name, self.version)
+    }
+}
+
+/// The result of [`resolve`].
+#[derive(Debug, Clone)]
+pub struct ResolveOutcome {
+    /// The resolved packages, sorted by name.
+    pub packages: Vec<Package>,
+}
+
+/// The result of [`lock`].
+#[derive(Debug, Clone)]
+pub struct LockOutcome {
+    /// The path to the lockfile.
+    pub lockfile: PathBuf,
+    /// The locked packages, sorted by name and version.
+    ///
+    /// A package may be locked at multiple versions, for different platforms or Python versions.
+    pub packages: Vec<Package>,
+    /// Whether the lockfile was created or modified.
+    pub changed: bool,
+}
+
+/// The result of [`sync`].
+#[derive(Debug, Clone)]
+pub struct SyncOutcome {
+    /// The root of the synced environment.
+    pub environment: PathBuf,
+    /// The packages that were installed (or reinstalled), sorted by name.
+    pub installed: Vec<Package>,
+    /// The packages that were uninstalled (or reinstalled), sorted by name.
+    pub uninstalled: Vec<Package>,
+}
+
+/// The result of [`tool_install`].
+#[derive(Debug, Clone)]
+pub struct ToolInstallOutcome {
+    /// The installed tool.
+    pub package: Package,
+    /// The root of the tool environment.
+    pub environment: PathBuf,
+    /// The executables that were installed for the tool.
+    pub executables: Vec<ToolExecutable>,
+}
+
+/// An executable installed for a tool.
+#[derive(Debug, Clone)]
+pub struct ToolExecutable {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Resolve the given requirements (e.g., `flask>=3`) for the requested Python interpreter.
+///
+/// Like `uv pip compile`, the resolution is specific to the interpreter; nothing is installed.
+pub async fn resolve(requirements: &[String], options: &Options) -> Result<ResolveOutcome, Error> {
+    Ok(resolve_requirements(requirements, options).await?)
+}
+
+/// Lock the project at the given path, updating its `uv.lock` if necessary.
+pub async fn lock(project: &Path, options: &Options) -> Result<LockOutcome, Error> {
+    Ok(lock_project(project, options).await?)
+}
+
+/// Sync the environment of the project at the given path with its lockfile, locking the project
+/// first if necessary.
+///
+/// As with `uv sync`, the project's development dependencies are included, and any extraneous
+/// packages are removed from the environment.
+pub async fn sync(project: &Path, options: &Options) -> Result<SyncOutcome, Error> {
+    Ok(sync_project(project, options).await?)
+}
+
+/// Install the given package as a tool, with its executables available in the tools `bin`
+/// directory.
+///
+/// If provided, `from` is the requirement from which to install the tool (e.g., `ruff>=0.6`, or
+/// a URL), in lieu of the package name; `with` includes additional requirements in the tool
+/// environment. Unless `force` is set, executables that were not installed by uv are not
+/// overwritten.
+pub async fn tool_install(
+    package: &PackageName,
+    from: Option<&str>,
+    with: &[String],
+    force: bool,
+    options: &Options,
+) -> Result<ToolInstallOutcome, Error> {
+    // As with `uv tool install`, the options are recorded in the tool receipt, such that they're
+    // respected on upgrade.
+    let resolver = options.resolver_options(options.user_config()?);
+    let settings = ResolverInstallerSettings::from(resolver.clone());
+
+    let with = with
+        .iter()
+        .cloned()
+        .map(RequirementsSource::Package)
+        .collect::<Vec<_>>();
+
+    let status = tool::install::install(
+        package.to_string(),
+        false,
+        from.map(ToString::to_string),
+        &with,
+        &[],
+        &[],
+        options.python.clone(),
+        force,
+        false,
+        None,
+        None,
+        EntrypointSelection::default(),
+        false,
+        BTreeMap::default(),
+        ToolFormat::Text,
+        resolver,
+        settings,
+        PreviewMode::Disabled,
+        options.python_preference,
+        options.python_downloads,
+        options.connectivity,
+        options.concurrency,
+        options.native_tls,
+        &options.cache,
+        Printer::Quiet,
+    )
+    .await?;
+
+    if !matches!(status, ExitStatus::Success) {
+        return Err(Error::NoExecutables(package.clone()));
+    }
+
+    // Read the installed tool from its receipt.
+    let installed_tools = InstalledTools::from_settings().map_err(anyhow::Error::from)?;
+    let name = ToolName::new(package.clone(), None).map_err(anyhow::Error::from)?;
+    let tool = installed_tools
+        .get_tool_receipt(&name)
+        .map_err(anyhow::Error::from)?
+        .ok_or_else(|| anyhow!("Failed to find the receipt for `{package}`"))?;
+    let version = installed_tools
+        .version(&name, &options.cache)
+        .map_err(anyhow::Error::from)?;
+
+    Ok(ToolInstallOutcome {
+        package: Package {
+            name: package.clone(),
+            version,
+        },
+        environment: installed_tools.tool_dir(&name),
+        executables: tool
+            .entrypoints()
+            .iter()
+            .map(|entrypoint| ToolExecutable {
+                name: entrypoint.name.clone(),
+                path: entrypoint.install_path.clone(),
+            })
+            .collect(),
+    })
+}
+
+async fn resolve_requirements(
+    requirements: &[String],
+    options: &Options,
+) -> anyhow::Result<ResolveOutcome> {
+    let settings =
+        ResolverInstallerSettings::from(options.resolver_options(options.user_config()?));
+
+    let client_builder = BaseClientBuilder::new()
+        .connectivity(options.connectivity)
+        .native_tls(options.native_tls);
+
+    let interpreter = PythonInstallation::find_or_download(
+        options.python_request(),
+        EnvironmentPreference::Any,
+        options.python_preference,
+        options.python_downloads,
+        &client_builder,
+        &options.cache,
+        None,
+    )
+    .await?
+    .into_interpreter();
+
+    let requirements = requirements
+        .iter()
+        .cloned()
+        .map(RequirementsSource::Package)
+        .collect::<Vec<_>>();
+    let spec =
+        RequirementsSpecification::from_sources(&requirements, &[], &[], &client_builder).await?;
+    spec.reject_git_editables()?;
+
+    let graph = project::resolve_environment(
+        &interpreter,
+        spec,
+        settings.as_ref().into(),
+        &SharedState::default(),
+        Box::new(SummaryResolveLogger),
+        PreviewMode::Disabled,
+        options.connectivity,
+        options.concurrency,
+        options.native_tls,
+        &options.cache,
+        Printer::Quiet,
+    )
+    .await?;
+
+    let mut packages = Resolution::from(&graph)
+        .packages()
+        .filter_map(|name| {
+            graph.version(name).map(|version| Package {
+                name: name.clone(),
+                version: version.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+    packages.sort();
+
+    Ok(ResolveOutcome { packages })
+}
+
+async fn lock_project(project: &Path, options: &Options) -> anyhow::Result<LockOutcome> {
+    let workspace = Workspace::discover(project, &DiscoveryOptions::default()).await?;
+    let settings = ResolverInstallerSettings::from(
+        options.resolver_options(options.workspace_config(&workspace)?),
+    );
+
+    let interpreter = FoundInterpreter::discover(
+        &workspace,
+        options.python_request(),
+        options.python_preference,
+        options.python_downloads,
+        options.connectivity,
+        options.native_tls,
+        &options.cache,
+        Printer::Quiet,
+    )
+    .await?
+    .into_interpreter();
+
+    let result = do_safe_lock(
+        false,
+        false,
+        &workspace,
+        &interpreter,
+        None,
+        settings.as_ref().into(),
+        Box::new(SummaryResolveLogger),
+        PreviewMode::Disabled,
+        options.connectivity,
+        options.concurrency,
+        options.native_tls,
+        &options.cache,
+        Printer::Quiet,
+    )
+    .await?;
+
+    let mut packages = result
+        .lock
+        .packages()
+        .iter()
+        .map(|package| Package {
+            name: package.name().clone(),
+            version: package.version().clone(),
+        })
+        .collect::<Vec<_>>();
+    packages.sort();
+
+    Ok(LockOutcome {
+        lockfile: workspace.install_path().join("uv.lock"),
+        packages,
+        changed: result.previous.as_ref() != Some(&result.lock),
+    })
+}
+
+async fn sync_project(project: &Path, options: &Options) -> anyhow::Result<SyncOutcome> {
+    let project = VirtualProject::discover(project, &DiscoveryOptions::default()).await?;
+    let settings = ResolverInstallerSettings::from(
+        options.resolver_options(options.workspace_config(project.workspace())?),
+    );
+
+    let venv = project::get_or_init_environment(
+        project.workspace(),
+        options.python_request(),
+        options.python_preference,
+        options.python_downloads,
+        options.connectivity,
+        options.native_tls,
+        &options.cache,
+        Printer::Quiet,
+    )
+    .await?;
+
+    let lock = do_safe_lock(
+        false,
+        false,
+        project.workspace(),
+        venv.interpreter(),
+        None,
+        settings.as_ref().into(),
+        Box::new(SummaryResolveLogger),
+        PreviewMode::Disabled,
+        options.connectivity,
+        options.concurrency,
+        options.native_tls,
+        &options.cache,
+        Printer::Quiet,
+    )
+    .await?;
+
+    let state = SharedState::default();
+    let logger = ChangeLogger::default();
+
+    do_sync(
+        &project,
+        None,
+        &venv,
+        &lock.lock,
+        &ExtrasSpecification::None,
+        true,
+        &[],
+        Modifications::Exact,
+        false,
+        false,
+        settings.as_ref().into(),
+        &state,
+        Box::new(logger.clone()),
+        PreviewMode::Disabled,
+        options.connectivity,
+        options.concurrency,
+        options.native_tls,
+        &options.cache,
+        Printer::Quiet,
+    )
+    .await?;
+
+    sync_dev_tools(
+        project.workspace(),
+        &venv,
+        &settings,
+        &state,
+        PreviewMode::Disabled,
+        options.connectivity,
+        options.concurrency,
+        options.native_tls,
+        &options.cache,
+        Printer::Quiet,
+    )
+    .await?;
+
+    let Changes {
+        mut installed,
+        mut uninstalled,
+    } = logger.into_changes();
+    installed.sort();
+    uninstalled.sort();
+
+    Ok(SyncOutcome {
+        environment: venv.root().to_path_buf(),
+        installed,
+        uninstalled,
+    })
+}
+
+/// The changes made to an environment.
+#[derive(Debug, Default)]
+struct Changes {
+    installed: Vec<Package>,
+    uninstalled: Vec<Package>,
+}
+
+/// An [`InstallLogger`] that records the changes to the environment, rather than printing them.
+#[derive(Debug, Default, Clone)]
+struct ChangeLogger(Arc<Mutex<Changes>>);
+
+impl ChangeLogger {
+    /// Return the changes recorded by the logger.
+    fn into_changes(self) -> Changes {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// Return the name and installed version of a local distribution.
+fn local_package(dist: LocalDist) -> Package {
+    let version = match dist.installed_version() {
+        InstalledVersion::Version(version) | InstalledVersion::Url(_, version) => version.clone(),
+    };
+    Package {
+        name: dist.name().clone(),
+        version,
+    }
+}
+
+impl InstallLogger for ChangeLogger {
+    fn on_audit(
+        &self,
+        _count: usize,
+        _start: std::time::Instant,
+        _printer: Printer,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    fn on_prepare(
+        &self,
+        _count: usize,
+        _start: std::time::Instant,
+        _printer: Printer,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    fn on_uninstall(
+        &self,
+        _count: usize,
+        _start: std::time::Instant,
+        _printer: Printer,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    fn on_install(
+        &self,
+        _count: usize,
+        _start: std::time::Instant,
+        _printer: Printer,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    fn on_complete(
+        &self,
+        installed: Vec<CachedDist>,
+        reinstalled: Vec<InstalledDist>,
+        uninstalled: Vec<InstalledDist>,
+        _printer: Printer,
+    ) -> fmt::Result {
+        let mut changes = self.0.lock().unwrap();
+
+        // Reinstalled packages are reported as uninstalled, and then installed at their new
+        // version.
+        changes.uninstalled.extend(
+            uninstalled
+                .into_iter()
+                .chain(reinstalled)
+                .map(LocalDist::from)
+                .map(local_package),
+        );
+        changes.installed.extend(
+            installed
+                .into_iter()
+                .map(LocalDist::from)
+                .map(local_package),
+        );
+
+        Ok(())
+    }
+}
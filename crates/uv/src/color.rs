@@ -0,0 +1,144 @@
+//! The policy for colored output.
+//!
+//! Colors are decided once, at startup, and applied to every output channel: the `anstream`
+//! streams used by the [`Printer`](crate::printer::Printer) and the `tracing` subscriber, along with
+//! the `console` terminal used by the progress bars and prompts. Messages styled with
+//! `owo-colors` are written through `anstream`, which strips the styles if colors are disabled.
+
+use std::ffi::OsString;
+
+use uv_cli::ColorChoice;
+
+/// Resolve the color choice from the command-line arguments and the environment.
+///
+/// An explicit `--color always` or `--color never` (or `--no-color`) takes precedence over the
+/// environment. Otherwise, a non-empty `NO_COLOR` disables colors, while a non-empty
+/// `FORCE_COLOR` or `CLICOLOR_FORCE` (other than `0`) enables them, with `NO_COLOR` taking
+/// precedence. In the absence of any of these, colors are enabled if the output stream is a
+/// terminal that supports them.
+pub(crate) fn resolve(
+    color: ColorChoice,
+    no_color: bool,
+    var: impl Fn(&str) -> Option<OsString>,
+) -> ColorChoice {
+    if no_color {
+        return ColorChoice::Never;
+    }
+    if !matches!(color, ColorChoice::Auto) {
+        return color;
+    }
+
+    let is_set = |name: &str| var(name).is_some_and(|value| !value.is_empty());
+    if is_set("NO_COLOR") {
+        ColorChoice::Never
+    } else if is_set("FORCE_COLOR")
+        || var("CLICOLOR_FORCE").is_some_and(|value| !value.is_empty() && value != "0")
+    {
+        ColorChoice::Always
+    } else {
+        ColorChoice::Auto
+    }
+}
+
+/// Apply the color choice to all output streams.
+///
+/// Must be called before any output is written, including the setup of the `tracing` subscriber.
+pub(crate) fn configure(color: ColorChoice) {
+    anstream::ColorChoice::write_global(color.into());
+
+    // On Windows, the console has to opt in to ANSI escape sequences. `anstream` falls back to the
+    // legacy console APIs if it can't, but the progress bars write escape sequences directly.
+    #[cfg(windows)]
+    let ansi = anstyle_query::windows::enable_ansi_colors().unwrap_or(true);
+    #[cfg(not(windows))]
+    let ansi = true;
+
+    // Align `console` (and, by extension, `indicatif`) with the per-stream choice made by
+    // `anstream`, which accounts for terminal support in `auto` mode.
+    console::set_colors_enabled(
+        ansi && !matches!(
+            anstream::Stdout::choice(&std::io::stdout()),
+            anstream::ColorChoice::Never
+        ),
+    );
+    console::set_colors_enabled_stderr(
+        ansi && !matches!(
+            anstream::Stderr::choice(&std::io::stderr()),
+            anstream::ColorChoice::Never
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use uv_cli::ColorChoice;
+
+    use super::resolve;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| OsString::from(value))
+        }
+    }
+
+    #[test]
+    fn resolve_color() {
+        // Without any configuration, defer to the terminal.
+        assert!(matches!(
+            resolve(ColorChoice::Auto, false, env(&[])),
+            ColorChoice::Auto
+        ));
+
+        // The environment variables apply in `auto` mode.
+        assert!(matches!(
+            resolve(ColorChoice::Auto, false, env(&[("NO_COLOR", "1")])),
+            ColorChoice::Never
+        ));
+        assert!(matches!(
+            resolve(ColorChoice::Auto, false, env(&[("FORCE_COLOR", "1")])),
+            ColorChoice::Always
+        ));
+        assert!(matches!(
+            resolve(ColorChoice::Auto, false, env(&[("CLICOLOR_FORCE", "1")])),
+            ColorChoice::Always
+        ));
+
+        // Empty values, and `CLICOLOR_FORCE=0`, are ignored.
+        assert!(matches!(
+            resolve(ColorChoice::Auto, false, env(&[("NO_COLOR", "")])),
+            ColorChoice::Auto
+        ));
+        assert!(matches!(
+            resolve(ColorChoice::Auto, false, env(&[("CLICOLOR_FORCE", "0")])),
+            ColorChoice::Auto
+        ));
+
+        // `NO_COLOR` takes precedence over `FORCE_COLOR`.
+        assert!(matches!(
+            resolve(
+                ColorChoice::Auto,
+                false,
+                env(&[("NO_COLOR", "1"), ("FORCE_COLOR", "1")])
+            ),
+            ColorChoice::Never
+        ));
+
+        // An explicit choice takes precedence over the environment.
+        assert!(matches!(
+            resolve(ColorChoice::Never, false, env(&[("FORCE_COLOR", "1")])),
+            ColorChoice::Never
+        ));
+        assert!(matches!(
+            resolve(ColorChoice::Always, false, env(&[("NO_COLOR", "1")])),
+            ColorChoice::Always
+        ));
+        assert!(matches!(
+            resolve(ColorChoice::Auto, true, env(&[("FORCE_COLOR", "1")])),
+            ColorChoice::Never
+        ));
+    }
+}
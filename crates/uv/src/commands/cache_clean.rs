@@ -1,11 +1,13 @@
 use std::fmt::Write;
 
 use anyhow::{Context, Result};
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 
-use uv_cache::Cache;
+use uv_cache::{Cache, CacheBucket, Removal};
 use uv_fs::Simplified;
 use uv_normalize::PackageName;
+use uv_warnings::warn_user;
 
 use crate::commands::{human_readable_bytes, ExitStatus};
 use crate::printer::Printer;
@@ -13,6 +15,7 @@ use crate::printer::Printer;
 /// Clear the cache, removing all entries or those linked to specific packages.
 pub(crate) fn cache_clean(
     packages: &[PackageName],
+    built_dependents: bool,
     cache: &Cache,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -67,6 +70,15 @@ pub(crate) fn cache_clean(
         }
 
         writeln!(printer.stderr())?;
+
+        // Buckets stored outside the cache directory may be shared, so they're left in place.
+        for bucket in CacheBucket::iter().filter(|bucket| cache.is_relocated(*bucket)) {
+            warn_user!(
+                "The `{}` bucket is stored outside the cache directory and was not cleared: {}",
+                bucket.name(),
+                cache.bucket(bucket).user_display()
+            );
+        }
     } else {
         for package in packages {
             let summary = cache.remove(package)?;
@@ -118,6 +130,56 @@ pub(crate) fn cache_clean(
             }
 
             writeln!(printer.stderr())?;
+
+            // Identify any wheels that were built from source with the package in their build
+            // environment, which may be stale now that the package has been removed.
+            let dependents = cache.built_dependents(package);
+            if dependents.is_empty() {
+                continue;
+            }
+            let names = dependents
+                .iter()
+                .map(|(name, _)| name)
+                .dedup()
+                .map(|name| name.cyan().to_string())
+                .join(", ");
+            if built_dependents {
+                let mut summary = Removal::default();
+                for (_, directory) in &dependents {
+                    summary += cache.remove_built_wheels(directory)?;
+                }
+
+                // Write a summary of the number of files removed.
+                let s = if summary.num_files == 1 { "" } else { "s" };
+                write!(
+                    printer.stderr(),
+                    "Removed {} file{s} for wheels built with {}: {names}",
+                    summary.num_files,
+                    package.cyan()
+                )?;
+
+                // If any, write a summary of the total byte count removed.
+                if summary.total_bytes > 0 {
+                    let bytes = if summary.total_bytes < 1024 {
+                        format!("{}B", summary.total_bytes)
+                    } else {
+                        let (bytes, unit) = human_readable_bytes(summary.total_bytes);
+                        format!("{bytes:.1}{unit}")
+                    };
+                    write!(printer.stderr(), " ({})", bytes.green())?;
+                }
+
+                writeln!(printer.stderr())?;
+            } else {
+                writeln!(
+                    printer.stderr(),
+                    "{}{} Wheels built with {} were retained: {names} (to rebuild them, use `{}`)",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.cyan(),
+                    "--built-dependents".green(),
+                )?;
+            }
         }
     }
 
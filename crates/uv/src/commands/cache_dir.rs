@@ -1,10 +1,20 @@
 use anstream::println;
 use owo_colors::OwoColorize;
 
-use uv_cache::Cache;
+use uv_cache::{Cache, CacheBucket};
 use uv_fs::Simplified;
 
 /// Show the cache directory.
-pub(crate) fn cache_dir(cache: &Cache) {
-    println!("{}", cache.root().simplified_display().cyan());
+pub(crate) fn cache_dir(cache: &Cache, per_bucket: bool) {
+    if per_bucket {
+        for bucket in CacheBucket::iter() {
+            println!(
+                "{} {}",
+                bucket.name(),
+                cache.bucket(bucket).simplified_display().cyan()
+            );
+        }
+    } else {
+        println!("{}", cache.root().simplified_display().cyan());
+    }
 }
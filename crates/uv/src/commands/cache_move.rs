@@ -0,0 +1,82 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::{absolutize_path, Simplified};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Move the cache to a different directory.
+pub(crate) fn cache_move(
+    destination: &Path,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if cache.is_temporary() {
+        bail!("Cannot move a temporary cache (i.e., with `--no-cache`)");
+    }
+
+    if !cache.root().exists() {
+        writeln!(
+            printer.stderr(),
+            "No cache found at: {}",
+            cache.root().user_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Resolve the canonical cache root, such that symlinks within the cache can be rebased.
+    let cache = cache.clone().init()?;
+
+    let destination = absolutize_path(destination)?.into_owned();
+    if destination.starts_with(cache.root()) {
+        bail!(
+            "Cannot move the cache into itself: {}",
+            destination.user_display()
+        );
+    }
+
+    writeln!(
+        printer.stderr(),
+        "Moving cache from {} to {}",
+        cache.root().user_display().cyan(),
+        destination.user_display().cyan()
+    )?;
+
+    let relocation = cache
+        .relocate(&destination)
+        .with_context(|| format!("Failed to move cache to: {}", destination.user_display()))?;
+
+    if relocation.renamed {
+        writeln!(printer.stderr(), "Renamed cache directory")?;
+    } else {
+        let s = if relocation.num_files == 1 { "" } else { "s" };
+        write!(printer.stderr(), "Copied {} file{s}", relocation.num_files)?;
+        if relocation.num_hard_links > 0 {
+            let s = if relocation.num_hard_links == 1 {
+                ""
+            } else {
+                "s"
+            };
+            write!(
+                printer.stderr(),
+                " (preserved {} hard link{s})",
+                relocation.num_hard_links
+            )?;
+        }
+        writeln!(printer.stderr())?;
+    }
+
+    writeln!(
+        printer.stderr(),
+        "To use the relocated cache, set `{}` or `{}`",
+        format!("UV_CACHE_DIR={}", destination.simplified_display()).green(),
+        format!("cache-dir = \"{}\"", destination.simplified_display()).green()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
@@ -1,16 +1,36 @@
 use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use uv_cache::Cache;
-use uv_fs::Simplified;
+use distribution_types::UnresolvedRequirement;
+use pep440_rs::{Operator, Version};
+use pypi_types::RequirementSource;
+use uv_cache::{Cache, Removal};
+use uv_client::{BaseClientBuilder, Connectivity};
+use uv_fs::{Simplified, CWD};
+use uv_normalize::PackageName;
+use uv_requirements::{RequirementsSource, RequirementsSpecification};
+use uv_resolver::Lock;
 
 use crate::commands::{human_readable_bytes, ExitStatus};
 use crate::printer::Printer;
 
 /// Prune all unreachable objects from the cache.
-pub(crate) fn cache_prune(ci: bool, cache: &Cache, printer: Printer) -> Result<ExitStatus> {
+pub(crate) async fn cache_prune(
+    ci: bool,
+    tool_runs: bool,
+    max_age: Option<u64>,
+    max_size: Option<u64>,
+    lockfiles: &[String],
+    builds_for: &[PackageName],
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
     if !cache.root().exists() {
         writeln!(
             printer.stderr(),
@@ -20,15 +40,49 @@ pub(crate) fn cache_prune(ci: bool, cache: &Cache, printer: Printer) -> Result<E
         return Ok(ExitStatus::Success);
     }
 
+    // Read the lockfiles before modifying the cache, such that any errors leave it intact.
+    let reachable = if lockfiles.is_empty() {
+        None
+    } else {
+        Some(Reachable::from_lockfiles(lockfiles).await?)
+    };
+
     writeln!(
         printer.stderr(),
         "Pruning cache at: {}",
         cache.root().user_display().cyan()
     )?;
 
-    let summary = cache
-        .prune(ci)
-        .with_context(|| format!("Failed to prune cache at: {}", cache.root().user_display()))?;
+    let summary = if tool_runs {
+        if max_age.is_some() || max_size.is_some() {
+            // Remove any cached environments that exceed the given limits.
+            let max_age = max_age.map(|days| Duration::from_secs(days * 24 * 60 * 60));
+            cache.prune_environments(max_age, max_size, None)
+        } else {
+            // Remove every cached environment, regardless of when it was last used.
+            cache.prune_environments(None, Some(0), None)
+        }
+    } else if !builds_for.is_empty() {
+        // Remove the built wheels for each of the given packages.
+        builds_for
+            .iter()
+            .try_fold(Removal::default(), |mut summary, name| {
+                summary += cache.remove_builds(name)?;
+                Ok(summary)
+            })
+    } else if let Some(reachable) = reachable {
+        // Remove any distributions that aren't reachable from the lockfiles, then remove any
+        // archives that are no longer referenced.
+        cache
+            .prune_unreachable(|name, version| reachable.contains(name, version))
+            .and_then(|mut summary| {
+                summary += cache.prune(ci)?;
+                Ok(summary)
+            })
+    } else {
+        cache.prune(ci)
+    }
+    .with_context(|| format!("Failed to prune cache at: {}", cache.root().user_display()))?;
 
     // Write a summary of the number of files and directories removed.
     match (summary.num_files, summary.num_dirs) {
@@ -64,3 +118,110 @@ pub(crate) fn cache_prune(ci: bool, cache: &Cache, printer: Printer) -> Result<E
 
     Ok(ExitStatus::Success)
 }
+
+/// The packages (and versions) that are reachable from a set of lockfiles.
+#[derive(Debug, Default)]
+struct Reachable(FxHashMap<PackageName, Option<FxHashSet<Version>>>);
+
+impl Reachable {
+    /// Collect the reachable packages from the lockfiles matching the given glob patterns.
+    ///
+    /// Each file is read as a `uv.lock` if it has a `.lock` extension, and as a
+    /// `requirements.txt` otherwise.
+    async fn from_lockfiles(patterns: &[String]) -> Result<Self> {
+        let mut paths = Vec::new();
+        for pattern in patterns {
+            let absolute = CWD.join(pattern);
+            let absolute = absolute.to_string_lossy();
+            let matches = glob::glob(&absolute)
+                .with_context(|| format!("Invalid glob pattern: `{pattern}`"))?
+                .collect::<Result<Vec<PathBuf>, _>>()?;
+            if matches.is_empty() {
+                bail!("No lockfiles found matching: `{pattern}`");
+            }
+            paths.extend(matches);
+        }
+
+        let mut reachable = Self::default();
+        for path in paths {
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "lock")
+            {
+                reachable.extend_lock(&path)?;
+            } else {
+                reachable.extend_requirements_txt(&path).await?;
+            }
+        }
+        Ok(reachable)
+    }
+
+    /// Add the packages in a `uv.lock` file.
+    fn extend_lock(&mut self, path: &Path) -> Result<()> {
+        let encoded = fs_err::read_to_string(path)?;
+        let lock = Lock::from_toml(&encoded)
+            .with_context(|| format!("Failed to parse lockfile: `{}`", path.user_display()))?;
+        for package in lock.packages() {
+            self.insert(package.name().clone(), Some(package.version().clone()));
+        }
+        Ok(())
+    }
+
+    /// Add the packages in a `requirements.txt` file. Packages that aren't pinned to an exact
+    /// version are reachable at any version.
+    async fn extend_requirements_txt(&mut self, path: &Path) -> Result<()> {
+        let spec = RequirementsSpecification::from_source(
+            &RequirementsSource::from_requirements_file(path.to_path_buf()),
+            &BaseClientBuilder::new().connectivity(Connectivity::Offline),
+        )
+        .await?;
+        for requirement in spec.requirements {
+            let UnresolvedRequirement::Named(requirement) = requirement.requirement else {
+                continue;
+            };
+            let version = match &requirement.source {
+                RequirementSource::Registry { specifier, .. } => match specifier.as_ref() {
+                    [specifier] if *specifier.operator() == Operator::Equal => {
+                        Some(specifier.version().clone())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            self.insert(requirement.name, version);
+        }
+        Ok(())
+    }
+
+    /// Mark the given package as reachable, at the given version or, if `None`, at any version.
+    fn insert(&mut self, name: PackageName, version: Option<Version>) {
+        match version {
+            Some(version) => {
+                if let Some(versions) = self
+                    .0
+                    .entry(name)
+                    .or_insert_with(|| Some(FxHashSet::default()))
+                {
+                    versions.insert(version);
+                }
+            }
+            None => {
+                self.0.insert(name, None);
+            }
+        }
+    }
+
+    /// Returns `true` if the given package is reachable at the given version or, if `None`, at
+    /// any version.
+    ///
+    /// Versions that can't be parsed are conservatively treated as reachable.
+    fn contains(&self, name: &PackageName, version: Option<&str>) -> bool {
+        match self.0.get(name) {
+            None => false,
+            Some(None) => true,
+            Some(Some(versions)) => version.map_or(true, |version| {
+                Version::from_str(version).map_or(true, |version| versions.contains(&version))
+            }),
+        }
+    }
+}
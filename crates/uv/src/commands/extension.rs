@@ -0,0 +1,96 @@
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use tokio::process::Command;
+use tracing::debug;
+
+use uv_cache::Cache;
+use uv_client::Connectivity;
+use uv_fs::Simplified;
+
+use crate::commands::ExitStatus;
+use crate::settings::GlobalSettings;
+
+/// Return the path to the executable that provides the external subcommand with the given name
+/// (i.e., a `uv-<name>` executable on the `PATH`), if any.
+pub(crate) fn find_extension(name: &OsStr) -> Option<PathBuf> {
+    let mut executable = OsString::from("uv-");
+    executable.push(name);
+    which::which(executable).ok()
+}
+
+/// Run an external subcommand, i.e., `uv <name>` for a `uv-<name>` executable on the `PATH`.
+///
+/// The resolved global settings are passed to the extension via environment variables, such that
+/// the extension (and any uv commands it invokes) respects them.
+pub(crate) async fn extension(
+    args: &[OsString],
+    config_file: Option<PathBuf>,
+    no_config: bool,
+    globals: &GlobalSettings,
+    cache: &Cache,
+) -> Result<ExitStatus> {
+    let [name, args @ ..] = args else {
+        bail!("No extension name was provided");
+    };
+    let executable = find_extension(name)
+        .with_context(|| format!("No extension found for `uv {}`", name.to_string_lossy()))?;
+
+    debug!(
+        "Running extension `{}` at: {}",
+        name.to_string_lossy(),
+        executable.user_display()
+    );
+    let mut process = Command::new(&executable);
+    process.args(args);
+
+    // Expose the path to the current uv executable, such that extensions can invoke it.
+    if let Ok(uv) = std::env::current_exe() {
+        process.env("UV", uv);
+    }
+
+    if cache.is_temporary() {
+        process.env("UV_NO_CACHE", "1");
+    } else {
+        process.env("UV_CACHE_DIR", cache.root());
+    }
+    if let Some(config_file) = config_file {
+        process.env("UV_CONFIG_FILE", config_file);
+    }
+    if no_config {
+        process.env("UV_NO_CONFIG", "1");
+    }
+    if globals.preview.is_enabled() {
+        process.env("UV_PREVIEW", "1");
+    }
+    if globals.native_tls {
+        process.env("UV_NATIVE_TLS", "1");
+    }
+    if matches!(globals.connectivity, Connectivity::Offline) {
+        process.env("UV_OFFLINE", "1");
+    }
+    if let Some(value) = globals.python_preference.to_possible_value() {
+        process.env("UV_PYTHON_PREFERENCE", value.get_name());
+    }
+    if let Some(value) = globals.progress_format.to_possible_value() {
+        process.env("UV_PROGRESS_FORMAT", value.get_name());
+    }
+
+    // Spawn and wait for completion; standard input, output, and error streams are all inherited.
+    let mut handle = process
+        .spawn()
+        .with_context(|| format!("Failed to spawn: `{}`", executable.user_display()))?;
+
+    // Ignore signals in the parent process, deferring them to the child.
+    let _handler = tokio::spawn(async { while tokio::signal::ctrl_c().await.is_ok() {} });
+
+    let status = handle.wait().await.context("Child process disappeared")?;
+
+    if status.success() {
+        Ok(ExitStatus::Success)
+    } else {
+        Ok(ExitStatus::Failure)
+    }
+}
@@ -1,13 +1,19 @@
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
 use std::time::Duration;
 use std::{fmt::Display, fmt::Write, process::ExitCode};
 
 use anyhow::Context;
 use owo_colors::OwoColorize;
+use tracing::debug;
 
 pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_dir::cache_dir;
+pub(crate) use cache_move::cache_move;
 pub(crate) use cache_prune::cache_prune;
 use distribution_types::InstalledMetadata;
+pub(crate) use extension::{extension, find_extension};
 pub(crate) use help::help;
 pub(crate) use pip::check::pip_check;
 pub(crate) use pip::compile::pip_compile;
@@ -19,12 +25,19 @@ pub(crate) use pip::sync::pip_sync;
 pub(crate) use pip::tree::pip_tree;
 pub(crate) use pip::uninstall::pip_uninstall;
 pub(crate) use project::add::add;
+pub(crate) use project::build::build;
+pub(crate) use project::diff::diff;
+pub(crate) use project::export::export;
 pub(crate) use project::init::init;
 pub(crate) use project::lock::lock;
 pub(crate) use project::remove::remove;
-pub(crate) use project::run::{parse_script, run};
+pub(crate) use project::run::{parse_script, run, run_all_pythons};
+pub(crate) use project::server::server;
 pub(crate) use project::sync::sync;
+pub(crate) use project::test::test;
 pub(crate) use project::tree::tree;
+pub(crate) use project::vendor::vendor;
+pub(crate) use project::why::why;
 pub(crate) use python::dir::dir as python_dir;
 pub(crate) use python::find::find as python_find;
 pub(crate) use python::install::install as python_install;
@@ -33,43 +46,69 @@ pub(crate) use python::pin::pin as python_pin;
 pub(crate) use python::uninstall::uninstall as python_uninstall;
 #[cfg(feature = "self-update")]
 pub(crate) use self_update::self_update;
+pub(crate) use stats::stats;
 pub(crate) use tool::dir::dir as tool_dir;
 pub(crate) use tool::install::install as tool_install;
+pub(crate) use tool::kill::kill as tool_kill;
 pub(crate) use tool::list::list as tool_list;
+pub(crate) use tool::prune::prune as tool_prune;
+pub(crate) use tool::ps::ps as tool_ps;
+pub(crate) use tool::repair::repair as tool_repair;
 pub(crate) use tool::run::run as tool_run;
 pub(crate) use tool::run::ToolRunCommand;
+pub(crate) use tool::show::show as tool_show;
 pub(crate) use tool::uninstall::uninstall as tool_uninstall;
 pub(crate) use tool::update_shell::update_shell as tool_update_shell;
 pub(crate) use tool::upgrade::upgrade as tool_upgrade;
-use uv_cache::Cache;
-use uv_fs::Simplified;
+use uv_cache::{Cache, CacheBucket};
+use uv_configuration::TyposquatCheck;
+use uv_fs::{LockedFile, Simplified};
 use uv_git::GitResolver;
-use uv_installer::compile_tree;
+use uv_installer::{compile_tree, SitePackages, VerifyImportsResult};
 use uv_normalize::PackageName;
 use uv_python::PythonEnvironment;
+use uv_requirements::PossibleTyposquat;
 use uv_resolver::InMemoryIndex;
 use uv_types::InFlight;
+use uv_warnings::warn_user;
 pub(crate) use venv::venv;
+pub(crate) use venv_list::venv_list;
+pub(crate) use venv_prune::venv_prune;
+pub(crate) use venv_remove::venv_remove;
 pub(crate) use version::version;
+pub(crate) use workspace::list::list as workspace_list;
+pub(crate) use workspace::tree::tree as workspace_tree;
 
 use crate::printer::Printer;
 
 mod cache_clean;
 mod cache_dir;
+mod cache_move;
 mod cache_prune;
+mod extension;
 mod help;
 pub(crate) mod pip;
-mod project;
+pub(crate) mod project;
 mod python;
 pub(crate) mod reporters;
-mod tool;
+pub(crate) mod tool;
 
 #[cfg(feature = "self-update")]
 mod self_update;
+mod stats;
 mod venv;
+mod venv_list;
+mod venv_prune;
+mod venv_remove;
 mod version;
+mod workspace;
 
-#[derive(Copy, Clone)]
+/// The exit status of a command.
+///
+/// Each status maps to a distinct exit code, such that scripts can branch on the class of failure
+/// without parsing the output. The exit codes are part of uv's public interface (see
+/// `docs/reference/exit-codes.md`); new classes must be assigned new codes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) enum ExitStatus {
     /// The command succeeded.
     Success,
@@ -79,15 +118,137 @@ pub(crate) enum ExitStatus {
 
     /// The command failed with an unexpected error.
     Error,
+
+    /// The command failed because the requirements could not be resolved.
+    NoSolution,
+
+    /// The command failed due to a network error, e.g., a failed connection, a timeout, or a
+    /// server error.
+    NetworkError,
+
+    /// The command failed because a distribution did not match its expected hash.
+    HashMismatch,
+
+    /// The command failed because a distribution could not be built.
+    BuildFailure,
+
+    /// The command failed due to invalid command-line arguments.
+    UsageError,
+
+    /// The command ran a child process, which exited with the given code.
+    External(u8),
+}
+
+impl ExitStatus {
+    /// The exit code of the process.
+    pub(crate) fn code(self) -> u8 {
+        match self {
+            Self::Success => 0,
+            Self::Failure => 1,
+            Self::Error => 2,
+            Self::NoSolution => 3,
+            Self::NetworkError => 4,
+            Self::HashMismatch => 5,
+            Self::BuildFailure => 6,
+            Self::UsageError => 7,
+            Self::External(code) => code,
+        }
+    }
+
+    /// Determine the [`ExitStatus`] for an error, based on the failures in its chain of causes.
+    ///
+    /// If the chain contains failures of multiple classes (e.g., a resolution failure while
+    /// installing the build requirements of a source distribution), network errors take
+    /// precedence, followed by hash mismatches, build failures, and resolution failures. Errors
+    /// that don't fall into any class map to [`ExitStatus::Error`].
+    pub(crate) fn from_error(err: &(dyn std::error::Error + 'static)) -> Self {
+        let statuses = std::iter::successors(Some(err), |err| err.source())
+            .filter_map(classify_error)
+            .collect::<Vec<_>>();
+        [
+            Self::NetworkError,
+            Self::HashMismatch,
+            Self::BuildFailure,
+            Self::NoSolution,
+        ]
+        .into_iter()
+        .find(|status| statuses.contains(status))
+        .unwrap_or(Self::Error)
+    }
+}
+
+/// Classify a single error in a chain of causes, if it represents a known class of failure.
+///
+/// Errors that wrap another error transparently don't expose the wrapped error in the chain of
+/// causes, so the common transparent wrappers are unwrapped here.
+fn classify_error(err: &(dyn std::error::Error + 'static)) -> Option<ExitStatus> {
+    if let Some(err) = err.downcast_ref::<project::ProjectError>() {
+        return match err {
+            project::ProjectError::Operation(err) => classify_error(err),
+            project::ProjectError::SourceHashMismatch(..) => Some(ExitStatus::HashMismatch),
+            project::ProjectError::Python(err) => classify_error(err),
+            _ => None,
+        };
+    }
+    if let Some(err) = err.downcast_ref::<pip::operations::Error>() {
+        return match err {
+            pip::operations::Error::Resolve(err) => classify_error(err),
+            pip::operations::Error::Anyhow(err) => classify_error(err.as_ref()),
+            _ => None,
+        };
+    }
+    if let Some(err) = err.downcast_ref::<uv_resolver::ResolveError>() {
+        return match err {
+            uv_resolver::ResolveError::NoSolution(_) => Some(ExitStatus::NoSolution),
+            uv_resolver::ResolveError::Client(err) => classify_error(err),
+            _ => None,
+        };
+    }
+    if let Some(err) = err.downcast_ref::<uv_distribution::Error>() {
+        return match err {
+            uv_distribution::Error::Client(err) => classify_error(err),
+            uv_distribution::Error::Reqwest(err) => classify_error(err),
+            uv_distribution::Error::MismatchedHashes { .. } => Some(ExitStatus::HashMismatch),
+            uv_distribution::Error::Build(..) | uv_distribution::Error::BuildEditable(..) => {
+                Some(ExitStatus::BuildFailure)
+            }
+            _ => None,
+        };
+    }
+    if let Some(err) = err.downcast_ref::<uv_python::Error>() {
+        return match err {
+            uv_python::Error::Download(err) => classify_error(err),
+            _ => None,
+        };
+    }
+    if let Some(err) = err.downcast_ref::<uv_python::downloads::Error>() {
+        return match err {
+            uv_python::downloads::Error::NetworkError(err) => classify_error(err),
+            uv_python::downloads::Error::HashMismatch { .. }
+            | uv_python::downloads::Error::PinnedHashMismatch { .. } => {
+                Some(ExitStatus::HashMismatch)
+            }
+            _ => None,
+        };
+    }
+    if let Some(err) = err.downcast_ref::<uv_client::Error>() {
+        return err.is_network().then_some(ExitStatus::NetworkError);
+    }
+    if let Some(err) = err.downcast_ref::<uv_client::WrappedReqwestError>() {
+        return err.is_network().then_some(ExitStatus::NetworkError);
+    }
+    if err.is::<uv_build::Error>() {
+        return Some(ExitStatus::BuildFailure);
+    }
+    if err.is::<uv_resolver::NoSolutionError>() {
+        return Some(ExitStatus::NoSolution);
+    }
+    None
 }
 
 impl From<ExitStatus> for ExitCode {
     fn from(status: ExitStatus) -> Self {
-        match status {
-            ExitStatus::Success => Self::from(0),
-            ExitStatus::Failure => Self::from(1),
-            ExitStatus::Error => Self::from(2),
-        }
+        Self::from(status.code())
     }
 }
 
@@ -163,6 +324,178 @@ pub(super) async fn compile_bytecode(
     Ok(())
 }
 
+/// The maximum time to wait for another process to release its lock on an environment, if any.
+static LOCK_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+
+/// Configure the maximum time to wait for another process to release its lock on an environment,
+/// per the `lock-timeout` setting.
+///
+/// Has no effect if the timeout was already configured.
+pub(crate) fn init_lock_timeout(timeout: Duration) {
+    let _ = LOCK_TIMEOUT.set(timeout);
+}
+
+/// Acquire a lock on the environment, to prevent concurrent modifications across processes
+/// (e.g., an editor and a terminal both syncing the same project).
+///
+/// By default, waits indefinitely for the lock to be released. If `lock-timeout` is set, gives
+/// up after the given number of seconds.
+pub(super) async fn lock_environment(venv: &PythonEnvironment) -> std::io::Result<LockedFile> {
+    venv.lock_with_timeout(LOCK_TIMEOUT.get().copied()).await
+}
+
+/// Acquire a lock on the virtual environment at the given path, which need not exist, to prevent
+/// concurrent processes from creating or removing the environment at the same time.
+///
+/// Unlike [`lock_environment`], the lockfile lives outside of the environment, such that it
+/// survives the removal of the environment.
+pub(super) async fn lock_environment_path(venv: &Path) -> std::io::Result<LockedFile> {
+    let venv = uv_fs::absolutize_path(venv)?;
+    LockedFile::acquire_with_timeout(
+        std::env::temp_dir().join(format!("uv-venv-{}.lock", cache_key::cache_digest(&venv))),
+        venv.user_display(),
+        LOCK_TIMEOUT.get().copied(),
+    )
+    .await
+}
+
+/// Wait for any in-progress modifications to the virtual environment at the given path (e.g., an
+/// install by another process) to finish, such that the environment can be removed.
+///
+/// The lock is released immediately, as the lockfile is removed along with the environment;
+/// processes that would recreate the environment are excluded by [`lock_environment_path`].
+pub(super) async fn wait_for_environment(venv: &Path) -> std::io::Result<()> {
+    if venv.join("pyvenv.cfg").is_file() {
+        drop(
+            LockedFile::acquire_with_timeout(
+                venv.join(".lock"),
+                venv.user_display(),
+                LOCK_TIMEOUT.get().copied(),
+            )
+            .await?,
+        );
+    }
+    Ok(())
+}
+
+/// Import each top-level module provided by the given packages in a separate Python process,
+/// to surface broken installations (e.g., native extensions with missing shared libraries)
+/// immediately.
+///
+/// See the `--verify-imports` option on `pip sync`, `pip install`, and `sync`.
+pub(super) async fn verify_imports(
+    packages: &[PackageName],
+    venv: &PythonEnvironment,
+    printer: Printer,
+) -> anyhow::Result<()> {
+    let start = std::time::Instant::now();
+
+    let site_packages = SitePackages::from_environment(venv)?;
+    let dists = packages
+        .iter()
+        .flat_map(|name| site_packages.get_packages(name))
+        .collect::<Vec<_>>();
+    let VerifyImportsResult { modules, failures } =
+        uv_installer::verify_imports(dists, venv).await?;
+
+    if !failures.is_empty() {
+        let s = if failures.len() == 1 { "" } else { "s" };
+        let mut message = format!(
+            "Failed to import {} module{s} after installation:",
+            failures.len()
+        );
+        for failure in failures {
+            write!(
+                message,
+                "\n- `{}` (from `{}`): {}",
+                failure.module, failure.package, failure.error
+            )?;
+        }
+        anyhow::bail!(message);
+    }
+
+    let s = if modules == 1 { "" } else { "s" };
+    writeln!(
+        printer.stderr(),
+        "{}",
+        format!(
+            "Verified imports of {} {}",
+            format!("{modules} module{s}").bold(),
+            format!("in {}", elapsed(start.elapsed())).dimmed()
+        )
+        .dimmed()
+    )?;
+    Ok(())
+}
+
+/// Check the names of the given packages for near-misses of popular packages (e.g., `reqeusts`),
+/// warning or failing per the [`TyposquatCheck`] policy.
+///
+/// The result of the check is cached per package name, such that repeated invocations (e.g., of
+/// the same tool via `uvx`) don't repeat it.
+///
+/// See the `--typosquat-check` option on `add` and `tool run`.
+pub(super) fn check_typosquats<'a>(
+    names: impl IntoIterator<Item = &'a PackageName>,
+    policy: TyposquatCheck,
+    cache: &Cache,
+) -> Result<(), PossibleTyposquat> {
+    if policy == TyposquatCheck::Ignore {
+        return Ok(());
+    }
+    for name in names {
+        let Some(typosquat) = detect_typosquat(name, cache) else {
+            continue;
+        };
+        match policy {
+            TyposquatCheck::Ignore => {}
+            TyposquatCheck::Warn => {
+                warn_user!("{typosquat}");
+            }
+            TyposquatCheck::Error => {
+                return Err(typosquat);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Detect whether the given package name is a near-miss for a popular package, reading the result
+/// from the cache if the name has been checked before.
+///
+/// Failures to read or write the cache are non-fatal, as the check can always be repeated.
+fn detect_typosquat(name: &PackageName, cache: &Cache) -> Option<PossibleTyposquat> {
+    let entry = cache.entry(CacheBucket::Typosquats, "", name.to_string());
+
+    // The entry contains the name of the popular package, or nothing if there's no match.
+    match fs_err::read_to_string(entry.path()) {
+        Ok(popular) if popular.is_empty() => return None,
+        Ok(popular) => match PackageName::from_str(&popular) {
+            Ok(popular) => {
+                return Some(PossibleTyposquat {
+                    name: name.clone(),
+                    popular,
+                })
+            }
+            Err(err) => debug!("Ignoring invalid typosquat cache entry for `{name}`: {err}"),
+        },
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => debug!("Failed to read typosquat cache entry for `{name}`: {err}"),
+    }
+
+    let typosquat = PossibleTyposquat::detect(name);
+    let popular = typosquat
+        .as_ref()
+        .map(|typosquat| typosquat.popular.to_string())
+        .unwrap_or_default();
+    if let Err(err) = fs_err::create_dir_all(entry.dir())
+        .and_then(|()| uv_fs::write_atomic_sync(entry.path(), popular))
+    {
+        debug!("Failed to write typosquat cache entry for `{name}`: {err}");
+    }
+    typosquat
+}
+
 /// Formats a number of bytes into a human readable SI-prefixed size.
 ///
 /// Returns a tuple of `(quantity, units)`.
@@ -179,6 +512,12 @@ pub(super) fn human_readable_bytes(bytes: u64) -> (f32, &'static str) {
     (bytes / 1024_f32.powi(i as i32), UNITS[i])
 }
 
+/// Format a number of bytes for display, e.g., `1.5MiB`.
+pub(super) fn format_bytes(bytes: u64) -> String {
+    let (bytes, unit) = human_readable_bytes(bytes);
+    format!("{bytes:.1}{unit}")
+}
+
 /// Shared state used during resolution and installation.
 #[derive(Default)]
 pub(crate) struct SharedState {
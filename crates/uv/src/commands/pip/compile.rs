@@ -1,39 +1,46 @@
 use std::env;
 use std::io::stdout;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use anstream::{eprint, AutoStream, StripStream};
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use tracing::debug;
 
-use distribution_types::{IndexLocations, UnresolvedRequirementSpecification, Verbatim};
+use distribution_types::{
+    IndexHashPolicies, IndexLocations, RequiredIndex, SourceAnnotation,
+    UnresolvedRequirementSpecification, Verbatim,
+};
 use install_wheel_rs::linker::LinkMode;
+use pep508_rs::{MarkerEnvironment, MarkerTree};
 use pypi_types::Requirement;
 use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
 use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
 use uv_configuration::{
-    BuildOptions, Concurrency, ConfigSettings, ExtrasSpecification, IndexStrategy, NoBinary,
-    NoBuild, PreviewMode, Reinstall, SetupPyStrategy, SourceStrategy, Upgrade,
+    BuildOptions, Concurrency, ConfigSettings, ExtrasSpecification, IndexStrategy, MacosArch,
+    NoBinary, NoBuild, PackageBuildEnv, PreviewMode, Reinstall, SetupPyStrategy, SourceStrategy,
+    Upgrade,
 };
 use uv_configuration::{KeyringProviderType, TargetTriple};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
-use uv_git::GitResolver;
-use uv_normalize::PackageName;
+use uv_git::{GitResolver, ResolvedRepositoryReference};
+use uv_normalize::{GroupName, PackageName};
 use uv_python::{
     EnvironmentPreference, PythonEnvironment, PythonInstallation, PythonPreference, PythonRequest,
     PythonVersion, VersionRequest,
 };
 use uv_requirements::{
-    upgrade::read_requirements_txt, RequirementsSource, RequirementsSpecification,
+    upgrade::{read_requirements_txt, LockedRequirements},
+    RequirementsSource, RequirementsSpecification,
 };
 use uv_resolver::{
     AnnotationStyle, DependencyMode, DisplayResolutionGraph, ExcludeNewer, FlatIndex,
     InMemoryIndex, OptionsBuilder, PrereleaseMode, PythonRequirement, RequiresPython,
-    ResolutionMode, ResolverMarkers,
+    ResolutionMode, ResolverMarkers, ResolverTrace,
 };
 use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy, InFlight};
 use uv_warnings::warn_user;
@@ -48,18 +55,25 @@ use crate::printer::Printer;
 pub(crate) async fn pip_compile(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
+    marked_constraints: &[(RequirementsSource, MarkerTree)],
     overrides: &[RequirementsSource],
     build_constraints: &[RequirementsSource],
     constraints_from_workspace: Vec<Requirement>,
     overrides_from_workspace: Vec<Requirement>,
     extras: ExtrasSpecification,
+    groups: Vec<GroupName>,
+    marker: Option<MarkerTree>,
     output_file: Option<&Path>,
+    resolver_trace: Option<&Path>,
     resolution_mode: ResolutionMode,
     prerelease_mode: PrereleaseMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     generate_hashes: bool,
+    index_hash_policy: &IndexHashPolicies,
     no_emit_packages: Vec<PackageName>,
+    omit: Vec<PackageName>,
+    only_direct: bool,
     include_extras: bool,
     include_markers: bool,
     include_annotations: bool,
@@ -70,17 +84,23 @@ pub(crate) async fn pip_compile(
     include_build_options: bool,
     include_marker_expression: bool,
     include_index_annotation: bool,
+    require_index: Vec<RequiredIndex>,
+    strict_extras: bool,
+    include_constraint_annotation: bool,
     index_locations: IndexLocations,
     index_strategy: IndexStrategy,
     keyring_provider: KeyringProviderType,
     setup_py: SetupPyStrategy,
     config_settings: ConfigSettings,
+    package_build_env: &PackageBuildEnv,
     connectivity: Connectivity,
     no_build_isolation: bool,
     no_build_isolation_package: Vec<PackageName>,
     build_options: BuildOptions,
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
+    macos_arch: MacosArch,
+    target_environment: Option<&Path>,
     universal: bool,
     exclude_newer: Option<ExcludeNewer>,
     sources: SourceStrategy,
@@ -104,6 +124,23 @@ pub(crate) async fn pip_compile(
         ));
     }
 
+    // Read the target environment, if any. Unless a Python version was requested, resolve for the
+    // target's Python version.
+    let target_environment = target_environment
+        .map(read_target_environment)
+        .transpose()?;
+    let python_version = match (python_version, target_environment.as_ref()) {
+        (None, Some(markers)) => Some(
+            PythonVersion::from_str(&markers.python_full_version().string).map_err(|err| {
+                anyhow!("Invalid `python_full_version` in target environment: {err}")
+            })?,
+        ),
+        (python_version, _) => python_version,
+    };
+
+    // Read the requirements from any requested dependency groups.
+    let group_requirements = operations::read_dependency_groups(requirements, &groups)?;
+
     let client_builder = BaseClientBuilder::new()
         .connectivity(connectivity)
         .native_tls(native_tls)
@@ -112,7 +149,8 @@ pub(crate) async fn pip_compile(
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
         project,
-        requirements,
+        mut requirements,
+        git_editables,
         constraints,
         overrides,
         source_trees,
@@ -131,9 +169,42 @@ pub(crate) async fn pip_compile(
     )
     .await?;
 
+    // Apply the requested marker to each of the requirements.
+    if let Some(marker) = marker {
+        for entry in &mut requirements {
+            entry.requirement.and_marker(marker.clone());
+        }
+    }
+
+    // Add the requirements from any requested dependency groups.
+    requirements.extend(group_requirements);
+
+    // Editable Git requirements need a local checkout, which `pip compile` doesn't create.
+    if let Some(entry) = git_editables.first() {
+        return Err(anyhow!(
+            "Editable Git requirements are not supported by `uv pip compile` (found: `{}`)",
+            entry.requirement
+        ));
+    }
+
+    // Read any constraints that only apply in environments that satisfy a given marker.
+    let mut constraints_from_markers = Vec::new();
+    for (source, marker) in marked_constraints {
+        for mut constraint in
+            operations::read_constraints(std::slice::from_ref(source), &client_builder).await?
+        {
+            match &mut constraint.marker {
+                Some(existing) => existing.and(marker.clone()),
+                None => constraint.marker = Some(marker.clone()),
+            }
+            constraints_from_markers.push(constraint);
+        }
+    }
+
     let constraints = constraints
         .iter()
         .cloned()
+        .chain(constraints_from_markers)
         .chain(constraints_from_workspace.into_iter())
         .collect();
 
@@ -247,16 +318,21 @@ pub(crate) async fn pip_compile(
     let (tags, markers) = if universal {
         (None, ResolverMarkers::universal(None))
     } else {
+        // Unless a platform was requested, select wheels for the target environment's platform,
+        // rather than the host's.
+        let python_platform = match (python_platform, target_environment.as_ref()) {
+            (None, Some(markers)) => Some(target_platform(markers)?),
+            (python_platform, _) => python_platform,
+        };
         let (tags, markers) =
-            resolution_environment(python_version, python_platform, &interpreter)?;
-        (
-            Some(tags),
-            ResolverMarkers::SpecificEnvironment((*markers).clone()),
-        )
+            resolution_environment(python_version, python_platform, macos_arch, &interpreter)?;
+        let markers = target_environment.unwrap_or_else(|| (*markers).clone());
+        (Some(tags), ResolverMarkers::SpecificEnvironment(markers))
     };
 
-    // Generate, but don't enforce hashes for the requirements.
-    let hasher = if generate_hashes {
+    // Generate, but don't enforce hashes for the requirements. The sidecar for `--only-direct`
+    // always includes hashes.
+    let hasher = if generate_hashes || only_direct {
         HashStrategy::Generate
     } else {
         HashStrategy::None
@@ -283,9 +359,22 @@ pub(crate) async fn pip_compile(
         .platform(interpreter.platform())
         .build();
 
+    // With `--only-direct`, the complete resolution is written to a sidecar, rather than the
+    // output file.
+    let sidecar = output_file.filter(|_| only_direct).map(sidecar_path);
+
     // Read the lockfile, if present.
-    let preferences = read_requirements_txt(output_file, &upgrade).await?;
+    let LockedRequirements {
+        preferences,
+        git: locked_git,
+    } = read_requirements_txt(sidecar.as_deref().or(output_file), &requirements, &upgrade).await?;
+
+    // Populate the Git resolver, such that any branches or tags resolve to the pinned commits.
     let git = GitResolver::default();
+    for ResolvedRepositoryReference { reference, sha } in locked_git {
+        debug!("Inserting Git reference into resolver: `{reference:?}` at `{sha}`");
+        git.insert(reference, sha);
+    }
 
     // Combine the `--no-binary` and `--no-build` flags from the requirements files.
     let build_options = build_options.combine(no_binary, no_build);
@@ -332,7 +421,8 @@ pub(crate) async fn pip_compile(
         sources,
         concurrency,
         preview,
-    );
+    )
+    .with_package_build_env(package_build_env.clone());
 
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
@@ -342,10 +432,20 @@ pub(crate) async fn pip_compile(
         .index_strategy(index_strategy)
         .build();
 
+    // Create the resolver trace, if requested.
+    let trace = resolver_trace
+        .map(|path| {
+            ResolverTrace::create(path).with_context(|| {
+                format!("Failed to create resolver trace: {}", path.user_display())
+            })
+        })
+        .transpose()?;
+
     // Resolve the requirements.
     let resolution = match operations::resolve(
         requirements,
         constraints,
+        operations::UserConstraints::get(),
         overrides,
         dev,
         source_trees,
@@ -365,6 +465,7 @@ pub(crate) async fn pip_compile(
         &build_dispatch,
         concurrency,
         options,
+        trace,
         Box::new(DefaultResolveLogger),
         printer,
         preview,
@@ -375,11 +476,59 @@ pub(crate) async fn pip_compile(
         Err(operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
             let report = miette::Report::msg(format!("{err}")).context(err.header());
             eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            return Ok(ExitStatus::NoSolution);
         }
         Err(err) => return Err(err.into()),
     };
 
+    // If requested, fail on any extras that aren't provided by the resolved packages.
+    if strict_extras {
+        operations::validate_extras(resolution.diagnostics())?;
+    }
+
+    // Verify that any packages pinned to an index were served by that index.
+    for required in &require_index {
+        for index in resolution.indexes(required.package()) {
+            match index {
+                Some(index) if required.matches(index) => {}
+                Some(index) => {
+                    return Err(anyhow!(
+                        "`{}` was served by `{}`, but `--require-index` requires `{}`",
+                        required.package(),
+                        index.redacted(),
+                        required.index().redacted()
+                    ));
+                }
+                None => {
+                    return Err(anyhow!(
+                        "`{}` was not resolved from an index, but `--require-index` requires `{}`",
+                        required.package(),
+                        required.index().redacted()
+                    ));
+                }
+            }
+        }
+    }
+
+    // Determine the packages to omit, along with any packages that are only required by them.
+    for package in omit.iter().filter(|package| !resolution.contains(package)) {
+        warn_user!("`{package}` was not found in the resolution, and so can't be omitted");
+    }
+    let omitted = resolution.omit(&omit);
+
+    // Verify that the remaining packages are still installable without the pruned packages.
+    let unsatisfied = resolution.verify_omitted(&omitted, markers.marker_environment());
+    if !unsatisfied.is_empty() {
+        return Err(anyhow!(
+            "Omitting {} leaves the following requirements unsatisfied:\n{}",
+            omit.iter().map(|package| format!("`{package}`")).join(", "),
+            unsatisfied
+                .iter()
+                .map(|(package, requirement)| format!("  `{package}` requires `{requirement}`"))
+                .join("\n")
+        ));
+    }
+
     // Write the resolved dependencies to the output channel.
     let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
 
@@ -397,11 +546,50 @@ pub(crate) async fn pip_compile(
                 cmd(
                     include_index_url,
                     include_find_links,
-                    custom_compile_command
+                    custom_compile_command.clone()
                 )
             )
             .green()
         )?;
+
+        // Point to the complete set of requirements.
+        if let Some(sidecar) = &sidecar {
+            writeln!(
+                writer,
+                "{}",
+                format!(
+                    "# Transitive dependencies were excluded due to `--only-direct`; see `{}` for the complete resolution.",
+                    sidecar.user_display()
+                )
+                .green()
+            )?;
+        }
+
+        // Make it clear that the output isn't a complete set of requirements.
+        if !dependency_mode.is_transitive() {
+            writeln!(
+                writer,
+                "{}",
+                "# Dependencies were excluded due to `--no-deps`, so this file may be incomplete."
+                    .green()
+            )?;
+        }
+
+        // Record the hash-checking policy of each index.
+        if !index_hash_policy.is_empty() {
+            writeln!(
+                writer,
+                "{}",
+                "# Hash-checking policies by index (`--index-hash-policy`):".green()
+            )?;
+            for (index, policy) in index_hash_policy.iter() {
+                writeln!(
+                    writer,
+                    "{}",
+                    format!("#    {}={policy}", index.redacted()).green()
+                )?;
+            }
+        }
     }
 
     if include_marker_expression {
@@ -475,25 +663,66 @@ pub(crate) async fn pip_compile(
         writeln!(writer)?;
     }
 
+    // Determine the effect of each constraint on the resolution, if requested.
+    let constraint_effects =
+        include_constraint_annotation.then(|| resolution.constraint_effects(&top_level_index));
+
+    // With `--only-direct`, exclude the transitive dependencies from the output file.
+    let transitive = if only_direct {
+        resolution.transitive_dependencies()
+    } else {
+        Vec::new()
+    };
+
     write!(
         writer,
         "{}",
         DisplayResolutionGraph::new(
             &resolution,
             &markers,
-            &no_emit_packages,
+            &no_emit_packages
+                .iter()
+                .chain(omitted.names())
+                .chain(&transitive)
+                .cloned()
+                .collect::<Vec<_>>(),
             generate_hashes,
             include_extras,
             include_markers || universal,
             include_annotations,
             include_index_annotation,
+            constraint_effects.as_ref(),
             annotation_style,
         )
     )?;
 
+    // If any constraints had no effect, notify the user.
+    if let Some(constraint_effects) = constraint_effects
+        .as_ref()
+        .filter(|effects| !effects.unused().is_empty())
+    {
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{}",
+            "# The following constraints had no effect on the resolution:".green()
+        )?;
+        for constraint in constraint_effects.unused() {
+            if let Some(origin) = &constraint.origin {
+                writeln!(
+                    writer,
+                    "# {constraint} (from {})",
+                    SourceAnnotation::Constraint(origin.clone())
+                )?;
+            } else {
+                writeln!(writer, "# {constraint}")?;
+            }
+        }
+    }
+
     // If any "unsafe" packages were excluded, notify the user.
     let excluded = no_emit_packages
-        .into_iter()
+        .iter()
         .filter(|name| resolution.contains(name))
         .collect::<Vec<_>>();
     if !excluded.is_empty() {
@@ -508,12 +737,99 @@ pub(crate) async fn pip_compile(
         }
     }
 
+    // If any packages were omitted, document what was omitted and why.
+    if !omitted.omitted().is_empty() {
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{}",
+            "# The following packages were omitted from the output, and must be provided by the environment:"
+                .green()
+        )?;
+        for package in omitted.omitted() {
+            if package.required_by().is_empty() {
+                writeln!(writer, "# {}=={}", package.name(), package.version())?;
+            } else {
+                writeln!(
+                    writer,
+                    "# {}=={} (required by {})",
+                    package.name(),
+                    package.version(),
+                    package.required_by().iter().join(", ")
+                )?;
+            }
+        }
+    }
+    if !omitted.pruned().is_empty() {
+        writeln!(writer)?;
+        writeln!(
+            writer,
+            "{}",
+            "# The following packages were pruned from the output, as they're only required by omitted packages:"
+                .green()
+        )?;
+        for package in omitted.pruned() {
+            writeln!(
+                writer,
+                "# {}=={} (required by {})",
+                package.name(),
+                package.version(),
+                package.required_by().iter().join(", ")
+            )?;
+        }
+    }
+
+    // Write the complete resolution, with hashes, to the sidecar.
+    if let (Some(sidecar), Some(output_file)) = (sidecar, output_file) {
+        let mut contents = String::new();
+        if include_header {
+            contents.push_str(&format!(
+                "# This file was autogenerated by uv via the following command:\n#    {}\n# It pins the complete resolution for `{}`, which only includes the direct dependencies.\n",
+                cmd(
+                    include_index_url,
+                    include_find_links,
+                    custom_compile_command
+                ),
+                output_file.user_display()
+            ));
+        }
+        // The resolution graph always renders its annotations in color, so strip any escape
+        // sequences before writing to disk.
+        let graph = DisplayResolutionGraph::new(
+            &resolution,
+            &markers,
+            &no_emit_packages
+                .iter()
+                .chain(omitted.names())
+                .cloned()
+                .collect::<Vec<_>>(),
+            true,
+            include_extras,
+            include_markers || universal,
+            include_annotations,
+            include_index_annotation,
+            None,
+            annotation_style,
+        )
+        .to_string();
+        contents.push_str(&anstream::adapter::strip_str(&graph).to_string());
+        uv_fs::write_atomic(&sidecar, contents).await?;
+    }
+
     // Notify the user of any resolution diagnostics.
     operations::diagnose_resolution(resolution.diagnostics(), printer)?;
 
     Ok(ExitStatus::Success)
 }
 
+/// Return the path of the sidecar that holds the complete resolution for an output file generated
+/// with `--only-direct`, e.g., `requirements.txt.lock` for `requirements.txt`.
+fn sidecar_path(output_file: &Path) -> PathBuf {
+    let mut path = output_file.as_os_str().to_owned();
+    path.push(".lock");
+    PathBuf::from(path)
+}
+
 /// Format the uv command used to generate the output file.
 #[allow(clippy::fn_params_excessive_bools)]
 fn cmd(
@@ -547,6 +863,16 @@ fn cmd(
                     *skip_next = Some(true);
                     return Some(None);
                 }
+
+                // Likewise, skip the index URLs required by `--require-index`.
+                if arg.starts_with("--require-index=") {
+                    *skip_next = None;
+                    return Some(None);
+                }
+                if arg == "--require-index" {
+                    *skip_next = Some(true);
+                    return Some(None);
+                }
             }
 
             // Skip any `--find-links` URLs, unless requested.
@@ -596,6 +922,12 @@ fn cmd(
                 return Some(None);
             }
 
+            // Quote any arguments that contain whitespace (e.g., markers), such that the command
+            // can be copied verbatim.
+            if arg.contains(char::is_whitespace) {
+                return Some(Some(format!("\"{arg}\"")));
+            }
+
             // Return the argument.
             Some(Some(arg))
         })
@@ -643,3 +975,43 @@ impl OutputWriter {
         Ok(())
     }
 }
+
+/// Read the [`MarkerEnvironment`] of a target environment from a JSON file.
+///
+/// The marker values can be provided at the top level, or under an `environment` key, as in the
+/// output of `pip inspect`.
+fn read_target_environment(path: &Path) -> Result<MarkerEnvironment> {
+    let contents = fs_err::read_to_string(path)?;
+    let mut value: serde_json::Value = serde_json::from_str(&contents).with_context(|| {
+        format!(
+            "Failed to parse target environment: `{}`",
+            path.user_display()
+        )
+    })?;
+    if let Some(environment) = value.get_mut("environment") {
+        value = environment.take();
+    }
+    serde_json::from_value(value)
+        .with_context(|| format!("Invalid target environment: `{}`", path.user_display()))
+}
+
+/// Determine the platform of a target environment from its `sys_platform` and `platform_machine`
+/// markers, such that wheels can be selected for it.
+fn target_platform(markers: &MarkerEnvironment) -> Result<TargetTriple> {
+    let machine = markers.platform_machine().to_ascii_lowercase();
+    let platform = match (markers.sys_platform(), machine.as_str()) {
+        ("win32", "amd64" | "x86_64") => TargetTriple::X8664PcWindowsMsvc,
+        ("win32", "arm64" | "aarch64") => TargetTriple::Aarch64PcWindowsMsvc,
+        ("linux", "x86_64" | "amd64") => TargetTriple::X8664UnknownLinuxGnu,
+        ("linux", "aarch64" | "arm64") => TargetTriple::Aarch64UnknownLinuxGnu,
+        ("darwin", "x86_64") => TargetTriple::X8664AppleDarwin,
+        ("darwin", "arm64" | "aarch64") => TargetTriple::Aarch64AppleDarwin,
+        (sys_platform, _) => {
+            return Err(anyhow!(
+                "Unable to determine the platform of the target environment (`sys_platform`: `{sys_platform}`, `platform_machine`: `{}`); provide one with `--python-platform`",
+                markers.platform_machine()
+            ));
+        }
+    };
+    Ok(platform)
+}
@@ -1,25 +1,34 @@
 use std::fmt::Write;
+use std::path::Path;
 
 use anstream::eprint;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use pep440_rs::VersionSpecifiers;
 use pep508_rs::PackageName;
 use tracing::{debug, enabled, Level};
 
-use distribution_types::{IndexLocations, Resolution, UnresolvedRequirementSpecification};
+use distribution_types::{
+    IndexHashPolicies, IndexLocations, Resolution, UnresolvedRequirement,
+    UnresolvedRequirementSpecification,
+};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ExcludePattern;
 use pypi_types::Requirement;
 use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
+use uv_cli::InstallSummary;
 use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
+use uv_configuration::{AbiCheck, FileConflicts, KeyringProviderType, MacosArch, TargetTriple};
 use uv_configuration::{
     BuildOptions, Concurrency, ConfigSettings, ExtrasSpecification, HashCheckingMode,
-    IndexStrategy, PreviewMode, Reinstall, SetupPyStrategy, SourceStrategy, Upgrade,
+    IndexStrategy, PackageBuildEnv, PreviewMode, Reinstall, SetupPyStrategy, SourceStrategy,
+    Upgrade,
 };
-use uv_configuration::{KeyringProviderType, TargetTriple};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::{SatisfiesResult, SitePackages};
+use uv_normalize::GroupName;
 use uv_python::{
     EnvironmentPreference, Prefix, PythonEnvironment, PythonRequest, PythonVersion, Target,
 };
@@ -33,7 +42,7 @@ use uv_types::{BuildIsolation, HashStrategy};
 use crate::commands::pip::loggers::{DefaultInstallLogger, DefaultResolveLogger};
 use crate::commands::pip::operations::Modifications;
 use crate::commands::pip::{operations, resolution_environment};
-use crate::commands::{elapsed, ExitStatus, SharedState};
+use crate::commands::{elapsed, lock_environment, ExitStatus, SharedState};
 use crate::printer::Printer;
 
 /// Install packages into the current environment.
@@ -43,9 +52,11 @@ pub(crate) async fn pip_install(
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
     build_constraints: &[RequirementsSource],
+    src: Option<&Path>,
     constraints_from_workspace: Vec<Requirement>,
     overrides_from_workspace: Vec<Requirement>,
     extras: &ExtrasSpecification,
+    groups: &[GroupName],
     resolution_mode: ResolutionMode,
     prerelease_mode: PrereleaseMode,
     dependency_mode: DependencyMode,
@@ -57,15 +68,28 @@ pub(crate) async fn pip_install(
     link_mode: LinkMode,
     compile: bool,
     hash_checking: Option<HashCheckingMode>,
+    index_hash_policy: &IndexHashPolicies,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
     config_settings: &ConfigSettings,
+    package_build_env: &PackageBuildEnv,
     no_build_isolation: bool,
     no_build_isolation_package: Vec<PackageName>,
     build_options: BuildOptions,
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
+    macos_arch: MacosArch,
+    abi_check: AbiCheck,
+    file_conflicts: FileConflicts,
+    lazy_install: &[PackageName],
+    exclude_package_data: &[ExcludePattern],
+    max_download_size: Option<u64>,
+    break_on_downgrade: bool,
+    verify_imports: bool,
+    ignore_requires_python: bool,
+    requires_python_override: Option<VersionSpecifiers>,
     strict: bool,
+    strict_extras: bool,
     exclude_newer: Option<ExcludeNewer>,
     sources: SourceStrategy,
     python: Option<String>,
@@ -78,6 +102,8 @@ pub(crate) async fn pip_install(
     preview: PreviewMode,
     cache: Cache,
     dry_run: bool,
+    summary: InstallSummary,
+    show_sizes: bool,
     printer: Printer,
 ) -> anyhow::Result<ExitStatus> {
     let start = std::time::Instant::now();
@@ -91,6 +117,7 @@ pub(crate) async fn pip_install(
     let RequirementsSpecification {
         project,
         requirements,
+        git_editables,
         constraints,
         overrides,
         source_trees,
@@ -106,10 +133,20 @@ pub(crate) async fn pip_install(
         constraints,
         overrides,
         extras,
+        groups,
         &client_builder,
     )
     .await?;
 
+    // Track the requested packages, to distinguish them from their dependencies in the summary.
+    let direct = requirements
+        .iter()
+        .filter_map(|entry| match &entry.requirement {
+            UnresolvedRequirement::Named(requirement) => Some(requirement.name.clone()),
+            UnresolvedRequirement::Unnamed(_) => None,
+        })
+        .collect::<Vec<_>>();
+
     // Read build constraints.
     let build_constraints =
         operations::read_constraints(build_constraints, &client_builder).await?;
@@ -183,9 +220,30 @@ pub(crate) async fn pip_install(
         }
     }
 
-    let _lock = environment.lock()?;
+    let _lock = lock_environment(&environment).await?;
+
+    // Check out any editable Git requirements, to install them from the local checkouts.
+    let requirements = {
+        let editables = operations::checkout_git_editables(
+            git_editables,
+            src,
+            &environment,
+            &upgrade,
+            &client_builder,
+            &cache,
+        )
+        .await?;
+        requirements
+            .into_iter()
+            .chain(editables)
+            .collect::<Vec<_>>()
+    };
 
-    // Determine the set of installed packages.
+    // Roll back any incomplete changes from an interrupted installation, and determine the set of
+    // installed packages.
+    if !dry_run {
+        operations::recover(&environment)?;
+    }
     let site_packages = SitePackages::from_environment(&environment)?;
 
     // Check if the current environment satisfies the requirements.
@@ -238,10 +296,23 @@ pub(crate) async fn pip_install(
         PythonRequirement::from_interpreter(interpreter)
     };
 
+    // Relax the `Requires-Python` check, if requested.
+    let python_requirement = if ignore_requires_python {
+        python_requirement.ignore_requires_python()
+    } else if let Some(requires_python_override) = requires_python_override.as_ref() {
+        python_requirement.with_requires_python_override(requires_python_override)?
+    } else {
+        python_requirement
+    };
+
     // Determine the environment for the resolution.
-    let (tags, markers) = resolution_environment(python_version, python_platform, interpreter)?;
+    let (tags, markers) =
+        resolution_environment(python_version, python_platform, macos_arch, interpreter)?;
 
     // Collect the set of required hashes.
+    // A hash-checking policy for any index implies `--verify-hashes` for the remaining packages.
+    let hash_checking = hash_checking
+        .or_else(|| (!index_hash_policy.is_empty()).then_some(HashCheckingMode::Verify));
     let hasher = if let Some(hash_checking) = hash_checking {
         HashStrategy::from_requirements(
             requirements
@@ -250,9 +321,17 @@ pub(crate) async fn pip_install(
                 .map(|entry| (&entry.requirement, entry.hashes.as_slice())),
             Some(&markers),
             hash_checking,
+            index_hash_policy,
         )?
     } else {
-        HashStrategy::None
+        // Even without hash-checking, verify any hashes embedded in direct URLs.
+        HashStrategy::from_url_fragments(
+            requirements
+                .iter()
+                .chain(overrides.iter())
+                .map(|entry| &entry.requirement),
+            Some(&markers),
+        )?
     };
 
     // When resolving, don't take any external preferences into account.
@@ -322,7 +401,8 @@ pub(crate) async fn pip_install(
         sources,
         concurrency,
         preview,
-    );
+    )
+    .with_package_build_env(package_build_env.clone());
 
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
@@ -336,6 +416,7 @@ pub(crate) async fn pip_install(
     let resolution = match operations::resolve(
         requirements,
         constraints,
+        operations::UserConstraints::get(),
         overrides,
         dev,
         source_trees,
@@ -355,6 +436,7 @@ pub(crate) async fn pip_install(
         &build_dispatch,
         concurrency,
         options,
+        None,
         Box::new(DefaultResolveLogger),
         printer,
         preview,
@@ -365,11 +447,16 @@ pub(crate) async fn pip_install(
         Err(operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
             let report = miette::Report::msg(format!("{err}")).context(err.header());
             eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            return Ok(ExitStatus::NoSolution);
         }
         Err(err) => return Err(err.into()),
     };
 
+    // If requested, fail on any extras that aren't provided by the resolved packages.
+    if strict_extras {
+        operations::validate_extras(resolution.diagnostics())?;
+    }
+
     // Sync the environment.
     operations::install(
         &resolution,
@@ -379,22 +466,38 @@ pub(crate) async fn pip_install(
         &build_options,
         link_mode,
         compile,
+        verify_imports,
         &index_locations,
         &hasher,
         &tags,
+        abi_check,
+        file_conflicts,
+        lazy_install,
+        exclude_package_data,
+        max_download_size,
+        break_on_downgrade,
         &client,
         &state.in_flight,
         concurrency,
         &build_dispatch,
         &cache,
         &environment,
-        Box::new(DefaultInstallLogger),
+        Box::new(DefaultInstallLogger::new(summary, direct).with_sizes(show_sizes)),
         dry_run,
         printer,
         preview,
     )
     .await?;
 
+    // Make it clear that the environment may be missing dependencies of the installed packages.
+    if !dependency_mode.is_transitive() {
+        writeln!(
+            printer.stderr(),
+            "Dependencies were excluded due to `--no-deps`; run `{}` to find any that are missing",
+            "uv pip check".green()
+        )?;
+    }
+
     // Notify the user of any resolution diagnostics.
     operations::diagnose_resolution(resolution.diagnostics(), printer)?;
 
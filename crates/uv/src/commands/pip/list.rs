@@ -13,12 +13,12 @@ use uv_cache::Cache;
 use uv_cli::ListFormat;
 use uv_configuration::PreviewMode;
 use uv_fs::Simplified;
-use uv_installer::SitePackages;
+use uv_installer::{InstalledSize, SitePackages};
 use uv_normalize::PackageName;
 use uv_python::PythonRequest;
 use uv_python::{EnvironmentPreference, PythonEnvironment};
 
-use crate::commands::ExitStatus;
+use crate::commands::{format_bytes, ExitStatus};
 use crate::printer::Printer;
 
 /// Enumerate the installed packages in the current environment.
@@ -27,6 +27,7 @@ pub(crate) fn pip_list(
     editable: Option<bool>,
     exclude: &[PackageName],
     format: &ListFormat,
+    sizes: bool,
     strict: bool,
     python: Option<&str>,
     system: bool,
@@ -58,9 +59,23 @@ pub(crate) fn pip_list(
         .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
         .collect_vec();
 
+    // Measure each package from its `RECORD`, if requested.
+    let sizes = if sizes {
+        results
+            .iter()
+            .map(|dist| uv_installer::installed_size(dist).map(Some))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        vec![None; results.len()]
+    };
+
     match format {
         ListFormat::Json => {
-            let rows = results.iter().copied().map(Entry::from).collect_vec();
+            let rows = results
+                .iter()
+                .zip(&sizes)
+                .map(|(dist, size)| Entry::new(dist, size.flatten()))
+                .collect_vec();
             let output = serde_json::to_string(&rows)?;
             writeln!(printer.stdout(), "{output}")?;
         }
@@ -102,6 +117,32 @@ pub(crate) fn pip_list(
                 });
             }
 
+            // Size columns are only displayed if requested.
+            if sizes.iter().any(Option::is_some) {
+                columns.push(Column {
+                    header: String::from("Size"),
+                    rows: sizes
+                        .iter()
+                        .map(|size| {
+                            size.flatten()
+                                .map(|size| format_bytes(size.bytes))
+                                .unwrap_or_default()
+                        })
+                        .collect_vec(),
+                });
+                columns.push(Column {
+                    header: String::from("Files"),
+                    rows: sizes
+                        .iter()
+                        .map(|size| {
+                            size.flatten()
+                                .map(|size| size.files.to_string())
+                                .unwrap_or_default()
+                        })
+                        .collect_vec(),
+                });
+            }
+
             for elems in MultiZip(columns.iter().map(Column::fmt).collect_vec()) {
                 writeln!(printer.stdout(), "{}", elems.join(" ").trim_end())?;
             }
@@ -142,16 +183,22 @@ struct Entry {
     version: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     editable_project_location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<usize>,
 }
 
-impl From<&InstalledDist> for Entry {
-    fn from(dist: &InstalledDist) -> Self {
+impl Entry {
+    fn new(dist: &InstalledDist, size: Option<InstalledSize>) -> Self {
         Self {
             name: dist.name().to_string(),
             version: dist.version().to_string(),
             editable_project_location: dist
                 .as_editable()
                 .map(|url| url.to_file_path().unwrap().simplified_display().to_string()),
+            size: size.map(|size| size.bytes),
+            files: size.map(|size| size.files),
         }
     }
 }
@@ -3,10 +3,17 @@ use std::fmt::Write;
 
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
 
-use distribution_types::{CachedDist, InstalledDist, InstalledMetadata, LocalDist, Name};
+use distribution_types::{
+    CachedDist, InstalledDist, InstalledMetadata, InstalledVersion, LocalDist, Name,
+};
+use pep440_rs::Version;
+use uv_cli::InstallSummary;
+use uv_installer::InstalledSize;
+use uv_normalize::PackageName;
 
-use crate::commands::{elapsed, ChangeEvent, ChangeEventKind};
+use crate::commands::{elapsed, format_bytes, ChangeEvent, ChangeEventKind};
 use crate::printer::Printer;
 
 /// A trait to handle logging during install operations.
@@ -36,11 +43,192 @@ pub(crate) trait InstallLogger {
         uninstalled: Vec<InstalledDist>,
         printer: Printer,
     ) -> fmt::Result;
+
+    /// Returns `true` if the logger reports the size of each installed package.
+    fn show_sizes(&self) -> bool {
+        false
+    }
+
+    /// Log the download size, installed size, and file count of each installed package.
+    fn on_sizes(&self, _sizes: &[PackageSize], _printer: Printer) -> fmt::Result {
+        Ok(())
+    }
+}
+
+/// The download and installed size of a package added to an environment.
+#[derive(Debug, Clone)]
+pub(crate) struct PackageSize {
+    pub(crate) name: PackageName,
+    pub(crate) version: Version,
+    /// The size of the distribution as reported by the index, if known.
+    pub(crate) download: Option<u64>,
+    /// The files installed into the environment, if known.
+    pub(crate) installed: Option<InstalledSize>,
 }
 
 /// The default logger for install operations.
-#[derive(Debug, Default, Clone, Copy)]
-pub(crate) struct DefaultInstallLogger;
+#[derive(Debug, Default, Clone)]
+pub(crate) struct DefaultInstallLogger {
+    /// The level of detail with which to summarize the changes to the environment.
+    summary: InstallSummary,
+    /// The packages that were requested directly, as opposed to transitive dependencies.
+    direct: FxHashSet<PackageName>,
+    /// Whether to report the size of each installed package.
+    sizes: bool,
+}
+
+impl DefaultInstallLogger {
+    /// The number of packages to list in each group of a minimal summary, unless `--verbose` is
+    /// provided.
+    const MINIMAL_LIMIT: usize = 10;
+
+    /// Create a logger that summarizes the changes to the environment at the given level of
+    /// detail, distinguishing the given direct dependencies from transitive dependencies.
+    pub(crate) fn new(
+        summary: InstallSummary,
+        direct: impl IntoIterator<Item = PackageName>,
+    ) -> Self {
+        Self {
+            summary,
+            direct: direct.into_iter().collect(),
+            sizes: false,
+        }
+    }
+
+    /// Report the download size, installed size, and file count of each installed package.
+    #[must_use]
+    pub(crate) fn with_sizes(mut self, sizes: bool) -> Self {
+        self.sizes = sizes;
+        self
+    }
+
+    /// Write a summary of the changes to the environment, grouped into added, changed, and
+    /// removed packages, with direct dependencies listed ahead of transitive dependencies.
+    fn write_minimal(
+        &self,
+        installed: Vec<CachedDist>,
+        reinstalled: Vec<InstalledDist>,
+        uninstalled: Vec<InstalledDist>,
+        printer: Printer,
+    ) -> fmt::Result {
+        let mut removed = uninstalled
+            .into_iter()
+            .chain(reinstalled)
+            .map(LocalDist::from)
+            .collect::<Vec<_>>();
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for dist in installed.into_iter().map(LocalDist::from) {
+            if let Some(index) = removed.iter().position(|prev| prev.name() == dist.name()) {
+                changed.push((removed.swap_remove(index), dist));
+            } else {
+                added.push(dist);
+            }
+        }
+
+        // List direct dependencies first, then transitive dependencies, each sorted by name.
+        added.sort_unstable_by(|a, b| {
+            self.is_transitive(a.name())
+                .cmp(&self.is_transitive(b.name()))
+                .then_with(|| a.name().cmp(b.name()))
+        });
+        changed.sort_unstable_by(|(a, _), (b, _)| {
+            self.is_transitive(a.name())
+                .cmp(&self.is_transitive(b.name()))
+                .then_with(|| a.name().cmp(b.name()))
+        });
+        removed.sort_unstable_by(|a, b| a.name().cmp(b.name()));
+
+        let limit = if printer == Printer::Verbose {
+            usize::MAX
+        } else {
+            Self::MINIMAL_LIMIT
+        };
+
+        if !added.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}",
+                self.heading("Added", added.iter().map(Name::name))
+            )?;
+            for dist in added.iter().take(limit) {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{}",
+                    "+".green(),
+                    dist.name().bold(),
+                    dist.installed_version().dimmed()
+                )?;
+            }
+            write_truncated(added.len(), limit, printer)?;
+        }
+
+        if !changed.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}",
+                self.heading("Changed", changed.iter().map(|(dist, _)| dist.name()))
+            )?;
+            for (prev, dist) in changed.iter().take(limit) {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{} {} {}",
+                    "~".yellow(),
+                    dist.name().bold(),
+                    prev.installed_version().dimmed(),
+                    "->".dimmed(),
+                    version(&dist.installed_version()).dimmed()
+                )?;
+            }
+            write_truncated(changed.len(), limit, printer)?;
+        }
+
+        if !removed.is_empty() {
+            writeln!(
+                printer.stderr(),
+                "{}",
+                self.heading("Removed", removed.iter().map(Name::name))
+            )?;
+            for dist in removed.iter().take(limit) {
+                writeln!(
+                    printer.stderr(),
+                    " {} {}{}",
+                    "-".red(),
+                    dist.name().bold(),
+                    dist.installed_version().dimmed()
+                )?;
+            }
+            write_truncated(removed.len(), limit, printer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the package is known to be a transitive dependency.
+    fn is_transitive(&self, name: &PackageName) -> bool {
+        !self.direct.is_empty() && !self.direct.contains(name)
+    }
+
+    /// Format the heading for a group of packages, e.g., `Added 3 packages (1 direct, 2 transitive):`.
+    fn heading<'a>(&self, label: &str, names: impl Iterator<Item = &'a PackageName>) -> String {
+        let names = names.collect::<Vec<_>>();
+        let count = names.len();
+        let s = if count == 1 { "" } else { "s" };
+        if self.direct.is_empty() {
+            format!("{label} {}:", format!("{count} package{s}").bold())
+        } else {
+            let direct = names
+                .iter()
+                .filter(|name| self.direct.contains(**name))
+                .count();
+            format!(
+                "{label} {} {}:",
+                format!("{count} package{s}").bold(),
+                format!("({direct} direct, {} transitive)", count - direct).dimmed()
+            )
+        }
+    }
+}
 
 impl InstallLogger for DefaultInstallLogger {
     fn on_audit(&self, count: usize, start: std::time::Instant, printer: Printer) -> fmt::Result {
@@ -111,6 +299,14 @@ impl InstallLogger for DefaultInstallLogger {
         uninstalled: Vec<InstalledDist>,
         printer: Printer,
     ) -> fmt::Result {
+        match self.summary {
+            InstallSummary::Detailed => {}
+            InstallSummary::Minimal => {
+                return self.write_minimal(installed, reinstalled, uninstalled, printer);
+            }
+            InstallSummary::None => return Ok(()),
+        }
+
         for event in uninstalled
             .into_iter()
             .chain(reinstalled)
@@ -153,6 +349,78 @@ impl InstallLogger for DefaultInstallLogger {
         }
         Ok(())
     }
+
+    fn show_sizes(&self) -> bool {
+        self.sizes
+    }
+
+    fn on_sizes(&self, sizes: &[PackageSize], printer: Printer) -> fmt::Result {
+        if sizes.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(printer.stderr(), "{}", "Package sizes:".bold())?;
+        let mut total_download = 0;
+        let mut total_installed = InstalledSize::default();
+        for size in sizes {
+            total_download += size.download.unwrap_or_default();
+            total_installed = total_installed + size.installed.unwrap_or_default();
+            writeln!(
+                printer.stderr(),
+                " {}{} {}",
+                size.name.bold(),
+                format!("=={}", size.version).dimmed(),
+                format_size(size.download, size.installed).dimmed()
+            )?;
+        }
+        writeln!(
+            printer.stderr(),
+            "{} {}",
+            "Total:".bold(),
+            format_size(Some(total_download), Some(total_installed)).dimmed()
+        )?;
+        Ok(())
+    }
+}
+
+/// Format the sizes of a package, e.g., `(1.2MiB download, 4.5MiB installed, 120 files)`.
+fn format_size(download: Option<u64>, installed: Option<InstalledSize>) -> String {
+    let mut parts = Vec::new();
+    if let Some(download) = download {
+        parts.push(format!("{} download", format_bytes(download)));
+    }
+    if let Some(InstalledSize { files, bytes }) = installed {
+        parts.push(format!("{} installed", format_bytes(bytes)));
+        parts.push(format!("{files} file{}", if files == 1 { "" } else { "s" }));
+    }
+    if parts.is_empty() {
+        String::from("(unknown size)")
+    } else {
+        format!("({})", parts.join(", "))
+    }
+}
+
+/// Write a note on the number of packages omitted from a group, if any.
+fn write_truncated(count: usize, limit: usize, printer: Printer) -> fmt::Result {
+    if count > limit {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                " ... and {} more (use `--verbose` to list all)",
+                count - limit
+            )
+            .dimmed()
+        )?;
+    }
+    Ok(())
+}
+
+/// Return the version of an installed distribution, omitting any URL.
+fn version<'a>(installed: &InstalledVersion<'a>) -> &'a Version {
+    match installed {
+        InstalledVersion::Version(version) | InstalledVersion::Url(_, version) => version,
+    }
 }
 
 /// A logger that only shows installs and uninstalls, the minimal logging necessary to understand
@@ -1,8 +1,8 @@
 use std::borrow::Cow;
 
 use pep508_rs::MarkerEnvironment;
-use platform_tags::{Tags, TagsError};
-use uv_configuration::TargetTriple;
+use platform_tags::{compatible_tags, CpuFeatures, Tags};
+use uv_configuration::{MacosArch, TargetTriple};
 use uv_python::{Interpreter, PythonVersion};
 
 pub(crate) mod check;
@@ -21,32 +21,45 @@ pub(crate) mod uninstall;
 pub(crate) fn resolution_environment(
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
+    macos_arch: MacosArch,
     interpreter: &Interpreter,
-) -> Result<(Cow<'_, Tags>, Cow<'_, MarkerEnvironment>), TagsError> {
-    let tags = match (python_platform, python_version.as_ref()) {
-        (Some(python_platform), Some(python_version)) => Cow::Owned(Tags::from_env(
-            &python_platform.platform(),
-            (python_version.major(), python_version.minor()),
-            interpreter.implementation_name(),
-            interpreter.implementation_tuple(),
-            interpreter.gil_disabled(),
-        )?),
-        (Some(python_platform), None) => Cow::Owned(Tags::from_env(
-            &python_platform.platform(),
-            interpreter.python_tuple(),
-            interpreter.implementation_name(),
-            interpreter.implementation_tuple(),
-            interpreter.gil_disabled(),
-        )?),
-        (None, Some(python_version)) => Cow::Owned(Tags::from_env(
-            interpreter.platform(),
-            (python_version.major(), python_version.minor()),
-            interpreter.implementation_name(),
-            interpreter.implementation_tuple(),
-            interpreter.gil_disabled(),
-        )?),
-        (None, None) => Cow::Borrowed(interpreter.tags()?),
-    };
+) -> anyhow::Result<(Cow<'_, Tags>, Cow<'_, MarkerEnvironment>)> {
+    let python_platform = macos_arch.python_platform(python_platform);
+
+    // Unless targeting another platform, ensure that the interpreter can load the selected wheels.
+    if python_platform.is_none() {
+        if let Err(err) = macos_arch.check(interpreter.platform()) {
+            anyhow::bail!("{err}");
+        }
+    }
+
+    let tags =
+        if python_platform.is_none() && python_version.is_none() && macos_arch == MacosArch::Native
+        {
+            Cow::Borrowed(interpreter.tags()?)
+        } else {
+            // The host's CPU features are irrelevant to other platforms, unless provided explicitly.
+            let cpu_features = if python_platform.is_some() {
+                CpuFeatures::from_env().unwrap_or_default()
+            } else {
+                CpuFeatures::host()
+            };
+            let platform = python_platform.map(|python_platform| python_platform.platform());
+            let platform = platform.as_ref().unwrap_or(interpreter.platform());
+            Cow::Owned(
+                Tags::from_platform_tags(
+                    macos_arch.platform_tags(platform, compatible_tags(platform)?),
+                    python_version.as_ref().map_or_else(
+                        || interpreter.python_tuple(),
+                        |python_version| (python_version.major(), python_version.minor()),
+                    ),
+                    interpreter.implementation_name(),
+                    interpreter.implementation_tuple(),
+                    interpreter.gil_disabled(),
+                )?
+                .with_cpu_features(cpu_features),
+            )
+        };
 
     // Apply the platform tags to the markers.
     let markers = match (python_platform, python_version) {
@@ -3,53 +3,108 @@
 use anyhow::{anyhow, Context};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashSet;
 use std::fmt::Write;
-use std::path::PathBuf;
-use tracing::debug;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use tracing::{debug, info};
 
 use distribution_types::{
     CachedDist, Diagnostic, InstalledDist, ResolutionDiagnostic, UnresolvedRequirementSpecification,
 };
 use distribution_types::{
-    DistributionMetadata, IndexLocations, InstalledMetadata, LocalDist, Name, Resolution,
+    Dist, DistributionMetadata, IndexLocations, InstalledMetadata, LocalDist, Name, RemoteSource,
+    Resolution, VersionOrUrlRef,
 };
 use install_wheel_rs::linker::LinkMode;
-use platform_tags::Tags;
+use install_wheel_rs::ExcludePattern;
+use platform_tags::{CpuFeatures, Tags};
 use pypi_types::Requirement;
 use uv_cache::Cache;
 use uv_client::{BaseClientBuilder, RegistryClient};
 use uv_configuration::{
-    BuildOptions, Concurrency, Constraints, ExtrasSpecification, Overrides, PreviewMode, Reinstall,
-    Upgrade,
+    AbiCheck, BuildOptions, Concurrency, Constraints, ExtrasSpecification, FileConflicts,
+    Overrides, PreviewMode, Reinstall, Upgrade,
 };
 use uv_dispatch::BuildDispatch;
 use uv_distribution::DistributionDatabase;
-use uv_fs::Simplified;
-use uv_installer::{Plan, Planner, Preparer, SitePackages};
+use uv_fs::{Simplified, CWD};
+use uv_installer::{FsyncPolicy, Journal, Plan, Planner, Preparer, SitePackages};
 use uv_normalize::{GroupName, PackageName};
 use uv_python::PythonEnvironment;
 use uv_requirements::{
-    LookaheadResolver, NamedRequirementsResolver, RequirementsSource, RequirementsSpecification,
-    SourceTreeResolver,
+    DependencyGroups, EditableCheckouts, LookaheadResolver, NamedRequirementsResolver,
+    RequirementsSource, RequirementsSpecification, SourceTreeResolver,
 };
 use uv_resolver::{
     DependencyMode, Exclusions, FlatIndex, InMemoryIndex, Manifest, Options, Preference,
-    Preferences, PythonRequirement, ResolutionGraph, Resolver, ResolverMarkers,
+    Preferences, PythonRequirement, ResolutionGraph, Resolver, ResolverMarkers, ResolverTrace,
 };
 use uv_types::{HashStrategy, InFlight, InstalledPackagesProvider};
 use uv_warnings::warn_user;
 
-use crate::commands::pip::loggers::{InstallLogger, ResolveLogger};
+use crate::commands::pip::loggers::{InstallLogger, PackageSize, ResolveLogger};
 use crate::commands::reporters::{InstallReporter, PrepareReporter, ResolverReporter};
-use crate::commands::{compile_bytecode, elapsed, ChangeEvent, ChangeEventKind, DryRunEvent};
+use crate::commands::{
+    compile_bytecode, elapsed, format_bytes, ChangeEvent, ChangeEventKind, DryRunEvent,
+};
+use crate::logging::LOG_FILE_TARGET;
 use crate::printer::Printer;
 
+/// The user-level constraints, applied to resolutions that aren't persisted to a lockfile.
+static USER_CONSTRAINTS: OnceLock<UserConstraints> = OnceLock::new();
+
+/// The constraints read from the user-level constraints file (e.g.,
+/// `~/.config/uv/constraints.txt`).
+#[derive(Debug)]
+pub(crate) struct UserConstraints {
+    path: PathBuf,
+    constraints: Vec<Requirement>,
+}
+
+impl UserConstraints {
+    /// Read the user-level constraints from the given `constraints.txt` file.
+    pub(crate) async fn read(
+        path: PathBuf,
+        client_builder: &BaseClientBuilder<'_>,
+    ) -> Result<Self, Error> {
+        let spec = RequirementsSpecification::from_sources(
+            &[],
+            &[RequirementsSource::from_constraints_txt(path.clone())],
+            &[],
+            client_builder,
+        )
+        .await?;
+        Ok(Self {
+            path,
+            constraints: spec.constraints,
+        })
+    }
+
+    /// Make the constraints available to all subsequent resolutions, via [`UserConstraints::get`].
+    pub(crate) fn install(self) {
+        debug!(
+            "Loaded {} user-level constraint(s) from: `{}`",
+            self.constraints.len(),
+            self.path.user_display()
+        );
+        let _ = USER_CONSTRAINTS.set(self);
+    }
+
+    /// Return the user-level constraints, if any were loaded.
+    pub(crate) fn get() -> Option<&'static Self> {
+        USER_CONSTRAINTS.get()
+    }
+}
+
 /// Consolidate the requirements for an installation.
 pub(crate) async fn read_requirements(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
     extras: &ExtrasSpecification,
+    groups: &[GroupName],
     client_builder: &BaseClientBuilder<'_>,
 ) -> Result<RequirementsSpecification, Error> {
     // If the user requests `extras` but does not provide a valid source (e.g., a `pyproject.toml`),
@@ -61,14 +116,82 @@ pub(crate) async fn read_requirements(
         .into());
     }
 
+    // Read the requirements from any requested dependency groups.
+    let group_requirements = read_dependency_groups(requirements, groups)?;
+
     // Read all requirements from the provided sources.
-    Ok(RequirementsSpecification::from_sources(
+    let mut spec = RequirementsSpecification::from_sources(
         requirements,
         constraints,
         overrides,
         client_builder,
     )
-    .await?)
+    .await?;
+    spec.requirements.extend(group_requirements);
+    Ok(spec)
+}
+
+/// Read the requirements of the given dependency groups from the `pyproject.toml` files among the
+/// provided sources.
+///
+/// Returns an error if there are no `pyproject.toml` sources, or if any of the groups isn't
+/// declared by at least one of them.
+pub(crate) fn read_dependency_groups(
+    requirements: &[RequirementsSource],
+    groups: &[GroupName],
+) -> Result<Vec<UnresolvedRequirementSpecification>, Error> {
+    if groups.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Dependency groups can only be read from a `pyproject.toml`.
+    let dependency_groups = requirements
+        .iter()
+        .filter_map(|source| match source {
+            RequirementsSource::PyprojectToml(path) => Some(path.as_path()),
+            _ => None,
+        })
+        .map(DependencyGroups::from_pyproject_toml)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if dependency_groups.is_empty() {
+        return Err(
+            anyhow!("Requesting dependency groups requires a `pyproject.toml` file.").into(),
+        );
+    }
+
+    let mut unused_groups = groups
+        .iter()
+        .filter(|group| {
+            !dependency_groups
+                .iter()
+                .any(|dependency_groups| dependency_groups.contains(group))
+        })
+        .collect::<Vec<_>>();
+    if !unused_groups.is_empty() {
+        unused_groups.sort_unstable();
+        unused_groups.dedup();
+        let s = if unused_groups.len() == 1 { "" } else { "s" };
+        return Err(anyhow!(
+            "Requested dependency group{s} not found: {}",
+            unused_groups.iter().join(", ")
+        )
+        .into());
+    }
+
+    let mut requirements = Vec::new();
+    for dependency_groups in &dependency_groups {
+        for group in groups {
+            if dependency_groups.contains(group) {
+                requirements.extend(
+                    dependency_groups
+                        .requirements(group)?
+                        .into_iter()
+                        .map(UnresolvedRequirementSpecification::from),
+                );
+            }
+        }
+    }
+    Ok(requirements)
 }
 
 /// Resolve a set of constraints.
@@ -83,10 +206,47 @@ pub(crate) async fn read_constraints(
     )
 }
 
+/// Check out any editable Git requirements, returning the equivalent editable requirements on
+/// the local checkouts.
+///
+/// By default, the checkouts are created in `src` within the environment if it's a virtual
+/// environment, and in `src` within the current directory otherwise (matching pip).
+pub(crate) async fn checkout_git_editables(
+    git_editables: Vec<UnresolvedRequirementSpecification>,
+    src: Option<&Path>,
+    environment: &PythonEnvironment,
+    upgrade: &Upgrade,
+    client_builder: &BaseClientBuilder<'_>,
+    cache: &Cache,
+) -> Result<Vec<UnresolvedRequirementSpecification>, Error> {
+    if git_editables.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let src = if let Some(src) = src {
+        CWD.join(src)
+    } else if environment.interpreter().is_virtualenv() {
+        environment.root().join("src")
+    } else {
+        CWD.join("src")
+    };
+    debug!(
+        "Checking out editable Git requirements into: {}",
+        src.user_display()
+    );
+
+    let client = client_builder.build();
+    let editables = EditableCheckouts::new(src, upgrade, &client, cache)
+        .checkout(git_editables)
+        .await?;
+    Ok(editables)
+}
+
 /// Resolve a set of requirements, similar to running `pip compile`.
 pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
     requirements: Vec<UnresolvedRequirementSpecification>,
     constraints: Vec<Requirement>,
+    user_constraints: Option<&UserConstraints>,
     overrides: Vec<UnresolvedRequirementSpecification>,
     dev: Vec<GroupName>,
     source_trees: Vec<PathBuf>,
@@ -106,6 +266,7 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
     build_dispatch: &BuildDispatch<'_>,
     concurrency: Concurrency,
     options: Options,
+    trace: Option<ResolverTrace>,
     logger: Box<dyn ResolveLogger>,
     printer: Printer,
     preview: PreviewMode,
@@ -191,10 +352,15 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
     .resolve()
     .await?;
 
-    // Collect constraints and overrides.
+    // Collect constraints and overrides, including any user-level constraints.
     let constraints = Constraints::from_requirements(
         constraints
             .into_iter()
+            .chain(
+                user_constraints
+                    .into_iter()
+                    .flat_map(|user_constraints| user_constraints.constraints.iter().cloned()),
+            )
             .chain(upgrade.constraints().cloned()),
     );
     let overrides = Overrides::from_requirements(overrides);
@@ -259,9 +425,34 @@ pub(crate) async fn resolve<InstalledPackages: InstalledPackagesProvider>(
         )?
         .with_reporter(reporter);
 
-        resolver.resolve().await?
+        let resolver = if let Some(trace) = trace {
+            resolver.with_trace(trace)
+        } else {
+            resolver
+        };
+
+        match resolver.resolve().await {
+            Ok(resolution) => resolution,
+            Err(uv_resolver::ResolveError::NoSolution(err)) => {
+                // Surface the user-level constraints, which aren't otherwise visible in the report.
+                let err = if let Some(user_constraints) = user_constraints {
+                    err.with_user_constraints(user_constraints.path.clone())
+                } else {
+                    err
+                };
+                return Err(uv_resolver::ResolveError::NoSolution(err).into());
+            }
+            Err(err) => return Err(err.into()),
+        }
     };
 
+    crate::stats::record_resolution();
+    info!(
+        target: LOG_FILE_TARGET,
+        event = "resolution",
+        packages = resolution.len(),
+        duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+    );
     logger.on_complete(resolution.len(), start, printer)?;
 
     Ok(resolution)
@@ -291,9 +482,16 @@ pub(crate) async fn install(
     build_options: &BuildOptions,
     link_mode: LinkMode,
     compile: bool,
+    verify_imports: bool,
     index_urls: &IndexLocations,
     hasher: &HashStrategy,
     tags: &Tags,
+    abi_check: AbiCheck,
+    file_conflicts: FileConflicts,
+    lazy_install: &[PackageName],
+    exclude_package_data: &[ExcludePattern],
+    max_download_size: Option<u64>,
+    break_on_downgrade: bool,
     client: &RegistryClient,
     in_flight: &InFlight,
     concurrency: Concurrency,
@@ -307,12 +505,43 @@ pub(crate) async fn install(
 ) -> Result<(), Error> {
     let start = std::time::Instant::now();
 
+    if link_mode == LinkMode::Farm && preview.is_disabled() {
+        return Err(anyhow!(
+            "`--link-mode=farm` is experimental and requires preview mode (`--preview`)"
+        )
+        .into());
+    }
+
+    // Files in lazily-installed packages are only materialized on first access, so they can't be
+    // excluded at installation time.
+    if !lazy_install.is_empty() && !exclude_package_data.is_empty() {
+        warn_user!(
+            "`--exclude-package-data` doesn't apply to packages installed with `--lazy-install`; their matching files will be retained"
+        );
+    }
+
+    // Roll back any changes left incomplete by a previous, interrupted installation, such that
+    // the plan reflects the packages that are fully installed.
+    let site_packages = if !dry_run && recover(venv)? {
+        SitePackages::from_environment(venv)?
+    } else {
+        site_packages
+    };
+
     // Extract the requirements from the resolution.
     let requirements = resolution.requirements().collect::<Vec<_>>();
 
+    // Retain the installed distributions, to check the new distributions for conflicting files.
+    let installed = if file_conflicts == FileConflicts::Ignore {
+        vec![]
+    } else {
+        site_packages.iter().cloned().collect::<Vec<_>>()
+    };
+
     // Partition into those that should be linked from the cache (`local`), those that need to be
     // downloaded (`remote`), and those that should be removed (`extraneous`).
     let plan = Planner::new(&requirements)
+        .with_resolution(resolution)
         .build(
             site_packages,
             reinstall,
@@ -336,6 +565,16 @@ pub(crate) async fn install(
         extraneous,
     } = plan;
 
+    crate::stats::record_install(cached.len(), remote.len());
+    info!(
+        target: LOG_FILE_TARGET,
+        event = "install-plan",
+        cached = cached.len(),
+        remote = remote.len(),
+        reinstalls = reinstalls.len(),
+        extraneous = extraneous.len(),
+    );
+
     // If we're in `install` mode, ignore any extraneous distributions.
     let extraneous = match modifications {
         Modifications::Sufficient => vec![],
@@ -348,6 +587,15 @@ pub(crate) async fn install(
         return Ok(());
     }
 
+    // Abort before making any changes if an installed package would be downgraded.
+    if break_on_downgrade {
+        check_downgrades(resolution, &reinstalls, printer)?;
+    }
+
+    // Notify any external tooling of the directories that are about to be written.
+    run_pre_install_hook(venv, cache)?;
+    let fsync = fsync_policy()?;
+
     // Map any registry-based requirements back to those returned by the resolver.
     let remote = remote
         .iter()
@@ -359,6 +607,11 @@ pub(crate) async fn install(
         })
         .collect::<Vec<_>>();
 
+    // Enforce the download budget before fetching any distributions.
+    if let Some(max_download_size) = max_download_size {
+        check_download_size(&remote, max_download_size, printer)?;
+    }
+
     // Download, build, and unzip any missing distributions.
     let wheels = if remote.is_empty() {
         vec![]
@@ -384,11 +637,83 @@ pub(crate) async fn install(
         wheels
     };
 
+    // Validate the wheels against the host before modifying the environment.
+    if abi_check != AbiCheck::Ignore {
+        let incompatible = uv_installer::incompatible_wheels(
+            wheels.iter().chain(cached.iter()),
+            venv.interpreter().tags()?,
+            venv.interpreter().platform(),
+            &CpuFeatures::detect(),
+        );
+        match abi_check {
+            AbiCheck::Ignore => {}
+            AbiCheck::Warn => {
+                for wheel in incompatible {
+                    warn_user!("{wheel}");
+                }
+            }
+            AbiCheck::Error => {
+                if let Some(wheel) = incompatible.into_iter().next() {
+                    return Err(wheel.into());
+                }
+            }
+        }
+    }
+
+    // Detect any files that would be provided by multiple distributions.
+    if file_conflicts != FileConflicts::Ignore {
+        // Compare against any distributions that will remain installed, i.e., those that aren't
+        // being replaced or removed.
+        let replaced = wheels
+            .iter()
+            .chain(cached.iter())
+            .map(Name::name)
+            .collect::<FxHashSet<_>>();
+        let remaining = installed.iter().filter(|dist| {
+            !replaced.contains(dist.name())
+                && !reinstalls
+                    .iter()
+                    .chain(extraneous.iter())
+                    .any(|removed| removed.path() == dist.path())
+        });
+        let conflicts =
+            uv_installer::file_conflicts(wheels.iter().chain(cached.iter()), remaining)?;
+        match file_conflicts {
+            FileConflicts::Ignore => {}
+            FileConflicts::Warn => {
+                for conflict in conflicts {
+                    warn_user!("{conflict}");
+                }
+            }
+            FileConflicts::Error => {
+                if let Some(conflict) = conflicts.into_iter().next() {
+                    return Err(conflict.into());
+                }
+            }
+        }
+    }
+
+    // If the environment was marked read-only, restore write access for the duration of the
+    // installation.
+    let write_access = WriteAccess::acquire(venv)?;
+
+    // Record each change to the environment as it's made, such that an interrupted installation
+    // can be rolled back, package-by-package.
+    let journal = Arc::new(Journal::create(venv)?);
+
     // Remove any upgraded or extraneous installations.
     if !extraneous.is_empty() || !reinstalls.is_empty() {
         let start = std::time::Instant::now();
 
+        // Reinstalled packages are only committed once their replacements are installed.
+        let replacements = wheels
+            .iter()
+            .chain(cached.iter())
+            .map(|wheel| wheel.name().clone())
+            .collect::<FxHashSet<_>>();
+
         for dist_info in extraneous.iter().chain(reinstalls.iter()) {
+            journal.uninstall(dist_info)?;
             match uv_installer::uninstall(dist_info).await {
                 Ok(summary) => {
                     debug!(
@@ -410,6 +735,9 @@ pub(crate) async fn install(
                 }
                 Err(err) => return Err(err.into()),
             }
+            if !replacements.contains(dist_info.name()) {
+                journal.commit(dist_info.name())?;
+            }
         }
 
         logger.on_uninstall(extraneous.len() + reinstalls.len(), start, printer)?;
@@ -421,7 +749,10 @@ pub(crate) async fn install(
         let start = std::time::Instant::now();
         wheels = uv_installer::Installer::new(venv)
             .with_link_mode(link_mode)
+            .with_lazy(lazy_install.to_vec())
+            .with_exclude(exclude_package_data.to_vec())
             .with_cache(cache)
+            .with_journal(journal.clone())
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             // This technically can block the runtime, but we are on the main thread and
             // have no other running tasks at this point, so this lets us avoid spawning a blocking
@@ -429,18 +760,345 @@ pub(crate) async fn install(
             .install_blocking(wheels)?;
 
         logger.on_install(wheels.len(), start, printer)?;
+
+        // Finalize the installed files before committing the installation.
+        finalize_installed(&wheels, venv, fsync)?;
     }
 
+    journal.finish()?;
+
     if compile {
         compile_bytecode(venv, cache, printer).await?;
     }
 
+    drop(write_access);
+
+    // Collect the installed packages, to verify their imports once the changes are reported.
+    let installed = if verify_imports {
+        wheels.iter().map(|wheel| wheel.name().clone()).collect()
+    } else {
+        vec![]
+    };
+
+    // Measure the installed packages, if requested, before the changes are reported. The
+    // installation has already been committed at this point, so failing to measure a package is
+    // reported as a warning rather than an error.
+    let sizes = if logger.show_sizes() {
+        package_sizes(&wheels, resolution, venv)
+    } else {
+        vec![]
+    };
+
     // Notify the user of any environment modifications.
     logger.on_complete(wheels, reinstalls, extraneous, printer)?;
+    logger.on_sizes(&sizes, printer)?;
+
+    if !installed.is_empty() {
+        crate::commands::verify_imports(&installed, venv, printer).await?;
+    }
 
     Ok(())
 }
 
+/// Determine the download size, installed size, and file count of each of the given wheels, as
+/// installed into the environment.
+///
+/// Packages that can't be measured are reported with an unknown installed size.
+fn package_sizes(
+    wheels: &[CachedDist],
+    resolution: &Resolution,
+    venv: &PythonEnvironment,
+) -> Vec<PackageSize> {
+    let site_packages = match SitePackages::from_environment(venv) {
+        Ok(site_packages) => Some(site_packages),
+        Err(err) => {
+            warn_user!("Failed to measure the installed packages: {err}");
+            None
+        }
+    };
+    let mut sizes = Vec::with_capacity(wheels.len());
+    for wheel in wheels {
+        let installed = site_packages.as_ref().and_then(|site_packages| {
+            match site_packages.get_packages(wheel.name()).as_slice() {
+                [dist] => match uv_installer::installed_size(dist) {
+                    Ok(installed) => installed,
+                    Err(err) => {
+                        warn_user!("{err}; its installed size is unknown");
+                        None
+                    }
+                },
+                _ => None,
+            }
+        });
+        sizes.push(PackageSize {
+            name: wheel.name().clone(),
+            version: wheel.filename().version.clone(),
+            download: resolution
+                .get_remote(wheel.name())
+                .and_then(RemoteSource::size),
+            installed,
+        });
+    }
+    sizes.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    sizes
+}
+
+/// Roll back any changes left incomplete by a previous, interrupted installation into the given
+/// environment, returning `true` if any packages were removed as a result.
+pub(crate) fn recover(venv: &PythonEnvironment) -> Result<bool, Error> {
+    let removed = Journal::recover(venv)?;
+    if removed.is_empty() {
+        return Ok(false);
+    }
+    warn_user!(
+        "A previous installation into {} was interrupted; removed the incomplete installations of: {}",
+        venv.root().user_display().cyan(),
+        removed.iter().map(|name| format!("`{name}`")).join(", ")
+    );
+    Ok(true)
+}
+
+/// Returns `true` if the `site-packages` directories of the environment are read-only (e.g., as
+/// marked by `--read-only-env`).
+pub(crate) fn is_read_only(venv: &PythonEnvironment) -> bool {
+    venv.site_packages()
+        .any(|site_packages| uv_fs::is_read_only(&*site_packages))
+}
+
+/// Remove (or restore) write access to the `site-packages` directories of the environment,
+/// returning the number of entries whose permissions were changed.
+pub(crate) fn set_read_only(venv: &PythonEnvironment, read_only: bool) -> Result<usize, Error> {
+    let mut changed = 0;
+    for site_packages in venv.site_packages() {
+        match uv_fs::set_read_only(&*site_packages, read_only) {
+            Ok(count) => changed += count,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(changed)
+}
+
+/// Restores write access to a read-only environment for as long as it's held.
+///
+/// The environment is marked read-only again on drop, including when the modification fails
+/// part-way through.
+pub(crate) struct WriteAccess<'a> {
+    venv: Option<&'a PythonEnvironment>,
+}
+
+impl<'a> WriteAccess<'a> {
+    /// Restore write access to the environment, if it was marked read-only.
+    pub(crate) fn acquire(venv: &'a PythonEnvironment) -> Result<Self, Error> {
+        if !is_read_only(venv) {
+            return Ok(Self { venv: None });
+        }
+        debug!(
+            "Restoring write access to read-only environment: {}",
+            venv.root().user_display()
+        );
+        // Hold the guard before restoring write access, such that a partial failure is reverted.
+        let guard = Self { venv: Some(venv) };
+        set_read_only(venv, false)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for WriteAccess<'_> {
+    fn drop(&mut self) {
+        let Some(venv) = self.venv else {
+            return;
+        };
+        if let Err(err) = set_read_only(venv, true) {
+            warn_user!(
+                "Failed to mark the environment at `{}` read-only again: {err}",
+                venv.root().user_display()
+            );
+        }
+    }
+}
+
+/// Run the command provided via `UV_PRE_INSTALL_HOOK`, if any, before modifying an environment.
+///
+/// The hook receives the directories that are about to be written via environment variables,
+/// such that it can, e.g., exclude them from on-access antivirus scanning (like Windows Defender
+/// or XProtect), which can otherwise dominate the time spent writing thousands of small files.
+fn run_pre_install_hook(venv: &PythonEnvironment, cache: &Cache) -> Result<(), Error> {
+    let Some(hook) = std::env::var_os("UV_PRE_INSTALL_HOOK").filter(|hook| !hook.is_empty()) else {
+        return Ok(());
+    };
+    let hook = hook.to_string_lossy().to_string();
+
+    debug!("Running pre-install hook: {hook}");
+    let mut command = if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.arg("/C").arg(&hook);
+        command
+    } else {
+        let mut command = std::process::Command::new("sh");
+        command.arg("-c").arg(&hook);
+        command
+    };
+    let status = command
+        .env("UV_HOOK_ENVIRONMENT", venv.root())
+        .env("UV_HOOK_SITE_PACKAGES", venv.interpreter().purelib())
+        .env("UV_HOOK_CACHE_DIR", cache.root())
+        .env("UV_HOOK_STAGING_DIR", cache.staging_root())
+        .status()?;
+    if !status.success() {
+        return Err(Error::PreInstallHook(hook, status));
+    }
+    Ok(())
+}
+
+/// Read the [`FsyncPolicy`] provided via `UV_INSTALL_FSYNC`, if any.
+fn fsync_policy() -> Result<FsyncPolicy, Error> {
+    match std::env::var("UV_INSTALL_FSYNC") {
+        Ok(policy) => Ok(FsyncPolicy::from_str(&policy).map_err(|err| anyhow!(err))?),
+        Err(_) => Ok(FsyncPolicy::default()),
+    }
+}
+
+/// Apply any post-write policies to the files installed for the given distributions, i.e., the
+/// given [`FsyncPolicy`] and, on macOS, `UV_CLEAR_QUARANTINE`.
+fn finalize_installed(
+    wheels: &[CachedDist],
+    venv: &PythonEnvironment,
+    fsync: FsyncPolicy,
+) -> Result<(), Error> {
+    let clear_quarantine =
+        cfg!(target_os = "macos") && std::env::var_os("UV_CLEAR_QUARANTINE").is_some();
+    if fsync == FsyncPolicy::Never && !clear_quarantine {
+        return Ok(());
+    }
+
+    let site_packages = SitePackages::from_environment(venv)?;
+    let dists = wheels
+        .iter()
+        .flat_map(|wheel| site_packages.get_packages(wheel.name()))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    #[cfg(target_os = "macos")]
+    if clear_quarantine {
+        let cleared = uv_installer::clear_quarantine(&dists)?;
+        debug!("Removed the quarantine attribute from {cleared} installed file(s)");
+    }
+
+    if fsync == FsyncPolicy::Batch {
+        let start = std::time::Instant::now();
+        let flushed = uv_installer::fsync_installed(&dists)?;
+        debug!(
+            "Flushed {flushed} installed file(s) to disk in {}",
+            elapsed(start.elapsed())
+        );
+    }
+
+    Ok(())
+}
+
+/// The number of distributions to include when reporting the largest downloads.
+const LARGEST_DOWNLOADS: usize = 10;
+
+/// Abort if the total size of the distributions to download exceeds the given budget, reporting
+/// the largest distributions.
+fn check_download_size(
+    remote: &[Dist],
+    max_download_size: u64,
+    printer: Printer,
+) -> Result<(), Error> {
+    let mut sizes = remote
+        .iter()
+        .filter_map(|dist| Some((dist, dist.size()?)))
+        .collect::<Vec<_>>();
+    let unknown = remote.len() - sizes.len();
+    if unknown > 0 {
+        debug!(
+            "Unable to determine the download size of {unknown} distribution{}",
+            if unknown == 1 { "" } else { "s" }
+        );
+    }
+
+    let total = sizes.iter().map(|(_, size)| size).sum::<u64>();
+    if total <= max_download_size {
+        debug!(
+            "Total download size ({}) is within the maximum download size ({})",
+            format_bytes(total),
+            format_bytes(max_download_size)
+        );
+        return Ok(());
+    }
+
+    sizes.sort_by(|(a_dist, a_size), (b_dist, b_size)| {
+        b_size
+            .cmp(a_size)
+            .then_with(|| a_dist.name().cmp(b_dist.name()))
+    });
+    writeln!(
+        printer.stderr(),
+        "{}",
+        "Largest distributions to download:".bold()
+    )?;
+    for (dist, size) in sizes.iter().take(LARGEST_DOWNLOADS) {
+        writeln!(
+            printer.stderr(),
+            " {} {}",
+            dist.to_string().bold(),
+            format!("({})", format_bytes(*size)).dimmed()
+        )?;
+    }
+
+    Err(Error::DownloadSizeExceeded {
+        size: format_bytes(total),
+        max_download_size: format_bytes(max_download_size),
+    })
+}
+
+/// Report any installed distributions that would be replaced by a lower version, returning an
+/// error if there are any.
+fn check_downgrades(
+    resolution: &Resolution,
+    reinstalls: &[InstalledDist],
+    printer: Printer,
+) -> Result<(), Error> {
+    let downgrades = reinstalls
+        .iter()
+        .filter_map(|installed| {
+            let dist = resolution.get_remote(installed.name())?;
+            let VersionOrUrlRef::Version(version) = dist.version_or_url() else {
+                return None;
+            };
+            (version < installed.version()).then_some((installed, version))
+        })
+        .sorted_by(|(a, _), (b, _)| a.name().cmp(b.name()))
+        .collect::<Vec<_>>();
+    if downgrades.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        printer.stderr(),
+        "{}",
+        "Packages that would be downgraded:".bold()
+    )?;
+    for (installed, version) in &downgrades {
+        writeln!(
+            printer.stderr(),
+            " {} {} {} {}",
+            installed.name().bold(),
+            installed.version().dimmed(),
+            "->".dimmed(),
+            version.to_string().bold()
+        )?;
+    }
+
+    Err(Error::Downgrade(format!(
+        "{} package{}",
+        downgrades.len(),
+        if downgrades.len() == 1 { "" } else { "s" }
+    )))
+}
+
 /// Report on the results of a dry-run installation.
 fn report_dry_run(
     resolution: &Resolution,
@@ -649,6 +1307,20 @@ pub(crate) fn diagnose_resolution(
     Ok(())
 }
 
+/// Return an error if any of the requested extras aren't provided by the resolved distributions.
+pub(crate) fn validate_extras(diagnostics: &[ResolutionDiagnostic]) -> Result<(), Error> {
+    let missing = diagnostics
+        .iter()
+        .filter(|diagnostic| matches!(diagnostic, ResolutionDiagnostic::MissingExtra { .. }))
+        .map(Diagnostic::message)
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::MissingExtras(missing))
+    }
+}
+
 /// Report any diagnostics on installed distributions in the Python environment.
 pub(crate) fn diagnose_environment(
     resolution: &Resolution,
@@ -682,6 +1354,9 @@ pub(crate) enum Error {
     #[error(transparent)]
     Uninstall(#[from] uv_installer::UninstallError),
 
+    #[error(transparent)]
+    Journal(#[from] uv_installer::JournalError),
+
     #[error(transparent)]
     Hash(#[from] uv_types::HashStrategyError),
 
@@ -702,4 +1377,33 @@ pub(crate) enum Error {
 
     #[error(transparent)]
     PubGrubSpecifier(#[from] uv_resolver::PubGrubSpecifierError),
+
+    #[error(transparent)]
+    Tags(#[from] platform_tags::TagsError),
+
+    #[error(transparent)]
+    IncompatibleWheel(#[from] uv_installer::IncompatibleWheel),
+
+    #[error(transparent)]
+    FileConflict(#[from] uv_installer::FileConflict),
+
+    #[error(transparent)]
+    Flush(#[from] uv_installer::FlushError),
+
+    #[error(
+        "The total download size ({size}) exceeds the maximum download size ({max_download_size})"
+    )]
+    DownloadSizeExceeded {
+        size: String,
+        max_download_size: String,
+    },
+
+    #[error("The installation would downgrade {0}, but `--break-on-downgrade` was provided")]
+    Downgrade(String),
+
+    #[error("The pre-install hook (`{0}`) failed with {1}")]
+    PreInstallHook(String, std::process::ExitStatus),
+
+    #[error("{}, but `--strict-extras` was provided", _0.join("; "))]
+    MissingExtras(Vec<String>),
 }
@@ -1,26 +1,39 @@
+use std::collections::hash_map::Entry;
 use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
 
 use anstream::eprint;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use owo_colors::OwoColorize;
-use pep508_rs::PackageName;
+use pep508_rs::{MarkerEnvironment, PackageName, RequirementOrigin};
+use rustc_hash::FxHashMap;
 use tracing::debug;
 
-use distribution_types::{IndexLocations, Resolution};
+use distribution_types::{
+    IndexHashPolicies, IndexLocations, Resolution, UnresolvedRequirement,
+    UnresolvedRequirementSpecification,
+};
 use install_wheel_rs::linker::LinkMode;
+use install_wheel_rs::ExcludePattern;
+use pep440_rs::{Operator, Version};
+use pypi_types::{Requirement, RequirementSource};
 use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
+use uv_cli::InstallSummary;
 use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
+use uv_configuration::{AbiCheck, FileConflicts, KeyringProviderType, MacosArch, TargetTriple};
 use uv_configuration::{
     BuildOptions, Concurrency, ConfigSettings, ExtrasSpecification, HashCheckingMode,
-    IndexStrategy, PreviewMode, Reinstall, SetupPyStrategy, SourceStrategy, Upgrade,
+    IndexStrategy, PackageBuildEnv, PreviewMode, Reinstall, SetupPyStrategy, SourceStrategy,
+    Upgrade,
 };
-use uv_configuration::{KeyringProviderType, TargetTriple};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Simplified;
 use uv_installer::SitePackages;
 use uv_python::{
-    EnvironmentPreference, Prefix, PythonEnvironment, PythonRequest, PythonVersion, Target,
+    EnvironmentPreference, Interpreter, Prefix, PythonEnvironment, PythonRequest, PythonVersion,
+    Target,
 };
 use uv_requirements::{RequirementsSource, RequirementsSpecification};
 use uv_resolver::{
@@ -32,7 +45,7 @@ use uv_types::{BuildIsolation, HashStrategy};
 use crate::commands::pip::loggers::{DefaultInstallLogger, DefaultResolveLogger};
 use crate::commands::pip::operations::Modifications;
 use crate::commands::pip::{operations, resolution_environment};
-use crate::commands::{ExitStatus, SharedState};
+use crate::commands::{lock_environment, ExitStatus, SharedState};
 use crate::printer::Printer;
 
 /// Install a set of locked requirements into the current Python environment.
@@ -41,10 +54,12 @@ pub(crate) async fn pip_sync(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     build_constraints: &[RequirementsSource],
+    src: Option<&Path>,
     reinstall: Reinstall,
     link_mode: LinkMode,
     compile: bool,
     hash_checking: Option<HashCheckingMode>,
+    index_hash_policy: &IndexHashPolicies,
     index_locations: IndexLocations,
     index_strategy: IndexStrategy,
     keyring_provider: KeyringProviderType,
@@ -52,14 +67,23 @@ pub(crate) async fn pip_sync(
     allow_empty_requirements: bool,
     connectivity: Connectivity,
     config_settings: &ConfigSettings,
+    package_build_env: &PackageBuildEnv,
     no_build_isolation: bool,
     no_build_isolation_package: Vec<PackageName>,
     build_options: BuildOptions,
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
+    macos_arch: MacosArch,
+    abi_check: AbiCheck,
+    file_conflicts: FileConflicts,
+    lazy_install: &[PackageName],
+    exclude_package_data: &[ExcludePattern],
+    max_download_size: Option<u64>,
+    verify_imports: bool,
     strict: bool,
     exclude_newer: Option<ExcludeNewer>,
     python: Option<String>,
+    python_executable: Option<&Path>,
     system: bool,
     break_system_packages: bool,
     target: Option<Target>,
@@ -70,6 +94,8 @@ pub(crate) async fn pip_sync(
     preview: PreviewMode,
     cache: Cache,
     dry_run: bool,
+    summary: InstallSummary,
+    show_sizes: bool,
     printer: Printer,
 ) -> Result<ExitStatus> {
     let client_builder = BaseClientBuilder::new()
@@ -89,6 +115,7 @@ pub(crate) async fn pip_sync(
     let RequirementsSpecification {
         project,
         requirements,
+        git_editables,
         constraints,
         overrides,
         source_trees,
@@ -104,32 +131,53 @@ pub(crate) async fn pip_sync(
         constraints,
         overrides,
         &extras,
+        &[],
         &client_builder,
     )
     .await?;
 
+    // Track the requested packages, to distinguish them from their dependencies in the summary.
+    let direct = requirements
+        .iter()
+        .filter_map(|entry| match &entry.requirement {
+            UnresolvedRequirement::Named(requirement) => Some(requirement.name.clone()),
+            UnresolvedRequirement::Unnamed(_) => None,
+        })
+        .collect::<Vec<_>>();
+
     // Read build constraints.
     let build_constraints =
         operations::read_constraints(build_constraints, &client_builder).await?;
 
     // Validate that the requirements are non-empty.
     if !allow_empty_requirements {
-        let num_requirements = requirements.len() + source_trees.len();
+        let num_requirements = requirements.len() + git_editables.len() + source_trees.len();
         if num_requirements == 0 {
             writeln!(printer.stderr(), "No requirements found (hint: use `--allow-empty-requirements` to clear the environment)")?;
             return Ok(ExitStatus::Success);
         }
     }
 
-    // Detect the current Python interpreter.
-    let environment = PythonEnvironment::find(
-        &python
-            .as_deref()
-            .map(PythonRequest::parse)
-            .unwrap_or_default(),
-        EnvironmentPreference::from_system_flag(system, true),
-        &cache,
-    )?;
+    // Detect the current Python interpreter. If an executable was provided explicitly, use it
+    // as-is, regardless of whether it belongs to a virtual environment.
+    let environment = if let Some(python_executable) = python_executable {
+        let interpreter = Interpreter::query(python_executable, &cache).with_context(|| {
+            format!(
+                "Failed to query Python interpreter at: {}",
+                python_executable.user_display()
+            )
+        })?;
+        PythonEnvironment::from_interpreter(interpreter)
+    } else {
+        PythonEnvironment::find(
+            &python
+                .as_deref()
+                .map(PythonRequest::parse)
+                .unwrap_or_default(),
+            EnvironmentPreference::from_system_flag(system, true),
+            &cache,
+        )?
+    };
 
     debug!(
         "Using Python {} environment at {}",
@@ -149,6 +197,26 @@ pub(crate) async fn pip_sync(
             "Using `--prefix` directory at {}",
             prefix.root().user_display()
         );
+
+        // If the `--prefix` directory was populated by a different Python version, installing
+        // into it would create a second, parallel `site-packages` directory that's invisible
+        // to the interpreter that owns the prefix.
+        let virtualenv = environment.interpreter().virtualenv();
+        let versions = prefix.site_packages_versions();
+        if !versions.is_empty()
+            && !prefix
+                .site_packages(virtualenv)
+                .any(|site_packages| site_packages.is_dir())
+        {
+            bail!(
+                "The `--prefix` directory at {} contains packages for Python {}, but the interpreter at {} is Python {}. Use `--python` or `--python-executable` to select a matching interpreter.",
+                prefix.root().user_display().cyan(),
+                versions.join(", "),
+                environment.python_executable().user_display().cyan(),
+                environment.interpreter().python_version(),
+            );
+        }
+
         environment.with_prefix(prefix)?
     } else {
         environment
@@ -174,7 +242,24 @@ pub(crate) async fn pip_sync(
         }
     }
 
-    let _lock = environment.lock()?;
+    let _lock = lock_environment(&environment).await?;
+
+    // Check out any editable Git requirements, to install them from the local checkouts.
+    let requirements = {
+        let editables = operations::checkout_git_editables(
+            git_editables,
+            src,
+            &environment,
+            &upgrade,
+            &client_builder,
+            &cache,
+        )
+        .await?;
+        requirements
+            .into_iter()
+            .chain(editables)
+            .collect::<Vec<_>>()
+    };
 
     let interpreter = environment.interpreter();
 
@@ -186,9 +271,16 @@ pub(crate) async fn pip_sync(
     };
 
     // Determine the environment for the resolution.
-    let (tags, markers) = resolution_environment(python_version, python_platform, interpreter)?;
+    let (tags, markers) =
+        resolution_environment(python_version, python_platform, macos_arch, interpreter)?;
+
+    // Validate that the requirements files agree on the version of each package.
+    validate_pins(&requirements, &markers)?;
 
     // Collect the set of required hashes.
+    // A hash-checking policy for any index implies `--verify-hashes` for the remaining packages.
+    let hash_checking = hash_checking
+        .or_else(|| (!index_hash_policy.is_empty()).then_some(HashCheckingMode::Verify));
     let hasher = if let Some(hash_checking) = hash_checking {
         HashStrategy::from_requirements(
             requirements
@@ -196,9 +288,14 @@ pub(crate) async fn pip_sync(
                 .map(|entry| (&entry.requirement, entry.hashes.as_slice())),
             Some(&markers),
             hash_checking,
+            index_hash_policy,
         )?
     } else {
-        HashStrategy::None
+        // Even without hash-checking, verify any hashes embedded in direct URLs.
+        HashStrategy::from_url_fragments(
+            requirements.iter().map(|entry| &entry.requirement),
+            Some(&markers),
+        )?
     };
 
     // Incorporate any index locations from the provided sources.
@@ -268,7 +365,8 @@ pub(crate) async fn pip_sync(
         sources,
         concurrency,
         preview,
-    );
+    )
+    .with_package_build_env(package_build_env.clone());
 
     // Determine the set of installed packages.
     let site_packages = SitePackages::from_environment(&environment)?;
@@ -284,6 +382,7 @@ pub(crate) async fn pip_sync(
     let resolution = match operations::resolve(
         requirements,
         constraints,
+        operations::UserConstraints::get(),
         overrides,
         dev,
         source_trees,
@@ -303,6 +402,7 @@ pub(crate) async fn pip_sync(
         &build_dispatch,
         concurrency,
         options,
+        None,
         Box::new(DefaultResolveLogger),
         printer,
         preview,
@@ -313,7 +413,7 @@ pub(crate) async fn pip_sync(
         Err(operations::Error::Resolve(uv_resolver::ResolveError::NoSolution(err))) => {
             let report = miette::Report::msg(format!("{err}")).context(err.header());
             eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            return Ok(ExitStatus::NoSolution);
         }
         Err(err) => return Err(err.into()),
     };
@@ -327,16 +427,23 @@ pub(crate) async fn pip_sync(
         &build_options,
         link_mode,
         compile,
+        verify_imports,
         &index_locations,
         &hasher,
         &tags,
+        abi_check,
+        file_conflicts,
+        lazy_install,
+        exclude_package_data,
+        max_download_size,
+        false,
         &client,
         &state.in_flight,
         concurrency,
         &build_dispatch,
         &cache,
         &environment,
-        Box::new(DefaultInstallLogger),
+        Box::new(DefaultInstallLogger::new(summary, direct).with_sizes(show_sizes)),
         dry_run,
         printer,
         preview,
@@ -353,3 +460,102 @@ pub(crate) async fn pip_sync(
 
     Ok(ExitStatus::Success)
 }
+
+/// Validate that no package is pinned to multiple versions across the requirements (e.g., when
+/// syncing several requirements files), considering only those that apply to the environment.
+fn validate_pins(
+    requirements: &[UnresolvedRequirementSpecification],
+    markers: &MarkerEnvironment,
+) -> Result<()> {
+    let mut pins = FxHashMap::<&PackageName, (&Requirement, &Version)>::default();
+    for entry in requirements {
+        let UnresolvedRequirement::Named(requirement) = &entry.requirement else {
+            continue;
+        };
+        if !requirement.evaluate_markers(Some(markers), &[]) {
+            continue;
+        }
+        let Some(version) = pinned_version(requirement) else {
+            continue;
+        };
+        match pins.entry(&requirement.name) {
+            Entry::Vacant(entry) => {
+                entry.insert((requirement, version));
+            }
+            Entry::Occupied(entry) => {
+                let (existing, existing_version) = *entry.get();
+                if existing_version != version {
+                    bail!(
+                        "`{}` is pinned to multiple versions: `{}` ({}) and `{}` ({})",
+                        requirement.name,
+                        existing,
+                        describe_origin(existing, existing_version),
+                        requirement,
+                        describe_origin(requirement, version),
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Return the version to which a requirement is pinned, if it's pinned to a single version.
+fn pinned_version(requirement: &Requirement) -> Option<&Version> {
+    let RequirementSource::Registry { specifier, .. } = &requirement.source else {
+        return None;
+    };
+    let [specifier] = specifier.as_ref() else {
+        return None;
+    };
+    if !matches!(specifier.operator(), Operator::Equal | Operator::ExactEqual) {
+        return None;
+    }
+    Some(specifier.version())
+}
+
+/// Describe where a requirement was declared, e.g., `requirements.txt:3`.
+fn describe_origin(requirement: &Requirement, version: &Version) -> String {
+    match &requirement.origin {
+        Some(RequirementOrigin::File(path)) => {
+            if let Some(line) = find_line(path, &requirement.name, version) {
+                format!("{}:{line}", path.user_display())
+            } else {
+                path.user_display().to_string()
+            }
+        }
+        Some(RequirementOrigin::Project(path, _)) => path.user_display().to_string(),
+        Some(RequirementOrigin::Group(path, _, group)) => {
+            format!("{}:{group}", path.user_display())
+        }
+        Some(RequirementOrigin::Workspace) => "workspace".to_string(),
+        None => "command line".to_string(),
+    }
+}
+
+/// Find the (one-indexed) line on which the given package is pinned in a requirements file,
+/// preferring a line that mentions the pinned version.
+///
+/// The parsed requirements don't retain their position, so the file is scanned for a line that
+/// starts with the package name.
+fn find_line(path: &Path, name: &PackageName, version: &Version) -> Option<usize> {
+    let contents = fs_err::read_to_string(path).ok()?;
+    let candidates = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim_start();
+            let token = line
+                .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .next()
+                .unwrap_or_default();
+            PackageName::from_str(token).is_ok_and(|token| token == *name)
+        })
+        .collect::<Vec<_>>();
+    let version = version.to_string();
+    candidates
+        .iter()
+        .find(|(_, line)| line.contains(version.as_str()))
+        .or_else(|| candidates.first())
+        .map(|(index, _)| index + 1)
+}
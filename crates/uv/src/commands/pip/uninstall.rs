@@ -18,7 +18,8 @@ use uv_python::PythonRequest;
 use uv_python::{Prefix, PythonEnvironment, Target};
 use uv_requirements::{RequirementsSource, RequirementsSpecification};
 
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::pip::operations;
+use crate::commands::{elapsed, lock_environment, ExitStatus};
 use crate::printer::Printer;
 
 /// Uninstall packages from the current environment.
@@ -99,7 +100,7 @@ pub(crate) async fn pip_uninstall(
         }
     }
 
-    let _lock = environment.lock()?;
+    let _lock = lock_environment(&environment).await?;
 
     // Index the current `site-packages` directory.
     let site_packages = uv_installer::SitePackages::from_environment(&environment)?;
@@ -188,6 +189,10 @@ pub(crate) async fn pip_uninstall(
         return Ok(ExitStatus::Success);
     }
 
+    // If the environment was marked read-only, restore write access for the duration of the
+    // uninstallation.
+    let write_access = operations::WriteAccess::acquire(&environment)?;
+
     // Uninstall each package.
     for distribution in &distributions {
         let summary = uv_installer::uninstall(distribution).await?;
@@ -201,6 +206,8 @@ pub(crate) async fn pip_uninstall(
         );
     }
 
+    drop(write_access);
+
     writeln!(
         printer.stderr(),
         "{}",
@@ -1,25 +1,36 @@
+use std::borrow::Cow;
 use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
+use std::fmt::Write;
 
 use anyhow::{Context, Result};
+use distribution_types::UnresolvedRequirement;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
-use pep508_rs::{ExtraName, Requirement, VersionOrUrl};
-use rustc_hash::{FxBuildHasher, FxHashMap};
+use pep440_rs::{VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{
+    ExtraName, MarkerExpression, MarkerTree, MarkerValueVersion, Requirement, VersionOrUrl,
+};
+use pypi_types::RequirementSource;
+use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
 use tracing::debug;
 use uv_auth::store_credentials_from_url;
 use uv_cache::Cache;
+use uv_cli::RequiresPythonFix;
 use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
 use uv_configuration::{
-    Concurrency, ExtrasSpecification, PreviewMode, SetupPyStrategy, SourceStrategy,
+    Concurrency, ExtrasSpecification, MacosArch, PackageBuildEnv, PreviewMode, SetupPyStrategy,
+    SourceStrategy, TyposquatCheck,
 };
 use uv_dispatch::BuildDispatch;
 use uv_distribution::DistributionDatabase;
-use uv_fs::CWD;
+use uv_fs::{Simplified, CWD};
 use uv_normalize::PackageName;
 use uv_python::{PythonDownloads, PythonPreference, PythonRequest};
 use uv_requirements::{NamedRequirementsResolver, RequirementsSource, RequirementsSpecification};
-use uv_resolver::FlatIndex;
+use uv_resolver::{FlatIndex, RequiresPython};
 use uv_types::{BuildIsolation, HashStrategy};
-use uv_warnings::warn_user_once;
+use uv_warnings::{warn_user, warn_user_once};
 use uv_workspace::pyproject::{DependencyType, Source, SourceError};
 use uv_workspace::pyproject_mut::{ArrayEdit, PyProjectTomlMut};
 use uv_workspace::{DiscoveryOptions, VirtualProject, Workspace};
@@ -29,7 +40,7 @@ use crate::commands::pip::operations::Modifications;
 use crate::commands::pip::resolution_environment;
 use crate::commands::project::ProjectError;
 use crate::commands::reporters::ResolverReporter;
-use crate::commands::{pip, project, ExitStatus, SharedState};
+use crate::commands::{check_typosquats, pip, project, ExitStatus, SharedState};
 use crate::printer::Printer;
 use crate::settings::ResolverInstallerSettings;
 
@@ -39,15 +50,20 @@ pub(crate) async fn add(
     locked: bool,
     frozen: bool,
     no_sync: bool,
-    requirements: Vec<RequirementsSource>,
+    suggest_requires_python: bool,
+    fix_requires_python: Option<RequiresPythonFix>,
+    requirements: Vec<(RequirementsSource, DependencyType)>,
+    sync_manifest: bool,
     editable: Option<bool>,
-    dependency_type: DependencyType,
     raw_sources: bool,
+    raw: bool,
     rev: Option<String>,
     tag: Option<String>,
     branch: Option<String>,
     extras: Vec<ExtraName>,
+    marker: Option<MarkerTree>,
     package: Option<PackageName>,
+    typosquat_check: TyposquatCheck,
     python: Option<String>,
     settings: ResolverInstallerSettings,
     python_preference: PythonPreference,
@@ -75,16 +91,26 @@ pub(crate) async fn add(
         VirtualProject::discover(&CWD, &DiscoveryOptions::default()).await?
     };
 
+    // Determine the dependency groups that the requirements are added to.
+    let mut dependency_types: Vec<DependencyType> = Vec::new();
+    for (_, dependency_type) in &requirements {
+        if !dependency_types.contains(dependency_type) {
+            dependency_types.push(dependency_type.clone());
+        }
+    }
+
     // For virtual projects, allow dev dependencies, but nothing else.
     if project.is_virtual() {
-        match dependency_type {
-            DependencyType::Production => {
-                anyhow::bail!("Found a virtual workspace root, but virtual projects do not support production dependencies (instead, use: `{}`)", "uv add --dev".green())
-            }
-            DependencyType::Optional(_) => {
-                anyhow::bail!("Found a virtual workspace root, but virtual projects do not support optional dependencies (instead, use: `{}`)", "uv add --dev".green())
+        for dependency_type in &dependency_types {
+            match dependency_type {
+                DependencyType::Production => {
+                    anyhow::bail!("Found a virtual workspace root, but virtual projects do not support production dependencies (instead, use: `{}`)", "uv add --dev".green())
+                }
+                DependencyType::Optional(_) => {
+                    anyhow::bail!("Found a virtual workspace root, but virtual projects do not support optional dependencies (instead, use: `{}`)", "uv add --dev".green())
+                }
+                DependencyType::Dev => (),
             }
-            DependencyType::Dev => (),
         }
     }
 
@@ -106,9 +132,53 @@ pub(crate) async fn add(
         .native_tls(native_tls)
         .keyring(settings.keyring_provider);
 
-    // Read the requirements.
-    let RequirementsSpecification { requirements, .. } =
-        RequirementsSpecification::from_simple_sources(&requirements, &client_builder).await?;
+    // Writing the requirements verbatim implies `--raw-sources`.
+    let raw_sources = raw_sources || raw;
+
+    // Read the requirements. Each source is read on its own, such that every requirement carries
+    // the dependency group it's added to and, with `--raw`, the string it was provided as.
+    let mut specification = RequirementsSpecification::default();
+    let mut verbatim = vec![];
+    let mut targets = vec![];
+    for (source, dependency_type) in &requirements {
+        let spec = RequirementsSpecification::from_simple_sources(
+            std::slice::from_ref(source),
+            &client_builder,
+        )
+        .await?;
+        let given = match source {
+            RequirementsSource::Package(given) if raw => {
+                if spec
+                    .requirements
+                    .iter()
+                    .any(|entry| matches!(entry.requirement, UnresolvedRequirement::Unnamed(..)))
+                {
+                    anyhow::bail!(
+                        "Cannot add `{given}` verbatim, as it doesn't include a package name. Specify the requirement as `<name> @ {given}`, or omit the `--raw` flag."
+                    );
+                }
+                Some(given.clone())
+            }
+            _ => None,
+        };
+        verbatim.extend(std::iter::repeat(given).take(spec.requirements.len()));
+        targets.extend(std::iter::repeat(dependency_type).take(spec.requirements.len()));
+        specification.requirements.extend(spec.requirements);
+        specification.constraints.extend(spec.constraints);
+    }
+    let RequirementsSpecification {
+        requirements,
+        constraints,
+        ..
+    } = specification;
+
+    // Constraints are only respected in the workspace root.
+    if !constraints.is_empty() && project.root() != project.workspace().install_path() {
+        anyhow::bail!(
+            "Constraints can only be added to the workspace root, but `{}` is a workspace member",
+            project.root().user_display()
+        );
+    }
 
     // TODO(charlie): These are all default values. We should consider whether we want to make them
     // optional on the downstream APIs.
@@ -119,8 +189,12 @@ pub(crate) async fn add(
     let build_isolation = BuildIsolation::default();
 
     // Determine the environment for the resolution.
-    let (tags, markers) =
-        resolution_environment(python_version, python_platform, venv.interpreter())?;
+    let (tags, markers) = resolution_environment(
+        python_version,
+        python_platform,
+        MacosArch::Native,
+        venv.interpreter(),
+    )?;
 
     // Add all authenticated sources to the cache.
     for url in settings.index_locations.urls() {
@@ -169,7 +243,8 @@ pub(crate) async fn add(
         sources,
         concurrency,
         preview,
-    );
+    )
+    .with_package_build_env(PackageBuildEnv::from(project.workspace().build_env()));
 
     // Resolve any unnamed requirements.
     let requirements = NamedRequirementsResolver::new(
@@ -182,16 +257,75 @@ pub(crate) async fn add(
     .resolve()
     .await?;
 
+    // Check any newly-added registry requirements for names that resemble popular packages.
+    if typosquat_check != TyposquatCheck::Ignore {
+        let lock = project::lock::read(project.workspace()).await?;
+        let locked = |name: &PackageName| {
+            lock.as_ref()
+                .is_some_and(|lock| lock.packages().iter().any(|package| package.name() == name))
+        };
+        check_typosquats(
+            requirements
+                .iter()
+                .filter(|requirement| {
+                    matches!(requirement.source, RequirementSource::Registry { .. })
+                })
+                .map(|requirement| &requirement.name)
+                .filter(|name| !locked(name)),
+            typosquat_check,
+            cache,
+        )
+        .map_err(ProjectError::from)?;
+    }
+
     // Add the requirements to the `pyproject.toml`.
     let existing = project.pyproject_toml();
     let mut pyproject = PyProjectTomlMut::from_toml(existing)?;
+
+    // With `--sync-manifest`, remove any dependencies that aren't listed in the requirements
+    // files imported into the same group. (Removals happen ahead of any additions, to retain the
+    // indices of the latter.)
+    if sync_manifest {
+        for dependency_type in &dependency_types {
+            let names = requirements
+                .iter()
+                .zip(&targets)
+                .filter(|&(_, &target)| target == dependency_type)
+                .map(|(requirement, _)| &requirement.name)
+                .collect::<FxHashSet<_>>();
+            for name in pyproject.dependency_names(dependency_type) {
+                if names.contains(&name) {
+                    continue;
+                }
+                debug!("Removing `{name}`, which is not listed in the requirements files");
+                match dependency_type {
+                    DependencyType::Production => pyproject.remove_dependency(&name)?,
+                    DependencyType::Dev => pyproject.remove_dev_dependency(&name)?,
+                    DependencyType::Optional(group) => {
+                        pyproject.remove_optional_dependency(&name, group)?
+                    }
+                };
+            }
+        }
+    }
+
     let mut edits = Vec::with_capacity(requirements.len());
-    for mut requirement in requirements {
+    for ((mut requirement, verbatim), dependency_type) in
+        requirements.into_iter().zip(verbatim).zip(targets)
+    {
         // Add the specified extras.
         requirement.extras.extend(extras.iter().cloned());
         requirement.extras.sort_unstable();
         requirement.extras.dedup();
 
+        // Add the specified marker.
+        if let Some(marker) = marker.as_ref() {
+            match requirement.marker.as_mut() {
+                Some(existing) => existing.and(marker.clone()),
+                None => requirement.marker = Some(marker.clone()),
+            }
+        }
+
         let (requirement, source) = if raw_sources {
             // Use the PEP 508 requirement directly.
             (pep508_rs::Requirement::from(requirement), None)
@@ -227,25 +361,49 @@ pub(crate) async fn add(
         };
 
         // Update the `pyproject.toml`.
+        let verbatim = verbatim.as_deref();
         let edit = match dependency_type {
             DependencyType::Production => {
-                pyproject.add_dependency(&requirement, source.as_ref())?
+                pyproject.add_dependency(&requirement, verbatim, source.as_ref())?
+            }
+            DependencyType::Dev => {
+                pyproject.add_dev_dependency(&requirement, verbatim, source.as_ref())?
             }
-            DependencyType::Dev => pyproject.add_dev_dependency(&requirement, source.as_ref())?,
-            DependencyType::Optional(ref group) => {
-                pyproject.add_optional_dependency(group, &requirement, source.as_ref())?
+            DependencyType::Optional(group) => {
+                pyproject.add_optional_dependency(group, &requirement, verbatim, source.as_ref())?
             }
         };
 
+        // If the dependency was inserted ahead of any earlier edits (e.g., to preserve
+        // alphabetical ordering), shift their indices accordingly.
+        if let ArrayEdit::Add(inserted) = edit {
+            for previous in &mut edits {
+                if previous.dependency_type != dependency_type {
+                    continue;
+                }
+                match &mut previous.edit {
+                    ArrayEdit::Add(index) | ArrayEdit::Update(index) if *index >= inserted => {
+                        *index += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
         // Keep track of the exact location of the edit.
         edits.push(DependencyEdit {
-            dependency_type: &dependency_type,
+            dependency_type,
             requirement,
             source,
             edit,
         });
     }
 
+    // Add any constraints to `tool.uv.constraint-dependencies`.
+    for constraint in constraints {
+        pyproject.add_constraint(&pep508_rs::Requirement::from(constraint))?;
+    }
+
     // Save the modified `pyproject.toml`.
     let mut modified = false;
     let content = pyproject.to_string();
@@ -263,46 +421,197 @@ pub(crate) async fn add(
     }
 
     // Update the `pypackage.toml` in-memory.
-    let project = project
+    let mut project = project
         .clone()
         .with_pyproject_toml(toml::from_str(&content)?)
         .context("Failed to update `pyproject.toml`")?;
 
     // Lock and sync the environment, if necessary.
-    let lock = match project::lock::do_safe_lock(
-        locked,
-        frozen,
-        project.workspace(),
-        venv.interpreter(),
-        settings.as_ref().into(),
-        Box::new(DefaultResolveLogger),
-        preview,
-        connectivity,
-        concurrency,
-        native_tls,
-        cache,
-        printer,
-    )
-    .await
-    {
-        Ok(lock) => lock,
-        Err(ProjectError::Operation(pip::operations::Error::Resolve(
-            uv_resolver::ResolveError::NoSolution(err),
-        ))) => {
-            let header = err.header();
-            let report = miette::Report::new(WithHelp { header, cause: err, help: Some("If this is intentional, run `uv add --frozen` to skip the lock and sync steps.") });
-            anstream::eprint!("{report:?}");
-
-            // Revert the changes to the `pyproject.toml`, if necessary.
-            if modified {
-                fs_err::write(project.root().join("pyproject.toml"), existing)?;
+    let mut fixed = false;
+    let lock = loop {
+        let err = match project::lock::do_safe_lock(
+            locked,
+            frozen,
+            project.workspace(),
+            venv.interpreter(),
+            None,
+            settings.as_ref().into(),
+            Box::new(DefaultResolveLogger),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await
+        {
+            Ok(lock) => break lock,
+            Err(ProjectError::Operation(pip::operations::Error::Resolve(
+                uv_resolver::ResolveError::NoSolution(err),
+            ))) => err,
+            Err(err) => return Err(err.into()),
+        };
+
+        // Determine whether any of the added dependencies were rejected due to the project's
+        // `requires-python`.
+        let requires_python = project::find_requires_python(project.workspace())?;
+        let conflicts = err
+            .requires_python_conflicts()
+            .into_iter()
+            .filter(|(name, _)| edits.iter().any(|edit| edit.requirement.name == *name))
+            .filter(|(_, version)| {
+                requires_python.as_ref().map_or(true, |requires_python| {
+                    RequiresPython::greater_than_equal_version(version).bound()
+                        > requires_python.bound()
+                })
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        // If requested, attempt to resolve the conflicts by editing the `pyproject.toml`, then
+        // retry the lock (once).
+        if let Some(fix) = fix_requires_python.filter(|_| !fixed && !conflicts.is_empty()) {
+            let applied = match fix {
+                RequiresPythonFix::Narrow => {
+                    let version = conflicts
+                        .values()
+                        .max()
+                        .expect("conflicts should be non-empty");
+
+                    // The `requires-python` of the workspace is the intersection of that of its
+                    // members, so narrowing the project only helps if every member supports the
+                    // required version.
+                    let excluded =
+                        project
+                            .workspace()
+                            .packages()
+                            .iter()
+                            .find_map(|(name, member)| {
+                                member
+                                    .pyproject_toml()
+                                    .project
+                                    .as_ref()
+                                    .and_then(|project| project.requires_python.as_ref())
+                                    .filter(|specifiers| !specifiers.contains(version))
+                                    .map(|specifiers| (name, specifiers))
+                            });
+                    if let Some((name, specifiers)) = excluded {
+                        warn_user!(
+                            "Unable to narrow `requires-python` to support {}, since `{name}` requires Python `{specifiers}`",
+                            conflicts.keys().join(", "),
+                        );
+                        false
+                    } else {
+                        // Raise the lower bound, while retaining any other constraints (like an
+                        // upper bound).
+                        let lower = VersionSpecifiers::from(
+                            VersionSpecifier::greater_than_equal_version(version.only_release()),
+                        );
+                        let requires_python = RequiresPython::intersection(
+                            project
+                                .pyproject_toml()
+                                .project
+                                .as_ref()
+                                .and_then(|project| project.requires_python.as_ref())
+                                .into_iter()
+                                .chain(std::iter::once(&lower)),
+                        )?
+                        .expect("`requires-python` should be non-empty");
+                        pyproject.set_requires_python(requires_python.specifiers())?;
+                        writeln!(
+                            printer.stderr(),
+                            "Updated `requires-python` to `{}`, as required by {}",
+                            requires_python.cyan(),
+                            conflicts.keys().map(|name| name.bold()).join(", "),
+                        )?;
+                        true
+                    }
+                }
+                RequiresPythonFix::Marker => {
+                    for edit in &mut edits {
+                        let Some(version) = conflicts.get(&edit.requirement.name) else {
+                            continue;
+                        };
+                        let (ArrayEdit::Add(index) | ArrayEdit::Update(index)) = edit.edit;
+                        let marker = MarkerTree::expression(MarkerExpression::Version {
+                            key: MarkerValueVersion::PythonFullVersion,
+                            specifier: VersionSpecifier::greater_than_equal_version(
+                                version.only_release(),
+                            ),
+                        });
+                        writeln!(
+                            printer.stderr(),
+                            "Limited {} to Python versions it supports, with the marker `{}`",
+                            edit.requirement.name.bold(),
+                            marker
+                                .contents()
+                                .map(|contents| contents.to_string())
+                                .unwrap_or_default()
+                                .cyan(),
+                        )?;
+                        pyproject.add_dependency_marker(
+                            edit.dependency_type,
+                            index,
+                            marker.clone(),
+                        )?;
+                        match edit.requirement.marker.as_mut() {
+                            Some(existing) => existing.and(marker),
+                            None => edit.requirement.marker = Some(marker),
+                        }
+                    }
+                    true
+                }
+            };
+
+            fixed = true;
+            if applied {
+                let content = pyproject.to_string();
+                fs_err::write(project.root().join("pyproject.toml"), &content)?;
+                modified = true;
+                project = project
+                    .with_pyproject_toml(toml::from_str(&content)?)
+                    .context("Failed to update `pyproject.toml`")?;
+                continue;
             }
+        }
 
-            return Ok(ExitStatus::Failure);
+        let help = if conflicts.is_empty() || fixed {
+            Cow::Borrowed(
+                "If this is intentional, run `uv add --frozen` to skip the lock and sync steps.",
+            )
+        } else {
+            Cow::Owned(format!(
+                "The project's `requires-python` includes Python versions that aren't supported by {}. To narrow the `requires-python`, run with `--fix-requires-python narrow`; to only require the dependency on the Python versions it supports, run with `--fix-requires-python marker`.",
+                conflicts
+                    .iter()
+                    .map(|(name, version)| format!("{name} (which requires Python >={version})"))
+                    .join(", "),
+            ))
+        };
+        let header = err.header();
+        let report = miette::Report::new(WithHelp {
+            header,
+            cause: err,
+            help: Some(help),
+        });
+        anstream::eprint!("{report:?}");
+
+        // Revert the changes to the `pyproject.toml`, if necessary.
+        if modified {
+            fs_err::write(project.root().join("pyproject.toml"), existing)?;
         }
-        Err(err) => return Err(err.into()),
+
+        return Ok(ExitStatus::NoSolution);
     };
 
+    if suggest_requires_python {
+        project::lock::suggest_requires_python(
+            &lock.python_exclusions,
+            lock.lock.requires_python(),
+            printer,
+        )?;
+    }
+
     // Avoid modifying the user request further if `--raw-sources` is set.
     if !raw_sources {
         // Extract the minimum-supported version for each dependency.
@@ -382,38 +691,38 @@ pub(crate) async fn add(
         return Ok(ExitStatus::Success);
     }
 
-    // Sync the environment.
-    let (extras, dev) = match dependency_type {
-        DependencyType::Production => {
-            let extras = ExtrasSpecification::None;
-            let dev = false;
-            (extras, dev)
-        }
-        DependencyType::Dev => {
-            let extras = ExtrasSpecification::None;
-            let dev = true;
-            (extras, dev)
-        }
-        DependencyType::Optional(ref group_name) => {
-            let extras = ExtrasSpecification::Some(vec![group_name.clone()]);
-            let dev = false;
-            (extras, dev)
-        }
+    // Sync the environment, including any groups that requirements were added to.
+    let extras = dependency_types
+        .iter()
+        .filter_map(|dependency_type| match dependency_type {
+            DependencyType::Optional(group_name) => Some(group_name.clone()),
+            DependencyType::Production | DependencyType::Dev => None,
+        })
+        .collect::<Vec<_>>();
+    let extras = if extras.is_empty() {
+        ExtrasSpecification::None
+    } else {
+        ExtrasSpecification::Some(extras)
     };
+    let dev = dependency_types.contains(&DependencyType::Dev);
 
     // Initialize any shared state.
     let state = SharedState::default();
 
     project::sync::do_sync(
         &project,
+        None,
         &venv,
         &lock.lock,
         &extras,
         dev,
+        &[],
         Modifications::Sufficient,
+        false,
+        false,
         settings.as_ref().into(),
         &state,
-        Box::new(DefaultInstallLogger),
+        Box::new(DefaultInstallLogger::default()),
         preview,
         connectivity,
         concurrency,
@@ -448,5 +757,5 @@ struct WithHelp {
 
     /// The help message to display.
     #[help]
-    help: Option<&'static str>,
+    help: Option<Cow<'static, str>>,
 }
@@ -0,0 +1,439 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_filename::{SourceDistFilename, WheelFilename};
+use pep440_rs::{Operator, Version};
+use pep508_rs::{PackageName, VersionOrUrl};
+use pypi_types::Metadata23;
+
+use uv_auth::store_credentials_from_url;
+use uv_cache::Cache;
+use uv_client::{Connectivity, FlatIndexClient, RegistryClientBuilder};
+use uv_configuration::{BuildKind, Concurrency, PackageBuildEnv, PreviewMode, SetupPyStrategy};
+use uv_dispatch::BuildDispatch;
+use uv_fs::{Simplified, CWD};
+use uv_python::{PythonDownloads, PythonEnvironment, PythonPreference, PythonRequest};
+use uv_resolver::{BuildDependency, FlatIndex};
+use uv_types::{BuildContext, BuildIsolation, HashStrategy};
+use uv_warnings::{warn_user, warn_user_once};
+use uv_workspace::pyproject_mut::PyProjectTomlMut;
+use uv_workspace::{
+    DiscoveryOptions, ProjectWorkspace, Workspace, WorkspaceGraph, WorkspaceMember,
+};
+
+use crate::commands::pip::loggers::DefaultResolveLogger;
+use crate::commands::project::lock::do_safe_lock;
+use crate::commands::project::{FoundInterpreter, ProjectError, SharedState};
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+use crate::settings::{ResolverSettings, ResolverSettingsRef};
+
+/// Build source distributions and wheels for the project, or for every member of the workspace.
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) async fn build(
+    src: Option<PathBuf>,
+    all: bool,
+    sdist: bool,
+    wheel: bool,
+    out_dir: Option<PathBuf>,
+    locked: bool,
+    python: Option<String>,
+    settings: ResolverSettings,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv build` is experimental and may change without warning");
+    }
+
+    // Identify the project, or the workspace and its members in dependency order.
+    let src = src.map_or_else(|| CWD.to_path_buf(), |src| CWD.join(src));
+    let (workspace, members, graph, default_out_dir) = if all {
+        let workspace = Workspace::discover(&src, &DiscoveryOptions::default()).await?;
+        let graph = WorkspaceGraph::from_workspace(&workspace)?;
+        let members = graph
+            .topological_order()?
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        let default_out_dir = workspace.install_path().join("dist");
+        (workspace, members, Some(graph), default_out_dir)
+    } else {
+        let project = ProjectWorkspace::discover(&src, &DiscoveryOptions::default()).await?;
+        let members = vec![project.project_name().clone()];
+        let default_out_dir = project.project_root().join("dist");
+        (project.workspace().clone(), members, None, default_out_dir)
+    };
+
+    // Find an interpreter for the project.
+    let interpreter = FoundInterpreter::discover(
+        &workspace,
+        python.as_deref().map(PythonRequest::parse),
+        python_preference,
+        python_downloads,
+        connectivity,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?
+    .into_interpreter();
+
+    // If requested, pin the build environments to the locked build requirements.
+    let lock = if locked {
+        Some(
+            do_safe_lock(
+                true,
+                false,
+                &workspace,
+                &interpreter,
+                None,
+                settings.as_ref(),
+                Box::new(DefaultResolveLogger),
+                preview,
+                connectivity,
+                concurrency,
+                native_tls,
+                cache,
+                printer,
+            )
+            .await?
+            .lock,
+        )
+    } else {
+        None
+    };
+
+    // Extract the project settings.
+    let ResolverSettingsRef {
+        index_locations,
+        index_strategy,
+        keyring_provider,
+        resolution: _,
+        prerelease: _,
+        config_setting,
+        no_build_isolation,
+        no_build_isolation_package,
+        exclude_newer,
+        link_mode,
+        upgrade: _,
+        build_options,
+        sources,
+    } = settings.as_ref();
+
+    // Add all authenticated sources to the cache.
+    for url in index_locations.urls() {
+        store_credentials_from_url(url);
+    }
+
+    // Initialize the registry client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .native_tls(native_tls)
+        .connectivity(connectivity)
+        .index_urls(index_locations.index_urls())
+        .index_strategy(index_strategy)
+        .keyring(keyring_provider)
+        .markers(interpreter.markers())
+        .platform(interpreter.platform())
+        .build();
+
+    // Determine whether to enable build isolation.
+    let environment;
+    let build_isolation = if no_build_isolation {
+        environment = PythonEnvironment::from_interpreter(interpreter.clone());
+        BuildIsolation::Shared(&environment)
+    } else if no_build_isolation_package.is_empty() {
+        BuildIsolation::Isolated
+    } else {
+        environment = PythonEnvironment::from_interpreter(interpreter.clone());
+        BuildIsolation::SharedPackage(&environment, no_build_isolation_package)
+    };
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, None, &HashStrategy::None, build_options)
+    };
+
+    let out_dir = out_dir.map_or(default_out_dir, |out_dir| CWD.join(out_dir));
+    fs_err::tokio::create_dir_all(&out_dir).await?;
+
+    // If neither a source distribution nor a wheel was requested, build both.
+    let (sdist, wheel) = if sdist || wheel {
+        (sdist, wheel)
+    } else {
+        (true, true)
+    };
+    let kinds = [(sdist, BuildKind::Sdist), (wheel, BuildKind::Wheel)]
+        .into_iter()
+        .filter_map(|(enabled, kind)| enabled.then_some(kind))
+        .collect::<Vec<_>>();
+
+    let state = SharedState::default();
+
+    // The versions of the members that have been built so far.
+    let mut built = BTreeMap::new();
+
+    for name in members {
+        let member = workspace
+            .packages()
+            .get(&name)
+            .ok_or_else(|| anyhow!("Workspace member `{name}` could not be found"))?;
+
+        let build_constraints = if let Some(lock) = lock.as_ref() {
+            let build_constraints = lock
+                .packages()
+                .iter()
+                .find(|package| *package.name() == name)
+                .map(|package| {
+                    package
+                        .build_dependencies()
+                        .iter()
+                        .map(BuildDependency::to_requirement)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            if build_constraints.is_empty() {
+                return Err(ProjectError::MissingBuildRequirements(name.clone()).into());
+            }
+            build_constraints
+        } else {
+            vec![]
+        };
+
+        // Create a build dispatch.
+        let build_dispatch = BuildDispatch::new(
+            &client,
+            cache,
+            &build_constraints,
+            &interpreter,
+            index_locations,
+            &flat_index,
+            &state.index,
+            &state.git,
+            &state.in_flight,
+            index_strategy,
+            SetupPyStrategy::default(),
+            config_setting,
+            build_isolation,
+            link_mode,
+            build_options,
+            exclude_newer,
+            sources,
+            concurrency,
+            preview,
+        )
+        .with_package_build_env(PackageBuildEnv::from(workspace.build_env()));
+
+        // When building the entire workspace, pin any dependencies on members that were already
+        // built to the built versions. The member is built from a temporary copy of its source
+        // tree, such that the original `pyproject.toml` is never modified.
+        let pinned = if let Some(graph) = graph.as_ref() {
+            PinnedSourceTree::pin(&name, member, graph, &built, &out_dir)?
+        } else {
+            None
+        };
+        let source_root = pinned
+            .as_ref()
+            .map_or(member.root().as_path(), PinnedSourceTree::path);
+
+        let version_id = name.to_string();
+        for kind in &kinds {
+            let builder = build_dispatch
+                .setup_build(source_root, None, &version_id, None, *kind)
+                .await?;
+            let filename = builder.build(&out_dir).await?;
+
+            // Ensure that the build backend respected the pinned dependencies.
+            if let (Some(pinned), BuildKind::Wheel) = (pinned.as_ref(), kind) {
+                pinned.verify(&out_dir.join(&filename))?;
+            }
+
+            if let Some(version) = built_version(&filename, *kind) {
+                built.entry(name.clone()).or_insert(version);
+            }
+
+            writeln!(
+                printer.stderr(),
+                "Successfully built {}",
+                out_dir.join(filename).user_display().cyan()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Extract the version from the filename of a built distribution.
+fn built_version(filename: &str, kind: BuildKind) -> Option<Version> {
+    match kind {
+        BuildKind::Wheel => WheelFilename::from_str(filename)
+            .ok()
+            .map(|filename| filename.version),
+        BuildKind::Sdist => SourceDistFilename::parsed_normalized_filename(filename)
+            .ok()
+            .map(|filename| filename.version),
+        BuildKind::Editable => None,
+    }
+}
+
+/// A temporary copy of a workspace member's source tree, with its dependencies on other members
+/// pinned to the built versions.
+///
+/// The copy is removed when dropped.
+struct PinnedSourceTree {
+    /// The copy of the source tree, created alongside the original such that relative paths (e.g.,
+    /// to a readme or license file outside the member) continue to resolve.
+    dir: tempfile::TempDir,
+    /// The versions to which the member's dependencies were pinned.
+    pins: BTreeMap<PackageName, Version>,
+}
+
+impl PinnedSourceTree {
+    /// Pin the member's dependencies on any of the given members, returning `None` if the member
+    /// doesn't depend on any of them.
+    fn pin(
+        name: &PackageName,
+        member: &WorkspaceMember,
+        graph: &WorkspaceGraph,
+        built: &BTreeMap<PackageName, Version>,
+        out_dir: &Path,
+    ) -> Result<Option<Self>> {
+        let Some(dependencies) = graph.get(name) else {
+            return Ok(None);
+        };
+
+        if dependencies.is_dynamic() {
+            warn_user!(
+                "The dependencies of `{name}` are dynamic, so they can't be pinned to the built versions of other workspace members"
+            );
+            return Ok(None);
+        }
+
+        let mut pyproject = PyProjectTomlMut::from_toml(member.pyproject_toml())?;
+        let pinned = pyproject.pin_dependencies(built)?;
+
+        for dependency in dependencies.members() {
+            if !built.contains_key(dependency) {
+                warn_user!(
+                    "`{name}` depends on `{dependency}`, which wasn't built, so the dependency won't be pinned"
+                );
+            } else if !pinned.contains(dependency) {
+                warn_user!(
+                    "`{name}` depends on `{dependency}` via a direct URL, so the dependency won't be pinned to the built version"
+                );
+            }
+        }
+
+        if pinned.is_empty() {
+            return Ok(None);
+        }
+
+        let root = member.root();
+        let parent = root
+            .parent()
+            .ok_or_else(|| anyhow!("Workspace member has no parent: {}", root.user_display()))?;
+        let dir = tempfile::Builder::new()
+            .prefix(".tmp-build-")
+            .tempdir_in(parent)?;
+        debug!(
+            "Copying `{}` to `{}` to pin workspace dependencies",
+            root.user_display(),
+            dir.path().user_display()
+        );
+        copy_source_tree(root, dir.path(), out_dir)?;
+        fs_err::write(dir.path().join("pyproject.toml"), pyproject.to_string())?;
+
+        let pins = built
+            .iter()
+            .filter(|(name, _)| pinned.contains(*name))
+            .map(|(name, version)| (name.clone(), version.clone()))
+            .collect();
+
+        Ok(Some(Self { dir, pins }))
+    }
+
+    /// The path to the copy of the source tree.
+    fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Verify that the `METADATA` of the built wheel pins each dependency to the built version.
+    ///
+    /// Build backends that derive their dependencies from somewhere other than the
+    /// `project.dependencies` table (e.g., `setup.py`) will ignore the pins.
+    fn verify(&self, wheel: &Path) -> Result<()> {
+        let filename = WheelFilename::from_str(
+            wheel
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| anyhow!("Invalid wheel path: {}", wheel.user_display()))?,
+        )?;
+        let mut archive = zip::ZipArchive::new(fs_err::File::open(wheel)?)?;
+        let metadata = install_wheel_rs::metadata::read_archive_metadata(&filename, &mut archive)?;
+        let metadata = Metadata23::parse_metadata(&metadata)
+            .with_context(|| format!("Failed to read metadata from: {}", wheel.user_display()))?;
+
+        for requirement in &metadata.requires_dist {
+            let Some(version) = self.pins.get(&requirement.name) else {
+                continue;
+            };
+            let pinned = match requirement.version_or_url.as_ref() {
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) => {
+                    specifiers.len() == 1
+                        && specifiers.iter().all(|specifier| {
+                            *specifier.operator() == Operator::Equal
+                                && specifier.version() == version
+                        })
+                }
+                _ => false,
+            };
+            if !pinned {
+                bail!(
+                    "The built wheel for `{}` doesn't pin its dependency on `{}` to the built version ({version}); the build backend may not read its dependencies from `pyproject.toml`",
+                    metadata.name,
+                    requirement.name,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Copy a source tree, omitting the output directory and any virtual environments or bytecode
+/// caches.
+fn copy_source_tree(source: &Path, target: &Path, out_dir: &Path) -> Result<()> {
+    let walker = walkdir::WalkDir::new(source)
+        .follow_links(true)
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.path() == out_dir
+                || (entry.file_type().is_dir()
+                    && matches!(entry.file_name().to_str(), Some(".venv" | "__pycache__"))))
+        });
+    for entry in walker {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(source)?;
+        let destination = target.join(relative);
+        if entry.file_type().is_dir() {
+            fs_err::create_dir_all(&destination)?;
+        } else {
+            fs_err::copy(entry.path(), &destination)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,474 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+use serde::Deserialize;
+use toml_edit::{value, Item, Table};
+use tracing::debug;
+
+use distribution_types::Name;
+use pypi_types::Requirement;
+use uv_cache::Cache;
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, PreviewMode};
+#[cfg(unix)]
+use uv_fs::replace_symlink;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_normalize::PackageName;
+use uv_python::{Interpreter, PythonEnvironment};
+use uv_requirements::upgrade::read_lock_requirements;
+use uv_requirements::RequirementsSpecification;
+use uv_resolver::{Lock, RequiresPython};
+use uv_tool::entrypoint_paths;
+use uv_warnings::warn_user;
+use uv_workspace::Workspace;
+
+use crate::commands::pip::loggers::{SummaryInstallLogger, SummaryResolveLogger};
+use crate::commands::project::{resolve_universal_environment, sync_environment, SharedState};
+use crate::printer::Printer;
+use crate::settings::ResolverInstallerSettings;
+
+/// The directory within the project environment that contains the development tool environments.
+const TOOLS_DIR: &str = "tools";
+
+/// The name of the lockfile for the development tools of a workspace.
+const DEV_TOOLS_LOCK: &str = ".uv-dev-tools.lock";
+
+/// The current version of the development tools lockfile format.
+const VERSION: u32 = 1;
+
+/// A lockfile for the development tools of a workspace, with a separate resolution for each tool.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct DevToolsLock {
+    /// The locked tools, keyed by package name.
+    tools: BTreeMap<PackageName, DevToolLock>,
+}
+
+/// The locked resolution for a single development tool, along with the requirement it was
+/// resolved from.
+///
+/// If the requirement changes, the locked resolution is outdated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DevToolLock {
+    requirement: String,
+    lock: Lock,
+}
+
+#[derive(Debug, Deserialize)]
+struct DevToolsLockWire {
+    version: u32,
+    #[serde(default)]
+    tools: BTreeMap<PackageName, DevToolLockWire>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DevToolLockWire {
+    requirement: String,
+    #[serde(flatten)]
+    lock: toml::Table,
+}
+
+impl DevToolsLock {
+    /// Read the lockfile for the development tools in the given workspace root, if it exists.
+    async fn read(root: &Path) -> Result<Option<Self>> {
+        let path = root.join(DEV_TOOLS_LOCK);
+        let contents = match fs_err::tokio::read_to_string(&path).await {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let lock = Self::from_toml(&contents)
+            .with_context(|| format!("Failed to parse `{}`", path.user_display()))?;
+        Ok(Some(lock))
+    }
+
+    /// Write the lockfile for the development tools in the given workspace root, removing it if
+    /// no tools are declared.
+    async fn write(&self, root: &Path) -> Result<()> {
+        let path = root.join(DEV_TOOLS_LOCK);
+        if self.tools.is_empty() {
+            debug!(
+                "Removing development tools lockfile: {}",
+                path.user_display()
+            );
+            match fs_err::tokio::remove_file(&path).await {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+            return Ok(());
+        }
+        debug!(
+            "Writing development tools lockfile to: {}",
+            path.user_display()
+        );
+        uv_fs::write_atomic(&path, self.to_toml()?).await?;
+        Ok(())
+    }
+
+    /// Deserialize the [`DevToolsLock`] from a TOML string.
+    fn from_toml(s: &str) -> Result<Self> {
+        let wire: DevToolsLockWire = toml::from_str(s)?;
+        if wire.version != VERSION {
+            bail!(
+                "Unsupported lockfile version {} (expected {VERSION})",
+                wire.version
+            );
+        }
+        let tools = wire
+            .tools
+            .into_iter()
+            .map(|(name, tool)| {
+                let lock = Lock::from_toml(&toml::to_string(&tool.lock)?)?;
+                Ok((
+                    name,
+                    DevToolLock {
+                        requirement: tool.requirement,
+                        lock,
+                    },
+                ))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { tools })
+    }
+
+    /// Returns the TOML representation of this lockfile.
+    ///
+    /// Each tool's resolution is embedded as a table with the same structure as a `uv.lock`.
+    fn to_toml(&self) -> Result<String> {
+        let mut doc = toml_edit::DocumentMut::new();
+        doc.insert("version", value(i64::from(VERSION)));
+
+        let mut tools = Table::new();
+        tools.set_implicit(true);
+        for (name, tool) in &self.tools {
+            let mut table = Table::new();
+            table.insert("requirement", value(&tool.requirement));
+            for (key, item) in tool.lock.to_toml_table()? {
+                table.insert(&key, item);
+            }
+            tools.insert(name.as_ref(), Item::Table(table));
+        }
+        doc.insert("tools", Item::Table(tools));
+
+        Ok(doc.to_string())
+    }
+}
+
+/// Install the development tools declared in `tool.uv.dev-tools` into isolated environments
+/// within the project environment, linking their executables into the project environment.
+///
+/// Each tool is resolved separately, for all platforms, and locked in the `.uv-dev-tools.lock` in
+/// the workspace root. If `frozen` is set, the locked resolutions are used as-is; if `locked` is
+/// set, they're required to be up-to-date.
+///
+/// Any tools that are no longer declared are removed, along with their executables.
+pub(crate) async fn sync_dev_tools(
+    workspace: &Workspace,
+    venv: &PythonEnvironment,
+    requires_python: Option<&RequiresPython>,
+    locked: bool,
+    frozen: bool,
+    settings: &ResolverInstallerSettings,
+    state: &SharedState,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<()> {
+    let requirements = workspace.dev_tools();
+    let tools_dir = venv.root().join(TOOLS_DIR);
+
+    // Remove any tools that were previously installed, but are no longer declared.
+    let declared = requirements
+        .iter()
+        .map(|requirement| requirement.name.clone())
+        .collect::<BTreeSet<_>>();
+    match fs_err::read_dir(&tools_dir) {
+        Ok(entries) => {
+            for entry in entries {
+                let entry = entry?;
+                let Ok(name) = PackageName::from_str(&entry.file_name().to_string_lossy()) else {
+                    continue;
+                };
+                if declared.contains(&name) {
+                    continue;
+                }
+                debug!("Removing development tool: `{name}`");
+                if let Ok(environment) = PythonEnvironment::from_root(entry.path(), cache) {
+                    remove_executables(venv, &environment, &name)?;
+                }
+                fs_err::remove_dir_all(entry.path())?;
+            }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    // Lock the tools, before installing any of them.
+    let existing = DevToolsLock::read(workspace.install_path()).await?;
+    let lock = lock_dev_tools(
+        &requirements,
+        existing.as_ref(),
+        venv.interpreter(),
+        requires_python,
+        frozen,
+        settings,
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?;
+    if !frozen && existing.clone().unwrap_or_default() != lock {
+        if locked {
+            bail!(
+                "The lockfile at `{DEV_TOOLS_LOCK}` needs to be updated, but `--locked` was provided. To update the lockfile, run `{}`.",
+                "uv sync".green()
+            );
+        }
+        lock.write(workspace.install_path()).await?;
+    }
+
+    for requirement in requirements {
+        let name = requirement.name.clone();
+        let root = tools_dir.join(name.as_ref());
+        let entry = &lock.tools[&name];
+
+        // Reuse the existing tool environment, if it's valid.
+        let environment = if let Ok(environment) = PythonEnvironment::from_root(&root, cache) {
+            environment
+        } else {
+            debug!(
+                "Creating environment for development tool `{name}`: {}",
+                root.user_display()
+            );
+            uv_virtualenv::create_venv(
+                &root,
+                venv.interpreter().clone(),
+                uv_virtualenv::Prompt::None,
+                false,
+                false,
+                false,
+            )?
+        };
+
+        // Install exactly the locked packages.
+        let resolution = entry.lock.to_resolution_for_requirements(
+            &[requirement],
+            workspace.install_path(),
+            environment.interpreter().markers(),
+            environment.interpreter().tags()?,
+            &settings.build_options,
+        )?;
+        let environment = sync_environment(
+            environment,
+            &resolution,
+            settings.as_ref().into(),
+            state,
+            Box::new(SummaryInstallLogger),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await
+        .with_context(|| format!("Failed to install development tool `{name}`"))?;
+
+        link_executables(venv, &environment, &name)?;
+    }
+
+    Ok(())
+}
+
+/// Lock the given development tools, reusing the entries of the `existing` lockfile for any tools
+/// whose requirement is unchanged (or, if `frozen` is set, for every tool).
+async fn lock_dev_tools(
+    requirements: &[Requirement],
+    existing: Option<&DevToolsLock>,
+    interpreter: &Interpreter,
+    requires_python: Option<&RequiresPython>,
+    frozen: bool,
+    settings: &ResolverInstallerSettings,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<DevToolsLock> {
+    // Lock for the project's `requires-python` or, if it doesn't declare one, for the minor
+    // version of the interpreter and newer.
+    let requires_python = requires_python.cloned().unwrap_or_else(|| {
+        RequiresPython::greater_than_equal_version(&interpreter.python_minor_version())
+    });
+
+    // Use isolated state for universal resolution, as in `uv lock`.
+    let state = SharedState::default();
+
+    let mut lock = DevToolsLock::default();
+    for requirement in requirements {
+        let name = &requirement.name;
+        let previous = existing.and_then(|existing| existing.tools.get(name));
+
+        if let Some(previous) = previous {
+            if frozen
+                || (previous.requirement == requirement.to_string() && settings.upgrade.is_none())
+            {
+                lock.tools.insert(name.clone(), previous.clone());
+                continue;
+            }
+        } else if frozen {
+            bail!(
+                "Unable to find `{name}` in `{DEV_TOOLS_LOCK}`. To lock the development tools, run `{}`.",
+                "uv sync".green()
+            );
+        }
+
+        debug!("Locking development tool: `{name}`");
+
+        // Prefer the previously locked versions, unless an upgrade was requested.
+        let preferences = previous
+            .map(|previous| read_lock_requirements(&previous.lock, &settings.upgrade).preferences)
+            .unwrap_or_default();
+
+        let spec = RequirementsSpecification::from_requirements(vec![requirement.clone()]);
+        let graph = resolve_universal_environment(
+            interpreter,
+            &requires_python,
+            spec,
+            preferences,
+            settings.as_ref().into(),
+            &state,
+            Box::new(SummaryResolveLogger),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await
+        .with_context(|| format!("Failed to lock development tool `{name}`"))?;
+
+        lock.tools.insert(
+            name.clone(),
+            DevToolLock {
+                requirement: requirement.to_string(),
+                lock: Lock::from_resolution_graph(&graph)?,
+            },
+        );
+    }
+
+    Ok(lock)
+}
+
+/// Link the executables provided by a development tool into the project environment.
+fn link_executables(
+    venv: &PythonEnvironment,
+    environment: &PythonEnvironment,
+    name: &PackageName,
+) -> Result<()> {
+    let site_packages = SitePackages::from_environment(environment)?;
+    let Some(dist) = site_packages.get_packages(name).first().copied() else {
+        return Ok(());
+    };
+
+    let entrypoints = entrypoint_paths(&site_packages, dist.name(), dist.version())?;
+    if entrypoints.is_empty() {
+        warn_user!("No executables are provided by development tool `{name}`");
+        return Ok(());
+    }
+
+    for (executable, source) in entrypoints {
+        let Some(file_name) = source.file_name() else {
+            continue;
+        };
+        let target = venv.scripts().join(file_name);
+
+        // Avoid replacing executables that were installed by the project's own dependencies.
+        if target.exists() && !is_tool_executable(&target, environment) {
+            warn_user!(
+                "Skipping executable `{executable}` from development tool `{name}`, which conflicts with an existing executable in the project environment"
+            );
+            continue;
+        }
+
+        #[cfg(unix)]
+        {
+            debug!("Linking executable for development tool `{name}`: `{executable}`");
+            replace_symlink(&source, &target).context("Failed to install executable")?;
+        }
+        #[cfg(windows)]
+        {
+            debug!("Copying executable for development tool `{name}`: `{executable}`");
+            fs_err::copy(&source, &target).context("Failed to install executable")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove the executables that were linked into the project environment for a development tool.
+fn remove_executables(
+    venv: &PythonEnvironment,
+    environment: &PythonEnvironment,
+    name: &PackageName,
+) -> Result<()> {
+    let site_packages = SitePackages::from_environment(environment)?;
+    for dist in site_packages.get_packages(name) {
+        for (executable, source) in entrypoint_paths(&site_packages, dist.name(), dist.version())? {
+            let Some(file_name) = source.file_name() else {
+                continue;
+            };
+            let target = venv.scripts().join(file_name);
+
+            // Only remove executables that point to the tool environment.
+            if !is_tool_executable(&target, environment) {
+                continue;
+            }
+
+            debug!("Removing executable for development tool `{name}`: `{executable}`");
+            match fs_err::remove_file(&target) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if the executable at `target` in the project environment was installed from the
+/// given development tool environment.
+///
+/// On Unix, executables are symlinked into the tool environment.
+#[cfg(unix)]
+fn is_tool_executable(target: &Path, environment: &PythonEnvironment) -> bool {
+    fs_err::read_link(target).is_ok_and(|link| link.starts_with(environment.root()))
+}
+
+/// Returns `true` if the executable at `target` in the project environment was installed from the
+/// given development tool environment.
+///
+/// On Windows, executables are copied from the tool environment, so they're identified by the
+/// path to the tool environment's interpreter, which is embedded in each launcher.
+#[cfg(windows)]
+fn is_tool_executable(target: &Path, environment: &PythonEnvironment) -> bool {
+    let Ok(contents) = fs_err::read(target) else {
+        return false;
+    };
+    let root = environment.root().simplified_display().to_string();
+    contents
+        .windows(root.len())
+        .any(|window| window == root.as_bytes())
+}
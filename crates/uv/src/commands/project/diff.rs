@@ -0,0 +1,397 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use itertools::{EitherOrBoth, Itertools};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use distribution_types::Name;
+use pep440_rs::Version;
+use pep508_rs::MarkerEnvironment;
+use uv_cache::Cache;
+use uv_cli::DiffFormat;
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_installer::SitePackages;
+use uv_normalize::PackageName;
+use uv_python::{EnvironmentPreference, PythonEnvironment, PythonRequest};
+use uv_resolver::Lock;
+use uv_warnings::warn_user_once;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Compare two lockfiles, or a lockfile and the current environment.
+pub(crate) fn diff(
+    old: &Path,
+    new: Option<&Path>,
+    format: DiffFormat,
+    python: Option<&str>,
+    preview: PreviewMode,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv diff` is experimental and may change without warning");
+    }
+
+    let (old, new) = if let Some(new) = new {
+        (
+            Snapshot::from_lockfile(old, None)?,
+            Snapshot::from_lockfile(new, None)?,
+        )
+    } else {
+        let environment = PythonEnvironment::find(
+            &python.map(PythonRequest::parse).unwrap_or_default(),
+            EnvironmentPreference::from_system_flag(false, false),
+            cache,
+        )?;
+
+        // Omit any locked packages that don't apply to the environment, such that packages that
+        // are excluded by their markers aren't reported as removed.
+        (
+            Snapshot::from_lockfile(old, Some(environment.interpreter().markers()))?,
+            Snapshot::from_environment(&environment)?,
+        )
+    };
+
+    let diff = Diff::between(&old, &new);
+
+    match format {
+        DiffFormat::Text => write_text(&diff, printer)?,
+        DiffFormat::Markdown => write_markdown(&diff, printer)?,
+        DiffFormat::Json => {
+            writeln!(printer.stdout(), "{}", serde_json::to_string_pretty(&diff)?)?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// The packages in a lockfile or environment, keyed by name.
+#[derive(Debug, Default)]
+struct Snapshot {
+    packages: BTreeMap<PackageName, Entry>,
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    /// The versions of the package; lockfiles may contain multiple versions for different forks.
+    versions: BTreeSet<Version>,
+    /// The sources of the package, if known.
+    sources: Option<BTreeSet<String>>,
+    /// The markers under which the package is required, if known. An empty set indicates that the
+    /// package is required unconditionally.
+    markers: Option<BTreeSet<String>>,
+}
+
+impl Snapshot {
+    /// Read a [`Snapshot`] from the lockfile at the given path.
+    ///
+    /// If a marker environment is provided, only the packages that apply to it are included.
+    fn from_lockfile(path: &Path, marker_env: Option<&MarkerEnvironment>) -> Result<Self> {
+        let contents = fs_err::read_to_string(path)?;
+        let lock = Lock::from_toml(&contents)
+            .with_context(|| format!("Failed to parse lockfile at: {}", path.user_display()))?;
+
+        let dependency_markers = lock.dependency_markers();
+
+        let locked = if let Some(marker_env) = marker_env {
+            lock.packages_for_environment(marker_env)
+        } else {
+            lock.packages().iter().collect()
+        };
+
+        let mut packages: BTreeMap<PackageName, Entry> = BTreeMap::new();
+        for package in locked {
+            let entry = packages.entry(package.name().clone()).or_default();
+            entry.versions.insert(package.version().clone());
+            entry
+                .sources
+                .get_or_insert_with(BTreeSet::new)
+                .insert(package.source_description());
+
+            // A package is required unconditionally if any edge to it is unconditional.
+            let markers = dependency_markers
+                .get(package.name())
+                .map(|markers| {
+                    markers
+                        .iter()
+                        .map(|marker| marker.and_then(|marker| marker.contents()))
+                        .collect::<Option<Vec<_>>>()
+                        .map(|markers| {
+                            markers
+                                .into_iter()
+                                .map(|marker| marker.to_string())
+                                .collect::<BTreeSet<_>>()
+                        })
+                        .unwrap_or_default()
+                })
+                .unwrap_or_default();
+            entry.markers = Some(markers);
+        }
+
+        Ok(Self { packages })
+    }
+
+    /// Read a [`Snapshot`] from the packages installed in the given environment.
+    fn from_environment(environment: &PythonEnvironment) -> Result<Self> {
+        let site_packages = SitePackages::from_environment(environment)?;
+
+        let mut packages: BTreeMap<PackageName, Entry> = BTreeMap::new();
+        for dist in site_packages.iter() {
+            packages
+                .entry(dist.name().clone())
+                .or_default()
+                .versions
+                .insert(dist.version().clone());
+        }
+
+        Ok(Self { packages })
+    }
+}
+
+/// The differences between two [`Snapshot`]s.
+#[derive(Debug, Default, Serialize)]
+struct Diff {
+    added: Vec<Change>,
+    removed: Vec<Change>,
+    changed: Vec<Change>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Change {
+    name: PackageName,
+    kind: ChangeKind,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    old_versions: Vec<Version>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    new_versions: Vec<Version>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_sources: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_sources: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_markers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_markers: Option<Vec<String>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum ChangeKind {
+    Added,
+    Removed,
+    /// The (highest) version increased.
+    Upgraded,
+    /// The (highest) version decreased.
+    Downgraded,
+    /// The versions are unchanged, but the sources or markers differ.
+    Modified,
+}
+
+impl std::fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added => write!(f, "added"),
+            Self::Removed => write!(f, "removed"),
+            Self::Upgraded => write!(f, "upgraded"),
+            Self::Downgraded => write!(f, "downgraded"),
+            Self::Modified => write!(f, "modified"),
+        }
+    }
+}
+
+impl Diff {
+    fn between(old: &Snapshot, new: &Snapshot) -> Self {
+        let mut diff = Self::default();
+
+        let entries = old
+            .packages
+            .iter()
+            .merge_join_by(&new.packages, |(old, _), (new, _)| old.cmp(new));
+
+        for entry in entries {
+            match entry {
+                EitherOrBoth::Right((name, entry)) => diff.added.push(Change {
+                    name: name.clone(),
+                    kind: ChangeKind::Added,
+                    old_versions: vec![],
+                    new_versions: entry.versions.iter().cloned().collect(),
+                    old_sources: None,
+                    new_sources: None,
+                    old_markers: None,
+                    new_markers: None,
+                }),
+                EitherOrBoth::Left((name, entry)) => diff.removed.push(Change {
+                    name: name.clone(),
+                    kind: ChangeKind::Removed,
+                    old_versions: entry.versions.iter().cloned().collect(),
+                    new_versions: vec![],
+                    old_sources: None,
+                    new_sources: None,
+                    old_markers: None,
+                    new_markers: None,
+                }),
+                EitherOrBoth::Both((name, old), (_, new)) => {
+                    // Sources and markers are only compared if they're known on both sides.
+                    let sources = match (&old.sources, &new.sources) {
+                        (Some(old), Some(new)) if old != new => Some((old, new)),
+                        _ => None,
+                    };
+                    let markers = match (&old.markers, &new.markers) {
+                        (Some(old), Some(new)) if old != new => Some((old, new)),
+                        _ => None,
+                    };
+
+                    let kind = match old.versions.last().cmp(&new.versions.last()) {
+                        std::cmp::Ordering::Less => ChangeKind::Upgraded,
+                        std::cmp::Ordering::Greater => ChangeKind::Downgraded,
+                        std::cmp::Ordering::Equal => {
+                            if old.versions == new.versions
+                                && sources.is_none()
+                                && markers.is_none()
+                            {
+                                continue;
+                            }
+                            ChangeKind::Modified
+                        }
+                    };
+
+                    diff.changed.push(Change {
+                        name: name.clone(),
+                        kind,
+                        old_versions: old.versions.iter().cloned().collect(),
+                        new_versions: new.versions.iter().cloned().collect(),
+                        old_sources: sources.map(|(old, _)| old.iter().cloned().collect()),
+                        new_sources: sources.map(|(_, new)| new.iter().cloned().collect()),
+                        old_markers: markers.map(|(old, _)| old.iter().cloned().collect()),
+                        new_markers: markers.map(|(_, new)| new.iter().cloned().collect()),
+                    });
+                }
+            }
+        }
+
+        diff
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Format a set of versions, e.g., as `1.0.0` or `1.0.0, 2.0.0`.
+fn versions(versions: &[Version]) -> String {
+    versions.iter().join(", ")
+}
+
+/// Format a set of markers, where an empty set indicates an unconditional requirement.
+fn markers(markers: &[String]) -> String {
+    if markers.is_empty() {
+        "(always)".to_string()
+    } else {
+        markers
+            .iter()
+            .map(|marker| format!("`{marker}`"))
+            .join(" or ")
+    }
+}
+
+fn write_text(diff: &Diff, printer: Printer) -> Result<()> {
+    if diff.is_empty() {
+        writeln!(printer.stderr(), "No differences found")?;
+        return Ok(());
+    }
+
+    for change in diff.added.iter().chain(&diff.removed).chain(&diff.changed) {
+        match change.kind {
+            ChangeKind::Added => writeln!(
+                printer.stdout(),
+                " {} {} {}",
+                "+".green(),
+                change.name.bold(),
+                versions(&change.new_versions)
+            )?,
+            ChangeKind::Removed => writeln!(
+                printer.stdout(),
+                " {} {} {}",
+                "-".red(),
+                change.name.bold(),
+                versions(&change.old_versions)
+            )?,
+            ChangeKind::Upgraded | ChangeKind::Downgraded | ChangeKind::Modified => {
+                if change.old_versions == change.new_versions {
+                    writeln!(
+                        printer.stdout(),
+                        " {} {} {}",
+                        "~".yellow(),
+                        change.name.bold(),
+                        versions(&change.new_versions)
+                    )?;
+                } else {
+                    writeln!(
+                        printer.stdout(),
+                        " {} {} {} -> {}",
+                        "~".yellow(),
+                        change.name.bold(),
+                        versions(&change.old_versions),
+                        versions(&change.new_versions)
+                    )?;
+                }
+                if let (Some(old), Some(new)) = (&change.old_sources, &change.new_sources) {
+                    writeln!(
+                        printer.stdout(),
+                        "     source: {} -> {}",
+                        old.join(", "),
+                        new.join(", ")
+                    )?;
+                }
+                if let (Some(old), Some(new)) = (&change.old_markers, &change.new_markers) {
+                    writeln!(
+                        printer.stdout(),
+                        "     markers: {} -> {}",
+                        markers(old),
+                        markers(new)
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_markdown(diff: &Diff, printer: Printer) -> Result<()> {
+    if diff.is_empty() {
+        writeln!(printer.stdout(), "No differences found.")?;
+        return Ok(());
+    }
+
+    writeln!(printer.stdout(), "| Package | Change | Old | New |")?;
+    writeln!(printer.stdout(), "| --- | --- | --- | --- |")?;
+    for change in diff.added.iter().chain(&diff.removed).chain(&diff.changed) {
+        let mut old = versions(&change.old_versions);
+        let mut new = versions(&change.new_versions);
+        if let (Some(old_sources), Some(new_sources)) = (&change.old_sources, &change.new_sources) {
+            write!(old, "<br>{}", old_sources.join(", "))?;
+            write!(new, "<br>{}", new_sources.join(", "))?;
+        }
+        if let (Some(old_markers), Some(new_markers)) = (&change.old_markers, &change.new_markers) {
+            write!(old, "<br>{}", markers(old_markers))?;
+            write!(new, "<br>{}", markers(new_markers))?;
+        }
+        writeln!(
+            printer.stdout(),
+            "| `{}` | {} | {} | {} |",
+            change.name,
+            change.kind,
+            old.replace('|', "\\|"),
+            new.replace('|', "\\|")
+        )?;
+    }
+
+    Ok(())
+}
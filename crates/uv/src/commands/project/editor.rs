@@ -0,0 +1,458 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tracing::debug;
+
+use uv_configuration::EditorConfig;
+use uv_fs::{PortablePath, Simplified};
+use uv_python::PythonEnvironment;
+use uv_warnings::warn_user;
+use uv_workspace::Workspace;
+
+/// The directory, relative to the workspace root and each member root, in which the environment
+/// metadata is written.
+const ENV_DIR: &str = ".uv";
+
+/// The environment metadata written to `.uv/env.json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct EnvironmentInfo<'a> {
+    /// The path to the environment's interpreter.
+    python: &'a Path,
+    /// The path to the environment.
+    environment: &'a Path,
+    /// The version of the environment's interpreter.
+    python_version: String,
+    /// The root of the workspace that owns the environment.
+    workspace_root: &'a Path,
+}
+
+/// Write the requested editor configuration for the given environment to the workspace root and
+/// the root of each workspace member.
+///
+/// The environment metadata (`.uv/env.json`) is always written; `vscode` and `pyright` update the
+/// respective configuration files in place, retaining any other settings. As those files are
+/// commonly committed, paths are written relative to the directory that contains them.
+pub(super) fn write_editor_config(
+    workspace: &Workspace,
+    venv: &PythonEnvironment,
+    editors: &[EditorConfig],
+) -> Result<()> {
+    let roots = std::iter::once(workspace.install_path().as_path())
+        .chain(
+            workspace
+                .packages()
+                .values()
+                .map(|member| member.root().as_path()),
+        )
+        .collect::<BTreeSet<_>>();
+
+    let info = EnvironmentInfo {
+        python: venv.python_executable(),
+        environment: venv.root(),
+        python_version: venv.interpreter().python_full_version().to_string(),
+        workspace_root: workspace.install_path(),
+    };
+
+    for root in roots {
+        write_env_info(root, &info)?;
+
+        for editor in editors {
+            match editor {
+                EditorConfig::Env => {}
+                EditorConfig::Vscode => {
+                    update_json(
+                        &root.join(".vscode").join("settings.json"),
+                        [(
+                            "python.defaultInterpreterPath",
+                            relative_path(venv.python_executable(), root).map_or_else(
+                                || venv.python_executable().simplified_display().to_string(),
+                                |path| format!("${{workspaceFolder}}/{path}"),
+                            ),
+                        )],
+                    )?;
+                }
+                EditorConfig::Pyright => {
+                    let (Some(parent), Some(name)) =
+                        (venv.root().parent(), venv.root().file_name())
+                    else {
+                        continue;
+                    };
+                    update_json(
+                        &root.join("pyrightconfig.json"),
+                        [
+                            (
+                                "venvPath",
+                                relative_path(parent, root)
+                                    .unwrap_or_else(|| parent.simplified_display().to_string()),
+                            ),
+                            ("venv", name.to_string_lossy().into_owned()),
+                        ],
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the environment metadata to `.uv/env.json` in the given directory.
+fn write_env_info(root: &Path, info: &EnvironmentInfo) -> Result<()> {
+    let dir = root.join(ENV_DIR);
+    fs_err::create_dir_all(&dir)?;
+
+    // Exclude the directory from version control, as in a virtual environment.
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs_err::write(&gitignore, "*")?;
+    }
+
+    let path = dir.join("env.json");
+    let contents = serde_json::to_string_pretty(info)? + "\n";
+    if fs_err::read_to_string(&path).is_ok_and(|existing| existing == contents) {
+        return Ok(());
+    }
+
+    debug!("Writing environment metadata to: {}", path.user_display());
+    uv_fs::write_atomic_sync(&path, contents)?;
+    Ok(())
+}
+
+/// Set the given keys in the JSON object at the given path, creating the file if necessary.
+///
+/// The file is edited in place: existing values are replaced, and missing keys are appended to
+/// the end of the object, such that formatting, key order, and comments (as permitted by VS Code)
+/// are retained. Files that can't be parsed as a JSON object are left unchanged.
+fn update_json<'a>(
+    path: &Path,
+    entries: impl IntoIterator<Item = (&'a str, String)>,
+) -> Result<()> {
+    let existing = match fs_err::read_to_string(path) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut contents = existing.clone().unwrap_or_else(|| "{}\n".to_string());
+    for (key, value) in entries {
+        let Some(updated) = set_key(&contents, key, &value) else {
+            warn_user!(
+                "Skipping editor configuration for `{}`, which could not be parsed as a JSON object",
+                path.user_display()
+            );
+            return Ok(());
+        };
+        contents = updated;
+    }
+    if existing.as_ref() == Some(&contents) {
+        return Ok(());
+    }
+
+    debug!("Updating editor configuration: {}", path.user_display());
+    if let Some(parent) = path.parent() {
+        fs_err::create_dir_all(parent)?;
+    }
+    uv_fs::write_atomic_sync(path, contents)
+        .with_context(|| format!("Failed to write `{}`", path.user_display()))?;
+    Ok(())
+}
+
+/// Return the path to `path`, relative to `root`, using forward slashes.
+///
+/// Returns `None` if `path` can't be expressed relative to `root` (e.g., it resides on a
+/// different drive).
+fn relative_path(path: &Path, root: &Path) -> Option<String> {
+    let relative = uv_fs::relative_to(path, root).ok()?;
+    Some(PortablePath::from(relative.as_path()).to_string())
+}
+
+/// Set `key` to the string `value` in the top-level object of the given JSON document, retaining
+/// the formatting of the rest of the document.
+///
+/// Returns `None` if the document isn't a JSON object.
+fn set_key(contents: &str, key: &str, value: &str) -> Option<String> {
+    let object = TopLevelObject::parse(contents)?;
+    let value_json = serde_json::to_string(value).ok()?;
+
+    // If the key is already present, replace its value.
+    if let Some(entry) = object.entries.iter().find(|entry| entry.key == key) {
+        let existing = &contents[entry.value.clone()];
+        if serde_json::from_str::<serde_json::Value>(existing)
+            .is_ok_and(|existing| existing.as_str() == Some(value))
+        {
+            return Some(contents.to_string());
+        }
+        return Some(format!(
+            "{}{value_json}{}",
+            &contents[..entry.value.start],
+            &contents[entry.value.end..]
+        ));
+    }
+
+    let key_json = serde_json::to_string(key).ok()?;
+    let Some(last) = object.entries.last() else {
+        // The object is empty: replace its (whitespace) contents with the new entry.
+        return Some(format!(
+            "{}\n    {key_json}: {value_json}\n{}",
+            &contents[..=object.open],
+            &contents[object.close..]
+        ));
+    };
+
+    // Otherwise, append the entry after the last value, matching the indentation of the first
+    // entry if it's on its own line.
+    let first = &object.entries[0];
+    let leading = &contents[object.open + 1..first.key_start];
+    let separator = match leading.rfind('\n') {
+        Some(index) if leading[index + 1..].chars().all(char::is_whitespace) => {
+            format!(",\n{}", &leading[index + 1..])
+        }
+        _ => ", ".to_string(),
+    };
+    Some(format!(
+        "{}{separator}{key_json}: {value_json}{}",
+        &contents[..last.value.end],
+        &contents[last.value.end..]
+    ))
+}
+
+/// An entry in the top-level object of a JSON document.
+#[derive(Debug)]
+struct Entry {
+    /// The decoded key.
+    key: String,
+    /// The byte offset at which the (quoted) key starts.
+    key_start: usize,
+    /// The byte range of the value.
+    value: std::ops::Range<usize>,
+}
+
+/// The top-level object of a JSON document, with the byte offsets of its entries.
+///
+/// Comments and trailing commas are tolerated, as in VS Code's settings files.
+#[derive(Debug)]
+struct TopLevelObject {
+    /// The byte offset of the opening brace.
+    open: usize,
+    /// The byte offset of the closing brace.
+    close: usize,
+    /// The entries of the object, in order.
+    entries: Vec<Entry>,
+}
+
+impl TopLevelObject {
+    fn parse(contents: &str) -> Option<Self> {
+        let mut scanner = Scanner {
+            bytes: contents.as_bytes(),
+            pos: 0,
+        };
+        scanner.skip_trivia()?;
+        if scanner.peek()? != b'{' {
+            return None;
+        }
+        let open = scanner.pos;
+        scanner.pos += 1;
+
+        let mut entries = Vec::new();
+        let close = loop {
+            scanner.skip_trivia()?;
+            if scanner.peek()? == b'}' {
+                break scanner.pos;
+            }
+            let key_start = scanner.pos;
+            scanner.skip_string()?;
+            let key = serde_json::from_str::<String>(&contents[key_start..scanner.pos]).ok()?;
+            scanner.skip_trivia()?;
+            if scanner.peek()? != b':' {
+                return None;
+            }
+            scanner.pos += 1;
+            scanner.skip_trivia()?;
+            let value_start = scanner.pos;
+            scanner.skip_value()?;
+            entries.push(Entry {
+                key,
+                key_start,
+                value: value_start..scanner.pos,
+            });
+            scanner.skip_trivia()?;
+            match scanner.peek()? {
+                b',' => scanner.pos += 1,
+                b'}' => break scanner.pos,
+                _ => return None,
+            }
+        };
+
+        Some(Self {
+            open,
+            close,
+            entries,
+        })
+    }
+}
+
+/// A scanner over the bytes of a JSON document.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    /// Skip any whitespace and comments.
+    fn skip_trivia(&mut self) -> Option<()> {
+        loop {
+            match (self.peek(), self.bytes.get(self.pos + 1)) {
+                (Some(byte), _) if byte.is_ascii_whitespace() => self.pos += 1,
+                (Some(b'/'), Some(b'/')) => {
+                    while self.peek().is_some_and(|byte| byte != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    self.pos += 2;
+                    while !self.bytes[self.pos..].starts_with(b"*/") {
+                        self.peek()?;
+                        self.pos += 1;
+                    }
+                    self.pos += 2;
+                }
+                _ => return Some(()),
+            }
+        }
+    }
+
+    /// Skip a string, including its quotes.
+    fn skip_string(&mut self) -> Option<()> {
+        if self.peek()? != b'"' {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            match self.peek()? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    /// Skip a value of any type, including nested objects and arrays.
+    fn skip_value(&mut self) -> Option<()> {
+        match self.peek()? {
+            b'"' => self.skip_string(),
+            b'{' | b'[' => {
+                let mut depth = 0usize;
+                loop {
+                    self.skip_trivia()?;
+                    match self.peek()? {
+                        b'"' => self.skip_string()?,
+                        b'{' | b'[' => {
+                            depth += 1;
+                            self.pos += 1;
+                        }
+                        b'}' | b']' => {
+                            depth -= 1;
+                            self.pos += 1;
+                            if depth == 0 {
+                                return Some(());
+                            }
+                        }
+                        _ => self.pos += 1,
+                    }
+                }
+            }
+            _ => {
+                let start = self.pos;
+                while self.peek().is_some_and(|byte| {
+                    !byte.is_ascii_whitespace() && !matches!(byte, b',' | b'}' | b']' | b'/')
+                }) {
+                    self.pos += 1;
+                }
+                (self.pos > start).then_some(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::set_key;
+
+    #[test]
+    fn replace() {
+        let contents = indoc::indoc! {r#"
+            {
+              // Format on save.
+              "editor.formatOnSave": true,
+              "python.defaultInterpreterPath": "/usr/bin/python3", /* system */
+              "files.exclude": { "**/.git": true },
+            }
+        "#};
+        assert_eq!(
+            set_key(
+                contents,
+                "python.defaultInterpreterPath",
+                ".venv/bin/python"
+            )
+            .unwrap(),
+            indoc::indoc! {r#"
+                {
+                  // Format on save.
+                  "editor.formatOnSave": true,
+                  "python.defaultInterpreterPath": ".venv/bin/python", /* system */
+                  "files.exclude": { "**/.git": true },
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn append() {
+        let contents = indoc::indoc! {r#"
+            {
+            	"z": [1, {"a": "}"}], // Trailing comment.
+            	"a": null
+            }
+        "#};
+        assert_eq!(
+            set_key(contents, "venv", ".venv").unwrap(),
+            indoc::indoc! {r#"
+                {
+                	"z": [1, {"a": "}"}], // Trailing comment.
+                	"a": null,
+                	"venv": ".venv"
+                }
+            "#}
+        );
+
+        assert_eq!(
+            set_key(r#"{ "a": 1 }"#, "venv", ".venv").unwrap(),
+            r#"{ "a": 1, "venv": ".venv" }"#
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            set_key("{}\n", "venv", ".venv").unwrap(),
+            "{\n    \"venv\": \".venv\"\n}\n"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(set_key("[]", "venv", ".venv").is_none());
+        assert!(set_key(r#"{ "a": }"#, "venv", ".venv").is_none());
+        assert!(set_key(r#"{ "a": 1 "#, "venv", ".venv").is_none());
+    }
+}
@@ -1,15 +1,22 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
 use tracing::debug;
 
 use cache_key::{cache_digest, hash_digest};
-use distribution_types::Resolution;
+use distribution_types::{Resolution, UnresolvedRequirement};
+use pypi_types::Requirement;
 use uv_cache::{Cache, CacheBucket};
 use uv_client::Connectivity;
 use uv_configuration::{Concurrency, PreviewMode};
 use uv_python::{Interpreter, PythonEnvironment};
 use uv_requirements::RequirementsSpecification;
+use uv_resolver::Lock;
 
 use crate::commands::pip::loggers::{InstallLogger, ResolveLogger};
-use crate::commands::project::{resolve_environment, sync_environment, ProjectError};
+use crate::commands::project::{
+    create_ephemeral_venv, resolve_environment, sync_environment, ProjectError,
+};
 use crate::commands::SharedState;
 use crate::printer::Printer;
 use crate::settings::ResolverInstallerSettings;
@@ -41,21 +48,24 @@ impl CachedEnvironment {
         cache: &Cache,
         printer: Printer,
     ) -> Result<Self, ProjectError> {
-        // When caching, always use the base interpreter, rather than that of the virtual
-        // environment.
-        let interpreter = if let Some(interpreter) = interpreter.to_base_interpreter(cache)? {
-            debug!(
-                "Caching via base interpreter: `{}`",
-                interpreter.sys_executable().display()
-            );
-            interpreter
-        } else {
-            debug!(
-                "Caching via interpreter: `{}`",
-                interpreter.sys_executable().display()
-            );
-            interpreter
-        };
+        let interpreter = Self::base_interpreter(interpreter, cache)?;
+
+        // Collect any requested extras, which are part of the environment's identity even if they
+        // don't introduce additional dependencies.
+        let extras = spec
+            .requirements
+            .iter()
+            .filter_map(|entry| match &entry.requirement {
+                UnresolvedRequirement::Named(requirement) => Some(requirement),
+                UnresolvedRequirement::Unnamed(_) => None,
+            })
+            .flat_map(|requirement| {
+                requirement
+                    .extras
+                    .iter()
+                    .map(move |extra| format!("{}[{extra}]", requirement.name))
+            })
+            .collect::<BTreeSet<_>>();
 
         // Resolve the requirements with the interpreter.
         let graph = resolve_environment(
@@ -74,12 +84,125 @@ impl CachedEnvironment {
         .await?;
         let resolution = Resolution::from(graph);
 
-        // Hash the resolution by hashing the generated lockfile.
+        Self::from_resolution(
+            &resolution,
+            &extras,
+            interpreter,
+            settings,
+            state,
+            install,
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await
+    }
+
+    /// Get or create a [`CachedEnvironment`] with the packages from a [`Lock`] that are required
+    /// by the given requirements, without resolving.
+    ///
+    /// Any local sources are resolved relative to the given install path.
+    pub(crate) async fn get_or_create_locked(
+        lock: &Lock,
+        requirements: &[Requirement],
+        install_path: &Path,
+        interpreter: Interpreter,
+        settings: &ResolverInstallerSettings,
+        state: &SharedState,
+        install: Box<dyn InstallLogger>,
+        preview: PreviewMode,
+        connectivity: Connectivity,
+        concurrency: Concurrency,
+        native_tls: bool,
+        cache: &Cache,
+        printer: Printer,
+    ) -> Result<Self, ProjectError> {
+        let interpreter = Self::base_interpreter(interpreter, cache)?;
+
+        let extras = requirements
+            .iter()
+            .flat_map(|requirement| {
+                requirement
+                    .extras
+                    .iter()
+                    .map(move |extra| format!("{}[{extra}]", requirement.name))
+            })
+            .collect::<BTreeSet<_>>();
+
+        let resolution = lock.to_resolution_for_requirements(
+            requirements,
+            install_path,
+            interpreter.markers(),
+            interpreter.tags()?,
+            &settings.build_options,
+        )?;
+
+        Self::from_resolution(
+            &resolution,
+            &extras,
+            interpreter,
+            settings,
+            state,
+            install,
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await
+    }
+
+    /// When caching, always use the base interpreter, rather than that of the virtual environment.
+    fn base_interpreter(
+        interpreter: Interpreter,
+        cache: &Cache,
+    ) -> Result<Interpreter, ProjectError> {
+        if let Some(interpreter) = interpreter.to_base_interpreter(cache)? {
+            debug!(
+                "Caching via base interpreter: `{}`",
+                interpreter.sys_executable().display()
+            );
+            Ok(interpreter)
+        } else {
+            debug!(
+                "Caching via interpreter: `{}`",
+                interpreter.sys_executable().display()
+            );
+            Ok(interpreter)
+        }
+    }
+
+    /// Get or create a [`CachedEnvironment`] with the packages in the given [`Resolution`], for the
+    /// given base interpreter.
+    async fn from_resolution(
+        resolution: &Resolution,
+        extras: &BTreeSet<String>,
+        interpreter: Interpreter,
+        settings: &ResolverInstallerSettings,
+        state: &SharedState,
+        install: Box<dyn InstallLogger>,
+        preview: PreviewMode,
+        connectivity: Connectivity,
+        concurrency: Concurrency,
+        native_tls: bool,
+        cache: &Cache,
+        printer: Printer,
+    ) -> Result<Self, ProjectError> {
+        // Hash the resolution by hashing the generated lockfile, along with the requested extras.
         // TODO(charlie): If the resolution contains any mutable metadata (like a path or URL
         // dependency), skip this step.
         let resolution_hash = {
             let distributions = resolution.distributions().collect::<Vec<_>>();
-            hash_digest(&distributions)
+            if extras.is_empty() {
+                hash_digest(&distributions)
+            } else {
+                hash_digest(&(&distributions, extras))
+            }
         };
 
         // Hash the interpreter based on its path.
@@ -91,26 +214,28 @@ impl CachedEnvironment {
 
         if settings.reinstall.is_none() {
             if let Ok(root) = fs_err::read_link(cache_entry.path()) {
-                if let Ok(environment) = PythonEnvironment::from_root(root, cache) {
-                    return Ok(Self(environment));
+                // Since the environment is relocatable, derive its interpreter from the base
+                // interpreter rather than querying it.
+                if root.join("pyvenv.cfg").is_file() {
+                    if let Ok(environment) =
+                        uv_virtualenv::relocatable_venv_at(&root, interpreter.clone())
+                    {
+                        if let Err(err) = cache.touch_environment(environment.root()) {
+                            debug!("Failed to mark cached environment as used: {err}");
+                        }
+                        return Ok(Self(environment));
+                    }
                 }
             }
         }
 
         // Create the environment in the cache, then relocate it to its content-addressed location.
         let temp_dir = cache.environment()?;
-        let venv = uv_virtualenv::create_venv(
-            temp_dir.path(),
-            interpreter,
-            uv_virtualenv::Prompt::None,
-            false,
-            false,
-            true,
-        )?;
+        let venv = create_ephemeral_venv(temp_dir.path(), interpreter, true, preview, cache)?;
 
-        sync_environment(
+        let venv = sync_environment(
             venv,
-            &resolution,
+            resolution,
             settings.as_ref().into(),
             state,
             install,
@@ -129,7 +254,11 @@ impl CachedEnvironment {
             .await?;
         let root = cache.archive(&id);
 
-        Ok(Self(PythonEnvironment::from_root(root, cache)?))
+        // Since the environment is relocatable, there's no need to query its interpreter again.
+        Ok(Self(uv_virtualenv::relocatable_venv_at(
+            &root,
+            venv.into_interpreter(),
+        )?))
     }
 
     /// Convert the [`CachedEnvironment`] into an [`Interpreter`].
@@ -0,0 +1,499 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use url::Url;
+
+use distribution_types::RemoteSource;
+use pep508_rs::MarkerTree;
+use pypi_types::{HashAlgorithm, VerbatimParsedUrl};
+use uv_cache::Cache;
+use uv_cli::ExportFormat;
+use uv_client::Connectivity;
+use uv_configuration::{
+    BuildOptions, Concurrency, ExtrasSpecification, PreviewMode, SourceStrategy,
+};
+use uv_distribution::{LoweredRequirement, RequiresDist};
+use uv_fs::{Simplified, CWD};
+use uv_normalize::{GroupName, PackageName, DEV_DEPENDENCIES};
+use uv_python::{PythonDownloads, PythonPreference, PythonRequest};
+use uv_resolver::Package;
+use uv_warnings::warn_user_once;
+use uv_workspace::{DiscoveryOptions, VirtualProject};
+
+use crate::commands::pip::loggers::DefaultResolveLogger;
+use crate::commands::project::lock::do_safe_lock;
+use crate::commands::project::{FoundInterpreter, ProjectError};
+use crate::commands::{pip, ExitStatus};
+use crate::printer::Printer;
+use crate::settings::ResolverSettings;
+
+/// Export the project's locked dependencies for use by an external build system.
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) async fn export(
+    format: ExportFormat,
+    extras: ExtrasSpecification,
+    dev: bool,
+    groups: Vec<GroupName>,
+    output_file: Option<&Path>,
+    locked: bool,
+    frozen: bool,
+    python: Option<String>,
+    settings: ResolverSettings,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv export` is experimental and may change without warning");
+    }
+
+    // Identify the project.
+    let project = VirtualProject::discover(&CWD, &DiscoveryOptions::default()).await?;
+
+    // Validate that the requested dependency groups are declared in the workspace.
+    let declared = project.workspace().groups();
+    if let Some(group) = groups.iter().find(|group| !declared.contains(*group)) {
+        return Err(ProjectError::MissingGroup(group.clone()).into());
+    }
+
+    // Include development dependencies, along with any requested dependency groups.
+    let dev = dev
+        .then(|| DEV_DEPENDENCIES.clone())
+        .into_iter()
+        .chain(groups)
+        .collect::<Vec<_>>();
+
+    // The top-level requirements are read from the manifest, without consulting the lockfile.
+    if matches!(format, ExportFormat::RequirementsIn) {
+        let requirements = direct_requirements(&project, &extras, &dev, preview).await?;
+        let contents = to_requirements_in(&requirements);
+        write_output(
+            &project,
+            output_file,
+            &contents,
+            requirements.len(),
+            printer,
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Find an interpreter for the project.
+    let interpreter = FoundInterpreter::discover(
+        project.workspace(),
+        python.as_deref().map(PythonRequest::parse),
+        python_preference,
+        python_downloads,
+        connectivity,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?
+    .into_interpreter();
+
+    let lock = match do_safe_lock(
+        locked,
+        frozen,
+        project.workspace(),
+        &interpreter,
+        None,
+        settings.as_ref(),
+        Box::new(DefaultResolveLogger),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await
+    {
+        Ok(result) => result.lock,
+        Err(ProjectError::Operation(pip::operations::Error::Resolve(
+            uv_resolver::ResolveError::NoSolution(err),
+        ))) => {
+            let report = miette::Report::msg(format!("{err}")).context(err.header());
+            anstream::eprint!("{report:?}");
+            return Ok(ExitStatus::NoSolution);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Select the packages that are required on any platform, along with all of their artifacts,
+    // such that the consumer can select the artifacts (and dependencies) for each target.
+    let graph = lock.to_dependency_graph(&project, &extras, &dev);
+
+    // Omit the workspace members themselves, which are built from source.
+    let members = project.workspace().packages();
+    let mut packages = Vec::new();
+    let mut unsupported = Vec::new();
+    for (package, dependencies) in graph {
+        if members.contains_key(package.name()) {
+            continue;
+        }
+        let Some(artifacts) = Artifact::from_package(package, &settings.build_options) else {
+            unsupported.push(package.name().to_string());
+            continue;
+        };
+        packages.push(ExportedPackage {
+            name: package.name().clone(),
+            version: package.version().to_string(),
+            artifacts,
+            dependencies: dependencies
+                .into_iter()
+                .filter(|(dependency, _)| !members.contains_key(dependency.name()))
+                .map(|(dependency, marker)| ExportedDependency {
+                    name: dependency.name().clone(),
+                    version: dependency.version().to_string(),
+                    marker: marker
+                        .and_then(MarkerTree::contents)
+                        .map(|marker| marker.to_string()),
+                })
+                .collect(),
+        });
+    }
+    if !unsupported.is_empty() {
+        bail!(
+            "Only dependencies with remote artifacts and known SHA-256 hashes can be exported, but the following do not: {}",
+            unsupported.iter().map(|name| format!("`{name}`")).join(", ")
+        );
+    }
+
+    let contents = match format {
+        ExportFormat::Nix => to_nix(&packages)?,
+        ExportFormat::Bazel => to_bazel(&packages)?,
+        ExportFormat::RequirementsIn => {
+            bail!("The `requirements.in` format is exported from the manifest, not the lockfile")
+        }
+    };
+    write_output(&project, output_file, &contents, packages.len(), printer)?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Write the exported contents to the output file, if provided, or to stdout.
+fn write_output(
+    project: &VirtualProject,
+    output_file: Option<&Path>,
+    contents: &str,
+    count: usize,
+    printer: Printer,
+) -> Result<()> {
+    if let Some(output_file) = output_file {
+        let output_file = project.workspace().install_path().join(output_file);
+        fs_err::write(&output_file, contents)?;
+        writeln!(
+            printer.stderr(),
+            "Exported {} to: {}",
+            format!("{count} package{}", if count == 1 { "" } else { "s" }).bold(),
+            output_file.user_display().cyan()
+        )?;
+    } else {
+        write!(printer.stdout(), "{contents}")?;
+    }
+    Ok(())
+}
+
+/// Collect the direct dependencies declared by the workspace members (i.e., in
+/// `project.dependencies`, the enabled `project.optional-dependencies`, and, if requested,
+/// `tool.uv.dev-dependencies` and the given dependency groups), omitting any dependencies on the
+/// members themselves.
+///
+/// The requirements are lowered against `tool.uv.sources`, such that Git, URL, and path sources
+/// are retained as direct references, and returned sorted by name and deduplicated, with any
+/// `extra` markers of the enabled extras removed.
+async fn direct_requirements(
+    project: &VirtualProject,
+    extras: &ExtrasSpecification,
+    dev: &[GroupName],
+    preview: PreviewMode,
+) -> Result<Vec<pep508_rs::Requirement<VerbatimParsedUrl>>> {
+    let workspace = project.workspace();
+    let members = workspace.packages();
+
+    let mut requirements = Vec::new();
+    for member in members.values() {
+        let metadata =
+            pypi_types::RequiresDist::parse_pyproject_toml(&member.pyproject_toml().raw)?;
+        let metadata = RequiresDist::from_project_maybe_workspace(
+            metadata,
+            member.root(),
+            workspace.install_path(),
+            SourceStrategy::Enabled,
+            preview,
+        )
+        .await?;
+
+        let enabled = match extras {
+            ExtrasSpecification::None => vec![],
+            ExtrasSpecification::All => metadata.provides_extras.clone(),
+            ExtrasSpecification::Some(extras) => extras.clone(),
+        };
+        for mut requirement in metadata.requires_dist {
+            if let Some(marker) = requirement.marker.take() {
+                if !marker.evaluate_extras(&enabled) {
+                    continue;
+                }
+                requirement.marker =
+                    Some(marker.simplify_extras(&enabled)).filter(|marker| !marker.is_true());
+            }
+            requirements.push(requirement);
+        }
+
+        for group in dev {
+            if let Some(group) = metadata.dev_dependencies.get(group) {
+                requirements.extend(group.iter().cloned());
+            }
+        }
+    }
+
+    // In a virtual workspace, the root can declare development dependencies of its own.
+    if dev.contains(&*DEV_DEPENDENCIES)
+        && !members
+            .values()
+            .any(|member| member.root() == workspace.install_path())
+    {
+        let tool_uv = workspace
+            .pyproject_toml()
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref());
+        let empty = BTreeMap::default();
+        let sources = tool_uv.and_then(|uv| uv.sources.as_ref()).unwrap_or(&empty);
+        for requirement in tool_uv
+            .and_then(|uv| uv.dev_dependencies.as_ref())
+            .into_iter()
+            .flatten()
+        {
+            let name = requirement.name.clone();
+            let requirement = LoweredRequirement::from_non_workspace_requirement(
+                requirement.clone(),
+                workspace.install_path(),
+                sources,
+                preview,
+            )
+            .with_context(|| format!("Failed to lower the development dependency `{name}`"))?;
+            requirements.push(requirement.into_inner());
+        }
+    }
+
+    let mut requirements = requirements
+        .into_iter()
+        .filter(|requirement| !members.contains_key(&requirement.name))
+        .map(pep508_rs::Requirement::<VerbatimParsedUrl>::from)
+        .collect::<Vec<_>>();
+    requirements.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| a.to_string().cmp(&b.to_string()))
+    });
+    requirements.dedup_by(|a, b| a.to_string() == b.to_string());
+    Ok(requirements)
+}
+
+/// A locked package, along with the artifacts that can be fetched for it.
+#[derive(Debug)]
+struct ExportedPackage {
+    name: PackageName,
+    version: String,
+    artifacts: Vec<Artifact>,
+    dependencies: Vec<ExportedDependency>,
+}
+
+/// A dependency of a locked package, along with the PEP 508 marker under which it applies.
+#[derive(Debug)]
+struct ExportedDependency {
+    name: PackageName,
+    version: String,
+    marker: Option<String>,
+}
+
+/// A remote artifact (i.e., a wheel or source distribution) with a known SHA-256 hash.
+#[derive(Debug)]
+struct Artifact {
+    filename: String,
+    url: Url,
+    sha256: String,
+}
+
+impl Artifact {
+    /// Determine the artifacts to fetch for a locked package, i.e., every wheel and source
+    /// distribution with a remote URL, omitting any that are disabled by `--no-binary` or
+    /// `--no-build`.
+    ///
+    /// Returns `None` if the package has no such artifacts, or if any of them lacks a SHA-256
+    /// hash.
+    fn from_package(package: &Package, build_options: &BuildOptions) -> Option<Vec<Self>> {
+        let no_binary = build_options.no_binary_package(package.name());
+        let no_build = build_options.no_build_package(package.name());
+
+        let mut artifacts = Vec::new();
+        for (url, hash) in package.remote_artifacts() {
+            let filename = url.filename().ok()?.to_string();
+            let wheel = Path::new(&filename)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("whl"));
+            if (wheel && no_binary) || (!wheel && no_build) {
+                continue;
+            }
+            let url = url.to_url();
+            if url.scheme() == "file" {
+                return None;
+            }
+            let sha256 = hash.filter(|hash| hash.algorithm() == HashAlgorithm::Sha256)?;
+            artifacts.push(Self {
+                filename,
+                url,
+                sha256: sha256.digest.to_string(),
+            });
+        }
+
+        if artifacts.is_empty() {
+            None
+        } else {
+            Some(artifacts)
+        }
+    }
+}
+
+/// The header that's prepended to the exported file.
+fn header(format: &str) -> String {
+    format!(
+        "# This file was autogenerated by uv via the following command:\n\
+         #    uv export --format {format}\n"
+    )
+}
+
+/// Render the requirements in the `requirements.in` format, one per line.
+fn to_requirements_in(requirements: &[pep508_rs::Requirement<VerbatimParsedUrl>]) -> String {
+    let mut output = header("requirements.in");
+    for requirement in requirements {
+        output.push_str(&requirement.to_string());
+        output.push('\n');
+    }
+    output
+}
+
+/// Render the packages as a Nix attribute set, keyed by package name and then by version.
+///
+/// Each artifact can be passed directly to `fetchurl`, e.g.,
+/// `fetchurl { inherit (artifact) url sha256; }`. Dependencies that only apply on some platforms
+/// include the PEP 508 marker under which they apply.
+fn to_nix(packages: &[ExportedPackage]) -> Result<String> {
+    /// Quote a value as a Nix string.
+    fn quote(value: &str) -> String {
+        format!(
+            "\"{}\"",
+            value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace("${", "\\${")
+        )
+    }
+
+    let mut output = header("nix");
+    writeln!(output, "{{")?;
+    for (name, versions) in &packages.iter().chunk_by(|package| &package.name) {
+        writeln!(output, "  {} = {{", quote(name.as_ref()))?;
+        for package in versions {
+            writeln!(output, "    {} = {{", quote(&package.version))?;
+            writeln!(output, "      artifacts = [")?;
+            for artifact in &package.artifacts {
+                writeln!(
+                    output,
+                    "        {{ filename = {}; url = {}; sha256 = {}; }}",
+                    quote(&artifact.filename),
+                    quote(artifact.url.as_str()),
+                    quote(&artifact.sha256)
+                )?;
+            }
+            writeln!(output, "      ];")?;
+            if package.dependencies.is_empty() {
+                writeln!(output, "      dependencies = [];")?;
+            } else {
+                writeln!(output, "      dependencies = [")?;
+                for dependency in &package.dependencies {
+                    write!(
+                        output,
+                        "        {{ name = {}; version = {};",
+                        quote(dependency.name.as_ref()),
+                        quote(&dependency.version)
+                    )?;
+                    if let Some(marker) = &dependency.marker {
+                        write!(output, " marker = {};", quote(marker))?;
+                    }
+                    writeln!(output, " }}")?;
+                }
+                writeln!(output, "      ];")?;
+            }
+            writeln!(output, "    }};")?;
+        }
+        writeln!(output, "  }};")?;
+    }
+    writeln!(output, "}}")?;
+    Ok(output)
+}
+
+/// Render the packages as a Starlark dictionary, keyed by package name and then by version, for
+/// use in a `.bzl` file.
+///
+/// Each artifact can be passed directly to `http_file`, e.g.,
+/// `http_file(name = ..., urls = [artifact["url"]], sha256 = artifact["sha256"])`. Dependencies
+/// that only apply on some platforms include the PEP 508 marker under which they apply.
+fn to_bazel(packages: &[ExportedPackage]) -> Result<String> {
+    /// Quote a value as a Starlark string.
+    fn quote(value: &str) -> String {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    let mut output = header("bazel");
+    writeln!(output, "PACKAGES = {{")?;
+    for (name, versions) in &packages.iter().chunk_by(|package| &package.name) {
+        writeln!(output, "    {}: {{", quote(name.as_ref()))?;
+        for package in versions {
+            writeln!(output, "        {}: {{", quote(&package.version))?;
+            writeln!(output, "            \"artifacts\": [")?;
+            for artifact in &package.artifacts {
+                writeln!(
+                    output,
+                    "                {{\"filename\": {}, \"url\": {}, \"sha256\": {}}},",
+                    quote(&artifact.filename),
+                    quote(artifact.url.as_str()),
+                    quote(&artifact.sha256)
+                )?;
+            }
+            writeln!(output, "            ],")?;
+            if package.dependencies.is_empty() {
+                writeln!(output, "            \"deps\": [],")?;
+            } else {
+                writeln!(output, "            \"deps\": [")?;
+                for dependency in &package.dependencies {
+                    write!(
+                        output,
+                        "                {{\"name\": {}, \"version\": {}",
+                        quote(dependency.name.as_ref()),
+                        quote(&dependency.version)
+                    )?;
+                    if let Some(marker) = &dependency.marker {
+                        write!(output, ", \"marker\": {}", quote(marker))?;
+                    }
+                    writeln!(output, "}},")?;
+                }
+                writeln!(output, "            ],")?;
+            }
+            writeln!(output, "        }},")?;
+        }
+        writeln!(output, "    }},")?;
+    }
+    writeln!(output, "}}")?;
+    Ok(output)
+}
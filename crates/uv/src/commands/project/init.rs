@@ -7,6 +7,7 @@ use owo_colors::OwoColorize;
 use pep440_rs::Version;
 use pep508_rs::PackageName;
 use uv_cache::Cache;
+use uv_cli::BuildBackend;
 use uv_client::{BaseClientBuilder, Connectivity};
 use uv_configuration::PreviewMode;
 use uv_fs::{absolutize_path, Simplified, CWD};
@@ -33,6 +34,7 @@ pub(crate) async fn init(
     no_readme: bool,
     python: Option<String>,
     no_workspace: bool,
+    build_backend: BuildBackend,
     preview: PreviewMode,
     python_preference: PythonPreference,
     python_downloads: PythonDownloads,
@@ -85,6 +87,7 @@ pub(crate) async fn init(
             no_readme,
             python,
             no_workspace,
+            build_backend,
             python_preference,
             python_downloads,
             connectivity,
@@ -159,6 +162,7 @@ async fn init_project(
     no_readme: bool,
     python: Option<String>,
     no_workspace: bool,
+    build_backend: BuildBackend,
     python_preference: PythonPreference,
     python_downloads: PythonDownloads,
     connectivity: Connectivity,
@@ -271,30 +275,17 @@ async fn init_project(
         requires-python = "{requires_python}"
         dependencies = []
 
-        [build-system]
-        requires = ["hatchling"]
-        build-backend = "hatchling.build"
-        "#,
+        {build_system}"#,
         readme = if no_readme { "" } else { "\nreadme = \"README.md\"" },
         requires_python = requires_python.specifiers(),
+        build_system = build_system(build_backend, name),
     };
 
     fs_err::create_dir_all(path)?;
     fs_err::write(path.join("pyproject.toml"), pyproject)?;
 
-    // Create `src/{name}/__init__.py`, if it doesn't exist already.
-    let src_dir = path.join("src").join(&*name.as_dist_info_name());
-    let init_py = src_dir.join("__init__.py");
-    if !init_py.try_exists()? {
-        fs_err::create_dir_all(&src_dir)?;
-        fs_err::write(
-            init_py,
-            indoc::formatdoc! {r#"
-            def hello() -> str:
-                return "Hello from {name}!"
-            "#},
-        )?;
-    }
+    // Create the package sources, if they don't exist already.
+    init_sources(path, name, build_backend)?;
 
     if let Some(workspace) = workspace {
         if workspace.excludes(path)? {
@@ -335,3 +326,175 @@ async fn init_project(
 
     Ok(())
 }
+
+/// Return the `[build-system]` table (and any backend-specific configuration) for a project.
+fn build_system(build_backend: BuildBackend, name: &PackageName) -> String {
+    let module = name.as_dist_info_name();
+    match build_backend {
+        BuildBackend::Hatchling => indoc::indoc! {r#"
+            [build-system]
+            requires = ["hatchling"]
+            build-backend = "hatchling.build"
+        "#}
+        .to_string(),
+        BuildBackend::Setuptools => indoc::indoc! {r#"
+            [build-system]
+            requires = ["setuptools>=61"]
+            build-backend = "setuptools.build_meta"
+        "#}
+        .to_string(),
+        BuildBackend::Flit => indoc::indoc! {r#"
+            [build-system]
+            requires = ["flit_core>=3.2,<4"]
+            build-backend = "flit_core.buildapi"
+        "#}
+        .to_string(),
+        BuildBackend::Maturin => indoc::formatdoc! {r#"
+            [tool.maturin]
+            module-name = "{module}._core"
+            python-packages = ["{module}"]
+            python-source = "src"
+
+            [build-system]
+            requires = ["maturin>=1.0,<2.0"]
+            build-backend = "maturin"
+        "#},
+        BuildBackend::ScikitBuildCore => indoc::indoc! {r#"
+            [tool.scikit-build]
+            minimum-version = "build-system.requires"
+            build-dir = "build/{wheel_tag}"
+
+            [build-system]
+            requires = ["scikit-build-core>=0.10", "pybind11"]
+            build-backend = "scikit_build_core.build"
+        "#}
+        .to_string(),
+    }
+}
+
+/// Write a file, if it doesn't exist already.
+fn write_if_missing(path: &Path, contents: &str) -> Result<()> {
+    if !path.try_exists()? {
+        if let Some(parent) = path.parent() {
+            fs_err::create_dir_all(parent)?;
+        }
+        fs_err::write(path, contents)?;
+    }
+    Ok(())
+}
+
+/// Create the package sources for a project, including the scaffolding for an extension module
+/// when using `maturin` or `scikit-build-core`.
+fn init_sources(path: &Path, name: &PackageName, build_backend: BuildBackend) -> Result<()> {
+    let module = name.as_dist_info_name();
+    let src_dir = path.join("src");
+    let package_dir = src_dir.join(&*module);
+
+    match build_backend {
+        BuildBackend::Hatchling | BuildBackend::Setuptools | BuildBackend::Flit => {
+            write_if_missing(
+                &package_dir.join("__init__.py"),
+                &indoc::formatdoc! {r#"
+                def hello() -> str:
+                    return "Hello from {name}!"
+                "#},
+            )?;
+        }
+        BuildBackend::Maturin | BuildBackend::ScikitBuildCore => {
+            write_if_missing(
+                &package_dir.join("__init__.py"),
+                &indoc::formatdoc! {r#"
+                from {module}._core import hello_from_bin
+
+
+                def hello() -> str:
+                    return hello_from_bin()
+                "#},
+            )?;
+            write_if_missing(
+                &package_dir.join("_core.pyi"),
+                indoc::indoc! {r"
+                def hello_from_bin() -> str: ...
+                "},
+            )?;
+        }
+    }
+
+    match build_backend {
+        BuildBackend::Hatchling | BuildBackend::Setuptools | BuildBackend::Flit => {}
+        BuildBackend::Maturin => {
+            write_if_missing(
+                &path.join("Cargo.toml"),
+                &indoc::formatdoc! {r#"
+                [package]
+                name = "{name}"
+                version = "0.1.0"
+                edition = "2021"
+
+                [lib]
+                name = "_core"
+                # "cdylib" is necessary to produce a shared library for Python to import from.
+                crate-type = ["cdylib"]
+
+                [dependencies]
+                # "extension-module" tells PyO3 to build an extension module, which skips linking
+                # against `libpython`.
+                pyo3 = {{ version = "0.22.2", features = ["extension-module"] }}
+                "#},
+            )?;
+            write_if_missing(
+                &src_dir.join("lib.rs"),
+                &indoc::formatdoc! {r#"
+                use pyo3::prelude::*;
+
+                #[pyfunction]
+                fn hello_from_bin() -> String {{
+                    "Hello from {name}!".to_string()
+                }}
+
+                /// A Python module implemented in Rust. The name of this function must match the
+                /// `lib.name` setting in the `Cargo.toml`, else Python won't be able to import the
+                /// module.
+                #[pymodule]
+                fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {{
+                    m.add_function(wrap_pyfunction!(hello_from_bin, m)?)?;
+                    Ok(())
+                }}
+                "#},
+            )?;
+        }
+        BuildBackend::ScikitBuildCore => {
+            write_if_missing(
+                &path.join("CMakeLists.txt"),
+                &indoc::formatdoc! {r"
+                cmake_minimum_required(VERSION 3.15)
+                project(${{SKBUILD_PROJECT_NAME}} LANGUAGES CXX)
+
+                set(PYBIND11_FINDPYTHON ON)
+                find_package(pybind11 CONFIG REQUIRED)
+
+                pybind11_add_module(_core MODULE src/main.cpp)
+                install(TARGETS _core DESTINATION {module})
+                "},
+            )?;
+            write_if_missing(
+                &src_dir.join("main.cpp"),
+                &indoc::formatdoc! {r#"
+                #include <pybind11/pybind11.h>
+
+                std::string hello_from_bin() {{ return "Hello from {name}!"; }}
+
+                namespace py = pybind11;
+
+                PYBIND11_MODULE(_core, m) {{
+                  m.def("hello_from_bin", &hello_from_bin, R"pbdoc(
+                      Return a greeting from the extension module.
+                  )pbdoc");
+                }}
+                "#},
+            )?;
+        }
+    }
+
+    Ok(())
+}
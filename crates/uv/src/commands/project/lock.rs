@@ -1,33 +1,51 @@
 #![allow(clippy::single_match_else)]
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Write;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use anstream::eprint;
+use anyhow::Context;
+use chrono::Utc;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 use rustc_hash::{FxBuildHasher, FxHashMap};
 use tracing::debug;
 
-use distribution_types::{Diagnostic, UnresolvedRequirementSpecification};
+use cache_key::CanonicalUrl;
+use distribution_types::{
+    Diagnostic, DistributionMetadata, Name, Resolution, UnresolvedRequirementSpecification,
+    VersionOrUrlRef,
+};
 use pep440_rs::Version;
+use pypi_types::{HashAlgorithm, HashDigest, Requirement};
 use uv_auth::store_credentials_from_url;
+use uv_build::SourceBuild;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndexClient, RegistryClientBuilder};
-use uv_configuration::{Concurrency, ExtrasSpecification, PreviewMode, Reinstall, SetupPyStrategy};
+use uv_client::{Connectivity, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_configuration::{
+    Concurrency, ExtrasSpecification, PackageBuildEnv, PreviewMode, Reinstall, SetupPyStrategy,
+    SysPlatform, Upgrade,
+};
 use uv_dispatch::BuildDispatch;
-use uv_fs::CWD;
+use uv_fs::{Simplified, CWD};
 use uv_git::ResolvedRepositoryReference;
-use uv_normalize::{PackageName, DEV_DEPENDENCIES};
+use uv_normalize::PackageName;
 use uv_python::{Interpreter, PythonDownloads, PythonEnvironment, PythonPreference, PythonRequest};
 use uv_requirements::upgrade::{read_lock_requirements, LockedRequirements};
 use uv_resolver::{
-    FlatIndex, Lock, OptionsBuilder, PythonRequirement, RequiresPython, ResolverMarkers,
+    BuildDependency, FlatIndex, Lock, Options, OptionsBuilder, Package, PythonExclusion,
+    PythonRequirement, RequiresPython, ResolutionGraph, ResolverMarkers,
 };
 use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy};
 use uv_warnings::{warn_user, warn_user_once};
+use uv_workspace::pyproject::Source;
 use uv_workspace::{DiscoveryOptions, Workspace};
 
 use crate::commands::pip::loggers::{DefaultResolveLogger, ResolveLogger, SummaryResolveLogger};
+use crate::commands::project::script_lock::lock_scripts;
 use crate::commands::project::{find_requires_python, FoundInterpreter, ProjectError, SharedState};
 use crate::commands::{pip, ExitStatus};
 use crate::printer::Printer;
@@ -40,12 +58,22 @@ pub(crate) struct LockResult {
     pub(crate) previous: Option<Lock>,
     /// The updated lock.
     pub(crate) lock: Lock,
+    /// The packages for which newer versions were excluded due to the supported Python range, if
+    /// the project was re-resolved.
+    pub(crate) python_exclusions: Vec<PythonExclusion>,
 }
 
 /// Resolve the project requirements into a lockfile.
+#[allow(clippy::fn_params_excessive_bools)]
 pub(crate) async fn lock(
     locked: bool,
     frozen: bool,
+    verify_determinism: Option<u32>,
+    shuffle: bool,
+    prune_platforms: Option<Vec<SysPlatform>>,
+    suggest_requires_python: bool,
+    restore: bool,
+    script: Option<PathBuf>,
     python: Option<String>,
     settings: ResolverSettings,
     preview: PreviewMode,
@@ -61,9 +89,34 @@ pub(crate) async fn lock(
         warn_user_once!("`uv lock` is experimental and may change without warning");
     }
 
+    // If requested, lock the given scripts rather than the project.
+    if let Some(script) = script {
+        return lock_scripts(
+            &script,
+            locked,
+            frozen,
+            python,
+            settings.as_ref(),
+            preview,
+            python_preference,
+            python_downloads,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await;
+    }
+
     // Find the project requirements.
     let workspace = Workspace::discover(&CWD, &DiscoveryOptions::default()).await?;
 
+    // If requested, revert to the most recent backup of the lockfile, without resolving.
+    if restore {
+        return restore_backup(&workspace, printer).await;
+    }
+
     // Find an interpreter for the project
     let interpreter = FoundInterpreter::discover(
         &workspace,
@@ -78,12 +131,42 @@ pub(crate) async fn lock(
     .await?
     .into_interpreter();
 
+    // If requested, audit the resolution rather than updating the lockfile.
+    if let Some(runs) = verify_determinism {
+        return match do_verify_determinism(
+            &workspace,
+            &interpreter,
+            runs,
+            shuffle,
+            settings.as_ref(),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await
+        {
+            Ok(status) => Ok(status),
+            Err(ProjectError::Operation(pip::operations::Error::Resolve(
+                uv_resolver::ResolveError::NoSolution(err),
+            ))) => {
+                let report = miette::Report::msg(format!("{err}")).context(err.header());
+                eprint!("{report:?}");
+                Ok(ExitStatus::NoSolution)
+            }
+            Err(err) => Err(err.into()),
+        };
+    }
+
     // Perform the lock operation.
     match do_safe_lock(
         locked,
         frozen,
         &workspace,
         &interpreter,
+        prune_platforms.as_deref(),
         settings.as_ref(),
         Box::new(DefaultResolveLogger),
         preview,
@@ -99,6 +182,13 @@ pub(crate) async fn lock(
             if let Some(previous) = lock.previous.as_ref() {
                 report_upgrades(previous, &lock.lock, printer)?;
             }
+            if suggest_requires_python {
+                self::suggest_requires_python(
+                    &lock.python_exclusions,
+                    lock.lock.requires_python(),
+                    printer,
+                )?;
+            }
             Ok(ExitStatus::Success)
         }
         Err(ProjectError::Operation(pip::operations::Error::Resolve(
@@ -106,18 +196,22 @@ pub(crate) async fn lock(
         ))) => {
             let report = miette::Report::msg(format!("{err}")).context(err.header());
             eprint!("{report:?}");
-            Ok(ExitStatus::Failure)
+            Ok(ExitStatus::NoSolution)
         }
         Err(err) => Err(err.into()),
     }
 }
 
 /// Perform a lock operation, respecting the `--locked` and `--frozen` parameters.
-pub(super) async fn do_safe_lock(
+///
+/// If `prune_platforms` is `None`, the platforms pruned from the existing lockfile (if any) are
+/// retained.
+pub(crate) async fn do_safe_lock(
     locked: bool,
     frozen: bool,
     workspace: &Workspace,
     interpreter: &Interpreter,
+    prune_platforms: Option<&[SysPlatform]>,
     settings: ResolverSettingsRef<'_>,
     logger: Box<dyn ResolveLogger>,
     preview: PreviewMode,
@@ -143,9 +237,17 @@ pub(super) async fn do_safe_lock(
         let existing = read(workspace)
             .await?
             .ok_or_else(|| ProjectError::MissingLockfile)?;
+
+        // Even if the lockfile is used as-is, enforce any hashes declared in the workspace.
+        validate_source_hashes(workspace, &existing)?;
+
+        // Since the lockfile isn't re-resolved, warn if it was generated with other settings.
+        warn_on_settings_drift(&existing, workspace, settings);
+
         Ok(LockResult {
             previous: None,
             lock: existing,
+            python_exclusions: vec![],
         })
     } else if locked {
         // Read the existing lockfile.
@@ -154,13 +256,15 @@ pub(super) async fn do_safe_lock(
             .ok_or_else(|| ProjectError::MissingLockfile)?;
 
         // Perform the lock operation, but don't write the lockfile to disk.
-        let lock = do_lock(
+        let (lock, python_exclusions) = do_lock(
             workspace,
             interpreter,
             Some(&existing),
+            prune_platforms,
             settings,
             &state,
             logger,
+            None,
             preview,
             connectivity,
             concurrency,
@@ -170,6 +274,8 @@ pub(super) async fn do_safe_lock(
         )
         .await?;
 
+        validate_source_hashes(workspace, &lock)?;
+
         // If the locks disagree, return an error.
         if lock != existing {
             return Err(ProjectError::LockMismatch);
@@ -178,19 +284,22 @@ pub(super) async fn do_safe_lock(
         Ok(LockResult {
             previous: Some(existing),
             lock,
+            python_exclusions,
         })
     } else {
         // Read the existing lockfile.
         let existing = read(workspace).await?;
 
         // Perform the lock operation.
-        let lock = do_lock(
+        let (lock, python_exclusions) = do_lock(
             workspace,
             interpreter,
             existing.as_ref(),
+            prune_platforms,
             settings,
             &state,
             logger,
+            None,
             preview,
             connectivity,
             concurrency,
@@ -200,6 +309,8 @@ pub(super) async fn do_safe_lock(
         )
         .await?;
 
+        validate_source_hashes(workspace, &lock)?;
+
         if !existing.as_ref().is_some_and(|existing| *existing == lock) {
             commit(&lock, workspace).await?;
         }
@@ -207,25 +318,162 @@ pub(super) async fn do_safe_lock(
         Ok(LockResult {
             previous: existing,
             lock,
+            python_exclusions,
         })
     }
 }
 
+/// Resolve the project from scratch the given number of times, reporting any packages that differ
+/// between the resulting lockfiles.
+///
+/// Any existing lockfile is ignored (and left unchanged), such that no resolution is guided by
+/// preferences. Each resolution also uses its own in-memory state.
+async fn do_verify_determinism(
+    workspace: &Workspace,
+    interpreter: &Interpreter,
+    runs: u32,
+    shuffle: bool,
+    settings: ResolverSettingsRef<'_>,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus, ProjectError> {
+    let mut baseline: Option<Lock> = None;
+    let mut mismatches = 0;
+    for run in 1..=runs {
+        debug!("Performing resolution {run} of {runs}");
+        let (lock, _) = do_lock(
+            workspace,
+            interpreter,
+            None,
+            None,
+            settings,
+            &SharedState::default(),
+            Box::new(SummaryResolveLogger),
+            shuffle.then_some(u64::from(run)),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+
+        let Some(baseline) = baseline.as_ref() else {
+            baseline = Some(lock);
+            continue;
+        };
+        if lock == *baseline {
+            continue;
+        }
+
+        mismatches += 1;
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!("Resolution {run} differs from resolution 1:").bold()
+        )?;
+        let differences = lock_differences(baseline, &lock);
+        if differences.is_empty() {
+            writeln!(printer.stderr(), "  The resolution markers differ")?;
+        }
+        for difference in differences {
+            writeln!(printer.stderr(), "  {difference}")?;
+        }
+    }
+
+    if mismatches == 0 {
+        writeln!(
+            printer.stderr(),
+            "Resolved the project {runs} times with identical results"
+        )?;
+        Ok(ExitStatus::Success)
+    } else {
+        writeln!(
+            printer.stderr(),
+            "{}: {mismatches} of {runs} resolutions differed from the first",
+            "error".red().bold(),
+        )?;
+        Ok(ExitStatus::Failure)
+    }
+}
+
+/// Describe the packages that differ between two lockfiles, e.g., `foo: v1.0.0 -> v1.1.0`.
+fn lock_differences(expected: &Lock, actual: &Lock) -> Vec<String> {
+    /// Group the packages in a lockfile by name.
+    fn by_name(lock: &Lock) -> BTreeMap<&PackageName, Vec<&Package>> {
+        let mut packages = BTreeMap::<_, Vec<_>>::new();
+        for package in lock.packages() {
+            packages.entry(package.name()).or_default().push(package);
+        }
+        packages
+    }
+
+    /// Format the versions of the given packages, e.g., `v1.0.0, v1.1.0`.
+    fn versions(packages: Option<&Vec<&Package>>) -> String {
+        let Some(packages) = packages else {
+            return "(missing)".to_string();
+        };
+        packages
+            .iter()
+            .map(|package| package.version())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|version| format!("v{version}"))
+            .join(", ")
+    }
+
+    let expected = by_name(expected);
+    let actual = by_name(actual);
+    expected
+        .keys()
+        .chain(actual.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|name| {
+            let (before, after) = (expected.get(*name), actual.get(*name));
+            if before == after {
+                return None;
+            }
+            let (before, after) = (versions(before), versions(after));
+            if before == after {
+                Some(format!(
+                    "{name} {before} (the sources, artifacts, or dependencies differ)"
+                ))
+            } else {
+                Some(format!("{name} {before} -> {after}"))
+            }
+        })
+        .collect()
+}
+
 /// Lock the project requirements into a lockfile.
-async fn do_lock(
+///
+/// If a `shuffle` seed is provided, the resolver considers candidate packages in a pseudo-random
+/// order derived from the seed, rather than in the order in which they're discovered.
+///
+/// If `prune_platforms` is `None`, the platforms pruned from the existing lock (if any) are
+/// retained.
+pub(super) async fn do_lock(
     workspace: &Workspace,
     interpreter: &Interpreter,
     existing_lock: Option<&Lock>,
+    prune_platforms: Option<&[SysPlatform]>,
     settings: ResolverSettingsRef<'_>,
     state: &SharedState,
     logger: Box<dyn ResolveLogger>,
+    shuffle: Option<u64>,
     preview: PreviewMode,
     connectivity: Connectivity,
     concurrency: Concurrency,
     native_tls: bool,
     cache: &Cache,
     printer: Printer,
-) -> Result<Lock, ProjectError> {
+) -> Result<(Lock, Vec<PythonExclusion>), ProjectError> {
     // Extract the project settings.
     let ResolverSettingsRef {
         index_locations,
@@ -254,8 +502,23 @@ async fn do_lock(
         .into_iter()
         .map(UnresolvedRequirementSpecification::from)
         .collect::<Vec<_>>();
+    let override_dependencies = Lock::recorded_overrides(&workspace.overrides());
     let constraints = workspace.constraints();
-    let dev = vec![DEV_DEPENDENCIES.clone()];
+    let build_env = PackageBuildEnv::from(workspace.build_env());
+    let indexes = Lock::recorded_indexes(index_locations);
+    let prune_platforms = prune_platforms
+        .map(|platforms| {
+            platforms
+                .iter()
+                .copied()
+                .sorted()
+                .dedup()
+                .collect::<Vec<_>>()
+        })
+        .or_else(|| existing_lock.map(|lock| lock.pruned_platforms().to_vec()))
+        .unwrap_or_default();
+    // Lock every dependency group declared in the workspace.
+    let dev = workspace.groups().into_iter().collect::<Vec<_>>();
     let source_trees = vec![];
 
     // Determine the supported Python range. If no range is defined, and warn and default to the
@@ -317,6 +580,7 @@ async fn do_lock(
         .prerelease_mode(prerelease)
         .exclude_newer(exclude_newer)
         .index_strategy(index_strategy)
+        .shuffle(shuffle)
         .build();
     let hasher = HashStrategy::Generate;
 
@@ -333,6 +597,18 @@ async fn do_lock(
         FlatIndex::from_entries(entries, None, &hasher, build_options)
     };
 
+    // If the indexes changed, warn, since the resolution may change as a result (e.g., if the
+    // indexes differ between collaborators).
+    if let Some(lock) = existing_lock {
+        if lock.indexes() != indexes {
+            warn_user!(
+                "The lockfile was generated with different indexes ({}) than are currently configured ({})",
+                display_indexes(lock.indexes()),
+                display_indexes(&indexes)
+            );
+        }
+    }
+
     // If any of the resolution-determining settings changed, invalidate the lock.
     let existing_lock = existing_lock.filter(|lock| {
         if lock.resolution_mode() != options.resolution_mode {
@@ -353,6 +629,15 @@ async fn do_lock(
             );
             return false;
         }
+        if lock.index_strategy() != options.index_strategy {
+            let _ = writeln!(
+                printer.stderr(),
+                "Ignoring existing lockfile due to change in index strategy: `{}` vs. `{}`",
+                lock.index_strategy().cyan(),
+                options.index_strategy.cyan()
+            );
+            return false;
+        }
         match (lock.exclude_newer(), options.exclude_newer) {
             (None, None) => (),
             (Some(existing), Some(provided)) if existing == provided => (),
@@ -385,6 +670,43 @@ async fn do_lock(
         true
     });
 
+    // If the pruned platforms changed, the existing lockfile may be missing the dependencies and
+    // artifacts for some platforms, so its metadata can't be reused. Its versions are still
+    // preferred, though.
+    let prune_platforms_changed =
+        existing_lock.is_some_and(|lock| lock.pruned_platforms() != prune_platforms.as_slice());
+    if prune_platforms_changed {
+        let _ = writeln!(
+            printer.stderr(),
+            "Re-resolving due to change in pruned platforms"
+        );
+    }
+
+    // If the build environment variables of any packages changed, discard their locked versions,
+    // since their metadata may depend on the variables.
+    let upgrade = &match existing_lock {
+        Some(lock) => {
+            let digests = Lock::build_env_digests(&build_env);
+            let changed = lock
+                .build_env()
+                .keys()
+                .chain(digests.keys())
+                .filter(|name| lock.build_env().get(*name) != digests.get(*name))
+                .cloned()
+                .collect::<BTreeSet<_>>();
+            if !changed.is_empty() {
+                let _ =
+                    writeln!(
+                    printer.stderr(),
+                    "Ignoring locked versions of {} due to change in build environment variables",
+                    changed.iter().map(|name| format!("`{}`", name.cyan())).join(", ")
+                );
+            }
+            upgrade.clone().with_packages(changed)
+        }
+        None => upgrade.clone(),
+    };
+
     // If an existing lockfile exists, build up a set of preferences.
     let LockedRequirements { preferences, git } = existing_lock
         .as_ref()
@@ -427,7 +749,7 @@ async fn do_lock(
         existing_lock.and_then(|lock| lock.fork_markers().clone())
     });
 
-    let resolution = match existing_lock.filter(|_| upgrade.is_none()) {
+    let resolution = match existing_lock.filter(|_| upgrade.is_none() && !prune_platforms_changed) {
         None => None,
 
         // Try to resolve using metadata in the lockfile.
@@ -462,12 +784,15 @@ async fn do_lock(
                 sources,
                 concurrency,
                 preview,
-            );
+            )
+            .with_package_build_env(build_env.clone());
 
-            // Resolve the requirements.
+            // Resolve the requirements. User-level constraints are omitted, such that the lockfile
+            // is reproducible across machines.
             pip::operations::resolve(
                 requirements.clone(),
                 constraints.clone(),
+                None,
                 overrides.clone(),
                 dev.clone(),
                 source_trees.clone(),
@@ -487,6 +812,7 @@ async fn do_lock(
                 &build_dispatch,
                 concurrency,
                 options,
+                None,
                 Box::new(SummaryResolveLogger),
                 printer,
                 preview,
@@ -540,12 +866,14 @@ async fn do_lock(
                 sources,
                 concurrency,
                 preview,
-            );
+            )
+            .with_package_build_env(build_env.clone());
 
             // Resolve the requirements.
             pip::operations::resolve(
                 requirements,
                 constraints,
+                None,
                 overrides,
                 dev,
                 source_trees,
@@ -558,13 +886,14 @@ async fn do_lock(
                 upgrade,
                 None,
                 resolver_markers,
-                python_requirement,
+                python_requirement.clone(),
                 &client,
                 &flat_index,
                 &state.index,
                 &build_dispatch,
                 concurrency,
                 options,
+                None,
                 Box::new(SummaryResolveLogger),
                 printer,
                 preview,
@@ -579,16 +908,385 @@ async fn do_lock(
     // Notify the user of any resolution diagnostics.
     pip::operations::diagnose_resolution(resolution.diagnostics(), printer)?;
 
-    Ok(Lock::from_resolution_graph(&resolution)?)
+    // Explain any newer versions that were excluded due to the supported Python range.
+    report_python_exclusions(&resolution, printer)?;
+    let python_exclusions = resolution.python_exclusions().to_vec();
+
+    let mut lock = Lock::from_resolution_graph(&resolution)?
+        .with_build_env(&build_env)
+        .with_indexes(indexes)
+        .with_override_dependencies(override_dependencies)
+        .with_pruned_platforms(prune_platforms);
+
+    // Lock the build requirements of any workspace members that opt in.
+    for (name, member) in workspace.packages() {
+        if !member
+            .pyproject_toml()
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.lock_build_requirements)
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        let requirements = SourceBuild::build_system_requirements(member.root())
+            .map_err(|err| ProjectError::BuildRequirements(name.clone(), err.into()))?;
+
+        // Prefer the existing pins, unless they're being upgraded.
+        let preferences = existing_lock
+            .and_then(|lock| {
+                lock.packages()
+                    .iter()
+                    .find(|package| package.name() == name)
+            })
+            .map(|package| {
+                package
+                    .build_dependencies()
+                    .iter()
+                    .filter(|dependency| !upgrade.contains(dependency.name()))
+                    .map(BuildDependency::to_requirement)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let build_dependencies = if preferences.is_empty() {
+            None
+        } else {
+            resolve_build_requirements(
+                &requirements,
+                &preferences,
+                &python_requirement,
+                interpreter,
+                &client,
+                &flat_index,
+                build_isolation,
+                settings,
+                options,
+                concurrency,
+                cache,
+                preview,
+            )
+            .await
+            .inspect_err(|err| debug!("Resolving build requirements with `uv.lock` failed: {err}"))
+            .ok()
+        };
+        let build_dependencies = match build_dependencies {
+            Some(build_dependencies) => build_dependencies,
+            None => resolve_build_requirements(
+                &requirements,
+                &[],
+                &python_requirement,
+                interpreter,
+                &client,
+                &flat_index,
+                build_isolation,
+                settings,
+                options,
+                concurrency,
+                cache,
+                preview,
+            )
+            .await
+            .map_err(|err| ProjectError::BuildRequirements(name.clone(), err))?,
+        };
+        lock = lock.with_build_dependencies(name, build_dependencies);
+    }
+
+    Ok((lock, python_exclusions))
 }
 
+/// Explain the packages for which newer versions were excluded from the resolution, as they
+/// require a Python version outside of the project's supported range.
+fn report_python_exclusions(
+    resolution: &ResolutionGraph,
+    printer: Printer,
+) -> Result<(), std::fmt::Error> {
+    let Some(requires_python) = resolution.requires_python() else {
+        return Ok(());
+    };
+    for exclusion in resolution.python_exclusions() {
+        writeln!(
+            printer.stderr(),
+            "{}{} {} was excluded because it requires Python {}, but the project supports Python {} (using {})",
+            "hint".bold().cyan(),
+            ":".bold(),
+            format!("{} v{}", exclusion.name(), exclusion.excluded()).bold(),
+            exclusion.requires_python().cyan(),
+            requires_python.cyan(),
+            format!("v{}", exclusion.selected()).bold(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Suggest a `requires-python` lower bound that would permit the newest versions of any packages
+/// that were excluded due to the supported Python range.
+pub(crate) fn suggest_requires_python(
+    exclusions: &[PythonExclusion],
+    requires_python: Option<&RequiresPython>,
+    printer: Printer,
+) -> Result<(), std::fmt::Error> {
+    // Ignore any exclusions that can't be resolved by raising the lower bound (e.g., if a package
+    // dropped support for newer Python versions).
+    let bounds = exclusions
+        .iter()
+        .filter_map(|exclusion| {
+            let bound = RequiresPython::from_specifiers(exclusion.requires_python())
+                .ok()?
+                .bound()
+                .clone();
+            requires_python
+                .map_or(true, |requires_python| bound > *requires_python.bound())
+                .then_some((exclusion, bound))
+        })
+        .collect::<Vec<_>>();
+
+    let Some((_, bound)) = bounds.iter().max_by(|(_, a), (_, b)| a.cmp(b)) else {
+        return Ok(());
+    };
+    let (Bound::Included(version) | Bound::Excluded(version)) = &**bound else {
+        return Ok(());
+    };
+
+    writeln!(
+        printer.stderr(),
+        "{}{} To use the newest versions of {}, raise the project's `requires-python` to `{}`",
+        "hint".bold().cyan(),
+        ":".bold(),
+        bounds
+            .iter()
+            .map(|(exclusion, _)| exclusion.name().bold().to_string())
+            .join(", "),
+        RequiresPython::greater_than_equal_version(version).green(),
+    )?;
+    Ok(())
+}
+
+/// Resolve the build requirements of a workspace member, subject to the given constraints.
+///
+/// Like the project itself, the build requirements are resolved universally, such that the locked
+/// versions are valid on every supported platform and Python version. Since a single version is
+/// recorded for each build dependency, a resolution that selects multiple versions of a package
+/// for different environments is rejected.
+async fn resolve_build_requirements(
+    requirements: &[Requirement],
+    constraints: &[Requirement],
+    python_requirement: &PythonRequirement,
+    interpreter: &Interpreter,
+    client: &RegistryClient,
+    flat_index: &FlatIndex,
+    build_isolation: BuildIsolation<'_>,
+    settings: ResolverSettingsRef<'_>,
+    options: Options,
+    concurrency: Concurrency,
+    cache: &Cache,
+    preview: PreviewMode,
+) -> anyhow::Result<Vec<BuildDependency>> {
+    // Avoid sharing state with the project's resolution, since the build requirements are
+    // resolved independently.
+    let state = SharedState::default();
+    let build_dispatch = BuildDispatch::new(
+        client,
+        cache,
+        &[],
+        interpreter,
+        settings.index_locations,
+        flat_index,
+        &state.index,
+        &state.git,
+        &state.in_flight,
+        settings.index_strategy,
+        SetupPyStrategy::default(),
+        settings.config_setting,
+        build_isolation,
+        settings.link_mode,
+        settings.build_options,
+        settings.exclude_newer,
+        settings.sources,
+        concurrency,
+        preview,
+    );
+
+    let graph = pip::operations::resolve(
+        requirements
+            .iter()
+            .cloned()
+            .map(UnresolvedRequirementSpecification::from)
+            .collect(),
+        constraints.to_vec(),
+        None,
+        vec![],
+        vec![],
+        vec![],
+        None,
+        &ExtrasSpecification::None,
+        vec![],
+        EmptyInstalledPackages,
+        &HashStrategy::None,
+        &Reinstall::default(),
+        &Upgrade::default(),
+        None,
+        ResolverMarkers::universal(None),
+        python_requirement.clone(),
+        client,
+        flat_index,
+        &state.index,
+        &build_dispatch,
+        concurrency,
+        options,
+        None,
+        Box::new(DefaultResolveLogger),
+        Printer::Quiet,
+        preview,
+    )
+    .await?;
+
+    let mut build_dependencies = Vec::new();
+    for dist in Resolution::from(&graph).distributions() {
+        if graph.indexes(dist.name()).count() > 1 {
+            anyhow::bail!(
+                "The build requirements resolve to different versions of `{}` for different environments, which can't be locked",
+                dist.name()
+            );
+        }
+
+        // Direct URL requirements are already pinned by the `build-system.requires` itself.
+        if let VersionOrUrlRef::Version(version) = dist.version_or_url() {
+            build_dependencies.push(BuildDependency::new(dist.name().clone(), version.clone()));
+        }
+    }
+    Ok(build_dependencies)
+}
+
+/// The directory, relative to the workspace root, in which backups of the lockfile are stored.
+const LOCK_BACKUP_DIR: &str = ".uv/lock-backups";
+
 /// Write the lockfile to disk.
+///
+/// The lockfile is written atomically. If `tool.uv.lock-backups` is set, the existing lockfile (if
+/// any) is first copied to a timestamped backup.
 pub(crate) async fn commit(lock: &Lock, workspace: &Workspace) -> Result<(), ProjectError> {
     let encoded = lock.to_toml()?;
-    fs_err::tokio::write(workspace.install_path().join("uv.lock"), encoded).await?;
+    let path = workspace.install_path().join("uv.lock");
+
+    let retain = workspace.lock_backups();
+    if retain > 0 {
+        backup(
+            &path,
+            &workspace.install_path().join(LOCK_BACKUP_DIR),
+            retain,
+        )
+        .await?;
+    }
+
+    uv_fs::write_atomic(&path, encoded).await?;
     Ok(())
 }
 
+/// Copy the lockfile at the given path to a timestamped backup in the given directory, then remove
+/// all but the `retain` most recent backups.
+///
+/// Returns the path to the backup, if the lockfile exists.
+async fn backup(path: &Path, dir: &Path, retain: usize) -> Result<Option<PathBuf>, ProjectError> {
+    let existing = match fs_err::tokio::read(path).await {
+        Ok(existing) => existing,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    fs_err::tokio::create_dir_all(dir).await?;
+
+    // Exclude the backups from version control.
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs_err::tokio::write(&gitignore, "*").await?;
+    }
+
+    // Use a fixed-width timestamp, such that the backups sort chronologically.
+    let backup = dir.join(format!(
+        "uv.lock.{}",
+        Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    debug!("Backing up lockfile to: {}", backup.user_display());
+    uv_fs::write_atomic(&backup, existing).await?;
+
+    for stale in lock_backups(dir)?.iter().rev().skip(retain) {
+        debug!("Removing stale lockfile backup: {}", stale.user_display());
+        fs_err::tokio::remove_file(stale).await?;
+    }
+
+    Ok(Some(backup))
+}
+
+/// Return the lockfile backups in the given directory, from oldest to newest.
+fn lock_backups(dir: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
+    let entries = match fs_err::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut backups = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("uv.lock."))
+        })
+        .collect::<Vec<_>>();
+    backups.sort();
+    Ok(backups)
+}
+
+/// Replace the lockfile with its most recent backup, removing the backup.
+///
+/// The current lockfile is itself backed up first, such that the restore can be reverted.
+async fn restore_backup(workspace: &Workspace, printer: Printer) -> anyhow::Result<ExitStatus> {
+    let dir = workspace.install_path().join(LOCK_BACKUP_DIR);
+    let Some(restored) = lock_backups(&dir)?.pop() else {
+        anyhow::bail!(
+            "No lockfile backups found in `{}` (hint: set `tool.uv.lock-backups` to back up the lockfile when it's updated)",
+            dir.user_display()
+        );
+    };
+
+    // Ensure that the backup is a valid lockfile before replacing the existing lockfile.
+    let encoded = fs_err::tokio::read_to_string(&restored).await?;
+    Lock::from_toml(&encoded).with_context(|| {
+        format!(
+            "Failed to parse lockfile backup: `{}`",
+            restored.user_display()
+        )
+    })?;
+
+    // Back up the current lockfile, retaining the restored backup until it's been written.
+    let path = workspace.install_path().join("uv.lock");
+    let retain = workspace.lock_backups().max(1);
+    let previous = backup(&path, &dir, retain + 1).await?;
+
+    uv_fs::write_atomic(&path, encoded).await?;
+    fs_err::tokio::remove_file(&restored).await?;
+
+    if let Some(previous) = previous {
+        writeln!(
+            printer.stderr(),
+            "Backed up the current `{}` to: `{}`",
+            "uv.lock".cyan(),
+            previous.user_display().cyan()
+        )?;
+    }
+    writeln!(
+        printer.stderr(),
+        "Restored `{}` from backup: `{}`",
+        "uv.lock".cyan(),
+        restored.user_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
 /// Read the lockfile from the workspace.
 ///
 /// Returns `Ok(None)` if the lockfile does not exist.
@@ -606,8 +1304,110 @@ pub(crate) async fn read(workspace: &Workspace) -> Result<Option<Lock>, ProjectE
     }
 }
 
+/// Verify that any URL sources pinned to a hash (i.e., `{ url = "...", hash = "sha256:..." }` in
+/// `tool.uv.sources`) match the URLs and hashes recorded in the lockfile, along with any hashes
+/// embedded in the fragments of direct URLs (e.g., `foo @ https://...#sha256=...`).
+///
+/// Since the locked hashes are in turn enforced when installing, this ensures that the installed
+/// archive is the one declared in the `pyproject.toml`.
+fn validate_source_hashes(workspace: &Workspace, lock: &Lock) -> Result<(), ProjectError> {
+    // Collect the sources from the workspace root, along with those of each member.
+    let root_sources = (
+        workspace.install_path().join("pyproject.toml"),
+        workspace.sources(),
+    );
+    let member_sources = workspace.packages().values().filter_map(|member| {
+        let sources = member
+            .pyproject_toml()
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.sources.as_ref())?;
+        Some((member.root().join("pyproject.toml"), sources))
+    });
+
+    for (path, sources) in std::iter::once(root_sources).chain(member_sources) {
+        for (name, source) in sources {
+            let Source::Url {
+                url,
+                hash: Some(hash),
+                ..
+            } = source
+            else {
+                continue;
+            };
+
+            let expected = HashDigest::from_str(hash)
+                .map_err(|err| ProjectError::SourceHash(name.clone(), path.clone(), err))?;
+            if expected.algorithm != HashAlgorithm::Sha256 {
+                return Err(ProjectError::SourceHashAlgorithm(
+                    name.clone(),
+                    path.clone(),
+                    hash.clone(),
+                ));
+            }
+
+            let canonical_url = CanonicalUrl::new(url);
+            for package in lock.packages() {
+                if package.name() != name {
+                    continue;
+                }
+                // A package locked from any other source (e.g., a stale lockfile that predates a
+                // change to the URL) can't satisfy the pinned hash.
+                if !package
+                    .direct_url()
+                    .is_some_and(|locked| CanonicalUrl::new(&locked) == canonical_url)
+                {
+                    return Err(ProjectError::SourceUrlMismatch(
+                        name.clone(),
+                        path.clone(),
+                        format!("direct+{url}"),
+                        package.source_description(),
+                    ));
+                }
+                let actual = package.hashes();
+                if !actual.contains(&expected) {
+                    return Err(ProjectError::SourceHashMismatch(
+                        name.clone(),
+                        path.clone(),
+                        expected,
+                        actual.iter().map(|hash| format!("  {hash}")).join("\n"),
+                    ));
+                }
+            }
+        }
+    }
+
+    // Hashes in URL fragments are verified against the archive that was locked.
+    for package in lock.packages() {
+        let Some(url) = package.direct_url() else {
+            continue;
+        };
+        let Some(expected) = HashStrategy::url_digest(&url)
+            .map_err(|err| ProjectError::UrlHash(package.name().clone(), url.to_string(), err))?
+        else {
+            continue;
+        };
+        let actual = package.hashes();
+        if !actual.contains(&expected) {
+            return Err(ProjectError::UrlHashMismatch(
+                package.name().clone(),
+                url.to_string(),
+                expected,
+                actual.iter().map(|hash| format!("  {hash}")).join("\n"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 /// Reports on the versions that were upgraded in the new lockfile.
-fn report_upgrades(existing_lock: &Lock, new_lock: &Lock, printer: Printer) -> anyhow::Result<()> {
+pub(super) fn report_upgrades(
+    existing_lock: &Lock,
+    new_lock: &Lock,
+    printer: Printer,
+) -> anyhow::Result<()> {
     let existing_packages: FxHashMap<&PackageName, BTreeSet<&Version>> =
         existing_lock.packages().iter().fold(
             FxHashMap::with_capacity_and_hasher(existing_lock.packages().len(), FxBuildHasher),
@@ -687,3 +1487,63 @@ fn report_upgrades(existing_lock: &Lock, new_lock: &Lock, printer: Printer) -> a
 
     Ok(())
 }
+
+/// Warn if the settings recorded in the lockfile differ from the current configuration.
+///
+/// When the lockfile is used as-is (i.e., with `--frozen`), any such differences would otherwise go
+/// unnoticed, e.g., if collaborators have configured different indexes.
+fn warn_on_settings_drift(lock: &Lock, workspace: &Workspace, settings: ResolverSettingsRef<'_>) {
+    if lock.resolution_mode() != settings.resolution {
+        warn_user!(
+            "The lockfile was generated with a different resolution mode (`{}`) than is currently configured (`{}`)",
+            lock.resolution_mode(),
+            settings.resolution
+        );
+    }
+    if lock.prerelease_mode() != settings.prerelease {
+        warn_user!(
+            "The lockfile was generated with a different pre-release mode (`{}`) than is currently configured (`{}`)",
+            lock.prerelease_mode(),
+            settings.prerelease
+        );
+    }
+    if lock.exclude_newer() != settings.exclude_newer {
+        warn_user!(
+            "The lockfile was generated with a different timestamp cutoff (`{}`) than is currently configured (`{}`)",
+            lock.exclude_newer()
+                .map_or_else(|| "none".to_string(), |exclude_newer| exclude_newer.to_string()),
+            settings
+                .exclude_newer
+                .map_or_else(|| "none".to_string(), |exclude_newer| exclude_newer.to_string())
+        );
+    }
+    if lock.index_strategy() != settings.index_strategy {
+        warn_user!(
+            "The lockfile was generated with a different index strategy (`{}`) than is currently configured (`{}`)",
+            lock.index_strategy(),
+            settings.index_strategy
+        );
+    }
+    let indexes = Lock::recorded_indexes(settings.index_locations);
+    if lock.indexes() != indexes {
+        warn_user!(
+            "The lockfile was generated with different indexes ({}) than are currently configured ({})",
+            display_indexes(lock.indexes()),
+            display_indexes(&indexes)
+        );
+    }
+    if lock.override_dependencies() != Lock::recorded_overrides(&workspace.overrides()) {
+        warn_user!(
+            "The lockfile was generated with different `override-dependencies` than are declared in the workspace; run `uv lock` to update it"
+        );
+    }
+}
+
+/// Format the indexes recorded in a lockfile for display.
+fn display_indexes(indexes: &[String]) -> String {
+    if indexes.is_empty() {
+        "the default index".to_string()
+    } else {
+        indexes.iter().map(|index| format!("`{index}`")).join(", ")
+    }
+}
@@ -1,5 +1,5 @@
 use std::fmt::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use itertools::Itertools;
 use owo_colors::OwoColorize;
@@ -7,45 +7,61 @@ use tracing::debug;
 
 use distribution_types::{Resolution, UnresolvedRequirementSpecification};
 use pep440_rs::{Version, VersionSpecifiers};
-use pypi_types::Requirement;
+use pypi_types::{HashDigest, Requirement};
 use uv_auth::store_credentials_from_url;
-use uv_cache::Cache;
+use uv_cache::{Cache, CacheBucket};
 use uv_client::{BaseClientBuilder, Connectivity, FlatIndexClient, RegistryClientBuilder};
 use uv_configuration::{
-    Concurrency, ExtrasSpecification, PreviewMode, Reinstall, SetupPyStrategy, Upgrade,
+    AbiCheck, Concurrency, ExtrasSpecification, FileConflicts, PreviewMode, Reinstall,
+    SetupPyStrategy, Upgrade,
 };
 use uv_dispatch::BuildDispatch;
 use uv_distribution::DistributionDatabase;
 use uv_fs::Simplified;
 use uv_installer::{SatisfiesResult, SitePackages};
-use uv_normalize::PackageName;
+use uv_normalize::{GroupName, PackageName};
 use uv_python::{
-    request_from_version_file, EnvironmentPreference, Interpreter, PythonDownloads,
-    PythonEnvironment, PythonInstallation, PythonPreference, PythonRequest, VersionRequest,
+    find_python_installations, request_from_version_file, EnvironmentPreference, Interpreter,
+    PythonDownloads, PythonEnvironment, PythonInstallation, PythonPreference, PythonRequest,
+    Target, VersionRequest,
 };
 use uv_requirements::{NamedRequirementsResolver, RequirementsSpecification};
 use uv_resolver::{
-    FlatIndex, OptionsBuilder, PythonRequirement, RequiresPython, ResolutionGraph, ResolverMarkers,
+    FlatIndex, OptionsBuilder, Preference, PythonRequirement, RequiresPython, ResolutionGraph,
+    ResolverMarkers,
 };
 use uv_types::{BuildIsolation, EmptyInstalledPackages, HashStrategy};
 use uv_warnings::{warn_user, warn_user_once};
+use uv_workspace::pyproject::RequiresImplementation;
 use uv_workspace::Workspace;
 
 use crate::commands::pip::loggers::{InstallLogger, ResolveLogger};
 use crate::commands::pip::operations::Modifications;
 use crate::commands::reporters::{PythonDownloadReporter, ResolverReporter};
-use crate::commands::{pip, SharedState};
+use crate::commands::{elapsed, lock_environment_path, pip, wait_for_environment, SharedState};
+use crate::environments;
 use crate::printer::Printer;
 use crate::settings::{InstallerSettingsRef, ResolverInstallerSettings, ResolverSettingsRef};
 
 pub(crate) mod add;
+pub(crate) mod build;
+pub(crate) mod dev_tools;
+pub(crate) mod diff;
+mod editor;
 pub(crate) mod environment;
+pub(crate) mod export;
 pub(crate) mod init;
 pub(crate) mod lock;
 pub(crate) mod remove;
 pub(crate) mod run;
+mod script_lock;
+mod scripts;
+pub(crate) mod server;
 pub(crate) mod sync;
+pub(crate) mod test;
 pub(crate) mod tree;
+pub(crate) mod vendor;
+pub(crate) mod why;
 
 #[derive(thiserror::Error, Debug)]
 pub(crate) enum ProjectError {
@@ -63,6 +79,9 @@ pub(crate) enum ProjectError {
     #[error("The requested Python interpreter ({0}) is incompatible with the project Python requirement: `{1}`")]
     RequestedPythonIncompatibility(Version, RequiresPython),
 
+    #[error("The requested Python interpreter ({0}) is incompatible with the project's supported implementations: {1}. Use `{flag}` to request a compatible interpreter.", flag = "--python".green())]
+    RequestedImplementationIncompatibility(String, RequiresImplementation),
+
     #[error("The requested Python interpreter ({0}) is incompatible with the project Python requirement: `{1}`. However, a workspace member (`{member}`) supports Python {3}. To install the workspace member on its own, navigate to `{path}`, then run `{venv}` followed by `{install}`.", member = _2.cyan(), venv = format!("uv venv --python {_0}").green(), install = "uv pip install -e .".green(), path = _4.user_display().cyan() )]
     RequestedMemberPythonIncompatibility(
         Version,
@@ -72,6 +91,53 @@ pub(crate) enum ProjectError {
         PathBuf,
     ),
 
+    #[error("Hash mismatch for `{0}`, which is pinned to a hash in `{1}`\n\nExpected:\n  {2}\n\nComputed:\n{3}", _1.user_display().cyan())]
+    SourceHashMismatch(PackageName, PathBuf, HashDigest, String),
+
+    #[error("`{0}` is pinned to a hash in `{1}`, but the lockfile contains a different source\n\nExpected:\n  {2}\n\nLocked:\n  {3}", _1.user_display().cyan())]
+    SourceUrlMismatch(PackageName, PathBuf, String, String),
+
+    #[error("Invalid hash for `{0}` in `{1}`: only `sha256` hashes are supported for URL sources, but found: `{2}`", _1.user_display().cyan())]
+    SourceHashAlgorithm(PackageName, PathBuf, String),
+
+    #[error("Hash mismatch for `{0}`, which is pinned to a hash in its URL (`{1}`)\n\nExpected:\n  {2}\n\nComputed:\n{3}")]
+    UrlHashMismatch(PackageName, String, HashDigest, String),
+
+    #[error("Invalid hash in the URL of `{0}`: `{1}`")]
+    UrlHash(PackageName, String, #[source] pypi_types::HashError),
+
+    #[error("Invalid hash for `{0}` in `{1}`", _1.user_display().cyan())]
+    SourceHash(PackageName, PathBuf, #[source] pypi_types::HashError),
+
+    #[error("The lockfile at `uv.lock` doesn't contain the build requirements of `{0}`. To lock them, set `tool.uv.lock-build-requirements = true` in its `pyproject.toml`, then run `uv lock`.")]
+    MissingBuildRequirements(PackageName),
+
+    #[error("Failed to lock the build requirements of `{0}`")]
+    BuildRequirements(PackageName, #[source] anyhow::Error),
+
+    #[error("No environment named `{0}` is defined in `tool.uv.envs`")]
+    MissingNamedEnvironment(String),
+
+    #[error("Dependency group `{0}` is not defined in the `dependency-groups` table of any workspace member")]
+    MissingGroup(GroupName),
+
+    #[error("Invalid environment name `{0}`: names may only contain letters, numbers, hyphens, and underscores")]
+    InvalidEnvironmentName(String),
+
+    #[error(
+        "Invalid script name `{0}` in `tool.uv.scripts`: names may not contain path separators"
+    )]
+    InvalidScriptName(String),
+
+    #[error("The script `{0}` declared in `tool.uv.scripts` was not found at: `{}`", _1.user_display())]
+    MissingScript(String, PathBuf),
+
+    #[error("Invalid script name `{0}` in `tool.uv.scripts`: the name is reserved by the virtual environment")]
+    ReservedScriptName(String),
+
+    #[error("The script `{0}` declared in `tool.uv.scripts` conflicts with an existing file: `{}`", _1.user_display())]
+    ScriptConflict(String, PathBuf),
+
     #[error(transparent)]
     Python(#[from] uv_python::Error),
 
@@ -102,6 +168,9 @@ pub(crate) enum ProjectError {
     #[error(transparent)]
     Tool(#[from] uv_tool::Error),
 
+    #[error(transparent)]
+    Typosquat(#[from] uv_requirements::PossibleTyposquat),
+
     #[error(transparent)]
     Fmt(#[from] std::fmt::Error),
 
@@ -128,12 +197,9 @@ pub(crate) fn find_requires_python(
     }))
 }
 
-/// Find the virtual environment for the current project.
-fn find_environment(
-    workspace: &Workspace,
-    cache: &Cache,
-) -> Result<PythonEnvironment, uv_python::Error> {
-    PythonEnvironment::from_root(workspace.venv(), cache)
+/// Find the virtual environment at the given root.
+fn find_environment(venv: &Path, cache: &Cache) -> Result<PythonEnvironment, uv_python::Error> {
+    PythonEnvironment::from_root(venv, cache)
 }
 
 /// Check if the given interpreter satisfies the project's requirements.
@@ -154,6 +220,52 @@ fn interpreter_meets_requirements(
     }
 }
 
+/// Check if the given interpreter is one of the project's supported implementations, as declared
+/// in `tool.uv.requires-implementation`.
+fn implementation_satisfies(
+    interpreter: &Interpreter,
+    requires_implementation: &RequiresImplementation,
+) -> bool {
+    requires_implementation.allows(
+        interpreter.implementation_name(),
+        interpreter.gil_disabled(),
+    )
+}
+
+/// Find an installed interpreter that satisfies both the Python request and the project's
+/// supported implementations, skipping any that are excluded by `tool.uv.requires-implementation`.
+fn find_installed_implementation(
+    python_request: &PythonRequest,
+    requires_implementation: &RequiresImplementation,
+    python_preference: PythonPreference,
+    cache: &Cache,
+) -> Result<Option<PythonInstallation>, ProjectError> {
+    for result in find_python_installations(
+        python_request,
+        EnvironmentPreference::OnlySystem,
+        python_preference,
+        cache,
+    ) {
+        let installation = match result {
+            Ok(Ok(installation)) => installation,
+            Ok(Err(_)) => continue,
+            Err(err) if err.is_critical() => return Err(uv_python::Error::from(err).into()),
+            Err(err) => {
+                debug!("Skipping interpreter due to discovery error: {err}");
+                continue;
+            }
+        };
+        if implementation_satisfies(installation.interpreter(), requires_implementation) {
+            return Ok(Some(installation));
+        }
+        debug!(
+            "Skipping interpreter at `{}`: does not meet the project's implementation requirement: {requires_implementation}",
+            installation.interpreter().sys_executable().user_display()
+        );
+    }
+    Ok(None)
+}
+
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum FoundInterpreter {
@@ -213,6 +325,36 @@ impl FoundInterpreter {
         native_tls: bool,
         cache: &Cache,
         printer: Printer,
+    ) -> Result<Self, ProjectError> {
+        // If the project installs its dependencies into a `__pypackages__` directory, ignore any
+        // existing virtual environment.
+        let venv = workspace.venv();
+        Self::discover_at(
+            workspace,
+            (!workspace.pypackages()).then_some(venv.as_path()),
+            python_request,
+            python_preference,
+            python_downloads,
+            connectivity,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await
+    }
+
+    /// Discover the interpreter to use in the current [`Workspace`], preferring the virtual
+    /// environment at the given root, if any.
+    async fn discover_at(
+        workspace: &Workspace,
+        venv: Option<&Path>,
+        python_request: Option<PythonRequest>,
+        python_preference: PythonPreference,
+        python_downloads: PythonDownloads,
+        connectivity: Connectivity,
+        native_tls: bool,
+        cache: &Cache,
+        printer: Printer,
     ) -> Result<Self, ProjectError> {
         // Resolve the Python request and requirement for the workspace.
         let WorkspacePython {
@@ -220,35 +362,45 @@ impl FoundInterpreter {
             requires_python,
         } = WorkspacePython::from_request(python_request, workspace).await?;
 
-        // Read from the virtual environment first.
-        match find_environment(workspace, cache) {
-            Ok(venv) => {
-                if interpreter_meets_requirements(
-                    venv.interpreter(),
-                    python_request.as_ref(),
-                    cache,
-                ) {
-                    if let Some(requires_python) = requires_python.as_ref() {
-                        if requires_python.contains(venv.interpreter().python_version()) {
+        // Read from the virtual environment first, if any.
+        if let Some(venv) = venv {
+            match find_environment(venv, cache) {
+                Ok(venv) => {
+                    if interpreter_meets_requirements(
+                        venv.interpreter(),
+                        python_request.as_ref(),
+                        cache,
+                    ) {
+                        if let Some(requires_implementation) =
+                            workspace.requires_implementation().filter(|requires| {
+                                !implementation_satisfies(venv.interpreter(), requires)
+                            })
+                        {
+                            debug!(
+                                "Interpreter does not meet the project's implementation requirement: {requires_implementation}"
+                            );
+                        } else if let Some(requires_python) = requires_python.as_ref() {
+                            if requires_python.contains(venv.interpreter().python_version()) {
+                                return Ok(Self::Environment(venv));
+                            }
+                            debug!(
+                                "Interpreter does not meet the project's Python requirement: `{requires_python}`"
+                            );
+                        } else {
                             return Ok(Self::Environment(venv));
                         }
-                        debug!(
-                            "Interpreter does not meet the project's Python requirement: `{requires_python}`"
-                        );
-                    } else {
-                        return Ok(Self::Environment(venv));
                     }
                 }
-            }
-            Err(uv_python::Error::MissingEnvironment(_)) => {}
-            Err(uv_python::Error::Query(uv_python::InterpreterError::NotFound(path))) => {
-                warn_user!(
-                    "Ignoring existing virtual environment linked to non-existent Python interpreter: {}",
-                    path.user_display().cyan()
-                );
-            }
-            Err(err) => return Err(err.into()),
-        };
+                Err(uv_python::Error::MissingEnvironment(_)) => {}
+                Err(uv_python::Error::Query(uv_python::InterpreterError::NotFound(path))) => {
+                    warn_user!(
+                        "Ignoring existing virtual environment linked to non-existent Python interpreter: {}",
+                        path.user_display().cyan()
+                    );
+                }
+                Err(err) => return Err(err.into()),
+            };
+        }
 
         let client_builder = BaseClientBuilder::default()
             .connectivity(connectivity)
@@ -256,17 +408,34 @@ impl FoundInterpreter {
 
         let reporter = PythonDownloadReporter::single(printer);
 
+        // If the project restricts its implementations, prefer an installed interpreter that
+        // satisfies the restriction over the first one that satisfies the request.
+        let installed = if let Some(requires_implementation) = workspace.requires_implementation() {
+            find_installed_implementation(
+                &python_request.clone().unwrap_or_default(),
+                requires_implementation,
+                python_preference,
+                cache,
+            )?
+        } else {
+            None
+        };
+
         // Locate the Python interpreter to use in the environment
-        let python = PythonInstallation::find_or_download(
-            python_request,
-            EnvironmentPreference::OnlySystem,
-            python_preference,
-            python_downloads,
-            &client_builder,
-            cache,
-            Some(&reporter),
-        )
-        .await?;
+        let python = if let Some(python) = installed {
+            python
+        } else {
+            PythonInstallation::find_or_download(
+                python_request,
+                EnvironmentPreference::OnlySystem,
+                python_preference,
+                python_downloads,
+                &client_builder,
+                cache,
+                Some(&reporter),
+            )
+            .await?
+        };
 
         let managed = python.source().is_managed();
         let interpreter = python.into_interpreter();
@@ -318,6 +487,24 @@ impl FoundInterpreter {
             }
         }
 
+        if let Some(requires_implementation) = workspace.requires_implementation() {
+            if !implementation_satisfies(&interpreter, requires_implementation) {
+                return Err(ProjectError::RequestedImplementationIncompatibility(
+                    format!(
+                        "{} {}{}",
+                        interpreter.implementation_name(),
+                        interpreter.python_version(),
+                        if interpreter.gil_disabled() {
+                            ", free-threaded"
+                        } else {
+                            ""
+                        }
+                    ),
+                    requires_implementation.clone(),
+                ));
+            }
+        }
+
         Ok(Self::Interpreter(interpreter))
     }
 
@@ -341,8 +528,79 @@ pub(crate) async fn get_or_init_environment(
     cache: &Cache,
     printer: Printer,
 ) -> Result<PythonEnvironment, ProjectError> {
-    match FoundInterpreter::discover(
+    // If requested, install into the `__pypackages__` directory for the discovered interpreter,
+    // in lieu of a virtual environment.
+    if workspace.pypackages() {
+        let interpreter = FoundInterpreter::discover(
+            workspace,
+            python,
+            python_preference,
+            python_downloads,
+            connectivity,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?
+        .into_interpreter();
+
+        let root = workspace.pypackages_dir();
+        let target = Target::from(
+            root.join(format!(
+                "{}.{}",
+                interpreter.python_major(),
+                interpreter.python_minor()
+            ))
+            .join("lib"),
+        );
+        debug!(
+            "Using `__pypackages__` directory at: {}",
+            target.root().user_display()
+        );
+
+        // Create a `.gitignore` file to ignore all files in the directory, as for a virtual
+        // environment.
+        fs_err::create_dir_all(&root)?;
+        let gitignore = root.join(".gitignore");
+        if !gitignore.exists() {
+            fs_err::write(gitignore, "*")?;
+        }
+
+        return Ok(PythonEnvironment::from_interpreter(interpreter).with_target(target)?);
+    }
+
+    get_or_init_environment_at(
+        workspace,
+        workspace.venv(),
+        python,
+        python_preference,
+        python_downloads,
+        connectivity,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await
+}
+
+/// Initialize the virtual environment at the given root for the current project.
+pub(crate) async fn get_or_init_environment_at(
+    workspace: &Workspace,
+    venv: PathBuf,
+    python: Option<PythonRequest>,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    connectivity: Connectivity,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<PythonEnvironment, ProjectError> {
+    // Prevent concurrent processes from creating or removing the environment at the same time.
+    let _lock = lock_environment_path(&venv).await?;
+
+    match FoundInterpreter::discover_at(
         workspace,
+        Some(&venv),
         python,
         python_preference,
         python_downloads,
@@ -358,10 +616,10 @@ pub(crate) async fn get_or_init_environment(
 
         // Otherwise, create a virtual environment with the discovered interpreter.
         FoundInterpreter::Interpreter(interpreter) => {
-            let venv = workspace.venv();
-
-            // Remove the existing virtual environment if it doesn't meet the requirements.
-            match fs_err::remove_dir_all(&venv) {
+            // Remove the existing virtual environment if it doesn't meet the requirements, once
+            // any in-progress modifications to it have finished.
+            wait_for_environment(&venv).await?;
+            match uv_fs::force_remove_dir_all(&venv) {
                 Ok(()) => {
                     writeln!(
                         printer.stderr(),
@@ -379,18 +637,213 @@ pub(crate) async fn get_or_init_environment(
                 venv.user_display().cyan()
             )?;
 
-            Ok(uv_virtualenv::create_venv(
+            let environment = uv_virtualenv::create_venv(
                 &venv,
                 interpreter,
                 uv_virtualenv::Prompt::None,
                 false,
                 false,
                 false,
-            )?)
+            )?;
+            environments::register(&environment, Some(workspace.install_path().as_path()));
+            Ok(environment)
         }
     }
 }
 
+/// Initialize a virtual environment for the current project, targeting an explicitly requested
+/// Python interpreter.
+///
+/// If the project environment satisfies the request, it's used as-is. Otherwise, rather than
+/// replacing the project environment, a secondary environment keyed by the Python implementation
+/// and version (e.g., `.venv-cpython-3.9`) is created, or reused if it's still compatible.
+pub(crate) async fn get_or_init_secondary_environment(
+    workspace: &Workspace,
+    python: PythonRequest,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    connectivity: Connectivity,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<PythonEnvironment, ProjectError> {
+    let interpreter = match FoundInterpreter::discover(
+        workspace,
+        Some(python),
+        python_preference,
+        python_downloads,
+        connectivity,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?
+    {
+        // If the project environment is compatible, use it.
+        FoundInterpreter::Environment(environment) => return Ok(environment),
+        FoundInterpreter::Interpreter(interpreter) => interpreter,
+    };
+
+    let venv = secondary_venv(workspace, &interpreter);
+
+    // Prevent concurrent processes from creating or removing the environment at the same time.
+    let _lock = lock_environment_path(&venv).await?;
+
+    // Reuse the secondary environment if it's based on the same interpreter.
+    match PythonEnvironment::from_root(&venv, cache) {
+        Ok(environment) => {
+            if environment.interpreter().python_version() == interpreter.python_version()
+                && environment.interpreter().sys_base_prefix() == interpreter.sys_base_prefix()
+            {
+                debug!(
+                    "Using existing secondary environment at: {}",
+                    venv.user_display()
+                );
+                return Ok(environment);
+            }
+
+            wait_for_environment(&venv).await?;
+            uv_fs::force_remove_dir_all(&venv)?;
+            writeln!(
+                printer.stderr(),
+                "Removed virtual environment at: {}",
+                venv.user_display().cyan()
+            )?;
+        }
+        Err(uv_python::Error::MissingEnvironment(_)) => {}
+        Err(err) => {
+            debug!("Ignoring invalid secondary environment: {err}");
+            match uv_fs::force_remove_dir_all(&venv) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    writeln!(
+        printer.stderr(),
+        "Creating virtualenv at: {}",
+        venv.user_display().cyan()
+    )?;
+
+    let environment = uv_virtualenv::create_venv(
+        &venv,
+        interpreter,
+        uv_virtualenv::Prompt::None,
+        false,
+        false,
+        false,
+    )?;
+    environments::register(&environment, Some(workspace.install_path().as_path()));
+    Ok(environment)
+}
+
+/// Create an ephemeral virtual environment at the given location, e.g., for `uv run --isolated`.
+///
+/// In preview mode, the environment is cloned from a template in the cache (omitting the
+/// activation scripts), rather than created from scratch. Neither path spawns the interpreter.
+pub(crate) fn create_ephemeral_venv(
+    location: &Path,
+    interpreter: Interpreter,
+    relocatable: bool,
+    preview: PreviewMode,
+    cache: &Cache,
+) -> Result<PythonEnvironment, uv_virtualenv::Error> {
+    let start = std::time::Instant::now();
+    let environment = if preview.is_enabled() {
+        uv_virtualenv::create_venv_from_template(
+            location,
+            interpreter,
+            &cache.bucket(CacheBucket::VenvTemplates),
+            false,
+            relocatable,
+        )
+    } else {
+        uv_virtualenv::create_venv(
+            location,
+            interpreter,
+            uv_virtualenv::Prompt::None,
+            false,
+            false,
+            relocatable,
+        )
+    }?;
+    debug!(
+        "Created ephemeral environment in {}",
+        elapsed(start.elapsed())
+    );
+    Ok(environment)
+}
+
+/// Return the path to the secondary virtual environment for the given interpreter, alongside the
+/// project environment.
+fn secondary_venv(workspace: &Workspace, interpreter: &Interpreter) -> PathBuf {
+    workspace.install_path().join(format!(
+        ".venv-{}-{}.{}",
+        interpreter.implementation_name(),
+        interpreter.python_major(),
+        interpreter.python_minor()
+    ))
+}
+
+/// A named environment, as declared in `tool.uv.envs`, with any command-line arguments applied.
+#[derive(Debug)]
+pub(crate) struct NamedEnvironment {
+    /// The root of the virtual environment.
+    pub(crate) venv: PathBuf,
+    /// The extras to install into the environment.
+    pub(crate) extras: ExtrasSpecification,
+    /// Whether to install the development dependencies into the environment.
+    pub(crate) dev: bool,
+    /// The Python interpreter to use for the environment, if any.
+    pub(crate) python: Option<String>,
+}
+
+impl NamedEnvironment {
+    /// Resolve the named environment from the [`Workspace`].
+    ///
+    /// Extras and a Python request provided on the command line take precedence over those
+    /// declared for the environment; `--no-dev` is respected regardless.
+    pub(crate) fn from_workspace(
+        workspace: &Workspace,
+        name: &str,
+        extras: ExtrasSpecification,
+        dev: bool,
+        python: Option<String>,
+    ) -> Result<Self, ProjectError> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ProjectError::InvalidEnvironmentName(name.to_string()));
+        }
+
+        let Some(environment) = workspace
+            .environments()
+            .and_then(|environments| environments.get(name))
+        else {
+            return Err(ProjectError::MissingNamedEnvironment(name.to_string()));
+        };
+
+        let extras = if extras.is_empty() {
+            ExtrasSpecification::from_args(false, environment.extras.clone().unwrap_or_default())
+        } else {
+            extras
+        };
+
+        debug!("Using named environment: `{name}`");
+
+        Ok(Self {
+            venv: workspace.named_venv(name),
+            extras,
+            dev: dev && environment.dev.unwrap_or(true),
+            python: python.or_else(|| environment.python.clone()),
+        })
+    }
+}
+
 /// Resolve any [`UnresolvedRequirementSpecification`] into a fully-qualified [`Requirement`].
 pub(crate) async fn resolve_names(
     requirements: Vec<UnresolvedRequirementSpecification>,
@@ -417,6 +870,7 @@ pub(crate) async fn resolve_names(
         exclude_newer,
         link_mode,
         compile_bytecode: _,
+        exclude_package_data: _,
         sources,
         upgrade: _,
         reinstall: _,
@@ -506,6 +960,114 @@ pub(crate) async fn resolve_environment<'a>(
     native_tls: bool,
     cache: &Cache,
     printer: Printer,
+) -> Result<ResolutionGraph, ProjectError> {
+    do_resolve_environment(
+        interpreter,
+        None,
+        spec,
+        Vec::default(),
+        settings,
+        state,
+        logger,
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await
+}
+
+/// Run dependency resolution for an interpreter, preferring the given versions, returning the
+/// [`ResolutionGraph`].
+pub(crate) async fn resolve_environment_with_preferences(
+    interpreter: &Interpreter,
+    spec: RequirementsSpecification,
+    preferences: Vec<Preference>,
+    settings: ResolverSettingsRef<'_>,
+    state: &SharedState,
+    logger: Box<dyn ResolveLogger>,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ResolutionGraph, ProjectError> {
+    do_resolve_environment(
+        interpreter,
+        None,
+        spec,
+        preferences,
+        settings,
+        state,
+        logger,
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await
+}
+
+/// Run a universal dependency resolution for the given Python requirement, returning the
+/// [`ResolutionGraph`].
+///
+/// Unlike [`resolve_environment`], the resolution applies to any platform (and any Python version
+/// within `requires_python`), rather than to the given interpreter, which is only used to build
+/// source distributions.
+pub(crate) async fn resolve_universal_environment(
+    interpreter: &Interpreter,
+    requires_python: &RequiresPython,
+    spec: RequirementsSpecification,
+    preferences: Vec<Preference>,
+    settings: ResolverSettingsRef<'_>,
+    state: &SharedState,
+    logger: Box<dyn ResolveLogger>,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ResolutionGraph, ProjectError> {
+    do_resolve_environment(
+        interpreter,
+        Some(requires_python),
+        spec,
+        preferences,
+        settings,
+        state,
+        logger,
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await
+}
+
+/// Run dependency resolution for an interpreter or, if `requires_python` is provided, for all
+/// platforms, returning the [`ResolutionGraph`].
+async fn do_resolve_environment(
+    interpreter: &Interpreter,
+    requires_python: Option<&RequiresPython>,
+    spec: RequirementsSpecification,
+    preferences: Vec<Preference>,
+    settings: ResolverSettingsRef<'_>,
+    state: &SharedState,
+    logger: Box<dyn ResolveLogger>,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
 ) -> Result<ResolutionGraph, ProjectError> {
     warn_on_requirements_txt_setting(&spec, settings);
 
@@ -538,7 +1100,17 @@ pub(crate) async fn resolve_environment<'a>(
     // Determine the tags, markers, and interpreter to use for resolution.
     let tags = interpreter.tags()?;
     let markers = interpreter.markers();
-    let python_requirement = PythonRequirement::from_interpreter(interpreter);
+    let (python_requirement, resolver_markers) = if let Some(requires_python) = requires_python {
+        (
+            PythonRequirement::from_requires_python(interpreter, requires_python),
+            ResolverMarkers::universal(None),
+        )
+    } else {
+        (
+            PythonRequirement::from_interpreter(interpreter),
+            ResolverMarkers::SpecificEnvironment(markers.clone()),
+        )
+    };
 
     // Add all authenticated sources to the cache.
     for url in index_locations.urls() {
@@ -579,11 +1151,18 @@ pub(crate) async fn resolve_environment<'a>(
     // optional on the downstream APIs.
     let dev = Vec::default();
     let extras = ExtrasSpecification::default();
-    let hasher = HashStrategy::default();
-    let preferences = Vec::default();
     let setup_py = SetupPyStrategy::default();
     let build_constraints = [];
 
+    // Verify any hashes embedded in direct URLs.
+    let hasher = HashStrategy::from_url_fragments(
+        requirements
+            .iter()
+            .chain(overrides.iter())
+            .map(|entry| &entry.requirement),
+        resolver_markers.marker_environment(),
+    )?;
+
     // When resolving from an interpreter, we assume an empty environment, so reinstalls and
     // upgrades aren't relevant.
     let reinstall = Reinstall::default();
@@ -623,6 +1202,7 @@ pub(crate) async fn resolve_environment<'a>(
     Ok(pip::operations::resolve(
         requirements,
         constraints,
+        pip::operations::UserConstraints::get(),
         overrides,
         dev,
         source_trees,
@@ -633,8 +1213,8 @@ pub(crate) async fn resolve_environment<'a>(
         &hasher,
         &reinstall,
         &upgrade,
-        Some(tags),
-        ResolverMarkers::SpecificEnvironment(markers.clone()),
+        requires_python.is_none().then_some(tags),
+        resolver_markers,
         python_requirement,
         &client,
         &flat_index,
@@ -642,6 +1222,7 @@ pub(crate) async fn resolve_environment<'a>(
         &resolve_dispatch,
         concurrency,
         options,
+        None,
         logger,
         printer,
         preview,
@@ -672,6 +1253,7 @@ pub(crate) async fn sync_environment(
         exclude_newer,
         link_mode,
         compile_bytecode,
+        exclude_package_data,
         reinstall,
         build_options,
         sources,
@@ -753,9 +1335,16 @@ pub(crate) async fn sync_environment(
         build_options,
         link_mode,
         compile_bytecode,
+        false,
         index_locations,
         &hasher,
         tags,
+        AbiCheck::Ignore,
+        FileConflicts::Ignore,
+        &[],
+        exclude_package_data,
+        None,
+        false,
         &client,
         &state.in_flight,
         concurrency,
@@ -775,6 +1364,16 @@ pub(crate) async fn sync_environment(
     Ok(venv)
 }
 
+/// The result of updating a [`PythonEnvironment`] via [`update_environment`].
+#[derive(Debug)]
+pub(crate) struct EnvironmentUpdate {
+    /// The updated environment.
+    pub(crate) environment: PythonEnvironment,
+    /// The resolution that was installed into the environment, or `None` if the environment
+    /// already satisfied the requirements.
+    pub(crate) resolution: Option<ResolutionGraph>,
+}
+
 /// Update a [`PythonEnvironment`] to satisfy a set of [`RequirementsSource`]s.
 pub(crate) async fn update_environment(
     venv: PythonEnvironment,
@@ -789,7 +1388,7 @@ pub(crate) async fn update_environment(
     native_tls: bool,
     cache: &Cache,
     printer: Printer,
-) -> anyhow::Result<PythonEnvironment> {
+) -> anyhow::Result<EnvironmentUpdate> {
     warn_on_requirements_txt_setting(&spec, settings.as_ref().into());
 
     let ResolverInstallerSettings {
@@ -804,6 +1403,7 @@ pub(crate) async fn update_environment(
         exclude_newer,
         link_mode,
         compile_bytecode,
+        exclude_package_data,
         sources,
         upgrade,
         reinstall,
@@ -820,7 +1420,9 @@ pub(crate) async fn update_environment(
         ..
     } = spec;
 
-    // Check if the current environment satisfies the requirements
+    // Check if the current environment satisfies the requirements, after rolling back any
+    // incomplete changes from an interrupted installation.
+    pip::operations::recover(&venv)?;
     let site_packages = SitePackages::from_environment(&venv)?;
     if source_trees.is_empty() && reinstall.is_none() && upgrade.is_none() && overrides.is_empty() {
         match site_packages.satisfies(&requirements, &constraints)? {
@@ -836,7 +1438,10 @@ pub(crate) async fn update_environment(
                         .sorted()
                         .join(" | ")
                 );
-                return Ok(venv);
+                return Ok(EnvironmentUpdate {
+                    environment: venv,
+                    resolution: None,
+                });
             }
             SatisfiesResult::Unsatisfied(requirement) => {
                 debug!("At least one requirement is not satisfied: {requirement}");
@@ -888,10 +1493,18 @@ pub(crate) async fn update_environment(
     let dev = Vec::default();
     let dry_run = false;
     let extras = ExtrasSpecification::default();
-    let hasher = HashStrategy::default();
     let preferences = Vec::default();
     let setup_py = SetupPyStrategy::default();
 
+    // Verify any hashes embedded in direct URLs.
+    let hasher = HashStrategy::from_url_fragments(
+        requirements
+            .iter()
+            .chain(overrides.iter())
+            .map(|entry| &entry.requirement),
+        Some(markers),
+    )?;
+
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
         let client = FlatIndexClient::new(&client, cache);
@@ -926,6 +1539,7 @@ pub(crate) async fn update_environment(
     let resolution = match pip::operations::resolve(
         requirements,
         constraints,
+        pip::operations::UserConstraints::get(),
         overrides,
         dev,
         source_trees,
@@ -945,15 +1559,17 @@ pub(crate) async fn update_environment(
         &build_dispatch,
         concurrency,
         options,
+        None,
         resolve,
         printer,
         preview,
     )
     .await
     {
-        Ok(resolution) => Resolution::from(resolution),
+        Ok(graph) => graph,
         Err(err) => return Err(err.into()),
     };
+    let resolution = Resolution::from(&graph);
 
     // Sync the environment.
     pip::operations::install(
@@ -964,9 +1580,16 @@ pub(crate) async fn update_environment(
         build_options,
         *link_mode,
         *compile_bytecode,
+        false,
         index_locations,
         &hasher,
         tags,
+        AbiCheck::Ignore,
+        FileConflicts::Ignore,
+        &[],
+        exclude_package_data,
+        None,
+        false,
         &client,
         &state.in_flight,
         concurrency,
@@ -983,7 +1606,10 @@ pub(crate) async fn update_environment(
     // Notify the user of any resolution diagnostics.
     pip::operations::diagnose_resolution(resolution.diagnostics(), printer)?;
 
-    Ok(venv)
+    Ok(EnvironmentUpdate {
+        environment: venv,
+        resolution: Some(graph),
+    })
 }
 
 /// Warn if the user provides (e.g.) an `--index-url` in a requirements file.
@@ -0,0 +1,39 @@
+import os
+import sys
+
+
+def _bootstrap():
+    # Remove this directory from `sys.path`, such that it doesn't shadow any other
+    # `sitecustomize` module.
+    bootstrap = os.path.dirname(os.path.abspath(__file__))
+    entries = [os.path.abspath(entry) if entry else entry for entry in sys.path]
+    index = entries.index(bootstrap) if bootstrap in entries else len(sys.path)
+    if index < len(sys.path):
+        del sys.path[index]
+
+    # The `PYTHONPATH` is inherited by subprocesses, which may run a different interpreter (e.g.,
+    # in a virtual environment); only expose the `__pypackages__` directory to the interpreter for
+    # which it was populated.
+    if os.path.realpath(sys.prefix) == os.path.realpath(PREFIX):
+        import site
+
+        # Add the directory (and any `.pth` files within it) in place of this one, ahead of
+        # `site-packages`.
+        known = len(sys.path)
+        site.addsitedir(LIB)
+        added = sys.path[known:]
+        del sys.path[known:]
+        sys.path[index:index] = added
+
+    # Defer to the next `sitecustomize` module (e.g., from the `__pypackages__` directory or the
+    # interpreter's `site-packages`), if any.
+    module = sys.modules.pop("sitecustomize")
+    try:
+        import sitecustomize  # noqa: F401
+    except ImportError as exc:
+        if exc.name != "sitecustomize":
+            raise
+        sys.modules["sitecustomize"] = module
+
+
+_bootstrap()
@@ -115,6 +115,7 @@ pub(crate) async fn remove(
         frozen,
         project.workspace(),
         venv.interpreter(),
+        None,
         settings.as_ref().into(),
         Box::new(DefaultResolveLogger),
         preview,
@@ -140,14 +141,18 @@ pub(crate) async fn remove(
 
     project::sync::do_sync(
         &VirtualProject::Project(project),
+        None,
         &venv,
         &lock.lock,
         &extras,
         dev,
+        &[],
         Modifications::Exact,
+        false,
+        false,
         settings.as_ref().into(),
         &state,
-        Box::new(DefaultInstallLogger),
+        Box::new(DefaultInstallLogger::default()),
         preview,
         connectivity,
         concurrency,
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::collections::BTreeMap;
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
 
@@ -11,10 +11,11 @@ use owo_colors::OwoColorize;
 use tokio::process::Command;
 use tracing::debug;
 
+use pypi_types::Requirement;
 use uv_cache::Cache;
 use uv_cli::ExternalCommand;
 use uv_client::{BaseClientBuilder, Connectivity};
-use uv_configuration::{Concurrency, ExtrasSpecification, PreviewMode};
+use uv_configuration::{Concurrency, ExtrasSpecification, ModulePath, PreviewMode};
 use uv_distribution::LoweredRequirement;
 use uv_fs::{PythonExt, Simplified, CWD};
 use uv_installer::{SatisfiesResult, SitePackages};
@@ -29,17 +30,23 @@ use uv_warnings::warn_user_once;
 use uv_workspace::{DiscoveryOptions, VirtualProject, Workspace, WorkspaceError};
 
 use crate::commands::pip::loggers::{
-    DefaultInstallLogger, DefaultResolveLogger, SummaryInstallLogger, SummaryResolveLogger,
+    DefaultInstallLogger, DefaultResolveLogger, InstallLogger, SummaryInstallLogger,
+    SummaryResolveLogger,
 };
 use crate::commands::pip::operations;
 use crate::commands::pip::operations::Modifications;
 use crate::commands::project::environment::CachedEnvironment;
+use crate::commands::project::script_lock::read_script_lock;
 use crate::commands::project::{ProjectError, WorkspacePython};
 use crate::commands::reporters::PythonDownloadReporter;
 use crate::commands::{pip, project, ExitStatus, SharedState};
 use crate::printer::Printer;
 use crate::settings::ResolverInstallerSettings;
 
+/// The `sitecustomize` module that exposes a `__pypackages__` directory, to be prefixed with its
+/// configuration.
+const PYPACKAGES_BOOTSTRAP: &str = include_str!("pypackages.py");
+
 /// Run a command.
 #[allow(clippy::fn_params_excessive_bools)]
 pub(crate) async fn run(
@@ -52,8 +59,10 @@ pub(crate) async fn run(
     isolated: bool,
     package: Option<PackageName>,
     no_project: bool,
+    env: Option<String>,
     extras: ExtrasSpecification,
     dev: bool,
+    module_path: Option<Vec<ModulePath>>,
     python: Option<String>,
     settings: ResolverInstallerSettings,
     preview: PreviewMode,
@@ -100,8 +109,12 @@ pub(crate) async fn run(
     // Initialize any output reporters.
     let download_reporter = PythonDownloadReporter::single(printer);
 
+    // The directories to add to `sys.path`, if the implicit entry should be replaced.
+    let mut sys_path = None;
+
     // Determine whether the command to execute is a PEP 723 script.
     let temp_dir;
+    let _environments_lock;
     let script_interpreter = if let Some(script) = script {
         writeln!(
             printer.stderr(),
@@ -109,84 +122,87 @@ pub(crate) async fn run(
             script.path.user_display().cyan()
         )?;
 
-        // (1) Explicit request from user
-        let python_request = if let Some(request) = python.as_deref() {
-            Some(PythonRequest::parse(request))
-            // (2) Request from `.python-version`
-        } else if let Some(request) = request_from_version_file(&CWD).await? {
-            Some(request)
-            // (3) `Requires-Python` in `pyproject.toml`
-        } else {
-            script.metadata.requires_python.map(|requires_python| {
-                PythonRequest::Version(VersionRequest::Range(requires_python))
-            })
-        };
-
-        let client_builder = BaseClientBuilder::new()
-            .connectivity(connectivity)
-            .native_tls(native_tls);
+        // For scripts, the `sys.path` entries are resolved relative to the script's directory.
+        let script_module_path = module_path.clone().or_else(|| {
+            script
+                .metadata
+                .tool
+                .as_ref()
+                .and_then(|tool| tool.uv.as_ref())
+                .and_then(|uv| uv.module_path.clone())
+        });
+        if let Some(entries) = script_module_path {
+            let script_dir = script.path.parent().expect("script path has no parent");
+            sys_path = Some(module_path_dirs(&entries, script_dir, None));
+        }
 
-        let interpreter = PythonInstallation::find_or_download(
-            python_request,
-            EnvironmentPreference::Any,
+        let interpreter = find_script_interpreter(
+            &script,
+            python.as_deref(),
             python_preference,
             python_downloads,
-            &client_builder,
+            connectivity,
+            native_tls,
             cache,
-            Some(&download_reporter),
+            &download_reporter,
         )
-        .await?
-        .into_interpreter();
+        .await?;
 
         // Install the script requirements, if necessary. Otherwise, use an isolated environment.
-        if let Some(dependencies) = script.metadata.dependencies {
-            // // Collect any `tool.uv.sources` from the script.
-            let empty = BTreeMap::default();
-            let script_sources = script
-                .metadata
-                .tool
-                .as_ref()
-                .and_then(|tool| tool.uv.as_ref())
-                .and_then(|uv| uv.sources.as_ref())
-                .unwrap_or(&empty);
+        if script.metadata.dependencies.is_some() {
+            let requirements = script_requirements(&script, preview)?;
             let script_dir = script.path.parent().expect("script path has no parent");
 
-            let requirements = dependencies
-                .into_iter()
-                .map(|requirement| {
-                    LoweredRequirement::from_non_workspace_requirement(
-                        requirement,
-                        script_dir,
-                        script_sources,
-                        preview,
-                    )
-                    .map(LoweredRequirement::into_inner)
-                })
-                .collect::<Result<_, _>>()?;
-            let spec = RequirementsSpecification::from_requirements(requirements);
-            let result = CachedEnvironment::get_or_create(
-                spec,
-                interpreter,
-                &settings,
-                &state,
-                if show_resolution {
-                    Box::new(DefaultResolveLogger)
-                } else {
-                    Box::new(SummaryResolveLogger)
-                },
-                if show_resolution {
-                    Box::new(DefaultInstallLogger)
-                } else {
-                    Box::new(SummaryInstallLogger)
-                },
-                preview,
-                connectivity,
-                concurrency,
-                native_tls,
-                cache,
-                printer,
-            )
-            .await;
+            let install: Box<dyn InstallLogger> = if show_resolution {
+                Box::new(DefaultInstallLogger::default())
+            } else {
+                Box::new(SummaryInstallLogger)
+            };
+
+            // Hold a shared lock on the cached environments while the environment is in use.
+            _environments_lock = cache.lock_environments()?;
+
+            // If the script is locked, install the locked versions, rather than resolving.
+            let result = if let Some(lock) =
+                read_script_lock(&script, &interpreter, locked, frozen).await?
+            {
+                CachedEnvironment::get_or_create_locked(
+                    &lock,
+                    &requirements,
+                    script_dir,
+                    interpreter,
+                    &settings,
+                    &state,
+                    install,
+                    preview,
+                    connectivity,
+                    concurrency,
+                    native_tls,
+                    cache,
+                    printer,
+                )
+                .await
+            } else {
+                CachedEnvironment::get_or_create(
+                    RequirementsSpecification::from_requirements(requirements),
+                    interpreter,
+                    &settings,
+                    &state,
+                    if show_resolution {
+                        Box::new(DefaultResolveLogger)
+                    } else {
+                        Box::new(SummaryResolveLogger)
+                    },
+                    install,
+                    preview,
+                    connectivity,
+                    concurrency,
+                    native_tls,
+                    cache,
+                    printer,
+                )
+                .await
+            };
 
             let environment = match result {
                 Ok(resolution) => resolution,
@@ -196,7 +212,7 @@ pub(crate) async fn run(
                     let report = miette::Report::msg(format!("{err}"))
                         .context(err.header().with_context("script"));
                     eprint!("{report:?}");
-                    return Ok(ExitStatus::Failure);
+                    return Ok(ExitStatus::NoSolution);
                 }
                 Err(err) => return Err(err.into()),
             };
@@ -205,13 +221,12 @@ pub(crate) async fn run(
         } else {
             // Create a virtual environment.
             temp_dir = cache.environment()?;
-            let environment = uv_virtualenv::create_venv(
+            let environment = project::create_ephemeral_venv(
                 temp_dir.path(),
                 interpreter,
-                uv_virtualenv::Prompt::None,
-                false,
-                false,
                 false,
+                preview,
+                cache,
             )?;
 
             Some(environment.into_interpreter())
@@ -230,6 +245,9 @@ pub(crate) async fn run(
         if !dev {
             warn_user_once!("`--no-dev` is not supported for Python scripts with inline metadata");
         }
+        if env.is_some() {
+            warn_user_once!("`--env` is not supported for Python scripts with inline metadata");
+        }
         if no_project {
             warn_user_once!(
                 "`--no-project` is a no-op for Python scripts with inline metadata, which always run in isolation"
@@ -240,16 +258,6 @@ pub(crate) async fn run(
                 "`--package` is a no-op for Python scripts with inline metadata, which always run in isolation"
             );
         }
-        if locked {
-            warn_user_once!(
-                "`--locked` is a no-op for Python scripts with inline metadata, which always run in isolation"
-            );
-        }
-        if frozen {
-            warn_user_once!(
-                "`--frozen` is a no-op for Python scripts with inline metadata, which always run in isolation"
-            );
-        }
         if isolated {
             warn_user_once!(
                 "`--isolated` is a no-op for Python scripts with inline metadata, which always run in isolation"
@@ -295,6 +303,9 @@ pub(crate) async fn run(
             if frozen {
                 warn_user_once!("`--frozen` has no effect when used alongside `--no-project`");
             }
+            if module_path.is_some() {
+                warn_user_once!("`--module-path` has no effect when used alongside `--no-project`");
+            }
 
             None
         } else {
@@ -306,12 +317,18 @@ pub(crate) async fn run(
                 if !dev {
                     warn_user_once!("`--no-dev` has no effect when used outside of a project");
                 }
+                if env.is_some() {
+                    warn_user_once!("`--env` has no effect when used outside of a project");
+                }
                 if locked {
                     warn_user_once!("`--locked` has no effect when used outside of a project");
                 }
                 if frozen {
                     warn_user_once!("`--frozen` has no effect when used outside of a project");
                 }
+                if module_path.is_some() {
+                    warn_user_once!("`--module-path` has no effect when used outside of a project");
+                }
             }
 
             project
@@ -330,6 +347,46 @@ pub(crate) async fn run(
                 );
             }
 
+            // Determine the directories to add to `sys.path`, preferring the command-line, then the
+            // current member, then the workspace root.
+            let project_module_path = module_path
+                .clone()
+                .or_else(|| {
+                    project
+                        .pyproject_toml()
+                        .tool
+                        .as_ref()
+                        .and_then(|tool| tool.uv.as_ref())
+                        .and_then(|uv| uv.module_path.clone())
+                })
+                .or_else(|| project.workspace().module_path().map(<[_]>::to_vec));
+            if let Some(entries) = project_module_path {
+                sys_path = Some(module_path_dirs(
+                    &entries,
+                    project.root(),
+                    Some(project.workspace()),
+                ));
+            }
+
+            // If a named environment was requested, apply its settings.
+            let (named, extras, dev, python) = if let Some(name) = env.as_deref() {
+                let project::NamedEnvironment {
+                    venv,
+                    extras,
+                    dev,
+                    python,
+                } = project::NamedEnvironment::from_workspace(
+                    project.workspace(),
+                    name,
+                    extras,
+                    dev,
+                    python,
+                )?;
+                (Some(venv), extras, dev, python)
+            } else {
+                (None, extras, dev, python)
+            };
+
             let venv = if isolated {
                 debug!("Creating isolated virtual environment");
 
@@ -363,14 +420,40 @@ pub(crate) async fn run(
 
                 // Create a virtual environment
                 temp_dir = cache.environment()?;
-                uv_virtualenv::create_venv(
-                    temp_dir.path(),
-                    interpreter,
-                    uv_virtualenv::Prompt::None,
-                    false,
-                    false,
-                    false,
-                )?
+                project::create_ephemeral_venv(temp_dir.path(), interpreter, false, preview, cache)?
+            } else if let Some(named) = named {
+                // If the user requested a named environment, use it in lieu of the base
+                // environment for the project.
+                project::get_or_init_environment_at(
+                    project.workspace(),
+                    named,
+                    python.as_deref().map(PythonRequest::parse),
+                    python_preference,
+                    python_downloads,
+                    connectivity,
+                    native_tls,
+                    cache,
+                    printer,
+                )
+                .await?
+            } else if let Some(python) = python
+                .as_deref()
+                .filter(|_| !project.workspace().pypackages())
+            {
+                // If the user requested a specific Python interpreter, avoid replacing the base
+                // environment for the project; instead, use a secondary environment for the
+                // requested interpreter.
+                project::get_or_init_secondary_environment(
+                    project.workspace(),
+                    PythonRequest::parse(python),
+                    python_preference,
+                    python_downloads,
+                    connectivity,
+                    native_tls,
+                    cache,
+                    printer,
+                )
+                .await?
             } else {
                 // If we're not isolating the environment, reuse the base environment for the
                 // project.
@@ -392,6 +475,7 @@ pub(crate) async fn run(
                 frozen,
                 project.workspace(),
                 venv.interpreter(),
+                None,
                 settings.as_ref().into(),
                 if show_resolution {
                     Box::new(DefaultResolveLogger)
@@ -413,22 +497,26 @@ pub(crate) async fn run(
                 ))) => {
                     let report = miette::Report::msg(format!("{err}")).context(err.header());
                     anstream::eprint!("{report:?}");
-                    return Ok(ExitStatus::Failure);
+                    return Ok(ExitStatus::NoSolution);
                 }
                 Err(err) => return Err(err.into()),
             };
 
             project::sync::do_sync(
                 &project,
+                None,
                 &venv,
                 &lock.lock,
                 &extras,
                 dev,
+                &[],
                 Modifications::Sufficient,
+                false,
+                false,
                 settings.as_ref().into(),
                 &state,
                 if show_resolution {
-                    Box::new(DefaultInstallLogger)
+                    Box::new(DefaultInstallLogger::default())
                 } else {
                     Box::new(SummaryInstallLogger)
                 },
@@ -470,13 +558,12 @@ pub(crate) async fn run(
 
                 // If we're isolating the environment, use an ephemeral virtual environment.
                 temp_dir = cache.environment()?;
-                let venv = uv_virtualenv::create_venv(
+                let venv = project::create_ephemeral_venv(
                     temp_dir.path(),
                     interpreter,
-                    uv_virtualenv::Prompt::None,
-                    false,
-                    false,
                     false,
+                    preview,
+                    cache,
                 )?;
                 venv.into_interpreter()
             } else {
@@ -509,6 +596,7 @@ pub(crate) async fn run(
 
     // If necessary, create an environment for the ephemeral requirements or command.
     let temp_dir;
+    let _environments_lock;
     let ephemeral_env = if can_skip_ephemeral(spec.as_ref(), &base_interpreter, &settings) {
         None
     } else {
@@ -518,18 +606,20 @@ pub(crate) async fn run(
             None => {
                 // Create a virtual environment
                 temp_dir = cache.environment()?;
-                uv_virtualenv::create_venv(
+                project::create_ephemeral_venv(
                     temp_dir.path(),
                     base_interpreter.clone(),
-                    uv_virtualenv::Prompt::None,
-                    false,
-                    false,
                     false,
+                    preview,
+                    cache,
                 )?
             }
             Some(spec) => {
                 debug!("Syncing ephemeral requirements");
 
+                // Hold a shared lock on the cached environments while the environment is in use.
+                _environments_lock = cache.lock_environments()?;
+
                 let result = CachedEnvironment::get_or_create(
                     spec,
                     base_interpreter.clone(),
@@ -541,7 +631,7 @@ pub(crate) async fn run(
                         Box::new(SummaryResolveLogger)
                     },
                     if show_resolution {
-                        Box::new(DefaultInstallLogger)
+                        Box::new(DefaultInstallLogger::default())
                     } else {
                         Box::new(SummaryInstallLogger)
                     },
@@ -562,7 +652,7 @@ pub(crate) async fn run(
                         let report = miette::Report::msg(format!("{err}"))
                             .context(err.header().with_context("`--with`"));
                         eprint!("{report:?}");
-                        return Ok(ExitStatus::Failure);
+                        return Ok(ExitStatus::NoSolution);
                     }
                     Err(err) => return Err(err.into()),
                 };
@@ -594,8 +684,59 @@ pub(crate) async fn run(
         )?;
     }
 
+    // If the base environment is a `__pypackages__` directory (and not loaded via an ephemeral
+    // environment), add a `sitecustomize.py` to a dedicated directory on the `PYTHONPATH`, which
+    // adds the `__pypackages__` directory to `sys.path` (resolving the `.pth` files within it,
+    // e.g., for editables) before deferring to any other `sitecustomize` module.
+    let bootstrap = if let Some(target) = base_interpreter
+        .target()
+        .filter(|_| ephemeral_env.is_none())
+    {
+        let bootstrap = target
+            .root()
+            .parent()
+            .unwrap_or(target.root())
+            .join("bootstrap");
+        fs_err::create_dir_all(&bootstrap)?;
+        fs_err::write(
+            bootstrap.join("sitecustomize.py"),
+            format!(
+                "# Generated by uv to expose the `__pypackages__` directory.\n\
+                 PREFIX = \"{}\"\n\
+                 LIB = \"{}\"\n\n\
+                 {PYPACKAGES_BOOTSTRAP}",
+                base_interpreter.sys_prefix().escape_for_python(),
+                target.root().escape_for_python()
+            ),
+        )?;
+        Some(bootstrap)
+    } else {
+        None
+    };
+
+    // If the `sys.path` entries are configured, disable the implicit entry for the directory of
+    // the script (or the current directory), such that imports behave as in the installed package.
+    // The interpreter is invoked with `-P`, rather than `PYTHONSAFEPATH`, which would otherwise be
+    // inherited by any subprocesses.
+    let safe_path = if let Some(sys_path) = sys_path.as_ref() {
+        for dir in sys_path {
+            debug!("Adding to `sys.path`: {}", dir.user_display());
+        }
+        if base_interpreter.python_tuple() < (3, 11) {
+            warn_user_once!(
+                "The implicit `sys.path` entry can only be disabled on Python 3.11 or later (found: {}); `module-path` will only add directories",
+                base_interpreter.python_version()
+            );
+            false
+        } else {
+            true
+        }
+    } else {
+        false
+    };
+
     debug!("Running `{command}`");
-    let mut process = Command::from(&command);
+    let mut process = command.to_command(safe_path);
 
     // Construct the `PATH` environment variable.
     let new_path = std::env::join_paths(
@@ -603,8 +744,13 @@ pub(crate) async fn run(
             .as_ref()
             .map(PythonEnvironment::scripts)
             .into_iter()
-            .chain(std::iter::once(base_interpreter.scripts()))
             .map(PathBuf::from)
+            .chain(
+                base_interpreter
+                    .target()
+                    .map(|target| target.scheme().scripts),
+            )
+            .chain(std::iter::once(PathBuf::from(base_interpreter.scripts())))
             .chain(
                 std::env::var_os("PATH")
                     .as_ref()
@@ -614,6 +760,20 @@ pub(crate) async fn run(
     )?;
     process.env("PATH", new_path);
 
+    // Construct the `PYTHONPATH` environment variable, to expose any configured `sys.path`
+    // entries and the `__pypackages__` directory.
+    if sys_path.as_ref().is_some_and(|dirs| !dirs.is_empty()) || bootstrap.is_some() {
+        let new_python_path = std::env::join_paths(
+            sys_path.into_iter().flatten().chain(bootstrap).chain(
+                std::env::var_os("PYTHONPATH")
+                    .as_ref()
+                    .iter()
+                    .flat_map(std::env::split_paths),
+            ),
+        )?;
+        process.env("PYTHONPATH", new_python_path);
+    }
+
     // Spawn and wait for completion
     // Standard input, output, and error streams are all inherited
     // TODO(zanieb): Throw a nicer error message if the command is not found
@@ -640,6 +800,161 @@ pub(crate) async fn run(
     }
 }
 
+/// Resolve the directories to add to `sys.path` for the given [`ModulePath`] entries, relative to
+/// the given project root.
+fn module_path_dirs(
+    entries: &[ModulePath],
+    root: &Path,
+    workspace: Option<&Workspace>,
+) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for entry in entries {
+        match entry {
+            ModulePath::ProjectRoot => dirs.push(root.to_path_buf()),
+            ModulePath::Src => {
+                let src = root.join("src");
+                if src.is_dir() {
+                    dirs.push(src);
+                }
+            }
+            ModulePath::Members => {
+                if let Some(workspace) = workspace {
+                    dirs.extend(
+                        workspace
+                            .packages()
+                            .values()
+                            .map(|member| member.root().clone()),
+                    );
+                }
+            }
+        }
+    }
+    dirs.into_iter().unique().collect()
+}
+
+/// Run a command against each of the Python versions declared in the workspace's
+/// `tool.uv.test-pythons` setting, displaying a summary of the results.
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) async fn run_all_pythons(
+    script: Option<Pep723Script>,
+    command: ExternalCommand,
+    requirements: Vec<RequirementsSource>,
+    show_resolution: bool,
+    locked: bool,
+    frozen: bool,
+    package: Option<PackageName>,
+    extras: ExtrasSpecification,
+    dev: bool,
+    module_path: Option<Vec<ModulePath>>,
+    settings: ResolverInstallerSettings,
+    preview: PreviewMode,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> anyhow::Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv run` is experimental and may change without warning");
+    }
+
+    if script.is_some() {
+        bail!("`--all-pythons` is not supported for Python scripts with inline metadata");
+    }
+
+    let workspace = match Workspace::discover(&CWD, &DiscoveryOptions::default()).await {
+        Ok(workspace) => workspace,
+        Err(WorkspaceError::MissingPyprojectToml | WorkspaceError::NonWorkspace(_)) => {
+            bail!("`--all-pythons` requires a project, but none was found");
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let pythons = workspace.test_pythons();
+    if pythons.is_empty() {
+        bail!(
+            "`--all-pythons` requires at least one Python version in `tool.uv.test-pythons`, but none were found"
+        );
+    }
+
+    // Run the command against each Python version in turn, continuing past any failures.
+    let mut results = Vec::with_capacity(pythons.len());
+    for python in pythons {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!(
+                "Running `{}` with Python {python}",
+                RunCommand::from(&command)
+            )
+            .bold()
+        )?;
+
+        let passed = match Box::pin(run(
+            None,
+            command.clone(),
+            requirements.clone(),
+            show_resolution,
+            locked,
+            frozen,
+            false,
+            package.clone(),
+            false,
+            None,
+            extras.clone(),
+            dev,
+            module_path.clone(),
+            Some(python.clone()),
+            settings.clone(),
+            preview,
+            python_preference,
+            python_downloads,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        ))
+        .await
+        {
+            Ok(ExitStatus::Success) => true,
+            Ok(_) => false,
+            Err(err) => {
+                let mut causes = err.chain();
+                writeln!(
+                    printer.stderr(),
+                    "{}: {}",
+                    "error".red().bold(),
+                    causes.next().unwrap()
+                )?;
+                for err in causes {
+                    writeln!(printer.stderr(), "  {}: {}", "Caused by".red().bold(), err)?;
+                }
+                false
+            }
+        };
+        results.push((python, passed));
+    }
+
+    writeln!(printer.stderr())?;
+    writeln!(printer.stderr(), "{}", "Summary:".bold())?;
+    for (python, passed) in &results {
+        if *passed {
+            writeln!(printer.stderr(), "  Python {python}: {}", "passed".green())?;
+        } else {
+            writeln!(printer.stderr(), "  Python {python}: {}", "failed".red())?;
+        }
+    }
+
+    if results.iter().all(|(_, passed)| *passed) {
+        Ok(ExitStatus::Success)
+    } else {
+        Ok(ExitStatus::Failure)
+    }
+}
+
 /// Read a [`Pep723Script`] from the given command.
 pub(crate) async fn parse_script(
     command: &ExternalCommand,
@@ -655,6 +970,87 @@ pub(crate) async fn parse_script(
     Pep723Script::read(&target).await
 }
 
+/// Find an interpreter for the given [`Pep723Script`], downloading it if necessary.
+///
+/// In order of precedence, respects the `--python` request, the `.python-version` file in the
+/// working directory, and the `requires-python` declared by the script.
+pub(super) async fn find_script_interpreter(
+    script: &Pep723Script,
+    python: Option<&str>,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    connectivity: Connectivity,
+    native_tls: bool,
+    cache: &Cache,
+    reporter: &PythonDownloadReporter,
+) -> anyhow::Result<Interpreter> {
+    // (1) Explicit request from user
+    let python_request = if let Some(request) = python {
+        Some(PythonRequest::parse(request))
+        // (2) Request from `.python-version`
+    } else if let Some(request) = request_from_version_file(&CWD).await? {
+        Some(request)
+        // (3) `Requires-Python` in `pyproject.toml`
+    } else {
+        script
+            .metadata
+            .requires_python
+            .clone()
+            .map(|requires_python| PythonRequest::Version(VersionRequest::Range(requires_python)))
+    };
+
+    let client_builder = BaseClientBuilder::new()
+        .connectivity(connectivity)
+        .native_tls(native_tls);
+
+    Ok(PythonInstallation::find_or_download(
+        python_request,
+        EnvironmentPreference::Any,
+        python_preference,
+        python_downloads,
+        &client_builder,
+        cache,
+        Some(reporter),
+    )
+    .await?
+    .into_interpreter())
+}
+
+/// Lower the dependencies declared by the given [`Pep723Script`], respecting any
+/// `tool.uv.sources` in the script.
+pub(super) fn script_requirements(
+    script: &Pep723Script,
+    preview: PreviewMode,
+) -> anyhow::Result<Vec<Requirement>> {
+    // Collect any `tool.uv.sources` from the script.
+    let empty = BTreeMap::default();
+    let script_sources = script
+        .metadata
+        .tool
+        .as_ref()
+        .and_then(|tool| tool.uv.as_ref())
+        .and_then(|uv| uv.sources.as_ref())
+        .unwrap_or(&empty);
+    let script_dir = script.path.parent().expect("script path has no parent");
+
+    Ok(script
+        .metadata
+        .dependencies
+        .iter()
+        .flatten()
+        .cloned()
+        .map(|requirement| {
+            LoweredRequirement::from_non_workspace_requirement(
+                requirement,
+                script_dir,
+                script_sources,
+                preview,
+            )
+            .map(LoweredRequirement::into_inner)
+        })
+        .collect::<Result<_, _>>()?)
+}
+
 /// Returns `true` if we can skip creating an additional ephemeral environment in `uv run`.
 fn can_skip_ephemeral(
     spec: Option<&RequirementsSpecification>,
@@ -771,21 +1167,51 @@ impl From<&ExternalCommand> for RunCommand {
     }
 }
 
-impl From<&RunCommand> for Command {
-    fn from(command: &RunCommand) -> Self {
-        match command {
-            RunCommand::Python(target, args) => {
+impl RunCommand {
+    /// Convert the [`RunCommand`] to a [`Command`].
+    ///
+    /// If `safe_path` is set, and the command invokes the Python interpreter directly, the
+    /// interpreter is passed `-P`, such that the directory of the script (or the current directory)
+    /// isn't prepended to `sys.path`. Other commands are unaffected, as their implicit entry is the
+    /// directory of the executable.
+    fn to_command(&self, safe_path: bool) -> Command {
+        match self {
+            Self::Python(target, args) => {
                 let mut process = Command::new("python");
+                if safe_path {
+                    process.arg("-P");
+                }
                 process.arg(target);
                 process.args(args);
                 process
             }
-            RunCommand::External(executable, args) => {
+            Self::External(executable, args) => {
                 let mut process = Command::new(executable);
+                if safe_path && is_python_executable(executable) {
+                    process.arg("-P");
+                }
                 process.args(args);
                 process
             }
-            RunCommand::Empty => Command::new("python"),
+            Self::Empty => {
+                let mut process = Command::new("python");
+                if safe_path {
+                    process.arg("-P");
+                }
+                process
+            }
         }
     }
 }
+
+/// Returns `true` if the executable is a Python interpreter, e.g., `python`, `python3.12`, or
+/// `pythonw.exe`.
+fn is_python_executable(executable: &OsStr) -> bool {
+    let Some(name) = Path::new(executable).file_name().and_then(OsStr::to_str) else {
+        return false;
+    };
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    let name = name.strip_suffix('w').unwrap_or(name);
+    name.strip_prefix("python")
+        .is_some_and(|version| version.chars().all(|c| c.is_ascii_digit() || c == '.'))
+}
@@ -0,0 +1,470 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anstream::eprint;
+use anyhow::{bail, Context};
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use toml_edit::{value, Array, InlineTable, Item, Table};
+use tracing::debug;
+
+use uv_cache::Cache;
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, PreviewMode};
+use uv_fs::{Simplified, CWD};
+use uv_normalize::PackageName;
+use uv_python::{Interpreter, PythonDownloads, PythonPreference};
+use uv_requirements::upgrade::read_lock_requirements;
+use uv_requirements::RequirementsSpecification;
+use uv_resolver::{Lock, RequiresPython};
+use uv_scripts::Pep723Script;
+use uv_settings::ResolverInstallerOptions;
+use uv_warnings::{warn_user, warn_user_once};
+use uv_workspace::pyproject::Source;
+
+use crate::commands::pip::loggers::DefaultResolveLogger;
+use crate::commands::project::lock::report_upgrades;
+use crate::commands::project::run::{find_script_interpreter, script_requirements};
+use crate::commands::project::{resolve_universal_environment, ProjectError};
+use crate::commands::reporters::PythonDownloadReporter;
+use crate::commands::{pip, ExitStatus, SharedState};
+use crate::printer::Printer;
+use crate::settings::ResolverSettingsRef;
+
+/// The name of the lockfile for the PEP 723 scripts in a directory.
+const SCRIPTS_LOCK: &str = ".uv-scripts.lock";
+
+/// The current version of the scripts lockfile format.
+const VERSION: u32 = 1;
+
+/// A lockfile for the PEP 723 scripts in a directory, with a separate resolution for each script.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct ScriptsLock {
+    /// The locked scripts, keyed by file name.
+    scripts: BTreeMap<String, ScriptLock>,
+}
+
+/// The locked resolution for a single script, along with the inline metadata it was resolved
+/// from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScriptLock {
+    metadata: ScriptMetadata,
+    lock: Lock,
+}
+
+/// The inline metadata of a script that determines its resolution.
+///
+/// If the script's metadata changes, its locked resolution is outdated.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ScriptMetadata {
+    #[serde(default)]
+    dependencies: Vec<String>,
+    requires_python: Option<String>,
+    /// The script's `[tool.uv.sources]`.
+    sources: Option<BTreeMap<PackageName, Source>>,
+    /// The script's `[tool.uv]` settings, e.g., its index URLs.
+    #[serde(default)]
+    settings: ResolverInstallerOptions,
+}
+
+impl From<&Pep723Script> for ScriptMetadata {
+    fn from(script: &Pep723Script) -> Self {
+        let tool_uv = script
+            .metadata
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref());
+        Self {
+            dependencies: script
+                .metadata
+                .dependencies
+                .iter()
+                .flatten()
+                .map(ToString::to_string)
+                .collect(),
+            requires_python: script
+                .metadata
+                .requires_python
+                .as_ref()
+                .map(ToString::to_string),
+            sources: tool_uv.and_then(|uv| uv.sources.clone()),
+            settings: tool_uv.map(|uv| uv.top_level.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ScriptsLockWire {
+    version: u32,
+    #[serde(default)]
+    scripts: BTreeMap<String, ScriptLockWire>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScriptLockWire {
+    #[serde(default)]
+    metadata: ScriptMetadata,
+    #[serde(flatten)]
+    lock: toml::Table,
+}
+
+impl ScriptsLock {
+    /// Read the lockfile for the scripts in the given directory, if it exists.
+    async fn read(dir: &Path) -> anyhow::Result<Option<Self>> {
+        let path = dir.join(SCRIPTS_LOCK);
+        let contents = match fs_err::tokio::read_to_string(&path).await {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let lock = Self::from_toml(&contents)
+            .with_context(|| format!("Failed to parse `{}`", path.user_display()))?;
+        Ok(Some(lock))
+    }
+
+    /// Write the lockfile for the scripts in the given directory.
+    async fn write(&self, dir: &Path) -> anyhow::Result<()> {
+        let path = dir.join(SCRIPTS_LOCK);
+        debug!("Writing scripts lockfile to: {}", path.user_display());
+        uv_fs::write_atomic(&path, self.to_toml()?).await?;
+        Ok(())
+    }
+
+    /// Deserialize the [`ScriptsLock`] from a TOML string.
+    fn from_toml(s: &str) -> anyhow::Result<Self> {
+        let wire: ScriptsLockWire = toml::from_str(s)?;
+        if wire.version != VERSION {
+            bail!(
+                "Unsupported lockfile version {} (expected {VERSION})",
+                wire.version
+            );
+        }
+        let scripts = wire
+            .scripts
+            .into_iter()
+            .map(|(name, script)| {
+                let lock = Lock::from_toml(&toml::to_string(&script.lock)?)?;
+                Ok((
+                    name,
+                    ScriptLock {
+                        metadata: script.metadata,
+                        lock,
+                    },
+                ))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { scripts })
+    }
+
+    /// Returns the TOML representation of this lockfile.
+    ///
+    /// Each script's resolution is embedded as a table with the same structure as a `uv.lock`.
+    fn to_toml(&self) -> anyhow::Result<String> {
+        let mut doc = toml_edit::DocumentMut::new();
+        doc.insert("version", value(i64::from(VERSION)));
+
+        let mut scripts = Table::new();
+        scripts.set_implicit(true);
+        for (name, script) in &self.scripts {
+            let mut metadata = Table::new();
+            metadata.insert(
+                "dependencies",
+                value(Array::from_iter(script.metadata.dependencies.iter())),
+            );
+            if let Some(requires_python) = &script.metadata.requires_python {
+                metadata.insert("requires-python", value(requires_python));
+            }
+            if let Some(sources) = &script.metadata.sources {
+                metadata.insert("sources", value(to_inline_table(sources)?));
+            }
+            if script.metadata.settings != ResolverInstallerOptions::default() {
+                metadata.insert(
+                    "settings",
+                    value(to_inline_table(&script.metadata.settings)?),
+                );
+            }
+
+            let mut table = Table::new();
+            table.insert("metadata", Item::Table(metadata));
+            for (key, item) in script.lock.to_toml_table()? {
+                table.insert(&key, item);
+            }
+            scripts.insert(name, Item::Table(table));
+        }
+        doc.insert("scripts", Item::Table(scripts));
+
+        Ok(doc.to_string())
+    }
+}
+
+/// Serialize a value as an inline TOML table.
+fn to_inline_table(value: &impl Serialize) -> anyhow::Result<InlineTable> {
+    let document = toml::to_string(value)?.parse::<toml_edit::DocumentMut>()?;
+    Ok(document.as_table().clone().into_inline_table())
+}
+
+/// Returns the directory containing the given script, along with its name within the directory.
+fn script_location(script: &Pep723Script) -> (PathBuf, String) {
+    let path = CWD.join(&script.path);
+    let dir = path
+        .parent()
+        .expect("script path has no parent")
+        .to_path_buf();
+    let name = path
+        .file_name()
+        .expect("script path has no file name")
+        .to_string_lossy()
+        .to_string();
+    (dir, name)
+}
+
+/// Read the locked resolution for the given script from the `.uv-scripts.lock` in its directory,
+/// if it can be used to run the script with the given interpreter.
+///
+/// If the script's inline metadata changed since it was locked, the resolution is ignored, unless
+/// `frozen` is set. If `locked` is set, an up-to-date resolution is required.
+///
+/// If the directory doesn't contain a `.uv-scripts.lock` at all, `locked` and `frozen` have no
+/// effect, as the script always runs in isolation.
+pub(super) async fn read_script_lock(
+    script: &Pep723Script,
+    interpreter: &Interpreter,
+    locked: bool,
+    frozen: bool,
+) -> anyhow::Result<Option<Lock>> {
+    let (dir, name) = script_location(script);
+
+    let Some(mut lock) = ScriptsLock::read(&dir).await? else {
+        if locked {
+            warn_user_once!(
+                "`--locked` has no effect for Python scripts with inline metadata that aren't locked in a `{SCRIPTS_LOCK}`"
+            );
+        }
+        if frozen {
+            warn_user_once!(
+                "`--frozen` has no effect for Python scripts with inline metadata that aren't locked in a `{SCRIPTS_LOCK}`"
+            );
+        }
+        return Ok(None);
+    };
+    let Some(entry) = lock.scripts.remove(&name) else {
+        if locked || frozen {
+            bail!(
+                "Unable to find `{name}` in `{SCRIPTS_LOCK}`. To lock the script, run `{}`.",
+                format!("uv lock --script {name}").green()
+            );
+        }
+        return Ok(None);
+    };
+
+    if !frozen && entry.metadata != ScriptMetadata::from(script) {
+        if locked {
+            bail!(
+                "The entry for `{name}` in `{SCRIPTS_LOCK}` needs to be updated, but `--locked` was provided. To update the lockfile, run `{}`.",
+                format!("uv lock --script {name}").green()
+            );
+        }
+        warn_user!(
+            "The inline metadata of `{name}` changed since it was locked; ignoring `{SCRIPTS_LOCK}`. To update the lockfile, run `{}`.",
+            format!("uv lock --script {name}").green()
+        );
+        return Ok(None);
+    }
+
+    if let Some(requires_python) = entry.lock.requires_python() {
+        if !requires_python.contains(interpreter.python_version()) {
+            if locked || frozen {
+                return Err(ProjectError::LockedPythonIncompatibility(
+                    interpreter.python_version().clone(),
+                    requires_python.clone(),
+                )
+                .into());
+            }
+            warn_user!(
+                "The Python interpreter ({}) is incompatible with the locked Python requirement of `{name}` (`{requires_python}`); ignoring `{SCRIPTS_LOCK}`",
+                interpreter.python_version()
+            );
+            return Ok(None);
+        }
+    }
+
+    debug!("Using locked resolution for `{name}` from `{SCRIPTS_LOCK}`");
+    Ok(Some(entry.lock))
+}
+
+/// Lock the PEP 723 script at the given path or, if the path is a directory, all PEP 723 scripts
+/// in the directory, into the `.uv-scripts.lock` in the same directory.
+///
+/// Each script is resolved separately, for all platforms. When locking a directory, the entries
+/// for any scripts that no longer exist are removed.
+pub(super) async fn lock_scripts(
+    path: &Path,
+    locked: bool,
+    frozen: bool,
+    python: Option<String>,
+    settings: ResolverSettingsRef<'_>,
+    preview: PreviewMode,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> anyhow::Result<ExitStatus> {
+    // Collect the scripts to lock.
+    let is_dir = path.is_dir();
+    let scripts = if is_dir {
+        let mut scripts = Vec::new();
+        for entry in fs_err::read_dir(path)? {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().map_or(true, |extension| extension != "py") {
+                continue;
+            }
+            if let Some(script) = Pep723Script::read(&path).await? {
+                scripts.push(script);
+            }
+        }
+        if scripts.is_empty() {
+            bail!(
+                "No scripts with inline metadata found in: `{}`",
+                path.user_display()
+            );
+        }
+        scripts.sort_by(|a, b| a.path.cmp(&b.path));
+        scripts
+    } else {
+        let Some(script) = Pep723Script::read(path).await? else {
+            bail!(
+                "`{}` does not contain inline script metadata",
+                path.user_display()
+            );
+        };
+        vec![script]
+    };
+
+    let dir = if is_dir {
+        CWD.join(path)
+    } else {
+        script_location(&scripts[0]).0
+    };
+    let existing = ScriptsLock::read(&dir).await?;
+
+    if frozen {
+        // Verify that each script is locked, without resolving.
+        for script in &scripts {
+            let (_, name) = script_location(script);
+            if !existing
+                .as_ref()
+                .is_some_and(|lock| lock.scripts.contains_key(&name))
+            {
+                bail!(
+                    "Unable to find `{name}` in `{SCRIPTS_LOCK}`. To lock the script, run `{}`.",
+                    format!("uv lock --script {name}").green()
+                );
+            }
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    // Use isolated state for universal resolution, as in `uv lock`.
+    let state = SharedState::default();
+    let reporter = PythonDownloadReporter::single(printer);
+
+    let mut lock = existing.clone().unwrap_or_default();
+    if is_dir {
+        // Remove any scripts that no longer exist.
+        lock.scripts.retain(|name, _| {
+            scripts
+                .iter()
+                .any(|script| script_location(script).1 == *name)
+        });
+    }
+
+    for script in &scripts {
+        let (_, name) = script_location(script);
+        writeln!(printer.stderr(), "Locking script: {}", name.cyan())?;
+
+        let interpreter = find_script_interpreter(
+            script,
+            python.as_deref(),
+            python_preference,
+            python_downloads,
+            connectivity,
+            native_tls,
+            cache,
+            &reporter,
+        )
+        .await?;
+
+        // Lock for the script's `requires-python` or, if it doesn't declare one, for the minor
+        // version of the interpreter and newer.
+        let requires_python = if let Some(specifiers) = script.metadata.requires_python.as_ref() {
+            RequiresPython::from_specifiers(specifiers)?
+        } else {
+            RequiresPython::greater_than_equal_version(&interpreter.python_minor_version())
+        };
+
+        // Prefer the previously locked versions, unless an upgrade was requested.
+        let previous = lock.scripts.get(&name).map(|entry| &entry.lock);
+        let preferences = previous
+            .map(|previous| read_lock_requirements(previous, settings.upgrade).preferences)
+            .unwrap_or_default();
+
+        let spec =
+            RequirementsSpecification::from_requirements(script_requirements(script, preview)?);
+        let result = resolve_universal_environment(
+            &interpreter,
+            &requires_python,
+            spec,
+            preferences,
+            settings,
+            &state,
+            Box::new(DefaultResolveLogger),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await;
+        let graph = match result {
+            Ok(graph) => graph,
+            Err(ProjectError::Operation(pip::operations::Error::Resolve(
+                uv_resolver::ResolveError::NoSolution(err),
+            ))) => {
+                let report = miette::Report::msg(format!("{err}")).context(err.header());
+                eprint!("{report:?}");
+                return Ok(ExitStatus::NoSolution);
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let entry = ScriptLock {
+            metadata: ScriptMetadata::from(script),
+            lock: Lock::from_resolution_graph(&graph)?,
+        };
+        if let Some(previous) = previous {
+            report_upgrades(previous, &entry.lock, printer)?;
+        }
+        lock.scripts.insert(name, entry);
+    }
+
+    if existing.as_ref() == Some(&lock) {
+        return Ok(ExitStatus::Success);
+    }
+
+    if locked {
+        bail!(
+            "The lockfile at `{SCRIPTS_LOCK}` needs to be updated, but `--locked` was provided. To update the lockfile, run `{}`.",
+            format!("uv lock --script {}", path.user_display()).green()
+        );
+    }
+
+    lock.write(&dir).await?;
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,208 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use tracing::debug;
+
+use distribution_types::Resolution;
+use uv_fs::Simplified;
+use uv_python::PythonEnvironment;
+use uv_workspace::Workspace;
+
+use crate::commands::pip::operations::Modifications;
+use crate::commands::project::ProjectError;
+
+/// A marker included in each generated wrapper, used to identify wrappers that can be removed
+/// once their script is no longer declared.
+const MARKER: &str = "This file was generated by uv from `tool.uv.scripts`; do not edit.";
+
+/// Generate wrappers in the environment's scripts directory for the file-based scripts declared
+/// in `tool.uv.scripts` by any workspace members in the resolution.
+///
+/// Each wrapper executes the declared file in place (with the environment's interpreter, for
+/// Python scripts), such that changes to the file take effect without re-syncing, as with an
+/// editable installation. In [`Modifications::Exact`] mode, any wrappers for scripts that are no
+/// longer declared are removed.
+pub(super) fn sync_scripts(
+    workspace: &Workspace,
+    resolution: &Resolution,
+    venv: &PythonEnvironment,
+    modifications: Modifications,
+) -> Result<(), ProjectError> {
+    let scripts_dir = venv.interpreter().layout().scheme.scripts;
+
+    let mut declared = BTreeSet::new();
+    for (name, member) in workspace.packages() {
+        if !resolution.packages().any(|package| package == name) {
+            continue;
+        }
+        let Some(scripts) = member
+            .pyproject_toml()
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.scripts.as_ref())
+        else {
+            continue;
+        };
+
+        for (script, path) in scripts {
+            if script.is_empty() || script.contains(['/', '\\']) || script.starts_with('.') {
+                return Err(ProjectError::InvalidScriptName(script.clone()));
+            }
+            if is_reserved(script) {
+                return Err(ProjectError::ReservedScriptName(script.clone()));
+            }
+
+            let path = member.root().join(path);
+            if !path.is_file() {
+                return Err(ProjectError::MissingScript(script.clone(), path));
+            }
+
+            let wrapper = scripts_dir.join(wrapper_name(script));
+
+            // Never overwrite a file that we didn't generate (e.g., a console script installed by
+            // a package), nor a script declared by another workspace member.
+            if declared.contains(&wrapper) {
+                return Err(ProjectError::ScriptConflict(script.clone(), wrapper));
+            }
+            match fs_err::symlink_metadata(&wrapper) {
+                Ok(metadata) => {
+                    if metadata.is_symlink() || !is_wrapper(&wrapper) {
+                        return Err(ProjectError::ScriptConflict(script.clone(), wrapper));
+                    }
+                    // Remove the existing wrapper, rather than writing through it.
+                    fs_err::remove_file(&wrapper)?;
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+
+            debug!(
+                "Generating wrapper for script `{script}`: {}",
+                wrapper.user_display()
+            );
+            fs_err::create_dir_all(&scripts_dir)?;
+            write_wrapper(&wrapper, &path, venv.python_executable())?;
+            declared.insert(wrapper);
+        }
+    }
+
+    if matches!(modifications, Modifications::Sufficient) {
+        return Ok(());
+    }
+
+    // Remove any wrappers for scripts that are no longer declared.
+    let entries = match fs_err::read_dir(&scripts_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if declared.contains(&path) || !is_wrapper(&path) {
+            continue;
+        }
+        debug!(
+            "Removing wrapper for undeclared script: {}",
+            path.user_display()
+        );
+        fs_err::remove_file(&path)?;
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if the file at the given path is a wrapper generated by [`sync_scripts`].
+fn is_wrapper(path: &Path) -> bool {
+    // Avoid reading large files (e.g., compiled executables), which can't be wrappers.
+    if !fs_err::metadata(path).is_ok_and(|metadata| metadata.is_file() && metadata.len() < 4096) {
+        return false;
+    }
+    fs_err::read(path).is_ok_and(|contents| String::from_utf8_lossy(&contents).contains(MARKER))
+}
+
+/// Returns `true` if a wrapper for the given script would shadow a file provided by the
+/// environment itself, such as the interpreter, `pip`, or the activation scripts.
+fn is_reserved(script: &str) -> bool {
+    /// Returns `true` if the script is the given name followed by an optional version, e.g.,
+    /// `python3.12` or `pip3`.
+    fn is_versioned(script: &str, name: &str) -> bool {
+        script
+            .strip_prefix(name)
+            .is_some_and(|suffix| suffix.chars().all(|c| c.is_ascii_digit() || c == '.'))
+    }
+
+    let script = script.to_ascii_lowercase();
+    is_versioned(&script, "python")
+        || is_versioned(&script, "pythonw")
+        || is_versioned(&script, "pip")
+        || is_versioned(&script, "pydoc")
+        || script == "activate"
+        || script.starts_with("activate.")
+        || script.starts_with("activate_")
+        || script == "deactivate"
+}
+
+/// Returns `true` if the given script should be executed with the Python interpreter, i.e., if it
+/// has a `.py` extension or a `python` shebang.
+fn is_python_script(path: &Path) -> Result<bool, std::io::Error> {
+    if path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("py"))
+    {
+        return Ok(true);
+    }
+    let contents = fs_err::read(path)?;
+    let first_line = contents
+        .split(|byte| *byte == b'\n')
+        .next()
+        .unwrap_or_default();
+    Ok(first_line.starts_with(b"#!") && String::from_utf8_lossy(first_line).contains("python"))
+}
+
+/// Return the file name of the wrapper for the given script.
+fn wrapper_name(script: &str) -> String {
+    if cfg!(windows) {
+        format!("{script}.cmd")
+    } else {
+        script.to_string()
+    }
+}
+
+/// Write a wrapper that executes the given script, forwarding any arguments.
+#[cfg(unix)]
+fn write_wrapper(wrapper: &Path, script: &Path, python: &Path) -> Result<(), std::io::Error> {
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Quote a value for use in a POSIX shell.
+    fn quote(path: &Path) -> String {
+        format!("'{}'", path.to_string_lossy().replace('\'', r"'\''"))
+    }
+
+    let command = if is_python_script(script)? {
+        format!("{} {}", quote(python), quote(script))
+    } else {
+        quote(script)
+    };
+    fs_err::write(
+        wrapper,
+        format!("#!/bin/sh\n# {MARKER}\nexec {command} \"$@\"\n"),
+    )?;
+    fs_err::set_permissions(wrapper, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+/// Write a wrapper that executes the given script, forwarding any arguments.
+#[cfg(windows)]
+fn write_wrapper(wrapper: &Path, script: &Path, python: &Path) -> Result<(), std::io::Error> {
+    let command = if is_python_script(script)? {
+        format!("\"{}\" \"{}\"", python.display(), script.display())
+    } else {
+        format!("\"{}\"", script.display())
+    };
+    fs_err::write(
+        wrapper,
+        format!("@echo off\r\nrem {MARKER}\r\n{command} %*\r\n"),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,464 @@
+use std::io::Write;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tracing::debug;
+
+use pep508_rs::PackageName;
+use pypi_types::{Requirement, VerbatimParsedUrl};
+use uv_cache::Cache;
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, PreviewMode};
+use uv_fs::CWD;
+use uv_python::{Interpreter, PythonDownloads, PythonEnvironment, PythonPreference, PythonRequest};
+use uv_requirements::RequirementsSpecification;
+use uv_resolver::Lock;
+use uv_warnings::warn_user_once;
+use uv_workspace::{DiscoveryOptions, Workspace, WorkspaceMember};
+
+use crate::commands::pip::loggers::DefaultResolveLogger;
+use crate::commands::project::lock::{do_lock, read};
+use crate::commands::project::{resolve_environment, FoundInterpreter, SharedState};
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+use crate::settings::ResolverSettings;
+
+/// The error code for a request that isn't valid JSON.
+const PARSE_ERROR: i64 = -32700;
+
+/// The error code for a request that isn't a valid JSON-RPC request.
+const INVALID_REQUEST: i64 = -32600;
+
+/// The error code for a request to an unknown method.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The error code for a request with invalid parameters.
+const INVALID_PARAMS: i64 = -32602;
+
+/// The error code for a request that failed while being handled (e.g., a failed resolution).
+const SERVER_ERROR: i64 = -32000;
+
+/// Serve queries about the project over JSON-RPC, reading requests from stdin and writing
+/// responses to stdout, one per line.
+pub(crate) async fn server(
+    python: Option<String>,
+    settings: ResolverSettings,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv server` is experimental and may change without warning");
+    }
+
+    // Find an interpreter for the project, which is reused across requests.
+    let workspace = Workspace::discover(&CWD, &DiscoveryOptions::default()).await?;
+    let interpreter = FoundInterpreter::discover(
+        &workspace,
+        python.as_deref().map(PythonRequest::parse),
+        python_preference,
+        python_downloads,
+        connectivity,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?
+    .into_interpreter();
+
+    let mut server = Server {
+        interpreter,
+        settings,
+        lock_state: SharedState::default(),
+        members: members(&workspace),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+    };
+
+    debug!("Listening for requests on stdin");
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (response, shutdown) = server.handle(&line).await;
+        if let Some(response) = response {
+            // Write the responses directly, such that they aren't suppressed by `--quiet`.
+            let mut stdout = std::io::stdout().lock();
+            writeln!(stdout, "{}", serde_json::to_string(&response)?)?;
+            stdout.flush()?;
+        }
+        if shutdown {
+            break;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// A JSON-RPC request.
+#[derive(Debug, Deserialize)]
+struct Request {
+    jsonrpc: String,
+    /// The identifier of the request, which is absent for notifications.
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// A JSON-RPC response.
+#[derive(Debug, Serialize)]
+struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ResponseError>,
+}
+
+impl Response {
+    fn result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    fn error(id: Value, code: i64, message: String) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(ResponseError { code, message }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+/// The parameters of a `dependencies/get` request.
+#[derive(Debug, Deserialize)]
+struct DependenciesParams {
+    package: PackageName,
+}
+
+/// The parameters of a `resolve` request.
+#[derive(Debug, Deserialize)]
+struct ResolveParams {
+    requirements: Vec<String>,
+}
+
+/// The state shared across requests.
+struct Server<'a> {
+    /// The interpreter used for locking and resolving.
+    interpreter: Interpreter,
+    settings: ResolverSettings,
+    /// The in-memory index used when locking the project.
+    ///
+    /// The index caches the metadata of the workspace members, so it's discarded whenever any of
+    /// their `pyproject.toml` files change.
+    lock_state: SharedState,
+    /// The contents of the `pyproject.toml` files of the workspace members, as of the last lock.
+    members: Vec<String>,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &'a Cache,
+}
+
+impl Server<'_> {
+    /// Handle a single line of input, returning the response (if any), and whether the server
+    /// should shut down.
+    async fn handle(&mut self, line: &str) -> (Option<Response>, bool) {
+        let request = match serde_json::from_str::<Value>(line) {
+            Ok(request) => request,
+            Err(err) => {
+                return (
+                    Some(Response::error(Value::Null, PARSE_ERROR, err.to_string())),
+                    false,
+                );
+            }
+        };
+        let request = match serde_json::from_value::<Request>(request) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            Ok(request) => {
+                return (
+                    Some(Response::error(
+                        request.id.unwrap_or_default(),
+                        INVALID_REQUEST,
+                        format!("Unsupported JSON-RPC version: `{}`", request.jsonrpc),
+                    )),
+                    false,
+                );
+            }
+            Err(err) => {
+                return (
+                    Some(Response::error(
+                        Value::Null,
+                        INVALID_REQUEST,
+                        err.to_string(),
+                    )),
+                    false,
+                );
+            }
+        };
+
+        debug!("Received request: `{}`", request.method);
+
+        let shutdown = request.method == "shutdown";
+        let result = match request.method.as_str() {
+            "shutdown" => Ok(Value::Null),
+            "environment/info" => self.environment_info().await,
+            "lock/status" => self.lock_status().await,
+            "dependencies/get" => match serde_json::from_value(request.params) {
+                Ok(params) => self.dependencies(params).await,
+                Err(err) => Err(RequestError::InvalidParams(err)),
+            },
+            "resolve" => match serde_json::from_value(request.params) {
+                Ok(params) => self.resolve(params).await,
+                Err(err) => Err(RequestError::InvalidParams(err)),
+            },
+            method => Err(RequestError::MethodNotFound(method.to_string())),
+        };
+
+        // Notifications don't receive a response.
+        let Some(id) = request.id else {
+            return (None, shutdown);
+        };
+
+        let response = match result {
+            Ok(result) => Response::result(id, result),
+            Err(RequestError::MethodNotFound(method)) => {
+                Response::error(id, METHOD_NOT_FOUND, format!("Unknown method: `{method}`"))
+            }
+            Err(RequestError::InvalidParams(err)) => {
+                Response::error(id, INVALID_PARAMS, err.to_string())
+            }
+            Err(RequestError::Server(err)) => Response::error(
+                id,
+                SERVER_ERROR,
+                err.chain()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(": "),
+            ),
+        };
+        (Some(response), shutdown)
+    }
+
+    /// Describe the interpreter used by the server and the project environment, if it exists.
+    async fn environment_info(&self) -> Result<Value, RequestError> {
+        let workspace = discover().await?;
+
+        let environment = PythonEnvironment::from_root(workspace.venv(), self.cache)
+            .ok()
+            .map(|environment| {
+                serde_json::json!({
+                    "root": environment.root(),
+                    "python": describe(environment.interpreter()),
+                })
+            });
+
+        Ok(serde_json::json!({
+            "workspace": workspace.install_path(),
+            "python": describe(&self.interpreter),
+            "environment": environment,
+        }))
+    }
+
+    /// Determine whether the lockfile exists and is up-to-date with the workspace.
+    async fn lock_status(&mut self) -> Result<Value, RequestError> {
+        let workspace = discover().await?;
+
+        let Some(existing) = read(&workspace).await.map_err(anyhow::Error::from)? else {
+            return Ok(serde_json::json!({ "exists": false, "up_to_date": false }));
+        };
+
+        // Discard the cached metadata if any workspace member has changed.
+        let members = members(&workspace);
+        if members != self.members {
+            debug!("Workspace members changed; invalidating in-memory index");
+            self.lock_state = SharedState::default();
+            self.members = members;
+        }
+
+        let (lock, _) = do_lock(
+            &workspace,
+            &self.interpreter,
+            Some(&existing),
+            None,
+            self.settings.as_ref(),
+            &self.lock_state,
+            Box::new(DefaultResolveLogger),
+            None,
+            self.preview,
+            self.connectivity,
+            self.concurrency,
+            self.native_tls,
+            self.cache,
+            Printer::Quiet,
+        )
+        .await
+        .map_err(anyhow::Error::from)?;
+
+        Ok(serde_json::json!({ "exists": true, "up_to_date": lock == existing }))
+    }
+
+    /// Describe a package in the lockfile, along with its dependencies and dependents.
+    async fn dependencies(&self, params: DependenciesParams) -> Result<Value, RequestError> {
+        let workspace = discover().await?;
+
+        let Some(lock) = read(&workspace).await.map_err(anyhow::Error::from)? else {
+            return Err(anyhow!("No `uv.lock` found; run `uv lock` to create one").into());
+        };
+
+        let mut package = None;
+        let mut dependents = Vec::new();
+        for dist in lock.packages() {
+            let metadata = dist
+                .to_metadata(workspace.install_path())
+                .map_err(anyhow::Error::from)?;
+            if *dist.name() == params.package {
+                package = Some((dist, metadata));
+            } else if metadata
+                .requires_dist
+                .iter()
+                .any(|requirement| requirement.name == params.package)
+            {
+                dependents.push(dist.name().to_string());
+            }
+        }
+
+        let Some((dist, metadata)) = package else {
+            return Err(
+                anyhow!("Package `{}` was not found in the lockfile", params.package).into(),
+            );
+        };
+
+        Ok(serde_json::json!({
+            "name": dist.name(),
+            "version": dist.version().to_string(),
+            "source": dist.source_description(),
+            "dependencies": metadata
+                .requires_dist
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            "dependents": dependents,
+        }))
+    }
+
+    /// Resolve a set of requirements for the server's interpreter, without installing them.
+    ///
+    /// Each request is resolved with a fresh in-memory index, such that long-lived servers observe
+    /// any packages published since a previous request.
+    async fn resolve(&self, params: ResolveParams) -> Result<Value, RequestError> {
+        let requirements = params
+            .requirements
+            .iter()
+            .map(|requirement| {
+                pep508_rs::Requirement::<VerbatimParsedUrl>::parse(requirement, &*CWD)
+                    .map(Requirement::from)
+                    .map_err(|err| anyhow!("Failed to parse `{requirement}`: {err}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if requirements.is_empty() {
+            return Err(anyhow!("At least one requirement must be provided").into());
+        }
+
+        let spec = RequirementsSpecification::from_constraints(requirements, vec![], vec![]);
+        let resolution = resolve_environment(
+            &self.interpreter,
+            spec,
+            self.settings.as_ref(),
+            &SharedState::default(),
+            Box::new(DefaultResolveLogger),
+            self.preview,
+            self.connectivity,
+            self.concurrency,
+            self.native_tls,
+            self.cache,
+            Printer::Quiet,
+        )
+        .await
+        .map_err(anyhow::Error::from)?;
+        let lock = Lock::from_resolution_graph(&resolution).map_err(anyhow::Error::from)?;
+
+        Ok(serde_json::json!({
+            "packages": lock
+                .packages()
+                .iter()
+                .map(|package| serde_json::json!({
+                    "name": package.name(),
+                    "version": package.version().to_string(),
+                }))
+                .collect::<Vec<_>>(),
+        }))
+    }
+}
+
+/// An error encountered while handling a request.
+#[derive(Debug)]
+enum RequestError {
+    MethodNotFound(String),
+    InvalidParams(serde_json::Error),
+    Server(anyhow::Error),
+}
+
+impl From<anyhow::Error> for RequestError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Server(err)
+    }
+}
+
+/// Describe an interpreter, for inclusion in a response.
+fn describe(interpreter: &Interpreter) -> Value {
+    serde_json::json!({
+        "version": interpreter.python_full_version().to_string(),
+        "implementation": interpreter.implementation_name(),
+        "executable": interpreter.sys_executable(),
+        "prefix": interpreter.sys_prefix(),
+    })
+}
+
+/// Discover the workspace in the current directory.
+///
+/// The workspace is discovered anew for each request, to respect any changes to its members.
+async fn discover() -> Result<Workspace> {
+    Ok(Workspace::discover(&CWD, &DiscoveryOptions::default()).await?)
+}
+
+/// Return the contents of the `pyproject.toml` files of the workspace members.
+fn members(workspace: &Workspace) -> Vec<String> {
+    std::iter::once(workspace.pyproject_toml())
+        .chain(
+            workspace
+                .packages()
+                .values()
+                .map(WorkspaceMember::pyproject_toml),
+        )
+        .map(|pyproject_toml| pyproject_toml.raw.clone())
+        .collect()
+}
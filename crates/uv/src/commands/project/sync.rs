@@ -1,26 +1,37 @@
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
 use anyhow::{Context, Result};
+use itertools::Itertools;
+use tracing::debug;
 
+use distribution_types::Resolution;
 use uv_auth::store_credentials_from_url;
-use uv_cache::Cache;
+use uv_cache::{Cache, Refresh};
+use uv_cli::InstallSummary;
 use uv_client::{Connectivity, FlatIndexClient, RegistryClientBuilder};
 use uv_configuration::{
-    Concurrency, ExtrasSpecification, HashCheckingMode, PreviewMode, SetupPyStrategy,
+    AbiCheck, Concurrency, EditorConfig, ExtrasSpecification, FileConflicts, HashCheckingMode,
+    PackageBuildEnv, PreviewMode, Reinstall, SetupPyStrategy,
 };
 use uv_dispatch::BuildDispatch;
-use uv_fs::CWD;
+use uv_fs::{absolutize_path, Simplified, CWD};
 use uv_installer::SitePackages;
-use uv_normalize::{PackageName, DEV_DEPENDENCIES};
+use uv_normalize::{GroupName, PackageName, DEV_DEPENDENCIES};
 use uv_python::{PythonDownloads, PythonEnvironment, PythonPreference, PythonRequest};
 use uv_resolver::{FlatIndex, Lock};
 use uv_types::{BuildIsolation, HashStrategy};
-use uv_warnings::warn_user_once;
-use uv_workspace::{DiscoveryOptions, VirtualProject, Workspace};
+use uv_warnings::{warn_user, warn_user_once};
+use uv_workspace::{DiscoveryOptions, VirtualProject, Workspace, WorkspaceGraph};
 
 use crate::commands::pip::loggers::{DefaultInstallLogger, DefaultResolveLogger, InstallLogger};
 use crate::commands::pip::operations::Modifications;
+use crate::commands::project::dev_tools::sync_dev_tools;
+use crate::commands::project::editor::write_editor_config;
 use crate::commands::project::lock::do_safe_lock;
-use crate::commands::project::{ProjectError, SharedState};
-use crate::commands::{pip, project, ExitStatus};
+use crate::commands::project::scripts::sync_scripts;
+use crate::commands::project::{NamedEnvironment, ProjectError, SharedState};
+use crate::commands::{lock_environment, pip, project, ExitStatus};
 use crate::printer::Printer;
 use crate::settings::{InstallerSettingsRef, ResolverInstallerSettings};
 
@@ -30,9 +41,18 @@ pub(crate) async fn sync(
     locked: bool,
     frozen: bool,
     package: Option<PackageName>,
+    only_affected_by: Option<Vec<PathBuf>>,
+    env: Option<String>,
     extras: ExtrasSpecification,
     dev: bool,
+    groups: Vec<GroupName>,
     modifications: Modifications,
+    verify_imports: bool,
+    reinstall_editables: bool,
+    summary: InstallSummary,
+    show_sizes: bool,
+    read_only_env: bool,
+    editor_config: Option<Vec<EditorConfig>>,
     python: Option<String>,
     python_preference: PythonPreference,
     python_downloads: PythonDownloads,
@@ -60,9 +80,50 @@ pub(crate) async fn sync(
         VirtualProject::discover(&CWD, &DiscoveryOptions::default()).await?
     };
 
+    // Validate that the requested dependency groups are declared in the workspace.
+    let declared = project.workspace().groups();
+    if let Some(group) = groups.iter().find(|group| !declared.contains(*group)) {
+        return Err(ProjectError::MissingGroup(group.clone()).into());
+    }
+
+    // If requested, determine the workspace members affected by the given changes.
+    let affected = if let Some(paths) = only_affected_by {
+        let graph = WorkspaceGraph::from_workspace(project.workspace())?;
+        let paths = paths
+            .iter()
+            .map(|path| absolutize_path(path).map(std::borrow::Cow::into_owned))
+            .collect::<Result<Vec<_>, _>>()?;
+        let affected = graph
+            .affected_by_paths(&paths)
+            .into_iter()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        debug!(
+            "Syncing affected workspace members: {}",
+            affected.iter().join(", ")
+        );
+        Some(affected)
+    } else {
+        None
+    };
+
+    // If a named environment was requested, sync it in lieu of the project environment.
+    let (root, extras, dev, python) = if let Some(name) = env.as_deref() {
+        let NamedEnvironment {
+            venv,
+            extras,
+            dev,
+            python,
+        } = NamedEnvironment::from_workspace(project.workspace(), name, extras, dev, python)?;
+        (venv, extras, dev, python)
+    } else {
+        (project.workspace().venv(), extras, dev, python)
+    };
+
     // Discover or create the virtual environment.
-    let venv = project::get_or_init_environment(
+    let venv = project::get_or_init_environment_at(
         project.workspace(),
+        root,
         python.as_deref().map(PythonRequest::parse),
         python_preference,
         python_downloads,
@@ -78,6 +139,7 @@ pub(crate) async fn sync(
         frozen,
         project.workspace(),
         venv.interpreter(),
+        None,
         settings.as_ref().into(),
         Box::new(DefaultResolveLogger),
         preview,
@@ -95,7 +157,7 @@ pub(crate) async fn sync(
         ))) => {
             let report = miette::Report::msg(format!("{err}")).context(err.header());
             anstream::eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            return Ok(ExitStatus::NoSolution);
         }
         Err(err) => return Err(err.into()),
     };
@@ -106,14 +168,24 @@ pub(crate) async fn sync(
     // Perform the sync operation.
     do_sync(
         &project,
+        affected.as_ref(),
         &venv,
         &lock.lock,
         &extras,
         dev,
+        &groups,
         modifications,
+        verify_imports,
+        reinstall_editables,
         settings.as_ref().into(),
         &state,
-        Box::new(DefaultInstallLogger),
+        Box::new(
+            DefaultInstallLogger::new(
+                summary,
+                direct_dependencies(project.workspace(), &lock.lock),
+            )
+            .with_sizes(show_sizes),
+        ),
         preview,
         connectivity,
         concurrency,
@@ -123,17 +195,193 @@ pub(crate) async fn sync(
     )
     .await?;
 
+    // Install any development tools into the project environment.
+    if dev {
+        sync_dev_tools(
+            project.workspace(),
+            &venv,
+            lock.lock.requires_python(),
+            locked,
+            frozen,
+            &settings,
+            &state,
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+    }
+
+    // Mark the environment as read-only, or restore write access if it was marked previously.
+    let was_read_only = pip::operations::is_read_only(&venv);
+    if read_only_env != was_read_only {
+        debug!(
+            "Marking environment as {}: {}",
+            if read_only_env {
+                "read-only"
+            } else {
+                "writable"
+            },
+            venv.root().user_display()
+        );
+        pip::operations::set_read_only(&venv, read_only_env)?;
+    } else if read_only_env {
+        // Detect any entries that regained write access since the environment was marked
+        // read-only (e.g., if modified outside of uv), and mark them read-only again.
+        let changed = pip::operations::set_read_only(&venv, true)?;
+        if changed > 0 {
+            warn_user!(
+                "Found {changed} writable {} in the read-only environment at `{}`; marking {} read-only again",
+                if changed == 1 { "entry" } else { "entries" },
+                venv.root().user_display(),
+                if changed == 1 { "it" } else { "them" },
+            );
+        }
+    }
+
     Ok(ExitStatus::Success)
 }
 
+/// Return the workspace members, along with the packages they depend on directly, as recorded in
+/// the lockfile.
+fn direct_dependencies(workspace: &Workspace, lock: &Lock) -> Vec<PackageName> {
+    let members = workspace.packages();
+    members
+        .keys()
+        .cloned()
+        .chain(
+            lock.packages()
+                .iter()
+                .filter(|package| members.contains_key(package.name()))
+                .flat_map(uv_resolver::Package::dependency_names)
+                .cloned(),
+        )
+        .collect()
+}
+
 /// Sync a lockfile with an environment.
-pub(super) async fn do_sync(
+///
+/// If `affected` is provided, only the given workspace members (and their dependencies) are
+/// synced. In that case, exact syncs leave any installed packages that are only required by the
+/// remaining members as-is.
+pub(crate) async fn do_sync(
     project: &VirtualProject,
+    affected: Option<&BTreeSet<PackageName>>,
     venv: &PythonEnvironment,
     lock: &Lock,
     extras: &ExtrasSpecification,
     dev: bool,
+    groups: &[GroupName],
+    modifications: Modifications,
+    verify_imports: bool,
+    reinstall_editables: bool,
+    settings: InstallerSettingsRef<'_>,
+    state: &SharedState,
+    logger: Box<dyn InstallLogger>,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<(), ProjectError> {
+    // Validate that the Python version is supported by the lockfile.
+    if let Some(requires_python) = lock.requires_python() {
+        if !requires_python.contains(venv.interpreter().python_version()) {
+            return Err(ProjectError::LockedPythonIncompatibility(
+                venv.interpreter().python_version().clone(),
+                requires_python.clone(),
+            ));
+        }
+    }
+
+    // Include development dependencies, along with any requested dependency groups.
+    let dev = dev
+        .then(|| DEV_DEPENDENCIES.clone())
+        .into_iter()
+        .chain(groups.iter().cloned())
+        .collect::<Vec<_>>();
+
+    let markers = venv.interpreter().markers();
+    let tags = venv.interpreter().tags()?;
+
+    // Read the lockfile.
+    let (resolution, retain) = if let Some(affected) = affected {
+        let restricted = project.clone().with_members(affected);
+        let resolution = lock.to_resolution(
+            &restricted,
+            markers,
+            tags,
+            extras,
+            &dev,
+            settings.build_options,
+        )?;
+        let retain = match modifications {
+            Modifications::Sufficient => BTreeSet::default(),
+            Modifications::Exact => {
+                // Avoid pruning packages that are only required by the unaffected members.
+                let required = resolution.packages().collect::<BTreeSet<_>>();
+                lock.to_resolution(project, markers, tags, extras, &dev, settings.build_options)?
+                    .packages()
+                    .filter(|name| !required.contains(name))
+                    .cloned()
+                    .collect()
+            }
+        };
+        (resolution, retain)
+    } else {
+        let resolution =
+            lock.to_resolution(project, markers, tags, extras, &dev, settings.build_options)?;
+        (resolution, BTreeSet::default())
+    };
+
+    // Sync the environment.
+    do_install(
+        &resolution,
+        &retain,
+        venv,
+        modifications,
+        verify_imports,
+        reinstall_editables,
+        &PackageBuildEnv::from(project.workspace().build_env()),
+        settings,
+        state,
+        logger,
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?;
+
+    // Generate wrappers for any file-based scripts declared by the workspace members. If only the
+    // affected members were synced, leave the wrappers of the remaining members in place.
+    let modifications = if affected.is_some() {
+        Modifications::Sufficient
+    } else {
+        modifications
+    };
+    sync_scripts(project.workspace(), &resolution, venv, modifications)?;
+
+    Ok(())
+}
+
+/// Install a resolution read from a lockfile into an environment.
+///
+/// Any installed packages in `retain` are left as-is, i.e., they're neither updated nor removed.
+pub(super) async fn do_install(
+    resolution: &Resolution,
+    retain: &BTreeSet<PackageName>,
+    venv: &PythonEnvironment,
     modifications: Modifications,
+    verify_imports: bool,
+    reinstall_editables: bool,
+    build_env: &PackageBuildEnv,
     settings: InstallerSettingsRef<'_>,
     state: &SharedState,
     logger: Box<dyn InstallLogger>,
@@ -154,34 +402,49 @@ pub(super) async fn do_sync(
         exclude_newer,
         link_mode,
         compile_bytecode,
+        exclude_package_data,
         reinstall,
         build_options,
         sources,
     } = settings;
 
-    // Validate that the Python version is supported by the lockfile.
-    if let Some(requires_python) = lock.requires_python() {
-        if !requires_python.contains(venv.interpreter().python_version()) {
-            return Err(ProjectError::LockedPythonIncompatibility(
-                venv.interpreter().python_version().clone(),
-                requires_python.clone(),
-            ));
-        }
+    // Prevent concurrent syncs (e.g., from an editor and a terminal) from racing on the
+    // environment.
+    let _lock = lock_environment(venv).await?;
+
+    // Hide any packages that should be retained from the installation plan.
+    let mut site_packages = SitePackages::from_environment(venv)?;
+    for name in retain {
+        site_packages.remove_packages(name);
     }
 
-    // Include development dependencies, if requested.
-    let dev = if dev {
-        vec![DEV_DEPENDENCIES.clone()]
+    // Rebuild any editables whose installed metadata no longer matches their `pyproject.toml`, or
+    // all editables, if requested.
+    let editables = if reinstall_editables {
+        uv_installer::editables(resolution)
+    } else {
+        uv_installer::stale_editables(resolution, &site_packages)
+    };
+    let (reinstall, cache) = if editables.is_empty() {
+        (reinstall.clone(), cache.clone())
     } else {
-        vec![]
+        debug!(
+            "Rebuilding editables: {}",
+            editables.iter().map(ToString::to_string).join(", ")
+        );
+        let reinstall = reinstall.clone().combine(Reinstall::Packages(editables));
+        let refresh = cache
+            .refresh()
+            .clone()
+            .combine(Refresh::from(reinstall.clone()));
+        (reinstall, cache.clone().with_refresh(refresh))
     };
+    let reinstall = &reinstall;
+    let cache = &cache;
 
     let markers = venv.interpreter().markers();
     let tags = venv.interpreter().tags()?;
 
-    // Read the lockfile.
-    let resolution = lock.to_resolution(project, markers, tags, extras, &dev)?;
-
     // Add all authenticated sources to the cache.
     for url in index_locations.urls() {
         store_credentials_from_url(url);
@@ -212,7 +475,7 @@ pub(super) async fn do_sync(
     let setup_py = SetupPyStrategy::default();
 
     // Extract the hashes from the lockfile.
-    let hasher = HashStrategy::from_resolution(&resolution, HashCheckingMode::Verify)?;
+    let hasher = HashStrategy::from_resolution(resolution, HashCheckingMode::Verify)?;
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
@@ -242,22 +505,28 @@ pub(super) async fn do_sync(
         sources,
         concurrency,
         preview,
-    );
-
-    let site_packages = SitePackages::from_environment(venv)?;
+    )
+    .with_package_build_env(build_env.clone());
 
     // Sync the environment.
     pip::operations::install(
-        &resolution,
+        resolution,
         site_packages,
         modifications,
         reinstall,
         build_options,
         link_mode,
         compile_bytecode,
+        verify_imports,
         index_locations,
         &hasher,
         tags,
+        AbiCheck::Ignore,
+        FileConflicts::Ignore,
+        &[],
+        exclude_package_data,
+        None,
+        false,
         &client,
         &state.in_flight,
         concurrency,
@@ -0,0 +1,360 @@
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+use tokio::process::Command;
+use tracing::debug;
+
+use uv_cache::Cache;
+use uv_cli::ExternalCommand;
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, ExtrasSpecification, PreviewMode};
+use uv_fs::CWD;
+use uv_normalize::GroupName;
+use uv_python::{PythonDownloads, PythonEnvironment, PythonPreference, PythonRequest};
+use uv_resolver::Lock;
+use uv_warnings::warn_user_once;
+use uv_workspace::{DiscoveryOptions, VirtualProject};
+
+use crate::commands::pip::loggers::{SummaryInstallLogger, SummaryResolveLogger};
+use crate::commands::pip::operations::Modifications;
+use crate::commands::project::lock::do_safe_lock;
+use crate::commands::project::sync::do_sync;
+use crate::commands::project::{ProjectError, SharedState};
+use crate::commands::{project, ExitStatus};
+use crate::printer::Printer;
+use crate::settings::ResolverInstallerSettings;
+
+/// Run the project's test command against each of its test Python versions, displaying a summary
+/// of the results.
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) async fn test(
+    command: Option<ExternalCommand>,
+    pythons: Vec<String>,
+    groups: Vec<GroupName>,
+    fail_fast: bool,
+    locked: bool,
+    frozen: bool,
+    settings: ResolverInstallerSettings,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv test` is experimental and may change without warning");
+    }
+
+    let project = VirtualProject::discover(&CWD, &DiscoveryOptions::default()).await?;
+    let workspace = project.workspace();
+
+    // Determine the command to run.
+    let (executable, args) = if let Some(command) = command.as_ref() {
+        match command.split() {
+            (Some(executable), args) => (executable.clone(), args.to_vec()),
+            (None, _) => bail!("`uv test` requires a command"),
+        }
+    } else if let Some([executable, args @ ..]) = workspace.test_command() {
+        (
+            OsString::from(executable),
+            args.iter().map(OsString::from).collect(),
+        )
+    } else {
+        bail!(
+            "`uv test` requires a command, either provided as arguments or declared in `tool.uv.test-command`"
+        );
+    };
+    let display = std::iter::once(&executable)
+        .chain(&args)
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // Include the groups declared for testing, along with any requested groups.
+    let mut groups = groups;
+    for group in workspace.test_groups() {
+        if !groups.contains(group) {
+            groups.push(group.clone());
+        }
+    }
+    let declared = workspace.groups();
+    if let Some(group) = groups.iter().find(|group| !declared.contains(*group)) {
+        return Err(ProjectError::MissingGroup(group.clone()).into());
+    }
+
+    let pythons = if pythons.is_empty() {
+        workspace.test_pythons().to_vec()
+    } else {
+        pythons
+    };
+
+    let mut lock = None;
+    let state = SharedState::default();
+
+    // Without any test Python versions, run the tests once, in the project environment.
+    if pythons.is_empty() {
+        let venv = project::get_or_init_environment(
+            workspace,
+            None,
+            python_preference,
+            python_downloads,
+            connectivity,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+        let lock = resolve_lock(
+            &mut lock,
+            &venv,
+            &project,
+            locked,
+            frozen,
+            &settings,
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+        sync_environment(
+            &project,
+            &venv,
+            lock,
+            &groups,
+            &settings,
+            &state,
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+        return run_command(&venv, &executable, &args, &display).await;
+    }
+
+    // Run the tests against each Python version in turn, continuing past any failures.
+    let mut results = Vec::with_capacity(pythons.len());
+    for python in pythons {
+        writeln!(
+            printer.stderr(),
+            "{}",
+            format!("Testing with Python {python}").bold()
+        )?;
+
+        let result = async {
+            let venv = project::get_or_init_secondary_environment(
+                workspace,
+                PythonRequest::parse(&python),
+                python_preference,
+                python_downloads,
+                connectivity,
+                native_tls,
+                cache,
+                printer,
+            )
+            .await?;
+            let lock = resolve_lock(
+                &mut lock,
+                &venv,
+                &project,
+                locked,
+                frozen,
+                &settings,
+                preview,
+                connectivity,
+                concurrency,
+                native_tls,
+                cache,
+                printer,
+            )
+            .await?;
+            sync_environment(
+                &project,
+                &venv,
+                lock,
+                &groups,
+                &settings,
+                &state,
+                preview,
+                connectivity,
+                concurrency,
+                native_tls,
+                cache,
+                printer,
+            )
+            .await?;
+            run_command(&venv, &executable, &args, &display).await
+        }
+        .await;
+
+        let status = match result {
+            Ok(status) => status,
+            Err(err) => {
+                let mut causes = err.chain();
+                writeln!(
+                    printer.stderr(),
+                    "{}: {}",
+                    "error".red().bold(),
+                    causes.next().unwrap()
+                )?;
+                for err in causes {
+                    writeln!(printer.stderr(), "  {}: {}", "Caused by".red().bold(), err)?;
+                }
+                ExitStatus::from_error(err.as_ref())
+            }
+        };
+        results.push((python, status));
+
+        if fail_fast && status != ExitStatus::Success {
+            break;
+        }
+    }
+
+    writeln!(printer.stderr())?;
+    writeln!(printer.stderr(), "{}", "Summary:".bold())?;
+    for (python, status) in &results {
+        if *status == ExitStatus::Success {
+            writeln!(printer.stderr(), "  Python {python}: {}", "passed".green())?;
+        } else {
+            writeln!(printer.stderr(), "  Python {python}: {}", "failed".red())?;
+        }
+    }
+
+    // Exit with the status of the first failure, e.g., the exit code of the test command.
+    Ok(results
+        .into_iter()
+        .map(|(_, status)| status)
+        .find(|status| *status != ExitStatus::Success)
+        .unwrap_or(ExitStatus::Success))
+}
+
+/// Lock the project, if it hasn't been locked already.
+///
+/// The lockfile is universal, so the project is locked once, with the interpreter of the first
+/// environment, and the same lockfile is used to sync every environment.
+async fn resolve_lock<'lock>(
+    lock: &'lock mut Option<Lock>,
+    venv: &PythonEnvironment,
+    project: &VirtualProject,
+    locked: bool,
+    frozen: bool,
+    settings: &ResolverInstallerSettings,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<&'lock Lock> {
+    if lock.is_none() {
+        let result = do_safe_lock(
+            locked,
+            frozen,
+            project.workspace(),
+            venv.interpreter(),
+            None,
+            settings.as_ref().into(),
+            Box::new(SummaryResolveLogger),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+        *lock = Some(result.lock);
+    }
+    Ok(lock.as_ref().expect("lock to be initialized"))
+}
+
+/// Sync the given environment with the lockfile, including the development dependencies and the
+/// given dependency groups.
+async fn sync_environment(
+    project: &VirtualProject,
+    venv: &PythonEnvironment,
+    lock: &Lock,
+    groups: &[GroupName],
+    settings: &ResolverInstallerSettings,
+    state: &SharedState,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<(), ProjectError> {
+    do_sync(
+        project,
+        None,
+        venv,
+        lock,
+        &ExtrasSpecification::None,
+        true,
+        groups,
+        Modifications::Sufficient,
+        false,
+        false,
+        settings.as_ref().into(),
+        state,
+        Box::new(SummaryInstallLogger),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await
+}
+
+/// Run the test command in the given environment.
+async fn run_command(
+    venv: &PythonEnvironment,
+    executable: &OsString,
+    args: &[OsString],
+    display: &str,
+) -> Result<ExitStatus> {
+    debug!("Running `{display}`");
+    let mut process = Command::new(executable);
+    process.args(args);
+
+    // Expose the environment's executables on the `PATH`.
+    let new_path = std::env::join_paths(
+        std::iter::once(PathBuf::from(venv.scripts())).chain(
+            std::env::var_os("PATH")
+                .as_ref()
+                .iter()
+                .flat_map(std::env::split_paths),
+        ),
+    )?;
+    process.env("PATH", new_path);
+    process.env("VIRTUAL_ENV", venv.root());
+
+    let mut handle = process
+        .spawn()
+        .with_context(|| format!("Failed to spawn: `{}`", executable.to_string_lossy()))?;
+    let status = handle.wait().await.context("Child process disappeared")?;
+
+    // Exit with the code of the test command, if it has one (i.e., wasn't terminated by a signal)
+    // that can be represented as an exit code.
+    if status.success() {
+        Ok(ExitStatus::Success)
+    } else {
+        Ok(status
+            .code()
+            .and_then(|code| u8::try_from(code).ok())
+            .map_or(ExitStatus::Failure, ExitStatus::External))
+    }
+}
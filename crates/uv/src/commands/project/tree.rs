@@ -5,6 +5,7 @@ use anyhow::Result;
 
 use pep508_rs::PackageName;
 use uv_cache::Cache;
+use uv_cli::TreeFormat;
 use uv_client::Connectivity;
 use uv_configuration::{Concurrency, PreviewMode, TargetTriple};
 use uv_fs::CWD;
@@ -30,6 +31,7 @@ pub(crate) async fn tree(
     package: Vec<PackageName>,
     no_dedupe: bool,
     invert: bool,
+    format: TreeFormat,
     python_version: Option<PythonVersion>,
     python_platform: Option<TargetTriple>,
     python: Option<String>,
@@ -70,6 +72,7 @@ pub(crate) async fn tree(
         frozen,
         &workspace,
         &interpreter,
+        None,
         settings.as_ref(),
         Box::new(DefaultResolveLogger),
         preview,
@@ -102,7 +105,16 @@ pub(crate) async fn tree(
         invert,
     );
 
-    write!(printer.stdout(), "{tree}")?;
+    match format {
+        TreeFormat::Text => write!(printer.stdout(), "{tree}")?,
+        TreeFormat::Dot => write!(printer.stdout(), "{}", tree.graph().to_dot())?,
+        TreeFormat::Mermaid => write!(printer.stdout(), "{}", tree.graph().to_mermaid())?,
+        TreeFormat::Json => writeln!(
+            printer.stdout(),
+            "{}",
+            serde_json::to_string_pretty(&tree.graph())?
+        )?,
+    }
 
     Ok(ExitStatus::Success)
 }
@@ -0,0 +1,290 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use tempfile::TempDir;
+
+use distribution_types::{InstalledDist, Name};
+use install_wheel_rs::{parse_wheel_file, LibKind};
+use uv_cache::Cache;
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, ExtrasSpecification, PackageBuildEnv, PreviewMode};
+use uv_fs::{Simplified, CWD};
+use uv_installer::SitePackages;
+use uv_normalize::DEV_DEPENDENCIES;
+use uv_python::{PythonDownloads, PythonEnvironment, PythonPreference, PythonRequest, Target};
+use uv_warnings::warn_user_once;
+use uv_workspace::{DiscoveryOptions, VirtualProject};
+
+use crate::commands::pip::loggers::{DefaultInstallLogger, DefaultResolveLogger};
+use crate::commands::pip::operations::Modifications;
+use crate::commands::project::lock::do_safe_lock;
+use crate::commands::project::sync::do_install;
+use crate::commands::project::{FoundInterpreter, ProjectError, SharedState};
+use crate::commands::{pip, ExitStatus};
+use crate::printer::Printer;
+use crate::settings::ResolverInstallerSettings;
+
+/// The name of the manifest written to the vendor directory.
+const MANIFEST: &str = "uv-vendor.json";
+
+/// The loader that adds the vendor directory to `sys.path` when imported.
+const LOADER: &str = "\
+# Generated by uv. Importing this module adds the vendored packages to `sys.path`.
+import os
+import site
+
+site.addsitedir(os.path.dirname(os.path.abspath(__file__)))
+";
+
+/// Unpack the locked dependencies of the project into a project-local directory.
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) async fn vendor(
+    locked: bool,
+    frozen: bool,
+    extras: ExtrasSpecification,
+    dev: bool,
+    output_dir: PathBuf,
+    python: Option<String>,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    settings: ResolverInstallerSettings,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv vendor` is experimental and may change without warning");
+    }
+
+    // Identify the project.
+    let project = VirtualProject::discover(&CWD, &DiscoveryOptions::default()).await?;
+
+    // Find an interpreter for the project.
+    let interpreter = FoundInterpreter::discover(
+        project.workspace(),
+        python.as_deref().map(PythonRequest::parse),
+        python_preference,
+        python_downloads,
+        connectivity,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?
+    .into_interpreter();
+
+    let lock = match do_safe_lock(
+        locked,
+        frozen,
+        project.workspace(),
+        &interpreter,
+        None,
+        settings.as_ref().into(),
+        Box::new(DefaultResolveLogger),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await
+    {
+        Ok(result) => result.lock,
+        Err(ProjectError::Operation(pip::operations::Error::Resolve(
+            uv_resolver::ResolveError::NoSolution(err),
+        ))) => {
+            let report = miette::Report::msg(format!("{err}")).context(err.header());
+            anstream::eprint!("{report:?}");
+            return Ok(ExitStatus::NoSolution);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Validate that the Python version is supported by the lockfile.
+    if let Some(requires_python) = lock.requires_python() {
+        if !requires_python.contains(interpreter.python_version()) {
+            return Err(ProjectError::LockedPythonIncompatibility(
+                interpreter.python_version().clone(),
+                requires_python.clone(),
+            )
+            .into());
+        }
+    }
+
+    // Include development dependencies, if requested.
+    let dev = if dev {
+        vec![DEV_DEPENDENCIES.clone()]
+    } else {
+        vec![]
+    };
+
+    // Read the lockfile, omitting the project itself. Any workspace members that the project
+    // depends on are vendored too; since editable installs point back to their source tree, they're
+    // installed as regular distributions instead.
+    let project_name = project.project_name().cloned();
+    let resolution = lock
+        .to_resolution(
+            &project,
+            interpreter.markers(),
+            interpreter.tags()?,
+            &extras,
+            &dev,
+            &settings.build_options,
+        )?
+        .filter(|dist| project_name.as_ref() != Some(dist.name()))
+        .non_editable();
+
+    let output_dir = project.workspace().install_path().join(output_dir);
+    ensure_replaceable(&output_dir)?;
+
+    // Install into a staging directory alongside the output, such that a failed install leaves
+    // any existing vendor directory intact. The staging directory is removed on drop, unless it
+    // replaces the output directory.
+    let staging = staging_dir(&output_dir)?;
+    let python_version = interpreter.python_version().clone();
+    let environment = PythonEnvironment::from_interpreter(interpreter)
+        .with_target(Target::from(staging.path().to_path_buf()))?;
+
+    do_install(
+        &resolution,
+        &BTreeSet::default(),
+        &environment,
+        Modifications::Exact,
+        false,
+        false,
+        &PackageBuildEnv::from(project.workspace().build_env()),
+        settings.as_ref().into(),
+        &SharedState::default(),
+        Box::new(DefaultInstallLogger::default()),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?;
+
+    // Reject any distributions that include compiled extensions.
+    let site_packages = SitePackages::from_environment(&environment)?;
+    let mut packages = Vec::new();
+    let mut platform = Vec::new();
+    for dist in site_packages.iter() {
+        if !is_pure(dist)? {
+            platform.push(dist.name().to_string());
+        }
+        packages.push(ManifestPackage {
+            name: dist.name().to_string(),
+            version: dist.version().to_string(),
+        });
+    }
+    if !platform.is_empty() {
+        bail!(
+            "Only pure-Python dependencies can be vendored, but the following include compiled extensions: {}",
+            platform.iter().sorted().map(|name| format!("`{name}`")).join(", ")
+        );
+    }
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // Entrypoints reference the interpreter they were installed with, so they aren't portable.
+    let scripts = staging.path().join("bin");
+    if scripts.is_dir() {
+        fs_err::remove_dir_all(&scripts)?;
+    }
+
+    fs_err::write(staging.path().join("__init__.py"), LOADER)?;
+    let manifest = Manifest {
+        python: python_version.to_string(),
+        packages,
+    };
+    fs_err::write(
+        staging.path().join(MANIFEST),
+        serde_json::to_string_pretty(&manifest)? + "\n",
+    )?;
+
+    // Replace the existing vendor directory, if any.
+    if output_dir.exists() {
+        fs_err::remove_dir_all(&output_dir)?;
+    }
+    fs_err::rename(staging.into_path(), &output_dir)?;
+
+    writeln!(
+        printer.stderr(),
+        "Vendored {} into: {}",
+        format!(
+            "{} package{}",
+            manifest.packages.len(),
+            if manifest.packages.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        )
+        .bold(),
+        output_dir.user_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// The manifest describing the contents of a vendor directory.
+#[derive(Debug, Serialize)]
+struct Manifest {
+    /// The version of the interpreter against which the dependencies were resolved.
+    python: String,
+    packages: Vec<ManifestPackage>,
+}
+
+#[derive(Debug, Serialize)]
+struct ManifestPackage {
+    name: String,
+    version: String,
+}
+
+/// Returns `true` if the installed distribution is pure Python, per its `WHEEL` file.
+fn is_pure(dist: &InstalledDist) -> Result<bool> {
+    let path = dist.path().join("WHEEL");
+    let contents = fs_err::read_to_string(&path)?;
+    let kind = parse_wheel_file(&contents)
+        .with_context(|| format!("Failed to parse: {}", path.user_display()))?;
+    Ok(matches!(kind, LibKind::Pure))
+}
+
+/// Ensure that the output directory is either absent, empty, or a previous vendor directory, to
+/// avoid removing unrelated files.
+fn ensure_replaceable(output_dir: &Path) -> Result<()> {
+    if !output_dir.exists() || output_dir.join(MANIFEST).is_file() {
+        return Ok(());
+    }
+    if !output_dir.is_dir() || fs_err::read_dir(output_dir)?.next().is_some() {
+        bail!(
+            "The output directory `{}` already exists and was not created by `uv vendor`",
+            output_dir.user_display()
+        );
+    }
+    Ok(())
+}
+
+/// Create a temporary staging directory alongside the given output directory, such that it can be
+/// renamed into place.
+fn staging_dir(output_dir: &Path) -> Result<TempDir> {
+    let file_name = output_dir
+        .file_name()
+        .with_context(|| format!("Invalid output directory: {}", output_dir.user_display()))?;
+    let parent = output_dir
+        .parent()
+        .with_context(|| format!("Invalid output directory: {}", output_dir.user_display()))?;
+    fs_err::create_dir_all(parent)?;
+    Ok(tempfile::Builder::new()
+        .prefix(&format!(".{}.", file_name.to_string_lossy()))
+        .tempdir_in(parent)?)
+}
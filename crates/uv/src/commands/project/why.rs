@@ -0,0 +1,187 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+
+use pep508_rs::{PackageName, Requirement, VersionOrUrl};
+use pypi_types::VerbatimParsedUrl;
+use uv_cache::Cache;
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, PreviewMode, TargetTriple};
+use uv_fs::CWD;
+use uv_installer::SitePackages;
+use uv_python::{
+    PythonDownloads, PythonEnvironment, PythonPreference, PythonRequest, PythonVersion,
+};
+use uv_resolver::{DependencyConstraints, WhyDisplay};
+use uv_warnings::warn_user_once;
+use uv_workspace::{DiscoveryOptions, Workspace};
+
+use crate::commands::pip::loggers::DefaultResolveLogger;
+use crate::commands::project::FoundInterpreter;
+use crate::commands::{project, ExitStatus};
+use crate::printer::Printer;
+use crate::settings::ResolverSettings;
+
+/// Explain why a package is included in the lockfile.
+#[allow(clippy::fn_params_excessive_bools)]
+pub(crate) async fn why(
+    package: PackageName,
+    locked: bool,
+    frozen: bool,
+    universal: bool,
+    python_version: Option<PythonVersion>,
+    python_platform: Option<TargetTriple>,
+    python: Option<String>,
+    settings: ResolverSettings,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv why` is experimental and may change without warning");
+    }
+
+    // Find the project requirements.
+    let workspace = Workspace::discover(&CWD, &DiscoveryOptions::default()).await?;
+
+    // Find an interpreter for the project
+    let interpreter = FoundInterpreter::discover(
+        &workspace,
+        python.as_deref().map(PythonRequest::parse),
+        python_preference,
+        python_downloads,
+        connectivity,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?
+    .into_interpreter();
+
+    // Update the lockfile, if necessary.
+    let lock = project::lock::do_safe_lock(
+        locked,
+        frozen,
+        &workspace,
+        &interpreter,
+        None,
+        settings.as_ref(),
+        Box::new(DefaultResolveLogger),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?;
+
+    if !lock
+        .lock
+        .packages()
+        .iter()
+        .any(|dist| *dist.name() == package)
+    {
+        bail!("Package `{package}` was not found in the lockfile");
+    }
+
+    // Apply the platform tags to the markers.
+    let markers = match (python_platform, python_version) {
+        (Some(python_platform), Some(python_version)) => {
+            Cow::Owned(python_version.markers(&python_platform.markers(interpreter.markers())))
+        }
+        (Some(python_platform), None) => Cow::Owned(python_platform.markers(interpreter.markers())),
+        (None, Some(python_version)) => Cow::Owned(python_version.markers(interpreter.markers())),
+        (None, None) => Cow::Borrowed(interpreter.markers()),
+    };
+
+    // Render the dependency chains.
+    let constraints = dependency_constraints(&workspace, cache);
+    let why = WhyDisplay::new(
+        &lock.lock,
+        (!universal).then(|| markers.as_ref()),
+        &package,
+        &constraints,
+    )
+    .to_string();
+
+    if why.is_empty() {
+        writeln!(
+            printer.stderr(),
+            "Package `{package}` is not required on the current platform (use `{}` to show all platforms)",
+            "--universal".green()
+        )?;
+    } else {
+        write!(printer.stdout(), "{why}")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Collect the version constraints that each package declares on its dependencies.
+///
+/// The lockfile doesn't record the constraints, so they're read from the `pyproject.toml` files of
+/// the workspace members and, for any other packages, from the metadata of the packages installed
+/// in the project environment, if it exists.
+fn dependency_constraints(workspace: &Workspace, cache: &Cache) -> DependencyConstraints {
+    let mut constraints = DependencyConstraints::default();
+
+    if let Ok(site_packages) = PythonEnvironment::from_root(workspace.venv(), cache)
+        .map_err(anyhow::Error::from)
+        .and_then(|environment| SitePackages::from_environment(&environment))
+    {
+        for dist in site_packages.iter() {
+            let Ok(metadata) = dist.metadata() else {
+                continue;
+            };
+            insert_constraints(&mut constraints, metadata.name, &metadata.requires_dist);
+        }
+    }
+
+    // The declared requirements of the workspace members take precedence over any (possibly
+    // stale) metadata in the environment.
+    for (name, member) in workspace.packages() {
+        constraints.remove(name);
+
+        if let Ok(metadata) =
+            pypi_types::RequiresDist::parse_pyproject_toml(&member.pyproject_toml().raw)
+        {
+            insert_constraints(&mut constraints, name.clone(), &metadata.requires_dist);
+        }
+
+        if let Some(dev_dependencies) = member
+            .pyproject_toml()
+            .tool
+            .as_ref()
+            .and_then(|tool| tool.uv.as_ref())
+            .and_then(|uv| uv.dev_dependencies.as_ref())
+        {
+            insert_constraints(&mut constraints, name.clone(), dev_dependencies);
+        }
+    }
+
+    constraints
+}
+
+/// Record the version specifiers of the given requirements as constraints of the given package.
+fn insert_constraints(
+    constraints: &mut DependencyConstraints,
+    name: PackageName,
+    requirements: &[Requirement<VerbatimParsedUrl>],
+) {
+    let entry = constraints.entry(name).or_default();
+    for requirement in requirements {
+        if let Some(VersionOrUrl::VersionSpecifier(specifiers)) = &requirement.version_or_url {
+            entry
+                .entry(requirement.name.clone())
+                .or_insert_with(|| specifiers.clone());
+        }
+    }
+}
@@ -6,14 +6,16 @@ use itertools::Itertools;
 use owo_colors::OwoColorize;
 use std::collections::BTreeSet;
 use std::fmt::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::debug;
 use uv_cache::Cache;
 use uv_client::Connectivity;
 use uv_configuration::PreviewMode;
 use uv_fs::CWD;
 use uv_python::downloads::{DownloadResult, ManagedPythonDownload, PythonDownloadRequest};
-use uv_python::managed::{ManagedPythonInstallation, ManagedPythonInstallations};
+use uv_python::managed::{
+    ManagedPythonInstallation, ManagedPythonInstallations, PythonInstallHooks,
+};
 use uv_python::{
     requests_from_version_file, PythonDownloads, PythonRequest, PYTHON_VERSIONS_FILENAME,
     PYTHON_VERSION_FILENAME,
@@ -26,9 +28,14 @@ use crate::commands::{elapsed, ExitStatus};
 use crate::printer::Printer;
 
 /// Download and install Python versions.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn install(
     targets: Vec<String>,
     reinstall: bool,
+    ensurepip: bool,
+    sitecustomize: Option<&Path>,
+    safe_path: bool,
+    allow_emulated: bool,
     python_downloads: PythonDownloads,
     native_tls: bool,
     connectivity: Connectivity,
@@ -43,6 +50,13 @@ pub(crate) async fn install(
 
     let start = std::time::Instant::now();
 
+    // Determine the post-install hooks to apply to each installation.
+    let hooks = PythonInstallHooks {
+        disable_ensurepip: !ensurepip,
+        sitecustomize: sitecustomize.map(fs::read_to_string).transpose()?,
+        safe_path,
+    };
+
     let installations = ManagedPythonInstallations::from_settings()?.init()?;
     let installations_dir = installations.root();
     let _lock = installations.acquire_lock()?;
@@ -108,6 +122,12 @@ pub(crate) async fn install(
                 fs::remove_dir_all(installation.path())?;
                 uninstalled.push(installation.key().clone());
                 unfilled_requests.push(download_request);
+            } else if installation.apply_hooks(&hooks)? {
+                writeln!(
+                    printer.stderr(),
+                    "Updated post-install hooks for: {}",
+                    installation.key().green()
+                )?;
             }
         } else {
             unfilled_requests.push(download_request);
@@ -136,9 +156,14 @@ pub(crate) async fn install(
 
     let downloads = unfilled_requests
         .into_iter()
-        // Populate the download requests with defaults
-        .map(PythonDownloadRequest::fill)
-        .map(|request| ManagedPythonDownload::from_request(&request))
+        .map(|request| {
+            if allow_emulated {
+                ManagedPythonDownload::from_request_or_emulated(&request)
+            } else {
+                // Populate the download requests with defaults
+                ManagedPythonDownload::from_request(&request.fill())
+            }
+        })
         .collect::<Result<Vec<_>, uv_python::downloads::Error>>()?;
 
     // Ensure we only download each version once
@@ -155,13 +180,15 @@ pub(crate) async fn install(
 
     let reporter = PythonDownloadReporter::new(printer, downloads.len() as u64);
 
+    let client = &client;
+    let reporter = &reporter;
     let mut tasks = FuturesUnordered::new();
     for download in &downloads {
-        tasks.push(async {
+        tasks.push(async move {
             (
                 download.key(),
                 download
-                    .fetch(&client, installations_dir, cache, Some(&reporter))
+                    .fetch(client, installations_dir, cache, Some(reporter))
                     .await,
             )
         });
@@ -183,6 +210,9 @@ pub(crate) async fn install(
                 // Ensure the installations have externally managed markers
                 let managed = ManagedPythonInstallation::new(path.clone())?;
                 managed.ensure_externally_managed()?;
+
+                // Apply any post-install hooks
+                managed.apply_hooks(&hooks)?;
             }
             Err(err) => {
                 errors.push((key, err));
@@ -242,6 +272,12 @@ pub(crate) async fn install(
     }
 
     if !errors.is_empty() {
+        // Report the class of the failure (e.g., a network error), if known.
+        let status = errors
+            .iter()
+            .map(|(_, err)| ExitStatus::from_error(err))
+            .find(|status| *status != ExitStatus::Error)
+            .unwrap_or(ExitStatus::Failure);
         for (key, err) in errors {
             writeln!(
                 printer.stderr(),
@@ -250,7 +286,7 @@ pub(crate) async fn install(
                 key.green(),
             )?;
         }
-        return Ok(ExitStatus::Failure);
+        return Ok(status);
     }
 
     Ok(ExitStatus::Success)
@@ -3,11 +3,14 @@ use std::fmt::Write;
 
 use anyhow::Result;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
 
 use uv_cache::Cache;
 use uv_configuration::PreviewMode;
 use uv_fs::Simplified;
-use uv_python::downloads::PythonDownloadRequest;
+use uv_python::downloads::{ManagedPythonDownload, PythonDownloadRequest};
+use uv_python::managed::ManagedPythonInstallations;
 use uv_python::{
     find_python_installations, DiscoveryError, EnvironmentPreference, PythonDownloads,
     PythonInstallation, PythonNotFound, PythonPreference, PythonRequest, PythonSource,
@@ -31,6 +34,7 @@ pub(crate) async fn list(
     kinds: PythonListKinds,
     all_versions: bool,
     all_platforms: bool,
+    json: bool,
     python_preference: PythonPreference,
     python_downloads: PythonDownloads,
     preview: PreviewMode,
@@ -42,57 +46,67 @@ pub(crate) async fn list(
     }
 
     let mut output = BTreeSet::new();
-    if python_preference != PythonPreference::OnlySystem {
-        let download_request = match kinds {
-            PythonListKinds::Installed => None,
-            PythonListKinds::Default => {
-                if python_downloads.is_automatic() {
-                    Some(if all_platforms {
-                        PythonDownloadRequest::default()
-                    } else {
-                        PythonDownloadRequest::from_env()?
-                    })
+    let download_request = match kinds {
+        PythonListKinds::Installed => None,
+        // If downloads were requested explicitly, show them regardless of the Python preference.
+        PythonListKinds::Downloads => Some(if all_platforms {
+            PythonDownloadRequest::default()
+        } else {
+            PythonDownloadRequest::from_env()?
+        }),
+        PythonListKinds::Default => {
+            if python_preference != PythonPreference::OnlySystem && python_downloads.is_automatic()
+            {
+                Some(if all_platforms {
+                    PythonDownloadRequest::default()
                 } else {
-                    // If fetching is not automatic, then don't show downloads as available by default
-                    None
-                }
+                    PythonDownloadRequest::from_env()?
+                })
+            } else {
+                // If fetching is not automatic, then don't show downloads as available by default
+                None
             }
-        };
-
-        let downloads = download_request
-            .as_ref()
-            .map(uv_python::downloads::PythonDownloadRequest::iter_downloads)
-            .into_iter()
-            .flatten();
-
-        for download in downloads {
-            output.insert((
-                download.python_version().version().clone(),
-                download.os().to_string(),
-                download.key().clone(),
-                Kind::Download,
-                None,
-            ));
         }
     };
 
-    let installed = find_python_installations(
-        &PythonRequest::Any,
-        EnvironmentPreference::OnlySystem,
-        python_preference,
-        cache,
-    )
-    // Raise discovery errors if critical
-    .filter(|result| {
-        result
-            .as_ref()
-            .err()
-            .map_or(true, DiscoveryError::is_critical)
-    })
-    .collect::<Result<Vec<Result<PythonInstallation, PythonNotFound>>, DiscoveryError>>()?
-    .into_iter()
-    // Drop any "missing" installations
-    .filter_map(Result::ok);
+    let downloads = download_request
+        .as_ref()
+        .map(uv_python::downloads::PythonDownloadRequest::iter_downloads)
+        .into_iter()
+        .flatten();
+
+    for download in downloads {
+        output.insert((
+            download.python_version().version().clone(),
+            download.os().to_string(),
+            download.key().clone(),
+            Kind::Download,
+            None,
+        ));
+    }
+
+    let installed = if matches!(kinds, PythonListKinds::Downloads) {
+        Vec::new()
+    } else {
+        find_python_installations(
+            &PythonRequest::Any,
+            EnvironmentPreference::OnlySystem,
+            python_preference,
+            cache,
+        )
+        // Raise discovery errors if critical
+        .filter(|result| {
+            result
+                .as_ref()
+                .err()
+                .map_or(true, DiscoveryError::is_critical)
+        })
+        .collect::<Result<Vec<Result<PythonInstallation, PythonNotFound>>, DiscoveryError>>()?
+        .into_iter()
+        // Drop any "missing" installations
+        .filter_map(Result::ok)
+        .collect()
+    };
 
     for installation in installed {
         let kind = if matches!(installation.source(), PythonSource::Managed) {
@@ -131,15 +145,79 @@ pub(crate) async fn list(
                 }
             }
         }
-        include.push((key, path));
+        include.push((key, kind, path));
+    }
+
+    if json {
+        // Look up the provenance recorded for each managed installation.
+        let provenance = ManagedPythonInstallations::from_settings()?
+            .find_all()?
+            .filter_map(|installation| {
+                let provenance = installation.provenance()?;
+                Some((installation.key().clone(), provenance))
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        let mut entries = Vec::with_capacity(include.len());
+        for (key, kind, path) in include {
+            let (url, sha256) = match kind {
+                Kind::Download => ManagedPythonDownload::iter_all()
+                    .find(|download| download.key() == key)
+                    .map(|download| {
+                        (
+                            Some(download.url().to_string()),
+                            download.sha256().map(ToString::to_string),
+                        )
+                    })
+                    .unwrap_or_default(),
+                Kind::Managed => provenance
+                    .get(key)
+                    .map(|provenance| {
+                        (
+                            Some(provenance.url.clone()),
+                            Some(provenance.sha256.clone()),
+                        )
+                    })
+                    .unwrap_or_default(),
+                Kind::System => (None, None),
+            };
+            let symlink = match path {
+                Some(path) if fs_err::symlink_metadata(path)?.is_symlink() => {
+                    Some(path.read_link()?.simplified_display().to_string())
+                }
+                _ => None,
+            };
+            entries.push(Entry {
+                key: key.to_string(),
+                version: key.version().to_string(),
+                implementation: key.implementation().to_string(),
+                os: key.os().to_string(),
+                arch: key.arch().to_string(),
+                libc: key.libc().to_string(),
+                kind: match kind {
+                    Kind::Download => "download",
+                    Kind::Managed => "managed",
+                    Kind::System => "system",
+                },
+                path: path
+                    .as_ref()
+                    .map(|path| path.simplified_display().to_string()),
+                symlink,
+                url,
+                sha256,
+            });
+        }
+
+        writeln!(printer.stdout(), "{}", serde_json::to_string(&entries)?)?;
+        return Ok(ExitStatus::Success);
     }
 
     // Compute the width of the first column.
     let width = include
         .iter()
-        .fold(0usize, |acc, (key, _)| acc.max(key.to_string().len()));
+        .fold(0usize, |acc, (key, _, _)| acc.max(key.to_string().len()));
 
-    for (key, path) in include {
+    for (key, _, path) in include {
         let key = key.to_string();
         if let Some(path) = path {
             let is_symlink = fs_err::symlink_metadata(path)?.is_symlink();
@@ -168,3 +246,19 @@ pub(crate) async fn list(
 
     Ok(ExitStatus::Success)
 }
+
+/// An entry in a JSON list of Python versions.
+#[derive(Debug, Serialize)]
+struct Entry {
+    key: String,
+    version: String,
+    implementation: String,
+    os: String,
+    arch: String,
+    libc: String,
+    kind: &'static str,
+    path: Option<String>,
+    symlink: Option<String>,
+    url: Option<String>,
+    sha256: Option<String>,
+}
@@ -6,6 +6,7 @@ use owo_colors::OwoColorize;
 use tracing::debug;
 
 use uv_cache::Cache;
+use uv_cli::ResolvedPin;
 use uv_configuration::PreviewMode;
 use uv_fs::{Simplified, CWD};
 use uv_python::{
@@ -22,7 +23,7 @@ use crate::printer::Printer;
 /// Pin to a specific Python version.
 pub(crate) async fn pin(
     request: Option<String>,
-    resolved: bool,
+    resolved: Option<ResolvedPin>,
     python_preference: PythonPreference,
     preview: PreviewMode,
     no_workspace: bool,
@@ -73,7 +74,7 @@ pub(crate) async fn pin(
     ) {
         Ok(python) => Some(python),
         // If no matching Python version is found, don't fail unless `resolved` was requested
-        Err(uv_python::Error::MissingPython(err)) if !resolved => {
+        Err(uv_python::Error::MissingPython(err)) if resolved.is_none() => {
             warn_user_once!("{err}");
             None
         }
@@ -103,7 +104,7 @@ pub(crate) async fn pin(
                     },
                     virtual_project,
                 ) {
-                    if resolved {
+                    if resolved.is_some() {
                         return Err(err);
                     };
                     warn_user_once!("{}", err);
@@ -112,16 +113,16 @@ pub(crate) async fn pin(
         };
     }
 
-    let output = if resolved {
-        // SAFETY: We exit early if Python is not found and resolved is `true`
-        python
+    let output = match resolved {
+        // SAFETY: We exit early if Python is not found and resolved is set
+        Some(ResolvedPin::Path) => python
             .unwrap()
             .interpreter()
             .sys_executable()
             .user_display()
-            .to_string()
-    } else {
-        request.to_canonical_string()
+            .to_string(),
+        Some(ResolvedPin::Key) => python.unwrap().key().to_string(),
+        None => request.to_canonical_string(),
     };
 
     let existing = request_from_version_file(&CWD).await.ok().flatten();
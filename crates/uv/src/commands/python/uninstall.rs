@@ -6,6 +6,7 @@ use futures::stream::FuturesUnordered;
 use futures::StreamExt;
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use tracing::debug;
 
 use uv_configuration::PreviewMode;
 use uv_python::downloads::PythonDownloadRequest;
@@ -96,6 +97,13 @@ pub(crate) async fn uninstall(
 
     let mut tasks = FuturesUnordered::new();
     for installation in &matching_installations {
+        // Revert any post-install hooks before removing the installation.
+        if let Err(err) = installation.remove_hooks() {
+            debug!(
+                "Failed to remove post-install hooks for `{}`: {err}",
+                installation.key()
+            );
+        }
         tasks.push(async {
             (
                 installation.key(),
@@ -5,6 +5,7 @@ use std::time::Duration;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use owo_colors::OwoColorize;
 use rustc_hash::FxHashMap;
+use serde::Serialize;
 use url::Url;
 
 use distribution_types::{
@@ -13,7 +14,7 @@ use distribution_types::{
 use uv_normalize::PackageName;
 use uv_python::PythonInstallationKey;
 
-use crate::printer::Printer;
+use crate::printer::{emit, Printer};
 
 #[derive(Debug)]
 struct ProgressReporter {
@@ -31,6 +32,86 @@ enum ProgressMode {
         multi_progress: MultiProgress,
         state: Arc<Mutex<BarState>>,
     },
+    /// Reports progress as a stream of JSON Lines events on stderr.
+    Json {
+        phase: Phase,
+        state: Arc<Mutex<EventState>>,
+    },
+}
+
+/// An operation for which progress is reported.
+#[derive(Debug, Copy, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Phase {
+    Resolve,
+    Prepare,
+    Install,
+    PythonDownload,
+}
+
+/// A progress event, reported as a single line of JSON when using `--progress-format json-lines`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+enum ProgressEvent<'a> {
+    /// An operation (e.g., resolution) started.
+    PhaseStart { phase: Phase },
+    /// An operation made progress, e.g., a package was resolved or installed.
+    PhaseProgress {
+        phase: Phase,
+        package: &'a str,
+        completed: u64,
+        /// The total number of items, if known (e.g., unlike during a resolution).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total: Option<u64>,
+    },
+    /// An operation completed.
+    PhaseComplete { phase: Phase },
+    /// A source distribution started building.
+    BuildStart { id: usize, source: &'a str },
+    /// A source distribution finished building.
+    BuildComplete { id: usize, source: &'a str },
+    /// A download started. The size is omitted if it isn't known ahead of time.
+    DownloadStart {
+        id: usize,
+        name: &'a str,
+        total_bytes: Option<u64>,
+    },
+    /// A download made progress.
+    DownloadProgress {
+        id: usize,
+        bytes: u64,
+        downloaded_bytes: u64,
+    },
+    /// A download completed.
+    DownloadComplete { id: usize },
+    /// A Git checkout started.
+    CheckoutStart {
+        id: usize,
+        url: &'a str,
+        rev: &'a str,
+    },
+    /// A Git checkout completed.
+    CheckoutComplete {
+        id: usize,
+        url: &'a str,
+        rev: &'a str,
+    },
+}
+
+#[derive(Default, Debug)]
+struct EventState {
+    /// The number of bytes downloaded so far, by download ID.
+    downloaded: FxHashMap<usize, u64>,
+    /// A monotonic counter for event IDs.
+    id: usize,
+}
+
+impl EventState {
+    /// Returns a unique ID for a new download, build, or checkout.
+    fn id(&mut self) -> usize {
+        self.id += 1;
+        self.id
+    }
 }
 
 #[derive(Default, Debug)]
@@ -54,8 +135,19 @@ impl BarState {
 }
 
 impl ProgressReporter {
-    fn new(root: ProgressBar, multi_progress: MultiProgress, printer: Printer) -> ProgressReporter {
-        let mode = if env::var("JPY_SESSION_NAME").is_ok() {
+    fn new(
+        phase: Phase,
+        root: ProgressBar,
+        multi_progress: MultiProgress,
+        printer: Printer,
+    ) -> ProgressReporter {
+        let mode = if printer.json_lines() {
+            emit(&ProgressEvent::PhaseStart { phase });
+            ProgressMode::Json {
+                phase,
+                state: Arc::default(),
+            }
+        } else if env::var("JPY_SESSION_NAME").is_ok() {
             // Disable concurrent progress bars when running inside a Jupyter notebook
             // because the Jupyter terminal does not support clearing previous lines.
             // See: https://github.com/astral-sh/uv/issues/3887.
@@ -74,7 +166,35 @@ impl ProgressReporter {
         }
     }
 
+    /// Report progress on the top-level operation, e.g., a resolved or installed package.
+    fn on_progress(&self, package: &str) {
+        if let ProgressMode::Json { phase, .. } = &self.mode {
+            emit(&ProgressEvent::PhaseProgress {
+                phase: *phase,
+                package,
+                completed: self.root.position(),
+                total: self.root.length(),
+            });
+        }
+    }
+
+    /// Report the completion of the top-level operation.
+    fn on_complete(&self) {
+        if let ProgressMode::Json { phase, .. } = &self.mode {
+            emit(&ProgressEvent::PhaseComplete { phase: *phase });
+        }
+    }
+
     fn on_build_start(&self, source: &BuildableSource) -> usize {
+        if let ProgressMode::Json { state, .. } = &self.mode {
+            let id = state.lock().unwrap().id();
+            emit(&ProgressEvent::BuildStart {
+                id,
+                source: &source.to_string(),
+            });
+            return id;
+        }
+
         let ProgressMode::Multi {
             multi_progress,
             state,
@@ -104,6 +224,20 @@ impl ProgressReporter {
     }
 
     fn on_build_complete(&self, source: &BuildableSource, id: usize) {
+        crate::stats::record_build(
+            source
+                .name()
+                .map_or_else(|| source.to_string(), ToString::to_string),
+        );
+
+        if let ProgressMode::Json { .. } = &self.mode {
+            emit(&ProgressEvent::BuildComplete {
+                id,
+                source: &source.to_string(),
+            });
+            return;
+        }
+
         let ProgressMode::Multi { state, .. } = &self.mode else {
             return;
         };
@@ -122,6 +256,21 @@ impl ProgressReporter {
     }
 
     fn on_download_start(&self, name: String, size: Option<u64>) -> usize {
+        if let ProgressMode::Json { state, .. } = &self.mode {
+            let id = {
+                let mut state = state.lock().unwrap();
+                let id = state.id();
+                state.downloaded.insert(id, 0);
+                id
+            };
+            emit(&ProgressEvent::DownloadStart {
+                id,
+                name: &name,
+                total_bytes: size,
+            });
+            return id;
+        }
+
         let ProgressMode::Multi {
             multi_progress,
             state,
@@ -163,6 +312,21 @@ impl ProgressReporter {
     }
 
     fn on_download_progress(&self, id: usize, bytes: u64) {
+        if let ProgressMode::Json { state, .. } = &self.mode {
+            let downloaded_bytes = {
+                let mut state = state.lock().unwrap();
+                let downloaded = state.downloaded.entry(id).or_default();
+                *downloaded += bytes;
+                *downloaded
+            };
+            emit(&ProgressEvent::DownloadProgress {
+                id,
+                bytes,
+                downloaded_bytes,
+            });
+            return;
+        }
+
         let ProgressMode::Multi { state, .. } = &self.mode else {
             return;
         };
@@ -171,6 +335,12 @@ impl ProgressReporter {
     }
 
     fn on_download_complete(&self, id: usize) {
+        if let ProgressMode::Json { state, .. } = &self.mode {
+            state.lock().unwrap().downloaded.remove(&id);
+            emit(&ProgressEvent::DownloadComplete { id });
+            return;
+        }
+
         let ProgressMode::Multi { state, .. } = &self.mode else {
             return;
         };
@@ -180,6 +350,16 @@ impl ProgressReporter {
     }
 
     fn on_checkout_start(&self, url: &Url, rev: &str) -> usize {
+        if let ProgressMode::Json { state, .. } = &self.mode {
+            let id = state.lock().unwrap().id();
+            emit(&ProgressEvent::CheckoutStart {
+                id,
+                url: url.as_str(),
+                rev,
+            });
+            return id;
+        }
+
         let ProgressMode::Multi {
             multi_progress,
             state,
@@ -211,6 +391,15 @@ impl ProgressReporter {
     }
 
     fn on_checkout_complete(&self, url: &Url, rev: &str, id: usize) {
+        if let ProgressMode::Json { .. } = &self.mode {
+            emit(&ProgressEvent::CheckoutComplete {
+                id,
+                url: url.as_str(),
+                rev,
+            });
+            return;
+        }
+
         let ProgressMode::Multi { state, .. } = &self.mode else {
             return;
         };
@@ -247,7 +436,7 @@ impl From<Printer> for PrepareReporter {
         );
         root.set_message("Preparing packages...");
 
-        let reporter = ProgressReporter::new(root, multi_progress, printer);
+        let reporter = ProgressReporter::new(Phase::Prepare, root, multi_progress, printer);
         Self { reporter }
     }
 }
@@ -261,11 +450,13 @@ impl PrepareReporter {
 }
 
 impl uv_installer::PrepareReporter for PrepareReporter {
-    fn on_progress(&self, _dist: &CachedDist) {
+    fn on_progress(&self, dist: &CachedDist) {
         self.reporter.root.inc(1);
+        self.reporter.on_progress(&dist.to_string());
     }
 
     fn on_complete(&self) {
+        self.reporter.on_complete();
         // Need an extra call to `set_message` here to fully clear avoid leaving ghost output
         // in Jupyter notebooks.
         self.reporter.root.set_message("");
@@ -326,24 +517,24 @@ impl From<Printer> for ResolverReporter {
         );
         root.set_message("Resolving dependencies...");
 
-        let reporter = ProgressReporter::new(root, multi_progress, printer);
+        let reporter = ProgressReporter::new(Phase::Resolve, root, multi_progress, printer);
         Self { reporter }
     }
 }
 
 impl uv_resolver::ResolverReporter for ResolverReporter {
     fn on_progress(&self, name: &PackageName, version_or_url: &VersionOrUrlRef) {
-        match version_or_url {
-            VersionOrUrlRef::Version(version) => {
-                self.reporter.root.set_message(format!("{name}=={version}"));
-            }
-            VersionOrUrlRef::Url(url) => {
-                self.reporter.root.set_message(format!("{name} @ {url}"));
-            }
-        }
+        let message = match version_or_url {
+            VersionOrUrlRef::Version(version) => format!("{name}=={version}"),
+            VersionOrUrlRef::Url(url) => format!("{name} @ {url}"),
+        };
+        self.reporter.root.inc(1);
+        self.reporter.on_progress(&message);
+        self.reporter.root.set_message(message);
     }
 
     fn on_complete(&self) {
+        self.reporter.on_complete();
         self.reporter.root.set_message("");
         self.reporter.root.finish_and_clear();
     }
@@ -409,6 +600,7 @@ impl uv_distribution::Reporter for ResolverReporter {
 
 #[derive(Debug)]
 pub(crate) struct InstallReporter {
+    printer: Printer,
     progress: ProgressBar,
 }
 
@@ -419,7 +611,12 @@ impl From<Printer> for InstallReporter {
             ProgressStyle::with_template("{bar:20} [{pos}/{len}] {wide_msg:.dim}").unwrap(),
         );
         progress.set_message("Installing wheels...");
-        Self { progress }
+        if printer.json_lines() {
+            emit(&ProgressEvent::PhaseStart {
+                phase: Phase::Install,
+            });
+        }
+        Self { printer, progress }
     }
 }
 
@@ -435,9 +632,22 @@ impl uv_installer::InstallReporter for InstallReporter {
     fn on_install_progress(&self, wheel: &CachedDist) {
         self.progress.set_message(format!("{wheel}"));
         self.progress.inc(1);
+        if self.printer.json_lines() {
+            emit(&ProgressEvent::PhaseProgress {
+                phase: Phase::Install,
+                package: &wheel.to_string(),
+                completed: self.progress.position(),
+                total: self.progress.length(),
+            });
+        }
     }
 
     fn on_install_complete(&self) {
+        if self.printer.json_lines() {
+            emit(&ProgressEvent::PhaseComplete {
+                phase: Phase::Install,
+            });
+        }
         self.progress.set_message("");
         self.progress.finish_and_clear();
     }
@@ -461,14 +671,16 @@ impl PythonDownloadReporter {
             Some(length),
             printer.target(),
         ));
-        let reporter = ProgressReporter::new(root, multi_progress, printer);
+        let reporter = ProgressReporter::new(Phase::PythonDownload, root, multi_progress, printer);
         Self { reporter }
     }
 }
 
 impl uv_python::downloads::Reporter for PythonDownloadReporter {
-    fn on_progress(&self, _name: &PythonInstallationKey, id: usize) {
+    fn on_progress(&self, name: &PythonInstallationKey, id: usize) {
         self.reporter.on_download_complete(id);
+        self.reporter.root.inc(1);
+        self.reporter.on_progress(&name.to_string());
     }
 
     fn on_download_start(&self, name: &PythonInstallationKey, size: Option<u64>) -> usize {
@@ -480,6 +692,7 @@ impl uv_python::downloads::Reporter for PythonDownloadReporter {
     }
 
     fn on_download_complete(&self) {
+        self.reporter.on_complete();
         self.reporter.root.set_message("");
         self.reporter.root.finish_and_clear();
     }
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_warnings::warn_user_once;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+use crate::stats::{self, Invocation};
+
+/// The number of frequently built packages to display.
+const TOP_BUILDS: usize = 10;
+
+/// A summary of the recorded usage statistics.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Summary {
+    /// The number of recorded invocations.
+    invocations: usize,
+    /// The timestamp of the earliest recorded invocation, in seconds since the Unix epoch.
+    since: Option<u64>,
+    /// The total number of resolutions performed.
+    resolutions: u64,
+    /// The total number of distributions installed from the cache.
+    cached: u64,
+    /// The total number of distributions that had to be downloaded or built.
+    downloaded: u64,
+    /// The fraction of installed distributions that were served from the cache.
+    cache_hit_rate: Option<f64>,
+    /// The invocation count and average duration for each command.
+    commands: BTreeMap<String, CommandSummary>,
+    /// The packages most frequently built from source, with the number of builds.
+    built: Vec<(String, usize)>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct CommandSummary {
+    invocations: usize,
+    failures: usize,
+    average_duration_ms: u64,
+}
+
+impl Summary {
+    #[allow(clippy::cast_precision_loss)]
+    fn from_invocations(invocations: &[Invocation]) -> Self {
+        let mut summary = Self {
+            invocations: invocations.len(),
+            since: invocations
+                .iter()
+                .map(|invocation| invocation.timestamp)
+                .min(),
+            ..Self::default()
+        };
+
+        let mut durations: BTreeMap<&str, u64> = BTreeMap::new();
+        let mut built: BTreeMap<&str, usize> = BTreeMap::new();
+        for invocation in invocations {
+            summary.resolutions += invocation.resolutions;
+            summary.cached += invocation.cached;
+            summary.downloaded += invocation.downloaded;
+
+            let command = summary
+                .commands
+                .entry(invocation.command.clone())
+                .or_default();
+            command.invocations += 1;
+            if !invocation.success {
+                command.failures += 1;
+            }
+            *durations.entry(invocation.command.as_str()).or_default() += invocation.duration_ms;
+
+            for source in &invocation.built {
+                *built.entry(source.as_str()).or_default() += 1;
+            }
+        }
+
+        for (name, command) in &mut summary.commands {
+            command.average_duration_ms = durations[name.as_str()] / command.invocations as u64;
+        }
+
+        let installed = summary.cached + summary.downloaded;
+        if installed > 0 {
+            summary.cache_hit_rate = Some(summary.cached as f64 / installed as f64);
+        }
+
+        summary.built = built
+            .into_iter()
+            .sorted_by(|(a_name, a_count), (b_name, b_count)| {
+                b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+            })
+            .take(TOP_BUILDS)
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+
+        summary
+    }
+}
+
+/// Show the locally recorded usage statistics.
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn stats(
+    json: bool,
+    clear: bool,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv stats` is experimental and may change without warning");
+    }
+
+    if clear {
+        let path = stats::log_path()?;
+        if stats::clear()? {
+            writeln!(
+                printer.stderr(),
+                "Removed usage statistics at: {}",
+                path.user_display().cyan()
+            )?;
+        } else {
+            writeln!(
+                printer.stderr(),
+                "No usage statistics found at: {}",
+                path.user_display().cyan()
+            )?;
+        }
+        return Ok(ExitStatus::Success);
+    }
+
+    let summary = Summary::from_invocations(&stats::read()?);
+
+    if json {
+        writeln!(
+            printer.stdout(),
+            "{}",
+            serde_json::to_string_pretty(&summary)?
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let Some(since) = summary.since else {
+        writeln!(printer.stderr(), "No usage statistics recorded")?;
+        return Ok(ExitStatus::Success);
+    };
+
+    let since = chrono::DateTime::from_timestamp(i64::try_from(since)?, 0)
+        .map(|since| since.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    writeln!(
+        printer.stdout(),
+        "{} invocations since {}",
+        summary.invocations.bold(),
+        since.cyan()
+    )?;
+    writeln!(printer.stdout(), "Resolutions: {}", summary.resolutions)?;
+    if let Some(cache_hit_rate) = summary.cache_hit_rate {
+        writeln!(
+            printer.stdout(),
+            "Cache hit rate: {} ({} of {} distributions)",
+            format!("{:.1}%", cache_hit_rate * 100.0).green(),
+            summary.cached,
+            summary.cached + summary.downloaded
+        )?;
+    }
+
+    writeln!(printer.stdout())?;
+    writeln!(printer.stdout(), "{}", "Average duration:".bold())?;
+    for (name, command) in &summary.commands {
+        writeln!(
+            printer.stdout(),
+            "  {}: {} ({} invocations)",
+            name,
+            format!("{:.2}s", command.average_duration_ms as f64 / 1000.0).cyan(),
+            command.invocations
+        )?;
+    }
+
+    if !summary.built.is_empty() {
+        writeln!(printer.stdout())?;
+        writeln!(
+            printer.stdout(),
+            "{}",
+            "Most frequently built from source:".bold()
+        )?;
+        for (name, count) in &summary.built {
+            writeln!(printer.stdout(), "  {name}: {count}")?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
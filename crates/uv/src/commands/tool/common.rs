@@ -1,26 +1,103 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsString;
 use std::fmt::Write;
-use std::{collections::BTreeSet, ffi::OsString};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
-use anyhow::{bail, Context};
+use anyhow::{anyhow, bail, Context};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use serde::Serialize;
 use tracing::{debug, warn};
 
-use distribution_types::{InstalledDist, Name};
+use distribution_filename::WheelFilename;
+use distribution_types::{
+    InstalledDist, Name, UnresolvedRequirement, UnresolvedRequirementSpecification,
+};
+use pep440_rs::{Version, VersionSpecifier, VersionSpecifiers};
 use pep508_rs::PackageName;
-use pypi_types::Requirement;
+use pypi_types::{Requirement, RequirementSource};
+use uv_cache::Cache;
+use uv_cli::ToolFormat;
+use uv_client::Connectivity;
+use uv_configuration::{Concurrency, PreviewMode};
 #[cfg(unix)]
 use uv_fs::replace_symlink;
 use uv_fs::Simplified;
 use uv_installer::SitePackages;
-use uv_python::PythonEnvironment;
+use uv_python::{Interpreter, PythonEnvironment};
+use uv_requirements::RequirementsSpecification;
+use uv_resolver::{Lock, Preference};
 use uv_settings::ToolOptions;
 use uv_shell::Shell;
-use uv_tool::{entrypoint_paths, find_executable_directory, InstalledTools, Tool, ToolEntrypoint};
+use uv_tool::{
+    entrypoint_paths, find_executable_directory, EntrypointSelection, InstalledTools, Tool,
+    ToolEntrypoint, ToolName,
+};
 use uv_warnings::warn_user;
 
-use crate::commands::ExitStatus;
+use crate::commands::pip::loggers::{DefaultInstallLogger, DefaultResolveLogger};
+use crate::commands::project::{resolve_environment_with_preferences, sync_environment};
+use crate::commands::{ExitStatus, SharedState};
 use crate::printer::Printer;
+use crate::settings::ResolverInstallerSettings;
+
+/// When offline, pin the tool requirement in the given specification to the newest version that's
+/// available as a compatible wheel in the cache, if any.
+///
+/// Used as a fallback when the tool can't be resolved offline, e.g., as the cached index responses
+/// reference newer versions that were never downloaded.
+pub(crate) fn offline_fallback(
+    from: &Requirement,
+    spec: &RequirementsSpecification,
+    connectivity: Connectivity,
+    interpreter: &Interpreter,
+    cache: &Cache,
+) -> Option<RequirementsSpecification> {
+    if connectivity.is_online() {
+        return None;
+    }
+    let RequirementSource::Registry { specifier, index } = &from.source else {
+        return None;
+    };
+
+    let tags = interpreter.tags().ok()?;
+    let version = cache
+        .registry_wheels(&from.name)
+        .iter()
+        .filter_map(|wheel| WheelFilename::from_str(wheel).ok())
+        .filter(|filename| filename.name == from.name && filename.is_compatible(tags))
+        .map(|filename| filename.version)
+        .filter(|version| specifier.contains(version))
+        .max()?;
+
+    // If the requirement is already pinned to the cached version, there's nothing to fall back to.
+    let pinned = VersionSpecifiers::from(VersionSpecifier::equals_version(version.clone()));
+    if *specifier == pinned {
+        return None;
+    }
+
+    warn_user!(
+        "Offline, using cached `{}`",
+        format!("{}=={version}", from.name).cyan()
+    );
+
+    let pinned = Requirement {
+        source: RequirementSource::Registry {
+            specifier: pinned,
+            index: index.clone(),
+        },
+        ..from.clone()
+    };
+    let mut spec = spec.clone();
+    for requirement in &mut spec.requirements {
+        if matches!(&requirement.requirement, UnresolvedRequirement::Named(named) if named == from)
+        {
+            *requirement = UnresolvedRequirementSpecification::from(pinned.clone());
+        }
+    }
+    Some(spec)
+}
 
 /// Return all packages which contain an executable with the given name.
 pub(super) fn matching_packages(name: &str, site_packages: &SitePackages) -> Vec<InstalledDist> {
@@ -61,6 +138,130 @@ pub(crate) fn remove_entrypoints(tool: &Tool) {
     }
 }
 
+/// Read the lockfile for the given tool, if it exists.
+///
+/// The lockfile records the resolution that was installed into the tool environment, and is
+/// stored alongside the receipt.
+pub(crate) fn read_tool_lock(
+    installed_tools: &InstalledTools,
+    name: &ToolName,
+) -> anyhow::Result<Option<Lock>> {
+    let path = installed_tools.tool_dir(name).join("uv.lock");
+    match fs_err::read_to_string(&path) {
+        Ok(contents) => Ok(Some(Lock::from_toml(&contents).with_context(|| {
+            format!("Failed to parse `uv.lock` at {}", path.user_display())
+        })?)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Write the lockfile for a tool into the given environment.
+///
+/// This is intended to be called on a staged environment, such that the lockfile is moved into
+/// place alongside the environment it describes.
+pub(crate) fn write_tool_lock(environment: &PythonEnvironment, lock: &Lock) -> anyhow::Result<()> {
+    let path = environment.root().join("uv.lock");
+    debug!("Writing tool lockfile to: {}", path.user_display());
+    // Write atomically, since the file may be shared with a staged copy of the environment.
+    uv_fs::write_atomic_sync(&path, lock.to_toml()?)?;
+    Ok(())
+}
+
+/// Ensure that a tool environment that already satisfies its requirements has a lockfile.
+///
+/// Environments installed before tools were locked have no lockfile. Resolve the requirements,
+/// preferring the installed versions, and sync the environment to match the new lockfile.
+pub(crate) async fn ensure_tool_lock(
+    environment: &PythonEnvironment,
+    spec: RequirementsSpecification,
+    settings: &ResolverInstallerSettings,
+    state: &SharedState,
+    preview: PreviewMode,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    printer: Printer,
+) -> anyhow::Result<()> {
+    if environment.root().join("uv.lock").is_file() {
+        return Ok(());
+    }
+
+    let preferences = SitePackages::from_environment(environment)?
+        .iter()
+        .map(Preference::from_installed)
+        .collect();
+    let graph = resolve_environment_with_preferences(
+        environment.interpreter(),
+        spec,
+        preferences,
+        settings.as_ref().into(),
+        state,
+        Box::new(DefaultResolveLogger),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?;
+    let lock = Lock::from_resolution_graph(&graph)?;
+
+    sync_environment(
+        environment.clone(),
+        &graph.into(),
+        settings.as_ref().into(),
+        state,
+        Box::new(DefaultInstallLogger::default()),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?;
+    write_tool_lock(environment, &lock)
+}
+
+/// Lock a local requirement by its absolute path, as in the receipt.
+///
+/// Tool lockfiles are read back relative to the tool directory, rather than the directory in which
+/// the tool was installed, so relative paths can't be preserved.
+pub(crate) fn absolute_lock_path(requirement: Requirement) -> Requirement {
+    let source = match requirement.source {
+        RequirementSource::Path {
+            install_path,
+            lock_path: _,
+            ext,
+            url,
+        } => RequirementSource::Path {
+            lock_path: install_path.clone(),
+            install_path,
+            ext,
+            url,
+        },
+        RequirementSource::Directory {
+            install_path,
+            lock_path: _,
+            editable,
+            url,
+        } => RequirementSource::Directory {
+            lock_path: install_path.clone(),
+            install_path,
+            editable,
+            url,
+        },
+        source => source,
+    };
+    Requirement {
+        source,
+        ..requirement
+    }
+}
+
 /// Represents the action to be performed on executables: update or install.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) enum InstallAction {
@@ -68,24 +269,172 @@ pub(crate) enum InstallAction {
     Install,
 }
 
+/// Validate the [`EntrypointSelection`] against a tool environment, before it's committed (and
+/// replaces any existing environment).
+///
+/// Returns `false` (after writing a message to that effect) if the tool doesn't provide any
+/// executables, in which case the environment shouldn't be committed.
+pub(crate) fn check_executables(
+    environment: &PythonEnvironment,
+    name: &ToolName,
+    selection: &EntrypointSelection,
+    printer: Printer,
+) -> anyhow::Result<bool> {
+    let site_packages = SitePackages::from_environment(environment)?;
+    let entry_points = entrypoints(&site_packages, name, selection)?;
+    if entry_points.is_empty() {
+        no_executables(name, &site_packages, printer)?;
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Return the executables provided by the tool (and, if selected, its dependencies), ensuring
+/// that every executable named in the [`EntrypointSelection`] is available and that the selection
+/// doesn't exclude all of them.
+fn entrypoints(
+    site_packages: &SitePackages,
+    name: &ToolName,
+    selection: &EntrypointSelection,
+) -> anyhow::Result<Vec<(String, PathBuf)>> {
+    let installed = site_packages.get_packages(name.package());
+    let Some(installed_dist) = installed.first().copied() else {
+        bail!("Expected at least one requirement")
+    };
+
+    let mut entry_points = entrypoint_paths(
+        site_packages,
+        installed_dist.name(),
+        installed_dist.version(),
+    )?;
+
+    // If requested, include the executables provided by the tool's dependencies.
+    if selection.include_deps {
+        for dist in site_packages.iter() {
+            if dist.name() == name.package() {
+                continue;
+            }
+            entry_points.extend(entrypoint_paths(
+                site_packages,
+                dist.name(),
+                dist.version(),
+            )?);
+        }
+    }
+
+    // Ensure that every explicitly requested executable is available.
+    let missing = selection
+        .include
+        .iter()
+        .chain(&selection.exclude)
+        .filter(|requested| {
+            !entry_points.iter().any(|(entry_point, _)| {
+                entry_point
+                    .strip_suffix(std::env::consts::EXE_SUFFIX)
+                    .unwrap_or(entry_point)
+                    == requested.as_str()
+            })
+        })
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        let (s, is) = if missing.len() == 1 {
+            ("", "is")
+        } else {
+            ("s", "are")
+        };
+        bail!(
+            "Executable{s} {} {is} not provided by `{}`{}",
+            missing.iter().map(|name| format!("`{name}`")).join(", "),
+            name.package(),
+            if selection.include_deps {
+                " or its dependencies"
+            } else {
+                " (use `--include-deps` to include executables from its dependencies)"
+            }
+        );
+    }
+
+    if !entry_points.is_empty()
+        && !entry_points
+            .iter()
+            .any(|(entry_point, _)| selection.matches(entry_point))
+    {
+        bail!(
+            "All executables provided by `{}` were excluded",
+            name.package()
+        );
+    }
+
+    Ok(entry_points)
+}
+
+/// Report that the tool doesn't provide any executables.
+fn no_executables(
+    name: &ToolName,
+    site_packages: &SitePackages,
+    printer: Printer,
+) -> anyhow::Result<()> {
+    writeln!(
+        printer.stdout(),
+        "No executables are provided by `{from}`",
+        from = name.cyan()
+    )?;
+    hint_executable_from_dependency(name.package(), site_packages, printer)
+}
+
 /// Installs tool executables for a given package and handles any conflicts.
+///
+/// If the tool has a suffix, it's appended to the name of each executable (e.g., `black@23`).
+///
+/// Only the executables that match the [`EntrypointSelection`] are installed. If the selection
+/// includes dependencies, the executables provided by any other package in the environment are
+/// considered too.
+///
+/// If any environment variables are provided, each executable is installed as a launcher that sets
+/// the variables before running the executable in the tool environment.
+///
+/// If `created` is set, the environment was created by the current command (i.e., the tool wasn't
+/// previously installed), and is removed if the executables can't be installed.
 pub(crate) fn install_executables(
     environment: &PythonEnvironment,
-    name: &PackageName,
+    name: &ToolName,
     installed_tools: &InstalledTools,
     options: ToolOptions,
     force: bool,
+    created: bool,
     python: Option<String>,
     requirements: Vec<Requirement>,
+    constraints: Vec<Requirement>,
+    overrides: Vec<Requirement>,
+    selection: EntrypointSelection,
+    env: BTreeMap<String, String>,
     action: InstallAction,
     printer: Printer,
 ) -> anyhow::Result<ExitStatus> {
     let site_packages = SitePackages::from_environment(environment)?;
-    let installed = site_packages.get_packages(name);
-    let Some(installed_dist) = installed.first().copied() else {
-        bail!("Expected at least one requirement")
+
+    // Validate the selection before modifying anything.
+    let entry_points = match entrypoints(&site_packages, name, &selection) {
+        Ok(entry_points) => entry_points,
+        Err(err) => {
+            if created {
+                installed_tools.remove_environment(name)?;
+            }
+            return Err(err);
+        }
     };
 
+    if entry_points.is_empty() {
+        no_executables(name, &site_packages, printer)?;
+
+        // Clean up the environment we just created.
+        if created {
+            installed_tools.remove_environment(name)?;
+        }
+
+        return Ok(ExitStatus::Failure);
+    }
+
     // Find a suitable path to install into
     let executable_directory = find_executable_directory()?;
     fs_err::create_dir_all(&executable_directory)
@@ -96,42 +445,26 @@ pub(crate) fn install_executables(
         executable_directory.user_display()
     );
 
-    let entry_points = entrypoint_paths(
-        &site_packages,
-        installed_dist.name(),
-        installed_dist.version(),
-    )?;
-
     // Determine the entry points targets
     // Use a sorted collection for deterministic output
+    let suffix = name.suffix().unwrap_or_default();
     let target_entry_points = entry_points
         .into_iter()
+        .filter(|(name, _)| selection.matches(name))
         .map(|(name, source_path)| {
-            let target_path = executable_directory.join(
-                source_path
-                    .file_name()
-                    .map(std::borrow::ToOwned::to_owned)
-                    .unwrap_or_else(|| OsString::from(name.clone())),
-            );
-            (name, source_path, target_path)
+            let file_name = source_path
+                .file_name()
+                .map(std::borrow::ToOwned::to_owned)
+                .unwrap_or_else(|| OsString::from(name.clone()));
+            let mut target_path =
+                executable_directory.join(with_suffix(&file_name.to_string_lossy(), suffix));
+            if !env.is_empty() {
+                target_path = launcher_path(&target_path);
+            }
+            (with_suffix(&name, suffix), source_path, target_path)
         })
         .collect::<BTreeSet<_>>();
 
-    if target_entry_points.is_empty() {
-        writeln!(
-            printer.stdout(),
-            "No executables are provided by `{from}`",
-            from = name.cyan()
-        )?;
-
-        hint_executable_from_dependency(name, &site_packages, printer)?;
-
-        // Clean up the environment we just created.
-        installed_tools.remove_environment(name)?;
-
-        return Ok(ExitStatus::Failure);
-    }
-
     // Check if they exist, before installing
     let mut existing_entry_points = target_entry_points
         .iter()
@@ -147,7 +480,9 @@ pub(crate) fn install_executables(
         }
     } else if existing_entry_points.peek().is_some() {
         // Clean up the environment we just created
-        installed_tools.remove_environment(name)?;
+        if created {
+            installed_tools.remove_environment(name)?;
+        }
 
         let existing_entry_points = existing_entry_points
             // SAFETY: We know the target has a filename because we just constructed it above
@@ -169,6 +504,11 @@ pub(crate) fn install_executables(
 
     for (name, source_path, target_path) in &target_entry_points {
         debug!("Installing executable: `{name}`");
+        if !env.is_empty() {
+            write_launcher(target_path, source_path, &env)
+                .context("Failed to install executable")?;
+            continue;
+        }
         #[cfg(unix)]
         replace_symlink(source_path, target_path).context("Failed to install executable")?;
         #[cfg(windows)]
@@ -197,10 +537,14 @@ pub(crate) fn install_executables(
     debug!("Adding receipt for tool `{}`", name);
     let tool = Tool::new(
         requirements.into_iter().collect(),
+        constraints,
+        overrides,
         python,
         target_entry_points
             .into_iter()
             .map(|(name, _, target_path)| ToolEntrypoint::new(name, target_path)),
+        selection,
+        env,
         options,
     );
     installed_tools.add_tool_receipt(name, tool)?;
@@ -239,6 +583,336 @@ pub(crate) fn install_executables(
     Ok(ExitStatus::Success)
 }
 
+/// Return the path of the launcher for the executable at the given path.
+///
+/// On Windows, launchers are batch files, so the executable's extension is replaced (e.g.,
+/// `black.exe` becomes `black.cmd`).
+fn launcher_path(path: &Path) -> PathBuf {
+    if cfg!(windows) {
+        path.with_extension("cmd")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Write a launcher that sets the given environment variables, then executes the executable at
+/// `source`, forwarding any arguments.
+#[cfg(unix)]
+fn write_launcher(
+    launcher: &Path,
+    source: &Path,
+    env: &BTreeMap<String, String>,
+) -> Result<(), std::io::Error> {
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Quote a value for use in a POSIX shell.
+    fn quote(value: &str) -> String {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+
+    let mut contents = String::from("#!/bin/sh\n");
+    for (key, value) in env {
+        contents.push_str(&format!("export {key}={}\n", quote(value)));
+    }
+    contents.push_str(&format!(
+        "exec {} \"$@\"\n",
+        quote(&source.to_string_lossy())
+    ));
+
+    // Replace any existing executable, rather than writing through a symlink into the tool
+    // environment.
+    match fs_err::remove_file(launcher) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    fs_err::write(launcher, contents)?;
+    fs_err::set_permissions(launcher, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+/// Write a launcher that sets the given environment variables, then executes the executable at
+/// `source`, forwarding any arguments.
+#[cfg(windows)]
+fn write_launcher(
+    launcher: &Path,
+    source: &Path,
+    env: &BTreeMap<String, String>,
+) -> Result<(), std::io::Error> {
+    /// Escape a value for use in an unquoted `set` command, such that `cmd` neither expands
+    /// variables (`%`) nor interprets quotes or operators (e.g., `&`) within it.
+    fn escape(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            match c {
+                '%' => escaped.push_str("%%"),
+                '^' | '&' | '|' | '<' | '>' | '"' => {
+                    escaped.push('^');
+                    escaped.push(c);
+                }
+                _ => escaped.push(c),
+            }
+        }
+        escaped
+    }
+
+    let mut contents = String::from("@echo off\r\nsetlocal\r\n");
+    for (key, value) in env {
+        contents.push_str(&format!("set {}={}\r\n", escape(key), escape(value)));
+    }
+    contents.push_str(&format!("\"{}\" %*\r\n", source.display()));
+    fs_err::write(launcher, contents)?;
+
+    // Remove any executable of the same name (e.g., from an installation without environment
+    // variables), which would otherwise take precedence over the launcher.
+    match fs_err::remove_file(launcher.with_extension("exe")) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    Ok(())
+}
+
+/// Apply a tool suffix to the name of an executable.
+///
+/// On Windows, the suffix is inserted before the extension (e.g., `black@23.exe`), such that the
+/// executable remains runnable.
+fn with_suffix(executable: &str, suffix: &str) -> String {
+    if suffix.is_empty() {
+        return executable.to_string();
+    }
+    if cfg!(windows) {
+        if let Some((stem, extension)) = executable.rsplit_once('.') {
+            return format!("{stem}{suffix}.{extension}");
+        }
+    }
+    format!("{executable}{suffix}")
+}
+
+/// A summary of an installed tool, for use in machine-readable output.
+#[derive(Debug, Serialize)]
+pub(crate) struct ToolSummary {
+    name: String,
+    version: String,
+    requirements: Vec<String>,
+    python: ToolPython,
+    path: String,
+    executables: Vec<ToolExecutable>,
+}
+
+/// The Python interpreter of an installed tool's environment.
+#[derive(Debug, Serialize)]
+struct ToolPython {
+    /// The Python request with which the tool was installed (e.g., `3.12`), if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request: Option<String>,
+    version: String,
+    executable: String,
+}
+
+/// An executable provided by an installed tool.
+#[derive(Debug, Serialize)]
+pub(crate) struct ToolExecutable {
+    pub(crate) name: String,
+    pub(crate) path: String,
+}
+
+impl ToolSummary {
+    /// Summarize the installed tool with the given name and receipt.
+    pub(crate) fn from_tool(
+        installed_tools: &InstalledTools,
+        name: &ToolName,
+        tool: &Tool,
+        cache: &Cache,
+    ) -> Result<Self, uv_tool::Error> {
+        let version = installed_tools.version(name, cache)?;
+        let path = installed_tools.tool_dir(name);
+        let environment = PythonEnvironment::from_root(&path, cache)?;
+        Ok(Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            requirements: tool
+                .requirements()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            python: ToolPython {
+                request: tool.python().clone(),
+                version: environment.interpreter().python_version().to_string(),
+                executable: environment
+                    .python_executable()
+                    .simplified_display()
+                    .to_string(),
+            },
+            path: path.simplified_display().to_string(),
+            executables: tool
+                .entrypoints()
+                .iter()
+                .map(|entrypoint| ToolExecutable {
+                    name: entrypoint.name.clone(),
+                    path: entrypoint.install_path.simplified_display().to_string(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Summarize the installed tool with the given name, reading its receipt.
+    pub(crate) fn read(
+        installed_tools: &InstalledTools,
+        name: &ToolName,
+        cache: &Cache,
+    ) -> anyhow::Result<Self> {
+        let tool = installed_tools
+            .get_tool_receipt(name)?
+            .ok_or_else(|| anyhow!("Expected a receipt for tool `{name}`"))?;
+        Ok(Self::from_tool(installed_tools, name, &tool, cache)?)
+    }
+}
+
+/// Write the summaries of the given tools in the requested machine-readable format.
+///
+/// The [`ToolFormat::Text`] format is rendered by each command directly, so writes nothing here.
+pub(crate) fn write_summaries(
+    summaries: &[ToolSummary],
+    format: ToolFormat,
+    printer: Printer,
+) -> anyhow::Result<()> {
+    /// The TOML representation of a list of tools, as an array of `[[tool]]` tables.
+    #[derive(Serialize)]
+    struct Tools<'a> {
+        tool: &'a [ToolSummary],
+    }
+
+    match format {
+        ToolFormat::Text => {}
+        ToolFormat::Json => {
+            let output = serde_json::to_string(summaries)?;
+            writeln!(printer.stdout(), "{output}")?;
+        }
+        ToolFormat::Toml => {
+            let output = toml::to_string(&Tools { tool: summaries })?;
+            write!(printer.stdout(), "{output}")?;
+        }
+        ToolFormat::Shell => {
+            let names = summaries
+                .iter()
+                .map(|summary| summary.name.as_str())
+                .collect::<Vec<_>>();
+            writeln!(
+                printer.stdout(),
+                "uv_tools={}",
+                shell_quote(&names.join(" "))
+            )?;
+            for summary in summaries {
+                let prefix = format!("uv_tool_{}", shell_identifier(&summary.name));
+                writeln!(
+                    printer.stdout(),
+                    "{prefix}_version={}",
+                    shell_quote(&summary.version)
+                )?;
+                writeln!(
+                    printer.stdout(),
+                    "{prefix}_path={}",
+                    shell_quote(&summary.path)
+                )?;
+                for executable in &summary.executables {
+                    writeln!(
+                        printer.stdout(),
+                        "{prefix}_bin_{}={}",
+                        shell_identifier(&executable.name),
+                        shell_quote(&executable.path)
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The version that an installed tool would be upgraded to, for use in machine-readable output.
+#[derive(Debug, Serialize)]
+pub(crate) struct ToolUpgradeReport {
+    name: String,
+    version: String,
+    latest: String,
+}
+
+impl ToolUpgradeReport {
+    /// Create a report for the tool with the given installed and latest versions.
+    pub(crate) fn new(name: &ToolName, version: &Version, latest: &Version) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            latest: latest.to_string(),
+        }
+    }
+}
+
+/// Write the upgrade reports for the given tools in the requested machine-readable format.
+///
+/// As with [`write_summaries`], the [`ToolFormat::Text`] format writes nothing here.
+pub(crate) fn write_upgrade_reports(
+    reports: &[ToolUpgradeReport],
+    format: ToolFormat,
+    printer: Printer,
+) -> anyhow::Result<()> {
+    /// The TOML representation of a list of reports, as an array of `[[tool]]` tables.
+    #[derive(Serialize)]
+    struct Tools<'a> {
+        tool: &'a [ToolUpgradeReport],
+    }
+
+    match format {
+        ToolFormat::Text => {}
+        ToolFormat::Json => {
+            let output = serde_json::to_string(reports)?;
+            writeln!(printer.stdout(), "{output}")?;
+        }
+        ToolFormat::Toml => {
+            let output = toml::to_string(&Tools { tool: reports })?;
+            write!(printer.stdout(), "{output}")?;
+        }
+        ToolFormat::Shell => {
+            let names = reports
+                .iter()
+                .map(|report| report.name.as_str())
+                .collect::<Vec<_>>();
+            writeln!(
+                printer.stdout(),
+                "uv_tools={}",
+                shell_quote(&names.join(" "))
+            )?;
+            for report in reports {
+                let prefix = format!("uv_tool_{}", shell_identifier(&report.name));
+                writeln!(
+                    printer.stdout(),
+                    "{prefix}_version={}",
+                    shell_quote(&report.version)
+                )?;
+                writeln!(
+                    printer.stdout(),
+                    "{prefix}_latest={}",
+                    shell_quote(&report.latest)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Convert a tool or executable name into a valid shell identifier, replacing any characters that
+/// aren't alphanumeric (e.g., `-` and `.`) with underscores.
+fn shell_identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Quote a value for use in a POSIX shell, wrapping it in single quotes.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
 /// Displays a hint if an executable matching the package name can be found in a dependency of the package.
 fn hint_executable_from_dependency(
     name: &PackageName,
@@ -0,0 +1,351 @@
+//! Tools that are launched in the background via `uv tool run --detach`.
+//!
+//! Each detached process is recorded in the `.processes` directory within the tools directory,
+//! alongside a log file that captures its output, such that it can later be listed with
+//! `uv tool ps` and stopped with `uv tool kill`.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+use anyhow::Context;
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use uv_fs::Simplified;
+use uv_tool::InstalledTools;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// A tool that was launched in the background.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct DetachedProcess {
+    /// The ID of the process.
+    pub(crate) pid: u32,
+    /// The command that was launched, including its arguments.
+    pub(crate) command: String,
+    /// The file to which the output of the process is written.
+    pub(crate) log: PathBuf,
+    /// The time at which the process was launched, in seconds since the Unix epoch.
+    pub(crate) started: u64,
+    /// The start time of the process, as reported by the operating system, used to detect if the
+    /// process exited and its ID was reused by another process.
+    ///
+    /// Only available on Linux and Windows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) start_time: Option<u64>,
+}
+
+impl DetachedProcess {
+    /// Returns `true` if the process is still running.
+    pub(crate) fn is_running(&self) -> bool {
+        is_running(self.pid) && self.is_same_process()
+    }
+
+    /// Returns `true` if the process with the recorded ID is the one that was launched, rather than
+    /// an unrelated process that reused its ID.
+    fn is_same_process(&self) -> bool {
+        self.start_time.map_or(true, |start_time| {
+            process_start_time(self.pid) == Some(start_time)
+        })
+    }
+
+    /// Stop the process, along with any of its child processes.
+    ///
+    /// Fails if the process has exited, or if its ID was reused by another process.
+    pub(crate) fn terminate(&self) -> std::io::Result<()> {
+        if !self.is_same_process() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "the process exited, and its ID was reused by another process",
+            ));
+        }
+        terminate(self.pid, self.start_time)
+    }
+
+    /// Remove the record of the process.
+    ///
+    /// The log file is retained, such that the output remains available after the process exits.
+    pub(crate) fn remove(&self) -> anyhow::Result<()> {
+        match fs_err::remove_file(processes_dir()?.join(format!("{}.json", self.pid))) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Return the directory in which detached processes are recorded.
+///
+/// Processes are recorded alongside the installed tools, in a hidden directory that isn't
+/// mistaken for a tool.
+fn processes_dir() -> anyhow::Result<PathBuf> {
+    Ok(InstalledTools::from_settings()?.root().join(".processes"))
+}
+
+/// Spawn the given command in the background, with its output redirected to a log file, and record
+/// it for use by `uv tool ps` and `uv tool kill`.
+pub(crate) fn spawn_detached(
+    mut process: Command,
+    name: &str,
+    display: String,
+    printer: Printer,
+) -> anyhow::Result<ExitStatus> {
+    let dir = processes_dir()?;
+    fs_err::create_dir_all(&dir)?;
+
+    let started = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    // Name the log after the command, replacing any characters that aren't safe in a file name.
+    let stem = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let log = dir.join(format!("{stem}-{started}.log"));
+    let stdout = std::fs::File::create(&log)
+        .with_context(|| format!("Failed to create log file: {}", log.user_display()))?;
+    let stderr = stdout.try_clone()?;
+
+    process.stdin(Stdio::null()).stdout(stdout).stderr(stderr);
+
+    // Start the process in a new process group, such that it isn't interrupted by signals sent to
+    // the terminal (e.g., Ctrl-C), and such that any of its child processes are stopped with it.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        process.process_group(0);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        use windows_sys::Win32::System::Threading::{CREATE_NEW_PROCESS_GROUP, DETACHED_PROCESS};
+        process.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+
+    let child = process
+        .spawn()
+        .with_context(|| format!("Failed to spawn: `{name}`"))?;
+
+    let record = DetachedProcess {
+        pid: child.id(),
+        command: display,
+        log,
+        started: u64::try_from(started / 1000).unwrap_or_default(),
+        start_time: process_start_time(child.id()),
+    };
+    let path = dir.join(format!("{}.json", record.pid));
+    debug!("Recording detached process at: {}", path.user_display());
+    fs_err::write(&path, serde_json::to_string(&record)?)?;
+
+    writeln!(
+        printer.stderr(),
+        "Started `{}` in the background (PID {})",
+        record.command.cyan(),
+        record.pid.to_string().bold()
+    )?;
+    writeln!(
+        printer.stderr(),
+        "Output is written to: {}",
+        record.log.user_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Read the records of all detached processes, sorted by launch time.
+///
+/// Any records for processes that are no longer running are removed.
+pub(crate) fn read_processes() -> anyhow::Result<Vec<DetachedProcess>> {
+    let dir = processes_dir()?;
+    let entries = match fs_err::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut processes = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path
+            .extension()
+            .map_or(true, |extension| extension != "json")
+        {
+            continue;
+        }
+        let record = match serde_json::from_str::<DetachedProcess>(&fs_err::read_to_string(&path)?)
+        {
+            Ok(record) => record,
+            Err(err) => {
+                debug!(
+                    "Ignoring invalid process record at {}: {err}",
+                    path.user_display()
+                );
+                continue;
+            }
+        };
+        if record.is_running() {
+            processes.push(record);
+        } else {
+            debug!("Removing record for exited process: {}", record.pid);
+            record.remove()?;
+        }
+    }
+    processes.sort_by_key(|process| (process.started, process.pid));
+    Ok(processes)
+}
+
+/// Returns `true` if a process with the given ID is running.
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    let Some(pid) = i32::try_from(pid)
+        .ok()
+        .and_then(rustix::process::Pid::from_raw)
+    else {
+        return false;
+    };
+    rustix::process::test_kill_process(pid).is_ok()
+}
+
+/// Returns `true` if a process with the given ID is running.
+#[cfg(windows)]
+fn is_running(pid: u32) -> bool {
+    use windows_sys::Win32::Foundation::{CloseHandle, STILL_ACTIVE};
+    use windows_sys::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    // SAFETY: The handle is checked for validity before use, and closed after use.
+    #[allow(unsafe_code)]
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle == 0 {
+            return false;
+        }
+        let mut code = 0;
+        let success = GetExitCodeProcess(handle, &mut code);
+        CloseHandle(handle);
+        success != 0 && i32::try_from(code).is_ok_and(|code| code == STILL_ACTIVE)
+    }
+}
+
+/// Return the start time of the process with the given ID, in clock ticks since boot.
+#[cfg(target_os = "linux")]
+fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = fs_err::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name is wrapped in parentheses, and may itself contain spaces or parentheses;
+    // the start time is the 20th field after it.
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+/// Return the start time of the process with the given ID.
+///
+/// Not supported on this platform.
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Return the creation time of the process with the given ID, as a `FILETIME`.
+#[cfg(windows)]
+fn process_start_time(pid: u32) -> Option<u64> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    // SAFETY: The handle is checked for validity before use, and closed after use.
+    #[allow(unsafe_code)]
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle == 0 {
+            return None;
+        }
+        let start_time = handle_start_time(handle);
+        CloseHandle(handle);
+        start_time
+    }
+}
+
+/// Return the creation time of the process with the given handle, as a `FILETIME`.
+#[cfg(windows)]
+fn handle_start_time(handle: windows_sys::Win32::Foundation::HANDLE) -> Option<u64> {
+    use windows_sys::Win32::Foundation::FILETIME;
+    use windows_sys::Win32::System::Threading::GetProcessTimes;
+
+    let empty = || FILETIME {
+        dwLowDateTime: 0,
+        dwHighDateTime: 0,
+    };
+    let (mut creation, mut exit, mut kernel, mut user) = (empty(), empty(), empty(), empty());
+    // SAFETY: The caller guarantees that the handle is valid.
+    #[allow(unsafe_code)]
+    let success =
+        unsafe { GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user) };
+    if success == 0 {
+        return None;
+    }
+    Some((u64::from(creation.dwHighDateTime) << 32) | u64::from(creation.dwLowDateTime))
+}
+
+/// Stop the process with the given ID, along with any of its child processes.
+#[cfg(unix)]
+fn terminate(pid: u32, _start_time: Option<u64>) -> std::io::Result<()> {
+    let Some(pid) = i32::try_from(pid)
+        .ok()
+        .and_then(rustix::process::Pid::from_raw)
+    else {
+        return Err(std::io::Error::from(std::io::ErrorKind::InvalidInput));
+    };
+    // The process was started as the leader of its own process group.
+    rustix::process::kill_process_group(pid, rustix::process::Signal::Term)?;
+    Ok(())
+}
+
+/// Stop the process with the given ID.
+///
+/// The start time is verified against the same handle that's used to stop the process, such that
+/// the ID can't be reused in between.
+#[cfg(windows)]
+fn terminate(pid: u32, start_time: Option<u64>) -> std::io::Result<()> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, TerminateProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE,
+    };
+
+    // SAFETY: The handle is checked for validity before use, and closed after use.
+    #[allow(unsafe_code)]
+    unsafe {
+        let handle = OpenProcess(
+            PROCESS_TERMINATE | PROCESS_QUERY_LIMITED_INFORMATION,
+            0,
+            pid,
+        );
+        if handle == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if start_time.is_some_and(|start_time| handle_start_time(handle) != Some(start_time)) {
+            CloseHandle(handle);
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "the process exited, and its ID was reused by another process",
+            ));
+        }
+        let success = TerminateProcess(handle, 1);
+        CloseHandle(handle);
+        if success == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
@@ -1,26 +1,39 @@
+use std::collections::BTreeMap;
 use std::fmt::Write;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use distribution_types::UnresolvedRequirementSpecification;
 use owo_colors::OwoColorize;
 use tracing::debug;
 
+use pep508_rs::VerbatimUrl;
+use pypi_types::{Requirement, RequirementSource};
+
 use uv_cache::Cache;
+use uv_cli::ToolFormat;
 use uv_client::{BaseClientBuilder, Connectivity};
 use uv_configuration::{Concurrency, PreviewMode};
+use uv_fs::{Simplified, CWD};
 use uv_normalize::PackageName;
 use uv_python::{
     EnvironmentPreference, PythonDownloads, PythonInstallation, PythonPreference, PythonRequest,
 };
 use uv_requirements::{RequirementsSource, RequirementsSpecification};
+use uv_resolver::Lock;
 use uv_settings::{ResolverInstallerOptions, ToolOptions};
-use uv_tool::InstalledTools;
+use uv_tool::{EntrypointSelection, InstalledTools, ToolName};
 use uv_warnings::{warn_user, warn_user_once};
+use uv_workspace::{DiscoveryOptions, Workspace};
 
 use crate::commands::pip::loggers::{DefaultInstallLogger, DefaultResolveLogger};
+use crate::commands::pip::operations;
 
-use crate::commands::tool::common::remove_entrypoints;
+use crate::commands::tool::common::{
+    absolute_lock_path, check_executables, ensure_tool_lock, offline_fallback, read_tool_lock,
+    remove_entrypoints, write_summaries, write_tool_lock, ToolSummary,
+};
 use crate::commands::{
     project::{resolve_environment, resolve_names, sync_environment, update_environment},
     tool::common::InstallAction,
@@ -31,13 +44,23 @@ use crate::printer::Printer;
 use crate::settings::ResolverInstallerSettings;
 
 /// Install a tool.
+#[allow(clippy::fn_params_excessive_bools)]
 pub(crate) async fn install(
     package: String,
     editable: bool,
     from: Option<String>,
     with: &[RequirementsSource],
+    constraints: &[RequirementsSource],
+    overrides: &[RequirementsSource],
     python: Option<String>,
     force: bool,
+    locked: bool,
+    from_lock: Option<PathBuf>,
+    suffix: Option<String>,
+    selection: EntrypointSelection,
+    read_only_env: bool,
+    env: BTreeMap<String, String>,
+    format: ToolFormat,
     options: ResolverInstallerOptions,
     settings: ResolverInstallerSettings,
     preview: PreviewMode,
@@ -82,8 +105,17 @@ pub(crate) async fn install(
         .connectivity(connectivity)
         .native_tls(native_tls);
 
+    // If `--from-lock` was provided, read the workspace lockfile, and locate the member.
+    let workspace_lock = if let Some(from_lock) = from_lock {
+        Some(WorkspaceLock::read(&package, &from_lock).await?)
+    } else {
+        None
+    };
+
     // Resolve the `from` requirement.
-    let from = if let Some(from) = from {
+    let from = if let Some(workspace_lock) = workspace_lock.as_ref() {
+        workspace_lock.requirement.clone()
+    } else if let Some(from) = from {
         // Parse the positional name. If the user provided more than a package name, it's an error
         // (e.g., `uv install foo==1.0 --from foo`).
         let Ok(package) = PackageName::from_str(&package) else {
@@ -95,9 +127,9 @@ pub(crate) async fn install(
         } else {
             RequirementsSource::Package(from)
         };
-        let requirements = RequirementsSpecification::from_source(&source, &client_builder)
-            .await?
-            .requirements;
+        let spec = RequirementsSpecification::from_source(&source, &client_builder).await?;
+        spec.reject_git_editables()?;
+        let requirements = spec.requirements;
 
         let from_requirement = {
             resolve_names(
@@ -134,9 +166,9 @@ pub(crate) async fn install(
         } else {
             RequirementsSource::Package(package.clone())
         };
-        let requirements = RequirementsSpecification::from_source(&source, &client_builder)
-            .await?
-            .requirements;
+        let spec = RequirementsSpecification::from_source(&source, &client_builder).await?;
+        spec.reject_git_editables()?;
+        let requirements = spec.requirements;
 
         resolve_names(
             requirements,
@@ -155,8 +187,14 @@ pub(crate) async fn install(
         .unwrap()
     };
 
-    // Read the `--with` requirements.
-    let spec = RequirementsSpecification::from_simple_sources(with, &client_builder).await?;
+    // Determine the name under which to install the tool.
+    let name = ToolName::new(from.name.clone(), suffix)?;
+
+    // Read the `--with` requirements, along with any constraints and overrides.
+    let spec =
+        RequirementsSpecification::from_sources(with, constraints, overrides, &client_builder)
+            .await?;
+    spec.reject_git_editables()?;
 
     // Resolve the `--from` and `--with` requirements.
     let requirements = {
@@ -178,8 +216,35 @@ pub(crate) async fn install(
             .await?,
         );
         requirements
+            .into_iter()
+            .map(absolute_lock_path)
+            .collect::<Vec<_>>()
     };
 
+    // Resolve the overrides, which may include unnamed requirements.
+    let constraints = spec
+        .constraints
+        .iter()
+        .cloned()
+        .map(absolute_lock_path)
+        .collect::<Vec<_>>();
+    let overrides = resolve_names(
+        spec.overrides.clone(),
+        &interpreter,
+        &settings,
+        &state,
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?
+    .into_iter()
+    .map(absolute_lock_path)
+    .collect::<Vec<_>>();
+
     // Convert to tool options.
     let options = ToolOptions::from(options);
 
@@ -194,60 +259,110 @@ pub(crate) async fn install(
     //
     // (If we find existing entrypoints later on, and the tool _doesn't_ exist, we'll avoid removing
     // the external tool's entrypoints (without `--force`).)
-    let (existing_tool_receipt, invalid_tool_receipt) =
-        match installed_tools.get_tool_receipt(&from.name) {
-            Ok(None) => (None, false),
-            Ok(Some(receipt)) => (Some(receipt), false),
-            Err(_) => {
-                // If the tool is not installed properly, remove the environment and continue.
-                match installed_tools.remove_environment(&from.name) {
-                    Ok(()) => {
-                        warn_user!(
-                            "Removed existing `{from}` with invalid receipt",
-                            from = from.name.cyan()
-                        );
-                    }
-                    Err(uv_tool::Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {}
-                    Err(err) => {
-                        return Err(err.into());
-                    }
+    let existing_tool_receipt = installed_tools.get_tool_receipt(&name);
+
+    // If the receipt was written by a newer version of uv, it isn't necessarily broken, so avoid
+    // removing the environment unless the user opted in with `--force`.
+    if !force
+        && matches!(
+            existing_tool_receipt,
+            Err(uv_tool::Error::UnsupportedReceiptVersion(..))
+        )
+    {
+        bail!(
+            "`{from}` was installed by a newer version of uv; upgrade uv, or use `--force` to reinstall it",
+            from = name
+        );
+    }
+
+    let (existing_tool_receipt, invalid_tool_receipt) = match existing_tool_receipt {
+        Ok(None) => (None, false),
+        Ok(Some(receipt)) => (Some(receipt), false),
+        Err(_) => {
+            // If the tool is not installed properly, remove the environment and continue.
+            match installed_tools.remove_environment(&name) {
+                Ok(()) => {
+                    warn_user!(
+                        "Removed existing `{from}` with invalid receipt",
+                        from = name.cyan()
+                    );
+                }
+                Err(uv_tool::Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(err.into());
                 }
-                (None, true)
             }
-        };
+            (None, true)
+        }
+    };
+
+    // If the tool wasn't installed previously, the environment is created by this command.
+    let created = existing_tool_receipt.is_none();
 
     let existing_environment =
         installed_tools
-            .get_environment(&from.name, cache)?
+            .get_environment(&name, cache)?
             .filter(|environment| {
                 python_request.as_ref().map_or(true, |python_request| {
                     if python_request.satisfied(environment.interpreter(), cache) {
-                        debug!("Found existing environment for `{from}`", from = from.name.cyan());
+                        debug!("Found existing environment for `{from}`", from = name.cyan());
                         true
                     } else {
                         let _ = writeln!(
                             printer.stderr(),
                             "Existing environment for `{from}` does not satisfy the requested Python interpreter",
-                            from = from.name.cyan(),
+                            from = name.cyan(),
                         );
                         false
                     }
                 })
             });
 
+    // If `--locked` was provided, read the lockfile, which must match the requested requirements.
+    let tool_lock = if locked {
+        if existing_tool_receipt.as_ref().map_or(true, |receipt| {
+            receipt.requirements() != requirements
+                || receipt.constraints() != constraints
+                || receipt.overrides() != overrides
+        }) {
+            bail!(
+                "The requirements for `{from}` do not match the lockfile; run without `--locked` to update it",
+                from = name
+            );
+        }
+        let Some(lock) = read_tool_lock(&installed_tools, &name)? else {
+            bail!(
+                "Unable to find a lockfile for `{from}`; run without `--locked` to create it",
+                from = name
+            );
+        };
+        Some(lock)
+    } else {
+        None
+    };
+
     // If the requested and receipt requirements are the same...
-    if existing_environment.is_some() {
+    if existing_environment.is_some() && tool_lock.is_none() && workspace_lock.is_none() {
         if let Some(tool_receipt) = existing_tool_receipt.as_ref() {
-            let receipt = tool_receipt.requirements().to_vec();
-            if requirements == receipt {
+            if tool_receipt.requirements() == requirements
+                && tool_receipt.constraints() == constraints
+                && tool_receipt.overrides() == overrides
+                && *tool_receipt.selection() == selection
+                && *tool_receipt.env() == env
+            {
                 // And the user didn't request a reinstall or upgrade...
                 if !force && settings.reinstall.is_none() && settings.upgrade.is_none() {
                     if *tool_receipt.options() != options {
                         // ...but the options differ, we need to update the receipt.
-                        installed_tools.add_tool_receipt(
-                            &from.name,
-                            tool_receipt.clone().with_options(options),
-                        )?;
+                        installed_tools
+                            .add_tool_receipt(&name, tool_receipt.clone().with_options(options))?;
+                    }
+
+                    // Mark the existing environment as read-only, if requested.
+                    if read_only_env {
+                        if let Some(environment) = existing_environment.as_ref() {
+                            operations::set_read_only(environment, true)?;
+                        }
                     }
 
                     // We're done, though we might need to update the receipt.
@@ -257,6 +372,11 @@ pub(crate) async fn install(
                         from = from.cyan()
                     )?;
 
+                    if format != ToolFormat::Text {
+                        let summary = ToolSummary::read(&installed_tools, &name, cache)?;
+                        write_summaries(&[summary], format, printer)?;
+                    }
+
                     return Ok(ExitStatus::Success);
                 }
             }
@@ -270,21 +390,99 @@ pub(crate) async fn install(
             .cloned()
             .map(UnresolvedRequirementSpecification::from)
             .collect(),
+        overrides: overrides
+            .iter()
+            .cloned()
+            .map(UnresolvedRequirementSpecification::from)
+            .collect(),
         ..spec
     };
 
-    // TODO(zanieb): Build the environment in the cache directory then copy into the tool directory.
-    // This lets us confirm the environment is valid before removing an existing install. However,
-    // entrypoints always contain an absolute path to the relevant Python interpreter, which would
-    // be invalidated by moving the environment.
-    let environment = if let Some(environment) = existing_environment {
-        let environment = update_environment(
-            environment,
-            spec,
+    // Build the environment in a staging directory, such that the existing installation (if any) is
+    // left intact until the new environment is complete.
+    let staged = if let Some(workspace_lock) = workspace_lock {
+        if let Some(requires_python) = workspace_lock.lock.requires_python() {
+            if !requires_python.contains(interpreter.python_version()) {
+                bail!(
+                    "The Python interpreter ({}) is incompatible with the `requires-python` of the lockfile ({requires_python})",
+                    interpreter.python_version()
+                );
+            }
+        }
+
+        let staged = installed_tools.stage_environment(&name, interpreter, cache)?;
+
+        // Install exactly the packages in the workspace lockfile, without resolving. Workspace
+        // members are locked as editable, but the tool shouldn't depend on the checkout.
+        let interpreter = staged.environment().interpreter();
+        let resolution = workspace_lock
+            .lock
+            .to_resolution_for_requirements(
+                &requirements,
+                &workspace_lock.root,
+                interpreter.markers(),
+                interpreter.tags()?,
+                &settings.build_options,
+            )?
+            .non_editable();
+        sync_environment(
+            staged.environment().clone(),
+            &resolution,
+            settings.as_ref().into(),
+            &state,
+            Box::new(DefaultInstallLogger::default()),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+
+        staged
+    } else if let Some(lock) = tool_lock {
+        let staged = if existing_environment.is_some() {
+            installed_tools.stage_existing_environment(&name, cache)?
+        } else {
+            installed_tools.stage_environment(&name, interpreter, cache)?
+        };
+
+        // Install exactly the packages in the lockfile, without resolving.
+        let interpreter = staged.environment().interpreter();
+        let resolution = lock.to_resolution_for_requirements(
+            &requirements,
+            &installed_tools.tool_dir(&name),
+            interpreter.markers(),
+            interpreter.tags()?,
+            &settings.build_options,
+        )?;
+        sync_environment(
+            staged.environment().clone(),
+            &resolution,
+            settings.as_ref().into(),
+            &state,
+            Box::new(DefaultInstallLogger::default()),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+        write_tool_lock(staged.environment(), &lock)?;
+
+        staged
+    } else if existing_environment.is_some() {
+        let staged = installed_tools.stage_existing_environment(&name, cache)?;
+        let update = update_environment(
+            staged.environment().clone(),
+            spec.clone(),
             &settings,
             &state,
             Box::new(DefaultResolveLogger),
-            Box::new(DefaultInstallLogger),
+            Box::new(DefaultInstallLogger::default()),
             preview,
             connectivity,
             concurrency,
@@ -294,19 +492,33 @@ pub(crate) async fn install(
         )
         .await?;
 
-        // At this point, we updated the existing environment, so we should remove any of its
-        // existing executables.
-        if let Some(existing_receipt) = existing_tool_receipt {
-            remove_entrypoints(&existing_receipt);
+        // If the environment was re-resolved, record the new resolution. Otherwise, ensure that
+        // the environment is locked.
+        if let Some(graph) = update.resolution {
+            write_tool_lock(staged.environment(), &Lock::from_resolution_graph(&graph)?)?;
+        } else {
+            ensure_tool_lock(
+                staged.environment(),
+                spec,
+                &settings,
+                &state,
+                preview,
+                connectivity,
+                concurrency,
+                native_tls,
+                cache,
+                printer,
+            )
+            .await?;
         }
 
-        environment
+        staged
     } else {
         // If we're creating a new environment, ensure that we can resolve the requirements prior
         // to removing any existing tools.
-        let resolution = resolve_environment(
+        let result = resolve_environment(
             &interpreter,
-            spec,
+            spec.clone(),
             settings.as_ref().into(),
             &state,
             Box::new(DefaultResolveLogger),
@@ -317,23 +529,44 @@ pub(crate) async fn install(
             cache,
             printer,
         )
-        .await?;
+        .await;
+        let resolution = match result {
+            Ok(resolution) => resolution,
+            Err(err) => {
+                // When offline, fall back to the newest version of the tool in the cache. The
+                // receipt retains the requested version, such that an upgrade isn't blocked.
+                let Some(spec) = offline_fallback(&from, &spec, connectivity, &interpreter, cache)
+                else {
+                    return Err(err.into());
+                };
+                debug!("Failed to resolve `{from}` offline: {err}");
+                resolve_environment(
+                    &interpreter,
+                    spec,
+                    settings.as_ref().into(),
+                    &state,
+                    Box::new(DefaultResolveLogger),
+                    preview,
+                    connectivity,
+                    concurrency,
+                    native_tls,
+                    cache,
+                    printer,
+                )
+                .await?
+            }
+        };
+        let lock = Lock::from_resolution_graph(&resolution)?;
 
-        let environment = installed_tools.create_environment(&from.name, interpreter)?;
-
-        // At this point, we removed any existing environment, so we should remove any of its
-        // executables.
-        if let Some(existing_receipt) = existing_tool_receipt {
-            remove_entrypoints(&existing_receipt);
-        }
+        let staged = installed_tools.stage_environment(&name, interpreter, cache)?;
 
         // Sync the environment with the resolved requirements.
         sync_environment(
-            environment,
+            staged.environment().clone(),
             &resolution.into(),
             settings.as_ref().into(),
             &state,
-            Box::new(DefaultInstallLogger),
+            Box::new(DefaultInstallLogger::default()),
             preview,
             connectivity,
             concurrency,
@@ -341,18 +574,110 @@ pub(crate) async fn install(
             cache,
             printer,
         )
-        .await?
+        .await?;
+        write_tool_lock(staged.environment(), &lock)?;
+
+        staged
     };
 
-    install_executables(
+    // Validate the executable selection before replacing any existing environment.
+    if !check_executables(staged.environment(), &name, &selection, printer)? {
+        return Ok(ExitStatus::Failure);
+    }
+
+    let environment = installed_tools.commit_environment(staged, cache)?;
+
+    // At this point, we replaced any existing environment, so we should remove any of its
+    // executables.
+    if let Some(existing_receipt) = existing_tool_receipt {
+        remove_entrypoints(&existing_receipt);
+    }
+
+    // Mark the tool environment as read-only, if requested.
+    if read_only_env {
+        operations::set_read_only(&environment, true)?;
+    }
+
+    let status = install_executables(
         &environment,
-        &from.name,
+        &name,
         &installed_tools,
         options,
         force || invalid_tool_receipt,
+        created,
         python,
         requirements,
+        constraints,
+        overrides,
+        selection,
+        env,
         InstallAction::Install,
         printer,
-    )
+    )?;
+
+    if matches!(status, ExitStatus::Success) && format != ToolFormat::Text {
+        let summary = ToolSummary::read(&installed_tools, &name, cache)?;
+        write_summaries(&[summary], format, printer)?;
+    }
+
+    Ok(status)
+}
+
+/// A workspace lockfile from which a member is installed as a tool, via `--from-lock`.
+struct WorkspaceLock {
+    /// The lockfile of the workspace.
+    lock: Lock,
+    /// The root of the workspace, against which the paths in the lockfile are resolved.
+    root: PathBuf,
+    /// The requirement for the workspace member.
+    requirement: Requirement,
+}
+
+impl WorkspaceLock {
+    /// Read the lockfile at the given path, and locate the workspace member with the given name.
+    async fn read(package: &str, path: &Path) -> Result<Self> {
+        let Ok(name) = PackageName::from_str(package) else {
+            bail!(
+                "Expected the name of a workspace member with `--from-lock`, but found: `{}`",
+                package.cyan()
+            );
+        };
+
+        let path = CWD.join(path);
+        let encoded = fs_err::tokio::read_to_string(&path).await?;
+        let lock = Lock::from_toml(&encoded)
+            .with_context(|| format!("Failed to parse lockfile: `{}`", path.user_display()))?;
+
+        // The lockfile is stored at the root of the workspace.
+        let root = path.parent().unwrap_or(&CWD);
+        let workspace = Workspace::discover(root, &DiscoveryOptions::default()).await?;
+        let Some(member) = workspace.packages().get(&name) else {
+            bail!(
+                "`{}` is not a member of the workspace at: `{}`",
+                name.cyan(),
+                workspace.install_path().user_display()
+            );
+        };
+
+        let install_path = member.root().clone();
+        let url = VerbatimUrl::from_path(&install_path)?.with_given(install_path.to_string_lossy());
+        let requirement = Requirement {
+            name,
+            extras: vec![],
+            marker: None,
+            source: RequirementSource::Directory {
+                lock_path: install_path.clone(),
+                install_path,
+                editable: false,
+                url,
+            },
+            origin: None,
+        };
+
+        Ok(Self {
+            lock,
+            root: workspace.install_path().clone(),
+            requirement,
+        })
+    }
 }
@@ -0,0 +1,59 @@
+use std::fmt::Write;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+
+use uv_configuration::PreviewMode;
+use uv_warnings::warn_user_once;
+
+use crate::commands::tool::detach::read_processes;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Stop tools that are running in the background.
+///
+/// Only processes that were launched via `uv tool run --detach` can be stopped.
+pub(crate) fn kill(
+    pids: &[u32],
+    all: bool,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv tool kill` is experimental and may change without warning");
+    }
+
+    let processes = read_processes()?;
+
+    let selected = if all {
+        if processes.is_empty() {
+            writeln!(printer.stderr(), "No tools are running in the background")?;
+            return Ok(ExitStatus::Success);
+        }
+        processes
+    } else {
+        let mut selected = Vec::with_capacity(pids.len());
+        for pid in pids {
+            let Some(process) = processes.iter().find(|process| process.pid == *pid) else {
+                bail!("No tool is running in the background with PID {pid}");
+            };
+            selected.push(process.clone());
+        }
+        selected
+    };
+
+    for process in selected {
+        process
+            .terminate()
+            .with_context(|| format!("Failed to stop process {}", process.pid))?;
+        process.remove()?;
+        writeln!(
+            printer.stderr(),
+            "Stopped `{}` (PID {})",
+            process.command.cyan(),
+            process.pid.to_string().bold()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
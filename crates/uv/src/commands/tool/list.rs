@@ -4,17 +4,20 @@ use anyhow::Result;
 use owo_colors::OwoColorize;
 
 use uv_cache::Cache;
+use uv_cli::ToolFormat;
 use uv_configuration::PreviewMode;
 use uv_fs::Simplified;
 use uv_tool::InstalledTools;
 use uv_warnings::{warn_user, warn_user_once};
 
+use crate::commands::tool::common::{write_summaries, ToolSummary};
 use crate::commands::ExitStatus;
 use crate::printer::Printer;
 
 /// List installed tools.
 pub(crate) async fn list(
     show_paths: bool,
+    format: ToolFormat,
     preview: PreviewMode,
     cache: &Cache,
     printer: Printer,
@@ -27,8 +30,7 @@ pub(crate) async fn list(
     let _lock = match installed_tools.acquire_lock() {
         Ok(lock) => lock,
         Err(uv_tool::Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
-            writeln!(printer.stderr(), "No tools installed")?;
-            return Ok(ExitStatus::Success);
+            return write_empty(format, printer);
         }
         Err(err) => return Err(err.into()),
     };
@@ -37,12 +39,18 @@ pub(crate) async fn list(
     tools.sort_by_key(|(name, _)| name.clone());
 
     if tools.is_empty() {
-        writeln!(printer.stderr(), "No tools installed")?;
-        return Ok(ExitStatus::Success);
+        return write_empty(format, printer);
     }
 
+    let mut summaries = Vec::with_capacity(tools.len());
     for (name, tool) in tools {
         // Skip invalid tools
+        if let Err(uv_tool::Error::UnsupportedReceiptVersion(..)) = tool {
+            warn_user!(
+                "Ignoring `{name}`, which was installed by a newer version of uv (upgrade uv to list it)"
+            );
+            continue;
+        }
         let Ok(tool) = tool else {
             warn_user!(
                 "Ignoring malformed tool `{name}` (run `{}` to remove)",
@@ -51,7 +59,14 @@ pub(crate) async fn list(
             continue;
         };
 
-        // Output tool name and version
+        if format != ToolFormat::Text {
+            match ToolSummary::from_tool(&installed_tools, &name, &tool, cache) {
+                Ok(summary) => summaries.push(summary),
+                Err(e) => writeln!(printer.stderr(), "{e}")?,
+            }
+            continue;
+        }
+
         let version = match installed_tools.version(&name, cache) {
             Ok(version) => version,
             Err(e) => {
@@ -60,6 +75,7 @@ pub(crate) async fn list(
             }
         };
 
+        // Output tool name and version
         if show_paths {
             writeln!(
                 printer.stdout(),
@@ -86,5 +102,17 @@ pub(crate) async fn list(
         }
     }
 
+    write_summaries(&summaries, format, printer)?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Write the output for an empty list of tools.
+fn write_empty(format: ToolFormat, printer: Printer) -> Result<ExitStatus> {
+    if format == ToolFormat::Text {
+        writeln!(printer.stderr(), "No tools installed")?;
+    } else {
+        write_summaries(&[], format, printer)?;
+    }
     Ok(ExitStatus::Success)
 }
@@ -1,8 +1,14 @@
 mod common;
+mod detach;
 pub(crate) mod dir;
 pub(crate) mod install;
+pub(crate) mod kill;
 pub(crate) mod list;
+pub(crate) mod prune;
+pub(crate) mod ps;
+pub(crate) mod repair;
 pub(crate) mod run;
+pub(crate) mod show;
 pub(crate) mod uninstall;
 pub(crate) mod update_shell;
 pub(crate) mod upgrade;
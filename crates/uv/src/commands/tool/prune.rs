@@ -0,0 +1,145 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_tool::{find_executable_directory, InstalledTools, ToolName};
+use uv_warnings::{warn_user, warn_user_once};
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Remove orphaned tool environments, along with any dangling executables.
+pub(crate) fn prune(dry_run: bool, preview: PreviewMode, printer: Printer) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv tool prune` is experimental and may change without warning");
+    }
+
+    let installed_tools = InstalledTools::from_settings()?;
+    let _lock = match installed_tools.acquire_lock() {
+        Ok(lock) => lock,
+        Err(uv_tool::Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+            writeln!(printer.stderr(), "Nothing to prune")?;
+            return Ok(ExitStatus::Success);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    // Find any environments that are missing a valid receipt, or all of their executables.
+    let mut orphans: Vec<(ToolName, &'static str)> = Vec::new();
+    for (name, receipt) in installed_tools.tools()? {
+        let reason = match receipt {
+            Ok(receipt) => {
+                let missing = receipt
+                    .entrypoints()
+                    .iter()
+                    .filter(|entrypoint| !entrypoint.install_path.exists())
+                    .count();
+                if missing == 0 {
+                    continue;
+                }
+                if missing < receipt.entrypoints().len() {
+                    writeln!(
+                        printer.stderr(),
+                        "`{}` is missing some of its executables; run `{}` to reinstall them",
+                        name.cyan(),
+                        format!("uv tool repair {name}").green()
+                    )?;
+                    continue;
+                }
+                "the executables are missing"
+            }
+            Err(uv_tool::Error::UnsupportedReceiptVersion(..)) => {
+                warn_user!(
+                    "Ignoring `{name}`, which was installed by a newer version of uv (upgrade uv to prune it)"
+                );
+                continue;
+            }
+            Err(uv_tool::Error::MissingToolReceipt(..)) => "the receipt is missing",
+            Err(_) => "the receipt is invalid",
+        };
+        orphans.push((name, reason));
+    }
+
+    // Find any executables that link into a tool environment that no longer exists.
+    let dangling = match find_executable_directory() {
+        Ok(executable_directory) => {
+            dangling_executables(&executable_directory, installed_tools.root())
+        }
+        Err(err) => {
+            debug!("Skipping search for dangling executables: {err}");
+            Vec::new()
+        }
+    };
+
+    if orphans.is_empty() && dangling.is_empty() {
+        writeln!(printer.stderr(), "Nothing to prune")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for (name, reason) in &orphans {
+        if dry_run {
+            writeln!(
+                printer.stderr(),
+                "Would remove `{}` ({})",
+                name.cyan(),
+                reason.dimmed()
+            )?;
+        } else {
+            installed_tools.remove_environment(name)?;
+            writeln!(
+                printer.stderr(),
+                "Removed `{}` ({})",
+                name.cyan(),
+                reason.dimmed()
+            )?;
+        }
+    }
+
+    for executable in &dangling {
+        if dry_run {
+            writeln!(
+                printer.stderr(),
+                "Would remove dangling executable: {}",
+                executable.user_display().cyan()
+            )?;
+        } else {
+            fs_err::remove_file(executable)?;
+            writeln!(
+                printer.stderr(),
+                "Removed dangling executable: {}",
+                executable.user_display().cyan()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Returns the executables in the given directory that link into the tools directory, but whose
+/// targets no longer exist.
+///
+/// Only symlinked executables are considered, since executables that were copied into place
+/// (e.g., on Windows) can't be traced back to their environment.
+fn dangling_executables(executable_directory: &Path, tools_directory: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs_err::read_dir(executable_directory) else {
+        return Vec::new();
+    };
+
+    let mut dangling = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let Ok(target) = fs_err::read_link(path) else {
+                return false;
+            };
+            target.starts_with(tools_directory) && !path.exists()
+        })
+        .collect::<Vec<_>>();
+    dangling.sort();
+    dangling
+}
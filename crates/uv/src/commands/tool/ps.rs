@@ -0,0 +1,41 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_warnings::warn_user_once;
+
+use crate::commands::tool::detach::read_processes;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// List the tools that are running in the background.
+pub(crate) fn ps(preview: PreviewMode, printer: Printer) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv tool ps` is experimental and may change without warning");
+    }
+
+    let processes = read_processes()?;
+    if processes.is_empty() {
+        writeln!(printer.stderr(), "No tools are running in the background")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for process in processes {
+        writeln!(
+            printer.stdout(),
+            "{} {}",
+            process.pid.to_string().bold(),
+            process.command
+        )?;
+        writeln!(
+            printer.stdout(),
+            "- {}",
+            process.log.simplified_display().cyan()
+        )?;
+    }
+
+    Ok(ExitStatus::Success)
+}
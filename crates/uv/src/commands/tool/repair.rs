@@ -0,0 +1,327 @@
+use std::fmt::Write;
+
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use uv_cache::Cache;
+use uv_client::{BaseClientBuilder, Connectivity};
+use uv_configuration::{Concurrency, PreviewMode};
+use uv_fs::Simplified;
+use uv_python::{
+    EnvironmentPreference, PythonDownloads, PythonEnvironment, PythonInstallation,
+    PythonPreference, PythonRequest,
+};
+use uv_requirements::RequirementsSpecification;
+use uv_resolver::Lock;
+use uv_settings::{Combine, ResolverInstallerOptions};
+use uv_tool::{InstalledTools, Tool, ToolName};
+use uv_warnings::warn_user_once;
+
+use crate::commands::pip::loggers::{DefaultInstallLogger, DefaultResolveLogger};
+use crate::commands::project::{resolve_environment, sync_environment};
+use crate::commands::reporters::PythonDownloadReporter;
+use crate::commands::tool::common::{
+    check_executables, install_executables, remove_entrypoints, write_tool_lock, InstallAction,
+};
+use crate::commands::{ExitStatus, SharedState};
+use crate::printer::Printer;
+use crate::settings::ResolverInstallerSettings;
+
+/// Repair a tool, or all broken tools.
+pub(crate) async fn repair(
+    name: Option<ToolName>,
+    filesystem: ResolverInstallerOptions,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv tool repair` is experimental and may change without warning");
+    }
+
+    let installed_tools = InstalledTools::from_settings()?.init()?;
+    let _lock = installed_tools.acquire_lock()?;
+
+    let tools = if let Some(name) = name {
+        let receipt = match installed_tools.get_tool_receipt(&name) {
+            Ok(Some(receipt)) => receipt,
+            Ok(None) => bail!("`{name}` is not installed"),
+            Err(_) => bail!(
+                "`{name}` is missing a valid receipt; run `uv tool install --force {name}` to reinstall"
+            ),
+        };
+        let Some(reason) = diagnose(&name, &receipt, &installed_tools, cache) else {
+            writeln!(
+                printer.stderr(),
+                "`{}` does not need to be repaired",
+                name.cyan()
+            )?;
+            return Ok(ExitStatus::Success);
+        };
+        vec![(name, receipt, reason)]
+    } else {
+        let mut tools = Vec::new();
+        for (name, receipt) in installed_tools.tools()? {
+            let Ok(receipt) = receipt else {
+                writeln!(
+                    printer.stderr(),
+                    "`{}` is missing a valid receipt; run `{}` to reinstall",
+                    name.cyan(),
+                    format!("uv tool install --force {name}").green()
+                )?;
+                continue;
+            };
+            if let Some(reason) = diagnose(&name, &receipt, &installed_tools, cache) {
+                tools.push((name, receipt, reason));
+            }
+        }
+        if tools.is_empty() {
+            writeln!(printer.stderr(), "Nothing to repair")?;
+            return Ok(ExitStatus::Success);
+        }
+        tools
+    };
+
+    // Initialize any shared state.
+    let state = SharedState::default();
+
+    let mut failed = false;
+    for (name, receipt, reason) in tools {
+        writeln!(
+            printer.stderr(),
+            "Repairing `{}` ({})",
+            name.cyan(),
+            reason.dimmed()
+        )?;
+
+        match repair_tool(
+            &name,
+            &receipt,
+            &installed_tools,
+            &filesystem,
+            &state,
+            python_preference,
+            python_downloads,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            preview,
+            printer,
+        )
+        .await
+        {
+            Ok(ExitStatus::Success) => {}
+            Ok(_) => failed = true,
+            Err(err) => {
+                writeln!(
+                    printer.stderr(),
+                    "{}: Failed to repair `{}`",
+                    "error".red().bold(),
+                    name.cyan()
+                )?;
+                for err in err.chain() {
+                    writeln!(printer.stderr(), "  {}: {}", "Caused by".red().bold(), err)?;
+                }
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        return Ok(ExitStatus::Failure);
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Determine whether the environment for a tool is broken, returning a description of the problem
+/// if so.
+///
+/// An environment is broken if its base interpreter no longer exists, or if any of its executables
+/// are missing or don't refer to the environment (e.g., because their shebangs are stale).
+fn diagnose(
+    name: &ToolName,
+    receipt: &Tool,
+    installed_tools: &InstalledTools,
+    cache: &Cache,
+) -> Option<&'static str> {
+    if !installed_tools.tool_dir(name).is_dir() {
+        return Some("the environment is missing");
+    }
+
+    let environment = match installed_tools.get_environment(name, cache) {
+        Ok(Some(environment)) => environment,
+        Ok(None) => return Some("the Python interpreter is missing"),
+        Err(err) => {
+            debug!("Failed to query environment for `{name}`: {err}");
+            return Some("the environment is invalid");
+        }
+    };
+
+    if receipt.entrypoints().iter().any(|entrypoint| {
+        is_stale(
+            &entrypoint.install_path,
+            installed_tools,
+            name,
+            &environment,
+        )
+    }) {
+        return Some("the executables are stale");
+    }
+
+    debug!("Environment for `{name}` is intact");
+    None
+}
+
+/// Returns `true` if the executable at the given path is missing, or doesn't refer to the tool
+/// environment.
+fn is_stale(
+    executable: &std::path::Path,
+    installed_tools: &InstalledTools,
+    name: &ToolName,
+    environment: &PythonEnvironment,
+) -> bool {
+    // On Unix, executables are symlinked into the environment, so a missing target also counts.
+    let Ok(contents) = fs_err::read(executable) else {
+        debug!("Executable is missing: {}", executable.user_display());
+        return true;
+    };
+
+    // The executable may refer to the environment by its canonical path, or by the path at which
+    // it was installed.
+    let contents = String::from_utf8_lossy(&contents);
+    let roots = [
+        environment.root().simplified_display().to_string(),
+        installed_tools
+            .tool_dir(name)
+            .simplified_display()
+            .to_string(),
+    ];
+    if roots.iter().any(|root| contents.contains(root.as_str())) {
+        return false;
+    }
+
+    debug!(
+        "Executable does not refer to the tool environment: {}",
+        executable.user_display()
+    );
+    true
+}
+
+/// Recreate the environment for a tool from its receipt, and reinstall its executables.
+async fn repair_tool(
+    name: &ToolName,
+    receipt: &Tool,
+    installed_tools: &InstalledTools,
+    filesystem: &ResolverInstallerOptions,
+    state: &SharedState,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let client_builder = BaseClientBuilder::new()
+        .connectivity(connectivity)
+        .native_tls(native_tls);
+
+    let reporter = PythonDownloadReporter::single(printer);
+
+    // Find an interpreter that satisfies the tool's original Python request.
+    let python_request = receipt.python().as_deref().map(PythonRequest::parse);
+    let interpreter = PythonInstallation::find_or_download(
+        python_request,
+        EnvironmentPreference::OnlySystem,
+        python_preference,
+        python_downloads,
+        &client_builder,
+        cache,
+        Some(&reporter),
+    )
+    .await?
+    .into_interpreter();
+
+    // Resolve the appropriate settings, preferring: receipt > user.
+    let options =
+        ResolverInstallerOptions::from(receipt.options().clone()).combine(filesystem.clone());
+    let settings = ResolverInstallerSettings::from(options);
+
+    // Resolve the requirements before touching the existing environment.
+    let requirements = receipt.requirements().to_vec();
+    let spec = RequirementsSpecification::from_constraints(
+        requirements.clone(),
+        receipt.constraints().to_vec(),
+        receipt.overrides().to_vec(),
+    );
+    let resolution = resolve_environment(
+        &interpreter,
+        spec,
+        settings.as_ref().into(),
+        state,
+        Box::new(DefaultResolveLogger),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?;
+    let lock = Lock::from_resolution_graph(&resolution)?;
+
+    // Build the new environment in a staging directory, replacing the broken environment once
+    // it's complete.
+    let staged = installed_tools.stage_environment(name, interpreter, cache)?;
+    sync_environment(
+        staged.environment().clone(),
+        &resolution.into(),
+        settings.as_ref().into(),
+        state,
+        Box::new(DefaultInstallLogger::default()),
+        preview,
+        connectivity,
+        concurrency,
+        native_tls,
+        cache,
+        printer,
+    )
+    .await?;
+    write_tool_lock(staged.environment(), &lock)?;
+
+    // Validate the executable selection before replacing the broken environment.
+    if !check_executables(staged.environment(), name, receipt.selection(), printer)? {
+        return Ok(ExitStatus::Failure);
+    }
+
+    let environment = installed_tools.commit_environment(staged, cache)?;
+
+    // Replace the existing executables, which refer to the broken environment.
+    remove_entrypoints(receipt);
+
+    install_executables(
+        &environment,
+        name,
+        installed_tools,
+        receipt.options().clone(),
+        true,
+        false,
+        receipt.python().to_owned(),
+        requirements,
+        receipt.constraints().to_vec(),
+        receipt.overrides().to_vec(),
+        receipt.selection().clone(),
+        receipt.env().clone(),
+        InstallAction::Install,
+        printer,
+    )
+}
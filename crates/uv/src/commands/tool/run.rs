@@ -2,6 +2,7 @@ use std::ffi::OsString;
 use std::fmt::Write;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 use std::{borrow::Cow, fmt::Display};
 
 use anstream::eprint;
@@ -13,31 +14,37 @@ use tracing::{debug, warn};
 
 use distribution_types::{Name, UnresolvedRequirementSpecification};
 use pep440_rs::Version;
-use pypi_types::Requirement;
+use pypi_types::{Requirement, RequirementSource};
 use uv_cache::Cache;
-use uv_cli::ExternalCommand;
+use uv_cli::{ExternalCommand, ToolFormat};
 use uv_client::{BaseClientBuilder, Connectivity};
-use uv_configuration::{Concurrency, PreviewMode};
+use uv_configuration::{Concurrency, ExtrasSpecification, PreviewMode, TyposquatCheck};
+use uv_fs::CWD;
 use uv_installer::{SatisfiesResult, SitePackages};
-use uv_normalize::PackageName;
+use uv_normalize::{ExtraName, PackageName};
 use uv_python::{
     EnvironmentPreference, PythonDownloads, PythonEnvironment, PythonInstallation,
     PythonPreference, PythonRequest,
 };
 use uv_requirements::{RequirementsSource, RequirementsSpecification};
-use uv_tool::{entrypoint_paths, InstalledTools};
+use uv_resolver::Lock;
+use uv_tool::{entrypoint_paths, InstalledTools, ToolName};
 use uv_warnings::{warn_user, warn_user_once};
+use uv_workspace::{DiscoveryOptions, VirtualProject};
 
 use crate::commands::pip::loggers::{
     DefaultInstallLogger, DefaultResolveLogger, SummaryInstallLogger, SummaryResolveLogger,
 };
 use crate::commands::pip::operations;
-use crate::commands::project::{resolve_names, ProjectError};
+use crate::commands::project::{lock, resolve_names, ProjectError};
 use crate::commands::reporters::PythonDownloadReporter;
+use crate::commands::tool::detach::spawn_detached;
+use crate::commands::{check_typosquats, ExitStatus, SharedState};
 use crate::commands::{
-    project::environment::CachedEnvironment, tool::common::matching_packages, tool_list,
+    project::environment::CachedEnvironment,
+    tool::common::{matching_packages, offline_fallback},
+    tool_list,
 };
-use crate::commands::{ExitStatus, SharedState};
 use crate::printer::Printer;
 use crate::settings::ResolverInstallerSettings;
 
@@ -69,6 +76,11 @@ pub(crate) async fn run(
     settings: ResolverInstallerSettings,
     invocation_source: ToolRunCommand,
     isolated: bool,
+    project_constraints: bool,
+    detach: bool,
+    cache_days: Option<u64>,
+    cache_max_size: Option<u64>,
+    typosquat_check: TyposquatCheck,
     preview: PreviewMode,
     python_preference: PythonPreference,
     python_downloads: PythonDownloads,
@@ -84,7 +96,14 @@ pub(crate) async fn run(
 
     // treat empty command as `uv tool list`
     let Some(command) = command else {
-        return tool_list(false, PreviewMode::Enabled, cache, printer).await;
+        return tool_list(
+            false,
+            ToolFormat::Text,
+            PreviewMode::Enabled,
+            cache,
+            printer,
+        )
+        .await;
     };
 
     let (target, args) = command.split();
@@ -93,11 +112,29 @@ pub(crate) async fn run(
     };
 
     let (target, from) = if let Some(from) = from {
-        (Cow::Borrowed(target), Cow::Owned(from))
+        // e.g. `--from 'black[d]@24.1.0'`, convert to `black[d]==24.1.0`, as for the target
+        let (_, from) = parse_target(&OsString::from(from))?;
+        (Cow::Borrowed(target), Cow::Owned(from.into_owned()))
     } else {
         parse_target(target)?
     };
 
+    // If requested, read the lockfile of the current project, to constrain the tool's
+    // dependencies to the locked versions.
+    let project = if project_constraints {
+        let project = VirtualProject::discover(&CWD, &DiscoveryOptions::default()).await?;
+        let Some(lock) = lock::read(project.workspace()).await? else {
+            return Err(ProjectError::MissingLockfile.into());
+        };
+        Some((project, lock))
+    } else {
+        None
+    };
+
+    // Hold a shared lock on the cached environments until the tool exits, such that a concurrent
+    // invocation can't prune the environment while it's in use.
+    let environments_lock = cache.lock_environments()?;
+
     // Get or create a compatible environment in which to execute the tool.
     let result = get_or_create_environment(
         &from,
@@ -106,6 +143,8 @@ pub(crate) async fn run(
         python.as_deref(),
         &settings,
         isolated,
+        project.as_ref().map(|(project, lock)| (project, lock)),
+        typosquat_check,
         preview,
         python_preference,
         python_downloads,
@@ -125,7 +164,7 @@ pub(crate) async fn run(
             let report =
                 miette::Report::msg(format!("{err}")).context(err.header().with_context("tool"));
             eprint!("{report:?}");
-            return Ok(ExitStatus::Failure);
+            return Ok(ExitStatus::NoSolution);
         }
         Err(err) => return Err(err.into()),
     };
@@ -134,7 +173,7 @@ pub(crate) async fn run(
     let executable = target;
 
     // Construct the command
-    let mut process = Command::new(executable.as_ref());
+    let mut process = std::process::Command::new(executable.as_ref());
     process.args(args);
 
     // Construct the `PATH` environment variable.
@@ -152,11 +191,12 @@ pub(crate) async fn run(
     // Standard input, output, and error streams are all inherited
     // TODO(zanieb): Throw a nicer error message if the command is not found
     let space = if args.is_empty() { "" } else { " " };
-    debug!(
-        "Running `{}{space}{}`",
+    let display = format!(
+        "{}{space}{}",
         executable.to_string_lossy(),
         args.iter().map(|arg| arg.to_string_lossy()).join(" ")
     );
+    debug!("Running `{display}`");
 
     let site_packages = SitePackages::from_environment(&environment)?;
 
@@ -170,6 +210,12 @@ pub(crate) async fn run(
         invocation_source,
     );
 
+    // If requested, run the command in the background, rather than waiting for it to complete.
+    if detach {
+        return spawn_detached(process, &executable.to_string_lossy(), display, printer);
+    }
+
+    let mut process = Command::from(process);
     let mut handle = match process.spawn() {
         Ok(handle) => Ok(handle),
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
@@ -213,12 +259,34 @@ pub(crate) async fn run(
     .with_context(|| format!("Failed to spawn: `{}`", executable.to_string_lossy()))?;
 
     // Ignore signals in the parent process, deferring them to the child. This is safe as long as
-    // the command is the last thing that runs in this process; otherwise, we'd need to restore the
-    // signal handlers after the command completes.
+    // the command is the last thing that runs in this process (aside from pruning the cache, which
+    // is brief); otherwise, we'd need to restore the signal handlers after the command completes.
     let _handler = tokio::spawn(async { while tokio::signal::ctrl_c().await.is_ok() {} });
 
     let status = handle.wait().await.context("Child process disappeared")?;
 
+    // Now that the tool has exited, remove any cached environments that exceed the configured
+    // limits, retaining the environment we just used. Pruning is skipped if it ran recently, or
+    // if any cached environments are in use by other invocations.
+    drop(environments_lock);
+    if cache_days.is_some() || cache_max_size.is_some() {
+        let max_age = cache_days.map(|days| Duration::from_secs(days * 24 * 60 * 60));
+        match cache.prune_environments_periodically(
+            max_age,
+            cache_max_size,
+            Some(environment.root()),
+        ) {
+            Ok(Some(summary)) if summary.num_dirs > 0 || summary.num_files > 0 => {
+                debug!(
+                    "Removed {} files and {} directories from cached environments",
+                    summary.num_files, summary.num_dirs
+                );
+            }
+            Ok(_) => {}
+            Err(err) => warn!("Failed to prune cached environments: {err}"),
+        }
+    }
+
     // Exit based on the result of the command
     // TODO(zanieb): Do we want to exit with the code of the child process? Probably.
     if status.success() {
@@ -302,6 +370,13 @@ fn parse_target(target: &OsString) -> anyhow::Result<(Cow<OsString>, Cow<str>)>
 
     // e.g. uv, no special handling
     let Some((name, version)) = target_str.split_once('@') else {
+        // e.g. `black[d]`, strip the extras from the command
+        if let Some(command) = strip_extras(target_str) {
+            return Ok((
+                Cow::Owned(OsString::from(command)),
+                Cow::Borrowed(target_str),
+            ));
+        }
         return Ok((Cow::Borrowed(target), Cow::Borrowed(target_str)));
     };
 
@@ -311,8 +386,11 @@ fn parse_target(target: &OsString) -> anyhow::Result<(Cow<OsString>, Cow<str>)>
         return Ok((Cow::Borrowed(target), Cow::Borrowed(target_str)));
     }
 
+    // e.g. `black[d]@24.1.0`, strip the extras from the command
+    let command = strip_extras(name).unwrap_or(name);
+
     // e.g. ignore `git+https://github.com/uv/uv.git@main`
-    if PackageName::from_str(name).is_err() {
+    if PackageName::from_str(command).is_err() {
         debug!("Ignoring non-package name `{name}` in command");
         return Ok((Cow::Borrowed(target), Cow::Borrowed(target_str)));
     }
@@ -320,7 +398,7 @@ fn parse_target(target: &OsString) -> anyhow::Result<(Cow<OsString>, Cow<str>)>
     // e.g. `uv@0.1.0`, convert to `uv==0.1.0`
     if let Ok(version) = Version::from_str(version) {
         return Ok((
-            Cow::Owned(OsString::from(name)),
+            Cow::Owned(OsString::from(command)),
             Cow::Owned(format!("{name}=={version}")),
         ));
     }
@@ -330,10 +408,24 @@ fn parse_target(target: &OsString) -> anyhow::Result<(Cow<OsString>, Cow<str>)>
     Ok((Cow::Borrowed(target), Cow::Borrowed(target_str)))
 }
 
+/// Strip the extras from a package name with extras (e.g., `black[d]` to `black`), returning
+/// `None` if the target isn't a package name with extras.
+fn strip_extras(target: &str) -> Option<&str> {
+    let (name, extras) = target.strip_suffix(']')?.split_once('[')?;
+    PackageName::from_str(name).ok()?;
+    extras
+        .split(',')
+        .all(|extra| ExtraName::from_str(extra.trim()).is_ok())
+        .then_some(name)
+}
+
 /// Get or create a [`PythonEnvironment`] in which to run the specified tools.
 ///
 /// If the target tool is already installed in a compatible environment, returns that
 /// [`PythonEnvironment`]. Otherwise, gets or creates a [`CachedEnvironment`].
+///
+/// If a project and its lockfile are provided, any dependencies that the tool shares with the
+/// project are constrained to their locked versions.
 async fn get_or_create_environment(
     from: &str,
     with: &[RequirementsSource],
@@ -341,6 +433,8 @@ async fn get_or_create_environment(
     python: Option<&str>,
     settings: &ResolverInstallerSettings,
     isolated: bool,
+    project: Option<(&VirtualProject, &Lock)>,
+    typosquat_check: TyposquatCheck,
     preview: PreviewMode,
     python_preference: PythonPreference,
     python_downloads: PythonDownloads,
@@ -423,19 +517,45 @@ async fn get_or_create_environment(
         requirements
     };
 
+    // Constrain the dependencies shared with the project to their locked versions, omitting the
+    // requested packages themselves.
+    let constraints = if let Some((project, lock)) = project {
+        let groups = project.workspace().groups().into_iter().collect::<Vec<_>>();
+        let constraints = lock
+            .to_constraints(
+                project,
+                interpreter.markers(),
+                &ExtrasSpecification::All,
+                &groups,
+            )
+            .into_iter()
+            .filter(|constraint| {
+                requirements
+                    .iter()
+                    .all(|requirement| requirement.name != constraint.name)
+            })
+            .collect::<Vec<_>>();
+        debug!(
+            "Constraining {} package(s) to the versions in the project lockfile",
+            constraints.len()
+        );
+        constraints
+    } else {
+        Vec::new()
+    };
+
     // Check if the tool is already installed in a compatible environment.
     if !isolated && settings.reinstall.is_none() && settings.upgrade.is_none() {
         let installed_tools = InstalledTools::from_settings()?.init()?;
         let _lock = installed_tools.acquire_lock()?;
 
-        let existing_environment =
-            installed_tools
-                .get_environment(&from.name, cache)?
-                .filter(|environment| {
-                    python_request.as_ref().map_or(true, |python_request| {
-                        python_request.satisfied(environment.interpreter(), cache)
-                    })
-                });
+        let existing_environment = installed_tools
+            .get_environment(&ToolName::from(from.name.clone()), cache)?
+            .filter(|environment| {
+                python_request.as_ref().map_or(true, |python_request| {
+                    python_request.satisfied(environment.interpreter(), cache)
+                })
+            });
         if let Some(environment) = existing_environment {
             // Check if the installed packages meet the requirements.
             let site_packages = SitePackages::from_environment(&environment)?;
@@ -445,7 +565,6 @@ async fn get_or_create_environment(
                 .cloned()
                 .map(UnresolvedRequirementSpecification::from)
                 .collect::<Vec<_>>();
-            let constraints = [];
 
             if matches!(
                 site_packages.satisfies(&requirements, &constraints),
@@ -457,21 +576,33 @@ async fn get_or_create_environment(
         }
     }
 
+    // Before installing anything, check the registry requirements for names that resemble popular
+    // packages.
+    check_typosquats(
+        requirements
+            .iter()
+            .filter(|requirement| matches!(requirement.source, RequirementSource::Registry { .. }))
+            .map(|requirement| &requirement.name),
+        typosquat_check,
+        cache,
+    )?;
+
     // Create a `RequirementsSpecification` from the resolved requirements, to avoid re-resolving.
     let spec = RequirementsSpecification {
         requirements: requirements
             .into_iter()
             .map(UnresolvedRequirementSpecification::from)
             .collect(),
+        constraints,
         ..spec
     };
 
     // TODO(zanieb): When implementing project-level tools, discover the project and check if it has the tool.
     // TODO(zanieb): Determine if we should layer on top of the project environment if it is present.
 
-    let environment = CachedEnvironment::get_or_create(
-        spec,
-        interpreter,
+    let result = CachedEnvironment::get_or_create(
+        spec.clone(),
+        interpreter.clone(),
         settings,
         &state,
         if show_resolution {
@@ -480,7 +611,7 @@ async fn get_or_create_environment(
             Box::new(SummaryResolveLogger)
         },
         if show_resolution {
-            Box::new(DefaultInstallLogger)
+            Box::new(DefaultInstallLogger::default())
         } else {
             Box::new(SummaryInstallLogger)
         },
@@ -491,7 +622,42 @@ async fn get_or_create_environment(
         cache,
         printer,
     )
-    .await?;
+    .await;
+
+    let environment = match result {
+        Ok(environment) => environment,
+        Err(err) => {
+            // When offline, fall back to the newest version of the tool in the cache.
+            let Some(spec) = offline_fallback(&from, &spec, connectivity, &interpreter, cache)
+            else {
+                return Err(err);
+            };
+            debug!("Failed to create an environment for `{from}` offline: {err}");
+            CachedEnvironment::get_or_create(
+                spec,
+                interpreter,
+                settings,
+                &state,
+                if show_resolution {
+                    Box::new(DefaultResolveLogger)
+                } else {
+                    Box::new(SummaryResolveLogger)
+                },
+                if show_resolution {
+                    Box::new(DefaultInstallLogger::default())
+                } else {
+                    Box::new(SummaryInstallLogger)
+                },
+                preview,
+                connectivity,
+                concurrency,
+                native_tls,
+                cache,
+                printer,
+            )
+            .await?
+        }
+    };
 
     Ok((from, environment.into()))
 }
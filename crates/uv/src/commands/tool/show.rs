@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use uv_cli::ToolShowFormat;
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_settings::ToolOptions;
+use uv_tool::{EntrypointSelection, InstalledTools, Tool, ToolName};
+use uv_warnings::warn_user_once;
+
+use crate::commands::tool::common::ToolExecutable;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// The receipt of an installed tool, for use in machine-readable output.
+#[derive(Debug, Serialize)]
+struct ReceiptSummary<'a> {
+    name: String,
+    /// The path to the tool environment.
+    path: String,
+    /// The path to the `uv-receipt.toml` file.
+    receipt: String,
+    requirements: Vec<String>,
+    constraints: Vec<String>,
+    overrides: Vec<String>,
+    /// The Python request with which the tool was installed (e.g., `3.12`), if any.
+    python: Option<&'a str>,
+    selection: &'a EntrypointSelection,
+    /// The environment variables set by the tool's executables.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    env: &'a BTreeMap<String, String>,
+    options: &'a ToolOptions,
+    executables: Vec<ToolExecutable>,
+}
+
+/// Show the receipt of an installed tool.
+pub(crate) fn show(
+    name: &ToolName,
+    format: ToolShowFormat,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv tool show` is experimental and may change without warning");
+    }
+
+    let installed_tools = InstalledTools::from_settings()?;
+    let _lock = match installed_tools.acquire_lock() {
+        Ok(lock) => lock,
+        Err(uv_tool::Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+            bail!("`{name}` is not installed");
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let tool = match installed_tools.get_tool_receipt(name) {
+        Ok(Some(tool)) => tool,
+        Ok(None) => bail!("`{name}` is not installed"),
+        Err(err) => return Err(err.into()),
+    };
+
+    match format {
+        ToolShowFormat::Text => write_text(name, &tool, &installed_tools, printer)?,
+        ToolShowFormat::Json => {
+            let path = installed_tools.tool_dir(name);
+            let receipt = ReceiptSummary {
+                name: name.to_string(),
+                path: path.simplified_display().to_string(),
+                receipt: path
+                    .join("uv-receipt.toml")
+                    .simplified_display()
+                    .to_string(),
+                requirements: tool
+                    .requirements()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                constraints: tool.constraints().iter().map(ToString::to_string).collect(),
+                overrides: tool.overrides().iter().map(ToString::to_string).collect(),
+                python: tool.python().as_deref(),
+                selection: tool.selection(),
+                env: tool.env(),
+                options: tool.options(),
+                executables: tool
+                    .entrypoints()
+                    .iter()
+                    .map(|entrypoint| ToolExecutable {
+                        name: entrypoint.name.clone(),
+                        path: entrypoint.install_path.simplified_display().to_string(),
+                    })
+                    .collect(),
+            };
+            let output = serde_json::to_string(&receipt)?;
+            writeln!(printer.stdout(), "{output}")?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Write the receipt of a tool in a human-readable format.
+fn write_text(
+    name: &ToolName,
+    tool: &Tool,
+    installed_tools: &InstalledTools,
+    printer: Printer,
+) -> Result<()> {
+    let path = installed_tools.tool_dir(name);
+    writeln!(printer.stdout(), "{}", name.to_string().bold())?;
+    writeln!(
+        printer.stdout(),
+        "Environment: {}",
+        path.simplified_display().cyan()
+    )?;
+    writeln!(
+        printer.stdout(),
+        "Receipt: {}",
+        path.join("uv-receipt.toml").simplified_display().cyan()
+    )?;
+    if let Some(python) = tool.python() {
+        writeln!(printer.stdout(), "Python: {python}")?;
+    }
+
+    for (label, requirements) in [
+        ("Requirements", tool.requirements()),
+        ("Constraints", tool.constraints()),
+        ("Overrides", tool.overrides()),
+    ] {
+        if requirements.is_empty() {
+            continue;
+        }
+        writeln!(printer.stdout(), "{label}:")?;
+        for requirement in requirements {
+            writeln!(printer.stdout(), "- {requirement}")?;
+        }
+    }
+
+    // Display any settings that differ from the defaults, in the same form as the receipt.
+    let options = toml::to_string(tool.options())?;
+    if !options.trim().is_empty() {
+        writeln!(printer.stdout(), "Options:")?;
+        for line in options.lines().filter(|line| !line.trim().is_empty()) {
+            writeln!(printer.stdout(), "- {line}")?;
+        }
+    }
+
+    let selection = tool.selection();
+    if !selection.include.is_empty() {
+        writeln!(
+            printer.stdout(),
+            "Included executables: {}",
+            selection.include.join(", ")
+        )?;
+    }
+    if !selection.exclude.is_empty() {
+        writeln!(
+            printer.stdout(),
+            "Excluded executables: {}",
+            selection.exclude.join(", ")
+        )?;
+    }
+    if selection.include_deps {
+        writeln!(printer.stdout(), "Executables from dependencies: included")?;
+    }
+
+    if !tool.env().is_empty() {
+        writeln!(printer.stdout(), "Environment variables:")?;
+        for (key, value) in tool.env() {
+            writeln!(printer.stdout(), "- {key}={value}")?;
+        }
+    }
+
+    writeln!(printer.stdout(), "Executables:")?;
+    for entrypoint in tool.entrypoints() {
+        writeln!(
+            printer.stdout(),
+            "- {} ({})",
+            entrypoint.name,
+            entrypoint.install_path.simplified_display().cyan()
+        )?;
+    }
+
+    Ok(())
+}
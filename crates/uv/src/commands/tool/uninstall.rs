@@ -7,8 +7,7 @@ use tracing::debug;
 
 use uv_configuration::PreviewMode;
 use uv_fs::Simplified;
-use uv_normalize::PackageName;
-use uv_tool::{InstalledTools, Tool, ToolEntrypoint};
+use uv_tool::{InstalledTools, Tool, ToolEntrypoint, ToolName};
 use uv_warnings::warn_user_once;
 
 use crate::commands::ExitStatus;
@@ -16,7 +15,8 @@ use crate::printer::Printer;
 
 /// Uninstall a tool.
 pub(crate) async fn uninstall(
-    name: Option<PackageName>,
+    name: Option<ToolName>,
+    dry_run: bool,
     preview: PreviewMode,
     printer: Printer,
 ) -> Result<ExitStatus> {
@@ -24,6 +24,10 @@ pub(crate) async fn uninstall(
         warn_user_once!("`uv tool uninstall` is experimental and may change without warning");
     }
 
+    if dry_run {
+        return report(name, printer);
+    }
+
     let installed_tools = InstalledTools::from_settings()?.init()?;
     let _lock = match installed_tools.acquire_lock() {
         Ok(lock) => lock,
@@ -110,9 +114,71 @@ pub(crate) async fn uninstall(
     Ok(ExitStatus::Success)
 }
 
+/// Report the environments and executables that would be removed by uninstalling a tool, or
+/// all tools.
+fn report(name: Option<ToolName>, printer: Printer) -> Result<ExitStatus> {
+    let installed_tools = InstalledTools::from_settings()?;
+    let _lock = match installed_tools.acquire_lock() {
+        Ok(lock) => lock,
+        Err(uv_tool::Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+            if let Some(name) = name {
+                bail!("`{name}` is not installed");
+            }
+            writeln!(printer.stderr(), "Nothing to uninstall")?;
+            return Ok(ExitStatus::Success);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let tools = if let Some(name) = name {
+        let receipt = installed_tools.get_tool_receipt(&name);
+        if matches!(receipt, Ok(None)) && !installed_tools.tool_dir(&name).is_dir() {
+            bail!("`{name}` is not installed");
+        }
+        vec![(name, receipt)]
+    } else {
+        installed_tools
+            .tools()?
+            .into_iter()
+            .map(|(name, receipt)| (name, receipt.map(Some)))
+            .collect()
+    };
+
+    if tools.is_empty() {
+        writeln!(printer.stderr(), "Nothing to uninstall")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for (name, receipt) in tools {
+        let Ok(Some(receipt)) = receipt else {
+            writeln!(
+                printer.stderr(),
+                "Would remove dangling environment for `{name}`: {}",
+                installed_tools.tool_dir(&name).user_display().cyan()
+            )?;
+            continue;
+        };
+        writeln!(
+            printer.stderr(),
+            "Would remove environment for `{name}`: {}",
+            installed_tools.tool_dir(&name).user_display().cyan()
+        )?;
+        for entrypoint in receipt.entrypoints() {
+            writeln!(
+                printer.stderr(),
+                "Would remove executable `{}`: {}",
+                entrypoint.name.bold(),
+                entrypoint.install_path.user_display().cyan()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
 /// Uninstall a tool.
 async fn uninstall_tool(
-    name: &PackageName,
+    name: &ToolName,
     receipt: &Tool,
     tools: &InstalledTools,
 ) -> Result<Vec<ToolEntrypoint>> {
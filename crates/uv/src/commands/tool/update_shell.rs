@@ -17,7 +17,13 @@ use crate::commands::ExitStatus;
 use crate::printer::Printer;
 
 /// Ensure that the executable directory is in PATH.
-pub(crate) async fn update_shell(preview: PreviewMode, printer: Printer) -> Result<ExitStatus> {
+///
+/// If `dry_run` is set, the changes that would be made are reported, but not applied.
+pub(crate) async fn update_shell(
+    dry_run: bool,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
     if preview.is_disabled() {
         warn_user_once!("`uv tool update-shell` is experimental and may change without warning");
     }
@@ -30,7 +36,21 @@ pub(crate) async fn update_shell(preview: PreviewMode, printer: Printer) -> Resu
 
     #[cfg(windows)]
     {
-        if uv_shell::windows::prepend_path(&executable_directory)? {
+        if dry_run {
+            if uv_shell::windows::contains_path(&executable_directory)? {
+                writeln!(
+                    printer.stderr(),
+                    "Executable directory {} is already in PATH",
+                    executable_directory.simplified_display().cyan()
+                )?;
+            } else {
+                writeln!(
+                    printer.stderr(),
+                    "Would update PATH to include executable directory {}",
+                    executable_directory.simplified_display().cyan()
+                )?;
+            }
+        } else if uv_shell::windows::prepend_path(&executable_directory)? {
             writeln!(
                 printer.stderr(),
                 "Updated PATH to include executable directory {}",
@@ -86,6 +106,16 @@ pub(crate) async fn update_shell(preview: PreviewMode, printer: Printer) -> Resu
                         continue;
                     }
 
+                    if dry_run {
+                        writeln!(
+                            printer.stderr(),
+                            "Would update configuration file: {}",
+                            file.simplified_display().cyan()
+                        )?;
+                        updated = true;
+                        continue;
+                    }
+
                     // Append the command to the file.
                     fs_err::tokio::OpenOptions::new()
                         .create(true)
@@ -104,6 +134,16 @@ pub(crate) async fn update_shell(preview: PreviewMode, printer: Printer) -> Resu
                     updated = true;
                 }
                 Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    if dry_run {
+                        writeln!(
+                            printer.stderr(),
+                            "Would create configuration file: {}",
+                            file.simplified_display().cyan()
+                        )?;
+                        updated = true;
+                        continue;
+                    }
+
                     // Ensure that the directory containing the file exists.
                     if let Some(parent) = file.parent() {
                         fs_err::tokio::create_dir_all(&parent).await?;
@@ -133,7 +173,15 @@ pub(crate) async fn update_shell(preview: PreviewMode, printer: Printer) -> Resu
         }
 
         if updated {
-            writeln!(printer.stderr(), "Restart your shell to apply changes")?;
+            if dry_run {
+                writeln!(
+                    printer.stderr(),
+                    "Would add the following to each file: {}",
+                    command.green()
+                )?;
+            } else {
+                writeln!(printer.stderr(), "Restart your shell to apply changes")?;
+            }
             Ok(ExitStatus::Success)
         } else {
             Err(anyhow::anyhow!("The executable directory {} is not in PATH, but the {shell} configuration files are already up-to-date", executable_directory.simplified_display().cyan()))
@@ -1,31 +1,58 @@
 use std::{collections::BTreeSet, fmt::Write};
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use futures::StreamExt;
+use itertools::Itertools;
 use owo_colors::OwoColorize;
 use tracing::debug;
 
 use crate::commands::pip::loggers::{DefaultInstallLogger, DefaultResolveLogger};
-use crate::commands::project::update_environment;
-use crate::commands::tool::common::{remove_entrypoints, InstallAction};
+use crate::commands::project::{
+    resolve_environment, resolve_names, sync_environment, update_environment,
+};
+use crate::commands::reporters::PythonDownloadReporter;
+use crate::commands::tool::common::{
+    check_executables, ensure_tool_lock, read_tool_lock, remove_entrypoints, write_summaries,
+    write_tool_lock, write_upgrade_reports, InstallAction, ToolSummary, ToolUpgradeReport,
+};
 use crate::commands::{tool::common::install_executables, ExitStatus, SharedState};
 use crate::printer::Printer;
 use crate::settings::ResolverInstallerSettings;
-use uv_cache::Cache;
-use uv_client::Connectivity;
-use uv_configuration::{Concurrency, PreviewMode};
+use distribution_types::UnresolvedRequirementSpecification;
+use pep440_rs::Version;
+use pypi_types::{Requirement, RequirementSource};
+use uv_cache::{Cache, Refresh};
+use uv_cli::ToolFormat;
+use uv_client::{BaseClientBuilder, Connectivity};
+use uv_configuration::{Concurrency, PreviewMode, Reinstall};
+use uv_fs::Simplified;
 use uv_normalize::PackageName;
-use uv_requirements::RequirementsSpecification;
+use uv_python::{
+    EnvironmentPreference, Interpreter, PythonDownloads, PythonInstallation, PythonPreference,
+    PythonRequest,
+};
+use uv_requirements::{RequirementsSource, RequirementsSpecification};
+use uv_resolver::Lock;
 use uv_settings::{Combine, ResolverInstallerOptions, ToolOptions};
-use uv_tool::InstalledTools;
-use uv_warnings::warn_user_once;
+use uv_tool::{InstalledTools, ToolName};
+use uv_warnings::{warn_user, warn_user_once};
 
-/// Upgrade a tool.
+/// Upgrade a tool, or all installed tools.
 pub(crate) async fn upgrade(
-    name: Option<PackageName>,
+    name: Option<ToolName>,
+    with: &[RequirementsSource],
+    without: &[PackageName],
+    frozen: bool,
+    dry_run: bool,
+    python: Option<String>,
+    format: ToolFormat,
+    python_preference: PythonPreference,
+    python_downloads: PythonDownloads,
     connectivity: Connectivity,
     args: ResolverInstallerOptions,
     filesystem: ResolverInstallerOptions,
     concurrency: Concurrency,
+    concurrent_upgrades: usize,
     native_tls: bool,
     cache: &Cache,
     preview: PreviewMode,
@@ -41,7 +68,8 @@ pub(crate) async fn upgrade(
     let installed_tools = InstalledTools::from_settings()?.init()?;
     let _lock = installed_tools.acquire_lock()?;
 
-    let names: BTreeSet<PackageName> =
+    let all = name.is_none();
+    let names: BTreeSet<ToolName> =
         name.map(|name| BTreeSet::from_iter([name]))
             .unwrap_or_else(|| {
                 installed_tools
@@ -54,81 +82,392 @@ pub(crate) async fn upgrade(
 
     if names.is_empty() {
         writeln!(printer.stderr(), "Nothing to upgrade")?;
+        if dry_run {
+            write_upgrade_reports(&[], format, printer)?;
+        } else {
+            write_summaries(&[], format, printer)?;
+        }
         return Ok(ExitStatus::Success);
     }
 
-    for name in names {
-        debug!("Upgrading tool: `{name}`");
+    // Read the `--with` requirements.
+    let client_builder = BaseClientBuilder::new()
+        .connectivity(connectivity)
+        .native_tls(native_tls);
+    let spec = RequirementsSpecification::from_simple_sources(with, &client_builder).await?;
 
-        // Ensure the tool is installed.
-        let existing_tool_receipt = match installed_tools.get_tool_receipt(&name) {
-            Ok(Some(receipt)) => receipt,
-            Ok(None) => {
-                let install_command = format!("uv tool install {name}");
+    // If a Python request was provided, find (or download) a matching interpreter, on which the
+    // tool environments are rebuilt.
+    let interpreter = if let Some(python) = python.as_deref() {
+        let reporter = PythonDownloadReporter::single(printer);
+        let interpreter = PythonInstallation::find_or_download(
+            Some(PythonRequest::parse(python)),
+            EnvironmentPreference::OnlySystem,
+            python_preference,
+            python_downloads,
+            &client_builder,
+            cache,
+            Some(&reporter),
+        )
+        .await?
+        .into_interpreter();
+        Some(interpreter)
+    } else {
+        None
+    };
+
+    // When upgrading multiple tools concurrently, omit the output of each upgrade, which would
+    // otherwise be interleaved; instead, the outcome for each tool is summarized.
+    let concurrent = names.len() > 1 && concurrent_upgrades > 1;
+    let tool_printer = if concurrent { Printer::Quiet } else { printer };
+
+    // Upgrade the tools concurrently, such that a failure to upgrade one tool doesn't prevent the
+    // others from being upgraded.
+    let mut outcomes = futures::stream::iter(names)
+        .map(|name| {
+            let installed_tools = &installed_tools;
+            let state = &state;
+            let with = spec.requirements.as_slice();
+            let args = &args;
+            let filesystem = &filesystem;
+            let python = python.as_deref().zip(interpreter.as_ref());
+            async move {
+                debug!("Upgrading tool: `{name}`");
+                let outcome = upgrade_tool(
+                    &name,
+                    installed_tools,
+                    with,
+                    without,
+                    frozen,
+                    dry_run,
+                    python,
+                    args,
+                    filesystem,
+                    state,
+                    connectivity,
+                    concurrency,
+                    native_tls,
+                    cache,
+                    preview,
+                    tool_printer,
+                )
+                .await;
+                (name, outcome)
+            }
+        })
+        .buffered(concurrent_upgrades);
+
+    // Report the outcome for each tool as it completes, in order.
+    let mut results = Vec::new();
+    while let Some((name, outcome)) = outcomes.next().await {
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            // If a single tool was requested, propagate the error as-is.
+            Err(err) if !all => return Err(err),
+            Err(err) => {
+                let mut reason = format!(
+                    "{}: Failed to upgrade `{}`",
+                    "error".red().bold(),
+                    name.cyan()
+                );
+                for err in err.chain() {
+                    write!(reason, "\n  {}: {}", "Caused by".red().bold(), err)?;
+                }
+                UpgradeOutcome::Failed { reason }
+            }
+        };
+        match &outcome {
+            UpgradeOutcome::Failed { reason } => {
+                writeln!(printer.stderr(), "{reason}")?;
+            }
+            UpgradeOutcome::Upgraded { from, to } if dry_run => {
                 writeln!(
                     printer.stderr(),
-                    "`{}` is not installed; run `{}` to install",
+                    "Would upgrade `{}` from {} to {}",
                     name.cyan(),
-                    install_command.green()
+                    format!("v{from}").bold(),
+                    format!("v{to}").bold()
                 )?;
-                return Ok(ExitStatus::Failure);
             }
-            Err(_) => {
-                let install_command = format!("uv tool install --force {name}");
+            UpgradeOutcome::Unchanged { version } if dry_run => {
                 writeln!(
                     printer.stderr(),
-                    "`{}` is missing a valid receipt; run `{}` to reinstall",
+                    "`{}` is already up-to-date ({})",
                     name.cyan(),
-                    install_command.green()
+                    format!("v{version}").bold()
                 )?;
-                return Ok(ExitStatus::Failure);
             }
-        };
+            UpgradeOutcome::Upgraded { .. } | UpgradeOutcome::Unchanged { .. } => {}
+        }
+        results.push((name, outcome));
+    }
 
-        let existing_environment = match installed_tools.get_environment(&name, cache) {
-            Ok(Some(environment)) => environment,
-            Ok(None) => {
-                let install_command = format!("uv tool install {name}");
-                writeln!(
+    // When upgrading all tools, summarize the outcome for each.
+    if all && !dry_run {
+        writeln!(printer.stderr())?;
+        writeln!(printer.stderr(), "{}", "Summary:".bold())?;
+        for (name, outcome) in &results {
+            match outcome {
+                UpgradeOutcome::Upgraded { from, to } => writeln!(
                     printer.stderr(),
+                    "  {}: {} (v{from} -> v{to})",
+                    name.cyan(),
+                    "upgraded".green()
+                )?,
+                UpgradeOutcome::Unchanged { version } => writeln!(
+                    printer.stderr(),
+                    "  {}: {} (v{version})",
+                    name.cyan(),
+                    "unchanged".dimmed()
+                )?,
+                UpgradeOutcome::Failed { .. } => {
+                    writeln!(printer.stderr(), "  {}: {}", name.cyan(), "failed".red())?;
+                }
+            }
+        }
+    }
+
+    if dry_run {
+        let reports = results
+            .iter()
+            .filter_map(|(name, outcome)| match outcome {
+                UpgradeOutcome::Upgraded { from, to } => {
+                    Some(ToolUpgradeReport::new(name, from, to))
+                }
+                UpgradeOutcome::Unchanged { version } => {
+                    Some(ToolUpgradeReport::new(name, version, version))
+                }
+                UpgradeOutcome::Failed { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        write_upgrade_reports(&reports, format, printer)?;
+    } else {
+        let mut summaries = Vec::new();
+        if format != ToolFormat::Text {
+            for (name, outcome) in &results {
+                if !matches!(outcome, UpgradeOutcome::Failed { .. }) {
+                    summaries.push(ToolSummary::read(&installed_tools, name, cache)?);
+                }
+            }
+        }
+        write_summaries(&summaries, format, printer)?;
+    }
+
+    if results
+        .iter()
+        .any(|(_, outcome)| matches!(outcome, UpgradeOutcome::Failed { .. }))
+    {
+        return Ok(ExitStatus::Failure);
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// The outcome of upgrading a single tool.
+#[derive(Debug)]
+enum UpgradeOutcome {
+    /// The tool was upgraded (or, with `--dry-run`, can be upgraded) to a new version.
+    Upgraded { from: Version, to: Version },
+    /// The tool was already up-to-date.
+    Unchanged { version: Version },
+    /// The tool couldn't be upgraded, for the given reason.
+    Failed { reason: String },
+}
+
+/// Upgrade a single tool.
+///
+/// If a Python request is provided (along with the matching interpreter), the tool environment
+/// is rebuilt on that interpreter, and the request is recorded in the receipt.
+#[allow(clippy::fn_params_excessive_bools)]
+async fn upgrade_tool(
+    name: &ToolName,
+    installed_tools: &InstalledTools,
+    with: &[UnresolvedRequirementSpecification],
+    without: &[PackageName],
+    frozen: bool,
+    dry_run: bool,
+    python: Option<(&str, &Interpreter)>,
+    args: &ResolverInstallerOptions,
+    filesystem: &ResolverInstallerOptions,
+    state: &SharedState,
+    connectivity: Connectivity,
+    concurrency: Concurrency,
+    native_tls: bool,
+    cache: &Cache,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<UpgradeOutcome> {
+    // Ensure the tool is installed.
+    let existing_tool_receipt = match installed_tools.get_tool_receipt(name) {
+        Ok(Some(receipt)) => receipt,
+        Ok(None) => {
+            let install_command = format!("uv tool install {name}");
+            return Ok(UpgradeOutcome::Failed {
+                reason: format!(
                     "`{}` is not installed; run `{}` to install",
                     name.cyan(),
                     install_command.green()
-                )?;
-                return Ok(ExitStatus::Failure);
+                ),
+            });
+        }
+        Err(uv_tool::Error::UnsupportedReceiptVersion(..)) => {
+            return Ok(UpgradeOutcome::Failed {
+                reason: format!(
+                    "`{}` was installed by a newer version of uv; upgrade uv to upgrade it",
+                    name.cyan(),
+                ),
+            });
+        }
+        Err(_) => {
+            let install_command = format!("uv tool install --force {name}");
+            return Ok(UpgradeOutcome::Failed {
+                reason: format!(
+                    "`{}` is missing a valid receipt; run `{}` to reinstall",
+                    name.cyan(),
+                    install_command.green()
+                ),
+            });
+        }
+    };
+
+    // Determine the interpreter for the upgraded environment: the requested interpreter, if any,
+    // or the existing environment's interpreter otherwise. Since the environment is rebuilt from
+    // scratch when an interpreter is requested, the existing environment is only required in the
+    // latter case (e.g., a tool whose interpreter was removed can be moved to a new one).
+    let environment;
+    let interpreter = if let Some((_, interpreter)) = python {
+        debug!(
+            "Rebuilding `{name}` with Python {} at: {}",
+            interpreter.python_full_version(),
+            interpreter.sys_executable().user_display()
+        );
+        interpreter
+    } else {
+        environment = match installed_tools.get_environment(name, cache) {
+            Ok(Some(environment)) => environment,
+            Ok(None) => {
+                let install_command = format!("uv tool install {name}");
+                return Ok(UpgradeOutcome::Failed {
+                    reason: format!(
+                        "`{}` is not installed; run `{}` to install",
+                        name.cyan(),
+                        install_command.green()
+                    ),
+                });
+            }
+            Err(uv_tool::Error::UnsupportedReceiptVersion(..)) => {
+                return Ok(UpgradeOutcome::Failed {
+                    reason: format!(
+                        "`{}` was installed by a newer version of uv; upgrade uv to upgrade it",
+                        name.cyan(),
+                    ),
+                });
             }
             Err(_) => {
                 let install_command = format!("uv tool install --force {name}");
-                writeln!(
-                    printer.stderr(),
-                    "`{}` is missing a valid environment; run `{}` to reinstall",
-                    name.cyan(),
-                    install_command.green()
-                )?;
-                return Ok(ExitStatus::Failure);
+                return Ok(UpgradeOutcome::Failed {
+                    reason: format!(
+                        "`{}` is missing a valid environment; run `{}` to reinstall",
+                        name.cyan(),
+                        install_command.green()
+                    ),
+                });
             }
         };
+        environment.interpreter()
+    };
+
+    // Resolve the appropriate settings, preferring: CLI > receipt > user.
+    let options = args.clone().combine(
+        ResolverInstallerOptions::from(existing_tool_receipt.options().clone())
+            .combine(filesystem.clone()),
+    );
+    let mut settings = ResolverInstallerSettings::from(options.clone());
+
+    // Apply any requested changes to the tool's extra requirements.
+    let mut requirements = existing_tool_receipt.requirements().to_vec();
+    for package in without {
+        if package == name.package() {
+            bail!("Cannot remove `{name}` from its own tool environment");
+        }
+        let len = requirements.len();
+        requirements.retain(|requirement| requirement.name != *package);
+        if requirements.len() == len {
+            warn_user!("`{package}` is not an extra requirement of `{name}`");
+        }
+    }
+    if !with.is_empty() {
+        let with = resolve_names(
+            with.to_vec(),
+            interpreter,
+            &settings,
+            state,
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+        for requirement in with {
+            if requirement.name == *name.package() {
+                bail!(
+                    "Cannot add `{name}` to its own tool environment; use `uv tool install` to change its requirement"
+                );
+            }
+            add_requirement(&mut requirements, requirement);
+        }
+    }
 
-        // Resolve the appropriate settings, preferring: CLI > receipt > user.
-        let options = args.clone().combine(
-            ResolverInstallerOptions::from(existing_tool_receipt.options().clone())
-                .combine(filesystem.clone()),
+    // Direct URL requirements aren't versioned by an index, so an archive at the same URL may
+    // have changed without a change in version. Reinstall them, such that the latest archive is
+    // installed. (Git requirements are re-fetched, and reinstalled if their commit has changed.)
+    let urls = requirements
+        .iter()
+        .filter(|requirement| matches!(requirement.source, RequirementSource::Url { .. }))
+        .map(|requirement| requirement.name.clone())
+        .collect::<Vec<_>>();
+    let cache = &if !frozen && !urls.is_empty() {
+        debug!(
+            "Reinstalling direct URL requirements: {}",
+            urls.iter().map(ToString::to_string).join(", ")
         );
-        let settings = ResolverInstallerSettings::from(options.clone());
+        settings.reinstall = match settings.reinstall {
+            Reinstall::None => Reinstall::Packages(urls),
+            Reinstall::All => Reinstall::All,
+            Reinstall::Packages(mut packages) => {
+                packages.extend(urls);
+                Reinstall::Packages(packages)
+            }
+        };
 
-        // Resolve the requirements.
-        let requirements = existing_tool_receipt.requirements();
-        let spec = RequirementsSpecification::from_requirements(requirements.to_vec());
+        // The cache's refresh policy was derived from the original `--reinstall` request, so
+        // extend it to the reinstalled packages, such that their archives are re-fetched rather
+        // than served from the cache.
+        let refresh = cache
+            .refresh()
+            .clone()
+            .combine(Refresh::from(settings.reinstall.clone()));
+        cache.clone().with_refresh(refresh)
+    } else {
+        cache.clone()
+    };
 
-        // TODO(zanieb): Build the environment in the cache directory then copy into the tool
-        // directory.
-        let environment = update_environment(
-            existing_environment,
+    // If `--dry-run` was provided, resolve the requirements and report the latest version,
+    // without modifying the environment.
+    if dry_run {
+        let spec = RequirementsSpecification::from_constraints(
+            requirements,
+            existing_tool_receipt.constraints().to_vec(),
+            existing_tool_receipt.overrides().to_vec(),
+        );
+        let resolution = resolve_environment(
+            interpreter,
             spec,
-            &settings,
-            &state,
+            settings.as_ref().into(),
+            state,
             Box::new(DefaultResolveLogger),
-            Box::new(DefaultInstallLogger),
             preview,
             connectivity,
             concurrency,
@@ -138,22 +477,184 @@ pub(crate) async fn upgrade(
         )
         .await?;
 
-        // At this point, we updated the existing environment, so we should remove any of its
-        // existing executables.
-        remove_entrypoints(&existing_tool_receipt);
-
-        install_executables(
-            &environment,
-            &name,
-            &installed_tools,
-            ToolOptions::from(options),
-            true,
-            existing_tool_receipt.python().to_owned(),
-            requirements.to_vec(),
-            InstallAction::Update,
-            printer,
+        let latest = resolution
+            .version(name.package())
+            .ok_or_else(|| anyhow!("Failed to find `{name}` in the resolution"))?
+            .clone();
+        return Ok(match installed_version(installed_tools, name, cache) {
+            Some(version) if version != latest => UpgradeOutcome::Upgraded {
+                from: version,
+                to: latest,
+            },
+            _ => UpgradeOutcome::Unchanged { version: latest },
+        });
+    }
+
+    // If `--frozen` was provided, ensure the tool has a lockfile.
+    let tool_lock = if frozen {
+        let Some(lock) = read_tool_lock(installed_tools, name)? else {
+            let install_command = format!("uv tool install --force {name}");
+            return Ok(UpgradeOutcome::Failed {
+                reason: format!(
+                    "`{}` is missing a lockfile; run `{}` to reinstall",
+                    name.cyan(),
+                    install_command.green()
+                ),
+            });
+        };
+        Some(lock)
+    } else {
+        None
+    };
+
+    // Record the installed version, to determine whether the tool was upgraded.
+    let previous = installed_version(installed_tools, name, cache);
+
+    // Upgrade a clone of the environment, such that the existing installation is restored if the
+    // upgrade fails. If the interpreter is changing, start from an empty environment instead,
+    // since the installed packages may not be compatible.
+    let staged = if python.is_some() {
+        installed_tools.stage_environment(name, interpreter.clone(), cache)?
+    } else {
+        installed_tools.stage_existing_environment(name, cache)?
+    };
+    if let Some(lock) = tool_lock {
+        // Restore the environment from the lockfile, without resolving.
+        let interpreter = staged.environment().interpreter();
+        let resolution = lock.to_resolution_for_requirements(
+            &requirements,
+            &installed_tools.tool_dir(name),
+            interpreter.markers(),
+            interpreter.tags()?,
+            &settings.build_options,
         )?;
+        sync_environment(
+            staged.environment().clone(),
+            &resolution,
+            settings.as_ref().into(),
+            state,
+            Box::new(DefaultInstallLogger::default()),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+    } else {
+        // Resolve the requirements, respecting the constraints and overrides in the receipt.
+        let spec = RequirementsSpecification::from_constraints(
+            requirements.clone(),
+            existing_tool_receipt.constraints().to_vec(),
+            existing_tool_receipt.overrides().to_vec(),
+        );
+        let update = update_environment(
+            staged.environment().clone(),
+            spec.clone(),
+            &settings,
+            state,
+            Box::new(DefaultResolveLogger),
+            Box::new(DefaultInstallLogger::default()),
+            preview,
+            connectivity,
+            concurrency,
+            native_tls,
+            cache,
+            printer,
+        )
+        .await?;
+
+        // If the environment was re-resolved, record the new resolution. Otherwise, ensure that
+        // the environment is locked.
+        if let Some(graph) = update.resolution {
+            write_tool_lock(staged.environment(), &Lock::from_resolution_graph(&graph)?)?;
+        } else {
+            ensure_tool_lock(
+                staged.environment(),
+                spec,
+                &settings,
+                state,
+                preview,
+                connectivity,
+                concurrency,
+                native_tls,
+                cache,
+                printer,
+            )
+            .await?;
+        }
     }
 
-    Ok(ExitStatus::Success)
+    // Validate the executable selection before replacing the existing environment.
+    if !check_executables(
+        staged.environment(),
+        name,
+        existing_tool_receipt.selection(),
+        printer,
+    )? {
+        bail!("The upgraded environment for `{name}` doesn't provide any executables");
+    }
+
+    let environment = installed_tools.commit_environment(staged, cache)?;
+
+    // At this point, we replaced the existing environment, so we should remove any of its
+    // existing executables.
+    remove_entrypoints(&existing_tool_receipt);
+
+    install_executables(
+        &environment,
+        name,
+        installed_tools,
+        ToolOptions::from(options),
+        true,
+        false,
+        python.map_or_else(
+            || existing_tool_receipt.python().to_owned(),
+            |(python, _)| Some(python.to_string()),
+        ),
+        requirements,
+        existing_tool_receipt.constraints().to_vec(),
+        existing_tool_receipt.overrides().to_vec(),
+        existing_tool_receipt.selection().clone(),
+        existing_tool_receipt.env().clone(),
+        InstallAction::Update,
+        printer,
+    )?;
+
+    let version = installed_tools.version(name, cache)?;
+    Ok(match previous {
+        Some(previous) if previous != version => UpgradeOutcome::Upgraded {
+            from: previous,
+            to: version,
+        },
+        _ => UpgradeOutcome::Unchanged { version },
+    })
+}
+
+/// Return the installed version of the tool, if its environment can be read.
+///
+/// The environment may be missing or broken when the tool is being moved to a new interpreter.
+fn installed_version(
+    installed_tools: &InstalledTools,
+    name: &ToolName,
+    cache: &Cache,
+) -> Option<Version> {
+    installed_tools
+        .version(name, cache)
+        .inspect_err(|err| debug!("Failed to read installed version of `{name}`: {err}"))
+        .ok()
+}
+
+/// Add a requirement to the tool's requirements, replacing any existing requirement for the same
+/// package.
+fn add_requirement(requirements: &mut Vec<Requirement>, requirement: Requirement) {
+    if let Some(existing) = requirements
+        .iter_mut()
+        .find(|existing| existing.name == requirement.name)
+    {
+        *existing = requirement;
+    } else {
+        requirements.push(requirement);
+    }
 }
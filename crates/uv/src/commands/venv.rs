@@ -34,6 +34,7 @@ use uv_workspace::{DiscoveryOptions, VirtualProject, WorkspaceError};
 use crate::commands::project::find_requires_python;
 use crate::commands::reporters::PythonDownloadReporter;
 use crate::commands::{pip, ExitStatus, SharedState};
+use crate::environments;
 use crate::printer::Printer;
 
 /// Create a virtual environment.
@@ -230,6 +231,9 @@ async fn venv_impl(
     )
     .map_err(VenvError::Creation)?;
 
+    // Record the environment, such that it can be found by `uv venv list`.
+    environments::register(&venv, None);
+
     // Install seed packages.
     if seed {
         // Extract the interpreter.
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_warnings::warn_user_once;
+
+use crate::commands::ExitStatus;
+use crate::environments::{self, EnvironmentRecord};
+use crate::printer::Printer;
+
+/// A recorded environment, for use in machine-readable output.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct EnvironmentSummary<'a> {
+    #[serde(flatten)]
+    record: &'a EnvironmentRecord,
+    /// Whether the environment still exists.
+    exists: bool,
+    /// Whether the environment was created for a project that no longer exists.
+    orphaned: bool,
+}
+
+/// List the virtual environments created by uv.
+pub(crate) fn venv_list(json: bool, preview: PreviewMode, printer: Printer) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv venv list` is experimental and may change without warning");
+    }
+
+    let environments = environments::read_environments()?;
+
+    if json {
+        let summaries = environments
+            .iter()
+            .map(|record| EnvironmentSummary {
+                record,
+                exists: record.exists(),
+                orphaned: record.is_orphaned(),
+            })
+            .collect::<Vec<_>>();
+        writeln!(
+            printer.stdout(),
+            "{}",
+            serde_json::to_string_pretty(&summaries)?
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    if environments.is_empty() {
+        writeln!(printer.stderr(), "No environments recorded")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    for record in &environments {
+        let status = if !record.exists() {
+            format!(" {}", "(missing)".red())
+        } else if record.is_orphaned() {
+            format!(" {}", "(orphaned)".yellow())
+        } else {
+            String::new()
+        };
+        writeln!(
+            printer.stdout(),
+            "{} ({}){status}",
+            record.path.simplified_display().cyan(),
+            record.python
+        )?;
+        if let Some(project) = &record.project {
+            writeln!(
+                printer.stdout(),
+                "- Project: {}",
+                project.simplified_display()
+            )?;
+        }
+        writeln!(printer.stdout(), "- Command: {}", record.command)?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,79 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_warnings::warn_user_once;
+
+use crate::commands::ExitStatus;
+use crate::environments;
+use crate::printer::Printer;
+
+/// Remove the virtual environments created for projects that no longer exist, along with the
+/// records of any environments that no longer exist.
+///
+/// Environments and projects that merely can't be reached (e.g., as they live on an unmounted
+/// drive) are left untouched.
+pub(crate) fn venv_prune(
+    dry_run: bool,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv venv prune` is experimental and may change without warning");
+    }
+
+    let mut pruned = 0;
+    for record in environments::read_environments()? {
+        if !record.exists() {
+            if !record.is_deleted() {
+                debug!(
+                    "Skipping unavailable virtual environment at: {}",
+                    record.path.user_display()
+                );
+                continue;
+            }
+            if dry_run {
+                writeln!(
+                    printer.stderr(),
+                    "Would remove record of missing virtual environment at: {}",
+                    record.path.user_display().cyan()
+                )?;
+            } else {
+                record.remove()?;
+                writeln!(
+                    printer.stderr(),
+                    "Removed record of missing virtual environment at: {}",
+                    record.path.user_display().cyan()
+                )?;
+            }
+            pruned += 1;
+        } else if record.is_project_deleted() {
+            if dry_run {
+                writeln!(
+                    printer.stderr(),
+                    "Would remove orphaned virtual environment at: {}",
+                    record.path.user_display().cyan()
+                )?;
+            } else {
+                uv_fs::force_remove_dir_all(&record.path)?;
+                record.remove()?;
+                writeln!(
+                    printer.stderr(),
+                    "Removed orphaned virtual environment at: {}",
+                    record.path.user_display().cyan()
+                )?;
+            }
+            pruned += 1;
+        }
+    }
+
+    if pruned == 0 {
+        writeln!(printer.stderr(), "Nothing to prune")?;
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,65 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+
+use uv_configuration::PreviewMode;
+use uv_fs::Simplified;
+use uv_warnings::warn_user_once;
+
+use crate::commands::ExitStatus;
+use crate::environments;
+use crate::printer::Printer;
+
+/// Remove the given virtual environments, along with their records.
+///
+/// Only environments that were recorded by uv can be removed.
+pub(crate) fn venv_remove(
+    paths: &[PathBuf],
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv venv remove` is experimental and may change without warning");
+    }
+
+    let recorded = environments::read_environments()?;
+
+    for path in paths {
+        let path = uv_fs::absolutize_path(path)?.into_owned();
+        let record = recorded.iter().find(|record| record.path == path);
+
+        // Only remove environments that were created by uv, rather than any directory that
+        // happens to contain a `pyvenv.cfg`.
+        let Some(record) = record else {
+            if path.join("pyvenv.cfg").is_file() {
+                bail!(
+                    "The virtual environment at `{}` was not recorded by uv; remove it manually instead",
+                    path.user_display()
+                );
+            }
+            bail!("No virtual environment found at: `{}`", path.user_display());
+        };
+
+        if record.exists() {
+            uv_fs::force_remove_dir_all(&path)?;
+            record.remove()?;
+            writeln!(
+                printer.stderr(),
+                "Removed virtual environment at: {}",
+                path.user_display().cyan()
+            )?;
+        } else {
+            // The environment was already deleted; only its record remains.
+            record.remove()?;
+            writeln!(
+                printer.stderr(),
+                "Removed record of missing virtual environment at: {}",
+                path.user_display().cyan()
+            )?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
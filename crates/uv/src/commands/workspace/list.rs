@@ -0,0 +1,98 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use uv_configuration::PreviewMode;
+use uv_normalize::PackageName;
+use uv_warnings::warn_user_once;
+
+use crate::commands::workspace::discover_graph;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// List the members of the workspace.
+pub(crate) async fn list(
+    affected_by: &[PackageName],
+    json: bool,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv workspace list` is experimental and may change without warning");
+    }
+
+    let (workspace, graph) = discover_graph().await?;
+
+    for name in affected_by {
+        if graph.get(name).is_none() {
+            bail!("`{name}` is not a member of the workspace");
+        }
+    }
+
+    // If requested, restrict the output to the members affected by a change to the given members.
+    let selected = if affected_by.is_empty() {
+        graph
+            .members()
+            .map(|(name, _)| name)
+            .collect::<BTreeSet<_>>()
+    } else {
+        graph.affected_by(affected_by)
+    };
+
+    if json {
+        let members = selected
+            .iter()
+            .filter_map(|name| graph.get(name).map(|dependencies| (*name, dependencies)))
+            .map(|(name, dependencies)| Member {
+                name,
+                path: dependencies.root(),
+                dependencies: dependencies.members(),
+                dev_dependencies: dependencies.dev_members(),
+                dependents: graph.dependents(name),
+                external_dependencies: dependencies.external(),
+                dynamic: dependencies.is_dynamic(),
+            })
+            .collect();
+        let report = Report {
+            root: workspace.install_path(),
+            members,
+            shared_dependencies: graph.shared_dependencies(),
+        };
+        writeln!(
+            printer.stdout(),
+            "{}",
+            serde_json::to_string_pretty(&report)?
+        )?;
+    } else {
+        for name in selected {
+            writeln!(printer.stdout(), "{name}")?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// The JSON representation of the workspace, as emitted by `uv workspace list --json`.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Report<'a> {
+    root: &'a PathBuf,
+    members: Vec<Member<'a>>,
+    shared_dependencies: BTreeMap<&'a PackageName, BTreeSet<&'a PackageName>>,
+}
+
+/// A single workspace member in the JSON output.
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Member<'a> {
+    name: &'a PackageName,
+    path: &'a PathBuf,
+    dependencies: &'a BTreeSet<PackageName>,
+    dev_dependencies: &'a BTreeSet<PackageName>,
+    dependents: BTreeSet<&'a PackageName>,
+    external_dependencies: &'a BTreeSet<PackageName>,
+    dynamic: bool,
+}
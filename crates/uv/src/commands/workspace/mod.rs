@@ -0,0 +1,24 @@
+use anyhow::Result;
+
+use uv_fs::CWD;
+use uv_warnings::warn_user;
+use uv_workspace::{DiscoveryOptions, Workspace, WorkspaceGraph};
+
+pub(crate) mod list;
+pub(crate) mod tree;
+
+/// Discover the workspace containing the current directory, and build its member graph.
+async fn discover_graph() -> Result<(Workspace, WorkspaceGraph)> {
+    let workspace = Workspace::discover(&CWD, &DiscoveryOptions::default()).await?;
+    let graph = WorkspaceGraph::from_workspace(&workspace)?;
+
+    for (name, dependencies) in graph.members() {
+        if dependencies.is_dynamic() {
+            warn_user!(
+                "The dependencies of `{name}` are declared as dynamic and will be omitted from the workspace graph"
+            );
+        }
+    }
+
+    Ok((workspace, graph))
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use anyhow::{bail, Result};
+
+use uv_configuration::PreviewMode;
+use uv_normalize::PackageName;
+use uv_warnings::warn_user_once;
+use uv_workspace::WorkspaceGraph;
+
+use crate::commands::workspace::discover_graph;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Display the dependencies between the members of the workspace.
+pub(crate) async fn tree(
+    package: &[PackageName],
+    invert: bool,
+    external: bool,
+    preview: PreviewMode,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    if preview.is_disabled() {
+        warn_user_once!("`uv workspace tree` is experimental and may change without warning");
+    }
+
+    let (_workspace, graph) = discover_graph().await?;
+
+    for name in package {
+        if graph.get(name).is_none() {
+            bail!("`{name}` is not a member of the workspace");
+        }
+    }
+
+    let display = MemberTree {
+        graph: &graph,
+        invert,
+        external,
+    };
+
+    // By default, start from the members that nothing depends on (or, when inverted, the members
+    // that depend on nothing else in the workspace).
+    let roots = if package.is_empty() {
+        graph
+            .members()
+            .filter(|(name, dependencies)| {
+                if invert {
+                    dependencies.members().is_empty() && dependencies.dev_members().is_empty()
+                } else {
+                    graph.dependents(name).is_empty()
+                }
+            })
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+    } else {
+        package.iter().collect()
+    };
+
+    let mut visited = BTreeSet::new();
+    for (index, root) in roots.into_iter().enumerate() {
+        if index != 0 && !package.is_empty() {
+            writeln!(printer.stdout())?;
+        }
+        let mut path = Vec::new();
+        for line in display.visit(root, false, &mut visited, &mut path) {
+            writeln!(printer.stdout(), "{line}")?;
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// A tree of the dependencies between workspace members.
+struct MemberTree<'graph> {
+    graph: &'graph WorkspaceGraph,
+    /// Whether to display the dependents of each member, rather than its dependencies.
+    invert: bool,
+    /// Whether to display dependencies on packages outside the workspace.
+    external: bool,
+}
+
+impl<'graph> MemberTree<'graph> {
+    /// Perform a depth-first traversal of the given member and its dependencies (or dependents).
+    fn visit(
+        &self,
+        name: &'graph PackageName,
+        dev: bool,
+        visited: &mut BTreeSet<&'graph PackageName>,
+        path: &mut Vec<&'graph PackageName>,
+    ) -> Vec<String> {
+        let mut line = name.to_string();
+        if dev {
+            line.push_str(" (dev)");
+        }
+
+        let Some(dependencies) = self.graph.get(name) else {
+            return vec![line];
+        };
+
+        // The children of this node, along with whether they're a development dependency.
+        let mut children: Vec<(&'graph PackageName, bool)> = if self.invert {
+            self.graph
+                .dependents(name)
+                .into_iter()
+                .map(|dependent| {
+                    let dev = self.graph.get(dependent).is_some_and(|dependent| {
+                        !dependent.members().contains(name)
+                            && dependent.dev_members().contains(name)
+                    });
+                    (dependent, dev)
+                })
+                .collect()
+        } else {
+            dependencies
+                .members()
+                .iter()
+                .map(|member| (member, false))
+                .chain(
+                    dependencies
+                        .dev_members()
+                        .iter()
+                        .filter(|member| !dependencies.members().contains(*member))
+                        .map(|member| (member, true)),
+                )
+                .collect()
+        };
+        children.sort();
+
+        let externals = if self.external && !self.invert {
+            dependencies.external().iter().collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+
+        // Skip the traversal if the member is part of a cycle, or was already displayed.
+        if path.contains(&name) || !visited.insert(name) {
+            return if children.is_empty() && externals.is_empty() {
+                vec![line]
+            } else {
+                vec![format!("{line} (*)")]
+            };
+        }
+
+        let mut lines = vec![line];
+
+        path.push(name);
+        let total = children.len() + externals.len();
+        let mut index = 0;
+        for (child, dev) in children {
+            let child_lines = self.visit(child, dev, visited, path);
+            index += 1;
+            push_prefixed(&mut lines, child_lines, index == total);
+        }
+        for dependency in externals {
+            index += 1;
+            push_prefixed(&mut lines, vec![dependency.to_string()], index == total);
+        }
+        path.pop();
+
+        lines
+    }
+}
+
+/// Append the lines of a subtree, prefixed with the appropriate branch characters.
+fn push_prefixed(lines: &mut Vec<String>, subtree: Vec<String>, last: bool) {
+    let (prefix_top, prefix_rest) = if last {
+        ("└── ", "    ")
+    } else {
+        ("├── ", "│   ")
+    };
+    for (index, line) in subtree.into_iter().enumerate() {
+        let prefix = if index == 0 { prefix_top } else { prefix_rest };
+        lines.push(format!("{prefix}{line}"));
+    }
+}
@@ -0,0 +1,184 @@
+//! The registry of virtual environments created by uv.
+//!
+//! Whenever uv creates a persistent virtual environment (via `uv venv`, or as the environment of a
+//! project), it records the location of the environment, along with its Python interpreter and the
+//! command that created it, in the `environments` directory within the uv data directory. The
+//! records are read back by `uv venv list`, `uv venv remove`, and `uv venv prune`, such that stray
+//! environments can be found and cleaned up.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use cache_key::cache_digest;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use uv_fs::Simplified;
+use uv_python::PythonEnvironment;
+use uv_state::{StateBucket, StateStore};
+
+use crate::logging;
+
+/// A virtual environment that was created by uv.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct EnvironmentRecord {
+    /// The absolute path to the root of the environment.
+    pub(crate) path: PathBuf,
+    /// The Python implementation and version of the environment (e.g., `cpython-3.12.1`).
+    pub(crate) python: String,
+    /// The root of the project for which the environment was created, if any.
+    pub(crate) project: Option<PathBuf>,
+    /// The command that created the environment.
+    pub(crate) command: String,
+    /// The time at which the environment was created, in seconds since the Unix epoch.
+    pub(crate) created: u64,
+}
+
+impl EnvironmentRecord {
+    /// Returns `true` if the environment still exists.
+    pub(crate) fn exists(&self) -> bool {
+        self.path.join("pyvenv.cfg").is_file()
+    }
+
+    /// Returns `true` if the environment was deleted.
+    ///
+    /// Unlike [`EnvironmentRecord::exists`], an environment that can't be reached (e.g., as it
+    /// lives on an unmounted drive) is not considered deleted.
+    pub(crate) fn is_deleted(&self) -> bool {
+        !self.exists() && is_deleted(&self.path)
+    }
+
+    /// Returns `true` if the environment was created for a project that no longer exists.
+    pub(crate) fn is_orphaned(&self) -> bool {
+        self.project
+            .as_ref()
+            .is_some_and(|project| !project.join("pyproject.toml").is_file())
+    }
+
+    /// Returns `true` if the environment was created for a project that was deleted.
+    ///
+    /// Unlike [`EnvironmentRecord::is_orphaned`], a project that can't be reached (e.g., as it
+    /// lives on an unmounted drive) is not considered deleted.
+    pub(crate) fn is_project_deleted(&self) -> bool {
+        self.project.as_ref().is_some_and(|project| {
+            !project.join("pyproject.toml").is_file() && (project.is_dir() || is_deleted(project))
+        })
+    }
+
+    /// Remove the record of the environment.
+    ///
+    /// The environment itself is left untouched.
+    pub(crate) fn remove(&self) -> std::io::Result<()> {
+        forget(&self.path)
+    }
+}
+
+/// Returns `true` if the given path is missing, but its parent directory exists, i.e., the path
+/// was deleted rather than being unavailable.
+fn is_deleted(path: &Path) -> bool {
+    !path.exists() && path.parent().is_some_and(Path::is_dir)
+}
+
+/// Return the directory in which environments are recorded, respecting `UV_ENVIRONMENT_DIR`.
+fn environments_dir() -> std::io::Result<PathBuf> {
+    if let Some(environment_dir) = std::env::var_os("UV_ENVIRONMENT_DIR") {
+        Ok(PathBuf::from(environment_dir))
+    } else {
+        Ok(StateStore::from_settings(None)?.bucket(StateBucket::Environments))
+    }
+}
+
+/// Return the path to the record of the environment at the given (absolute) path.
+fn record_path(dir: &Path, path: &Path) -> PathBuf {
+    dir.join(format!("{}.json", cache_digest(&path)))
+}
+
+/// Record that uv created the given environment, optionally on behalf of a project.
+///
+/// Failures are logged, but otherwise ignored, since the registry is purely informational.
+pub(crate) fn register(environment: &PythonEnvironment, project: Option<&Path>) {
+    if let Err(err) = write_record(environment, project) {
+        debug!(
+            "Failed to record environment at {}: {err}",
+            environment.root().user_display()
+        );
+    }
+}
+
+fn write_record(environment: &PythonEnvironment, project: Option<&Path>) -> anyhow::Result<()> {
+    let dir = environments_dir()?;
+    fs_err::create_dir_all(&dir)?;
+
+    let path = uv_fs::absolutize_path(environment.root())?.into_owned();
+    let project = project
+        .map(uv_fs::absolutize_path)
+        .transpose()?
+        .map(std::borrow::Cow::into_owned);
+    let interpreter = environment.interpreter();
+    let record = EnvironmentRecord {
+        python: format!(
+            "{}-{}",
+            interpreter.implementation_name().to_lowercase(),
+            interpreter.python_version()
+        ),
+        project,
+        // Redact any credentials (e.g., in an `--index-url`), as the record is stored in plaintext.
+        command: std::iter::once("uv".to_string())
+            .chain(logging::redact_args(std::env::args().skip(1)))
+            .collect::<Vec<_>>()
+            .join(" "),
+        created: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+        path,
+    };
+
+    let file = record_path(&dir, &record.path);
+    debug!("Recording environment at: {}", file.user_display());
+    fs_err::write(&file, serde_json::to_string(&record)?)?;
+    Ok(())
+}
+
+/// Remove the record of the environment at the given path, if any.
+pub(crate) fn forget(path: &Path) -> std::io::Result<()> {
+    let path = uv_fs::absolutize_path(path)?;
+    match fs_err::remove_file(record_path(&environments_dir()?, &path)) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Read the records of all environments created by uv, sorted by creation time.
+pub(crate) fn read_environments() -> anyhow::Result<Vec<EnvironmentRecord>> {
+    let dir = environments_dir()?;
+    let entries = match fs_err::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut environments = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path
+            .extension()
+            .map_or(true, |extension| extension != "json")
+        {
+            continue;
+        }
+        match serde_json::from_str::<EnvironmentRecord>(&fs_err::read_to_string(&path)?) {
+            Ok(record) => environments.push(record),
+            Err(err) => {
+                debug!(
+                    "Ignoring invalid environment record at {}: {err}",
+                    path.user_display()
+                );
+            }
+        }
+    }
+    environments.sort_by(|a, b| a.created.cmp(&b.created).then_with(|| a.path.cmp(&b.path)));
+    Ok(environments)
+}
@@ -3,58 +3,55 @@ use std::fmt::Write;
 use std::io::stdout;
 use std::path::PathBuf;
 use std::process::ExitCode;
+use std::str::FromStr;
 
 use anstream::eprintln;
-use anyhow::Result;
-use clap::error::{ContextKind, ContextValue};
+use anyhow::{Context, Result};
+use clap::error::{ContextKind, ContextValue, ErrorKind};
 use clap::{CommandFactory, Parser};
 use owo_colors::OwoColorize;
-use tracing::{debug, instrument};
+use pep440_rs::Version;
+use tracing::{debug, info, instrument};
 
 use settings::PipTreeSettings;
 use uv_cache::{Cache, Refresh, Timestamp};
 use uv_cli::{
     compat::CompatArgs, CacheCommand, CacheNamespace, Cli, Commands, PipCommand, PipNamespace,
-    ProjectCommand,
+    ProgressFormat, ProjectCommand,
 };
 use uv_cli::{PythonCommand, PythonNamespace, ToolCommand, ToolNamespace};
 #[cfg(feature = "self-update")]
 use uv_cli::{SelfCommand, SelfNamespace};
-use uv_configuration::Concurrency;
+use uv_cli::{VenvArgs, VenvCommand};
+use uv_cli::{WorkspaceCommand, WorkspaceNamespace};
+use uv_client::BaseClientBuilder;
+use uv_configuration::{Concurrency, IndexCredentialHelper, PackageBuildEnv};
 use uv_fs::CWD;
 use uv_requirements::RequirementsSource;
 use uv_scripts::Pep723Script;
 use uv_settings::{Combine, FilesystemOptions, Options};
+use uv_state::{StateBucket, StateStore};
 use uv_warnings::{warn_user, warn_user_once};
 use uv_workspace::{DiscoveryOptions, Workspace};
 
+use crate::commands::pip::operations::UserConstraints;
 use crate::commands::{parse_script, ExitStatus, ToolRunCommand};
+use crate::logging::LOG_FILE_TARGET;
 use crate::printer::Printer;
 use crate::settings::{
     CacheSettings, GlobalSettings, PipCheckSettings, PipCompileSettings, PipFreezeSettings,
     PipInstallSettings, PipListSettings, PipShowSettings, PipSyncSettings, PipUninstallSettings,
 };
 
-#[cfg(target_os = "windows")]
-#[global_allocator]
-static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
-
-#[cfg(all(
-    not(target_os = "windows"),
-    not(target_os = "openbsd"),
-    any(
-        target_arch = "x86_64",
-        target_arch = "aarch64",
-        target_arch = "powerpc64"
-    )
-))]
-#[global_allocator]
-static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
-
+#[doc(hidden)]
+pub mod api;
+pub(crate) mod color;
 pub(crate) mod commands;
+pub(crate) mod environments;
 pub(crate) mod logging;
 pub(crate) mod printer;
 pub(crate) mod settings;
+pub(crate) mod stats;
 pub(crate) mod version;
 
 #[instrument(skip_all)]
@@ -64,9 +61,11 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         uv_warnings::enable();
     }
 
-    // Switch directories as early as possible.
+    // Switch directories as early as possible, i.e., before the current directory is used for
+    // configuration and project discovery, or for displaying paths.
     if let Some(directory) = cli.global_args.directory.as_ref() {
-        std::env::set_current_dir(directory)?;
+        std::env::set_current_dir(directory)
+            .with_context(|| format!("Failed to change to directory: `{}`", directory.display()))?;
     }
 
     // The `--isolated` argument is deprecated on preview APIs, and warns on non-preview APIs.
@@ -87,7 +86,10 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             }
 
             // Preview APIs. Ignore `--isolated` and warn.
-            Commands::Project(_) | Commands::Tool(_) | Commands::Python(_) => {
+            Commands::Project(_)
+            | Commands::Workspace(_)
+            | Commands::Tool(_)
+            | Commands::Python(_) => {
                 warn_user!("The `--isolated` flag is deprecated and has no effect. Instead, use `--no-config` to prevent uv from discovering configuration files.");
                 false
             }
@@ -152,12 +154,39 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         .map(FilesystemOptions::from)
         .combine(filesystem);
 
+    // Validate that the running version of uv satisfies the project's `required-version`, if any.
+    // `uv self update` is exempt, as it's the means by which a mismatch is resolved.
+    if !matches!(&*cli.command, Commands::Self_(_)) {
+        if let Some(required_version) = filesystem
+            .as_ref()
+            .and_then(|filesystem| filesystem.globals.required_version.as_ref())
+        {
+            let uv_version = Version::from_str(env!("CARGO_PKG_VERSION"))?;
+            if !required_version.contains(&uv_version) {
+                anyhow::bail!(
+                    "Required uv version `{required_version}` does not match the running version `{uv_version}`; update uv (e.g., with `uv self update`) or install a version that satisfies `{required_version}`"
+                );
+            }
+        }
+    }
+
     // Resolve the global settings.
     let globals = GlobalSettings::resolve(&cli.command, &cli.global_args, filesystem.as_ref());
 
     // Resolve the cache settings.
     let cache_settings = CacheSettings::resolve(*cli.cache_args, filesystem.as_ref());
 
+    // Configure colors before any output is written, including by the `tracing` subscriber.
+    color::configure(globals.color);
+
+    // Report all output on stderr as JSON Lines events, if requested, such that it isn't mixed
+    // with human-readable text.
+    let json_lines = globals.progress_format == ProgressFormat::JsonLines;
+    if json_lines {
+        printer::enable_json_lines();
+        uv_warnings::enable_json();
+    }
+
     // Configure the `tracing` crate, which controls internal logging.
     #[cfg(feature = "tracing-durations-export")]
     let (duration_layer, _duration_guard) = logging::setup_duration()?;
@@ -170,8 +199,17 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             2.. => logging::Level::ExtraVerbose,
         },
         duration_layer,
+        cli.global_args.log_file.as_deref(),
+        json_lines,
     )?;
 
+    info!(
+        target: LOG_FILE_TARGET,
+        event = "command",
+        version = %version::version(),
+        args = ?logging::redact_args(std::env::args()),
+    );
+
     // Configure the `Printer`, which controls user-facing output in the CLI.
     let printer = if globals.quiet {
         Printer::Quiet
@@ -190,7 +228,70 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         uv_warnings::enable();
     }
 
-    anstream::ColorChoice::write_global(globals.color.into());
+    // Configure how HTTP clients connect to hosts, e.g., to work around broken IPv6 connectivity.
+    uv_client::ConnectOverrides::new(globals.ip_version, globals.index_resolve.clone()).init();
+
+    // Configure the HTTP connections made by clients, e.g., to enable HTTP/2.
+    globals.http.clone().init();
+
+    // Configure how long to wait for other processes to release their locks on environments.
+    if let Some(timeout) = globals.lock_timeout {
+        commands::init_lock_timeout(timeout);
+    }
+
+    // Configure the command used to repair wheels built from source.
+    if let Some(command) = globals.repair_wheel_command.clone() {
+        uv_dispatch::init_repair_wheel_command(command);
+    }
+
+    // Configure the verification of indexes against their TUF metadata.
+    if globals.index_verification.is_enabled() {
+        uv_client::TufSettings::new(
+            globals.index_verification,
+            StateStore::from_settings(None)?.bucket(StateBucket::TrustedIndexes),
+        )
+        .init();
+    }
+
+    // Configure the checksums that managed Python downloads must match.
+    if let Some(checksums) = filesystem
+        .as_ref()
+        .and_then(|filesystem| filesystem.globals.python_install_checksums.clone())
+    {
+        uv_python::downloads::PinnedChecksums::new(checksums).init();
+    }
+
+    // Allow automatic Python downloads to fall back to builds that run under emulation.
+    if filesystem
+        .as_ref()
+        .and_then(|filesystem| filesystem.globals.python_install_allow_emulated)
+        .unwrap_or(false)
+    {
+        uv_python::downloads::allow_emulated();
+    }
+
+    // Read the extra environment variables to set when building specific packages, which are
+    // respected by the `pip` interface as well as projects.
+    let package_build_env = PackageBuildEnv::from(
+        filesystem
+            .as_ref()
+            .and_then(|filesystem| filesystem.build_env.clone())
+            .unwrap_or_default(),
+    );
+
+    // Configure any external commands that provide credentials for package indexes.
+    if let Some(credential_helpers) = filesystem
+        .as_ref()
+        .and_then(|filesystem| filesystem.globals.credential_helpers.as_ref())
+    {
+        uv_auth::CredentialHelpers::new(
+            credential_helpers
+                .iter()
+                .map(IndexCredentialHelper::to_helper)
+                .collect(),
+        )
+        .init();
+    }
 
     miette::set_hook(Box::new(|_| {
         Box::new(
@@ -208,12 +309,22 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
     // Write out any resolved settings.
     macro_rules! show_settings {
         ($arg:expr) => {
+            debug!(
+                target: LOG_FILE_TARGET,
+                event = "settings",
+                settings = %logging::redact_credentials(&format!("{:?}", $arg)),
+            );
             if globals.show_settings {
                 writeln!(printer.stdout(), "{:#?}", $arg)?;
                 return Ok(ExitStatus::Success);
             }
         };
         ($arg:expr, false) => {
+            debug!(
+                target: LOG_FILE_TARGET,
+                event = "settings",
+                settings = %logging::redact_credentials(&format!("{:?}", $arg)),
+            );
             if globals.show_settings {
                 writeln!(printer.stdout(), "{:#?}", $arg)?;
             }
@@ -223,7 +334,27 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
     show_settings!(cache_settings, false);
 
     // Configure the cache.
-    let cache = Cache::from_settings(cache_settings.no_cache, cache_settings.cache_dir)?;
+    let cache = Cache::from_settings(
+        cache_settings.no_cache,
+        cache_settings.cache_dir,
+        cache_settings.staging_dir,
+    )?
+    .with_buckets(cache_settings.cache_buckets)
+    .with_interpreter_cache(!cache_settings.no_interpreter_cache);
+
+    // Load the user-level constraints, for commands that resolve outside of a lockfile.
+    if applies_user_constraints(&cli.command)
+        && !(deprecated_isolated || cli.no_config || cli.no_user_constraints)
+    {
+        if let Some(path) = uv_settings::user_constraints_file() {
+            let client_builder = BaseClientBuilder::new()
+                .connectivity(globals.connectivity)
+                .native_tls(globals.native_tls);
+            UserConstraints::read(path, &client_builder)
+                .await?
+                .install();
+        }
+    }
 
     match *cli.command {
         Commands::Help(args) => commands::help(
@@ -262,6 +393,11 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 .into_iter()
                 .map(RequirementsSource::from_constraints_txt)
                 .collect::<Vec<_>>();
+            let marked_constraints = args
+                .constraint_marker
+                .into_iter()
+                .map(|(path, marker)| (RequirementsSource::from_constraints_txt(path), marker))
+                .collect::<Vec<_>>();
             let overrides = args
                 .r#override
                 .into_iter()
@@ -276,18 +412,25 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             commands::pip_compile(
                 &requirements,
                 &constraints,
+                &marked_constraints,
                 &overrides,
                 &build_constraints,
                 args.constraints_from_workspace,
                 args.overrides_from_workspace,
                 args.settings.extras,
+                args.group,
+                args.marker,
                 args.settings.output_file.as_deref(),
+                args.resolver_trace.as_deref(),
                 args.settings.resolution,
                 args.settings.prerelease,
                 args.settings.dependency_mode,
                 args.settings.upgrade,
                 args.settings.generate_hashes,
+                &args.settings.index_hash_policy,
                 args.settings.no_emit_package,
+                args.omit,
+                args.only_direct,
                 args.settings.no_strip_extras,
                 args.settings.no_strip_markers,
                 !args.settings.no_annotate,
@@ -298,17 +441,23 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.settings.emit_build_options,
                 args.settings.emit_marker_expression,
                 args.settings.emit_index_annotation,
+                args.require_index,
+                args.settings.strict_extras,
+                args.settings.emit_constraint_annotation,
                 args.settings.index_locations,
                 args.settings.index_strategy,
                 args.settings.keyring_provider,
                 args.settings.setup_py,
                 args.settings.config_setting,
+                &package_build_env,
                 globals.connectivity,
                 args.settings.no_build_isolation,
                 args.settings.no_build_isolation_package,
                 args.settings.build_options,
                 args.settings.python_version,
                 args.settings.python_platform,
+                args.settings.macos_arch,
+                args.target_environment.as_deref(),
                 args.settings.universal,
                 args.settings.exclude_newer,
                 args.settings.sources,
@@ -367,10 +516,12 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 &requirements,
                 &constraints,
                 &build_constraints,
+                args.src.as_deref(),
                 args.settings.reinstall,
                 args.settings.link_mode,
                 args.settings.compile_bytecode,
                 args.settings.hash_checking,
+                &args.settings.index_hash_policy,
                 args.settings.index_locations,
                 args.settings.index_strategy,
                 args.settings.keyring_provider,
@@ -378,14 +529,23 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.settings.allow_empty_requirements,
                 globals.connectivity,
                 &args.settings.config_setting,
+                &package_build_env,
                 args.settings.no_build_isolation,
                 args.settings.no_build_isolation_package,
                 args.settings.build_options,
                 args.settings.python_version,
                 args.settings.python_platform,
+                args.settings.macos_arch,
+                args.abi_check,
+                args.file_conflicts,
+                &args.lazy_install,
+                &args.settings.exclude_package_data,
+                args.max_download_size,
+                args.verify_imports,
                 args.settings.strict,
                 args.settings.exclude_newer,
                 args.settings.python,
+                args.python_executable.as_deref(),
                 args.settings.system,
                 args.settings.break_system_packages,
                 args.settings.target,
@@ -396,6 +556,8 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 globals.preview,
                 cache,
                 args.dry_run,
+                args.summary,
+                args.show_sizes,
                 printer,
             )
             .await
@@ -454,9 +616,11 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 &constraints,
                 &overrides,
                 &build_constraints,
+                args.src.as_deref(),
                 args.constraints_from_workspace,
                 args.overrides_from_workspace,
                 &args.settings.extras,
+                &args.group,
                 args.settings.resolution,
                 args.settings.prerelease,
                 args.settings.dependency_mode,
@@ -468,15 +632,28 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.settings.link_mode,
                 args.settings.compile_bytecode,
                 args.settings.hash_checking,
+                &args.settings.index_hash_policy,
                 args.settings.setup_py,
                 globals.connectivity,
                 &args.settings.config_setting,
+                &package_build_env,
                 args.settings.no_build_isolation,
                 args.settings.no_build_isolation_package,
                 args.settings.build_options,
                 args.settings.python_version,
                 args.settings.python_platform,
+                args.settings.macos_arch,
+                args.abi_check,
+                args.file_conflicts,
+                &args.lazy_install,
+                &args.settings.exclude_package_data,
+                args.max_download_size,
+                args.break_on_downgrade,
+                args.verify_imports,
+                args.ignore_requires_python,
+                args.requires_python_override,
                 args.settings.strict,
+                args.settings.strict_extras,
                 args.settings.exclude_newer,
                 args.settings.sources,
                 args.settings.python,
@@ -489,6 +666,8 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 globals.preview,
                 cache,
                 args.dry_run,
+                args.summary,
+                args.show_sizes,
                 printer,
             )
             .await
@@ -537,7 +716,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::pip_freeze(
                 args.exclude_editable,
@@ -559,12 +738,13 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::pip_list(
                 args.editable,
                 &args.exclude,
                 &args.format,
+                args.sizes,
                 args.settings.strict,
                 args.settings.python.as_deref(),
                 args.settings.system,
@@ -581,7 +761,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::pip_show(
                 args.package,
@@ -600,7 +780,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             let args = PipTreeSettings::resolve(args, filesystem);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::pip_tree(
                 args.show_version_specifiers,
@@ -624,7 +804,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::pip_check(
                 args.settings.python.as_deref(),
@@ -639,20 +819,62 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         })
         | Commands::Clean(args) => {
             show_settings!(args);
-            commands::cache_clean(&args.package, &cache, printer)
+            commands::cache_clean(&args.package, args.built_dependents, &cache, printer)
         }
         Commands::Cache(CacheNamespace {
             command: CacheCommand::Prune(args),
         }) => {
             show_settings!(args);
-            commands::cache_prune(args.ci, &cache, printer)
+            commands::cache_prune(
+                args.ci,
+                args.tool_runs,
+                args.max_age,
+                args.max_size,
+                &args.lockfiles,
+                &args.builds_for,
+                &cache,
+                printer,
+            )
+            .await
         }
         Commands::Cache(CacheNamespace {
-            command: CacheCommand::Dir,
+            command: CacheCommand::Dir(args),
         }) => {
-            commands::cache_dir(&cache);
+            show_settings!(args);
+            commands::cache_dir(&cache, args.per_bucket);
             Ok(ExitStatus::Success)
         }
+        Commands::Cache(CacheNamespace {
+            command: CacheCommand::Move(args),
+        }) => {
+            show_settings!(args);
+            commands::cache_move(&args.destination, &cache, printer)
+        }
+        Commands::Stats(args) => {
+            show_settings!(args);
+            commands::stats(args.json, args.clear, globals.preview, printer)
+        }
+        Commands::Venv(VenvArgs {
+            command: Some(VenvCommand::List(args)),
+            ..
+        }) => {
+            show_settings!(args);
+            commands::venv_list(args.json, globals.preview, printer)
+        }
+        Commands::Venv(VenvArgs {
+            command: Some(VenvCommand::Remove(args)),
+            ..
+        }) => {
+            show_settings!(args);
+            commands::venv_remove(&args.paths, globals.preview, printer)
+        }
+        Commands::Venv(VenvArgs {
+            command: Some(VenvCommand::Prune(args)),
+            ..
+        }) => {
+            show_settings!(args);
+            commands::venv_prune(args.dry_run, globals.preview, printer)
+        }
         Commands::Venv(args) => {
             args.compat_args.validate()?;
 
@@ -669,7 +891,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             // Since we use ".venv" as the default name, we use "." as the default prompt.
             let prompt = args.prompt.or_else(|| {
@@ -706,6 +928,31 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
         Commands::Project(project) => {
             run_project(project, script, globals, filesystem, cache, printer).await
         }
+        Commands::Workspace(WorkspaceNamespace {
+            command: WorkspaceCommand::List(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::WorkspaceListSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            commands::workspace_list(&args.affected_by, args.json, globals.preview, printer).await
+        }
+        Commands::Workspace(WorkspaceNamespace {
+            command: WorkspaceCommand::Tree(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::WorkspaceTreeSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            commands::workspace_tree(
+                &args.package,
+                args.invert,
+                args.external,
+                globals.preview,
+                printer,
+            )
+            .await
+        }
         #[cfg(feature = "self-update")]
         Commands::Self_(SelfNamespace {
             command: SelfCommand::Update,
@@ -759,6 +1006,11 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                 args.settings,
                 invocation_source,
                 args.isolated,
+                args.project_constraints,
+                args.detach,
+                args.cache_days,
+                args.cache_max_size,
+                args.typosquat_check,
                 globals.preview,
                 globals.python_preference,
                 globals.python_downloads,
@@ -794,14 +1046,38 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
                         .map(RequirementsSource::from_requirements_file),
                 )
                 .collect::<Vec<_>>();
+            let constraints = args
+                .constraint
+                .into_iter()
+                .map(RequirementsSource::from_constraints_txt)
+                .collect::<Vec<_>>();
+            let overrides = args
+                .r#override
+                .into_iter()
+                .map(RequirementsSource::from_overrides_txt)
+                .collect::<Vec<_>>();
+            let selection = uv_tool::EntrypointSelection {
+                include: args.entrypoint,
+                exclude: args.exclude_entrypoint,
+                include_deps: args.include_deps,
+            };
 
             commands::tool_install(
                 args.package,
                 args.editable,
                 args.from,
                 &requirements,
+                &constraints,
+                &overrides,
                 args.python,
                 args.force,
+                args.locked,
+                args.from_lock,
+                args.suffix,
+                selection,
+                args.read_only_env,
+                args.env,
+                args.format,
                 args.options,
                 args.settings,
                 globals.preview,
@@ -825,7 +1101,14 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             // Initialize the cache.
             let cache = cache.init()?;
 
-            commands::tool_list(args.show_paths, globals.preview, &cache, printer).await
+            commands::tool_list(
+                args.show_paths,
+                args.format,
+                globals.preview,
+                &cache,
+                printer,
+            )
+            .await
         }
         Commands::Tool(ToolNamespace {
             command: ToolCommand::Upgrade(args),
@@ -835,14 +1118,31 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             // Initialize the cache.
-            let cache = cache.init()?.with_refresh(Refresh::All(Timestamp::now()));
+            let cache = cache
+                .init()?
+                .with_refresh(Refresh::All(Timestamp::now()).combine(args.refresh));
+
+            let requirements = args
+                .with
+                .into_iter()
+                .map(RequirementsSource::from_package)
+                .collect::<Vec<_>>();
 
             commands::tool_upgrade(
                 args.name,
+                &requirements,
+                &args.without,
+                args.frozen,
+                args.dry_run,
+                args.python,
+                args.format,
+                globals.python_preference,
+                globals.python_downloads,
                 globals.connectivity,
                 args.args,
                 args.filesystem,
-                Concurrency::default(),
+                args.concurrency,
+                args.concurrent_upgrades,
                 globals.native_tls,
                 &cache,
                 globals.preview,
@@ -857,12 +1157,58 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             let args = settings::ToolUninstallSettings::resolve(args, filesystem);
             show_settings!(args);
 
-            commands::tool_uninstall(args.name, globals.preview, printer).await
+            commands::tool_uninstall(args.name, args.dry_run, globals.preview, printer).await
+        }
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::Show(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ToolShowSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            commands::tool_show(&args.name, args.format, globals.preview, printer)
         }
         Commands::Tool(ToolNamespace {
-            command: ToolCommand::UpdateShell,
+            command: ToolCommand::Repair(args),
         }) => {
-            commands::tool_update_shell(globals.preview, printer).await?;
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ToolRepairSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::tool_repair(
+                args.name,
+                args.filesystem,
+                globals.python_preference,
+                globals.python_downloads,
+                globals.connectivity,
+                Concurrency::default(),
+                globals.native_tls,
+                &cache,
+                globals.preview,
+                printer,
+            )
+            .await
+        }
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::Prune(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ToolPruneSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            commands::tool_prune(args.dry_run, globals.preview, printer)
+        }
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::UpdateShell(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ToolUpdateShellSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            commands::tool_update_shell(args.dry_run, globals.preview, printer).await?;
             Ok(ExitStatus::Success)
         }
         Commands::Tool(ToolNamespace {
@@ -875,6 +1221,18 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             commands::tool_dir(args.bin, globals.preview)?;
             Ok(ExitStatus::Success)
         }
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::Ps,
+        }) => commands::tool_ps(globals.preview, printer),
+        Commands::Tool(ToolNamespace {
+            command: ToolCommand::Kill(args),
+        }) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ToolKillSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            commands::tool_kill(&args.pids, args.all, globals.preview, printer)
+        }
         Commands::Python(PythonNamespace {
             command: PythonCommand::List(args),
         }) => {
@@ -883,12 +1241,13 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             show_settings!(args);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::python_list(
                 args.kinds,
                 args.all_versions,
                 args.all_platforms,
+                args.json,
                 globals.python_preference,
                 globals.python_downloads,
                 globals.preview,
@@ -910,6 +1269,10 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             commands::python_install(
                 args.targets,
                 args.reinstall,
+                args.ensurepip,
+                args.sitecustomize.as_deref(),
+                args.safe_path,
+                args.allow_emulated,
                 globals.python_downloads,
                 globals.native_tls,
                 globals.connectivity,
@@ -936,7 +1299,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             let args = settings::PythonFindSettings::resolve(args, filesystem);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::python_find(
                 args.request,
@@ -954,7 +1317,7 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             let args = settings::PythonPinSettings::resolve(args, filesystem);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::python_pin(
                 args.request,
@@ -973,6 +1336,9 @@ async fn run(cli: Cli) -> Result<ExitStatus> {
             commands::python_dir(globals.preview)?;
             Ok(ExitStatus::Success)
         }
+        Commands::External(args) => {
+            commands::extension(&args, cli.config_file, cli.no_config, &globals, &cache).await
+        }
     }
 }
 
@@ -988,6 +1354,11 @@ async fn run_project(
     // Write out any resolved settings.
     macro_rules! show_settings {
         ($arg:expr) => {
+            debug!(
+                target: LOG_FILE_TARGET,
+                event = "settings",
+                settings = %logging::redact_credentials(&format!("{:?}", $arg)),
+            );
             if globals.show_settings {
                 writeln!(printer.stdout(), "{:#?}", $arg)?;
                 return Ok(ExitStatus::Success);
@@ -1016,6 +1387,7 @@ async fn run_project(
                 args.no_readme,
                 args.python,
                 args.no_workspace,
+                args.build_backend,
                 globals.preview,
                 globals.python_preference,
                 globals.python_downloads,
@@ -1049,6 +1421,31 @@ async fn run_project(
                 )
                 .collect::<Vec<_>>();
 
+            if args.all_pythons {
+                return Box::pin(commands::run_all_pythons(
+                    script,
+                    args.command,
+                    requirements,
+                    args.show_resolution || globals.verbose > 0,
+                    args.locked,
+                    args.frozen,
+                    args.package,
+                    args.extras,
+                    args.dev,
+                    args.module_path,
+                    args.settings,
+                    globals.preview,
+                    globals.python_preference,
+                    globals.python_downloads,
+                    globals.connectivity,
+                    Concurrency::default(),
+                    globals.native_tls,
+                    &cache,
+                    printer,
+                ))
+                .await;
+            }
+
             Box::pin(commands::run(
                 script,
                 args.command,
@@ -1059,8 +1456,10 @@ async fn run_project(
                 args.isolated,
                 args.package,
                 args.no_project,
+                args.env,
                 args.extras,
                 args.dev,
+                args.module_path,
                 args.python,
                 args.settings,
                 globals.preview,
@@ -1090,9 +1489,18 @@ async fn run_project(
                 args.locked,
                 args.frozen,
                 args.package,
+                args.only_affected_by,
+                args.env,
                 args.extras,
                 args.dev,
+                args.group,
                 args.modifications,
+                args.verify_imports,
+                args.reinstall_editables,
+                args.summary,
+                args.show_sizes,
+                args.read_only_env,
+                args.editor_config,
                 args.python,
                 globals.python_preference,
                 globals.python_downloads,
@@ -1106,6 +1514,37 @@ async fn run_project(
             )
             .await
         }
+        ProjectCommand::Test(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::TestSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            // Initialize the cache.
+            let cache = cache.init()?.with_refresh(
+                args.refresh
+                    .combine(Refresh::from(args.settings.reinstall.clone()))
+                    .combine(Refresh::from(args.settings.upgrade.clone())),
+            );
+
+            Box::pin(commands::test(
+                args.command,
+                args.python,
+                args.group,
+                args.fail_fast,
+                args.locked,
+                args.frozen,
+                args.settings,
+                globals.python_preference,
+                globals.python_downloads,
+                globals.preview,
+                globals.connectivity,
+                args.concurrency,
+                globals.native_tls,
+                &cache,
+                printer,
+            ))
+            .await
+        }
         ProjectCommand::Lock(args) => {
             // Resolve the settings from the command-line arguments and workspace configuration.
             let args = settings::LockSettings::resolve(args, filesystem);
@@ -1117,6 +1556,12 @@ async fn run_project(
             commands::lock(
                 args.locked,
                 args.frozen,
+                args.verify_determinism,
+                args.shuffle,
+                args.prune_platforms,
+                args.suggest_requires_python,
+                args.restore,
+                args.script,
                 args.python,
                 args.settings,
                 globals.preview,
@@ -1146,15 +1591,20 @@ async fn run_project(
                 args.locked,
                 args.frozen,
                 args.no_sync,
+                args.suggest_requires_python,
+                args.fix_requires_python,
                 args.requirements,
+                args.sync_manifest,
                 args.editable,
-                args.dependency_type,
                 args.raw_sources,
+                args.raw,
                 args.rev,
                 args.tag,
                 args.branch,
                 args.extras,
+                args.marker,
                 args.package,
+                args.typosquat_check,
                 args.python,
                 args.settings,
                 globals.python_preference,
@@ -1206,7 +1656,7 @@ async fn run_project(
             show_settings!(args);
 
             // Initialize the cache.
-            let cache = cache.init()?;
+            let cache = cache.init()?.with_refresh(args.refresh);
 
             commands::tree(
                 args.locked,
@@ -1217,6 +1667,35 @@ async fn run_project(
                 args.package,
                 args.no_dedupe,
                 args.invert,
+                args.format,
+                args.python_version,
+                args.python_platform,
+                args.python,
+                args.resolver,
+                globals.python_preference,
+                globals.python_downloads,
+                globals.preview,
+                globals.connectivity,
+                Concurrency::default(),
+                globals.native_tls,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        ProjectCommand::Why(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::WhySettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            // Initialize the cache.
+            let cache = cache.init()?.with_refresh(args.refresh);
+
+            commands::why(
+                args.package,
+                args.locked,
+                args.frozen,
+                args.universal,
                 args.python_version,
                 args.python_platform,
                 args.python,
@@ -1232,6 +1711,138 @@ async fn run_project(
             )
             .await
         }
+        ProjectCommand::Server(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ServerSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::server(
+                args.python,
+                args.resolver,
+                globals.python_preference,
+                globals.python_downloads,
+                globals.preview,
+                globals.connectivity,
+                args.concurrency,
+                globals.native_tls,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        ProjectCommand::Build(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::BuildSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            // Initialize the cache.
+            let cache = cache.init()?.with_refresh(
+                args.refresh
+                    .combine(Refresh::from(args.settings.upgrade.clone())),
+            );
+
+            commands::build(
+                args.src,
+                args.all,
+                args.sdist,
+                args.wheel,
+                args.out_dir,
+                args.locked,
+                args.python,
+                args.settings,
+                globals.python_preference,
+                globals.python_downloads,
+                globals.preview,
+                globals.connectivity,
+                Concurrency::default(),
+                globals.native_tls,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        ProjectCommand::Vendor(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::VendorSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            // Initialize the cache.
+            let cache = cache.init()?.with_refresh(
+                args.refresh
+                    .combine(Refresh::from(args.settings.reinstall.clone()))
+                    .combine(Refresh::from(args.settings.upgrade.clone())),
+            );
+
+            commands::vendor(
+                args.locked,
+                args.frozen,
+                args.extras,
+                args.dev,
+                args.output_dir,
+                args.python,
+                globals.python_preference,
+                globals.python_downloads,
+                args.settings,
+                globals.preview,
+                globals.connectivity,
+                Concurrency::default(),
+                globals.native_tls,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        ProjectCommand::Export(args) => {
+            // Resolve the settings from the command-line arguments and workspace configuration.
+            let args = settings::ExportSettings::resolve(args, filesystem);
+            show_settings!(args);
+
+            // Initialize the cache.
+            let cache = cache.init()?.with_refresh(
+                args.refresh
+                    .combine(Refresh::from(args.settings.upgrade.clone())),
+            );
+
+            commands::export(
+                args.format,
+                args.extras,
+                args.dev,
+                args.group,
+                args.output_file.as_deref(),
+                args.locked,
+                args.frozen,
+                args.python,
+                args.settings,
+                globals.python_preference,
+                globals.python_downloads,
+                globals.preview,
+                globals.connectivity,
+                Concurrency::default(),
+                globals.native_tls,
+                &cache,
+                printer,
+            )
+            .await
+        }
+        ProjectCommand::Diff(args) => {
+            show_settings!(args);
+
+            // Initialize the cache.
+            let cache = cache.init()?;
+
+            commands::diff(
+                &args.old,
+                args.new.as_deref(),
+                args.format,
+                args.python.as_deref(),
+                globals.preview,
+                &cache,
+                printer,
+            )
+        }
     }
 }
 
@@ -1242,6 +1853,94 @@ async fn run_project(
 /// point, uv assumes it is running in a process it controls and that the
 /// entire process lifetime is managed by uv. Unexpected behavior may be
 /// encountered if this entry pointis called multiple times in a single process.
+/// Exit with the given argument parsing error, suggesting the `uv pip` equivalent of any unknown
+/// subcommand that's commonly mistaken for a top-level command.
+/// Returns `true` if the command may perform a resolution that respects the user-level
+/// constraints, i.e., a resolution that isn't persisted to a project lockfile.
+fn applies_user_constraints(command: &Commands) -> bool {
+    match command {
+        Commands::Pip(PipNamespace {
+            command: PipCommand::Compile(_) | PipCommand::Install(_) | PipCommand::Sync(_),
+        }) => true,
+        Commands::Tool(ToolNamespace {
+            command:
+                ToolCommand::Run(_)
+                | ToolCommand::Uvx(_)
+                | ToolCommand::Install(_)
+                | ToolCommand::Upgrade(_)
+                | ToolCommand::Repair(_),
+        }) => true,
+        Commands::Project(command) => matches!(
+            **command,
+            ProjectCommand::Run(_) | ProjectCommand::Sync(_) | ProjectCommand::Server(_)
+        ),
+        _ => false,
+    }
+}
+
+fn exit_with_suggestion(mut err: clap::Error) -> ! {
+    if let Some(ContextValue::String(subcommand)) = err.get(ContextKind::InvalidSubcommand) {
+        match subcommand.as_str() {
+            "compile" | "lock" => {
+                err.insert(
+                    ContextKind::SuggestedSubcommand,
+                    ContextValue::String("uv pip compile".to_string()),
+                );
+            }
+            "sync" => {
+                err.insert(
+                    ContextKind::SuggestedSubcommand,
+                    ContextValue::String("uv pip sync".to_string()),
+                );
+            }
+            "install" | "add" => {
+                err.insert(
+                    ContextKind::SuggestedSubcommand,
+                    ContextValue::String("uv pip install".to_string()),
+                );
+            }
+            "uninstall" | "remove" => {
+                err.insert(
+                    ContextKind::SuggestedSubcommand,
+                    ContextValue::String("uv pip uninstall".to_string()),
+                );
+            }
+            "freeze" => {
+                err.insert(
+                    ContextKind::SuggestedSubcommand,
+                    ContextValue::String("uv pip freeze".to_string()),
+                );
+            }
+            "list" => {
+                err.insert(
+                    ContextKind::SuggestedSubcommand,
+                    ContextValue::String("uv pip list".to_string()),
+                );
+            }
+            "show" => {
+                err.insert(
+                    ContextKind::SuggestedSubcommand,
+                    ContextValue::String("uv pip show".to_string()),
+                );
+            }
+            "tree" => {
+                err.insert(
+                    ContextKind::SuggestedSubcommand,
+                    ContextValue::String("uv pip tree".to_string()),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    // Help and version requests exit successfully; anything else is a usage error.
+    if !err.use_stderr() {
+        err.exit()
+    }
+    let _ = err.print();
+    std::process::exit(i32::from(ExitStatus::UsageError.code()))
+}
+
 pub fn main<I, T>(args: I) -> ExitCode
 where
     I: IntoIterator<Item = T>,
@@ -1251,65 +1950,30 @@ where
     // https://github.com/rust-lang/rust/pull/48005
     let cli = match Cli::try_parse_from(args) {
         Ok(cli) => cli,
-        Err(mut err) => {
-            if let Some(ContextValue::String(subcommand)) = err.get(ContextKind::InvalidSubcommand)
-            {
-                match subcommand.as_str() {
-                    "compile" | "lock" => {
-                        err.insert(
-                            ContextKind::SuggestedSubcommand,
-                            ContextValue::String("uv pip compile".to_string()),
-                        );
-                    }
-                    "sync" => {
-                        err.insert(
-                            ContextKind::SuggestedSubcommand,
-                            ContextValue::String("uv pip sync".to_string()),
-                        );
-                    }
-                    "install" | "add" => {
-                        err.insert(
-                            ContextKind::SuggestedSubcommand,
-                            ContextValue::String("uv pip install".to_string()),
-                        );
-                    }
-                    "uninstall" | "remove" => {
-                        err.insert(
-                            ContextKind::SuggestedSubcommand,
-                            ContextValue::String("uv pip uninstall".to_string()),
-                        );
-                    }
-                    "freeze" => {
-                        err.insert(
-                            ContextKind::SuggestedSubcommand,
-                            ContextValue::String("uv pip freeze".to_string()),
-                        );
-                    }
-                    "list" => {
-                        err.insert(
-                            ContextKind::SuggestedSubcommand,
-                            ContextValue::String("uv pip list".to_string()),
-                        );
-                    }
-                    "show" => {
-                        err.insert(
-                            ContextKind::SuggestedSubcommand,
-                            ContextValue::String("uv pip show".to_string()),
-                        );
-                    }
-                    "tree" => {
-                        err.insert(
-                            ContextKind::SuggestedSubcommand,
-                            ContextValue::String("uv pip tree".to_string()),
-                        );
-                    }
-                    _ => {}
-                }
-            }
-            err.exit()
-        }
+        Err(err) => exit_with_suggestion(err),
     };
 
+    // An unknown subcommand is only accepted if it's provided by an extension, i.e., a
+    // `uv-<name>` executable on the `PATH`.
+    if let Commands::External(args) = &*cli.command {
+        let name = args.first().map(|name| name.to_string_lossy().to_string());
+        if let Some(name) = name.filter(|name| commands::find_extension(name.as_ref()).is_none()) {
+            let mut uv = Cli::command();
+            uv.build();
+            let mut err = clap::Error::new(ErrorKind::InvalidSubcommand).with_cmd(&uv);
+            err.insert(ContextKind::InvalidSubcommand, ContextValue::String(name));
+            err.insert(
+                ContextKind::Usage,
+                ContextValue::StyledStr(uv.render_usage()),
+            );
+            exit_with_suggestion(err);
+        }
+    }
+
+    // Record usage statistics for commands that resolve or install packages.
+    let stats_command = stats::command_name(&cli.command);
+    let start = std::time::Instant::now();
+
     // Windows has a default stack size of 1MB, which is lower than the linux and mac default.
     // https://learn.microsoft.com/en-us/cpp/build/reference/stack-stack-allocations?view=msvc-170
     // We support increasing the stack size to avoid stack overflows in debug mode on Windows. In
@@ -1350,15 +2014,47 @@ where
         result
     };
 
+    let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+    match &result {
+        Ok(status) => info!(
+            target: LOG_FILE_TARGET,
+            event = "exit",
+            success = matches!(status, ExitStatus::Success),
+            duration_ms,
+        ),
+        Err(err) => info!(
+            target: LOG_FILE_TARGET,
+            event = "exit",
+            success = false,
+            error = %format!("{err:#}"),
+            duration_ms,
+        ),
+    }
+
+    if let Some(command) = stats_command {
+        stats::flush(
+            command,
+            start.elapsed(),
+            matches!(result, Ok(ExitStatus::Success)),
+        );
+    }
+
     match result {
         Ok(code) => code.into(),
         Err(err) => {
             let mut causes = err.chain();
-            eprintln!("{}: {}", "error".red().bold(), causes.next().unwrap());
-            for err in causes {
-                eprintln!("  {}: {}", "Caused by".red().bold(), err);
+            if printer::json_lines() {
+                printer::emit(&printer::OutputEvent::Error {
+                    message: &causes.next().unwrap().to_string(),
+                    causes: causes.map(ToString::to_string).collect(),
+                });
+            } else {
+                eprintln!("{}: {}", "error".red().bold(), causes.next().unwrap());
+                for err in causes {
+                    eprintln!("  {}: {}", "Caused by".red().bold(), err);
+                }
             }
-            ExitStatus::Error.into()
+            ExitStatus::from_error(err.as_ref()).into()
         }
     }
 }
@@ -1,5 +1,46 @@
-use anstream::{eprint, print};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use anstream::{eprint, eprintln, print};
 use indicatif::ProgressDrawTarget;
+use serde::Serialize;
+
+/// Whether to report progress and other output on stderr as JSON Lines events (i.e., with
+/// `--progress-format json-lines`).
+static JSON_LINES: AtomicBool = AtomicBool::new(false);
+
+/// Report all output on stderr as JSON Lines events.
+pub(crate) fn enable_json_lines() {
+    JSON_LINES.store(true, Ordering::SeqCst);
+}
+
+/// Returns `true` if output on stderr is reported as JSON Lines events.
+pub(crate) fn json_lines() -> bool {
+    JSON_LINES.load(Ordering::SeqCst)
+}
+
+/// Write an event to stderr, as a single line of JSON.
+pub(crate) fn emit(event: &impl Serialize) {
+    if let Ok(event) = serde_json::to_string(event) {
+        #[allow(clippy::print_stderr, clippy::ignored_unit_patterns)]
+        {
+            eprintln!("{event}");
+        }
+    }
+}
+
+/// An event for output other than progress, reported when using `--progress-format json-lines`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub(crate) enum OutputEvent<'a> {
+    /// A line of human-readable output, e.g., a summary of the installed packages.
+    Message { message: &'a str },
+    /// The error that caused the command to fail, along with its chain of causes.
+    Error {
+        message: &'a str,
+        causes: Vec<String>,
+    },
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Printer {
@@ -26,6 +67,12 @@ impl Printer {
         }
     }
 
+    /// Returns `true` if progress should be reported as JSON Lines events, in lieu of progress
+    /// bars.
+    pub(crate) fn json_lines(self) -> bool {
+        self != Self::Quiet && json_lines()
+    }
+
     /// Return the [`Stdout`] for this printer.
     pub(crate) fn stdout(self) -> Stdout {
         match self {
@@ -39,8 +86,9 @@ impl Printer {
     /// Return the [`Stderr`] for this printer.
     pub(crate) fn stderr(self) -> Stderr {
         match self {
-            Self::Default => Stderr::Enabled,
             Self::Quiet => Stderr::Disabled,
+            _ if json_lines() => Stderr::Json,
+            Self::Default => Stderr::Enabled,
             Self::Verbose => Stderr::Enabled,
             Self::NoProgress => Stderr::Enabled,
         }
@@ -73,8 +121,13 @@ impl std::fmt::Write for Stdout {
 pub(crate) enum Stderr {
     Enabled,
     Disabled,
+    /// Report each line of output as an [`OutputEvent::Message`].
+    Json,
 }
 
+/// The output written to a [`Stderr::Json`] since the last complete line.
+static PENDING: Mutex<String> = Mutex::new(String::new());
+
 impl std::fmt::Write for Stderr {
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
         match self {
@@ -85,6 +138,17 @@ impl std::fmt::Write for Stderr {
                 }
             }
             Self::Disabled => {}
+            Self::Json => {
+                let mut pending = PENDING.lock().unwrap();
+                pending.push_str(s);
+                while let Some(newline) = pending.find('\n') {
+                    let line = pending.drain(..=newline).collect::<String>();
+                    let message = anstream::adapter::strip_str(line.trim_end()).to_string();
+                    if !message.is_empty() {
+                        emit(&OutputEvent::Message { message: &message });
+                    }
+                }
+            }
         }
 
         Ok(())
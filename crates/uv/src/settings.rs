@@ -1,41 +1,54 @@
+use std::collections::BTreeMap;
 use std::env::VarError;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::process;
 use std::str::FromStr;
+use std::time::Duration;
 
-use distribution_types::IndexLocations;
+use distribution_types::{
+    IndexHashPolicies, IndexLocations, IndexResolve, IndexUrl, RequiredIndex,
+};
 use install_wheel_rs::linker::LinkMode;
-use pep508_rs::{ExtraName, RequirementOrigin};
+use install_wheel_rs::ExcludePattern;
+use pep440_rs::VersionSpecifiers;
+use pep508_rs::{ExtraName, MarkerTree, RequirementOrigin};
 use pypi_types::Requirement;
-use uv_cache::{CacheArgs, Refresh};
+use uv_cache::{CacheArgs, CacheBucket, Refresh};
 use uv_cli::{
     options::{flag, resolver_installer_options, resolver_options},
     ToolUpgradeArgs,
 };
 use uv_cli::{
-    AddArgs, ColorChoice, Commands, ExternalCommand, GlobalArgs, InitArgs, ListFormat, LockArgs,
-    Maybe, PipCheckArgs, PipCompileArgs, PipFreezeArgs, PipInstallArgs, PipListArgs, PipShowArgs,
-    PipSyncArgs, PipTreeArgs, PipUninstallArgs, PythonFindArgs, PythonInstallArgs, PythonListArgs,
-    PythonPinArgs, PythonUninstallArgs, RemoveArgs, RunArgs, SyncArgs, ToolDirArgs,
-    ToolInstallArgs, ToolListArgs, ToolRunArgs, ToolUninstallArgs, TreeArgs, VenvArgs,
+    AddArgs, BuildBackend, ColorChoice, Commands, ExportArgs, ExportFormat, ExternalCommand,
+    GlobalArgs, InitArgs, InstallSummary, ListFormat, LockArgs, Maybe, PipCheckArgs,
+    PipCompileArgs, PipFreezeArgs, PipInstallArgs, PipListArgs, PipShowArgs, PipSyncArgs,
+    PipTreeArgs, PipUninstallArgs, ProgressFormat, ProjectBuildArgs, PythonFindArgs,
+    PythonInstallArgs, PythonListArgs, PythonPinArgs, PythonUninstallArgs, RemoveArgs,
+    RequiresPythonFix, ResolvedPin, RunArgs, ServerArgs, SyncArgs, TestArgs, ToolDirArgs,
+    ToolFormat, ToolInstallArgs, ToolKillArgs, ToolListArgs, ToolPruneArgs, ToolRepairArgs,
+    ToolRunArgs, ToolShowArgs, ToolShowFormat, ToolUninstallArgs, ToolUpdateShellArgs, TreeArgs,
+    TreeFormat, VendorArgs, VenvArgs, WhyArgs, WorkspaceListArgs, WorkspaceTreeArgs,
 };
-use uv_client::Connectivity;
+use uv_client::{Connectivity, HttpSettings};
 use uv_configuration::{
-    BuildOptions, Concurrency, ConfigSettings, ExtrasSpecification, HashCheckingMode,
-    IndexStrategy, KeyringProviderType, NoBinary, NoBuild, PreviewMode, Reinstall, SetupPyStrategy,
-    SourceStrategy, TargetTriple, Upgrade,
+    AbiCheck, BuildOptions, Concurrency, ConfigSettings, EditorConfig, ExtrasSpecification,
+    FileConflicts, HashCheckingMode, IndexStrategy, IndexVerification, IpVersion,
+    KeyringProviderType, MacosArch, ModulePath, NoBinary, NoBuild, PreferSource, PreviewMode,
+    Reinstall, SetupPyStrategy, SourceStrategy, SysPlatform, TargetTriple, TyposquatCheck, Upgrade,
 };
-use uv_normalize::PackageName;
+use uv_normalize::{GroupName, PackageName};
 use uv_python::{Prefix, PythonDownloads, PythonPreference, PythonVersion, Target};
 use uv_requirements::RequirementsSource;
 use uv_resolver::{AnnotationStyle, DependencyMode, ExcludeNewer, PrereleaseMode, ResolutionMode};
 use uv_settings::{
     Combine, FilesystemOptions, Options, PipOptions, ResolverInstallerOptions, ResolverOptions,
 };
+use uv_tool::ToolName;
 use uv_warnings::warn_user_once;
 use uv_workspace::pyproject::DependencyType;
 
+use crate::color;
 use crate::commands::pip::operations::Modifications;
 
 /// The resolved global settings to use for any invocation of the CLI.
@@ -46,12 +59,19 @@ pub(crate) struct GlobalSettings {
     pub(crate) verbose: u8,
     pub(crate) color: ColorChoice,
     pub(crate) native_tls: bool,
+    pub(crate) ip_version: Option<IpVersion>,
+    pub(crate) index_resolve: IndexResolve,
+    pub(crate) http: HttpSettings,
+    pub(crate) index_verification: IndexVerification,
     pub(crate) connectivity: Connectivity,
     pub(crate) show_settings: bool,
     pub(crate) preview: PreviewMode,
     pub(crate) python_preference: PythonPreference,
     pub(crate) python_downloads: PythonDownloads,
     pub(crate) no_progress: bool,
+    pub(crate) progress_format: ProgressFormat,
+    pub(crate) lock_timeout: Option<Duration>,
+    pub(crate) repair_wheel_command: Option<String>,
 }
 
 impl GlobalSettings {
@@ -83,26 +103,47 @@ impl GlobalSettings {
         Self {
             quiet: args.quiet,
             verbose: args.verbose,
-            color: if args.no_color
-                || std::env::var_os("NO_COLOR")
-                    .filter(|v| !v.is_empty())
-                    .is_some()
-            {
-                ColorChoice::Never
-            } else if std::env::var_os("FORCE_COLOR")
-                .filter(|v| !v.is_empty())
-                .is_some()
-                || std::env::var_os("CLICOLOR_FORCE")
-                    .filter(|v| !v.is_empty())
-                    .is_some()
-            {
-                ColorChoice::Always
-            } else {
-                args.color
-            },
+            color: color::resolve(args.color, args.no_color, std::env::var_os),
             native_tls: flag(args.native_tls, args.no_native_tls)
                 .combine(workspace.and_then(|workspace| workspace.globals.native_tls))
                 .unwrap_or(false),
+            ip_version: args
+                .ip_version
+                .combine(workspace.and_then(|workspace| workspace.globals.ip_version)),
+            index_resolve: Some(args.index_resolve.iter().cloned().collect::<IndexResolve>())
+                .filter(|index_resolve| !index_resolve.is_empty())
+                .combine(workspace.and_then(|workspace| workspace.globals.index_resolve.clone()))
+                .unwrap_or_default(),
+            http: {
+                let defaults = HttpSettings::default();
+                HttpSettings {
+                    http2: env(env::HTTP2)
+                        .combine(workspace.and_then(|workspace| workspace.globals.http2))
+                        .unwrap_or(defaults.http2),
+                    pool_max_idle_per_host: env(env::HTTP_POOL_MAX_IDLE_PER_HOST)
+                        .combine(
+                            workspace.and_then(|workspace| {
+                                workspace.globals.http_pool_max_idle_per_host
+                            }),
+                        )
+                        .unwrap_or(defaults.pool_max_idle_per_host),
+                    pool_idle_timeout: env(env::HTTP_POOL_IDLE_TIMEOUT)
+                        .combine(
+                            workspace
+                                .and_then(|workspace| workspace.globals.http_pool_idle_timeout),
+                        )
+                        .map_or(defaults.pool_idle_timeout, Duration::from_secs),
+                    tcp_keepalive: env(env::HTTP_TCP_KEEPALIVE)
+                        .combine(
+                            workspace.and_then(|workspace| workspace.globals.http_tcp_keepalive),
+                        )
+                        .map(Duration::from_secs),
+                }
+            },
+            index_verification: args
+                .index_verification
+                .combine(workspace.and_then(|workspace| workspace.globals.index_verification))
+                .unwrap_or_default(),
             connectivity: if flag(args.offline, args.no_offline)
                 .combine(workspace.and_then(|workspace| workspace.globals.offline))
                 .unwrap_or(false)
@@ -122,6 +163,14 @@ impl GlobalSettings {
                 .combine(workspace.and_then(|workspace| workspace.globals.python_downloads))
                 .unwrap_or_default(),
             no_progress: args.no_progress,
+            progress_format: args.progress_format.unwrap_or_default(),
+            lock_timeout: env(env::LOCK_TIMEOUT)
+                .combine(workspace.and_then(|workspace| workspace.globals.lock_timeout))
+                .map(Duration::from_secs),
+            repair_wheel_command: args
+                .repair_wheel_command
+                .clone()
+                .filter(|command| !command.is_empty()),
         }
     }
 }
@@ -132,6 +181,9 @@ impl GlobalSettings {
 pub(crate) struct CacheSettings {
     pub(crate) no_cache: bool,
     pub(crate) cache_dir: Option<PathBuf>,
+    pub(crate) cache_buckets: BTreeMap<CacheBucket, PathBuf>,
+    pub(crate) staging_dir: Option<PathBuf>,
+    pub(crate) no_interpreter_cache: bool,
 }
 
 impl CacheSettings {
@@ -145,6 +197,13 @@ impl CacheSettings {
             cache_dir: args
                 .cache_dir
                 .or_else(|| workspace.and_then(|workspace| workspace.globals.cache_dir.clone())),
+            cache_buckets: workspace
+                .and_then(|workspace| workspace.globals.cache_buckets.clone())
+                .unwrap_or_default(),
+            staging_dir: args
+                .staging_dir
+                .or_else(|| workspace.and_then(|workspace| workspace.globals.staging_dir.clone())),
+            no_interpreter_cache: args.no_interpreter_cache,
         }
     }
 }
@@ -158,6 +217,7 @@ pub(crate) struct InitSettings {
     pub(crate) r#virtual: bool,
     pub(crate) no_readme: bool,
     pub(crate) no_workspace: bool,
+    pub(crate) build_backend: BuildBackend,
     pub(crate) python: Option<String>,
 }
 
@@ -171,6 +231,7 @@ impl InitSettings {
             r#virtual,
             no_readme,
             no_workspace,
+            build_backend,
             python,
         } = args;
 
@@ -180,6 +241,7 @@ impl InitSettings {
             r#virtual,
             no_readme,
             no_workspace,
+            build_backend: build_backend.unwrap_or_default(),
             python,
         }
     }
@@ -197,10 +259,13 @@ pub(crate) struct RunSettings {
     pub(crate) with: Vec<String>,
     pub(crate) with_requirements: Vec<PathBuf>,
     pub(crate) isolated: bool,
+    pub(crate) module_path: Option<Vec<ModulePath>>,
     pub(crate) show_resolution: bool,
     pub(crate) package: Option<PackageName>,
     pub(crate) no_project: bool,
+    pub(crate) env: Option<String>,
     pub(crate) python: Option<String>,
+    pub(crate) all_pythons: bool,
     pub(crate) refresh: Refresh,
     pub(crate) settings: ResolverInstallerSettings,
 }
@@ -219,6 +284,8 @@ impl RunSettings {
             with,
             with_requirements,
             isolated,
+            module_path,
+            no_module_path,
             locked,
             frozen,
             installer,
@@ -226,7 +293,9 @@ impl RunSettings {
             refresh,
             package,
             no_project,
+            env,
             python,
+            all_pythons,
             show_resolution,
         } = args;
 
@@ -245,10 +314,17 @@ impl RunSettings {
                 .filter_map(Maybe::into_option)
                 .collect(),
             isolated,
+            module_path: if no_module_path {
+                Some(Vec::new())
+            } else {
+                module_path
+            },
             show_resolution,
             package,
             no_project,
+            env,
             python,
+            all_pythons,
             refresh: Refresh::from(refresh),
             settings: ResolverInstallerSettings::combine(
                 resolver_installer_options(installer, build),
@@ -267,7 +343,12 @@ pub(crate) struct ToolRunSettings {
     pub(crate) with: Vec<String>,
     pub(crate) with_requirements: Vec<PathBuf>,
     pub(crate) isolated: bool,
+    pub(crate) project_constraints: bool,
+    pub(crate) detach: bool,
+    pub(crate) cache_days: Option<u64>,
+    pub(crate) cache_max_size: Option<u64>,
     pub(crate) show_resolution: bool,
+    pub(crate) typosquat_check: TyposquatCheck,
     pub(crate) python: Option<String>,
     pub(crate) refresh: Refresh,
     pub(crate) settings: ResolverInstallerSettings,
@@ -283,7 +364,12 @@ impl ToolRunSettings {
             with,
             with_requirements,
             isolated,
+            project_constraints,
+            detach,
+            cache_days,
+            cache_max_size,
             show_resolution,
+            typosquat_check,
             installer,
             build,
             refresh,
@@ -299,7 +385,12 @@ impl ToolRunSettings {
                 .filter_map(Maybe::into_option)
                 .collect(),
             isolated,
+            project_constraints,
+            detach,
+            cache_days,
+            cache_max_size,
             show_resolution,
+            typosquat_check: typosquat_check.unwrap_or_default(),
             python,
             refresh: Refresh::from(refresh),
             settings: ResolverInstallerSettings::combine(
@@ -318,12 +409,23 @@ pub(crate) struct ToolInstallSettings {
     pub(crate) from: Option<String>,
     pub(crate) with: Vec<String>,
     pub(crate) with_requirements: Vec<PathBuf>,
+    pub(crate) constraint: Vec<PathBuf>,
+    pub(crate) r#override: Vec<PathBuf>,
     pub(crate) python: Option<String>,
     pub(crate) refresh: Refresh,
     pub(crate) options: ResolverInstallerOptions,
     pub(crate) settings: ResolverInstallerSettings,
     pub(crate) force: bool,
+    pub(crate) locked: bool,
+    pub(crate) from_lock: Option<PathBuf>,
+    pub(crate) suffix: Option<String>,
+    pub(crate) entrypoint: Vec<String>,
+    pub(crate) exclude_entrypoint: Vec<String>,
+    pub(crate) include_deps: bool,
+    pub(crate) read_only_env: bool,
+    pub(crate) env: BTreeMap<String, String>,
     pub(crate) editable: bool,
+    pub(crate) format: ToolFormat,
 }
 
 impl ToolInstallSettings {
@@ -336,11 +438,22 @@ impl ToolInstallSettings {
             from,
             with,
             with_requirements,
+            constraint,
+            r#override,
             installer,
             force,
+            locked,
+            from_lock,
+            suffix,
+            entrypoint,
+            exclude_entrypoint,
+            include_deps,
+            read_only_env,
+            env,
             build,
             refresh,
             python,
+            format,
         } = args;
 
         let options = resolver_installer_options(installer, build).combine(
@@ -360,9 +473,26 @@ impl ToolInstallSettings {
                 .into_iter()
                 .filter_map(Maybe::into_option)
                 .collect(),
+            constraint: constraint
+                .into_iter()
+                .filter_map(Maybe::into_option)
+                .collect(),
+            r#override: r#override
+                .into_iter()
+                .filter_map(Maybe::into_option)
+                .collect(),
             python,
             force,
+            locked,
+            from_lock,
+            suffix,
+            entrypoint,
+            exclude_entrypoint,
+            include_deps,
+            read_only_env,
+            env: env.into_iter().collect(),
             editable,
+            format,
             refresh: Refresh::from(refresh),
             options,
             settings,
@@ -374,9 +504,18 @@ impl ToolInstallSettings {
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
 pub(crate) struct ToolUpgradeSettings {
-    pub(crate) name: Option<PackageName>,
+    pub(crate) name: Option<ToolName>,
+    pub(crate) with: Vec<String>,
+    pub(crate) without: Vec<PackageName>,
+    pub(crate) frozen: bool,
+    pub(crate) dry_run: bool,
+    pub(crate) python: Option<String>,
+    pub(crate) format: ToolFormat,
+    pub(crate) refresh: Refresh,
     pub(crate) args: ResolverInstallerOptions,
     pub(crate) filesystem: ResolverInstallerOptions,
+    pub(crate) concurrency: Concurrency,
+    pub(crate) concurrent_upgrades: usize,
 }
 
 impl ToolUpgradeSettings {
@@ -386,28 +525,64 @@ impl ToolUpgradeSettings {
         let ToolUpgradeArgs {
             name,
             all,
+            with,
+            without,
+            frozen,
+            dry_run,
+            concurrent_upgrades,
+            python,
+            format,
             mut installer,
             build,
+            refresh,
         } = args;
 
-        if installer.upgrade {
-            // If `--upgrade` was passed explicitly, warn.
-            warn_user_once!("`--upgrade` is enabled by default on `uv tool upgrade`");
-        } else if installer.upgrade_package.is_empty() {
-            // If neither `--upgrade` nor `--upgrade-package` were passed in, assume `--upgrade`.
-            installer.upgrade = true;
+        // With `--frozen`, the environment is restored from the lockfile rather than upgraded.
+        if !frozen {
+            if installer.upgrade {
+                // If `--upgrade` was passed explicitly, warn.
+                warn_user_once!("`--upgrade` is enabled by default on `uv tool upgrade`");
+            } else if installer.upgrade_package.is_empty() {
+                // If neither `--upgrade` nor `--upgrade-package` were passed in, assume `--upgrade`.
+                installer.upgrade = true;
+            }
         }
 
         let args = resolver_installer_options(installer, build);
-        let filesystem = filesystem
-            .map(FilesystemOptions::into_options)
-            .map(|options| options.top_level)
-            .unwrap_or_default();
+        let options = filesystem.map(FilesystemOptions::into_options);
+        let concurrent_upgrades = concurrent_upgrades
+            .combine(
+                options
+                    .as_ref()
+                    .and_then(|options| options.globals.concurrent_tool_upgrades),
+            )
+            .map(NonZeroUsize::get)
+            .unwrap_or_else(Concurrency::threads);
+        let filesystem = options.map(|options| options.top_level).unwrap_or_default();
 
         Self {
             name: name.filter(|_| !all),
+            with,
+            without,
+            frozen,
+            dry_run,
+            python,
+            format,
+            refresh: Refresh::from(refresh),
             args,
             filesystem,
+            concurrency: Concurrency {
+                downloads: env(env::CONCURRENT_DOWNLOADS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or(Concurrency::DEFAULT_DOWNLOADS),
+                builds: env(env::CONCURRENT_BUILDS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or_else(Concurrency::threads),
+                installs: env(env::CONCURRENT_INSTALLS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or_else(Concurrency::threads),
+            },
+            concurrent_upgrades,
         }
     }
 }
@@ -417,15 +592,16 @@ impl ToolUpgradeSettings {
 #[derive(Debug, Clone)]
 pub(crate) struct ToolListSettings {
     pub(crate) show_paths: bool,
+    pub(crate) format: ToolFormat,
 }
 
 impl ToolListSettings {
     /// Resolve the [`ToolListSettings`] from the CLI and filesystem configuration.
     #[allow(clippy::needless_pass_by_value)]
     pub(crate) fn resolve(args: ToolListArgs, _filesystem: Option<FilesystemOptions>) -> Self {
-        let ToolListArgs { show_paths } = args;
+        let ToolListArgs { show_paths, format } = args;
 
-        Self { show_paths }
+        Self { show_paths, format }
     }
 }
 
@@ -433,21 +609,98 @@ impl ToolListSettings {
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
 pub(crate) struct ToolUninstallSettings {
-    pub(crate) name: Option<PackageName>,
+    pub(crate) name: Option<ToolName>,
+    pub(crate) dry_run: bool,
 }
 
 impl ToolUninstallSettings {
     /// Resolve the [`ToolUninstallSettings`] from the CLI and filesystem configuration.
     #[allow(clippy::needless_pass_by_value)]
     pub(crate) fn resolve(args: ToolUninstallArgs, _filesystem: Option<FilesystemOptions>) -> Self {
-        let ToolUninstallArgs { name, all } = args;
+        let ToolUninstallArgs { name, all, dry_run } = args;
 
         Self {
             name: name.filter(|_| !all),
+            dry_run,
         }
     }
 }
 
+/// The resolved settings to use for a `tool show` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct ToolShowSettings {
+    pub(crate) name: ToolName,
+    pub(crate) format: ToolShowFormat,
+}
+
+impl ToolShowSettings {
+    /// Resolve the [`ToolShowSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: ToolShowArgs, _filesystem: Option<FilesystemOptions>) -> Self {
+        let ToolShowArgs { name, format } = args;
+
+        Self { name, format }
+    }
+}
+
+/// The resolved settings to use for a `tool repair` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct ToolRepairSettings {
+    pub(crate) name: Option<ToolName>,
+    pub(crate) filesystem: ResolverInstallerOptions,
+}
+
+impl ToolRepairSettings {
+    /// Resolve the [`ToolRepairSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: ToolRepairArgs, filesystem: Option<FilesystemOptions>) -> Self {
+        let ToolRepairArgs { name, all } = args;
+
+        let filesystem = filesystem
+            .map(FilesystemOptions::into_options)
+            .map(|options| options.top_level)
+            .unwrap_or_default();
+
+        Self {
+            name: name.filter(|_| !all),
+            filesystem,
+        }
+    }
+}
+
+/// The resolved settings to use for a `tool prune` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct ToolPruneSettings {
+    pub(crate) dry_run: bool,
+}
+
+impl ToolPruneSettings {
+    /// Resolve the [`ToolPruneSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: ToolPruneArgs, _filesystem: Option<FilesystemOptions>) -> Self {
+        let ToolPruneArgs { dry_run } = args;
+
+        Self { dry_run }
+    }
+}
+
+/// The resolved settings to use for a `tool kill` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct ToolKillSettings {
+    pub(crate) pids: Vec<u32>,
+    pub(crate) all: bool,
+}
+
+impl ToolKillSettings {
+    /// Resolve the [`ToolKillSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: ToolKillArgs, _filesystem: Option<FilesystemOptions>) -> Self {
+        let ToolKillArgs { pid, all } = args;
+
+        Self { pids: pid, all }
+    }
+}
+
 /// The resolved settings to use for a `tool dir` invocation.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
@@ -465,11 +718,31 @@ impl ToolDirSettings {
     }
 }
 
+/// The resolved settings to use for a `tool update-shell` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct ToolUpdateShellSettings {
+    pub(crate) dry_run: bool,
+}
+
+impl ToolUpdateShellSettings {
+    /// Resolve the [`ToolUpdateShellSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(
+        args: ToolUpdateShellArgs,
+        _filesystem: Option<FilesystemOptions>,
+    ) -> Self {
+        let ToolUpdateShellArgs { dry_run } = args;
+
+        Self { dry_run }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub(crate) enum PythonListKinds {
     #[default]
     Default,
     Installed,
+    Downloads,
 }
 
 /// The resolved settings to use for a `tool run` invocation.
@@ -479,6 +752,8 @@ pub(crate) struct PythonListSettings {
     pub(crate) kinds: PythonListKinds,
     pub(crate) all_platforms: bool,
     pub(crate) all_versions: bool,
+    pub(crate) json: bool,
+    pub(crate) refresh: Refresh,
 }
 
 impl PythonListSettings {
@@ -489,10 +764,15 @@ impl PythonListSettings {
             all_versions,
             all_platforms,
             only_installed,
+            only_downloads,
+            json,
+            refresh,
         } = args;
 
         let kinds = if only_installed {
             PythonListKinds::Installed
+        } else if only_downloads {
+            PythonListKinds::Downloads
         } else {
             PythonListKinds::default()
         };
@@ -501,6 +781,8 @@ impl PythonListSettings {
             kinds,
             all_platforms,
             all_versions,
+            json,
+            refresh: Refresh::from(refresh),
         }
     }
 }
@@ -511,15 +793,39 @@ impl PythonListSettings {
 pub(crate) struct PythonInstallSettings {
     pub(crate) targets: Vec<String>,
     pub(crate) reinstall: bool,
+    pub(crate) ensurepip: bool,
+    pub(crate) sitecustomize: Option<PathBuf>,
+    pub(crate) safe_path: bool,
+    pub(crate) allow_emulated: bool,
 }
 
 impl PythonInstallSettings {
     /// Resolve the [`PythonInstallSettings`] from the CLI and filesystem configuration.
     #[allow(clippy::needless_pass_by_value)]
-    pub(crate) fn resolve(args: PythonInstallArgs, _filesystem: Option<FilesystemOptions>) -> Self {
-        let PythonInstallArgs { targets, reinstall } = args;
+    pub(crate) fn resolve(args: PythonInstallArgs, filesystem: Option<FilesystemOptions>) -> Self {
+        let PythonInstallArgs {
+            targets,
+            reinstall,
+            no_ensurepip,
+            sitecustomize,
+            safe_path,
+            allow_emulated,
+        } = args;
+
+        let globals = filesystem
+            .map(FilesystemOptions::into_options)
+            .map(|options| options.globals)
+            .unwrap_or_default();
 
-        Self { targets, reinstall }
+        Self {
+            targets,
+            reinstall,
+            ensurepip: !no_ensurepip && globals.python_install_ensurepip.unwrap_or(true),
+            sitecustomize: sitecustomize.or(globals.python_install_sitecustomize),
+            safe_path: safe_path || globals.python_install_safe_path.unwrap_or(false),
+            allow_emulated: allow_emulated
+                || globals.python_install_allow_emulated.unwrap_or(false),
+        }
     }
 }
 
@@ -549,15 +855,19 @@ impl PythonUninstallSettings {
 #[derive(Debug, Clone)]
 pub(crate) struct PythonFindSettings {
     pub(crate) request: Option<String>,
+    pub(crate) refresh: Refresh,
 }
 
 impl PythonFindSettings {
     /// Resolve the [`PythonFindSettings`] from the CLI and workspace configuration.
     #[allow(clippy::needless_pass_by_value)]
     pub(crate) fn resolve(args: PythonFindArgs, _filesystem: Option<FilesystemOptions>) -> Self {
-        let PythonFindArgs { request } = args;
+        let PythonFindArgs { request, refresh } = args;
 
-        Self { request }
+        Self {
+            request,
+            refresh: Refresh::from(refresh),
+        }
     }
 }
 
@@ -566,8 +876,9 @@ impl PythonFindSettings {
 #[derive(Debug, Clone)]
 pub(crate) struct PythonPinSettings {
     pub(crate) request: Option<String>,
-    pub(crate) resolved: bool,
+    pub(crate) resolved: Option<ResolvedPin>,
     pub(crate) no_workspace: bool,
+    pub(crate) refresh: Refresh,
 }
 
 impl PythonPinSettings {
@@ -579,12 +890,14 @@ impl PythonPinSettings {
             no_resolved,
             resolved,
             no_workspace,
+            refresh,
         } = args;
 
         Self {
             request,
-            resolved: flag(resolved, no_resolved).unwrap_or(false),
+            resolved: resolved.filter(|_| !no_resolved),
             no_workspace,
+            refresh: Refresh::from(refresh),
         }
     }
 }
@@ -596,8 +909,17 @@ pub(crate) struct SyncSettings {
     pub(crate) frozen: bool,
     pub(crate) extras: ExtrasSpecification,
     pub(crate) dev: bool,
+    pub(crate) group: Vec<GroupName>,
     pub(crate) modifications: Modifications,
+    pub(crate) verify_imports: bool,
+    pub(crate) reinstall_editables: bool,
+    pub(crate) summary: InstallSummary,
+    pub(crate) show_sizes: bool,
+    pub(crate) read_only_env: bool,
+    pub(crate) editor_config: Option<Vec<EditorConfig>>,
     pub(crate) package: Option<PackageName>,
+    pub(crate) only_affected_by: Option<Vec<PathBuf>>,
+    pub(crate) env: Option<String>,
     pub(crate) python: Option<String>,
     pub(crate) refresh: Refresh,
     pub(crate) settings: ResolverInstallerSettings,
@@ -615,11 +937,21 @@ impl SyncSettings {
             no_all_extras,
             dev,
             no_dev,
+            group,
             no_clean,
+            verify_imports,
+            reinstall_editables,
+            summary,
+            show_sizes,
+            read_only_env,
+            editor_config,
+            no_editor_config,
             installer,
             build,
             refresh,
             package,
+            only_affected_by,
+            env,
             python,
         } = args;
 
@@ -644,8 +976,21 @@ impl SyncSettings {
                 extra.unwrap_or_default(),
             ),
             dev: flag(dev, no_dev).unwrap_or(true),
+            group,
             modifications,
+            verify_imports,
+            reinstall_editables,
+            summary,
+            show_sizes,
+            read_only_env,
+            editor_config: if no_editor_config {
+                Some(Vec::new())
+            } else {
+                editor_config
+            },
             package,
+            only_affected_by,
+            env,
             python,
             refresh: Refresh::from(refresh),
             settings,
@@ -653,12 +998,183 @@ impl SyncSettings {
     }
 }
 
+/// The resolved settings to use for a `test` invocation.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone)]
+pub(crate) struct TestSettings {
+    pub(crate) command: Option<ExternalCommand>,
+    pub(crate) group: Vec<GroupName>,
+    pub(crate) fail_fast: bool,
+    pub(crate) locked: bool,
+    pub(crate) frozen: bool,
+    pub(crate) python: Vec<String>,
+    pub(crate) refresh: Refresh,
+    pub(crate) settings: ResolverInstallerSettings,
+    pub(crate) concurrency: Concurrency,
+}
+
+impl TestSettings {
+    /// Resolve the [`TestSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: TestArgs, filesystem: Option<FilesystemOptions>) -> Self {
+        let TestArgs {
+            command,
+            group,
+            fail_fast,
+            locked,
+            frozen,
+            installer,
+            build,
+            refresh,
+            python,
+        } = args;
+
+        Self {
+            command,
+            group,
+            fail_fast,
+            locked,
+            frozen,
+            python,
+            refresh: Refresh::from(refresh),
+            settings: ResolverInstallerSettings::combine(
+                resolver_installer_options(installer, build),
+                filesystem,
+            ),
+            concurrency: Concurrency {
+                downloads: env(env::CONCURRENT_DOWNLOADS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or(Concurrency::DEFAULT_DOWNLOADS),
+                builds: env(env::CONCURRENT_BUILDS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or_else(Concurrency::threads),
+                installs: env(env::CONCURRENT_INSTALLS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or_else(Concurrency::threads),
+            },
+        }
+    }
+}
+
+/// The resolved settings to use for a `vendor` invocation.
+#[allow(clippy::struct_excessive_bools, dead_code)]
+#[derive(Debug, Clone)]
+pub(crate) struct VendorSettings {
+    pub(crate) locked: bool,
+    pub(crate) frozen: bool,
+    pub(crate) extras: ExtrasSpecification,
+    pub(crate) dev: bool,
+    pub(crate) output_dir: PathBuf,
+    pub(crate) python: Option<String>,
+    pub(crate) refresh: Refresh,
+    pub(crate) settings: ResolverInstallerSettings,
+}
+
+impl VendorSettings {
+    /// Resolve the [`VendorSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: VendorArgs, filesystem: Option<FilesystemOptions>) -> Self {
+        let VendorArgs {
+            extra,
+            all_extras,
+            no_all_extras,
+            dev,
+            no_dev,
+            output_dir,
+            locked,
+            frozen,
+            installer,
+            build,
+            refresh,
+            python,
+        } = args;
+
+        Self {
+            locked,
+            frozen,
+            extras: ExtrasSpecification::from_args(
+                flag(all_extras, no_all_extras).unwrap_or_default(),
+                extra.unwrap_or_default(),
+            ),
+            dev: flag(dev, no_dev).unwrap_or(false),
+            output_dir,
+            python,
+            refresh: Refresh::from(refresh),
+            settings: ResolverInstallerSettings::combine(
+                resolver_installer_options(installer, build),
+                filesystem,
+            ),
+        }
+    }
+}
+
+/// The resolved settings to use for an `export` invocation.
+#[allow(clippy::struct_excessive_bools, dead_code)]
+#[derive(Debug, Clone)]
+pub(crate) struct ExportSettings {
+    pub(crate) format: ExportFormat,
+    pub(crate) extras: ExtrasSpecification,
+    pub(crate) dev: bool,
+    pub(crate) group: Vec<GroupName>,
+    pub(crate) output_file: Option<PathBuf>,
+    pub(crate) locked: bool,
+    pub(crate) frozen: bool,
+    pub(crate) python: Option<String>,
+    pub(crate) refresh: Refresh,
+    pub(crate) settings: ResolverSettings,
+}
+
+impl ExportSettings {
+    /// Resolve the [`ExportSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: ExportArgs, filesystem: Option<FilesystemOptions>) -> Self {
+        let ExportArgs {
+            format,
+            extra,
+            all_extras,
+            no_all_extras,
+            dev,
+            no_dev,
+            group,
+            output_file,
+            locked,
+            frozen,
+            resolver,
+            build,
+            refresh,
+            python,
+        } = args;
+
+        Self {
+            format,
+            extras: ExtrasSpecification::from_args(
+                flag(all_extras, no_all_extras).unwrap_or_default(),
+                extra.unwrap_or_default(),
+            ),
+            dev: flag(dev, no_dev).unwrap_or(false),
+            group,
+            output_file,
+            locked,
+            frozen,
+            python,
+            refresh: Refresh::from(refresh),
+            settings: ResolverSettings::combine(resolver_options(resolver, build), filesystem),
+        }
+    }
+}
+
 /// The resolved settings to use for a `lock` invocation.
 #[allow(clippy::struct_excessive_bools, dead_code)]
 #[derive(Debug, Clone)]
 pub(crate) struct LockSettings {
     pub(crate) locked: bool,
     pub(crate) frozen: bool,
+    pub(crate) verify_determinism: Option<u32>,
+    pub(crate) shuffle: bool,
+    pub(crate) prune_platforms: Option<Vec<SysPlatform>>,
+    pub(crate) suggest_requires_python: bool,
+    pub(crate) restore: bool,
+    pub(crate) script: Option<PathBuf>,
     pub(crate) python: Option<String>,
     pub(crate) refresh: Refresh,
     pub(crate) settings: ResolverSettings,
@@ -671,15 +1187,83 @@ impl LockSettings {
         let LockArgs {
             locked,
             frozen,
+            verify_determinism,
+            shuffle,
+            prune_platform,
+            no_prune_platform,
+            suggest_requires_python,
+            restore,
+            script,
             resolver,
             build,
             refresh,
             python,
         } = args;
 
+        // Unless requested, retain any platforms that were pruned from the existing lockfile.
+        let prune_platforms = if no_prune_platform {
+            Some(vec![])
+        } else if prune_platform.is_empty() {
+            None
+        } else {
+            Some(prune_platform)
+        };
+
         Self {
             locked,
             frozen,
+            verify_determinism,
+            shuffle,
+            prune_platforms,
+            suggest_requires_python,
+            restore,
+            script,
+            python,
+            refresh: Refresh::from(refresh),
+            settings: ResolverSettings::combine(resolver_options(resolver, build), filesystem),
+        }
+    }
+}
+
+/// The resolved settings to use for a `build` invocation.
+#[allow(clippy::struct_excessive_bools, dead_code)]
+#[derive(Debug, Clone)]
+pub(crate) struct BuildSettings {
+    pub(crate) src: Option<PathBuf>,
+    pub(crate) all: bool,
+    pub(crate) sdist: bool,
+    pub(crate) wheel: bool,
+    pub(crate) out_dir: Option<PathBuf>,
+    pub(crate) locked: bool,
+    pub(crate) python: Option<String>,
+    pub(crate) refresh: Refresh,
+    pub(crate) settings: ResolverSettings,
+}
+
+impl BuildSettings {
+    /// Resolve the [`BuildSettings`] from the CLI and filesystem configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: ProjectBuildArgs, filesystem: Option<FilesystemOptions>) -> Self {
+        let ProjectBuildArgs {
+            src,
+            all,
+            sdist,
+            wheel,
+            out_dir,
+            locked,
+            resolver,
+            build,
+            refresh,
+            python,
+        } = args;
+
+        Self {
+            src,
+            all,
+            sdist,
+            wheel,
+            out_dir,
+            locked,
             python,
             refresh: Refresh::from(refresh),
             settings: ResolverSettings::combine(resolver_options(resolver, build), filesystem),
@@ -694,15 +1278,20 @@ pub(crate) struct AddSettings {
     pub(crate) locked: bool,
     pub(crate) frozen: bool,
     pub(crate) no_sync: bool,
-    pub(crate) requirements: Vec<RequirementsSource>,
-    pub(crate) dependency_type: DependencyType,
+    pub(crate) suggest_requires_python: bool,
+    pub(crate) fix_requires_python: Option<RequiresPythonFix>,
+    pub(crate) requirements: Vec<(RequirementsSource, DependencyType)>,
+    pub(crate) sync_manifest: bool,
     pub(crate) editable: Option<bool>,
     pub(crate) extras: Vec<ExtraName>,
+    pub(crate) marker: Option<MarkerTree>,
     pub(crate) raw_sources: bool,
+    pub(crate) raw: bool,
     pub(crate) rev: Option<String>,
     pub(crate) tag: Option<String>,
     pub(crate) branch: Option<String>,
     pub(crate) package: Option<PackageName>,
+    pub(crate) typosquat_check: TyposquatCheck,
     pub(crate) python: Option<String>,
     pub(crate) refresh: Refresh,
     pub(crate) settings: ResolverInstallerSettings,
@@ -713,31 +1302,35 @@ impl AddSettings {
     #[allow(clippy::needless_pass_by_value)]
     pub(crate) fn resolve(args: AddArgs, filesystem: Option<FilesystemOptions>) -> Self {
         let AddArgs {
+            packages,
             requirements,
+            dev_requirements,
+            optional_requirements,
+            sync_manifest,
             dev,
             optional,
             editable,
             no_editable,
             extra,
+            marker,
             raw_sources,
+            raw,
             rev,
             tag,
             branch,
             no_sync,
             locked,
             frozen,
+            suggest_requires_python,
+            fix_requires_python,
             installer,
             build,
             refresh,
             package,
+            typosquat_check,
             python,
         } = args;
 
-        let requirements = requirements
-            .into_iter()
-            .map(RequirementsSource::Package)
-            .collect::<Vec<_>>();
-
         let dependency_type = if let Some(group) = optional {
             DependencyType::Optional(group)
         } else if dev {
@@ -746,20 +1339,49 @@ impl AddSettings {
             DependencyType::Production
         };
 
+        // Classify each source by the dependency group it's added to.
+        let requirements = packages
+            .into_iter()
+            .map(RequirementsSource::Package)
+            .chain(
+                requirements
+                    .into_iter()
+                    .map(RequirementsSource::from_requirements_file),
+            )
+            .map(|source| (source, dependency_type.clone()))
+            .chain(dev_requirements.into_iter().map(|path| {
+                (
+                    RequirementsSource::from_requirements_file(path),
+                    DependencyType::Dev,
+                )
+            }))
+            .chain(optional_requirements.into_iter().map(|requirements| {
+                (
+                    RequirementsSource::from_requirements_file(requirements.path),
+                    DependencyType::Optional(requirements.extra),
+                )
+            }))
+            .collect::<Vec<_>>();
+
         Self {
             locked,
             frozen,
             no_sync,
+            suggest_requires_python,
+            fix_requires_python,
             requirements,
-            dependency_type,
+            sync_manifest,
             raw_sources,
+            raw,
             rev,
             tag,
             branch,
             package,
+            typosquat_check: typosquat_check.unwrap_or_default(),
             python,
             editable: flag(editable, no_editable),
             extras: extra.unwrap_or_default(),
+            marker,
             refresh: Refresh::from(refresh),
             settings: ResolverInstallerSettings::combine(
                 resolver_installer_options(installer, build),
@@ -839,9 +1461,11 @@ pub(crate) struct TreeSettings {
     pub(crate) package: Vec<PackageName>,
     pub(crate) no_dedupe: bool,
     pub(crate) invert: bool,
+    pub(crate) format: TreeFormat,
     pub(crate) python_version: Option<PythonVersion>,
     pub(crate) python_platform: Option<TargetTriple>,
     pub(crate) python: Option<String>,
+    pub(crate) refresh: Refresh,
     pub(crate) resolver: ResolverSettings,
 }
 
@@ -850,11 +1474,13 @@ impl TreeSettings {
     pub(crate) fn resolve(args: TreeArgs, filesystem: Option<FilesystemOptions>) -> Self {
         let TreeArgs {
             tree,
+            format,
             universal,
             locked,
             frozen,
             build,
             resolver,
+            refresh,
             python_version,
             python_platform,
             python,
@@ -869,23 +1495,159 @@ impl TreeSettings {
             package: tree.package,
             no_dedupe: tree.no_dedupe,
             invert: tree.invert,
+            format,
+            python_version,
+            python_platform,
+            python,
+            refresh: Refresh::from(refresh),
+            resolver: ResolverSettings::combine(resolver_options(resolver, build), filesystem),
+        }
+    }
+}
+
+/// The resolved settings to use for a `why` invocation.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone)]
+pub(crate) struct WhySettings {
+    pub(crate) package: PackageName,
+    pub(crate) locked: bool,
+    pub(crate) frozen: bool,
+    pub(crate) universal: bool,
+    pub(crate) python_version: Option<PythonVersion>,
+    pub(crate) python_platform: Option<TargetTriple>,
+    pub(crate) python: Option<String>,
+    pub(crate) refresh: Refresh,
+    pub(crate) resolver: ResolverSettings,
+}
+
+impl WhySettings {
+    /// Resolve the [`WhySettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: WhyArgs, filesystem: Option<FilesystemOptions>) -> Self {
+        let WhyArgs {
+            package,
+            universal,
+            locked,
+            frozen,
+            build,
+            resolver,
+            refresh,
+            python_version,
+            python_platform,
+            python,
+        } = args;
+
+        Self {
+            package,
+            locked,
+            frozen,
+            universal,
             python_version,
             python_platform,
+            python,
+            refresh: Refresh::from(refresh),
+            resolver: ResolverSettings::combine(resolver_options(resolver, build), filesystem),
+        }
+    }
+}
+
+/// The resolved settings to use for a `server` invocation.
+#[derive(Debug, Clone)]
+pub(crate) struct ServerSettings {
+    pub(crate) python: Option<String>,
+    pub(crate) resolver: ResolverSettings,
+    pub(crate) concurrency: Concurrency,
+}
+
+impl ServerSettings {
+    /// Resolve the [`ServerSettings`] from the CLI and workspace configuration.
+    pub(crate) fn resolve(args: ServerArgs, filesystem: Option<FilesystemOptions>) -> Self {
+        let ServerArgs {
+            build,
+            resolver,
+            python,
+        } = args;
+
+        Self {
             python,
             resolver: ResolverSettings::combine(resolver_options(resolver, build), filesystem),
+            concurrency: Concurrency {
+                downloads: env(env::CONCURRENT_DOWNLOADS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or(Concurrency::DEFAULT_DOWNLOADS),
+                builds: env(env::CONCURRENT_BUILDS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or_else(Concurrency::threads),
+                installs: env(env::CONCURRENT_INSTALLS)
+                    .map(NonZeroUsize::get)
+                    .unwrap_or_else(Concurrency::threads),
+            },
         }
     }
 }
+
+/// The resolved settings to use for a `workspace list` invocation.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone)]
+pub(crate) struct WorkspaceListSettings {
+    pub(crate) affected_by: Vec<PackageName>,
+    pub(crate) json: bool,
+}
+
+impl WorkspaceListSettings {
+    /// Resolve the [`WorkspaceListSettings`] from the CLI and workspace configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: WorkspaceListArgs, _filesystem: Option<FilesystemOptions>) -> Self {
+        let WorkspaceListArgs { affected_by, json } = args;
+
+        Self { affected_by, json }
+    }
+}
+
+/// The resolved settings to use for a `workspace tree` invocation.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone)]
+pub(crate) struct WorkspaceTreeSettings {
+    pub(crate) package: Vec<PackageName>,
+    pub(crate) invert: bool,
+    pub(crate) external: bool,
+}
+
+impl WorkspaceTreeSettings {
+    /// Resolve the [`WorkspaceTreeSettings`] from the CLI and workspace configuration.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn resolve(args: WorkspaceTreeArgs, _filesystem: Option<FilesystemOptions>) -> Self {
+        let WorkspaceTreeArgs {
+            package,
+            invert,
+            external,
+        } = args;
+
+        Self {
+            package,
+            invert,
+            external,
+        }
+    }
+}
+
 /// The resolved settings to use for a `pip compile` invocation.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
 pub(crate) struct PipCompileSettings {
     pub(crate) src_file: Vec<PathBuf>,
     pub(crate) constraint: Vec<PathBuf>,
+    pub(crate) constraint_marker: Vec<(PathBuf, MarkerTree)>,
     pub(crate) r#override: Vec<PathBuf>,
     pub(crate) constraints_from_workspace: Vec<Requirement>,
     pub(crate) overrides_from_workspace: Vec<Requirement>,
     pub(crate) build_constraint: Vec<PathBuf>,
+    pub(crate) group: Vec<GroupName>,
+    pub(crate) marker: Option<MarkerTree>,
+    pub(crate) target_environment: Option<PathBuf>,
+    pub(crate) require_index: Vec<RequiredIndex>,
+    pub(crate) omit: Vec<PackageName>,
+    pub(crate) only_direct: bool,
+    pub(crate) resolver_trace: Option<PathBuf>,
     pub(crate) refresh: Refresh,
     pub(crate) settings: PipSettings,
 }
@@ -896,15 +1658,19 @@ impl PipCompileSettings {
         let PipCompileArgs {
             src_file,
             constraint,
+            constraint_marker,
             r#override,
             extra,
             all_extras,
             no_all_extras,
+            group,
+            marker,
             build_constraint,
             refresh,
             no_deps,
             deps,
             output_file,
+            only_direct,
             no_strip_extras,
             strip_extras,
             no_strip_markers,
@@ -921,6 +1687,7 @@ impl PipCompileSettings {
             no_system,
             generate_hashes,
             no_generate_hashes,
+            index_hash_policy,
             legacy_setup_py,
             no_legacy_setup_py,
             no_build,
@@ -929,6 +1696,8 @@ impl PipCompileSettings {
             only_binary,
             python_version,
             python_platform,
+            macos_arch,
+            target_environment,
             universal,
             no_universal,
             no_emit_package,
@@ -942,6 +1711,13 @@ impl PipCompileSettings {
             no_emit_marker_expression,
             emit_index_annotation,
             no_emit_index_annotation,
+            require_index,
+            strict_extras,
+            no_strict_extras,
+            omit,
+            emit_constraint_annotation,
+            no_emit_constraint_annotation,
+            resolver_trace,
             compat_args: _,
         } = args;
 
@@ -979,16 +1755,27 @@ impl PipCompileSettings {
                 .into_iter()
                 .filter_map(Maybe::into_option)
                 .collect(),
+            constraint_marker: constraint_marker
+                .into_iter()
+                .map(|constraints| (constraints.path, constraints.marker))
+                .collect(),
             build_constraint: build_constraint
                 .into_iter()
                 .filter_map(Maybe::into_option)
                 .collect(),
+            group,
+            marker,
+            target_environment,
+            require_index,
+            omit,
+            only_direct,
             r#override: r#override
                 .into_iter()
                 .filter_map(Maybe::into_option)
                 .collect(),
             constraints_from_workspace,
             overrides_from_workspace,
+            resolver_trace,
             refresh: Refresh::from(refresh),
             settings: PipSettings::combine(
                 PipOptions {
@@ -1007,16 +1794,24 @@ impl PipCompileSettings {
                     no_header: flag(no_header, header),
                     custom_compile_command,
                     generate_hashes: flag(generate_hashes, no_generate_hashes),
+                    index_hash_policy: index_hash_policy
+                        .map(|policies| policies.into_iter().collect::<IndexHashPolicies>()),
                     legacy_setup_py: flag(legacy_setup_py, no_legacy_setup_py),
                     python_version,
                     python_platform,
+                    macos_arch,
                     universal: flag(universal, no_universal),
+                    strict_extras: flag(strict_extras, no_strict_extras),
                     no_emit_package,
                     emit_index_url: flag(emit_index_url, no_emit_index_url),
                     emit_find_links: flag(emit_find_links, no_emit_find_links),
                     emit_build_options: flag(emit_build_options, no_emit_build_options),
                     emit_marker_expression: flag(emit_marker_expression, no_emit_marker_expression),
                     emit_index_annotation: flag(emit_index_annotation, no_emit_index_annotation),
+                    emit_constraint_annotation: flag(
+                        emit_constraint_annotation,
+                        no_emit_constraint_annotation,
+                    ),
                     annotation_style,
                     concurrent_builds: env(env::CONCURRENT_BUILDS),
                     concurrent_downloads: env(env::CONCURRENT_DOWNLOADS),
@@ -1036,7 +1831,16 @@ pub(crate) struct PipSyncSettings {
     pub(crate) src_file: Vec<PathBuf>,
     pub(crate) constraint: Vec<PathBuf>,
     pub(crate) build_constraint: Vec<PathBuf>,
+    pub(crate) src: Option<PathBuf>,
+    pub(crate) python_executable: Option<PathBuf>,
+    pub(crate) abi_check: AbiCheck,
+    pub(crate) file_conflicts: FileConflicts,
+    pub(crate) lazy_install: Vec<PackageName>,
+    pub(crate) max_download_size: Option<u64>,
+    pub(crate) verify_imports: bool,
     pub(crate) dry_run: bool,
+    pub(crate) summary: InstallSummary,
+    pub(crate) show_sizes: bool,
     pub(crate) refresh: Refresh,
     pub(crate) settings: PipSettings,
 }
@@ -1048,13 +1852,16 @@ impl PipSyncSettings {
             src_file,
             constraint,
             build_constraint,
+            src,
             installer,
             refresh,
             require_hashes,
             no_require_hashes,
             verify_hashes,
             no_verify_hashes,
+            index_hash_policy,
             python,
+            python_executable,
             system,
             no_system,
             break_system_packages,
@@ -1071,9 +1878,18 @@ impl PipSyncSettings {
             only_binary,
             python_version,
             python_platform,
+            macos_arch,
+            abi_check,
+            file_conflicts,
+            lazy_install,
+            exclude_package_data,
+            max_download_size,
+            verify_imports,
             strict,
             no_strict,
             dry_run,
+            summary,
+            show_sizes,
             compat_args: _,
         } = *args;
 
@@ -1087,7 +1903,16 @@ impl PipSyncSettings {
                 .into_iter()
                 .filter_map(Maybe::into_option)
                 .collect(),
+            src,
+            python_executable,
+            abi_check: abi_check.unwrap_or_default(),
+            file_conflicts: file_conflicts.unwrap_or_default(),
+            lazy_install,
+            max_download_size,
+            verify_imports,
             dry_run,
+            summary,
+            show_sizes,
             refresh: Refresh::from(refresh),
             settings: PipSettings::combine(
                 PipOptions {
@@ -1098,6 +1923,8 @@ impl PipSyncSettings {
                     prefix,
                     require_hashes: flag(require_hashes, no_require_hashes),
                     verify_hashes: flag(verify_hashes, no_verify_hashes),
+                    index_hash_policy: index_hash_policy
+                        .map(|policies| policies.into_iter().collect::<IndexHashPolicies>()),
                     no_build: flag(no_build, build),
                     no_binary,
                     only_binary,
@@ -1108,7 +1935,13 @@ impl PipSyncSettings {
                     legacy_setup_py: flag(legacy_setup_py, no_legacy_setup_py),
                     python_version,
                     python_platform,
+                    macos_arch,
                     strict: flag(strict, no_strict),
+                    exclude_package_data: if exclude_package_data.is_empty() {
+                        None
+                    } else {
+                        Some(exclude_package_data)
+                    },
                     concurrent_builds: env(env::CONCURRENT_BUILDS),
                     concurrent_downloads: env(env::CONCURRENT_DOWNLOADS),
                     concurrent_installs: env(env::CONCURRENT_INSTALLS),
@@ -1127,10 +1960,22 @@ pub(crate) struct PipInstallSettings {
     pub(crate) package: Vec<String>,
     pub(crate) requirement: Vec<PathBuf>,
     pub(crate) editable: Vec<String>,
+    pub(crate) src: Option<PathBuf>,
     pub(crate) constraint: Vec<PathBuf>,
     pub(crate) r#override: Vec<PathBuf>,
     pub(crate) build_constraint: Vec<PathBuf>,
+    pub(crate) group: Vec<GroupName>,
+    pub(crate) abi_check: AbiCheck,
+    pub(crate) file_conflicts: FileConflicts,
+    pub(crate) lazy_install: Vec<PackageName>,
+    pub(crate) max_download_size: Option<u64>,
+    pub(crate) break_on_downgrade: bool,
+    pub(crate) verify_imports: bool,
+    pub(crate) ignore_requires_python: bool,
+    pub(crate) requires_python_override: Option<VersionSpecifiers>,
     pub(crate) dry_run: bool,
+    pub(crate) summary: InstallSummary,
+    pub(crate) show_sizes: bool,
     pub(crate) constraints_from_workspace: Vec<Requirement>,
     pub(crate) overrides_from_workspace: Vec<Requirement>,
     pub(crate) refresh: Refresh,
@@ -1144,11 +1989,13 @@ impl PipInstallSettings {
             package,
             requirement,
             editable,
+            src,
             constraint,
             r#override,
             extra,
             all_extras,
             no_all_extras,
+            group,
             build_constraint,
             refresh,
             no_deps,
@@ -1158,6 +2005,7 @@ impl PipInstallSettings {
             installer,
             verify_hashes,
             no_verify_hashes,
+            index_hash_policy,
             python,
             system,
             no_system,
@@ -1172,10 +2020,24 @@ impl PipInstallSettings {
             no_binary,
             only_binary,
             python_version,
+            ignore_requires_python,
+            requires_python_override,
             python_platform,
+            macos_arch,
+            abi_check,
+            file_conflicts,
+            lazy_install,
+            exclude_package_data,
+            max_download_size,
+            break_on_downgrade,
+            verify_imports,
             strict,
             no_strict,
+            strict_extras,
+            no_strict_extras,
             dry_run,
+            summary,
+            show_sizes,
             compat_args: _,
         } = args;
 
@@ -1211,6 +2073,7 @@ impl PipInstallSettings {
             package,
             requirement,
             editable,
+            src,
             constraint: constraint
                 .into_iter()
                 .filter_map(Maybe::into_option)
@@ -1223,7 +2086,18 @@ impl PipInstallSettings {
                 .into_iter()
                 .filter_map(Maybe::into_option)
                 .collect(),
+            group,
+            abi_check: abi_check.unwrap_or_default(),
+            file_conflicts: file_conflicts.unwrap_or_default(),
+            lazy_install,
+            max_download_size,
+            break_on_downgrade,
+            verify_imports,
+            ignore_requires_python,
+            requires_python_override,
             dry_run,
+            summary,
+            show_sizes,
             constraints_from_workspace,
             overrides_from_workspace,
             refresh: Refresh::from(refresh),
@@ -1238,14 +2112,23 @@ impl PipInstallSettings {
                     no_binary,
                     only_binary,
                     strict: flag(strict, no_strict),
+                    strict_extras: flag(strict_extras, no_strict_extras),
                     extra,
                     all_extras: flag(all_extras, no_all_extras),
                     no_deps: flag(no_deps, deps),
                     legacy_setup_py: flag(legacy_setup_py, no_legacy_setup_py),
                     python_version,
                     python_platform,
+                    macos_arch,
                     require_hashes: flag(require_hashes, no_require_hashes),
                     verify_hashes: flag(verify_hashes, no_verify_hashes),
+                    index_hash_policy: index_hash_policy
+                        .map(|policies| policies.into_iter().collect::<IndexHashPolicies>()),
+                    exclude_package_data: if exclude_package_data.is_empty() {
+                        None
+                    } else {
+                        Some(exclude_package_data)
+                    },
                     concurrent_builds: env(env::CONCURRENT_BUILDS),
                     concurrent_downloads: env(env::CONCURRENT_DOWNLOADS),
                     concurrent_installs: env(env::CONCURRENT_INSTALLS),
@@ -1307,6 +2190,7 @@ impl PipUninstallSettings {
 #[derive(Debug, Clone)]
 pub(crate) struct PipFreezeSettings {
     pub(crate) exclude_editable: bool,
+    pub(crate) refresh: Refresh,
     pub(crate) settings: PipSettings,
 }
 
@@ -1320,11 +2204,13 @@ impl PipFreezeSettings {
             python,
             system,
             no_system,
+            refresh,
             compat_args: _,
         } = args;
 
         Self {
             exclude_editable,
+            refresh: Refresh::from(refresh),
             settings: PipSettings::combine(
                 PipOptions {
                     python,
@@ -1345,6 +2231,8 @@ pub(crate) struct PipListSettings {
     pub(crate) editable: Option<bool>,
     pub(crate) exclude: Vec<PackageName>,
     pub(crate) format: ListFormat,
+    pub(crate) sizes: bool,
+    pub(crate) refresh: Refresh,
     pub(crate) settings: PipSettings,
 }
 
@@ -1356,11 +2244,13 @@ impl PipListSettings {
             exclude_editable,
             exclude,
             format,
+            sizes,
             strict,
             no_strict,
             python,
             system,
             no_system,
+            refresh,
             compat_args: _,
         } = args;
 
@@ -1368,6 +2258,8 @@ impl PipListSettings {
             editable: flag(editable, exclude_editable),
             exclude,
             format,
+            sizes,
+            refresh: Refresh::from(refresh),
             settings: PipSettings::combine(
                 PipOptions {
                     python,
@@ -1386,6 +2278,7 @@ impl PipListSettings {
 #[derive(Debug, Clone)]
 pub(crate) struct PipShowSettings {
     pub(crate) package: Vec<PackageName>,
+    pub(crate) refresh: Refresh,
     pub(crate) settings: PipSettings,
 }
 
@@ -1399,11 +2292,13 @@ impl PipShowSettings {
             python,
             system,
             no_system,
+            refresh,
             compat_args: _,
         } = args;
 
         Self {
             package,
+            refresh: Refresh::from(refresh),
             settings: PipSettings::combine(
                 PipOptions {
                     python,
@@ -1427,6 +2322,7 @@ pub(crate) struct PipTreeSettings {
     pub(crate) package: Vec<PackageName>,
     pub(crate) no_dedupe: bool,
     pub(crate) invert: bool,
+    pub(crate) refresh: Refresh,
     // CLI-only settings.
     pub(crate) shared: PipSettings,
 }
@@ -1442,6 +2338,7 @@ impl PipTreeSettings {
             python,
             system,
             no_system,
+            refresh,
             compat_args: _,
         } = args;
 
@@ -1452,6 +2349,7 @@ impl PipTreeSettings {
             no_dedupe: tree.no_dedupe,
             invert: tree.invert,
             package: tree.package,
+            refresh: Refresh::from(refresh),
             // Shared settings.
             shared: PipSettings::combine(
                 PipOptions {
@@ -1470,6 +2368,7 @@ impl PipTreeSettings {
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone)]
 pub(crate) struct PipCheckSettings {
+    pub(crate) refresh: Refresh,
     pub(crate) settings: PipSettings,
 }
 
@@ -1480,9 +2379,11 @@ impl PipCheckSettings {
             python,
             system,
             no_system,
+            refresh,
         } = args;
 
         Self {
+            refresh: Refresh::from(refresh),
             settings: PipSettings::combine(
                 PipOptions {
                     python,
@@ -1505,6 +2406,7 @@ pub(crate) struct VenvSettings {
     pub(crate) prompt: Option<String>,
     pub(crate) system_site_packages: bool,
     pub(crate) relocatable: bool,
+    pub(crate) refresh: Refresh,
     pub(crate) settings: PipSettings,
 }
 
@@ -1526,7 +2428,9 @@ impl VenvSettings {
             keyring_provider,
             exclude_newer,
             link_mode,
+            refresh,
             compat_args: _,
+            command: _,
         } = args;
 
         Self {
@@ -1536,6 +2440,7 @@ impl VenvSettings {
             prompt,
             system_site_packages,
             relocatable,
+            refresh: Refresh::from(refresh),
             settings: PipSettings::combine(
                 PipOptions {
                     python,
@@ -1566,6 +2471,7 @@ pub(crate) struct InstallerSettingsRef<'a> {
     pub(crate) exclude_newer: Option<ExcludeNewer>,
     pub(crate) link_mode: LinkMode,
     pub(crate) compile_bytecode: bool,
+    pub(crate) exclude_package_data: &'a [ExcludePattern],
     pub(crate) reinstall: &'a Reinstall,
     pub(crate) build_options: &'a BuildOptions,
     pub(crate) sources: SourceStrategy,
@@ -1650,7 +2556,8 @@ impl From<ResolverOptions> for ResolverSettings {
                 value.extra_index_url.unwrap_or_default(),
                 value.find_links.unwrap_or_default(),
                 value.no_index.unwrap_or_default(),
-            ),
+            )
+            .with_mirrors(value.index_mirrors.unwrap_or_default()),
             resolution: value.resolution.unwrap_or_default(),
             prerelease: value.prerelease.unwrap_or_default(),
             index_strategy: value.index_strategy.unwrap_or_default(),
@@ -1673,7 +2580,11 @@ impl From<ResolverOptions> for ResolverSettings {
             build_options: BuildOptions::new(
                 NoBinary::from_args(value.no_binary, value.no_binary_package.unwrap_or_default()),
                 NoBuild::from_args(value.no_build, value.no_build_package.unwrap_or_default()),
-            ),
+            )
+            .with_prefer_source(PreferSource::from_args(
+                value.prefer_source,
+                value.prefer_source_package.unwrap_or_default(),
+            )),
         }
     }
 }
@@ -1691,6 +2602,7 @@ pub(crate) struct ResolverInstallerSettingsRef<'a> {
     pub(crate) exclude_newer: Option<ExcludeNewer>,
     pub(crate) link_mode: LinkMode,
     pub(crate) compile_bytecode: bool,
+    pub(crate) exclude_package_data: &'a [ExcludePattern],
     pub(crate) sources: SourceStrategy,
     pub(crate) upgrade: &'a Upgrade,
     pub(crate) reinstall: &'a Reinstall,
@@ -1717,6 +2629,7 @@ pub(crate) struct ResolverInstallerSettings {
     pub(crate) exclude_newer: Option<ExcludeNewer>,
     pub(crate) link_mode: LinkMode,
     pub(crate) compile_bytecode: bool,
+    pub(crate) exclude_package_data: Vec<ExcludePattern>,
     pub(crate) sources: SourceStrategy,
     pub(crate) upgrade: Upgrade,
     pub(crate) reinstall: Reinstall,
@@ -1752,6 +2665,7 @@ impl ResolverInstallerSettings {
             exclude_newer: self.exclude_newer,
             link_mode: self.link_mode,
             compile_bytecode: self.compile_bytecode,
+            exclude_package_data: &self.exclude_package_data,
             sources: self.sources,
             upgrade: &self.upgrade,
             reinstall: &self.reinstall,
@@ -1768,7 +2682,8 @@ impl From<ResolverInstallerOptions> for ResolverInstallerSettings {
                 value.extra_index_url.unwrap_or_default(),
                 value.find_links.unwrap_or_default(),
                 value.no_index.unwrap_or_default(),
-            ),
+            )
+            .with_mirrors(value.index_mirrors.unwrap_or_default()),
             resolution: value.resolution.unwrap_or_default(),
             prerelease: value.prerelease.unwrap_or_default(),
             index_strategy: value.index_strategy.unwrap_or_default(),
@@ -1780,6 +2695,7 @@ impl From<ResolverInstallerOptions> for ResolverInstallerSettings {
             link_mode: value.link_mode.unwrap_or_default(),
             sources: SourceStrategy::from_args(value.no_sources.unwrap_or_default()),
             compile_bytecode: value.compile_bytecode.unwrap_or_default(),
+            exclude_package_data: value.exclude_package_data.unwrap_or_default(),
             upgrade: Upgrade::from_args(
                 value.upgrade,
                 value
@@ -1796,7 +2712,11 @@ impl From<ResolverInstallerOptions> for ResolverInstallerSettings {
             build_options: BuildOptions::new(
                 NoBinary::from_args(value.no_binary, value.no_binary_package.unwrap_or_default()),
                 NoBuild::from_args(value.no_build, value.no_build_package.unwrap_or_default()),
-            ),
+            )
+            .with_prefer_source(PreferSource::from_args(
+                value.prefer_source,
+                value.prefer_source_package.unwrap_or_default(),
+            )),
         }
     }
 }
@@ -1822,6 +2742,7 @@ pub(crate) struct PipSettings {
     pub(crate) build_options: BuildOptions,
     pub(crate) allow_empty_requirements: bool,
     pub(crate) strict: bool,
+    pub(crate) strict_extras: bool,
     pub(crate) dependency_mode: DependencyMode,
     pub(crate) resolution: ResolutionMode,
     pub(crate) prerelease: PrereleaseMode,
@@ -1836,6 +2757,7 @@ pub(crate) struct PipSettings {
     pub(crate) config_setting: ConfigSettings,
     pub(crate) python_version: Option<PythonVersion>,
     pub(crate) python_platform: Option<TargetTriple>,
+    pub(crate) macos_arch: MacosArch,
     pub(crate) universal: bool,
     pub(crate) exclude_newer: Option<ExcludeNewer>,
     pub(crate) no_emit_package: Vec<PackageName>,
@@ -1844,11 +2766,14 @@ pub(crate) struct PipSettings {
     pub(crate) emit_build_options: bool,
     pub(crate) emit_marker_expression: bool,
     pub(crate) emit_index_annotation: bool,
+    pub(crate) emit_constraint_annotation: bool,
     pub(crate) annotation_style: AnnotationStyle,
     pub(crate) link_mode: LinkMode,
     pub(crate) compile_bytecode: bool,
+    pub(crate) exclude_package_data: Vec<ExcludePattern>,
     pub(crate) sources: SourceStrategy,
     pub(crate) hash_checking: Option<HashCheckingMode>,
+    pub(crate) index_hash_policy: IndexHashPolicies,
     pub(crate) upgrade: Upgrade,
     pub(crate) reinstall: Reinstall,
     pub(crate) concurrency: Concurrency,
@@ -1871,6 +2796,7 @@ impl PipSettings {
             extra_index_url,
             no_index,
             find_links,
+            index_mirrors,
             index_strategy,
             keyring_provider,
             no_build,
@@ -1879,6 +2805,7 @@ impl PipSettings {
             no_build_isolation,
             no_build_isolation_package,
             strict,
+            strict_extras,
             extra,
             all_extras,
             no_deps,
@@ -1896,6 +2823,7 @@ impl PipSettings {
             config_settings,
             python_version,
             python_platform,
+            macos_arch,
             universal,
             exclude_newer,
             no_emit_package,
@@ -1904,11 +2832,14 @@ impl PipSettings {
             emit_build_options,
             emit_marker_expression,
             emit_index_annotation,
+            emit_constraint_annotation,
             annotation_style,
             link_mode,
             compile_bytecode,
+            exclude_package_data,
             require_hashes,
             verify_hashes,
+            index_hash_policy,
             no_sources,
             upgrade,
             upgrade_package,
@@ -1924,6 +2855,7 @@ impl PipSettings {
             extra_index_url: top_level_extra_index_url,
             no_index: top_level_no_index,
             find_links: top_level_find_links,
+            index_mirrors: top_level_index_mirrors,
             index_strategy: top_level_index_strategy,
             keyring_provider: top_level_keyring_provider,
             resolution: top_level_resolution,
@@ -1934,6 +2866,7 @@ impl PipSettings {
             exclude_newer: top_level_exclude_newer,
             link_mode: top_level_link_mode,
             compile_bytecode: top_level_compile_bytecode,
+            exclude_package_data: top_level_exclude_package_data,
             no_sources: top_level_no_sources,
             upgrade: top_level_upgrade,
             upgrade_package: top_level_upgrade_package,
@@ -1943,6 +2876,8 @@ impl PipSettings {
             no_build_package: top_level_no_build_package,
             no_binary: top_level_no_binary,
             no_binary_package: top_level_no_binary_package,
+            prefer_source: top_level_prefer_source,
+            prefer_source_package: top_level_prefer_source_package,
         } = top_level;
 
         // Merge the top-level options (`tool.uv`) with the pip-specific options (`tool.uv.pip`),
@@ -1953,6 +2888,7 @@ impl PipSettings {
         let extra_index_url = extra_index_url.combine(top_level_extra_index_url);
         let no_index = no_index.combine(top_level_no_index);
         let find_links = find_links.combine(top_level_find_links);
+        let index_mirrors = index_mirrors.combine(top_level_index_mirrors);
         let index_strategy = index_strategy.combine(top_level_index_strategy);
         let keyring_provider = keyring_provider.combine(top_level_keyring_provider);
         let resolution = resolution.combine(top_level_resolution);
@@ -1964,6 +2900,7 @@ impl PipSettings {
         let exclude_newer = exclude_newer.combine(top_level_exclude_newer);
         let link_mode = link_mode.combine(top_level_link_mode);
         let compile_bytecode = compile_bytecode.combine(top_level_compile_bytecode);
+        let exclude_package_data = exclude_package_data.combine(top_level_exclude_package_data);
         let no_sources = no_sources.combine(top_level_no_sources);
         let upgrade = upgrade.combine(top_level_upgrade);
         let upgrade_package = upgrade_package.combine(top_level_upgrade_package);
@@ -1978,7 +2915,8 @@ impl PipSettings {
                     .unwrap_or_default(),
                 args.find_links.combine(find_links).unwrap_or_default(),
                 args.no_index.combine(no_index).unwrap_or_default(),
-            ),
+            )
+            .with_mirrors(index_mirrors.unwrap_or_default()),
             extras: ExtrasSpecification::from_args(
                 args.all_extras.combine(all_extras).unwrap_or_default(),
                 args.extra.combine(extra).unwrap_or_default(),
@@ -2045,6 +2983,7 @@ impl PipSettings {
                 .unwrap_or_default(),
             python_version: args.python_version.combine(python_version),
             python_platform: args.python_platform.combine(python_platform),
+            macos_arch: args.macos_arch.combine(macos_arch).unwrap_or_default(),
             universal: args.universal.combine(universal).unwrap_or_default(),
             exclude_newer: args.exclude_newer.combine(exclude_newer),
             no_emit_package: args
@@ -2071,6 +3010,10 @@ impl PipSettings {
                 .emit_index_annotation
                 .combine(emit_index_annotation)
                 .unwrap_or_default(),
+            emit_constraint_annotation: args
+                .emit_constraint_annotation
+                .combine(emit_constraint_annotation)
+                .unwrap_or_default(),
             link_mode: args.link_mode.combine(link_mode).unwrap_or_default(),
             hash_checking: HashCheckingMode::from_args(
                 args.require_hashes
@@ -2080,6 +3023,10 @@ impl PipSettings {
                     .combine(verify_hashes)
                     .unwrap_or_default(),
             ),
+            index_hash_policy: args
+                .index_hash_policy
+                .combine(index_hash_policy)
+                .unwrap_or_default(),
             python: args.python.combine(python),
             system: args.system.combine(system).unwrap_or_default(),
             break_system_packages: args
@@ -2092,10 +3039,18 @@ impl PipSettings {
                 .compile_bytecode
                 .combine(compile_bytecode)
                 .unwrap_or_default(),
+            exclude_package_data: args
+                .exclude_package_data
+                .combine(exclude_package_data)
+                .unwrap_or_default(),
             sources: SourceStrategy::from_args(
                 args.no_sources.combine(no_sources).unwrap_or_default(),
             ),
             strict: args.strict.combine(strict).unwrap_or_default(),
+            strict_extras: args
+                .strict_extras
+                .combine(strict_extras)
+                .unwrap_or_default(),
             upgrade: Upgrade::from_args(
                 args.upgrade.combine(upgrade),
                 args.upgrade_package
@@ -2142,7 +3097,11 @@ impl PipSettings {
                     top_level_no_build,
                     top_level_no_build_package.unwrap_or_default(),
                 )),
-            ),
+            )
+            .with_prefer_source(PreferSource::from_args(
+                top_level_prefer_source,
+                top_level_prefer_source_package.unwrap_or_default(),
+            )),
         }
     }
 }
@@ -2178,6 +3137,7 @@ impl<'a> From<ResolverInstallerSettingsRef<'a>> for InstallerSettingsRef<'a> {
             exclude_newer: settings.exclude_newer,
             link_mode: settings.link_mode,
             compile_bytecode: settings.compile_bytecode,
+            exclude_package_data: settings.exclude_package_data,
             reinstall: settings.reinstall,
             build_options: settings.build_options,
             sources: settings.sources,
@@ -2195,6 +3155,20 @@ mod env {
 
     pub(super) const CONCURRENT_INSTALLS: (&str, &str) =
         ("UV_CONCURRENT_INSTALLS", "a non-zero integer");
+
+    pub(super) const LOCK_TIMEOUT: (&str, &str) =
+        ("UV_LOCK_TIMEOUT", "an integer number of seconds");
+
+    pub(super) const HTTP2: (&str, &str) = ("UV_HTTP2", "a boolean (`true` or `false`)");
+
+    pub(super) const HTTP_POOL_MAX_IDLE_PER_HOST: (&str, &str) =
+        ("UV_HTTP_POOL_MAX_IDLE_PER_HOST", "an integer");
+
+    pub(super) const HTTP_POOL_IDLE_TIMEOUT: (&str, &str) =
+        ("UV_HTTP_POOL_IDLE_TIMEOUT", "an integer number of seconds");
+
+    pub(super) const HTTP_TCP_KEEPALIVE: (&str, &str) =
+        ("UV_HTTP_TCP_KEEPALIVE", "an integer number of seconds");
 }
 
 /// Attempt to load and parse an environment variable with the given name.
@@ -0,0 +1,209 @@
+//! Local usage statistics.
+//!
+//! If enabled via `UV_STATS`, after each invocation of a command that resolves or installs
+//! packages, uv appends a summary of the invocation (e.g., how many distributions were served from
+//! the cache, and which packages were built from source) to a log in the user-level data
+//! directory. The log is never sent anywhere; it's only read back by `uv stats`.
+//!
+//! `UV_NO_STATS` disables recording, even if `UV_STATS` is set. Once the log exceeds
+//! [`MAX_LOG_SIZE`], it's rotated, such that only the previous log is retained.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use uv_cli::{Commands, PipCommand, PipNamespace, ProjectCommand, ToolCommand, ToolNamespace};
+use uv_state::{StateBucket, StateStore};
+
+/// The name of the log file within the stats directory.
+const LOG: &str = "invocations.jsonl";
+
+/// The name of the rotated log file within the stats directory.
+const ROTATED_LOG: &str = "invocations.1.jsonl";
+
+/// The size, in bytes, beyond which the log is rotated.
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+static RESOLUTIONS: AtomicU64 = AtomicU64::new(0);
+static CACHED: AtomicU64 = AtomicU64::new(0);
+static DOWNLOADED: AtomicU64 = AtomicU64::new(0);
+static BUILT: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// A summary of a single invocation, as stored in the log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Invocation {
+    /// The time at which the invocation completed, in seconds since the Unix epoch.
+    pub(crate) timestamp: u64,
+    /// The command that was invoked (e.g., `pip install`).
+    pub(crate) command: String,
+    /// The wall-clock duration of the invocation, in milliseconds.
+    pub(crate) duration_ms: u64,
+    /// Whether the invocation succeeded.
+    pub(crate) success: bool,
+    /// The number of resolutions performed.
+    pub(crate) resolutions: u64,
+    /// The number of distributions that were installed from the cache.
+    pub(crate) cached: u64,
+    /// The number of distributions that had to be downloaded or built.
+    pub(crate) downloaded: u64,
+    /// The packages (or, for unnamed requirements, the sources) that were built from source.
+    pub(crate) built: Vec<String>,
+}
+
+/// Record that a resolution was performed.
+pub(crate) fn record_resolution() {
+    RESOLUTIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Record the number of distributions that were installed from the cache, and the number that had
+/// to be fetched.
+pub(crate) fn record_install(cached: usize, downloaded: usize) {
+    CACHED.fetch_add(cached as u64, Ordering::Relaxed);
+    DOWNLOADED.fetch_add(downloaded as u64, Ordering::Relaxed);
+}
+
+/// Record that a distribution was built from source.
+pub(crate) fn record_build(source: String) {
+    BUILT.lock().unwrap().push(source);
+}
+
+/// Return the name under which the given command is recorded, if it's recorded at all.
+///
+/// Only commands that resolve or install packages are recorded.
+pub(crate) fn command_name(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::Pip(PipNamespace { command }) => match command {
+            PipCommand::Compile(_) => Some("pip compile"),
+            PipCommand::Sync(_) => Some("pip sync"),
+            PipCommand::Install(_) => Some("pip install"),
+            _ => None,
+        },
+        Commands::Project(command) => match command.as_ref() {
+            ProjectCommand::Run(_) => Some("run"),
+            ProjectCommand::Sync(_) => Some("sync"),
+            ProjectCommand::Test(_) => Some("test"),
+            ProjectCommand::Lock(_) => Some("lock"),
+            ProjectCommand::Add(_) => Some("add"),
+            ProjectCommand::Remove(_) => Some("remove"),
+            _ => None,
+        },
+        Commands::Tool(ToolNamespace { command }) => match command {
+            ToolCommand::Run(_) | ToolCommand::Uvx(_) => Some("tool run"),
+            ToolCommand::Install(_) => Some("tool install"),
+            ToolCommand::Upgrade(_) => Some("tool upgrade"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns `true` if recording is enabled, i.e., `UV_STATS` is set and `UV_NO_STATS` isn't.
+fn is_enabled() -> bool {
+    std::env::var_os("UV_STATS").is_some() && std::env::var_os("UV_NO_STATS").is_none()
+}
+
+/// Return the directory containing the log, respecting `UV_STATS_DIR`.
+fn stats_dir() -> std::io::Result<PathBuf> {
+    if let Some(stats_dir) = std::env::var_os("UV_STATS_DIR") {
+        Ok(PathBuf::from(stats_dir))
+    } else {
+        Ok(StateStore::from_settings(None)?.bucket(StateBucket::Stats))
+    }
+}
+
+/// Return the path to the log.
+pub(crate) fn log_path() -> std::io::Result<PathBuf> {
+    Ok(stats_dir()?.join(LOG))
+}
+
+/// Append the statistics collected during this invocation to the log.
+///
+/// Failures are logged, rather than surfaced, since they shouldn't affect the outcome of the
+/// command itself.
+pub(crate) fn flush(command: &str, duration: Duration, success: bool) {
+    if !is_enabled() {
+        return;
+    }
+
+    let invocation = Invocation {
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default(),
+        command: command.to_string(),
+        duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        success,
+        resolutions: RESOLUTIONS.load(Ordering::Relaxed),
+        cached: CACHED.load(Ordering::Relaxed),
+        downloaded: DOWNLOADED.load(Ordering::Relaxed),
+        built: std::mem::take(&mut *BUILT.lock().unwrap()),
+    };
+
+    if let Err(err) = append(&invocation) {
+        debug!("Failed to record usage statistics: {err}");
+    }
+}
+
+fn append(invocation: &Invocation) -> anyhow::Result<()> {
+    let dir = stats_dir()?;
+    fs_err::create_dir_all(&dir)?;
+    let path = dir.join(LOG);
+
+    // Rotate the log once it exceeds the maximum size, discarding the previously rotated log.
+    if fs_err::metadata(&path).is_ok_and(|metadata| metadata.len() >= MAX_LOG_SIZE) {
+        debug!("Rotating usage statistics log: {}", path.display());
+        fs_err::rename(&path, dir.join(ROTATED_LOG))?;
+    }
+
+    let mut line = serde_json::to_string(invocation)?;
+    line.push('\n');
+    let mut file = fs_err::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Read all invocations from the log, including the rotated log, skipping any malformed entries.
+pub(crate) fn read() -> anyhow::Result<Vec<Invocation>> {
+    let dir = stats_dir()?;
+
+    let mut invocations = Vec::new();
+    for path in [dir.join(ROTATED_LOG), dir.join(LOG)] {
+        let file = match fs_err::File::open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            match serde_json::from_str::<Invocation>(&line) {
+                Ok(invocation) => invocations.push(invocation),
+                Err(err) => debug!("Skipping malformed entry in {}: {err}", path.display()),
+            }
+        }
+    }
+    Ok(invocations)
+}
+
+/// Remove the log, including the rotated log.
+pub(crate) fn clear() -> anyhow::Result<bool> {
+    let dir = stats_dir()?;
+
+    let mut removed = false;
+    for path in [dir.join(ROTATED_LOG), dir.join(LOG)] {
+        match fs_err::remove_file(&path) {
+            Ok(()) => removed = true,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(removed)
+}
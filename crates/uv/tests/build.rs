@@ -0,0 +1,375 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::prelude::*;
+use common::uv_snapshot;
+use insta::assert_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `build` command for the given context.
+fn build_command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command.arg("build");
+    context.add_shared_args(&mut command);
+    command
+}
+
+/// Build a source distribution and a wheel for a project.
+#[test]
+fn build() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+        "#,
+    )?;
+    context
+        .temp_dir
+        .child("src")
+        .child("project")
+        .child("__init__.py")
+        .touch()?;
+
+    uv_snapshot!(context.filters(), build_command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv build` is experimental and may change without warning
+    Successfully built dist/project-0.1.0.tar.gz
+    Successfully built dist/project-0.1.0-py3-none-any.whl
+    "###);
+
+    context
+        .temp_dir
+        .child("dist")
+        .child("project-0.1.0.tar.gz")
+        .assert(predicates::path::is_file());
+    context
+        .temp_dir
+        .child("dist")
+        .child("project-0.1.0-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+
+    // Build only a wheel, into a custom output directory.
+    uv_snapshot!(context.filters(), build_command(&context).arg("--wheel").arg("--out-dir").arg("out"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv build` is experimental and may change without warning
+    Successfully built out/project-0.1.0-py3-none-any.whl
+    "###);
+
+    context
+        .temp_dir
+        .child("out")
+        .child("project-0.1.0.tar.gz")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// Lock the build requirements of a project, and build it against the locked versions.
+#[test]
+fn build_locked() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+
+        [tool.uv]
+        lock-build-requirements = true
+        "#,
+    )?;
+    context
+        .temp_dir
+        .child("src")
+        .child("project")
+        .child("__init__.py")
+        .touch()?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    "###);
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            lock, @r###"
+        version = 1
+        requires-python = ">=3.12"
+
+        [options]
+        exclude-newer = "2024-03-25 00:00:00 UTC"
+
+        [[package]]
+        name = "project"
+        version = "0.1.0"
+        source = { editable = "." }
+        build-dependencies = [
+            { name = "setuptools", version = "69.2.0" },
+        ]
+        "###
+        );
+    });
+
+    uv_snapshot!(context.filters(), build_command(&context).arg("--locked"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv build` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Successfully built dist/project-0.1.0.tar.gz
+    Successfully built dist/project-0.1.0-py3-none-any.whl
+    "###);
+
+    // Changing the build requirements should invalidate the lockfile.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools<69"]
+        build-backend = "setuptools.build_meta"
+
+        [tool.uv]
+        lock-build-requirements = true
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), build_command(&context).arg("--locked"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv build` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    error: The lockfile at `uv.lock` needs to be updated, but `--locked` was provided. To update the lockfile, run `uv lock`.
+    "###);
+
+    Ok(())
+}
+
+/// Lock build requirements for every supported Python version, not only the current interpreter.
+#[test]
+fn build_locked_universal() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.8"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=42", "tomli ; python_version < '3.11'"]
+        build-backend = "setuptools.build_meta"
+
+        [tool.uv]
+        lock-build-requirements = true
+        "#,
+    )?;
+    context
+        .temp_dir
+        .child("src")
+        .child("project")
+        .child("__init__.py")
+        .touch()?;
+
+    context.lock().assert().success();
+
+    // `tomli` isn't required on Python 3.12, but is still locked for older versions.
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock"))?;
+    assert!(
+        lock.contains(r#"{ name = "tomli", version = "2.0.1" }"#),
+        "{lock}"
+    );
+
+    build_command(&context).arg("--locked").assert().success();
+
+    Ok(())
+}
+
+/// Building with `--locked` requires that the build requirements were locked.
+#[test]
+fn build_locked_missing_build_requirements() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+        "#,
+    )?;
+
+    context.lock().assert().success();
+
+    uv_snapshot!(context.filters(), build_command(&context).arg("--locked"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv build` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    error: The lockfile at `uv.lock` doesn't contain the build requirements of `project`. To lock them, set `tool.uv.lock-build-requirements = true` in its `pyproject.toml`, then run `uv lock`.
+    "###);
+
+    Ok(())
+}
+
+/// Build every member of a workspace, in dependency order, into a shared `dist` directory.
+#[test]
+fn build_all() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject = r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["child"]
+
+        [tool.uv.sources]
+        child = { workspace = true }
+
+        [tool.uv.workspace]
+        members = ["child"]
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+        "#;
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(pyproject)?;
+    context
+        .temp_dir
+        .child("src")
+        .child("project")
+        .child("__init__.py")
+        .touch()?;
+
+    let child = context.temp_dir.child("child");
+    child.child("pyproject.toml").write_str(
+        r#"
+        [project]
+        name = "child"
+        version = "0.2.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+        "#,
+    )?;
+    child
+        .child("src")
+        .child("child")
+        .child("__init__.py")
+        .touch()?;
+
+    // The child should be built before the project that depends on it.
+    uv_snapshot!(context.filters(), build_command(&context).arg("--all"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv build` is experimental and may change without warning
+    Successfully built dist/child-0.2.0.tar.gz
+    Successfully built dist/child-0.2.0-py3-none-any.whl
+    Successfully built dist/project-0.1.0.tar.gz
+    Successfully built dist/project-0.1.0-py3-none-any.whl
+    "###);
+
+    context
+        .temp_dir
+        .child("dist")
+        .child("child-0.2.0-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+    context
+        .temp_dir
+        .child("dist")
+        .child("project-0.1.0-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+
+    // The dependency on the child is pinned in a temporary copy of the project, leaving the
+    // original `pyproject.toml` untouched.
+    assert_eq!(fs_err::read_to_string(pyproject_toml.path())?, pyproject);
+    assert!(fs_err::read_dir(context.temp_dir.path().parent().unwrap())?
+        .filter_map(Result::ok)
+        .all(|entry| !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(".tmp-build-")));
+
+    // Building the child directly should still write to its own `dist` directory.
+    uv_snapshot!(context.filters(), build_command(&context).arg("child").arg("--wheel"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv build` is experimental and may change without warning
+    Successfully built child/dist/child-0.2.0-py3-none-any.whl
+    "###);
+
+    Ok(())
+}
@@ -131,3 +131,97 @@ fn clean_package_index() -> Result<()> {
 
     Ok(())
 }
+
+/// `cache clean pluggy` should remove any cached tool environments that include `pluggy`.
+#[test]
+fn clean_package_environments() -> Result<()> {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Run a tool, to populate the cached environments.
+    context
+        .tool_run()
+        .arg("pytest@8.0.0")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), context.clean().arg("pluggy"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Removed [N] files for pluggy ([SIZE])
+    "###);
+
+    // The environment that included `pluggy` should be removed.
+    let environments = fs_err::read_dir(context.cache_dir.child("environments-v1"))?
+        .map(|interpreter| fs_err::read_dir(interpreter?.path()).map(Iterator::count))
+        .sum::<std::io::Result<usize>>()?;
+    assert_eq!(environments, 0);
+
+    Ok(())
+}
+
+/// `cache clean flit-core` should identify, and with `--built-dependents` remove, any wheels that
+/// were built with `flit-core` as the build backend.
+#[test]
+fn clean_package_built_dependents() -> Result<()> {
+    let context = TestContext::new("3.12").with_filtered_counts();
+
+    let project = context.temp_dir.child("dependent");
+    project.child("pyproject.toml").write_str(
+        r#"
+        [project]
+        name = "dependent"
+        version = "0.1.0"
+        description = "A package built with flit."
+
+        [build-system]
+        requires = ["flit_core>=3.4,<4"]
+        build-backend = "flit_core.buildapi"
+        "#,
+    )?;
+    project.child("dependent.py").touch()?;
+
+    // Build and install the package, to populate the cache.
+    context.pip_install().arg("./dependent").assert().success();
+
+    // By default, the wheel built with `flit-core` is retained.
+    uv_snapshot!(context.filters(), context.clean().arg("flit-core"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Removed [N] files for flit-core ([SIZE])
+    hint: Wheels built with flit-core were retained: dependent (to rebuild them, use `--built-dependents`)
+    "###);
+
+    // With `--built-dependents`, it's removed.
+    uv_snapshot!(context.filters(), context.clean().arg("flit-core").arg("--built-dependents"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No cache entries found for flit-core
+    Removed [N] files for wheels built with flit-core: dependent ([SIZE])
+    "###);
+
+    // The removed wheels are no longer reported.
+    uv_snapshot!(context.filters(), context.clean().arg("flit-core"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No cache entries found for flit-core
+    "###);
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// `cache move` should rename the cache, rebasing any symlinks into the new directory.
+#[test]
+fn move_cache() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig")?;
+
+    // Install a requirement, to populate the cache.
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    let destination = context.temp_dir.child("moved");
+
+    let mut command = Command::new(get_bin());
+    command.arg("cache").arg("move").arg(destination.path());
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Moving cache from [CACHE_DIR]/ to moved
+    Renamed cache directory
+    To use the relocated cache, set `UV_CACHE_DIR=[TEMP_DIR]/moved` or `cache-dir = "[TEMP_DIR]/moved"`
+    "###);
+
+    context.cache_dir.assert(predicates::path::missing());
+    destination
+        .child("CACHEDIR.TAG")
+        .assert(predicates::path::exists());
+
+    Ok(())
+}
+
+/// `cache move` should refuse to overwrite a non-empty directory.
+#[test]
+fn move_cache_non_empty() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig")?;
+
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    let destination = context.temp_dir.child("moved");
+    destination.child("file.txt").touch()?;
+
+    let mut command = Command::new(get_bin());
+    command.arg("cache").arg("move").arg(destination.path());
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Moving cache from [CACHE_DIR]/ to moved
+    error: Failed to move cache to: moved
+      Caused by: Destination already exists and is not empty: [TEMP_DIR]/moved
+    "###);
+
+    Ok(())
+}
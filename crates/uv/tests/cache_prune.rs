@@ -127,6 +127,213 @@ fn prune_cached_env() {
     "###);
 }
 
+/// `cache prune --tool-runs` should only remove the cached environments.
+#[test]
+fn prune_tool_runs() -> Result<()> {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    context
+        .tool_run()
+        .arg("pytest@8.0.0")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([
+            // The cache entry does not have a stable key, so we filter it out
+            (
+                r"\[CACHE_DIR\](\\|\/)(.+)(\\|\/).*",
+                "[CACHE_DIR]/$2/[ENTRY]",
+            ),
+        ])
+        .collect();
+
+    uv_snapshot!(filters, context.prune().arg("--tool-runs").arg("--verbose"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    DEBUG uv [VERSION] ([COMMIT] DATE)
+    Pruning cache at: [CACHE_DIR]/
+    DEBUG Removing cached environment: [CACHE_DIR]/archive-v0/[ENTRY]
+    Removed [N] files ([SIZE])
+    "###);
+
+    // The wheels should be retained.
+    context
+        .cache_dir
+        .child("wheels-v1")
+        .assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
+/// `cache prune --tool-runs` with `--max-age` or `--max-size` should only remove the cached
+/// environments that exceed the given limits.
+#[test]
+fn prune_tool_runs_limits() -> Result<()> {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    context
+        .tool_run()
+        .arg("pytest@8.0.0")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([
+            // The cache entry does not have a stable key, so we filter it out
+            (
+                r"\[CACHE_DIR\](\\|\/)(.+)(\\|\/).*",
+                "[CACHE_DIR]/$2/[ENTRY]",
+            ),
+        ])
+        .collect();
+
+    // The environment was just used, so it should be retained.
+    uv_snapshot!(filters, context.prune().arg("--tool-runs").arg("--max-age").arg("1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    No unused entries found
+    "###);
+
+    // The environment exceeds the size limit, so it should be removed.
+    uv_snapshot!(filters, context.prune().arg("--tool-runs").arg("--max-size").arg("1KB").arg("--verbose"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    DEBUG uv [VERSION] ([COMMIT] DATE)
+    Pruning cache at: [CACHE_DIR]/
+    DEBUG Removing cached environment: [CACHE_DIR]/archive-v0/[ENTRY]
+    Removed [N] files ([SIZE])
+    "###);
+
+    Ok(())
+}
+
+/// `cache prune --unused` should remove any distributions that aren't reachable from the provided
+/// lockfiles.
+#[test]
+fn prune_unused() -> Result<()> {
+    let context = TestContext::new("3.12").with_filtered_counts();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        anyio==4.0.0
+        idna==3.6
+        sniffio==1.3.1
+        iniconfig==2.0.0
+    "})?;
+
+    // Install the requirements, to populate the cache.
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    // Only `iniconfig` is required by the current lockfiles.
+    let lockfile = context.temp_dir.child("ci").child("requirements.txt");
+    lockfile.write_str("iniconfig==2.0.0")?;
+
+    uv_snapshot!(context.filters(), context.prune().arg("--unused").arg("--lockfiles").arg("ci/*.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    Removed [N] files ([SIZE])
+    "###);
+
+    // The reachable distributions should be retained.
+    let wheels = context.cache_dir.child("wheels-v1").child("pypi");
+    wheels.child("iniconfig").assert(predicates::path::is_dir());
+    wheels.child("anyio").assert(predicates::path::missing());
+
+    // If no lockfiles match, the cache should be left intact.
+    uv_snapshot!(context.filters(), context.prune().arg("--unused").arg("--lockfiles").arg("missing/*.lock"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No lockfiles found matching: `missing/*.lock`
+    "###);
+
+    wheels.child("iniconfig").assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
+/// `cache prune --builds-for` should remove the built wheels for the given package, while retaining
+/// any pre-built wheels.
+#[test]
+fn prune_builds_for() -> Result<()> {
+    let context = TestContext::new("3.12").with_filtered_counts();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! { r"
+        source-distribution==0.0.1
+        iniconfig
+    " })?;
+
+    // Install the requirements, to populate the cache.
+    context
+        .pip_sync()
+        .env_remove("UV_EXCLUDE_NEWER")
+        .arg("requirements.txt")
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), context.prune().arg("--builds-for").arg("source-distribution"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Pruning cache at: [CACHE_DIR]/
+    Removed [N] files ([SIZE])
+    "###);
+
+    // The built wheel should be removed, but the pre-built wheel should be retained.
+    context
+        .cache_dir
+        .child("built-wheels-v3")
+        .child("pypi")
+        .child("source-distribution")
+        .assert(predicates::path::missing());
+    context
+        .cache_dir
+        .child("wheels-v1")
+        .child("pypi")
+        .child("iniconfig")
+        .assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
 /// `cache prune` should remove any stale symlink from the cache.
 #[test]
 fn prune_stale_symlink() -> Result<()> {
@@ -233,7 +440,7 @@ fn prune_unzipped() -> Result<()> {
     " })?;
     uv_snapshot!(context.filters(), context.pip_sync().env_remove("UV_EXCLUDE_NEWER").arg("requirements.txt").arg("--reinstall").arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
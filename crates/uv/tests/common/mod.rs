@@ -354,6 +354,7 @@ impl TestContext {
             .env("UV_PYTHON_INSTALL_DIR", "")
             .env("UV_TEST_PYTHON_PATH", self.python_path())
             .env("UV_EXCLUDE_NEWER", EXCLUDE_NEWER)
+            .env("UV_NO_STATS", "1")
             .current_dir(self.temp_dir.path());
 
         if cfg!(all(windows, debug_assertions)) {
@@ -363,6 +364,30 @@ impl TestContext {
         }
     }
 
+    /// Create a `uv venv list` command with options shared across scenarios.
+    pub fn venv_list(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("venv").arg("list");
+        self.add_shared_args(&mut command);
+        command
+    }
+
+    /// Create a `uv venv remove` command with options shared across scenarios.
+    pub fn venv_remove(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("venv").arg("remove");
+        self.add_shared_args(&mut command);
+        command
+    }
+
+    /// Create a `uv venv prune` command with options shared across scenarios.
+    pub fn venv_prune(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("venv").arg("prune");
+        self.add_shared_args(&mut command);
+        command
+    }
+
     /// Create a `pip compile` command for testing.
     pub fn pip_compile(&self) -> Command {
         let mut command = Command::new(get_bin());
@@ -443,6 +468,14 @@ impl TestContext {
         command
     }
 
+    /// Create a `uv test` command with options shared across scenarios.
+    pub fn test(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("test");
+        self.add_shared_args(&mut command);
+        command
+    }
+
     /// Create a `uv lock` command with options shared across scenarios.
     pub fn lock(&self) -> Command {
         let mut command = Command::new(get_bin());
@@ -477,6 +510,26 @@ impl TestContext {
         command
     }
 
+    /// Create a `uv python list` command with options shared across scenarios.
+    pub fn python_list(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("python").arg("list");
+        self.add_shared_args(&mut command);
+        command
+    }
+
+    /// Create a `uv python install` command with options shared across scenarios.
+    pub fn python_install(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command
+            .arg("python")
+            .arg("install")
+            .env("UV_PREVIEW", "1")
+            .current_dir(&self.temp_dir);
+        self.add_shared_args(&mut command);
+        command
+    }
+
     /// Create a `uv python dir` command with options shared across scenarios.
     pub fn python_dir(&self) -> Command {
         let mut command = Command::new(get_bin());
@@ -529,6 +582,14 @@ impl TestContext {
         command
     }
 
+    /// Create a `uv tool show` command with options shared across scenarios.
+    pub fn tool_show(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("tool").arg("show");
+        self.add_shared_args(&mut command);
+        command
+    }
+
     /// Create a `uv tool dir` command with options shared across scenarios.
     pub fn tool_dir(&self) -> Command {
         let mut command = Command::new(get_bin());
@@ -537,6 +598,46 @@ impl TestContext {
         command
     }
 
+    /// Create a `uv tool update-shell` command with options shared across scenarios.
+    pub fn tool_update_shell(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("tool").arg("update-shell");
+        self.add_shared_args(&mut command);
+        command
+    }
+
+    /// Create a `uv tool ps` command with options shared across scenarios.
+    pub fn tool_ps(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("tool").arg("ps");
+        self.add_shared_args(&mut command);
+        command
+    }
+
+    /// Create a `uv tool kill` command with options shared across scenarios.
+    pub fn tool_kill(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("tool").arg("kill");
+        self.add_shared_args(&mut command);
+        command
+    }
+
+    /// Create a `uv tool prune` command with options shared across scenarios.
+    pub fn tool_prune(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("tool").arg("prune");
+        self.add_shared_args(&mut command);
+        command
+    }
+
+    /// Create a `uv tool repair` command with options shared across scenarios.
+    pub fn tool_repair(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("tool").arg("repair");
+        self.add_shared_args(&mut command);
+        command
+    }
+
     /// Create a `uv tool uninstall` command with options shared across scenarios.
     pub fn tool_uninstall(&self) -> Command {
         let mut command = Command::new(get_bin());
@@ -569,6 +670,22 @@ impl TestContext {
         command
     }
 
+    /// Create a `uv why` command with options shared across scenarios.
+    pub fn why(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("why");
+        self.add_shared_args(&mut command);
+        command
+    }
+
+    /// Create a `uv server` command with options shared across scenarios.
+    pub fn server(&self) -> Command {
+        let mut command = Command::new(get_bin());
+        command.arg("server");
+        self.add_shared_args(&mut command);
+        command
+    }
+
     /// Create a `uv cache clean` command.
     pub fn clean(&self) -> Command {
         let mut command = Command::new(get_bin());
@@ -779,6 +896,8 @@ pub fn create_venv_from_executable<P: AsRef<std::path::Path>>(
         .arg(cache_dir.path())
         .arg("--python")
         .arg(python)
+        // Avoid recording the environment in the user-level registry.
+        .env("UV_ENVIRONMENT_DIR", cache_dir.child("environments").path())
         .current_dir(path.as_ref().parent().unwrap())
         .assert()
         .success();
@@ -0,0 +1,153 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Compare the lockfiles before and after changing the project's dependencies.
+#[test]
+fn diff_lockfiles() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==3.7.0"]
+        "#,
+    )?;
+    context.lock().assert().success();
+    fs_err::copy(
+        context.temp_dir.join("uv.lock"),
+        context.temp_dir.join("old.lock"),
+    )?;
+
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==4.3.0", "iniconfig"]
+        "#,
+    )?;
+    context.lock().assert().success();
+
+    let mut command = Command::new(get_bin());
+    command.arg("diff").arg("old.lock").arg("uv.lock");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+     + iniconfig 2.0.0
+     ~ anyio 3.7.0 -> 4.3.0
+
+    ----- stderr -----
+    warning: `uv diff` is experimental and may change without warning
+    "###);
+
+    let mut command = Command::new(get_bin());
+    command
+        .arg("diff")
+        .arg("old.lock")
+        .arg("uv.lock")
+        .arg("--format")
+        .arg("markdown")
+        .arg("--preview");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    | Package | Change | Old | New |
+    | --- | --- | --- | --- |
+    | `iniconfig` | added |  | 2.0.0 |
+    | `anyio` | upgraded | 3.7.0 | 4.3.0 |
+
+    ----- stderr -----
+    "###);
+
+    // Comparing a lockfile against itself should report no differences.
+    let mut command = Command::new(get_bin());
+    command
+        .arg("diff")
+        .arg("uv.lock")
+        .arg("uv.lock")
+        .arg("--preview");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No differences found
+    "###);
+
+    Ok(())
+}
+
+/// Compare a lockfile against the environment it was synced to, omitting any locked packages
+/// that are excluded by their markers.
+#[test]
+fn diff_environment() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig", "anyio ; sys_platform == 'emscripten'"]
+        "#,
+    )?;
+    context.lock().assert().success();
+    context.sync().assert().success();
+
+    let mut command = Command::new(get_bin());
+    command.arg("diff").arg("uv.lock").arg("--preview");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No differences found
+    "###);
+
+    // Removing a package from the environment should be reported.
+    context.pip_uninstall().arg("iniconfig").assert().success();
+
+    let mut command = Command::new(get_bin());
+    command.arg("diff").arg("uv.lock").arg("--preview");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+     - iniconfig 2.0.0
+
+    ----- stderr -----
+    "###);
+
+    Ok(())
+}
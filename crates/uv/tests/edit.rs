@@ -1,6 +1,7 @@
 #![cfg(all(feature = "python", feature = "pypi"))]
 
 use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
 use assert_fs::prelude::*;
 use indoc::indoc;
 use insta::assert_snapshot;
@@ -577,7 +578,7 @@ fn add_raw_error() -> Result<()> {
     // Provide a tag without a Git source.
     uv_snapshot!(context.filters(), context.add(&[]).arg("uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage").arg("--tag").arg("0.0.1").arg("--raw-sources").arg("--preview"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -2125,6 +2126,253 @@ fn add_puts_default_indentation_in_pyproject_toml_if_not_observed() -> Result<()
 }
 
 /// Add a requirement without updating the lockfile.
+/// Preserve comments, blank lines, and alphabetical ordering when editing a hand-maintained
+/// dependency array.
+#[test]
+fn add_remove_preserves_formatting() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            # Runtime dependencies.
+            "anyio==3.7.0",  # Pinned for compatibility.
+
+            "sniffio==1.3.1",
+        ]
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.add(&["iniconfig==2.0.0"]), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    Resolved 5 packages in [TIME]
+    Prepared 5 packages in [TIME]
+    Installed 5 packages in [TIME]
+     + anyio==3.7.0
+     + idna==3.6
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+     + sniffio==1.3.1
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    // `iniconfig` should be inserted in alphabetical order, leaving the comments in place.
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            # Runtime dependencies.
+            "anyio==3.7.0",  # Pinned for compatibility.
+            "iniconfig==2.0.0",
+
+            "sniffio==1.3.1",
+        ]
+        "###
+        );
+    });
+
+    uv_snapshot!(context.filters(), context.remove(&["iniconfig"]), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv remove` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    Prepared 1 package in [TIME]
+    Uninstalled 2 packages in [TIME]
+    Installed 1 package in [TIME]
+     - iniconfig==2.0.0
+     - project==0.1.0 (from file://[TEMP_DIR]/)
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    // The original formatting should be restored.
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            # Runtime dependencies.
+            "anyio==3.7.0",  # Pinned for compatibility.
+
+            "sniffio==1.3.1",
+        ]
+        "###
+        );
+    });
+
+    Ok(())
+}
+
+/// Append to the end of a dependency array that isn't sorted.
+#[test]
+fn add_unsorted() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "sniffio==1.3.1",
+            "anyio==3.7.0",
+        ]
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.add(&["iniconfig==2.0.0"]), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    Resolved 5 packages in [TIME]
+    Prepared 5 packages in [TIME]
+    Installed 5 packages in [TIME]
+     + anyio==3.7.0
+     + idna==3.6
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+     + sniffio==1.3.1
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "sniffio==1.3.1",
+            "anyio==3.7.0",
+            "iniconfig==2.0.0",
+        ]
+        "###
+        );
+    });
+
+    Ok(())
+}
+
+/// Write the requirement verbatim with `--raw`.
+#[test]
+fn add_raw() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+    "#})?;
+
+    // The requirement should be neither normalized nor given a lower bound.
+    uv_snapshot!(context.filters(), context.add(&["Iniconfig >= 1"]).arg("--raw"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "Iniconfig >= 1",
+        ]
+        "###
+        );
+    });
+
+    Ok(())
+}
+
+/// Requirements without a package name can't be written verbatim with `--raw`.
+#[test]
+fn add_raw_unnamed() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.add(&["https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl"]).arg("--raw"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    error: Cannot add `https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl` verbatim, as it doesn't include a package name. Specify the requirement as `<name> @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl`, or omit the `--raw` flag.
+    "###);
+
+    // The `pyproject.toml` should be unchanged.
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+    assert_snapshot!(
+        pyproject_toml, @r###"
+    [project]
+    name = "project"
+    version = "0.1.0"
+    requires-python = ">=3.12"
+    dependencies = []
+    "###
+    );
+
+    Ok(())
+}
+
 #[test]
 fn add_frozen() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -2234,7 +2482,7 @@ fn add_reject_multiple_git_ref_flags() {
         .arg("--branch")
         .arg("test"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -2255,7 +2503,7 @@ fn add_reject_multiple_git_ref_flags() {
         .arg("--rev")
         .arg("326b943"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -2276,7 +2524,7 @@ fn add_reject_multiple_git_ref_flags() {
         .arg("--tag")
         .arg("0.0.2"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -2306,7 +2554,7 @@ fn add_error() -> Result<()> {
 
     uv_snapshot!(context.filters(), context.add(&["xyz"]), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2713,6 +2961,12 @@ fn add_lower_bound_local() -> Result<()> {
         version = 1
         requires-python = ">=3.12"
 
+        [options]
+        indexes = [
+            "https://pypi.org/simple",
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "local-simple-a"
         version = "1.2.3+foo"
@@ -2912,3 +3166,467 @@ fn add_repeat() -> Result<()> {
 
     Ok(())
 }
+
+/// Add platform-specific requirements with `--marker`.
+#[test]
+fn add_marker() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.add(&["anyio==3.7.0"]).arg("--marker").arg("sys_platform == 'win32'").arg("--frozen"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    "###);
+
+    // The requirement for another platform should be added alongside the existing requirement,
+    // and the marker should be combined with the requirement's own marker.
+    uv_snapshot!(context.filters(), context.add(&["anyio==4.3.0 ; python_version >= '3.12'"]).arg("-m").arg("sys_platform != 'win32'").arg("--frozen"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "anyio==3.7.0 ; sys_platform == 'win32'",
+            "anyio==4.3.0 ; python_full_version >= '3.12' and sys_platform != 'win32'",
+        ]
+        "###
+        );
+    });
+
+    // Adding a requirement with the same marker should update the existing requirement.
+    uv_snapshot!(context.filters(), context.add(&["anyio==3.6.2"]).arg("--marker").arg("sys_platform == 'win32'").arg("--frozen"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "anyio==3.6.2 ; sys_platform == 'win32'",
+            "anyio==4.3.0 ; python_full_version >= '3.12' and sys_platform != 'win32'",
+        ]
+        "###
+        );
+    });
+
+    Ok(())
+}
+
+/// Add a dependency that doesn't support the project's full `requires-python` range, resolving the
+/// conflict with `--fix-requires-python`.
+#[test]
+fn add_fix_requires_python() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Every version of `pygls>=1.1.0` requires Python 3.7.9 or later.
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.7"
+        dependencies = []
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.add(&["pygls>=1.1.0"]).arg("--fix-requires-python").arg("narrow").arg("--no-sync"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    Updated `requires-python` to `>=3.7.9`, as required by pygls
+    Resolved 9 packages in [TIME]
+    hint: pygls v1.3.0 was excluded because it requires Python >=3.8, but the project supports Python >=3.7.9 (using v1.2.1)
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.7.9"
+        dependencies = [
+            "pygls>=1.1.0",
+        ]
+        "###
+        );
+    });
+
+    // Alternatively, limit the dependency to the Python versions it supports.
+    fs_err::remove_file(context.temp_dir.join("uv.lock"))?;
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.7"
+        dependencies = []
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.add(&["pygls>=1.1.0"]).arg("--fix-requires-python").arg("marker").arg("--no-sync"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    Limited pygls to Python versions it supports, with the marker `python_full_version >= '3.7.9'`
+    Resolved 9 packages in [TIME]
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.7"
+        dependencies = [
+            "pygls>=1.1.0 ; python_full_version >= '3.7.9'",
+        ]
+        "###
+        );
+    });
+
+    Ok(())
+}
+
+/// Narrowing `requires-python` with `--fix-requires-python` should retain any upper bound.
+#[test]
+fn add_fix_requires_python_upper_bound() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.7,<3.13"
+        dependencies = []
+    "#})?;
+
+    context
+        .add(&["pygls>=1.1.0"])
+        .arg("--fix-requires-python")
+        .arg("narrow")
+        .arg("--no-sync")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains(
+            "Updated `requires-python` to `>=3.7.9, <3.13`, as required by pygls",
+        ));
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.7.9, <3.13"
+        dependencies = [
+            "pygls>=1.1.0",
+        ]
+        "###
+        );
+    });
+
+    Ok(())
+}
+
+/// Warn about, or refuse to add, a package whose name resembles that of a popular package.
+#[test]
+fn add_typosquat() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.add(&["reqeusts"]).arg("--typosquat-check").arg("error"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    error: The package `reqeusts` is similar to the popular package `requests`; did you mean `requests`?
+    "###);
+
+    // The `pyproject.toml` should be unchanged.
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+    assert!(!pyproject_toml.contains("reqeusts"));
+
+    // The result of the check should be cached...
+    let entry = context.cache_dir.child("typosquats-v0").child("reqeusts");
+    assert_eq!(fs_err::read_to_string(entry.path())?, "requests");
+
+    // ...and the policy should still be applied to the cached result.
+    uv_snapshot!(context.filters(), context.add(&["reqeusts"]).arg("--typosquat-check").arg("error"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    error: The package `reqeusts` is similar to the popular package `requests`; did you mean `requests`?
+    "###);
+
+    Ok(())
+}
+
+/// Import the requirements from a `requirements.txt` file, along with its constraints.
+#[test]
+fn add_requirements_file() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "iniconfig",
+        ]
+    "#})?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        -c constraints.txt
+        anyio==3.7.0
+        typing-extensions
+    "})?;
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("idna<3.7")?;
+
+    // Without `--sync-manifest`, existing dependencies are retained.
+    uv_snapshot!(context.filters(), context.add(&[]).arg("-r").arg("requirements.txt").arg("--frozen"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "iniconfig",
+            "anyio==3.7.0",
+            "typing-extensions",
+        ]
+
+        [tool.uv]
+        constraint-dependencies = [
+            "idna<3.7",
+        ]
+        "###
+        );
+    });
+
+    // With `--sync-manifest`, the dependencies are made to match the requirements file.
+    uv_snapshot!(context.filters(), context.add(&[]).arg("-r").arg("requirements.txt").arg("--sync-manifest"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    Resolved 5 packages in [TIME]
+    Prepared 5 packages in [TIME]
+    Installed 5 packages in [TIME]
+     + anyio==3.7.0
+     + idna==3.6
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+     + sniffio==1.3.1
+     + typing-extensions==4.10.0
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "anyio==3.7.0",
+            "typing-extensions",
+        ]
+
+        [tool.uv]
+        constraint-dependencies = [
+            "idna<3.7",
+        ]
+        "###
+        );
+    });
+
+    Ok(())
+}
+
+/// Import the requirements files of a project into their respective dependency groups.
+#[test]
+fn add_requirements_file_groups() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [project.optional-dependencies]
+        async = ["sniffio"]
+
+        [tool.uv]
+        dev-dependencies = ["pytest"]
+    "#})?;
+
+    context
+        .temp_dir
+        .child("requirements.txt")
+        .write_str("anyio==3.7.0")?;
+    context
+        .temp_dir
+        .child("requirements-dev.txt")
+        .write_str("iniconfig")?;
+    context
+        .temp_dir
+        .child("requirements-async.txt")
+        .write_str("typing-extensions")?;
+
+    uv_snapshot!(context.filters(), context.add(&[])
+        .arg("-r").arg("requirements.txt")
+        .arg("--dev-requirements").arg("requirements-dev.txt")
+        .arg("--optional-requirements").arg("async=requirements-async.txt")
+        .arg("--sync-manifest")
+        .arg("--frozen"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv add` is experimental and may change without warning
+    "###);
+
+    let pyproject_toml = fs_err::read_to_string(context.temp_dir.join("pyproject.toml"))?;
+
+    // Each group is made to match the requirements file imported into it.
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject_toml, @r###"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "anyio==3.7.0",
+        ]
+
+        [project.optional-dependencies]
+        async = [
+            "typing-extensions",
+        ]
+
+        [tool.uv]
+        dev-dependencies = [
+            "iniconfig",
+        ]
+        "###
+        );
+    });
+
+    // The extra must be given alongside the path.
+    uv_snapshot!(context.filters(), context.add(&[])
+        .arg("--optional-requirements").arg("requirements-async.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'requirements-async.txt' for '--optional-requirements <OPTIONAL_REQUIREMENTS>': expected an extra and a path, separated by `=` (e.g., `docs=requirements-docs.txt`), but found: `requirements-async.txt`
+
+    For more information, try '--help'.
+    "###);
+
+    Ok(())
+}
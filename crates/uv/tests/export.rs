@@ -0,0 +1,321 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Create a `uv export` command for the given format.
+fn export(context: &TestContext, format: &str) -> Command {
+    let mut command = Command::new(get_bin());
+    command.arg("export").arg("--format").arg(format);
+    context.add_shared_args(&mut command);
+    command
+}
+
+/// Export the locked dependencies of a project as a Nix expression and a Bazel file, including
+/// the artifacts and dependencies for every platform.
+#[test]
+fn export_formats() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==3.7.0", "click==8.1.7"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), export(&context, "nix"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv export --format nix
+    {
+      "anyio" = {
+        "3.7.0" = {
+          artifacts = [
+            { filename = "anyio-3.7.0-py3-none-any.whl"; url = "https://files.pythonhosted.org/packages/68/fe/7ce1926952c8a403b35029e194555558514b365ad77d75125f521a2bec62/anyio-3.7.0-py3-none-any.whl"; sha256 = "eddca883c4175f14df8aedce21054bfca3adb70ffe76a9f607aef9d7fa2ea7f0"; }
+            { filename = "anyio-3.7.0.tar.gz"; url = "https://files.pythonhosted.org/packages/c6/b3/fefbf7e78ab3b805dec67d698dc18dd505af7a18a8dd08868c9b4fa736b5/anyio-3.7.0.tar.gz"; sha256 = "275d9973793619a5374e1c89a4f4ad3f4b0a5510a2b5b939444bee8f4c4d37ce"; }
+          ];
+          dependencies = [
+            { name = "idna"; version = "3.6"; }
+            { name = "sniffio"; version = "1.3.1"; }
+          ];
+        };
+      };
+      "click" = {
+        "8.1.7" = {
+          artifacts = [
+            { filename = "click-8.1.7-py3-none-any.whl"; url = "https://files.pythonhosted.org/packages/00/2e/d53fa4befbf2cfa713304affc7ca780ce4fc1fd8710527771b58311a3229/click-8.1.7-py3-none-any.whl"; sha256 = "ae74fb96c20a0277a1d615f1e4d73c8414f5a98db8b799a7931d1582f3390c28"; }
+            { filename = "click-8.1.7.tar.gz"; url = "https://files.pythonhosted.org/packages/96/d3/f04c7bfcf5c1862a2a5b845c6b2b360488cf47af55dfa79c98f6a6bf98b5/click-8.1.7.tar.gz"; sha256 = "ca9853ad459e787e2192211578cc907e7594e294c7ccc834310722b41b9ca6de"; }
+          ];
+          dependencies = [
+            { name = "colorama"; version = "0.4.6"; marker = "platform_system == 'Windows'"; }
+          ];
+        };
+      };
+      "colorama" = {
+        "0.4.6" = {
+          artifacts = [
+            { filename = "colorama-0.4.6-py2.py3-none-any.whl"; url = "https://files.pythonhosted.org/packages/d1/d6/3965ed04c63042e047cb6a3e6ed1a63a35087b6a609aa3a15ed8ac56c221/colorama-0.4.6-py2.py3-none-any.whl"; sha256 = "4f1d9991f5acc0ca119f9d443620b77f9d6b33703e51011c16baf57afb285fc6"; }
+            { filename = "colorama-0.4.6.tar.gz"; url = "https://files.pythonhosted.org/packages/d8/53/6f443c9a4a8358a93a6792e2acffb9d9d5cb0a5cfd8802644b7b1c9a02e4/colorama-0.4.6.tar.gz"; sha256 = "08695f5cb7ed6e0531a20572697297273c47b8cae5a63ffc6d6ed5c201be6e44"; }
+          ];
+          dependencies = [];
+        };
+      };
+      "idna" = {
+        "3.6" = {
+          artifacts = [
+            { filename = "idna-3.6-py3-none-any.whl"; url = "https://files.pythonhosted.org/packages/c2/e7/a82b05cf63a603df6e68d59ae6a68bf5064484a0718ea5033660af4b54a9/idna-3.6-py3-none-any.whl"; sha256 = "c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f"; }
+            { filename = "idna-3.6.tar.gz"; url = "https://files.pythonhosted.org/packages/bf/3f/ea4b9117521a1e9c50344b909be7886dd00a519552724809bb1f486986c2/idna-3.6.tar.gz"; sha256 = "9ecdbbd083b06798ae1e86adcbfe8ab1479cf864e4ee30fe4e46a003d12491ca"; }
+          ];
+          dependencies = [];
+        };
+      };
+      "sniffio" = {
+        "1.3.1" = {
+          artifacts = [
+            { filename = "sniffio-1.3.1-py3-none-any.whl"; url = "https://files.pythonhosted.org/packages/e9/44/75a9c9421471a6c4805dbf2356f7c181a29c1879239abab1ea2cc8f38b40/sniffio-1.3.1-py3-none-any.whl"; sha256 = "2f6da418d1f1e0fddd844478f41680e794e6051915791a034ff65e5f100525a2"; }
+            { filename = "sniffio-1.3.1.tar.gz"; url = "https://files.pythonhosted.org/packages/a2/87/a6771e1546d97e7e041b6ae58d80074f81b7d5121207425c964ddf5cfdbd/sniffio-1.3.1.tar.gz"; sha256 = "f4324edc670a0f49750a81b895f35c3adb843cca46f0530f79fc1babb23789dc"; }
+          ];
+          dependencies = [];
+        };
+      };
+    }
+
+    ----- stderr -----
+    warning: `uv export` is experimental and may change without warning
+    Resolved 7 packages in [TIME]
+    "###);
+
+    uv_snapshot!(context.filters(), export(&context, "bazel").arg("--locked"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv export --format bazel
+    PACKAGES = {
+        "anyio": {
+            "3.7.0": {
+                "artifacts": [
+                    {"filename": "anyio-3.7.0-py3-none-any.whl", "url": "https://files.pythonhosted.org/packages/68/fe/7ce1926952c8a403b35029e194555558514b365ad77d75125f521a2bec62/anyio-3.7.0-py3-none-any.whl", "sha256": "eddca883c4175f14df8aedce21054bfca3adb70ffe76a9f607aef9d7fa2ea7f0"},
+                    {"filename": "anyio-3.7.0.tar.gz", "url": "https://files.pythonhosted.org/packages/c6/b3/fefbf7e78ab3b805dec67d698dc18dd505af7a18a8dd08868c9b4fa736b5/anyio-3.7.0.tar.gz", "sha256": "275d9973793619a5374e1c89a4f4ad3f4b0a5510a2b5b939444bee8f4c4d37ce"},
+                ],
+                "deps": [
+                    {"name": "idna", "version": "3.6"},
+                    {"name": "sniffio", "version": "1.3.1"},
+                ],
+            },
+        },
+        "click": {
+            "8.1.7": {
+                "artifacts": [
+                    {"filename": "click-8.1.7-py3-none-any.whl", "url": "https://files.pythonhosted.org/packages/00/2e/d53fa4befbf2cfa713304affc7ca780ce4fc1fd8710527771b58311a3229/click-8.1.7-py3-none-any.whl", "sha256": "ae74fb96c20a0277a1d615f1e4d73c8414f5a98db8b799a7931d1582f3390c28"},
+                    {"filename": "click-8.1.7.tar.gz", "url": "https://files.pythonhosted.org/packages/96/d3/f04c7bfcf5c1862a2a5b845c6b2b360488cf47af55dfa79c98f6a6bf98b5/click-8.1.7.tar.gz", "sha256": "ca9853ad459e787e2192211578cc907e7594e294c7ccc834310722b41b9ca6de"},
+                ],
+                "deps": [
+                    {"name": "colorama", "version": "0.4.6", "marker": "platform_system == 'Windows'"},
+                ],
+            },
+        },
+        "colorama": {
+            "0.4.6": {
+                "artifacts": [
+                    {"filename": "colorama-0.4.6-py2.py3-none-any.whl", "url": "https://files.pythonhosted.org/packages/d1/d6/3965ed04c63042e047cb6a3e6ed1a63a35087b6a609aa3a15ed8ac56c221/colorama-0.4.6-py2.py3-none-any.whl", "sha256": "4f1d9991f5acc0ca119f9d443620b77f9d6b33703e51011c16baf57afb285fc6"},
+                    {"filename": "colorama-0.4.6.tar.gz", "url": "https://files.pythonhosted.org/packages/d8/53/6f443c9a4a8358a93a6792e2acffb9d9d5cb0a5cfd8802644b7b1c9a02e4/colorama-0.4.6.tar.gz", "sha256": "08695f5cb7ed6e0531a20572697297273c47b8cae5a63ffc6d6ed5c201be6e44"},
+                ],
+                "deps": [],
+            },
+        },
+        "idna": {
+            "3.6": {
+                "artifacts": [
+                    {"filename": "idna-3.6-py3-none-any.whl", "url": "https://files.pythonhosted.org/packages/c2/e7/a82b05cf63a603df6e68d59ae6a68bf5064484a0718ea5033660af4b54a9/idna-3.6-py3-none-any.whl", "sha256": "c05567e9c24a6b9faaa835c4821bad0590fbb9d5779e7caa6e1cc4978e7eb24f"},
+                    {"filename": "idna-3.6.tar.gz", "url": "https://files.pythonhosted.org/packages/bf/3f/ea4b9117521a1e9c50344b909be7886dd00a519552724809bb1f486986c2/idna-3.6.tar.gz", "sha256": "9ecdbbd083b06798ae1e86adcbfe8ab1479cf864e4ee30fe4e46a003d12491ca"},
+                ],
+                "deps": [],
+            },
+        },
+        "sniffio": {
+            "1.3.1": {
+                "artifacts": [
+                    {"filename": "sniffio-1.3.1-py3-none-any.whl", "url": "https://files.pythonhosted.org/packages/e9/44/75a9c9421471a6c4805dbf2356f7c181a29c1879239abab1ea2cc8f38b40/sniffio-1.3.1-py3-none-any.whl", "sha256": "2f6da418d1f1e0fddd844478f41680e794e6051915791a034ff65e5f100525a2"},
+                    {"filename": "sniffio-1.3.1.tar.gz", "url": "https://files.pythonhosted.org/packages/a2/87/a6771e1546d97e7e041b6ae58d80074f81b7d5121207425c964ddf5cfdbd/sniffio-1.3.1.tar.gz", "sha256": "f4324edc670a0f49750a81b895f35c3adb843cca46f0530f79fc1babb23789dc"},
+                ],
+                "deps": [],
+            },
+        },
+    }
+
+    ----- stderr -----
+    warning: `uv export` is experimental and may change without warning
+    Resolved 7 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Dependencies without remote artifacts (e.g., local paths) can't be exported.
+#[test]
+fn export_local_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let child = context.temp_dir.child("child");
+    child.child("pyproject.toml").write_str(
+        r#"
+        [project]
+        name = "child"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+        "#,
+    )?;
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["child"]
+
+        [tool.uv.sources]
+        child = { path = "child" }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), export(&context, "nix"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv export` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    error: Only dependencies with remote artifacts and known SHA-256 hashes can be exported, but the following do not: `child`
+    "###);
+
+    Ok(())
+}
+
+/// Export the direct dependencies of a project, as declared, without locking.
+#[test]
+fn export_requirements_in() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio>=3", "typing-extensions ; python_version < '3.13'"]
+
+        [project.optional-dependencies]
+        async = ["sniffio>=1.3"]
+        docs = ["mkdocs"]
+
+        [tool.uv]
+        dev-dependencies = ["pytest>=8"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), export(&context, "requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv export --format requirements.in
+    anyio>=3
+    typing-extensions ; python_full_version < '3.13'
+
+    ----- stderr -----
+    warning: `uv export` is experimental and may change without warning
+    "###);
+
+    uv_snapshot!(context.filters(), export(&context, "requirements.in").arg("--extra").arg("async").arg("--dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv export --format requirements.in
+    anyio>=3
+    pytest>=8
+    sniffio>=1.3
+    typing-extensions ; python_full_version < '3.13'
+
+    ----- stderr -----
+    warning: `uv export` is experimental and may change without warning
+    "###);
+
+    // The lockfile isn't consulted (or created).
+    assert!(!context.temp_dir.child("uv.lock").exists());
+
+    Ok(())
+}
+
+/// Export the direct dependencies of a project, retaining any `tool.uv.sources` and including the
+/// requested dependency groups.
+#[test]
+fn export_requirements_in_sources_and_groups() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio>=3", "iniconfig"]
+
+        [dependency-groups]
+        lint = ["ruff>=0.5"]
+        test = ["pytest>=8", { include-group = "lint" }]
+
+        [tool.uv.sources]
+        iniconfig = { url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl" }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), export(&context, "requirements.in").arg("--group").arg("test"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv export --format requirements.in
+    anyio>=3
+    iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl
+    pytest>=8
+    ruff>=0.5
+
+    ----- stderr -----
+    warning: `uv export` is experimental and may change without warning
+    warning: `uv.sources` is experimental and may change without warning
+    "###);
+
+    uv_snapshot!(context.filters(), export(&context, "requirements.in").arg("--group").arg("docs"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv export` is experimental and may change without warning
+    error: Dependency group `docs` is not defined in the `dependency-groups` table of any workspace member
+    "###);
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+#![cfg(all(feature = "python", feature = "pypi", unix))]
+
+use std::os::unix::fs::PermissionsExt;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+/// An unknown subcommand runs the matching `uv-<name>` executable on the `PATH`, with the global
+/// settings passed via environment variables.
+#[test]
+fn extension() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let bin = context.temp_dir.child("bin");
+    let extension = bin.child("uv-hello");
+    extension.write_str(indoc! { r#"
+        #!/bin/sh
+        echo "args:" "$@"
+        echo "cache: $UV_CACHE_DIR/"
+        echo "preview: ${UV_PREVIEW:-0}"
+        echo "offline: ${UV_OFFLINE:-0}"
+        echo "python-preference: $UV_PYTHON_PREFERENCE"
+        "#
+    })?;
+    fs_err::set_permissions(&extension, std::fs::Permissions::from_mode(0o755))?;
+
+    let path = std::env::join_paths(std::iter::once(bin.to_path_buf()).chain(
+        std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+    ))?;
+
+    uv_snapshot!(context.filters(), context.command()
+        .arg("hello")
+        .arg("world")
+        .arg("--flag")
+        .env("PATH", &path), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    args: world --flag
+    cache: [CACHE_DIR]/
+    preview: 0
+    offline: 0
+    python-preference: only-system
+
+    ----- stderr -----
+    "###);
+
+    // Global flags provided before the subcommand are passed along.
+    uv_snapshot!(context.filters(), context.command()
+        .arg("--preview")
+        .arg("--offline")
+        .arg("--python-preference")
+        .arg("system")
+        .arg("hello")
+        .env("PATH", &path), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    args:
+    cache: [CACHE_DIR]/
+    preview: 1
+    offline: 1
+    python-preference: system
+
+    ----- stderr -----
+    "###);
+
+    Ok(())
+}
+
+/// Without a matching executable, an unknown subcommand is an error.
+#[test]
+fn extension_missing() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), context.command().arg("hello"), @r###"
+    success: false
+    exit_code: 7
+    ----- stdout -----
+
+    ----- stderr -----
+    error: unrecognized subcommand 'hello'
+
+    Usage: uv [OPTIONS] <COMMAND>
+
+    For more information, try '--help'.
+    "###);
+}
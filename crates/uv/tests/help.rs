@@ -16,20 +16,28 @@ fn help() {
     Usage: uv [OPTIONS] <COMMAND>
 
     Commands:
-      run      Run a command or script (experimental)
-      init     Create a new project (experimental)
-      add      Add dependencies to the project (experimental)
-      remove   Remove dependencies from the project (experimental)
-      sync     Update the project's environment (experimental)
-      lock     Update the project's lockfile (experimental)
-      tree     Display the project's dependency tree (experimental)
-      tool     Run and manage tools provided by Python packages (experimental)
-      python   Manage Python versions and installations (experimental)
-      pip      Manage Python packages with a pip-compatible interface
-      venv     Create a virtual environment
-      cache    Manage uv's cache
-      version  Display uv's version
-      help     Display documentation for a command
+      run        Run a command or script (experimental)
+      init       Create a new project (experimental)
+      add        Add dependencies to the project (experimental)
+      remove     Remove dependencies from the project (experimental)
+      sync       Update the project's environment (experimental)
+      test       Run the project's tests against each of its test Python versions (experimental)
+      lock       Update the project's lockfile (experimental)
+      tree       Display the project's dependency tree (experimental)
+      why        Explain why a package is included in the project's lockfile (experimental)
+      diff       Compare two lockfiles, or a lockfile and the current environment (experimental)
+      vendor     Unpack the project's locked dependencies into a project-local directory (experimental)
+      export     Export the project's locked dependencies for use by Nix or Bazel (experimental)
+      build      Build the project into source distributions and wheels (experimental)
+      server     Answer queries about the project over JSON-RPC, for editor integrations (experimental)
+      workspace  Inspect the members of the workspace and the dependencies between them (experimental)
+      tool       Run and manage tools provided by Python packages (experimental)
+      python     Manage Python versions and installations (experimental)
+      pip        Manage Python packages with a pip-compatible interface
+      venv       Create a virtual environment
+      cache      Manage uv's cache
+      version    Display uv's version
+      help       Display documentation for a command
 
     Cache options:
       -n, --no-cache               Avoid reading from or writing to the cache, instead using a temporary
@@ -52,10 +60,16 @@ fn help() {
                                        certificate store [env: UV_NATIVE_TLS=]
           --offline                    Disable network access
           --no-progress                Hide all progress outputs
+          --log-file <LOG_FILE>        Write a structured log of the invocation to the given file, in
+                                       JSON Lines format [env: UV_LOG_FILE=]
+          --directory <DIRECTORY>      Change to the given directory prior to running the command [env:
+                                       UV_WORKING_DIRECTORY=]
           --config-file <CONFIG_FILE>  The path to a `uv.toml` file to use for configuration [env:
                                        UV_CONFIG_FILE=]
           --no-config                  Avoid discovering configuration files (`pyproject.toml`,
                                        `uv.toml`) [env: UV_NO_CONFIG=]
+          --no-user-constraints        Avoid applying the user-level constraints file [env:
+                                       UV_NO_USER_CONSTRAINTS=]
       -h, --help                       Display the concise help for this command
       -V, --version                    Display the uv version
 
@@ -78,20 +92,28 @@ fn help_flag() {
     Usage: uv [OPTIONS] <COMMAND>
 
     Commands:
-      run      Run a command or script (experimental)
-      init     Create a new project (experimental)
-      add      Add dependencies to the project (experimental)
-      remove   Remove dependencies from the project (experimental)
-      sync     Update the project's environment (experimental)
-      lock     Update the project's lockfile (experimental)
-      tree     Display the project's dependency tree (experimental)
-      tool     Run and manage tools provided by Python packages (experimental)
-      python   Manage Python versions and installations (experimental)
-      pip      Manage Python packages with a pip-compatible interface
-      venv     Create a virtual environment
-      cache    Manage uv's cache
-      version  Display uv's version
-      help     Display documentation for a command
+      run        Run a command or script (experimental)
+      init       Create a new project (experimental)
+      add        Add dependencies to the project (experimental)
+      remove     Remove dependencies from the project (experimental)
+      sync       Update the project's environment (experimental)
+      test       Run the project's tests against each of its test Python versions (experimental)
+      lock       Update the project's lockfile (experimental)
+      tree       Display the project's dependency tree (experimental)
+      why        Explain why a package is included in the project's lockfile (experimental)
+      diff       Compare two lockfiles, or a lockfile and the current environment (experimental)
+      vendor     Unpack the project's locked dependencies into a project-local directory (experimental)
+      export     Export the project's locked dependencies for use by Nix or Bazel (experimental)
+      build      Build the project into source distributions and wheels (experimental)
+      server     Answer queries about the project over JSON-RPC, for editor integrations (experimental)
+      workspace  Inspect the members of the workspace and the dependencies between them (experimental)
+      tool       Run and manage tools provided by Python packages (experimental)
+      python     Manage Python versions and installations (experimental)
+      pip        Manage Python packages with a pip-compatible interface
+      venv       Create a virtual environment
+      cache      Manage uv's cache
+      version    Display uv's version
+      help       Display documentation for a command
 
     Cache options:
       -n, --no-cache               Avoid reading from or writing to the cache, instead using a temporary
@@ -114,10 +136,16 @@ fn help_flag() {
                                        certificate store [env: UV_NATIVE_TLS=]
           --offline                    Disable network access
           --no-progress                Hide all progress outputs
+          --log-file <LOG_FILE>        Write a structured log of the invocation to the given file, in
+                                       JSON Lines format [env: UV_LOG_FILE=]
+          --directory <DIRECTORY>      Change to the given directory prior to running the command [env:
+                                       UV_WORKING_DIRECTORY=]
           --config-file <CONFIG_FILE>  The path to a `uv.toml` file to use for configuration [env:
                                        UV_CONFIG_FILE=]
           --no-config                  Avoid discovering configuration files (`pyproject.toml`,
                                        `uv.toml`) [env: UV_NO_CONFIG=]
+          --no-user-constraints        Avoid applying the user-level constraints file [env:
+                                       UV_NO_USER_CONSTRAINTS=]
       -h, --help                       Display the concise help for this command
       -V, --version                    Display the uv version
 
@@ -139,20 +167,28 @@ fn help_short_flag() {
     Usage: uv [OPTIONS] <COMMAND>
 
     Commands:
-      run      Run a command or script (experimental)
-      init     Create a new project (experimental)
-      add      Add dependencies to the project (experimental)
-      remove   Remove dependencies from the project (experimental)
-      sync     Update the project's environment (experimental)
-      lock     Update the project's lockfile (experimental)
-      tree     Display the project's dependency tree (experimental)
-      tool     Run and manage tools provided by Python packages (experimental)
-      python   Manage Python versions and installations (experimental)
-      pip      Manage Python packages with a pip-compatible interface
-      venv     Create a virtual environment
-      cache    Manage uv's cache
-      version  Display uv's version
-      help     Display documentation for a command
+      run        Run a command or script (experimental)
+      init       Create a new project (experimental)
+      add        Add dependencies to the project (experimental)
+      remove     Remove dependencies from the project (experimental)
+      sync       Update the project's environment (experimental)
+      test       Run the project's tests against each of its test Python versions (experimental)
+      lock       Update the project's lockfile (experimental)
+      tree       Display the project's dependency tree (experimental)
+      why        Explain why a package is included in the project's lockfile (experimental)
+      diff       Compare two lockfiles, or a lockfile and the current environment (experimental)
+      vendor     Unpack the project's locked dependencies into a project-local directory (experimental)
+      export     Export the project's locked dependencies for use by Nix or Bazel (experimental)
+      build      Build the project into source distributions and wheels (experimental)
+      server     Answer queries about the project over JSON-RPC, for editor integrations (experimental)
+      workspace  Inspect the members of the workspace and the dependencies between them (experimental)
+      tool       Run and manage tools provided by Python packages (experimental)
+      python     Manage Python versions and installations (experimental)
+      pip        Manage Python packages with a pip-compatible interface
+      venv       Create a virtual environment
+      cache      Manage uv's cache
+      version    Display uv's version
+      help       Display documentation for a command
 
     Cache options:
       -n, --no-cache               Avoid reading from or writing to the cache, instead using a temporary
@@ -175,10 +211,16 @@ fn help_short_flag() {
                                        certificate store [env: UV_NATIVE_TLS=]
           --offline                    Disable network access
           --no-progress                Hide all progress outputs
+          --log-file <LOG_FILE>        Write a structured log of the invocation to the given file, in
+                                       JSON Lines format [env: UV_LOG_FILE=]
+          --directory <DIRECTORY>      Change to the given directory prior to running the command [env:
+                                       UV_WORKING_DIRECTORY=]
           --config-file <CONFIG_FILE>  The path to a `uv.toml` file to use for configuration [env:
                                        UV_CONFIG_FILE=]
           --no-config                  Avoid discovering configuration files (`pyproject.toml`,
                                        `uv.toml`) [env: UV_NO_CONFIG=]
+          --no-user-constraints        Avoid applying the user-level constraints file [env:
+                                       UV_NO_USER_CONSTRAINTS=]
       -h, --help                       Display the concise help for this command
       -V, --version                    Display the uv version
 
@@ -330,6 +372,24 @@ fn help_subcommand() {
               
               For example, spinners or progress bars.
 
+          --log-file <LOG_FILE>
+              Write a structured log of the invocation to the given file, in JSON Lines format.
+              
+              The log includes the command, the resolved settings, a summary of each resolution, the
+              network requests, and timings, independent of the console verbosity. If the file already
+              exists, entries are appended to it.
+              
+              [env: UV_LOG_FILE=]
+
+          --directory <DIRECTORY>
+              Change to the given directory prior to running the command.
+              
+              Relative paths provided on the command line are resolved against the given directory, and
+              paths in the output are displayed relative to it, as if uv had been invoked from within
+              the directory.
+              
+              [env: UV_WORKING_DIRECTORY=]
+
           --config-file <CONFIG_FILE>
               The path to a `uv.toml` file to use for configuration.
               
@@ -346,6 +406,16 @@ fn help_subcommand() {
               
               [env: UV_NO_CONFIG=]
 
+          --no-user-constraints
+              Avoid applying the user-level constraints file.
+              
+              Normally, the constraints in `constraints.txt` in the user configuration directory
+              (e.g., `~/.config/uv/constraints.txt`) are applied to the `pip` interface, tools, and
+              scripts. They're not applied when locking a project, such that the lockfile remains
+              reproducible.
+              
+              [env: UV_NO_USER_CONSTRAINTS=]
+
       -h, --help
               Display the concise help for this command
 
@@ -477,6 +547,24 @@ fn help_subsubcommand() {
               
               For example, spinners or progress bars.
 
+          --log-file <LOG_FILE>
+              Write a structured log of the invocation to the given file, in JSON Lines format.
+              
+              The log includes the command, the resolved settings, a summary of each resolution, the
+              network requests, and timings, independent of the console verbosity. If the file already
+              exists, entries are appended to it.
+              
+              [env: UV_LOG_FILE=]
+
+          --directory <DIRECTORY>
+              Change to the given directory prior to running the command.
+              
+              Relative paths provided on the command line are resolved against the given directory, and
+              paths in the output are displayed relative to it, as if uv had been invoked from within
+              the directory.
+              
+              [env: UV_WORKING_DIRECTORY=]
+
           --config-file <CONFIG_FILE>
               The path to a `uv.toml` file to use for configuration.
               
@@ -493,6 +581,16 @@ fn help_subsubcommand() {
               
               [env: UV_NO_CONFIG=]
 
+          --no-user-constraints
+              Avoid applying the user-level constraints file.
+              
+              Normally, the constraints in `constraints.txt` in the user configuration directory
+              (e.g., `~/.config/uv/constraints.txt`) are applied to the `pip` interface, tools, and
+              scripts. They're not applied when locking a project, such that the lockfile remains
+              reproducible.
+              
+              [env: UV_NO_USER_CONSTRAINTS=]
+
       -h, --help
               Display the concise help for this command
 
@@ -545,10 +643,16 @@ fn help_flag_subcommand() {
                                        certificate store [env: UV_NATIVE_TLS=]
           --offline                    Disable network access
           --no-progress                Hide all progress outputs
+          --log-file <LOG_FILE>        Write a structured log of the invocation to the given file, in
+                                       JSON Lines format [env: UV_LOG_FILE=]
+          --directory <DIRECTORY>      Change to the given directory prior to running the command [env:
+                                       UV_WORKING_DIRECTORY=]
           --config-file <CONFIG_FILE>  The path to a `uv.toml` file to use for configuration [env:
                                        UV_CONFIG_FILE=]
           --no-config                  Avoid discovering configuration files (`pyproject.toml`,
                                        `uv.toml`) [env: UV_NO_CONFIG=]
+          --no-user-constraints        Avoid applying the user-level constraints file [env:
+                                       UV_NO_USER_CONSTRAINTS=]
       -h, --help                       Display the concise help for this command
       -V, --version                    Display the uv version
 
@@ -597,10 +701,16 @@ fn help_flag_subsubcommand() {
                                        certificate store [env: UV_NATIVE_TLS=]
           --offline                    Disable network access
           --no-progress                Hide all progress outputs
+          --log-file <LOG_FILE>        Write a structured log of the invocation to the given file, in
+                                       JSON Lines format [env: UV_LOG_FILE=]
+          --directory <DIRECTORY>      Change to the given directory prior to running the command [env:
+                                       UV_WORKING_DIRECTORY=]
           --config-file <CONFIG_FILE>  The path to a `uv.toml` file to use for configuration [env:
                                        UV_CONFIG_FILE=]
           --no-config                  Avoid discovering configuration files (`pyproject.toml`,
                                        `uv.toml`) [env: UV_NO_CONFIG=]
+          --no-user-constraints        Avoid applying the user-level constraints file [env:
+                                       UV_NO_USER_CONSTRAINTS=]
       -h, --help                       Display the concise help for this command
       -V, --version                    Display the uv version
 
@@ -690,20 +800,28 @@ fn help_with_global_option() {
     Usage: uv [OPTIONS] <COMMAND>
 
     Commands:
-      run      Run a command or script (experimental)
-      init     Create a new project (experimental)
-      add      Add dependencies to the project (experimental)
-      remove   Remove dependencies from the project (experimental)
-      sync     Update the project's environment (experimental)
-      lock     Update the project's lockfile (experimental)
-      tree     Display the project's dependency tree (experimental)
-      tool     Run and manage tools provided by Python packages (experimental)
-      python   Manage Python versions and installations (experimental)
-      pip      Manage Python packages with a pip-compatible interface
-      venv     Create a virtual environment
-      cache    Manage uv's cache
-      version  Display uv's version
-      help     Display documentation for a command
+      run        Run a command or script (experimental)
+      init       Create a new project (experimental)
+      add        Add dependencies to the project (experimental)
+      remove     Remove dependencies from the project (experimental)
+      sync       Update the project's environment (experimental)
+      test       Run the project's tests against each of its test Python versions (experimental)
+      lock       Update the project's lockfile (experimental)
+      tree       Display the project's dependency tree (experimental)
+      why        Explain why a package is included in the project's lockfile (experimental)
+      diff       Compare two lockfiles, or a lockfile and the current environment (experimental)
+      vendor     Unpack the project's locked dependencies into a project-local directory (experimental)
+      export     Export the project's locked dependencies for use by Nix or Bazel (experimental)
+      build      Build the project into source distributions and wheels (experimental)
+      server     Answer queries about the project over JSON-RPC, for editor integrations (experimental)
+      workspace  Inspect the members of the workspace and the dependencies between them (experimental)
+      tool       Run and manage tools provided by Python packages (experimental)
+      python     Manage Python versions and installations (experimental)
+      pip        Manage Python packages with a pip-compatible interface
+      venv       Create a virtual environment
+      cache      Manage uv's cache
+      version    Display uv's version
+      help       Display documentation for a command
 
     Cache options:
       -n, --no-cache               Avoid reading from or writing to the cache, instead using a temporary
@@ -726,10 +844,16 @@ fn help_with_global_option() {
                                        certificate store [env: UV_NATIVE_TLS=]
           --offline                    Disable network access
           --no-progress                Hide all progress outputs
+          --log-file <LOG_FILE>        Write a structured log of the invocation to the given file, in
+                                       JSON Lines format [env: UV_LOG_FILE=]
+          --directory <DIRECTORY>      Change to the given directory prior to running the command [env:
+                                       UV_WORKING_DIRECTORY=]
           --config-file <CONFIG_FILE>  The path to a `uv.toml` file to use for configuration [env:
                                        UV_CONFIG_FILE=]
           --no-config                  Avoid discovering configuration files (`pyproject.toml`,
                                        `uv.toml`) [env: UV_NO_CONFIG=]
+          --no-user-constraints        Avoid applying the user-level constraints file [env:
+                                       UV_NO_USER_CONSTRAINTS=]
       -h, --help                       Display the concise help for this command
       -V, --version                    Display the uv version
 
@@ -788,20 +912,28 @@ fn help_with_no_pager() {
     Usage: uv [OPTIONS] <COMMAND>
 
     Commands:
-      run      Run a command or script (experimental)
-      init     Create a new project (experimental)
-      add      Add dependencies to the project (experimental)
-      remove   Remove dependencies from the project (experimental)
-      sync     Update the project's environment (experimental)
-      lock     Update the project's lockfile (experimental)
-      tree     Display the project's dependency tree (experimental)
-      tool     Run and manage tools provided by Python packages (experimental)
-      python   Manage Python versions and installations (experimental)
-      pip      Manage Python packages with a pip-compatible interface
-      venv     Create a virtual environment
-      cache    Manage uv's cache
-      version  Display uv's version
-      help     Display documentation for a command
+      run        Run a command or script (experimental)
+      init       Create a new project (experimental)
+      add        Add dependencies to the project (experimental)
+      remove     Remove dependencies from the project (experimental)
+      sync       Update the project's environment (experimental)
+      test       Run the project's tests against each of its test Python versions (experimental)
+      lock       Update the project's lockfile (experimental)
+      tree       Display the project's dependency tree (experimental)
+      why        Explain why a package is included in the project's lockfile (experimental)
+      diff       Compare two lockfiles, or a lockfile and the current environment (experimental)
+      vendor     Unpack the project's locked dependencies into a project-local directory (experimental)
+      export     Export the project's locked dependencies for use by Nix or Bazel (experimental)
+      build      Build the project into source distributions and wheels (experimental)
+      server     Answer queries about the project over JSON-RPC, for editor integrations (experimental)
+      workspace  Inspect the members of the workspace and the dependencies between them (experimental)
+      tool       Run and manage tools provided by Python packages (experimental)
+      python     Manage Python versions and installations (experimental)
+      pip        Manage Python packages with a pip-compatible interface
+      venv       Create a virtual environment
+      cache      Manage uv's cache
+      version    Display uv's version
+      help       Display documentation for a command
 
     Cache options:
       -n, --no-cache               Avoid reading from or writing to the cache, instead using a temporary
@@ -824,10 +956,16 @@ fn help_with_no_pager() {
                                        certificate store [env: UV_NATIVE_TLS=]
           --offline                    Disable network access
           --no-progress                Hide all progress outputs
+          --log-file <LOG_FILE>        Write a structured log of the invocation to the given file, in
+                                       JSON Lines format [env: UV_LOG_FILE=]
+          --directory <DIRECTORY>      Change to the given directory prior to running the command [env:
+                                       UV_WORKING_DIRECTORY=]
           --config-file <CONFIG_FILE>  The path to a `uv.toml` file to use for configuration [env:
                                        UV_CONFIG_FILE=]
           --no-config                  Avoid discovering configuration files (`pyproject.toml`,
                                        `uv.toml`) [env: UV_NO_CONFIG=]
+          --no-user-constraints        Avoid applying the user-level constraints file [env:
+                                       UV_NO_USER_CONSTRAINTS=]
       -h, --help                       Display the concise help for this command
       -V, --version                    Display the uv version
 
@@ -1288,3 +1288,137 @@ fn init_hidden() {
     error: Not a valid package or extra name: ".foo". Names must start and end with a letter or digit and may only contain -, _, ., and alphanumeric characters.
     "###);
 }
+
+/// Initialize a project with the `setuptools` build backend.
+#[test]
+fn init_build_backend_setuptools() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), context.init().arg("foo").arg("--build-backend").arg("setuptools"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv init` is experimental and may change without warning
+    Initialized project `foo` at `[TEMP_DIR]/foo`
+    "###);
+
+    let pyproject = fs_err::read_to_string(context.temp_dir.join("foo/pyproject.toml"))?;
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject, @r###"
+        [project]
+        name = "foo"
+        version = "0.1.0"
+        description = "Add your description here"
+        readme = "README.md"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=61"]
+        build-backend = "setuptools.build_meta"
+        "###
+        );
+    });
+
+    context
+        .temp_dir
+        .child("foo/src/foo/__init__.py")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+/// Initialize a project with the `maturin` build backend, including a Rust extension module.
+#[test]
+fn init_build_backend_maturin() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), context.init().arg("foo-bar").arg("--build-backend").arg("maturin"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv init` is experimental and may change without warning
+    Initialized project `foo-bar` at `[TEMP_DIR]/foo-bar`
+    "###);
+
+    let pyproject = fs_err::read_to_string(context.temp_dir.join("foo-bar/pyproject.toml"))?;
+    let init_py = fs_err::read_to_string(context.temp_dir.join("foo-bar/src/foo_bar/__init__.py"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            pyproject, @r###"
+        [project]
+        name = "foo-bar"
+        version = "0.1.0"
+        description = "Add your description here"
+        readme = "README.md"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.maturin]
+        module-name = "foo_bar._core"
+        python-packages = ["foo_bar"]
+        python-source = "src"
+
+        [build-system]
+        requires = ["maturin>=1.0,<2.0"]
+        build-backend = "maturin"
+        "###
+        );
+    });
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            init_py, @r###"
+        from foo_bar._core import hello_from_bin
+
+
+        def hello() -> str:
+            return hello_from_bin()
+        "###
+        );
+    });
+
+    let project = context.temp_dir.child("foo-bar");
+    project
+        .child("Cargo.toml")
+        .assert(predicates::str::contains(r#"name = "_core""#));
+    project
+        .child("src/lib.rs")
+        .assert(predicates::str::contains("fn _core("));
+    project
+        .child("src/foo_bar/_core.pyi")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+/// The build backend can't be provided for a virtual workspace.
+#[test]
+fn init_build_backend_virtual() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), context.init().arg("foo").arg("--virtual").arg("--build-backend").arg("flit"), @r###"
+    success: false
+    exit_code: 7
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--virtual' cannot be used with '--build-backend <BUILD_BACKEND>'
+
+    Usage: uv init --cache-dir [CACHE_DIR] --virtual [PATH]
+
+    For more information, try '--help'.
+    "###);
+}
@@ -931,6 +931,198 @@ fn lock_wheel_url() -> Result<()> {
     Ok(())
 }
 
+/// Lock a requirement from a direct URL source that's pinned to a hash in `tool.uv.sources`.
+#[test]
+fn lock_wheel_url_hash() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio"]
+
+        [tool.uv.sources]
+        anyio = { url = "https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl", hash = "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8" }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    "###);
+
+    // Change the declared hash; the existing lockfile should be rejected.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio"]
+
+        [tool.uv.sources]
+        anyio = { url = "https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl", hash = "sha256:0000000000000000000000000000000000000000000000000000000000000000" }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: false
+    exit_code: 5
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    error: Hash mismatch for `anyio`, which is pinned to a hash in `pyproject.toml`
+
+    Expected:
+      sha256:0000000000000000000000000000000000000000000000000000000000000000
+
+    Computed:
+      sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8
+    "###);
+
+    // The mismatch should also be enforced when installing from the lockfile.
+    uv_snapshot!(context.filters(), context.sync().arg("--frozen"), @r###"
+    success: false
+    exit_code: 5
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    error: Hash mismatch for `anyio`, which is pinned to a hash in `pyproject.toml`
+
+    Expected:
+      sha256:0000000000000000000000000000000000000000000000000000000000000000
+
+    Computed:
+      sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8
+    "###);
+
+    // Only SHA-256 hashes are supported.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio"]
+
+        [tool.uv.sources]
+        anyio = { url = "https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl", hash = "md5:00000000000000000000000000000000" }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    error: Invalid hash for `anyio` in `pyproject.toml`: only `sha256` hashes are supported for URL sources, but found: `md5:00000000000000000000000000000000`
+    "###);
+
+    // Change the declared URL, but not the hash; the existing lockfile should be rejected, rather
+    // than validating the hash of the previously locked archive.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio"]
+
+        [tool.uv.sources]
+        anyio = { url = "https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl", hash = "sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8" }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync().arg("--frozen"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    error: `anyio` is pinned to a hash in `pyproject.toml`, but the lockfile contains a different source
+
+    Expected:
+      direct+https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl
+
+    Locked:
+      direct+https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl
+    "###);
+
+    Ok(())
+}
+
+/// Lock a direct URL dependency with a hash embedded in its fragment, which must match the locked
+/// archive.
+#[test]
+fn lock_wheel_url_fragment_hash() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio @ https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl#sha256=0000000000000000000000000000000000000000000000000000000000000000"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    error: Hash mismatch for `anyio`, which is pinned to a hash in its URL (`https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl#sha256=0000000000000000000000000000000000000000000000000000000000000000`)
+
+    Expected:
+      sha256:0000000000000000000000000000000000000000000000000000000000000000
+
+    Computed:
+      sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8
+    "###);
+
+    // With the correct hash, the lock succeeds.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio @ https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl#sha256=048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
 /// Lock a requirement from a direct URL to a source distribution.
 #[test]
 fn lock_sdist_url() -> Result<()> {
@@ -1771,7 +1963,7 @@ fn lock_dependency_non_existent_extra() -> Result<()> {
         ----- stderr -----
         warning: `uv lock` is experimental and may change without warning
         Resolved 9 packages in [TIME]
-        warning: The package `flask==3.0.2` does not have an extra named `foo`
+        warning: The package `flask==3.0.2` does not have an extra named `foo` (available extras: `async`, `dotenv`)
         "###);
 
         let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock")).unwrap();
@@ -1905,7 +2097,7 @@ fn lock_dependency_non_existent_extra() -> Result<()> {
     ----- stderr -----
     warning: `uv lock` is experimental and may change without warning
     Resolved 9 packages in [TIME]
-    warning: The package `flask==3.0.2` does not have an extra named `foo`
+    warning: The package `flask==3.0.2` does not have an extra named `foo` (available extras: `async`, `dotenv`)
     "###);
 
     // Install from the lockfile.
@@ -2609,6 +2801,14 @@ fn lock_requires_python() -> Result<()> {
 
     let lockfile = context.temp_dir.join("uv.lock");
 
+    // Versions excluded by `requires-python` are only noted when re-resolving, not when reusing
+    // the existing lockfile (see `lock_suggest_requires_python`).
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([(r"hint: .* was excluded because it requires Python .*\n", "")])
+        .collect();
+
     // Require >=3.7, which is incompatible with newer versions of `pygls` (>=1.1.0).
     let pyproject_toml = context.temp_dir.child("pyproject.toml");
     pyproject_toml.write_str(
@@ -2624,7 +2824,7 @@ fn lock_requires_python() -> Result<()> {
     deterministic! { context =>
         uv_snapshot!(context.filters(), context.lock(), @r###"
         success: false
-        exit_code: 1
+        exit_code: 3
         ----- stdout -----
 
         ----- stderr -----
@@ -2660,7 +2860,7 @@ fn lock_requires_python() -> Result<()> {
     )?;
 
     deterministic! { context =>
-        uv_snapshot!(context.filters(), context.lock(), @r###"
+        uv_snapshot!(filters, context.lock(), @r###"
         success: true
         exit_code: 0
         ----- stdout -----
@@ -2811,7 +3011,7 @@ fn lock_requires_python() -> Result<()> {
     )?;
 
     deterministic! { context =>
-        uv_snapshot!(context.filters(), context.lock(), @r###"
+        uv_snapshot!(filters, context.lock(), @r###"
         success: true
         exit_code: 0
         ----- stdout -----
@@ -2952,7 +3152,7 @@ fn lock_requires_python() -> Result<()> {
     )?;
 
     deterministic! { context =>
-        uv_snapshot!(context.filters(), context.lock(), @r###"
+        uv_snapshot!(filters, context.lock(), @r###"
         success: true
         exit_code: 0
         ----- stdout -----
@@ -3049,7 +3249,7 @@ fn lock_requires_python() -> Result<()> {
         .collect();
 
     // Re-run with `--locked`.
-    uv_snapshot!(context.filters(), context.lock().arg("--locked"), @r###"
+    uv_snapshot!(filters, context.lock().arg("--locked"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
@@ -3074,6 +3274,60 @@ fn lock_requires_python() -> Result<()> {
     Ok(())
 }
 
+/// Suggest a `requires-python` bound that permits the newest versions of any dependencies that
+/// were excluded due to the project's supported Python range.
+#[test]
+fn lock_suggest_requires_python() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Require >=3.7, which is incompatible with the newest version of `pygls` (1.3.0).
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.7"
+        dependencies = ["pygls"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock().arg("--suggest-requires-python"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 10 packages in [TIME]
+    hint: pygls v1.3.0 was excluded because it requires Python >=3.8, but the project supports Python >=3.7 (using v1.0.1)
+    hint: To use the newest versions of pygls, raise the project's `requires-python` to `>=3.8`
+    "###);
+
+    // With the bound raised, the newest version is selected.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.8"
+        dependencies = ["pygls"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock().arg("--suggest-requires-python"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 7 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
 /// Lock a requirement from PyPI, respecting the `Requires-Python` metadata
 #[test]
 fn lock_requires_python_wheels() -> Result<()> {
@@ -4505,7 +4759,7 @@ fn lock_invalid_hash() -> Result<()> {
     // Install from the lockfile.
     uv_snapshot!(context.filters(), context.sync().arg("--frozen").env_remove("UV_EXCLUDE_NEWER"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4689,10 +4943,10 @@ fn lock_resolution_mode() -> Result<()> {
     Ok(())
 }
 
-/// Lock a requirement from PyPI, filtering out wheels that target an ABI that is non-overlapping
-/// with the `Requires-Python` constraint.
+/// Record the index strategy and overrides in the lockfile, and warn when installing from a
+/// lockfile that was generated with different settings.
 #[test]
-fn lock_requires_python_no_wheels() -> Result<()> {
+fn lock_settings_drift() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let pyproject_toml = context.temp_dir.child("pyproject.toml");
@@ -4702,46 +4956,256 @@ fn lock_requires_python_no_wheels() -> Result<()> {
         name = "project"
         version = "0.1.0"
         requires-python = ">=3.12"
-        dependencies = ["dearpygui==1.9.1"]
+        dependencies = ["iniconfig"]
+
+        [tool.uv]
+        override-dependencies = ["iniconfig>=2"]
         "#,
     )?;
 
-    uv_snapshot!(context.filters(), context.lock(), @r###"
-    success: false
-    exit_code: 1
+    uv_snapshot!(context.filters(), context.lock().arg("--index-strategy").arg("unsafe-best-match"), @r###"
+    success: true
+    exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
     warning: `uv lock` is experimental and may change without warning
-      × No solution found when resolving dependencies:
-      ╰─▶ Because dearpygui==1.9.1 has no wheels with a matching Python ABI tag and project==0.1.0 depends on dearpygui==1.9.1, we can conclude that project==0.1.0 cannot be used.
-          And because only project==0.1.0 is available and you require project, we can conclude that the requirements are unsatisfiable.
+    Resolved 2 packages in [TIME]
     "###);
 
-    Ok(())
-}
-
-/// In this case, a package is included twice at the same version, but pointing to different direct
-/// URLs.
-#[test]
-fn lock_same_version_multiple_urls() -> Result<()> {
-    let context = TestContext::new("3.12");
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock")).unwrap();
 
-    let v1 = context.temp_dir.child("v1");
-    fs_err::create_dir_all(&v1)?;
-    let pyproject_toml = v1.child("pyproject.toml");
-    pyproject_toml.write_str(
-        r#"
-        [project]
-        name = "dependency"
-        version = "0.0.1"
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            lock, @r###"
+        version = 1
         requires-python = ">=3.12"
-        dependencies = ["anyio==3.7.0"]
-        "#,
-    )?;
 
-    let v2 = context.temp_dir.child("v2");
-    fs_err::create_dir_all(&v2)?;
+        [options]
+        exclude-newer = "2024-03-25 00:00:00 UTC"
+        index-strategy = "unsafe-best-match"
+        override-dependencies = [
+            "iniconfig>=2",
+        ]
+
+        [[package]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = { registry = "https://pypi.org/simple" }
+        sdist = { url = "https://files.pythonhosted.org/packages/d7/4b/cbd8e699e64a6f16ca3a8220661b5f83792b3017d0f79807cb8708d33913/iniconfig-2.0.0.tar.gz", hash = "sha256:2d91e135bf72d31a410b17c16da610a82cb55f6b0477d1a902134b24a455b8b3", size = 4646 }
+        wheels = [
+            { url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl", hash = "sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374", size = 5892 },
+        ]
+
+        [[package]]
+        name = "project"
+        version = "0.1.0"
+        source = { editable = "." }
+        dependencies = [
+            { name = "iniconfig" },
+        ]
+        "###
+        );
+    });
+
+    // Installing from the lockfile with different settings should warn.
+    uv_snapshot!(context.filters(), context.sync().arg("--frozen").arg("--extra-index-url").arg("https://test.pypi.org/simple"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    warning: The lockfile was generated with a different index strategy (`unsafe-best-match`) than is currently configured (`first-index`)
+    warning: The lockfile was generated with different indexes (the default index) than are currently configured (`https://pypi.org/simple`, `https://test.pypi.org/simple`)
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    // Locking without the index strategy should ignore the existing lockfile.
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Ignoring existing lockfile due to change in index strategy: `unsafe-best-match` vs. `first-index`
+    Resolved 2 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Declare build environment variables for a package, and ensure that their digest is recorded in
+/// the lockfile, and that changing them re-resolves the package.
+#[test]
+fn lock_build_env() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+
+        [tool.uv.build-env]
+        iniconfig = { CMAKE_ARGS = "-DGGML_CUDA=on" }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    "###);
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock")).unwrap();
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            lock, @r###"
+        version = 1
+        requires-python = ">=3.12"
+
+        [options]
+        exclude-newer = "2024-03-25 00:00:00 UTC"
+
+        [build-env]
+        iniconfig = "sha256:ec0e53396ff00efe4cacb486b5b73adaeaf6f1f319609ad12ca6fed13f908b90"
+
+        [[package]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = { registry = "https://pypi.org/simple" }
+        sdist = { url = "https://files.pythonhosted.org/packages/d7/4b/cbd8e699e64a6f16ca3a8220661b5f83792b3017d0f79807cb8708d33913/iniconfig-2.0.0.tar.gz", hash = "sha256:2d91e135bf72d31a410b17c16da610a82cb55f6b0477d1a902134b24a455b8b3", size = 4646 }
+        wheels = [
+            { url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl", hash = "sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374", size = 5892 },
+        ]
+
+        [[package]]
+        name = "project"
+        version = "0.1.0"
+        source = { editable = "." }
+        dependencies = [
+            { name = "iniconfig" },
+        ]
+        "###
+        );
+    });
+
+    // Locking again should be a no-op.
+    uv_snapshot!(context.filters(), context.lock().arg("--locked"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    "###);
+
+    // Changing the variables should ignore the locked version of the affected package.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+
+        [tool.uv.build-env]
+        iniconfig = { CMAKE_ARGS = "-DGGML_METAL=on" }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Ignoring locked versions of `iniconfig` due to change in build environment variables
+    Resolved 2 packages in [TIME]
+    "###);
+
+    // The variables themselves aren't written to the lockfile.
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock")).unwrap();
+    assert!(lock.contains(
+        r#"iniconfig = "sha256:4e2a04ecf1aefcdfe968ad33d5b1c42555d2e8269e78973013d7fdea2fb5e295""#
+    ));
+    assert!(!lock.contains("GGML"));
+
+    Ok(())
+}
+
+/// Lock a requirement from PyPI, filtering out wheels that target an ABI that is non-overlapping
+/// with the `Requires-Python` constraint.
+#[test]
+fn lock_requires_python_no_wheels() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["dearpygui==1.9.1"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock(), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+      × No solution found when resolving dependencies:
+      ╰─▶ Because dearpygui==1.9.1 has no wheels with a matching Python ABI tag and project==0.1.0 depends on dearpygui==1.9.1, we can conclude that project==0.1.0 cannot be used.
+          And because only project==0.1.0 is available and you require project, we can conclude that the requirements are unsatisfiable.
+    "###);
+
+    Ok(())
+}
+
+/// In this case, a package is included twice at the same version, but pointing to different direct
+/// URLs.
+#[test]
+fn lock_same_version_multiple_urls() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let v1 = context.temp_dir.child("v1");
+    fs_err::create_dir_all(&v1)?;
+    let pyproject_toml = v1.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "dependency"
+        version = "0.0.1"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==3.7.0"]
+        "#,
+    )?;
+
+    let v2 = context.temp_dir.child("v2");
+    fs_err::create_dir_all(&v2)?;
     let pyproject_toml = v2.child("pyproject.toml");
     pyproject_toml.write_str(
         r#"
@@ -5172,6 +5636,9 @@ fn lock_redact() -> Result<()> {
 
         [options]
         exclude-newer = "2024-03-25 00:00:00 UTC"
+        indexes = [
+            "https://pypi-proxy.fly.dev/basic-auth/simple",
+        ]
 
         [[package]]
         name = "foo"
@@ -5214,6 +5681,7 @@ fn lock_redact() -> Result<()> {
 
     ----- stderr -----
     warning: `uv sync` is experimental and may change without warning
+    warning: The lockfile was generated with different indexes (`https://pypi-proxy.fly.dev/basic-auth/simple`) than are currently configured (the default index)
     error: Failed to prepare distributions
       Caused by: Failed to fetch wheel: iniconfig==2.0.0
       Caused by: HTTP status client error (401 Unauthorized) for url (https://pypi-proxy.fly.dev/basic-auth/files/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl)
@@ -5871,3 +6339,657 @@ fn warn_missing_transitive_lower_bounds() -> Result<()> {
 
     Ok(())
 }
+
+/// Resolve the project repeatedly, with and without shuffling the candidate order, to verify that
+/// the resolution is deterministic.
+#[test]
+fn lock_verify_determinism() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==3.7.0", "iniconfig"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock().arg("--verify-determinism"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved the project 3 times with identical results
+    "###);
+
+    uv_snapshot!(context.filters(), context.lock().arg("--verify-determinism").arg("5").arg("--shuffle"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved the project 5 times with identical results
+    "###);
+
+    // The lockfile should not be written.
+    assert!(!context.temp_dir.child("uv.lock").exists());
+
+    Ok(())
+}
+
+/// Lock a project in another directory, via `--directory` and `UV_WORKING_DIRECTORY`.
+#[test]
+fn lock_directory() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project = context.temp_dir.child("project");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+        "#})?;
+
+    uv_snapshot!(context.filters(), context.lock().arg("--directory").arg("project"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    "###);
+
+    // The lockfile should be written to the project directory.
+    project.child("uv.lock").assert(predicates::path::is_file());
+    context
+        .temp_dir
+        .child("uv.lock")
+        .assert(predicates::path::missing());
+
+    // The environment variable should be respected, too.
+    uv_snapshot!(context.filters(), context.lock().arg("--locked").env("UV_WORKING_DIRECTORY", "project"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Prune the dependencies that are specific to Windows from the lockfile.
+#[test]
+fn lock_prune_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["click==8.1.7"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock().arg("--prune-platform").arg("windows"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 3 packages in [TIME]
+    "###);
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock")).unwrap();
+
+    // `colorama` is only required on Windows, so it should be omitted.
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            lock, @r###"
+        version = 1
+        requires-python = ">=3.12"
+
+        [options]
+        exclude-newer = "2024-03-25 00:00:00 UTC"
+        prune-platforms = ["windows"]
+
+        [[package]]
+        name = "click"
+        version = "8.1.7"
+        source = { registry = "https://pypi.org/simple" }
+        sdist = { url = "https://files.pythonhosted.org/packages/96/d3/f04c7bfcf5c1862a2a5b845c6b2b360488cf47af55dfa79c98f6a6bf98b5/click-8.1.7.tar.gz", hash = "sha256:ca9853ad459e787e2192211578cc907e7594e294c7ccc834310722b41b9ca6de", size = 336121 }
+        wheels = [
+            { url = "https://files.pythonhosted.org/packages/00/2e/d53fa4befbf2cfa713304affc7ca780ce4fc1fd8710527771b58311a3229/click-8.1.7-py3-none-any.whl", hash = "sha256:ae74fb96c20a0277a1d615f1e4d73c8414f5a98db8b799a7931d1582f3390c28", size = 97941 },
+        ]
+
+        [[package]]
+        name = "project"
+        version = "0.1.0"
+        source = { editable = "." }
+        dependencies = [
+            { name = "click" },
+        ]
+        "###
+        );
+    });
+
+    // The pruned platforms should be retained when re-locking.
+    uv_snapshot!(context.filters(), context.lock().arg("--locked"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 3 packages in [TIME]
+    "###);
+
+    // Restore the full lockfile.
+    uv_snapshot!(context.filters(), context.lock().arg("--no-prune-platform"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Re-resolving due to change in pruned platforms
+    Resolved 3 packages in [TIME]
+    "###);
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock")).unwrap();
+    assert!(!lock.contains("prune-platforms"));
+    assert!(lock.contains(r#"name = "colorama""#));
+
+    Ok(())
+}
+
+/// Toggling `--prune-platform` should retain the locked versions.
+#[test]
+fn lock_prune_platform_preferences() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig==1.1.1"]
+        "#,
+    )?;
+
+    context
+        .lock()
+        .arg("--prune-platform")
+        .arg("windows")
+        .assert()
+        .success();
+
+    // Relax the requirement, such that a newer version would be selected without a preference.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.lock().arg("--no-prune-platform"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Re-resolving due to change in pruned platforms
+    Resolved 2 packages in [TIME]
+    "###);
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock")).unwrap();
+    assert!(
+        lock.contains("name = \"iniconfig\"\nversion = \"1.1.1\""),
+        "{lock}"
+    );
+
+    Ok(())
+}
+
+/// A lockfile can't be installed on a platform that was pruned from it.
+#[test]
+#[cfg(target_os = "linux")]
+fn sync_pruned_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+        "#,
+    )?;
+
+    context
+        .lock()
+        .arg("--prune-platform")
+        .arg("linux")
+        .assert()
+        .success();
+
+    context
+        .sync()
+        .arg("--frozen")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "the lockfile doesn't support `linux`, as it was pruned with `--prune-platform linux`",
+        ));
+
+    Ok(())
+}
+
+/// Back up the lockfile when it's updated, and restore the most recent backup.
+#[test]
+fn lock_backup_restore() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig==1.1.1"]
+
+        [tool.uv]
+        lock-backups = 1
+        "#,
+    )?;
+
+    // Without an existing lockfile, there's nothing to back up, or to restore.
+    context.lock().assert().success();
+    assert!(!context.temp_dir.child(".uv").child("lock-backups").exists());
+
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([(r"uv\.lock\.\d{8}T\d{6}\.\d{3}Z", "uv.lock.[TIMESTAMP]")])
+        .collect();
+
+    uv_snapshot!(filters, context.lock().arg("--restore"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    error: No lockfile backups found in `.uv/lock-backups` (hint: set `tool.uv.lock-backups` to back up the lockfile when it's updated)
+    "###);
+
+    let original = fs_err::read_to_string(context.temp_dir.join("uv.lock"))?;
+
+    // Updating the lockfile should back up the previous lockfile.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig==2.0.0"]
+
+        [tool.uv]
+        lock-backups = 1
+        "#,
+    )?;
+    context.lock().assert().success();
+    let updated = fs_err::read_to_string(context.temp_dir.join("uv.lock"))?;
+    assert_ne!(updated, original);
+
+    // Restoring the backup should back up the current lockfile in turn.
+    uv_snapshot!(filters, context.lock().arg("--restore"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Backed up the current `uv.lock` to: `.uv/lock-backups/uv.lock.[TIMESTAMP]`
+    Restored `uv.lock` from backup: `.uv/lock-backups/uv.lock.[TIMESTAMP]`
+    "###);
+
+    assert_eq!(
+        fs_err::read_to_string(context.temp_dir.join("uv.lock"))?,
+        original
+    );
+
+    // Such that the restore can be reverted.
+    uv_snapshot!(filters, context.lock().arg("--restore"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Backed up the current `uv.lock` to: `.uv/lock-backups/uv.lock.[TIMESTAMP]`
+    Restored `uv.lock` from backup: `.uv/lock-backups/uv.lock.[TIMESTAMP]`
+    "###);
+
+    assert_eq!(
+        fs_err::read_to_string(context.temp_dir.join("uv.lock"))?,
+        updated
+    );
+
+    Ok(())
+}
+
+/// Lock a PEP 723 script into the shared `.uv-scripts.lock`, and run it with the locked versions.
+#[test]
+fn lock_script() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let script = context.temp_dir.child("main.py");
+    script.write_str(indoc! { r#"
+        # /// script
+        # requires-python = ">=3.11"
+        # dependencies = [
+        #   "iniconfig",
+        # ]
+        # ///
+
+        import iniconfig
+       "#
+    })?;
+
+    uv_snapshot!(context.filters(), context.lock().arg("--script").arg("main.py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Locking script: main.py
+    Resolved 1 package in [TIME]
+    "###);
+
+    let lock = fs_err::read_to_string(context.temp_dir.join(".uv-scripts.lock"))?;
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            lock, @r###"
+        version = 1
+
+        [scripts."main.py"]
+        version = 1
+        requires-python = ">=3.11"
+
+        [scripts."main.py".metadata]
+        dependencies = ["iniconfig"]
+        requires-python = ">=3.11"
+
+        [scripts."main.py".options]
+        exclude-newer = "2024-03-25 00:00:00 UTC"
+
+        [[scripts."main.py".package]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = { registry = "https://pypi.org/simple" }
+        sdist = { url = "https://files.pythonhosted.org/packages/d7/4b/cbd8e699e64a6f16ca3a8220661b5f83792b3017d0f79807cb8708d33913/iniconfig-2.0.0.tar.gz", hash = "sha256:2d91e135bf72d31a410b17c16da610a82cb55f6b0477d1a902134b24a455b8b3", size = 4646 }
+        wheels = [
+            { url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl", hash = "sha256:b6a85871a79d2e3b22d2d1b94ac2824226a63c6b741c88f7ae975f18b6778374", size = 5892 },
+        ]
+        "###
+        );
+    });
+
+    // Re-locking should be a no-op, which `--locked` asserts.
+    context
+        .lock()
+        .arg("--script")
+        .arg("main.py")
+        .arg("--locked")
+        .assert()
+        .success();
+
+    // Running the script should install the locked versions, without resolving.
+    uv_snapshot!(context.filters(), context.run().arg("--preview").arg("main.py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Reading inline script metadata from: main.py
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###);
+
+    // If the script's dependencies change, the lockfile is outdated.
+    script.write_str(indoc! { r#"
+        # /// script
+        # requires-python = ">=3.11"
+        # dependencies = [
+        #   "iniconfig<2",
+        # ]
+        # ///
+
+        import iniconfig
+       "#
+    })?;
+
+    uv_snapshot!(context.filters(), context.run().arg("--preview").arg("--locked").arg("main.py"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Reading inline script metadata from: main.py
+    error: The entry for `main.py` in `.uv-scripts.lock` needs to be updated, but `--locked` was provided. To update the lockfile, run `uv lock --script main.py`.
+    "###);
+
+    uv_snapshot!(context.filters(), context.lock().arg("--script").arg("main.py").arg("--locked"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Locking script: main.py
+    Resolved 1 package in [TIME]
+    error: The lockfile at `.uv-scripts.lock` needs to be updated, but `--locked` was provided. To update the lockfile, run `uv lock --script main.py`.
+    "###);
+
+    Ok(())
+}
+
+/// User-level constraints aren't applied when locking, since the lockfile is shared across
+/// machines.
+#[test]
+fn lock_ignores_user_constraints() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==3.7.0"]
+        "#,
+    )?;
+
+    let xdg = context.temp_dir.child("config");
+    xdg.child("uv")
+        .child("constraints.txt")
+        .write_str("idna<3.4")?;
+
+    uv_snapshot!(context.filters(), context.lock().env("XDG_CONFIG_HOME", xdg.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv lock` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    "###);
+
+    let lock = fs_err::read_to_string(context.temp_dir.join("uv.lock"))?;
+    assert!(
+        lock.contains("name = \"idna\"\nversion = \"3.6\""),
+        "{lock}"
+    );
+
+    // Commands that don't resolve don't read the constraints file at all.
+    xdg.child("uv")
+        .child("constraints.txt")
+        .write_str("not a valid requirement!")?;
+
+    context
+        .command()
+        .arg("cache")
+        .arg("dir")
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+/// `--locked` and `--frozen` only apply to scripts in a directory with a `.uv-scripts.lock`, and
+/// changes to the script's sources or settings invalidate its locked resolution.
+#[test]
+fn lock_script_locked() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let script = context.temp_dir.child("main.py");
+    script.write_str(indoc! { r#"
+        # /// script
+        # requires-python = ">=3.11"
+        # dependencies = [
+        #   "iniconfig",
+        # ]
+        # ///
+
+        import iniconfig
+       "#
+    })?;
+
+    // Without a lockfile, `--locked` has no effect.
+    uv_snapshot!(context.filters(), context.run().arg("--preview").arg("--locked").arg("main.py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Reading inline script metadata from: main.py
+    warning: `--locked` has no effect for Python scripts with inline metadata that aren't locked in a `.uv-scripts.lock`
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###);
+
+    context
+        .lock()
+        .arg("--script")
+        .arg("main.py")
+        .assert()
+        .success();
+
+    // Changing the script's index settings should invalidate the lockfile.
+    script.write_str(indoc! { r#"
+        # /// script
+        # requires-python = ">=3.11"
+        # dependencies = [
+        #   "iniconfig",
+        # ]
+        #
+        # [tool.uv]
+        # index-url = "https://test.pypi.org/simple"
+        # ///
+
+        import iniconfig
+       "#
+    })?;
+
+    uv_snapshot!(context.filters(), context.run().arg("--preview").arg("--locked").arg("main.py"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Reading inline script metadata from: main.py
+    error: The entry for `main.py` in `.uv-scripts.lock` needs to be updated, but `--locked` was provided. To update the lockfile, run `uv lock --script main.py`.
+    "###);
+
+    // As should changing its sources.
+    script.write_str(indoc! { r#"
+        # /// script
+        # requires-python = ">=3.11"
+        # dependencies = [
+        #   "iniconfig",
+        # ]
+        #
+        # [tool.uv.sources]
+        # iniconfig = { url = "https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl" }
+        # ///
+
+        import iniconfig
+       "#
+    })?;
+
+    uv_snapshot!(context.filters(), context.run().arg("--preview").arg("--locked").arg("main.py"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Reading inline script metadata from: main.py
+    error: The entry for `main.py` in `.uv-scripts.lock` needs to be updated, but `--locked` was provided. To update the lockfile, run `uv lock --script main.py`.
+    "###);
+
+    // Once re-locked, the sources are recorded alongside the dependencies.
+    context
+        .lock()
+        .arg("--script")
+        .arg("main.py")
+        .assert()
+        .success();
+
+    let lock = fs_err::read_to_string(context.temp_dir.join(".uv-scripts.lock"))?;
+    assert!(lock.contains("sources = { iniconfig = { url = "), "{lock}");
+
+    context
+        .run()
+        .arg("--preview")
+        .arg("--locked")
+        .arg("main.py")
+        .assert()
+        .success();
+
+    Ok(())
+}
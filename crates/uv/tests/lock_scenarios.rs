@@ -92,6 +92,11 @@ fn fork_allows_non_conflicting_non_overlapping_dependencies() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -200,6 +205,11 @@ fn fork_allows_non_conflicting_repeated_dependencies() -> Result<()> {
         version = 1
         requires-python = ">=3.8"
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -300,6 +310,11 @@ fn fork_basic() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -407,7 +422,7 @@ fn conflict_in_fork() -> Result<()> {
     cmd.arg("--index-url").arg(packse_index_url());
     uv_snapshot!(filters, cmd, @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -477,7 +492,7 @@ fn fork_conflict_unsatisfiable() -> Result<()> {
     cmd.arg("--index-url").arg(packse_index_url());
     uv_snapshot!(filters, cmd, @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -585,6 +600,11 @@ fn fork_filter_sibling_dependencies() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "4.3.0"
@@ -752,6 +772,11 @@ fn fork_upgrade() -> Result<()> {
         version = 1
         requires-python = ">=3.8"
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-bar"
         version = "2.0.0"
@@ -876,6 +901,11 @@ fn fork_incomplete_markers() -> Result<()> {
             "python_version >= '3.10' and python_version < '3.11'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -1019,6 +1049,11 @@ fn fork_marker_accrue() -> Result<()> {
         version = 1
         requires-python = ">=3.8"
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -1130,7 +1165,7 @@ fn fork_marker_disjoint() -> Result<()> {
     cmd.arg("--index-url").arg(packse_index_url());
     uv_snapshot!(filters, cmd, @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1227,6 +1262,11 @@ fn fork_marker_inherit_combined_allowed() -> Result<()> {
             "implementation_name != 'cpython' and implementation_name != 'pypy' and sys_platform == 'darwin'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -1403,6 +1443,11 @@ fn fork_marker_inherit_combined_disallowed() -> Result<()> {
             "implementation_name != 'cpython' and implementation_name != 'pypy' and sys_platform == 'darwin'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -1568,6 +1613,11 @@ fn fork_marker_inherit_combined() -> Result<()> {
             "implementation_name != 'cpython' and implementation_name != 'pypy' and sys_platform == 'darwin'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -1724,6 +1774,11 @@ fn fork_marker_inherit_isolated() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -1868,6 +1923,11 @@ fn fork_marker_inherit_transitive() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -2020,6 +2080,11 @@ fn fork_marker_inherit() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -2154,6 +2219,11 @@ fn fork_marker_limited_inherit() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -2304,6 +2374,11 @@ fn fork_marker_selection() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "0.1.0"
@@ -2454,6 +2529,11 @@ fn fork_marker_track() -> Result<()> {
             "sys_platform != 'darwin' and sys_platform != 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.3.1"
@@ -2596,6 +2676,11 @@ fn fork_non_fork_marker_transitive() -> Result<()> {
         version = 1
         requires-python = ">=3.8"
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -2708,7 +2793,7 @@ fn fork_non_local_fork_marker_direct() -> Result<()> {
     cmd.arg("--index-url").arg(packse_index_url());
     uv_snapshot!(filters, cmd, @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2782,7 +2867,7 @@ fn fork_non_local_fork_marker_transitive() -> Result<()> {
     cmd.arg("--index-url").arg(packse_index_url());
     uv_snapshot!(filters, cmd, @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2899,6 +2984,11 @@ fn fork_overlapping_markers_basic() -> Result<()> {
             "python_version >= '3.10'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.2.0"
@@ -3060,6 +3150,11 @@ fn preferences_dependent_forking_bistable() -> Result<()> {
             "sys_platform == 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-cleaver"
         version = "1.0.0"
@@ -3438,6 +3533,11 @@ fn preferences_dependent_forking_tristable() -> Result<()> {
             "sys_platform == 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-bar"
         version = "1.0.0"
@@ -3716,6 +3816,11 @@ fn preferences_dependent_forking() -> Result<()> {
             "sys_platform == 'linux'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-bar"
         version = "1.0.0"
@@ -3888,6 +3993,11 @@ fn fork_remaining_universe_partitioning() -> Result<()> {
             "os_name != 'darwin' and os_name != 'linux' and sys_platform == 'illumos'",
         ]
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -4029,6 +4139,11 @@ fn fork_requires_python_full_prerelease() -> Result<()> {
         version = 1
         requires-python = ">=3.10"
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "project"
         version = "0.1.0"
@@ -4112,6 +4227,11 @@ fn fork_requires_python_full() -> Result<()> {
         version = 1
         requires-python = ">=3.10"
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "project"
         version = "0.1.0"
@@ -4199,6 +4319,11 @@ fn fork_requires_python_patch_overlap() -> Result<()> {
         version = 1
         requires-python = ">=3.10.1"
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "package-a"
         version = "1.0.0"
@@ -4291,6 +4416,11 @@ fn fork_requires_python() -> Result<()> {
         version = 1
         requires-python = ">=3.10"
 
+        [options]
+        indexes = [
+            "https://astral-sh.github.io/packse/PACKSE_VERSION/simple-html/",
+        ]
+
         [[package]]
         name = "project"
         version = "0.1.0"
@@ -300,6 +300,338 @@ fn compile_constraints_txt() -> Result<()> {
     Ok(())
 }
 
+/// Apply constraints that are tagged with a marker, either inline or via `--constraint-marker`,
+/// only in environments that satisfy the marker.
+#[test]
+fn compile_constraints_marker() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("sniffio<1.3; sys_platform == 'win32'")?;
+
+    let constraints_win_txt = context.temp_dir.child("constraints-win.txt");
+    constraints_win_txt.write_str("idna<3.3")?;
+
+    let constraints_linux_txt = context.temp_dir.child("constraints-linux.txt");
+    constraints_linux_txt.write_str("idna<3.4")?;
+
+    // On Linux, only the Linux constraints apply.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt")
+            .arg("--constraint-marker")
+            .arg("constraints-win.txt; sys_platform == 'win32'")
+            .arg("--constraint-marker")
+            .arg("constraints-linux.txt; sys_platform == 'linux'")
+            .arg("--python-platform")
+            .arg("linux"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --constraint constraints.txt --constraint-marker "constraints-win.txt; sys_platform == 'win32'" --constraint-marker "constraints-linux.txt; sys_platform == 'linux'" --python-platform linux
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.3
+        # via
+        #   -c constraints-linux.txt
+        #   anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // On Windows, only the Windows constraints apply, along with the inline constraint.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt")
+            .arg("--constraint-marker")
+            .arg("constraints-win.txt; sys_platform == 'win32'")
+            .arg("--constraint-marker")
+            .arg("constraints-linux.txt; sys_platform == 'linux'")
+            .arg("--python-platform")
+            .arg("windows"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --constraint constraints.txt --constraint-marker "constraints-win.txt; sys_platform == 'win32'" --constraint-marker "constraints-linux.txt; sys_platform == 'linux'" --python-platform windows
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.2
+        # via
+        #   -c constraints-win.txt
+        #   anyio
+    sniffio==1.2.0
+        # via
+        #   -c constraints.txt
+        #   anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // A marker is required.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--constraint-marker")
+            .arg("constraints-win.txt"), @r###"
+    success: false
+    exit_code: 7
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value 'constraints-win.txt' for '--constraint-marker <CONSTRAINT_MARKER>': expected a path and a marker, separated by a semicolon (e.g., `constraints-win.txt; sys_platform == 'win32'`)
+
+    For more information, try '--help'.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve for an exact target environment, evaluating every marker against it.
+#[test]
+fn compile_target_environment() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc! {r"
+        anyio==3.7.0 ; sys_platform == 'win32'
+        iniconfig ; sys_platform == 'linux'
+        pywin32==306 ; sys_platform == 'win32'
+    "})?;
+
+    // The environment, as reported by `pip inspect`.
+    let environment_json = context.temp_dir.child("environment.json");
+    environment_json.write_str(indoc! {r#"
+        {
+          "version": "1",
+          "environment": {
+            "implementation_name": "cpython",
+            "implementation_version": "3.12.1",
+            "os_name": "nt",
+            "platform_machine": "AMD64",
+            "platform_python_implementation": "CPython",
+            "platform_release": "10",
+            "platform_system": "Windows",
+            "platform_version": "10.0.19045",
+            "python_full_version": "3.12.1",
+            "python_version": "3.12",
+            "sys_platform": "win32"
+          }
+        }
+    "#})?;
+
+    // `pywin32` only provides Windows wheels, which are selected for the target's platform.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--target-environment")
+            .arg("environment.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --target-environment environment.json
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    pywin32==306
+        # via -r requirements.in
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    // The platform of the target environment must be known to select wheels for it.
+    let emscripten_json = context.temp_dir.child("emscripten.json");
+    emscripten_json.write_str(indoc! {r#"
+        {
+          "implementation_name": "cpython",
+          "implementation_version": "3.12.1",
+          "os_name": "posix",
+          "platform_machine": "wasm32",
+          "platform_python_implementation": "CPython",
+          "platform_release": "3.1.45",
+          "platform_system": "Emscripten",
+          "platform_version": "#1",
+          "python_full_version": "3.12.1",
+          "python_version": "3.12",
+          "sys_platform": "emscripten"
+        }
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--target-environment")
+            .arg("emscripten.json"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unable to determine the platform of the target environment (`sys_platform`: `emscripten`, `platform_machine`: `wasm32`); provide one with `--python-platform`
+    "###
+    );
+
+    // Markers can't be retained in the output.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--target-environment")
+            .arg("environment.json")
+            .arg("--universal"), @r###"
+    success: false
+    exit_code: 7
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--target-environment <TARGET_ENVIRONMENT>' cannot be used with '--universal'
+
+    Usage: uv pip compile --cache-dir [CACHE_DIR] --target-environment <TARGET_ENVIRONMENT> --exclude-newer <EXCLUDE_NEWER> <SRC_FILE>...
+
+    For more information, try '--help'.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Apply the user-level constraints file to the resolution, unless `--no-user-constraints` is
+/// provided.
+#[test]
+fn compile_user_constraints() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let xdg = context.temp_dir.child("config");
+    xdg.child("uv")
+        .child("constraints.txt")
+        .write_str("idna<3.4")?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .env("XDG_CONFIG_HOME", xdg.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.3
+        # via
+        #   -c config/uv/constraints.txt
+        #   anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // The constraints can be ignored.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--no-user-constraints")
+            .env("XDG_CONFIG_HOME", xdg.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --no-user-constraints
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    // If the constraints make the resolution fail, their origin is shown.
+    requirements_in.write_str("idna==3.6")?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .env("XDG_CONFIG_HOME", xdg.path()), @r###"
+    success: false
+    exit_code: 3
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because you require idna==3.6 and idna<3.4, we can conclude that the requirements are unsatisfiable.
+
+          hint: The constraints in `config/uv/constraints.txt` were applied to the resolution (use `--no-user-constraints` to ignore them)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Annotate the packages that were pinned by a constraint, and report the constraints that had no
+/// effect on the resolution.
+#[test]
+fn compile_constraints_txt_emit_constraint_annotation() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    // `idna<3.4` excludes the latest versions of `idna`, while `sniffio>=1` is already implied by
+    // `anyio`, and `urllib3` isn't part of the resolution at all.
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str(indoc! {r"
+        idna<3.4
+        sniffio>=1
+        urllib3<2
+    "})?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt")
+            .arg("--emit-constraint-annotation"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --constraint constraints.txt --emit-constraint-annotation
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.3
+        # via anyio
+        # constrained-by -c constraints.txt
+    sniffio==1.3.1
+        # via anyio
+
+    # The following constraints had no effect on the resolution:
+    # sniffio>=1 (from -c constraints.txt)
+    # urllib3<2 (from -c constraints.txt)
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a package from a `requirements.in` file, with an inline constraint.
 #[test]
 fn compile_constraints_inline() -> Result<()> {
@@ -537,6 +869,94 @@ optional-dependencies.foo = [
     Ok(())
 }
 
+/// Resolve an extra and a dependency group (which includes another group) from a
+/// `pyproject.toml` file.
+#[test]
+fn compile_pyproject_toml_group() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools", "wheel"]
+
+[project]
+name = "project"
+version = "0.1.0"
+dependencies = []
+optional-dependencies.foo = [
+    "anyio==3.7.0",
+]
+
+[dependency-groups]
+docs = ["iniconfig==2.0.0"]
+test = [
+    {include-group = "docs"},
+    "sniffio==1.3.1",
+]
+"#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("pyproject.toml")
+            .arg("--extra")
+            .arg("foo")
+            .arg("--group")
+            .arg("test"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] pyproject.toml --extra foo --group test
+    anyio==3.7.0
+        # via project (pyproject.toml)
+    idna==3.6
+        # via anyio
+    iniconfig==2.0.0
+        # via project (pyproject.toml:test)
+    sniffio==1.3.1
+        # via
+        #   project (pyproject.toml:test)
+        #   anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    // Request a group that doesn't exist.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("pyproject.toml")
+            .arg("--group")
+            .arg("lint"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requested dependency group not found: lint
+    "###
+    );
+
+    // Groups can't be requested from a `requirements.in` file.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--group")
+            .arg("test"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requesting dependency groups requires a `pyproject.toml` file.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Compile a `pyproject.toml` file with a `poetry` section.
 #[test]
 fn compile_pyproject_toml_poetry() -> Result<()> {
@@ -992,7 +1412,7 @@ optional-dependencies.foo = [
             .arg("--extra")
             .arg("invalid name!"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -1091,6 +1511,7 @@ fn compile_python_312_no_deps() -> Result<()> {
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --no-deps --python-version 3.12
+    # Dependencies were excluded due to `--no-deps`, so this file may be incomplete.
     black==23.10.1
         # via -r requirements.in
 
@@ -1126,7 +1547,7 @@ fn compile_python_37() -> Result<()> {
             .arg("--python-version")
             .arg("3.7"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1183,7 +1604,7 @@ fn compile_python_invalid_version() -> Result<()> {
             .arg("--python-version")
             .arg("3.7.x"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -1208,7 +1629,7 @@ fn compile_python_dev_version() -> Result<()> {
             .arg("--python-version")
             .arg("3.7-dev"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -1425,6 +1846,64 @@ fn compile_git_branch_https_dependency() -> Result<()> {
     Ok(())
 }
 
+/// Respect the commit that a Git branch was pinned to in an existing output file, unless the
+/// package is upgraded.
+#[test]
+#[cfg(feature = "git")]
+fn compile_git_branch_https_dependency_preference() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(
+        "uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@test-branch",
+    )?;
+
+    // Pin the branch to a commit other than its current head.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@b270df1a2fb5d012294e9aaf05e7e0bab1e6a389
+            # via -r requirements.in
+    "})?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --output-file requirements.txt
+    uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@b270df1a2fb5d012294e9aaf05e7e0bab1e6a389
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // Upgrading the package should resolve the branch to its current head.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("--upgrade-package")
+            .arg("uv-public-pypackage"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --output-file requirements.txt --upgrade-package uv-public-pypackage
+    uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@0dacfd662c64cb4ceb16e6cf65a157a8b715b979
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a specific tag via a Git HTTPS dependency.
 #[test]
 #[cfg(feature = "git")]
@@ -1754,7 +2233,7 @@ fn conflicting_direct_url_dependency() -> Result<()> {
     uv_snapshot!(context.filters(), context.pip_compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1880,7 +2359,7 @@ fn conflicting_transitive_url_dependency() -> Result<()> {
     uv_snapshot!(context.filters(), context.pip_compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2232,7 +2711,7 @@ fn requirement_constraint_override_url() -> Result<()> {
         .arg("--override")
         .arg("overrides.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2420,7 +2899,7 @@ optional-dependencies.bar = [
             .arg("foo"),
             @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -2454,7 +2933,7 @@ dependencies = ["anyio==3.7.0", "anyio==4.0.0"]
     uv_snapshot!(context.filters(), context.pip_compile()
             .arg("pyproject.toml"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2486,7 +2965,7 @@ dependencies = ["anyio==300.1.4"]
     uv_snapshot!(context.filters(), context.pip_compile()
             .arg("pyproject.toml"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2555,7 +3034,7 @@ fn compile_exclude_newer() -> Result<()> {
         .arg("--exclude-newer")
         .arg("2022-04-04+02:00"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -2928,7 +3407,7 @@ fn compile_yanked_version_indirect() -> Result<()> {
     uv_snapshot!(context.filters(), context.pip_compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3398,7 +3877,21 @@ fn missing_registry_extra() -> Result<()> {
 
     ----- stderr -----
     Resolved 6 packages in [TIME]
-    warning: The package `black==23.10.1` does not have an extra named `tensorboard`
+    warning: The package `black==23.10.1` does not have an extra named `tensorboard` (available extras: `colorama`, `d`, `jupyter`, `uvloop`)
+    "###
+    );
+
+    // With `--strict-extras`, the missing extra is an error.
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--strict-extras"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    error: The package `black==23.10.1` does not have an extra named `tensorboard` (available extras: `colorama`, `d`, `jupyter`, `uvloop`), but `--strict-extras` was provided
     "###
     );
 
@@ -3438,7 +3931,7 @@ fn missing_url_extra() -> Result<()> {
 
     ----- stderr -----
     Resolved 7 packages in [TIME]
-    warning: The package `flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl` does not have an extra named `tensorboard`
+    warning: The package `flask @ https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl` does not have an extra named `tensorboard` (available extras: `async`, `dotenv`)
     "###
     );
 
@@ -4739,29 +5232,135 @@ fn upgrade_none() -> Result<()> {
     uv_snapshot!(context.filters(), context.pip_compile()
         .arg("requirements.in")
         .arg("--output-file")
-        .arg("requirements.txt"), @r###"
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --output-file requirements.txt
+    black==23.10.1
+        # via -r requirements.in
+    click==8.1.2
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.0
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Only include the direct dependencies in the output file, pinning the complete resolution in a
+/// sidecar. The sidecar should be used to preserve the pinned transitive dependencies, and should
+/// be installable with `pip sync`.
+#[test]
+fn only_direct() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let sidecar = context.temp_dir.child("requirements.txt.lock");
+    sidecar.write_str(indoc! {r"
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt")
+        .arg("--only-direct"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --output-file requirements.txt --only-direct
+    # Transitive dependencies were excluded due to `--only-direct`; see `requirements.txt.lock` for the complete resolution.
+    black==23.10.1
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    // The sidecar should include every package, with hashes, and no escape sequences.
+    let contents = fs::read_to_string(sidecar.path())?;
+    for package in [
+        "black==23.10.1",
+        "click==8.1.2",
+        "mypy-extensions==1.0.0",
+        "packaging==23.2",
+        "pathspec==0.11.0",
+        "platformdirs==4.0.0",
+    ] {
+        assert!(
+            contents.contains(&format!("{package} \\\n    --hash=sha256:")),
+            "{package} is missing from the sidecar:\n{contents}"
+        );
+    }
+    assert!(!contents.contains('\x1b'), "{contents:?}");
+
+    // Re-compiling should read the pins from, and preserve, the sidecar.
+    uv_snapshot!(context.filters(), context.pip_compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt")
+        .arg("--only-direct"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --output-file requirements.txt
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --output-file requirements.txt --only-direct
+    # Transitive dependencies were excluded due to `--only-direct`; see `requirements.txt.lock` for the complete resolution.
     black==23.10.1
         # via -r requirements.in
-    click==8.1.2
-        # via black
-    mypy-extensions==1.0.0
-        # via black
-    packaging==23.2
-        # via black
-    pathspec==0.11.0
-        # via black
-    platformdirs==4.0.0
-        # via black
 
     ----- stderr -----
     Resolved 6 packages in [TIME]
     "###
     );
+    assert_eq!(fs::read_to_string(sidecar.path())?, contents);
+
+    // The sidecar should be installable as-is.
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt.lock"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    Prepared 6 packages in [TIME]
+    Installed 6 packages in [TIME]
+     + black==23.10.1
+     + click==8.1.2
+     + mypy-extensions==1.0.0
+     + packaging==23.2
+     + pathspec==0.11.0
+     + platformdirs==4.0.0
+    "###
+    );
+
+    context.assert_command("import black").success();
 
     Ok(())
 }
@@ -5389,7 +5988,7 @@ fn no_index_requirements_txt() -> Result<()> {
     uv_snapshot!(context.filters(), context.pip_compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5498,7 +6097,7 @@ fn offline_registry() -> Result<()> {
             .arg("requirements.in")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5629,7 +6228,7 @@ fn offline_find_links() -> Result<()> {
             .arg("https://download.pytorch.org/whl/torch_stable.html")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5648,7 +6247,7 @@ fn offline_find_links() -> Result<()> {
             .arg("--no-index")
             .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5734,7 +6333,7 @@ fn invalid_metadata_requires_python() -> Result<()> {
             .arg("--find-links")
             .arg(context.workspace_root.join("scripts").join("links")), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -5766,7 +6365,7 @@ fn invalid_metadata_multiple_dist_info() -> Result<()> {
             .arg("--find-links")
             .arg(context.workspace_root.join("scripts").join("links")), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -6035,7 +6634,7 @@ fn compile_constraints_incompatible_url() -> Result<()> {
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -6058,7 +6657,7 @@ fn index_url_in_requirements() -> Result<()> {
     uv_snapshot!(context.filters(), context.pip_compile()
             .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -6142,6 +6741,7 @@ fn no_deps_valid_extra() -> Result<()> {
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --no-deps
+    # Dependencies were excluded due to `--no-deps`, so this file may be incomplete.
     flask==3.0.2
         # via -r requirements.in
 
@@ -6168,12 +6768,13 @@ fn no_deps_invalid_extra() -> Result<()> {
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --no-deps
+    # Dependencies were excluded due to `--no-deps`, so this file may be incomplete.
     flask==3.0.2
         # via -r requirements.in
 
     ----- stderr -----
     Resolved 1 package in [TIME]
-    warning: The package `flask==3.0.2` does not have an extra named `empty`
+    warning: The package `flask==3.0.2` does not have an extra named `empty` (available extras: `async`, `dotenv`)
     "###
     );
 
@@ -6237,6 +6838,7 @@ dependencies = [
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --no-deps
+    # Dependencies were excluded due to `--no-deps`, so this file may be incomplete.
     -e [TEMP_DIR]/editable1
         # via -r requirements.in
     -e [TEMP_DIR]/editable2
@@ -6270,7 +6872,7 @@ fn editable_invalid_extra() -> Result<()> {
 
     ----- stderr -----
     Resolved 1 package in [TIME]
-    warning: The package `black @ file://[WORKSPACE]/scripts/packages/black_editable` does not have an extra named `empty`
+    warning: The package `black @ file://[WORKSPACE]/scripts/packages/black_editable` does not have an extra named `empty` (available extras: `colorama`, `d`, `dev`, `jupyter`, `uvloop`)
     "###);
 
     Ok(())
@@ -6543,6 +7145,39 @@ fn no_strip_markers_transitive_marker() -> Result<()> {
     Ok(())
 }
 
+/// Resolve for Windows on ARM, which requires `win_arm64` wheels and evaluates `platform_machine`
+/// as on an ARM64 machine.
+#[test]
+fn python_platform_windows_arm64() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc::indoc! {r"
+        pywin32 ; platform_machine == 'ARM64'
+        colorama ; platform_machine == 'x86_64'
+    "})?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+            .arg("requirements.in")
+            .arg("--only-binary")
+            .arg("pywin32")
+            .arg("--python-platform")
+            .arg("aarch64-pc-windows-msvc"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --only-binary pywin32 --python-platform aarch64-pc-windows-msvc
+    pywin32==306
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Perform a universal resolution with a package that has a marker.
 #[test]
 fn universal() -> Result<()> {
@@ -6588,6 +7223,39 @@ fn universal() -> Result<()> {
     Ok(())
 }
 
+/// Apply a marker to the requirements in the input files with `--marker`.
+#[test]
+fn universal_marker_flag() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(indoc::indoc! {r"
+        idna
+        sniffio ; python_version >= '3.12'
+    "})?;
+
+    uv_snapshot!(context.filters(), windows_filters=false, context.pip_compile()
+            .arg("requirements.in")
+            .arg("--universal")
+            .arg("--marker")
+            .arg("sys_platform == 'linux'"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --universal --marker "sys_platform == 'linux'"
+    idna==3.6 ; sys_platform == 'linux'
+        # via -r requirements.in
+    sniffio==1.3.1 ; python_full_version >= '3.12' and sys_platform == 'linux'
+        # via -r requirements.in
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Perform a universal resolution with conflicting versions and markers.
 #[test]
 fn universal_conflicting() -> Result<()> {
@@ -6804,7 +7472,7 @@ fn universal_multi_version() -> Result<()> {
             .arg("constraints.txt")
             .arg("--universal"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -7972,7 +8640,7 @@ fn universal_requires_python_incomplete() -> Result<()> {
             .arg("3.7")
             .arg("--universal"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -8348,7 +9016,7 @@ fn compile_constraints_incompatible_version() -> Result<()> {
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -8376,7 +9044,7 @@ fn conflicting_url_markers() -> Result<()> {
             .arg("--constraint")
             .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -8530,7 +9198,7 @@ fn override_with_incompatible_constraint() -> Result<()> {
             .arg("--override")
             .arg("overrides.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -9057,7 +9725,7 @@ requires-python = "<=3.8"
     uv_snapshot!(context.filters(), context.pip_compile()
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -9109,7 +9777,7 @@ requires-python = "<=3.8"
         .arg("requirements.in")
         .arg("--python-version=3.11"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -9337,7 +10005,7 @@ requires-python = "<=3.8"
     uv_snapshot!(context.filters(), context.pip_compile()
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -10472,7 +11140,7 @@ requires-python = ">3.8"
         .arg("--override")
         .arg("overrides.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -10645,7 +11313,7 @@ fn compile_index_url_first_match() -> Result<()> {
         .arg("requirements.in")
         .arg("--no-deps"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -10683,6 +11351,7 @@ fn compile_index_url_fallback() -> Result<()> {
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --index-strategy unsafe-any-match requirements.in --no-deps
+    # Dependencies were excluded due to `--no-deps`, so this file may be incomplete.
     jinja2==3.1.0
         # via -r requirements.in
 
@@ -10723,6 +11392,7 @@ fn compile_index_url_fallback_prefer_primary() -> Result<()> {
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --index-strategy unsafe-any-match requirements.in --no-deps
+    # Dependencies were excluded due to `--no-deps`, so this file may be incomplete.
     anyio==3.5.0
         # via -r requirements.in
 
@@ -10763,6 +11433,7 @@ fn compile_index_url_unsafe_highest() -> Result<()> {
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --index-strategy unsafe-best-match requirements.in --no-deps
+    # Dependencies were excluded due to `--no-deps`, so this file may be incomplete.
     anyio==4.3.0
         # via -r requirements.in
 
@@ -10808,6 +11479,7 @@ fn compile_index_url_unsafe_lowest() -> Result<()> {
     ----- stdout -----
     # This file was autogenerated by uv via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --resolution lowest --index-strategy unsafe-best-match requirements.in --no-deps
+    # Dependencies were excluded due to `--no-deps`, so this file may be incomplete.
     anyio==1.0.0
         # via -r requirements.in
 
@@ -11011,6 +11683,208 @@ fn emit_index_annotation_multiple_indexes() -> Result<()> {
     Ok(())
 }
 
+/// Require that packages are served by a specific index, when multiple indexes are provided.
+#[test]
+fn require_index() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("uv\nrequests")?;
+
+    // `uv` is served by PyPI, as required.
+    uv_snapshot!(context.filters(), context.pip_compile()
+        .arg("requirements.in")
+        .arg("--extra-index-url")
+        .arg("https://test.pypi.org/simple")
+        .arg("--require-index")
+        .arg("uv=https://pypi.org/simple/")
+        .arg("--emit-index-annotation"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --emit-index-annotation
+    requests==2.5.4.1
+        # via -r requirements.in
+        # from https://test.pypi.org/simple
+    uv==0.1.24
+        # via -r requirements.in
+        # from https://pypi.org/simple
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    // `requests` is served by the extra index, rather than PyPI.
+    uv_snapshot!(context.filters(), context.pip_compile()
+        .arg("requirements.in")
+        .arg("--extra-index-url")
+        .arg("https://test.pypi.org/simple")
+        .arg("--require-index")
+        .arg("requests=https://pypi.org/simple"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    error: `requests` was served by `https://test.pypi.org/simple`, but `--require-index` requires `https://pypi.org/simple`
+    "###
+    );
+
+    Ok(())
+}
+
+/// Omit packages from the output with `--omit`, pruning any dependencies that are only required
+/// by omitted packages.
+#[test]
+fn omit() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask")?;
+
+    // `markupsafe` is required by both `jinja2` and `werkzeug`, so it's retained when omitting
+    // only one of them.
+    uv_snapshot!(context.filters(), context.pip_compile()
+        .arg("requirements.in")
+        .arg("--omit")
+        .arg("jinja2"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --omit jinja2
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.2
+        # via -r requirements.in
+    itsdangerous==2.1.2
+        # via flask
+    markupsafe==2.1.5
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    # The following packages were omitted from the output, and must be provided by the environment:
+    # jinja2==3.1.3 (required by flask)
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    // Omitting both prunes `markupsafe`.
+    uv_snapshot!(context.filters(), context.pip_compile()
+        .arg("requirements.in")
+        .arg("--omit")
+        .arg("jinja2")
+        .arg("--omit")
+        .arg("werkzeug"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --omit jinja2 --omit werkzeug
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.2
+        # via -r requirements.in
+    itsdangerous==2.1.2
+        # via flask
+
+    # The following packages were omitted from the output, and must be provided by the environment:
+    # jinja2==3.1.3 (required by flask)
+    # werkzeug==3.0.1 (required by flask)
+
+    # The following packages were pruned from the output, as they're only required by omitted packages:
+    # markupsafe==2.1.5 (required by jinja2, werkzeug)
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    // The remaining packages are re-verified against their metadata, which must account for
+    // requirements that don't apply within the supported Python versions (e.g., `tomli`).
+    let requirements_in = context.temp_dir.child("black.in");
+    requirements_in.write_str("black")?;
+
+    uv_snapshot!(context.filters(), context.pip_compile()
+        .arg("black.in")
+        .arg("--universal")
+        .arg("--omit")
+        .arg("click"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] black.in --universal --omit click
+    black==24.3.0
+        # via -r black.in
+    mypy-extensions==1.0.0
+        # via black
+    packaging==24.0
+        # via black
+    pathspec==0.12.1
+        # via black
+    platformdirs==4.2.0
+        # via black
+
+    # The following packages were omitted from the output, and must be provided by the environment:
+    # click==8.1.7 (required by black)
+
+    # The following packages were pruned from the output, as they're only required by omitted packages:
+    # colorama==0.4.6 (required by click)
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    // Packages that aren't in the resolution can't be omitted.
+    uv_snapshot!(context.filters(), context.pip_compile()
+        .arg("requirements.in")
+        .arg("--omit")
+        .arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --omit anyio
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.2
+        # via -r requirements.in
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.3
+        # via flask
+    markupsafe==2.1.5
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    warning: `anyio` was not found in the resolution, and so can't be omitted
+    "###
+    );
+
+    Ok(())
+}
+
 /// Test error message when direct dependency is an empty set.
 #[test]
 fn no_version_for_direct_dependency() -> Result<()> {
@@ -11024,7 +11898,7 @@ fn no_version_for_direct_dependency() -> Result<()> {
         // Must error before we make any network requests
         .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -11672,7 +12546,7 @@ fn no_binary_only_binary() -> Result<()> {
         .arg("--only-binary")
         .arg(":all:"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -11756,7 +12630,7 @@ fn incompatible_build_constraint() -> Result<()> {
         .arg("--build-constraint")
         .arg("build_constraints.txt"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -11800,3 +12674,66 @@ fn compatible_build_constraint() -> Result<()> {
 
     Ok(())
 }
+
+/// Write a trace of the resolver's decisions to a file with `--resolver-trace`.
+#[test]
+fn compile_resolver_trace() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context
+        .pip_compile()
+        .arg("requirements.in")
+        .arg("--resolver-trace")
+        .arg("trace.jsonl"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] requirements.in --resolver-trace trace.jsonl
+    anyio==3.7.0
+        # via -r requirements.in
+    idna==3.6
+        # via anyio
+    sniffio==1.3.1
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    // Every line should be a standalone JSON event, and each selected version should be recorded.
+    let trace = fs::read_to_string(context.temp_dir.child("trace.jsonl"))?;
+    let events = trace
+        .lines()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<Result<Vec<_>, _>>()?;
+    let selected = events
+        .iter()
+        .filter(|event| event["event"] == "select")
+        .map(|event| {
+            format!(
+                "{}=={}",
+                event["package"].as_str().unwrap(),
+                event["version"].as_str().unwrap()
+            )
+        })
+        .collect::<Vec<_>>();
+    assert!(selected.contains(&"anyio==3.7.0".to_string()));
+    assert!(selected.contains(&"idna==3.6".to_string()));
+    assert!(selected.contains(&"sniffio==1.3.1".to_string()));
+    assert!(events.iter().any(|event| event["event"] == "solved"));
+
+    // The constraints derived from the dependencies of `anyio` should be propagated.
+    let propagated = events
+        .iter()
+        .filter(|event| event["event"] == "propagate" && event["package"] == "anyio")
+        .flat_map(|event| event["constraints"].as_array().unwrap())
+        .map(|constraint| constraint["package"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert!(propagated.contains(&"idna"));
+    assert!(propagated.contains(&"sniffio"));
+
+    Ok(())
+}
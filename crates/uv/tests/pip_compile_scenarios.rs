@@ -121,7 +121,7 @@ fn compatible_python_incompatible_override() -> Result<()> {
         .arg("--python-version=3.9")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -175,7 +175,7 @@ fn incompatible_python_compatible_override_unavailable_no_wheels() -> Result<()>
         .arg("--python-version=3.11")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -287,7 +287,7 @@ fn incompatible_python_compatible_override_no_compatible_wheels() -> Result<()>
         .arg("--python-version=3.11")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -345,7 +345,7 @@ fn incompatible_python_compatible_override_other_wheel() -> Result<()> {
         .arg("--python-version=3.11")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
@@ -401,7 +401,7 @@ fn python_patch_override_no_patch() -> Result<()> {
         .arg("--python-version=3.8")
         , @r###"
                  success: false
-                 exit_code: 1
+                 exit_code: 3
                  ----- stdout -----
 
                  ----- stderr -----
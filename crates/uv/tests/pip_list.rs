@@ -214,7 +214,7 @@ fn list_editable_only() {
         .arg("--editable")
         .arg("--exclude-editable"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -538,3 +538,65 @@ Version: 0.1-bulbasaur
 
     Ok(())
 }
+
+/// Include the installed size and file count of each package with `--sizes`.
+#[test]
+fn list_sizes() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(context.pip_install()
+        .arg("-r")
+        .arg("requirements.txt")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    // The installed sizes depend on the platform, so omit them.
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([
+            (r"\d+\.\d(B|KiB|MiB) +\d+", "[SIZE]  [N]"),
+            (r#""size":\d+,"files":\d+"#, r#""size":[SIZE],"files":[N]"#),
+        ])
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, list_command(&context).arg("--sizes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Package    Version Size    Files
+    ---------- ------- ------- -----
+    markupsafe 2.1.3   [SIZE]  [N]
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(filters, list_command(&context)
+        .arg("--sizes")
+        .arg("--format")
+        .arg("json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [{"name":"markupsafe","version":"2.1.3","size":[SIZE],"files":[N]}]
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
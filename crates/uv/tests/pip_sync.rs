@@ -236,9 +236,415 @@ fn install_symlink() -> Result<()> {
     Ok(())
 }
 
+/// Install a package into a virtual environment as a farm of links into the cache.
+#[test]
+fn install_farm() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    // Farm link mode requires preview mode.
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--link-mode")
+        .arg("farm")
+        .arg("--strict"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: `--link-mode=farm` is experimental and requires preview mode (`--preview`)
+    "###
+    );
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--link-mode")
+        .arg("farm")
+        .arg("--preview")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    // The package directory should be linked as a whole, while the metadata is installed in place.
+    assert!(context
+        .site_packages()
+        .join("markupsafe")
+        .symlink_metadata()?
+        .is_symlink());
+    assert!(!context
+        .site_packages()
+        .join("MarkupSafe-2.1.3.dist-info")
+        .symlink_metadata()?
+        .is_symlink());
+    assert!(context
+        .site_packages()
+        .join("MarkupSafe-2.1.3.dist-info")
+        .join("uv_link_mode")
+        .is_file());
+
+    context
+        .assert_command("from markupsafe import Markup")
+        .success();
+
+    // Bytecode should be written to the environment, rather than to the shared cache.
+    let target = fs_err::read_link(context.site_packages().join("markupsafe"))?;
+    let target = context.site_packages().join(target);
+    assert!(!target.join("__pycache__").exists());
+    assert!(context.venv.join("__pycache__").is_dir());
+
+    // Uninstalling the package should remove the link, but leave the cache intact.
+    uv_snapshot!(context.filters(), context.pip_uninstall()
+        .arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - markupsafe==2.1.3
+    "###
+    );
+
+    assert!(!context.site_packages().join("markupsafe").exists());
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--link-mode")
+        .arg("farm")
+        .arg("--preview")
+        .arg("--offline")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    context
+        .assert_command("from markupsafe import Markup")
+        .success();
+
+    Ok(())
+}
+
 /// Reject attempts to use symlink semantics with `--no-cache`.
 #[test]
-fn install_symlink_no_cache() -> Result<()> {
+fn install_symlink_no_cache() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--link-mode")
+        .arg("symlink")
+        .arg("--no-cache")
+        .arg("--strict"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    error: Symlink-based installation is not supported with `--no-cache`. The created environment will be rendered unusable by the removal of the cache.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Install a package lazily, such that its modules are materialized on first import.
+#[test]
+fn install_lazy() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--lazy-install")
+        .arg("markupsafe")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    // Only the metadata and the loader should be installed upfront.
+    assert!(context
+        .site_packages()
+        .join("MarkupSafe-2.1.3.dist-info")
+        .is_dir());
+    assert!(context
+        .site_packages()
+        .join("_uv_lazy_markupsafe.pth")
+        .is_file());
+    assert!(!context.site_packages().join("markupsafe").exists());
+
+    context
+        .assert_command("from markupsafe import Markup")
+        .success();
+
+    // Once imported, the package should be materialized, and should no longer depend on the cache.
+    assert!(context.site_packages().join("markupsafe").is_dir());
+
+    fs::remove_dir_all(context.cache_dir.path())?;
+
+    context
+        .assert_command("from markupsafe import Markup")
+        .success();
+
+    Ok(())
+}
+
+/// Exclude files matching a pattern from an installed package, omitting them from the `RECORD`.
+#[test]
+fn install_exclude_package_data() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--exclude-package-data")
+        .arg("**/*.pyi")
+        .arg("--exclude-package-data")
+        .arg("**/py.typed")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    let site_packages = context.site_packages();
+    assert!(site_packages
+        .join("markupsafe")
+        .join("__init__.py")
+        .is_file());
+    assert!(!site_packages.join("markupsafe").join("py.typed").exists());
+    assert!(!site_packages
+        .join("markupsafe")
+        .join("_speedups.pyi")
+        .exists());
+
+    let record = fs::read_to_string(
+        site_packages
+            .join("MarkupSafe-2.1.3.dist-info")
+            .join("RECORD"),
+    )?;
+    assert!(record.contains("markupsafe/__init__.py"));
+    assert!(!record.contains("py.typed"));
+    assert!(!record.contains(".pyi"));
+
+    context
+        .assert_command("from markupsafe import Markup")
+        .success();
+
+    // The package should still be uninstalled cleanly.
+    uv_snapshot!(context.pip_uninstall()
+        .arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - markupsafe==2.1.3
+    "###
+    );
+
+    assert!(!site_packages.join("markupsafe").exists());
+
+    Ok(())
+}
+
+/// Warn that files can't be excluded from lazily-installed packages.
+#[test]
+fn install_exclude_package_data_lazy() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--lazy-install")
+        .arg("markupsafe")
+        .arg("--exclude-package-data")
+        .arg("**/py.typed")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: `--exclude-package-data` doesn't apply to packages installed with `--lazy-install`; their matching files will be retained
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    Ok(())
+}
+
+/// Abort an installation that exceeds the maximum download size, before downloading anything.
+#[test]
+fn install_max_download_size() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0\ntomli==2.0.1")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--max-download-size")
+        .arg("10KiB"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Largest distributions to download:
+     tomli==2.0.1 (12.5KiB)
+     iniconfig==2.0.0 (5.8KiB)
+    error: The total download size (18.2KiB) exceeds the maximum download size (10.0KiB)
+    "###
+    );
+
+    context.assert_command("import tomli").failure();
+
+    // Within the budget, the installation should succeed.
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--max-download-size")
+        .arg("1MB"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + tomli==2.0.1
+    "###
+    );
+
+    Ok(())
+}
+
+/// Roll back the incomplete changes from an interrupted installation, then resume it.
+#[test]
+fn install_interrupted() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig==2.0.0\ntomli==2.0.1")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + tomli==2.0.1
+    "###
+    );
+
+    // Simulate an installation that was interrupted while removing `tomli`, after `iniconfig` had
+    // already been committed.
+    let site_packages = context.site_packages();
+    fs::remove_file(site_packages.join("tomli").join("__init__.py"))?;
+    let journal = [
+        serde_json::json!({
+            "type": "uninstall",
+            "name": "iniconfig",
+            "path": site_packages.join("iniconfig-2.0.0.dist-info"),
+        }),
+        serde_json::json!({ "type": "commit", "name": "iniconfig" }),
+        serde_json::json!({
+            "type": "uninstall",
+            "name": "tomli",
+            "path": site_packages.join("tomli-2.0.1.dist-info"),
+        }),
+    ]
+    .iter()
+    .map(|entry| format!("{entry}\n"))
+    .collect::<String>();
+    fs::write(site_packages.join(".uv-journal.jsonl"), journal)?;
+
+    // The partially-removed `tomli` should be removed entirely, and then reinstalled.
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    warning: A previous installation into .venv was interrupted; removed the incomplete installations of: `tomli`
+    Installed 1 package in [TIME]
+     + tomli==2.0.1
+    "###
+    );
+
+    assert!(!site_packages.join(".uv-journal.jsonl").exists());
+    context.assert_command("import iniconfig").success();
+    context.assert_command("import tomli").success();
+
+    Ok(())
+}
+
+/// Install a package lazily with `--link-mode=symlink`, which should be respected both when
+/// installing the metadata and when materializing the modules.
+#[test]
+#[cfg(unix)]
+fn install_lazy_symlink() -> Result<()> {
     let context = TestContext::new("3.12");
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
@@ -246,21 +652,42 @@ fn install_symlink_no_cache() -> Result<()> {
 
     uv_snapshot!(context.pip_sync()
         .arg("requirements.txt")
+        .arg("--lazy-install")
+        .arg("markupsafe")
         .arg("--link-mode")
         .arg("symlink")
-        .arg("--no-cache")
         .arg("--strict"), @r###"
-    success: false
-    exit_code: 2
+    success: true
+    exit_code: 0
     ----- stdout -----
 
     ----- stderr -----
     Resolved 1 package in [TIME]
     Prepared 1 package in [TIME]
-    error: Symlink-based installation is not supported with `--no-cache`. The created environment will be rendered unusable by the removal of the cache.
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
     "###
     );
 
+    // The metadata should be linked into the cache, apart from the `RECORD`.
+    let dist_info = context.site_packages().join("MarkupSafe-2.1.3.dist-info");
+    assert!(fs::symlink_metadata(dist_info.join("METADATA"))?.is_symlink());
+    assert!(!fs::symlink_metadata(dist_info.join("RECORD"))?.is_symlink());
+    assert!(!context.site_packages().join("markupsafe").exists());
+
+    context
+        .assert_command("from markupsafe import Markup")
+        .success();
+
+    // Once imported, the package should be materialized as links into the cache.
+    assert!(fs::symlink_metadata(
+        context
+            .site_packages()
+            .join("markupsafe")
+            .join("__init__.py")
+    )?
+    .is_symlink());
+
     Ok(())
 }
 
@@ -929,7 +1356,7 @@ fn install_no_index() -> Result<()> {
         .arg("--no-index")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -978,7 +1405,7 @@ fn install_no_index_cached() -> Result<()> {
         .arg("--no-index")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1245,7 +1672,7 @@ fn mismatched_name() -> Result<()> {
         .arg("requirements.txt")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1914,7 +2341,7 @@ fn duplicate_package_overlap() -> Result<()> {
         .arg("requirements.txt")
         .arg("--strict"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2782,7 +3209,7 @@ fn find_links_offline_no_match() -> Result<()> {
         .arg("--find-links")
         .arg(context.workspace_root.join("scripts/links/")), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2909,7 +3336,7 @@ fn offline() -> Result<()> {
         .arg("requirements.in")
         .arg("--offline"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3000,7 +3427,7 @@ fn incompatible_constraint() -> Result<()> {
         .arg("--constraint")
         .arg("constraints.txt"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3097,7 +3524,7 @@ fn repeat_requirement_incompatible() -> Result<()> {
     uv_snapshot!(context.pip_sync()
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3316,6 +3743,75 @@ fn compile() -> Result<()> {
     Ok(())
 }
 
+/// Verify the imports of the installed packages with `--verify-imports`.
+#[test]
+fn verify_imports() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    uv_snapshot!(context.filters(), context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--verify-imports")
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    Verified imports of 1 module in [TIME]
+    "###
+    );
+
+    // Create a package that fails to import, as if a shared library were missing.
+    let project = context.temp_dir.child("broken");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "broken"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+    "#})?;
+    project
+        .child("src")
+        .child("broken")
+        .child("__init__.py")
+        .write_str(r#"raise ImportError("libbroken.so.1: cannot open shared object file")"#)?;
+
+    requirements_txt.write_str(indoc! {r"
+        MarkupSafe==2.1.3
+        ./broken
+    "})?;
+
+    // Only the newly installed package is verified, and the failure is reported.
+    uv_snapshot!(context.filters(), context.pip_sync()
+        .arg("requirements.txt")
+        .arg("--verify-imports"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + broken==0.1.0 (from file://[TEMP_DIR]/broken)
+    error: Failed to import 1 module after installation:
+    - `broken` (from `broken`): ImportError: libbroken.so.1: cannot open shared object file
+    "###
+    );
+
+    Ok(())
+}
+
 /// Raise an error when an editable's `Requires-Python` constraint is not met.
 #[test]
 fn requires_python_editable() -> Result<()> {
@@ -3343,7 +3839,7 @@ requires-python = "<=3.5"
     uv_snapshot!(context.filters(), context.pip_sync()
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3413,7 +3909,7 @@ requires-python = "<=3.5"
     uv_snapshot!(context.filters(), context.pip_sync()
         .arg("requirements.in"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -3587,7 +4083,7 @@ fn require_hashes_wheel_no_binary() -> Result<()> {
         .arg(":all:")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3681,7 +4177,7 @@ fn require_hashes_source_only_binary() -> Result<()> {
         .arg(":all:")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3714,7 +4210,7 @@ fn require_hashes_wrong_digest() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3747,7 +4243,7 @@ fn require_hashes_wrong_algorithm() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3820,7 +4316,7 @@ fn require_hashes_source_url() -> Result<()> {
         .arg("--reinstall")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3851,7 +4347,7 @@ fn require_hashes_source_url_mismatch() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3922,7 +4418,7 @@ fn require_hashes_wheel_url() -> Result<()> {
         .arg("--reinstall")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -3964,6 +4460,57 @@ fn require_hashes_wheel_url() -> Result<()> {
     Ok(())
 }
 
+/// A hash embedded in the fragment of a direct URL is verified, even without `--require-hashes`.
+#[test]
+fn verify_url_fragment_hash() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Sync with the wrong hash in the fragment.
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt
+        .write_str("anyio @ https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl#sha256=afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 5
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    error: Failed to prepare distributions
+      Caused by: Failed to fetch wheel: anyio @ https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl#sha256=afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+      Caused by: Hash mismatch for `anyio @ https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl#sha256=afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f`
+
+    Expected:
+      sha256:afdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+
+    Computed:
+      sha256:cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f
+    "###
+    );
+
+    // Sync with the right hash in the fragment.
+    requirements_txt
+        .write_str("anyio @ https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl#sha256=cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + anyio==4.0.0 (from https://files.pythonhosted.org/packages/36/55/ad4de788d84a630656ece71059665e01ca793c04294c463fd84132f40fe6/anyio-4.0.0-py3-none-any.whl#sha256=cfdb2b588b9fc25ede96d8db56ed50848b0b649dca3dd1df0b11f683bb9e0b5f)
+    "###
+    );
+
+    Ok(())
+}
+
 /// Include the _wrong_ hash for a built distribution specified as a direct URL dependency.
 #[test]
 fn require_hashes_wheel_url_mismatch() -> Result<()> {
@@ -3977,7 +4524,7 @@ fn require_hashes_wheel_url_mismatch() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4086,7 +4633,7 @@ fn require_hashes_re_download() -> Result<()> {
         .arg("--reinstall")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4179,7 +4726,7 @@ fn require_hashes_wheel_path_mismatch() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4249,7 +4796,7 @@ fn require_hashes_source_path_mismatch() -> Result<()> {
         .arg("requirements.txt")
         .arg("--require-hashes"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4458,7 +5005,7 @@ fn require_hashes_repeated_hash() -> Result<()> {
         .arg("--require-hashes")
         .arg("--reinstall"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4592,7 +5139,7 @@ fn require_hashes_find_links_no_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/no-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4622,7 +5169,7 @@ fn require_hashes_find_links_no_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/no-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4714,7 +5261,7 @@ fn require_hashes_find_links_invalid_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/invalid-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4743,7 +5290,7 @@ fn require_hashes_find_links_invalid_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/invalid-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4825,7 +5372,7 @@ fn require_hashes_find_links_invalid_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://raw.githubusercontent.com/astral-test/astral-test-hash/main/invalid-hash/simple-html/example-a-961b4c22/index.html"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4890,7 +5437,7 @@ fn require_hashes_registry_valid_hash() -> Result<()> {
         .arg("--find-links")
         .arg("https://astral-test.github.io/astral-test-hash/valid-hash/simple-html/"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -4919,7 +5466,7 @@ fn require_hashes_registry_invalid_hash() -> Result<()> {
         .arg("--index-url")
         .arg("https://astral-test.github.io/astral-test-hash/invalid-hash/simple-html/"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -4949,7 +5496,7 @@ fn require_hashes_registry_invalid_hash() -> Result<()> {
         .arg("--index-url")
         .arg("https://astral-test.github.io/astral-test-hash/invalid-hash/simple-html/"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -5034,7 +5581,7 @@ fn require_hashes_registry_invalid_hash() -> Result<()> {
         .arg("--index-url")
         .arg("https://astral-test.github.io/astral-test-hash/invalid-hash/simple-html/"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 5
     ----- stdout -----
 
     ----- stderr -----
@@ -5326,6 +5873,106 @@ fn prefix() -> Result<()> {
     Ok(())
 }
 
+/// Reject a `--prefix` directory that was populated by a different Python version.
+#[test]
+#[cfg(unix)]
+fn prefix_version_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig==2.0.0")?;
+
+    let prefix = context.temp_dir.child("prefix");
+    prefix
+        .child("lib")
+        .child("python3.11")
+        .child("site-packages")
+        .create_dir_all()?;
+
+    uv_snapshot!(context.filters(), context.pip_sync()
+        .arg("requirements.in")
+        .arg("--prefix")
+        .arg(prefix.path()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: The `--prefix` directory at [TEMP_DIR]/prefix contains packages for Python 3.11, but the interpreter at [VENV]/bin/python is Python 3.12.[X]. Use `--python` or `--python-executable` to select a matching interpreter.
+    "###);
+
+    // Once the prefix contains a layout for the current version, it should be accepted.
+    prefix
+        .child("lib")
+        .child("python3.12")
+        .child("site-packages")
+        .create_dir_all()?;
+
+    uv_snapshot!(context.filters(), context.pip_sync()
+        .arg("requirements.in")
+        .arg("--prefix")
+        .arg(prefix.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###);
+
+    Ok(())
+}
+
+/// Sync into the environment of an explicit `--python-executable`, rather than the discovered
+/// virtual environment.
+#[test]
+fn python_executable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a second environment, outside of uv's discovery rules.
+    let other = context.temp_dir.child("other");
+    context
+        .venv()
+        .arg(other.path())
+        .arg("--python")
+        .arg("3.12")
+        .assert()
+        .success();
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("iniconfig==2.0.0")?;
+
+    uv_snapshot!(context.filters(), context.pip_sync()
+        .arg("requirements.in")
+        .arg("--python-executable")
+        .arg(venv_to_interpreter(&other)), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###);
+
+    // The package should be installed into the other environment, not the discovered one.
+    context.assert_command("import iniconfig").failure();
+    Command::new(venv_to_interpreter(&other))
+        .arg("-B")
+        .arg("-c")
+        .arg("import iniconfig")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    Ok(())
+}
+
 /// Ensure that we install packages with markers on them.
 #[test]
 fn preserve_markers() -> Result<()> {
@@ -5366,7 +6013,7 @@ fn incompatible_build_constraint() -> Result<()> {
         .arg("--build-constraint")
         .arg("build_constraints.txt"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -5409,3 +6056,68 @@ fn compatible_build_constraint() -> Result<()> {
 
     Ok(())
 }
+
+/// Sync the union of multiple requirements files.
+#[test]
+fn sync_multiple_files() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("anyio==4.3.0\nidna==3.6\nsniffio==1.3.1")?;
+
+    // The same pin may appear in several files, and pins that don't apply to the environment are
+    // ignored.
+    let dev_txt = context.temp_dir.child("dev.txt");
+    dev_txt.write_str(indoc::indoc! {r"
+        iniconfig==2.0.0
+        anyio==4.3.0
+        anyio==3.7.0 ; python_version < '3.8'
+    "})?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("dev.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    Prepared 4 packages in [TIME]
+    Installed 4 packages in [TIME]
+     + anyio==4.3.0
+     + idna==3.6
+     + iniconfig==2.0.0
+     + sniffio==1.3.1
+    "###
+    );
+
+    Ok(())
+}
+
+/// Pinning the same package to different versions across requirements files should error,
+/// naming both files.
+#[test]
+fn sync_multiple_files_conflict() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("idna==3.6\nanyio==4.3.0")?;
+
+    let dev_txt = context.temp_dir.child("dev.txt");
+    dev_txt.write_str("iniconfig==2.0.0\nanyio==3.7.0")?;
+
+    uv_snapshot!(context.pip_sync()
+        .arg("requirements.txt")
+        .arg("dev.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `anyio` is pinned to multiple versions: `anyio==4.3.0` (requirements.txt:2) and `anyio==3.7.0` (dev.txt:2)
+    "###
+    );
+
+    Ok(())
+}
@@ -20,7 +20,7 @@ fn no_arguments() -> Result<()> {
         .arg("uninstall")
         .current_dir(&temp_dir), @r###"
     success: false
-    exit_code: 2
+    exit_code: 7
     ----- stdout -----
 
     ----- stderr -----
@@ -148,3 +148,63 @@ fn python_find() {
     ----- stderr -----
     "###);
 }
+
+#[test]
+fn python_find_refresh_python() -> anyhow::Result<()> {
+    let context: TestContext = TestContext::new_with_versions(&["3.11", "3.12"]);
+
+    // Populate the interpreter cache.
+    uv_snapshot!(context.filters(), context.python_find().arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [PYTHON-3.12]
+
+    ----- stderr -----
+    "###);
+
+    // Backdate the cached interpreter metadata, such that any re-query is observable.
+    let entries = || {
+        fs_err::read_dir(context.cache_dir.join("interpreter-v2"))
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "msgpack"))
+            .collect::<Vec<_>>()
+    };
+    let is_backdated = |path: &std::path::Path| {
+        fs_err::metadata(path).is_ok_and(|metadata| {
+            filetime::FileTime::from_last_modification_time(&metadata)
+                == filetime::FileTime::from_unix_time(0, 0)
+        })
+    };
+    assert!(!entries().is_empty());
+    for path in entries() {
+        filetime::set_file_mtime(&path, filetime::FileTime::from_unix_time(0, 0))?;
+    }
+
+    // `--refresh-package` doesn't affect interpreter metadata, but is accepted.
+    uv_snapshot!(context.filters(), context.python_find().arg("3.12").arg("--refresh-package").arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [PYTHON-3.12]
+
+    ----- stderr -----
+    "###);
+    assert!(entries().iter().all(|path| is_backdated(path)));
+
+    // Re-query the interpreters, bypassing the cached metadata.
+    uv_snapshot!(context.filters(), context.python_find().arg("3.12").arg("--refresh-python"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [PYTHON-3.12]
+
+    ----- stderr -----
+    "###);
+    assert!(entries().iter().any(|path| !is_backdated(path)));
+
+    Ok(())
+}
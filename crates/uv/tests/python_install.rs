@@ -0,0 +1,51 @@
+use anyhow::Result;
+use assert_fs::fixture::{FileWriteStr, PathChild, PathCreateDir};
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+/// Changing the post-install hooks of an existing installation leaves a `sitecustomize` module
+/// in place if it was replaced after it was installed by uv.
+#[test]
+fn python_install_hooks_modified_sitecustomize() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an installation with the `--safe-path` hook applied.
+    let python_dir = context.temp_dir.child("python");
+    let installation = python_dir.child("cpython-3.12.4-linux-x86_64-gnu");
+    let site_packages = installation
+        .child("install")
+        .child("lib")
+        .child("python3.12")
+        .child("site-packages");
+    site_packages.create_dir_all()?;
+    installation
+        .child("uv-hooks.json")
+        .write_str(r#"{ "safe-path": true }"#)?;
+
+    // Replace the installed `sitecustomize` module.
+    let sitecustomize = site_packages.child("sitecustomize.py");
+    sitecustomize.write_str("import site\n")?;
+
+    uv_snapshot!(context.filters(), context.python_install()
+        .arg("cpython-3.12.4-linux-x86_64-gnu")
+        .env("UV_PYTHON_INSTALL_DIR", python_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Searching for Python versions matching: cpython-3.12.4-linux-x86_64-gnu
+    Found existing installation for cpython-3.12.4-linux-x86_64-gnu: cpython-3.12.4-linux-x86_64-gnu
+    warning: Leaving `[TEMP_DIR]/python/cpython-3.12.4-linux-x86_64-gnu/install/lib/python3.12/site-packages/sitecustomize.py` in place, since it was modified after it was installed by uv
+    Updated post-install hooks for: cpython-3.12.4-linux-x86_64-gnu
+    "###
+    );
+
+    // The module is retained, but the hooks are no longer recorded.
+    assert_eq!(fs_err::read_to_string(&sitecustomize)?, "import site\n");
+    assert!(!installation.child("uv-hooks.json").exists());
+
+    Ok(())
+}
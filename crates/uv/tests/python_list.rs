@@ -0,0 +1,62 @@
+use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+#[test]
+fn python_list_only_downloads_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let output = context
+        .python_list()
+        .arg("--only-downloads")
+        .arg("--all-versions")
+        .arg("--all-platforms")
+        .arg("--json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output)?;
+
+    // The full catalog is listed, and no installed versions are included.
+    assert!(entries.len() > 100);
+    assert!(entries.iter().all(|entry| entry["kind"] == "download"
+        && entry["path"].is_null()
+        && entry["url"].is_string()));
+
+    // Each entry describes the build.
+    let entry = entries
+        .iter()
+        .find(|entry| entry["key"] == "cpython-3.12.4-linux-x86_64-gnu")
+        .expect("CPython 3.12.4 should be available for x86-64 Linux");
+    assert_eq!(entry["version"], "3.12.4");
+    assert_eq!(entry["implementation"], "cpython");
+    assert_eq!(entry["os"], "linux");
+    assert_eq!(entry["arch"], "x86_64");
+    assert_eq!(entry["libc"], "gnu");
+    assert!(entry["sha256"].is_string());
+
+    Ok(())
+}
+
+#[test]
+fn python_list_only_downloads_conflicts() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context.filters(), context.python_list().arg("--only-downloads").arg("--only-installed"), @r###"
+    success: false
+    exit_code: 7
+    ----- stdout -----
+
+    ----- stderr -----
+    error: the argument '--only-downloads' cannot be used with '--only-installed'
+
+    Usage: uv python list --only-downloads
+
+    For more information, try '--help'.
+    "###);
+}
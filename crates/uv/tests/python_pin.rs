@@ -631,6 +631,59 @@ fn python_pin_resolve() {
     });
 }
 
+/// With `--resolved=key`, we pin the key of the discovered interpreter, rather than its path.
+#[test]
+fn python_pin_resolve_key() {
+    let context: TestContext = TestContext::new_with_versions(&["3.11", "3.12"]);
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(
+            r"(cpython-3\.\d+\.\[X\])-[a-z0-9_]+-[a-z0-9_]+-[a-z0-9_]+",
+            "$1-[PLATFORM]".to_string(),
+        )])
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, context.python_pin().arg("--resolved=key").arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Pinned `.python-version` to `cpython-3.12.[X]-[PLATFORM]`
+
+    ----- stderr -----
+    "###);
+
+    let python_version =
+        fs_err::read_to_string(context.temp_dir.join(PYTHON_VERSION_FILENAME)).unwrap();
+    insta::with_settings!({
+        filters => filters.clone(),
+    }, {
+        assert_snapshot!(python_version, @r###"
+        cpython-3.12.[X]-[PLATFORM]
+        "###);
+    });
+
+    // The pinned key is used to discover the interpreter.
+    uv_snapshot!(filters, context.python_find(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [PYTHON-3.12]
+
+    ----- stderr -----
+    "###);
+
+    // `--resolved=path` is equivalent to `--resolved`.
+    uv_snapshot!(filters, context.python_pin().arg("--resolved=path").arg("3.11"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Updated `.python-version` from `cpython-3.12.[X]-[PLATFORM]` -> `[PYTHON-3.11]`
+
+    ----- stderr -----
+    "###);
+}
+
 #[test]
 fn python_pin_with_comments() -> Result<()> {
     let context = TestContext::new_with_versions(&[]);
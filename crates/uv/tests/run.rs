@@ -106,8 +106,7 @@ fn run_with_python_version() -> Result<()> {
 
     ----- stderr -----
     Using Python 3.11.[X] interpreter at: [PYTHON-3.11]
-    Removed virtual environment at: .venv
-    Creating virtualenv at: .venv
+    Creating virtualenv at: .venv-cpython-3.11
     Resolved 5 packages in [TIME]
     Prepared 3 packages in [TIME]
     Installed 4 packages in [TIME]
@@ -117,6 +116,51 @@ fn run_with_python_version() -> Result<()> {
      + sniffio==1.3.1
     "###);
 
+    // Targeting Python 3.11 again should reuse the secondary environment.
+    let mut command = context.run();
+    let command_with_args = command
+        .arg("--preview")
+        .arg("-p")
+        .arg("3.11")
+        .arg("python")
+        .arg("-B")
+        .arg("main.py")
+        .env_remove("VIRTUAL_ENV");
+
+    uv_snapshot!(context.filters(), command_with_args, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    3.11.[X]
+    3.6.0
+
+    ----- stderr -----
+    Using Python 3.11.[X] interpreter at: [PYTHON-3.11]
+    Resolved 5 packages in [TIME]
+    Audited 4 packages in [TIME]
+    "###);
+
+    // Without a request, the project environment should be left untouched.
+    let mut command = context.run();
+    let command_with_args = command
+        .arg("--preview")
+        .arg("python")
+        .arg("-B")
+        .arg("main.py")
+        .env_remove("VIRTUAL_ENV");
+
+    uv_snapshot!(context.filters(), command_with_args, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    3.12.[X]
+    3.7.0
+
+    ----- stderr -----
+    Resolved 5 packages in [TIME]
+    Audited 4 packages in [TIME]
+    "###);
+
     // This time, we target Python 3.8 instead.
     let mut command = context.run();
     let command_with_args = command
@@ -141,6 +185,90 @@ fn run_with_python_version() -> Result<()> {
     Ok(())
 }
 
+/// Run a command against each of the Python versions in `tool.uv.test-pythons`.
+#[test]
+fn run_all_pythons() -> Result<()> {
+    let context = TestContext::new_with_versions(&["3.12", "3.11"]);
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! { r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+        requires-python = ">=3.11, <4"
+        dependencies = []
+
+        [tool.uv]
+        test-pythons = ["3.11", "3.12"]
+        "#
+    })?;
+
+    // The command fails on Python 3.11, but succeeds on Python 3.12.
+    uv_snapshot!(context.filters(), context.run()
+        .arg("--preview")
+        .arg("--all-pythons")
+        .arg("python")
+        .arg("-c")
+        .arg("import sys; sys.exit(sys.version_info < (3, 12))")
+        .env_remove("VIRTUAL_ENV"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Running `python -c import sys; sys.exit(sys.version_info < (3, 12))` with Python 3.11
+    Using Python 3.11.[X] interpreter at: [PYTHON-3.11]
+    Creating virtualenv at: .venv-cpython-3.11
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + foo==1.0.0 (from file://[TEMP_DIR]/)
+    Running `python -c import sys; sys.exit(sys.version_info < (3, 12))` with Python 3.12
+    Using Python 3.12.[X] interpreter at: [PYTHON-3.12]
+    Creating virtualenv at: .venv-cpython-3.12
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + foo==1.0.0 (from file://[TEMP_DIR]/)
+
+    Summary:
+      Python 3.11: failed
+      Python 3.12: passed
+    "###);
+
+    // The project environment is left untouched.
+    context
+        .temp_dir
+        .child(".venv")
+        .assert(predicates::path::missing());
+
+    // Without any declared versions, `--all-pythons` is an error.
+    pyproject_toml.write_str(indoc! { r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+        requires-python = ">=3.11, <4"
+        dependencies = []
+        "#
+    })?;
+
+    uv_snapshot!(context.filters(), context.run()
+        .arg("--preview")
+        .arg("--all-pythons")
+        .arg("python")
+        .arg("-c")
+        .arg("pass"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `--all-pythons` requires at least one Python version in `tool.uv.test-pythons`, but none were found
+    "###);
+
+    Ok(())
+}
+
 #[test]
 fn run_args() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -346,7 +474,7 @@ fn run_pep723_script() -> Result<()> {
 
     uv_snapshot!(context.filters(), context.run().arg("--preview").arg("--no-project").arg("main.py"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -531,7 +659,7 @@ fn run_with() -> Result<()> {
     // If the dependencies can't be resolved, we should reference `--with`.
     uv_snapshot!(context.filters(), context.run().arg("--with").arg("add").arg("main.py"), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -1264,3 +1392,264 @@ fn run_no_project() -> Result<()> {
 
     Ok(())
 }
+
+/// In preview, ephemeral environments are cloned from a template in the cache.
+#[test]
+fn run_isolated_template() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // The first invocation creates the template.
+    uv_snapshot!(context.filters(), context.run().arg("--preview").arg("--no-project").arg("--isolated").arg("python").arg("-c").arg("import sys; print(sys.prefix != sys.base_prefix)"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    True
+
+    ----- stderr -----
+    "###);
+
+    let templates = fs_err::read_dir(context.cache_dir.child("venv-templates-v0"))?
+        .filter_map(Result::ok)
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with(".tmp"))
+        .collect::<Vec<_>>();
+    assert_eq!(templates.len(), 1);
+    assert!(templates[0].path().join("pyvenv.cfg").is_file());
+
+    // Subsequent invocations clone it.
+    uv_snapshot!(context.filters(), context.run().arg("--preview").arg("--no-project").arg("--isolated").arg("python").arg("-c").arg("import sys; print(sys.prefix != sys.base_prefix)"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    True
+
+    ----- stderr -----
+    "###);
+
+    Ok(())
+}
+
+/// Run a command in a project that installs its dependencies into `__pypackages__`.
+#[test]
+fn run_pypackages() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! { r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+        requires-python = ">=3.8"
+        dependencies = ["iniconfig"]
+
+        [build-system]
+        requires = ["setuptools>=42", "wheel"]
+        build-backend = "setuptools.build_meta"
+
+        [tool.uv]
+        pypackages = true
+        "#
+    })?;
+
+    // The dependencies should be installed into `__pypackages__`, rather than the virtual
+    // environment.
+    uv_snapshot!(context.filters(), context.run().arg("python").arg("-c").arg("import iniconfig; print(iniconfig.__file__)"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [TEMP_DIR]/__pypackages__/3.12/lib/iniconfig/__init__.py
+
+    ----- stderr -----
+    warning: `uv run` is experimental and may change without warning
+    Using Python 3.12.[X] interpreter at: [PYTHON-3.12]
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + foo==1.0.0 (from file://[TEMP_DIR]/)
+     + iniconfig==2.0.0
+    "###);
+
+    context
+        .temp_dir
+        .child("__pypackages__")
+        .child(".gitignore")
+        .assert(predicates::path::is_file());
+
+    // A `sitecustomize` module in the `__pypackages__` directory should still be executed.
+    context
+        .temp_dir
+        .child("__pypackages__")
+        .child("3.12")
+        .child("lib")
+        .child("sitecustomize.py")
+        .write_str("print('Hello from sitecustomize')")?;
+
+    uv_snapshot!(context.filters(), context.run().arg("python").arg("-c").arg("import iniconfig"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Hello from sitecustomize
+
+    ----- stderr -----
+    warning: `uv run` is experimental and may change without warning
+    Using Python 3.12.[X] interpreter at: [PYTHON-3.12]
+    Resolved 2 packages in [TIME]
+    Audited 2 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Replace the implicit `sys.path` entry with the directories in `tool.uv.module-path`.
+#[test]
+fn run_module_path() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! { r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+        requires-python = ">=3.8"
+        dependencies = []
+
+        [build-system]
+        requires = ["setuptools>=42", "wheel"]
+        build-backend = "setuptools.build_meta"
+
+        [tool.uv]
+        module-path = ["project-root"]
+        "#
+    })?;
+
+    // The implicit entry should be disabled, and the project root added to the `PYTHONPATH`. The
+    // interpreter should be invoked with `-P`, such that subprocesses don't inherit the setting.
+    uv_snapshot!(context.filters(), context.run().arg("python").arg("-c").arg("import os, sys; print(sys.flags.safe_path); print(os.environ['PYTHONPATH']); print(os.environ.get('PYTHONSAFEPATH'))"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    True
+    [TEMP_DIR]
+    None
+
+    ----- stderr -----
+    warning: `uv run` is experimental and may change without warning
+    Using Python 3.12.[X] interpreter at: [PYTHON-3.12]
+    Creating virtualenv at: .venv
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + foo==1.0.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    // `--no-module-path` should override the setting, such that no directories are added.
+    uv_snapshot!(context.filters(), context.run().arg("--no-module-path").arg("python").arg("-c").arg("import os, sys; print(sys.flags.safe_path); print(os.environ.get('PYTHONPATH'))"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    True
+    None
+
+    ----- stderr -----
+    warning: `uv run` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Audited 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// A workspace member's `tool.uv.module-path` takes precedence over that of the workspace root.
+#[test]
+fn run_module_path_member() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! { r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+        requires-python = ">=3.8"
+        dependencies = []
+
+        [tool.uv]
+        module-path = []
+
+        [tool.uv.workspace]
+        members = ["bar"]
+        "#
+    })?;
+
+    let bar = context.temp_dir.child("bar");
+    bar.child("pyproject.toml").write_str(indoc! { r#"
+        [project]
+        name = "bar"
+        version = "1.0.0"
+        requires-python = ">=3.8"
+        dependencies = []
+
+        [tool.uv]
+        module-path = ["project-root"]
+        "#
+    })?;
+
+    let output = context
+        .run()
+        .arg("python")
+        .arg("-c")
+        .arg("import os; print(os.environ['PYTHONPATH'])")
+        .current_dir(bar.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(
+        String::from_utf8(output)?.trim(),
+        bar.path().display().to_string()
+    );
+
+    Ok(())
+}
+
+/// A script's `tool.uv.module-path` determines its `sys.path` entries, relative to the script.
+#[test]
+fn run_module_path_script() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context
+        .temp_dir
+        .child("src")
+        .child("helper.py")
+        .write_str("MESSAGE = 'Hello from src'")?;
+
+    let test_script = context.temp_dir.child("main.py");
+    test_script.write_str(indoc! { r#"
+        # /// script
+        # requires-python = ">=3.11"
+        #
+        # [tool.uv]
+        # module-path = ["src"]
+        # ///
+
+        import sys
+
+        import helper
+
+        print(sys.flags.safe_path)
+        print(helper.MESSAGE)
+       "#
+    })?;
+
+    uv_snapshot!(context.filters(), context.run().arg("--preview").arg("main.py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    True
+    Hello from src
+
+    ----- stderr -----
+    Reading inline script metadata from: main.py
+    "###);
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::prelude::*;
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+#[test]
+fn server() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==3.7.0"]
+    "#,
+    )?;
+
+    context.lock().assert().success();
+
+    let requests = context.temp_dir.child("requests.jsonl");
+    requests.write_str(indoc::indoc! {r#"
+        {"jsonrpc": "2.0", "id": 1, "method": "lock/status"}
+        {"jsonrpc": "2.0", "id": 2, "method": "dependencies/get", "params": {"package": "sniffio"}}
+        {"jsonrpc": "2.0", "id": 3, "method": "dependencies/get", "params": {"package": "flask"}}
+        {"jsonrpc": "2.0", "id": 4, "method": "dependencies/get"}
+        {"jsonrpc": "2.0", "id": 5, "method": "resolve", "params": {"requirements": ["iniconfig"]}}
+        {"jsonrpc": "2.0", "id": 6, "method": "lock/fix"}
+        not json
+        {"jsonrpc": "2.0", "method": "lock/status"}
+        {"jsonrpc": "2.0", "id": 7, "method": "shutdown"}
+        {"jsonrpc": "2.0", "id": 8, "method": "lock/status"}
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.server().stdin(std::fs::File::open(requests.path())?), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"jsonrpc":"2.0","id":1,"result":{"exists":true,"up_to_date":true}}
+    {"jsonrpc":"2.0","id":2,"result":{"dependencies":[],"dependents":["anyio"],"name":"sniffio","source":"registry+https://pypi.org/simple","version":"1.3.1"}}
+    {"jsonrpc":"2.0","id":3,"error":{"code":-32000,"message":"Package `flask` was not found in the lockfile"}}
+    {"jsonrpc":"2.0","id":4,"error":{"code":-32602,"message":"invalid type: null, expected struct DependenciesParams"}}
+    {"jsonrpc":"2.0","id":5,"result":{"packages":[{"name":"iniconfig","version":"2.0.0"}]}}
+    {"jsonrpc":"2.0","id":6,"error":{"code":-32601,"message":"Unknown method: `lock/fix`"}}
+    {"jsonrpc":"2.0","id":null,"error":{"code":-32700,"message":"expected ident at line 1 column 2"}}
+    {"jsonrpc":"2.0","id":7,"result":null}
+
+    ----- stderr -----
+    warning: `uv server` is experimental and may change without warning
+    "###);
+
+    // After the project changes, the lockfile is reported as outdated.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==3.7.0", "iniconfig"]
+    "#,
+    )?;
+
+    requests.write_str(indoc::indoc! {r#"
+        {"jsonrpc": "2.0", "id": 1, "method": "lock/status"}
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.server().stdin(std::fs::File::open(requests.path())?), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"jsonrpc":"2.0","id":1,"result":{"exists":true,"up_to_date":false}}
+
+    ----- stderr -----
+    warning: `uv server` is experimental and may change without warning
+    "###);
+
+    Ok(())
+}
@@ -52,18 +52,35 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -74,6 +91,12 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -111,6 +134,9 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -125,9 +151,11 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -144,6 +172,7 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -156,11 +185,16 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -188,18 +222,35 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -210,6 +261,12 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -247,6 +304,9 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -261,9 +321,11 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -280,6 +342,7 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -292,11 +355,16 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -325,18 +393,35 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -347,6 +432,12 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -384,6 +475,9 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -398,9 +492,11 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -417,6 +513,7 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -429,11 +526,16 @@ fn resolve_uv_toml() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -494,18 +596,35 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -516,6 +635,12 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -553,6 +678,9 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -567,9 +695,11 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -586,6 +716,7 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -598,11 +729,16 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -632,18 +768,35 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -654,6 +807,12 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -668,6 +827,9 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -682,9 +844,11 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -701,6 +865,7 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -713,11 +878,16 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -756,18 +926,35 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -778,6 +965,12 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -815,6 +1008,9 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -829,9 +1025,11 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -848,6 +1046,7 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -860,11 +1059,16 @@ fn resolve_pyproject_toml() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -917,18 +1121,35 @@ fn resolve_index_url() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -939,6 +1160,12 @@ fn resolve_index_url() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -999,6 +1226,9 @@ fn resolve_index_url() -> anyhow::Result<()> {
                 ],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -1013,9 +1243,11 @@ fn resolve_index_url() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -1032,6 +1264,7 @@ fn resolve_index_url() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -1044,11 +1277,16 @@ fn resolve_index_url() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -1078,18 +1316,35 @@ fn resolve_index_url() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -1100,6 +1355,12 @@ fn resolve_index_url() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -1182,6 +1443,9 @@ fn resolve_index_url() -> anyhow::Result<()> {
                 ],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -1196,9 +1460,11 @@ fn resolve_index_url() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -1215,6 +1481,7 @@ fn resolve_index_url() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -1227,11 +1494,16 @@ fn resolve_index_url() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -1284,18 +1556,35 @@ fn resolve_find_links() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -1306,6 +1595,12 @@ fn resolve_find_links() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -1343,6 +1638,9 @@ fn resolve_find_links() -> anyhow::Result<()> {
                     ),
                 ],
                 no_index: true,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -1357,9 +1655,11 @@ fn resolve_find_links() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -1376,6 +1676,7 @@ fn resolve_find_links() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -1388,11 +1689,16 @@ fn resolve_find_links() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -1444,18 +1750,35 @@ fn resolve_top_level() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -1466,6 +1789,12 @@ fn resolve_top_level() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -1480,6 +1809,9 @@ fn resolve_top_level() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -1494,9 +1826,11 @@ fn resolve_top_level() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -1513,6 +1847,7 @@ fn resolve_top_level() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -1525,11 +1860,16 @@ fn resolve_top_level() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -1574,18 +1914,35 @@ fn resolve_top_level() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -1596,6 +1953,12 @@ fn resolve_top_level() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -1655,6 +2018,9 @@ fn resolve_top_level() -> anyhow::Result<()> {
                 ],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -1669,9 +2035,11 @@ fn resolve_top_level() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -1688,6 +2056,7 @@ fn resolve_top_level() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -1700,11 +2069,16 @@ fn resolve_top_level() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -1732,18 +2106,35 @@ fn resolve_top_level() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -1754,6 +2145,12 @@ fn resolve_top_level() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -1813,6 +2210,9 @@ fn resolve_top_level() -> anyhow::Result<()> {
                 ],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -1827,9 +2227,11 @@ fn resolve_top_level() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -1846,6 +2248,7 @@ fn resolve_top_level() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -1858,11 +2261,16 @@ fn resolve_top_level() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -1914,18 +2322,35 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -1936,6 +2361,12 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -1950,6 +2381,9 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -1964,9 +2398,11 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -1983,6 +2419,7 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -1995,11 +2432,16 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -2034,18 +2476,35 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -2056,6 +2515,12 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -2070,6 +2535,9 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -2084,9 +2552,11 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -2103,6 +2573,7 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -2115,11 +2586,16 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -2154,18 +2630,35 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -2176,6 +2669,12 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -2190,6 +2689,9 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -2204,9 +2706,11 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -2223,6 +2727,7 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -2235,11 +2740,16 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -2276,18 +2786,35 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -2298,6 +2825,12 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -2312,6 +2845,9 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -2326,9 +2862,11 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -2345,6 +2883,7 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -2357,11 +2896,16 @@ fn resolve_user_configuration() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -2417,24 +2961,43 @@ fn resolve_tool() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     ToolInstallSettings {
         package: "requirements.in",
         from: None,
         with: [],
         with_requirements: [],
+        constraint: [],
+        override: [],
         python: None,
         refresh: None(
             Timestamp(
@@ -2449,6 +3012,7 @@ fn resolve_tool() -> anyhow::Result<()> {
             extra_index_url: None,
             no_index: None,
             find_links: None,
+            index_mirrors: None,
             index_strategy: None,
             keyring_provider: None,
             resolution: Some(
@@ -2467,6 +3031,7 @@ fn resolve_tool() -> anyhow::Result<()> {
                 Clone,
             ),
             compile_bytecode: None,
+            exclude_package_data: None,
             no_sources: None,
             upgrade: None,
             upgrade_package: None,
@@ -2476,6 +3041,8 @@ fn resolve_tool() -> anyhow::Result<()> {
             no_build_package: None,
             no_binary: None,
             no_binary_package: None,
+            prefer_source: None,
+            prefer_source_package: None,
         },
         settings: ResolverInstallerSettings {
             index_locations: IndexLocations {
@@ -2483,6 +3050,9 @@ fn resolve_tool() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             index_strategy: FirstIndex,
             keyring_provider: Disabled,
@@ -2500,16 +3070,27 @@ fn resolve_tool() -> anyhow::Result<()> {
             ),
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             upgrade: None,
             reinstall: None,
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
         },
         force: false,
+        locked: false,
+        from_lock: None,
+        suffix: None,
+        entrypoint: [],
+        exclude_entrypoint: [],
+        include_deps: false,
+        read_only_env: false,
+        env: {},
         editable: false,
+        format: Text,
     }
 
     ----- stderr -----
@@ -2563,18 +3144,35 @@ fn resolve_poetry_toml() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -2585,6 +3183,12 @@ fn resolve_poetry_toml() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -2599,6 +3203,9 @@ fn resolve_poetry_toml() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -2613,9 +3220,11 @@ fn resolve_poetry_toml() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -2632,6 +3241,7 @@ fn resolve_poetry_toml() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -2644,11 +3254,16 @@ fn resolve_poetry_toml() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -2711,18 +3326,35 @@ fn resolve_both() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -2733,6 +3365,12 @@ fn resolve_both() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -2770,6 +3408,9 @@ fn resolve_both() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -2784,9 +3425,11 @@ fn resolve_both() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -2803,6 +3446,7 @@ fn resolve_both() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -2815,11 +3459,16 @@ fn resolve_both() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -2874,18 +3523,35 @@ fn resolve_config_file() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -2896,6 +3562,12 @@ fn resolve_config_file() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -2933,6 +3605,9 @@ fn resolve_config_file() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -2947,9 +3622,11 @@ fn resolve_config_file() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -2966,6 +3643,7 @@ fn resolve_config_file() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -2978,11 +3656,16 @@ fn resolve_config_file() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -3112,18 +3795,35 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -3134,6 +3834,12 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -3148,6 +3854,9 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -3162,9 +3871,11 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: LowestDirect,
             prerelease: IfNecessaryOrExplicit,
@@ -3181,6 +3892,7 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -3193,11 +3905,16 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -3235,18 +3952,35 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
         verbose: 0,
         color: Auto,
         native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: false,
+            pool_max_idle_per_host: 20,
+            pool_idle_timeout: 90s,
+            tcp_keepalive: None,
+        },
+        index_verification: Disabled,
         connectivity: Online,
         show_settings: true,
         preview: Disabled,
         python_preference: OnlySystem,
         python_downloads: Automatic,
         no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
     }
     CacheSettings {
         no_cache: false,
         cache_dir: Some(
             "[CACHE_DIR]/",
         ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
     }
     PipCompileSettings {
         src_file: [
@@ -3257,6 +3991,12 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
         constraints_from_workspace: [],
         overrides_from_workspace: [],
         build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
         refresh: None(
             Timestamp(
                 SystemTime {
@@ -3271,6 +4011,9 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
                 extra_index: [],
                 flat_index: [],
                 no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
             },
             python: None,
             system: false,
@@ -3285,9 +4028,11 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
             build_options: BuildOptions {
                 no_binary: None,
                 no_build: None,
+                prefer_source: None,
             },
             allow_empty_requirements: false,
             strict: false,
+            strict_extras: false,
             dependency_mode: Transitive,
             resolution: Highest,
             prerelease: IfNecessaryOrExplicit,
@@ -3304,6 +4049,7 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
             ),
             python_version: None,
             python_platform: None,
+            macos_arch: Native,
             universal: false,
             exclude_newer: Some(
                 ExcludeNewer(
@@ -3316,11 +4062,185 @@ fn resolve_skip_empty() -> anyhow::Result<()> {
             emit_build_options: false,
             emit_marker_expression: false,
             emit_index_annotation: false,
+            emit_constraint_annotation: false,
             annotation_style: Split,
             link_mode: Clone,
             compile_bytecode: false,
+            exclude_package_data: [],
             sources: Enabled,
             hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
+            upgrade: None,
+            reinstall: None,
+            concurrency: Concurrency {
+                downloads: 50,
+                builds: 16,
+                installs: 8,
+            },
+        },
+    }
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Read the HTTP connection settings from a `uv.toml`, with environment variables taking
+/// precedence.
+#[test]
+#[cfg_attr(
+    windows,
+    ignore = "Configuration tests are not yet supported on Windows"
+)]
+fn resolve_http_settings() -> anyhow::Result<()> {
+    let context = TestContext::new("3.12");
+
+    let config = context.temp_dir.child("uv.toml");
+    config.write_str(indoc::indoc! {r#"
+        http2 = true
+        http-pool-max-idle-per-host = 4
+        http-tcp-keepalive = 60
+    "#})?;
+
+    uv_snapshot!(context.filters(), add_shared_args(context.pip_compile())
+        .arg("--show-settings")
+        .arg("requirements.in")
+        .env("UV_HTTP_POOL_MAX_IDLE_PER_HOST", "0")
+        .env("UV_HTTP_POOL_IDLE_TIMEOUT", "30"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    GlobalSettings {
+        quiet: false,
+        verbose: 0,
+        color: Auto,
+        native_tls: false,
+        ip_version: None,
+        index_resolve: IndexResolve(
+            [],
+        ),
+        http: HttpSettings {
+            http2: true,
+            pool_max_idle_per_host: 0,
+            pool_idle_timeout: 30s,
+            tcp_keepalive: Some(
+                60s,
+            ),
+        },
+        index_verification: Disabled,
+        connectivity: Online,
+        show_settings: true,
+        preview: Disabled,
+        python_preference: OnlySystem,
+        python_downloads: Automatic,
+        no_progress: false,
+        progress_format: Bars,
+        lock_timeout: None,
+        repair_wheel_command: None,
+    }
+    CacheSettings {
+        no_cache: false,
+        cache_dir: Some(
+            "[CACHE_DIR]/",
+        ),
+        cache_buckets: {},
+        staging_dir: None,
+        no_interpreter_cache: false,
+    }
+    PipCompileSettings {
+        src_file: [
+            "requirements.in",
+        ],
+        constraint: [],
+        override: [],
+        constraints_from_workspace: [],
+        overrides_from_workspace: [],
+        build_constraint: [],
+        group: [],
+        target_environment: None,
+        require_index: [],
+        omit: [],
+        only_direct: false,
+        resolver_trace: None,
+        refresh: None(
+            Timestamp(
+                SystemTime {
+                    tv_sec: [TIME],
+                    tv_nsec: [TIME],
+                },
+            ),
+        ),
+        settings: PipSettings {
+            index_locations: IndexLocations {
+                index: None,
+                extra_index: [],
+                flat_index: [],
+                no_index: false,
+                mirrors: IndexMirrors(
+                    [],
+                ),
+            },
+            python: None,
+            system: false,
+            extras: None,
+            break_system_packages: false,
+            target: None,
+            prefix: None,
+            index_strategy: FirstIndex,
+            keyring_provider: Disabled,
+            no_build_isolation: false,
+            no_build_isolation_package: [],
+            build_options: BuildOptions {
+                no_binary: None,
+                no_build: None,
+                prefer_source: None,
+            },
+            allow_empty_requirements: false,
+            strict: false,
+            strict_extras: false,
+            dependency_mode: Transitive,
+            resolution: Highest,
+            prerelease: IfNecessaryOrExplicit,
+            output_file: None,
+            no_strip_extras: false,
+            no_strip_markers: false,
+            no_annotate: false,
+            no_header: false,
+            custom_compile_command: None,
+            generate_hashes: false,
+            setup_py: Pep517,
+            config_setting: ConfigSettings(
+                {},
+            ),
+            python_version: None,
+            python_platform: None,
+            macos_arch: Native,
+            universal: false,
+            exclude_newer: Some(
+                ExcludeNewer(
+                    2024-03-25T00:00:00Z,
+                ),
+            ),
+            no_emit_package: [],
+            emit_index_url: false,
+            emit_find_links: false,
+            emit_build_options: false,
+            emit_marker_expression: false,
+            emit_index_annotation: false,
+            emit_constraint_annotation: false,
+            annotation_style: Split,
+            link_mode: Clone,
+            compile_bytecode: false,
+            exclude_package_data: [],
+            sources: Enabled,
+            hash_checking: None,
+            index_hash_policy: IndexHashPolicies(
+                [],
+            ),
             upgrade: None,
             reinstall: None,
             concurrency: Concurrency {
@@ -0,0 +1,131 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::uv_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// `stats` should summarize the invocations recorded by previous commands.
+#[test]
+fn stats() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let stats_dir = context.temp_dir.child("stats");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("iniconfig")?;
+
+    // Recording is opt-in, so this invocation shouldn't be recorded.
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .env_remove("UV_NO_STATS")
+        .env("UV_STATS_DIR", stats_dir.path())
+        .assert()
+        .success();
+    stats_dir.assert(predicates::path::missing());
+
+    // Install a requirement, then reinstall it from the cache.
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .arg("--reinstall")
+        .env_remove("UV_NO_STATS")
+        .env("UV_STATS", "1")
+        .env("UV_STATS_DIR", stats_dir.path())
+        .assert()
+        .success();
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .arg("--reinstall")
+        .env_remove("UV_NO_STATS")
+        .env("UV_STATS", "1")
+        .env("UV_STATS_DIR", stats_dir.path())
+        .assert()
+        .success();
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(r"\d{4}-\d{2}-\d{2}", "[DATE]")])
+        .collect::<Vec<_>>();
+
+    let mut command = Command::new(get_bin());
+    command.arg("stats");
+    command.env("UV_STATS_DIR", stats_dir.path());
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(filters, command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    2 invocations since [DATE]
+    Resolutions: 2
+    Cache hit rate: 50.0% (1 of 2 distributions)
+
+    Average duration:
+      pip sync: [TIME] (2 invocations)
+
+    ----- stderr -----
+    warning: `uv stats` is experimental and may change without warning
+    "###);
+
+    // Clearing the statistics should remove the log.
+    let mut command = Command::new(get_bin());
+    command.arg("stats").arg("--clear").arg("--preview");
+    command.env("UV_STATS_DIR", stats_dir.path());
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Removed usage statistics at: stats/invocations.jsonl
+    "###);
+
+    let mut command = Command::new(get_bin());
+    command.arg("stats").arg("--preview");
+    command.env("UV_STATS_DIR", stats_dir.path());
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    No usage statistics recorded
+    "###);
+
+    // Once the log exceeds the maximum size, it should be rotated.
+    stats_dir
+        .child("invocations.jsonl")
+        .write_str(&"malformed\n".repeat(128 * 1024))?;
+    context
+        .pip_sync()
+        .arg("requirements.txt")
+        .env_remove("UV_NO_STATS")
+        .env("UV_STATS", "1")
+        .env("UV_STATS_DIR", stats_dir.path())
+        .assert()
+        .success();
+    stats_dir
+        .child("invocations.1.jsonl")
+        .assert(predicates::path::is_file());
+    assert_eq!(
+        fs_err::read_to_string(stats_dir.child("invocations.jsonl").path())?
+            .lines()
+            .count(),
+        1
+    );
+
+    Ok(())
+}
@@ -5,7 +5,7 @@ use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
 use insta::assert_snapshot;
 
-use common::{uv_snapshot, TestContext};
+use common::{uv_snapshot, venv_bin_path, TestContext};
 
 mod common;
 
@@ -457,7 +457,7 @@ fn sync_build_isolation() -> Result<()> {
         .collect::<Vec<_>>();
     uv_snapshot!(filters, context.sync().arg("--no-build-isolation"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -611,7 +611,7 @@ fn sync_build_isolation_package() -> Result<()> {
         .collect::<Vec<_>>();
     uv_snapshot!(filters, context.sync().arg("--no-build-isolation-package").arg("iniconfig"), @r###"
     success: false
-    exit_code: 2
+    exit_code: 6
     ----- stdout -----
 
     ----- stderr -----
@@ -679,6 +679,153 @@ fn sync_build_isolation_package() -> Result<()> {
     Ok(())
 }
 
+/// Sync with `--prefer-source`, which should build packages from their source distributions, even
+/// if a compatible wheel is locked.
+#[test]
+fn sync_prefer_source() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig==2.0.0"]
+        "#,
+    )?;
+
+    // The first installation should use the pre-built wheel.
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    assert!(!context
+        .cache_dir
+        .child("built-wheels-v3")
+        .child("pypi")
+        .child("iniconfig")
+        .exists());
+
+    // Installed packages are left as-is, even if source distributions are preferred.
+    uv_snapshot!(context.filters(), context.sync().arg("--prefer-source"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Audited 2 packages in [TIME]
+    "###);
+
+    // With `--reinstall-package`, the package should be built from source, rather than reusing
+    // the cached wheel.
+    uv_snapshot!(context.filters(), context.sync().arg("--prefer-source").arg("--reinstall-package").arg("iniconfig"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - iniconfig==2.0.0
+     + iniconfig==2.0.0
+    "###);
+
+    assert!(context
+        .cache_dir
+        .child("built-wheels-v3")
+        .child("pypi")
+        .child("iniconfig")
+        .exists());
+
+    Ok(())
+}
+
+/// Sync with `--no-prefer-source`, which should override `prefer-source` in the configuration and
+/// install the published wheel, rather than reusing the wheel that was built from source.
+#[test]
+fn sync_no_prefer_source() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig==2.0.0"]
+
+        [tool.uv]
+        prefer-source = true
+        "#,
+    )?;
+
+    // The package should be built from source, per the configuration.
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    assert!(!context
+        .cache_dir
+        .child("wheels-v1")
+        .child("pypi")
+        .child("iniconfig")
+        .exists());
+
+    // With `--no-prefer-source`, the published wheel should be downloaded, rather than reusing the
+    // wheel that was built from source.
+    uv_snapshot!(context.filters(), context.sync().arg("--no-prefer-source").arg("--reinstall-package").arg("iniconfig"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - iniconfig==2.0.0
+     + iniconfig==2.0.0
+    "###);
+
+    assert!(context
+        .cache_dir
+        .child("wheels-v1")
+        .child("pypi")
+        .child("iniconfig")
+        .exists());
+
+    Ok(())
+}
+
 /// Test that relative wheel paths are correctly preserved.
 #[test]
 fn sync_relative_wheel() -> Result<()> {
@@ -779,3 +926,975 @@ fn sync_relative_wheel() -> Result<()> {
 
     Ok(())
 }
+
+/// Install the `tool.uv.dev-tools` into isolated environments within the project environment.
+#[test]
+fn sync_dev_tools() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+
+        [tool.uv]
+        dev-tools = ["black==24.3.0"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    Installed 6 packages in [TIME]
+    "###);
+
+    // The tool should be installed into its own environment, and not the project environment.
+    let bin = venv_bin_path(&context.venv);
+    assert!(bin
+        .join(format!("black{}", std::env::consts::EXE_SUFFIX))
+        .exists());
+    context
+        .venv
+        .child("tools")
+        .child("black")
+        .assert(predicates::path::is_dir());
+    context.assert_command("import black").failure();
+
+    // The tool should be locked.
+    let lock = fs_err::read_to_string(context.temp_dir.join(".uv-dev-tools.lock"))?;
+    assert!(lock.contains(r#"requirement = "black==24.3.0""#), "{lock}");
+
+    // Changing the tool's requirement should invalidate the lockfile.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+
+        [tool.uv]
+        dev-tools = ["black==24.2.0"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync().arg("--locked"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Audited 2 packages in [TIME]
+    error: The lockfile at `.uv-dev-tools.lock` needs to be updated, but `--locked` was provided. To update the lockfile, run `uv sync`.
+    "###);
+
+    // With `--frozen`, the locked version should be installed as-is.
+    uv_snapshot!(context.filters(), context.sync().arg("--frozen"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Audited 2 packages in [TIME]
+    "###);
+
+    let lock = fs_err::read_to_string(context.temp_dir.join(".uv-dev-tools.lock"))?;
+    assert!(lock.contains(r#"requirement = "black==24.3.0""#), "{lock}");
+
+    // Removing the tool should remove its environment and executables.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Audited 2 packages in [TIME]
+    "###);
+
+    assert!(!bin
+        .join(format!("black{}", std::env::consts::EXE_SUFFIX))
+        .exists());
+    assert!(!context.venv.child("tools").child("black").exists());
+    assert!(!context.temp_dir.child(".uv-dev-tools.lock").exists());
+
+    Ok(())
+}
+
+/// Rebuild an editable whose entry points changed, even if its `pyproject.toml` wasn't modified
+/// after it was installed.
+#[test]
+fn sync_stale_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    // Add an entry point, but backdate the `pyproject.toml`, such that it appears older than the
+    // installed distribution.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [project.scripts]
+        hello = "project:main"
+        "#,
+    )?;
+    filetime::set_file_mtime(&pyproject_toml, filetime::FileTime::from_unix_time(0, 0))?;
+
+    // The editable should be rebuilt, since its entry points are out of date.
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - project==0.1.0 (from file://[TEMP_DIR]/)
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    // Once rebuilt, the editable should be left as-is.
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Audited 1 package in [TIME]
+    "###);
+
+    // Unless `--reinstall-editables` is provided.
+    uv_snapshot!(context.filters(), context.sync().arg("--reinstall-editables"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Uninstalled 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - project==0.1.0 (from file://[TEMP_DIR]/)
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    Ok(())
+}
+
+/// Sync a named environment, declared in `tool.uv.envs`, alongside the project environment.
+#[test]
+fn sync_named_environment() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [project.optional-dependencies]
+        docs = ["iniconfig"]
+
+        [tool.uv]
+        envs.docs = { extras = ["docs"] }
+        "#,
+    )?;
+
+    // Normalize the path separator of the named environment on Windows.
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([(r"\.venvs\\docs", ".venvs/docs")])
+        .collect();
+
+    // The named environment should include the extra.
+    uv_snapshot!(filters, context.sync().arg("--env").arg("docs"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Using Python 3.12.[X] interpreter at: [PYTHON-3.12]
+    Creating virtualenv at: .venvs/docs
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    // The project environment should be unaffected.
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Installed 1 package in [TIME]
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    // Requesting an undeclared environment should fail.
+    uv_snapshot!(context.filters(), context.sync().arg("--env").arg("gpu"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    error: No environment named `gpu` is defined in `tool.uv.envs`
+    "###);
+
+    Ok(())
+}
+
+/// Sync a dependency group that's composed of other groups via `include-group`.
+#[test]
+fn sync_group_include() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["typing-extensions"]
+
+        [dependency-groups]
+        foo = ["anyio"]
+        bar = ["iniconfig"]
+        ci = [{ include-group = "foo" }, { include-group = "bar" }]
+        "#,
+    )?;
+
+    // By default, the dependency groups should be locked, but not installed.
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 6 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+     + typing-extensions==4.10.0
+    "###);
+
+    // Syncing the composed group should install the dependencies of each included group.
+    uv_snapshot!(context.filters(), context.sync().arg("--group").arg("ci"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 6 packages in [TIME]
+    Prepared 4 packages in [TIME]
+    Installed 4 packages in [TIME]
+     + anyio==4.3.0
+     + idna==3.6
+     + iniconfig==2.0.0
+     + sniffio==1.3.1
+    "###);
+
+    // Requesting an undeclared group should fail.
+    uv_snapshot!(context.filters(), context.sync().arg("--group").arg("docs"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    error: Dependency group `docs` is not defined in the `dependency-groups` table of any workspace member
+    "###);
+
+    Ok(())
+}
+
+/// Reject interpreters that aren't among the implementations declared in
+/// `tool.uv.requires-implementation`.
+#[test]
+fn sync_requires_implementation() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.uv]
+        requires-implementation = { names = ["pypy"] }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Using Python 3.12.[X] interpreter at: [PYTHON-3.12]
+    error: The requested Python interpreter (cpython 3.12.[X]) is incompatible with the project's supported implementations: `pypy`. Use `--python` to request a compatible interpreter.
+    "###);
+
+    // A matching implementation (compared case-insensitively) should accept the existing
+    // environment.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.uv]
+        requires-implementation = { names = ["CPython"], free-threaded = false }
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    Ok(())
+}
+
+/// Generate wrappers for the file-based scripts declared in `tool.uv.scripts`.
+#[test]
+#[cfg(unix)]
+fn sync_scripts() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.uv]
+        scripts = { hello = "bin/hello.py" }
+        "#,
+    )?;
+
+    let script = context.temp_dir.child("bin").child("hello.py");
+    script.write_str("import sys\nprint(sys.prefix != sys.base_prefix)\n")?;
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    // The wrapper should execute the script with the environment's interpreter.
+    let wrapper = venv_bin_path(&context.venv).join("hello");
+    uv_snapshot!(context.filters(), std::process::Command::new(&wrapper), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    True
+
+    ----- stderr -----
+    "###);
+
+    // Changes to the script should take effect without re-syncing.
+    script.write_str("print('Goodbye')\n")?;
+    uv_snapshot!(context.filters(), std::process::Command::new(&wrapper), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Goodbye
+
+    ----- stderr -----
+    "###);
+
+    // Once the script is no longer declared, the wrapper should be removed.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+        "#,
+    )?;
+
+    context.sync().assert().success();
+    assert!(!wrapper.exists());
+
+    Ok(())
+}
+
+/// Mark the project environment as read-only with `--read-only-env`.
+#[test]
+#[cfg(unix)]
+fn sync_read_only_env() -> Result<()> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync().arg("--read-only-env").arg("--link-mode").arg("hardlink"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    let site_packages = context.site_packages();
+    assert!(fs_err::metadata(&site_packages)?.permissions().readonly());
+    assert!(fs_err::metadata(site_packages.join("iniconfig"))?
+        .permissions()
+        .readonly());
+
+    // Files hardlinked from the cache should be copied before being marked read-only, leaving the
+    // cache itself writable.
+    let init = site_packages.join("iniconfig").join("__init__.py");
+    assert!(fs_err::metadata(&init)?.permissions().readonly());
+    assert_eq!(fs_err::metadata(&init)?.nlink(), 1);
+    for entry in fs_err::read_dir(context.cache_dir.join("archive-v0"))? {
+        let cached = entry?.path().join("iniconfig").join("__init__.py");
+        if cached.exists() {
+            assert!(!fs_err::metadata(&cached)?.permissions().readonly());
+        }
+    }
+
+    // If an entry regains write access outside of uv, it should be marked read-only again.
+    fs_err::set_permissions(&init, std::fs::Permissions::from_mode(0o644))?;
+    uv_snapshot!(context.filters(), context.sync().arg("--read-only-env"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Audited 2 packages in [TIME]
+    warning: Found 1 writable entry in the read-only environment at `.venv`; marking it read-only again
+    "###);
+    assert!(fs_err::metadata(&init)?.permissions().readonly());
+
+    // uv should restore write access to modify the environment, and mark it read-only again.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig", "typing-extensions"]
+        "#,
+    )?;
+
+    context.sync().arg("--read-only-env").assert().success();
+    assert!(site_packages.join("typing_extensions.py").is_file());
+    assert!(fs_err::metadata(&site_packages)?.permissions().readonly());
+
+    // Syncing without `--read-only-env` should restore write access.
+    context.sync().assert().success();
+    assert!(!fs_err::metadata(&site_packages)?.permissions().readonly());
+    assert!(!fs_err::metadata(site_packages.join("iniconfig"))?
+        .permissions()
+        .readonly());
+
+    Ok(())
+}
+
+/// Generate editor configuration for the project environment with `tool.uv.editor-config`.
+#[test]
+fn sync_editor_config() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.uv]
+        editor-config = ["vscode", "pyright"]
+        "#,
+    )?;
+
+    // Any existing settings should be retained, along with their formatting and comments.
+    let settings_json = context.temp_dir.child(".vscode").child("settings.json");
+    settings_json.write_str(indoc::indoc! {r#"
+        {
+          // Format on save.
+          "editor.formatOnSave": true,
+          "editor.rulers": [100]
+        }
+    "#})?;
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    let env_json: serde_json::Value = serde_json::from_str(&fs_err::read_to_string(
+        context.temp_dir.join(".uv/env.json"),
+    )?)?;
+    assert!(env_json["environment"]
+        .as_str()
+        .is_some_and(|environment| environment.ends_with(".venv")));
+    assert!(env_json["python-version"]
+        .as_str()
+        .is_some_and(|version| version.starts_with("3.12")));
+    assert!(context.temp_dir.child(".uv").child(".gitignore").exists());
+
+    // Paths should be written relative to the project, since these files are commonly committed.
+    let settings = fs_err::read_to_string(&settings_json)?;
+    let python = if cfg!(windows) {
+        ".venv/Scripts/python.exe"
+    } else {
+        ".venv/bin/python"
+    };
+    assert_eq!(
+        settings,
+        indoc::formatdoc! {r#"
+            {{
+              // Format on save.
+              "editor.formatOnSave": true,
+              "editor.rulers": [100],
+              "python.defaultInterpreterPath": "${{workspaceFolder}}/{python}"
+            }}
+        "#}
+    );
+
+    let pyright = fs_err::read_to_string(context.temp_dir.join("pyrightconfig.json"))?;
+    assert_eq!(
+        pyright,
+        indoc::indoc! {r#"
+            {
+                "venvPath": ".",
+                "venv": ".venv"
+            }
+        "#}
+    );
+
+    // Re-running the sync shouldn't modify the configuration.
+    context.sync().assert().success();
+    assert_eq!(fs_err::read_to_string(&settings_json)?, settings);
+
+    // With `--no-editor-config`, the configuration should be left unchanged.
+    fs_err::remove_file(context.temp_dir.join("pyrightconfig.json"))?;
+    context.sync().arg("--no-editor-config").assert().success();
+    assert!(!context.temp_dir.child("pyrightconfig.json").exists());
+
+    Ok(())
+}
+
+/// Sync with a `required-version` that the running version of uv doesn't satisfy.
+#[test]
+fn sync_required_version() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.uv]
+        required-version = "<0.1.0"
+        "#,
+    )?;
+
+    let filters: Vec<_> = context
+        .filters()
+        .into_iter()
+        .chain([(r"running version `[^`]+`", "running version `[VERSION]`")])
+        .collect();
+
+    uv_snapshot!(filters, context.sync(), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Required uv version `<0.1.0` does not match the running version `[VERSION]`; update uv (e.g., with `uv self update`) or install a version that satisfies `<0.1.0`
+    "###);
+
+    // A satisfied requirement should be accepted.
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.uv]
+        required-version = ">=0.1.0"
+        "#,
+    )?;
+
+    context.sync().assert().success();
+
+    Ok(())
+}
+
+/// Refuse to generate wrappers that would shadow files in the environment.
+#[test]
+fn sync_scripts_conflict() -> Result<()> {
+    let context = TestContext::new("3.12").with_filtered_virtualenv_bin();
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(r"tool\.cmd", "tool")])
+        .collect::<Vec<_>>();
+
+    context
+        .temp_dir
+        .child("bin")
+        .child("hello.py")
+        .write_str("print('Hello')\n")?;
+
+    // Names reserved by the environment, like `pip`, should be rejected.
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.uv]
+        scripts = { pip = "bin/hello.py" }
+        "#,
+    )?;
+
+    uv_snapshot!(filters, context.sync(), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    error: Invalid script name `pip` in `tool.uv.scripts`: the name is reserved by the virtual environment
+    "###);
+
+    // An existing file that wasn't generated by uv should not be overwritten.
+    let existing =
+        venv_bin_path(&context.venv).join(if cfg!(windows) { "tool.cmd" } else { "tool" });
+    fs_err::write(&existing, "existing")?;
+
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [tool.uv]
+        scripts = { hello = "bin/hello.py", tool = "bin/hello.py" }
+        "#,
+    )?;
+
+    uv_snapshot!(filters, context.sync(), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 1 package in [TIME]
+    Audited 1 package in [TIME]
+    error: The script `tool` declared in `tool.uv.scripts` conflicts with an existing file: `.venv/[BIN]/tool`
+    "###);
+
+    assert_eq!(fs_err::read_to_string(&existing)?, "existing");
+
+    // Once the conflict is resolved, the wrappers should be generated on every platform.
+    fs_err::remove_file(&existing)?;
+    context.sync().assert().success();
+    assert!(existing.is_file());
+    assert!(fs_err::read_to_string(&existing)?.contains("generated by uv"));
+
+    Ok(())
+}
+
+/// Sync only the workspace members affected by a change, leaving the packages required by the
+/// remaining members as-is.
+#[test]
+fn sync_only_affected_by() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [tool.uv.workspace]
+        members = ["alpha", "beta"]
+        "#,
+    )?;
+
+    for (name, dependency) in [("alpha", "iniconfig"), ("beta", "typing-extensions")] {
+        let member = context.temp_dir.child(name);
+        member.child("pyproject.toml").write_str(&format!(
+            r#"
+            [project]
+            name = "{name}"
+            version = "0.1.0"
+            requires-python = ">=3.12"
+            dependencies = ["{dependency}"]
+            "#
+        ))?;
+        member
+            .child("src")
+            .child(name)
+            .child("__init__.py")
+            .touch()?;
+    }
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    Prepared 4 packages in [TIME]
+    Installed 4 packages in [TIME]
+     + alpha==0.1.0 (from file://[TEMP_DIR]/alpha)
+     + beta==0.1.0 (from file://[TEMP_DIR]/beta)
+     + iniconfig==2.0.0
+     + typing-extensions==4.10.0
+    "###);
+
+    // Syncing the members affected by a change to `alpha` shouldn't remove `beta`, or its
+    // dependencies.
+    uv_snapshot!(context.filters(), context.sync().arg("--only-affected-by").arg("alpha/src/alpha/__init__.py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    Audited 2 packages in [TIME]
+    "###);
+
+    // Nor should it update the dependencies of `beta`, even if they don't match the lockfile.
+    context
+        .pip_install()
+        .arg("typing-extensions==4.9.0")
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), context.sync().arg("--only-affected-by").arg("alpha/src/alpha/__init__.py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    Audited 2 packages in [TIME]
+    "###);
+
+    context.assert_command(
+        "import importlib.metadata; assert importlib.metadata.version('typing-extensions') == '4.9.0'",
+    ).success();
+
+    // Packages that aren't required by any member are still removed.
+    context
+        .pip_install()
+        .arg("anyio==4.3.0")
+        .arg("--no-deps")
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), context.sync().arg("--only-affected-by").arg("alpha/src/alpha/__init__.py"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    Uninstalled 1 package in [TIME]
+     - anyio==4.3.0
+    "###);
+
+    Ok(())
+}
+
+/// Exclude files from the project environment via `tool.uv.exclude-package-data`.
+#[test]
+fn sync_exclude_package_data() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+
+        [tool.uv]
+        exclude-package-data = ["**/py.typed"]
+        "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.sync(), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv sync` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + iniconfig==2.0.0
+     + project==0.1.0 (from file://[TEMP_DIR]/)
+    "###);
+
+    let site_packages = context.site_packages();
+    assert!(site_packages
+        .join("iniconfig")
+        .join("__init__.py")
+        .is_file());
+    assert!(!site_packages.join("iniconfig").join("py.typed").exists());
+
+    let record = fs_err::read_to_string(
+        site_packages
+            .join("iniconfig-2.0.0.dist-info")
+            .join("RECORD"),
+    )?;
+    assert!(!record.contains("py.typed"));
+
+    Ok(())
+}
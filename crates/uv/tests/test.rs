@@ -0,0 +1,148 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+/// Run the test command against each of the Python versions in `tool.uv.test-pythons`.
+#[test]
+fn test_pythons() -> Result<()> {
+    let context = TestContext::new_with_versions(&["3.12", "3.11"]);
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! { r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+        requires-python = ">=3.11, <4"
+        dependencies = []
+
+        [tool.uv]
+        test-pythons = ["3.11", "3.12"]
+        test-command = ["python", "-c", "import sys; sys.exit(sys.version_info < (3, 12))"]
+        "#
+    })?;
+
+    // The tests fail on Python 3.11, but pass on Python 3.12.
+    uv_snapshot!(context.filters(), context.test()
+        .arg("--preview")
+        .env_remove("VIRTUAL_ENV"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Testing with Python 3.11
+    Using Python 3.11.[X] interpreter at: [PYTHON-3.11]
+    Creating virtualenv at: .venv-cpython-3.11
+    Resolved 1 package in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + foo==1.0.0 (from file://[TEMP_DIR]/)
+    Testing with Python 3.12
+    Using Python 3.12.[X] interpreter at: [PYTHON-3.12]
+    Creating virtualenv at: .venv-cpython-3.12
+    Installed 1 package in [TIME]
+     + foo==1.0.0 (from file://[TEMP_DIR]/)
+
+    Summary:
+      Python 3.11: failed
+      Python 3.12: passed
+    "###);
+
+    // With `--fail-fast`, the remaining versions are skipped.
+    uv_snapshot!(context.filters(), context.test()
+        .arg("--preview")
+        .arg("--fail-fast")
+        .env_remove("VIRTUAL_ENV"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Testing with Python 3.11
+    Resolved 1 package in [TIME]
+    Audited 1 package in [TIME]
+
+    Summary:
+      Python 3.11: failed
+    "###);
+
+    // A command provided as arguments takes precedence over `tool.uv.test-command`.
+    uv_snapshot!(context.filters(), context.test()
+        .arg("--preview")
+        .arg("--python")
+        .arg("3.11")
+        .arg("python")
+        .arg("-c")
+        .arg("pass")
+        .env_remove("VIRTUAL_ENV"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Testing with Python 3.11
+    Resolved 1 package in [TIME]
+    Audited 1 package in [TIME]
+
+    Summary:
+      Python 3.11: passed
+    "###);
+
+    // The exit code of the test command is propagated.
+    uv_snapshot!(context.filters(), context.test()
+        .arg("--preview")
+        .arg("--python")
+        .arg("3.11")
+        .arg("python")
+        .arg("-c")
+        .arg("import sys; sys.exit(42)")
+        .env_remove("VIRTUAL_ENV"), @r###"
+    success: false
+    exit_code: 42
+    ----- stdout -----
+
+    ----- stderr -----
+    Testing with Python 3.11
+    Resolved 1 package in [TIME]
+    Audited 1 package in [TIME]
+
+    Summary:
+      Python 3.11: failed
+    "###);
+
+    Ok(())
+}
+
+/// Without a test command, `uv test` is an error.
+#[test]
+fn test_missing_command() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! { r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+        requires-python = ">=3.12"
+        dependencies = []
+        "#
+    })?;
+
+    uv_snapshot!(context.filters(), context.test(), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv test` is experimental and may change without warning
+    error: `uv test` requires a command, either provided as arguments or declared in `tool.uv.test-command`
+    "###);
+
+    Ok(())
+}
@@ -3,13 +3,14 @@
 use std::process::Command;
 
 use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
 use assert_fs::{
     assert::PathAssert,
     fixture::{FileTouch, FileWriteStr, PathChild},
 };
 use indoc::indoc;
 use insta::assert_snapshot;
-use predicates::prelude::predicate;
+use predicates::prelude::{predicate, PredicateBooleanExt};
 
 use common::{uv_snapshot, TestContext};
 
@@ -81,6 +82,8 @@ fn tool_install() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -164,6 +167,8 @@ fn tool_install() {
         filters => context.filters(),
     }, {
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("flask").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "flask" }]
         entrypoints = [
@@ -304,6 +309,8 @@ fn tool_install_version() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black", specifier = "==24.2.0" }]
         entrypoints = [
@@ -387,6 +394,8 @@ fn tool_install_editable() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black", editable = "[WORKSPACE]/scripts/packages/black_editable" }]
         entrypoints = [
@@ -427,6 +436,8 @@ fn tool_install_editable() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -471,6 +482,8 @@ fn tool_install_editable() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black", specifier = "==24.2.0" }]
         entrypoints = [
@@ -520,6 +533,8 @@ fn tool_install_remove_on_empty() -> Result<()> {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -612,6 +627,8 @@ fn tool_install_remove_on_empty() -> Result<()> {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -689,6 +706,8 @@ fn tool_install_editable_from() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black", editable = "[WORKSPACE]/scripts/packages/black_editable" }]
         entrypoints = [
@@ -710,6 +729,33 @@ fn tool_install_editable_from() {
     "###);
 }
 
+/// Editable Git requirements can't be installed as tools, since they'd need a persistent checkout.
+#[test]
+fn tool_install_editable_git() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("-e")
+        .arg("uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@0.0.1")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    error: Editable Git requirements are only supported by `uv pip install` and `uv pip sync` (found: `uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@0.0.1`)
+    "###);
+
+    tool_dir
+        .child("uv-public-pypackage")
+        .assert(predicate::path::missing());
+}
+
 /// Test installing a tool with `uv tool install --from`
 #[test]
 fn tool_install_from() {
@@ -843,6 +889,8 @@ fn tool_install_already_installed() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -880,6 +928,8 @@ fn tool_install_already_installed() {
     }, {
         // We should not have an additional tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -1191,6 +1241,8 @@ fn tool_install_entry_point_exists() {
     }, {
         // We write a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -1227,6 +1279,8 @@ fn tool_install_entry_point_exists() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -1460,6 +1514,8 @@ fn tool_install_unnamed_package() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black", url = "https://files.pythonhosted.org/packages/0f/89/294c9a6b6c75a08da55e9d05321d0707e9418735e3062b12ef0f54c33474/black-24.4.2-py3-none-any.whl" }]
         entrypoints = [
@@ -1575,6 +1631,8 @@ fn tool_install_unnamed_from() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black", url = "https://files.pythonhosted.org/packages/0f/89/294c9a6b6c75a08da55e9d05321d0707e9418735e3062b12ef0f54c33474/black-24.4.2-py3-none-any.whl" }]
         entrypoints = [
@@ -1665,6 +1723,8 @@ fn tool_install_unnamed_with() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [
             { name = "black" },
@@ -1735,6 +1795,8 @@ fn tool_install_requirements_txt() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [
             { name = "black" },
@@ -1781,6 +1843,8 @@ fn tool_install_requirements_txt() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [
             { name = "black" },
@@ -1846,6 +1910,8 @@ fn tool_install_requirements_txt_arguments() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [
             { name = "black" },
@@ -1966,6 +2032,8 @@ fn tool_install_upgrade() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black", specifier = "==24.1.1" }]
         entrypoints = [
@@ -1999,6 +2067,8 @@ fn tool_install_upgrade() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -2037,6 +2107,8 @@ fn tool_install_upgrade() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [
             { name = "black" },
@@ -2081,6 +2153,8 @@ fn tool_install_upgrade() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black" }]
         entrypoints = [
@@ -2216,7 +2290,7 @@ fn tool_install_preserve_environment() {
         .env("XDG_BIN_HOME", bin_dir.as_os_str())
         .env("PATH", bin_dir.as_os_str()), @r###"
     success: false
-    exit_code: 2
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -2241,6 +2315,166 @@ fn tool_install_preserve_environment() {
     "###);
 }
 
+/// Test that modifying an installed tool stages the new environment and moves it into place,
+/// such that any newly installed scripts refer to the final location of the environment.
+#[test]
+fn tool_install_staged_environment() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black==24.1.1")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.1.1
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black, blackd
+    "###);
+
+    // Install `black` with an additional requirement, which updates the existing environment.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black==24.1.1")
+        .arg("--with")
+        .arg("flask")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + blinker==1.7.0
+     + flask==3.0.2
+     + itsdangerous==2.1.2
+     + jinja2==3.1.3
+     + markupsafe==2.1.5
+     + werkzeug==3.0.1
+    Installed 2 executables: black, blackd
+    "###);
+
+    // The staging directory should not be left behind.
+    let entries = fs_err::read_dir(&tool_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name != ".gitignore" && name != ".lock")
+        .collect::<Vec<_>>();
+    assert_eq!(entries, vec!["black".to_string()]);
+
+    // On Windows, we can't snapshot an executable file.
+    #[cfg(not(windows))]
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        // The newly installed script should refer to the tool environment, not the staging directory.
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("bin").join("flask")).unwrap(), @r###"
+        #![TEMP_DIR]/tools/black/bin/python
+        # -*- coding: utf-8 -*-
+        import re
+        import sys
+        from flask.cli import main
+        if __name__ == "__main__":
+            sys.argv[0] = re.sub(r"(-script\.pyw|\.exe)?$", "", sys.argv[0])
+            sys.exit(main())
+        "###);
+    });
+}
+
+/// Test that a failed modification of an installed tool restores the existing environment.
+#[test]
+fn tool_install_rollback() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`.
+    context
+        .tool_install()
+        .arg("black==24.1.1")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Add a requirement, but request an executable that `black` doesn't provide, which fails after
+    // the requirement is installed into the environment.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black==24.1.1")
+        .arg("--with")
+        .arg("iniconfig")
+        .arg("--entrypoint")
+        .arg("missing")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + iniconfig==2.0.0
+    error: Executable `missing` is not provided by `black` (use `--include-deps` to include executables from its dependencies)
+    "###);
+
+    // The existing environment should be restored, without the new requirement.
+    #[cfg(not(windows))]
+    assert!(!tool_dir
+        .join("black")
+        .join("lib")
+        .join("python3.12")
+        .join("site-packages")
+        .join("iniconfig")
+        .exists());
+
+    // The staging directory should not be left behind.
+    let entries = fs_err::read_dir(&tool_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .filter(|name| name != ".gitignore" && name != ".lock")
+        .collect::<Vec<_>>();
+    assert_eq!(entries, vec!["black".to_string()]);
+
+    // The tool should still be installed and functional.
+    uv_snapshot!(context.filters(), Command::new("black").arg("--version").env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black, 24.1.1 (compiled: yes)
+    Python (CPython) 3.12.[X]
+
+    ----- stderr -----
+    "###);
+}
+
 /// Test warning when the binary directory is not on the user's PATH.
 #[test]
 #[cfg(unix)]
@@ -2348,6 +2582,98 @@ fn tool_install_bad_receipt() -> Result<()> {
     Ok(())
 }
 
+/// Test reinstalling a tool with a receipt written by a newer version of uv.
+#[test]
+fn tool_install_newer_receipt() -> Result<()> {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black, blackd
+    "###);
+
+    // Override the `uv-receipt.toml` file with a receipt that uses an unsupported schema.
+    tool_dir.child("black").child("uv-receipt.toml").write_str(
+        r#"
+        schema-version = 99
+
+        [tool]
+        requirements = { black = "*" }
+        entrypoints = []
+        "#,
+    )?;
+
+    // Reinstall `black`, which should leave the environment in place.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    error: `black` was installed by a newer version of uv; upgrade uv, or use `--force` to reinstall it
+    "###);
+
+    tool_dir
+        .child("black")
+        .child("uv-receipt.toml")
+        .assert(predicate::str::contains("schema-version = 99"));
+
+    // Reinstall `black` with `--force`, which should replace the environment.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--force")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    warning: Removed existing `black` with invalid receipt
+    Resolved [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black, blackd
+    "###);
+
+    Ok(())
+}
+
 /// Test installing a tool with a malformed `.dist-info` directory (i.e., a `.dist-info` directory
 /// that isn't properly normalized).
 #[test]
@@ -2410,6 +2736,8 @@ fn tool_install_malformed_dist_info() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("babel").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "babel" }]
         entrypoints = [
@@ -2489,6 +2817,8 @@ fn tool_install_settings() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("flask").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "flask", specifier = ">=3" }]
         entrypoints = [
@@ -2523,6 +2853,8 @@ fn tool_install_settings() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("flask").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "flask", specifier = ">=3" }]
         entrypoints = [
@@ -2564,6 +2896,8 @@ fn tool_install_settings() {
     }, {
         // We should have a tool receipt
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("flask").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "flask", specifier = ">=3" }]
         entrypoints = [
@@ -2576,3 +2910,838 @@ fn tool_install_settings() {
         "###);
     });
 }
+
+/// Test reinstalling a tool from its lockfile with `--locked`.
+#[test]
+fn tool_install_locked() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Without an existing installation, `--locked` should fail.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("flask>=3")
+        .arg("--locked")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    error: The requirements for `flask` do not match the lockfile; run without `--locked` to update it
+    "###);
+
+    // Install `flask`, which should write a lockfile alongside the receipt.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("flask>=3")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + blinker==1.7.0
+     + click==8.1.7
+     + flask==3.0.2
+     + itsdangerous==2.1.2
+     + jinja2==3.1.3
+     + markupsafe==2.1.5
+     + werkzeug==3.0.1
+    Installed 1 executable: flask
+    "###);
+
+    tool_dir
+        .child("flask")
+        .child("uv.lock")
+        .assert(predicate::path::exists());
+
+    // Reinstall from the lockfile, without resolving.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("flask>=3")
+        .arg("--locked")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Audited [N] packages in [TIME]
+    Installed 1 executable: flask
+    "###);
+
+    // If the requirements change, `--locked` should fail.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("flask>=3.0.1")
+        .arg("--locked")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    error: The requirements for `flask` do not match the lockfile; run without `--locked` to update it
+    "###);
+
+    // If the lockfile is missing, it should be recreated, even if the environment is already
+    // satisfied.
+    fs_err::remove_file(tool_dir.child("flask").child("uv.lock")).unwrap();
+
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("flask>=3")
+        .arg("--force")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Audited [N] packages in [TIME]
+    Installed 1 executable: flask
+    "###);
+
+    tool_dir
+        .child("flask")
+        .child("uv.lock")
+        .assert(predicate::path::exists());
+}
+
+/// Test reinstalling a tool from a local directory with `--locked`, from a different working
+/// directory than the one in which it was installed.
+#[test]
+fn tool_install_locked_path() -> Result<()> {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    let project = context.temp_dir.child("foo");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "foo"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = []
+
+        [project.scripts]
+        foo = "foo:main"
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+
+        [tool.setuptools]
+        py-modules = ["foo"]
+    "#})?;
+    project
+        .child("foo.py")
+        .write_str("def main():\n    print('foo')\n")?;
+
+    // Install `foo` via a relative path.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("./foo")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + foo==0.1.0 (from file://[TEMP_DIR]/foo)
+    Installed 1 executable: foo
+    "###);
+
+    // The lockfile should be independent of the working directory.
+    let subdir = context.temp_dir.child("subdir");
+    fs_err::create_dir_all(&subdir)?;
+
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg(project.path())
+        .arg("--locked")
+        .current_dir(&subdir)
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Audited [N] packages in [TIME]
+    Installed 1 executable: foo
+    "###);
+
+    Ok(())
+}
+
+/// Test installing multiple versions of a tool side-by-side with `--suffix`.
+#[test]
+fn tool_install_suffix() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`.
+    context
+        .tool_install()
+        .arg("black==24.2.0")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Install an older version of `black` under a suffixed name.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black==23.1.0")
+        .arg("--suffix")
+        .arg("@23")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==23.1.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black@23, blackd@23
+    "###);
+
+    tool_dir
+        .child("black@23")
+        .child("uv-receipt.toml")
+        .assert(predicate::path::exists());
+    bin_dir
+        .child(format!("black@23{}", std::env::consts::EXE_SUFFIX))
+        .assert(predicate::path::exists());
+    bin_dir
+        .child(format!("black{}", std::env::consts::EXE_SUFFIX))
+        .assert(predicate::path::exists());
+
+    // Both installations should be listed.
+    uv_snapshot!(context.filters(), context.tool_list()
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black v24.2.0
+    - black
+    - blackd
+    black@23 v23.1.0
+    - black@23
+    - blackd@23
+
+    ----- stderr -----
+    warning: `uv tool list` is experimental and may change without warning
+    "###);
+
+    // The suffixed installation should be upgraded independently, retaining its suffix.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black@23")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Audited [N] packages in [TIME]
+    Updated 2 executables: black@23, blackd@23
+    "###);
+
+    // Uninstalling the suffixed installation should leave the other intact.
+    uv_snapshot!(context.filters(), context.tool_uninstall()
+        .arg("black@23")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool uninstall` is experimental and may change without warning
+    Uninstalled 2 executables: black@23, blackd@23
+    "###);
+
+    bin_dir
+        .child(format!("black{}", std::env::consts::EXE_SUFFIX))
+        .assert(predicate::path::exists());
+
+    // An invalid suffix should be rejected.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black==23.1.0")
+        .arg("--suffix=-23")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    error: Tool suffixes must start with `@`, followed by letters, digits, `.`, `-`, or `_`; found: `-23`
+    "###);
+}
+
+/// Test installing a tool with constraints and overrides, which should be recorded in the receipt
+/// and respected on upgrade.
+#[test]
+fn tool_install_constraints_overrides() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("click==8.1.3").unwrap();
+
+    let overrides_txt = context.temp_dir.child("overrides.txt");
+    overrides_txt.write_str("packaging==23.2").unwrap();
+
+    // Install `black`.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--constraint")
+        .arg("constraints.txt")
+        .arg("--override")
+        .arg("overrides.txt")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.3
+     + mypy-extensions==1.0.0
+     + packaging==23.2
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black, blackd
+    "###);
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        // The constraints and overrides should be recorded in the receipt.
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [{ name = "black" }]
+        constraints = [{ name = "click", specifier = "==8.1.3" }]
+        overrides = [{ name = "packaging", specifier = "==23.2" }]
+        entrypoints = [
+            { name = "black", install-path = "[TEMP_DIR]/bin/black" },
+            { name = "blackd", install-path = "[TEMP_DIR]/bin/blackd" },
+        ]
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+
+    // Upgrade `black`. The constraints and overrides should be respected, even though newer
+    // versions of `click` and `packaging` are available.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Audited [N] packages in [TIME]
+    Updated 2 executables: black, blackd
+    "###);
+}
+
+/// Test installing a subset of the executables provided by a tool, which should be recorded in the
+/// receipt and respected on upgrade.
+#[test]
+fn tool_install_entrypoints() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`, but only link the `blackd` executable.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--entrypoint")
+        .arg("blackd")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 1 executable: blackd
+    "###);
+
+    bin_dir
+        .child(format!("black{}", std::env::consts::EXE_SUFFIX))
+        .assert(predicate::path::missing());
+    bin_dir
+        .child(format!("blackd{}", std::env::consts::EXE_SUFFIX))
+        .assert(predicate::path::exists());
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        // The selection should be recorded in the receipt.
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [{ name = "black" }]
+        entrypoints = [
+            { name = "blackd", install-path = "[TEMP_DIR]/bin/blackd" },
+        ]
+
+        [tool.selection]
+        include = ["blackd"]
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+
+    // Upgrading `black` should reinstall the same subset of executables.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Audited [N] packages in [TIME]
+    Updated 1 executable: blackd
+    "###);
+
+    // Excluding an executable instead should replace the selection.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--exclude-entrypoint")
+        .arg("blackd")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Audited [N] packages in [TIME]
+    Installed 1 executable: black
+    "###);
+
+    bin_dir
+        .child(format!("black{}", std::env::consts::EXE_SUFFIX))
+        .assert(predicate::path::exists());
+    bin_dir
+        .child(format!("blackd{}", std::env::consts::EXE_SUFFIX))
+        .assert(predicate::path::missing());
+
+    // Requesting an executable that isn't provided by the tool should fail.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--entrypoint")
+        .arg("flake8")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Audited [N] packages in [TIME]
+    error: Executable `flake8` is not provided by `black` (use `--include-deps` to include executables from its dependencies)
+    "###);
+
+    // The existing installation should be left intact.
+    tool_dir
+        .child("black")
+        .child("uv-receipt.toml")
+        .assert(predicate::path::exists());
+    bin_dir
+        .child(format!("black{}", std::env::consts::EXE_SUFFIX))
+        .assert(predicate::path::exists());
+    uv_snapshot!(context.filters(), Command::new("black").arg("--version").env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black, 24.3.0 (compiled: yes)
+    Python (CPython) 3.12.[X]
+
+    ----- stderr -----
+    "###);
+}
+
+/// Test installing a tool with environment variables, which should be set by launchers for each
+/// executable, recorded in the receipt, and respected on upgrade.
+#[test]
+#[cfg(unix)]
+fn tool_install_env() {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--entrypoint")
+        .arg("black")
+        .arg("--env")
+        .arg("BLACK_NUM_WORKERS=1")
+        .arg("--env")
+        .arg("GREETING=it's a test")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 1 executable: black
+    "###);
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        // The executable should be a launcher that sets the variables.
+        assert_snapshot!(fs_err::read_to_string(bin_dir.join("black")).unwrap(), @r###"
+        #!/bin/sh
+        export BLACK_NUM_WORKERS='1'
+        export GREETING='it'\''s a test'
+        exec '[TEMP_DIR]/tools/black/bin/black' "$@"
+        "###);
+
+        // The variables should be recorded in the receipt.
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [{ name = "black" }]
+        entrypoints = [
+            { name = "black", install-path = "[TEMP_DIR]/bin/black" },
+        ]
+
+        [tool.selection]
+        include = ["black"]
+
+        [tool.env]
+        BLACK_NUM_WORKERS = "1"
+        GREETING = "it's a test"
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+
+    uv_snapshot!(context.filters(), Command::new("black").arg("--version").env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black, 24.3.0 (compiled: yes)
+    Python (CPython) 3.12.[X]
+
+    ----- stderr -----
+    "###);
+
+    // Upgrading `black` should reinstall the launcher.
+    context
+        .tool_upgrade()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    assert!(fs_err::read_to_string(bin_dir.join("black"))
+        .unwrap()
+        .contains("export BLACK_NUM_WORKERS='1'"));
+
+    // An invalid variable name should be rejected.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--env")
+        .arg("1FOO=bar")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value '1FOO=bar' for '--env <KEY=VALUE>': invalid environment variable name: `1FOO`
+
+    For more information, try '--help'.
+    "###);
+}
+
+/// Test installing a workspace member as a tool, from the workspace's lockfile.
+#[test]
+fn tool_install_from_lock() -> Result<()> {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Create a workspace with a member that provides an executable.
+    context
+        .temp_dir
+        .child("pyproject.toml")
+        .write_str(indoc! { r#"
+        [project]
+        name = "root"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["hello"]
+
+        [tool.uv.sources]
+        hello = { workspace = true }
+
+        [tool.uv.workspace]
+        members = ["packages/*"]
+        "#
+        })?;
+
+    let hello = context.temp_dir.child("packages").child("hello");
+    hello.child("pyproject.toml").write_str(indoc! { r#"
+        [project]
+        name = "hello"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["anyio==3.7.0"]
+
+        [project.scripts]
+        hello = "hello:main"
+
+        [build-system]
+        requires = ["hatchling"]
+        build-backend = "hatchling.build"
+        "#
+    })?;
+    hello
+        .child("src")
+        .child("hello")
+        .child("__init__.py")
+        .write_str("def main():\n    print('Hello!')\n")?;
+
+    context.lock().assert().success();
+
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("hello")
+        .arg("--from-lock")
+        .arg("uv.lock")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + anyio==3.7.0
+     + hello==0.1.0 (from file://[TEMP_DIR]/packages/hello)
+     + idna==3.6
+     + sniffio==1.3.1
+    Installed 1 executable: hello
+    "###);
+
+    // Only workspace members can be installed from the lockfile.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("anyio")
+        .arg("--from-lock")
+        .arg("uv.lock")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    error: `anyio` is not a member of the workspace at: `[TEMP_DIR]/`
+    "###);
+
+    Ok(())
+}
+
+/// Test installing a tool with extras provided via `--from`, which should be persisted in the
+/// receipt.
+#[test]
+fn tool_install_from_extras() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--from")
+        .arg("black[colorama]")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved 7 packages in [TIME]
+    Prepared 7 packages in [TIME]
+    Installed 7 packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + colorama==0.4.6
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black, blackd
+    "###);
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [{ name = "black", extras = ["colorama"] }]
+        entrypoints = [
+            { name = "black", install-path = "[TEMP_DIR]/bin/black" },
+            { name = "blackd", install-path = "[TEMP_DIR]/bin/blackd" },
+        ]
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+
+    // Requesting the tool without the extra should not be considered a no-op.
+    context
+        .tool_install()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("is already installed").not());
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [{ name = "black" }]
+        entrypoints = [
+            { name = "black", install-path = "[TEMP_DIR]/bin/black" },
+            { name = "blackd", install-path = "[TEMP_DIR]/bin/blackd" },
+        ]
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+}
@@ -69,6 +69,84 @@ fn tool_list_paths() {
     "###);
 }
 
+#[test]
+fn tool_list_format() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`
+    context
+        .tool_install()
+        .arg("black==24.2.0")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    let filters = context
+        .filters()
+        .into_iter()
+        .chain([(r"3\.12\.\d+", "3.12.[X]")])
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, context.tool_list().arg("--format").arg("json")
+    .env("UV_TOOL_DIR", tool_dir.as_os_str())
+    .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [{"name":"black","version":"24.2.0","requirements":["black==24.2.0"],"python":{"version":"3.12.[X]","executable":"[TEMP_DIR]/tools/black/bin/python"},"path":"[TEMP_DIR]/tools/black","executables":[{"name":"black","path":"[TEMP_DIR]/bin/black"},{"name":"blackd","path":"[TEMP_DIR]/bin/blackd"}]}]
+
+    ----- stderr -----
+    warning: `uv tool list` is experimental and may change without warning
+    "###);
+
+    uv_snapshot!(filters, context.tool_list().arg("--format").arg("toml")
+    .env("UV_TOOL_DIR", tool_dir.as_os_str())
+    .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [[tool]]
+    name = "black"
+    version = "24.2.0"
+    requirements = ["black==24.2.0"]
+    path = "[TEMP_DIR]/tools/black"
+
+    [tool.python]
+    version = "3.12.[X]"
+    executable = "[TEMP_DIR]/tools/black/bin/python"
+
+    [[tool.executables]]
+    name = "black"
+    path = "[TEMP_DIR]/bin/black"
+
+    [[tool.executables]]
+    name = "blackd"
+    path = "[TEMP_DIR]/bin/blackd"
+
+    ----- stderr -----
+    warning: `uv tool list` is experimental and may change without warning
+    "###);
+
+    uv_snapshot!(context.filters(), context.tool_list().arg("--format").arg("shell")
+    .env("UV_TOOL_DIR", tool_dir.as_os_str())
+    .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    uv_tools='black'
+    uv_tool_black_version='24.2.0'
+    uv_tool_black_path='[TEMP_DIR]/tools/black'
+    uv_tool_black_bin_black='[TEMP_DIR]/bin/black'
+    uv_tool_black_bin_blackd='[TEMP_DIR]/bin/blackd'
+
+    ----- stderr -----
+    warning: `uv tool list` is experimental and may change without warning
+    "###);
+}
+
 #[test]
 fn tool_list_empty() {
     let context = TestContext::new("3.12").with_filtered_exe_suffix();
@@ -191,6 +269,8 @@ fn tool_list_deprecated() -> Result<()> {
         filters => context.filters(),
     }, {
         assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
         [tool]
         requirements = [{ name = "black", specifier = "==24.2.0" }]
         entrypoints = [
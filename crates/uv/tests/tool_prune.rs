@@ -0,0 +1,79 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::PathChild;
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+#[test]
+fn tool_prune() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black` and `flask`.
+    context
+        .tool_install()
+        .arg("black==24.2.0")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+    context
+        .tool_install()
+        .arg("flask==3.0.2")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // With every executable in place, there's nothing to prune.
+    uv_snapshot!(context.filters(), context.tool_prune()
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool prune` is experimental and may change without warning
+    Nothing to prune
+    "###);
+
+    // Remove all of the executables for `black`, as if the executable directory had been cleaned
+    // by hand.
+    fs_err::remove_file(bin_dir.child(format!("black{}", std::env::consts::EXE_SUFFIX))).unwrap();
+    fs_err::remove_file(bin_dir.child(format!("blackd{}", std::env::consts::EXE_SUFFIX))).unwrap();
+
+    uv_snapshot!(context.filters(), context.tool_prune().arg("--dry-run")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool prune` is experimental and may change without warning
+    Would remove `black` (the executables are missing)
+    "###);
+
+    // A dry run leaves the environment in place.
+    assert!(tool_dir.child("black").exists());
+
+    uv_snapshot!(context.filters(), context.tool_prune()
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool prune` is experimental and may change without warning
+    Removed `black` (the executables are missing)
+    "###);
+
+    assert!(!tool_dir.child("black").exists());
+    assert!(tool_dir.child("flask").exists());
+}
@@ -0,0 +1,156 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::PathChild;
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+#[test]
+fn tool_repair() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`.
+    context
+        .tool_install()
+        .arg("black==24.2.0")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // An intact environment doesn't need to be repaired.
+    uv_snapshot!(context.filters(), context.tool_repair().arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool repair` is experimental and may change without warning
+    `black` does not need to be repaired
+    "###);
+
+    // Break the environment by removing its interpreter, as if the base Python had been removed.
+    let python = if cfg!(windows) {
+        tool_dir.child("black").child("Scripts").child("python.exe")
+    } else {
+        tool_dir.child("black").child("bin").child("python")
+    };
+    fs_err::remove_file(&python).unwrap();
+
+    uv_snapshot!(context.filters(), context.tool_repair().arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool repair` is experimental and may change without warning
+    Repairing `black` (the Python interpreter is missing)
+    Resolved 6 packages in [TIME]
+    Installed 6 packages in [TIME]
+     + black==24.2.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black, blackd
+    "###);
+
+    // The executables should work again.
+    context
+        .command()
+        .arg("tool")
+        .arg("run")
+        .arg("black")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Once repaired, there's nothing left to repair.
+    uv_snapshot!(context.filters(), context.tool_repair().arg("--all")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool repair` is experimental and may change without warning
+    Nothing to repair
+    "###);
+}
+
+#[test]
+fn tool_repair_stale_executables() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`.
+    context
+        .tool_install()
+        .arg("black==24.2.0")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Remove one of the executables.
+    fs_err::remove_file(bin_dir.child(format!("black{}", std::env::consts::EXE_SUFFIX))).unwrap();
+
+    uv_snapshot!(context.filters(), context.tool_repair().arg("--all")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool repair` is experimental and may change without warning
+    Repairing `black` (the executables are stale)
+    Resolved 6 packages in [TIME]
+    Installed 6 packages in [TIME]
+     + black==24.2.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black, blackd
+    "###);
+
+    assert!(bin_dir
+        .child(format!("black{}", std::env::consts::EXE_SUFFIX))
+        .exists());
+}
+
+#[test]
+fn tool_repair_not_installed() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    uv_snapshot!(context.filters(), context.tool_repair().arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool repair` is experimental and may change without warning
+    error: `black` is not installed
+    "###);
+}
@@ -196,6 +196,57 @@ fn tool_run_from_version() {
     "###);
 }
 
+/// When offline, fall back to the newest version of the tool that's available in the cache.
+#[test]
+fn tool_run_offline_fallback() {
+    let context = TestContext::new("3.12");
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Populate the cache with an older version of `pytest`.
+    context
+        .tool_run()
+        .arg("--from")
+        .arg("pytest==8.0.0")
+        .arg("pytest")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // The newest version (`8.1.1`) was never downloaded, so the cached version should be used.
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("--offline")
+        .arg("pytest")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    pytest 8.0.0
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    warning: Offline, using cached `pytest==8.0.0`
+    Resolved 4 packages in [TIME]
+    "###);
+
+    // A requirement that isn't satisfied by the cache should still fail.
+    context
+        .tool_run()
+        .arg("--offline")
+        .arg("--from")
+        .arg("pytest>8.0.0")
+        .arg("pytest")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .failure();
+}
+
 #[test]
 fn tool_run_suggest_valid_commands() {
     let context = TestContext::new("3.12").with_filtered_exe_suffix();
@@ -696,6 +747,36 @@ fn tool_run_url() {
     "###);
 }
 
+/// A hash embedded in the fragment of a direct URL is verified.
+#[test]
+fn tool_run_url_fragment_hash() {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("--from")
+        .arg("anyio @ https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl#sha256=0000000000000000000000000000000000000000000000000000000000000000")
+        .arg("anyio")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    error: Failed to download `anyio @ https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl#sha256=0000000000000000000000000000000000000000000000000000000000000000`
+      Caused by: Hash mismatch for `anyio @ https://files.pythonhosted.org/packages/14/fd/2f20c40b45e4fb4324834aea24bd4afdf1143390242c0b33774da0e2e34f/anyio-4.3.0-py3-none-any.whl#sha256=0000000000000000000000000000000000000000000000000000000000000000`
+
+    Expected:
+      sha256:0000000000000000000000000000000000000000000000000000000000000000
+
+    Computed:
+      sha256:048e05d0f6caeed70d731f3db756d35dcc1f35747c8c403364a8332c630441b8
+    "###);
+}
+
 /// Read requirements from a `requirements.txt` file.
 #[test]
 fn tool_run_requirements_txt() {
@@ -915,7 +996,7 @@ fn tool_run_resolution_error() {
         .env("UV_TOOL_DIR", tool_dir.as_os_str())
         .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
     success: false
-    exit_code: 1
+    exit_code: 3
     ----- stdout -----
 
     ----- stderr -----
@@ -924,3 +1005,361 @@ fn tool_run_resolution_error() {
       ╰─▶ Because there are no versions of add and you require add, we can conclude that the requirements are unsatisfiable.
     "###);
 }
+
+#[test]
+fn tool_run_extras() {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("black")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black, 24.3.0 (compiled: yes)
+    Python (CPython) 3.12.[X]
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    "###);
+
+    // The extras should be stripped from the command, and the environment without the extra
+    // should not be reused.
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("black[colorama]")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black, 24.3.0 (compiled: yes)
+    Python (CPython) 3.12.[X]
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + colorama==0.4.6
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    "###);
+
+    // Extras can be combined with a version request.
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("black[colorama]@24.3.0")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black, 24.3.0 (compiled: yes)
+    Python (CPython) 3.12.[X]
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    "###);
+
+    // Extras provided via `--from` should reuse the environment with the extra, including when
+    // combined with a version request.
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("--from")
+        .arg("black[colorama]")
+        .arg("black")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black, 24.3.0 (compiled: yes)
+    Python (CPython) 3.12.[X]
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    "###);
+
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("--from")
+        .arg("black[colorama]@24.3.0")
+        .arg("black")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black, 24.3.0 (compiled: yes)
+    Python (CPython) 3.12.[X]
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    "###);
+}
+
+/// Cached tool environments beyond `--cache-max-size` should be removed, least-recently used first.
+#[test]
+fn tool_run_cache_max_size() {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    context
+        .tool_run()
+        .arg("pytest@8.0.0")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Running a different tool with a size cap of zero should evict the previous environment, but
+    // retain the one that's in use.
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("--cache-max-size")
+        .arg("0")
+        .arg("pytest@8.1.1")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    pytest 8.1.1
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + iniconfig==2.0.0
+     + packaging==24.0
+     + pluggy==1.4.0
+     + pytest==8.1.1
+    "###);
+
+    // The evicted environment should be recreated from the cached wheels.
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("pytest@8.0.0")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    pytest 8.0.0
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + iniconfig==2.0.0
+     + packaging==24.0
+     + pluggy==1.4.0
+     + pytest==8.0.0
+    "###);
+
+    // The environment that was in use should have been retained.
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("pytest@8.1.1")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    pytest 8.1.1
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    "###);
+
+    // Since the cached environments were pruned recently, they shouldn't be pruned again, so
+    // running `pytest@8.0.0` with a size cap of zero should retain the `pytest@8.1.1` environment.
+    context
+        .tool_run()
+        .arg("--cache-max-size")
+        .arg("0")
+        .arg("pytest@8.0.0")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("pytest@8.1.1")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    pytest 8.1.1
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    "###);
+}
+
+#[test]
+fn tool_run_detach() {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+    let mut filters = context.filters();
+    filters.push((r"PID \d+", "PID [PID]"));
+    filters.push((r"(?m)^\d+ ", "[PID] "));
+    filters.push((r"python-\d+\.log", "python-[TIMESTAMP].log"));
+
+    // Launch a long-running command in the background.
+    uv_snapshot!(filters, context.tool_run()
+        .arg("--detach")
+        .arg("--from")
+        .arg("iniconfig")
+        .arg("python")
+        .arg("-c")
+        .arg("import time; time.sleep(60)")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + iniconfig==2.0.0
+    Started `python -c import time; time.sleep(60)` in the background (PID [PID])
+    Output is written to: [TEMP_DIR]/tools/.processes/python-[TIMESTAMP].log
+    "###);
+
+    // The process should be listed.
+    uv_snapshot!(filters, context.tool_ps()
+        .env("UV_TOOL_DIR", tool_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [PID] python -c import time; time.sleep(60)
+    - [TEMP_DIR]/tools/.processes/python-[TIMESTAMP].log
+
+    ----- stderr -----
+    warning: `uv tool ps` is experimental and may change without warning
+    "###);
+
+    // Stop the process.
+    uv_snapshot!(filters, context.tool_kill()
+        .arg("--all")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool kill` is experimental and may change without warning
+    Stopped `python -c import time; time.sleep(60)` (PID [PID])
+    "###);
+
+    // A process that isn't running in the background can't be stopped.
+    uv_snapshot!(filters, context.tool_kill()
+        .arg("1")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool kill` is experimental and may change without warning
+    error: No tool is running in the background with PID [PID]
+    "###);
+}
+
+/// With `--project-constraints`, dependencies shared with the project should be pinned to the
+/// versions in the project's lockfile.
+#[test]
+fn tool_run_project_constraints() -> anyhow::Result<()> {
+    let context = TestContext::new("3.12").with_filtered_counts();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Without a lockfile, the tool should fail to run.
+    context
+        .temp_dir
+        .child("pyproject.toml")
+        .write_str(indoc! { r#"
+        [project]
+        name = "foo"
+        version = "1.0.0"
+        requires-python = ">=3.8"
+        dependencies = ["packaging==23.2"]
+    "#
+        })?;
+
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("--project-constraints")
+        .arg("pytest")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    error: Unable to find lockfile at `uv.lock`. To create a lockfile, run `uv lock` or `uv sync`.
+    "###);
+
+    context.lock().assert().success();
+
+    // `packaging` should be installed at the locked version, rather than the latest.
+    uv_snapshot!(context.filters(), context.tool_run()
+        .arg("--project-constraints")
+        .arg("pytest")
+        .arg("--version")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    pytest 8.1.1
+
+    ----- stderr -----
+    warning: `uv tool run` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + iniconfig==2.0.0
+     + packaging==23.2
+     + pluggy==1.4.0
+     + pytest==8.1.1
+    "###);
+
+    Ok(())
+}
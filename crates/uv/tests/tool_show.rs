@@ -0,0 +1,76 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::fixture::PathChild;
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+#[test]
+fn tool_show() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`
+    context
+        .tool_install()
+        .arg("black==24.2.0")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), context.tool_show().arg("black")
+    .env("UV_TOOL_DIR", tool_dir.as_os_str())
+    .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black
+    Environment: [TEMP_DIR]/tools/black
+    Receipt: [TEMP_DIR]/tools/black/uv-receipt.toml
+    Requirements:
+    - black==24.2.0
+    Options:
+    - exclude-newer = "2024-03-25T00:00:00Z"
+    Executables:
+    - black ([TEMP_DIR]/bin/black)
+    - blackd ([TEMP_DIR]/bin/blackd)
+
+    ----- stderr -----
+    warning: `uv tool show` is experimental and may change without warning
+    "###);
+
+    uv_snapshot!(context.filters(), context.tool_show().arg("black").arg("--format").arg("json")
+    .env("UV_TOOL_DIR", tool_dir.as_os_str())
+    .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {"name":"black","path":"[TEMP_DIR]/tools/black","receipt":"[TEMP_DIR]/tools/black/uv-receipt.toml","requirements":["black==24.2.0"],"constraints":[],"overrides":[],"python":null,"selection":{},"options":{"exclude-newer":"2024-03-25T00:00:00Z"},"executables":[{"name":"black","path":"[TEMP_DIR]/bin/black"},{"name":"blackd","path":"[TEMP_DIR]/bin/blackd"}]}
+
+    ----- stderr -----
+    warning: `uv tool show` is experimental and may change without warning
+    "###);
+}
+
+#[test]
+fn tool_show_not_installed() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    uv_snapshot!(context.filters(), context.tool_show().arg("black")
+    .env("UV_TOOL_DIR", tool_dir.as_os_str())
+    .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool show` is experimental and may change without warning
+    error: `black` is not installed
+    "###);
+}
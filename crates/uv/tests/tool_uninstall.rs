@@ -71,6 +71,51 @@ fn tool_uninstall() {
     "###);
 }
 
+#[test]
+fn tool_uninstall_dry_run() {
+    let context = TestContext::new("3.12").with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`
+    context
+        .tool_install()
+        .arg("black==24.2.0")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    uv_snapshot!(context.filters(), context.tool_uninstall().arg("black").arg("--dry-run")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool uninstall` is experimental and may change without warning
+    Would remove environment for `black`: [TEMP_DIR]/tools/black
+    Would remove executable `black`: [TEMP_DIR]/bin/black
+    Would remove executable `blackd`: [TEMP_DIR]/bin/blackd
+    "###);
+
+    // The tool should still be installed.
+    uv_snapshot!(context.filters(), context.tool_list()
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    black v24.2.0
+    - black
+    - blackd
+
+    ----- stderr -----
+    warning: `uv tool list` is experimental and may change without warning
+    "###);
+}
+
 #[test]
 fn tool_uninstall_not_installed() {
     let context = TestContext::new("3.12").with_filtered_exe_suffix();
@@ -0,0 +1,83 @@
+#![cfg(all(feature = "python", feature = "pypi", unix))]
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+/// With `--dry-run`, report the configuration files that would be updated, without modifying them.
+#[test]
+fn tool_update_shell_dry_run() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let bin_dir = context.temp_dir.child("bin");
+
+    let bashrc = context.temp_dir.child(".bashrc");
+    bashrc.write_str("alias ll='ls -l'\n")?;
+
+    uv_snapshot!(context.filters(), context.tool_update_shell()
+        .arg("--dry-run")
+        .env("HOME", context.temp_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("BASH_VERSION", "5.2")
+        .env_remove("NU_VERSION")
+        .env_remove("FISH_VERSION")
+        .env_remove("PATH"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool update-shell` is experimental and may change without warning
+    Would create configuration file: [TEMP_DIR]/.bash_profile
+    Would update configuration file: [TEMP_DIR]/.bashrc
+    Would add the following to each file: export PATH="[TEMP_DIR]/bin:$PATH"
+    "###);
+
+    // Neither file should have been modified.
+    context
+        .temp_dir
+        .child(".bash_profile")
+        .assert(predicates::path::missing());
+    bashrc.assert("alias ll='ls -l'\n");
+
+    // Without `--dry-run`, both files should be updated.
+    uv_snapshot!(context.filters(), context.tool_update_shell()
+        .env("HOME", context.temp_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("BASH_VERSION", "5.2")
+        .env_remove("NU_VERSION")
+        .env_remove("FISH_VERSION")
+        .env_remove("PATH"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool update-shell` is experimental and may change without warning
+    Created configuration file: [TEMP_DIR]/.bash_profile
+    Updated configuration file: [TEMP_DIR]/.bashrc
+    Restart your shell to apply changes
+    "###);
+
+    // Running again should be a no-op, since the files are already up-to-date.
+    uv_snapshot!(context.filters(), context.tool_update_shell()
+        .arg("--dry-run")
+        .env("HOME", context.temp_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("BASH_VERSION", "5.2")
+        .env_remove("NU_VERSION")
+        .env_remove("FISH_VERSION")
+        .env_remove("PATH"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool update-shell` is experimental and may change without warning
+    error: The executable directory [TEMP_DIR]/bin is not in PATH, but the Bash configuration files are already up-to-date
+    "###);
+
+    Ok(())
+}
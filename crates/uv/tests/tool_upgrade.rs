@@ -1,8 +1,11 @@
 #![cfg(all(feature = "python", feature = "pypi"))]
 
+use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
+use insta::assert_snapshot;
+use predicates::prelude::predicate;
 
-use common::{uv_snapshot, TestContext};
+use common::{site_packages_path, uv_snapshot, TestContext};
 
 mod common;
 
@@ -110,9 +113,12 @@ fn test_tool_upgrade_all() {
     Installed 2 executables: py.test, pytest
     "###);
 
-    // Upgrade all. This is a no-op, since we have the latest versions already.
+    // Upgrade all. This is a no-op, since we have the latest versions already. (Tools are upgraded
+    // one at a time, such that the output of each upgrade is shown.)
     uv_snapshot!(context.filters(), context.tool_upgrade()
         .arg("--all")
+        .arg("--concurrent-upgrades")
+        .arg("1")
         .env("UV_TOOL_DIR", tool_dir.as_os_str())
         .env("XDG_BIN_HOME", bin_dir.as_os_str())
         .env("PATH", bin_dir.as_os_str()), @r###"
@@ -128,6 +134,89 @@ fn test_tool_upgrade_all() {
     Resolved [N] packages in [TIME]
     Audited [N] packages in [TIME]
     Updated 2 executables: py.test, pytest
+
+    Summary:
+      black: unchanged (v23.1.0)
+      pytest: unchanged (v8.0.0)
+    "###);
+
+    // Upgrade all concurrently, in which case only the summary is shown.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("--all")
+        .env("UV_CONCURRENT_TOOL_UPGRADES", "2")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+
+    Summary:
+      black: unchanged (v23.1.0)
+      pytest: unchanged (v8.0.0)
+    "###);
+}
+
+#[test]
+fn test_tool_upgrade_all_partial_failure() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black` and `babel==2.6.0`.
+    context
+        .tool_install()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    context
+        .tool_install()
+        .arg("babel==2.6.0")
+        .arg("--index-url")
+        .arg("https://test.pypi.org/simple/")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Corrupt the receipt for `black`.
+    tool_dir
+        .child("black")
+        .child("uv-receipt.toml")
+        .write_str("invalid")
+        .unwrap();
+
+    // Upgrading all tools should upgrade `babel`, despite the failure for `black`.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("--all")
+        .arg("--index-url")
+        .arg("https://pypi.org/simple/")
+        .arg("--concurrent-upgrades")
+        .arg("2")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    `black` is missing a valid receipt; run `uv tool install --force black` to reinstall
+
+    Summary:
+      babel: upgraded (v2.6.0 -> v2.14.0)
+      black: failed
     "###);
 }
 
@@ -345,3 +434,576 @@ fn test_tool_upgrade_constraint() {
     Updated 1 executable: pybabel
     "###);
 }
+
+#[test]
+fn test_tool_upgrade_frozen() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `flask`, which should lock the tool environment.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("flask>=3")
+        .arg("--resolution=lowest-direct")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + blinker==1.7.0
+     + click==8.1.7
+     + flask==3.0.0
+     + itsdangerous==2.1.2
+     + jinja2==3.1.3
+     + markupsafe==2.1.5
+     + werkzeug==3.0.1
+    Installed 1 executable: flask
+    "###);
+
+    tool_dir
+        .child("flask")
+        .child("uv.lock")
+        .assert(predicate::path::exists());
+
+    // Modify the tool environment directly.
+    uv_snapshot!(context.filters(), context.pip_install()
+        .arg("flask==3.0.2")
+        .env("VIRTUAL_ENV", tool_dir.child("flask").as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Uninstalled [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     - flask==3.0.0
+     + flask==3.0.2
+    "###);
+
+    // Upgrading with `--frozen` should restore the locked version, without resolving.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("flask")
+        .arg("--frozen")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Uninstalled [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     - flask==3.0.2
+     + flask==3.0.0
+    Updated 1 executable: flask
+    "###);
+
+    // Without a lockfile, `--frozen` should fail.
+    fs_err::remove_file(tool_dir.child("flask").child("uv.lock")).unwrap();
+
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("flask")
+        .arg("--frozen")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    `flask` is missing a lockfile; run `uv tool install --force flask` to reinstall
+    "###);
+}
+
+#[test]
+fn test_tool_upgrade_dry_run() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `babel` from Test PyPI, to get an outdated version.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("babel")
+        .arg("--index-url")
+        .arg("https://test.pypi.org/simple/")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + babel==2.6.0
+     + pytz==2018.5
+    Installed 1 executable: pybabel
+    "###);
+
+    // Report the available upgrade, without modifying the environment.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("babel")
+        .arg("--dry-run")
+        .arg("--index-url")
+        .arg("https://pypi.org/simple/")
+        .arg("--format")
+        .arg("json")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    [{"name":"babel","version":"2.6.0","latest":"2.14.0"}]
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Would upgrade `babel` from v2.6.0 to v2.14.0
+    "###);
+
+    // The tool should still be at the outdated version.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("babel")
+        .arg("--dry-run")
+        .arg("--index-url")
+        .arg("https://test.pypi.org/simple/")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    `babel` is already up-to-date (v2.6.0)
+    "###);
+}
+
+#[test]
+fn test_tool_upgrade_with() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`.
+    context
+        .tool_install()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Add `iniconfig` to the tool environment.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .arg("--with")
+        .arg("iniconfig")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + iniconfig==2.0.0
+    Updated 2 executables: black, blackd
+    "###);
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        // The receipt should include the extra requirement.
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [
+            { name = "black" },
+            { name = "iniconfig" },
+        ]
+        entrypoints = [
+            { name = "black", install-path = "[TEMP_DIR]/bin/black" },
+            { name = "blackd", install-path = "[TEMP_DIR]/bin/blackd" },
+        ]
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+
+    // Remove `iniconfig` from the tool environment.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .arg("--without")
+        .arg("iniconfig")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Uninstalled [N] packages in [TIME]
+     - iniconfig==2.0.0
+    Updated 2 executables: black, blackd
+    "###);
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        // The receipt should no longer include the extra requirement.
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [{ name = "black" }]
+        entrypoints = [
+            { name = "black", install-path = "[TEMP_DIR]/bin/black" },
+            { name = "blackd", install-path = "[TEMP_DIR]/bin/blackd" },
+        ]
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+
+    // The tool itself can't be removed.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .arg("--without")
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    error: Cannot remove `black` from its own tool environment
+    "###);
+}
+
+/// Tools installed from a direct URL should be reinstalled on upgrade, since the archive may have
+/// changed without a change in version.
+#[test]
+fn test_tool_upgrade_direct_url() {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black` from a direct URL.
+    context
+        .tool_install()
+        .arg("https://files.pythonhosted.org/packages/0f/89/294c9a6b6c75a08da55e9d05321d0707e9418735e3062b12ef0f54c33474/black-24.4.2-py3-none-any.whl")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Upgrading should re-fetch and reinstall `black`, but leave its dependencies intact.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Uninstalled [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     - black==24.4.2 (from https://files.pythonhosted.org/packages/0f/89/294c9a6b6c75a08da55e9d05321d0707e9418735e3062b12ef0f54c33474/black-24.4.2-py3-none-any.whl)
+     + black==24.4.2 (from https://files.pythonhosted.org/packages/0f/89/294c9a6b6c75a08da55e9d05321d0707e9418735e3062b12ef0f54c33474/black-24.4.2-py3-none-any.whl)
+    Updated 2 executables: black, blackd
+    "###);
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        // The receipt should retain the original URL.
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [{ name = "black", url = "https://files.pythonhosted.org/packages/0f/89/294c9a6b6c75a08da55e9d05321d0707e9418735e3062b12ef0f54c33474/black-24.4.2-py3-none-any.whl" }]
+        entrypoints = [
+            { name = "black", install-path = "[TEMP_DIR]/bin/black" },
+            { name = "blackd", install-path = "[TEMP_DIR]/bin/blackd" },
+        ]
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+}
+
+/// Tools with Git requirements should be reinstalled on upgrade if the requested reference now
+/// resolves to a different commit.
+#[test]
+#[cfg(feature = "git")]
+fn test_tool_upgrade_git() -> anyhow::Result<()> {
+    let context = TestContext::new("3.12")
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black`, with an extra requirement from Git.
+    context
+        .tool_install()
+        .arg("black")
+        .arg("--with")
+        .arg("uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@0.0.1")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // If the reference still resolves to the installed commit, nothing should be reinstalled.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Audited [N] packages in [TIME]
+    Updated 2 executables: black, blackd
+    "###);
+
+    // Simulate the reference having moved since installation, by rewriting the installed commit.
+    let direct_url = site_packages_path(&tool_dir.join("black"), "python3.12")
+        .join("uv_public_pypackage-0.1.0.dist-info")
+        .join("direct_url.json");
+    let contents = fs_err::read_to_string(&direct_url)?;
+    fs_err::write(
+        &direct_url,
+        contents.replace(
+            "0dacfd662c64cb4ceb16e6cf65a157a8b715b979",
+            "0000000000000000000000000000000000000000",
+        ),
+    )?;
+
+    // Upgrading should reinstall the requirement at the commit that the reference resolves to.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Uninstalled [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     - uv-public-pypackage==0.1.0 (from git+https://github.com/astral-test/uv-public-pypackage@0000000000000000000000000000000000000000)
+     + uv-public-pypackage==0.1.0 (from git+https://github.com/astral-test/uv-public-pypackage@0dacfd662c64cb4ceb16e6cf65a157a8b715b979)
+    Updated 2 executables: black, blackd
+    "###);
+
+    Ok(())
+}
+
+/// Upgrade a tool with `--python`, rebuilding its environment on a different interpreter.
+#[test]
+fn test_tool_upgrade_python() {
+    let context = TestContext::new_with_versions(&["3.11", "3.12"])
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black` with Python 3.11.
+    uv_snapshot!(context.filters(), context.tool_install()
+        .arg("black")
+        .arg("--python")
+        .arg("3.11")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool install` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Prepared [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Installed 2 executables: black, blackd
+    "###);
+
+    // Upgrade `black` with Python 3.12. The environment should be rebuilt from scratch.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .arg("--python")
+        .arg("3.12")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Updated 2 executables: black, blackd
+    "###);
+
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        // The receipt should record the new Python request.
+        assert_snapshot!(fs_err::read_to_string(tool_dir.join("black").join("uv-receipt.toml")).unwrap(), @r###"
+        schema-version = 1
+
+        [tool]
+        requirements = [{ name = "black" }]
+        python = "3.12"
+        entrypoints = [
+            { name = "black", install-path = "[TEMP_DIR]/bin/black" },
+            { name = "blackd", install-path = "[TEMP_DIR]/bin/blackd" },
+        ]
+
+        [tool.options]
+        exclude-newer = "2024-03-25T00:00:00Z"
+        "###);
+    });
+}
+
+/// Upgrade a tool whose interpreter was removed with `--python`, moving it to a new interpreter.
+#[test]
+fn test_tool_upgrade_python_missing_interpreter() {
+    let context = TestContext::new_with_versions(&["3.11", "3.12"])
+        .with_filtered_counts()
+        .with_filtered_exe_suffix();
+    let tool_dir = context.temp_dir.child("tools");
+    let bin_dir = context.temp_dir.child("bin");
+
+    // Install `black` with Python 3.11.
+    context
+        .tool_install()
+        .arg("black")
+        .arg("--python")
+        .arg("3.11")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str())
+        .assert()
+        .success();
+
+    // Break the environment by removing its interpreter, as if the base Python had been removed.
+    let python = if cfg!(windows) {
+        tool_dir.child("black").child("Scripts").child("python.exe")
+    } else {
+        tool_dir.child("black").child("bin").child("python")
+    };
+    fs_err::remove_file(&python).unwrap();
+
+    // Without `--python`, the tool can't be upgraded.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    `black` is not installed; run `uv tool install black` to install
+    "###);
+
+    // With `--python`, the environment should be rebuilt on the requested interpreter.
+    uv_snapshot!(context.filters(), context.tool_upgrade()
+        .arg("black")
+        .arg("--python")
+        .arg("3.12")
+        .env("UV_TOOL_DIR", tool_dir.as_os_str())
+        .env("XDG_BIN_HOME", bin_dir.as_os_str())
+        .env("PATH", bin_dir.as_os_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv tool upgrade` is experimental and may change without warning
+    Resolved [N] packages in [TIME]
+    Installed [N] packages in [TIME]
+     + black==24.3.0
+     + click==8.1.7
+     + mypy-extensions==1.0.0
+     + packaging==24.0
+     + pathspec==0.12.1
+     + platformdirs==4.2.0
+    Updated 2 executables: black, blackd
+    "###);
+}
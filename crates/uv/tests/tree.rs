@@ -584,3 +584,108 @@ fn optional_dependencies_inverted() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn graph_formats() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        # ...
+        requires-python = ">=3.12"
+        dependencies = ["click"]
+
+        [project.optional-dependencies]
+        async = ["sniffio"]
+    "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.tree().arg("--universal").arg("--format").arg("dot"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    digraph {
+        "project==0.1.0" [label="project v0.1.0"]
+        "click==8.1.7" [label="click v8.1.7"]
+        "sniffio==1.3.1" [label="sniffio v1.3.1"]
+        "colorama==0.4.6" [label="colorama v0.4.6"]
+        "project==0.1.0" -> "click==8.1.7"
+        "project==0.1.0" -> "sniffio==1.3.1" [label="extra: async"]
+        "click==8.1.7" -> "colorama==0.4.6" [label="platform_system == 'Windows'"]
+    }
+
+    ----- stderr -----
+    warning: `uv tree` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.filters(), context.tree().arg("--universal").arg("--format").arg("mermaid"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    flowchart TD
+        n0["project v0.1.0"]
+        n1["click v8.1.7"]
+        n2["sniffio v1.3.1"]
+        n3["colorama v0.4.6"]
+        n0 --> n1
+        n0 -->|"extra: async"| n2
+        n1 -->|"platform_system == 'Windows'"| n3
+
+    ----- stderr -----
+    warning: `uv tree` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    uv_snapshot!(context.filters(), context.tree().arg("--universal").arg("--format").arg("json").arg("--depth").arg("1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "nodes": [
+        {
+          "id": "project==0.1.0",
+          "name": "project",
+          "version": "0.1.0",
+          "source": "editable+."
+        },
+        {
+          "id": "click==8.1.7",
+          "name": "click",
+          "version": "8.1.7",
+          "source": "registry+https://pypi.org/simple"
+        },
+        {
+          "id": "sniffio==1.3.1",
+          "name": "sniffio",
+          "version": "1.3.1",
+          "source": "registry+https://pypi.org/simple"
+        }
+      ],
+      "edges": [
+        {
+          "from": "project==0.1.0",
+          "to": "click==8.1.7"
+        },
+        {
+          "from": "project==0.1.0",
+          "to": "sniffio==1.3.1",
+          "extra": "async"
+        }
+      ]
+    }
+
+    ----- stderr -----
+    warning: `uv tree` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,233 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use common::uv_snapshot;
+use insta::assert_snapshot;
+
+use crate::common::{get_bin, TestContext};
+
+mod common;
+
+/// Vendor the pure-Python dependencies of a project.
+#[test]
+fn vendor() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+
+        [tool.uv]
+        dev-dependencies = ["typing-extensions"]
+        "#,
+    )?;
+
+    let mut command = Command::new(get_bin());
+    command.arg("vendor");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv vendor` is experimental and may change without warning
+    Resolved 3 packages in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    Vendored 1 package into: vendor
+    "###);
+
+    let vendor = context.temp_dir.child("vendor");
+    vendor.child("iniconfig").assert(predicates::path::is_dir());
+    vendor
+        .child("__init__.py")
+        .assert(predicates::path::is_file());
+
+    let manifest = fs_err::read_to_string(vendor.child("uv-vendor.json"))?;
+    insta::with_settings!({
+        filters => context.filters(),
+    }, {
+        assert_snapshot!(
+            manifest, @r###"
+        {
+          "python": "3.12.[X]",
+          "packages": [
+            {
+              "name": "iniconfig",
+              "version": "2.0.0"
+            }
+          ]
+        }
+        "###
+        );
+    });
+
+    // The vendored packages should be importable via the loader.
+    context
+        .assert_command("import sys; sys.path.insert(0, '.'); import vendor; import iniconfig")
+        .success();
+
+    Ok(())
+}
+
+/// Vendor the dependencies of a project within a workspace, including the workspace members it
+/// depends on.
+#[test]
+fn vendor_workspace_member() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["child"]
+
+        [tool.uv.sources]
+        child = { workspace = true }
+
+        [tool.uv.workspace]
+        members = ["child"]
+        "#,
+    )?;
+
+    let child = context.temp_dir.child("child");
+    child.child("pyproject.toml").write_str(
+        r#"
+        [project]
+        name = "child"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+        "#,
+    )?;
+    child
+        .child("src")
+        .child("child")
+        .child("__init__.py")
+        .touch()?;
+
+    let mut command = Command::new(get_bin());
+    command.arg("vendor");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv vendor` is experimental and may change without warning
+    warning: `uv.sources` is experimental and may change without warning
+    Resolved 3 packages in [TIME]
+    Prepared 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + child==0.1.0 (from file://[TEMP_DIR]/child)
+     + iniconfig==2.0.0
+    Vendored 2 packages into: vendor
+    "###);
+
+    // The workspace member should be installed as a regular (non-editable) distribution.
+    let vendor = context.temp_dir.child("vendor");
+    vendor
+        .child("child")
+        .child("__init__.py")
+        .assert(predicates::path::is_file());
+    vendor.child("iniconfig").assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
+/// Packages with compiled extensions can't be vendored.
+#[test]
+fn vendor_platform_specific() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["markupsafe==2.1.5"]
+        "#,
+    )?;
+
+    let mut command = Command::new(get_bin());
+    command.arg("vendor");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv vendor` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    Prepared 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.5
+    error: Only pure-Python dependencies can be vendored, but the following include compiled extensions: `markupsafe`
+    "###);
+
+    // Neither the vendor directory nor the staging directory should be left behind.
+    assert!(!context.temp_dir.child("vendor").exists());
+    assert!(!fs_err::read_dir(&context.temp_dir)?.any(|entry| entry
+        .is_ok_and(|entry| entry.file_name().to_string_lossy().starts_with(".vendor."))));
+
+    Ok(())
+}
+
+/// Refuse to replace a directory that wasn't created by `uv vendor`.
+#[test]
+fn vendor_existing_directory() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = ["iniconfig"]
+        "#,
+    )?;
+    context
+        .temp_dir
+        .child("vendor")
+        .child("README.md")
+        .touch()?;
+
+    let mut command = Command::new(get_bin());
+    command.arg("vendor");
+    context.add_shared_args(&mut command);
+
+    uv_snapshot!(context.filters(), command, @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv vendor` is experimental and may change without warning
+    Resolved 2 packages in [TIME]
+    error: The output directory `vendor` already exists and was not created by `uv vendor`
+    "###);
+
+    Ok(())
+}
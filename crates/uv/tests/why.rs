@@ -0,0 +1,116 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use anyhow::Result;
+use assert_cmd::assert::OutputAssertExt;
+use assert_fs::prelude::*;
+use common::{uv_snapshot, TestContext};
+
+mod common;
+
+#[test]
+fn why() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "scikit-learn==1.4.1.post1"
+        ]
+    "#,
+    )?;
+
+    // Without a project environment, only the constraints declared by the project are known.
+    uv_snapshot!(context.filters(), context.why().arg("numpy").arg("--universal"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    project v0.1.0 -> scikit-learn v1.4.1.post1 (requires: ==1.4.1.post1) -> numpy v1.26.4
+    project v0.1.0 -> scikit-learn v1.4.1.post1 (requires: ==1.4.1.post1) -> scipy v1.12.0 -> numpy v1.26.4
+
+    ----- stderr -----
+    warning: `uv why` is experimental and may change without warning
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    // Once the environment is synced, the constraints of the transitive dependencies are shown.
+    context.sync().assert().success();
+
+    uv_snapshot!(context.filters(), context.why().arg("numpy").arg("--universal"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    project v0.1.0 -> scikit-learn v1.4.1.post1 (requires: ==1.4.1.post1) -> numpy v1.26.4 (requires: >=1.19.5)
+    project v0.1.0 -> scikit-learn v1.4.1.post1 (requires: ==1.4.1.post1) -> scipy v1.12.0 (requires: >=1.6.0) -> numpy v1.26.4 (requires: <1.29.0, >=1.22.4)
+
+    ----- stderr -----
+    warning: `uv why` is experimental and may change without warning
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn why_markers() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.12"
+        dependencies = [
+            "anyio>=4 ; sys_platform == 'win32'",
+        ]
+    "#,
+    )?;
+
+    uv_snapshot!(context.filters(), context.why().arg("idna").arg("--universal"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    project v0.1.0 -> anyio v4.3.0 (requires: >=4, marker: sys_platform == 'win32') -> idna v3.6
+
+    ----- stderr -----
+    warning: `uv why` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    // On other platforms, `idna` isn't required.
+    uv_snapshot!(context.filters(), context.why().arg("idna").arg("--python-platform").arg("linux"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv why` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    Package `idna` is not required on the current platform (use `--universal` to show all platforms)
+    "###
+    );
+
+    // Packages outside the lockfile are rejected.
+    uv_snapshot!(context.filters(), context.why().arg("flask"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: `uv why` is experimental and may change without warning
+    Resolved 4 packages in [TIME]
+    error: Package `flask` was not found in the lockfile
+    "###
+    );
+
+    Ok(())
+}
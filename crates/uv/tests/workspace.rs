@@ -10,7 +10,7 @@ use indoc::indoc;
 use insta::assert_json_snapshot;
 use serde::{Deserialize, Serialize};
 
-use crate::common::{copy_dir_ignore, make_project, uv_snapshot, TestContext};
+use crate::common::{copy_dir_ignore, get_bin, make_project, uv_snapshot, TestContext};
 
 mod common;
 
@@ -955,3 +955,68 @@ fn workspace_hidden_member() -> Result<()> {
 
     Ok(())
 }
+
+/// Display the graph of workspace members, and the members affected by a change.
+#[test]
+fn workspace_graph() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let work_dir = context.temp_dir.join("albatross-root-workspace");
+
+    copy_dir_ignore(workspaces_dir().join("albatross-root-workspace"), &work_dir)?;
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("workspace")
+        .arg("tree")
+        .arg("--external")
+        .arg("--preview")
+        .current_dir(&work_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    albatross
+    ├── bird-feeder
+    │   ├── seeds
+    │   │   └── idna
+    │   └── anyio
+    └── tqdm
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("workspace")
+        .arg("tree")
+        .arg("--invert")
+        .arg("--preview")
+        .current_dir(&work_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    seeds
+    └── bird-feeder
+        └── albatross
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("workspace")
+        .arg("list")
+        .arg("--affected-by")
+        .arg("bird-feeder")
+        .arg("--preview")
+        .current_dir(&work_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    albatross
+    bird-feeder
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}